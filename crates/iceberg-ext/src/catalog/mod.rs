@@ -28,7 +28,14 @@ pub mod rest {
     pub use table::{
         CommitTableRequest, CommitTableResponse, CommitTransactionRequest, CreateTableRequest,
         ListTablesResponse, LoadCredentialsResponse, LoadTableResult, RegisterTableRequest,
-        RenameTableRequest, StorageCredential,
+        RenameTableRequest, StorageCredential, TableStats,
+    };
+
+    mod scan_planning;
+    pub use scan_planning::{
+        ContentFile, ContentType, CountMap, FetchPlanningResult, FetchScanTasksRequest,
+        FetchScanTasksResult, FileScanTask, PlanStatus, PlanTableScanRequest, PlanTableScanResult,
+        ScanTasks, ValueMap,
     };
 
     mod view;
@@ -92,6 +92,9 @@ pub enum OAuthErrorType {
     UnauthorizedClient,
     UnsupportedGrantType,
     InvalidScope,
+    /// The server failed to process a valid request, for example because an upstream
+    /// identity provider is unavailable. Defined in RFC 6749 Section 4.1.2.1.
+    ServerError,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
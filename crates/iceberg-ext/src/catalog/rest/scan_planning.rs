@@ -0,0 +1,285 @@
+#[cfg(feature = "axum")]
+use super::impl_into_response;
+use super::{ErrorModel, StorageCredential};
+
+fn default_true() -> bool {
+    true
+}
+
+/// Request to plan a table scan on the server.
+///
+/// Point-in-time scans use `snapshot-id`; incremental scans use
+/// `start-snapshot-id` and `end-snapshot-id`. If neither is set, the
+/// current snapshot of the table is scanned.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct PlanTableScanRequest {
+    /// Identifier for the snapshot to scan in a point-in-time scan
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snapshot_id: Option<i64>,
+    /// List of selected schema fields
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub select: Option<Vec<String>>,
+    /// Expression used to filter the table data
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<serde_json::Value>,
+    /// Enables case sensitive field matching for filter and select
+    #[serde(default = "default_true")]
+    pub case_sensitive: bool,
+    /// Whether to use the schema at the time the snapshot was written.
+    #[serde(default)]
+    pub use_snapshot_schema: bool,
+    /// Starting snapshot ID for an incremental scan (exclusive)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_snapshot_id: Option<i64>,
+    /// Ending snapshot ID for an incremental scan (inclusive)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_snapshot_id: Option<i64>,
+    /// List of fields for which the service should send column stats.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stats_fields: Option<Vec<String>>,
+}
+
+impl Default for PlanTableScanRequest {
+    fn default() -> Self {
+        Self {
+            snapshot_id: None,
+            select: None,
+            filter: None,
+            case_sensitive: true,
+            use_snapshot_schema: false,
+            start_snapshot_id: None,
+            end_snapshot_id: None,
+            stats_fields: None,
+        }
+    }
+}
+
+/// Status of a server-side planning operation
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, strum_macros::Display)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum PlanStatus {
+    Completed,
+    Submitted,
+    Cancelled,
+    Failed,
+}
+
+/// Scan and planning tasks for server-side scan planning.
+///
+/// Each plan task must be passed to the `fetchScanTasks` endpoint to fetch
+/// the file scan tasks for the plan task. `delete-files` contains all delete
+/// files referenced by `file-scan-tasks`.
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ScanTasks {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub delete_files: Vec<ContentFile>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub file_scan_tasks: Vec<FileScanTask>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub plan_tasks: Vec<String>,
+}
+
+/// Result of server-side scan planning for `planTableScan`
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(
+    tag = "status",
+    rename_all = "kebab-case",
+    rename_all_fields = "kebab-case"
+)]
+pub enum PlanTableScanResult {
+    Completed {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        plan_id: Option<String>,
+        #[serde(flatten)]
+        scan_tasks: ScanTasks,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        storage_credentials: Option<Vec<StorageCredential>>,
+    },
+    Submitted {
+        plan_id: String,
+    },
+    Cancelled,
+    Failed {
+        error: ErrorModel,
+    },
+}
+
+/// Result of server-side scan planning for `fetchPlanningResult`
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(
+    tag = "status",
+    rename_all = "kebab-case",
+    rename_all_fields = "kebab-case"
+)]
+pub enum FetchPlanningResult {
+    Completed {
+        #[serde(flatten)]
+        scan_tasks: ScanTasks,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        storage_credentials: Option<Vec<StorageCredential>>,
+    },
+    Submitted,
+    Cancelled,
+    Failed {
+        error: ErrorModel,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct FetchScanTasksRequest {
+    pub plan_task: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct FetchScanTasksResult {
+    #[serde(flatten)]
+    pub scan_tasks: ScanTasks,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage_credentials: Option<Vec<StorageCredential>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct FileScanTask {
+    pub data_file: ContentFile,
+    /// A list of indices in the delete files array (0-based)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delete_file_references: Option<Vec<usize>>,
+    /// An optional filter to be applied to rows in this file scan task.
+    /// If not present, the client must use the original filter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub residual_filter: Option<serde_json::Value>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ContentType {
+    Data,
+    PositionDeletes,
+    EqualityDeletes,
+}
+
+/// Data file or delete file as returned by scan planning.
+///
+/// Column statistics are only populated for data files and only for the
+/// fields requested via `stats-fields`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ContentFile {
+    pub content: ContentType,
+    pub file_path: String,
+    pub file_format: String,
+    pub spec_id: i32,
+    /// Partition values ordered by the fields of the partition spec `spec-id`
+    pub partition: Vec<serde_json::Value>,
+    pub file_size_in_bytes: i64,
+    pub record_count: i64,
+    /// Encryption key metadata blob, hex encoded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_metadata: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub split_offsets: Option<Vec<i64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_order_id: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column_sizes: Option<CountMap>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_counts: Option<CountMap>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub null_value_counts: Option<CountMap>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nan_value_counts: Option<CountMap>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lower_bounds: Option<ValueMap>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upper_bounds: Option<ValueMap>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub equality_ids: Option<Vec<i32>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct CountMap {
+    pub keys: Vec<i32>,
+    pub values: Vec<i64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct ValueMap {
+    pub keys: Vec<i32>,
+    pub values: Vec<serde_json::Value>,
+}
+
+#[cfg(feature = "axum")]
+impl_into_response!(PlanTableScanResult);
+#[cfg(feature = "axum")]
+impl_into_response!(FetchPlanningResult);
+#[cfg(feature = "axum")]
+impl_into_response!(FetchScanTasksResult);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_plan_table_scan_request_defaults() {
+        let request: PlanTableScanRequest = serde_json::from_value(serde_json::json!({
+            "snapshot-id": 1,
+            "select": ["id"]
+        }))
+        .unwrap();
+        assert!(request.case_sensitive);
+        assert!(!request.use_snapshot_schema);
+        assert_eq!(request.snapshot_id, Some(1));
+    }
+
+    #[test]
+    fn test_plan_table_scan_result_serialization() {
+        let result = PlanTableScanResult::Completed {
+            plan_id: Some("plan".to_string()),
+            scan_tasks: ScanTasks {
+                delete_files: vec![],
+                file_scan_tasks: vec![],
+                plan_tasks: vec!["task-1".to_string()],
+            },
+            storage_credentials: None,
+        };
+        assert_eq!(
+            serde_json::to_value(&result).unwrap(),
+            serde_json::json!({
+                "status": "completed",
+                "plan-id": "plan",
+                "plan-tasks": ["task-1"]
+            })
+        );
+
+        let result = PlanTableScanResult::Submitted {
+            plan_id: "plan".to_string(),
+        };
+        assert_eq!(
+            serde_json::to_value(&result).unwrap(),
+            serde_json::json!({
+                "status": "submitted",
+                "plan-id": "plan"
+            })
+        );
+    }
+
+    #[test]
+    fn test_fetch_planning_result_failed() {
+        let result: FetchPlanningResult = serde_json::from_value(serde_json::json!({
+            "status": "failed",
+            "error": {
+                "message": "boom",
+                "type": "PlanningFailed",
+                "code": 500
+            }
+        }))
+        .unwrap();
+        assert!(matches!(result, FetchPlanningResult::Failed { .. }));
+    }
+}
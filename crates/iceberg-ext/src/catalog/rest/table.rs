@@ -51,6 +51,27 @@ pub struct RegisterTableRequest {
     #[serde(default)]
     #[builder(default)]
     pub overwrite: bool,
+    /// Register the table as external table. Files of external tables are owned by
+    /// another system: they are never purged or maintained by the catalog.
+    #[serde(default)]
+    #[builder(default)]
+    pub external: bool,
+    /// Import metadata and snapshot log entries of previous metadata files that are
+    /// no longer part of the registered metadata file.
+    #[serde(default)]
+    #[builder(default)]
+    pub import_history: bool,
+    /// Verify the metadata file before anything is changed: it must exist and neither
+    /// its table uuid nor its location may belong to another table of the warehouse.
+    #[serde(default)]
+    #[builder(default)]
+    pub verify: bool,
+    /// Adopt a table that was moved from a different base location. All absolute locations
+    /// in the metadata starting with its table location are rewritten to start with this
+    /// location and the result is written as a new metadata file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[builder(default)]
+    pub adopt_location: Option<String>,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -73,6 +94,28 @@ pub struct ListTablesResponse {
     pub table_uuids: Option<Vec<uuid::Uuid>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub protection_status: Option<Vec<bool>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_status: Option<Vec<bool>>,
+    /// Statistics of the tables, in the same order as `identifiers`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stats: Option<Vec<TableStats>>,
+}
+
+/// Basic statistics of a table, as returned by Lakekeeper when listing tables.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableStats {
+    /// Time of the last commit in milliseconds since epoch
+    pub last_updated_ms: Option<i64>,
+    /// Timestamp of the most recent snapshot in milliseconds since epoch
+    pub last_snapshot_timestamp_ms: Option<i64>,
+    /// Number of snapshots of the table
+    pub snapshot_count: i64,
+    /// Number of top-level columns of the current schema
+    pub column_count: i64,
+    /// Total size of all files of the current snapshot in bytes, as reported by the
+    /// `total-files-size` property of its summary. Empty if the writer did not report it.
+    pub total_data_size_bytes: Option<i64>,
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
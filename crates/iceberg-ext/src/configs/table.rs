@@ -25,6 +25,9 @@ impl TableProperties {
             } else if key.starts_with("gcs") {
                 gcs::validate(&key, &value)?;
                 config.props.insert(key, value);
+            } else if key.starts_with("oss") {
+                oss::validate(&key, &value)?;
+                config.props.insert(key, value);
             } else {
                 let pair = custom::CustomConfig {
                     key: key.clone(),
@@ -66,6 +69,10 @@ pub mod s3 {
             Signer, String, "s3.signer", "s3_signer";
             SignerUri, String, "s3.signer.uri", "s3_signer_uri";
             SignerEndpoint, String, "s3.signer.endpoint", "s3_signer_endpoint";
+            SseType, String, "s3.sse.type", "s3_sse_type";
+            SseKey, String, "s3.sse.key", "s3_sse_key";
+            SseMd5, String, "s3.sse.md5", "s3_sse_md5";
+            RequesterPays, bool, "s3.requester-pays-enabled", "s3_requester_pays_enabled";
          }
     );
 }
@@ -88,6 +95,23 @@ pub mod gcs {
     );
 }
 
+pub mod oss {
+    use url::Url;
+
+    use super::{
+        super::ConfigProperty, ConfigParseError, NotCustomProp, ParseFromStr, TableProperties,
+        TableProperty,
+    };
+    use crate::configs::impl_config_values;
+
+    impl_config_values!(
+        Table,
+        {
+            Endpoint, Url, "oss.endpoint", "oss_endpoint";
+        }
+    );
+}
+
 pub mod client {
     use super::{
         super::ConfigProperty, ConfigParseError, NotCustomProp, ParseFromStr, TableProperties,
@@ -98,6 +122,9 @@ pub mod client {
         Table,
         {
             Region, String, "client.region", "client_region";
+            AccessKeyId, String, "client.access-key-id", "client_access_key_id";
+            AccessKeySecret, String, "client.access-key-secret", "client_access_key_secret";
+            SecurityToken, String, "client.security-token", "client_security_token";
         }
     );
 }
@@ -120,5 +147,8 @@ mod tests {
         assert_eq!(iceberg::io::S3_PATH_STYLE_ACCESS, s3::PathStyleAccess::KEY);
         assert_eq!(iceberg::io::S3_ACCESS_KEY_ID, s3::AccessKeyId::KEY);
         assert_eq!(iceberg::io::S3_SECRET_ACCESS_KEY, s3::SecretAccessKey::KEY);
+        assert_eq!(iceberg::io::S3_SSE_TYPE, s3::SseType::KEY);
+        assert_eq!(iceberg::io::S3_SSE_KEY, s3::SseKey::KEY);
+        assert_eq!(iceberg::io::S3_SSE_MD5, s3::SseMd5::KEY);
     }
 }
@@ -16,7 +16,7 @@ use lakekeeper::{
     },
     AuthZBackend, CONFIG,
 };
-use tracing_subscriber::{filter::LevelFilter, EnvFilter};
+use tracing_subscriber::{layer::SubscriberExt as _, util::SubscriberInitExt as _};
 
 mod healthcheck;
 mod serve;
@@ -109,17 +109,20 @@ enum Commands {
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
 
-    tracing_subscriber::fmt()
-        .json()
-        .flatten_event(true)
-        .with_current_span(true)
-        .with_file(true)
-        .with_line_number(true)
-        .with_env_filter(
-            EnvFilter::builder()
-                .with_default_directive(LevelFilter::INFO.into())
-                .from_env_lossy(),
+    // Spans are flushed when the guard is dropped at the end of `main`.
+    let (otlp_layer, _otlp_guard) = lakekeeper::otel::otlp_tracing_layer()?.unzip();
+    // The filter comes first, so that its level can be changed at runtime.
+    tracing_subscriber::registry()
+        .with(lakekeeper::service::log_level::env_filter_layer())
+        .with(
+            tracing_subscriber::fmt::layer()
+                .json()
+                .flatten_event(true)
+                .with_current_span(true)
+                .with_file(true)
+                .with_line_number(true),
         )
+        .with(otlp_layer)
         .init();
 
     match cli.command {
@@ -13,6 +13,8 @@ use lakekeeper::{
         },
         endpoint_statistics::EndpointStatisticsSink,
         event_publisher::get_default_cloud_event_backends_from_config,
+        metadata_push::build_metadata_push_target_from_config,
+        search_index::build_search_index_from_config,
         Catalog, SecretStore,
     },
 };
@@ -70,6 +72,8 @@ async fn serve_inner<C: Catalog, S: SecretStore, A: Authorizer, N: Authenticator
     stats: Vec<Arc<dyn EndpointStatisticsSink + 'static>>,
 ) -> anyhow::Result<()> {
     let cloud_event_sinks = get_default_cloud_event_backends_from_config().await?;
+    let search_index = build_search_index_from_config()?;
+    let metadata_push_target = build_metadata_push_target_from_config()?;
 
     let config = ServeConfiguration::<C, _, _, _>::builder()
         .bind_addr(bind)
@@ -80,6 +84,8 @@ async fn serve_inner<C: Catalog, S: SecretStore, A: Authorizer, N: Authenticator
         .stats(stats)
         .modify_router_fn(Some(add_ui_routes))
         .cloud_event_sinks(cloud_event_sinks)
+        .search_index(search_index)
+        .metadata_push_target(metadata_push_target)
         .build();
 
     serve(config).await
@@ -0,0 +1,22 @@
+create type scan_plan_status as enum ('submitted', 'completed', 'cancelled', 'failed');
+
+create table scan_plan
+(
+    plan_id      uuid primary key,
+    warehouse_id uuid references warehouse (warehouse_id) on delete cascade not null,
+    table_id     uuid references "table" (table_id) on delete cascade        not null,
+    snapshot_id  bigint,
+    request      jsonb                                                      not null,
+    status       scan_plan_status                                           not null,
+    result       jsonb,
+    error        jsonb,
+    expires_at   timestamptz                                                not null
+);
+
+call add_time_columns('scan_plan');
+select trigger_updated_at('scan_plan');
+
+create index if not exists scan_plan_warehouse_id_table_id_idx
+    on scan_plan (warehouse_id, table_id);
+create index if not exists scan_plan_expires_at_idx
+    on scan_plan (expires_at);
@@ -0,0 +1,18 @@
+create table user_impersonation
+(
+    impersonation_id uuid primary key,
+    impersonator_id  text                                                not null,
+    user_id          text references users (id) on delete cascade       not null,
+    reason           text,
+    expires_at       timestamptz                                         not null,
+    ended_at         timestamptz
+);
+
+call add_time_columns('user_impersonation');
+select trigger_updated_at('user_impersonation');
+
+create index if not exists user_impersonation_impersonator_id_idx
+    on user_impersonation (impersonator_id);
+
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-start-impersonation';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-stop-impersonation';
@@ -0,0 +1,2 @@
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-set-table-comment';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-set-column-comments';
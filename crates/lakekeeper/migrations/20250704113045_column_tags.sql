@@ -0,0 +1,20 @@
+-- Tags are attached to field ids, which are stable across column renames
+-- and type promotions.
+create table column_tag
+(
+    warehouse_id uuid not null references warehouse (warehouse_id) on delete cascade,
+    table_id     uuid not null references "table" (table_id) on delete cascade,
+    field_id     int  not null,
+    tag          text not null,
+    primary key (table_id, field_id, tag)
+);
+
+call add_time_columns('column_tag');
+select trigger_updated_at('column_tag');
+
+create index if not exists column_tag_warehouse_id_tag_idx
+    on column_tag (warehouse_id, tag);
+
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-get-column-tags';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-set-column-tags';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-list-tagged-columns';
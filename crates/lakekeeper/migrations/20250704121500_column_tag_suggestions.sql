@@ -0,0 +1,27 @@
+-- Tag suggestions produced by the PII detection task queue. Suggestions are
+-- attached to field ids, just like column tags. Reviewed suggestions are kept
+-- so that rejected suggestions are not proposed again.
+create type column_tag_suggestion_status as enum ('pending', 'accepted', 'rejected');
+
+create table column_tag_suggestion
+(
+    warehouse_id uuid                         not null references warehouse (warehouse_id) on delete cascade,
+    table_id     uuid                         not null references "table" (table_id) on delete cascade,
+    field_id     int                          not null,
+    tag          text                         not null,
+    confidence   double precision             not null check (confidence >= 0 and confidence <= 1),
+    reason       text                         not null,
+    status       column_tag_suggestion_status not null default 'pending',
+    reviewed_by  text,
+    primary key (table_id, field_id, tag)
+);
+
+call add_time_columns('column_tag_suggestion');
+select trigger_updated_at('column_tag_suggestion');
+
+create index if not exists column_tag_suggestion_warehouse_id_status_idx
+    on column_tag_suggestion (warehouse_id, status);
+
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-list-column-tag-suggestions';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-review-column-tag-suggestions';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-detect-pii';
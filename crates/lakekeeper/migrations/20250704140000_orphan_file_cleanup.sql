@@ -0,0 +1,2 @@
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-get-orphan-file-cleanup';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-cleanup-orphan-files';
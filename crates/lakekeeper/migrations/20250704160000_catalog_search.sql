@@ -0,0 +1,22 @@
+-- Indexes backing the faceted catalog search.
+create index if not exists tabular_name_trgm_idx
+    on tabular using gin (lower(name collate "C") gin_trgm_ops)
+    where deleted_at is null;
+
+create index if not exists tabular_last_modified_idx
+    on tabular (coalesce(updated_at, created_at))
+    where deleted_at is null;
+
+create index if not exists namespace_name_trgm_idx
+    on namespace using gin (lower(namespace_name[array_length(namespace_name, 1)] collate "C") gin_trgm_ops);
+
+create index if not exists namespace_owner_idx
+    on namespace ((namespace_properties ->> 'owner'));
+
+create index if not exists table_properties_key_value_idx
+    on table_properties (key, value);
+
+create index if not exists view_properties_key_value_idx
+    on view_properties (key, value);
+
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-search-catalog';
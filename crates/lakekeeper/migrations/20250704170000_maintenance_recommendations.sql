@@ -0,0 +1,2 @@
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-get-maintenance-recommendations';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-trigger-compaction';
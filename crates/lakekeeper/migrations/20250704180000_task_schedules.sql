@@ -0,0 +1,34 @@
+-- Recurring schedules that enqueue a task of an entity on a cron calendar.
+-- Schedules are fired by the scheduler of any Lakekeeper instance. Due
+-- schedules are locked with `FOR UPDATE SKIP LOCKED`, so that each schedule
+-- fires exactly once per occurrence.
+create table task_schedule
+(
+    schedule_id       uuid primary key,
+    warehouse_id      uuid        not null references warehouse (warehouse_id) on delete cascade,
+    queue_name        text        not null,
+    entity_type       entity_type not null,
+    entity_id         uuid        not null references tabular (tabular_id) on delete cascade,
+    cron              text        not null,
+    jitter_seconds    int         not null default 0 check (jitter_seconds >= 0),
+    payload           jsonb       not null default '{}'::jsonb,
+    paused            boolean     not null default false,
+    next_run_at       timestamptz not null,
+    last_fired_at     timestamptz,
+    last_task_id      uuid,
+    -- The last occurrence was skipped because the previous task was still running.
+    last_fire_skipped boolean     not null default false,
+    unique (warehouse_id, entity_type, entity_id, queue_name)
+);
+
+call add_time_columns('task_schedule');
+select trigger_updated_at('task_schedule');
+
+create index if not exists task_schedule_next_run_at_idx
+    on task_schedule (next_run_at) where not paused;
+
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-create-task-schedule';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-list-task-schedules';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-delete-task-schedule';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-pause-task-schedule';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-resume-task-schedule';
@@ -0,0 +1,9 @@
+-- Lease duration requested by an external worker. Overrides the
+-- max_time_since_last_heartbeat of the queue while the task is running.
+alter table task
+    add column visibility_timeout interval;
+
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-poll-task';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-heartbeat-task';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-complete-task';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-fail-task';
@@ -0,0 +1,28 @@
+alter table task_config
+    add column max_attempts  integer check (max_attempts > 0),
+    add column retry_backoff interval;
+
+-- Tasks that exhausted their attempts. They are kept until they are requeued
+-- or their warehouse is deleted.
+create table task_dead_letter
+(
+    task_id         uuid primary key,
+    warehouse_id    uuid references warehouse (warehouse_id) on delete cascade not null,
+    queue_name      text                                                       not null,
+    task_data       jsonb                                                      not null,
+    entity_id       uuid                                                       not null,
+    entity_type     entity_type                                                not null,
+    parent_task_id  uuid,
+    attempt         integer                                                    not null,
+    last_error      text,
+    task_created_at timestamptz                                                not null
+);
+
+call add_time_columns('task_dead_letter');
+
+create index if not exists task_dead_letter_warehouse_id_queue_name_idx
+    on task_dead_letter (warehouse_id, queue_name, created_at);
+
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-list-dead-letter-tasks';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-get-dead-letter-task';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-requeue-dead-letter-task';
@@ -0,0 +1,17 @@
+-- Lists of namespaces, tabulars, roles and task schedules are paginated by their
+-- time-ordered id alone. Replace the single column filter indexes with indexes
+-- that serve both the filter and the keyset.
+create index if not exists namespace_warehouse_id_namespace_id_idx
+    on namespace (warehouse_id, namespace_id);
+drop index if exists namespace_warehouse_id_idx;
+
+create index if not exists tabular_namespace_id_tabular_id_idx
+    on tabular (namespace_id, tabular_id);
+drop index if exists tabular_namespace_id_idx;
+
+create index if not exists role_project_id_id_idx
+    on role (project_id, id);
+drop index if exists role_project_id_idx;
+
+create index if not exists task_schedule_warehouse_id_schedule_id_idx
+    on task_schedule (warehouse_id, schedule_id);
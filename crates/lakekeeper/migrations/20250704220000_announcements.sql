@@ -0,0 +1,25 @@
+-- Server-wide announcements of operators, such as maintenance notices.
+-- An announcement is active between `starts_at` and `ends_at`.
+create type announcement_severity as enum ('info', 'warning', 'critical');
+
+create table announcement
+(
+    announcement_id uuid primary key,
+    title           text                  not null check (length(title) > 0),
+    message         text,
+    severity        announcement_severity not null default 'info',
+    starts_at       timestamptz           not null default now(),
+    ends_at         timestamptz,
+    check (ends_at is null or ends_at > starts_at)
+);
+
+call add_time_columns('announcement');
+select trigger_updated_at('announcement');
+
+create index if not exists announcement_ends_at_idx on announcement (ends_at);
+
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-create-announcement';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-list-announcements';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-list-active-announcements';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-update-announcement';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-delete-announcement';
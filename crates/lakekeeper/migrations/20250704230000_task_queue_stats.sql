@@ -0,0 +1,4 @@
+-- Task queue statistics count the attempts finished within the last hour.
+create index if not exists task_log_created_at_idx on task_log (created_at);
+
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-list-task-queues';
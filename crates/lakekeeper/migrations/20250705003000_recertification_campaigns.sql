@@ -0,0 +1,51 @@
+-- Access recertification: reviewers confirm or revoke the members and grants of roles.
+-- Items are a snapshot of the role assignments at the time the campaign was created.
+create type recertification_campaign_status as enum ('open', 'closed');
+create type recertification_access_kind as enum ('member', 'grant');
+create type recertification_decision as enum ('pending', 'approved', 'revoked', 'auto-revoked');
+
+create table recertification_campaign
+(
+    campaign_id            uuid primary key,
+    project_id             text                            not null references project (project_id) on delete cascade on update cascade,
+    name                   text                            not null check (length(name) > 0),
+    description            text,
+    reviewers              text[]                          not null check (cardinality(reviewers) > 0),
+    deadline               timestamptz                     not null,
+    auto_revoke_unreviewed boolean                         not null default false,
+    status                 recertification_campaign_status not null default 'open',
+    created_by             text,
+    closed_at              timestamptz
+);
+
+call add_time_columns('recertification_campaign');
+select trigger_updated_at('recertification_campaign');
+
+create index if not exists recertification_campaign_project_id_idx
+    on recertification_campaign (project_id, campaign_id);
+
+create table recertification_item
+(
+    item_id     uuid primary key,
+    campaign_id uuid                        not null references recertification_campaign (campaign_id) on delete cascade,
+    role_id     uuid                        not null,
+    role_name   text                        not null,
+    kind        recertification_access_kind not null,
+    principal   text                        not null,
+    relation    text                        not null,
+    object      text                        not null,
+    decision    recertification_decision    not null default 'pending',
+    decided_by  text,
+    decided_at  timestamptz,
+    comment     text
+);
+
+create index if not exists recertification_item_campaign_id_idx
+    on recertification_item (campaign_id, item_id);
+
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-create-recertification-campaign';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-list-recertification-campaigns';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-get-recertification-campaign';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-review-recertification-items';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-close-recertification-campaign';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-export-recertification-campaign';
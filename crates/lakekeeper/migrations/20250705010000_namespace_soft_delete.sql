@@ -0,0 +1,12 @@
+alter table namespace
+    add column deleted_at timestamptz;
+
+alter table namespace
+    drop constraint unique_namespace_per_warehouse,
+    add constraint unique_namespace_per_warehouse unique NULLS not distinct (warehouse_id, namespace_name, deleted_at);
+
+create index if not exists namespace_warehouse_id_deleted_at_idx
+    on namespace (warehouse_id, deleted_at)
+    where deleted_at is not null;
+
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-undrop-namespace';
@@ -0,0 +1,13 @@
+create type endpoint_group as enum (
+    'register-table',
+    'purge-drops',
+    'views',
+    'scan-planning',
+    'transactions',
+    'remote-signing'
+);
+
+alter table warehouse
+    add column disabled_endpoint_groups endpoint_group[] not null default '{}';
+
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-set-warehouse-disabled-endpoint-groups';
@@ -0,0 +1,2 @@
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-list-view-versions';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-rollback-view';
@@ -0,0 +1,29 @@
+-- Proposed changes of table properties. Users with read access can propose
+-- changes which are applied through a regular table commit once a user that
+-- is allowed to commit to the table approves them.
+create type table_property_proposal_status as enum ('pending', 'approved', 'rejected');
+
+create table table_property_proposal
+(
+    proposal_id       uuid primary key,
+    warehouse_id      uuid                           not null references warehouse (warehouse_id) on delete cascade,
+    table_id          uuid                           not null references "table" (table_id) on delete cascade,
+    set_properties    jsonb                          not null,
+    remove_properties text[]                         not null,
+    reason            text,
+    status            table_property_proposal_status not null default 'pending',
+    proposed_by       text,
+    reviewed_by       text,
+    reviewed_at       timestamptz,
+    review_comment    text
+);
+
+call add_time_columns('table_property_proposal');
+select trigger_updated_at('table_property_proposal');
+
+create index if not exists table_property_proposal_table_id_created_at_idx
+    on table_property_proposal (table_id, created_at);
+
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-create-table-property-proposal';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-list-table-property-proposals';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-review-table-property-proposal';
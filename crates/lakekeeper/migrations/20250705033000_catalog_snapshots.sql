@@ -0,0 +1,25 @@
+-- Logical snapshots of the catalog state of a warehouse. The manifests are
+-- exported to the storage of the warehouse by the `catalog_snapshot_export`
+-- task queue and can be used to verify a restored catalog.
+alter type entity_type add value 'warehouse';
+
+create table catalog_snapshot
+(
+    snapshot_id     uuid primary key,
+    warehouse_id    uuid   not null references warehouse (warehouse_id) on delete cascade,
+    location        text   not null,
+    checksum        text   not null,
+    namespace_count bigint not null,
+    tabular_count   bigint not null,
+    task_id         uuid
+);
+
+call add_time_columns('catalog_snapshot');
+select trigger_updated_at('catalog_snapshot');
+
+create index if not exists catalog_snapshot_warehouse_id_created_at_idx
+    on catalog_snapshot (warehouse_id, created_at);
+
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-list-catalog-snapshots';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-export-catalog-snapshot';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-verify-catalog-snapshot';
@@ -0,0 +1,10 @@
+-- Differential catalog snapshots contain the changes since their parent
+-- snapshot and are replayed onto the full snapshot they are based on.
+create type catalog_snapshot_kind as enum ('full', 'differential');
+
+alter table catalog_snapshot
+    add column kind               catalog_snapshot_kind not null default 'full',
+    add column base_snapshot_id   uuid,
+    add column parent_snapshot_id uuid;
+
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-get-catalog-snapshot-state';
@@ -0,0 +1,19 @@
+-- Slugs of warehouse-scoped catalog URLs `/catalog/w/{slug}/v1/...`.
+-- Replaced slugs are kept as aliases of their warehouse, so that existing
+-- URLs keep working. Slugs are unique across all projects.
+create table warehouse_slug
+(
+    slug         text primary key,
+    warehouse_id uuid    not null references warehouse (warehouse_id) on delete cascade,
+    is_current   boolean not null default true
+);
+
+call add_time_columns('warehouse_slug');
+select trigger_updated_at('warehouse_slug');
+
+create unique index if not exists warehouse_slug_current_idx
+    on warehouse_slug (warehouse_id) where is_current;
+
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-get-warehouse-slug';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-set-warehouse-slug';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-delete-warehouse-slug';
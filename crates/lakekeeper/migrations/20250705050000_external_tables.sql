@@ -0,0 +1,5 @@
+-- External tables are registered with Lakekeeper but their files are owned by
+-- another system. They are never purged, not maintained and only read
+-- credentials are vended for them.
+alter table tabular
+    add column external bool not null default false;
@@ -0,0 +1,19 @@
+-- Intent log for storage operations that accompany pointer updates of tables.
+-- Intents that are not completed, i.e. because the server crashed, are recovered
+-- by deleting the affected files.
+create type storage_intent_kind as enum ('write-metadata', 'delete-file');
+
+create table storage_intent
+(
+    storage_intent_id uuid primary key,
+    warehouse_id      uuid                not null references warehouse (warehouse_id) on delete cascade,
+    table_id          uuid                not null,
+    kind              storage_intent_kind not null,
+    location          text                not null
+);
+
+call add_time_columns('storage_intent');
+select trigger_updated_at('storage_intent');
+
+create index if not exists storage_intent_created_at_idx
+    on storage_intent (created_at);
@@ -0,0 +1,16 @@
+-- Storage secrets that were replaced by a credential rotation. They stay valid for
+-- in-flight requests until `retire_at` and are deleted afterwards.
+create table retired_storage_secret
+(
+    secret_id    uuid primary key,
+    warehouse_id uuid        not null,
+    retire_at    timestamptz not null
+);
+
+call add_time_columns('retired_storage_secret');
+select trigger_updated_at('retired_storage_secret');
+
+create index if not exists retired_storage_secret_retire_at_idx
+    on retired_storage_secret (retire_at);
+
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-rotate-storage-credential';
@@ -0,0 +1,21 @@
+-- Streaming writers that coordinate their commits to a table through Lakekeeper.
+-- Every start of a writer increments its epoch, which fences commits of previous
+-- instances. `committed_checkpoint` makes commits of a checkpoint idempotent.
+create table table_writer
+(
+    table_id             uuid   not null references "table" (table_id) on delete cascade,
+    writer_id            text   not null,
+    warehouse_id         uuid   not null references warehouse (warehouse_id) on delete cascade,
+    epoch                bigint not null,
+    committed_checkpoint bigint,
+    prepared_checkpoint  bigint,
+    prepared_commit      jsonb,
+    primary key (table_id, writer_id)
+);
+
+call add_time_columns('table_writer');
+select trigger_updated_at('table_writer');
+
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-begin-table-writer';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-prepare-table-writer-commit';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-commit-table-writer';
@@ -0,0 +1,21 @@
+-- Audit log of remote-signing requests. Entries outlive the table they refer to,
+-- so that access to dropped tables can still be investigated.
+create table s3_sign_audit
+(
+    s3_sign_audit_id uuid primary key,
+    warehouse_id     uuid   not null references warehouse (warehouse_id) on delete cascade,
+    table_id         uuid   not null,
+    request_id       uuid   not null,
+    principal        text,
+    assumed_role_id  uuid,
+    method           text   not null,
+    locations        text[] not null,
+    signed_at        timestamptz not null default now()
+);
+
+create index if not exists s3_sign_audit_table_id_idx
+    on s3_sign_audit (table_id, s3_sign_audit_id);
+create index if not exists s3_sign_audit_signed_at_idx
+    on s3_sign_audit (signed_at);
+
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-list-table-signings';
@@ -0,0 +1,9 @@
+create type client_workaround as enum (
+    'omit-empty-next-page-token',
+    'legacy-oauth-tokens-endpoint'
+);
+
+alter table warehouse
+    add column client_workarounds client_workaround[] not null default '{}';
+
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-set-warehouse-client-workarounds';
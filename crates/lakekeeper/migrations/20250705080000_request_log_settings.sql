@@ -0,0 +1,17 @@
+-- Server-wide request log settings set via the management API.
+-- If no row exists, the settings of the configuration apply.
+create table request_log_settings
+(
+    single_row           bool PRIMARY KEY DEFAULT true,
+    enabled              boolean not null,
+    include_request_body boolean not null,
+    redact_credentials   boolean not null,
+    redacted_properties  text[]  not null default '{}',
+    CONSTRAINT single_row CHECK (single_row)
+);
+
+call add_time_columns('request_log_settings');
+select trigger_updated_at('request_log_settings');
+
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-get-request-log-settings';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-set-request-log-settings';
@@ -0,0 +1,22 @@
+-- Lifecycle events of server instances, shown in the server event feed of the management API.
+create type server_event_type as enum (
+    'startup', 'shutdown', 'migration-applied', 'task-worker-crashed', 'settings-changed'
+);
+
+create table server_event
+(
+    server_event_id uuid primary key,
+    event_type      server_event_type not null,
+    instance_id     uuid              not null,
+    hostname        text,
+    principal       text,
+    details         jsonb             not null default '{}',
+    created_at      timestamptz       not null default now()
+);
+
+create index if not exists server_event_created_at_idx
+    on server_event (created_at);
+create index if not exists server_event_event_type_idx
+    on server_event (event_type, server_event_id);
+
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-list-server-events';
@@ -0,0 +1,4 @@
+alter table warehouse
+    add column default_branch text;
+
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-set-warehouse-default-branch';
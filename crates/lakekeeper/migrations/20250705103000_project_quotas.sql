@@ -0,0 +1,8 @@
+alter table project
+    add column max_warehouses          bigint,
+    add column max_tables              bigint,
+    add column max_namespace_depth     bigint,
+    add column max_snapshots_per_table bigint;
+
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-get-project-quotas';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-set-project-quotas';
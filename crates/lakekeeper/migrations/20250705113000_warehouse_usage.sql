@@ -0,0 +1,18 @@
+-- Daily usage per warehouse, aggregated from the endpoint statistics.
+-- Counts of entities are taken at the time of the last aggregation of the day.
+create table warehouse_usage
+(
+    warehouse_id  uuid   not null references warehouse (warehouse_id) on delete cascade,
+    day           date   not null,
+    api_calls     bigint not null,
+    sign_requests bigint not null,
+    tables        bigint not null,
+    views         bigint not null,
+    snapshots     bigint not null,
+    primary key (warehouse_id, day)
+);
+
+call add_time_columns('warehouse_usage');
+select trigger_updated_at('warehouse_usage');
+
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-get-usage-statistics';
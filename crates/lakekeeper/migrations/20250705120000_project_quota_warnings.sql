@@ -0,0 +1,14 @@
+alter table project
+    add column quota_warning_threshold_percent bigint;
+
+create type project_quota_kind as enum ('warehouses', 'tables');
+
+create table project_quota_warning
+(
+    project_id text               not null references project (project_id) on delete cascade on update cascade,
+    quota      project_quota_kind not null,
+    usage      bigint             not null,
+    "limit"    bigint             not null,
+    raised_at  timestamptz        not null default now(),
+    primary key (project_id, quota)
+);
@@ -0,0 +1,41 @@
+-- One-shot imports of the namespaces and tables of another Iceberg REST catalog into a
+-- warehouse. Imports are run by the `catalog_import` task queue, the id of an import
+-- is the id of its task.
+create type catalog_import_status as enum ('pending', 'running', 'succeeded', 'failed');
+create type catalog_import_table_status as enum ('imported', 'skipped', 'failed');
+
+create table catalog_import
+(
+    import_id        uuid primary key,
+    warehouse_id     uuid                  not null references warehouse (warehouse_id) on delete cascade,
+    source_uri       text                  not null,
+    source_warehouse text,
+    status           catalog_import_status not null default 'pending',
+    error            text,
+    created_by       text,
+    finished_at      timestamptz
+);
+
+call add_time_columns('catalog_import');
+select trigger_updated_at('catalog_import');
+
+create index if not exists catalog_import_warehouse_id_created_at_idx
+    on catalog_import (warehouse_id, created_at);
+
+create table catalog_import_table
+(
+    import_id         uuid                        not null references catalog_import (import_id) on delete cascade,
+    namespace         text[]                      not null,
+    table_name        text                        not null,
+    metadata_location text,
+    status            catalog_import_table_status not null,
+    table_id          uuid,
+    error             text,
+    primary key (import_id, namespace, table_name)
+);
+
+call add_time_columns('catalog_import_table');
+select trigger_updated_at('catalog_import_table');
+
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-import-catalog';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-get-catalog-import';
@@ -0,0 +1,6 @@
+-- Typed custom properties of namespaces and tables, declared per project.
+alter table project
+    add column property_schemas jsonb not null default '[]'::jsonb;
+
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-get-property-schemas';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-set-property-schemas';
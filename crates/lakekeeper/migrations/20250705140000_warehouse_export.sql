@@ -0,0 +1,2 @@
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-export-warehouse';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-import-warehouse';
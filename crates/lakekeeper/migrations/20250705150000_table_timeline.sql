@@ -0,0 +1,21 @@
+-- Changes of tables that leave no trace in their metadata or the task log, merged with
+-- commits and maintenance runs into the timeline of a table.
+create type table_timeline_event_type as enum (
+    'commit', 'properties-changed', 'grants-changed', 'maintenance-run', 'dropped', 'undropped'
+);
+
+create table table_activity
+(
+    activity_id  uuid primary key,
+    warehouse_id uuid                      not null references warehouse (warehouse_id) on delete cascade,
+    table_id     uuid                      not null references "table" (table_id) on delete cascade,
+    event_type   table_timeline_event_type not null,
+    principal    text,
+    details      jsonb                     not null default '{}',
+    created_at   timestamptz               not null default now()
+);
+
+create index if not exists table_activity_table_id_created_at_idx
+    on table_activity (table_id, created_at);
+
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-get-table-timeline';
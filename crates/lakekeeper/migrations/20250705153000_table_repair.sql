@@ -0,0 +1 @@
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-repair-table';
@@ -0,0 +1,17 @@
+-- Tables referenced by the SQL of a view version. Identifiers are stored as written in the
+-- view, so that tables created after the view are resolved as well.
+create table view_lineage
+(
+    view_id          uuid   not null,
+    view_version_id  bigint not null,
+    source_namespace text[] collate "case_insensitive" not null,
+    source_name      text collate "case_insensitive"   not null,
+    FOREIGN KEY (view_id, view_version_id) REFERENCES view_version (view_id, version_id) ON DELETE CASCADE,
+    PRIMARY KEY (view_id, view_version_id, source_namespace, source_name)
+);
+
+create index view_lineage_source_idx on view_lineage (source_name, source_namespace);
+
+call add_time_columns('view_lineage');
+
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-get-view-lineage';
@@ -0,0 +1,4 @@
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-get-log-levels';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-set-log-level';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-add-trace-target';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-reset-log-levels';
@@ -0,0 +1,25 @@
+-- Lineage between datasets reported by engines as OpenLineage run events.
+-- One row per input, output and job, updated by later runs of the job.
+create table table_lineage
+(
+    upstream_namespace    text        not null,
+    upstream_name         text        not null,
+    downstream_namespace  text        not null,
+    downstream_name       text        not null,
+    job_namespace         text        not null,
+    job_name              text        not null,
+    upstream_table_id     uuid        references tabular (tabular_id) on delete set null,
+    downstream_table_id   uuid        references tabular (tabular_id) on delete set null,
+    last_run_id           uuid        not null,
+    last_event_time       timestamptz not null,
+    PRIMARY KEY (upstream_namespace, upstream_name, downstream_namespace, downstream_name,
+                 job_namespace, job_name)
+);
+
+create index table_lineage_upstream_table_id_idx on table_lineage (upstream_table_id);
+create index table_lineage_downstream_table_id_idx on table_lineage (downstream_table_id);
+
+call add_time_columns('table_lineage');
+
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-ingest-lineage-event';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-get-table-lineage';
@@ -0,0 +1,28 @@
+create type project_membership as enum ('describe', 'select', 'create', 'modify', 'data-admin');
+
+-- Invitations to a project. Invitations are bound to an email address, a link token or both.
+-- Only the sha256 hash of link tokens is stored.
+create table project_invitation
+(
+    invitation_id uuid primary key,
+    project_id    text               not null references project (project_id) on delete cascade on update cascade,
+    email         text collate "case_insensitive",
+    token_hash    text unique,
+    membership    project_membership not null default 'describe',
+    role_ids      uuid[]             not null default '{}',
+    created_by    text,
+    expires_at    timestamptz        not null,
+    redeemed_at   timestamptz,
+    redeemed_by   text,
+    revoked_at    timestamptz,
+    CONSTRAINT project_invitation_email_or_token CHECK (email is not null or token_hash is not null)
+);
+
+create index project_invitation_project_id_idx on project_invitation (project_id, invitation_id);
+
+call add_time_columns('project_invitation');
+
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-create-invitation';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-list-invitations';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-revoke-invitation';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-redeem-invitation';
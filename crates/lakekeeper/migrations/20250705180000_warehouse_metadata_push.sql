@@ -0,0 +1,4 @@
+alter table warehouse
+    add column metadata_push_enabled boolean not null default false;
+
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-set-warehouse-metadata-push';
@@ -0,0 +1,5 @@
+alter table warehouse
+    add column event_routing_rules jsonb not null default '[]';
+
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-get-warehouse-event-routing';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-set-warehouse-event-routing';
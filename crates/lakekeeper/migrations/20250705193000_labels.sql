@@ -0,0 +1,20 @@
+alter table project
+    add column label_settings jsonb not null default '{}';
+
+alter table warehouse
+    add column labels jsonb not null default '{}';
+
+alter table namespace
+    add column labels jsonb not null default '{}';
+
+alter table tabular
+    add column labels jsonb not null default '{}';
+
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-get-label-settings';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-set-label-settings';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-get-warehouse-labels';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-set-warehouse-labels';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-get-namespace-labels';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-set-namespace-labels';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-get-table-labels';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-set-table-labels';
@@ -0,0 +1,8 @@
+-- Trigram indexes backing the global search over property values, e.g. comments.
+create index if not exists table_properties_value_trgm_idx
+    on table_properties using gin (lower(value collate "C") gin_trgm_ops);
+
+create index if not exists view_properties_value_trgm_idx
+    on view_properties using gin (lower(value collate "C") gin_trgm_ops);
+
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-global-search';
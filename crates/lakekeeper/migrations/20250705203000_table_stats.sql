@@ -0,0 +1,13 @@
+-- Total size of the files of the current snapshot, maintained on commit from the
+-- `total-files-size` property of the snapshot summary.
+alter table "table" add column total_data_size_bytes bigint;
+
+update "table" t
+set total_data_size_bytes = case
+    when s.table_id is null then 0
+    when s.summary ->> 'total-files-size' ~ '^[0-9]+$' then (s.summary ->> 'total-files-size')::bigint
+end
+from "table" tt
+left join table_refs r on r.table_id = tt.table_id and r.table_ref_name = 'main'
+left join table_snapshot s on s.table_id = r.table_id and s.snapshot_id = r.snapshot_id
+where tt.table_id = t.table_id;
@@ -0,0 +1,4 @@
+ALTER TABLE users ADD COLUMN active boolean NOT NULL DEFAULT true;
+
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-deactivate-user';
+ALTER TYPE api_endpoints ADD VALUE 'management-v1-activate-user';
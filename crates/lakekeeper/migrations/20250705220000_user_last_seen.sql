@@ -0,0 +1,10 @@
+ALTER TABLE users ADD COLUMN last_seen_at timestamptz;
+
+-- Recording that a user was seen is not an update of the user
+DROP TRIGGER set_updated_at ON users;
+CREATE TRIGGER set_updated_at
+    BEFORE UPDATE
+    ON users
+    FOR EACH ROW
+    WHEN ((to_jsonb(OLD) - 'last_seen_at') IS DISTINCT FROM (to_jsonb(NEW) - 'last_seen_at'))
+EXECUTE FUNCTION set_updated_at();
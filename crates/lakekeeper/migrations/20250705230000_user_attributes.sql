@@ -0,0 +1 @@
+ALTER TABLE users ADD COLUMN attributes jsonb NOT NULL DEFAULT '{}';
@@ -120,18 +120,6 @@ macro_rules! generate_endpoints {
     };
 }
 
-impl CatalogV1Endpoint {
-    pub fn unimplemented(self) -> bool {
-        matches!(
-            self,
-            CatalogV1Endpoint::PlanTableScan
-                | CatalogV1Endpoint::FetchPlanningResult
-                | CatalogV1Endpoint::CancelPlanning
-                | CatalogV1Endpoint::FetchScanTasks
-        )
-    }
-}
-
 generate_endpoints! {
     enum CatalogV1 {
         GetConfig(GET, "/catalog/v1/config"),
@@ -175,14 +163,23 @@ generate_endpoints! {
         ServerInfo(GET, "/management/v1/info"),
         Bootstrap(POST, "/management/v1/bootstrap"),
         CreateUser(POST, "/management/v1/user"),
+        BulkCreateUsers(POST, "/management/v1/user/bulk"),
         SearchUser(POST, "/management/v1/search/user"),
         GetUser(GET, "/management/v1/user/{user_id}"),
         Whoami(GET, "/management/v1/whoami"),
         UpdateUser(PUT, "/management/v1/user/{user_id}"),
         ListUser(GET, "/management/v1/user"),
         DeleteUser(DELETE, "/management/v1/user/{user_id}"),
+        HardDeleteUser(POST, "/management/v1/user/{user_id}/hard-delete"),
+        MergeUser(POST, "/management/v1/user/{user_id}/merge-from/{source_user_id}"),
+        DeactivateUser(POST, "/management/v1/user/{user_id}/deactivate"),
+        ActivateUser(POST, "/management/v1/user/{user_id}/activate"),
+        StartImpersonation(POST, "/management/v1/user/{user_id}/impersonate"),
+        StopImpersonation(DELETE, "/management/v1/impersonation/{impersonation_id}"),
         CreateRole(POST, "/management/v1/role"),
         SearchRole(POST, "/management/v1/search/role"),
+        SearchCatalog(POST, "/management/v1/search"),
+        GlobalSearch(GET, "/management/v1/search"),
         ListRole(GET, "/management/v1/role"),
         DeleteRole(DELETE, "/management/v1/role/{role_id}"),
         GetRole(GET, "/management/v1/role/{role_id}"),
@@ -196,32 +193,134 @@ generate_endpoints! {
         DeleteProjectById(DELETE, "/management/v1/project/{project_id}"),
         RenameDefaultProject(POST, "/management/v1/project/rename"),
         RenameProjectById(POST, "/management/v1/project/{project_id}/rename"),
+        GetProjectQuotas(GET, "/management/v1/project/{project_id}/quotas"),
+        SetProjectQuotas(POST, "/management/v1/project/{project_id}/quotas"),
+        GetPropertySchemas(GET, "/management/v1/project/{project_id}/property-schemas"),
+        SetPropertySchemas(POST, "/management/v1/project/{project_id}/property-schemas"),
+        GetLabelSettings(GET, "/management/v1/project/{project_id}/label-settings"),
+        SetLabelSettings(POST, "/management/v1/project/{project_id}/label-settings"),
         ListWarehouses(GET, "/management/v1/warehouse"),
         GetWarehouse(GET, "/management/v1/warehouse/{warehouse_id}"),
         DeleteWarehouse(DELETE, "/management/v1/warehouse/{warehouse_id}"),
         RenameWarehouse(POST, "/management/v1/warehouse/{warehouse_id}/rename"),
         UpdateWarehouseDeleteProfile(POST, "/management/v1/warehouse/{warehouse_id}/delete-profile"),
+        SetWarehouseDisabledEndpointGroups(POST, "/management/v1/warehouse/{warehouse_id}/disabled-endpoint-groups"),
+        SetWarehouseClientWorkarounds(POST, "/management/v1/warehouse/{warehouse_id}/client-workarounds"),
+        SetWarehouseDefaultBranch(POST, "/management/v1/warehouse/{warehouse_id}/default-branch"),
+        SetWarehouseMetadataPush(POST, "/management/v1/warehouse/{warehouse_id}/metadata-push"),
+        GetWarehouseEventRouting(GET, "/management/v1/warehouse/{warehouse_id}/event-routing"),
+        SetWarehouseEventRouting(POST, "/management/v1/warehouse/{warehouse_id}/event-routing"),
+        ListReferencedFiles(GET, "/management/v1/warehouse/{warehouse_id}/referenced-files"),
+        GetWarehouseLabels(GET, "/management/v1/warehouse/{warehouse_id}/labels"),
+        SetWarehouseLabels(POST, "/management/v1/warehouse/{warehouse_id}/labels"),
+        GetWarehouseSlug(GET, "/management/v1/warehouse/{warehouse_id}/slug"),
+        SetWarehouseSlug(POST, "/management/v1/warehouse/{warehouse_id}/slug"),
+        DeleteWarehouseSlug(DELETE, "/management/v1/warehouse/{warehouse_id}/slug"),
         DeactivateWarehouse(POST, "/management/v1/warehouse/{warehouse_id}/deactivate"),
         ActivateWarehouse(POST, "/management/v1/warehouse/{warehouse_id}/activate"),
         UpdateStorageProfile(POST, "/management/v1/warehouse/{warehouse_id}/storage"),
+        ProbeStorageQuirks(POST, "/management/v1/warehouse/{warehouse_id}/storage/probe-quirks"),
         UpdateStorageCredential(POST, "/management/v1/warehouse/{warehouse_id}/storage-credential"),
+        RotateStorageCredential(POST, "/management/v1/warehouse/{warehouse_id}/storage-credential/rotate"),
         GetWarehouseStatistics(GET, "/management/v1/warehouse/{warehouse_id}/statistics"),
         LoadEndpointStatistics(POST, "/management/v1/endpoint-statistics"),
+        GetUsageStatistics(GET, "/management/v1/usage-statistics"),
         ListDeletedTabulars(GET, "/management/v1/warehouse/{warehouse_id}/deleted-tabulars"),
         UndropTabularsDeprecated(POST, "/management/v1/warehouse/{warehouse_id}/deleted_tabulars/undrop"),
         UndropTabulars(POST, "/management/v1/warehouse/{warehouse_id}/deleted-tabulars/undrop"),
         GetTableProtection(GET, "/management/v1/warehouse/{warehouse_id}/table/{table_id}/protection"),
         SetTableProtection(POST, "/management/v1/warehouse/{warehouse_id}/table/{table_id}/protection"),
+        GetTableLabels(GET, "/management/v1/warehouse/{warehouse_id}/table/{table_id}/labels"),
+        SetTableLabels(POST, "/management/v1/warehouse/{warehouse_id}/table/{table_id}/labels"),
+        SetTableComment(POST, "/management/v1/warehouse/{warehouse_id}/table/{table_id}/comment"),
+        SetColumnComments(POST, "/management/v1/warehouse/{warehouse_id}/table/{table_id}/column-comments"),
+        GetColumnTags(GET, "/management/v1/warehouse/{warehouse_id}/table/{table_id}/column-tags"),
+        SetColumnTags(POST, "/management/v1/warehouse/{warehouse_id}/table/{table_id}/column-tags"),
+        ListTaggedColumns(GET, "/management/v1/warehouse/{warehouse_id}/column-tags/{tag}"),
+        ListColumnTagSuggestions(GET, "/management/v1/warehouse/{warehouse_id}/table/{table_id}/column-tag-suggestions"),
+        ReviewColumnTagSuggestions(POST, "/management/v1/warehouse/{warehouse_id}/table/{table_id}/column-tag-suggestions/review"),
+        DetectPii(POST, "/management/v1/warehouse/{warehouse_id}/table/{table_id}/detect-pii"),
+        CreateTablePropertyProposal(POST, "/management/v1/warehouse/{warehouse_id}/table/{table_id}/property-proposals"),
+        ListTablePropertyProposals(GET, "/management/v1/warehouse/{warehouse_id}/table/{table_id}/property-proposals"),
+        ReviewTablePropertyProposal(POST, "/management/v1/warehouse/{warehouse_id}/table/{table_id}/property-proposals/{proposal_id}/review"),
+        GetSnapshotExpiration(GET, "/management/v1/warehouse/{warehouse_id}/table/{table_id}/snapshot-expiration"),
+        GetTableCompatibility(GET, "/management/v1/warehouse/{warehouse_id}/table/{table_id}/compatibility"),
+        GetOrphanFileCleanup(GET, "/management/v1/warehouse/{warehouse_id}/table/{table_id}/orphan-files"),
+        CleanupOrphanFiles(POST, "/management/v1/warehouse/{warehouse_id}/table/{table_id}/orphan-files/cleanup"),
+        GetMaintenanceRecommendations(GET, "/management/v1/warehouse/{warehouse_id}/table/{table_id}/maintenance-recommendations"),
+        GetTableTimeline(GET, "/management/v1/warehouse/{warehouse_id}/table/{table_id}/timeline"),
+        GetTableLineage(GET, "/management/v1/warehouse/{warehouse_id}/table/{table_id}/lineage"),
+        RepairTable(POST, "/management/v1/warehouse/{warehouse_id}/table/{table_id}/repair"),
+        TriggerCompaction(POST, "/management/v1/warehouse/{warehouse_id}/table/{table_id}/maintenance-recommendations/trigger"),
+        BeginTableWriter(POST, "/management/v1/warehouse/{warehouse_id}/table/{table_id}/writer/{writer_id}/begin"),
+        PrepareTableWriterCommit(POST, "/management/v1/warehouse/{warehouse_id}/table/{table_id}/writer/{writer_id}/prepare"),
+        CommitTableWriter(POST, "/management/v1/warehouse/{warehouse_id}/table/{table_id}/writer/{writer_id}/commit"),
+        ListTableSignings(GET, "/management/v1/warehouse/{warehouse_id}/table/{table_id}/signings"),
         GetViewProtection(GET, "/management/v1/warehouse/{warehouse_id}/view/{view_id}/protection"),
         SetViewProtection(POST, "/management/v1/warehouse/{warehouse_id}/view/{view_id}/protection"),
+        ListViewVersions(GET, "/management/v1/warehouse/{warehouse_id}/view/{view_id}/versions"),
+        GetViewLineage(GET, "/management/v1/warehouse/{warehouse_id}/view/{view_id}/lineage"),
+        RollbackView(POST, "/management/v1/warehouse/{warehouse_id}/view/{view_id}/rollback"),
         SetNamespaceProtection(POST, "/management/v1/warehouse/{warehouse_id}/namespace/{namespace_id}/protection"),
         GetNamespaceProtection(GET, "/management/v1/warehouse/{warehouse_id}/namespace/{namespace_id}/protection"),
+        GetNamespaceLabels(GET, "/management/v1/warehouse/{warehouse_id}/namespace/{namespace_id}/labels"),
+        SetNamespaceLabels(POST, "/management/v1/warehouse/{warehouse_id}/namespace/{namespace_id}/labels"),
+        UndropNamespace(POST, "/management/v1/warehouse/{warehouse_id}/namespace/{namespace_id}/undrop"),
         SetWarehouseProtection(POST, "/management/v1/warehouse/{warehouse_id}/protection"),
         GetDefaultProjectDeprecated(GET, "/management/v1/default-project"),
         DeleteDefaultProjectDeprecated(DELETE, "/management/v1/default-project"),
         RenameDefaultProjectDeprecated(POST, "/management/v1/default-project/rename"),
         SetTaskQueueConfig(POST, "/management/v1/{warehouse_id}/task-queue/{queue_name}/config"),
-        GetTaskQueueConfig(GET, "/management/v1/{warehouse_id}/task-queue/{queue_name}/config")
+        GetTaskQueueConfig(GET, "/management/v1/{warehouse_id}/task-queue/{queue_name}/config"),
+        CreateTaskSchedule(POST, "/management/v1/warehouse/{warehouse_id}/task-schedule"),
+        ListTaskSchedules(GET, "/management/v1/warehouse/{warehouse_id}/task-schedule"),
+        DeleteTaskSchedule(DELETE, "/management/v1/warehouse/{warehouse_id}/task-schedule/{schedule_id}"),
+        PauseTaskSchedule(POST, "/management/v1/warehouse/{warehouse_id}/task-schedule/{schedule_id}/pause"),
+        ResumeTaskSchedule(POST, "/management/v1/warehouse/{warehouse_id}/task-schedule/{schedule_id}/resume"),
+        PollTask(POST, "/management/v1/task-queue/{queue_name}/poll"),
+        HeartbeatTask(POST, "/management/v1/task/{task_id}/heartbeat"),
+        CompleteTask(POST, "/management/v1/task/{task_id}/complete"),
+        FailTask(POST, "/management/v1/task/{task_id}/fail"),
+        ListDeadLetterTasks(GET, "/management/v1/warehouse/{warehouse_id}/dead-letter-task"),
+        GetDeadLetterTask(GET, "/management/v1/warehouse/{warehouse_id}/dead-letter-task/{task_id}"),
+        RequeueDeadLetterTask(POST, "/management/v1/warehouse/{warehouse_id}/dead-letter-task/{task_id}/requeue"),
+        ListCatalogSnapshots(GET, "/management/v1/warehouse/{warehouse_id}/catalog-snapshots"),
+        ExportCatalogSnapshot(POST, "/management/v1/warehouse/{warehouse_id}/catalog-snapshots/export"),
+        VerifyCatalogSnapshot(POST, "/management/v1/warehouse/{warehouse_id}/catalog-snapshots/{snapshot_id}/verify"),
+        GetCatalogSnapshotState(GET, "/management/v1/warehouse/{warehouse_id}/catalog-snapshots/{snapshot_id}/state"),
+        RestoreCatalogSnapshot(POST, "/management/v1/warehouse/{warehouse_id}/catalog-snapshots/{snapshot_id}/restore"),
+        ImportCatalog(POST, "/management/v1/warehouse/{warehouse_id}/import"),
+        GetCatalogImport(GET, "/management/v1/warehouse/{warehouse_id}/import/{import_id}"),
+        ExportWarehouse(GET, "/management/v1/warehouse/{warehouse_id}/export"),
+        ImportWarehouse(POST, "/management/v1/warehouse/{warehouse_id}/import-manifest"),
+        CreateAnnouncement(POST, "/management/v1/announcement"),
+        ListAnnouncements(GET, "/management/v1/announcement"),
+        ListActiveAnnouncements(GET, "/management/v1/announcement/active"),
+        UpdateAnnouncement(POST, "/management/v1/announcement/{announcement_id}"),
+        DeleteAnnouncement(DELETE, "/management/v1/announcement/{announcement_id}"),
+        ListTaskQueues(GET, "/management/v1/task-queues"),
+        CreateRecertificationCampaign(POST, "/management/v1/recertification-campaign"),
+        ListRecertificationCampaigns(GET, "/management/v1/recertification-campaign"),
+        GetRecertificationCampaign(GET, "/management/v1/recertification-campaign/{campaign_id}"),
+        ReviewRecertificationItems(POST, "/management/v1/recertification-campaign/{campaign_id}/review"),
+        CloseRecertificationCampaign(POST, "/management/v1/recertification-campaign/{campaign_id}/close"),
+        ExportRecertificationCampaign(GET, "/management/v1/recertification-campaign/{campaign_id}/export"),
+        ExportAccess(GET, "/management/v1/access-export/{entity}"),
+        CreateInvitation(POST, "/management/v1/invitation"),
+        ListInvitations(GET, "/management/v1/invitation"),
+        RevokeInvitation(DELETE, "/management/v1/invitation/{invitation_id}"),
+        RedeemInvitation(POST, "/management/v1/invitation/redeem"),
+        GetRequestLogSettings(GET, "/management/v1/request-log"),
+        SetRequestLogSettings(POST, "/management/v1/request-log"),
+        ListServerEvents(GET, "/management/v1/server-events"),
+        FlushMetadataCache(POST, "/management/v1/metadata-cache/flush"),
+        GetLogLevels(GET, "/management/v1/logging"),
+        SetLogLevel(POST, "/management/v1/logging/level"),
+        AddTraceTarget(POST, "/management/v1/logging/trace-target"),
+        ResetLogLevels(POST, "/management/v1/logging/reset"),
+        IngestLineageEvent(POST, "/management/v1/lineage"),
+        Watch(GET, "/management/v1/warehouse/{warehouse_id}/watch"),
+        GetReplicationState(GET, "/management/v1/warehouse/{warehouse_id}/replication-state")
     }
 
     enum PermissionV1 {
@@ -360,10 +459,15 @@ mod test {
         use strum::IntoEnumIterator;
 
         use crate::api::endpoints::Endpoint;
-        let exempt_config_paths = [
-            "management/v1/{warehouse_id}/task-queue/tabular_expiration/config",
-            "management/v1/{warehouse_id}/task-queue/tabular_purge/config",
-        ];
+        let exempt_config_paths = crate::service::task_queue::BUILT_IN_API_CONFIGS
+            .iter()
+            .map(|config| {
+                format!(
+                    "management/v1/{{warehouse_id}}/task-queue/{}/config",
+                    config.queue_name
+                )
+            })
+            .collect::<HashSet<_>>();
         // Load YAML files
         let management_yaml = include_str!("../../../../docs/docs/api/management-open-api.yaml");
         let catalog_yaml = include_str!("../../../../docs/docs/api/rest-catalog-open-api.yaml");
@@ -443,7 +547,7 @@ mod test {
             .filter(|(_method, path)| !path.starts_with("management/v1/permissions"))
             // We remove the parameterized endpoints with {queue_name} and expand them using actually
             // registered queues
-            .filter(|(_method, path)| !exempt_config_paths.contains(&path.as_str()))
+            .filter(|(_method, path)| !exempt_config_paths.contains(path))
             .collect::<Vec<_>>();
 
         if !missing_endpoints.is_empty() {
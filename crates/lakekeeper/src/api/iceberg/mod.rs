@@ -2,6 +2,8 @@ use std::sync::LazyLock;
 
 use strum::IntoEnumIterator as _;
 
+use crate::service::endpoint_policy::{is_endpoint_enabled, is_write_endpoint, EndpointGroup};
+
 pub mod types;
 
 pub mod v1 {
@@ -24,7 +26,7 @@ pub mod v1 {
 
     pub use self::{
         namespace::{ListNamespacesQuery, NamespaceParameters, PaginationQuery},
-        tables::{DataAccess, ListTablesQuery, TableParameters},
+        tables::{DataAccess, ListTablesInclude, ListTablesQuery, TableParameters},
         views::ViewParameters,
     };
     pub use crate::{
@@ -75,6 +77,10 @@ pub mod v1 {
         config::router::<C, S>()
     }
 
+    pub fn new_v1_oauth_router<C: oauth::Service<S>, S: ThreadSafe>() -> Router<ApiContext<S>> {
+        oauth::router::<C, S>()
+    }
+
     #[derive(Debug, Default)]
     pub struct PaginatedMapping<T, Z>
     where
@@ -207,15 +213,27 @@ pub mod v1 {
     }
 }
 
-static SUPPORTED_ENDPOINTS: LazyLock<Vec<String>> = LazyLock::new(|| {
+static SUPPORTED_ENDPOINTS: LazyLock<Vec<String>> =
+    LazyLock::new(|| supported_endpoints_without(&crate::CONFIG.disabled_endpoint_groups));
+
+pub(crate) fn supported_endpoints() -> &'static [String] {
+    &SUPPORTED_ENDPOINTS
+}
+
+/// Supported endpoints, excluding those of the disabled endpoint groups.
+pub(crate) fn supported_endpoints_without(disabled: &[EndpointGroup]) -> Vec<String> {
     crate::api::endpoints::CatalogV1Endpoint::iter()
-        .filter(|s| !s.unimplemented())
+        .filter(|e| is_endpoint_enabled((*e).into(), disabled))
         .map(|s| s.as_http_route().replace(" /catalog/", " /"))
         .collect()
-});
+}
 
-pub(crate) fn supported_endpoints() -> &'static [String] {
-    &SUPPORTED_ENDPOINTS
+/// Supported endpoints of a read-only warehouse, excluding those of the disabled endpoint groups.
+pub(crate) fn read_only_endpoints_without(disabled: &[EndpointGroup]) -> Vec<String> {
+    crate::api::endpoints::CatalogV1Endpoint::iter()
+        .filter(|e| is_endpoint_enabled((*e).into(), disabled) && !is_write_endpoint((*e).into()))
+        .map(|s| s.as_http_route().replace(" /catalog/", " /"))
+        .collect()
 }
 
 #[cfg(test)]
@@ -229,12 +247,7 @@ mod test {
         let openapi = include_str!("../../../../../docs/docs/api/rest-catalog-open-api.yaml");
         let s: serde_json::Value = serde_yml::from_str(openapi).unwrap();
         let paths = s["paths"].as_object().unwrap();
-        let unsupported = &[
-            "/v1/oauth/tokens",
-            "/v1/{prefix}/namespaces/{namespace}/tables/{table}/plan",
-            "/v1/{prefix}/namespaces/{namespace}/tables/{table}/plan/{plan-id}",
-            "/v1/{prefix}/namespaces/{namespace}/tables/{table}/tasks",
-        ];
+        let unsupported = &["/v1/oauth/tokens"];
         // Check that openapi endpoints are in the supported endpoints
         paths
             .into_iter()
@@ -1,12 +1,45 @@
 use async_trait::async_trait;
-use axum::{extract::State, routing::post, Extension, Form, Router};
-use iceberg_ext::catalog::rest::{OAuthTokenRequest, OAuthTokenResponse};
-
-use crate::{
-    api::{ApiContext, Result},
-    request_metadata::RequestMetadata,
+use axum::{
+    extract::State,
+    response::{IntoResponse, Response},
+    routing::post,
+    Extension, Form, Json, Router,
+};
+use http::StatusCode;
+use iceberg_ext::catalog::rest::{
+    OAuthError, OAuthErrorType, OAuthTokenRequest, OAuthTokenResponse,
 };
 
+use crate::{api::ApiContext, request_metadata::RequestMetadata};
+
+/// Error of the token endpoint, serialized as defined in
+/// [RFC 6749 Section 5.2](https://datatracker.ietf.org/doc/html/rfc6749#section-5.2).
+#[derive(Debug, Clone, PartialEq)]
+pub struct OAuthErrorResponse {
+    pub status: StatusCode,
+    pub error: OAuthError,
+}
+
+impl OAuthErrorResponse {
+    #[must_use]
+    pub fn new(status: StatusCode, error: OAuthErrorType, description: impl Into<String>) -> Self {
+        Self {
+            status,
+            error: OAuthError {
+                error,
+                error_description: Some(description.into()),
+                error_uri: None,
+            },
+        }
+    }
+}
+
+impl IntoResponse for OAuthErrorResponse {
+    fn into_response(self) -> Response {
+        (self.status, Json(self.error)).into_response()
+    }
+}
+
 #[async_trait]
 pub trait Service<S: crate::api::ThreadSafe>
 where
@@ -17,7 +50,7 @@ where
         request_metadata: RequestMetadata,
         // application/x-www-form-urlencoded
         request: OAuthTokenRequest,
-    ) -> Result<OAuthTokenResponse>;
+    ) -> std::result::Result<OAuthTokenResponse, OAuthErrorResponse>;
 }
 
 pub fn router<I: Service<S>, S: crate::api::ThreadSafe>() -> Router<ApiContext<S>> {
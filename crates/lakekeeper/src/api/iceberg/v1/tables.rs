@@ -7,7 +7,10 @@ use axum::{
 };
 use http::{HeaderMap, StatusCode};
 use iceberg::TableIdent;
-use iceberg_ext::catalog::rest::LoadCredentialsResponse;
+use iceberg_ext::catalog::rest::{
+    FetchPlanningResult, FetchScanTasksRequest, FetchScanTasksResult, LoadCredentialsResponse,
+    PlanTableScanRequest, PlanTableScanResult,
+};
 
 use super::{PageToken, PaginationQuery};
 use crate::{
@@ -38,6 +41,22 @@ pub struct ListTablesQuery {
     pub return_uuids: bool,
     #[serde(default)]
     pub return_protection_status: bool,
+    /// Flag to indicate if the response should include whether tables are external.
+    /// Default is false.
+    #[serde(default)]
+    pub return_external_status: bool,
+    /// Additional information to include for each table. Ignored when listing views.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include: Option<ListTablesInclude>,
+}
+
+/// Additional information that can be requested when listing tables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ListTablesInclude {
+    /// Include the `stats` field in the response
+    Stats,
 }
 
 impl From<ListTablesQuery> for PaginationQuery {
@@ -133,6 +152,41 @@ where
         state: ApiContext<S>,
         request_metadata: RequestMetadata,
     ) -> Result<()>;
+
+    /// Submit a scan for server-side planning
+    async fn plan_table_scan(
+        parameters: TableParameters,
+        request: PlanTableScanRequest,
+        data_access: DataAccess,
+        state: ApiContext<S>,
+        request_metadata: RequestMetadata,
+    ) -> Result<PlanTableScanResult>;
+
+    /// Fetch the result of scan planning for a plan-id
+    async fn fetch_planning_result(
+        parameters: TableParameters,
+        plan_id: String,
+        data_access: DataAccess,
+        state: ApiContext<S>,
+        request_metadata: RequestMetadata,
+    ) -> Result<FetchPlanningResult>;
+
+    /// Cancel scan planning for a plan-id
+    async fn cancel_planning(
+        parameters: TableParameters,
+        plan_id: String,
+        state: ApiContext<S>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()>;
+
+    /// Fetch the file scan tasks for a plan task
+    async fn fetch_scan_tasks(
+        parameters: TableParameters,
+        request: FetchScanTasksRequest,
+        data_access: DataAccess,
+        state: ApiContext<S>,
+        request_metadata: RequestMetadata,
+    ) -> Result<FetchScanTasksResult>;
 }
 
 #[allow(clippy::too_many_lines)]
@@ -287,8 +341,8 @@ pub fn router<I: TablesService<S>, S: crate::api::ThreadSafe>() -> Router<ApiCon
         )
         // {prefix}/namespaces/{namespace}/tables/{table}/credentials
         .route(
-            "/{prefix}/namespaces/{namespace}/tables/{namespace}/credentials",
-            // Load a table from the catalog
+            "/{prefix}/namespaces/{namespace}/tables/{table}/credentials",
+            // Load vended credentials of a table without loading its metadata
             get(
                 |Path((prefix, namespace, table)): Path<(Prefix, NamespaceIdentUrl, String)>,
                  State(api_context): State<ApiContext<S>>,
@@ -309,6 +363,114 @@ pub fn router<I: TablesService<S>, S: crate::api::ThreadSafe>() -> Router<ApiCon
                 },
             ),
         )
+        // /{prefix}/namespaces/{namespace}/tables/{table}/plan
+        .route(
+            "/{prefix}/namespaces/{namespace}/tables/{table}/plan",
+            // Submit a scan for planning
+            post(
+                |Path((prefix, namespace, table)): Path<(Prefix, NamespaceIdentUrl, String)>,
+                 State(api_context): State<ApiContext<S>>,
+                 headers: HeaderMap,
+                 Extension(metadata): Extension<RequestMetadata>,
+                 Json(request): Json<PlanTableScanRequest>| {
+                    I::plan_table_scan(
+                        TableParameters {
+                            prefix: Some(prefix),
+                            table: TableIdent {
+                                namespace: namespace.into(),
+                                name: table,
+                            },
+                        },
+                        request,
+                        parse_data_access(&headers),
+                        api_context,
+                        metadata,
+                    )
+                },
+            ),
+        )
+        // /{prefix}/namespaces/{namespace}/tables/{table}/plan/{plan-id}
+        .route(
+            "/{prefix}/namespaces/{namespace}/tables/{table}/plan/{plan-id}",
+            // Fetch the result of scan planning for a plan-id
+            get(
+                |Path((prefix, namespace, table, plan_id)): Path<(
+                    Prefix,
+                    NamespaceIdentUrl,
+                    String,
+                    String,
+                )>,
+                 State(api_context): State<ApiContext<S>>,
+                 headers: HeaderMap,
+                 Extension(metadata): Extension<RequestMetadata>| {
+                    I::fetch_planning_result(
+                        TableParameters {
+                            prefix: Some(prefix),
+                            table: TableIdent {
+                                namespace: namespace.into(),
+                                name: table,
+                            },
+                        },
+                        plan_id,
+                        parse_data_access(&headers),
+                        api_context,
+                        metadata,
+                    )
+                },
+            )
+            // Cancel scan planning for a plan-id
+            .delete(
+                |Path((prefix, namespace, table, plan_id)): Path<(
+                    Prefix,
+                    NamespaceIdentUrl,
+                    String,
+                    String,
+                )>,
+                 State(api_context): State<ApiContext<S>>,
+                 Extension(metadata): Extension<RequestMetadata>| async {
+                    I::cancel_planning(
+                        TableParameters {
+                            prefix: Some(prefix),
+                            table: TableIdent {
+                                namespace: namespace.into(),
+                                name: table,
+                            },
+                        },
+                        plan_id,
+                        api_context,
+                        metadata,
+                    )
+                    .await
+                    .map(|()| StatusCode::NO_CONTENT.into_response())
+                },
+            ),
+        )
+        // /{prefix}/namespaces/{namespace}/tables/{table}/tasks
+        .route(
+            "/{prefix}/namespaces/{namespace}/tables/{table}/tasks",
+            // Fetch result tasks for a plan task
+            post(
+                |Path((prefix, namespace, table)): Path<(Prefix, NamespaceIdentUrl, String)>,
+                 State(api_context): State<ApiContext<S>>,
+                 headers: HeaderMap,
+                 Extension(metadata): Extension<RequestMetadata>,
+                 Json(request): Json<FetchScanTasksRequest>| {
+                    I::fetch_scan_tasks(
+                        TableParameters {
+                            prefix: Some(prefix),
+                            table: TableIdent {
+                                namespace: namespace.into(),
+                                name: table,
+                            },
+                        },
+                        request,
+                        parse_data_access(&headers),
+                        api_context,
+                        metadata,
+                    )
+                },
+            ),
+        )
         // /{prefix}/tables/rename
         .route(
             "/{prefix}/tables/rename",
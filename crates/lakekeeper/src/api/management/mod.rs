@@ -1,54 +1,144 @@
 #![allow(deprecated)]
 
 pub mod v1 {
+    pub mod access_export;
+    pub mod announcement;
     pub mod bootstrap;
+    pub mod catalog_import;
+    pub mod catalog_snapshot;
+    pub mod invitation;
+    pub mod label;
+    pub mod lineage;
+    pub mod log_level;
+    pub mod metadata_cache;
     pub mod namespace;
     pub mod project;
+    pub mod recertification;
+    pub mod referenced_files;
+    pub mod replication;
+    pub mod request_log;
     pub mod role;
+    pub mod search;
+    pub mod server_event;
     pub mod table;
+    pub mod task;
+    pub mod task_schedule;
+    pub mod usage;
     pub mod user;
     pub mod view;
     pub mod warehouse;
+    pub mod warehouse_export;
+    pub mod watch;
 
     use std::marker::PhantomData;
 
+    use access_export::{AccessExportEntity, ExportAccessQuery, Service as _};
+    use announcement::{
+        Announcement, CreateAnnouncementRequest, ListActiveAnnouncementsResponse,
+        ListAnnouncementsQuery, ListAnnouncementsResponse, Service as _, UpdateAnnouncementRequest,
+    };
     use axum::{
         extract::{Path, Query, State as AxumState},
         response::{IntoResponse, Response},
-        routing::{get, post},
+        routing::{delete, get, post},
         Extension, Json, Router,
     };
     use bootstrap::{BootstrapRequest, ServerInfo, Service as _};
+    use catalog_import::{
+        GetCatalogImportResponse, ImportCatalogRequest, ImportCatalogResponse, Service as _,
+    };
+    use catalog_snapshot::{
+        CatalogSnapshotStateResponse, ListCatalogSnapshotsResponse, RestoreCatalogSnapshotRequest,
+        RestoreCatalogSnapshotResponse, Service as _, VerifyCatalogSnapshotResponse,
+    };
     use http::StatusCode;
     use iceberg_ext::catalog::rest::ErrorModel;
+    use invitation::{
+        CreateInvitationRequest, CreateInvitationResponse, ListInvitationsQuery,
+        ListInvitationsResponse, RedeemInvitationRequest, RedeemInvitationResponse, Service as _,
+    };
+    use label::{GetLabelsResponse, Service as _, SetLabelsRequest};
+    use lineage::{IngestLineageEventResponse, Service as _};
+    use log_level::{AddTraceTargetRequest, Service as _, SetLogLevelRequest};
+    use metadata_cache::Service as _;
     use namespace::NamespaceManagementService as _;
     use project::{
-        CreateProjectRequest, CreateProjectResponse, GetProjectResponse, ListProjectsResponse,
-        RenameProjectRequest, Service as _,
+        CreateProjectRequest, CreateProjectResponse, GetProjectQuotasResponse, GetProjectResponse,
+        ListProjectsResponse, ProjectPropertySchemas, ProjectQuotas, RenameProjectRequest,
+        Service as _,
     };
+    use recertification::{
+        CreateRecertificationCampaignRequest, GetRecertificationCampaignResponse,
+        ListRecertificationCampaignsQuery, ListRecertificationCampaignsResponse,
+        ReviewRecertificationItemsRequest, ReviewRecertificationItemsResponse, Service as _,
+    };
+    use referenced_files::{ListReferencedFilesQuery, ListReferencedFilesResponse, Service as _};
+    use replication::{ReplicationStateResponse, Service as _};
+    use request_log::Service as _;
     use role::{
         CreateRoleRequest, ListRolesQuery, ListRolesResponse, Role, SearchRoleRequest,
         SearchRoleResponse, Service as _, UpdateRoleRequest,
     };
+    use search::{
+        GlobalSearchQuery, GlobalSearchResponse, SearchCatalogRequest, SearchCatalogResponse,
+        Service as _,
+    };
     use serde::{Deserialize, Serialize};
-    use table::TableManagementService as _;
+    use server_event::{ListServerEventsQuery, ListServerEventsResponse, Service as _};
+    use table::{
+        CleanupOrphanFilesRequest, CommitTableWriterRequest, CommitTableWriterResponse,
+        CreateTablePropertyProposalRequest, GetColumnTagsResponse,
+        GetMaintenanceRecommendationsResponse, GetOrphanFileCleanupResponse,
+        GetSnapshotExpirationResponse, GetTableCompatibilityQuery, GetTableCompatibilityResponse,
+        GetTableTimelineQuery, GetTableTimelineResponse, ListColumnTagSuggestionsQuery,
+        ListColumnTagSuggestionsResponse, ListTablePropertyProposalsQuery,
+        ListTablePropertyProposalsResponse, ListTableSigningsQuery, ListTableSigningsResponse,
+        ListTaggedColumnsResponse, PrepareTableWriterCommitRequest, RepairTableRequest,
+        RepairTableResponse, ReviewColumnTagSuggestionsRequest, ReviewTablePropertyProposalRequest,
+        SetColumnCommentsRequest, SetColumnTagsRequest, SetTableCommentRequest,
+        TableManagementService as _, TablePropertyProposal, TableWriterState,
+        TriggerCompactionResponse,
+    };
+    use task::{
+        CompleteTaskRequest, FailTaskRequest, GetDeadLetterTaskResponse, HeartbeatTaskRequest,
+        HeartbeatTaskResponse, ListDeadLetterTasksQuery, ListDeadLetterTasksResponse,
+        ListTaskQueuesResponse, PollTaskRequest, PollTaskResponse, RequeueDeadLetterTaskResponse,
+        Service as _,
+    };
+    use task_schedule::{
+        CreateTaskScheduleRequest, ListTaskSchedulesQuery, ListTaskSchedulesResponse, Service as _,
+    };
     use typed_builder::TypedBuilder;
+    use usage::{GetUsageStatisticsQuery, GetUsageStatisticsResponse, Service as _};
     use user::{
-        CreateUserRequest, SearchUserRequest, SearchUserResponse, Service as _, UpdateUserRequest,
-        User,
+        BulkCreateUsersRequest, BulkCreateUsersResponse, CreateUserRequest, HardDeleteUserResponse,
+        Impersonation, MergeUserResponse, SearchUserRequest, SearchUserResponse, Service as _,
+        StartImpersonationRequest, UpdateUserRequest, User,
     };
     use utoipa::{
         openapi::{security::SecurityScheme, KnownFormat, RefOr},
         OpenApi, ToSchema,
     };
-    use view::ViewManagementService as _;
+    use view::{
+        GetViewLineageResponse, ListViewVersionsResponse, RollbackViewRequest,
+        RollbackViewResponse, ViewManagementService as _,
+    };
     use warehouse::{
-        CreateWarehouseRequest, CreateWarehouseResponse, GetWarehouseResponse,
-        ListDeletedTabularsQuery, ListWarehousesRequest, ListWarehousesResponse,
-        RenameWarehouseRequest, Service as _, UpdateWarehouseCredentialRequest,
+        CreateWarehouseRequest, CreateWarehouseResponse, GetWarehouseEventRoutingResponse,
+        GetWarehouseResponse, GetWarehouseSlugResponse, ListDeletedTabularsQuery,
+        ListWarehousesRequest, ListWarehousesResponse, RenameWarehouseRequest,
+        RotateStorageCredentialRequest, RotateStorageCredentialResponse, S3QuirksProbe,
+        Service as _, SetWarehouseClientWorkaroundsRequest, SetWarehouseDefaultBranchRequest,
+        SetWarehouseDisabledEndpointGroupsRequest, SetWarehouseEventRoutingRequest,
+        SetWarehouseMetadataPushRequest, SetWarehouseSlugRequest, UpdateWarehouseCredentialRequest,
         UpdateWarehouseDeleteProfileRequest, UpdateWarehouseStorageRequest,
         WarehouseStatisticsResponse,
     };
+    use warehouse_export::{
+        parse_manifest, ExportWarehouseQuery, ImportWarehouseQuery, ImportWarehouseResponse,
+        Service as _, WarehouseExportManifest,
+    };
+    use watch::{Service as _, WatchQuery, WatchResponse};
 
     use crate::{
         api::{
@@ -65,9 +155,15 @@ pub mod v1 {
         },
         request_metadata::RequestMetadata,
         service::{
-            authn::UserId, authz::Authorizer, task_queue::QueueApiConfig, Actor, Catalog,
-            CreateOrUpdateUserResponse, NamespaceId, RoleId, SecretStore, State, TableId,
-            TabularId, ViewId,
+            authn::UserId,
+            authz::Authorizer,
+            labels::LabelSettings,
+            log_level::LogLevelStatus,
+            request_log::RequestLogSettings,
+            table_lineage::{LineageRunEvent, TableLineage},
+            task_queue::{schedule::TaskSchedule, QueueApiConfig},
+            Actor, Catalog, CreateOrUpdateUserResponse, NamespaceId, RoleId, SecretStore, State,
+            TableId, TabularId, ViewId,
         },
         ProjectId, WarehouseId,
     };
@@ -93,52 +189,163 @@ pub mod v1 {
             ("bearerAuth" = [])
         ),
         paths(
+            activate_user,
             activate_warehouse,
             bootstrap,
+            bulk_create_users,
+            cleanup_orphan_files,
             create_project,
             create_role,
+            create_task_schedule,
             create_user,
             create_warehouse,
+            deactivate_user,
             deactivate_warehouse,
             delete_default_project,
             delete_default_project_deprecated,
             delete_project_by_id,
             delete_role,
             delete_user,
+            hard_delete_user,
             delete_warehouse,
+            detect_pii,
+            export_access,
             get_default_project,
             get_default_project_deprecated,
+            get_column_tags,
             get_endpoint_statistics,
+            get_usage_statistics,
+            get_maintenance_recommendations,
+            get_orphan_file_cleanup,
             get_project_by_id,
+            get_project_quotas,
+            get_property_schemas,
+            get_label_settings,
             get_role,
             get_server_info,
+            get_snapshot_expiration,
+            get_table_compatibility,
             get_user,
             get_warehouse,
             get_warehouse_statistics,
             list_deleted_tabulars,
             list_projects,
             list_roles,
+            list_column_tag_suggestions,
+            list_tagged_columns,
             list_user,
+            list_view_versions,
             list_warehouses,
+            merge_user,
+            probe_storage_quirks,
             rename_default_project,
+            review_column_tag_suggestions,
+            create_table_property_proposal,
+            list_table_property_proposals,
+            review_table_property_proposal,
             rename_default_project_deprecated,
+            rollback_view,
             rename_project_by_id,
             rename_warehouse,
+            search_catalog,
+            global_search,
             search_role,
             search_user,
+            set_column_comments,
+            set_column_tags,
             set_namespace_protection,
+            set_project_quotas,
+            set_property_schemas,
+            set_label_settings,
+            set_table_comment,
             set_table_protection,
             set_task_queue_config,
             get_task_queue_config,
+            list_task_schedules,
+            delete_task_schedule,
+            pause_task_schedule,
+            resume_task_schedule,
+            poll_task,
+            heartbeat_task,
+            complete_task,
+            fail_task,
+            list_dead_letter_tasks,
+            get_dead_letter_task,
+            requeue_dead_letter_task,
+            list_catalog_snapshots,
+            export_catalog_snapshot,
+            verify_catalog_snapshot,
+            get_catalog_snapshot_state,
+            restore_catalog_snapshot,
+            import_catalog,
+            get_catalog_import,
+            export_warehouse,
+            import_warehouse,
+            get_replication_state,
+            create_announcement,
+            list_announcements,
+            list_active_announcements,
+            update_announcement,
+            delete_announcement,
+            list_task_queues,
+            get_request_log_settings,
+            set_request_log_settings,
+            list_server_events,
+            flush_metadata_cache,
+            get_log_levels,
+            set_log_level,
+            add_trace_target,
+            reset_log_levels,
+            ingest_lineage_event,
+            create_recertification_campaign,
+            list_recertification_campaigns,
+            get_recertification_campaign,
+            review_recertification_items,
+            close_recertification_campaign,
+            export_recertification_campaign,
+            create_invitation,
+            list_invitations,
+            revoke_invitation,
+            redeem_invitation,
             set_view_protection,
+            set_warehouse_disabled_endpoint_groups,
+            set_warehouse_client_workarounds,
+            set_warehouse_default_branch,
+            set_warehouse_metadata_push,
+            get_warehouse_event_routing,
+            set_warehouse_event_routing,
+            list_referenced_files,
+            get_warehouse_labels,
+            set_warehouse_labels,
+            get_namespace_labels,
+            set_namespace_labels,
+            get_table_labels,
+            set_table_labels,
+            watch,
+            get_warehouse_slug,
+            set_warehouse_slug,
+            delete_warehouse_slug,
             set_warehouse_protection,
+            start_impersonation,
+            stop_impersonation,
+            trigger_compaction,
+            begin_table_writer,
+            prepare_table_writer_commit,
+            commit_table_writer,
+            list_table_signings,
+            get_table_timeline,
+            get_table_lineage,
+            repair_table,
             get_namespace_protection,
             get_table_protection,
             get_view_protection,
+            get_view_lineage,
+            undrop_namespace,
             undrop_tabulars,
             undrop_tabulars_deprecated,
             update_role,
             update_storage_credential,
+            rotate_storage_credential,
             update_storage_profile,
             update_user,
             update_warehouse_delete_profile,
@@ -246,6 +453,30 @@ pub mod v1 {
             })
     }
 
+    /// Provision Users in Bulk
+    ///
+    /// Creates or updates up to 10000 users at once, for example when migrating from another catalog.
+    /// Users are written in chunks, each in its own transaction. The response contains the outcome
+    /// of every user in the order of the request; users that fail don't prevent others from being
+    /// provisioned.
+    #[utoipa::path(
+        post,
+        tag = "user",
+        path = ManagementV1Endpoint::BulkCreateUsers.path(),
+        request_body = BulkCreateUsersRequest,
+        responses(
+            (status = 200, description = "Outcome of every user", body = BulkCreateUsersResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn bulk_create_users<C: Catalog, A: Authorizer, S: SecretStore>(
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<BulkCreateUsersRequest>,
+    ) -> Result<BulkCreateUsersResponse> {
+        ApiServer::<C, A, S>::bulk_create_users(api_context, metadata, request).await
+    }
+
     /// Search User
     ///
     /// Performs a fuzzy search for users based on the provided criteria.
@@ -392,6 +623,154 @@ pub mod v1 {
             .map(|()| (StatusCode::NO_CONTENT, ()))
     }
 
+    /// Hard-Delete User
+    ///
+    /// Erases a user including their name and email, for example to fulfill a GDPR request.
+    /// Historical records of the user, such as impersonations, are re-pointed to a new
+    /// anonymized tombstone principal within the same transaction. The response lists
+    /// all references that were re-pointed.
+    #[utoipa::path(
+        post,
+        tag = "user",
+        path = ManagementV1Endpoint::HardDeleteUser.path(),
+        params(("user_id" = String,)),
+        responses(
+            (status = 200, body = HardDeleteUserResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn hard_delete_user<C: Catalog, A: Authorizer, S: SecretStore>(
+        Path(user_id): Path<UserId>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<HardDeleteUserResponse> {
+        ApiServer::<C, A, S>::hard_delete_user(api_context, metadata, user_id).await
+    }
+
+    /// Merge User
+    ///
+    /// Merges the user `source_user_id` into the user `user_id`, for example after users were
+    /// duplicated under new subject ids by a migration of the identity provider.
+    /// Role assignments, ownerships and all other permissions as well as audit records of the
+    /// source user are re-pointed to the user, and the source user is soft-deleted.
+    /// The user keeps its name, email and activation. Attributes of the source user are
+    /// added unless the user has an attribute with the same key.
+    #[utoipa::path(
+        post,
+        tag = "user",
+        path = ManagementV1Endpoint::MergeUser.path(),
+        params(("user_id" = String,), ("source_user_id" = String,)),
+        responses(
+            (status = 200, body = MergeUserResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn merge_user<C: Catalog, A: Authorizer, S: SecretStore>(
+        Path((user_id, source_user_id)): Path<(UserId, UserId)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<MergeUserResponse> {
+        ApiServer::<C, A, S>::merge_user(api_context, metadata, user_id, source_user_id).await
+    }
+
+    /// Deactivate User
+    ///
+    /// Blocks a user without deleting them. Tokens of deactivated users are rejected,
+    /// while the user and all records referencing them are kept.
+    /// Users cannot deactivate themselves.
+    #[utoipa::path(
+        post,
+        tag = "user",
+        path = ManagementV1Endpoint::DeactivateUser.path(),
+        params(("user_id" = String,)),
+        responses(
+            (status = 204, description = "User deactivated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn deactivate_user<C: Catalog, A: Authorizer, S: SecretStore>(
+        Path(user_id): Path<UserId>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<(StatusCode, ())> {
+        ApiServer::<C, A, S>::deactivate_user(api_context, metadata, user_id)
+            .await
+            .map(|()| (StatusCode::NO_CONTENT, ()))
+    }
+
+    /// Activate User
+    ///
+    /// Re-activates a previously deactivated user.
+    #[utoipa::path(
+        post,
+        tag = "user",
+        path = ManagementV1Endpoint::ActivateUser.path(),
+        params(("user_id" = String,)),
+        responses(
+            (status = 204, description = "User activated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn activate_user<C: Catalog, A: Authorizer, S: SecretStore>(
+        Path(user_id): Path<UserId>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<(StatusCode, ())> {
+        ApiServer::<C, A, S>::activate_user(api_context, metadata, user_id)
+            .await
+            .map(|()| (StatusCode::NO_CONTENT, ()))
+    }
+
+    /// Start Impersonation
+    ///
+    /// Allows a server admin to act as another user for troubleshooting.
+    /// Requests that carry the returned `impersonation-id` in the `x-impersonation-id` header
+    /// are executed as the impersonated user until the impersonation is stopped or expires.
+    /// Both identities are recorded for every request and emitted event.
+    #[utoipa::path(
+        post,
+        tag = "user",
+        path = ManagementV1Endpoint::StartImpersonation.path(),
+        params(("user_id" = String,)),
+        request_body = StartImpersonationRequest,
+        responses(
+            (status = 201, description = "Impersonation started", body = Impersonation),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn start_impersonation<C: Catalog, A: Authorizer, S: SecretStore>(
+        Path(user_id): Path<UserId>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<StartImpersonationRequest>,
+    ) -> Result<Impersonation> {
+        ApiServer::<C, A, S>::start_impersonation(api_context, metadata, user_id, request).await
+    }
+
+    /// Stop Impersonation
+    ///
+    /// Ends an impersonation before it expires.
+    /// Can be called by the impersonating admin - also while impersonating - or by other server admins.
+    #[utoipa::path(
+        delete,
+        tag = "user",
+        path = ManagementV1Endpoint::StopImpersonation.path(),
+        params(("impersonation_id" = Uuid,)),
+        responses(
+            (status = 204, description = "Impersonation stopped"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn stop_impersonation<C: Catalog, A: Authorizer, S: SecretStore>(
+        Path(impersonation_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<(StatusCode, ())> {
+        ApiServer::<C, A, S>::stop_impersonation(api_context, metadata, impersonation_id)
+            .await
+            .map(|()| (StatusCode::NO_CONTENT, ()))
+    }
+
     /// Create Role
     ///
     /// Creates a role with the specified name, description, and permissions.
@@ -437,6 +816,58 @@ pub mod v1 {
         ApiServer::<C, A, S>::search_role(api_context, metadata, request).await
     }
 
+    /// Search Catalog
+    ///
+    /// Searches namespaces, tables and views across all warehouses of the project.
+    /// Results can be narrowed down by entity type, warehouse, column tags, owner,
+    /// certification and last-modified time. The response contains the number of
+    /// matching entities per facet value to back catalog browsing.
+    ///
+    /// Only entities the current user can see are returned and counted.
+    #[utoipa::path(
+        post,
+        tag = "project",
+        path = ManagementV1Endpoint::SearchCatalog.path(),
+        request_body = SearchCatalogRequest,
+        responses(
+            (status = 200, description = "Matching entities and facets", body = SearchCatalogResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn search_catalog<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<SearchCatalogRequest>,
+    ) -> Result<SearchCatalogResponse> {
+        ApiServer::<C, A, S>::search_catalog(api_context, metadata, request).await
+    }
+
+    /// Global Search
+    ///
+    /// Fuzzy search over the names, comments and property values of namespaces, tables and
+    /// views across all warehouses of the project. Tolerates typos and returns the best
+    /// match per entity with its warehouse, best matches first. Intended for quick global
+    /// search in user interfaces; use the catalog search to browse with filters and facets.
+    ///
+    /// Only entities the current user can see are returned.
+    #[utoipa::path(
+        get,
+        tag = "project",
+        path = ManagementV1Endpoint::GlobalSearch.path(),
+        params(GlobalSearchQuery),
+        responses(
+            (status = 200, description = "Best matching entities", body = GlobalSearchResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn global_search<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Query(query): Query<GlobalSearchQuery>,
+    ) -> Result<GlobalSearchResponse> {
+        ApiServer::<C, A, S>::global_search(api_context, metadata, query).await
+    }
+
     /// List Roles
     ///
     /// Returns all roles in the project that the current user has access to view.
@@ -787,6 +1218,146 @@ pub mod v1 {
         ApiServer::<C, A, S>::rename_project(Some(project_id), request, api_context, metadata).await
     }
 
+    /// Get Project Quotas
+    ///
+    /// Returns the quotas of a project together with their current usage.
+    /// Limits that are `null` are not enforced.
+    #[utoipa::path(
+        get,
+        tag = "project",
+        path = ManagementV1Endpoint::GetProjectQuotas.path(),
+        params(("project_id" = String,)),
+        responses(
+            (status = 200, description = "Quotas of the project", body = GetProjectQuotasResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_project_quotas<C: Catalog, A: Authorizer, S: SecretStore>(
+        Path(project_id): Path<ProjectId>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<GetProjectQuotasResponse> {
+        ApiServer::<C, A, S>::get_project_quotas(project_id, api_context, metadata).await
+    }
+
+    /// Set Project Quotas
+    ///
+    /// Replaces all quotas of a project. Omitted limits are removed.
+    /// Existing resources are not affected if a quota is lowered below the current usage,
+    /// but no further resources can be created until the usage drops below the quota.
+    #[utoipa::path(
+        post,
+        tag = "project",
+        path = ManagementV1Endpoint::SetProjectQuotas.path(),
+        params(("project_id" = String,)),
+        request_body = ProjectQuotas,
+        responses(
+            (status = 200, description = "Updated quotas of the project", body = GetProjectQuotasResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn set_project_quotas<C: Catalog, A: Authorizer, S: SecretStore>(
+        Path(project_id): Path<ProjectId>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<ProjectQuotas>,
+    ) -> Result<GetProjectQuotasResponse> {
+        ApiServer::<C, A, S>::set_project_quotas(project_id, request, api_context, metadata).await
+    }
+
+    /// Get Property Schemas
+    ///
+    /// Returns the custom properties of namespaces and tables declared by a project.
+    #[utoipa::path(
+        get,
+        tag = "project",
+        path = ManagementV1Endpoint::GetPropertySchemas.path(),
+        params(("project_id" = String,)),
+        responses(
+            (status = 200, description = "Property schemas of the project", body = ProjectPropertySchemas),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_property_schemas<C: Catalog, A: Authorizer, S: SecretStore>(
+        Path(project_id): Path<ProjectId>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<ProjectPropertySchemas> {
+        ApiServer::<C, A, S>::get_property_schemas(project_id, api_context, metadata).await
+    }
+
+    /// Set Property Schemas
+    ///
+    /// Replaces the custom properties declared by a project. Values of declared properties
+    /// are validated whenever namespaces and tables are created, registered or their
+    /// properties are updated. Existing values are not checked.
+    #[utoipa::path(
+        post,
+        tag = "project",
+        path = ManagementV1Endpoint::SetPropertySchemas.path(),
+        params(("project_id" = String,)),
+        request_body = ProjectPropertySchemas,
+        responses(
+            (status = 200, description = "Updated property schemas of the project", body = ProjectPropertySchemas),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn set_property_schemas<C: Catalog, A: Authorizer, S: SecretStore>(
+        Path(project_id): Path<ProjectId>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<ProjectPropertySchemas>,
+    ) -> Result<ProjectPropertySchemas> {
+        ApiServer::<C, A, S>::set_property_schemas(project_id, request, api_context, metadata).await
+    }
+
+    /// Get Label Settings
+    ///
+    /// Returns the label definitions and label policies of a project.
+    #[utoipa::path(
+        get,
+        tag = "project",
+        path = ManagementV1Endpoint::GetLabelSettings.path(),
+        params(("project_id" = String,)),
+        responses(
+            (status = 200, description = "Label settings of the project", body = LabelSettings),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_label_settings<C: Catalog, A: Authorizer, S: SecretStore>(
+        Path(project_id): Path<ProjectId>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<LabelSettings> {
+        ApiServer::<C, A, S>::get_label_settings(project_id, api_context, metadata).await
+    }
+
+    /// Set Label Settings
+    ///
+    /// Replaces the label definitions and label policies of a project. Definitions restrict
+    /// the values and entities of a label key and are checked whenever labels are set.
+    /// Existing labels are not checked. Policies restrict access to the data of labeled
+    /// tables to actors that assume one of the allowed roles.
+    #[utoipa::path(
+        post,
+        tag = "project",
+        path = ManagementV1Endpoint::SetLabelSettings.path(),
+        params(("project_id" = String,)),
+        request_body = LabelSettings,
+        responses(
+            (status = 200, description = "Updated label settings of the project", body = LabelSettings),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn set_label_settings<C: Catalog, A: Authorizer, S: SecretStore>(
+        Path(project_id): Path<ProjectId>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<LabelSettings>,
+    ) -> Result<LabelSettings> {
+        ApiServer::<C, A, S>::set_label_settings(project_id, request, api_context, metadata).await
+    }
+
     /// List Warehouses
     ///
     /// Returns all warehouses in the project that the current user has access to.
@@ -919,94 +1490,124 @@ pub mod v1 {
         .await
     }
 
-    /// Deactivate Warehouse
+    /// Set Disabled Endpoint Groups
     ///
-    /// Temporarily disables access to a warehouse without deleting its data.
+    /// Disables groups of catalog endpoints, such as registering tables or
+    /// the view API, for a single warehouse. Requests to disabled endpoints are
+    /// rejected with `403 Forbidden`.
     #[utoipa::path(
         post,
         tag = "warehouse",
-        path = ManagementV1Endpoint::DeactivateWarehouse.path(),
+        path = ManagementV1Endpoint::SetWarehouseDisabledEndpointGroups.path(),
         params(("warehouse_id" = Uuid,)),
+        request_body = SetWarehouseDisabledEndpointGroupsRequest,
         responses(
-            (status = 200, description = "Warehouse deactivated successfully"),
+            (status = 200, description = "Disabled endpoint groups updated successfully"),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn deactivate_warehouse<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    async fn set_warehouse_disabled_endpoint_groups<
+        C: Catalog,
+        A: Authorizer + Clone,
+        S: SecretStore,
+    >(
         Path(warehouse_id): Path<uuid::Uuid>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<SetWarehouseDisabledEndpointGroupsRequest>,
     ) -> Result<()> {
-        ApiServer::<C, A, S>::deactivate_warehouse(warehouse_id.into(), api_context, metadata).await
+        ApiServer::<C, A, S>::set_warehouse_disabled_endpoint_groups(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
     }
 
-    /// Activate Warehouse
+    /// Set Client Workarounds
     ///
-    /// Re-enables access to a previously deactivated warehouse.
+    /// Enables workarounds for known client bugs for a single warehouse.
+    /// Workarounds enabled for the whole deployment remain active.
     #[utoipa::path(
         post,
         tag = "warehouse",
-        path = ManagementV1Endpoint::ActivateWarehouse.path(),
+        path = ManagementV1Endpoint::SetWarehouseClientWorkarounds.path(),
         params(("warehouse_id" = Uuid,)),
+        request_body = SetWarehouseClientWorkaroundsRequest,
         responses(
-            (status = 200, description = "Warehouse activated successfully"),
+            (status = 200, description = "Client workarounds updated successfully"),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn activate_warehouse<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    async fn set_warehouse_client_workarounds<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
         Path(warehouse_id): Path<uuid::Uuid>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<SetWarehouseClientWorkaroundsRequest>,
     ) -> Result<()> {
-        ApiServer::<C, A, S>::activate_warehouse(warehouse_id.into(), api_context, metadata).await
+        ApiServer::<C, A, S>::set_warehouse_client_workarounds(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
     }
 
-    /// Update Storage Profile
+    /// Set Default Branch
     ///
-    /// Updates both the storage profile and credentials of a warehouse.
+    /// Sets the branch that `loadTable` serves as `main` for tables of the warehouse,
+    /// for example to publish data blue/green by moving the branch.
+    /// Namespaces can override the branch with the `default_branch` property.
     #[utoipa::path(
         post,
         tag = "warehouse",
-        path = ManagementV1Endpoint::UpdateStorageProfile.path(),
+        path = ManagementV1Endpoint::SetWarehouseDefaultBranch.path(),
         params(("warehouse_id" = Uuid,)),
-        request_body = UpdateWarehouseStorageRequest,
+        request_body = SetWarehouseDefaultBranchRequest,
         responses(
-            (status = 200, description = "Storage profile updated successfully"),
+            (status = 200, description = "Default branch updated successfully"),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn update_storage_profile<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    async fn set_warehouse_default_branch<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
         Path(warehouse_id): Path<uuid::Uuid>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
-        Json(request): Json<UpdateWarehouseStorageRequest>,
+        Json(request): Json<SetWarehouseDefaultBranchRequest>,
     ) -> Result<()> {
-        ApiServer::<C, A, S>::update_storage(warehouse_id.into(), request, api_context, metadata)
-            .await
-    }
+        ApiServer::<C, A, S>::set_warehouse_default_branch(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+    }
 
-    /// Update Storage Credential
+    /// Set Metadata Push
     ///
-    /// Updates only the storage credential of a warehouse without modifying the storage profile.
-    /// Useful for refreshing expiring credentials.
+    /// Enables or disables pushing schema, ownership and property changes of tables in the
+    /// warehouse to the metadata platform (DataHub or OpenMetadata) configured for the server.
     #[utoipa::path(
         post,
         tag = "warehouse",
-        path = ManagementV1Endpoint::UpdateStorageCredential.path(),
+        path = ManagementV1Endpoint::SetWarehouseMetadataPush.path(),
         params(("warehouse_id" = Uuid,)),
-        request_body = UpdateWarehouseCredentialRequest,
+        request_body = SetWarehouseMetadataPushRequest,
         responses(
-            (status = 200, description = "Storage credential updated successfully"),
+            (status = 200, description = "Metadata push updated successfully"),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn update_storage_credential<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    async fn set_warehouse_metadata_push<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
         Path(warehouse_id): Path<uuid::Uuid>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
-        Json(request): Json<UpdateWarehouseCredentialRequest>,
+        Json(request): Json<SetWarehouseMetadataPushRequest>,
     ) -> Result<()> {
-        ApiServer::<C, A, S>::update_storage_credential(
+        ApiServer::<C, A, S>::set_warehouse_metadata_push(
             warehouse_id.into(),
             request,
             api_context,
@@ -1015,501 +1616,3115 @@ pub mod v1 {
         .await
     }
 
-    #[derive(Serialize, Deserialize)]
-    struct RecursiveDeleteQuery {
-        #[serde(default)]
-        force: bool,
-        #[serde(default)]
-        purge: bool,
-    }
-
-    #[derive(Deserialize, Debug, ToSchema)]
-    pub struct SetProtectionRequest {
-        /// Setting this to `true` will prevent the entity from being deleted unless `force` is used.
-        pub protected: bool,
-    }
-
-    #[derive(Debug, Deserialize, Serialize, utoipa::IntoParams)]
-    pub struct GetWarehouseStatisticsQuery {
-        /// Next page token
-        #[serde(skip_serializing_if = "PageToken::skip_serialize")]
-        #[param(value_type=String)]
-        pub page_token: PageToken,
-        /// Signals an upper bound of the number of results that a client will receive.
-        #[serde(skip_serializing_if = "Option::is_none")]
-        #[serde(default)]
-        pub page_size: Option<i64>,
-    }
-
-    impl GetWarehouseStatisticsQuery {
-        fn to_pagination_query(&self) -> PaginationQuery {
-            PaginationQuery {
-                page_token: self.page_token.clone(),
-                page_size: self.page_size,
-            }
-        }
-    }
-
-    /// Get Warehouse Statistics
-    ///
-    /// Retrieves statistical data about a warehouse's usage and resources over time.
-    /// Statistics are aggregated hourly when changes occur.
-    ///
-    /// We lazily create a new statistics entry every hour, in between hours, the existing entry is
-    /// being updated. If there's a change at created_at + 1 hour, a new entry is created.
-    /// If there's been no change, no new entry is created, meaning there may be gaps.
+    /// Get Event Routing
     ///
-    /// Example:
-    /// - 00:16:32: warehouse created:
-    ///     - timestamp: 01:00:00, created_at: 00:16:32, updated_at: null, 0 tables, 0 views
-    /// - 00:30:00: table created:
-    ///     - timestamp: 01:00:00, created_at: 00:16:32, updated_at: 00:30:00, 1 table, 0 views
-    /// - 00:45:00: view created:
-    ///     - timestamp: 01:00:00, created_at: 00:16:32, updated_at: 00:45:00, 1 table, 1 view
-    /// - 01:00:36: table deleted:
-    ///     - timestamp: 02:00:00, created_at: 01:00:36, updated_at: null, 0 tables, 1 view
-    ///     - timestamp: 01:00:00, created_at: 00:16:32, updated_at: 00:45:00, 1 table, 1 view
+    /// Returns the rules routing cloud events of the warehouse to specific sinks and topics.
     #[utoipa::path(
         get,
         tag = "warehouse",
-        path = ManagementV1Endpoint::GetWarehouseStatistics.path(),
-        params(("warehouse_id" = Uuid,), GetWarehouseStatisticsQuery),
+        path = ManagementV1Endpoint::GetWarehouseEventRouting.path(),
+        params(("warehouse_id" = Uuid,)),
         responses(
-            (status = 200, description = "Warehouse statistics", body = WarehouseStatisticsResponse),
+            (status = 200, description = "Event routing rules of the warehouse", body = GetWarehouseEventRoutingResponse),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn get_warehouse_statistics<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    async fn get_warehouse_event_routing<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
         Path(warehouse_id): Path<uuid::Uuid>,
-        Query(query): Query<GetWarehouseStatisticsQuery>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
-    ) -> Result<Json<WarehouseStatisticsResponse>> {
-        ApiServer::<C, A, S>::get_warehouse_statistics(
+    ) -> Result<GetWarehouseEventRoutingResponse> {
+        ApiServer::<C, A, S>::get_warehouse_event_routing(
             warehouse_id.into(),
-            query,
             api_context,
             metadata,
         )
         .await
-        .map(Json)
     }
 
-    /// Get API Statistics
-    ///
-    /// Retrieves detailed endpoint call statistics for your project, allowing you to monitor API usage patterns,
-    /// track frequency of operations, and analyze response codes.
-    ///
-    /// ## Data Collection
-    ///
-    /// The statistics include:
-    /// - Endpoint paths and HTTP methods
-    /// - Response status codes
-    /// - Call counts per endpoint
-    /// - Warehouse context (when applicable)
-    /// - Timestamps of activity
-    ///
-    /// ## Time Aggregation
-    ///
-    /// Statistics are aggregated hourly. Within each hour window:
-    /// - An initial entry is created on the first API call
-    /// - Subsequent calls update the existing hourly entry
-    /// - Each hour boundary creates a new aggregation bucket
-    /// - Hours with no API activity have no entries (gaps in data)
-    ///
-    /// ## Response Format
-    ///
-    /// The response includes timestamp buckets (in UTC) and corresponding endpoint metrics,
-    /// allowing for time-series analysis of API usage patterns.
+    /// Set Event Routing
     ///
-    /// Example:
-    /// - 00:00:00-00:16:32: no activity
-    ///     - timestamps: []
-    /// - 00:16:32: warehouse created:
-    ///     - timestamps: ["01:00:00"], called_endpoints: [[{"count": 1, "http_route": "POST /management/v1/warehouse", "status_code": 201, "warehouse_id": null, "warehouse_name": null, "created_at": "00:16:32", "updated_at": null}]]
-    /// - 00:30:00: table created:
-    ///     - timestamps: ["01:00:00"], called_endpoints: [[{"count": 1, "http_route": "POST /management/v1/warehouse", "status_code": 201, "warehouse_id": null, "warehouse_name": null, "created_at": "00:16:32", "updated_at": null},
-    ///                                                  {"count": 1, "http_route": "POST /catalog/v1/{prefix}/namespaces/{namespace}/tables", "status_code": 201, "warehouse_id": "ff17f1d0-90ad-4e7d-bf02-be718b78c2ee", "warehouse_name": "staging", "created_at": "00:30:00", "updated_at": null}]]
-    /// - 00:45:00: table created:
-    ///     - timestamps: ["01:00:00"], called_endpoints: [[{"count": 1, "http_route": "POST /management/v1/warehouse", "status_code": 201, "warehouse_id": null, "warehouse_name": null, "created_at": "00:16:32", "updated_at": null},
-    ///                                                  {"count": 1, "http_route": "POST /catalog/v1/{prefix}/namespaces/{namespace}/tables", "status_code": 201, "warehouse_id": "ff17f1d0-90ad-4e7d-bf02-be718b78c2ee", "warehouse_name": "staging", "created_at": "00:30:00", "updated_at": "00:45:00"}]]
-    /// - 01:00:36: table deleted:
-    ///     - timestamps: ["01:00:00","02:00:00"], called_endpoints: [[{"count": 1, "http_route": "POST /management/v1/warehouse", "status_code": 201, "warehouse_id": null, "warehouse_name": null, "created_at": "00:16:32", "updated_at": null},
-    ///                                                  {"count": 1, "http_route": "POST /catalog/v1/{prefix}/namespaces/{namespace}/tables", "status_code": 201, "warehouse_id": "ff17f1d0-90ad-4e7d-bf02-be718b78c2ee", "warehouse_name": "staging", "created_at": "00:30:00", "updated_at": "00:45:00"}],
-    ///                                                   [{"count": 1, "http_route": "DELETE /catalog/v1/{prefix}/namespaces/{namespace}/tables/{table}", "status_code": 200, "warehouse_id": "ff17f1d0-90ad-4e7d-bf02-be718b78c2ee", "warehouse_name": "staging", "created_at": "01:00:36", "updated_at": "null"}]]
+    /// Replaces the rules routing cloud events of the warehouse to specific sinks and topics,
+    /// for example to publish commits in a namespace to a dedicated Kafka topic.
+    /// Changes apply to all Lakekeeper instances within 30 seconds.
     #[utoipa::path(
         post,
-        tag = "project",
-        path = ManagementV1Endpoint::LoadEndpointStatistics.path(),
-        request_body = GetEndpointStatisticsRequest,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::SetWarehouseEventRouting.path(),
+        params(("warehouse_id" = Uuid,)),
+        request_body = SetWarehouseEventRoutingRequest,
         responses(
-            (status = 200, description = "Endpoint statistics", body = EndpointStatisticsResponse),
+            (status = 200, description = "Event routing rules updated successfully"),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn get_endpoint_statistics<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    async fn set_warehouse_event_routing<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
-        Json(query): Json<GetEndpointStatisticsRequest>,
-    ) -> Result<Json<EndpointStatisticsResponse>> {
-        ApiServer::<C, A, S>::get_endpoint_statistics(api_context, query, metadata)
-            .await
-            .map(Json)
+        Json(request): Json<SetWarehouseEventRoutingRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::set_warehouse_event_routing(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
     }
 
-    /// List Soft-Deleted Tabulars
+    /// List Referenced Files
     ///
-    /// Returns all soft-deleted tables and views in the warehouse that are visible to the current user.
+    /// Lists the metadata files, manifest lists and statistics files referenced by the tables
+    /// and views of a warehouse, including soft-deleted ones, so that external garbage
+    /// collection and backup tools don't need to load every table.
+    /// Each page is read consistently. Files that are not listed on any page and are older
+    /// than `listing-started-at` are no longer referenced by the catalog.
     #[utoipa::path(
         get,
         tag = "warehouse",
-        path = ManagementV1Endpoint::ListDeletedTabulars.path(),
-        params(("warehouse_id" = Uuid,), ListDeletedTabularsQuery),
+        path = ManagementV1Endpoint::ListReferencedFiles.path(),
+        params(("warehouse_id" = Uuid,), ListReferencedFilesQuery),
         responses(
-            (status = 200, description = "List of soft-deleted tabulars", body = ListDeletedTabularsResponse),
+            (status = 200, description = "Files referenced by the tables and views of the warehouse", body = ListReferencedFilesResponse),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn list_deleted_tabulars<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    async fn list_referenced_files<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
         Path(warehouse_id): Path<uuid::Uuid>,
-        Query(query): Query<ListDeletedTabularsQuery>,
+        Query(query): Query<ListReferencedFilesQuery>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
-    ) -> Result<Json<ListDeletedTabularsResponse>> {
-        ApiServer::<C, A, S>::list_soft_deleted_tabulars(
+    ) -> Result<ListReferencedFilesResponse> {
+        ApiServer::<C, A, S>::list_referenced_files(
             warehouse_id.into(),
             query,
             api_context,
             metadata,
         )
         .await
-        .map(Json)
     }
 
-    /// Undrop Tabular
+    /// Get Warehouse Labels
     ///
-    /// Restores previously deleted tables or views to make them accessible again.
-    /// This endpoint is deprecated and will be removed soon.
+    /// Returns the labels set on a warehouse.
     #[utoipa::path(
-        post,
+        get,
         tag = "warehouse",
-        path = ManagementV1Endpoint::UndropTabularsDeprecated.path(),
+        path = ManagementV1Endpoint::GetWarehouseLabels.path(),
         params(("warehouse_id" = Uuid,)),
         responses(
-            (status = 204, description = "Tabular undropped successfully"),
+            (status = 200, body = GetLabelsResponse),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    #[deprecated(
-        since = "0.7.0",
-        note = "This endpoint is deprecated and will be removed soon, please use /management/v1/warehouse/{warehouse_id}/deleted-tabulars/undrop instead."
-    )]
-    async fn undrop_tabulars_deprecated<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    async fn get_warehouse_labels<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
         Path(warehouse_id): Path<uuid::Uuid>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
-        Json(request): Json<UndropTabularsRequest>,
-    ) -> Result<StatusCode> {
-        ApiServer::<C, A, S>::undrop_tabulars(
-            WarehouseId::from(warehouse_id),
-            metadata,
-            request,
-            api_context,
-        )
-        .await?;
-        Ok(StatusCode::NO_CONTENT)
+    ) -> Result<GetLabelsResponse> {
+        ApiServer::<C, A, S>::get_warehouse_labels(warehouse_id.into(), api_context, metadata).await
     }
 
-    /// Undrop Tabular
+    /// Set Warehouse Labels
     ///
-    /// Restores previously deleted tables or views to make them accessible again.
+    /// Replaces the labels of a warehouse. Labels are validated against the label definitions
+    /// of the project. Labels of a warehouse are inherited by all its tables.
     #[utoipa::path(
         post,
         tag = "warehouse",
-        path = ManagementV1Endpoint::UndropTabulars.path(),
+        path = ManagementV1Endpoint::SetWarehouseLabels.path(),
         params(("warehouse_id" = Uuid,)),
+        request_body = SetLabelsRequest,
         responses(
-            (status = 204, description = "Tabular undropped successfully"),
+            (status = 200, description = "Updated labels of the warehouse", body = GetLabelsResponse),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn undrop_tabulars<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    async fn set_warehouse_labels<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
         Path(warehouse_id): Path<uuid::Uuid>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
-        Json(request): Json<UndropTabularsRequest>,
-    ) -> Result<StatusCode> {
-        ApiServer::<C, A, S>::undrop_tabulars(
-            WarehouseId::from(warehouse_id),
-            metadata,
+        Json(request): Json<SetLabelsRequest>,
+    ) -> Result<GetLabelsResponse> {
+        ApiServer::<C, A, S>::set_warehouse_labels(
+            warehouse_id.into(),
             request,
             api_context,
+            metadata,
         )
-        .await?;
-        Ok(StatusCode::NO_CONTENT)
-    }
-
-    #[derive(Serialize, Deserialize, Debug, utoipa::ToSchema)]
-    pub struct ProtectionResponse {
-        /// Indicates whether the entity is protected
-        pub protected: bool,
-        /// Updated at
-        pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
-    }
-
-    impl IntoResponse for ProtectionResponse {
-        fn into_response(self) -> Response {
-            (StatusCode::OK, Json(self)).into_response()
-        }
+        .await
     }
 
-    /// Get Table Protection
+    /// Get Namespace Labels
     ///
-    /// Retrieves whether a table is protected from deletion.
+    /// Returns the labels set on a namespace. Labels inherited from parent namespaces
+    /// and the warehouse are not included.
     #[utoipa::path(
         get,
         tag = "warehouse",
-        path = ManagementV1Endpoint::GetTableProtection.path(),
-        params(("warehouse_id" = Uuid,),("table_id" = Uuid,)),
+        path = ManagementV1Endpoint::GetNamespaceLabels.path(),
+        params(("warehouse_id" = Uuid,),("namespace_id" = Uuid,)),
         responses(
-            (status = 200, body =  ProtectionResponse),
+            (status = 200, body = GetLabelsResponse),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn get_table_protection<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
-        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
-        Extension(metadata): Extension<RequestMetadata>,
+    async fn get_namespace_labels<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, namespace_id)): Path<(uuid::Uuid, uuid::Uuid)>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
-    ) -> Result<ProtectionResponse> {
-        ApiServer::<C, A, S>::get_table_protection(
-            TableId::from(table_id),
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<GetLabelsResponse> {
+        ApiServer::<C, A, S>::get_namespace_labels(
             warehouse_id.into(),
+            NamespaceId::from(namespace_id),
             api_context,
             metadata,
         )
         .await
     }
 
-    /// Set Table Protection
+    /// Set Namespace Labels
     ///
-    /// Configures whether a table should be protected from deletion.
+    /// Replaces the labels of a namespace. Labels of a namespace are inherited by all
+    /// tables in the namespace and its children.
     #[utoipa::path(
         post,
         tag = "warehouse",
-        path = ManagementV1Endpoint::SetTableProtection.path(),
-        params(("warehouse_id" = Uuid,),("table_id" = Uuid,)),
+        path = ManagementV1Endpoint::SetNamespaceLabels.path(),
+        params(("warehouse_id" = Uuid,),("namespace_id" = Uuid,)),
+        request_body = SetLabelsRequest,
         responses(
-            (status = 200, body =  ProtectionResponse, description = "Table protection set successfully"),
+            (status = 200, description = "Updated labels of the namespace", body = GetLabelsResponse),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn set_table_protection<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
-        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
-        Extension(metadata): Extension<RequestMetadata>,
+    async fn set_namespace_labels<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, namespace_id)): Path<(uuid::Uuid, uuid::Uuid)>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
-        Json(SetProtectionRequest { protected }): Json<SetProtectionRequest>,
-    ) -> Result<ProtectionResponse> {
-        ApiServer::<C, A, S>::set_table_protection(
-            TableId::from(table_id),
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<SetLabelsRequest>,
+    ) -> Result<GetLabelsResponse> {
+        ApiServer::<C, A, S>::set_namespace_labels(
             warehouse_id.into(),
-            protected,
+            NamespaceId::from(namespace_id),
+            request,
             api_context,
             metadata,
         )
         .await
     }
 
-    /// Get View Protection
+    /// Get Table Labels
     ///
-    /// Retrieves whether a view is protected from deletion.
+    /// Returns the labels set on a table. Labels inherited from its namespaces
+    /// and the warehouse are not included.
     #[utoipa::path(
         get,
         tag = "warehouse",
-        path = ManagementV1Endpoint::GetViewProtection.path(),
-        params(("warehouse_id" = Uuid,),("view_id" = Uuid,)),
+        path = ManagementV1Endpoint::GetTableLabels.path(),
+        params(("warehouse_id" = Uuid,),("table_id" = Uuid,)),
         responses(
-            (status = 200, body = ProtectionResponse),
+            (status = 200, body = GetLabelsResponse),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn get_view_protection<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
-        Path((warehouse_id, view_id)): Path<(uuid::Uuid, uuid::Uuid)>,
-        Extension(metadata): Extension<RequestMetadata>,
+    async fn get_table_labels<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
-    ) -> Result<ProtectionResponse> {
-        ApiServer::<C, A, S>::get_view_protection(
-            ViewId::from(view_id),
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<GetLabelsResponse> {
+        ApiServer::<C, A, S>::get_table_labels(
             warehouse_id.into(),
+            TableId::from(table_id),
             api_context,
             metadata,
         )
         .await
     }
 
-    /// Set View Protection
+    /// Set Table Labels
     ///
-    /// Configures whether a view should be protected from deletion.
+    /// Replaces the labels of a table. Adding, changing or removing a label that is covered
+    /// by a label policy requires the permission to manage labels of the project.
     #[utoipa::path(
         post,
         tag = "warehouse",
-        path = ManagementV1Endpoint::SetViewProtection.path(),
-        params(("warehouse_id" = Uuid,),("view_id" = Uuid,)),
+        path = ManagementV1Endpoint::SetTableLabels.path(),
+        params(("warehouse_id" = Uuid,),("table_id" = Uuid,)),
+        request_body = SetLabelsRequest,
         responses(
-            (status = 200, body = ProtectionResponse, description = "View protection set successfully"),
+            (status = 200, description = "Updated labels of the table", body = GetLabelsResponse),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn set_view_protection<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
-        Path((warehouse_id, view_id)): Path<(uuid::Uuid, uuid::Uuid)>,
-        Extension(metadata): Extension<RequestMetadata>,
+    async fn set_table_labels<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
-        Json(SetProtectionRequest { protected }): Json<SetProtectionRequest>,
-    ) -> Result<ProtectionResponse> {
-        ApiServer::<C, A, S>::set_view_protection(
-            ViewId::from(view_id),
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<SetLabelsRequest>,
+    ) -> Result<GetLabelsResponse> {
+        ApiServer::<C, A, S>::set_table_labels(
             warehouse_id.into(),
-            protected,
+            TableId::from(table_id),
+            request,
             api_context,
             metadata,
         )
         .await
     }
 
-    /// Get Namespace Protection
+    /// Watch Table or View
     ///
-    /// Retrieves whether a namespace is protected from deletion.
+    /// Waits until the watched table or view is committed to or dropped, so that orchestrators
+    /// can react to the next commit of a table without consuming the events of the whole catalog.
+    /// Pass the `cursor` of the previous response to resume watching. Changes that happened
+    /// in between are returned immediately. Returns `changed: false` if the entity did not
+    /// change within `timeoutSeconds`.
     #[utoipa::path(
         get,
         tag = "warehouse",
-        path = ManagementV1Endpoint::GetNamespaceProtection.path(),
-        params(("warehouse_id" = Uuid,),("namespace_id" = Uuid,)),
+        path = ManagementV1Endpoint::Watch.path(),
+        params(("warehouse_id" = Uuid,), WatchQuery),
         responses(
-            (status = 200, body = ProtectionResponse),
+            (status = 200, body = WatchResponse),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn get_namespace_protection<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
-        Path((warehouse_id, namespace_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+    async fn watch<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        Query(query): Query<WatchQuery>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<WatchResponse> {
+        ApiServer::<C, A, S>::watch(warehouse_id.into(), query, api_context, metadata).await
+    }
+
+    /// Get Replication State
+    ///
+    /// Returns the current state of the warehouse, including the metadata locations of all
+    /// tables and views. Read replicas of this Lakekeeper poll this endpoint to mirror the
+    /// warehouse.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::GetReplicationState.path(),
+        params(("warehouse_id" = Uuid,)),
+        responses(
+            (status = 200, body = ReplicationStateResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_replication_state<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<ReplicationStateResponse> {
+        ApiServer::<C, A, S>::get_replication_state(warehouse_id.into(), api_context, metadata)
+            .await
+    }
+
+    /// Get Warehouse Slug
+    ///
+    /// Returns the slug of the warehouse-scoped catalog URL and its aliases.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::GetWarehouseSlug.path(),
+        params(("warehouse_id" = Uuid,)),
+        responses(
+            (status = 200, description = "Slug of the warehouse", body = GetWarehouseSlugResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_warehouse_slug<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
-    ) -> Result<ProtectionResponse> {
-        ApiServer::<C, A, S>::get_namespace_protection(
-            NamespaceId::from(namespace_id),
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<GetWarehouseSlugResponse> {
+        ApiServer::<C, A, S>::get_warehouse_slug(warehouse_id.into(), api_context, metadata).await
+    }
+
+    /// Set Warehouse Slug
+    ///
+    /// Sets the slug of the warehouse-scoped catalog URL `/catalog/w/{slug}/v1`.
+    /// Engines can use this URL as catalog URI without specifying a warehouse.
+    /// The previous slug remains an alias, so that existing URLs keep working.
+    /// Slugs are unique across all projects.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::SetWarehouseSlug.path(),
+        params(("warehouse_id" = Uuid,)),
+        request_body = SetWarehouseSlugRequest,
+        responses(
+            (status = 200, description = "Slug set successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn set_warehouse_slug<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<SetWarehouseSlugRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::set_warehouse_slug(
             warehouse_id.into(),
+            request,
             api_context,
             metadata,
         )
         .await
     }
 
-    /// Set Namespace Protection
+    /// Delete Warehouse Slug
     ///
-    /// Configures whether a namespace should be protected from deletion.
+    /// Deletes the slug of the warehouse and all of its aliases.
+    /// Warehouse-scoped URLs of the warehouse stop working.
+    #[utoipa::path(
+        delete,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::DeleteWarehouseSlug.path(),
+        params(("warehouse_id" = Uuid,)),
+        responses(
+            (status = 200, description = "Slugs deleted successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn delete_warehouse_slug<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::delete_warehouse_slug(warehouse_id.into(), api_context, metadata)
+            .await
+    }
+
+    /// Deactivate Warehouse
+    ///
+    /// Temporarily disables access to a warehouse without deleting its data.
     #[utoipa::path(
         post,
         tag = "warehouse",
-        path = ManagementV1Endpoint::SetNamespaceProtection.path(),
-        params(("warehouse_id" = Uuid,),("namespace_id" = Uuid,)),
+        path = ManagementV1Endpoint::DeactivateWarehouse.path(),
+        params(("warehouse_id" = Uuid,)),
+        responses(
+            (status = 200, description = "Warehouse deactivated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn deactivate_warehouse<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::deactivate_warehouse(warehouse_id.into(), api_context, metadata).await
+    }
+
+    /// Activate Warehouse
+    ///
+    /// Re-enables access to a previously deactivated warehouse.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::ActivateWarehouse.path(),
+        params(("warehouse_id" = Uuid,)),
+        responses(
+            (status = 200, description = "Warehouse activated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn activate_warehouse<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::activate_warehouse(warehouse_id.into(), api_context, metadata).await
+    }
+
+    /// Update Storage Profile
+    ///
+    /// Updates both the storage profile and credentials of a warehouse.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::UpdateStorageProfile.path(),
+        params(("warehouse_id" = Uuid,)),
+        request_body = UpdateWarehouseStorageRequest,
+        responses(
+            (status = 200, description = "Storage profile updated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn update_storage_profile<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<UpdateWarehouseStorageRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::update_storage(warehouse_id.into(), request, api_context, metadata)
+            .await
+    }
+
+    /// Probe Storage Quirks
+    ///
+    /// Detects the vendor of the S3-compatible storage of a warehouse and tests whether
+    /// listings immediately reflect written and deleted objects.
+    /// Returns the quirks that should be configured in the storage profile.
+    /// Objects are written to and removed from a random location below the key prefix.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::ProbeStorageQuirks.path(),
+        params(("warehouse_id" = Uuid,)),
+        responses(
+            (status = 200, description = "Storage probed successfully", body = S3QuirksProbe),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn probe_storage_quirks<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<Json<S3QuirksProbe>> {
+        ApiServer::<C, A, S>::probe_storage_quirks(warehouse_id.into(), api_context, metadata)
+            .await
+            .map(Json)
+    }
+
+    /// Update Storage Credential
+    ///
+    /// Updates only the storage credential of a warehouse without modifying the storage profile.
+    /// Useful for refreshing expiring credentials.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::UpdateStorageCredential.path(),
+        params(("warehouse_id" = Uuid,)),
+        request_body = UpdateWarehouseCredentialRequest,
+        responses(
+            (status = 200, description = "Storage credential updated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn update_storage_credential<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<UpdateWarehouseCredentialRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::update_storage_credential(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Rotate Storage Credential
+    ///
+    /// Replaces the storage credential of a warehouse without interrupting running requests.
+    /// The previous credential remains available for requests that are already in flight,
+    /// such as remote signing, and is deleted once the grace period ends.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::RotateStorageCredential.path(),
+        params(("warehouse_id" = Uuid,)),
+        request_body = RotateStorageCredentialRequest,
+        responses(
+            (status = 200, description = "Storage credential rotated successfully", body = RotateStorageCredentialResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn rotate_storage_credential<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<RotateStorageCredentialRequest>,
+    ) -> Result<Json<RotateStorageCredentialResponse>> {
+        ApiServer::<C, A, S>::rotate_storage_credential(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+        .map(Json)
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct RecursiveDeleteQuery {
+        #[serde(default)]
+        force: bool,
+        #[serde(default)]
+        purge: bool,
+    }
+
+    #[derive(Deserialize, Debug, ToSchema)]
+    pub struct SetProtectionRequest {
+        /// Setting this to `true` will prevent the entity from being deleted unless `force` is used.
+        pub protected: bool,
+    }
+
+    #[derive(Debug, Deserialize, Serialize, utoipa::IntoParams)]
+    pub struct GetWarehouseStatisticsQuery {
+        /// Next page token
+        #[serde(skip_serializing_if = "PageToken::skip_serialize")]
+        #[param(value_type=String)]
+        pub page_token: PageToken,
+        /// Signals an upper bound of the number of results that a client will receive.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        pub page_size: Option<i64>,
+    }
+
+    impl GetWarehouseStatisticsQuery {
+        fn to_pagination_query(&self) -> PaginationQuery {
+            PaginationQuery {
+                page_token: self.page_token.clone(),
+                page_size: self.page_size,
+            }
+        }
+    }
+
+    /// Get Warehouse Statistics
+    ///
+    /// Retrieves statistical data about a warehouse's usage and resources over time.
+    /// Statistics are aggregated hourly when changes occur.
+    ///
+    /// We lazily create a new statistics entry every hour, in between hours, the existing entry is
+    /// being updated. If there's a change at created_at + 1 hour, a new entry is created.
+    /// If there's been no change, no new entry is created, meaning there may be gaps.
+    ///
+    /// Example:
+    /// - 00:16:32: warehouse created:
+    ///     - timestamp: 01:00:00, created_at: 00:16:32, updated_at: null, 0 tables, 0 views
+    /// - 00:30:00: table created:
+    ///     - timestamp: 01:00:00, created_at: 00:16:32, updated_at: 00:30:00, 1 table, 0 views
+    /// - 00:45:00: view created:
+    ///     - timestamp: 01:00:00, created_at: 00:16:32, updated_at: 00:45:00, 1 table, 1 view
+    /// - 01:00:36: table deleted:
+    ///     - timestamp: 02:00:00, created_at: 01:00:36, updated_at: null, 0 tables, 1 view
+    ///     - timestamp: 01:00:00, created_at: 00:16:32, updated_at: 00:45:00, 1 table, 1 view
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::GetWarehouseStatistics.path(),
+        params(("warehouse_id" = Uuid,), GetWarehouseStatisticsQuery),
+        responses(
+            (status = 200, description = "Warehouse statistics", body = WarehouseStatisticsResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_warehouse_statistics<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        Query(query): Query<GetWarehouseStatisticsQuery>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<Json<WarehouseStatisticsResponse>> {
+        ApiServer::<C, A, S>::get_warehouse_statistics(
+            warehouse_id.into(),
+            query,
+            api_context,
+            metadata,
+        )
+        .await
+        .map(Json)
+    }
+
+    /// Get API Statistics
+    ///
+    /// Retrieves detailed endpoint call statistics for your project, allowing you to monitor API usage patterns,
+    /// track frequency of operations, and analyze response codes.
+    ///
+    /// ## Data Collection
+    ///
+    /// The statistics include:
+    /// - Endpoint paths and HTTP methods
+    /// - Response status codes
+    /// - Call counts per endpoint
+    /// - Warehouse context (when applicable)
+    /// - Timestamps of activity
+    ///
+    /// ## Time Aggregation
+    ///
+    /// Statistics are aggregated hourly. Within each hour window:
+    /// - An initial entry is created on the first API call
+    /// - Subsequent calls update the existing hourly entry
+    /// - Each hour boundary creates a new aggregation bucket
+    /// - Hours with no API activity have no entries (gaps in data)
+    ///
+    /// ## Response Format
+    ///
+    /// The response includes timestamp buckets (in UTC) and corresponding endpoint metrics,
+    /// allowing for time-series analysis of API usage patterns.
+    ///
+    /// Example:
+    /// - 00:00:00-00:16:32: no activity
+    ///     - timestamps: []
+    /// - 00:16:32: warehouse created:
+    ///     - timestamps: ["01:00:00"], called_endpoints: [[{"count": 1, "http_route": "POST /management/v1/warehouse", "status_code": 201, "warehouse_id": null, "warehouse_name": null, "created_at": "00:16:32", "updated_at": null}]]
+    /// - 00:30:00: table created:
+    ///     - timestamps: ["01:00:00"], called_endpoints: [[{"count": 1, "http_route": "POST /management/v1/warehouse", "status_code": 201, "warehouse_id": null, "warehouse_name": null, "created_at": "00:16:32", "updated_at": null},
+    ///                                                  {"count": 1, "http_route": "POST /catalog/v1/{prefix}/namespaces/{namespace}/tables", "status_code": 201, "warehouse_id": "ff17f1d0-90ad-4e7d-bf02-be718b78c2ee", "warehouse_name": "staging", "created_at": "00:30:00", "updated_at": null}]]
+    /// - 00:45:00: table created:
+    ///     - timestamps: ["01:00:00"], called_endpoints: [[{"count": 1, "http_route": "POST /management/v1/warehouse", "status_code": 201, "warehouse_id": null, "warehouse_name": null, "created_at": "00:16:32", "updated_at": null},
+    ///                                                  {"count": 1, "http_route": "POST /catalog/v1/{prefix}/namespaces/{namespace}/tables", "status_code": 201, "warehouse_id": "ff17f1d0-90ad-4e7d-bf02-be718b78c2ee", "warehouse_name": "staging", "created_at": "00:30:00", "updated_at": "00:45:00"}]]
+    /// - 01:00:36: table deleted:
+    ///     - timestamps: ["01:00:00","02:00:00"], called_endpoints: [[{"count": 1, "http_route": "POST /management/v1/warehouse", "status_code": 201, "warehouse_id": null, "warehouse_name": null, "created_at": "00:16:32", "updated_at": null},
+    ///                                                  {"count": 1, "http_route": "POST /catalog/v1/{prefix}/namespaces/{namespace}/tables", "status_code": 201, "warehouse_id": "ff17f1d0-90ad-4e7d-bf02-be718b78c2ee", "warehouse_name": "staging", "created_at": "00:30:00", "updated_at": "00:45:00"}],
+    ///                                                   [{"count": 1, "http_route": "DELETE /catalog/v1/{prefix}/namespaces/{namespace}/tables/{table}", "status_code": 200, "warehouse_id": "ff17f1d0-90ad-4e7d-bf02-be718b78c2ee", "warehouse_name": "staging", "created_at": "01:00:36", "updated_at": "null"}]]
+    #[utoipa::path(
+        post,
+        tag = "project",
+        path = ManagementV1Endpoint::LoadEndpointStatistics.path(),
+        request_body = GetEndpointStatisticsRequest,
+        responses(
+            (status = 200, description = "Endpoint statistics", body = EndpointStatisticsResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_endpoint_statistics<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(query): Json<GetEndpointStatisticsRequest>,
+    ) -> Result<Json<EndpointStatisticsResponse>> {
+        ApiServer::<C, A, S>::get_endpoint_statistics(api_context, query, metadata)
+            .await
+            .map(Json)
+    }
+
+    /// Get Usage Statistics
+    ///
+    /// Returns the number of API calls, remote signing requests, and the number of tables, views
+    /// and snapshots per warehouse of the project, aggregated per day or month (UTC).
+    /// Usage is aggregated periodically from the endpoint statistics, so the current day
+    /// lags behind by up to 15 minutes.
+    #[utoipa::path(
+        get,
+        tag = "project",
+        path = ManagementV1Endpoint::GetUsageStatistics.path(),
+        params(GetUsageStatisticsQuery),
+        responses(
+            (status = 200, body = GetUsageStatisticsResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_usage_statistics<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Query(query): Query<GetUsageStatisticsQuery>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<GetUsageStatisticsResponse> {
+        ApiServer::<C, A, S>::get_usage_statistics(query, api_context, metadata).await
+    }
+
+    /// List Soft-Deleted Tabulars
+    ///
+    /// Returns all soft-deleted tables and views in the warehouse that are visible to the current user.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::ListDeletedTabulars.path(),
+        params(("warehouse_id" = Uuid,), ListDeletedTabularsQuery),
+        responses(
+            (status = 200, description = "List of soft-deleted tabulars", body = ListDeletedTabularsResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn list_deleted_tabulars<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        Query(query): Query<ListDeletedTabularsQuery>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<Json<ListDeletedTabularsResponse>> {
+        ApiServer::<C, A, S>::list_soft_deleted_tabulars(
+            warehouse_id.into(),
+            query,
+            api_context,
+            metadata,
+        )
+        .await
+        .map(Json)
+    }
+
+    /// Undrop Tabular
+    ///
+    /// Restores previously deleted tables or views to make them accessible again.
+    /// This endpoint is deprecated and will be removed soon.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::UndropTabularsDeprecated.path(),
+        params(("warehouse_id" = Uuid,)),
+        responses(
+            (status = 204, description = "Tabular undropped successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    #[deprecated(
+        since = "0.7.0",
+        note = "This endpoint is deprecated and will be removed soon, please use /management/v1/warehouse/{warehouse_id}/deleted-tabulars/undrop instead."
+    )]
+    async fn undrop_tabulars_deprecated<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<UndropTabularsRequest>,
+    ) -> Result<StatusCode> {
+        ApiServer::<C, A, S>::undrop_tabulars(
+            WarehouseId::from(warehouse_id),
+            metadata,
+            request,
+            api_context,
+        )
+        .await?;
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    /// Undrop Tabular
+    ///
+    /// Restores previously deleted tables or views to make them accessible again.
+    /// Their pending expiration tasks are cancelled. Tabulars that are currently being expired cannot be restored.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::UndropTabulars.path(),
+        params(("warehouse_id" = Uuid,)),
+        responses(
+            (status = 204, description = "Tabular undropped successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn undrop_tabulars<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<UndropTabularsRequest>,
+    ) -> Result<StatusCode> {
+        ApiServer::<C, A, S>::undrop_tabulars(
+            WarehouseId::from(warehouse_id),
+            metadata,
+            request,
+            api_context,
+        )
+        .await?;
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    #[derive(Serialize, Deserialize, Debug, utoipa::ToSchema)]
+    pub struct ProtectionResponse {
+        /// Indicates whether the entity is protected
+        pub protected: bool,
+        /// Updated at
+        pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    }
+
+    impl IntoResponse for ProtectionResponse {
+        fn into_response(self) -> Response {
+            (StatusCode::OK, Json(self)).into_response()
+        }
+    }
+
+    /// Get Table Protection
+    ///
+    /// Retrieves whether a table is protected from deletion.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::GetTableProtection.path(),
+        params(("warehouse_id" = Uuid,),("table_id" = Uuid,)),
+        responses(
+            (status = 200, body =  ProtectionResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_table_protection<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+    ) -> Result<ProtectionResponse> {
+        ApiServer::<C, A, S>::get_table_protection(
+            TableId::from(table_id),
+            warehouse_id.into(),
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Set Table Protection
+    ///
+    /// Configures whether a table should be protected from deletion.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::SetTableProtection.path(),
+        params(("warehouse_id" = Uuid,),("table_id" = Uuid,)),
+        responses(
+            (status = 200, body =  ProtectionResponse, description = "Table protection set successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn set_table_protection<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Json(SetProtectionRequest { protected }): Json<SetProtectionRequest>,
+    ) -> Result<ProtectionResponse> {
+        ApiServer::<C, A, S>::set_table_protection(
+            TableId::from(table_id),
+            warehouse_id.into(),
+            protected,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Set Table Comment
+    ///
+    /// Sets or removes the comment of a table without constructing a full table commit.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::SetTableComment.path(),
+        params(("warehouse_id" = Uuid,),("table_id" = Uuid,)),
+        request_body = SetTableCommentRequest,
+        responses(
+            (status = 204, description = "Table comment updated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn set_table_comment<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Json(request): Json<SetTableCommentRequest>,
+    ) -> Result<(StatusCode, ())> {
+        ApiServer::<C, A, S>::set_table_comment(
+            TableId::from(table_id),
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+        .map(|()| (StatusCode::NO_CONTENT, ()))
+    }
+
+    /// Set Column Comments
+    ///
+    /// Sets or removes the comments of columns of a table. A new schema is added to the table and set as current schema.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::SetColumnComments.path(),
+        params(("warehouse_id" = Uuid,),("table_id" = Uuid,)),
+        request_body = SetColumnCommentsRequest,
+        responses(
+            (status = 204, description = "Column comments updated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn set_column_comments<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Json(request): Json<SetColumnCommentsRequest>,
+    ) -> Result<(StatusCode, ())> {
+        ApiServer::<C, A, S>::set_column_comments(
+            TableId::from(table_id),
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+        .map(|()| (StatusCode::NO_CONTENT, ()))
+    }
+
+    /// Get Column Tags
+    ///
+    /// Returns the tags of all tagged columns of a table. Tags are attached to
+    /// field ids and therefore survive column renames and type promotions.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::GetColumnTags.path(),
+        params(("warehouse_id" = Uuid,),("table_id" = Uuid,)),
+        responses(
+            (status = 200, body = GetColumnTagsResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_column_tags<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+    ) -> Result<GetColumnTagsResponse> {
+        ApiServer::<C, A, S>::get_column_tags(
+            TableId::from(table_id),
+            warehouse_id.into(),
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Set Column Tags
+    ///
+    /// Replaces the tags of the given columns of a table.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::SetColumnTags.path(),
+        params(("warehouse_id" = Uuid,),("table_id" = Uuid,)),
+        request_body = SetColumnTagsRequest,
+        responses(
+            (status = 204, description = "Column tags updated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn set_column_tags<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Json(request): Json<SetColumnTagsRequest>,
+    ) -> Result<(StatusCode, ())> {
+        ApiServer::<C, A, S>::set_column_tags(
+            TableId::from(table_id),
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+        .map(|()| (StatusCode::NO_CONTENT, ()))
+    }
+
+    /// List Tagged Columns
+    ///
+    /// Lists all columns of tables in a warehouse that carry the given tag.
+    /// Only tables the caller can access are included.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::ListTaggedColumns.path(),
+        params(("warehouse_id" = Uuid,),("tag" = String,)),
+        responses(
+            (status = 200, body = ListTaggedColumnsResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn list_tagged_columns<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, tag)): Path<(uuid::Uuid, String)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+    ) -> Result<ListTaggedColumnsResponse> {
+        ApiServer::<C, A, S>::list_tagged_columns(warehouse_id.into(), tag, api_context, metadata)
+            .await
+    }
+
+    /// List Column Tag Suggestions
+    ///
+    /// Lists the tags suggested for the columns of a table by the PII detection
+    /// task queue. Suggestions must be reviewed before they become column tags.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::ListColumnTagSuggestions.path(),
+        params(("warehouse_id" = Uuid,),("table_id" = Uuid,), ListColumnTagSuggestionsQuery),
+        responses(
+            (status = 200, body = ListColumnTagSuggestionsResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn list_column_tag_suggestions<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Query(query): Query<ListColumnTagSuggestionsQuery>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+    ) -> Result<ListColumnTagSuggestionsResponse> {
+        ApiServer::<C, A, S>::list_column_tag_suggestions(
+            TableId::from(table_id),
+            warehouse_id.into(),
+            query,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Review Column Tag Suggestions
+    ///
+    /// Accepts or rejects pending tag suggestions of a table. Accepted tags are
+    /// added to the column tags, rejected tags are not suggested again.
+    /// Fails if any of the suggestions does not exist or is not pending.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::ReviewColumnTagSuggestions.path(),
+        params(("warehouse_id" = Uuid,),("table_id" = Uuid,)),
+        request_body = ReviewColumnTagSuggestionsRequest,
+        responses(
+            (status = 204, description = "Suggestions reviewed successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn review_column_tag_suggestions<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Json(request): Json<ReviewColumnTagSuggestionsRequest>,
+    ) -> Result<(StatusCode, ())> {
+        ApiServer::<C, A, S>::review_column_tag_suggestions(
+            TableId::from(table_id),
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+        .map(|()| (StatusCode::NO_CONTENT, ()))
+    }
+
+    /// Propose Table Property Changes
+    ///
+    /// Proposes to set or remove properties of a table, i.e. to change its retention.
+    /// Requires read access to the table. The change is applied once a user
+    /// that is allowed to commit to the table approves the proposal.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::CreateTablePropertyProposal.path(),
+        params(("warehouse_id" = Uuid,),("table_id" = Uuid,)),
+        request_body = CreateTablePropertyProposalRequest,
+        responses(
+            (status = 201, body = TablePropertyProposal),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn create_table_property_proposal<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Json(request): Json<CreateTablePropertyProposalRequest>,
+    ) -> Result<(StatusCode, Json<TablePropertyProposal>)> {
+        ApiServer::<C, A, S>::create_table_property_proposal(
+            TableId::from(table_id),
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+        .map(|proposal| (StatusCode::CREATED, Json(proposal)))
+    }
+
+    /// List Table Property Proposals
+    ///
+    /// Lists the property change proposals of a table, newest first.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::ListTablePropertyProposals.path(),
+        params(("warehouse_id" = Uuid,),("table_id" = Uuid,), ListTablePropertyProposalsQuery),
+        responses(
+            (status = 200, body = ListTablePropertyProposalsResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn list_table_property_proposals<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Query(query): Query<ListTablePropertyProposalsQuery>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+    ) -> Result<ListTablePropertyProposalsResponse> {
+        ApiServer::<C, A, S>::list_table_property_proposals(
+            TableId::from(table_id),
+            warehouse_id.into(),
+            query,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Review Table Property Proposal
+    ///
+    /// Approves or rejects a pending property change proposal. Requires permission
+    /// to commit to the table. Approved changes are committed to the table on behalf
+    /// of the reviewer; the proposal records both the proposer and the reviewer.
+    /// If the commit fails, the proposal stays pending.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::ReviewTablePropertyProposal.path(),
+        params(("warehouse_id" = Uuid,),("table_id" = Uuid,),("proposal_id" = Uuid,)),
+        request_body = ReviewTablePropertyProposalRequest,
+        responses(
+            (status = 200, body = TablePropertyProposal),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn review_table_property_proposal<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id, proposal_id)): Path<(uuid::Uuid, uuid::Uuid, uuid::Uuid)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Json(request): Json<ReviewTablePropertyProposalRequest>,
+    ) -> Result<TablePropertyProposal> {
+        ApiServer::<C, A, S>::review_table_property_proposal(
+            TableId::from(table_id),
+            warehouse_id.into(),
+            proposal_id,
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Detect PII
+    ///
+    /// Queues a PII detection run for a table. Requires PII detection to be
+    /// enabled on the server. Results are available via the column tag
+    /// suggestions endpoint once the task completed.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::DetectPii.path(),
+        params(("warehouse_id" = Uuid,),("table_id" = Uuid,)),
+        responses(
+            (status = 202, description = "PII detection queued"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn detect_pii<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+    ) -> Result<(StatusCode, ())> {
+        ApiServer::<C, A, S>::detect_pii(
+            TableId::from(table_id),
+            warehouse_id.into(),
+            api_context,
+            metadata,
+        )
+        .await
+        .map(|()| (StatusCode::ACCEPTED, ()))
+    }
+
+    /// Get Snapshot Expiration
+    ///
+    /// Returns the next scheduled snapshot expiration of a table and the
+    /// results of its most recent runs.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::GetSnapshotExpiration.path(),
+        params(("warehouse_id" = Uuid,),("table_id" = Uuid,)),
+        responses(
+            (status = 200, body = GetSnapshotExpirationResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_snapshot_expiration<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+    ) -> Result<GetSnapshotExpirationResponse> {
+        ApiServer::<C, A, S>::get_snapshot_expiration(
+            TableId::from(table_id),
+            warehouse_id.into(),
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Get Table Compatibility
+    ///
+    /// Checks the current metadata of a table against known limitations of query
+    /// engines and returns the issues found per engine.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::GetTableCompatibility.path(),
+        params(("warehouse_id" = Uuid,),("table_id" = Uuid,), GetTableCompatibilityQuery),
+        responses(
+            (status = 200, body = GetTableCompatibilityResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_table_compatibility<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Query(query): Query<GetTableCompatibilityQuery>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+    ) -> Result<GetTableCompatibilityResponse> {
+        ApiServer::<C, A, S>::get_table_compatibility(
+            TableId::from(table_id),
+            warehouse_id.into(),
+            query,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Get Orphan File Cleanup
+    ///
+    /// Returns the next scheduled orphan file cleanup of a table and the
+    /// results of its most recent runs, including the detected orphans.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::GetOrphanFileCleanup.path(),
+        params(("warehouse_id" = Uuid,),("table_id" = Uuid,)),
+        responses(
+            (status = 200, body = GetOrphanFileCleanupResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_orphan_file_cleanup<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+    ) -> Result<GetOrphanFileCleanupResponse> {
+        ApiServer::<C, A, S>::get_orphan_file_cleanup(
+            TableId::from(table_id),
+            warehouse_id.into(),
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Cleanup Orphan Files
+    ///
+    /// Runs the orphan file cleanup of a table now instead of at its next
+    /// scheduled time. Requires orphan file cleanup to be enabled on the server.
+    /// Results are available via the orphan files endpoint once the task completed.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::CleanupOrphanFiles.path(),
+        params(("warehouse_id" = Uuid,),("table_id" = Uuid,)),
+        request_body = CleanupOrphanFilesRequest,
+        responses(
+            (status = 202, description = "Orphan file cleanup queued"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn cleanup_orphan_files<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Json(request): Json<CleanupOrphanFilesRequest>,
+    ) -> Result<(StatusCode, ())> {
+        ApiServer::<C, A, S>::cleanup_orphan_files(
+            TableId::from(table_id),
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+        .map(|()| (StatusCode::ACCEPTED, ()))
+    }
+
+    /// Get Maintenance Recommendations
+    ///
+    /// Analyzes the data files of the current snapshot of a table and returns
+    /// the partitions that should be compacted, together with the small data files
+    /// and delete files to rewrite.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::GetMaintenanceRecommendations.path(),
+        params(("warehouse_id" = Uuid,),("table_id" = Uuid,)),
+        responses(
+            (status = 200, body = GetMaintenanceRecommendationsResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_maintenance_recommendations<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+    ) -> Result<GetMaintenanceRecommendationsResponse> {
+        ApiServer::<C, A, S>::get_maintenance_recommendations(
+            TableId::from(table_id),
+            warehouse_id.into(),
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Trigger Compaction
+    ///
+    /// Publishes a `compactionRequested` event for every partition of the table
+    /// that should be compacted. Lakekeeper does not rewrite files itself;
+    /// external compaction jobs subscribe to these events.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::TriggerCompaction.path(),
+        params(("warehouse_id" = Uuid,),("table_id" = Uuid,)),
+        responses(
+            (status = 200, body = TriggerCompactionResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn trigger_compaction<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+    ) -> Result<TriggerCompactionResponse> {
+        ApiServer::<C, A, S>::trigger_compaction(
+            TableId::from(table_id),
+            warehouse_id.into(),
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Begin Table Writer
+    ///
+    /// Starts a new epoch of a streaming writer of a table and creates the writer
+    /// if it does not exist. Requests of previous epochs of the writer are rejected
+    /// from now on. Returns the last committed checkpoint of the writer and the
+    /// prepared checkpoint that a restarted writer should commit first.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::BeginTableWriter.path(),
+        params(("warehouse_id" = Uuid,),("table_id" = Uuid,),("writer_id" = String,)),
+        responses(
+            (status = 200, body = TableWriterState),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn begin_table_writer<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id, writer_id)): Path<(uuid::Uuid, uuid::Uuid, String)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+    ) -> Result<TableWriterState> {
+        ApiServer::<C, A, S>::begin_table_writer(
+            TableId::from(table_id),
+            warehouse_id.into(),
+            writer_id,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Prepare Table Writer Commit
+    ///
+    /// Stores the changes of a checkpoint of a writer without committing them.
+    /// Replaces a previously prepared checkpoint. Preparing a checkpoint that has
+    /// already been committed has no effect.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::PrepareTableWriterCommit.path(),
+        params(("warehouse_id" = Uuid,),("table_id" = Uuid,),("writer_id" = String,)),
+        request_body = PrepareTableWriterCommitRequest,
+        responses(
+            (status = 204, description = "Checkpoint prepared"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn prepare_table_writer_commit<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id, writer_id)): Path<(uuid::Uuid, uuid::Uuid, String)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Json(request): Json<PrepareTableWriterCommitRequest>,
+    ) -> Result<(StatusCode, ())> {
+        ApiServer::<C, A, S>::prepare_table_writer_commit(
+            TableId::from(table_id),
+            warehouse_id.into(),
+            writer_id,
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+        .map(|()| (StatusCode::NO_CONTENT, ()))
+    }
+
+    /// Commit Table Writer
+    ///
+    /// Commits the prepared checkpoint of a writer to the table. The table commit and
+    /// the committed checkpoint of the writer are stored atomically, so retried
+    /// requests don't change the table twice.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::CommitTableWriter.path(),
+        params(("warehouse_id" = Uuid,),("table_id" = Uuid,),("writer_id" = String,)),
+        request_body = CommitTableWriterRequest,
+        responses(
+            (status = 200, body = CommitTableWriterResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn commit_table_writer<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id, writer_id)): Path<(uuid::Uuid, uuid::Uuid, String)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Json(request): Json<CommitTableWriterRequest>,
+    ) -> Result<CommitTableWriterResponse> {
+        ApiServer::<C, A, S>::commit_table_writer(
+            TableId::from(table_id),
+            warehouse_id.into(),
+            writer_id,
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Get Table Timeline
+    ///
+    /// Lists what happened to a table, newest first: commits, property changes,
+    /// granted and revoked privileges, finished maintenance tasks, drops and undrops.
+    /// Commits are read from the snapshots of the table, so commits of expired snapshots
+    /// are not listed. Soft-deleted tables can be queried as well.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::GetTableTimeline.path(),
+        params(("warehouse_id" = Uuid,),("table_id" = Uuid,), GetTableTimelineQuery),
+        responses(
+            (status = 200, body = GetTableTimelineResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_table_timeline<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Query(query): Query<GetTableTimelineQuery>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<GetTableTimelineResponse> {
+        ApiServer::<C, A, S>::get_table_timeline(
+            TableId::from(table_id),
+            warehouse_id.into(),
+            query,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Get Table Lineage
+    ///
+    /// Returns the datasets read by jobs that wrote the table (upstream) and the datasets
+    /// written by jobs that read it (downstream), as reported via `POST /management/v1/lineage`.
+    /// Datasets that are tables of this catalog include their warehouse and table id.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::GetTableLineage.path(),
+        params(("warehouse_id" = Uuid,),("table_id" = Uuid,)),
+        responses(
+            (status = 200, body = TableLineage),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_table_lineage<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<TableLineage> {
+        ApiServer::<C, A, S>::get_table_lineage(
+            TableId::from(table_id),
+            warehouse_id.into(),
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Repair Table
+    ///
+    /// Points a table whose current metadata file is corrupted or missing to a previous
+    /// metadata file of its metadata log. The table state is reset to the selected file,
+    /// snapshots added after it are removed. Without `metadata-location`, the most recent
+    /// readable file is selected if the current metadata file can't be read.
+    /// Use `dry-run` to list the candidates and the changes without repairing the table.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::RepairTable.path(),
+        params(("warehouse_id" = Uuid,),("table_id" = Uuid,)),
+        request_body = RepairTableRequest,
+        responses(
+            (status = 200, body = RepairTableResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn repair_table<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<RepairTableRequest>,
+    ) -> Result<RepairTableResponse> {
+        ApiServer::<C, A, S>::repair_table(
+            TableId::from(table_id),
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// List Table Signings
+    ///
+    /// Lists requests to the storage of a table that have been signed by the S3
+    /// remote signer, newest first. Requests are only recorded if the signing audit
+    /// is enabled. Signings of dropped tables are kept until their retention ends.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::ListTableSignings.path(),
+        params(("warehouse_id" = Uuid,),("table_id" = Uuid,), ListTableSigningsQuery),
+        responses(
+            (status = 200, body = ListTableSigningsResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn list_table_signings<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Query(query): Query<ListTableSigningsQuery>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<ListTableSigningsResponse> {
+        ApiServer::<C, A, S>::list_table_signings(
+            TableId::from(table_id),
+            warehouse_id.into(),
+            query,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Get View Protection
+    ///
+    /// Retrieves whether a view is protected from deletion.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::GetViewProtection.path(),
+        params(("warehouse_id" = Uuid,),("view_id" = Uuid,)),
+        responses(
+            (status = 200, body = ProtectionResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_view_protection<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, view_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+    ) -> Result<ProtectionResponse> {
+        ApiServer::<C, A, S>::get_view_protection(
+            ViewId::from(view_id),
+            warehouse_id.into(),
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Set View Protection
+    ///
+    /// Configures whether a view should be protected from deletion.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::SetViewProtection.path(),
+        params(("warehouse_id" = Uuid,),("view_id" = Uuid,)),
+        responses(
+            (status = 200, body = ProtectionResponse, description = "View protection set successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn set_view_protection<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, view_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Json(SetProtectionRequest { protected }): Json<SetProtectionRequest>,
+    ) -> Result<ProtectionResponse> {
+        ApiServer::<C, A, S>::set_view_protection(
+            ViewId::from(view_id),
+            warehouse_id.into(),
+            protected,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// List View Versions
+    ///
+    /// Returns all versions of a view, oldest first. Every version contains
+    /// unified diffs of its SQL against the preceding version, per dialect.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::ListViewVersions.path(),
+        params(("warehouse_id" = Uuid,),("view_id" = Uuid,)),
+        responses(
+            (status = 200, body = ListViewVersionsResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn list_view_versions<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, view_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+    ) -> Result<ListViewVersionsResponse> {
+        ApiServer::<C, A, S>::list_view_versions(
+            ViewId::from(view_id),
+            warehouse_id.into(),
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Get View Lineage
+    ///
+    /// Returns the tables referenced by the SQL of the current version of a view.
+    /// References are resolved within the warehouse of the view. Tables that don't
+    /// exist in the warehouse are returned without id.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::GetViewLineage.path(),
+        params(("warehouse_id" = Uuid,),("view_id" = Uuid,)),
+        responses(
+            (status = 200, body = GetViewLineageResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_view_lineage<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, view_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+    ) -> Result<GetViewLineageResponse> {
+        ApiServer::<C, A, S>::get_view_lineage(
+            ViewId::from(view_id),
+            warehouse_id.into(),
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Rollback View
+    ///
+    /// Sets the current version of a view back to one of its existing versions.
+    /// Requires the permission to commit to the view. The change is committed like
+    /// any other view update and additionally emits a `rollbackView` event.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::RollbackView.path(),
+        params(("warehouse_id" = Uuid,),("view_id" = Uuid,)),
+        request_body = RollbackViewRequest,
+        responses(
+            (status = 200, body = RollbackViewResponse, description = "View rolled back successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn rollback_view<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, view_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Json(request): Json<RollbackViewRequest>,
+    ) -> Result<RollbackViewResponse> {
+        ApiServer::<C, A, S>::rollback_view(
+            ViewId::from(view_id),
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Get Namespace Protection
+    ///
+    /// Retrieves whether a namespace is protected from deletion.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::GetNamespaceProtection.path(),
+        params(("warehouse_id" = Uuid,),("namespace_id" = Uuid,)),
+        responses(
+            (status = 200, body = ProtectionResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_namespace_protection<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, namespace_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+    ) -> Result<ProtectionResponse> {
+        ApiServer::<C, A, S>::get_namespace_protection(
+            NamespaceId::from(namespace_id),
+            warehouse_id.into(),
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Set Namespace Protection
+    ///
+    /// Configures whether a namespace should be protected from deletion.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::SetNamespaceProtection.path(),
+        params(("warehouse_id" = Uuid,),("namespace_id" = Uuid,)),
+        responses(
+            (status = 200, body = ProtectionResponse, description = "Namespace protection set successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn set_namespace_protection<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, namespace_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Json(SetProtectionRequest { protected }): Json<SetProtectionRequest>,
+    ) -> Result<ProtectionResponse> {
+        ApiServer::<C, A, S>::set_namespace_protection(
+            NamespaceId::from(namespace_id),
+            warehouse_id.into(),
+            protected,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Undrop Namespace
+    ///
+    /// Restores a soft-deleted namespace together with the child namespaces, tables and views
+    /// that were dropped along with it. Pending expiration tasks of the restored tables and views
+    /// are cancelled. The parent namespace must exist.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::UndropNamespace.path(),
+        params(("warehouse_id" = Uuid,),("namespace_id" = Uuid,)),
+        responses(
+            (status = 204, description = "Namespace undropped successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn undrop_namespace<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, namespace_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+    ) -> Result<StatusCode> {
+        ApiServer::<C, A, S>::undrop_namespace(
+            NamespaceId::from(namespace_id),
+            warehouse_id.into(),
+            api_context,
+            metadata,
+        )
+        .await?;
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    /// Set Warehouse Protection
+    ///
+    /// Configures whether a warehouse should be protected from deletion.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::SetWarehouseProtection.path(),
+        params(("warehouse_id" = Uuid,)),
+        responses(
+            (status = 200, body = ProtectionResponse, description = "Warehouse protection set successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn set_warehouse_protection<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Json(SetProtectionRequest { protected }): Json<SetProtectionRequest>,
+    ) -> Result<ProtectionResponse> {
+        ApiServer::<C, A, S>::set_warehouse_protection(
+            warehouse_id.into(),
+            protected,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Set task-queue config
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::SetTaskQueueConfig.path(),
+        params(("warehouse_id" = Uuid,)),
+        responses(
+            (status = 204, description = "Task queue config set successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn set_task_queue_config<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, queue_name)): Path<(uuid::Uuid, String)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Json(request): Json<SetTaskQueueConfigRequest>,
+    ) -> Result<StatusCode> {
+        ApiServer::<C, A, S>::set_task_queue_config(
+            warehouse_id.into(),
+            queue_name,
+            request,
+            api_context,
+            metadata,
+        )
+        .await?;
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    /// Get task-queue config
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::SetTaskQueueConfig.path(),
+        params(("warehouse_id" = Uuid,),("queue_name" = String,)),
+        responses(
+            (status = 200, body = GetTaskQueueConfigResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_task_queue_config<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, queue_name)): Path<(uuid::Uuid, String)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+    ) -> Result<GetTaskQueueConfigResponse> {
+        ApiServer::<C, A, S>::get_task_queue_config(
+            warehouse_id.into(),
+            &queue_name,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Create Task Schedule
+    ///
+    /// Creates a recurring schedule that enqueues a task of a table or view into a task queue
+    /// whenever the cron expression fires. Expressions are evaluated in UTC.
+    /// If the previous task is still running, the occurrence is skipped.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::CreateTaskSchedule.path(),
+        params(("warehouse_id" = Uuid,)),
+        request_body = CreateTaskScheduleRequest,
+        responses(
+            (status = 201, body = TaskSchedule),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn create_task_schedule<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Json(request): Json<CreateTaskScheduleRequest>,
+    ) -> Result<(StatusCode, Json<TaskSchedule>)> {
+        ApiServer::<C, A, S>::create_task_schedule(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+        .map(|schedule| (StatusCode::CREATED, Json(schedule)))
+    }
+
+    /// List Task Schedules
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::ListTaskSchedules.path(),
+        params(("warehouse_id" = Uuid,), ListTaskSchedulesQuery),
+        responses(
+            (status = 200, body = ListTaskSchedulesResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn list_task_schedules<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        Query(query): Query<ListTaskSchedulesQuery>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+    ) -> Result<ListTaskSchedulesResponse> {
+        ApiServer::<C, A, S>::list_task_schedules(warehouse_id.into(), query, api_context, metadata)
+            .await
+    }
+
+    /// Delete Task Schedule
+    ///
+    /// Tasks that were already enqueued by the schedule are not cancelled.
+    #[utoipa::path(
+        delete,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::DeleteTaskSchedule.path(),
+        params(("warehouse_id" = Uuid,),("schedule_id" = Uuid,)),
+        responses(
+            (status = 204, description = "Task schedule deleted successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn delete_task_schedule<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, schedule_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+    ) -> Result<StatusCode> {
+        ApiServer::<C, A, S>::delete_task_schedule(
+            warehouse_id.into(),
+            schedule_id,
+            api_context,
+            metadata,
+        )
+        .await?;
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    /// Pause Task Schedule
+    ///
+    /// A paused schedule does not fire until it is resumed.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::PauseTaskSchedule.path(),
+        params(("warehouse_id" = Uuid,),("schedule_id" = Uuid,)),
+        responses(
+            (status = 200, body = TaskSchedule),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn pause_task_schedule<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, schedule_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+    ) -> Result<TaskSchedule> {
+        ApiServer::<C, A, S>::pause_task_schedule(
+            warehouse_id.into(),
+            schedule_id,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Resume Task Schedule
+    ///
+    /// The schedule fires at its next occurrence. Occurrences missed while paused are skipped.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::ResumeTaskSchedule.path(),
+        params(("warehouse_id" = Uuid,),("schedule_id" = Uuid,)),
+        responses(
+            (status = 200, body = TaskSchedule),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn resume_task_schedule<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, schedule_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+    ) -> Result<TaskSchedule> {
+        ApiServer::<C, A, S>::resume_task_schedule(
+            warehouse_id.into(),
+            schedule_id,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Poll Task
+    ///
+    /// Lease the next due task of a queue for processing by an external worker.
+    /// The worker must send heartbeats more often than the visibility timeout,
+    /// otherwise the task is handed to another worker.
+    /// Built-in workers of the queue can be disabled via `LAKEKEEPER__DISABLED_TASK_QUEUE_WORKERS`.
+    #[utoipa::path(
+        post,
+        tag = "server",
+        path = ManagementV1Endpoint::PollTask.path(),
+        params(("queue_name" = String,)),
+        request_body = PollTaskRequest,
+        responses(
+            (status = 200, body = PollTaskResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn poll_task<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(queue_name): Path<String>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Json(request): Json<PollTaskRequest>,
+    ) -> Result<PollTaskResponse> {
+        ApiServer::<C, A, S>::poll_task(queue_name, request, api_context, metadata).await
+    }
+
+    /// Heartbeat Task
+    ///
+    /// Extend the lease of a task. Returns `409 Conflict` if the lease was lost.
+    #[utoipa::path(
+        post,
+        tag = "server",
+        path = ManagementV1Endpoint::HeartbeatTask.path(),
+        params(("task_id" = Uuid,)),
+        request_body = HeartbeatTaskRequest,
+        responses(
+            (status = 200, body = HeartbeatTaskResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn heartbeat_task<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(task_id): Path<uuid::Uuid>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Json(request): Json<HeartbeatTaskRequest>,
+    ) -> Result<HeartbeatTaskResponse> {
+        ApiServer::<C, A, S>::heartbeat_task(task_id.into(), request, api_context, metadata).await
+    }
+
+    /// Complete Task
+    ///
+    /// Report successful processing of a leased task. Returns `409 Conflict` if the lease was lost.
+    #[utoipa::path(
+        post,
+        tag = "server",
+        path = ManagementV1Endpoint::CompleteTask.path(),
+        params(("task_id" = Uuid,)),
+        request_body = CompleteTaskRequest,
+        responses(
+            (status = 204, description = "Task completed successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn complete_task<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(task_id): Path<uuid::Uuid>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Json(request): Json<CompleteTaskRequest>,
+    ) -> Result<StatusCode> {
+        ApiServer::<C, A, S>::complete_task(task_id.into(), request, api_context, metadata).await?;
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    /// Fail Task
+    ///
+    /// Report a failed attempt of a leased task. The task is retried until the maximum
+    /// number of retries is reached. Returns `409 Conflict` if the lease was lost.
+    #[utoipa::path(
+        post,
+        tag = "server",
+        path = ManagementV1Endpoint::FailTask.path(),
+        params(("task_id" = Uuid,)),
+        request_body = FailTaskRequest,
+        responses(
+            (status = 204, description = "Task failure recorded successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn fail_task<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(task_id): Path<uuid::Uuid>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Json(request): Json<FailTaskRequest>,
+    ) -> Result<StatusCode> {
+        ApiServer::<C, A, S>::fail_task(task_id.into(), request, api_context, metadata).await?;
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    /// Create Announcement
+    ///
+    /// Announcements inform all users of the server, for example about planned maintenance.
+    #[utoipa::path(
+        post,
+        tag = "server",
+        path = ManagementV1Endpoint::CreateAnnouncement.path(),
+        request_body = CreateAnnouncementRequest,
+        responses(
+            (status = 201, body = Announcement),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn create_announcement<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<CreateAnnouncementRequest>,
+    ) -> Result<(StatusCode, Json<Announcement>)> {
+        ApiServer::<C, A, S>::create_announcement(request, api_context, metadata)
+            .await
+            .map(|announcement| (StatusCode::CREATED, Json(announcement)))
+    }
+
+    /// List Announcements
+    ///
+    /// Lists all announcements, including past and upcoming ones.
+    #[utoipa::path(
+        get,
+        tag = "server",
+        path = ManagementV1Endpoint::ListAnnouncements.path(),
+        params(ListAnnouncementsQuery),
+        responses(
+            (status = 200, body = ListAnnouncementsResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn list_announcements<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Query(query): Query<ListAnnouncementsQuery>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<ListAnnouncementsResponse> {
+        ApiServer::<C, A, S>::list_announcements(query, api_context, metadata).await
+    }
+
+    /// List Active Announcements
+    ///
+    /// Lists the announcements that are currently active. Available to all authenticated users.
+    #[utoipa::path(
+        get,
+        tag = "server",
+        path = ManagementV1Endpoint::ListActiveAnnouncements.path(),
+        responses(
+            (status = 200, body = ListActiveAnnouncementsResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn list_active_announcements<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<ListActiveAnnouncementsResponse> {
+        ApiServer::<C, A, S>::list_active_announcements(api_context, metadata).await
+    }
+
+    /// Update Announcement
+    ///
+    /// Replaces the content of an announcement.
+    #[utoipa::path(
+        post,
+        tag = "server",
+        path = ManagementV1Endpoint::UpdateAnnouncement.path(),
+        params(("announcement_id" = Uuid,)),
+        request_body = UpdateAnnouncementRequest,
+        responses(
+            (status = 200, body = Announcement),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn update_announcement<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(announcement_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<UpdateAnnouncementRequest>,
+    ) -> Result<Announcement> {
+        ApiServer::<C, A, S>::update_announcement(announcement_id, request, api_context, metadata)
+            .await
+    }
+
+    /// Delete Announcement
+    #[utoipa::path(
+        delete,
+        tag = "server",
+        path = ManagementV1Endpoint::DeleteAnnouncement.path(),
+        params(("announcement_id" = Uuid,)),
+        responses(
+            (status = 204, description = "Announcement deleted successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn delete_announcement<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(announcement_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<StatusCode> {
+        ApiServer::<C, A, S>::delete_announcement(announcement_id, api_context, metadata).await?;
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    /// List Dead-Letter Tasks
+    ///
+    /// Tasks are moved to the dead-letter queue once they exhausted the
+    /// `max-attempts` of their queue.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::ListDeadLetterTasks.path(),
+        params(("warehouse_id" = Uuid,), ListDeadLetterTasksQuery),
+        responses(
+            (status = 200, body = ListDeadLetterTasksResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn list_dead_letter_tasks<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        Query(query): Query<ListDeadLetterTasksQuery>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<ListDeadLetterTasksResponse> {
+        ApiServer::<C, A, S>::list_dead_letter_tasks(
+            warehouse_id.into(),
+            query,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Get Dead-Letter Task
+    ///
+    /// Returns the task including the errors of all its attempts.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::GetDeadLetterTask.path(),
+        params(("warehouse_id" = Uuid,),("task_id" = Uuid,)),
+        responses(
+            (status = 200, body = GetDeadLetterTaskResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_dead_letter_task<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, task_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+    ) -> Result<GetDeadLetterTaskResponse> {
+        ApiServer::<C, A, S>::get_dead_letter_task(
+            warehouse_id.into(),
+            task_id.into(),
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Requeue Dead-Letter Task
+    ///
+    /// Enqueues the task again with a new task id and a fresh set of attempts.
+    /// Fails with `409 Conflict` if another task for the same entity is already queued.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::RequeueDeadLetterTask.path(),
+        params(("warehouse_id" = Uuid,),("task_id" = Uuid,)),
+        responses(
+            (status = 200, body = RequeueDeadLetterTaskResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn requeue_dead_letter_task<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, task_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+    ) -> Result<RequeueDeadLetterTaskResponse> {
+        ApiServer::<C, A, S>::requeue_dead_letter_task(
+            warehouse_id.into(),
+            task_id.into(),
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// List Catalog Snapshots
+    ///
+    /// Returns the most recent catalog snapshots of the warehouse and the next scheduled export.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::ListCatalogSnapshots.path(),
+        params(("warehouse_id" = Uuid,)),
+        responses(
+            (status = 200, body = ListCatalogSnapshotsResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn list_catalog_snapshots<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+    ) -> Result<ListCatalogSnapshotsResponse> {
+        ApiServer::<C, A, S>::list_catalog_snapshots(warehouse_id.into(), api_context, metadata)
+            .await
+    }
+
+    /// Export Catalog Snapshot
+    ///
+    /// Runs the periodic catalog snapshot export of the warehouse now.
+    /// Fails with `409 Conflict` if an export is already running.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::ExportCatalogSnapshot.path(),
+        params(("warehouse_id" = Uuid,)),
+        responses(
+            (status = 204, description = "Export scheduled"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn export_catalog_snapshot<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+    ) -> Result<StatusCode> {
+        ApiServer::<C, A, S>::export_catalog_snapshot(warehouse_id.into(), api_context, metadata)
+            .await
+            .map(|()| StatusCode::NO_CONTENT)
+    }
+
+    /// Verify Catalog Snapshot
+    ///
+    /// Compares the current catalog state of the warehouse with an exported snapshot,
+    /// e.g. after restoring the catalog database from a backup.
+    /// Snapshots that are unknown to the catalog are read from the default snapshot location.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::VerifyCatalogSnapshot.path(),
+        params(("warehouse_id" = Uuid,),("snapshot_id" = Uuid,)),
+        responses(
+            (status = 200, body = VerifyCatalogSnapshotResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn verify_catalog_snapshot<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, snapshot_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+    ) -> Result<VerifyCatalogSnapshotResponse> {
+        ApiServer::<C, A, S>::verify_catalog_snapshot(
+            warehouse_id.into(),
+            snapshot_id,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Get Catalog Snapshot State
+    ///
+    /// Returns the catalog state of the warehouse at the time of a snapshot.
+    /// Differential snapshots are replayed onto the full snapshot they are based on.
+    /// Use the state to restore namespaces, tables and views after a loss of the catalog database.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::GetCatalogSnapshotState.path(),
+        params(("warehouse_id" = Uuid,),("snapshot_id" = Uuid,)),
+        responses(
+            (status = 200, body = CatalogSnapshotStateResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_catalog_snapshot_state<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, snapshot_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+    ) -> Result<CatalogSnapshotStateResponse> {
+        ApiServer::<C, A, S>::get_catalog_snapshot_state(
+            warehouse_id.into(),
+            snapshot_id,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Restore Catalog Snapshot
+    ///
+    /// Brings the namespaces, tables, views and grants of the warehouse back to the state of a
+    /// snapshot: Entities created after the snapshot are dropped, soft-deleted entities are
+    /// undropped and missing entities are re-created from their metadata files.
+    /// Users that privileges were granted to are re-created if they were deleted.
+    /// Warehouse settings and task queue configurations are not restored.
+    /// Use `dry-run` to list the changes without applying them.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::RestoreCatalogSnapshot.path(),
+        params(("warehouse_id" = Uuid,),("snapshot_id" = Uuid,)),
+        request_body = RestoreCatalogSnapshotRequest,
+        responses(
+            (status = 200, body = RestoreCatalogSnapshotResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn restore_catalog_snapshot<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, snapshot_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Json(request): Json<RestoreCatalogSnapshotRequest>,
+    ) -> Result<RestoreCatalogSnapshotResponse> {
+        ApiServer::<C, A, S>::restore_catalog_snapshot(
+            warehouse_id.into(),
+            snapshot_id,
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Import Catalog
+    ///
+    /// Imports the namespaces and tables of another Iceberg REST catalog into the warehouse.
+    /// Tables are registered with their current metadata location, data files are not copied.
+    /// The import runs in the background, use the returned id to query its progress.
+    /// Fails with `409 Conflict` if an import into the warehouse is already running.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::ImportCatalog.path(),
+        params(("warehouse_id" = Uuid,)),
+        request_body = ImportCatalogRequest,
+        responses(
+            (status = 202, body = ImportCatalogResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn import_catalog<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Json(request): Json<ImportCatalogRequest>,
+    ) -> Result<ImportCatalogResponse> {
+        ApiServer::<C, A, S>::import_catalog(warehouse_id.into(), request, api_context, metadata)
+            .await
+    }
+
+    /// Get Catalog Import
+    ///
+    /// Returns the status of a catalog import and the outcome for every table of the source catalog.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::GetCatalogImport.path(),
+        params(("warehouse_id" = Uuid,),("import_id" = Uuid,)),
+        responses(
+            (status = 200, body = GetCatalogImportResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_catalog_import<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, import_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+    ) -> Result<GetCatalogImportResponse> {
+        ApiServer::<C, A, S>::get_catalog_import(
+            warehouse_id.into(),
+            import_id,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Export Warehouse
+    ///
+    /// Exports the namespaces of the warehouse with their properties and the metadata
+    /// locations of its tables and views as a portable manifest. Soft-deleted and staged
+    /// tables are not exported. Use Import Warehouse to register the content in a warehouse
+    /// of another Lakekeeper installation that has access to the same storage.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::ExportWarehouse.path(),
+        params(("warehouse_id" = Uuid,), ExportWarehouseQuery),
+        responses(
+            (status = 200, content(
+                (WarehouseExportManifest = "application/json"),
+                (String = "application/x-ndjson"),
+            )),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn export_warehouse<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        Query(query): Query<ExportWarehouseQuery>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<Response> {
+        let export = ApiServer::<C, A, S>::export_warehouse(
+            warehouse_id.into(),
+            query,
+            api_context,
+            metadata,
+        )
+        .await?;
+        Ok((
+            [
+                (http::header::CONTENT_TYPE, export.content_type.to_string()),
+                (
+                    http::header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"{}\"", export.file_name),
+                ),
+            ],
+            export.body,
+        )
+            .into_response())
+    }
+
+    /// Import Warehouse
+    ///
+    /// Creates the namespaces and registers the tables and views of a manifest created by
+    /// Export Warehouse. The manifest is sent as `application/json` or `application/x-ndjson`.
+    /// Existing namespaces are reused and tabulars that are already registered with the same
+    /// metadata location are skipped, so a partially failed import can be retried.
+    /// Namespace locations outside of the warehouse are replaced by the default location.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = ManagementV1Endpoint::ImportWarehouse.path(),
+        params(("warehouse_id" = Uuid,), ImportWarehouseQuery),
+        request_body(content(
+            (WarehouseExportManifest = "application/json"),
+            (String = "application/x-ndjson"),
+        )),
+        responses(
+            (status = 200, body = ImportWarehouseResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn import_warehouse<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        Query(query): Query<ImportWarehouseQuery>,
+        Extension(metadata): Extension<RequestMetadata>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        headers: http::HeaderMap,
+        body: axum::body::Bytes,
+    ) -> Result<ImportWarehouseResponse> {
+        let content_type = headers
+            .get(http::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok());
+        let manifest = parse_manifest(content_type, &body)?;
+        ApiServer::<C, A, S>::import_warehouse(
+            warehouse_id.into(),
+            query,
+            manifest,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// List Task Queues
+    ///
+    /// Returns the depth, in-flight tasks, age of the oldest due task and the failure
+    /// statistics of the last hour for every task queue across all warehouses.
+    #[utoipa::path(
+        get,
+        tag = "server",
+        path = ManagementV1Endpoint::ListTaskQueues.path(),
+        responses(
+            (status = 200, body = ListTaskQueuesResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn list_task_queues<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<ListTaskQueuesResponse> {
+        ApiServer::<C, A, S>::list_task_queues(api_context, metadata).await
+    }
+
+    /// Get Request Log Settings
+    ///
+    /// Returns the settings of request logging. If they were never set,
+    /// the settings of the configuration are returned.
+    #[utoipa::path(
+        get,
+        tag = "server",
+        path = ManagementV1Endpoint::GetRequestLogSettings.path(),
+        responses(
+            (status = 200, body = RequestLogSettings),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_request_log_settings<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<RequestLogSettings> {
+        ApiServer::<C, A, S>::get_request_log_settings(api_context, metadata).await
+    }
+
+    /// Set Request Log Settings
+    ///
+    /// Replaces the settings of request logging on all instances of the server.
+    /// Omitted fields are set to the values of the configuration.
+    /// Other instances apply the change within 30 seconds.
+    #[utoipa::path(
+        post,
+        tag = "server",
+        path = ManagementV1Endpoint::SetRequestLogSettings.path(),
+        request_body = RequestLogSettings,
+        responses(
+            (status = 200, body = RequestLogSettings),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn set_request_log_settings<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<RequestLogSettings>,
+    ) -> Result<RequestLogSettings> {
+        ApiServer::<C, A, S>::set_request_log_settings(request, api_context, metadata).await
+    }
+
+    /// List Server Events
+    ///
+    /// Returns lifecycle events of all instances of the server, newest first, such as
+    /// startups, shutdowns, applied migrations, crashed task workers and changed settings.
+    #[utoipa::path(
+        get,
+        tag = "server",
+        path = ManagementV1Endpoint::ListServerEvents.path(),
+        params(ListServerEventsQuery),
+        responses(
+            (status = 200, body = ListServerEventsResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn list_server_events<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Query(query): Query<ListServerEventsQuery>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<ListServerEventsResponse> {
+        ApiServer::<C, A, S>::list_server_events(query, api_context, metadata).await
+    }
+
+    /// Flush Metadata Cache
+    ///
+    /// Removes all table metadata files cached in memory by the instance that receives the request.
+    /// Other instances keep their entries until they expire.
+    #[utoipa::path(
+        post,
+        tag = "server",
+        path = ManagementV1Endpoint::FlushMetadataCache.path(),
+        responses(
+            (status = 204, description = "Metadata cache flushed"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn flush_metadata_cache<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<StatusCode> {
+        ApiServer::<C, A, S>::flush_metadata_cache(api_context, metadata).await?;
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    /// Ingest Lineage Event
+    ///
+    /// Records the lineage of a completed job from an OpenLineage run event.
+    /// Input and output datasets are matched to tables by location, so the dataset namespace
+    /// must hold the scheme and bucket, such as `s3://bucket`, and the name the path of the table.
+    /// Requires permission to read the data of matched inputs and to write the data of
+    /// matched outputs.
+    /// Events other than `COMPLETE` are accepted but not recorded.
+    #[utoipa::path(
+        post,
+        tag = "server",
+        path = ManagementV1Endpoint::IngestLineageEvent.path(),
+        request_body = LineageRunEvent,
+        responses(
+            (status = 200, body = IngestLineageEventResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn ingest_lineage_event<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(event): Json<LineageRunEvent>,
+    ) -> Result<IngestLineageEventResponse> {
+        ApiServer::<C, A, S>::ingest_lineage_event(event, api_context, metadata).await
+    }
+
+    /// Get Log Levels
+    ///
+    /// Returns the log directives and active trace targets of the instance that receives the request.
+    #[utoipa::path(
+        get,
+        tag = "server",
+        path = ManagementV1Endpoint::GetLogLevels.path(),
+        responses(
+            (status = 200, body = LogLevelStatus),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_log_levels<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<LogLevelStatus> {
+        ApiServer::<C, A, S>::get_log_levels(api_context, metadata).await
+    }
+
+    /// Set Log Level
+    ///
+    /// Changes the log level of a module, or the default level if no target is given,
+    /// without restarting the server. Only the instance that receives the request is changed.
+    /// Changes are lost on restart.
+    #[utoipa::path(
+        post,
+        tag = "server",
+        path = ManagementV1Endpoint::SetLogLevel.path(),
+        request_body = SetLogLevelRequest,
+        responses(
+            (status = 200, body = LogLevelStatus),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn set_log_level<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<SetLogLevelRequest>,
+    ) -> Result<LogLevelStatus> {
+        ApiServer::<C, A, S>::set_log_level(request, api_context, metadata).await
+    }
+
+    /// Add Trace Target
+    ///
+    /// Logs all events of requests by a principal or to a warehouse at the given level
+    /// for a limited time. Only the instance that receives the request is changed.
+    #[utoipa::path(
+        post,
+        tag = "server",
+        path = ManagementV1Endpoint::AddTraceTarget.path(),
+        request_body = AddTraceTargetRequest,
+        responses(
+            (status = 200, body = LogLevelStatus),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn add_trace_target<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<AddTraceTargetRequest>,
+    ) -> Result<LogLevelStatus> {
+        ApiServer::<C, A, S>::add_trace_target(request, api_context, metadata).await
+    }
+
+    /// Reset Log Levels
+    ///
+    /// Removes all log level changes and trace targets of the instance that receives the request.
+    #[utoipa::path(
+        post,
+        tag = "server",
+        path = ManagementV1Endpoint::ResetLogLevels.path(),
+        responses(
+            (status = 200, body = LogLevelStatus),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn reset_log_levels<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<LogLevelStatus> {
+        ApiServer::<C, A, S>::reset_log_levels(api_context, metadata).await
+    }
+
+    /// Create Recertification Campaign
+    ///
+    /// Creates a campaign to review the members and grants of roles in the project.
+    /// The members and grants at the time of creation become the items of the campaign.
+    #[utoipa::path(
+        post,
+        tag = "role",
+        path = ManagementV1Endpoint::CreateRecertificationCampaign.path(),
+        request_body = CreateRecertificationCampaignRequest,
+        responses(
+            (status = 201, body = GetRecertificationCampaignResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn create_recertification_campaign<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<CreateRecertificationCampaignRequest>,
+    ) -> Result<(StatusCode, Json<GetRecertificationCampaignResponse>)> {
+        ApiServer::<C, A, S>::create_recertification_campaign(request, api_context, metadata)
+            .await
+            .map(|campaign| (StatusCode::CREATED, Json(campaign)))
+    }
+
+    /// List Recertification Campaigns
+    ///
+    /// Lists the campaigns of the project. Reviewers that may not manage access reviews
+    /// only see the campaigns they are assigned to.
+    #[utoipa::path(
+        get,
+        tag = "role",
+        path = ManagementV1Endpoint::ListRecertificationCampaigns.path(),
+        params(ListRecertificationCampaignsQuery),
+        responses(
+            (status = 200, body = ListRecertificationCampaignsResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn list_recertification_campaigns<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Query(query): Query<ListRecertificationCampaignsQuery>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<ListRecertificationCampaignsResponse> {
+        ApiServer::<C, A, S>::list_recertification_campaigns(query, api_context, metadata).await
+    }
+
+    /// Get Recertification Campaign
+    #[utoipa::path(
+        get,
+        tag = "role",
+        path = ManagementV1Endpoint::GetRecertificationCampaign.path(),
+        params(("campaign_id" = Uuid,)),
+        responses(
+            (status = 200, body = GetRecertificationCampaignResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_recertification_campaign<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(campaign_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<GetRecertificationCampaignResponse> {
+        ApiServer::<C, A, S>::get_recertification_campaign(campaign_id, api_context, metadata).await
+    }
+
+    /// Review Recertification Items
+    ///
+    /// Approve or revoke pending items of a campaign. Only reviewers of the campaign can
+    /// decide on items, and only until the deadline of the campaign has passed.
+    /// Revoked members and grants are removed immediately.
+    #[utoipa::path(
+        post,
+        tag = "role",
+        path = ManagementV1Endpoint::ReviewRecertificationItems.path(),
+        params(("campaign_id" = Uuid,)),
+        request_body = ReviewRecertificationItemsRequest,
+        responses(
+            (status = 200, body = ReviewRecertificationItemsResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn review_recertification_items<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(campaign_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<ReviewRecertificationItemsRequest>,
+    ) -> Result<ReviewRecertificationItemsResponse> {
+        ApiServer::<C, A, S>::review_recertification_items(
+            campaign_id,
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Close Recertification Campaign
+    ///
+    /// Closes a campaign. If `auto-revoke-unreviewed` is set for the campaign,
+    /// all pending items are revoked.
+    #[utoipa::path(
+        post,
+        tag = "role",
+        path = ManagementV1Endpoint::CloseRecertificationCampaign.path(),
+        params(("campaign_id" = Uuid,)),
+        responses(
+            (status = 200, body = GetRecertificationCampaignResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn close_recertification_campaign<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(campaign_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<GetRecertificationCampaignResponse> {
+        ApiServer::<C, A, S>::close_recertification_campaign(campaign_id, api_context, metadata)
+            .await
+    }
+
+    /// Export Recertification Campaign
+    ///
+    /// Exports the items of a campaign and their decisions as CSV.
+    #[utoipa::path(
+        get,
+        tag = "role",
+        path = ManagementV1Endpoint::ExportRecertificationCampaign.path(),
+        params(("campaign_id" = Uuid,)),
+        responses(
+            (status = 200, body = String, content_type = "text/csv"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn export_recertification_campaign<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(campaign_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<Response> {
+        let csv = ApiServer::<C, A, S>::export_recertification_campaign(
+            campaign_id,
+            api_context,
+            metadata,
+        )
+        .await?;
+        Ok(([(http::header::CONTENT_TYPE, "text/csv")], csv).into_response())
+    }
+
+    /// Create Invitation
+    ///
+    /// Creates an invitation to the project. Invitations are bound to an email address,
+    /// to a link token or to both. The link token is only returned in this response.
+    #[utoipa::path(
+        post,
+        tag = "project",
+        path = ManagementV1Endpoint::CreateInvitation.path(),
+        request_body = CreateInvitationRequest,
+        responses(
+            (status = 201, body = CreateInvitationResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn create_invitation<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<CreateInvitationRequest>,
+    ) -> Result<(StatusCode, Json<CreateInvitationResponse>)> {
+        ApiServer::<C, A, S>::create_invitation(request, api_context, metadata)
+            .await
+            .map(|invitation| (StatusCode::CREATED, Json(invitation)))
+    }
+
+    /// List Invitations
+    ///
+    /// Lists all invitations of the project, including redeemed, revoked and expired ones.
+    #[utoipa::path(
+        get,
+        tag = "project",
+        path = ManagementV1Endpoint::ListInvitations.path(),
+        params(ListInvitationsQuery),
         responses(
-            (status = 200, body = ProtectionResponse, description = "Namespace protection set successfully"),
+            (status = 200, body = ListInvitationsResponse),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn set_namespace_protection<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
-        Path((warehouse_id, namespace_id)): Path<(uuid::Uuid, uuid::Uuid)>,
-        Extension(metadata): Extension<RequestMetadata>,
+    async fn list_invitations<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Query(query): Query<ListInvitationsQuery>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
-        Json(SetProtectionRequest { protected }): Json<SetProtectionRequest>,
-    ) -> Result<ProtectionResponse> {
-        ApiServer::<C, A, S>::set_namespace_protection(
-            NamespaceId::from(namespace_id),
-            warehouse_id.into(),
-            protected,
-            api_context,
-            metadata,
-        )
-        .await
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<ListInvitationsResponse> {
+        ApiServer::<C, A, S>::list_invitations(query, api_context, metadata).await
     }
 
-    /// Set Warehouse Protection
+    /// Revoke Invitation
     ///
-    /// Configures whether a warehouse should be protected from deletion.
+    /// Revokes an invitation that was not redeemed yet.
+    /// Access of users that already redeemed the invitation is not changed.
     #[utoipa::path(
-        post,
-        tag = "warehouse",
-        path = ManagementV1Endpoint::SetWarehouseProtection.path(),
-        params(("warehouse_id" = Uuid,)),
+        delete,
+        tag = "project",
+        path = ManagementV1Endpoint::RevokeInvitation.path(),
+        params(("invitation_id" = Uuid,)),
         responses(
-            (status = 200, body = ProtectionResponse, description = "Warehouse protection set successfully"),
+            (status = 204, description = "Invitation revoked successfully"),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn set_warehouse_protection<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
-        Path(warehouse_id): Path<uuid::Uuid>,
-        Extension(metadata): Extension<RequestMetadata>,
+    async fn revoke_invitation<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(invitation_id): Path<uuid::Uuid>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
-        Json(SetProtectionRequest { protected }): Json<SetProtectionRequest>,
-    ) -> Result<ProtectionResponse> {
-        ApiServer::<C, A, S>::set_warehouse_protection(
-            warehouse_id.into(),
-            protected,
-            api_context,
-            metadata,
-        )
-        .await
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<StatusCode> {
+        ApiServer::<C, A, S>::revoke_invitation(invitation_id, api_context, metadata).await?;
+        Ok(StatusCode::NO_CONTENT)
     }
 
-    /// Set task-queue config
+    /// Redeem Invitation
+    ///
+    /// Redeems an invitation as the authenticated user. The user is provisioned if it does
+    /// not exist yet, receives the membership of the invitation on the project and is
+    /// assigned to its roles. Invitations can only be redeemed once.
     #[utoipa::path(
         post,
-        tag = "warehouse",
-        path = ManagementV1Endpoint::SetTaskQueueConfig.path(),
-        params(("warehouse_id" = Uuid,)),
+        tag = "project",
+        path = ManagementV1Endpoint::RedeemInvitation.path(),
+        request_body = RedeemInvitationRequest,
         responses(
-            (status = 204, description = "Task queue config set successfully"),
+            (status = 200, body = RedeemInvitationResponse),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn set_task_queue_config<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
-        Path((warehouse_id, queue_name)): Path<(uuid::Uuid, String)>,
-        Extension(metadata): Extension<RequestMetadata>,
+    async fn redeem_invitation<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
-        Json(request): Json<SetTaskQueueConfigRequest>,
-    ) -> Result<StatusCode> {
-        ApiServer::<C, A, S>::set_task_queue_config(
-            warehouse_id.into(),
-            queue_name,
-            request,
-            api_context,
-            metadata,
-        )
-        .await?;
-        Ok(StatusCode::NO_CONTENT)
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<RedeemInvitationRequest>,
+    ) -> Result<RedeemInvitationResponse> {
+        ApiServer::<C, A, S>::redeem_invitation(request, api_context, metadata).await
     }
 
-    /// Get task-queue config
+    /// Export Access
+    ///
+    /// Exports all users of the server, or all roles, role memberships or grants to roles of
+    /// the project as CSV or Parquet in a single response, without pagination.
+    /// The export is streamed while it is read, so errors after the first page abort the
+    /// response instead of returning an error status.
+    /// Grants that are not assigned via roles are not included.
     #[utoipa::path(
         get,
-        tag = "warehouse",
-        path = ManagementV1Endpoint::SetTaskQueueConfig.path(),
-        params(("warehouse_id" = Uuid,),("queue_name" = String,)),
+        tag = "role",
+        path = ManagementV1Endpoint::ExportAccess.path(),
+        params(("entity" = AccessExportEntity,), ExportAccessQuery),
         responses(
-            (status = 200, body = GetTaskQueueConfigResponse),
+            (status = 200, content(
+                (String = "text/csv"),
+                (Vec<u8> = "application/vnd.apache.parquet"),
+            )),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn get_task_queue_config<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
-        Path((warehouse_id, queue_name)): Path<(uuid::Uuid, String)>,
-        Extension(metadata): Extension<RequestMetadata>,
+    async fn export_access<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(entity): Path<AccessExportEntity>,
+        Query(query): Query<ExportAccessQuery>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
-    ) -> Result<GetTaskQueueConfigResponse> {
-        ApiServer::<C, A, S>::get_task_queue_config(
-            warehouse_id.into(),
-            &queue_name,
-            api_context,
-            metadata,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<Response> {
+        let export =
+            ApiServer::<C, A, S>::export_access(entity, query, api_context, metadata).await?;
+        Ok((
+            [
+                (http::header::CONTENT_TYPE, export.content_type.to_string()),
+                (
+                    http::header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"{}\"", export.file_name),
+                ),
+            ],
+            export.body,
         )
-        .await
+            .into_response())
     }
 
     #[derive(Debug, Serialize, utoipa::ToSchema)]
@@ -1579,6 +4794,86 @@ pub mod v1 {
         Purge,
     }
 
+    /// Field to sort a list by. Entries with the same value are ordered by id.
+    #[derive(
+        Debug,
+        Deserialize,
+        Serialize,
+        utoipa::ToSchema,
+        Clone,
+        Copy,
+        PartialEq,
+        Eq,
+        Default,
+        strum_macros::Display,
+    )]
+    #[serde(rename_all = "kebab-case")]
+    #[strum(serialize_all = "kebab-case")]
+    pub enum SortBy {
+        /// Case-insensitive name
+        Name,
+        /// Time of the creation
+        #[default]
+        #[serde(alias = "created_at")]
+        CreatedAt,
+        /// Time of the last update, or of the creation if never updated
+        #[serde(alias = "updated_at")]
+        UpdatedAt,
+    }
+
+    #[derive(
+        Debug,
+        Deserialize,
+        Serialize,
+        utoipa::ToSchema,
+        Clone,
+        Copy,
+        PartialEq,
+        Eq,
+        Default,
+        strum_macros::Display,
+    )]
+    #[serde(rename_all = "kebab-case")]
+    #[strum(serialize_all = "kebab-case")]
+    pub enum SortOrder {
+        #[default]
+        Asc,
+        Desc,
+    }
+
+    /// Order of a list requested by a client.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub struct ListSort {
+        pub by: SortBy,
+        pub order: SortOrder,
+    }
+
+    impl ListSort {
+        /// Sort requested by the `sortBy` and `order` query parameters, if any was set.
+        /// An order without a field sorts by creation time.
+        #[must_use]
+        pub fn from_query(by: Option<SortBy>, order: Option<SortOrder>) -> Option<Self> {
+            if by.is_none() && order.is_none() {
+                return None;
+            }
+            Some(Self {
+                by: by.unwrap_or_default(),
+                order: order.unwrap_or_default(),
+            })
+        }
+
+        #[must_use]
+        pub fn is_desc(self) -> bool {
+            self.order == SortOrder::Desc
+        }
+    }
+
+    impl std::fmt::Display for ListSort {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}.{}", self.by, self.order)
+        }
+    }
+
     /// Get the `OpenAPI` documentation for the management API.
     ///
     /// # Errors
@@ -1701,12 +4996,14 @@ pub mod v1 {
                 .route("/info", get(get_server_info))
                 .route("/bootstrap", post(bootstrap))
                 .route("/endpoint-statistics", post(get_endpoint_statistics))
+                .route("/usage-statistics", get(get_usage_statistics))
                 // Role management
                 .route("/role", get(list_roles).post(create_role))
                 .route(
                     "/role/{role_id}",
                     get(get_role).post(update_role).delete(delete_role),
                 )
+                .route("/search", get(global_search).post(search_catalog))
                 .route("/search/role", post(search_role))
                 // User management
                 .route("/whoami", get(whoami))
@@ -1716,6 +5013,19 @@ pub mod v1 {
                     get(get_user).put(update_user).delete(delete_user),
                 )
                 .route("/user", get(list_user).post(create_user))
+                .route("/user/bulk", post(bulk_create_users))
+                .route("/user/{user_id}/hard-delete", post(hard_delete_user))
+                .route(
+                    "/user/{user_id}/merge-from/{source_user_id}",
+                    post(merge_user),
+                )
+                .route("/user/{user_id}/deactivate", post(deactivate_user))
+                .route("/user/{user_id}/activate", post(activate_user))
+                .route("/user/{user_id}/impersonate", post(start_impersonation))
+                .route(
+                    "/impersonation/{impersonation_id}",
+                    delete(stop_impersonation),
+                )
                 // Default project
                 .route(
                     "/default-project",
@@ -1738,6 +5048,18 @@ pub mod v1 {
                     get(get_project_by_id).delete(delete_project_by_id),
                 )
                 .route("/project/{project_id}/rename", post(rename_project_by_id))
+                .route(
+                    "/project/{project_id}/quotas",
+                    get(get_project_quotas).post(set_project_quotas),
+                )
+                .route(
+                    "/project/{project_id}/property-schemas",
+                    get(get_property_schemas).post(set_property_schemas),
+                )
+                .route(
+                    "/project/{project_id}/label-settings",
+                    get(get_label_settings).post(set_label_settings),
+                )
                 // Create a new warehouse
                 .route("/warehouse", post(create_warehouse).get(list_warehouses))
                 // List all projects
@@ -1764,11 +5086,20 @@ pub mod v1 {
                     "/warehouse/{warehouse_id}/storage",
                     post(update_storage_profile),
                 )
+                .route(
+                    "/warehouse/{warehouse_id}/storage/probe-quirks",
+                    post(probe_storage_quirks),
+                )
                 // Update only the storage credential - keep the storage profile as is
                 .route(
                     "/warehouse/{warehouse_id}/storage-credential",
                     post(update_storage_credential),
                 )
+                // Replace the storage credential, keeping the previous one for a grace period
+                .route(
+                    "/warehouse/{warehouse_id}/storage-credential/rotate",
+                    post(rotate_storage_credential),
+                )
                 // Get warehouse statistics
                 .route(
                     "/warehouse/{warehouse_id}/statistics",
@@ -1791,18 +5122,169 @@ pub mod v1 {
                     "/warehouse/{warehouse_id}/delete-profile",
                     post(update_warehouse_delete_profile),
                 )
+                .route(
+                    "/warehouse/{warehouse_id}/disabled-endpoint-groups",
+                    post(set_warehouse_disabled_endpoint_groups),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/client-workarounds",
+                    post(set_warehouse_client_workarounds),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/default-branch",
+                    post(set_warehouse_default_branch),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/metadata-push",
+                    post(set_warehouse_metadata_push),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/event-routing",
+                    get(get_warehouse_event_routing).post(set_warehouse_event_routing),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/referenced-files",
+                    get(list_referenced_files),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/labels",
+                    get(get_warehouse_labels).post(set_warehouse_labels),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/namespace/{namespace_id}/labels",
+                    get(get_namespace_labels).post(set_namespace_labels),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/labels",
+                    get(get_table_labels).post(set_table_labels),
+                )
+                .route("/warehouse/{warehouse_id}/watch", get(watch))
+                .route(
+                    "/warehouse/{warehouse_id}/replication-state",
+                    get(get_replication_state),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/slug",
+                    get(get_warehouse_slug)
+                        .post(set_warehouse_slug)
+                        .delete(delete_warehouse_slug),
+                )
                 .route(
                     "/warehouse/{warehouse_id}/table/{table_id}/protection",
                     get(get_table_protection).post(set_table_protection),
                 )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/comment",
+                    post(set_table_comment),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/column-comments",
+                    post(set_column_comments),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/column-tags",
+                    get(get_column_tags).post(set_column_tags),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/column-tags/{tag}",
+                    get(list_tagged_columns),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/column-tag-suggestions",
+                    get(list_column_tag_suggestions),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/column-tag-suggestions/review",
+                    post(review_column_tag_suggestions),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/detect-pii",
+                    post(detect_pii),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/property-proposals",
+                    get(list_table_property_proposals).post(create_table_property_proposal),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/property-proposals/{proposal_id}/review",
+                    post(review_table_property_proposal),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/snapshot-expiration",
+                    get(get_snapshot_expiration),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/compatibility",
+                    get(get_table_compatibility),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/orphan-files",
+                    get(get_orphan_file_cleanup),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/orphan-files/cleanup",
+                    post(cleanup_orphan_files),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/maintenance-recommendations",
+                    get(get_maintenance_recommendations),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/maintenance-recommendations/trigger",
+                    post(trigger_compaction),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/writer/{writer_id}/begin",
+                    post(begin_table_writer),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/writer/{writer_id}/prepare",
+                    post(prepare_table_writer_commit),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/writer/{writer_id}/commit",
+                    post(commit_table_writer),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/signings",
+                    get(list_table_signings),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/timeline",
+                    get(get_table_timeline),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/lineage",
+                    get(get_table_lineage),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/repair",
+                    post(repair_table),
+                )
                 .route(
                     "/warehouse/{warehouse_id}/view/{view_id}/protection",
                     get(get_view_protection).post(set_view_protection),
                 )
+                .route(
+                    "/warehouse/{warehouse_id}/view/{view_id}/versions",
+                    get(list_view_versions),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/view/{view_id}/lineage",
+                    get(get_view_lineage),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/view/{view_id}/rollback",
+                    post(rollback_view),
+                )
                 .route(
                     "/warehouse/{warehouse_id}/namespace/{namespace_id}/protection",
                     get(get_namespace_protection).post(set_namespace_protection),
                 )
+                .route(
+                    "/warehouse/{warehouse_id}/namespace/{namespace_id}/undrop",
+                    post(undrop_namespace),
+                )
                 .route(
                     "/warehouse/{warehouse_id}/protection",
                     post(set_warehouse_protection),
@@ -1811,6 +5293,116 @@ pub mod v1 {
                     "/warehouse/{warehouse_id}/task-queue/{queue_name}/config",
                     post(set_task_queue_config).get(get_task_queue_config),
                 )
+                .route(
+                    "/warehouse/{warehouse_id}/task-schedule",
+                    get(list_task_schedules).post(create_task_schedule),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/task-schedule/{schedule_id}",
+                    delete(delete_task_schedule),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/task-schedule/{schedule_id}/pause",
+                    post(pause_task_schedule),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/task-schedule/{schedule_id}/resume",
+                    post(resume_task_schedule),
+                )
+                .route("/task-queue/{queue_name}/poll", post(poll_task))
+                .route("/task/{task_id}/heartbeat", post(heartbeat_task))
+                .route("/task/{task_id}/complete", post(complete_task))
+                .route("/task/{task_id}/fail", post(fail_task))
+                .route(
+                    "/warehouse/{warehouse_id}/dead-letter-task",
+                    get(list_dead_letter_tasks),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/dead-letter-task/{task_id}",
+                    get(get_dead_letter_task),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/dead-letter-task/{task_id}/requeue",
+                    post(requeue_dead_letter_task),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/catalog-snapshots",
+                    get(list_catalog_snapshots),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/catalog-snapshots/export",
+                    post(export_catalog_snapshot),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/catalog-snapshots/{snapshot_id}/verify",
+                    post(verify_catalog_snapshot),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/catalog-snapshots/{snapshot_id}/state",
+                    get(get_catalog_snapshot_state),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/catalog-snapshots/{snapshot_id}/restore",
+                    post(restore_catalog_snapshot),
+                )
+                .route("/warehouse/{warehouse_id}/import", post(import_catalog))
+                .route(
+                    "/warehouse/{warehouse_id}/import/{import_id}",
+                    get(get_catalog_import),
+                )
+                .route("/warehouse/{warehouse_id}/export", get(export_warehouse))
+                .route(
+                    "/warehouse/{warehouse_id}/import-manifest",
+                    post(import_warehouse),
+                )
+                .route(
+                    "/announcement",
+                    get(list_announcements).post(create_announcement),
+                )
+                .route("/announcement/active", get(list_active_announcements))
+                .route(
+                    "/announcement/{announcement_id}",
+                    post(update_announcement).delete(delete_announcement),
+                )
+                .route("/task-queues", get(list_task_queues))
+                .route(
+                    "/request-log",
+                    get(get_request_log_settings).post(set_request_log_settings),
+                )
+                .route("/server-events", get(list_server_events))
+                .route("/metadata-cache/flush", post(flush_metadata_cache))
+                .route("/logging", get(get_log_levels))
+                .route("/logging/level", post(set_log_level))
+                .route("/logging/trace-target", post(add_trace_target))
+                .route("/logging/reset", post(reset_log_levels))
+                .route("/lineage", post(ingest_lineage_event))
+                .route(
+                    "/recertification-campaign",
+                    get(list_recertification_campaigns).post(create_recertification_campaign),
+                )
+                .route(
+                    "/recertification-campaign/{campaign_id}",
+                    get(get_recertification_campaign),
+                )
+                .route(
+                    "/recertification-campaign/{campaign_id}/review",
+                    post(review_recertification_items),
+                )
+                .route(
+                    "/recertification-campaign/{campaign_id}/close",
+                    post(close_recertification_campaign),
+                )
+                .route(
+                    "/recertification-campaign/{campaign_id}/export",
+                    get(export_recertification_campaign),
+                )
+                .route(
+                    "/invitation",
+                    get(list_invitations).post(create_invitation),
+                )
+                .route("/invitation/redeem", post(redeem_invitation))
+                .route("/invitation/{invitation_id}", delete(revoke_invitation))
+                .route("/access-export/{entity}", get(export_access))
                 .merge(authorizer.new_router())
         }
     }
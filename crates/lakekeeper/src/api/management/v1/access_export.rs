@@ -0,0 +1,514 @@
+#[cfg(feature = "parquet-export")]
+use std::sync::Arc;
+
+#[cfg(feature = "parquet-export")]
+use arrow_array::{ArrayRef, RecordBatch, StringArray};
+#[cfg(feature = "parquet-export")]
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use axum::body::{Body, Bytes};
+use futures::{Stream, StreamExt as _};
+use iceberg_ext::catalog::rest::ErrorModel;
+#[cfg(feature = "parquet-export")]
+use parquet::arrow::ArrowWriter;
+use serde::Deserialize;
+
+use crate::{
+    api::{
+        iceberg::v1::{PageToken, PaginationQuery, MAX_PAGE_SIZE},
+        management::v1::{
+            recertification::csv_field,
+            role::Role,
+            user::{User, UserLastUpdatedWith, UserType},
+            ApiServer, ListSort,
+        },
+        ApiContext,
+    },
+    request_metadata::RequestMetadata,
+    service::{
+        authz::{Authorizer, CatalogProjectAction, CatalogServerAction, RoleAccessKind},
+        Catalog, Result, SecretStore, State,
+    },
+    ProjectId,
+};
+
+/// Entities that can be exported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum AccessExportEntity {
+    /// All users of the server
+    Users,
+    /// Roles of the project
+    Roles,
+    /// Users and roles assigned to roles of the project
+    Memberships,
+    /// Privileges granted to roles of the project
+    Grants,
+}
+
+impl AccessExportEntity {
+    fn columns(self) -> &'static [&'static str] {
+        match self {
+            AccessExportEntity::Users => &[
+                "id",
+                "name",
+                "email",
+                "user_type",
+                "last_updated_with",
+                "created_at",
+                "updated_at",
+            ],
+            AccessExportEntity::Roles => &[
+                "id",
+                "name",
+                "description",
+                "project_id",
+                "created_at",
+                "updated_at",
+            ],
+            AccessExportEntity::Memberships | AccessExportEntity::Grants => {
+                &["role_id", "role_name", "principal", "relation", "object"]
+            }
+        }
+    }
+
+    fn file_name(self) -> &'static str {
+        match self {
+            AccessExportEntity::Users => "users",
+            AccessExportEntity::Roles => "roles",
+            AccessExportEntity::Memberships => "memberships",
+            AccessExportEntity::Grants => "grants",
+        }
+    }
+}
+
+/// File format of an export
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum AccessExportFormat {
+    #[default]
+    Csv,
+    Parquet,
+}
+
+impl AccessExportFormat {
+    fn content_type(self) -> &'static str {
+        match self {
+            AccessExportFormat::Csv => "text/csv",
+            AccessExportFormat::Parquet => "application/vnd.apache.parquet",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            AccessExportFormat::Csv => "csv",
+            AccessExportFormat::Parquet => "parquet",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportAccessQuery {
+    /// Format of the export.
+    /// Default: `csv`
+    #[serde(default)]
+    #[param(inline)]
+    pub format: AccessExportFormat,
+}
+
+/// Streamed body of an export and the headers describing it.
+pub struct AccessExport {
+    pub content_type: &'static str,
+    pub file_name: String,
+    pub body: Body,
+}
+
+/// A row of an export. Missing values are empty in CSV and null in Parquet.
+type Row = Vec<Option<String>>;
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
+
+#[async_trait::async_trait]
+pub(crate) trait Service<C: Catalog, A: Authorizer + Clone, S: SecretStore> {
+    /// Export all entities of a kind without pagination.
+    /// Pages are read from the catalog while the response is streamed.
+    async fn export_access(
+        entity: AccessExportEntity,
+        query: ExportAccessQuery,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<AccessExport> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        let project_id = match entity {
+            AccessExportEntity::Users => {
+                authorizer
+                    .require_server_action(&request_metadata, CatalogServerAction::CanListUsers)
+                    .await?;
+                None
+            }
+            AccessExportEntity::Roles => {
+                let project_id = request_metadata.require_project_id(None)?;
+                authorizer
+                    .require_project_action(
+                        &request_metadata,
+                        &project_id,
+                        CatalogProjectAction::CanListRoles,
+                    )
+                    .await?;
+                Some(project_id)
+            }
+            AccessExportEntity::Memberships | AccessExportEntity::Grants => {
+                let project_id = request_metadata.require_project_id(None)?;
+                authorizer
+                    .require_project_action(
+                        &request_metadata,
+                        &project_id,
+                        CatalogProjectAction::CanManageAccessReviews,
+                    )
+                    .await?;
+                Some(project_id)
+            }
+        };
+
+        // ------------------- Business Logic -------------------
+        let pages = export_pages::<C, A>(entity, project_id, context.v1_state.catalog, authorizer);
+        let columns = entity.columns();
+        let body = match query.format {
+            AccessExportFormat::Csv => {
+                Body::from_stream(into_body_stream(csv_stream(columns, pages)))
+            }
+            AccessExportFormat::Parquet => parquet_body(columns, pages)?,
+        };
+
+        Ok(AccessExport {
+            content_type: query.format.content_type(),
+            file_name: format!("{}.{}", entity.file_name(), query.format.extension()),
+            body,
+        })
+    }
+}
+
+/// Rows of `entity`, one item per page of the catalog.
+fn export_pages<C: Catalog, A: Authorizer + Clone>(
+    entity: AccessExportEntity,
+    project_id: Option<ProjectId>,
+    catalog_state: C::State,
+    authorizer: A,
+) -> impl Stream<Item = Result<Vec<Row>>> + Send {
+    async_stream::try_stream! {
+        let mut page_token = PageToken::Empty;
+        loop {
+            let pagination = PaginationQuery {
+                page_token,
+                page_size: Some(MAX_PAGE_SIZE),
+            };
+            let (rows, next_page_token) = match entity {
+                AccessExportEntity::Users => {
+                    let page = C::list_user(
+                        None,
+                        None,
+                        None,
+                        None,
+                        ListSort::default(),
+                        pagination,
+                        catalog_state.clone(),
+                    )
+                    .await?;
+                    let rows = page.users.iter().map(user_row).collect::<Vec<_>>();
+                    (rows, page.next_page_token.filter(|_| !page.users.is_empty()))
+                }
+                AccessExportEntity::Roles
+                | AccessExportEntity::Memberships
+                | AccessExportEntity::Grants => {
+                    let page = C::list_roles(
+                        project_id.clone(),
+                        None,
+                        None,
+                        None,
+                        pagination,
+                        catalog_state.clone(),
+                    )
+                    .await?;
+                    let rows = match entity {
+                        AccessExportEntity::Memberships => {
+                            role_access_rows(&page.roles, RoleAccessKind::Member, &authorizer)
+                                .await?
+                        }
+                        AccessExportEntity::Grants => {
+                            role_access_rows(&page.roles, RoleAccessKind::Grant, &authorizer)
+                                .await?
+                        }
+                        AccessExportEntity::Users | AccessExportEntity::Roles => {
+                            page.roles.iter().map(role_row).collect()
+                        }
+                    };
+                    (rows, page.next_page_token.filter(|_| !page.roles.is_empty()))
+                }
+            };
+            yield rows;
+            match next_page_token {
+                Some(token) => page_token = PageToken::Present(token),
+                None => break,
+            }
+        }
+    }
+}
+
+async fn role_access_rows<A: Authorizer>(
+    roles: &[Role],
+    kind: RoleAccessKind,
+    authorizer: &A,
+) -> Result<Vec<Row>> {
+    let role_access = futures::future::try_join_all(
+        roles
+            .iter()
+            .map(|role| authorizer.list_role_access(role.id)),
+    )
+    .await?;
+    Ok(roles
+        .iter()
+        .zip(role_access)
+        .flat_map(|(role, entries)| {
+            entries.into_iter().filter(|e| e.kind == kind).map(|e| {
+                vec![
+                    Some(role.id.to_string()),
+                    Some(role.name.clone()),
+                    Some(e.principal),
+                    Some(e.relation),
+                    Some(e.object),
+                ]
+            })
+        })
+        .collect())
+}
+
+fn user_row(user: &User) -> Row {
+    let user_type = match user.user_type {
+        UserType::Human => "human",
+        UserType::Application => "application",
+    };
+    let last_updated_with = match user.last_updated_with {
+        UserLastUpdatedWith::CreateEndpoint => "create-endpoint",
+        UserLastUpdatedWith::ConfigCallCreation => "config-call-creation",
+        UserLastUpdatedWith::UpdateEndpoint => "update-endpoint",
+    };
+    vec![
+        Some(user.id.to_string()),
+        Some(user.name.clone()),
+        user.email.clone(),
+        Some(user_type.to_string()),
+        Some(last_updated_with.to_string()),
+        Some(user.created_at.to_rfc3339()),
+        user.updated_at.map(|t| t.to_rfc3339()),
+    ]
+}
+
+fn role_row(role: &Role) -> Row {
+    vec![
+        Some(role.id.to_string()),
+        Some(role.name.clone()),
+        role.description.clone(),
+        Some(role.project_id.to_string()),
+        Some(role.created_at.to_rfc3339()),
+        role.updated_at.map(|t| t.to_rfc3339()),
+    ]
+}
+
+/// The header followed by one chunk per page.
+fn csv_stream(
+    columns: &'static [&'static str],
+    pages: impl Stream<Item = Result<Vec<Row>>> + Send,
+) -> impl Stream<Item = Result<Bytes>> + Send {
+    async_stream::try_stream! {
+        yield Bytes::from(format!("{}\n", columns.join(",")));
+        for await page in pages {
+            yield Bytes::from(rows_to_csv(&page?));
+        }
+    }
+}
+
+fn rows_to_csv(rows: &[Row]) -> String {
+    let mut csv = String::new();
+    for row in rows {
+        csv.push_str(
+            &row.iter()
+                .map(|f| csv_field(f.as_deref().unwrap_or_default()))
+                .collect::<Vec<_>>()
+                .join(","),
+        );
+        csv.push('\n');
+    }
+    csv
+}
+
+#[cfg(feature = "parquet-export")]
+#[allow(clippy::unnecessary_wraps)]
+fn parquet_body(
+    columns: &'static [&'static str],
+    pages: impl Stream<Item = Result<Vec<Row>>> + Send + 'static,
+) -> Result<Body> {
+    Ok(Body::from_stream(into_body_stream(parquet_stream(
+        columns, pages,
+    ))))
+}
+
+#[cfg(not(feature = "parquet-export"))]
+#[allow(clippy::needless_pass_by_value)]
+fn parquet_body(
+    _columns: &'static [&'static str],
+    _pages: impl Stream<Item = Result<Vec<Row>>> + Send + 'static,
+) -> Result<Body> {
+    Err(ErrorModel::not_implemented(
+        "Lakekeeper was built without the `parquet-export` feature",
+        "ParquetExportUnsupported",
+        None,
+    )
+    .into())
+}
+
+/// A Parquet file with one row group per page. Row groups are sent once they leave the
+/// buffer of the writer, the footer once all pages are read.
+#[cfg(feature = "parquet-export")]
+fn parquet_stream(
+    columns: &'static [&'static str],
+    pages: impl Stream<Item = Result<Vec<Row>>> + Send,
+) -> impl Stream<Item = Result<Bytes>> + Send {
+    async_stream::try_stream! {
+        let schema = parquet_schema(columns);
+        let mut writer =
+            ArrowWriter::try_new(Vec::new(), schema.clone(), None).map_err(parquet_error)?;
+        for await page in pages {
+            let page = page?;
+            if page.is_empty() {
+                continue;
+            }
+            writer
+                .write(&rows_to_record_batch(&schema, &page)?)
+                .map_err(parquet_error)?;
+            writer.flush().map_err(parquet_error)?;
+            // Offsets are tracked by the writer, so bytes that reached the buffer can be taken out.
+            yield Bytes::from(std::mem::take(writer.inner_mut()));
+        }
+        yield Bytes::from(writer.into_inner().map_err(parquet_error)?);
+    }
+}
+
+#[cfg(feature = "parquet-export")]
+fn parquet_schema(columns: &[&str]) -> SchemaRef {
+    Arc::new(Schema::new(
+        columns
+            .iter()
+            .map(|c| Field::new(*c, DataType::Utf8, true))
+            .collect::<Vec<_>>(),
+    ))
+}
+
+#[cfg(feature = "parquet-export")]
+fn rows_to_record_batch(schema: &SchemaRef, rows: &[Row]) -> Result<RecordBatch> {
+    let arrays = (0..schema.fields().len())
+        .map(|i| {
+            Arc::new(
+                rows.iter()
+                    .map(|row| row.get(i).and_then(Option::as_deref))
+                    .collect::<StringArray>(),
+            ) as ArrayRef
+        })
+        .collect::<Vec<_>>();
+    RecordBatch::try_new(schema.clone(), arrays).map_err(|e| {
+        ErrorModel::internal(
+            "Failed to build record batch of export",
+            "AccessExportError",
+            Some(Box::new(e)),
+        )
+        .into()
+    })
+}
+
+#[cfg(feature = "parquet-export")]
+#[allow(clippy::needless_pass_by_value)]
+fn parquet_error(e: parquet::errors::ParquetError) -> crate::api::IcebergErrorResponse {
+    ErrorModel::internal(
+        "Failed to write Parquet export",
+        "AccessExportError",
+        Some(Box::new(e)),
+    )
+    .into()
+}
+
+/// Errors after the response started can only abort the body.
+fn into_body_stream(
+    stream: impl Stream<Item = Result<Bytes>> + Send + 'static,
+) -> impl Stream<Item = std::result::Result<Bytes, std::io::Error>> + Send + 'static {
+    stream.map(|chunk| {
+        chunk.map_err(|e| {
+            tracing::error!(?e, "Aborting access export: {}", e.error.message);
+            std::io::Error::other(e.error.message)
+        })
+    })
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(feature = "parquet-export")]
+    use arrow_array::Array as _;
+    use futures::TryStreamExt as _;
+    #[cfg(feature = "parquet-export")]
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    use super::*;
+
+    fn pages() -> impl Stream<Item = Result<Vec<Row>>> + Send {
+        futures::stream::iter(vec![
+            Ok(vec![vec![
+                Some("1".to_string()),
+                Some("Analysts, EU".to_string()),
+                None,
+            ]]),
+            Ok(vec![]),
+            Ok(vec![vec![
+                Some("2".to_string()),
+                Some("say \"hi\"".to_string()),
+                Some("x".to_string()),
+            ]]),
+        ])
+    }
+
+    #[tokio::test]
+    async fn test_csv_stream() {
+        let chunks = csv_stream(&["id", "name", "description"], pages())
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        let csv = String::from_utf8(chunks.concat()).unwrap();
+        assert_eq!(
+            csv,
+            "id,name,description\n1,\"Analysts, EU\",\n2,\"say \"\"hi\"\"\",x\n"
+        );
+    }
+
+    #[cfg(feature = "parquet-export")]
+    #[tokio::test]
+    async fn test_parquet_stream() {
+        let chunks = parquet_stream(&["id", "name", "description"], pages())
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        let file = Bytes::from(chunks.concat());
+        let batches = ParquetRecordBatchReaderBuilder::try_new(file)
+            .unwrap()
+            .build()
+            .unwrap()
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .unwrap();
+        let rows = batches.iter().map(RecordBatch::num_rows).sum::<usize>();
+        assert_eq!(rows, 2);
+        let descriptions = batches[0]
+            .column_by_name("description")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert!(descriptions.is_null(0));
+    }
+}
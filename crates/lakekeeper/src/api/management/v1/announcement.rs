@@ -0,0 +1,454 @@
+use std::{
+    sync::{Arc, LazyLock},
+    time::Duration,
+};
+
+use axum::{
+    extract::{Request, State as AxumState},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use http::HeaderValue;
+use iceberg_ext::catalog::rest::ErrorModel;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::default_page_size;
+use crate::{
+    api::{
+        iceberg::v1::{PageToken, PaginationQuery},
+        management::v1::ApiServer,
+        ApiContext,
+    },
+    request_metadata::RequestMetadata,
+    service::{
+        authz::{Authorizer, CatalogServerAction},
+        Catalog, Result, SecretStore, State, Transaction,
+    },
+};
+
+/// Name of the response header carrying active announcements.
+pub const ANNOUNCEMENT_HEADER: &str = "lakekeeper-announcement";
+const MAX_TITLE_LENGTH: usize = 256;
+const MAX_MESSAGE_LENGTH: usize = 4096;
+
+/// Active announcements are cached for the header middleware, so that
+/// responses don't require an additional database query.
+static ACTIVE_ANNOUNCEMENTS_CACHE: LazyLock<moka::future::Cache<(), Arc<Vec<Announcement>>>> =
+    LazyLock::new(|| {
+        moka::future::Cache::builder()
+            .max_capacity(1)
+            .time_to_live(Duration::from_secs(30))
+            .build()
+    });
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(feature = "sqlx-postgres", derive(sqlx::Type))]
+#[cfg_attr(
+    feature = "sqlx-postgres",
+    sqlx(type_name = "announcement_severity", rename_all = "kebab-case")
+)]
+pub enum AnnouncementSeverity {
+    #[default]
+    Info,
+    Warning,
+    Critical,
+}
+
+impl AnnouncementSeverity {
+    fn as_str(self) -> &'static str {
+        match self {
+            AnnouncementSeverity::Info => "info",
+            AnnouncementSeverity::Warning => "warning",
+            AnnouncementSeverity::Critical => "critical",
+        }
+    }
+}
+
+/// A server-wide notice of operators, such as planned maintenance.
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct Announcement {
+    pub announcement_id: Uuid,
+    /// Short summary of the announcement
+    pub title: String,
+    /// Details of the announcement
+    pub message: Option<String>,
+    pub severity: AnnouncementSeverity,
+    /// Time from which the announcement is shown
+    pub starts_at: DateTime<Utc>,
+    /// Time until which the announcement is shown. Shown indefinitely if not set.
+    pub ends_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct CreateAnnouncementRequest {
+    /// Short summary of the announcement, at most 256 characters
+    pub title: String,
+    /// Details of the announcement, at most 4096 characters
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(default)]
+    pub severity: AnnouncementSeverity,
+    /// Time from which the announcement is shown. Default: now
+    #[serde(default)]
+    pub starts_at: Option<DateTime<Utc>>,
+    /// Time until which the announcement is shown. Shown indefinitely if not set.
+    #[serde(default)]
+    pub ends_at: Option<DateTime<Utc>>,
+}
+
+/// Replaces all fields of an announcement.
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateAnnouncementRequest {
+    /// Short summary of the announcement, at most 256 characters
+    pub title: String,
+    /// Details of the announcement. If not set, the message is removed.
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(default)]
+    pub severity: AnnouncementSeverity,
+    /// Time from which the announcement is shown. Keeps the current value if not set.
+    #[serde(default)]
+    pub starts_at: Option<DateTime<Utc>>,
+    /// Time until which the announcement is shown. If not set, it is shown indefinitely.
+    #[serde(default)]
+    pub ends_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct ListAnnouncementsQuery {
+    /// Next page token
+    #[serde(default)]
+    pub page_token: Option<String>,
+    /// Signals an upper bound of the number of results that a client will receive.
+    /// Default: 100
+    #[serde(default = "default_page_size")]
+    pub page_size: i64,
+}
+
+impl ListAnnouncementsQuery {
+    #[must_use]
+    pub fn pagination_query(&self) -> PaginationQuery {
+        PaginationQuery {
+            page_token: self
+                .page_token
+                .clone()
+                .map_or(PageToken::Empty, PageToken::Present),
+            page_size: Some(self.page_size),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ListAnnouncementsResponse {
+    /// All announcements, including past and upcoming ones
+    pub announcements: Vec<Announcement>,
+    pub next_page_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ListActiveAnnouncementsResponse {
+    /// Currently active announcements, most severe first
+    pub announcements: Vec<Announcement>,
+}
+
+impl IntoResponse for Announcement {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl IntoResponse for ListAnnouncementsResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl IntoResponse for ListActiveAnnouncementsResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
+
+#[async_trait::async_trait]
+pub(crate) trait Service<C: Catalog, A: Authorizer + Clone, S: SecretStore> {
+    async fn create_announcement(
+        request: CreateAnnouncementRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<Announcement> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_server_action(
+                &request_metadata,
+                CatalogServerAction::CanManageAnnouncements,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        validate_announcement(
+            &request.title,
+            request.message.as_deref(),
+            request.starts_at.unwrap_or_else(Utc::now),
+            request.ends_at,
+        )?;
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let announcement =
+            C::create_announcement(Uuid::now_v7(), request, transaction.transaction()).await?;
+        transaction.commit().await?;
+        ACTIVE_ANNOUNCEMENTS_CACHE.invalidate_all();
+
+        Ok(announcement)
+    }
+
+    async fn list_announcements(
+        query: ListAnnouncementsQuery,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<ListAnnouncementsResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_server_action(
+                &request_metadata,
+                CatalogServerAction::CanManageAnnouncements,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_read(context.v1_state.catalog).await?;
+        let response =
+            C::list_announcements(query.pagination_query(), transaction.transaction()).await?;
+        transaction.commit().await?;
+
+        Ok(response)
+    }
+
+    /// Active announcements are visible to every authenticated user.
+    async fn list_active_announcements(
+        context: ApiContext<State<A, C, S>>,
+        _request_metadata: RequestMetadata,
+    ) -> Result<ListActiveAnnouncementsResponse> {
+        let announcements = C::list_active_announcements(context.v1_state.catalog).await?;
+        Ok(ListActiveAnnouncementsResponse { announcements })
+    }
+
+    async fn update_announcement(
+        announcement_id: Uuid,
+        request: UpdateAnnouncementRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<Announcement> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_server_action(
+                &request_metadata,
+                CatalogServerAction::CanManageAnnouncements,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let existing = C::get_announcement(announcement_id, transaction.transaction())
+            .await?
+            .ok_or_else(|| announcement_not_found(announcement_id))?;
+        validate_announcement(
+            &request.title,
+            request.message.as_deref(),
+            request.starts_at.unwrap_or(existing.starts_at),
+            request.ends_at,
+        )?;
+        let announcement =
+            C::update_announcement(announcement_id, request, transaction.transaction())
+                .await?
+                .ok_or_else(|| announcement_not_found(announcement_id))?;
+        transaction.commit().await?;
+        ACTIVE_ANNOUNCEMENTS_CACHE.invalidate_all();
+
+        Ok(announcement)
+    }
+
+    async fn delete_announcement(
+        announcement_id: Uuid,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_server_action(
+                &request_metadata,
+                CatalogServerAction::CanManageAnnouncements,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::delete_announcement(announcement_id, transaction.transaction())
+            .await?
+            .ok_or_else(|| announcement_not_found(announcement_id))?;
+        transaction.commit().await?;
+        ACTIVE_ANNOUNCEMENTS_CACHE.invalidate_all();
+
+        Ok(())
+    }
+}
+
+/// Adds a `lakekeeper-announcement` header for each active announcement to the response.
+/// The header value is `<severity>; <title>`. Characters that are not allowed in
+/// header values are replaced by `?`.
+pub(crate) async fn announcement_header_middleware_fn<C: Catalog>(
+    AxumState(catalog_state): AxumState<C::State>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let mut response = next.run(request).await;
+
+    let announcements = ACTIVE_ANNOUNCEMENTS_CACHE
+        .try_get_with((), async {
+            C::list_active_announcements(catalog_state)
+                .await
+                .map(Arc::new)
+        })
+        .await;
+    match announcements {
+        Ok(announcements) => {
+            for announcement in announcements.iter() {
+                if let Ok(value) = HeaderValue::from_str(&header_value(announcement)) {
+                    response.headers_mut().append(ANNOUNCEMENT_HEADER, value);
+                }
+            }
+        }
+        Err(e) => {
+            tracing::warn!(error=?e, "Failed to load active announcements for response headers");
+        }
+    }
+
+    response
+}
+
+fn header_value(announcement: &Announcement) -> String {
+    let title = announcement
+        .title
+        .chars()
+        .map(|c| {
+            if c == ' ' || c.is_ascii_graphic() {
+                c
+            } else {
+                '?'
+            }
+        })
+        .collect::<String>();
+    format!("{}; {title}", announcement.severity.as_str())
+}
+
+fn validate_announcement(
+    title: &str,
+    message: Option<&str>,
+    starts_at: DateTime<Utc>,
+    ends_at: Option<DateTime<Utc>>,
+) -> Result<()> {
+    if title.trim().is_empty() || title.chars().count() > MAX_TITLE_LENGTH {
+        return Err(ErrorModel::bad_request(
+            format!("Announcement title must be between 1 and {MAX_TITLE_LENGTH} characters"),
+            "InvalidAnnouncement",
+            None,
+        )
+        .into());
+    }
+    if message.is_some_and(|m| m.chars().count() > MAX_MESSAGE_LENGTH) {
+        return Err(ErrorModel::bad_request(
+            format!("Announcement message must be at most {MAX_MESSAGE_LENGTH} characters"),
+            "InvalidAnnouncement",
+            None,
+        )
+        .into());
+    }
+    if ends_at.is_some_and(|ends_at| ends_at <= starts_at) {
+        return Err(ErrorModel::bad_request(
+            "Announcement must end after it starts",
+            "InvalidAnnouncement",
+            None,
+        )
+        .into());
+    }
+    Ok(())
+}
+
+fn announcement_not_found(announcement_id: Uuid) -> ErrorModel {
+    ErrorModel::not_found(
+        format!("Announcement {announcement_id} not found"),
+        "AnnouncementNotFound",
+        None,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn announcement(title: &str, severity: AnnouncementSeverity) -> Announcement {
+        Announcement {
+            announcement_id: Uuid::now_v7(),
+            title: title.to_string(),
+            message: None,
+            severity,
+            starts_at: Utc::now(),
+            ends_at: None,
+            created_at: Utc::now(),
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_header_value() {
+        let value = header_value(&announcement(
+            "Maintenance on Sunday 02:00 UTC",
+            AnnouncementSeverity::Warning,
+        ));
+        assert_eq!(value, "warning; Maintenance on Sunday 02:00 UTC");
+        assert!(HeaderValue::from_str(&value).is_ok());
+    }
+
+    #[test]
+    fn test_header_value_replaces_invalid_characters() {
+        let value = header_value(&announcement(
+            "Wartung\nam Männertag",
+            AnnouncementSeverity::Critical,
+        ));
+        assert_eq!(value, "critical; Wartung?am M?nnertag");
+        assert!(HeaderValue::from_str(&value).is_ok());
+    }
+
+    #[test]
+    fn test_validate_announcement() {
+        let now = Utc::now();
+        assert!(validate_announcement("Maintenance", None, now, None).is_ok());
+        assert!(validate_announcement(" ", None, now, None).is_err());
+        assert!(validate_announcement(&"a".repeat(257), None, now, None).is_err());
+        assert!(validate_announcement("Maintenance", Some(&"a".repeat(4097)), now, None).is_err());
+        assert!(validate_announcement("Maintenance", None, now, Some(now)).is_err());
+        assert!(validate_announcement(
+            "Maintenance",
+            None,
+            now,
+            Some(now + chrono::Duration::hours(1))
+        )
+        .is_ok());
+    }
+}
@@ -0,0 +1,313 @@
+use axum::{response::IntoResponse, Json};
+use iceberg_ext::catalog::rest::ErrorModel;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    api::{management::v1::ApiServer, ApiContext},
+    request_metadata::RequestMetadata,
+    service::{
+        authz::{Authorizer, CatalogWarehouseAction},
+        task_queue::{
+            catalog_import_queue::{
+                CatalogImport, CatalogImportCredential, CatalogImportPayload, CatalogImportSource,
+                CatalogImportTable, CatalogImportTableStatus,
+            },
+            EntityId, TaskId, TaskMetadata,
+        },
+        Catalog, Result, SecretStore, State, Transaction,
+    },
+    WarehouseId,
+};
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ImportCatalogRequest {
+    #[serde(flatten)]
+    pub source: CatalogImportSource,
+    /// Credential for the source catalog. Either a `token` or a client `credential`.
+    #[serde(flatten)]
+    pub credential: CatalogImportCredential,
+    /// Register imported tables as external tables, whose files are owned by the
+    /// source catalog. They are never purged or maintained by Lakekeeper. Default: false
+    #[serde(default)]
+    pub external: bool,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ImportCatalogResponse {
+    /// Id of the import, used to query its status
+    pub import_id: Uuid,
+}
+
+impl IntoResponse for ImportCatalogResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::ACCEPTED, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct GetCatalogImportResponse {
+    #[serde(flatten)]
+    pub import: CatalogImport,
+    /// Number of registered tables
+    pub imported_tables: usize,
+    /// Number of skipped tables
+    pub skipped_tables: usize,
+    /// Number of tables that failed to import
+    pub failed_tables: usize,
+    /// Outcome per table, ordered by namespace and name
+    pub tables: Vec<CatalogImportTable>,
+}
+
+impl IntoResponse for GetCatalogImportResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
+
+#[async_trait::async_trait]
+pub(crate) trait Service<C: Catalog, A: Authorizer + Clone, S: SecretStore> {
+    async fn import_catalog(
+        warehouse_id: WarehouseId,
+        request: ImportCatalogRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<ImportCatalogResponse> {
+        // ------------------- VALIDATIONS -------------------
+        let ImportCatalogRequest {
+            source,
+            credential,
+            external,
+        } = request;
+        validate_import_source(&source, &credential)?;
+
+        // ------------------- AuthZ -------------------
+        // Namespaces and tables are created with the permissions of the user
+        // that started the import, which are checked again by the import task.
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanCreateNamespace,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let secrets = &context.v1_state.secrets;
+        let credential_secret_id = if credential.is_empty() {
+            None
+        } else {
+            Some(secrets.create_secret(credential).await?)
+        };
+        let payload = CatalogImportPayload {
+            source,
+            credential_secret_id: credential_secret_id.map(Into::into),
+            owner: request_metadata.user_id().cloned(),
+            external,
+        };
+        let queued = queue_import::<C>(warehouse_id, &payload, context.v1_state.catalog).await;
+
+        match queued {
+            Ok(import_id) => {
+                tracing::info!(
+                    %warehouse_id,
+                    %import_id,
+                    "Queued import of catalog {} into warehouse",
+                    payload.source.uri
+                );
+                Ok(ImportCatalogResponse {
+                    import_id: *import_id,
+                })
+            }
+            Err(e) => {
+                if let Some(secret_id) = credential_secret_id {
+                    if let Err(delete_error) = secrets.delete_secret(&secret_id).await {
+                        tracing::warn!(
+                            "Failed to delete credential of catalog import that was not queued: {}",
+                            delete_error.error
+                        );
+                    }
+                }
+                Err(e)
+            }
+        }
+    }
+
+    async fn get_catalog_import(
+        warehouse_id: WarehouseId,
+        import_id: Uuid,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<GetCatalogImportResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanListEverything,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_read(context.v1_state.catalog).await?;
+        let import = C::get_catalog_import(warehouse_id, import_id, transaction.transaction())
+            .await?
+            .ok_or_else(|| {
+                ErrorModel::not_found(
+                    format!("Catalog import {import_id} not found in warehouse {warehouse_id}"),
+                    "CatalogImportNotFound",
+                    None,
+                )
+            })?;
+        let tables = C::list_catalog_import_tables(import_id, transaction.transaction()).await?;
+        transaction.commit().await?;
+
+        let count = |status| tables.iter().filter(|t| t.status == status).count();
+        Ok(GetCatalogImportResponse {
+            import,
+            imported_tables: count(CatalogImportTableStatus::Imported),
+            skipped_tables: count(CatalogImportTableStatus::Skipped),
+            failed_tables: count(CatalogImportTableStatus::Failed),
+            tables,
+        })
+    }
+}
+
+async fn queue_import<C: Catalog>(
+    warehouse_id: WarehouseId,
+    payload: &CatalogImportPayload,
+    catalog_state: C::State,
+) -> Result<TaskId> {
+    let mut transaction = C::Transaction::begin_write(catalog_state).await?;
+    let import_id = C::queue_catalog_import(
+        TaskMetadata {
+            warehouse_id,
+            parent_task_id: None,
+            entity_id: EntityId::Warehouse(*warehouse_id),
+            schedule_for: None,
+        },
+        payload.clone(),
+        transaction.transaction(),
+    )
+    .await?
+    .ok_or_else(|| {
+        ErrorModel::conflict(
+            "A catalog import into the warehouse is already running",
+            "CatalogImportRunning",
+            None,
+        )
+    })?;
+    C::create_catalog_import(
+        *import_id,
+        warehouse_id,
+        &payload.source,
+        payload.owner.as_ref().map(ToString::to_string).as_deref(),
+        transaction.transaction(),
+    )
+    .await?;
+    transaction.commit().await?;
+    Ok(import_id)
+}
+
+fn validate_import_source(
+    source: &CatalogImportSource,
+    credential: &CatalogImportCredential,
+) -> Result<()> {
+    if !matches!(source.uri.scheme(), "http" | "https") {
+        return Err(ErrorModel::bad_request(
+            "The URI of the source catalog must use http or https",
+            "InvalidCatalogImportSource",
+            None,
+        )
+        .into());
+    }
+    if credential.token.is_some() && credential.credential.is_some() {
+        return Err(ErrorModel::bad_request(
+            "Only one of `token` and `credential` can be specified",
+            "InvalidCatalogImportCredential",
+            None,
+        )
+        .into());
+    }
+    if credential
+        .credential
+        .as_ref()
+        .is_some_and(|c| !c.contains(':'))
+    {
+        return Err(ErrorModel::bad_request(
+            "Credential must have the format `<client_id>:<client_secret>`",
+            "InvalidCatalogImportCredential",
+            None,
+        )
+        .into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn source(uri: &str) -> CatalogImportSource {
+        CatalogImportSource {
+            uri: url::Url::parse(uri).unwrap(),
+            warehouse: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_import_source() {
+        let credential = CatalogImportCredential::default();
+        assert!(validate_import_source(
+            &source("https://polaris.example.com/api/catalog"),
+            &credential
+        )
+        .is_ok());
+        assert!(validate_import_source(&source("file:///tmp/catalog"), &credential).is_err());
+
+        let credential = CatalogImportCredential {
+            credential: Some("client-id".to_string()),
+            ..Default::default()
+        };
+        assert!(
+            validate_import_source(&source("https://polaris.example.com"), &credential).is_err()
+        );
+
+        let credential = CatalogImportCredential {
+            token: Some("token".to_string()),
+            credential: Some("client-id:client-secret".to_string()),
+            ..Default::default()
+        };
+        assert!(
+            validate_import_source(&source("https://polaris.example.com"), &credential).is_err()
+        );
+    }
+
+    #[test]
+    fn test_deserialize_request() {
+        let request: ImportCatalogRequest = serde_json::from_value(serde_json::json!({
+            "uri": "https://polaris.example.com/api/catalog",
+            "warehouse": "analytics",
+            "credential": "client-id:client-secret",
+            "scope": "PRINCIPAL_ROLE:ALL"
+        }))
+        .unwrap();
+        assert_eq!(request.source.warehouse.as_deref(), Some("analytics"));
+        assert_eq!(
+            request.credential.credential.as_deref(),
+            Some("client-id:client-secret")
+        );
+        assert_eq!(
+            request.credential.scope.as_deref(),
+            Some("PRINCIPAL_ROLE:ALL")
+        );
+        assert!(!request.external);
+    }
+}
@@ -0,0 +1,1003 @@
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    str::FromStr as _,
+};
+
+use axum::{response::IntoResponse, Json};
+use iceberg_ext::{catalog::rest::ErrorModel, configs::Location};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    api::{
+        management::v1::{user::UserLastUpdatedWith, ApiServer},
+        ApiContext,
+    },
+    catalog::maybe_get_secret,
+    request_metadata::RequestMetadata,
+    service::{
+        authz::{Authorizer, CatalogWarehouseAction, ObjectGrant},
+        mirror::{apply_catalog_state, MirrorOptions, MirrorPlan},
+        task_queue::{
+            catalog_snapshot_export_queue::{
+                self, catalog_snapshot_location, diff_catalog_state, grant_key, grant_name,
+                load_catalog_snapshot_access, load_catalog_snapshot_users, read_catalog_snapshot,
+                replay_catalog_snapshot, CatalogSnapshot, CatalogSnapshotAccess,
+                CatalogSnapshotDifference, CatalogSnapshotEntityType, CatalogSnapshotExportPayload,
+                CatalogSnapshotKind, CatalogSnapshotNamespace, CatalogSnapshotState,
+                CatalogSnapshotTabular, CatalogSnapshotUser, ReplayedCatalogSnapshot,
+            },
+            EntityId, TaskFilter, TaskInfo, TaskMetadata,
+        },
+        Catalog, NamespaceId, Result, SecretStore, State, TableId, Transaction,
+    },
+    WarehouseId, CONFIG,
+};
+
+/// Number of snapshots returned per warehouse.
+const CATALOG_SNAPSHOTS_LIMIT: i64 = 100;
+
+/// Restored tables and views are managed by the warehouse, and entities that are soft-deleted
+/// in the snapshot stay soft-deleted, so that they can still be undropped.
+const RESTORE_OPTIONS: MirrorOptions = MirrorOptions {
+    external_tables: false,
+    keep_soft_deleted: true,
+};
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ListCatalogSnapshotsResponse {
+    /// Next scheduled or currently running export of the warehouse.
+    /// `null` if no export is scheduled.
+    pub next_export: Option<TaskInfo>,
+    /// Most recent snapshots, newest first
+    pub snapshots: Vec<CatalogSnapshot>,
+}
+
+impl IntoResponse for ListCatalogSnapshotsResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct VerifyCatalogSnapshotResponse {
+    pub snapshot_id: Uuid,
+    /// Time the snapshot was exported
+    pub snapshot_created_at: chrono::DateTime<chrono::Utc>,
+    /// Location of the manifest that was verified
+    pub location: String,
+    /// Checksum stored in the manifest
+    pub checksum: String,
+    /// The manifest and the manifests of all replayed snapshots match their
+    /// checksums, i.e. they were not modified or corrupted.
+    pub checksum_valid: bool,
+    /// Snapshots that were replayed to restore the catalog state,
+    /// starting with the full base snapshot.
+    pub replayed_snapshots: Vec<Uuid>,
+    /// The checksum is valid and the current catalog state matches the snapshot.
+    pub consistent: bool,
+    /// Differences between the snapshot and the current catalog state
+    pub differences: Vec<CatalogSnapshotDifference>,
+}
+
+impl IntoResponse for VerifyCatalogSnapshotResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct CatalogSnapshotStateResponse {
+    pub snapshot_id: Uuid,
+    pub kind: CatalogSnapshotKind,
+    /// Time the snapshot was exported
+    pub snapshot_created_at: chrono::DateTime<chrono::Utc>,
+    /// All replayed manifests match their checksums
+    pub checksums_valid: bool,
+    /// Snapshots that were replayed to restore the catalog state,
+    /// starting with the full base snapshot.
+    pub replayed_snapshots: Vec<Uuid>,
+    /// Catalog state of the warehouse at the time of the snapshot
+    pub state: CatalogSnapshotState,
+}
+
+impl IntoResponse for CatalogSnapshotStateResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Default, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct RestoreCatalogSnapshotRequest {
+    /// Only compute the changes required to restore the snapshot without applying them.
+    /// Default: false
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum CatalogSnapshotRestoreAction {
+    Create,
+    Update,
+    Drop,
+    Undrop,
+    Grant,
+    Revoke,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct CatalogSnapshotRestoreChange {
+    pub entity_type: CatalogSnapshotEntityType,
+    pub entity_id: Option<Uuid>,
+    /// Qualified name of the entity, the user name or a description of the grant
+    pub name: String,
+    pub action: CatalogSnapshotRestoreAction,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct RestoreCatalogSnapshotResponse {
+    pub snapshot_id: Uuid,
+    /// Time the snapshot was exported
+    pub snapshot_created_at: chrono::DateTime<chrono::Utc>,
+    /// Snapshots that were replayed to restore the catalog state,
+    /// starting with the full base snapshot.
+    pub replayed_snapshots: Vec<Uuid>,
+    pub dry_run: bool,
+    /// Changes applied to the warehouse, or the changes that would be applied in a dry run
+    pub changes: Vec<CatalogSnapshotRestoreChange>,
+    /// Differences between the snapshot and the catalog after the restore.
+    /// Warehouse settings and task queue configurations are not restored, tables and
+    /// views whose metadata files can't be read are skipped.
+    /// For dry runs, the differences before the restore.
+    pub differences: Vec<CatalogSnapshotDifference>,
+}
+
+impl IntoResponse for RestoreCatalogSnapshotResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
+
+#[async_trait::async_trait]
+pub(crate) trait Service<C: Catalog, A: Authorizer + Clone, S: SecretStore> {
+    async fn list_catalog_snapshots(
+        warehouse_id: WarehouseId,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<ListCatalogSnapshotsResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanGetMetadata,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let next_export = C::get_entity_task(
+            warehouse_id,
+            EntityId::Warehouse(*warehouse_id),
+            catalog_snapshot_export_queue::QUEUE_NAME,
+            context.v1_state.catalog.clone(),
+        )
+        .await?;
+        let mut transaction = C::Transaction::begin_read(context.v1_state.catalog).await?;
+        let snapshots = C::list_catalog_snapshots(
+            warehouse_id,
+            CATALOG_SNAPSHOTS_LIMIT,
+            transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await?;
+
+        Ok(ListCatalogSnapshotsResponse {
+            next_export,
+            snapshots,
+        })
+    }
+
+    async fn export_catalog_snapshot(
+        warehouse_id: WarehouseId,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- VALIDATIONS -------------------
+        if !CONFIG.enable_catalog_snapshot_export {
+            return Err(ErrorModel::bad_request(
+                "Catalog snapshot export is not enabled on this server",
+                "CatalogSnapshotExportDisabled",
+                None,
+            )
+            .into());
+        }
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanModifyTaskQueueConfig,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        // The scheduled export of the warehouse is moved to now.
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let task_id = C::reschedule_catalog_snapshot_export(
+            TaskMetadata {
+                warehouse_id,
+                parent_task_id: None,
+                entity_id: EntityId::Warehouse(*warehouse_id),
+                schedule_for: None,
+            },
+            CatalogSnapshotExportPayload::default(),
+            transaction.transaction(),
+        )
+        .await?;
+        if task_id.is_none() {
+            return Err(ErrorModel::conflict(
+                "A catalog snapshot export of the warehouse is already running",
+                "CatalogSnapshotExportRunning",
+                None,
+            )
+            .into());
+        }
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn verify_catalog_snapshot(
+        warehouse_id: WarehouseId,
+        snapshot_id: Uuid,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<VerifyCatalogSnapshotResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanListEverything,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let (location, replayed) = load_catalog_snapshot::<C, S>(
+            warehouse_id,
+            snapshot_id,
+            context.v1_state.catalog.clone(),
+            &context.v1_state.secrets,
+        )
+        .await?;
+        let checksum_valid = replayed.checksums_valid;
+
+        let current_state = load_current_state::<C, A>(
+            warehouse_id,
+            replayed.state.access.is_some(),
+            &authorizer,
+            context.v1_state.catalog,
+        )
+        .await?;
+        let differences = diff_catalog_state(&replayed.state, &current_state);
+        let consistent = checksum_valid && differences.is_empty();
+        tracing::info!(
+            %warehouse_id,
+            %snapshot_id,
+            checksum_valid,
+            differences = differences.len(),
+            "Verified catalog against snapshot: consistent={consistent}"
+        );
+
+        Ok(VerifyCatalogSnapshotResponse {
+            snapshot_id,
+            snapshot_created_at: replayed.created_at,
+            location: location.to_string(),
+            checksum: replayed.checksum,
+            checksum_valid,
+            replayed_snapshots: replayed.replayed_snapshots,
+            consistent,
+            differences,
+        })
+    }
+
+    async fn get_catalog_snapshot_state(
+        warehouse_id: WarehouseId,
+        snapshot_id: Uuid,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<CatalogSnapshotStateResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanListEverything,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let (_, replayed) = load_catalog_snapshot::<C, S>(
+            warehouse_id,
+            snapshot_id,
+            context.v1_state.catalog,
+            &context.v1_state.secrets,
+        )
+        .await?;
+
+        Ok(CatalogSnapshotStateResponse {
+            snapshot_id,
+            kind: replayed.kind,
+            snapshot_created_at: replayed.created_at,
+            checksums_valid: replayed.checksums_valid,
+            replayed_snapshots: replayed.replayed_snapshots,
+            state: replayed.state,
+        })
+    }
+
+    async fn restore_catalog_snapshot(
+        warehouse_id: WarehouseId,
+        snapshot_id: Uuid,
+        request: RestoreCatalogSnapshotRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<RestoreCatalogSnapshotResponse> {
+        // ------------------- AuthZ -------------------
+        // A restore can drop and replace every entity of the warehouse.
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanDelete,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let catalog_state = context.v1_state.catalog;
+        let (_, replayed) = load_catalog_snapshot::<C, S>(
+            warehouse_id,
+            snapshot_id,
+            catalog_state.clone(),
+            &context.v1_state.secrets,
+        )
+        .await?;
+        if !replayed.checksums_valid {
+            return Err(ErrorModel::conflict(
+                format!(
+                    "Manifests of catalog snapshot {snapshot_id} don't match their checksums. Refusing to restore a modified or corrupted snapshot."
+                ),
+                "CatalogSnapshotChecksumInvalid",
+                None,
+            )
+            .into());
+        }
+        let target = replayed.state;
+        let current = load_current_state::<C, A>(
+            warehouse_id,
+            target.access.is_some(),
+            &authorizer,
+            catalog_state.clone(),
+        )
+        .await?;
+        let (undrop_namespaces, undrop_tabulars) = soft_deleted_in_current(&current, &target);
+        let mut changes = undrop_changes(&undrop_namespaces, &undrop_tabulars);
+
+        if request.dry_run {
+            let mut undropped = current.clone();
+            mark_undropped(&mut undropped, &target);
+            changes.extend(plan_changes(&MirrorPlan::with_options(
+                &undropped,
+                &target,
+                RESTORE_OPTIONS,
+            )));
+            if let (Some(target_access), Some(current_access)) = (&target.access, &current.access) {
+                let existing_users = load_catalog_snapshot_users::<C>(
+                    target_access.users.iter().map(|u| u.id.clone()).collect(),
+                    catalog_state.clone(),
+                )
+                .await?;
+                let access = AccessChanges::between(target_access, current_access, &existing_users);
+                changes.extend(access.changes());
+            }
+            return Ok(RestoreCatalogSnapshotResponse {
+                snapshot_id,
+                snapshot_created_at: replayed.created_at,
+                replayed_snapshots: replayed.replayed_snapshots,
+                dry_run: true,
+                changes,
+                differences: diff_catalog_state(&target, &current),
+            });
+        }
+
+        undrop_soft_deleted::<C>(warehouse_id, &target, catalog_state.clone()).await?;
+        let plan = apply_catalog_state::<C, A, S>(
+            warehouse_id,
+            &target,
+            catalog_state.clone(),
+            &authorizer,
+            &context.v1_state.secrets,
+            &request_metadata,
+            RESTORE_OPTIONS,
+        )
+        .await?;
+        changes.extend(plan_changes(&plan));
+        if let Some(target_access) = &target.access {
+            changes.extend(
+                restore_access::<C, A>(
+                    warehouse_id,
+                    target_access,
+                    &authorizer,
+                    catalog_state.clone(),
+                )
+                .await?,
+            );
+        }
+
+        let restored = load_current_state::<C, A>(
+            warehouse_id,
+            target.access.is_some(),
+            &authorizer,
+            catalog_state,
+        )
+        .await?;
+        let differences = diff_catalog_state(&target, &restored);
+        tracing::info!(
+            %warehouse_id,
+            %snapshot_id,
+            changes = changes.len(),
+            differences = differences.len(),
+            "Restored warehouse from catalog snapshot"
+        );
+
+        Ok(RestoreCatalogSnapshotResponse {
+            snapshot_id,
+            snapshot_created_at: replayed.created_at,
+            replayed_snapshots: replayed.replayed_snapshots,
+            dry_run: false,
+            changes,
+            differences,
+        })
+    }
+}
+
+/// Current catalog state of the warehouse, including its grants if `with_access` is set.
+async fn load_current_state<C: Catalog, A: Authorizer>(
+    warehouse_id: WarehouseId,
+    with_access: bool,
+    authorizer: &A,
+    catalog_state: C::State,
+) -> Result<CatalogSnapshotState> {
+    let mut state = C::load_catalog_snapshot_state(warehouse_id, catalog_state.clone())
+        .await?
+        .ok_or_else(|| {
+            ErrorModel::not_found(
+                format!("Warehouse {warehouse_id} not found"),
+                "WarehouseNotFound",
+                None,
+            )
+        })?;
+    if with_access {
+        state.access = Some(
+            load_catalog_snapshot_access::<C, A>(warehouse_id, &state, authorizer, catalog_state)
+                .await?,
+        );
+    }
+    Ok(state)
+}
+
+/// Namespaces, tables and views that are soft-deleted in `current` but live in `target`.
+fn soft_deleted_in_current<'a>(
+    current: &'a CatalogSnapshotState,
+    target: &CatalogSnapshotState,
+) -> (
+    Vec<&'a CatalogSnapshotNamespace>,
+    Vec<&'a CatalogSnapshotTabular>,
+) {
+    let live_namespaces = target
+        .namespaces
+        .iter()
+        .filter(|n| !n.deleted)
+        .map(|n| n.namespace_id)
+        .collect::<HashSet<_>>();
+    let live_tabulars = target
+        .tabulars
+        .iter()
+        .filter(|t| !t.deleted && t.metadata_location.is_some())
+        .map(|t| t.tabular_id)
+        .collect::<HashSet<_>>();
+    (
+        current
+            .namespaces
+            .iter()
+            .filter(|n| n.deleted && live_namespaces.contains(&n.namespace_id))
+            .collect(),
+        current
+            .tabulars
+            .iter()
+            .filter(|t| t.deleted && live_tabulars.contains(&t.tabular_id))
+            .collect(),
+    )
+}
+
+fn mark_undropped(current: &mut CatalogSnapshotState, target: &CatalogSnapshotState) {
+    let (namespaces, tabulars) = soft_deleted_in_current(current, target);
+    let namespaces = namespaces
+        .into_iter()
+        .map(|n| n.namespace_id)
+        .collect::<HashSet<_>>();
+    let tabulars = tabulars
+        .into_iter()
+        .map(|t| t.tabular_id)
+        .collect::<HashSet<_>>();
+    for namespace in &mut current.namespaces {
+        if namespaces.contains(&namespace.namespace_id) {
+            namespace.deleted = false;
+        }
+    }
+    for tabular in &mut current.tabulars {
+        if tabulars.contains(&tabular.tabular_id) {
+            tabular.deleted = false;
+        }
+    }
+}
+
+fn undrop_changes(
+    namespaces: &[&CatalogSnapshotNamespace],
+    tabulars: &[&CatalogSnapshotTabular],
+) -> Vec<CatalogSnapshotRestoreChange> {
+    namespaces
+        .iter()
+        .map(|n| namespace_change(n, CatalogSnapshotRestoreAction::Undrop))
+        .chain(
+            tabulars
+                .iter()
+                .map(|t| tabular_change(t, CatalogSnapshotRestoreAction::Undrop)),
+        )
+        .collect()
+}
+
+/// Undrops namespaces, tables and views that were soft-deleted after the snapshot, so that
+/// they are restored with their history instead of being re-created.
+/// Child namespaces that were dropped together with their parent are undropped with it,
+/// so the state is reloaded after each level of the hierarchy.
+async fn undrop_soft_deleted<C: Catalog>(
+    warehouse_id: WarehouseId,
+    target: &CatalogSnapshotState,
+    catalog_state: C::State,
+) -> Result<()> {
+    loop {
+        let current = C::load_catalog_snapshot_state(warehouse_id, catalog_state.clone())
+            .await?
+            .ok_or_else(|| {
+                ErrorModel::not_found(
+                    format!("Warehouse {warehouse_id} not found"),
+                    "WarehouseNotFound",
+                    None,
+                )
+            })?;
+        let (namespaces, tabulars) = soft_deleted_in_current(&current, target);
+        let mut t = C::Transaction::begin_write(catalog_state.clone()).await?;
+        if namespaces.is_empty() {
+            if !tabulars.is_empty() {
+                let tabular_ids = tabulars
+                    .iter()
+                    .map(|tabular| TableId::from(tabular.tabular_id))
+                    .collect::<Vec<_>>();
+                let undropped =
+                    C::undrop_tabulars(&tabular_ids, warehouse_id, t.transaction()).await?;
+                C::cancel_tabular_expiration(
+                    TaskFilter::TaskIds(undropped.iter().map(|r| r.task_id).collect()),
+                    t.transaction(),
+                )
+                .await?;
+            }
+            t.commit().await?;
+            return Ok(());
+        }
+
+        let names = namespaces
+            .iter()
+            .map(|n| n.name.as_slice())
+            .collect::<HashSet<_>>();
+        // Children whose parent is undropped as well are handled with the next iteration
+        for namespace in namespaces
+            .iter()
+            .filter(|n| !matches!(n.name.split_last(), Some((_, parent)) if names.contains(parent)))
+        {
+            let task_ids = C::undrop_namespace(
+                warehouse_id,
+                NamespaceId::from(namespace.namespace_id),
+                t.transaction(),
+            )
+            .await?;
+            C::cancel_tabular_expiration(TaskFilter::TaskIds(task_ids), t.transaction()).await?;
+        }
+        t.commit().await?;
+    }
+}
+
+fn namespace_change(
+    namespace: &CatalogSnapshotNamespace,
+    action: CatalogSnapshotRestoreAction,
+) -> CatalogSnapshotRestoreChange {
+    CatalogSnapshotRestoreChange {
+        entity_type: CatalogSnapshotEntityType::Namespace,
+        entity_id: Some(namespace.namespace_id),
+        name: namespace.name.join("."),
+        action,
+    }
+}
+
+fn tabular_change(
+    tabular: &CatalogSnapshotTabular,
+    action: CatalogSnapshotRestoreAction,
+) -> CatalogSnapshotRestoreChange {
+    CatalogSnapshotRestoreChange {
+        entity_type: tabular.entity_type(),
+        entity_id: Some(tabular.tabular_id),
+        name: tabular.qualified_name(),
+        action,
+    }
+}
+
+fn plan_changes(plan: &MirrorPlan) -> Vec<CatalogSnapshotRestoreChange> {
+    use CatalogSnapshotRestoreAction as Action;
+
+    let namespaces = plan
+        .create_namespaces
+        .iter()
+        .map(|n| namespace_change(n, Action::Create))
+        .chain(
+            plan.update_namespaces
+                .iter()
+                .map(|n| namespace_change(n, Action::Update)),
+        )
+        .chain(
+            plan.drop_namespaces
+                .iter()
+                .map(|n| namespace_change(n, Action::Drop)),
+        );
+    let tabulars = plan
+        .create_tabulars
+        .iter()
+        .map(|t| tabular_change(t, Action::Create))
+        .chain(
+            plan.update_tabulars
+                .iter()
+                .map(|(_, t)| tabular_change(t, Action::Update)),
+        )
+        .chain(
+            plan.drop_tabulars
+                .iter()
+                .map(|t| tabular_change(t, Action::Drop)),
+        );
+    namespaces.chain(tabulars).collect()
+}
+
+/// Users and grants that differ between a snapshot and the catalog.
+#[derive(Debug, Default, PartialEq)]
+struct AccessChanges {
+    /// Users that were deleted or changed after the snapshot, with their state in the snapshot
+    users: Vec<(CatalogSnapshotUser, CatalogSnapshotRestoreAction)>,
+    grants: Vec<ObjectGrant>,
+    revokes: Vec<ObjectGrant>,
+}
+
+impl AccessChanges {
+    fn between(
+        target: &CatalogSnapshotAccess,
+        current: &CatalogSnapshotAccess,
+        existing_users: &[CatalogSnapshotUser],
+    ) -> Self {
+        let existing_users = existing_users
+            .iter()
+            .map(|u| (u.id.to_string(), u))
+            .collect::<HashMap<_, _>>();
+        let users = target
+            .users
+            .iter()
+            .filter_map(|user| match existing_users.get(&user.id.to_string()) {
+                None => Some((user.clone(), CatalogSnapshotRestoreAction::Create)),
+                Some(existing) if *existing != user => {
+                    Some((user.clone(), CatalogSnapshotRestoreAction::Update))
+                }
+                Some(_) => None,
+            })
+            .collect();
+
+        let target_grants = target.grants.iter().map(grant_key).collect::<BTreeSet<_>>();
+        let current_grants = current
+            .grants
+            .iter()
+            .map(grant_key)
+            .collect::<BTreeSet<_>>();
+        Self {
+            users,
+            grants: target
+                .grants
+                .iter()
+                .filter(|g| !current_grants.contains(&grant_key(g)))
+                .cloned()
+                .collect(),
+            revokes: current
+                .grants
+                .iter()
+                .filter(|g| !target_grants.contains(&grant_key(g)))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    fn changes(&self) -> Vec<CatalogSnapshotRestoreChange> {
+        let grant_change = |grant: &ObjectGrant, action| CatalogSnapshotRestoreChange {
+            entity_type: CatalogSnapshotEntityType::Grant,
+            entity_id: Some(grant.object.id()),
+            name: grant_name(grant),
+            action,
+        };
+        self.users
+            .iter()
+            .map(|(user, action)| CatalogSnapshotRestoreChange {
+                entity_type: CatalogSnapshotEntityType::User,
+                entity_id: None,
+                name: user.id.to_string(),
+                action: *action,
+            })
+            .chain(
+                self.grants
+                    .iter()
+                    .map(|g| grant_change(g, CatalogSnapshotRestoreAction::Grant)),
+            )
+            .chain(
+                self.revokes
+                    .iter()
+                    .map(|g| grant_change(g, CatalogSnapshotRestoreAction::Revoke)),
+            )
+            .collect()
+    }
+}
+
+/// Re-creates users that were deleted after the snapshot and brings the grants on the
+/// warehouse and its entities back to the state of the snapshot.
+async fn restore_access<C: Catalog, A: Authorizer>(
+    warehouse_id: WarehouseId,
+    target: &CatalogSnapshotAccess,
+    authorizer: &A,
+    catalog_state: C::State,
+) -> Result<Vec<CatalogSnapshotRestoreChange>> {
+    let current = load_current_state::<C, A>(warehouse_id, true, authorizer, catalog_state.clone())
+        .await?
+        .access
+        .unwrap_or_default();
+    let existing_users = load_catalog_snapshot_users::<C>(
+        target.users.iter().map(|u| u.id.clone()).collect(),
+        catalog_state.clone(),
+    )
+    .await?;
+    let access = AccessChanges::between(target, &current, &existing_users);
+
+    if !access.users.is_empty() {
+        let mut t = C::Transaction::begin_write(catalog_state).await?;
+        for (user, _) in &access.users {
+            C::create_or_update_user(
+                &user.id,
+                &user.name,
+                user.email.as_deref(),
+                UserLastUpdatedWith::UpdateEndpoint,
+                user.user_type,
+                t.transaction(),
+            )
+            .await?;
+        }
+        t.commit().await?;
+    }
+    authorizer.write_object_grants(&access.grants).await?;
+    authorizer.delete_object_grants(&access.revokes).await?;
+    Ok(access.changes())
+}
+
+/// Reads the manifest of a snapshot and replays it onto its full base snapshot.
+async fn load_catalog_snapshot<C: Catalog, S: SecretStore>(
+    warehouse_id: WarehouseId,
+    snapshot_id: Uuid,
+    catalog_state: C::State,
+    secret_state: &S,
+) -> Result<(Location, ReplayedCatalogSnapshot)> {
+    let mut transaction = C::Transaction::begin_read(catalog_state).await?;
+    let warehouse = C::require_warehouse(warehouse_id, transaction.transaction()).await?;
+    let snapshot =
+        C::get_catalog_snapshot(warehouse_id, snapshot_id, transaction.transaction()).await?;
+    transaction.commit().await?;
+
+    // Snapshots newer than a restored database are not known to the catalog.
+    // Their manifest is looked up in the default location.
+    let location = match &snapshot {
+        Some(snapshot) => Location::from_str(&snapshot.location).map_err(|e| {
+            ErrorModel::internal(
+                format!(
+                    "Location '{}' of catalog snapshot is invalid",
+                    snapshot.location
+                ),
+                "InvalidCatalogSnapshotLocation",
+                Some(Box::new(e)),
+            )
+        })?,
+        None => catalog_snapshot_location(&warehouse.storage_profile, snapshot_id)?,
+    };
+    let secret = maybe_get_secret(warehouse.storage_secret_id, secret_state).await?;
+    let file_io = warehouse.storage_profile.file_io(secret.as_ref()).await?;
+    let snapshot_file = match read_catalog_snapshot(&file_io, &location).await {
+        Ok(snapshot_file) => snapshot_file,
+        Err(e) if snapshot.is_none() => {
+            return Err(ErrorModel::not_found(
+                format!("Catalog snapshot {snapshot_id} not found in warehouse {warehouse_id}"),
+                "CatalogSnapshotNotFound",
+                Some(Box::new(e)),
+            )
+            .into());
+        }
+        Err(e) => return Err(e),
+    };
+
+    let manifest = &snapshot_file.manifest;
+    if manifest.snapshot_id != snapshot_id || manifest.warehouse_id != warehouse_id {
+        return Err(ErrorModel::bad_request(
+            format!(
+                "Manifest at {location} belongs to snapshot {} of warehouse {}",
+                manifest.snapshot_id, manifest.warehouse_id
+            ),
+            "CatalogSnapshotMismatch",
+            None,
+        )
+        .into());
+    }
+
+    let replayed =
+        replay_catalog_snapshot(snapshot_file, &file_io, &warehouse.storage_profile).await?;
+    Ok((location, replayed))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::{
+        api::management::v1::{user::UserType, TabularType},
+        service::{
+            authz::{GrantObject, GrantPrincipal},
+            task_queue::catalog_snapshot_export_queue::CatalogSnapshotWarehouse,
+            UserId,
+        },
+    };
+
+    fn state(deleted: bool) -> CatalogSnapshotState {
+        CatalogSnapshotState {
+            warehouse: CatalogSnapshotWarehouse {
+                name: "wh".to_string(),
+                storage_profile: serde_json::json!({"type": "s3", "bucket": "b"}),
+                tabular_expiration_seconds: Some(3600),
+                protected: false,
+                disabled_endpoint_groups: vec![],
+            },
+            task_queue_configs: BTreeMap::new(),
+            namespaces: vec![CatalogSnapshotNamespace {
+                namespace_id: Uuid::from_u128(1),
+                name: vec!["ns".to_string()],
+                properties: BTreeMap::new(),
+                protected: false,
+                deleted,
+            }],
+            tabulars: vec![CatalogSnapshotTabular {
+                tabular_id: Uuid::from_u128(2),
+                typ: TabularType::Table,
+                namespace: vec!["ns".to_string()],
+                name: "tab".to_string(),
+                metadata_location: Some("s3://b/ns/tab/metadata/00001.metadata.json".to_string()),
+                protected: false,
+                deleted,
+            }],
+            access: None,
+        }
+    }
+
+    fn grant(object: GrantObject, relation: &str) -> ObjectGrant {
+        ObjectGrant {
+            object,
+            relation: relation.to_string(),
+            principal: GrantPrincipal::User(UserId::new_unchecked("oidc", "alice")),
+        }
+    }
+
+    fn user(name: &str) -> CatalogSnapshotUser {
+        CatalogSnapshotUser {
+            id: UserId::new_unchecked("oidc", "alice"),
+            name: name.to_string(),
+            email: None,
+            user_type: UserType::Human,
+        }
+    }
+
+    #[test]
+    fn test_soft_deleted_entities_are_undropped() {
+        let current = state(true);
+        let target = state(false);
+        let (namespaces, tabulars) = soft_deleted_in_current(&current, &target);
+        assert_eq!(namespaces, vec![&current.namespaces[0]]);
+        assert_eq!(tabulars, vec![&current.tabulars[0]]);
+
+        // Entities that are soft-deleted in the snapshot as well stay soft-deleted
+        let (namespaces, tabulars) = soft_deleted_in_current(&current, &current);
+        assert!(namespaces.is_empty());
+        assert!(tabulars.is_empty());
+
+        // Undropped entities are not re-created
+        let mut undropped = current.clone();
+        mark_undropped(&mut undropped, &target);
+        assert_eq!(undropped, target);
+        assert!(MirrorPlan::with_options(&undropped, &target, RESTORE_OPTIONS).is_empty());
+    }
+
+    #[test]
+    fn test_access_changes() {
+        let kept = grant(GrantObject::Namespace(Uuid::from_u128(1)), "ownership");
+        let removed = grant(GrantObject::Table(Uuid::from_u128(2)), "select");
+        let added = grant(GrantObject::Table(Uuid::from_u128(2)), "modify");
+        let target = CatalogSnapshotAccess {
+            grants: vec![kept.clone(), removed.clone()],
+            users: vec![user("Alice")],
+        };
+        let current = CatalogSnapshotAccess {
+            grants: vec![kept, added.clone()],
+            users: vec![],
+        };
+
+        let changes = AccessChanges::between(&target, &current, &[user("Alice")]);
+        assert_eq!(
+            changes,
+            AccessChanges {
+                users: vec![],
+                grants: vec![removed.clone()],
+                revokes: vec![added.clone()],
+            }
+        );
+        assert_eq!(
+            changes
+                .changes()
+                .into_iter()
+                .map(|c| (c.name, c.action))
+                .collect::<Vec<_>>(),
+            vec![
+                (grant_name(&removed), CatalogSnapshotRestoreAction::Grant),
+                (grant_name(&added), CatalogSnapshotRestoreAction::Revoke),
+            ]
+        );
+
+        let changes = AccessChanges::between(&target, &target, &[user("Bob")]);
+        assert_eq!(
+            changes.users,
+            vec![(user("Alice"), CatalogSnapshotRestoreAction::Update)]
+        );
+        let changes = AccessChanges::between(&target, &target, &[]);
+        assert_eq!(
+            changes.users,
+            vec![(user("Alice"), CatalogSnapshotRestoreAction::Create)]
+        );
+        assert!(changes.grants.is_empty() && changes.revokes.is_empty());
+    }
+}
@@ -0,0 +1,571 @@
+use std::collections::HashSet;
+
+use axum::{response::IntoResponse, Json};
+use base64::Engine as _;
+use chrono::{DateTime, Utc};
+use iceberg_ext::catalog::rest::ErrorModel;
+use rand::RngCore as _;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest as _, Sha256};
+use uuid::Uuid;
+
+use super::default_page_size;
+use crate::{
+    api::{
+        iceberg::v1::{PageToken, PaginationQuery},
+        management::v1::{
+            user::{parse_create_user_request, UserLastUpdatedWith},
+            ApiServer,
+        },
+        ApiContext,
+    },
+    request_metadata::RequestMetadata,
+    service::{
+        authz::{Authorizer, CatalogProjectAction, ProjectMembership},
+        Catalog, Result, RoleId, SecretStore, State, Transaction, UserId,
+    },
+    ProjectId,
+};
+
+/// Default validity of invitations: 7 days
+const DEFAULT_EXPIRES_IN_SECONDS: u64 = 7 * 24 * 60 * 60;
+const MAX_EXPIRES_IN_SECONDS: u64 = 90 * 24 * 60 * 60;
+const MAX_ROLES_PER_INVITATION: usize = 100;
+const TOKEN_BYTES: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, strum_macros::Display, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum InvitationStatus {
+    /// The invitation can be redeemed.
+    Pending,
+    /// A user redeemed the invitation.
+    Redeemed,
+    /// The invitation was revoked before it was redeemed.
+    Revoked,
+    /// The invitation expired before it was redeemed.
+    Expired,
+}
+
+/// Invitation to a project. Users that redeem the invitation are added to
+/// the project with `membership` and assigned to the roles of the invitation.
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ProjectInvitation {
+    pub invitation_id: Uuid,
+    #[schema(value_type=String)]
+    pub project_id: ProjectId,
+    /// Only users with this email address can redeem the invitation
+    pub email: Option<String>,
+    /// Whether the invitation can be redeemed with a link token
+    pub has_token: bool,
+    pub membership: ProjectMembership,
+    /// Roles of the project the user is assigned to
+    #[schema(value_type=Vec<uuid::Uuid>)]
+    pub role_ids: Vec<RoleId>,
+    pub status: InvitationStatus,
+    pub created_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub redeemed_at: Option<DateTime<Utc>>,
+    pub redeemed_by: Option<String>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl InvitationStatus {
+    #[must_use]
+    pub fn of(
+        expires_at: DateTime<Utc>,
+        redeemed_at: Option<DateTime<Utc>>,
+        revoked_at: Option<DateTime<Utc>>,
+    ) -> Self {
+        if redeemed_at.is_some() {
+            InvitationStatus::Redeemed
+        } else if revoked_at.is_some() {
+            InvitationStatus::Revoked
+        } else if expires_at <= Utc::now() {
+            InvitationStatus::Expired
+        } else {
+            InvitationStatus::Pending
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct CreateInvitationRequest {
+    /// Only users with this email address can redeem the invitation.
+    /// The email is taken from the token the user authenticates with.
+    #[serde(default)]
+    pub email: Option<String>,
+    /// Create a link token that can be redeemed by any authenticated user.
+    /// Invitations without an email always get a link token. Default: false
+    #[serde(default)]
+    pub create_token: bool,
+    /// Privilege on the project. Default: describe
+    #[serde(default)]
+    pub membership: ProjectMembership,
+    /// Roles of the project to assign the user to. Default: none
+    #[serde(default)]
+    #[schema(value_type=Vec<uuid::Uuid>)]
+    pub role_ids: Vec<RoleId>,
+    /// Validity of the invitation in seconds, at most 90 days. Default: 7 days
+    #[serde(default = "default_expires_in_seconds")]
+    pub expires_in_seconds: u64,
+}
+
+fn default_expires_in_seconds() -> u64 {
+    DEFAULT_EXPIRES_IN_SECONDS
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct CreateInvitationResponse {
+    pub invitation: ProjectInvitation,
+    /// Link token to redeem the invitation. Only returned once, it cannot be retrieved later.
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct RedeemInvitationRequest {
+    /// Link token of the invitation
+    #[serde(default)]
+    pub token: Option<String>,
+    /// Id of an invitation for the email of the user. Not required if `token` is set.
+    #[serde(default)]
+    pub invitation_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct RedeemInvitationResponse {
+    #[schema(value_type=String)]
+    pub project_id: ProjectId,
+    #[schema(value_type=String)]
+    pub user_id: UserId,
+    pub membership: ProjectMembership,
+    /// Roles the user was assigned to. Roles deleted after the invitation was created are skipped.
+    #[schema(value_type=Vec<uuid::Uuid>)]
+    pub role_ids: Vec<RoleId>,
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct ListInvitationsQuery {
+    /// Next page token
+    #[serde(default)]
+    pub page_token: Option<String>,
+    /// Signals an upper bound of the number of results that a client will receive.
+    /// Default: 100
+    #[serde(default = "default_page_size")]
+    pub page_size: i64,
+}
+
+impl ListInvitationsQuery {
+    #[must_use]
+    pub fn pagination_query(&self) -> PaginationQuery {
+        PaginationQuery {
+            page_token: self
+                .page_token
+                .clone()
+                .map_or(PageToken::Empty, PageToken::Present),
+            page_size: Some(self.page_size),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ListInvitationsResponse {
+    pub invitations: Vec<ProjectInvitation>,
+    pub next_page_token: Option<String>,
+}
+
+impl IntoResponse for ListInvitationsResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl IntoResponse for RedeemInvitationResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// Only the hash of link tokens is stored.
+pub(crate) fn hash_invitation_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+fn generate_invitation_token() -> String {
+    let mut bytes = [0u8; TOKEN_BYTES];
+    rand::rng().fill_bytes(&mut bytes);
+    base64::prelude::BASE64_URL_SAFE_NO_PAD.encode(bytes)
+}
+
+fn validate_create_invitation_request(request: &CreateInvitationRequest) -> Result<()> {
+    if request.email.as_deref().is_some_and(|e| !e.contains('@')) {
+        return Err(ErrorModel::bad_request(
+            "Email of the invitation is not a valid email address",
+            "InvalidInvitationEmail",
+            None,
+        )
+        .into());
+    }
+    if request.expires_in_seconds == 0 || request.expires_in_seconds > MAX_EXPIRES_IN_SECONDS {
+        return Err(ErrorModel::bad_request(
+            format!(
+                "Invitations must expire within 1 and {MAX_EXPIRES_IN_SECONDS} seconds, got {}",
+                request.expires_in_seconds
+            ),
+            "InvalidInvitationExpiry",
+            None,
+        )
+        .into());
+    }
+    if request.role_ids.len() > MAX_ROLES_PER_INVITATION {
+        return Err(ErrorModel::bad_request(
+            format!("Invitations can assign at most {MAX_ROLES_PER_INVITATION} roles"),
+            "TooManyInvitationRoles",
+            None,
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Returns the roles of `role_ids` that exist in the project.
+async fn existing_project_roles<C: Catalog>(
+    project_id: &ProjectId,
+    role_ids: &[RoleId],
+    catalog_state: C::State,
+) -> Result<Vec<RoleId>> {
+    if role_ids.is_empty() {
+        return Ok(vec![]);
+    }
+    let found = C::list_roles(
+        Some(project_id.clone()),
+        Some(role_ids.to_vec()),
+        None,
+        None,
+        PaginationQuery {
+            page_token: PageToken::Empty,
+            page_size: Some(i64::try_from(MAX_ROLES_PER_INVITATION).unwrap_or(i64::MAX)),
+        },
+        catalog_state,
+    )
+    .await?
+    .roles
+    .into_iter()
+    .map(|r| r.id)
+    .collect::<HashSet<_>>();
+    Ok(role_ids
+        .iter()
+        .filter(|id| found.contains(id))
+        .copied()
+        .collect())
+}
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
+
+#[async_trait::async_trait]
+pub(crate) trait Service<C: Catalog, A: Authorizer + Clone, S: SecretStore> {
+    async fn create_invitation(
+        mut request: CreateInvitationRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<CreateInvitationResponse> {
+        // ------------------- Validations -------------------
+        request.email = request
+            .email
+            .map(|e| e.trim().to_string())
+            .filter(|e| !e.is_empty());
+        validate_create_invitation_request(&request)?;
+        let project_id = request_metadata.require_project_id(None)?;
+
+        // ------------------- AuthZ -------------------
+        context
+            .v1_state
+            .authz
+            .require_project_action(
+                &request_metadata,
+                &project_id,
+                CatalogProjectAction::CanManageInvitations,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut seen = HashSet::new();
+        request.role_ids.retain(|id| seen.insert(*id));
+        let existing = existing_project_roles::<C>(
+            &project_id,
+            &request.role_ids,
+            context.v1_state.catalog.clone(),
+        )
+        .await?;
+        if let Some(missing) = request.role_ids.iter().find(|id| !existing.contains(id)) {
+            return Err(ErrorModel::not_found(
+                format!("Role {missing} does not exist in project {project_id}"),
+                "RoleNotFound",
+                None,
+            )
+            .into());
+        }
+
+        let token =
+            (request.email.is_none() || request.create_token).then(generate_invitation_token);
+        let token_hash = token.as_deref().map(hash_invitation_token);
+        let expires_at = Utc::now()
+            + chrono::Duration::seconds(
+                i64::try_from(request.expires_in_seconds).unwrap_or(i64::MAX),
+            );
+        let created_by = request_metadata.user_id().map(ToString::to_string);
+
+        let mut t = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let invitation = C::create_project_invitation(
+            Uuid::now_v7(),
+            &project_id,
+            &request,
+            token_hash.as_deref(),
+            expires_at,
+            created_by.as_deref(),
+            t.transaction(),
+        )
+        .await?;
+        t.commit().await?;
+
+        Ok(CreateInvitationResponse { invitation, token })
+    }
+
+    async fn list_invitations(
+        query: ListInvitationsQuery,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<ListInvitationsResponse> {
+        let project_id = request_metadata.require_project_id(None)?;
+
+        // ------------------- AuthZ -------------------
+        context
+            .v1_state
+            .authz
+            .require_project_action(
+                &request_metadata,
+                &project_id,
+                CatalogProjectAction::CanManageInvitations,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        C::list_project_invitations(
+            &project_id,
+            query.pagination_query(),
+            context.v1_state.catalog,
+        )
+        .await
+    }
+
+    async fn revoke_invitation(
+        invitation_id: Uuid,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        let mut t = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let invitation = C::lock_project_invitation(Some(invitation_id), None, t.transaction())
+            .await?
+            .ok_or_else(|| invitation_not_found(invitation_id))?;
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        let can_manage = authorizer
+            .is_allowed_project_action(
+                &request_metadata,
+                &invitation.project_id,
+                CatalogProjectAction::CanManageInvitations,
+            )
+            .await?;
+        if !can_manage {
+            // Don't reveal invitations of other projects
+            return Err(invitation_not_found(invitation_id).into());
+        }
+
+        // ------------------- Business Logic -------------------
+        if invitation.status == InvitationStatus::Redeemed {
+            return Err(ErrorModel::conflict(
+                format!("Invitation {invitation_id} was already redeemed"),
+                "InvitationAlreadyRedeemed",
+                None,
+            )
+            .into());
+        }
+        C::revoke_project_invitation(invitation_id, t.transaction()).await?;
+        t.commit().await?;
+
+        Ok(())
+    }
+
+    /// Redeem an invitation as the authenticated user. The user is created if it
+    /// does not exist yet, added to the project and assigned to the roles of the invitation.
+    async fn redeem_invitation(
+        request: RedeemInvitationRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<RedeemInvitationResponse> {
+        // ------------------- Validations -------------------
+        let token_hash = request.token.as_deref().map(hash_invitation_token);
+        if token_hash.is_none() && request.invitation_id.is_none() {
+            return Err(ErrorModel::bad_request(
+                "Either `token` or `invitation-id` is required to redeem an invitation",
+                "MissingInvitationReference",
+                None,
+            )
+            .into());
+        }
+        let Some(user_id) = request_metadata.user_id().cloned() else {
+            return Err(ErrorModel::unauthorized(
+                "Redeeming an invitation requires authentication",
+                "AuthenticationRequired",
+                None,
+            )
+            .into());
+        };
+
+        // ------------------- AuthZ -------------------
+        // Possession of the link token or the invited email authorizes the redemption.
+        let mut t = C::Transaction::begin_write(context.v1_state.catalog.clone()).await?;
+        let invitation = C::lock_project_invitation(
+            request.invitation_id,
+            token_hash.as_deref(),
+            t.transaction(),
+        )
+        .await?
+        .ok_or_else(|| {
+            ErrorModel::not_found(
+                "Invitation does not exist or the token is invalid",
+                "InvitationNotFound",
+                None,
+            )
+        })?;
+        if let Some(invited_email) = &invitation.email {
+            let email = request_metadata.authentication().and_then(|a| a.email());
+            if !email.is_some_and(|e| e.eq_ignore_ascii_case(invited_email)) {
+                return Err(ErrorModel::forbidden(
+                    "The invitation was issued for a different email address",
+                    "InvitationEmailMismatch",
+                    None,
+                )
+                .into());
+            }
+        } else if token_hash.is_none() {
+            return Err(ErrorModel::forbidden(
+                "The invitation can only be redeemed with its link token",
+                "InvitationTokenRequired",
+                None,
+            )
+            .into());
+        }
+
+        // ------------------- Business Logic -------------------
+        if invitation.status != InvitationStatus::Pending {
+            return Err(ErrorModel::conflict(
+                format!(
+                    "Invitation {} cannot be redeemed, it is {}",
+                    invitation.invitation_id, invitation.status
+                ),
+                "InvitationNotPending",
+                None,
+            )
+            .into());
+        }
+
+        let (creation_user_id, name, user_type, email) =
+            parse_create_user_request(&request_metadata, None)?;
+        C::create_or_update_user(
+            &creation_user_id,
+            &name,
+            email.as_deref(),
+            UserLastUpdatedWith::CreateEndpoint,
+            user_type,
+            t.transaction(),
+        )
+        .await?;
+        C::mark_project_invitation_redeemed(
+            invitation.invitation_id,
+            user_id.to_string().as_str(),
+            t.transaction(),
+        )
+        .await?;
+
+        let role_ids = existing_project_roles::<C>(
+            &invitation.project_id,
+            &invitation.role_ids,
+            context.v1_state.catalog,
+        )
+        .await?;
+        // The invitation is only marked as redeemed if the authorizer succeeds.
+        context
+            .v1_state
+            .authz
+            .add_project_member(
+                &invitation.project_id,
+                &user_id,
+                invitation.membership,
+                &role_ids,
+            )
+            .await?;
+        t.commit().await?;
+
+        Ok(RedeemInvitationResponse {
+            project_id: invitation.project_id,
+            user_id,
+            membership: invitation.membership,
+            role_ids,
+        })
+    }
+}
+
+fn invitation_not_found(invitation_id: Uuid) -> ErrorModel {
+    ErrorModel::not_found(
+        format!("Invitation {invitation_id} does not exist"),
+        "InvitationNotFound",
+        None,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_invitation_token_hash() {
+        let token = generate_invitation_token();
+        assert_eq!(token.len(), 43);
+        assert_ne!(token, generate_invitation_token());
+        assert_eq!(hash_invitation_token(&token), hash_invitation_token(&token));
+        assert_ne!(hash_invitation_token(&token), token);
+        assert_eq!(hash_invitation_token(&token).len(), 64);
+    }
+
+    #[test]
+    fn test_invitation_status() {
+        let future = Utc::now() + chrono::Duration::days(1);
+        let past = Utc::now() - chrono::Duration::days(1);
+        assert_eq!(
+            InvitationStatus::of(future, None, None),
+            InvitationStatus::Pending
+        );
+        assert_eq!(
+            InvitationStatus::of(past, None, None),
+            InvitationStatus::Expired
+        );
+        assert_eq!(
+            InvitationStatus::of(past, Some(past), None),
+            InvitationStatus::Redeemed
+        );
+        assert_eq!(
+            InvitationStatus::of(future, None, Some(past)),
+            InvitationStatus::Revoked
+        );
+    }
+}
@@ -0,0 +1,318 @@
+//! Labels of warehouses, namespaces and tables, and the label settings of projects.
+//!
+//! See [`crate::service::labels`] for how labels are validated and used in label policies.
+use axum::{response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::{management::v1::ApiServer, ApiContext, ErrorModel, IcebergErrorResponse},
+    request_metadata::RequestMetadata,
+    service::{
+        authz::{
+            Authorizer, CatalogNamespaceAction, CatalogProjectAction, CatalogTableAction,
+            CatalogWarehouseAction,
+        },
+        labels::{
+            changes_policy_labels, invalidate_label_policies, validate_label_settings,
+            validate_labels, LabelEntity, LabelSettings, Labels,
+        },
+        Catalog, NamespaceId, ProjectId, Result, SecretStore, State, TableId, Transaction,
+    },
+    WarehouseId,
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct GetLabelsResponse {
+    /// Labels set on the entity itself. Labels inherited from the warehouse or
+    /// parent namespaces are not included.
+    pub labels: Labels,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct SetLabelsRequest {
+    /// Labels of the entity. Replaces all existing labels.
+    pub labels: Labels,
+}
+
+impl IntoResponse for GetLabelsResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl IntoResponse for LabelSettings {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
+
+#[async_trait::async_trait]
+pub(crate) trait Service<C: Catalog, A: Authorizer + Clone, S: SecretStore> {
+    async fn get_label_settings(
+        project_id: ProjectId,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<LabelSettings> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_project_action(
+                &request_metadata,
+                &project_id,
+                CatalogProjectAction::CanGetMetadata,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut t = C::Transaction::begin_read(context.v1_state.catalog).await?;
+        let settings = C::get_label_settings(&project_id, t.transaction())
+            .await?
+            .unwrap_or_default();
+        t.commit().await?;
+
+        Ok(settings)
+    }
+
+    async fn set_label_settings(
+        project_id: ProjectId,
+        request: LabelSettings,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<LabelSettings> {
+        // ------------------- VALIDATIONS -------------------
+        validate_label_settings(&request)?;
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_project_action(
+                &request_metadata,
+                &project_id,
+                CatalogProjectAction::CanManageLabels,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut t = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::set_label_settings(&project_id, &request, t.transaction()).await?;
+        t.commit().await?;
+        invalidate_label_policies().await;
+
+        Ok(request)
+    }
+
+    async fn get_warehouse_labels(
+        warehouse_id: WarehouseId,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<GetLabelsResponse> {
+        // ------------------- AuthZ -------------------
+        context
+            .v1_state
+            .authz
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanGetMetadata,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        get_labels::<A, C, S>(warehouse_id, LabelEntity::Warehouse, context).await
+    }
+
+    async fn set_warehouse_labels(
+        warehouse_id: WarehouseId,
+        request: SetLabelsRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<GetLabelsResponse> {
+        // ------------------- AuthZ -------------------
+        context
+            .v1_state
+            .authz
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanUpdateStorage,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        set_labels::<A, C, S>(
+            warehouse_id,
+            LabelEntity::Warehouse,
+            request.labels,
+            context,
+            request_metadata,
+        )
+        .await
+    }
+
+    async fn get_namespace_labels(
+        warehouse_id: WarehouseId,
+        namespace_id: NamespaceId,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<GetLabelsResponse> {
+        // ------------------- AuthZ -------------------
+        context
+            .v1_state
+            .authz
+            .require_namespace_action(
+                &request_metadata,
+                Ok(Some(namespace_id)),
+                CatalogNamespaceAction::CanGetMetadata,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        get_labels::<A, C, S>(warehouse_id, LabelEntity::Namespace(namespace_id), context).await
+    }
+
+    async fn set_namespace_labels(
+        warehouse_id: WarehouseId,
+        namespace_id: NamespaceId,
+        request: SetLabelsRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<GetLabelsResponse> {
+        // ------------------- AuthZ -------------------
+        context
+            .v1_state
+            .authz
+            .require_namespace_action(
+                &request_metadata,
+                Ok(Some(namespace_id)),
+                CatalogNamespaceAction::CanUpdateProperties,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        set_labels::<A, C, S>(
+            warehouse_id,
+            LabelEntity::Namespace(namespace_id),
+            request.labels,
+            context,
+            request_metadata,
+        )
+        .await
+    }
+
+    async fn get_table_labels(
+        warehouse_id: WarehouseId,
+        table_id: TableId,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<GetLabelsResponse> {
+        // ------------------- AuthZ -------------------
+        context
+            .v1_state
+            .authz
+            .require_table_action(
+                &request_metadata,
+                Ok(Some(table_id)),
+                CatalogTableAction::CanGetMetadata,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        get_labels::<A, C, S>(warehouse_id, LabelEntity::Table(table_id), context).await
+    }
+
+    async fn set_table_labels(
+        warehouse_id: WarehouseId,
+        table_id: TableId,
+        request: SetLabelsRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<GetLabelsResponse> {
+        // ------------------- AuthZ -------------------
+        context
+            .v1_state
+            .authz
+            .require_table_action(
+                &request_metadata,
+                Ok(Some(table_id)),
+                CatalogTableAction::CanCommit,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        set_labels::<A, C, S>(
+            warehouse_id,
+            LabelEntity::Table(table_id),
+            request.labels,
+            context,
+            request_metadata,
+        )
+        .await
+    }
+}
+
+async fn get_labels<A: Authorizer, C: Catalog, S: SecretStore>(
+    warehouse_id: WarehouseId,
+    entity: LabelEntity,
+    context: ApiContext<State<A, C, S>>,
+) -> Result<GetLabelsResponse> {
+    let mut t = C::Transaction::begin_read(context.v1_state.catalog).await?;
+    let labels = C::get_labels(warehouse_id, entity, t.transaction()).await?;
+    t.commit().await?;
+
+    labels
+        .map(|labels| GetLabelsResponse { labels })
+        .ok_or_else(|| not_found(entity))
+}
+
+/// Replace the labels of an entity after validating them against the label definitions
+/// of the project. Changing labels that are covered by a label policy alters who can
+/// access data, so it additionally requires the permission to manage labels of the project.
+async fn set_labels<A: Authorizer, C: Catalog, S: SecretStore>(
+    warehouse_id: WarehouseId,
+    entity: LabelEntity,
+    labels: Labels,
+    context: ApiContext<State<A, C, S>>,
+    request_metadata: RequestMetadata,
+) -> Result<GetLabelsResponse> {
+    let mut t = C::Transaction::begin_write(context.v1_state.catalog).await?;
+    let project_id = C::require_warehouse(warehouse_id, t.transaction())
+        .await?
+        .project_id;
+    let settings = C::get_label_settings(&project_id, t.transaction())
+        .await?
+        .unwrap_or_default();
+    validate_labels(&settings, entity.entity_type(), &labels)?;
+
+    let previous = C::get_labels(warehouse_id, entity, t.transaction())
+        .await?
+        .ok_or_else(|| not_found(entity))?;
+    if changes_policy_labels(&settings.policies, &previous, &labels) {
+        context
+            .v1_state
+            .authz
+            .require_project_action(
+                &request_metadata,
+                &project_id,
+                CatalogProjectAction::CanManageLabels,
+            )
+            .await?;
+    }
+
+    C::set_labels(warehouse_id, entity, &labels, t.transaction()).await?;
+    t.commit().await?;
+
+    Ok(GetLabelsResponse { labels })
+}
+
+fn not_found(entity: LabelEntity) -> IcebergErrorResponse {
+    let (message, r#type) = match entity {
+        LabelEntity::Warehouse => ("Warehouse not found", "WarehouseNotFound"),
+        LabelEntity::Namespace(_) => ("Namespace not found", "NamespaceNotFound"),
+        LabelEntity::Table(_) => ("Table not found", "TableNotFound"),
+    };
+    ErrorModel::not_found(message, r#type, None).into()
+}
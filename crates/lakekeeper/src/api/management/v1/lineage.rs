@@ -0,0 +1,134 @@
+use std::collections::{BTreeSet, HashMap};
+
+use axum::{response::IntoResponse, Json};
+use serde::Serialize;
+
+use crate::{
+    api::{management::v1::ApiServer, ApiContext},
+    request_metadata::RequestMetadata,
+    service::{
+        authz::{Authorizer, CatalogTableAction},
+        table_lineage::{LineageRunEvent, TableLineage},
+        Catalog, ListFlags, Result, SecretStore, State, TableId, Transaction,
+    },
+    WarehouseId,
+};
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct IngestLineageEventResponse {
+    /// Number of recorded input-output pairs. Events other than `COMPLETE`
+    /// and pairs without a table of this catalog are not recorded.
+    pub recorded_edges: usize,
+}
+
+impl IntoResponse for IngestLineageEventResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl IntoResponse for TableLineage {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
+
+#[async_trait::async_trait]
+pub(crate) trait Service<C: Catalog, A: Authorizer + Clone, S: SecretStore> {
+    async fn ingest_lineage_event(
+        event: LineageRunEvent,
+        state: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<IngestLineageEventResponse> {
+        if !event.is_complete() {
+            return Ok(IngestLineageEventResponse { recorded_edges: 0 });
+        }
+
+        let locations = event
+            .inputs
+            .iter()
+            .chain(&event.outputs)
+            .filter_map(|dataset| dataset.location())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        let tables = if locations.is_empty() {
+            HashMap::new()
+        } else {
+            C::resolve_table_locations(&locations, state.v1_state.catalog.clone()).await?
+        };
+        let edges = event.edges(|dataset| {
+            dataset
+                .location()
+                .and_then(|location| tables.get(&location).copied())
+        });
+
+        // ------------------- AUTHZ -------------------
+        // Reporting lineage requires the permissions the job needed to read its inputs
+        // and to write its outputs.
+        let authorizer = state.v1_state.authz;
+        let upstream = edges
+            .iter()
+            .filter_map(|edge| edge.upstream_table.map(|t| t.table_id))
+            .collect::<BTreeSet<_>>();
+        let downstream = edges
+            .iter()
+            .filter_map(|edge| edge.downstream_table.map(|t| t.table_id))
+            .collect::<BTreeSet<_>>();
+        for table_id in upstream {
+            authorizer
+                .require_table_action(
+                    &request_metadata,
+                    Ok(Some(table_id)),
+                    CatalogTableAction::CanReadData,
+                )
+                .await?;
+        }
+        for table_id in downstream {
+            authorizer
+                .require_table_action(
+                    &request_metadata,
+                    Ok(Some(table_id)),
+                    CatalogTableAction::CanWriteData,
+                )
+                .await?;
+        }
+
+        // ------------------- BUSINESS LOGIC -------------------
+        if !edges.is_empty() {
+            let mut t = C::Transaction::begin_write(state.v1_state.catalog).await?;
+            C::record_table_lineage(&edges, t.transaction()).await?;
+            t.commit().await?;
+        }
+
+        Ok(IngestLineageEventResponse {
+            recorded_edges: edges.len(),
+        })
+    }
+
+    async fn get_table_lineage(
+        table_id: TableId,
+        warehouse_id: WarehouseId,
+        state: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<TableLineage> {
+        // ------------------- AUTHZ -------------------
+        let authorizer = state.v1_state.authz.clone();
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            state.v1_state.catalog.clone(),
+        )
+        .await;
+        authorizer
+            .require_table_action(&request_metadata, table, CatalogTableAction::CanGetMetadata)
+            .await?;
+
+        // ------------------- BUSINESS LOGIC -------------------
+        C::get_table_lineage(warehouse_id, table_id, state.v1_state.catalog).await
+    }
+}
@@ -0,0 +1,166 @@
+use axum::{response::IntoResponse, Json};
+use serde::Deserialize;
+
+use crate::{
+    api::{management::v1::ApiServer, ApiContext},
+    request_metadata::RequestMetadata,
+    service::{
+        authz::{Authorizer, CatalogServerAction},
+        log_level::{
+            add_trace_target, log_level_status, reset_log_levels, set_log_level, LogLevel,
+            LogLevelStatus,
+        },
+        server_events::{emit_server_event, ServerEvent, ServerEventType},
+        Catalog, Result, SecretStore, State,
+    },
+    WarehouseId,
+};
+
+/// Duration of trace targets if not specified.
+const DEFAULT_TRACE_TARGET_DURATION_SECONDS: u64 = 10 * 60;
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct SetLogLevelRequest {
+    /// Module path to change the level of, such as `lakekeeper::implementations::postgres`.
+    /// Changes the default level if not set.
+    #[serde(default)]
+    pub target: Option<String>,
+    pub level: LogLevel,
+    /// Seconds after which the change is reverted. Kept until restart if not set.
+    #[serde(default)]
+    pub duration_seconds: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct AddTraceTargetRequest {
+    /// Trace requests of this principal, such as `oidc~<subject>`
+    #[serde(default)]
+    pub principal: Option<String>,
+    /// Trace requests to this warehouse
+    #[serde(default)]
+    #[schema(value_type = Option<uuid::Uuid>)]
+    pub warehouse_id: Option<WarehouseId>,
+    /// Level of events logged for matching requests. Defaults to `debug`.
+    #[serde(default = "default_trace_level")]
+    pub level: LogLevel,
+    /// Seconds the target is active. Defaults to 600.
+    #[serde(default = "default_trace_target_duration")]
+    pub duration_seconds: u64,
+}
+
+fn default_trace_level() -> LogLevel {
+    LogLevel::Debug
+}
+
+fn default_trace_target_duration() -> u64 {
+    DEFAULT_TRACE_TARGET_DURATION_SECONDS
+}
+
+impl IntoResponse for LogLevelStatus {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
+
+#[async_trait::async_trait]
+pub(crate) trait Service<C: Catalog, A: Authorizer + Clone, S: SecretStore> {
+    async fn get_log_levels(
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<LogLevelStatus> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_server_action(&request_metadata, CatalogServerAction::CanConfigureLogging)
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        Ok(log_level_status())
+    }
+
+    async fn set_log_level(
+        request: SetLogLevelRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<LogLevelStatus> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_server_action(&request_metadata, CatalogServerAction::CanConfigureLogging)
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let SetLogLevelRequest {
+            target,
+            level,
+            duration_seconds,
+        } = request;
+        let status = set_log_level(target.clone(), level, duration_seconds)?;
+        emit_server_event(ServerEvent::new(
+            ServerEventType::SettingsChanged,
+            request_metadata.user_id(),
+            serde_json::json!({ "log-level": {
+                "target": target,
+                "level": level,
+                "duration-seconds": duration_seconds,
+            }}),
+        ));
+        Ok(status)
+    }
+
+    async fn add_trace_target(
+        request: AddTraceTargetRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<LogLevelStatus> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_server_action(&request_metadata, CatalogServerAction::CanConfigureLogging)
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let AddTraceTargetRequest {
+            principal,
+            warehouse_id,
+            level,
+            duration_seconds,
+        } = request;
+        let status = add_trace_target(principal.clone(), warehouse_id, level, duration_seconds)?;
+        emit_server_event(ServerEvent::new(
+            ServerEventType::SettingsChanged,
+            request_metadata.user_id(),
+            serde_json::json!({ "trace-target": {
+                "principal": principal,
+                "warehouse-id": warehouse_id.map(|id| *id),
+                "level": level,
+                "duration-seconds": duration_seconds,
+            }}),
+        ));
+        Ok(status)
+    }
+
+    async fn reset_log_levels(
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<LogLevelStatus> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_server_action(&request_metadata, CatalogServerAction::CanConfigureLogging)
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let status = reset_log_levels()?;
+        emit_server_event(ServerEvent::new(
+            ServerEventType::SettingsChanged,
+            request_metadata.user_id(),
+            serde_json::json!({ "log-level": "reset" }),
+        ));
+        Ok(status)
+    }
+}
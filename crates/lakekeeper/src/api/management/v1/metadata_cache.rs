@@ -0,0 +1,36 @@
+use crate::{
+    api::{management::v1::ApiServer, ApiContext},
+    catalog::metadata_cache,
+    request_metadata::RequestMetadata,
+    service::{
+        authz::{Authorizer, CatalogServerAction},
+        Catalog, Result, SecretStore, State,
+    },
+};
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
+
+#[async_trait::async_trait]
+pub(crate) trait Service<C: Catalog, A: Authorizer + Clone, S: SecretStore> {
+    async fn flush_metadata_cache(
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_server_action(
+                &request_metadata,
+                CatalogServerAction::CanFlushMetadataCache,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        metadata_cache::flush();
+        tracing::info!(
+            principal = ?request_metadata.user_id(),
+            "Flushed table metadata cache"
+        );
+        Ok(())
+    }
+}
@@ -2,7 +2,8 @@ use super::{ApiServer, ProtectionResponse};
 use crate::{
     api::{ApiContext, RequestMetadata, Result},
     service::{
-        authz::{Authorizer, CatalogNamespaceAction},
+        authz::{Authorizer, CatalogNamespaceAction, CatalogWarehouseAction},
+        task_queue::TaskFilter,
         Catalog, NamespaceId, SecretStore, State, Transaction,
     },
     WarehouseId,
@@ -66,4 +67,36 @@ where
         t.commit().await?;
         Ok(status)
     }
+
+    async fn undrop_namespace(
+        namespace_id: NamespaceId,
+        warehouse_id: WarehouseId,
+        state: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        //  ------------------- AUTHZ -------------------
+        let authorizer = state.v1_state.authz.clone();
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanUse,
+            )
+            .await?;
+        authorizer
+            .require_namespace_action(
+                &request_metadata,
+                Ok(Some(namespace_id)),
+                CatalogNamespaceAction::CanDelete,
+            )
+            .await?;
+
+        //  ------------------- BUSINESS LOGIC -------------------
+        let mut t = C::Transaction::begin_write(state.v1_state.catalog.clone()).await?;
+        let task_ids = C::undrop_namespace(warehouse_id, namespace_id, t.transaction()).await?;
+        C::cancel_tabular_expiration(TaskFilter::TaskIds(task_ids), t.transaction()).await?;
+        t.commit().await?;
+        tracing::debug!("Undropped namespace {namespace_id}");
+        Ok(())
+    }
 }
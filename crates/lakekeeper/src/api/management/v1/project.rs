@@ -5,6 +5,8 @@ use utoipa::ToSchema;
 use uuid::Uuid;
 
 pub use crate::service::{
+    property_schemas::{PropertyEntityType, PropertySchema, PropertyType},
+    quotas::{ProjectQuotaKind, ProjectQuotaUsage, ProjectQuotaWarning, ProjectQuotas},
     storage::{
         AdlsProfile, AzCredential, GcsCredential, GcsProfile, GcsServiceKey, S3Credential,
         S3Profile, StorageCredential, StorageProfile,
@@ -19,6 +21,8 @@ use crate::{
             Authorizer, CatalogProjectAction, CatalogServerAction, CatalogWarehouseAction,
             ListProjectsResponse as AuthZListProjectsResponse,
         },
+        property_schemas::{invalidate_property_schemas, validate_property_schemas},
+        quotas::{invalidate_project_quotas, validate_project_quotas},
         secrets::SecretStore,
         Catalog, State, Transaction,
     },
@@ -68,6 +72,38 @@ pub struct CreateProjectResponse {
     pub project_id: ProjectId,
 }
 
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct GetProjectQuotasResponse {
+    /// Quotas of the project
+    pub quotas: ProjectQuotas,
+    /// Current usage of the quotas
+    pub usage: ProjectQuotaUsage,
+    /// Quotas whose usage reached the warning threshold.
+    /// Updated periodically, so changes of usage or quotas are reflected with a delay.
+    pub warnings: Vec<ProjectQuotaWarning>,
+}
+
+impl axum::response::IntoResponse for GetProjectQuotasResponse {
+    fn into_response(self) -> axum::http::Response<axum::body::Body> {
+        (http::StatusCode::OK, axum::Json(self)).into_response()
+    }
+}
+
+/// Custom properties of namespaces and tables declared by a project.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ProjectPropertySchemas {
+    /// Declared properties. Values of undeclared properties are not restricted.
+    pub properties: Vec<PropertySchema>,
+}
+
+impl axum::response::IntoResponse for ProjectPropertySchemas {
+    fn into_response(self) -> axum::http::Response<axum::body::Body> {
+        (http::StatusCode::OK, axum::Json(self)).into_response()
+    }
+}
+
 impl axum::response::IntoResponse for CreateProjectResponse {
     fn into_response(self) -> axum::http::Response<axum::body::Body> {
         (http::StatusCode::CREATED, axum::Json(self)).into_response()
@@ -172,6 +208,130 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
         })
     }
 
+    async fn get_project_quotas(
+        project_id: ProjectId,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<GetProjectQuotasResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_project_action(
+                &request_metadata,
+                &project_id,
+                CatalogProjectAction::CanGetMetadata,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut t = C::Transaction::begin_read(context.v1_state.catalog).await?;
+        let quotas = C::get_project_quotas(&project_id, t.transaction())
+            .await?
+            .ok_or(ErrorModel::not_found(
+                format!("Project with id {project_id} not found."),
+                "ProjectNotFound",
+                None,
+            ))?;
+        let usage = C::get_project_quota_usage(&project_id, t.transaction()).await?;
+        let warnings = C::list_project_quota_warnings(&project_id, t.transaction()).await?;
+        t.commit().await?;
+
+        Ok(GetProjectQuotasResponse {
+            quotas,
+            usage,
+            warnings,
+        })
+    }
+
+    async fn set_project_quotas(
+        project_id: ProjectId,
+        request: ProjectQuotas,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<GetProjectQuotasResponse> {
+        // ------------------- AuthZ -------------------
+        // Project admins must not be able to lift the limits of their own project.
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_server_action(
+                &request_metadata,
+                CatalogServerAction::CanManageProjectQuotas,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        validate_project_quotas(&request)?;
+        let mut t = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::set_project_quotas(&project_id, &request, t.transaction()).await?;
+        let usage = C::get_project_quota_usage(&project_id, t.transaction()).await?;
+        let warnings = C::list_project_quota_warnings(&project_id, t.transaction()).await?;
+        t.commit().await?;
+        invalidate_project_quotas(&project_id).await;
+
+        Ok(GetProjectQuotasResponse {
+            quotas: request,
+            usage,
+            warnings,
+        })
+    }
+
+    async fn get_property_schemas(
+        project_id: ProjectId,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<ProjectPropertySchemas> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_project_action(
+                &request_metadata,
+                &project_id,
+                CatalogProjectAction::CanGetMetadata,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut t = C::Transaction::begin_read(context.v1_state.catalog).await?;
+        let properties = C::get_property_schemas(&project_id, t.transaction())
+            .await?
+            .ok_or(ErrorModel::not_found(
+                format!("Project with id {project_id} not found."),
+                "ProjectNotFound",
+                None,
+            ))?;
+        t.commit().await?;
+
+        Ok(ProjectPropertySchemas { properties })
+    }
+
+    async fn set_property_schemas(
+        project_id: ProjectId,
+        request: ProjectPropertySchemas,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<ProjectPropertySchemas> {
+        // ------------------- VALIDATIONS -------------------
+        validate_property_schemas(&request.properties)?;
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_project_action(
+                &request_metadata,
+                &project_id,
+                CatalogProjectAction::CanManagePropertySchemas,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut t = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::set_property_schemas(&project_id, &request.properties, t.transaction()).await?;
+        t.commit().await?;
+        invalidate_property_schemas(&project_id).await;
+
+        Ok(request)
+    }
+
     async fn delete_project(
         project_id: Option<ProjectId>,
         context: ApiContext<State<A, C, S>>,
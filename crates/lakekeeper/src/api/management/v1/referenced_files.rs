@@ -0,0 +1,142 @@
+//! Files referenced by the tables and views of a warehouse.
+//!
+//! External garbage-collection and backup tools use this listing to determine which metadata
+//! files, manifest lists and statistics files of a warehouse are still in use, without loading
+//! every table individually. Data files and manifests are not listed, as they are only
+//! referenced through manifest lists.
+use axum::{response::IntoResponse, Json};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::default_page_size;
+use crate::{
+    api::{
+        iceberg::v1::{PageToken, PaginationQuery},
+        management::v1::{ApiServer, TabularType},
+        ApiContext,
+    },
+    request_metadata::RequestMetadata,
+    service::{
+        authz::{Authorizer, CatalogWarehouseAction},
+        Catalog, Result, SecretStore, State, Transaction,
+    },
+    WarehouseId,
+};
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct ListReferencedFilesQuery {
+    /// Next page token
+    #[serde(default)]
+    pub page_token: Option<String>,
+    /// Signals an upper bound of the number of tables and views that a client will receive.
+    /// All files of a table or view are returned on the same page.
+    /// Default: 100
+    #[serde(default = "default_page_size")]
+    pub page_size: i64,
+}
+
+impl ListReferencedFilesQuery {
+    #[must_use]
+    pub fn pagination_query(&self) -> PaginationQuery {
+        PaginationQuery {
+            page_token: self
+                .page_token
+                .clone()
+                .map_or(PageToken::Empty, PageToken::Present),
+            page_size: Some(self.page_size),
+        }
+    }
+}
+
+/// Role of a referenced file in its table or view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReferencedFileKind {
+    /// Current metadata file of the table or view
+    Metadata,
+    /// Metadata file listed in the metadata log of the table
+    PreviousMetadata,
+    /// Manifest list of a snapshot of the table
+    ManifestList,
+    /// Puffin statistics file of a snapshot of the table
+    Statistics,
+    /// Partition statistics file of a snapshot of the table
+    PartitionStatistics,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ReferencedFile {
+    pub kind: ReferencedFileKind,
+    pub location: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TabularReferencedFiles {
+    pub tabular_id: Uuid,
+    pub typ: TabularType,
+    pub namespace: Vec<String>,
+    pub name: String,
+    /// Base location of the table or view
+    pub location: String,
+    /// Whether the table or view is soft-deleted. Its files remain referenced until
+    /// it is purged.
+    pub deleted: bool,
+    pub files: Vec<ReferencedFile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ListReferencedFilesResponse {
+    /// Start of the listing, the same on all pages. Each page is read consistently, but
+    /// tables may be committed to between pages. Only treat files that are older than this
+    /// time and not listed on any page as unreferenced.
+    pub listing_started_at: DateTime<Utc>,
+    /// Tables and views ordered by id, including soft-deleted ones.
+    /// Staged tables have no files and are not listed.
+    pub tabulars: Vec<TabularReferencedFiles>,
+    pub next_page_token: Option<String>,
+}
+
+impl IntoResponse for ListReferencedFilesResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
+
+#[async_trait::async_trait]
+pub(crate) trait Service<C: Catalog, A: Authorizer + Clone, S: SecretStore> {
+    async fn list_referenced_files(
+        warehouse_id: WarehouseId,
+        query: ListReferencedFilesQuery,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<ListReferencedFilesResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanListEverything,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_read(context.v1_state.catalog).await?;
+        let response = C::list_referenced_files(
+            warehouse_id,
+            query.pagination_query(),
+            transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await?;
+
+        Ok(response)
+    }
+}
@@ -0,0 +1,65 @@
+use axum::{response::IntoResponse, Json};
+use iceberg_ext::catalog::rest::ErrorModel;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::{management::v1::ApiServer, ApiContext},
+    request_metadata::RequestMetadata,
+    service::{
+        authz::{Authorizer, CatalogWarehouseAction},
+        task_queue::catalog_snapshot_export_queue::CatalogSnapshotState,
+        Catalog, Result, SecretStore, State,
+    },
+    WarehouseId,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ReplicationStateResponse {
+    pub warehouse_id: uuid::Uuid,
+    /// Current state of the warehouse, including soft-deleted namespaces, tables and views
+    pub state: CatalogSnapshotState,
+}
+
+impl IntoResponse for ReplicationStateResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
+
+#[async_trait::async_trait]
+pub(crate) trait Service<C: Catalog, A: Authorizer + Clone, S: SecretStore> {
+    async fn get_replication_state(
+        warehouse_id: WarehouseId,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<ReplicationStateResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanListEverything,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let state = C::load_catalog_snapshot_state(warehouse_id, context.v1_state.catalog)
+            .await?
+            .ok_or_else(|| {
+                ErrorModel::not_found(
+                    format!("Warehouse {warehouse_id} not found"),
+                    "WarehouseNotFound",
+                    None,
+                )
+            })?;
+
+        Ok(ReplicationStateResponse {
+            warehouse_id: *warehouse_id,
+            state,
+        })
+    }
+}
@@ -0,0 +1,100 @@
+use axum::{response::IntoResponse, Json};
+use iceberg_ext::catalog::rest::ErrorModel;
+
+use crate::{
+    api::{management::v1::ApiServer, ApiContext},
+    request_metadata::RequestMetadata,
+    service::{
+        authz::{Authorizer, CatalogServerAction},
+        request_log::{invalidate_request_log_settings, RequestLogSettings},
+        server_events::{emit_server_event, ServerEvent, ServerEventType},
+        Catalog, Result, SecretStore, State, Transaction,
+    },
+};
+
+/// Property patterns are matched against every key of logged request bodies.
+const MAX_REDACTED_PROPERTIES: usize = 100;
+
+impl IntoResponse for RequestLogSettings {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
+
+#[async_trait::async_trait]
+pub(crate) trait Service<C: Catalog, A: Authorizer + Clone, S: SecretStore> {
+    async fn get_request_log_settings(
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<RequestLogSettings> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_server_action(
+                &request_metadata,
+                CatalogServerAction::CanConfigureRequestLog,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let settings = C::get_request_log_settings(context.v1_state.catalog)
+            .await?
+            .unwrap_or_else(RequestLogSettings::from_config);
+        Ok(settings)
+    }
+
+    async fn set_request_log_settings(
+        request: RequestLogSettings,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<RequestLogSettings> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_server_action(
+                &request_metadata,
+                CatalogServerAction::CanConfigureRequestLog,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        validate_request_log_settings(&request)?;
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let settings = C::set_request_log_settings(request, transaction.transaction()).await?;
+        transaction.commit().await?;
+        invalidate_request_log_settings();
+        emit_server_event(ServerEvent::new(
+            ServerEventType::SettingsChanged,
+            request_metadata.user_id(),
+            serde_json::json!({ "request-log": settings }),
+        ));
+
+        Ok(settings)
+    }
+}
+
+fn validate_request_log_settings(settings: &RequestLogSettings) -> Result<()> {
+    if settings.redacted_properties.len() > MAX_REDACTED_PROPERTIES {
+        return Err(ErrorModel::bad_request(
+            format!("At most {MAX_REDACTED_PROPERTIES} redacted properties can be specified"),
+            "InvalidRequestLogSettings",
+            None,
+        )
+        .into());
+    }
+    if settings
+        .redacted_properties
+        .iter()
+        .any(|p| p.trim_end_matches('*').is_empty())
+    {
+        return Err(ErrorModel::bad_request(
+            "Redacted properties must not be empty or match all keys",
+            "InvalidRequestLogSettings",
+            None,
+        )
+        .into());
+    }
+    Ok(())
+}
@@ -6,7 +6,7 @@ use super::default_page_size;
 use crate::{
     api::{
         iceberg::{types::PageToken, v1::PaginationQuery},
-        management::v1::ApiServer,
+        management::v1::{ApiServer, ListSort, SortBy, SortOrder},
         ApiContext,
     },
     request_metadata::RequestMetadata,
@@ -112,9 +112,23 @@ pub struct ListRolesQuery {
     #[serde(default)]
     #[param(value_type=Option::<String>)]
     pub project_id: Option<ProjectId>,
+    /// Field to sort roles by. If neither `sortBy` nor `order` is set,
+    /// roles are ordered by id.
+    #[serde(default, alias = "sort_by")]
+    #[param(nullable = false, required = false)]
+    pub sort_by: Option<SortBy>,
+    /// Sort order. Default: asc
+    #[serde(default)]
+    #[param(nullable = false, required = false)]
+    pub order: Option<SortOrder>,
 }
 
 impl ListRolesQuery {
+    #[must_use]
+    pub fn sort(&self) -> Option<ListSort> {
+        ListSort::from_query(self.sort_by, self.order)
+    }
+
     #[must_use]
     pub fn pagination_query(&self) -> PaginationQuery {
         PaginationQuery {
@@ -205,10 +219,12 @@ pub(crate) trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
         // -------------------- Business Logic --------------------
         let filter_role_id = None;
         let pagination_query = query.pagination_query();
+        let sort = query.sort();
         C::list_roles(
             Some(project_id),
             filter_role_id,
             query.name,
+            sort,
             pagination_query,
             context.v1_state.catalog,
         )
@@ -231,6 +247,7 @@ pub(crate) trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
             None,
             Some(vec![role_id]),
             None,
+            None,
             PaginationQuery {
                 page_size: Some(1),
                 page_token: PageToken::NotSpecified,
@@ -0,0 +1,785 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::Arc,
+};
+
+use axum::{response::IntoResponse, Json};
+use iceberg_ext::catalog::rest::ErrorModel;
+use serde::{Deserialize, Serialize};
+
+use super::default_page_size;
+use crate::{
+    api::{management::v1::ApiServer, ApiContext},
+    request_metadata::RequestMetadata,
+    service::{
+        authz::{
+            Authorizer, CatalogNamespaceAction, CatalogProjectAction, CatalogTableAction,
+            CatalogViewAction, CatalogWarehouseAction,
+        },
+        labels::LabelSelector,
+        search_index::{reindex_entities, SearchIndex},
+        Catalog, NamespaceId, Result, SecretStore, State, TableId, ViewId,
+    },
+    ProjectId, WarehouseId,
+};
+
+/// Property of namespaces, tables and views that holds the owner of the entity.
+pub const OWNER_PROPERTY: &str = "owner";
+/// Property of namespaces, tables and views that holds the certification status of the entity,
+/// e.g. `certified` or `deprecated`.
+pub const CERTIFICATION_PROPERTY: &str = "lakekeeper.certification";
+/// Maximum number of matching entities that are authorized and aggregated into facets.
+/// If more entities match, the response is marked as truncated.
+pub(crate) const MAX_SEARCH_CANDIDATES: i64 = 1000;
+const MAX_SEARCH_PAGE_SIZE: i64 = 1000;
+const MAX_GLOBAL_SEARCH_LIMIT: i64 = 100;
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    utoipa::ToSchema,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum SearchEntityType {
+    Namespace,
+    Table,
+    View,
+}
+
+impl SearchEntityType {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Namespace => "namespace",
+            Self::Table => "table",
+            Self::View => "view",
+        }
+    }
+}
+
+/// Ranges of the last-modified facet. Ranges don't overlap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum LastModifiedRange {
+    /// Within the last 24 hours
+    LastDay,
+    /// Within the last 7 days, but not the last 24 hours
+    LastWeek,
+    /// Within the last 30 days, but not the last 7 days
+    LastMonth,
+    /// Within the last 365 days, but not the last 30 days
+    LastYear,
+    /// Longer than 365 days ago
+    Older,
+}
+
+impl LastModifiedRange {
+    fn of(
+        last_modified: chrono::DateTime<chrono::Utc>,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Self {
+        let age = now - last_modified;
+        if age <= chrono::Duration::days(1) {
+            Self::LastDay
+        } else if age <= chrono::Duration::days(7) {
+            Self::LastWeek
+        } else if age <= chrono::Duration::days(30) {
+            Self::LastMonth
+        } else if age <= chrono::Duration::days(365) {
+            Self::LastYear
+        } else {
+            Self::Older
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::LastDay => "last-day",
+            Self::LastWeek => "last-week",
+            Self::LastMonth => "last-month",
+            Self::LastYear => "last-year",
+            Self::Older => "older",
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct SearchCatalogRequest {
+    /// Only return entities whose name contains this string, case-insensitive.
+    /// Length is truncated to 64 characters.
+    #[serde(default)]
+    pub search: Option<String>,
+    /// Only return entities of these types
+    #[serde(default)]
+    pub entity_types: Option<Vec<SearchEntityType>>,
+    /// Only return entities in these warehouses
+    #[serde(default)]
+    #[schema(value_type = Option<Vec<uuid::Uuid>>)]
+    pub warehouse_ids: Option<Vec<WarehouseId>>,
+    /// Only return tables with a column that carries any of these tags
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+    /// Only return entities whose `owner` property is any of these values
+    #[serde(default)]
+    pub owners: Option<Vec<String>>,
+    /// Only return entities whose `lakekeeper.certification` property is any of these values
+    #[serde(default)]
+    pub certifications: Option<Vec<String>>,
+    /// Only return namespaces and tables carrying all of these labels. `key` requires
+    /// the label to be set, `key=value` requires it to have the value.
+    /// Labels inherited from parents are not considered.
+    #[serde(default)]
+    pub labels: Option<Vec<String>>,
+    /// Only return entities modified at or after this time
+    #[serde(default)]
+    pub modified_after: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only return entities modified before this time
+    #[serde(default)]
+    pub modified_before: Option<chrono::DateTime<chrono::Utc>>,
+    /// Next page token
+    #[serde(default)]
+    pub page_token: Option<String>,
+    /// Signals an upper bound of the number of results that a client will receive.
+    /// Default: 100
+    #[serde(default = "default_page_size")]
+    pub page_size: i64,
+}
+
+/// Filter of a catalog search. `None` matches everything.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CatalogSearchFilter {
+    pub search: Option<String>,
+    pub entity_types: Option<Vec<SearchEntityType>>,
+    pub warehouse_ids: Option<Vec<WarehouseId>>,
+    pub tags: Option<Vec<String>>,
+    pub owners: Option<Vec<String>>,
+    pub certifications: Option<Vec<String>>,
+    pub labels: Option<Vec<LabelSelector>>,
+    pub modified_after: Option<chrono::DateTime<chrono::Utc>>,
+    pub modified_before: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only return entities with these ids
+    pub ids: Option<Vec<uuid::Uuid>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct SearchCatalogResult {
+    pub entity_type: SearchEntityType,
+    /// Id of the namespace, table or view
+    pub id: uuid::Uuid,
+    #[schema(value_type = uuid::Uuid)]
+    pub warehouse_id: WarehouseId,
+    pub warehouse_name: String,
+    /// Namespace containing the entity. For namespaces, this is the parent namespace.
+    pub namespace: Vec<String>,
+    pub name: String,
+    pub owner: Option<String>,
+    pub certification: Option<String>,
+    /// Tags of the columns of tables
+    pub tags: Vec<String>,
+    pub last_modified: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct FacetCount {
+    pub value: String,
+    /// Human readable name of the value, i.e. the name of a warehouse
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    pub count: usize,
+}
+
+/// Number of matching entities per facet value. Values without matches are omitted.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct SearchFacets {
+    pub entity_type: Vec<FacetCount>,
+    pub warehouse: Vec<FacetCount>,
+    pub tag: Vec<FacetCount>,
+    pub owner: Vec<FacetCount>,
+    pub certification: Vec<FacetCount>,
+    pub last_modified: Vec<FacetCount>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct SearchCatalogResponse {
+    /// Matching entities, most recently modified first.
+    /// If a search index is configured and `search` is set, best matches come first.
+    pub results: Vec<SearchCatalogResult>,
+    /// Facets over all matching entities, not only the current page
+    pub facets: SearchFacets,
+    /// Number of matching entities
+    pub total_count: usize,
+    /// More entities matched than can be aggregated. Facets and `total-count`
+    /// only cover the most recently modified matches. Narrow down the search.
+    pub truncated: bool,
+    pub next_page_token: Option<String>,
+}
+
+impl IntoResponse for SearchCatalogResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct GlobalSearchQuery {
+    /// Search term. Matched fuzzily against names, comments and property values.
+    /// Length is truncated to 64 characters.
+    pub q: String,
+    /// Only return entities in this warehouse
+    #[serde(default)]
+    #[param(value_type=uuid::Uuid)]
+    pub warehouse_id: Option<WarehouseId>,
+    /// Maximum number of hits. Default: 20, maximum: 100
+    #[serde(default = "default_global_search_limit")]
+    pub limit: i64,
+}
+
+fn default_global_search_limit() -> i64 {
+    20
+}
+
+/// Field of an entity that matched a global search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum GlobalSearchField {
+    /// Name of the namespace, table or view
+    Name,
+    /// The `comment` property
+    Comment,
+    /// Value of any other property
+    Property,
+}
+
+/// Best match of an entity for a global search term.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlobalSearchMatch {
+    pub entity_type: SearchEntityType,
+    pub id: uuid::Uuid,
+    pub matched_field: GlobalSearchField,
+    pub matched_value: String,
+    /// Trigram word similarity of the search term and the matched value, between 0 and 1
+    pub score: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct GlobalSearchHit {
+    #[serde(flatten)]
+    pub entity: SearchCatalogResult,
+    pub matched_field: GlobalSearchField,
+    /// Value that matched the search term, i.e. the name or the comment
+    pub matched_value: String,
+    /// Similarity of the search term and the matched value, between 0 and 1
+    pub score: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct GlobalSearchResponse {
+    /// Best matches first
+    pub hits: Vec<GlobalSearchHit>,
+}
+
+impl IntoResponse for GlobalSearchResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
+
+#[async_trait::async_trait]
+pub(crate) trait Service<C: Catalog, A: Authorizer + Clone, S: SecretStore> {
+    async fn search_catalog(
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+        request: SearchCatalogRequest,
+    ) -> Result<SearchCatalogResponse> {
+        // -------------------- VALIDATIONS --------------------
+        let SearchCatalogRequest {
+            search,
+            entity_types,
+            warehouse_ids,
+            tags,
+            owners,
+            certifications,
+            labels,
+            modified_after,
+            modified_before,
+            page_token,
+            page_size,
+        } = request;
+        let page_size = page_size.clamp(1, MAX_SEARCH_PAGE_SIZE);
+        let offset = page_token
+            .as_deref()
+            .map(|t| {
+                t.parse::<usize>().map_err(|_| {
+                    ErrorModel::bad_request("Invalid page token", "InvalidPageToken", None)
+                })
+            })
+            .transpose()?
+            .unwrap_or_default();
+        let labels = labels
+            .map(|selectors| {
+                selectors
+                    .iter()
+                    .map(|s| s.parse::<LabelSelector>())
+                    .collect::<Result<Vec<_>>>()
+            })
+            .transpose()?;
+        let filter = CatalogSearchFilter {
+            search: search
+                .map(|s| s.chars().take(64).collect::<String>())
+                .filter(|s| !s.is_empty()),
+            entity_types,
+            warehouse_ids,
+            tags,
+            owners,
+            certifications,
+            labels,
+            modified_after,
+            modified_before,
+            ids: None,
+        };
+        let project_id = request_metadata.require_project_id(None)?;
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_project_action(
+                &request_metadata,
+                &project_id,
+                CatalogProjectAction::CanListWarehouses,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut candidates = search_candidates::<C>(
+            context.v1_state.search_index.as_ref(),
+            &project_id,
+            &filter,
+            context.v1_state.catalog,
+        )
+        .await?;
+        let truncated = candidates.len() > usize::try_from(MAX_SEARCH_CANDIDATES).unwrap_or(0);
+        candidates.truncate(usize::try_from(MAX_SEARCH_CANDIDATES).unwrap_or(0));
+
+        let results = authorized_results(&authorizer, &request_metadata, candidates).await?;
+        let facets = facets(&results, chrono::Utc::now());
+        let total_count = results.len();
+        let page_size = usize::try_from(page_size).unwrap_or(1);
+        let next_page_token =
+            (offset + page_size < total_count).then(|| (offset + page_size).to_string());
+        let results = results.into_iter().skip(offset).take(page_size).collect();
+
+        Ok(SearchCatalogResponse {
+            results,
+            facets,
+            total_count,
+            truncated,
+            next_page_token,
+        })
+    }
+
+    async fn global_search(
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+        query: GlobalSearchQuery,
+    ) -> Result<GlobalSearchResponse> {
+        // -------------------- VALIDATIONS --------------------
+        let search = query.q.trim().chars().take(64).collect::<String>();
+        if search.is_empty() {
+            return Err(ErrorModel::bad_request(
+                "Search term must not be empty",
+                "EmptySearchTerm",
+                None,
+            )
+            .into());
+        }
+        let limit = query.limit.clamp(1, MAX_GLOBAL_SEARCH_LIMIT);
+        let project_id = request_metadata.require_project_id(None)?;
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_project_action(
+                &request_metadata,
+                &project_id,
+                CatalogProjectAction::CanListWarehouses,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        // Fetch more matches than requested, as some may be hidden from the user.
+        let matches = C::global_search(
+            &project_id,
+            &search,
+            query.warehouse_id,
+            limit * 4,
+            context.v1_state.catalog.clone(),
+        )
+        .await?;
+        if matches.is_empty() {
+            return Ok(GlobalSearchResponse { hits: vec![] });
+        }
+
+        let entities = C::search_catalog(
+            &project_id,
+            &CatalogSearchFilter {
+                ids: Some(matches.iter().map(|m| m.id).collect()),
+                ..Default::default()
+            },
+            i64::try_from(matches.len()).unwrap_or(i64::MAX),
+            context.v1_state.catalog,
+        )
+        .await?;
+        let mut entities = authorized_results(&authorizer, &request_metadata, entities)
+            .await?
+            .into_iter()
+            .map(|r| (r.id, r))
+            .collect::<HashMap<_, _>>();
+
+        let hits = matches
+            .into_iter()
+            .filter_map(|m| {
+                entities.remove(&m.id).map(|entity| GlobalSearchHit {
+                    entity,
+                    matched_field: m.matched_field,
+                    matched_value: m.matched_value,
+                    score: m.score,
+                })
+            })
+            .take(usize::try_from(limit).unwrap_or(usize::MAX))
+            .collect();
+
+        Ok(GlobalSearchResponse { hits })
+    }
+}
+
+/// Queries the search index if configured, falling back to the catalog if it fails.
+///
+/// Ids returned by the index are loaded from the catalog, which re-applies all filters
+/// except the free-text search. Ids that are missing in the catalog are re-indexed in the background.
+async fn search_candidates<C: Catalog>(
+    search_index: Option<&Arc<dyn SearchIndex>>,
+    project_id: &ProjectId,
+    filter: &CatalogSearchFilter,
+    catalog_state: C::State,
+) -> Result<Vec<SearchCatalogResult>> {
+    let limit = usize::try_from(MAX_SEARCH_CANDIDATES + 1).unwrap_or(usize::MAX);
+    // Labels are not indexed. Filtering the candidates of the index by labels
+    // could miss matches, so label filters are always answered by the catalog.
+    let Some(search_index) = search_index.filter(|_| filter.labels.is_none()) else {
+        return C::search_catalog(project_id, filter, MAX_SEARCH_CANDIDATES + 1, catalog_state)
+            .await;
+    };
+
+    let ids = match search_index.search(project_id, filter, limit).await {
+        Ok(ids) => ids,
+        Err(e) => {
+            tracing::warn!(
+                "Search index '{}' failed, falling back to catalog search: {e:?}",
+                search_index.name()
+            );
+            return C::search_catalog(project_id, filter, MAX_SEARCH_CANDIDATES + 1, catalog_state)
+                .await;
+        }
+    };
+    if ids.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let mut results = C::search_catalog(
+        project_id,
+        &CatalogSearchFilter {
+            search: None,
+            ids: Some(ids.clone()),
+            ..filter.clone()
+        },
+        i64::try_from(ids.len()).unwrap_or(i64::MAX),
+        catalog_state.clone(),
+    )
+    .await?;
+
+    let rank = ids
+        .iter()
+        .enumerate()
+        .map(|(rank, id)| (*id, rank))
+        .collect::<HashMap<_, _>>();
+    results.sort_by_key(|r| rank.get(&r.id).copied().unwrap_or(usize::MAX));
+
+    let found = results.iter().map(|r| r.id).collect::<HashSet<_>>();
+    let stale = ids
+        .into_iter()
+        .filter(|id| !found.contains(id))
+        .collect::<Vec<_>>();
+    if !stale.is_empty() {
+        let search_index = search_index.clone();
+        let project_id = project_id.clone();
+        tokio::spawn(async move {
+            if let Err(e) = reindex_entities::<C>(
+                search_index.as_ref(),
+                catalog_state,
+                &project_id,
+                None,
+                stale,
+            )
+            .await
+            {
+                tracing::warn!("Failed to re-index stale search index entries: {e:?}");
+            }
+        });
+    }
+
+    Ok(results)
+}
+
+/// Drops results of warehouses the user can't use and entities the user can't see.
+async fn authorized_results<A: Authorizer>(
+    authorizer: &A,
+    request_metadata: &RequestMetadata,
+    candidates: Vec<SearchCatalogResult>,
+) -> Result<Vec<SearchCatalogResult>> {
+    let warehouse_ids = candidates
+        .iter()
+        .map(|r| r.warehouse_id)
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+    let allowed_warehouses = futures::future::try_join_all(warehouse_ids.iter().map(|id| {
+        authorizer.is_allowed_warehouse_action(
+            request_metadata,
+            *id,
+            CatalogWarehouseAction::CanUse,
+        )
+    }))
+    .await?
+    .into_iter()
+    .zip(warehouse_ids)
+    .filter_map(|(allowed, id)| allowed.then_some(id))
+    .collect::<HashSet<_>>();
+
+    let candidates = candidates
+        .into_iter()
+        .filter(|r| allowed_warehouses.contains(&r.warehouse_id))
+        .collect::<Vec<_>>();
+    let allowed = futures::future::try_join_all(candidates.iter().map(|r| async move {
+        match r.entity_type {
+            SearchEntityType::Namespace => {
+                authorizer
+                    .is_allowed_namespace_action(
+                        request_metadata,
+                        NamespaceId::from(r.id),
+                        CatalogNamespaceAction::CanGetMetadata,
+                    )
+                    .await
+            }
+            SearchEntityType::Table => {
+                authorizer
+                    .is_allowed_table_action(
+                        request_metadata,
+                        TableId::from(r.id),
+                        CatalogTableAction::CanIncludeInList,
+                    )
+                    .await
+            }
+            SearchEntityType::View => {
+                authorizer
+                    .is_allowed_view_action(
+                        request_metadata,
+                        ViewId::from(r.id),
+                        CatalogViewAction::CanIncludeInList,
+                    )
+                    .await
+            }
+        }
+    }))
+    .await?;
+
+    Ok(candidates
+        .into_iter()
+        .zip(allowed)
+        .filter_map(|(r, allowed)| allowed.then_some(r))
+        .collect())
+}
+
+fn facets(results: &[SearchCatalogResult], now: chrono::DateTime<chrono::Utc>) -> SearchFacets {
+    fn counts<K: Ord>(values: impl Iterator<Item = K>) -> BTreeMap<K, usize> {
+        let mut counts = BTreeMap::new();
+        for value in values {
+            *counts.entry(value).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Sorts by descending count, then by value.
+    fn by_count(counts: BTreeMap<String, usize>) -> Vec<FacetCount> {
+        let mut facet = counts
+            .into_iter()
+            .map(|(value, count)| FacetCount {
+                value,
+                label: None,
+                count,
+            })
+            .collect::<Vec<_>>();
+        facet.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+        facet
+    }
+
+    let warehouse_names = results
+        .iter()
+        .map(|r| (r.warehouse_id, r.warehouse_name.as_str()))
+        .collect::<HashMap<_, _>>();
+    let mut warehouse = by_count(counts(results.iter().map(|r| r.warehouse_id.to_string())));
+    for facet in &mut warehouse {
+        facet.label = facet
+            .value
+            .parse::<uuid::Uuid>()
+            .ok()
+            .and_then(|id| warehouse_names.get(&WarehouseId::from(id)))
+            .map(ToString::to_string);
+    }
+
+    SearchFacets {
+        entity_type: counts(results.iter().map(|r| r.entity_type))
+            .into_iter()
+            .map(|(value, count)| FacetCount {
+                value: value.as_str().to_string(),
+                label: None,
+                count,
+            })
+            .collect(),
+        warehouse,
+        tag: by_count(counts(results.iter().flat_map(|r| r.tags.iter().cloned()))),
+        owner: by_count(counts(results.iter().filter_map(|r| r.owner.clone()))),
+        certification: by_count(counts(
+            results.iter().filter_map(|r| r.certification.clone()),
+        )),
+        last_modified: counts(
+            results
+                .iter()
+                .map(|r| LastModifiedRange::of(r.last_modified, now)),
+        )
+        .into_iter()
+        .map(|(value, count)| FacetCount {
+            value: value.as_str().to_string(),
+            label: None,
+            count,
+        })
+        .collect(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn result(
+        entity_type: SearchEntityType,
+        warehouse_id: WarehouseId,
+        tags: &[&str],
+        owner: Option<&str>,
+        last_modified: chrono::DateTime<chrono::Utc>,
+    ) -> SearchCatalogResult {
+        SearchCatalogResult {
+            entity_type,
+            id: uuid::Uuid::now_v7(),
+            warehouse_id,
+            warehouse_name: format!("wh-{warehouse_id}"),
+            namespace: vec!["ns".to_string()],
+            name: "entity".to_string(),
+            owner: owner.map(ToString::to_string),
+            certification: None,
+            tags: tags.iter().map(ToString::to_string).collect(),
+            last_modified,
+        }
+    }
+
+    #[test]
+    fn test_facets() {
+        let now = chrono::Utc::now();
+        let wh_1 = WarehouseId::new_random();
+        let wh_2 = WarehouseId::new_random();
+        let results = vec![
+            result(
+                SearchEntityType::Table,
+                wh_1,
+                &["pii", "email"],
+                Some("alice"),
+                now,
+            ),
+            result(
+                SearchEntityType::Table,
+                wh_1,
+                &["pii"],
+                Some("bob"),
+                now - chrono::Duration::days(3),
+            ),
+            result(
+                SearchEntityType::View,
+                wh_2,
+                &[],
+                Some("alice"),
+                now - chrono::Duration::days(400),
+            ),
+            result(
+                SearchEntityType::Namespace,
+                wh_1,
+                &[],
+                None,
+                now - chrono::Duration::hours(1),
+            ),
+        ];
+
+        let facets = facets(&results, now);
+        let pairs = |facet: &[FacetCount]| {
+            facet
+                .iter()
+                .map(|f| (f.value.clone(), f.count))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            pairs(&facets.entity_type),
+            vec![
+                ("namespace".to_string(), 1),
+                ("table".to_string(), 2),
+                ("view".to_string(), 1)
+            ]
+        );
+        assert_eq!(
+            pairs(&facets.warehouse),
+            vec![(wh_1.to_string(), 3), (wh_2.to_string(), 1)]
+        );
+        assert_eq!(facets.warehouse[0].label, Some(format!("wh-{wh_1}")));
+        assert_eq!(
+            pairs(&facets.tag),
+            vec![("pii".to_string(), 2), ("email".to_string(), 1)]
+        );
+        assert_eq!(
+            pairs(&facets.owner),
+            vec![("alice".to_string(), 2), ("bob".to_string(), 1)]
+        );
+        assert!(facets.certification.is_empty());
+        assert_eq!(
+            pairs(&facets.last_modified),
+            vec![
+                ("last-day".to_string(), 2),
+                ("last-week".to_string(), 1),
+                ("older".to_string(), 1)
+            ]
+        );
+    }
+}
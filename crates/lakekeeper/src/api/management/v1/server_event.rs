@@ -0,0 +1,84 @@
+use axum::{response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+
+use super::default_page_size;
+use crate::{
+    api::{
+        iceberg::v1::{PageToken, PaginationQuery},
+        management::v1::ApiServer,
+        ApiContext,
+    },
+    request_metadata::RequestMetadata,
+    service::{
+        authz::{Authorizer, CatalogServerAction},
+        server_events::{ServerEvent, ServerEventType},
+        Catalog, Result, SecretStore, State,
+    },
+};
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct ListServerEventsQuery {
+    /// Only return events of this type
+    #[serde(default)]
+    pub event_type: Option<ServerEventType>,
+    /// Next page token
+    #[serde(default)]
+    pub page_token: Option<String>,
+    /// Signals an upper bound of the number of results that a client will receive.
+    /// Default: 100
+    #[serde(default = "default_page_size")]
+    pub page_size: i64,
+}
+
+impl ListServerEventsQuery {
+    #[must_use]
+    pub fn pagination_query(&self) -> PaginationQuery {
+        PaginationQuery {
+            page_token: self
+                .page_token
+                .clone()
+                .map_or(PageToken::Empty, PageToken::Present),
+            page_size: Some(self.page_size),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ListServerEventsResponse {
+    /// Events of all instances of the server, newest first
+    pub events: Vec<ServerEvent>,
+    pub next_page_token: Option<String>,
+}
+
+impl IntoResponse for ListServerEventsResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
+
+#[async_trait::async_trait]
+pub(crate) trait Service<C: Catalog, A: Authorizer + Clone, S: SecretStore> {
+    async fn list_server_events(
+        query: ListServerEventsQuery,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<ListServerEventsResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_server_action(&request_metadata, CatalogServerAction::CanListServerEvents)
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        C::list_server_events(
+            query.event_type.map(|t| vec![t]),
+            query.pagination_query(),
+            context.v1_state.catalog,
+        )
+        .await
+    }
+}
@@ -1,13 +1,722 @@
-use super::{ApiServer, ProtectionResponse};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    str::FromStr as _,
+    sync::Arc,
+};
+
+use axum::{response::IntoResponse, Json};
+use http::StatusCode;
+use iceberg::{
+    spec::{
+        ListType, MapType, NestedField, NestedFieldRef, Schema, StructType, TableMetadata, Type,
+    },
+    TableRequirement, TableUpdate,
+};
+use iceberg_ext::configs::Location;
+use serde::{Deserialize, Serialize};
+
+use super::{default_page_size, ApiServer, ProtectionResponse};
 use crate::{
-    api::{ApiContext, RequestMetadata, Result},
+    api::{
+        iceberg::v1::{
+            CommitTableRequest, CommitTransactionRequest, PageToken, PaginationQuery, Prefix,
+        },
+        ApiContext, ErrorModel, RequestMetadata, Result,
+    },
+    catalog::{
+        compression_codec::CompressionCodec,
+        io::{read_file, StorageIo},
+        maybe_get_secret,
+        tables::{
+            commit_tables_internal, commit_tables_with_writer_checkpoint,
+            invalidate_table_metadata, CommitContext,
+        },
+    },
     service::{
-        authz::{Authorizer, CatalogTableAction},
-        Catalog, SecretStore, State, TableId, TabularId, Transaction,
+        authz::{Authorizer, CatalogTableAction, CatalogWarehouseAction},
+        engine_compatibility::{self, EngineCompatibility},
+        maintenance::{self, CompactionRecommendation, MaintenanceAnalysis},
+        table_timeline::TableTimelineEvent,
+        task_queue::{
+            orphan_file_cleanup_queue::{self, OrphanFileCleanupPayload, OrphanFileCleanupResult},
+            pii_detection_queue::PiiDetectionPayload,
+            snapshot_expiration_queue::{self, SnapshotExpirationResult},
+            EntityId, TaskInfo, TaskMetadata, TaskOutcome,
+        },
+        Catalog, GetTableMetadataResponse, ListFlags, LoadTableResponse, SecretStore, State,
+        TableId, TabularId, Transaction,
     },
-    WarehouseId,
+    WarehouseId, CONFIG,
 };
 
+/// Table property that holds the table comment.
+const TABLE_COMMENT_PROPERTY: &str = "comment";
+/// Number of past snapshot expiration runs returned per table.
+const SNAPSHOT_EXPIRATION_RUNS_LIMIT: i64 = 10;
+/// Number of past orphan file cleanup runs returned per table.
+const ORPHAN_FILE_CLEANUP_RUNS_LIMIT: i64 = 10;
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct SetTableCommentRequest {
+    /// New comment of the table. If `null` or omitted, the comment is removed.
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct SetColumnCommentsRequest {
+    /// Map of column names to their new comment. Nested columns are
+    /// addressed by their full dotted name, i.e. `address.street`.
+    /// A `null` value removes the comment of the column.
+    pub columns: HashMap<String, Option<String>>,
+}
+
+/// Maximum length of a column tag.
+const MAX_COLUMN_TAG_LENGTH: usize = 128;
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct SetColumnTagsRequest {
+    /// Map of column names to their tags. Nested columns are addressed by
+    /// their full dotted name, i.e. `address.street`. The tags of each listed
+    /// column are replaced; an empty list removes all tags of the column.
+    /// Columns not listed keep their tags.
+    pub columns: HashMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ColumnTags {
+    /// Iceberg field id of the column
+    pub field_id: i32,
+    /// Name of the column in the current schema.
+    /// `null` if the column has been dropped from the current schema.
+    pub column_name: Option<String>,
+    /// Tags of the column, sorted alphabetically
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct GetColumnTagsResponse {
+    /// Tagged columns of the table, ordered by field id
+    pub columns: Vec<ColumnTags>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TaggedColumn {
+    /// Id of the table
+    #[schema(value_type = uuid::Uuid)]
+    pub table_id: TableId,
+    /// List of namespace parts the table belongs to
+    pub namespace: Vec<String>,
+    /// Name of the table
+    pub table_name: String,
+    /// Iceberg field id of the column
+    pub field_id: i32,
+    /// Name of the column in the current schema of the table
+    pub column_name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ListTaggedColumnsResponse {
+    /// Columns carrying the tag
+    pub columns: Vec<TaggedColumn>,
+}
+
+impl IntoResponse for GetColumnTagsResponse {
+    fn into_response(self) -> axum::response::Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl IntoResponse for ListTaggedColumnsResponse {
+    fn into_response(self) -> axum::response::Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// Review status of a column tag suggestion
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum ColumnTagSuggestionStatus {
+    Pending,
+    Accepted,
+    Rejected,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ColumnTagSuggestion {
+    /// Iceberg field id of the column
+    pub field_id: i32,
+    /// Name of the column in the current schema.
+    /// `null` if the column has been dropped from the current schema.
+    pub column_name: Option<String>,
+    /// Suggested tag, i.e. `pii:email`
+    pub tag: String,
+    /// Confidence of the suggestion between 0 and 1
+    pub confidence: f64,
+    /// Explanation of why the tag was suggested
+    pub reason: String,
+    pub status: ColumnTagSuggestionStatus,
+    /// Id of the user that reviewed the suggestion
+    pub reviewed_by: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ListColumnTagSuggestionsResponse {
+    /// Suggestions ordered by field id and tag
+    pub suggestions: Vec<ColumnTagSuggestion>,
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct ListColumnTagSuggestionsQuery {
+    /// Only return suggestions with this status
+    #[param(inline)]
+    pub status: Option<ColumnTagSuggestionStatus>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ColumnTagSuggestionKey {
+    /// Iceberg field id of the column
+    pub field_id: i32,
+    /// Suggested tag
+    pub tag: String,
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ReviewColumnTagSuggestionsRequest {
+    /// Suggestions to accept. Accepted tags are added to the column.
+    #[serde(default)]
+    pub accept: Vec<ColumnTagSuggestionKey>,
+    /// Suggestions to reject. Rejected tags are not suggested again.
+    #[serde(default)]
+    pub reject: Vec<ColumnTagSuggestionKey>,
+}
+
+/// Status of a table property change proposal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum TablePropertyProposalStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TablePropertyProposal {
+    pub proposal_id: uuid::Uuid,
+    /// Properties to set when the proposal is approved
+    pub set_properties: HashMap<String, String>,
+    /// Properties to remove when the proposal is approved
+    pub remove_properties: Vec<String>,
+    /// Justification given by the proposer
+    pub reason: Option<String>,
+    pub status: TablePropertyProposalStatus,
+    /// Id of the user that created the proposal
+    pub proposed_by: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Id of the user that approved or rejected the proposal
+    pub reviewed_by: Option<String>,
+    pub reviewed_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Comment of the reviewer
+    pub review_comment: Option<String>,
+}
+
+impl IntoResponse for TablePropertyProposal {
+    fn into_response(self) -> axum::response::Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ListTablePropertyProposalsResponse {
+    /// Proposals of the table, newest first
+    pub proposals: Vec<TablePropertyProposal>,
+}
+
+impl IntoResponse for ListTablePropertyProposalsResponse {
+    fn into_response(self) -> axum::response::Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct ListTablePropertyProposalsQuery {
+    /// Only return proposals with this status
+    #[param(inline)]
+    pub status: Option<TablePropertyProposalStatus>,
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct CreateTablePropertyProposalRequest {
+    /// Properties to set, i.e. `history.expire.max-snapshot-age-ms` to change retention.
+    #[serde(default)]
+    pub set_properties: HashMap<String, String>,
+    /// Properties to remove
+    #[serde(default)]
+    pub remove_properties: Vec<String>,
+    /// Justification for the change, shown to reviewers
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// Decision of a reviewer on a table property change proposal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum TablePropertyProposalDecision {
+    Approve,
+    Reject,
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ReviewTablePropertyProposalRequest {
+    pub decision: TablePropertyProposalDecision,
+    /// Optional comment of the reviewer
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+/// A finished attempt of a snapshot expiration run
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct SnapshotExpirationRun {
+    pub task_id: uuid::Uuid,
+    pub attempt: i32,
+    pub status: TaskOutcome,
+    pub started_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub duration_ms: Option<i64>,
+    /// Outcome of successful runs
+    pub result: Option<SnapshotExpirationResult>,
+    /// Error of failed runs
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct GetSnapshotExpirationResponse {
+    /// Next scheduled or currently running expiration of the table.
+    /// `null` if snapshot expiration is not scheduled for the table.
+    pub next_run: Option<TaskInfo>,
+    /// Most recent runs, newest first
+    pub runs: Vec<SnapshotExpirationRun>,
+}
+
+impl IntoResponse for GetSnapshotExpirationResponse {
+    fn into_response(self) -> axum::response::Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTableCompatibilityQuery {
+    /// Comma separated list of engines to check the table against, e.g. `trino-440,spark-1.7.1`.
+    /// For Spark, the version is the version of the Iceberg Spark runtime.
+    /// Defaults to the engines configured on the server.
+    pub engines: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct GetTableCompatibilityResponse {
+    pub format_version: u8,
+    /// One entry per checked engine
+    pub engines: Vec<EngineCompatibility>,
+}
+
+impl IntoResponse for GetTableCompatibilityResponse {
+    fn into_response(self) -> axum::response::Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Default, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct CleanupOrphanFilesRequest {
+    /// Only report orphans, don't delete them.
+    /// Defaults to the `dry-run` setting of the `orphan_file_cleanup` queue of the warehouse.
+    #[serde(default)]
+    pub dry_run: Option<bool>,
+}
+
+/// A finished attempt of an orphan file cleanup run
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct OrphanFileCleanupRun {
+    pub task_id: uuid::Uuid,
+    pub attempt: i32,
+    pub status: TaskOutcome,
+    pub started_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub duration_ms: Option<i64>,
+    /// Outcome of successful runs
+    pub result: Option<OrphanFileCleanupResult>,
+    /// Error of failed runs
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct GetOrphanFileCleanupResponse {
+    /// Next scheduled or currently running cleanup of the table.
+    /// `null` if orphan file cleanup is not scheduled for the table.
+    pub next_run: Option<TaskInfo>,
+    /// Most recent runs, newest first
+    pub runs: Vec<OrphanFileCleanupRun>,
+}
+
+impl IntoResponse for GetOrphanFileCleanupResponse {
+    fn into_response(self) -> axum::response::Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct GetMaintenanceRecommendationsResponse {
+    #[serde(flatten)]
+    pub analysis: MaintenanceAnalysis,
+}
+
+impl IntoResponse for GetMaintenanceRecommendationsResponse {
+    fn into_response(self) -> axum::response::Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TriggerCompactionResponse {
+    /// Analyzed snapshot. `None` if the table has no snapshot.
+    pub snapshot_id: Option<i64>,
+    /// Partitions a `compactionRequested` event was published for.
+    /// Empty if no partition needs to be compacted.
+    pub requested: Vec<CompactionRecommendation>,
+}
+
+impl IntoResponse for TriggerCompactionResponse {
+    fn into_response(self) -> axum::response::Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// Maximum length of the id of a table writer.
+const MAX_TABLE_WRITER_ID_LENGTH: usize = 128;
+
+/// Commit coordination state of a writer of a table.
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableWriterState {
+    /// Epoch of the current instance of the writer. Requests with an older
+    /// epoch are rejected.
+    pub epoch: i64,
+    /// Last checkpoint committed by the writer, if any
+    pub committed_checkpoint: Option<i64>,
+    /// Checkpoint that has been prepared but not yet committed, if any.
+    /// A restarted writer should commit it before writing new data.
+    pub prepared_checkpoint: Option<i64>,
+}
+
+impl IntoResponse for TableWriterState {
+    fn into_response(self) -> axum::response::Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// Changes of a checkpoint that are committed once the checkpoint is committed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct PreparedTableWriterCommit {
+    pub requirements: Vec<TableRequirement>,
+    pub updates: Vec<TableUpdate>,
+}
+
+/// A writer of a table together with the changes of its prepared checkpoint.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableWriter {
+    pub state: TableWriterState,
+    pub prepared_commit: Option<PreparedTableWriterCommit>,
+}
+
+/// Checkpoint that is marked as committed together with the table commit it belongs to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableWriterCheckpoint {
+    pub table_id: TableId,
+    pub writer_id: String,
+    pub epoch: i64,
+    pub checkpoint_id: i64,
+}
+
+/// Error type if the epoch of a writer or its last committed checkpoint changed concurrently.
+pub(crate) const TABLE_WRITER_CONFLICT_ERROR_TYPE: &str = "TableWriterConflict";
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct PrepareTableWriterCommitRequest {
+    /// Epoch returned by the last `begin` call of the writer
+    pub epoch: i64,
+    /// Id of the checkpoint, must increase with every checkpoint of the writer
+    pub checkpoint_id: i64,
+    /// Requirements of the commit, as for a regular table commit
+    #[serde(default)]
+    #[schema(value_type = Vec<Object>)]
+    pub requirements: Vec<TableRequirement>,
+    /// Updates of the commit, as for a regular table commit
+    #[schema(value_type = Vec<Object>)]
+    pub updates: Vec<TableUpdate>,
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct CommitTableWriterRequest {
+    /// Epoch returned by the last `begin` call of the writer
+    pub epoch: i64,
+    /// Id of the prepared checkpoint to commit
+    pub checkpoint_id: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct CommitTableWriterResponse {
+    /// `true` if the checkpoint had already been committed before, i.e. by a
+    /// retried request. The table has not been changed again in this case.
+    pub already_committed: bool,
+}
+
+impl IntoResponse for CommitTableWriterResponse {
+    fn into_response(self) -> axum::response::Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// A request to the table's storage that has been signed by the remote signer.
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableSigning {
+    /// Id of the entry. Ids of later signings are greater.
+    pub signing_id: uuid::Uuid,
+    pub table_id: uuid::Uuid,
+    /// Id of the sign request, as used in the logs of Lakekeeper
+    pub request_id: uuid::Uuid,
+    /// User the request has been signed for. Not set for anonymous requests.
+    pub principal: Option<String>,
+    /// Role assumed by the user, if any
+    pub assumed_role_id: Option<uuid::Uuid>,
+    /// HTTP method of the signed request, i.e. `GET` or `PUT`
+    pub method: String,
+    /// Locations of the objects accessed by the signed request
+    pub locations: Vec<String>,
+    pub signed_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ListTableSigningsResponse {
+    /// Signed requests of the table, newest first
+    pub signings: Vec<TableSigning>,
+    pub next_page_token: Option<String>,
+}
+
+impl IntoResponse for ListTableSigningsResponse {
+    fn into_response(self) -> axum::response::Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct ListTableSigningsQuery {
+    /// Next page token
+    #[serde(default)]
+    pub page_token: Option<String>,
+    /// Signals an upper bound of the number of results that a client will receive.
+    /// Default: 100
+    #[serde(default = "default_page_size")]
+    pub page_size: i64,
+}
+
+impl ListTableSigningsQuery {
+    #[must_use]
+    pub fn pagination_query(&self) -> PaginationQuery {
+        PaginationQuery {
+            page_token: self
+                .page_token
+                .clone()
+                .map_or(PageToken::Empty, PageToken::Present),
+            page_size: Some(self.page_size),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct GetTableTimelineResponse {
+    /// Commits, property changes, grants, maintenance runs, drops and undrops
+    /// of the table, newest first
+    pub events: Vec<TableTimelineEvent>,
+    pub next_page_token: Option<String>,
+}
+
+impl IntoResponse for GetTableTimelineResponse {
+    fn into_response(self) -> axum::response::Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTableTimelineQuery {
+    /// Next page token
+    #[serde(default)]
+    pub page_token: Option<String>,
+    /// Signals an upper bound of the number of results that a client will receive.
+    /// Default: 100
+    #[serde(default = "default_page_size")]
+    pub page_size: i64,
+}
+
+impl GetTableTimelineQuery {
+    #[must_use]
+    pub fn pagination_query(&self) -> PaginationQuery {
+        PaginationQuery {
+            page_token: self
+                .page_token
+                .clone()
+                .map_or(PageToken::Empty, PageToken::Present),
+            page_size: Some(self.page_size),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct RepairTableRequest {
+    /// Metadata file to point the table to. Must be part of the metadata log of the table.
+    /// Defaults to the most recent readable metadata file of the metadata log if the
+    /// current metadata file can't be read.
+    #[serde(default)]
+    pub metadata_location: Option<String>,
+    /// Only list the candidates and the changes of the repair without applying it.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct MetadataFileCandidate {
+    pub metadata_location: String,
+    /// Time the metadata file was replaced by its successor, from the metadata log.
+    /// Not set for the current metadata file.
+    pub timestamp_ms: Option<i64>,
+    /// Whether the table currently points to this metadata file
+    pub current: bool,
+    /// Whether the file can be read and contains metadata of this table
+    pub readable: bool,
+    /// Why the file can't be used, if it isn't readable
+    pub error: Option<String>,
+}
+
+/// Changes of the table state caused by a repair.
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableRepairDiff {
+    /// Snapshots that are not part of the selected metadata file and are removed
+    pub removed_snapshot_ids: Vec<i64>,
+    /// Snapshots that are only part of the selected metadata file and are restored
+    pub added_snapshot_ids: Vec<i64>,
+    pub previous_current_snapshot_id: Option<i64>,
+    pub current_snapshot_id: Option<i64>,
+    pub previous_current_schema_id: i32,
+    pub current_schema_id: i32,
+    pub previous_last_sequence_number: i64,
+    pub last_sequence_number: i64,
+    /// Properties that are added or changed
+    pub set_properties: BTreeMap<String, String>,
+    pub removed_properties: Vec<String>,
+}
+
+impl TableRepairDiff {
+    fn between(previous: &TableMetadata, new: &TableMetadata) -> Self {
+        let previous_snapshots = previous
+            .snapshots()
+            .map(|s| s.snapshot_id())
+            .collect::<BTreeSet<_>>();
+        let new_snapshots = new
+            .snapshots()
+            .map(|s| s.snapshot_id())
+            .collect::<BTreeSet<_>>();
+        Self {
+            removed_snapshot_ids: previous_snapshots
+                .difference(&new_snapshots)
+                .copied()
+                .collect(),
+            added_snapshot_ids: new_snapshots
+                .difference(&previous_snapshots)
+                .copied()
+                .collect(),
+            previous_current_snapshot_id: previous.current_snapshot_id(),
+            current_snapshot_id: new.current_snapshot_id(),
+            previous_current_schema_id: previous.current_schema_id(),
+            current_schema_id: new.current_schema_id(),
+            previous_last_sequence_number: previous.last_sequence_number(),
+            last_sequence_number: new.last_sequence_number(),
+            set_properties: new
+                .properties()
+                .iter()
+                .filter(|(k, v)| previous.properties().get(*k) != Some(*v))
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+            removed_properties: previous
+                .properties()
+                .keys()
+                .filter(|k| !new.properties().contains_key(*k))
+                .cloned()
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct RepairTableResponse {
+    pub dry_run: bool,
+    /// Whether the table now points to the selected metadata file
+    pub repaired: bool,
+    /// The current metadata file followed by the files of the metadata log, newest first
+    pub candidates: Vec<MetadataFileCandidate>,
+    /// Metadata file the table is or would be pointed to. Not set if the current
+    /// metadata file is readable and no metadata file was requested.
+    pub selected_metadata_location: Option<String>,
+    pub diff: Option<TableRepairDiff>,
+}
+
+impl IntoResponse for RepairTableResponse {
+    fn into_response(self) -> axum::response::Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl IntoResponse for ListColumnTagSuggestionsResponse {
+    fn into_response(self) -> axum::response::Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
 impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> TableManagementService<C, A, S>
     for ApiServer<C, A, S>
 {
@@ -65,4 +774,1630 @@ where
         t.commit().await?;
         Ok(status)
     }
+
+    async fn set_table_comment(
+        table_id: TableId,
+        warehouse_id: WarehouseId,
+        request: SetTableCommentRequest,
+        state: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        let updates = match request.comment {
+            Some(comment) => TableUpdate::SetProperties {
+                updates: HashMap::from([(TABLE_COMMENT_PROPERTY.to_string(), comment)]),
+            },
+            None => TableUpdate::RemoveProperties {
+                removals: vec![TABLE_COMMENT_PROPERTY.to_string()],
+            },
+        };
+        commit_table_by_id(
+            table_id,
+            warehouse_id,
+            |_| Ok((vec![], vec![updates])),
+            state,
+            request_metadata,
+        )
+        .await
+    }
+
+    async fn set_column_comments(
+        table_id: TableId,
+        warehouse_id: WarehouseId,
+        request: SetColumnCommentsRequest,
+        state: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        if request.columns.is_empty() {
+            return Ok(());
+        }
+
+        commit_table_by_id(
+            table_id,
+            warehouse_id,
+            |table_metadata| {
+                let current_schema = table_metadata.current_schema();
+                let schema = schema_with_column_comments(current_schema, &request.columns)?;
+                Ok((
+                    vec![TableRequirement::CurrentSchemaIdMatch {
+                        current_schema_id: current_schema.schema_id(),
+                    }],
+                    vec![
+                        TableUpdate::AddSchema { schema },
+                        TableUpdate::SetCurrentSchema { schema_id: -1 },
+                    ],
+                ))
+            },
+            state,
+            request_metadata,
+        )
+        .await
+    }
+
+    async fn get_column_tags(
+        table_id: TableId,
+        warehouse_id: WarehouseId,
+        state: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<GetColumnTagsResponse> {
+        // ------------------- AUTHZ -------------------
+        let authorizer = state.v1_state.authz.clone();
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            state.v1_state.catalog.clone(),
+        )
+        .await;
+        authorizer
+            .require_table_action(&request_metadata, table, CatalogTableAction::CanGetMetadata)
+            .await?;
+
+        // ------------------- BUSINESS LOGIC -------------------
+        let metadata = load_table_metadata(table_id, warehouse_id, &state).await?;
+        let schema = metadata.current_schema();
+        let tags = C::get_column_tags(table_id, state.v1_state.catalog).await?;
+
+        let mut columns = tags
+            .into_iter()
+            .map(|(field_id, tags)| ColumnTags {
+                field_id,
+                column_name: schema.name_by_field_id(field_id).map(ToString::to_string),
+                tags,
+            })
+            .collect::<Vec<_>>();
+        columns.sort_by_key(|c| c.field_id);
+
+        Ok(GetColumnTagsResponse { columns })
+    }
+
+    async fn set_column_tags(
+        table_id: TableId,
+        warehouse_id: WarehouseId,
+        request: SetColumnTagsRequest,
+        state: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- VALIDATIONS -------------------
+        for tag in request.columns.values().flatten() {
+            validate_column_tag(tag)?;
+        }
+
+        // ------------------- AUTHZ -------------------
+        let authorizer = state.v1_state.authz.clone();
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            state.v1_state.catalog.clone(),
+        )
+        .await;
+        authorizer
+            .require_table_action(&request_metadata, table, CatalogTableAction::CanCommit)
+            .await?;
+
+        // ------------------- BUSINESS LOGIC -------------------
+        if request.columns.is_empty() {
+            return Ok(());
+        }
+
+        // Tags are stored by field id so that they survive renames and type promotions.
+        let metadata = load_table_metadata(table_id, warehouse_id, &state).await?;
+        let schema = metadata.current_schema();
+        let mut tags_by_field_id = HashMap::with_capacity(request.columns.len());
+        for (name, tags) in request.columns {
+            let field = schema.field_by_name(&name).ok_or_else(|| {
+                ErrorModel::bad_request(
+                    format!("Column '{name}' does not exist in the current schema"),
+                    "ColumnNotFound",
+                    None,
+                )
+            })?;
+            tags_by_field_id
+                .entry(field.id)
+                .or_insert_with(Vec::new)
+                .extend(tags);
+        }
+        for tags in tags_by_field_id.values_mut() {
+            tags.sort();
+            tags.dedup();
+        }
+
+        let mut t = C::Transaction::begin_write(state.v1_state.catalog).await?;
+        C::set_column_tags(warehouse_id, table_id, &tags_by_field_id, t.transaction()).await?;
+        t.commit().await?;
+
+        Ok(())
+    }
+
+    async fn list_tagged_columns(
+        warehouse_id: WarehouseId,
+        tag: String,
+        state: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<ListTaggedColumnsResponse> {
+        // ------------------- AUTHZ -------------------
+        let authorizer = state.v1_state.authz.clone();
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanUse,
+            )
+            .await?;
+
+        // ------------------- BUSINESS LOGIC -------------------
+        let columns =
+            C::list_tagged_columns(warehouse_id, &tag, state.v1_state.catalog.clone()).await?;
+
+        let table_ids = columns
+            .iter()
+            .map(|c| c.table_id)
+            .collect::<std::collections::HashSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        let allowed = futures::future::try_join_all(table_ids.iter().map(|table_id| {
+            authorizer.is_allowed_table_action(
+                &request_metadata,
+                *table_id,
+                CatalogTableAction::CanGetMetadata,
+            )
+        }))
+        .await?;
+        let allowed_table_ids = table_ids
+            .into_iter()
+            .zip(allowed)
+            .filter_map(|(table_id, allowed)| allowed.then_some(table_id))
+            .collect::<std::collections::HashSet<_>>();
+
+        Ok(ListTaggedColumnsResponse {
+            columns: columns
+                .into_iter()
+                .filter(|c| allowed_table_ids.contains(&c.table_id))
+                .collect(),
+        })
+    }
+
+    async fn list_column_tag_suggestions(
+        table_id: TableId,
+        warehouse_id: WarehouseId,
+        query: ListColumnTagSuggestionsQuery,
+        state: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<ListColumnTagSuggestionsResponse> {
+        // ------------------- AUTHZ -------------------
+        let authorizer = state.v1_state.authz.clone();
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            state.v1_state.catalog.clone(),
+        )
+        .await;
+        authorizer
+            .require_table_action(&request_metadata, table, CatalogTableAction::CanGetMetadata)
+            .await?;
+
+        // ------------------- BUSINESS LOGIC -------------------
+        let suggestions =
+            C::list_column_tag_suggestions(table_id, query.status, state.v1_state.catalog).await?;
+
+        Ok(ListColumnTagSuggestionsResponse { suggestions })
+    }
+
+    async fn review_column_tag_suggestions(
+        table_id: TableId,
+        warehouse_id: WarehouseId,
+        request: ReviewColumnTagSuggestionsRequest,
+        state: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- VALIDATIONS -------------------
+        let mut keys = std::collections::HashSet::new();
+        for key in request.accept.iter().chain(&request.reject) {
+            if !keys.insert(key) {
+                return Err(ErrorModel::bad_request(
+                    format!(
+                        "Suggestion of tag '{}' for field {} is listed more than once",
+                        key.tag, key.field_id
+                    ),
+                    "DuplicateColumnTagSuggestion",
+                    None,
+                )
+                .into());
+            }
+        }
+
+        // ------------------- AUTHZ -------------------
+        let authorizer = state.v1_state.authz.clone();
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            state.v1_state.catalog.clone(),
+        )
+        .await;
+        authorizer
+            .require_table_action(&request_metadata, table, CatalogTableAction::CanCommit)
+            .await?;
+
+        // ------------------- BUSINESS LOGIC -------------------
+        if keys.is_empty() {
+            return Ok(());
+        }
+
+        let reviewed_by = request_metadata.user_id().map(ToString::to_string);
+        let mut t = C::Transaction::begin_write(state.v1_state.catalog).await?;
+        let n_reviewed = C::review_column_tag_suggestions(
+            warehouse_id,
+            table_id,
+            &request.accept,
+            &request.reject,
+            reviewed_by.as_deref(),
+            t.transaction(),
+        )
+        .await?;
+        if n_reviewed != keys.len() as u64 {
+            t.rollback().await?;
+            return Err(ErrorModel::not_found(
+                "At least one of the suggestions does not exist or has already been reviewed",
+                "NoSuchPendingColumnTagSuggestion",
+                None,
+            )
+            .into());
+        }
+        t.commit().await?;
+
+        Ok(())
+    }
+
+    async fn create_table_property_proposal(
+        table_id: TableId,
+        warehouse_id: WarehouseId,
+        request: CreateTablePropertyProposalRequest,
+        state: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<TablePropertyProposal> {
+        // ------------------- VALIDATIONS -------------------
+        validate_table_property_proposal(&request)?;
+
+        // ------------------- AUTHZ -------------------
+        // Proposing a change only requires read access, the change is applied
+        // with the permissions of the reviewer.
+        let authorizer = state.v1_state.authz.clone();
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            state.v1_state.catalog.clone(),
+        )
+        .await;
+        authorizer
+            .require_table_action(&request_metadata, table, CatalogTableAction::CanGetMetadata)
+            .await?;
+
+        // ------------------- BUSINESS LOGIC -------------------
+        let proposed_by = request_metadata.user_id().map(ToString::to_string);
+        let mut t = C::Transaction::begin_write(state.v1_state.catalog).await?;
+        let proposal = C::create_table_property_proposal(
+            warehouse_id,
+            table_id,
+            uuid::Uuid::now_v7(),
+            &request,
+            proposed_by.as_deref(),
+            t.transaction(),
+        )
+        .await?;
+        t.commit().await?;
+
+        Ok(proposal)
+    }
+
+    async fn list_table_property_proposals(
+        table_id: TableId,
+        warehouse_id: WarehouseId,
+        query: ListTablePropertyProposalsQuery,
+        state: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<ListTablePropertyProposalsResponse> {
+        // ------------------- AUTHZ -------------------
+        let authorizer = state.v1_state.authz.clone();
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            state.v1_state.catalog.clone(),
+        )
+        .await;
+        authorizer
+            .require_table_action(&request_metadata, table, CatalogTableAction::CanGetMetadata)
+            .await?;
+
+        // ------------------- BUSINESS LOGIC -------------------
+        let proposals =
+            C::list_table_property_proposals(table_id, query.status, state.v1_state.catalog)
+                .await?;
+
+        Ok(ListTablePropertyProposalsResponse { proposals })
+    }
+
+    async fn review_table_property_proposal(
+        table_id: TableId,
+        warehouse_id: WarehouseId,
+        proposal_id: uuid::Uuid,
+        request: ReviewTablePropertyProposalRequest,
+        state: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<TablePropertyProposal> {
+        // ------------------- AUTHZ -------------------
+        let authorizer = state.v1_state.authz.clone();
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            state.v1_state.catalog.clone(),
+        )
+        .await;
+        authorizer
+            .require_table_action(&request_metadata, table, CatalogTableAction::CanCommit)
+            .await?;
+
+        // ------------------- BUSINESS LOGIC -------------------
+        let status = match request.decision {
+            TablePropertyProposalDecision::Approve => TablePropertyProposalStatus::Approved,
+            TablePropertyProposalDecision::Reject => TablePropertyProposalStatus::Rejected,
+        };
+        let reviewed_by = request_metadata.user_id().map(ToString::to_string);
+        // The proposal row stays locked until the change is committed, so that
+        // concurrent reviews can't apply a proposal twice.
+        let mut t = C::Transaction::begin_write(state.v1_state.catalog.clone()).await?;
+        let Some(proposal) = C::review_table_property_proposal(
+            table_id,
+            proposal_id,
+            status,
+            reviewed_by.as_deref(),
+            request.comment.as_deref(),
+            t.transaction(),
+        )
+        .await?
+        else {
+            t.rollback().await?;
+            return Err(ErrorModel::not_found(
+                format!("Proposal {proposal_id} does not exist or has already been reviewed"),
+                "NoSuchPendingTablePropertyProposal",
+                None,
+            )
+            .into());
+        };
+
+        if status == TablePropertyProposalStatus::Approved {
+            let mut updates = vec![];
+            if !proposal.set_properties.is_empty() {
+                updates.push(TableUpdate::SetProperties {
+                    updates: proposal.set_properties.clone(),
+                });
+            }
+            if !proposal.remove_properties.is_empty() {
+                updates.push(TableUpdate::RemoveProperties {
+                    removals: proposal.remove_properties.clone(),
+                });
+            }
+            if let Err(e) = commit_table_by_id(
+                table_id,
+                warehouse_id,
+                |_| Ok((vec![], updates)),
+                state,
+                request_metadata,
+            )
+            .await
+            {
+                t.rollback().await?;
+                return Err(e);
+            }
+            tracing::info!(
+                "Applied property proposal {proposal_id} of table {table_id} proposed by {} and approved by {}",
+                proposal.proposed_by.as_deref().unwrap_or("anonymous"),
+                proposal.reviewed_by.as_deref().unwrap_or("anonymous"),
+            );
+        }
+        t.commit().await?;
+
+        Ok(proposal)
+    }
+
+    async fn detect_pii(
+        table_id: TableId,
+        warehouse_id: WarehouseId,
+        state: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- VALIDATIONS -------------------
+        if !CONFIG.enable_pii_detection {
+            return Err(ErrorModel::bad_request(
+                "PII detection is not enabled on this server",
+                "PiiDetectionDisabled",
+                None,
+            )
+            .into());
+        }
+
+        // ------------------- AUTHZ -------------------
+        let authorizer = state.v1_state.authz.clone();
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            state.v1_state.catalog.clone(),
+        )
+        .await;
+        authorizer
+            .require_table_action(&request_metadata, table, CatalogTableAction::CanCommit)
+            .await?;
+
+        // ------------------- BUSINESS LOGIC -------------------
+        // Resubmitting while a detection is pending for the table is a no-op.
+        let mut t = C::Transaction::begin_write(state.v1_state.catalog).await?;
+        C::queue_pii_detection(
+            TaskMetadata {
+                warehouse_id,
+                parent_task_id: None,
+                entity_id: EntityId::Tabular(*table_id),
+                schedule_for: None,
+            },
+            PiiDetectionPayload {},
+            t.transaction(),
+        )
+        .await?;
+        t.commit().await?;
+
+        Ok(())
+    }
+
+    async fn get_table_compatibility(
+        table_id: TableId,
+        warehouse_id: WarehouseId,
+        query: GetTableCompatibilityQuery,
+        state: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<GetTableCompatibilityResponse> {
+        // ------------------- VALIDATIONS -------------------
+        let engines = match query.engines.as_deref() {
+            Some(engines) => engine_compatibility::parse_engines(engines)
+                .map_err(|e| ErrorModel::bad_request(e, "InvalidEngine", None))?,
+            None => CONFIG.compatibility_engines.clone(),
+        };
+
+        // ------------------- AUTHZ -------------------
+        let authorizer = state.v1_state.authz.clone();
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            state.v1_state.catalog.clone(),
+        )
+        .await;
+        authorizer
+            .require_table_action(&request_metadata, table, CatalogTableAction::CanGetMetadata)
+            .await?;
+
+        // ------------------- BUSINESS LOGIC -------------------
+        let metadata = load_table_metadata(table_id, warehouse_id, &state).await?;
+        Ok(GetTableCompatibilityResponse {
+            format_version: metadata.format_version() as u8,
+            engines: engine_compatibility::check_table(&metadata, &engines),
+        })
+    }
+
+    async fn get_maintenance_recommendations(
+        table_id: TableId,
+        warehouse_id: WarehouseId,
+        state: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<GetMaintenanceRecommendationsResponse> {
+        // ------------------- AUTHZ -------------------
+        let authorizer = state.v1_state.authz.clone();
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            state.v1_state.catalog.clone(),
+        )
+        .await;
+        let table = authorizer
+            .require_table_action(&request_metadata, table, CatalogTableAction::CanGetMetadata)
+            .await?;
+
+        // ------------------- BUSINESS LOGIC -------------------
+        let analysis = analyze_table_maintenance(&table, &state).await?;
+        Ok(GetMaintenanceRecommendationsResponse { analysis })
+    }
+
+    async fn trigger_compaction(
+        table_id: TableId,
+        warehouse_id: WarehouseId,
+        state: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<TriggerCompactionResponse> {
+        // ------------------- AUTHZ -------------------
+        let authorizer = state.v1_state.authz.clone();
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            state.v1_state.catalog.clone(),
+        )
+        .await;
+        let table = authorizer
+            .require_table_action(&request_metadata, table, CatalogTableAction::CanCommit)
+            .await?;
+
+        // ------------------- BUSINESS LOGIC -------------------
+        let analysis = analyze_table_maintenance(&table, &state).await?;
+        if !analysis.recommendations.is_empty() {
+            state
+                .v1_state
+                .hooks
+                .compaction_requested(
+                    warehouse_id,
+                    table_id,
+                    Arc::new(table.table),
+                    Arc::new(analysis.recommendations.clone()),
+                    Arc::new(request_metadata),
+                )
+                .await;
+        }
+
+        Ok(TriggerCompactionResponse {
+            snapshot_id: analysis.snapshot_id,
+            requested: analysis.recommendations,
+        })
+    }
+
+    async fn get_snapshot_expiration(
+        table_id: TableId,
+        warehouse_id: WarehouseId,
+        state: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<GetSnapshotExpirationResponse> {
+        // ------------------- AUTHZ -------------------
+        let authorizer = state.v1_state.authz.clone();
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            state.v1_state.catalog.clone(),
+        )
+        .await;
+        authorizer
+            .require_table_action(&request_metadata, table, CatalogTableAction::CanGetMetadata)
+            .await?;
+
+        // ------------------- BUSINESS LOGIC -------------------
+        let entity_id = EntityId::Tabular(*table_id);
+        let next_run = C::get_entity_task(
+            warehouse_id,
+            entity_id,
+            snapshot_expiration_queue::QUEUE_NAME,
+            state.v1_state.catalog.clone(),
+        )
+        .await?;
+        let runs = C::list_entity_task_log(
+            warehouse_id,
+            entity_id,
+            snapshot_expiration_queue::QUEUE_NAME,
+            SNAPSHOT_EXPIRATION_RUNS_LIMIT,
+            state.v1_state.catalog,
+        )
+        .await?
+        .into_iter()
+        .map(|entry| {
+            let (result, error) = match entry.status {
+                TaskOutcome::Success => (
+                    entry
+                        .message
+                        .as_deref()
+                        .and_then(|m| serde_json::from_str(m).ok()),
+                    None,
+                ),
+                TaskOutcome::Failed | TaskOutcome::Cancelled => (None, entry.message),
+            };
+            SnapshotExpirationRun {
+                task_id: *entry.task_id,
+                attempt: entry.attempt,
+                status: entry.status,
+                started_at: entry.started_at,
+                duration_ms: entry.duration_ms,
+                result,
+                error,
+            }
+        })
+        .collect();
+
+        Ok(GetSnapshotExpirationResponse { next_run, runs })
+    }
+
+    async fn cleanup_orphan_files(
+        table_id: TableId,
+        warehouse_id: WarehouseId,
+        request: CleanupOrphanFilesRequest,
+        state: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- VALIDATIONS -------------------
+        if !CONFIG.enable_orphan_file_cleanup {
+            return Err(ErrorModel::bad_request(
+                "Orphan file cleanup is not enabled on this server",
+                "OrphanFileCleanupDisabled",
+                None,
+            )
+            .into());
+        }
+
+        // ------------------- AUTHZ -------------------
+        let authorizer = state.v1_state.authz.clone();
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            state.v1_state.catalog.clone(),
+        )
+        .await;
+        authorizer
+            .require_table_action(&request_metadata, table, CatalogTableAction::CanCommit)
+            .await?;
+
+        // ------------------- BUSINESS LOGIC -------------------
+        // The scheduled run of the table is moved to now.
+        let mut t = C::Transaction::begin_write(state.v1_state.catalog).await?;
+        let task_id = C::reschedule_orphan_file_cleanup(
+            TaskMetadata {
+                warehouse_id,
+                parent_task_id: None,
+                entity_id: EntityId::Tabular(*table_id),
+                schedule_for: None,
+            },
+            OrphanFileCleanupPayload {
+                dry_run: request.dry_run,
+            },
+            t.transaction(),
+        )
+        .await?;
+        if task_id.is_none() {
+            return Err(ErrorModel::conflict(
+                "Orphan file cleanup of the table is already running",
+                "OrphanFileCleanupRunning",
+                None,
+            )
+            .into());
+        }
+        t.commit().await?;
+
+        Ok(())
+    }
+
+    async fn get_orphan_file_cleanup(
+        table_id: TableId,
+        warehouse_id: WarehouseId,
+        state: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<GetOrphanFileCleanupResponse> {
+        // ------------------- AUTHZ -------------------
+        let authorizer = state.v1_state.authz.clone();
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            state.v1_state.catalog.clone(),
+        )
+        .await;
+        authorizer
+            .require_table_action(&request_metadata, table, CatalogTableAction::CanGetMetadata)
+            .await?;
+
+        // ------------------- BUSINESS LOGIC -------------------
+        let entity_id = EntityId::Tabular(*table_id);
+        let next_run = C::get_entity_task(
+            warehouse_id,
+            entity_id,
+            orphan_file_cleanup_queue::QUEUE_NAME,
+            state.v1_state.catalog.clone(),
+        )
+        .await?;
+        let runs = C::list_entity_task_log(
+            warehouse_id,
+            entity_id,
+            orphan_file_cleanup_queue::QUEUE_NAME,
+            ORPHAN_FILE_CLEANUP_RUNS_LIMIT,
+            state.v1_state.catalog,
+        )
+        .await?
+        .into_iter()
+        .map(|entry| {
+            let (result, error) = match entry.status {
+                TaskOutcome::Success => (
+                    entry
+                        .message
+                        .as_deref()
+                        .and_then(|m| serde_json::from_str(m).ok()),
+                    None,
+                ),
+                TaskOutcome::Failed | TaskOutcome::Cancelled => (None, entry.message),
+            };
+            OrphanFileCleanupRun {
+                task_id: *entry.task_id,
+                attempt: entry.attempt,
+                status: entry.status,
+                started_at: entry.started_at,
+                duration_ms: entry.duration_ms,
+                result,
+                error,
+            }
+        })
+        .collect();
+
+        Ok(GetOrphanFileCleanupResponse { next_run, runs })
+    }
+
+    async fn begin_table_writer(
+        table_id: TableId,
+        warehouse_id: WarehouseId,
+        writer_id: String,
+        state: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<TableWriterState> {
+        // ------------------- VALIDATIONS -------------------
+        validate_table_writer_id(&writer_id)?;
+
+        // ------------------- AUTHZ -------------------
+        let authorizer = state.v1_state.authz.clone();
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            state.v1_state.catalog.clone(),
+        )
+        .await;
+        authorizer
+            .require_table_action(&request_metadata, table, CatalogTableAction::CanCommit)
+            .await?;
+
+        // ------------------- BUSINESS LOGIC -------------------
+        let mut t = C::Transaction::begin_write(state.v1_state.catalog).await?;
+        let writer =
+            C::begin_table_writer(warehouse_id, table_id, &writer_id, t.transaction()).await?;
+        t.commit().await?;
+
+        Ok(writer)
+    }
+
+    async fn prepare_table_writer_commit(
+        table_id: TableId,
+        warehouse_id: WarehouseId,
+        writer_id: String,
+        request: PrepareTableWriterCommitRequest,
+        state: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- VALIDATIONS -------------------
+        validate_table_writer_id(&writer_id)?;
+
+        // ------------------- AUTHZ -------------------
+        let authorizer = state.v1_state.authz.clone();
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            state.v1_state.catalog.clone(),
+        )
+        .await;
+        authorizer
+            .require_table_action(&request_metadata, table, CatalogTableAction::CanCommit)
+            .await?;
+
+        // ------------------- BUSINESS LOGIC -------------------
+        let writer = require_table_writer(table_id, &writer_id, &state).await?;
+        require_current_writer_epoch(&writer_id, &writer.state, request.epoch)?;
+        if is_checkpoint_committed(&writer.state, request.checkpoint_id) {
+            // Retried request of a checkpoint that has been committed since
+            return Ok(());
+        }
+
+        let commit = PreparedTableWriterCommit {
+            requirements: request.requirements,
+            updates: request.updates,
+        };
+        let mut t = C::Transaction::begin_write(state.v1_state.catalog).await?;
+        let prepared = C::prepare_table_writer_commit(
+            table_id,
+            &writer_id,
+            request.epoch,
+            request.checkpoint_id,
+            &commit,
+            t.transaction(),
+        )
+        .await?;
+        if !prepared {
+            return Err(table_writer_fenced(&writer_id, request.epoch).into());
+        }
+        t.commit().await?;
+
+        Ok(())
+    }
+
+    async fn commit_table_writer(
+        table_id: TableId,
+        warehouse_id: WarehouseId,
+        writer_id: String,
+        request: CommitTableWriterRequest,
+        state: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<CommitTableWriterResponse> {
+        // ------------------- VALIDATIONS -------------------
+        validate_table_writer_id(&writer_id)?;
+
+        // ------------------- AUTHZ -------------------
+        let authorizer = state.v1_state.authz.clone();
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            state.v1_state.catalog.clone(),
+        )
+        .await;
+        let table = authorizer
+            .require_table_action(&request_metadata, table, CatalogTableAction::CanCommit)
+            .await?;
+
+        // ------------------- BUSINESS LOGIC -------------------
+        let writer = require_table_writer(table_id, &writer_id, &state).await?;
+        require_current_writer_epoch(&writer_id, &writer.state, request.epoch)?;
+        if is_checkpoint_committed(&writer.state, request.checkpoint_id) {
+            return Ok(CommitTableWriterResponse {
+                already_committed: true,
+            });
+        }
+        let Some(commit) = writer
+            .prepared_commit
+            .filter(|_| writer.state.prepared_checkpoint == Some(request.checkpoint_id))
+        else {
+            return Err(ErrorModel::not_found(
+                format!(
+                    "Checkpoint {} of writer {writer_id} has not been prepared",
+                    request.checkpoint_id
+                ),
+                "NoSuchPreparedCheckpoint",
+                None,
+            )
+            .into());
+        };
+
+        let mut requirements = commit.requirements;
+        requirements.push(TableRequirement::UuidMatch { uuid: *table_id });
+        let result = commit_tables_with_writer_checkpoint(
+            Some(Prefix(warehouse_id.to_string())),
+            CommitTransactionRequest {
+                table_changes: vec![CommitTableRequest {
+                    identifier: Some(table.table),
+                    requirements,
+                    updates: commit.updates,
+                }],
+            },
+            Some(TableWriterCheckpoint {
+                table_id,
+                writer_id: writer_id.clone(),
+                epoch: request.epoch,
+                checkpoint_id: request.checkpoint_id,
+            }),
+            state.clone(),
+            request_metadata,
+        )
+        .await;
+
+        match result {
+            Ok(_) => Ok(CommitTableWriterResponse {
+                already_committed: false,
+            }),
+            // A concurrent request of the same writer may have committed the checkpoint
+            Err(e) if e.error.r#type == TABLE_WRITER_CONFLICT_ERROR_TYPE => {
+                let writer = require_table_writer(table_id, &writer_id, &state).await?;
+                if is_checkpoint_committed(&writer.state, request.checkpoint_id) {
+                    Ok(CommitTableWriterResponse {
+                        already_committed: true,
+                    })
+                } else {
+                    Err(e)
+                }
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn list_table_signings(
+        table_id: TableId,
+        warehouse_id: WarehouseId,
+        query: ListTableSigningsQuery,
+        state: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<ListTableSigningsResponse> {
+        // ------------------- AUTHZ -------------------
+        // Signings are kept after a table is dropped, so access is governed by the
+        // warehouse. Only those managing the storage of the warehouse may see them.
+        state
+            .v1_state
+            .authz
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanUpdateStorage,
+            )
+            .await?;
+
+        // ------------------- BUSINESS LOGIC -------------------
+        C::list_table_signings(
+            warehouse_id,
+            table_id,
+            query.pagination_query(),
+            state.v1_state.catalog,
+        )
+        .await
+    }
+
+    async fn get_table_timeline(
+        table_id: TableId,
+        warehouse_id: WarehouseId,
+        query: GetTableTimelineQuery,
+        state: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<GetTableTimelineResponse> {
+        // ------------------- AUTHZ -------------------
+        // The timeline includes drops and undrops, so soft-deleted tables are included.
+        let authorizer = state.v1_state.authz.clone();
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags {
+                include_deleted: true,
+                ..ListFlags::default()
+            },
+            state.v1_state.catalog.clone(),
+        )
+        .await;
+        authorizer
+            .require_table_action(&request_metadata, table, CatalogTableAction::CanGetMetadata)
+            .await?;
+
+        // ------------------- BUSINESS LOGIC -------------------
+        C::get_table_timeline(
+            warehouse_id,
+            table_id,
+            query.pagination_query(),
+            state.v1_state.catalog,
+        )
+        .await
+    }
+
+    async fn repair_table(
+        table_id: TableId,
+        warehouse_id: WarehouseId,
+        request: RepairTableRequest,
+        state: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<RepairTableResponse> {
+        // ------------------- AUTHZ -------------------
+        // Repairs can remove snapshots, so they require the permission to drop the table.
+        let authorizer = state.v1_state.authz.clone();
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            state.v1_state.catalog.clone(),
+        )
+        .await;
+        let table = authorizer
+            .require_table_action(&request_metadata, table, CatalogTableAction::CanDrop)
+            .await?;
+
+        // ------------------- BUSINESS LOGIC -------------------
+        let secret = maybe_get_secret(table.storage_secret_ident, &state.v1_state.secrets).await?;
+        let file_io = table.storage_profile.file_io(secret.as_ref()).await?;
+        let current = load_table(table_id, warehouse_id, &state).await?;
+
+        let mut candidates = vec![];
+        let mut readable = HashMap::new();
+        let current_file = current
+            .metadata_location
+            .as_ref()
+            .map(|l| (l.to_string(), None));
+        let log_files = current
+            .table_metadata
+            .metadata_log()
+            .iter()
+            .rev()
+            .map(|log| (log.metadata_file.clone(), Some(log.timestamp_ms)));
+        for (metadata_location, timestamp_ms) in current_file.into_iter().chain(log_files) {
+            let is_current = timestamp_ms.is_none();
+            let error = match read_candidate(&file_io, &metadata_location, table_id).await {
+                Ok(metadata) => {
+                    readable.insert(metadata_location.clone(), metadata);
+                    None
+                }
+                Err(e) => Some(e),
+            };
+            candidates.push(MetadataFileCandidate {
+                metadata_location,
+                timestamp_ms,
+                current: is_current,
+                readable: error.is_none(),
+                error,
+            });
+        }
+
+        let Some(selected) = select_repair_candidate(&candidates, request.metadata_location)?
+        else {
+            return Ok(RepairTableResponse {
+                dry_run: request.dry_run,
+                repaired: false,
+                candidates,
+                selected_metadata_location: None,
+                diff: None,
+            });
+        };
+        let new_metadata = readable.remove(&selected).ok_or_else(|| {
+            ErrorModel::bad_request(
+                format!("Metadata file '{selected}' can't be used to repair the table"),
+                "MetadataFileUnusable",
+                None,
+            )
+        })?;
+        let diff = TableRepairDiff::between(&current.table_metadata, &new_metadata);
+        if request.dry_run {
+            return Ok(RepairTableResponse {
+                dry_run: true,
+                repaired: false,
+                candidates,
+                selected_metadata_location: Some(selected),
+                diff: Some(diff),
+            });
+        }
+
+        let new_metadata_location = Location::from_str(&selected).map_err(|e| {
+            ErrorModel::bad_request(
+                format!("Invalid metadata location '{selected}'"),
+                "InvalidMetadataLocation",
+                Some(Box::new(e)),
+            )
+        })?;
+        // Files are read outside of the transaction. Concurrent commits fail the repair.
+        let mut t = C::Transaction::begin_write(state.v1_state.catalog.clone()).await?;
+        let previous = C::load_tables(warehouse_id, [table_id], false, t.transaction())
+            .await?
+            .remove(&table_id);
+        if previous.as_ref().map(|p| &p.metadata_location) != Some(&current.metadata_location) {
+            return Err(ErrorModel::conflict(
+                format!("Table {table_id} was changed during the repair"),
+                "ConcurrentTableRepair",
+                None,
+            )
+            .into());
+        }
+        // The metadata log is kept, it still documents the files written after the
+        // selected one.
+        let commit = CommitContext {
+            new_compression_codec: CompressionCodec::try_from_metadata(&new_metadata)?,
+            new_metadata,
+            new_metadata_location,
+            previous_metadata: current.table_metadata,
+            previous_metadata_location: current.metadata_location,
+            updates: vec![],
+            number_expired_metadata_log_entries: 0,
+            number_added_metadata_log_entries: 0,
+        };
+        C::commit_table_transaction(warehouse_id, [commit.commit()], t.transaction()).await?;
+        t.commit().await?;
+        invalidate_table_metadata(warehouse_id, [table_id]).await;
+        tracing::info!(
+            "Table {table_id} in warehouse {warehouse_id} was repaired to point to '{selected}'"
+        );
+
+        Ok(RepairTableResponse {
+            dry_run: false,
+            repaired: true,
+            candidates,
+            selected_metadata_location: Some(selected),
+            diff: Some(diff),
+        })
+    }
+}
+
+/// Reads a metadata file bypassing the metadata cache, which may still hold
+/// the content of a file that is gone.
+async fn read_candidate(
+    file_io: &StorageIo,
+    metadata_location: &str,
+    table_id: TableId,
+) -> std::result::Result<TableMetadata, String> {
+    let location = Location::from_str(metadata_location).map_err(|e| e.to_string())?;
+    let content = read_file(file_io, &location)
+        .await
+        .map_err(|e| e.to_string())?;
+    let metadata = serde_json::from_slice::<TableMetadata>(&content)
+        .map_err(|e| format!("Invalid table metadata: {e}"))?;
+    if TableId::from(metadata.uuid()) != table_id {
+        return Err(format!(
+            "Metadata file belongs to table {} instead of {table_id}",
+            metadata.uuid()
+        ));
+    }
+    Ok(metadata)
+}
+
+/// Selects the metadata file to repair the table with. Without an explicitly requested
+/// file, the most recent readable file of the metadata log is selected if the current
+/// metadata file is unreadable.
+fn select_repair_candidate(
+    candidates: &[MetadataFileCandidate],
+    requested: Option<String>,
+) -> Result<Option<String>> {
+    if let Some(requested) = requested {
+        if !candidates
+            .iter()
+            .any(|c| !c.current && c.metadata_location == requested)
+        {
+            return Err(ErrorModel::bad_request(
+                format!("Metadata file '{requested}' is not part of the metadata log of the table"),
+                "MetadataFileNotInLog",
+                None,
+            )
+            .into());
+        }
+        return Ok(Some(requested));
+    }
+    if candidates.iter().any(|c| c.current && c.readable) {
+        return Ok(None);
+    }
+    candidates
+        .iter()
+        .find(|c| !c.current && c.readable)
+        .map(|c| Some(c.metadata_location.clone()))
+        .ok_or_else(|| {
+            ErrorModel::conflict(
+                "None of the metadata files of the table can be read",
+                "NoReadableMetadataFile",
+                None,
+            )
+            .into()
+        })
+}
+
+/// Commits updates to a single table addressed by its id.
+///
+/// `changes` receives the current metadata of the table and returns the
+/// requirements and updates to commit. The commit goes through the regular
+/// table commit path, so hooks, contract verification and retries apply.
+async fn commit_table_by_id<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    table_id: TableId,
+    warehouse_id: WarehouseId,
+    changes: impl FnOnce(&TableMetadata) -> Result<(Vec<TableRequirement>, Vec<TableUpdate>)>,
+    state: ApiContext<State<A, C, S>>,
+    request_metadata: RequestMetadata,
+) -> Result<()> {
+    // ------------------- AUTHZ -------------------
+    let authorizer = state.v1_state.authz.clone();
+    let table = C::get_table_metadata_by_id(
+        warehouse_id,
+        table_id,
+        ListFlags::default(),
+        state.v1_state.catalog.clone(),
+    )
+    .await;
+    let table = authorizer
+        .require_table_action(&request_metadata, table, CatalogTableAction::CanCommit)
+        .await?;
+
+    // ------------------- BUSINESS LOGIC -------------------
+    let metadata = load_table_metadata(table_id, warehouse_id, &state).await?;
+    let (mut requirements, updates) = changes(&metadata)?;
+    requirements.push(TableRequirement::UuidMatch { uuid: *table_id });
+
+    commit_tables_internal(
+        Some(Prefix(warehouse_id.to_string())),
+        CommitTransactionRequest {
+            table_changes: vec![CommitTableRequest {
+                identifier: Some(table.table),
+                requirements,
+                updates,
+            }],
+        },
+        state,
+        request_metadata,
+    )
+    .await?;
+
+    Ok(())
+}
+
+fn validate_table_writer_id(writer_id: &str) -> Result<()> {
+    if writer_id.is_empty() || writer_id.len() > MAX_TABLE_WRITER_ID_LENGTH {
+        return Err(ErrorModel::bad_request(
+            format!(
+                "Writer ids must be between 1 and {MAX_TABLE_WRITER_ID_LENGTH} characters long"
+            ),
+            "InvalidTableWriterId",
+            None,
+        )
+        .into());
+    }
+    Ok(())
+}
+
+async fn require_table_writer<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    table_id: TableId,
+    writer_id: &str,
+    state: &ApiContext<State<A, C, S>>,
+) -> Result<TableWriter> {
+    C::get_table_writer(table_id, writer_id, state.v1_state.catalog.clone())
+        .await?
+        .ok_or_else(|| {
+            ErrorModel::not_found(
+                format!(
+                    "Writer {writer_id} of table {table_id} does not exist. Call `begin` first."
+                ),
+                "NoSuchTableWriter",
+                None,
+            )
+            .into()
+        })
+}
+
+fn require_current_writer_epoch(
+    writer_id: &str,
+    writer: &TableWriterState,
+    epoch: i64,
+) -> Result<()> {
+    if writer.epoch != epoch {
+        return Err(table_writer_fenced(writer_id, epoch).into());
+    }
+    Ok(())
+}
+
+fn table_writer_fenced(writer_id: &str, epoch: i64) -> ErrorModel {
+    ErrorModel::conflict(
+        format!("Epoch {epoch} of writer {writer_id} has been fenced by a newer instance"),
+        "TableWriterFenced",
+        None,
+    )
+}
+
+fn is_checkpoint_committed(writer: &TableWriterState, checkpoint_id: i64) -> bool {
+    writer
+        .committed_checkpoint
+        .is_some_and(|committed| committed >= checkpoint_id)
+}
+
+/// Loads the current metadata of a table. Authorization must be checked by the caller.
+async fn load_table_metadata<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    table_id: TableId,
+    warehouse_id: WarehouseId,
+    state: &ApiContext<State<A, C, S>>,
+) -> Result<TableMetadata> {
+    load_table(table_id, warehouse_id, state)
+        .await
+        .map(|table| table.table_metadata)
+}
+
+async fn load_table<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    table_id: TableId,
+    warehouse_id: WarehouseId,
+    state: &ApiContext<State<A, C, S>>,
+) -> Result<LoadTableResponse> {
+    let mut t = C::Transaction::begin_read(state.v1_state.catalog.clone()).await?;
+    let mut tables = C::load_tables(warehouse_id, [table_id], false, t.transaction()).await?;
+    t.commit().await?;
+    tables.remove(&table_id).ok_or_else(|| {
+        ErrorModel::not_found(
+            format!("Table with id {table_id} does not exist"),
+            "TableNotFound",
+            None,
+        )
+        .into()
+    })
+}
+
+async fn analyze_table_maintenance<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    table: &GetTableMetadataResponse,
+    state: &ApiContext<State<A, C, S>>,
+) -> Result<MaintenanceAnalysis> {
+    let metadata = load_table_metadata(table.table_id, table.warehouse_id, state).await?;
+    let secret = maybe_get_secret(table.storage_secret_ident, &state.v1_state.secrets).await?;
+    let file_io = table.storage_profile.file_io(secret.as_ref()).await?;
+    maintenance::analyze_table(&metadata, file_io.iceberg_file_io("Maintenance analysis")?).await
+}
+
+fn validate_column_tag(tag: &str) -> Result<()> {
+    if tag.is_empty() || tag.len() > MAX_COLUMN_TAG_LENGTH {
+        return Err(ErrorModel::bad_request(
+            format!("Column tags must be between 1 and {MAX_COLUMN_TAG_LENGTH} characters long"),
+            "InvalidColumnTag",
+            None,
+        )
+        .into());
+    }
+    if !tag
+        .chars()
+        .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.' | ':' | '/'))
+    {
+        return Err(ErrorModel::bad_request(
+            format!(
+                "Column tag '{tag}' contains invalid characters. Allowed are alphanumeric characters and '-', '_', '.', ':', '/'"
+            ),
+            "InvalidColumnTag",
+            None,
+        )
+        .into());
+    }
+    Ok(())
+}
+
+fn validate_table_property_proposal(request: &CreateTablePropertyProposalRequest) -> Result<()> {
+    let invalid = |message: String| ErrorModel::bad_request(message, "InvalidProposal", None);
+    if request.set_properties.is_empty() && request.remove_properties.is_empty() {
+        return Err(invalid("Proposal does not contain any changes".to_string()).into());
+    }
+    if let Some(key) = request
+        .set_properties
+        .keys()
+        .chain(&request.remove_properties)
+        .find(|key| key.trim().is_empty())
+    {
+        return Err(invalid(format!("Property name '{key}' must not be empty")).into());
+    }
+    if let Some(key) = request
+        .remove_properties
+        .iter()
+        .find(|key| request.set_properties.contains_key(*key))
+    {
+        return Err(invalid(format!("Property '{key}' is both set and removed")).into());
+    }
+    Ok(())
+}
+
+/// Returns a copy of `schema` where the docs of the given columns are replaced.
+fn schema_with_column_comments(
+    schema: &Schema,
+    comments: &HashMap<String, Option<String>>,
+) -> Result<Schema> {
+    let mut comments_by_id = HashMap::with_capacity(comments.len());
+    for (name, comment) in comments {
+        let field = schema.field_by_name(name).ok_or_else(|| {
+            ErrorModel::bad_request(
+                format!("Column '{name}' does not exist in the current schema"),
+                "ColumnNotFound",
+                None,
+            )
+        })?;
+        comments_by_id.insert(field.id, comment.clone());
+    }
+
+    let fields = schema
+        .as_struct()
+        .fields()
+        .iter()
+        .map(|field| field_with_comments(field, &comments_by_id))
+        .collect::<Vec<_>>();
+
+    Schema::builder()
+        .with_fields(fields)
+        .with_identifier_field_ids(schema.identifier_field_ids())
+        .build()
+        .map_err(|e| {
+            ErrorModel::internal(
+                "Failed to build schema with updated column comments",
+                "SchemaBuildError",
+                Some(Box::new(e)),
+            )
+            .into()
+        })
+}
+
+fn field_with_comments(
+    field: &NestedFieldRef,
+    comments: &HashMap<i32, Option<String>>,
+) -> NestedFieldRef {
+    let mut field: NestedField = field.as_ref().clone();
+    if let Some(comment) = comments.get(&field.id) {
+        field.doc.clone_from(comment);
+    }
+    field.field_type = Box::new(match *field.field_type {
+        Type::Struct(struct_type) => Type::Struct(StructType::new(
+            struct_type
+                .fields()
+                .iter()
+                .map(|f| field_with_comments(f, comments))
+                .collect(),
+        )),
+        Type::List(list_type) => Type::List(ListType::new(field_with_comments(
+            &list_type.element_field,
+            comments,
+        ))),
+        Type::Map(map_type) => Type::Map(MapType::new(
+            field_with_comments(&map_type.key_field, comments),
+            field_with_comments(&map_type.value_field, comments),
+        )),
+        primitive @ Type::Primitive(_) => primitive,
+    });
+    Arc::new(field)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use iceberg::spec::{ListType, NestedField, PrimitiveType, Schema, StructType, Type};
+
+    use super::{
+        schema_with_column_comments, validate_column_tag, validate_table_property_proposal,
+        CreateTablePropertyProposalRequest,
+    };
+
+    fn schema() -> Schema {
+        Schema::builder()
+            .with_fields(vec![
+                NestedField::required(1, "id", Type::Primitive(PrimitiveType::Long)).into(),
+                NestedField::optional(
+                    2,
+                    "address",
+                    Type::Struct(StructType::new(vec![NestedField::optional(
+                        3,
+                        "street",
+                        Type::Primitive(PrimitiveType::String),
+                    )
+                    .with_doc("old")
+                    .into()])),
+                )
+                .into(),
+                NestedField::optional(
+                    4,
+                    "tags",
+                    Type::List(ListType::new(
+                        NestedField::list_element(5, Type::Primitive(PrimitiveType::String), true)
+                            .into(),
+                    )),
+                )
+                .into(),
+            ])
+            .with_identifier_field_ids(vec![1])
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_schema_with_column_comments() {
+        let schema = schema();
+        let updated = schema_with_column_comments(
+            &schema,
+            &HashMap::from([
+                ("id".to_string(), Some("Primary key".to_string())),
+                ("address.street".to_string(), None),
+                ("tags.element".to_string(), Some("A tag".to_string())),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            updated.field_by_id(1).unwrap().doc.as_deref(),
+            Some("Primary key")
+        );
+        assert_eq!(updated.field_by_id(3).unwrap().doc, None);
+        assert_eq!(
+            updated.field_by_id(5).unwrap().doc.as_deref(),
+            Some("A tag")
+        );
+        assert_eq!(
+            updated.identifier_field_ids().collect::<Vec<_>>(),
+            schema.identifier_field_ids().collect::<Vec<_>>()
+        );
+        assert_eq!(updated.highest_field_id(), schema.highest_field_id());
+    }
+
+    #[test]
+    fn test_schema_with_column_comments_unknown_column() {
+        let err = schema_with_column_comments(
+            &schema(),
+            &HashMap::from([("does-not-exist".to_string(), None)]),
+        )
+        .unwrap_err();
+        assert_eq!(err.error.r#type, "ColumnNotFound");
+    }
+
+    #[test]
+    fn test_validate_column_tag() {
+        validate_column_tag("pii").unwrap();
+        validate_column_tag("gdpr:personal-data").unwrap();
+        validate_column_tag("").unwrap_err();
+        validate_column_tag("with space").unwrap_err();
+        validate_column_tag(&"a".repeat(129)).unwrap_err();
+    }
+
+    #[test]
+    fn test_validate_table_property_proposal() {
+        let proposal = |set: &[(&str, &str)], remove: &[&str]| CreateTablePropertyProposalRequest {
+            set_properties: set
+                .iter()
+                .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                .collect(),
+            remove_properties: remove.iter().map(ToString::to_string).collect(),
+            reason: None,
+        };
+
+        validate_table_property_proposal(&proposal(
+            &[("history.expire.max-snapshot-age-ms", "86400000")],
+            &["write.metadata.delete-after-commit.enabled"],
+        ))
+        .unwrap();
+        validate_table_property_proposal(&proposal(&[], &[])).unwrap_err();
+        validate_table_property_proposal(&proposal(&[(" ", "a")], &[])).unwrap_err();
+        validate_table_property_proposal(&proposal(&[("a", "b")], &["a"])).unwrap_err();
+    }
+
+    #[test]
+    fn test_select_repair_candidate() {
+        let candidate = |location: &str, current: bool, readable: bool| MetadataFileCandidate {
+            metadata_location: location.to_string(),
+            timestamp_ms: (!current).then_some(0),
+            current,
+            readable,
+            error: (!readable).then(|| "unreadable".to_string()),
+        };
+        let broken = [
+            candidate("s3://t/metadata/3.json", true, false),
+            candidate("s3://t/metadata/2.json", false, false),
+            candidate("s3://t/metadata/1.json", false, true),
+            candidate("s3://t/metadata/0.json", false, true),
+        ];
+        assert_eq!(
+            select_repair_candidate(&broken, None).unwrap().as_deref(),
+            Some("s3://t/metadata/1.json")
+        );
+        assert_eq!(
+            select_repair_candidate(&broken, Some("s3://t/metadata/0.json".to_string()))
+                .unwrap()
+                .as_deref(),
+            Some("s3://t/metadata/0.json")
+        );
+        let e = select_repair_candidate(&broken, Some("s3://t/metadata/3.json".to_string()))
+            .unwrap_err();
+        assert_eq!(e.error.r#type, "MetadataFileNotInLog");
+
+        let healthy = [
+            candidate("s3://t/metadata/1.json", true, true),
+            candidate("s3://t/metadata/0.json", false, true),
+        ];
+        assert_eq!(select_repair_candidate(&healthy, None).unwrap(), None);
+
+        let lost = [
+            candidate("s3://t/metadata/1.json", true, false),
+            candidate("s3://t/metadata/0.json", false, false),
+        ];
+        let e = select_repair_candidate(&lost, None).unwrap_err();
+        assert_eq!(e.error.r#type, "NoReadableMetadataFile");
+    }
 }
@@ -0,0 +1,590 @@
+use axum::{response::IntoResponse, Json};
+use chrono::{DateTime, Utc};
+use iceberg_ext::catalog::rest::ErrorModel;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::default_page_size;
+use crate::{
+    api::{
+        iceberg::v1::{PageToken, PaginationQuery},
+        management::v1::ApiServer,
+        ApiContext,
+    },
+    request_metadata::RequestMetadata,
+    service::{
+        authz::{Authorizer, CatalogServerAction, CatalogWarehouseAction},
+        task_queue::{
+            metrics::task_queue_stats, EntityId, Task, TaskCheckState, TaskId, TaskLogEntry,
+            TaskOutcome, TaskQueueStats, DEFAULT_MAX_RETRIES,
+            DEFAULT_MAX_TIME_SINCE_LAST_HEARTBEAT,
+        },
+        Catalog, Result, SecretStore, State, Transaction,
+    },
+    WarehouseId,
+};
+
+/// Upper bound for the visibility timeout an external worker may request.
+pub const MAX_VISIBILITY_TIMEOUT_SECONDS: u32 = 12 * 60 * 60;
+
+#[derive(Debug, Clone, Default, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct PollTaskRequest {
+    /// The task is handed to another worker if no heartbeat is received for this many seconds.
+    /// Defaults to the `max-seconds-since-last-heartbeat` configured for the queue.
+    /// Must not exceed 43200 (12 hours).
+    #[serde(default)]
+    pub visibility_timeout_seconds: Option<u32>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct PollTaskResponse {
+    /// The leased task. Empty if no task is currently due.
+    pub task: Option<LeasedTask>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum TaskEntityType {
+    Tabular,
+    Warehouse,
+}
+
+/// A task leased by an external worker.
+///
+/// The lease is identified by `task-id` and `attempt`. Both must be sent
+/// with every heartbeat and with the final result.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct LeasedTask {
+    pub task_id: Uuid,
+    pub queue_name: String,
+    pub warehouse_id: WarehouseId,
+    pub entity_type: TaskEntityType,
+    pub entity_id: Uuid,
+    pub parent_task_id: Option<Uuid>,
+    /// Number of times the task has been picked up, including this lease
+    pub attempt: i32,
+    /// Queue specific payload of the task
+    pub payload: serde_json::Value,
+    /// Queue configuration of the warehouse, if set
+    pub config: Option<serde_json::Value>,
+}
+
+impl From<Task> for LeasedTask {
+    fn from(task: Task) -> Self {
+        let (entity_type, entity_id) = match task.task_metadata.entity_id {
+            EntityId::Tabular(id) => (TaskEntityType::Tabular, id),
+            EntityId::Warehouse(id) => (TaskEntityType::Warehouse, id),
+        };
+        Self {
+            task_id: *task.task_id,
+            queue_name: task.queue_name,
+            warehouse_id: task.task_metadata.warehouse_id,
+            entity_type,
+            entity_id,
+            parent_task_id: task.task_metadata.parent_task_id.map(|id| *id),
+            attempt: task.attempt,
+            payload: task.state,
+            config: task.config,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct HeartbeatTaskRequest {
+    /// Attempt returned when the task was leased
+    pub attempt: i32,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct HeartbeatTaskResponse {
+    /// The task was cancelled. The worker should stop processing and report the result.
+    pub should_stop: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct CompleteTaskRequest {
+    /// Attempt returned when the task was leased
+    pub attempt: i32,
+    /// Optional message stored in the task log
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct FailTaskRequest {
+    /// Attempt returned when the task was leased
+    pub attempt: i32,
+    /// Error stored in the task log
+    pub error: String,
+}
+
+/// A task that exhausted its attempts and was moved to the dead-letter queue.
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct DeadLetterTask {
+    pub task_id: Uuid,
+    pub warehouse_id: WarehouseId,
+    pub queue_name: String,
+    pub entity_type: TaskEntityType,
+    pub entity_id: Uuid,
+    pub parent_task_id: Option<Uuid>,
+    /// Number of attempts made before the task was dead-lettered
+    pub attempt: i32,
+    /// Error of the last attempt
+    pub last_error: Option<String>,
+    /// Queue specific payload of the task
+    pub payload: serde_json::Value,
+    /// Time the task was originally enqueued
+    pub enqueued_at: DateTime<Utc>,
+    /// Time the task was moved to the dead-letter queue
+    pub dead_lettered_at: DateTime<Utc>,
+}
+
+/// A finished attempt of a task.
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TaskAttempt {
+    pub attempt: i32,
+    pub status: TaskOutcome,
+    pub started_at: Option<DateTime<Utc>>,
+    pub duration_ms: Option<i64>,
+    /// Message recorded by the task handler: the error for failed attempts
+    pub message: Option<String>,
+    pub finished_at: DateTime<Utc>,
+}
+
+impl From<TaskLogEntry> for TaskAttempt {
+    fn from(entry: TaskLogEntry) -> Self {
+        Self {
+            attempt: entry.attempt,
+            status: entry.status,
+            started_at: entry.started_at,
+            duration_ms: entry.duration_ms,
+            message: entry.message,
+            finished_at: entry.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct ListDeadLetterTasksQuery {
+    /// Only list tasks of this queue
+    #[serde(default)]
+    pub queue_name: Option<String>,
+    /// Next page token
+    #[serde(default)]
+    pub page_token: Option<String>,
+    /// Signals an upper bound of the number of results that a client will receive.
+    /// Default: 100
+    #[serde(default = "default_page_size")]
+    pub page_size: i64,
+}
+
+impl ListDeadLetterTasksQuery {
+    #[must_use]
+    pub fn pagination_query(&self) -> PaginationQuery {
+        PaginationQuery {
+            page_token: self
+                .page_token
+                .clone()
+                .map_or(PageToken::Empty, PageToken::Present),
+            page_size: Some(self.page_size),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ListDeadLetterTasksResponse {
+    /// Dead-lettered tasks, oldest first
+    pub tasks: Vec<DeadLetterTask>,
+    pub next_page_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct GetDeadLetterTaskResponse {
+    pub task: DeadLetterTask,
+    /// All recorded attempts of the task, oldest first
+    pub attempts: Vec<TaskAttempt>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct RequeueDeadLetterTaskResponse {
+    /// ID of the newly enqueued task
+    pub task_id: Uuid,
+}
+
+/// Statistics of a task queue across all warehouses.
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TaskQueueStatistics {
+    pub queue_name: String,
+    /// Tasks waiting to be picked up, including tasks scheduled for the future
+    pub depth: i64,
+    /// Waiting tasks that are due
+    pub due: i64,
+    /// Tasks currently being processed
+    pub in_flight: i64,
+    /// Seconds the oldest due task has been waiting for a worker
+    pub oldest_due_task_age_seconds: Option<i64>,
+    /// Failed attempts within the last hour
+    pub failed_last_hour: i64,
+    /// Successful attempts within the last hour
+    pub succeeded_last_hour: i64,
+    /// Share of failed attempts among all attempts finished within the last hour
+    pub failure_rate: Option<f64>,
+    /// Tasks in the dead-letter queue
+    pub dead_lettered: i64,
+}
+
+impl TaskQueueStatistics {
+    fn new(stats: TaskQueueStats, now: DateTime<Utc>) -> Self {
+        Self {
+            oldest_due_task_age_seconds: stats.oldest_due_task_age_seconds(now),
+            failure_rate: stats.failure_rate(),
+            queue_name: stats.queue_name,
+            depth: stats.scheduled,
+            due: stats.due,
+            in_flight: stats.in_flight,
+            failed_last_hour: stats.failed,
+            succeeded_last_hour: stats.succeeded,
+            dead_lettered: stats.dead_lettered,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ListTaskQueuesResponse {
+    /// Statistics of all registered queues and of all other queues holding tasks,
+    /// ordered by queue name
+    pub queues: Vec<TaskQueueStatistics>,
+}
+
+impl IntoResponse for ListTaskQueuesResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl IntoResponse for ListDeadLetterTasksResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl IntoResponse for GetDeadLetterTaskResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl IntoResponse for RequeueDeadLetterTaskResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl IntoResponse for PollTaskResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl IntoResponse for HeartbeatTaskResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
+
+#[async_trait::async_trait]
+pub(crate) trait Service<C: Catalog, A: Authorizer + Clone, S: SecretStore> {
+    async fn poll_task(
+        queue_name: String,
+        request: PollTaskRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<PollTaskResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_server_action(&request_metadata, CatalogServerAction::CanRunTasks)
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let task_queues = context.v1_state.registered_task_queues;
+        if task_queues.validate_config_fn(&queue_name).is_none() {
+            let existing_queue_names = task_queues.queue_names();
+            return Err(ErrorModel::bad_request(
+                format!(
+                    "Queue '{queue_name}' not found! Existing queues: [{existing_queue_names:?}]"
+                ),
+                "QueueNotFound",
+                None,
+            )
+            .into());
+        }
+        let visibility_timeout = request
+            .visibility_timeout_seconds
+            .map(|seconds| {
+                if seconds == 0 || seconds > MAX_VISIBILITY_TIMEOUT_SECONDS {
+                    return Err(ErrorModel::bad_request(
+                        format!(
+                            "Visibility timeout must be between 1 and {MAX_VISIBILITY_TIMEOUT_SECONDS} seconds"
+                        ),
+                        "InvalidVisibilityTimeout",
+                        None,
+                    ));
+                }
+                Ok(chrono::Duration::seconds(i64::from(seconds)))
+            })
+            .transpose()?;
+
+        let task = C::lease_task(
+            &queue_name,
+            DEFAULT_MAX_TIME_SINCE_LAST_HEARTBEAT,
+            visibility_timeout,
+            context.v1_state.catalog,
+        )
+        .await?;
+        if let Some(task) = &task {
+            tracing::debug!(
+                "Leased task {} of queue '{queue_name}' to external worker, attempt {}",
+                task.task_id,
+                task.attempt
+            );
+        }
+
+        Ok(PollTaskResponse {
+            task: task.map(LeasedTask::from),
+        })
+    }
+
+    async fn heartbeat_task(
+        task_id: TaskId,
+        request: HeartbeatTaskRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<HeartbeatTaskResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_server_action(&request_metadata, CatalogServerAction::CanRunTasks)
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        require_lease::<C>(task_id, request.attempt, transaction.transaction()).await?;
+        let state = C::check_and_heartbeat_task(task_id, transaction.transaction()).await?;
+        transaction.commit().await?;
+
+        Ok(HeartbeatTaskResponse {
+            should_stop: matches!(state, Some(TaskCheckState::Stop)),
+        })
+    }
+
+    async fn complete_task(
+        task_id: TaskId,
+        request: CompleteTaskRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_server_action(&request_metadata, CatalogServerAction::CanRunTasks)
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        require_lease::<C>(task_id, request.attempt, transaction.transaction()).await?;
+        C::record_task_success(
+            task_id,
+            request.message.as_deref(),
+            &mut transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await
+    }
+
+    async fn fail_task(
+        task_id: TaskId,
+        request: FailTaskRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_server_action(&request_metadata, CatalogServerAction::CanRunTasks)
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        require_lease::<C>(task_id, request.attempt, transaction.transaction()).await?;
+        C::record_task_failure(
+            task_id,
+            &request.error,
+            DEFAULT_MAX_RETRIES,
+            &mut transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await
+    }
+    async fn list_dead_letter_tasks(
+        warehouse_id: WarehouseId,
+        query: ListDeadLetterTasksQuery,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<ListDeadLetterTasksResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanGetTaskQueueConfig,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let pagination_query = query.pagination_query();
+        let mut transaction = C::Transaction::begin_read(context.v1_state.catalog).await?;
+        let response = C::list_dead_letter_tasks(
+            warehouse_id,
+            query.queue_name.as_deref(),
+            pagination_query,
+            transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await?;
+
+        Ok(response)
+    }
+
+    async fn get_dead_letter_task(
+        warehouse_id: WarehouseId,
+        task_id: TaskId,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<GetDeadLetterTaskResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanGetTaskQueueConfig,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_read(context.v1_state.catalog).await?;
+        let task = C::get_dead_letter_task(warehouse_id, task_id, transaction.transaction())
+            .await?
+            .ok_or_else(|| dead_letter_task_not_found(warehouse_id, task_id))?;
+        let attempts = C::list_task_attempts(warehouse_id, task_id, transaction.transaction())
+            .await?
+            .into_iter()
+            .map(TaskAttempt::from)
+            .collect();
+        transaction.commit().await?;
+
+        Ok(GetDeadLetterTaskResponse { task, attempts })
+    }
+
+    async fn requeue_dead_letter_task(
+        warehouse_id: WarehouseId,
+        task_id: TaskId,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<RequeueDeadLetterTaskResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanModifyTaskQueueConfig,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let new_task_id = C::requeue_dead_letter_task(
+            warehouse_id,
+            task_id,
+            TaskId::from(Uuid::now_v7()),
+            transaction.transaction(),
+        )
+        .await?
+        .ok_or_else(|| dead_letter_task_not_found(warehouse_id, task_id))?;
+        transaction.commit().await?;
+
+        Ok(RequeueDeadLetterTaskResponse {
+            task_id: *new_task_id,
+        })
+    }
+
+    async fn list_task_queues(
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<ListTaskQueuesResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_server_action(&request_metadata, CatalogServerAction::CanGetTaskQueueStats)
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let queue_names = context.v1_state.registered_task_queues.queue_names();
+        let stats = task_queue_stats::<C>(&queue_names, context.v1_state.catalog).await?;
+        let now = Utc::now();
+
+        Ok(ListTaskQueuesResponse {
+            queues: stats
+                .into_iter()
+                .map(|stats| TaskQueueStatistics::new(stats, now))
+                .collect(),
+        })
+    }
+}
+
+fn dead_letter_task_not_found(warehouse_id: WarehouseId, task_id: TaskId) -> ErrorModel {
+    ErrorModel::not_found(
+        format!("Dead-letter task {task_id} not found in warehouse {warehouse_id}"),
+        "DeadLetterTaskNotFound",
+        None,
+    )
+}
+
+/// Fails with `409 Conflict` if the worker no longer holds the lease, i.e. the task was
+/// finished, rescheduled or handed to another worker after the visibility timeout expired.
+async fn require_lease<C: Catalog>(
+    task_id: TaskId,
+    attempt: i32,
+    transaction: <C::Transaction as Transaction<C::State>>::Transaction<'_>,
+) -> Result<()> {
+    if C::is_task_lease_valid(task_id, attempt, transaction).await? {
+        Ok(())
+    } else {
+        Err(ErrorModel::conflict(
+            format!("Lease of attempt {attempt} of task {task_id} was lost"),
+            "TaskLeaseLost",
+            None,
+        )
+        .into())
+    }
+}
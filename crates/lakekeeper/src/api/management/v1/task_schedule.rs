@@ -0,0 +1,325 @@
+use std::str::FromStr as _;
+
+use axum::{response::IntoResponse, Json};
+use chrono::Utc;
+use iceberg_ext::catalog::rest::ErrorModel;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::default_page_size;
+use crate::{
+    api::{
+        iceberg::v1::{PageToken, PaginationQuery},
+        management::v1::ApiServer,
+        ApiContext,
+    },
+    request_metadata::RequestMetadata,
+    service::{
+        authz::{Authorizer, CatalogWarehouseAction},
+        task_queue::schedule::{
+            next_run_at, CronSchedule, TaskSchedule, TaskScheduleInput, MAX_JITTER_SECONDS,
+        },
+        Catalog, Result, SecretStore, State, Transaction,
+    },
+    WarehouseId,
+};
+
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct CreateTaskScheduleRequest {
+    /// Name of the task queue, e.g. `tabular_purge` or `snapshot_expiration`
+    pub queue_name: String,
+    /// Table or view to enqueue tasks for
+    pub tabular_id: Uuid,
+    /// Cron expression with five fields (minute hour day-of-month month day-of-week),
+    /// evaluated in UTC. Macros like `@daily` are supported.
+    pub cron: String,
+    /// Delay each run by a random duration of up to this many seconds.
+    /// Spreads load if many schedules share the same expression. Default: 0
+    #[serde(default)]
+    pub jitter_seconds: u32,
+    /// Payload of the enqueued tasks. Default: `{}`
+    #[serde(default)]
+    pub payload: Option<serde_json::Value>,
+    /// Create the schedule in paused state. Default: false
+    #[serde(default)]
+    pub paused: bool,
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct ListTaskSchedulesQuery {
+    /// Only list schedules of this queue
+    #[serde(default)]
+    pub queue_name: Option<String>,
+    /// Next page token
+    #[serde(default)]
+    pub page_token: Option<String>,
+    /// Signals an upper bound of the number of results that a client will receive.
+    /// Default: 100
+    #[serde(default = "default_page_size")]
+    pub page_size: i64,
+}
+
+impl ListTaskSchedulesQuery {
+    #[must_use]
+    pub fn pagination_query(&self) -> PaginationQuery {
+        PaginationQuery {
+            page_token: self
+                .page_token
+                .clone()
+                .map_or(PageToken::Empty, PageToken::Present),
+            page_size: Some(self.page_size),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ListTaskSchedulesResponse {
+    pub schedules: Vec<TaskSchedule>,
+    pub next_page_token: Option<String>,
+}
+
+impl IntoResponse for ListTaskSchedulesResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl IntoResponse for TaskSchedule {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
+
+#[async_trait::async_trait]
+pub(crate) trait Service<C: Catalog, A: Authorizer + Clone, S: SecretStore> {
+    async fn create_task_schedule(
+        warehouse_id: WarehouseId,
+        request: CreateTaskScheduleRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<TaskSchedule> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanModifyTaskQueueConfig,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let CreateTaskScheduleRequest {
+            queue_name,
+            tabular_id,
+            cron,
+            jitter_seconds,
+            payload,
+            paused,
+        } = request;
+
+        let task_queues = context.v1_state.registered_task_queues;
+        if task_queues.validate_config_fn(&queue_name).is_none() {
+            let existing_queue_names = task_queues.queue_names();
+            return Err(ErrorModel::bad_request(
+                format!(
+                    "Queue '{queue_name}' not found! Existing queues: [{existing_queue_names:?}]"
+                ),
+                "QueueNotFound",
+                None,
+            )
+            .into());
+        }
+        let cron_schedule = parse_cron(&cron)?;
+        if jitter_seconds > MAX_JITTER_SECONDS {
+            return Err(ErrorModel::bad_request(
+                format!("Jitter must not exceed {MAX_JITTER_SECONDS} seconds"),
+                "InvalidJitter",
+                None,
+            )
+            .into());
+        }
+        let jitter_seconds = i32::try_from(jitter_seconds).unwrap_or(i32::MAX);
+        let next_run_at = next_run_at(&cron_schedule, jitter_seconds, Utc::now())
+            .ok_or_else(|| invalid_cron(&cron, "never fires"))?;
+
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let schedule = C::create_task_schedule(
+            TaskScheduleInput {
+                schedule_id: Uuid::now_v7(),
+                warehouse_id,
+                queue_name,
+                tabular_id,
+                cron: cron_schedule.to_string(),
+                jitter_seconds,
+                payload: payload.unwrap_or_else(|| serde_json::json!({})),
+                paused,
+                next_run_at,
+            },
+            transaction.transaction(),
+        )
+        .await?
+        .ok_or_else(|| {
+            ErrorModel::not_found(
+                format!("Tabular {tabular_id} not found in warehouse {warehouse_id}"),
+                "TabularNotFound",
+                None,
+            )
+        })?;
+        transaction.commit().await?;
+
+        Ok(schedule)
+    }
+
+    async fn list_task_schedules(
+        warehouse_id: WarehouseId,
+        query: ListTaskSchedulesQuery,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<ListTaskSchedulesResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanGetTaskQueueConfig,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let pagination_query = query.pagination_query();
+        let mut transaction = C::Transaction::begin_read(context.v1_state.catalog).await?;
+        let response = C::list_task_schedules(
+            warehouse_id,
+            query.queue_name.as_deref(),
+            pagination_query,
+            transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await?;
+        Ok(response)
+    }
+
+    async fn delete_task_schedule(
+        warehouse_id: WarehouseId,
+        schedule_id: Uuid,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanModifyTaskQueueConfig,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::delete_task_schedule(warehouse_id, schedule_id, transaction.transaction())
+            .await?
+            .ok_or_else(|| schedule_not_found(schedule_id))?;
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    /// Pausing a schedule does not cancel tasks it already enqueued.
+    async fn pause_task_schedule(
+        warehouse_id: WarehouseId,
+        schedule_id: Uuid,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<TaskSchedule> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanModifyTaskQueueConfig,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let schedule = C::set_task_schedule_paused(
+            warehouse_id,
+            schedule_id,
+            true,
+            None,
+            transaction.transaction(),
+        )
+        .await?
+        .ok_or_else(|| schedule_not_found(schedule_id))?;
+        transaction.commit().await?;
+        Ok(schedule)
+    }
+
+    /// Resumed schedules fire at their next occurrence after now.
+    /// Occurrences missed while paused are not caught up.
+    async fn resume_task_schedule(
+        warehouse_id: WarehouseId,
+        schedule_id: Uuid,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<TaskSchedule> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanModifyTaskQueueConfig,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let schedule = C::get_task_schedule(warehouse_id, schedule_id, transaction.transaction())
+            .await?
+            .ok_or_else(|| schedule_not_found(schedule_id))?;
+        let cron = parse_cron(&schedule.cron)?;
+        let next_run_at = next_run_at(&cron, schedule.jitter_seconds, Utc::now())
+            .ok_or_else(|| invalid_cron(&schedule.cron, "never fires"))?;
+        let schedule = C::set_task_schedule_paused(
+            warehouse_id,
+            schedule_id,
+            false,
+            Some(next_run_at),
+            transaction.transaction(),
+        )
+        .await?
+        .ok_or_else(|| schedule_not_found(schedule_id))?;
+        transaction.commit().await?;
+        Ok(schedule)
+    }
+}
+
+fn parse_cron(cron: &str) -> Result<CronSchedule> {
+    CronSchedule::from_str(cron)
+        .map_err(|e| ErrorModel::bad_request(e, "InvalidCronExpression", None).into())
+}
+
+fn invalid_cron(cron: &str, reason: &str) -> ErrorModel {
+    ErrorModel::bad_request(
+        format!("Invalid cron expression '{cron}': {reason}"),
+        "InvalidCronExpression",
+        None,
+    )
+}
+
+fn schedule_not_found(schedule_id: Uuid) -> ErrorModel {
+    ErrorModel::not_found(
+        format!("Task schedule {schedule_id} not found"),
+        "TaskScheduleNotFound",
+        None,
+    )
+}
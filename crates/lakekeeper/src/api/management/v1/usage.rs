@@ -0,0 +1,158 @@
+use axum::{response::IntoResponse, Json};
+use chrono::{Days, NaiveDate, Utc};
+use iceberg_ext::catalog::rest::ErrorModel;
+use serde::{Deserialize, Serialize};
+
+pub use crate::service::usage::{UsageGranularity, WarehouseUsage};
+use crate::{
+    api::{management::v1::ApiServer, ApiContext},
+    request_metadata::RequestMetadata,
+    service::{
+        authz::{Authorizer, CatalogProjectAction, CatalogWarehouseAction},
+        usage::summarize_usage,
+        Catalog, Result, SecretStore, State,
+    },
+    WarehouseId,
+};
+
+/// Days returned if no start is specified.
+const DEFAULT_USAGE_DAYS: u64 = 30;
+/// Longest range that can be requested at once.
+const MAX_USAGE_DAYS: i64 = 3 * 366;
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct GetUsageStatisticsQuery {
+    /// Aggregate usage per `day` or `month`.
+    /// Default: `day`
+    #[serde(default)]
+    #[param(inline)]
+    pub granularity: UsageGranularity,
+    /// First day (UTC) to include. Months include the whole month containing this day.
+    /// Default: 30 days before `end`
+    #[serde(default)]
+    pub start: Option<NaiveDate>,
+    /// Last day (UTC) to include.
+    /// Default: today
+    #[serde(default)]
+    pub end: Option<NaiveDate>,
+    /// Only return usage of this warehouse. Otherwise, usage of all warehouses of the project
+    /// is returned.
+    #[serde(default)]
+    pub warehouse_id: Option<uuid::Uuid>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct GetUsageStatisticsResponse {
+    pub granularity: UsageGranularity,
+    /// Usage per warehouse and period, ordered by warehouse and period.
+    /// Periods without recorded usage are omitted.
+    pub usage: Vec<WarehouseUsage>,
+}
+
+impl IntoResponse for GetUsageStatisticsResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
+
+#[async_trait::async_trait]
+pub(crate) trait Service<C: Catalog, A: Authorizer + Clone, S: SecretStore> {
+    async fn get_usage_statistics(
+        query: GetUsageStatisticsQuery,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<GetUsageStatisticsResponse> {
+        let project_id = request_metadata.require_project_id(None)?;
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        match query.warehouse_id {
+            Some(warehouse_id) => {
+                authorizer
+                    .require_warehouse_action(
+                        &request_metadata,
+                        warehouse_id.into(),
+                        CatalogWarehouseAction::CanGetMetadata,
+                    )
+                    .await?;
+            }
+            None => {
+                authorizer
+                    .require_project_action(
+                        &request_metadata,
+                        &project_id,
+                        CatalogProjectAction::CanGetMetadata,
+                    )
+                    .await?;
+            }
+        }
+
+        // ------------------- Business Logic -------------------
+        let (start, end) = usage_range(query.start, query.end)?;
+        // The day before the first period is the baseline for its growth.
+        let first_day = query
+            .granularity
+            .period_start(start)
+            .checked_sub_days(Days::new(1))
+            .unwrap_or(start);
+        let days = C::list_warehouse_usage(
+            &project_id,
+            query.warehouse_id.map(WarehouseId::from),
+            first_day,
+            end,
+            context.v1_state.catalog,
+        )
+        .await?;
+
+        Ok(GetUsageStatisticsResponse {
+            granularity: query.granularity,
+            usage: summarize_usage(days, query.granularity, start),
+        })
+    }
+}
+
+fn usage_range(start: Option<NaiveDate>, end: Option<NaiveDate>) -> Result<(NaiveDate, NaiveDate)> {
+    let end = end.unwrap_or_else(|| Utc::now().date_naive());
+    let start = start.unwrap_or_else(|| {
+        end.checked_sub_days(Days::new(DEFAULT_USAGE_DAYS))
+            .unwrap_or(end)
+    });
+    if start > end {
+        return Err(ErrorModel::bad_request(
+            format!("Start {start} must not be after end {end}."),
+            "InvalidUsageRange",
+            None,
+        )
+        .into());
+    }
+    if (end - start).num_days() > MAX_USAGE_DAYS {
+        return Err(ErrorModel::bad_request(
+            format!("Usage can be requested for at most {MAX_USAGE_DAYS} days at once."),
+            "InvalidUsageRange",
+            None,
+        )
+        .into());
+    }
+    Ok((start, end))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_usage_range() {
+        let end = NaiveDate::from_ymd_opt(2025, 3, 31).unwrap();
+        let (start, _) = usage_range(None, Some(end)).unwrap();
+        assert_eq!(start, NaiveDate::from_ymd_opt(2025, 3, 1).unwrap());
+
+        let err = usage_range(Some(end), NaiveDate::from_ymd_opt(2025, 3, 30)).unwrap_err();
+        assert_eq!(err.error.r#type, "InvalidUsageRange");
+        let err = usage_range(NaiveDate::from_ymd_opt(2020, 1, 1), Some(end)).unwrap_err();
+        assert_eq!(err.error.r#type, "InvalidUsageRange");
+    }
+}
@@ -1,3 +1,8 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::Arc,
+};
+
 use axum::{response::IntoResponse, Json};
 use iceberg_ext::catalog::rest::ErrorModel;
 use serde::{Deserialize, Serialize};
@@ -6,16 +11,28 @@ use super::default_page_size;
 use crate::{
     api::{
         iceberg::v1::{PageToken, PaginationQuery},
-        management::v1::ApiServer,
+        management::v1::{ApiServer, ListSort, SortBy, SortOrder},
         ApiContext,
     },
     request_metadata::RequestMetadata,
     service::{
+        authn::invalidate_user_active,
         authz::{Authorizer, CatalogServerAction, CatalogUserAction},
         Catalog, CreateOrUpdateUserResponse, Result, SecretStore, State, Transaction, UserId,
     },
+    CONFIG,
 };
 
+/// Custom attributes of a user by key, such as their team or cost center.
+pub type UserAttributes = BTreeMap<String, String>;
+
+/// Maximum number of attributes of a single user.
+pub const MAX_USER_ATTRIBUTES: usize = 64;
+/// Maximum number of characters of an attribute key.
+pub const MAX_USER_ATTRIBUTE_KEY_LENGTH: usize = 128;
+/// Maximum number of characters of an attribute value.
+pub const MAX_USER_ATTRIBUTE_VALUE_LENGTH: usize = 1024;
+
 /// How the user was last updated
 #[derive(Debug, Serialize, utoipa::ToSchema, Clone)]
 #[serde(rename_all = "kebab-case")]
@@ -63,6 +80,14 @@ pub struct User {
     pub user_type: UserType,
     /// The endpoint that last updated the user
     pub last_updated_with: UserLastUpdatedWith,
+    /// Whether the user is active. Tokens of deactivated users are rejected.
+    pub active: bool,
+    /// Timestamp when the user was last authenticated.
+    /// Updated at most every 5 minutes.
+    pub last_seen_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Custom attributes of the user
+    #[serde(default)]
+    pub attributes: UserAttributes,
     /// Timestamp when the user was created
     pub created_at: chrono::DateTime<chrono::Utc>,
     /// Timestamp when the user was last updated
@@ -83,6 +108,9 @@ pub struct SearchUser {
     /// from the provided token.
     #[serde(default)]
     pub email: Option<String>,
+    /// Custom attributes of the user
+    #[serde(default)]
+    pub attributes: UserAttributes,
 }
 
 #[derive(Debug, Deserialize, utoipa::ToSchema, Clone)]
@@ -134,9 +162,31 @@ pub struct ListUsersQuery {
     /// Default: 100
     #[serde(default = "default_page_size")]
     pub page_size: i64,
+    /// Only list users of this type
+    #[serde(default)]
+    #[param(nullable = false, required = false)]
+    pub user_type: Option<UserType>,
+    /// Only list users that have not been seen for at least this many days.
+    /// Users that were never seen count from their creation.
+    #[serde(default, alias = "inactive_for_days")]
+    #[param(nullable = false, required = false)]
+    pub inactive_for_days: Option<u32>,
+    /// Field to sort users by. Default: created-at
+    #[serde(default, alias = "sort_by")]
+    #[param(nullable = false, required = false)]
+    pub sort_by: Option<SortBy>,
+    /// Sort order. Default: asc
+    #[serde(default)]
+    #[param(nullable = false, required = false)]
+    pub order: Option<SortOrder>,
 }
 
 impl ListUsersQuery {
+    #[must_use]
+    pub fn sort(&self) -> ListSort {
+        ListSort::from_query(self.sort_by, self.order).unwrap_or_default()
+    }
+
     #[must_use]
     pub fn pagination_query(&self) -> PaginationQuery {
         PaginationQuery {
@@ -184,6 +234,225 @@ pub struct UpdateUserRequest {
     pub email: Option<String>,
     #[serde(alias = "user_type")]
     pub user_type: UserType,
+    /// Custom attributes of the user, such as their team or cost center.
+    /// Replaces all attributes if set, attributes are unchanged if omitted.
+    /// At most 64 attributes with keys of up to 128 and values of up to 1024 characters
+    /// are allowed.
+    #[serde(default)]
+    pub attributes: Option<UserAttributes>,
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct StartImpersonationRequest {
+    /// Reason for the impersonation. Recorded for auditing.
+    #[serde(default)]
+    pub reason: Option<String>,
+    /// Lifetime of the impersonation in seconds.
+    /// Defaults to and is capped by `LAKEKEEPER__MAX_IMPERSONATION_DURATION_SECONDS`.
+    #[serde(default)]
+    pub duration_seconds: Option<u64>,
+}
+
+/// Impersonation of a user by a server admin
+///
+/// While the impersonation is active, requests of the impersonator that carry
+/// the `x-impersonation-id` header are executed as the impersonated user.
+#[derive(Debug, Serialize, utoipa::ToSchema, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct Impersonation {
+    /// ID of the impersonation. Pass as `x-impersonation-id` header.
+    pub impersonation_id: uuid::Uuid,
+    /// ID of the impersonated user
+    #[schema(value_type=String)]
+    pub user_id: UserId,
+    /// ID of the admin impersonating the user
+    #[schema(value_type=String)]
+    pub impersonator_id: UserId,
+    /// Reason for the impersonation
+    pub reason: Option<String>,
+    /// Timestamp when the impersonation was started
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Timestamp when the impersonation expires
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    /// Timestamp when the impersonation was stopped explicitly
+    pub ended_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl Impersonation {
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        self.ended_at.is_none() && self.expires_at > chrono::Utc::now()
+    }
+}
+
+impl IntoResponse for Impersonation {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::CREATED, Json(self)).into_response()
+    }
+}
+
+/// Kind of record that referenced a hard-deleted or merged user
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum UserReferenceKind {
+    /// Impersonations of the user. On hard-deletion, their reason is removed.
+    ImpersonatedUser,
+    /// Impersonations started by the user
+    Impersonator,
+    /// Column tag suggestions reviewed by the user
+    ColumnTagSuggestionReviewer,
+    /// Table property proposals made by the user
+    TablePropertyProposer,
+    /// Table property proposals reviewed by the user
+    TablePropertyReviewer,
+    /// Recertification campaigns created by the user
+    RecertificationCampaignCreator,
+    /// Recertification items that grant the user access
+    RecertificationItemPrincipal,
+    /// Recertification items decided by the user
+    RecertificationItemDecider,
+    /// Project invitations created by the user
+    InvitationCreator,
+    /// Project invitations redeemed by the user
+    InvitationRedeemer,
+    /// Catalog imports started by the user
+    CatalogImportCreator,
+    /// Audit records of S3 requests signed for the user
+    S3SignAuditPrincipal,
+    /// Lifecycle events of the server triggered by the user
+    ServerEventPrincipal,
+    /// Timeline activities of tables performed by the user
+    TableActivityPrincipal,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct UserReferenceReport {
+    pub kind: UserReferenceKind,
+    /// Number of records that were re-pointed to the tombstone or merged principal
+    pub count: i64,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct MergeUserResponse {
+    /// The user that the source user was merged into
+    pub user: User,
+    /// All references to the source user that were re-pointed to the user
+    pub references: Vec<UserReferenceReport>,
+}
+
+impl IntoResponse for MergeUserResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct HardDeleteUserResponse {
+    /// Anonymized principal that now holds the historical records of the user.
+    /// A new tombstone is created for every deleted user.
+    #[schema(value_type=String)]
+    pub tombstone_id: UserId,
+    /// All references to the user that were re-pointed to the tombstone
+    pub references: Vec<UserReferenceReport>,
+}
+
+impl IntoResponse for HardDeleteUserResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// Maximum number of users of a single bulk import.
+pub const MAX_BULK_CREATE_USERS: usize = 10_000;
+/// Number of users that are written in one transaction during a bulk import.
+const BULK_CREATE_USERS_CHUNK_SIZE: usize = 500;
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct BulkCreateUsersRequest {
+    /// Users to provision. The `id` of every user is required.
+    /// At most 10000 users can be imported at once.
+    pub users: Vec<CreateUserRequest>,
+}
+
+/// Outcome of a single user of a bulk import
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum BulkCreateUserStatus {
+    Created,
+    Updated,
+    Failed,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct BulkCreateUserResult {
+    /// ID of the user as given in the request
+    #[schema(value_type=Option<String>)]
+    pub id: Option<UserId>,
+    pub status: BulkCreateUserStatus,
+    /// The created or updated user
+    pub user: Option<User>,
+    /// Type of the error if the user could not be provisioned
+    pub error_type: Option<String>,
+    /// Reason why the user could not be provisioned
+    pub error: Option<String>,
+}
+
+impl BulkCreateUserResult {
+    fn failed(id: Option<UserId>, error: &ErrorModel) -> Self {
+        Self {
+            id,
+            status: BulkCreateUserStatus::Failed,
+            user: None,
+            error_type: Some(error.r#type.clone()),
+            error: Some(error.message.clone()),
+        }
+    }
+}
+
+impl From<CreateOrUpdateUserResponse> for BulkCreateUserResult {
+    fn from(response: CreateOrUpdateUserResponse) -> Self {
+        let (status, user) = match response {
+            CreateOrUpdateUserResponse::Created(user) => (BulkCreateUserStatus::Created, user),
+            CreateOrUpdateUserResponse::Updated(user) => (BulkCreateUserStatus::Updated, user),
+        };
+        Self {
+            id: Some(user.id.clone()),
+            status,
+            user: Some(user),
+            error_type: None,
+            error: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct BulkCreateUsersResponse {
+    /// Outcome for each user, in the order of the request
+    pub results: Vec<BulkCreateUserResult>,
+}
+
+impl IntoResponse for BulkCreateUsersResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// A validated user of a bulk import that is yet to be written.
+#[derive(Debug)]
+struct PendingUser {
+    index: usize,
+    update_if_exists: bool,
+    id: UserId,
+    name: String,
+    user_type: UserType,
+    email: Option<String>,
 }
 
 impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
@@ -311,6 +580,103 @@ pub(crate) trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
         Ok(user)
     }
 
+    async fn bulk_create_users(
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+        request: BulkCreateUsersRequest,
+    ) -> Result<BulkCreateUsersResponse> {
+        // ------------------- VALIDATIONS -------------------
+        if request.users.len() > MAX_BULK_CREATE_USERS {
+            return Err(ErrorModel::bad_request(
+                format!("At most {MAX_BULK_CREATE_USERS} users can be imported at once"),
+                "TooManyUsers",
+                None,
+            )
+            .into());
+        }
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_server_action(&request_metadata, CatalogServerAction::CanProvisionUsers)
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        // Invalid users fail individually. All others get a placeholder result that is
+        // replaced once their chunk is written.
+        let mut results = Vec::with_capacity(request.users.len());
+        let mut pending = Vec::new();
+        let mut seen_ids = HashSet::new();
+        for (index, user) in request.users.into_iter().enumerate() {
+            let id = user.id.clone();
+            match parse_bulk_user(&request_metadata, index, user, &mut seen_ids) {
+                Ok(user) => {
+                    results.push(BulkCreateUserResult::failed(
+                        id,
+                        &ErrorModel::internal("User was not processed", "UserNotProcessed", None),
+                    ));
+                    pending.push(user);
+                }
+                Err(e) => results.push(BulkCreateUserResult::failed(id, &e.error)),
+            }
+        }
+
+        for chunk in pending.chunks(BULK_CREATE_USERS_CHUNK_SIZE) {
+            let existing_ids = C::list_user(
+                Some(chunk.iter().map(|u| u.id.clone()).collect()),
+                None,
+                None,
+                None,
+                ListSort::default(),
+                PaginationQuery {
+                    page_size: Some(i64::try_from(chunk.len()).unwrap_or(i64::MAX)),
+                    page_token: PageToken::NotSpecified,
+                },
+                context.v1_state.catalog.clone(),
+            )
+            .await?
+            .users
+            .into_iter()
+            .map(|u| u.id.to_string())
+            .collect::<HashSet<_>>();
+
+            let (conflicts, to_write): (Vec<_>, Vec<_>) = chunk
+                .iter()
+                .partition(|u| !u.update_if_exists && existing_ids.contains(&u.id.to_string()));
+            for user in conflicts {
+                let error = ErrorModel::conflict(
+                    format!("User with id {} already exists.", user.id),
+                    "UserAlreadyExists",
+                    None,
+                );
+                results[user.index] = BulkCreateUserResult::failed(Some(user.id.clone()), &error);
+            }
+
+            match create_users_in_transaction::<C>(&to_write, context.v1_state.catalog.clone())
+                .await
+            {
+                Ok(responses) => {
+                    for (user, response) in to_write.iter().zip(responses) {
+                        results[user.index] = response.into();
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        "Failed to write chunk of {} users of bulk import: {:?}",
+                        to_write.len(),
+                        e.error
+                    );
+                    for user in &to_write {
+                        results[user.index] =
+                            BulkCreateUserResult::failed(Some(user.id.clone()), &e.error);
+                    }
+                }
+            }
+        }
+
+        Ok(BulkCreateUsersResponse { results })
+    }
+
     async fn search_user(
         context: ApiContext<State<A, C, S>>,
         request_metadata: RequestMetadata,
@@ -343,6 +709,9 @@ pub(crate) trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
         let users = C::list_user(
             filter_user_id,
             filter_name,
+            None,
+            None,
+            ListSort::default(),
             PaginationQuery {
                 page_size: Some(1),
                 page_token: PageToken::NotSpecified,
@@ -374,9 +743,13 @@ pub(crate) trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
         // ------------------- Business Logic -------------------
         let filter_user_id = None;
         let pagination_query = query.pagination_query();
+        let sort = query.sort();
         let users = C::list_user(
             filter_user_id,
             query.name,
+            query.user_type,
+            query.inactive_for_days,
+            sort,
             pagination_query,
             context.v1_state.catalog,
         )
@@ -394,6 +767,9 @@ pub(crate) trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
         if request.name.is_empty() {
             return Err(ErrorModel::bad_request("Name cannot be empty", "EmptyName", None).into());
         }
+        if let Some(attributes) = &request.attributes {
+            validate_user_attributes(attributes)?;
+        }
         // ------------------- AuthZ -------------------
         let authorizer = context.v1_state.authz;
         authorizer
@@ -415,10 +791,12 @@ pub(crate) trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
 
         if matches!(user, CreateOrUpdateUserResponse::Created(_)) {
             t.rollback().await?;
-            Err(ErrorModel::not_found("User does not exist", "UserNotFound", None).into())
-        } else {
-            t.commit().await
+            return Err(ErrorModel::not_found("User does not exist", "UserNotFound", None).into());
+        }
+        if let Some(attributes) = &request.attributes {
+            C::set_user_attributes(&user_id, attributes, t.transaction()).await?;
         }
+        t.commit().await
     }
 
     async fn delete_user(
@@ -446,6 +824,385 @@ pub(crate) trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
         authorizer.delete_user(&request_metadata, user_id).await?;
         t.commit().await
     }
+
+    async fn hard_delete_user(
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+        user_id: UserId,
+    ) -> Result<HardDeleteUserResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_server_action(&request_metadata, CatalogServerAction::CanDeleteUsers)
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let tombstone_id = UserId::new_tombstone();
+        let mut t = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let references =
+            C::hard_delete_user(user_id.clone(), tombstone_id.clone(), t.transaction())
+                .await?
+                .ok_or_else(|| {
+                    ErrorModel::not_found(
+                        format!("User with id {user_id} not found."),
+                        "UserNotFound",
+                        None,
+                    )
+                })?;
+        authorizer.delete_user(&request_metadata, user_id).await?;
+        t.commit().await?;
+
+        tracing::info!(
+            tombstone_id = %tombstone_id,
+            "Hard-deleted user and re-pointed {} references to tombstone",
+            references.iter().map(|r| r.count).sum::<i64>()
+        );
+
+        Ok(HardDeleteUserResponse {
+            tombstone_id,
+            references,
+        })
+    }
+
+    async fn merge_user(
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+        target_id: UserId,
+        source_id: UserId,
+    ) -> Result<MergeUserResponse> {
+        // ------------------- VALIDATIONS -------------------
+        if target_id == source_id {
+            return Err(ErrorModel::bad_request(
+                "A user cannot be merged into itself",
+                "MergeUserIntoItself",
+                None,
+            )
+            .into());
+        }
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_server_action(&request_metadata, CatalogServerAction::CanUpdateUsers)
+            .await?;
+        authorizer
+            .require_server_action(&request_metadata, CatalogServerAction::CanDeleteUsers)
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut t = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let response = C::merge_user(source_id.clone(), target_id.clone(), t.transaction())
+            .await?
+            .ok_or_else(|| {
+                ErrorModel::not_found(
+                    format!("User with id {source_id} or {target_id} not found."),
+                    "UserNotFound",
+                    None,
+                )
+            })?;
+        authorizer
+            .merge_user(&request_metadata, source_id.clone(), target_id.clone())
+            .await?;
+        t.commit().await?;
+
+        tracing::info!(
+            source_id = %source_id,
+            target_id = %target_id,
+            "Merged user and re-pointed {} references",
+            response.references.iter().map(|r| r.count).sum::<i64>()
+        );
+
+        Ok(response)
+    }
+
+    async fn deactivate_user(
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+        user_id: UserId,
+    ) -> Result<()> {
+        // ------------------- VALIDATIONS -------------------
+        if request_metadata.user_id() == Some(&user_id) {
+            return Err(ErrorModel::bad_request(
+                "Users cannot deactivate themselves",
+                "CannotDeactivateSelf",
+                None,
+            )
+            .into());
+        }
+
+        set_user_active::<C, A, S>(context, &request_metadata, user_id, false).await
+    }
+
+    async fn activate_user(
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+        user_id: UserId,
+    ) -> Result<()> {
+        set_user_active::<C, A, S>(context, &request_metadata, user_id, true).await
+    }
+
+    async fn start_impersonation(
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+        user_id: UserId,
+        request: StartImpersonationRequest,
+    ) -> Result<Impersonation> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_server_action(&request_metadata, CatalogServerAction::CanUpdateUsers)
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        if request_metadata.impersonated_by().is_some() {
+            return Err(ErrorModel::bad_request(
+                "Impersonations cannot be nested",
+                "NestedImpersonation",
+                None,
+            )
+            .into());
+        }
+        let impersonator_id = request_metadata.user_id().cloned().ok_or_else(|| {
+            ErrorModel::unauthorized(
+                "Impersonation requires an authenticated user",
+                "ImpersonationWithoutUser",
+                None,
+            )
+        })?;
+        if impersonator_id == user_id {
+            return Err(ErrorModel::bad_request(
+                "Users cannot impersonate themselves",
+                "SelfImpersonation",
+                None,
+            )
+            .into());
+        }
+
+        let users = C::list_user(
+            Some(vec![user_id.clone()]),
+            None,
+            None,
+            None,
+            ListSort::default(),
+            PaginationQuery {
+                page_size: Some(1),
+                page_token: PageToken::NotSpecified,
+            },
+            context.v1_state.catalog.clone(),
+        )
+        .await?;
+        if users.users.is_empty() {
+            return Err(ErrorModel::not_found(
+                format!("User with id {user_id} not found."),
+                "UserNotFound",
+                None,
+            )
+            .into());
+        }
+
+        let max_duration = CONFIG.max_impersonation_duration_seconds;
+        let duration = request
+            .duration_seconds
+            .and_then(|s| i64::try_from(s).ok())
+            .map_or(max_duration, chrono::Duration::seconds)
+            .min(max_duration);
+        let now = chrono::Utc::now();
+        let impersonation = Impersonation {
+            impersonation_id: uuid::Uuid::now_v7(),
+            user_id,
+            impersonator_id,
+            reason: request.reason.filter(|r| !r.is_empty()),
+            created_at: now,
+            expires_at: now + duration,
+            ended_at: None,
+        };
+
+        let mut t = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::create_impersonation(&impersonation, t.transaction()).await?;
+        t.commit().await?;
+
+        tracing::info!(
+            impersonation_id = %impersonation.impersonation_id,
+            impersonator = %impersonation.impersonator_id,
+            user = %impersonation.user_id,
+            "Impersonation started"
+        );
+        context
+            .v1_state
+            .hooks
+            .start_impersonation(Arc::new(impersonation.clone()), Arc::new(request_metadata))
+            .await;
+
+        Ok(impersonation)
+    }
+
+    async fn stop_impersonation(
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+        impersonation_id: uuid::Uuid,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        let not_found = || {
+            ErrorModel::not_found(
+                format!("Impersonation with id {impersonation_id} not found."),
+                "ImpersonationNotFound",
+                None,
+            )
+        };
+        let impersonation =
+            C::get_impersonation(impersonation_id, context.v1_state.catalog.clone())
+                .await?
+                .ok_or_else(not_found)?;
+
+        // The impersonator may stop the impersonation, even while impersonating.
+        let acting_user_id = request_metadata
+            .impersonated_by()
+            .or(request_metadata.user_id());
+        if acting_user_id != Some(&impersonation.impersonator_id) {
+            authorizer
+                .require_server_action(&request_metadata, CatalogServerAction::CanUpdateUsers)
+                .await?;
+        }
+
+        // ------------------- Business Logic -------------------
+        let mut t = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let impersonation = C::end_impersonation(impersonation_id, t.transaction())
+            .await?
+            .ok_or_else(not_found)?;
+        t.commit().await?;
+
+        tracing::info!(
+            impersonation_id = %impersonation.impersonation_id,
+            impersonator = %impersonation.impersonator_id,
+            user = %impersonation.user_id,
+            "Impersonation stopped"
+        );
+        context
+            .v1_state
+            .hooks
+            .stop_impersonation(Arc::new(impersonation), Arc::new(request_metadata))
+            .await;
+
+        Ok(())
+    }
+}
+
+/// Validates a user of a bulk import. Unlike single user provisioning, the id is required
+/// and may only be listed once.
+fn parse_bulk_user(
+    request_metadata: &RequestMetadata,
+    index: usize,
+    user: CreateUserRequest,
+    seen_ids: &mut HashSet<String>,
+) -> Result<PendingUser> {
+    let Some(id) = &user.id else {
+        return Err(ErrorModel::bad_request(
+            "The id of each user must be provided for a bulk import",
+            "MissingUserId",
+            None,
+        )
+        .into());
+    };
+    if !seen_ids.insert(id.to_string()) {
+        return Err(ErrorModel::bad_request(
+            format!("User {id} is listed more than once"),
+            "DuplicateUserId",
+            None,
+        )
+        .into());
+    }
+    let update_if_exists = user.update_if_exists;
+    let (id, name, user_type, email) = parse_create_user_request(request_metadata, Some(user))?;
+    Ok(PendingUser {
+        index,
+        update_if_exists,
+        id,
+        name,
+        user_type,
+        email,
+    })
+}
+
+/// Creates or updates all users in a single transaction.
+/// If any user fails, none of them are written.
+async fn create_users_in_transaction<C: Catalog>(
+    users: &[&PendingUser],
+    catalog_state: C::State,
+) -> Result<Vec<CreateOrUpdateUserResponse>> {
+    let mut t = C::Transaction::begin_write(catalog_state).await?;
+    let mut responses = Vec::with_capacity(users.len());
+    for user in users {
+        responses.push(
+            C::create_or_update_user(
+                &user.id,
+                &user.name,
+                user.email.as_deref(),
+                UserLastUpdatedWith::CreateEndpoint,
+                user.user_type,
+                t.transaction(),
+            )
+            .await?,
+        );
+    }
+    t.commit().await?;
+    Ok(responses)
+}
+
+async fn set_user_active<C: Catalog, A: Authorizer, S: SecretStore>(
+    context: ApiContext<State<A, C, S>>,
+    request_metadata: &RequestMetadata,
+    user_id: UserId,
+    active: bool,
+) -> Result<()> {
+    // ------------------- AuthZ -------------------
+    context
+        .v1_state
+        .authz
+        .require_server_action(request_metadata, CatalogServerAction::CanUpdateUsers)
+        .await?;
+
+    // ------------------- Business Logic -------------------
+    let mut t = C::Transaction::begin_write(context.v1_state.catalog).await?;
+    C::set_user_active(user_id.clone(), active, t.transaction())
+        .await?
+        .ok_or_else(|| {
+            ErrorModel::not_found(
+                format!("User with id {user_id} not found."),
+                "UserNotFound",
+                None,
+            )
+        })?;
+    t.commit().await?;
+    invalidate_user_active(&user_id).await;
+
+    tracing::info!(user_id = %user_id, active, "Changed activation of user");
+    Ok(())
+}
+
+fn validate_user_attributes(attributes: &UserAttributes) -> Result<()> {
+    let invalid = |message: String| ErrorModel::bad_request(message, "InvalidUserAttributes", None);
+    if attributes.len() > MAX_USER_ATTRIBUTES {
+        return Err(invalid(format!(
+            "At most {MAX_USER_ATTRIBUTES} attributes are allowed per user, got {}.",
+            attributes.len()
+        ))
+        .into());
+    }
+    for (key, value) in attributes {
+        if key.is_empty() || key.chars().count() > MAX_USER_ATTRIBUTE_KEY_LENGTH {
+            return Err(invalid(format!(
+                "Attribute keys must have between 1 and {MAX_USER_ATTRIBUTE_KEY_LENGTH} characters."
+            ))
+            .into());
+        }
+        if value.chars().count() > MAX_USER_ATTRIBUTE_VALUE_LENGTH {
+            return Err(invalid(format!(
+                "Value of attribute '{key}' must have at most {MAX_USER_ATTRIBUTE_VALUE_LENGTH} characters."
+            )).into());
+        }
+    }
+    Ok(())
 }
 
 fn is_self_provisioning(acting_user_id: Option<&UserId>, request_id: Option<&UserId>) -> bool {
@@ -466,6 +1223,34 @@ mod test {
 
     use super::*;
 
+    #[test]
+    fn test_validate_user_attributes() {
+        let valid = UserAttributes::from([
+            ("team".to_string(), "data-platform".to_string()),
+            ("slack".to_string(), "@alice".to_string()),
+        ]);
+        validate_user_attributes(&valid).unwrap();
+
+        let invalid = [
+            UserAttributes::from([(String::new(), "value".to_string())]),
+            UserAttributes::from([(
+                "k".repeat(MAX_USER_ATTRIBUTE_KEY_LENGTH + 1),
+                "value".to_string(),
+            )]),
+            UserAttributes::from([(
+                "key".to_string(),
+                "v".repeat(MAX_USER_ATTRIBUTE_VALUE_LENGTH + 1),
+            )]),
+            (0..=MAX_USER_ATTRIBUTES)
+                .map(|i| (format!("key-{i}"), "value".to_string()))
+                .collect(),
+        ];
+        for attributes in invalid {
+            let e = validate_user_attributes(&attributes).unwrap_err();
+            assert_eq!(e.error.r#type, "InvalidUserAttributes");
+        }
+    }
+
     #[test]
     fn test_deserialize_create_user_request() {
         // Test minimal request with just user-type
@@ -507,4 +1292,77 @@ mod test {
         assert_eq!(request.email, None);
         assert_eq!(request.id, None);
     }
+
+    #[sqlx::test]
+    async fn test_bulk_create_users(pool: sqlx::PgPool) {
+        use crate::{
+            implementations::postgres::{PostgresCatalog, SecretsState},
+            service::authz::AllowAllAuthorizer,
+        };
+
+        let ctx = crate::tests::get_api_context(&pool, AllowAllAuthorizer);
+        let user = |id: Option<&str>, name: &str, update_if_exists: bool| CreateUserRequest {
+            update_if_exists,
+            name: Some(name.to_string()),
+            email: None,
+            user_type: Some(UserType::Application),
+            id: id.map(|id| UserId::try_from(id).unwrap()),
+        };
+        let bulk_create = |users: Vec<CreateUserRequest>| {
+            ApiServer::<PostgresCatalog, AllowAllAuthorizer, SecretsState>::bulk_create_users(
+                ctx.clone(),
+                crate::tests::random_request_metadata(),
+                BulkCreateUsersRequest { users },
+            )
+        };
+
+        let response = bulk_create(vec![
+            user(Some("oidc~app-1"), "App 1", false),
+            user(None, "No Id", false),
+            user(Some("oidc~app-2"), "App 2", false),
+            user(Some("oidc~app-1"), "App 1 again", false),
+        ])
+        .await
+        .unwrap();
+        let statuses = response
+            .results
+            .iter()
+            .map(|r| r.status)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            statuses,
+            vec![
+                BulkCreateUserStatus::Created,
+                BulkCreateUserStatus::Failed,
+                BulkCreateUserStatus::Created,
+                BulkCreateUserStatus::Failed,
+            ]
+        );
+        assert_eq!(
+            response.results[1].error_type.as_deref(),
+            Some("MissingUserId")
+        );
+        assert_eq!(
+            response.results[3].error_type.as_deref(),
+            Some("DuplicateUserId")
+        );
+
+        let response = bulk_create(vec![
+            user(Some("oidc~app-1"), "App 1", false),
+            user(Some("oidc~app-2"), "App 2 renamed", true),
+            user(Some("oidc~app-3"), "App 3", false),
+        ])
+        .await
+        .unwrap();
+        assert_eq!(
+            response.results[0].error_type.as_deref(),
+            Some("UserAlreadyExists")
+        );
+        assert_eq!(response.results[1].status, BulkCreateUserStatus::Updated);
+        assert_eq!(
+            response.results[1].user.as_ref().unwrap().name,
+            "App 2 renamed"
+        );
+        assert_eq!(response.results[2].status, BulkCreateUserStatus::Created);
+    }
 }
@@ -1,13 +1,109 @@
+use std::{collections::HashMap, sync::Arc};
+
+use axum::{response::IntoResponse, Json};
+use http::StatusCode;
+use iceberg::spec::{SqlViewRepresentation, ViewMetadata, ViewRepresentation, ViewVersion};
+use iceberg_ext::catalog::{rest::ViewUpdate, AssertViewUuid, ViewRequirement};
+use itertools::Itertools as _;
+use serde::{Deserialize, Serialize};
+
 use super::{ApiServer, ProtectionResponse};
 use crate::{
-    api::{ApiContext, RequestMetadata, Result},
+    api::{
+        iceberg::v1::{CommitViewRequest, DataAccess, Prefix, ViewParameters},
+        ApiContext, ErrorModel, RequestMetadata, Result,
+    },
+    catalog::views::commit_view,
     service::{
         authz::{Authorizer, CatalogViewAction},
-        Catalog, SecretStore, State, TabularId, Transaction, ViewId,
+        view_lineage::ViewLineageSource,
+        Catalog, SecretStore, State, TableIdent, TabularId, Transaction, ViewId,
     },
     WarehouseId,
 };
 
+/// Number of unchanged lines shown around each change of a SQL diff.
+const SQL_DIFF_CONTEXT_LINES: usize = 3;
+
+/// SQL of a view version in a single dialect
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ViewSqlRepresentation {
+    pub dialect: String,
+    pub sql: String,
+}
+
+/// Change of the SQL of a dialect compared to the preceding version
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ViewSqlDiff {
+    pub dialect: String,
+    /// Unified diff from the SQL of the preceding version to the SQL of this version.
+    /// Dialects that were added or removed are diffed against an empty SQL.
+    pub diff: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ViewVersionHistoryEntry {
+    pub version_id: i32,
+    pub schema_id: i32,
+    pub timestamp_ms: i64,
+    pub summary: HashMap<String, String>,
+    pub representations: Vec<ViewSqlRepresentation>,
+    /// Dialects whose SQL changed compared to the preceding version.
+    /// Empty for the first version.
+    pub sql_diffs: Vec<ViewSqlDiff>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ListViewVersionsResponse {
+    pub current_version_id: i32,
+    /// All versions of the view, oldest first
+    pub versions: Vec<ViewVersionHistoryEntry>,
+}
+
+impl IntoResponse for ListViewVersionsResponse {
+    fn into_response(self) -> axum::response::Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct RollbackViewRequest {
+    /// Existing version of the view that becomes the current version.
+    pub version_id: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct RollbackViewResponse {
+    /// Version that was current before the rollback
+    pub previous_version_id: i32,
+    pub current_version_id: i32,
+}
+
+impl IntoResponse for RollbackViewResponse {
+    fn into_response(self) -> axum::response::Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct GetViewLineageResponse {
+    /// Tables referenced by the SQL of the current version of the view
+    pub sources: Vec<ViewLineageSource>,
+}
+
+impl IntoResponse for GetViewLineageResponse {
+    fn into_response(self) -> axum::response::Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
 impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> ViewManagementService<C, A, S>
     for ApiServer<C, A, S>
 {
@@ -65,4 +161,343 @@ where
         t.commit().await?;
         Ok(status)
     }
+
+    async fn list_view_versions(
+        view_id: ViewId,
+        warehouse_id: WarehouseId,
+        state: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<ListViewVersionsResponse> {
+        // ------------------- AUTHZ -------------------
+        authorized_view_ident(
+            warehouse_id,
+            view_id,
+            CatalogViewAction::CanGetMetadata,
+            &state,
+            &request_metadata,
+        )
+        .await?;
+
+        // ------------------- BUSINESS LOGIC -------------------
+        let mut t = C::Transaction::begin_read(state.v1_state.catalog).await?;
+        let metadata = C::load_view(view_id, false, t.transaction())
+            .await?
+            .metadata;
+        t.commit().await?;
+
+        Ok(ListViewVersionsResponse {
+            current_version_id: metadata.current_version_id(),
+            versions: view_version_history(&metadata),
+        })
+    }
+
+    async fn get_view_lineage(
+        view_id: ViewId,
+        warehouse_id: WarehouseId,
+        state: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<GetViewLineageResponse> {
+        // ------------------- AUTHZ -------------------
+        authorized_view_ident(
+            warehouse_id,
+            view_id,
+            CatalogViewAction::CanGetMetadata,
+            &state,
+            &request_metadata,
+        )
+        .await?;
+
+        // ------------------- BUSINESS LOGIC -------------------
+        let mut t = C::Transaction::begin_read(state.v1_state.catalog).await?;
+        let sources = C::get_view_lineage(warehouse_id, view_id, t.transaction()).await?;
+        t.commit().await?;
+
+        Ok(GetViewLineageResponse { sources })
+    }
+
+    async fn rollback_view(
+        view_id: ViewId,
+        warehouse_id: WarehouseId,
+        request: RollbackViewRequest,
+        state: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<RollbackViewResponse> {
+        // ------------------- AUTHZ -------------------
+        let view = authorized_view_ident(
+            warehouse_id,
+            view_id,
+            CatalogViewAction::CanCommit,
+            &state,
+            &request_metadata,
+        )
+        .await?;
+
+        // ------------------- BUSINESS LOGIC -------------------
+        let mut t = C::Transaction::begin_read(state.v1_state.catalog.clone()).await?;
+        let metadata = C::load_view(view_id, false, t.transaction())
+            .await?
+            .metadata;
+        t.commit().await?;
+
+        let previous_version_id = metadata.current_version_id();
+        if metadata.version_by_id(request.version_id).is_none() {
+            return Err(ErrorModel::not_found(
+                format!(
+                    "Version {} does not exist for view with id {view_id}",
+                    request.version_id
+                ),
+                "ViewVersionNotFound",
+                None,
+            )
+            .into());
+        }
+        if request.version_id == previous_version_id {
+            return Err(ErrorModel::bad_request(
+                format!(
+                    "Version {} is already the current version of view with id {view_id}",
+                    request.version_id
+                ),
+                "ViewVersionAlreadyCurrent",
+                None,
+            )
+            .into());
+        }
+
+        // Moving the pointer goes through the regular commit path,
+        // so contract verification, retries and the `updateView` event apply.
+        let result = commit_view(
+            ViewParameters {
+                prefix: Some(Prefix(warehouse_id.to_string())),
+                view: view.clone(),
+            },
+            CommitViewRequest {
+                identifier: Some(view.clone()),
+                requirements: Some(vec![ViewRequirement::AssertViewUuid(AssertViewUuid {
+                    uuid: *view_id,
+                })]),
+                updates: vec![ViewUpdate::SetCurrentViewVersion {
+                    view_version_id: request.version_id,
+                }],
+            },
+            state.clone(),
+            DataAccess::not_specified(),
+            request_metadata.clone(),
+        )
+        .await?;
+
+        let rollback = RollbackViewResponse {
+            previous_version_id,
+            current_version_id: result.metadata.current_version_id(),
+        };
+        tracing::info!(
+            "Rolled back view {view_id} in warehouse {warehouse_id} from version {previous_version_id} to version {}",
+            rollback.current_version_id
+        );
+        state
+            .v1_state
+            .hooks
+            .rollback_view(
+                warehouse_id,
+                view_id,
+                Arc::new(view),
+                Arc::new(rollback.clone()),
+                Arc::new(request_metadata),
+            )
+            .await;
+
+        Ok(rollback)
+    }
+}
+
+/// Resolves the identifier of a view by its id and requires `action` on it.
+async fn authorized_view_ident<C: Catalog, A: Authorizer, S: SecretStore>(
+    warehouse_id: WarehouseId,
+    view_id: ViewId,
+    action: CatalogViewAction,
+    state: &ApiContext<State<A, C, S>>,
+    request_metadata: &RequestMetadata,
+) -> Result<TableIdent> {
+    let mut t = C::Transaction::begin_read(state.v1_state.catalog.clone()).await?;
+    let view = C::view_id_to_ident(warehouse_id, view_id, t.transaction()).await?;
+    t.commit().await?;
+
+    state
+        .v1_state
+        .authz
+        .require_view_action(request_metadata, Ok(view.as_ref().map(|_| view_id)), action)
+        .await?;
+
+    // Missing views are already rejected by the authorizer
+    view.ok_or_else(|| {
+        ErrorModel::not_found(
+            format!("View with id {view_id} does not exist"),
+            "ViewNotFound",
+            None,
+        )
+        .into()
+    })
+}
+
+/// Versions of the view ordered by version id, each with the SQL diffs to its predecessor.
+fn view_version_history(metadata: &ViewMetadata) -> Vec<ViewVersionHistoryEntry> {
+    let mut previous: Option<Vec<ViewSqlRepresentation>> = None;
+    metadata
+        .versions()
+        .sorted_by_key(|version| version.version_id())
+        .map(|version| {
+            let representations = sql_representations(version);
+            let sql_diffs = previous
+                .as_deref()
+                .map(|previous| sql_diffs(previous, &representations))
+                .unwrap_or_default();
+            previous = Some(representations.clone());
+            ViewVersionHistoryEntry {
+                version_id: version.version_id(),
+                schema_id: version.schema_id(),
+                timestamp_ms: version.timestamp_ms(),
+                summary: version.summary().clone(),
+                representations,
+                sql_diffs,
+            }
+        })
+        .collect()
+}
+
+fn sql_representations(version: &ViewVersion) -> Vec<ViewSqlRepresentation> {
+    version
+        .representations()
+        .iter()
+        .map(|representation| {
+            let ViewRepresentation::Sql(SqlViewRepresentation { sql, dialect }) = representation;
+            ViewSqlRepresentation {
+                dialect: dialect.clone(),
+                sql: sql.clone(),
+            }
+        })
+        .collect()
+}
+
+fn sql_diffs(
+    previous: &[ViewSqlRepresentation],
+    current: &[ViewSqlRepresentation],
+) -> Vec<ViewSqlDiff> {
+    let sql_of = |representations: &[ViewSqlRepresentation], dialect: &str| {
+        representations
+            .iter()
+            .find(|r| r.dialect == dialect)
+            .map_or(String::new(), |r| r.sql.clone())
+    };
+
+    current
+        .iter()
+        .chain(previous)
+        .map(|r| r.dialect.as_str())
+        .unique()
+        .filter_map(|dialect| {
+            let old = sql_of(previous, dialect);
+            let new = sql_of(current, dialect);
+            (old != new).then(|| ViewSqlDiff {
+                dialect: dialect.to_string(),
+                diff: similar::TextDiff::from_lines(&old, &new)
+                    .unified_diff()
+                    .context_radius(SQL_DIFF_CONTEXT_LINES)
+                    .missing_newline_hint(false)
+                    .to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn representation(dialect: &str, sql: &str) -> ViewSqlRepresentation {
+        ViewSqlRepresentation {
+            dialect: dialect.to_string(),
+            sql: sql.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_sql_diffs() {
+        let previous = vec![
+            representation("spark", "SELECT id,\nname\nFROM t\n"),
+            representation("trino", "SELECT id FROM t"),
+        ];
+        let current = vec![
+            representation("spark", "SELECT id,\nemail\nFROM t\n"),
+            representation("trino", "SELECT id FROM t"),
+            representation("duckdb", "SELECT id FROM t"),
+        ];
+
+        let diffs = sql_diffs(&previous, &current);
+        assert_eq!(diffs.len(), 2);
+        assert_eq!(diffs[0].dialect, "spark");
+        assert_eq!(
+            diffs[0].diff,
+            "@@ -1,3 +1,3 @@\n SELECT id,\n-name\n+email\n FROM t\n"
+        );
+        assert_eq!(diffs[1].dialect, "duckdb");
+        assert_eq!(diffs[1].diff, "@@ -0,0 +1 @@\n+SELECT id FROM t\n");
+
+        assert!(sql_diffs(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn test_view_version_history() {
+        let metadata: ViewMetadata = serde_json::from_value(serde_json::json!({
+            "format-version": 1,
+            "view-uuid": uuid::Uuid::now_v7().to_string(),
+            "location": "s3://bucket/view",
+            "current-version-id": 2,
+            "versions": [
+                {
+                    "version-id": 2,
+                    "schema-id": 0,
+                    "timestamp-ms": 1_719_559_081_510_i64,
+                    "summary": {},
+                    "representations": [
+                        {"type": "sql", "sql": "select id from t", "dialect": "spark"}
+                    ],
+                    "default-namespace": []
+                },
+                {
+                    "version-id": 1,
+                    "schema-id": 0,
+                    "timestamp-ms": 1_719_559_079_091_i64,
+                    "summary": {},
+                    "representations": [
+                        {"type": "sql", "sql": "select id, name from t", "dialect": "spark"}
+                    ],
+                    "default-namespace": []
+                }
+            ],
+            "version-log": [
+                {"version-id": 1, "timestamp-ms": 1_719_559_079_095_i64},
+                {"version-id": 2, "timestamp-ms": 1_719_559_081_515_i64}
+            ],
+            "schemas": [
+                {"schema-id": 0, "type": "struct", "fields": [
+                    {"id": 0, "name": "id", "required": false, "type": "long"}
+                ]}
+            ],
+            "properties": {}
+        }))
+        .unwrap();
+
+        let history = view_version_history(&metadata);
+        assert_eq!(
+            history.iter().map(|v| v.version_id).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert!(history[0].sql_diffs.is_empty());
+        assert_eq!(
+            history[1].sql_diffs,
+            vec![ViewSqlDiff {
+                dialect: "spark".to_string(),
+                diff: "@@ -1 +1 @@\n-select id, name from t\n+select id from t\n".to_string(),
+            }]
+        );
+    }
 }
@@ -11,9 +11,12 @@ use utoipa::ToSchema;
 
 use super::{default_page_size, DeleteWarehouseQuery, ProtectionResponse};
 pub use crate::service::{
+    client_compatibility::ClientWorkaround,
+    endpoint_policy::EndpointGroup,
     storage::{
-        AdlsProfile, AzCredential, GcsCredential, GcsProfile, GcsServiceKey, S3Credential,
-        S3Profile, StorageCredential, StorageProfile,
+        AdlsProfile, AzCredential, GcsCredential, GcsExternalAccountConfig, GcsProfile,
+        GcsServiceKey, S3Credential, S3Profile, S3Quirks, S3QuirksProbe, S3Vendor,
+        StorageCredential, StorageProfile,
     },
     WarehouseStatus,
 };
@@ -22,19 +25,33 @@ use crate::{
         iceberg::v1::{PageToken, PaginationQuery},
         management::v1::{
             ApiServer, DeletedTabularResponse, GetWarehouseStatisticsQuery,
-            ListDeletedTabularsResponse,
+            ListDeletedTabularsResponse, ListSort, SortBy, SortOrder, TabularType,
         },
         ApiContext, Result,
     },
-    catalog::UnfilteredPage,
+    catalog::{invalidate_warehouse_caches, UnfilteredPage},
     request_metadata::RequestMetadata,
     service::{
         authz::{Authorizer, CatalogProjectAction, CatalogWarehouseAction},
-        secrets::SecretStore,
-        task_queue::TaskFilter,
-        Catalog, ListFlags, NamespaceId, State, TableId, TabularId, Transaction,
+        client_compatibility::invalidate_warehouse_client_workarounds,
+        default_branch::{invalidate_default_branches, validate_default_branch},
+        endpoint_policy::invalidate_warehouse_disabled_groups,
+        event_publisher::routing::{
+            invalidate_event_routing_rules, validate_event_routing_rules, EventRoutingRule,
+        },
+        labels::{matches_label_selectors, parse_label_selectors, Labels},
+        quotas::require_warehouse_quota,
+        secrets::{SecretIdent, SecretStore},
+        storage_policy::{
+            require_storage_policy, storage_policy_checks_credentials, storage_policy_violations,
+        },
+        task_queue::{
+            catalog_snapshot_export_queue::maybe_queue_catalog_snapshot_export, TaskFilter,
+        },
+        warehouse_slug::{invalidate_warehouse_slugs, validate_warehouse_slug},
+        Catalog, ListFlags, NamespaceId, State, TableId, TabularId, TabularInfo, Transaction,
     },
-    ProjectId, WarehouseId, DEFAULT_PROJECT_ID,
+    ProjectId, WarehouseId, CONFIG, DEFAULT_PROJECT_ID,
 };
 
 #[derive(Debug, Deserialize, utoipa::IntoParams)]
@@ -44,6 +61,24 @@ pub struct ListDeletedTabularsQuery {
     #[serde(default)]
     #[param(value_type=uuid::Uuid)]
     pub namespace_id: Option<NamespaceId>,
+    /// Only list tables or only list views
+    #[serde(default)]
+    pub tabular_type: Option<TabularType>,
+    /// Only list tabulars whose name contains this string (case-insensitive)
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Only list tabulars deleted at or after this time
+    #[serde(default)]
+    pub deleted_after: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only list tabulars deleted before this time
+    #[serde(default)]
+    pub deleted_before: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only list tabulars that expire before this time
+    #[serde(default)]
+    pub expires_before: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only list protected or only list unprotected tabulars
+    #[serde(default)]
+    pub protected: Option<bool>,
     /// Next page token
     #[serde(default)]
     pub page_token: Option<String>,
@@ -53,7 +88,50 @@ pub struct ListDeletedTabularsQuery {
     pub page_size: i64,
 }
 
+impl Default for ListDeletedTabularsQuery {
+    fn default() -> Self {
+        Self {
+            namespace_id: None,
+            tabular_type: None,
+            name: None,
+            deleted_after: None,
+            deleted_before: None,
+            expires_before: None,
+            protected: None,
+            page_token: None,
+            page_size: default_page_size(),
+        }
+    }
+}
+
 impl ListDeletedTabularsQuery {
+    /// Whether a soft-deleted tabular matches all filters except `namespace_id`,
+    /// which is applied by the catalog.
+    fn matches(&self, id: TabularId, info: &TabularInfo) -> bool {
+        let Some(deletion) = &info.deletion_details else {
+            return false;
+        };
+        self.tabular_type
+            .map_or(true, |t| t == TabularType::from(id))
+            && self.name.as_ref().map_or(true, |name| {
+                info.table_ident
+                    .inner()
+                    .name
+                    .to_lowercase()
+                    .contains(&name.to_lowercase())
+            })
+            && self
+                .deleted_after
+                .map_or(true, |t| deletion.deleted_at >= t)
+            && self
+                .deleted_before
+                .map_or(true, |t| deletion.deleted_at < t)
+            && self
+                .expires_before
+                .map_or(true, |t| deletion.expiration_date < t)
+            && self.protected.map_or(true, |p| info.protected == p)
+    }
+
     #[must_use]
     pub fn pagination_query(&self) -> PaginationQuery {
         PaginationQuery {
@@ -175,6 +253,25 @@ pub struct ListWarehousesRequest {
     #[serde(default)]
     #[param(value_type=Option::<String>)]
     pub project_id: Option<ProjectId>,
+    /// Optional comma separated label selectors. Only warehouses matching all
+    /// selectors are returned. `key` requires the label to be set,
+    /// `key=value` requires it to have the value, for example `team=sales,pii`.
+    #[serde(default)]
+    #[param(nullable = false, required = false)]
+    pub label: Option<String>,
+    /// Optional filter to return only protected or only unprotected warehouses.
+    #[serde(default)]
+    #[param(nullable = false, required = false)]
+    pub protected: Option<bool>,
+    /// Field to sort warehouses by. If neither `sortBy` nor `order` is set,
+    /// warehouses are returned in no particular order.
+    #[serde(default, alias = "sort_by")]
+    #[param(nullable = false, required = false)]
+    pub sort_by: Option<SortBy>,
+    /// Sort order. Default: asc
+    #[serde(default)]
+    #[param(nullable = false, required = false)]
+    pub order: Option<SortOrder>,
 }
 
 #[derive(Debug, Clone, serde::Deserialize, ToSchema)]
@@ -190,6 +287,78 @@ pub struct UpdateWarehouseDeleteProfileRequest {
     pub delete_profile: TabularDeleteProfile,
 }
 
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct SetWarehouseDisabledEndpointGroupsRequest {
+    /// Endpoint groups to disable for the warehouse. Replaces the
+    /// previously disabled groups; an empty list enables all groups again.
+    pub disabled_endpoint_groups: Vec<EndpointGroup>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct SetWarehouseClientWorkaroundsRequest {
+    /// Client workarounds to enable for the warehouse. Replaces the
+    /// previously enabled workarounds; an empty list disables all of them.
+    pub client_workarounds: Vec<ClientWorkaround>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct SetWarehouseDefaultBranchRequest {
+    /// Branch that `loadTable` serves as `main` for tables of the warehouse.
+    /// Tables without the branch are served as stored. `null` serves `main`.
+    /// Namespaces can override it with the `default_branch` property.
+    #[serde(default)]
+    pub default_branch: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct SetWarehouseMetadataPushRequest {
+    /// Whether schema, ownership and property changes of tables in the warehouse are
+    /// pushed to the metadata platform configured for the server.
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct SetWarehouseSlugRequest {
+    /// Slug of the warehouse-scoped catalog URL `/catalog/w/{slug}/v1`.
+    /// Lowercase letters, digits and hyphens, at most 63 characters.
+    /// The previous slug remains an alias of the warehouse.
+    pub slug: String,
+}
+
+/// Replaces all event routing rules of the warehouse.
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct SetWarehouseEventRoutingRequest {
+    /// Rules routing events of the warehouse to specific sinks and topics.
+    /// An event is published for every matching rule. Events matching no rule
+    /// are published to all sinks with their configured topics.
+    pub rules: Vec<EventRoutingRule>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct GetWarehouseEventRoutingResponse {
+    pub rules: Vec<EventRoutingRule>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct GetWarehouseSlugResponse {
+    /// Current slug of the warehouse. `null` if no slug is set.
+    pub slug: Option<String>,
+    /// Catalog URI to configure in engines, `{base-uri}/catalog/w/{slug}`.
+    /// `null` if no slug is set.
+    pub catalog_uri: Option<String>,
+    /// Previous slugs of the warehouse, newest first.
+    /// Requests to these slugs are routed to the warehouse as well.
+    pub aliases: Vec<String>,
+}
+
 #[derive(Debug, Clone, serde::Deserialize, ToSchema)]
 #[serde(rename_all = "kebab-case")]
 pub struct RenameProjectRequest {
@@ -215,6 +384,23 @@ pub struct GetWarehouseResponse {
     pub status: WarehouseStatus,
     /// Whether the warehouse is protected from being deleted.
     pub protected: bool,
+    /// Endpoint groups that are disabled for the warehouse.
+    pub disabled_endpoint_groups: Vec<EndpointGroup>,
+    /// Client workarounds that are enabled for the warehouse.
+    pub client_workarounds: Vec<ClientWorkaround>,
+    /// Branch that is served as `main` when loading tables of the warehouse.
+    pub default_branch: Option<String>,
+    /// Whether table changes are pushed to the metadata platform configured for the server.
+    pub metadata_push_enabled: bool,
+    /// Labels of the warehouse.
+    pub labels: Labels,
+    /// Time the warehouse was created.
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Time the warehouse was last updated.
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Storage settings of the warehouse that are deprecated by the storage policy of the server.
+    /// The warehouse keeps working, but should be migrated to supported settings.
+    pub storage_policy_violations: Vec<String>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, ToSchema)]
@@ -232,6 +418,30 @@ pub struct UpdateWarehouseCredentialRequest {
     pub new_storage_credential: Option<StorageCredential>,
 }
 
+/// Default time the previous credential remains available after a rotation.
+pub const DEFAULT_CREDENTIAL_ROTATION_GRACE_PERIOD_SECONDS: u64 = 15 * 60;
+/// Maximum time the previous credential remains available after a rotation.
+pub const MAX_CREDENTIAL_ROTATION_GRACE_PERIOD_SECONDS: u64 = 24 * 60 * 60;
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct RotateStorageCredentialRequest {
+    /// New storage credential to use for the warehouse.
+    pub new_storage_credential: StorageCredential,
+    /// Seconds the previous credential remains available for in-flight requests
+    /// before it is deleted. Defaults to 900, at most 86400.
+    #[serde(default)]
+    pub grace_period_seconds: Option<u64>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct RotateStorageCredentialResponse {
+    /// Point in time at which the previous credential is deleted.
+    /// `null` if the warehouse had no credential before the rotation.
+    pub previous_credential_retires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
 impl axum::response::IntoResponse for CreateWarehouseResponse {
     fn into_response(self) -> axum::http::Response<axum::body::Body> {
         (http::StatusCode::CREATED, axum::Json(self)).into_response()
@@ -311,6 +521,8 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
         // ------------------- Business Logic -------------------
         validate_warehouse_name(&warehouse_name)?;
         storage_profile.normalize(storage_credential.as_ref())?;
+        require_storage_policy(None, &storage_profile, storage_credential.as_ref(), None)?;
+        require_warehouse_quota::<C>(&project_id, context.v1_state.catalog.clone()).await?;
 
         // Run validation and overlap check in parallel
         let validation_future =
@@ -373,6 +585,8 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
         authorizer
             .create_warehouse(&request_metadata, warehouse_id, &project_id)
             .await?;
+        maybe_queue_catalog_snapshot_export::<C>(warehouse_id, None, transaction.transaction())
+            .await?;
 
         transaction.commit().await?;
 
@@ -384,6 +598,14 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
         context: ApiContext<State<A, C, S>>,
         request_metadata: RequestMetadata,
     ) -> Result<ListWarehousesResponse> {
+        // ------------------- VALIDATIONS -------------------
+        let label_selectors = request
+            .label
+            .as_deref()
+            .map(parse_label_selectors)
+            .transpose()?
+            .unwrap_or_default();
+
         // ------------------- AuthZ -------------------
         let project_id = request_metadata.require_project_id(request.project_id)?;
 
@@ -402,6 +624,14 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
         let warehouses =
             C::list_warehouses(&project_id, request.warehouse_status, trx.transaction()).await?;
         trx.commit().await?;
+        let mut warehouses = warehouses
+            .into_iter()
+            .filter(|w| request.protected.map_or(true, |p| w.protected == p))
+            .filter(|w| matches_label_selectors(&label_selectors, &w.labels))
+            .collect::<Vec<_>>();
+        if let Some(sort) = ListSort::from_query(request.sort_by, request.order) {
+            sort_warehouses(&mut warehouses, sort);
+        }
 
         let warehouses = futures::future::try_join_all(warehouses.iter().map(|w| {
             authorizer.is_allowed_warehouse_action(
@@ -413,14 +643,12 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
         .await?
         .into_iter()
         .zip(warehouses.into_iter())
-        .filter_map(|(allowed, warehouse)| {
-            if allowed {
-                Some(warehouse.into())
-            } else {
-                None
-            }
-        })
-        .collect();
+        .filter_map(|(allowed, warehouse)| if allowed { Some(warehouse) } else { None });
+        let secrets = &context.v1_state.secrets;
+        let warehouses = futures::future::join_all(
+            warehouses.map(|warehouse| with_storage_policy_violations(warehouse, secrets)),
+        )
+        .await;
 
         Ok(ListWarehousesResponse { warehouses })
     }
@@ -442,9 +670,9 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
 
         // ------------------- Business Logic -------------------
         let mut transaction = C::Transaction::begin_read(context.v1_state.catalog).await?;
-        let warehouses = C::require_warehouse(warehouse_id, transaction.transaction()).await?;
+        let warehouse = C::require_warehouse(warehouse_id, transaction.transaction()).await?;
         transaction.commit().await?;
-        Ok(warehouses.into())
+        Ok(with_storage_policy_violations(warehouse, &context.v1_state.secrets).await)
     }
 
     async fn get_warehouse_statistics(
@@ -495,6 +723,7 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
             .delete_warehouse(&request_metadata, warehouse_id)
             .await?;
         transaction.commit().await?;
+        invalidate_warehouse_caches(warehouse_id).await;
 
         Ok(())
     }
@@ -552,6 +781,87 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
         Ok(())
     }
 
+    async fn get_warehouse_slug(
+        warehouse_id: WarehouseId,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<GetWarehouseSlugResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanGetMetadata,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_read(context.v1_state.catalog).await?;
+        let slugs = C::list_warehouse_slugs(warehouse_id, transaction.transaction()).await?;
+        transaction.commit().await?;
+
+        let (current, aliases): (Vec<_>, Vec<_>) = slugs.into_iter().partition(|s| s.is_current);
+        let slug = current.into_iter().next().map(|s| s.slug);
+        Ok(GetWarehouseSlugResponse {
+            catalog_uri: slug
+                .as_ref()
+                .map(|slug| format!("{}/w/{slug}", request_metadata.base_uri_catalog())),
+            slug,
+            aliases: aliases.into_iter().map(|s| s.slug).collect(),
+        })
+    }
+
+    async fn set_warehouse_slug(
+        warehouse_id: WarehouseId,
+        request: SetWarehouseSlugRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanRename,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        validate_warehouse_slug(&request.slug)?;
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::set_warehouse_slug(warehouse_id, &request.slug, transaction.transaction()).await?;
+        transaction.commit().await?;
+        invalidate_warehouse_slugs();
+
+        Ok(())
+    }
+
+    async fn delete_warehouse_slug(
+        warehouse_id: WarehouseId,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanRename,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::delete_warehouse_slugs(warehouse_id, transaction.transaction()).await?;
+        transaction.commit().await?;
+        invalidate_warehouse_slugs();
+
+        Ok(())
+    }
+
     async fn update_warehouse_delete_profile(
         warehouse_id: WarehouseId,
         request: UpdateWarehouseDeleteProfileRequest,
@@ -577,6 +887,202 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
         )
         .await?;
         transaction.commit().await?;
+        invalidate_warehouse_caches(warehouse_id).await;
+
+        Ok(())
+    }
+
+    async fn set_warehouse_disabled_endpoint_groups(
+        warehouse_id: WarehouseId,
+        request: SetWarehouseDisabledEndpointGroupsRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanUpdateStorage,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let disabled_endpoint_groups = request
+            .disabled_endpoint_groups
+            .into_iter()
+            .sorted()
+            .dedup()
+            .collect::<Vec<_>>();
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::set_warehouse_disabled_endpoint_groups(
+            warehouse_id,
+            &disabled_endpoint_groups,
+            transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await?;
+        invalidate_warehouse_caches(warehouse_id).await;
+        invalidate_warehouse_disabled_groups(warehouse_id).await;
+
+        Ok(())
+    }
+
+    async fn set_warehouse_client_workarounds(
+        warehouse_id: WarehouseId,
+        request: SetWarehouseClientWorkaroundsRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanUpdateStorage,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let client_workarounds = request
+            .client_workarounds
+            .into_iter()
+            .sorted()
+            .dedup()
+            .collect::<Vec<_>>();
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::set_warehouse_client_workarounds(
+            warehouse_id,
+            &client_workarounds,
+            transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await?;
+        invalidate_warehouse_client_workarounds(warehouse_id).await;
+
+        Ok(())
+    }
+
+    async fn set_warehouse_default_branch(
+        warehouse_id: WarehouseId,
+        request: SetWarehouseDefaultBranchRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanUpdateStorage,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        if let Some(default_branch) = &request.default_branch {
+            validate_default_branch(default_branch)?;
+        }
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::set_warehouse_default_branch(
+            warehouse_id,
+            request.default_branch.as_deref(),
+            transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await?;
+        invalidate_default_branches(warehouse_id).await;
+
+        Ok(())
+    }
+
+    async fn set_warehouse_metadata_push(
+        warehouse_id: WarehouseId,
+        request: SetWarehouseMetadataPushRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanUpdateStorage,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        if request.enabled && CONFIG.metadata_push_backend.is_none() {
+            return Err(ErrorModel::bad_request(
+                "No metadata platform is configured for this server. Set `LAKEKEEPER__METADATA_PUSH_BACKEND` to enable metadata push.",
+                "MetadataPushNotConfigured",
+                None,
+            )
+            .into());
+        }
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::set_warehouse_metadata_push(warehouse_id, request.enabled, transaction.transaction())
+            .await?;
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn get_warehouse_event_routing(
+        warehouse_id: WarehouseId,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<GetWarehouseEventRoutingResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanGetMetadata,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let rules = C::get_warehouse_event_routing_rules(warehouse_id, context.v1_state.catalog)
+            .await?
+            .ok_or_else(|| {
+                ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None)
+            })?;
+
+        Ok(GetWarehouseEventRoutingResponse { rules })
+    }
+
+    async fn set_warehouse_event_routing(
+        warehouse_id: WarehouseId,
+        request: SetWarehouseEventRoutingRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanUpdateStorage,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        validate_event_routing_rules(&request.rules)?;
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::set_warehouse_event_routing_rules(
+            warehouse_id,
+            &request.rules,
+            transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await?;
+        invalidate_event_routing_rules(warehouse_id).await;
 
         Ok(())
     }
@@ -607,6 +1113,7 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
         .await?;
 
         transaction.commit().await?;
+        invalidate_warehouse_caches(warehouse_id).await;
 
         Ok(())
     }
@@ -637,6 +1144,7 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
         .await?;
 
         transaction.commit().await?;
+        invalidate_warehouse_caches(warehouse_id).await;
 
         Ok(())
     }
@@ -670,8 +1178,17 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
 
         let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
         let warehouse = C::require_warehouse(warehouse_id, transaction.transaction()).await?;
-        let storage_profile = warehouse.storage_profile.update_with(storage_profile)?;
         let old_secret_id = warehouse.storage_secret_id;
+        let current_credential =
+            storage_policy_credential(&context.v1_state.secrets, old_secret_id).await?;
+        let current_profile = warehouse.storage_profile.clone();
+        let storage_profile = warehouse.storage_profile.update_with(storage_profile)?;
+        require_storage_policy(
+            Some(warehouse_id),
+            &storage_profile,
+            storage_credential.as_ref(),
+            Some((&current_profile, current_credential.as_ref())),
+        )?;
 
         let secret_id = if let Some(storage_credential) = storage_credential {
             Some(
@@ -694,6 +1211,7 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
         .await?;
 
         transaction.commit().await?;
+        invalidate_warehouse_caches(warehouse_id).await;
 
         // Delete the old secret if it exists - never fail the request if the deletion fails
         if let Some(old_secret_id) = old_secret_id {
@@ -711,6 +1229,55 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
         Ok(())
     }
 
+    async fn probe_storage_quirks(
+        warehouse_id: WarehouseId,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<S3QuirksProbe> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanUpdateStorage,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_read(context.v1_state.catalog).await?;
+        let warehouse = C::require_warehouse(warehouse_id, transaction.transaction()).await?;
+        transaction.commit().await?;
+
+        let StorageProfile::S3(storage_profile) = warehouse.storage_profile else {
+            return Err(ErrorModel::bad_request(
+                "Storage quirks can only be probed for S3 storage profiles",
+                "StorageProfileNotS3",
+                None,
+            )
+            .into());
+        };
+
+        let storage_credential = if let Some(secret_id) = warehouse.storage_secret_id {
+            Some(
+                context
+                    .v1_state
+                    .secrets
+                    .get_secret_by_id::<StorageCredential>(secret_id)
+                    .await?
+                    .secret,
+            )
+        } else {
+            None
+        };
+        let s3_credential = storage_credential
+            .as_ref()
+            .map(StorageCredential::try_to_s3)
+            .transpose()?;
+
+        Ok(storage_profile.probe_quirks(s3_credential).await?)
+    }
+
     async fn update_storage_credential(
         warehouse_id: WarehouseId,
         request: UpdateWarehouseCredentialRequest,
@@ -736,6 +1303,14 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
         let warehouse = C::require_warehouse(warehouse_id, transaction.transaction()).await?;
         let old_secret_id = warehouse.storage_secret_id;
         let storage_profile = warehouse.storage_profile;
+        let current_credential =
+            storage_policy_credential(&context.v1_state.secrets, old_secret_id).await?;
+        require_storage_policy(
+            Some(warehouse_id),
+            &storage_profile,
+            new_storage_credential.as_ref(),
+            Some((&storage_profile, current_credential.as_ref())),
+        )?;
 
         storage_profile
             .validate_access(new_storage_credential.as_ref(), None, &request_metadata)
@@ -762,6 +1337,7 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
         .await?;
 
         transaction.commit().await?;
+        invalidate_warehouse_caches(warehouse_id).await;
 
         // Delete the old secret if it exists - never fail the request if the deletion fails
         if let Some(old_secret_id) = old_secret_id {
@@ -779,6 +1355,98 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
         Ok(())
     }
 
+    async fn rotate_storage_credential(
+        warehouse_id: WarehouseId,
+        request: RotateStorageCredentialRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<RotateStorageCredentialResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanUpdateStorageCredential,
+            )
+            .await?;
+
+        // ------------------- Validations -------------------
+        let RotateStorageCredentialRequest {
+            new_storage_credential,
+            grace_period_seconds,
+        } = request;
+        let grace_period_seconds =
+            grace_period_seconds.unwrap_or(DEFAULT_CREDENTIAL_ROTATION_GRACE_PERIOD_SECONDS);
+        if grace_period_seconds > MAX_CREDENTIAL_ROTATION_GRACE_PERIOD_SECONDS {
+            return Err(ErrorModel::bad_request(
+                format!(
+                    "Grace period must not exceed {MAX_CREDENTIAL_ROTATION_GRACE_PERIOD_SECONDS} seconds"
+                ),
+                "GracePeriodTooLong",
+                None,
+            )
+            .into());
+        }
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let warehouse = C::require_warehouse(warehouse_id, transaction.transaction()).await?;
+        let old_secret_id = warehouse.storage_secret_id;
+        let storage_profile = warehouse.storage_profile;
+        let current_credential =
+            storage_policy_credential(&context.v1_state.secrets, old_secret_id).await?;
+        require_storage_policy(
+            Some(warehouse_id),
+            &storage_profile,
+            Some(&new_storage_credential),
+            Some((&storage_profile, current_credential.as_ref())),
+        )?;
+
+        storage_profile
+            .validate_access(Some(&new_storage_credential), None, &request_metadata)
+            .await?;
+
+        let secret_id = context
+            .v1_state
+            .secrets
+            .create_secret(new_storage_credential)
+            .await?;
+
+        C::update_storage_profile(
+            warehouse_id,
+            storage_profile,
+            Some(secret_id),
+            transaction.transaction(),
+        )
+        .await?;
+
+        // The previous secret is deleted by a background worker once the grace period ends
+        let previous_credential_retires_at = if let Some(old_secret_id) = old_secret_id {
+            let retire_at = chrono::Utc::now()
+                + chrono::Duration::seconds(
+                    i64::try_from(grace_period_seconds).unwrap_or(i64::MAX),
+                );
+            C::retire_storage_secret(
+                warehouse_id,
+                old_secret_id,
+                retire_at,
+                transaction.transaction(),
+            )
+            .await?;
+            Some(retire_at)
+        } else {
+            None
+        };
+
+        transaction.commit().await?;
+        invalidate_warehouse_caches(warehouse_id).await;
+
+        Ok(RotateStorageCredentialResponse {
+            previous_credential_retires_at,
+        })
+    }
+
     async fn undrop_tabulars(
         warehouse_id: WarehouseId,
         request_metadata: RequestMetadata,
@@ -852,6 +1520,7 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
         // ------------------- Business Logic -------------------
         let pagination_query = query.pagination_query();
         let namespace_id = query.namespace_id;
+        let query = Arc::new(query);
         let mut t = C::Transaction::begin_read(catalog.clone()).await?;
         let (tabulars, idents, next_page_token) =
             crate::catalog::fetch_until_full_page::<_, _, _, C>(
@@ -860,6 +1529,7 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
                 |page_size, page_token, t| {
                     let authorizer = authorizer.clone();
                     let request_metadata = request_metadata.clone();
+                    let filter = Arc::clone(&query);
                     async move {
                         let query = PaginationQuery {
                             page_size: Some(page_size),
@@ -882,18 +1552,38 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
                             Vec<_>,
                             Vec<_>,
                             Vec<bool>,
-                        ) = futures::future::try_join_all(ids.iter().map(|tid| match tid {
-                            TabularId::View(id) => authorizer.is_allowed_view_action(
-                                &request_metadata,
-                                (*id).into(),
-                                crate::service::authz::CatalogViewAction::CanIncludeInList,
-                            ),
-                            TabularId::Table(id) => authorizer.is_allowed_table_action(
-                                &request_metadata,
-                                (*id).into(),
-                                crate::service::authz::CatalogTableAction::CanIncludeInList,
-                            ),
-                        }))
+                        ) = futures::future::try_join_all(ids.iter().zip(idents.iter()).map(
+                            |(tid, info)| {
+                                let matches = filter.matches(*tid, info);
+                                let authorizer = &authorizer;
+                                let request_metadata = &request_metadata;
+                                async move {
+                                    if !matches {
+                                        return Ok(false);
+                                    }
+                                    match tid {
+                                        TabularId::View(id) => {
+                                            authorizer
+                                                .is_allowed_view_action(
+                                                    request_metadata,
+                                                    (*id).into(),
+                                                    crate::service::authz::CatalogViewAction::CanIncludeInList,
+                                                )
+                                                .await
+                                        }
+                                        TabularId::Table(id) => {
+                                            authorizer
+                                                .is_allowed_table_action(
+                                                    request_metadata,
+                                                    (*id).into(),
+                                                    crate::service::authz::CatalogTableAction::CanIncludeInList,
+                                                )
+                                                .await
+                                        }
+                                    }
+                                }
+                            },
+                        ))
                         .await?
                         .into_iter()
                         .zip(idents.into_iter().zip(ids.into_iter()))
@@ -992,6 +1682,26 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
             .into());
         }
 
+        if request.max_attempts.is_some_and(|attempts| attempts < 1) {
+            return Err(ErrorModel::bad_request(
+                "max-attempts must be at least 1",
+                "InvalidQueueConfig",
+                None,
+            )
+            .into());
+        }
+        if request
+            .retry_backoff_seconds
+            .is_some_and(|seconds| !(0..=86400).contains(&seconds))
+        {
+            return Err(ErrorModel::bad_request(
+                "retry-backoff-seconds must be between 0 and 86400",
+                "InvalidQueueConfig",
+                None,
+            )
+            .into());
+        }
+
         let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
 
         C::set_task_queue_config(
@@ -1040,6 +1750,14 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
 pub struct SetTaskQueueConfigRequest {
     pub queue_config: QueueConfig,
     pub max_seconds_since_last_heartbeat: Option<i64>,
+    /// Number of attempts after which a failing task is moved to the dead-letter queue.
+    /// Keeps the current value if not set. Default: 5
+    #[serde(default)]
+    pub max_attempts: Option<i32>,
+    /// Delay before a failed task is retried. Doubles with every attempt, up to one day.
+    /// Keeps the current value if not set. Default: 0
+    #[serde(default)]
+    pub retry_backoff_seconds: Option<i64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -1051,6 +1769,8 @@ pub struct QueueConfig(pub(crate) serde_json::Value);
 pub struct GetTaskQueueConfigResponse {
     pub queue_config: QueueConfigResponse,
     pub max_seconds_since_last_heartbeat: Option<i64>,
+    pub max_attempts: Option<i32>,
+    pub retry_backoff_seconds: Option<i64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -1079,8 +1799,21 @@ impl axum::response::IntoResponse for GetWarehouseResponse {
     }
 }
 
+impl axum::response::IntoResponse for GetWarehouseEventRoutingResponse {
+    fn into_response(self) -> axum::http::Response<axum::body::Body> {
+        axum::Json(self).into_response()
+    }
+}
+
+impl axum::response::IntoResponse for GetWarehouseSlugResponse {
+    fn into_response(self) -> axum::http::Response<axum::body::Body> {
+        axum::Json(self).into_response()
+    }
+}
+
 impl From<crate::service::GetWarehouseResponse> for GetWarehouseResponse {
     fn from(warehouse: crate::service::GetWarehouseResponse) -> Self {
+        let storage_policy_violations = storage_policy_violations(&warehouse.storage_profile, None);
         Self {
             id: warehouse.id.to_uuid(),
             name: warehouse.name,
@@ -1089,10 +1822,80 @@ impl From<crate::service::GetWarehouseResponse> for GetWarehouseResponse {
             status: warehouse.status,
             delete_profile: warehouse.tabular_delete_profile,
             protected: warehouse.protected,
+            disabled_endpoint_groups: warehouse.disabled_endpoint_groups,
+            client_workarounds: warehouse.client_workarounds,
+            default_branch: warehouse.default_branch,
+            metadata_push_enabled: warehouse.metadata_push_enabled,
+            labels: warehouse.labels,
+            created_at: warehouse.created_at,
+            updated_at: warehouse.updated_at,
+            storage_policy_violations,
         }
     }
 }
 
+/// Loads the credential of a warehouse if the storage policy has rules for credentials.
+async fn storage_policy_credential<S: SecretStore>(
+    secrets: &S,
+    secret_id: Option<SecretIdent>,
+) -> Result<Option<StorageCredential>> {
+    match secret_id {
+        Some(secret_id) if storage_policy_checks_credentials() => Ok(Some(
+            secrets
+                .get_secret_by_id::<StorageCredential>(secret_id)
+                .await?
+                .secret,
+        )),
+        _ => Ok(None),
+    }
+}
+
+/// Converts a warehouse into a response that includes violations of the storage policy,
+/// including those of its credential. If the credential cannot be loaded, only the storage
+/// profile is checked.
+async fn with_storage_policy_violations<S: SecretStore>(
+    warehouse: crate::service::GetWarehouseResponse,
+    secrets: &S,
+) -> GetWarehouseResponse {
+    let credential = storage_policy_credential(secrets, warehouse.storage_secret_id)
+        .await
+        .map_err(|e| {
+            tracing::warn!(
+                "Failed to load storage credential of warehouse {} to check the storage policy: {:?}",
+                warehouse.id,
+                e.error
+            );
+        })
+        .ok()
+        .flatten();
+    let storage_policy_violations =
+        storage_policy_violations(&warehouse.storage_profile, credential.as_ref());
+    GetWarehouseResponse {
+        storage_policy_violations,
+        ..warehouse.into()
+    }
+}
+
+/// Sorts warehouses in place. Warehouses with the same sort key are ordered by id.
+fn sort_warehouses(warehouses: &mut [crate::service::GetWarehouseResponse], sort: ListSort) {
+    warehouses.sort_by(|a, b| {
+        let ordering = match sort.by {
+            SortBy::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            SortBy::CreatedAt => a.created_at.cmp(&b.created_at),
+            SortBy::UpdatedAt => a
+                .updated_at
+                .unwrap_or(a.created_at)
+                .cmp(&b.updated_at.unwrap_or(b.created_at)),
+        }
+        .then_with(|| a.id.cmp(&b.id));
+        if sort.is_desc() {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+}
+
 fn validate_warehouse_name(warehouse_name: &str) -> Result<()> {
     if warehouse_name.is_empty() {
         return Err(ErrorModel::bad_request(
@@ -1153,6 +1956,60 @@ mod test {
         assert_eq!(s3_profile.path_style_access, Some(true));
     }
 
+    #[test]
+    fn test_list_deleted_tabulars_query_matches() {
+        use iceberg::{NamespaceIdent, TableIdent};
+
+        use crate::service::{DeletionDetails, TabularId, TabularIdentOwned, TabularInfo};
+
+        let deleted_at = chrono::Utc::now();
+        let id = TabularId::Table(uuid::Uuid::now_v7());
+        let info = TabularInfo {
+            table_ident: TabularIdentOwned::Table(TableIdent::new(
+                NamespaceIdent::new("ns".to_string()),
+                "My_Table".to_string(),
+            )),
+            deletion_details: Some(DeletionDetails {
+                expiration_task_id: uuid::Uuid::now_v7(),
+                expiration_date: deleted_at + chrono::Duration::days(7),
+                deleted_at,
+                created_at: deleted_at - chrono::Duration::days(1),
+            }),
+            protected: false,
+            external: false,
+        };
+
+        let matches = |query: super::ListDeletedTabularsQuery| query.matches(id, &info);
+        assert!(matches(super::ListDeletedTabularsQuery::default()));
+        assert!(matches(super::ListDeletedTabularsQuery {
+            tabular_type: Some(super::TabularType::Table),
+            name: Some("my_t".to_string()),
+            deleted_after: Some(deleted_at),
+            expires_before: Some(deleted_at + chrono::Duration::days(8)),
+            ..Default::default()
+        }));
+        assert!(!matches(super::ListDeletedTabularsQuery {
+            tabular_type: Some(super::TabularType::View),
+            ..Default::default()
+        }));
+        assert!(!matches(super::ListDeletedTabularsQuery {
+            name: Some("other".to_string()),
+            ..Default::default()
+        }));
+        assert!(!matches(super::ListDeletedTabularsQuery {
+            deleted_before: Some(deleted_at),
+            ..Default::default()
+        }));
+        assert!(!matches(super::ListDeletedTabularsQuery {
+            expires_before: Some(deleted_at),
+            ..Default::default()
+        }));
+        assert!(!matches(super::ListDeletedTabularsQuery {
+            protected: Some(true),
+            ..Default::default()
+        }));
+    }
+
     use iceberg::TableIdent;
     use itertools::Itertools;
     use sqlx::PgPool;
@@ -1336,6 +2193,7 @@ mod test {
                 namespace_id: None,
                 page_size: 11,
                 page_token: None,
+                ..Default::default()
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -1351,6 +2209,7 @@ mod test {
                 namespace_id: None,
                 page_size: 10,
                 page_token: None,
+                ..Default::default()
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -1366,6 +2225,7 @@ mod test {
                 namespace_id: None,
                 page_size: 10,
                 page_token: all.next_page_token,
+                ..Default::default()
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -1381,6 +2241,7 @@ mod test {
                 namespace_id: None,
                 page_size: 6,
                 page_token: None,
+                ..Default::default()
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -1406,6 +2267,7 @@ mod test {
                 namespace_id: None,
                 page_size: 6,
                 page_token: first_six.next_page_token,
+                ..Default::default()
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -1439,6 +2301,7 @@ mod test {
                 namespace_id: None,
                 page_size: 5,
                 page_token: None,
+                ..Default::default()
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -1465,6 +2328,7 @@ mod test {
                 namespace_id: None,
                 page_size: 6,
                 page_token: page.next_page_token,
+                ..Default::default()
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -0,0 +1,568 @@
+//! Export of the logical content of a warehouse to a portable manifest, and import of such
+//! a manifest into a warehouse of another Lakekeeper installation.
+//!
+//! The manifest contains namespaces with their properties and the metadata locations of
+//! tables and views. Data and metadata files are not touched: imported tables and views
+//! are registered by their metadata location, so the target warehouse must have access
+//! to the storage of the exported warehouse.
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use axum::{response::IntoResponse, Json};
+use chrono::{DateTime, Utc};
+use iceberg_ext::catalog::rest::ErrorModel;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    api::{
+        management::v1::{ApiServer, TabularType},
+        ApiContext, IcebergErrorResponse,
+    },
+    catalog::{
+        maybe_get_secret, namespace::invalidate_namespace_ids, tables::require_active_warehouse,
+    },
+    request_metadata::RequestMetadata,
+    service::{
+        authz::{Authorizer, CatalogWarehouseAction},
+        task_queue::catalog_import_queue::{
+            CatalogImportTable, CatalogImportTableStatus, Importer,
+        },
+        Catalog, Result, SecretStore, State, Transaction,
+    },
+    WarehouseId,
+};
+
+const MANIFEST_FORMAT_VERSION: u8 = 1;
+pub(crate) const NDJSON_CONTENT_TYPE: &str = "application/x-ndjson";
+
+/// File format of a warehouse export
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum WarehouseExportFormat {
+    /// A single JSON document
+    #[default]
+    Json,
+    /// One JSON document per line: the warehouse first, then namespaces, then tabulars
+    Ndjson,
+}
+
+impl WarehouseExportFormat {
+    fn content_type(self) -> &'static str {
+        match self {
+            WarehouseExportFormat::Json => "application/json",
+            WarehouseExportFormat::Ndjson => NDJSON_CONTENT_TYPE,
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            WarehouseExportFormat::Json => "json",
+            WarehouseExportFormat::Ndjson => "ndjson",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportWarehouseQuery {
+    /// Format of the export.
+    /// Default: `json`
+    #[serde(default)]
+    #[param(inline)]
+    pub format: WarehouseExportFormat,
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportWarehouseQuery {
+    /// Register imported tables as external tables, whose files are owned by the
+    /// exported warehouse. They are never purged or maintained by Lakekeeper. Default: false
+    #[serde(default)]
+    pub external: bool,
+}
+
+/// The exported warehouse. First line of an NDJSON export.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct WarehouseExportHeader {
+    pub format_version: u8,
+    #[schema(value_type = uuid::Uuid)]
+    pub warehouse_id: WarehouseId,
+    pub warehouse_name: String,
+    pub exported_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct WarehouseExportNamespace {
+    pub namespace_id: Uuid,
+    pub name: Vec<String>,
+    #[serde(default)]
+    pub properties: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct WarehouseExportTabular {
+    pub tabular_id: Uuid,
+    pub typ: TabularType,
+    pub namespace: Vec<String>,
+    pub name: String,
+    pub metadata_location: String,
+}
+
+/// Portable manifest of the namespaces, tables and views of a warehouse.
+/// Soft-deleted and staged tabulars are not exported.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct WarehouseExportManifest {
+    #[serde(flatten)]
+    pub header: WarehouseExportHeader,
+    /// Namespaces ordered by name, parents before their children
+    pub namespaces: Vec<WarehouseExportNamespace>,
+    /// Tables and views ordered by namespace and name
+    pub tabulars: Vec<WarehouseExportTabular>,
+}
+
+/// A line of an NDJSON export.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "entry", rename_all = "kebab-case")]
+enum WarehouseExportEntry {
+    Warehouse(WarehouseExportHeader),
+    Namespace(WarehouseExportNamespace),
+    Tabular(WarehouseExportTabular),
+}
+
+/// Body of an export and the headers describing it.
+pub struct WarehouseExport {
+    pub content_type: &'static str,
+    pub file_name: String,
+    pub body: Vec<u8>,
+}
+
+/// Outcome of the import of a namespace.
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct WarehouseImportNamespace {
+    pub name: Vec<String>,
+    /// Id of the namespace in the warehouse
+    pub namespace_id: Option<Uuid>,
+    pub error: Option<String>,
+}
+
+/// Outcome of the import of a table or view.
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct WarehouseImportTabular {
+    pub typ: TabularType,
+    #[serde(flatten)]
+    pub outcome: CatalogImportTable,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ImportWarehouseResponse {
+    /// Number of registered tables and views
+    pub imported_tabulars: usize,
+    /// Number of tables and views that are already registered
+    pub skipped_tabulars: usize,
+    /// Number of tables and views that failed to import
+    pub failed_tabulars: usize,
+    /// Outcome per namespace, parents before their children
+    pub namespaces: Vec<WarehouseImportNamespace>,
+    /// Outcome per table and view, in the order of the manifest
+    pub tabulars: Vec<WarehouseImportTabular>,
+}
+
+impl IntoResponse for ImportWarehouseResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
+
+#[async_trait::async_trait]
+pub(crate) trait Service<C: Catalog, A: Authorizer + Clone, S: SecretStore> {
+    async fn export_warehouse(
+        warehouse_id: WarehouseId,
+        query: ExportWarehouseQuery,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<WarehouseExport> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanListEverything,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let state = C::load_catalog_snapshot_state(warehouse_id, context.v1_state.catalog)
+            .await?
+            .ok_or_else(|| {
+                ErrorModel::not_found(
+                    format!("Warehouse {warehouse_id} not found"),
+                    "WarehouseNotFound",
+                    None,
+                )
+            })?;
+
+        let mut namespaces = state
+            .namespaces
+            .into_iter()
+            .filter(|n| !n.deleted)
+            .map(|n| WarehouseExportNamespace {
+                namespace_id: n.namespace_id,
+                name: n.name,
+                properties: n.properties,
+            })
+            .collect::<Vec<_>>();
+        namespaces.sort_by(|a, b| a.name.cmp(&b.name));
+        let mut tabulars = state
+            .tabulars
+            .into_iter()
+            .filter(|t| !t.deleted)
+            .filter_map(|t| {
+                Some(WarehouseExportTabular {
+                    tabular_id: t.tabular_id,
+                    typ: t.typ,
+                    metadata_location: t.metadata_location?,
+                    namespace: t.namespace,
+                    name: t.name,
+                })
+            })
+            .collect::<Vec<_>>();
+        tabulars.sort_by(|a, b| (&a.namespace, &a.name).cmp(&(&b.namespace, &b.name)));
+
+        let manifest = WarehouseExportManifest {
+            header: WarehouseExportHeader {
+                format_version: MANIFEST_FORMAT_VERSION,
+                warehouse_id,
+                warehouse_name: state.warehouse.name,
+                exported_at: Utc::now(),
+            },
+            namespaces,
+            tabulars,
+        };
+        let file_name = format!(
+            "{}.{}",
+            manifest.header.warehouse_name,
+            query.format.extension()
+        );
+        Ok(WarehouseExport {
+            content_type: query.format.content_type(),
+            file_name,
+            body: serialize_manifest(manifest, query.format)?,
+        })
+    }
+
+    async fn import_warehouse(
+        warehouse_id: WarehouseId,
+        query: ImportWarehouseQuery,
+        manifest: WarehouseExportManifest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<ImportWarehouseResponse> {
+        // ------------------- VALIDATIONS -------------------
+        if manifest.header.format_version != MANIFEST_FORMAT_VERSION {
+            return Err(ErrorModel::bad_request(
+                format!(
+                    "Unsupported format version {} of the warehouse export. Supported: {MANIFEST_FORMAT_VERSION}",
+                    manifest.header.format_version
+                ),
+                "UnsupportedWarehouseExportVersion",
+                None,
+            )
+            .into());
+        }
+
+        // ------------------- AuthZ -------------------
+        // Namespaces, tables and views are checked individually while they are imported.
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                CatalogWarehouseAction::CanCreateNamespace,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let catalog_state = context.v1_state.catalog;
+        let mut t = C::Transaction::begin_read(catalog_state.clone()).await?;
+        let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
+        t.commit().await?;
+        require_active_warehouse(warehouse.status)?;
+        let storage_secret =
+            maybe_get_secret(warehouse.storage_secret_id, &context.v1_state.secrets).await?;
+        let file_io = warehouse
+            .storage_profile
+            .file_io(storage_secret.as_ref())
+            .await?;
+
+        let importer = Importer::<C, A> {
+            catalog_state,
+            authorizer: &authorizer,
+            warehouse: &warehouse,
+            file_io: &file_io,
+            owner: &request_metadata,
+            external: query.external,
+        };
+
+        let mut namespace_ids = HashMap::new();
+        let mut namespaces = Vec::new();
+        for (name, properties) in namespaces_to_import(&manifest) {
+            let result = importer
+                .ensure_namespace(&name, properties, &namespace_ids)
+                .await;
+            let (namespace_id, error) = match result {
+                Ok(namespace_id) => {
+                    namespace_ids.insert(name.clone(), namespace_id);
+                    (Some(*namespace_id), None)
+                }
+                Err(e) => (None, Some(e.error.message)),
+            };
+            namespaces.push(WarehouseImportNamespace {
+                name,
+                namespace_id,
+                error,
+            });
+        }
+
+        let mut tabulars = Vec::with_capacity(manifest.tabulars.len());
+        for tabular in manifest.tabulars {
+            let outcome = match namespace_ids.get(&tabular.namespace).copied() {
+                Some(namespace_id) => match tabular.typ {
+                    TabularType::Table => {
+                        importer
+                            .import_table(
+                                namespace_id,
+                                &tabular.namespace,
+                                tabular.name,
+                                Ok(Some(tabular.metadata_location)),
+                            )
+                            .await
+                    }
+                    TabularType::View => {
+                        importer
+                            .import_view(
+                                namespace_id,
+                                &tabular.namespace,
+                                tabular.name,
+                                tabular.metadata_location,
+                            )
+                            .await
+                    }
+                },
+                None => CatalogImportTable {
+                    namespace: tabular.namespace,
+                    name: tabular.name,
+                    metadata_location: Some(tabular.metadata_location),
+                    status: CatalogImportTableStatus::Failed,
+                    table_id: None,
+                    error: Some("Failed to create namespace".to_string()),
+                },
+            };
+            tabulars.push(WarehouseImportTabular {
+                typ: tabular.typ,
+                outcome,
+            });
+        }
+        invalidate_namespace_ids(warehouse_id).await;
+
+        let count = |status| {
+            tabulars
+                .iter()
+                .filter(|t| t.outcome.status == status)
+                .count()
+        };
+        Ok(ImportWarehouseResponse {
+            imported_tabulars: count(CatalogImportTableStatus::Imported),
+            skipped_tabulars: count(CatalogImportTableStatus::Skipped),
+            failed_tabulars: count(CatalogImportTableStatus::Failed),
+            namespaces,
+            tabulars,
+        })
+    }
+}
+
+fn serialize_manifest(
+    manifest: WarehouseExportManifest,
+    format: WarehouseExportFormat,
+) -> Result<Vec<u8>> {
+    let serialization_error = |e: serde_json::Error| -> IcebergErrorResponse {
+        ErrorModel::internal(
+            "Failed to serialize warehouse export",
+            "WarehouseExportSerializationError",
+            Some(Box::new(e)),
+        )
+        .into()
+    };
+    match format {
+        WarehouseExportFormat::Json => serde_json::to_vec(&manifest).map_err(serialization_error),
+        WarehouseExportFormat::Ndjson => {
+            let entries = std::iter::once(WarehouseExportEntry::Warehouse(manifest.header))
+                .chain(
+                    manifest
+                        .namespaces
+                        .into_iter()
+                        .map(WarehouseExportEntry::Namespace),
+                )
+                .chain(
+                    manifest
+                        .tabulars
+                        .into_iter()
+                        .map(WarehouseExportEntry::Tabular),
+                );
+            let mut body = Vec::new();
+            for entry in entries {
+                serde_json::to_writer(&mut body, &entry).map_err(serialization_error)?;
+                body.push(b'\n');
+            }
+            Ok(body)
+        }
+    }
+}
+
+/// Parses a manifest in the format of `content_type`, which defaults to JSON.
+pub(crate) fn parse_manifest(
+    content_type: Option<&str>,
+    body: &[u8],
+) -> Result<WarehouseExportManifest> {
+    let invalid = |e: serde_json::Error| -> IcebergErrorResponse {
+        ErrorModel::bad_request(
+            format!("Invalid warehouse export: {e}"),
+            "InvalidWarehouseExport",
+            Some(Box::new(e)),
+        )
+        .into()
+    };
+    let is_ndjson = content_type.is_some_and(|c| c.starts_with(NDJSON_CONTENT_TYPE));
+    if !is_ndjson {
+        return serde_json::from_slice(body).map_err(invalid);
+    }
+
+    let mut header = None;
+    let mut namespaces = Vec::new();
+    let mut tabulars = Vec::new();
+    for line in body.split(|b| *b == b'\n') {
+        if line.iter().all(u8::is_ascii_whitespace) {
+            continue;
+        }
+        match serde_json::from_slice(line).map_err(invalid)? {
+            WarehouseExportEntry::Warehouse(h) => header = Some(h),
+            WarehouseExportEntry::Namespace(n) => namespaces.push(n),
+            WarehouseExportEntry::Tabular(t) => tabulars.push(t),
+        }
+    }
+    let header = header.ok_or_else(|| {
+        ErrorModel::bad_request(
+            "Invalid warehouse export: the `warehouse` entry is missing",
+            "InvalidWarehouseExport",
+            None,
+        )
+    })?;
+    Ok(WarehouseExportManifest {
+        header,
+        namespaces,
+        tabulars,
+    })
+}
+
+/// Namespaces of the manifest and the namespaces of its tabulars, including all
+/// parents, ordered so that parents are imported before their children.
+fn namespaces_to_import(
+    manifest: &WarehouseExportManifest,
+) -> Vec<(Vec<String>, HashMap<String, String>)> {
+    let mut properties = manifest
+        .namespaces
+        .iter()
+        .map(|n| (n.name.clone(), n.properties.clone()))
+        .collect::<HashMap<_, _>>();
+    let names = manifest
+        .namespaces
+        .iter()
+        .map(|n| &n.name)
+        .chain(manifest.tabulars.iter().map(|t| &t.namespace))
+        .flat_map(|name| (1..=name.len()).map(|depth| name[..depth].to_vec()))
+        .collect::<BTreeSet<_>>();
+    names
+        .into_iter()
+        .map(|name| {
+            let properties = properties.remove(&name).unwrap_or_default();
+            (name, properties.into_iter().collect())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn manifest() -> WarehouseExportManifest {
+        WarehouseExportManifest {
+            header: WarehouseExportHeader {
+                format_version: MANIFEST_FORMAT_VERSION,
+                warehouse_id: WarehouseId::new_random(),
+                warehouse_name: "analytics".to_string(),
+                exported_at: Utc::now(),
+            },
+            namespaces: vec![WarehouseExportNamespace {
+                namespace_id: Uuid::now_v7(),
+                name: vec!["sales".to_string()],
+                properties: BTreeMap::from([("owner".to_string(), "finance".to_string())]),
+            }],
+            tabulars: vec![WarehouseExportTabular {
+                tabular_id: Uuid::now_v7(),
+                typ: TabularType::Table,
+                namespace: vec!["sales".to_string(), "eu".to_string()],
+                name: "orders".to_string(),
+                metadata_location: "s3://bucket/orders/metadata/00001.metadata.json".to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let manifest = manifest();
+        for (format, content_type) in [
+            (WarehouseExportFormat::Json, None),
+            (WarehouseExportFormat::Ndjson, Some(NDJSON_CONTENT_TYPE)),
+        ] {
+            let body = serialize_manifest(manifest.clone(), format).unwrap();
+            assert_eq!(parse_manifest(content_type, &body).unwrap(), manifest);
+        }
+    }
+
+    #[test]
+    fn test_ndjson_requires_warehouse_entry() {
+        let body = serialize_manifest(manifest(), WarehouseExportFormat::Ndjson).unwrap();
+        let without_header = body
+            .split(|b| *b == b'\n')
+            .skip(1)
+            .collect::<Vec<_>>()
+            .join(&b'\n');
+        assert!(parse_manifest(Some(NDJSON_CONTENT_TYPE), &without_header).is_err());
+    }
+
+    #[test]
+    fn test_namespaces_to_import() {
+        let namespaces = namespaces_to_import(&manifest());
+        assert_eq!(
+            namespaces
+                .iter()
+                .map(|(name, _)| name.join("."))
+                .collect::<Vec<_>>(),
+            vec!["sales", "sales.eu"]
+        );
+        assert_eq!(
+            namespaces[0].1.get("owner").map(String::as_str),
+            Some("finance")
+        );
+        assert!(namespaces[1].1.is_empty());
+    }
+}
@@ -0,0 +1,231 @@
+use std::time::Duration;
+
+use axum::{response::IntoResponse, Json};
+use base64::Engine as _;
+use iceberg_ext::catalog::rest::ErrorModel;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    api::{management::v1::ApiServer, ApiContext},
+    request_metadata::RequestMetadata,
+    service::{
+        authz::{Authorizer, CatalogTableAction, CatalogViewAction},
+        watch::{subscribe, wait_for_change, TabularChange, WATCH_POLL_INTERVAL},
+        Catalog, Result, SecretStore, State, TableId, TabularId, TabularVersion, Transaction,
+        ViewId,
+    },
+    WarehouseId,
+};
+
+/// Requests must complete before the request timeout of 30 seconds.
+const MAX_WATCH_TIMEOUT_SECONDS: u64 = 25;
+
+fn default_watch_timeout_seconds() -> u64 {
+    20
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchQuery {
+    /// Entity to watch, either `table:{table-id}` or `view:{view-id}`
+    pub resource: String,
+    /// Cursor returned by a previous watch request. If set, the request waits until
+    /// the entity differs from the cursor. Otherwise, the current version is returned
+    /// immediately.
+    #[serde(default)]
+    pub cursor: Option<String>,
+    /// Seconds to wait for a change. At most 25.
+    /// Default: 20
+    #[serde(default = "default_watch_timeout_seconds")]
+    pub timeout_seconds: u64,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct WatchResponse {
+    /// Watched entity as passed in the request
+    pub resource: String,
+    /// Whether the entity changed compared to the cursor of the request.
+    /// `false` if the request timed out or no cursor was passed.
+    pub changed: bool,
+    /// Cursor to pass to the next request to wait for the next change
+    pub cursor: String,
+    /// Location of the current metadata file. `None` for staged tables and
+    /// entities that were removed.
+    pub metadata_location: Option<String>,
+    /// Whether the entity was dropped
+    pub dropped: bool,
+}
+
+impl IntoResponse for WatchResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
+
+#[async_trait::async_trait]
+pub(crate) trait Service<C: Catalog, A: Authorizer + Clone, S: SecretStore> {
+    async fn watch(
+        warehouse_id: WarehouseId,
+        query: WatchQuery,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<WatchResponse> {
+        let tabular_id = parse_watch_resource(&query.resource)?;
+        let timeout = Duration::from_secs(query.timeout_seconds.min(MAX_WATCH_TIMEOUT_SECONDS));
+        let catalog = context.v1_state.catalog;
+
+        // Subscribe before reading the version, so that no commit in between is missed.
+        let mut changes = subscribe();
+        let version = get_version::<C>(warehouse_id, tabular_id, catalog.clone()).await?;
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        match tabular_id {
+            TabularId::Table(id) => {
+                authorizer
+                    .require_table_action(
+                        &request_metadata,
+                        Ok(version.as_ref().map(|_| TableId::from(id))),
+                        CatalogTableAction::CanGetMetadata,
+                    )
+                    .await?;
+            }
+            TabularId::View(id) => {
+                authorizer
+                    .require_view_action(
+                        &request_metadata,
+                        Ok(version.as_ref().map(|_| ViewId::from(id))),
+                        CatalogViewAction::CanGetMetadata,
+                    )
+                    .await?;
+            }
+        }
+
+        // ------------------- Business Logic -------------------
+        let Some(previous_cursor) = query.cursor else {
+            return Ok(watch_response(query.resource, version.as_ref(), false));
+        };
+        let change = TabularChange {
+            warehouse_id,
+            tabular_id: *tabular_id,
+        };
+        let deadline = tokio::time::Instant::now() + timeout;
+        let mut version = version;
+        loop {
+            let changed = watch_cursor(version.as_ref()) != previous_cursor;
+            let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+            if changed || remaining.is_zero() {
+                return Ok(watch_response(query.resource, version.as_ref(), changed));
+            }
+            wait_for_change(&mut changes, change, remaining.min(WATCH_POLL_INTERVAL)).await;
+            version = get_version::<C>(warehouse_id, tabular_id, catalog.clone()).await?;
+        }
+    }
+}
+
+async fn get_version<C: Catalog>(
+    warehouse_id: WarehouseId,
+    tabular_id: TabularId,
+    catalog_state: C::State,
+) -> Result<Option<TabularVersion>> {
+    let mut t = C::Transaction::begin_read(catalog_state).await?;
+    let version = C::get_tabular_version(warehouse_id, tabular_id, t.transaction()).await?;
+    t.commit().await?;
+    Ok(version)
+}
+
+fn parse_watch_resource(resource: &str) -> Result<TabularId> {
+    resource
+        .split_once(':')
+        .and_then(|(typ, id)| {
+            let id = Uuid::parse_str(id).ok()?;
+            match typ {
+                "table" => Some(TabularId::Table(id)),
+                "view" => Some(TabularId::View(id)),
+                _ => None,
+            }
+        })
+        .ok_or_else(|| {
+            ErrorModel::bad_request(
+                format!(
+                    "Invalid watch resource '{resource}'. Expected 'table:{{table-id}}' or 'view:{{view-id}}'."
+                ),
+                "InvalidWatchResource",
+                None,
+            )
+            .into()
+        })
+}
+
+/// Tables and views that were removed after the watch started are reported as dropped.
+fn watch_response(
+    resource: String,
+    version: Option<&TabularVersion>,
+    changed: bool,
+) -> WatchResponse {
+    WatchResponse {
+        resource,
+        changed,
+        cursor: watch_cursor(version),
+        metadata_location: version.and_then(|v| v.metadata_location.clone()),
+        dropped: version.map_or(true, |v| v.deleted),
+    }
+}
+
+/// Cursors are opaque to clients, so that their content can change in the future.
+fn watch_cursor(version: Option<&TabularVersion>) -> String {
+    let (metadata_location, dropped) = version.map_or((None, true), |v| {
+        (v.metadata_location.as_deref(), v.deleted)
+    });
+    let cursor = serde_json::json!([metadata_location, dropped]).to_string();
+    base64::prelude::BASE64_URL_SAFE_NO_PAD.encode(cursor)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_watch_resource() {
+        let id = Uuid::now_v7();
+        assert_eq!(
+            parse_watch_resource(&format!("table:{id}")).unwrap(),
+            TabularId::Table(id)
+        );
+        assert_eq!(
+            parse_watch_resource(&format!("view:{id}")).unwrap(),
+            TabularId::View(id)
+        );
+        for invalid in [
+            "table".to_string(),
+            "table:not-a-uuid".to_string(),
+            format!("namespace:{id}"),
+        ] {
+            let err = parse_watch_resource(&invalid).unwrap_err();
+            assert_eq!(err.error.r#type, "InvalidWatchResource");
+        }
+    }
+
+    #[test]
+    fn test_watch_cursor_changes_with_version() {
+        let version = TabularVersion {
+            metadata_location: Some("s3://bucket/table/metadata/00001.metadata.json".to_string()),
+            deleted: false,
+        };
+        let committed = TabularVersion {
+            metadata_location: Some("s3://bucket/table/metadata/00002.metadata.json".to_string()),
+            deleted: false,
+        };
+        let dropped = TabularVersion {
+            deleted: true,
+            ..committed.clone()
+        };
+        assert_ne!(watch_cursor(Some(&version)), watch_cursor(Some(&committed)));
+        assert_ne!(watch_cursor(Some(&committed)), watch_cursor(Some(&dropped)));
+        assert_ne!(watch_cursor(Some(&dropped)), watch_cursor(None));
+    }
+}
@@ -1,4 +1,7 @@
-use std::{fmt::Debug, sync::LazyLock};
+use std::{
+    fmt::Debug,
+    sync::{Arc, LazyLock},
+};
 
 use axum::{response::IntoResponse, routing::get, Json, Router};
 use axum_extra::middleware::option_layer;
@@ -14,18 +17,26 @@ use tower_http::{
 
 use crate::{
     api::{
-        iceberg::v1::new_v1_full_router,
-        management::v1::{api_doc as v1_api_doc, ApiServer},
+        iceberg::v1::{new_v1_full_router, new_v1_oauth_router},
+        management::v1::{
+            announcement::{announcement_header_middleware_fn, ANNOUNCEMENT_HEADER},
+            api_doc as v1_api_doc, ApiServer,
+        },
         shutdown_signal, ApiContext,
     },
     request_metadata::create_request_metadata_with_trace_and_project_fn,
     service::{
-        authn::{auth_middleware_fn, AuthMiddlewareState},
+        authn::{
+            auth_middleware_fn, impersonation_middleware_fn, AuthMiddlewareState,
+            ImpersonationMiddlewareState,
+        },
         authz::Authorizer,
         contract_verification::ContractVerifiers,
         endpoint_hooks::EndpointHookCollection,
         health::ServiceHealthProvider,
+        search_index::SearchIndex,
         task_queue::{QueueApiConfig, RegisteredTaskQueues},
+        token_cache::CACHE_HINT_HEADER,
         Catalog, EndpointStatisticsTrackerTx, SecretStore, State,
     },
     tracing::{MakeRequestUuid7, RestMakeSpan},
@@ -51,6 +62,7 @@ pub struct RouterArgs<C: Catalog, A: Authorizer + Clone, S: SecretStore, N: Auth
     pub endpoint_statistics_tracker_tx: EndpointStatisticsTrackerTx,
     pub hooks: EndpointHookCollection,
     pub registered_task_queues: RegisteredTaskQueues,
+    pub search_index: Option<Arc<dyn SearchIndex>>,
 }
 
 impl<C: Catalog, A: Authorizer + Clone, S: SecretStore, N: Authenticator + Debug> Debug
@@ -75,6 +87,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore, N: Authenticator + Debug
             )
             .field("endpoint_hooks", &self.hooks)
             .field("registered_task_queues", &self.registered_task_queues)
+            .field("search_index", &self.search_index)
             .finish()
     }
 }
@@ -101,6 +114,7 @@ pub fn new_full_router<
         endpoint_statistics_tracker_tx,
         hooks,
         registered_task_queues,
+        search_index,
     }: RouterArgs<C, A, S, N>,
 ) -> anyhow::Result<Router> {
     let v1_routes = new_v1_full_router::<crate::catalog::CatalogServer<C, A, S>, State<A, C, S>>();
@@ -131,12 +145,17 @@ pub fn new_full_router<
                 Method::DELETE,
                 Method::OPTIONS,
             ])
+            .expose_headers([
+                header::HeaderName::from_static(ANNOUNCEMENT_HEADER),
+                header::HeaderName::from_static(CACHE_HINT_HEADER),
+            ])
     }));
 
     let maybe_auth_layer = if let Some(authenticator) = authenticator {
         option_layer(Some(axum::middleware::from_fn_with_state(
-            AuthMiddlewareState {
+            AuthMiddlewareState::<_, C, A> {
                 authenticator,
+                catalog_state: catalog_state.clone(),
                 authorizer: authorizer.clone(),
             },
             auth_middleware_fn,
@@ -145,13 +164,50 @@ pub fn new_full_router<
         option_layer(None)
     };
 
+    let maybe_announcement_layer = option_layer(CONFIG.announcement_headers.then(|| {
+        axum::middleware::from_fn_with_state(
+            catalog_state.clone(),
+            announcement_header_middleware_fn::<C>,
+        )
+    }));
+
+    let slug_catalog_state = catalog_state.clone();
     let router = Router::new()
         .nest("/catalog/v1", v1_routes)
         .nest("/management/v1", management_routes)
+        .layer(maybe_announcement_layer)
+        .layer(axum::middleware::from_fn(
+            crate::service::token_cache::token_cache_middleware_fn,
+        ))
+        .layer(axum::middleware::from_fn(
+            crate::service::log_level::debug_trace_middleware_fn,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            catalog_state.clone(),
+            crate::service::endpoint_policy::endpoint_policy_middleware_fn::<C>,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            catalog_state.clone(),
+            crate::service::rate_limit::rate_limit_middleware_fn::<C>,
+        ))
         .layer(axum::middleware::from_fn_with_state(
             endpoint_statistics_tracker_tx,
             crate::service::endpoint_statistics::endpoint_statistics_middleware_fn,
         ))
+        .layer(axum::middleware::from_fn(
+            crate::service::warehouse_metrics::warehouse_metrics_middleware_fn,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            catalog_state.clone(),
+            crate::service::request_log::request_log_middleware_fn::<C>,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            ImpersonationMiddlewareState::<C, A> {
+                catalog_state: catalog_state.clone(),
+                authorizer: authorizer.clone(),
+            },
+            impersonation_middleware_fn,
+        ))
         .layer(maybe_auth_layer)
         .route(
             "/health",
@@ -160,6 +216,15 @@ pub fn new_full_router<
                 Json(health).into_response()
             }),
         );
+    // Token requests are sent without credentials, so the endpoint is not authenticated.
+    let router = if crate::service::client_compatibility::serve_legacy_oauth_tokens_endpoint() {
+        router.nest(
+            "/catalog/v1",
+            new_v1_oauth_router::<crate::catalog::CatalogServer<C, A, S>, State<A, C, S>>(),
+        )
+    } else {
+        router
+    };
     let router = maybe_merge_swagger_router(router, registered_task_queues.api_config())
         .layer(axum::middleware::from_fn(
             create_request_metadata_with_trace_and_project_fn,
@@ -190,9 +255,20 @@ pub fn new_full_router<
                 contract_verifiers: table_change_checkers,
                 registered_task_queues,
                 hooks,
+                search_index,
             },
         });
 
+    // Warehouse-scoped URLs are rewritten before routing. Layers of a router only run
+    // after routing, so the router becomes the fallback of an otherwise empty router.
+    let router =
+        Router::new()
+            .fallback_service(router)
+            .layer(axum::middleware::from_fn_with_state(
+                slug_catalog_state,
+                crate::service::warehouse_slug::warehouse_slug_middleware_fn::<C>,
+            ));
+
     Ok(if let Some(metrics_layer) = metrics_layer {
         router.layer(metrics_layer)
     } else {
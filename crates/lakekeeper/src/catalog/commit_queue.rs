@@ -0,0 +1,186 @@
+//! Per-table queue for commits handled by this server.
+//!
+//! Concurrent commits to the same table conflict in the database and are only retried a
+//! few times before the client receives a `409`. High-frequency writers, such as streaming
+//! jobs, run into this regularly. If enabled via `CONFIG.commit_queue_max_wait`, commits
+//! wait for their turn instead. Waiting commits are served in the order they arrived.
+//! Requirements are validated once a commit holds the queue, so they are checked against
+//! the latest state of the table.
+//!
+//! Commits of other Lakekeeper instances are not queued and still resolve via retries.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, LazyLock},
+    time::Duration,
+};
+
+use iceberg_ext::catalog::rest::ErrorModel;
+use tokio::sync::{Mutex, OwnedMutexGuard};
+
+use crate::service::{Result, TableId};
+
+pub(crate) static COMMIT_QUEUES: LazyLock<CommitQueues> = LazyLock::new(CommitQueues::default);
+
+#[derive(Debug, Default)]
+pub(crate) struct CommitQueues {
+    // `tokio::sync::Mutex` hands out the lock in FIFO order.
+    queues: std::sync::Mutex<HashMap<TableId, Arc<Mutex<()>>>>,
+}
+
+/// Holds the queues of all tables of a commit. Releases them on drop.
+#[derive(Debug)]
+pub(crate) struct CommitQueueGuard<'a> {
+    queues: &'a CommitQueues,
+    table_ids: Vec<TableId>,
+    guards: Vec<OwnedMutexGuard<()>>,
+}
+
+impl CommitQueues {
+    /// Waits until no other commit of this server holds any of the tables.
+    ///
+    /// # Errors
+    /// Fails with a conflict if the queues are not acquired within `max_wait`.
+    pub(crate) async fn enqueue(
+        &self,
+        table_ids: impl IntoIterator<Item = TableId>,
+        max_wait: Duration,
+    ) -> Result<CommitQueueGuard<'_>> {
+        let deadline = tokio::time::Instant::now() + max_wait;
+        // Queues are always acquired in the same order to prevent deadlocks
+        // between multi-table transactions.
+        let mut table_ids = table_ids.into_iter().collect::<Vec<_>>();
+        table_ids.sort_unstable();
+        table_ids.dedup();
+
+        let mut guard = CommitQueueGuard {
+            queues: self,
+            table_ids,
+            guards: Vec::new(),
+        };
+        for table_id in guard.table_ids.clone() {
+            let queue = self.queue(table_id);
+            match tokio::time::timeout_at(deadline, queue.lock_owned()).await {
+                Ok(table_guard) => guard.guards.push(table_guard),
+                Err(_) => {
+                    return Err(ErrorModel::conflict(
+                        format!(
+                            "Timed out after {}ms waiting for concurrent commits to table {table_id}",
+                            max_wait.as_millis()
+                        ),
+                        "CommitQueueTimeout",
+                        None,
+                    )
+                    .into());
+                }
+            }
+        }
+        Ok(guard)
+    }
+
+    fn queue(&self, table_id: TableId) -> Arc<Mutex<()>> {
+        self.queues
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .entry(table_id)
+            .or_default()
+            .clone()
+    }
+
+    /// Removes queues that no commit holds or waits for.
+    fn release(&self, table_ids: &[TableId]) {
+        let mut queues = self
+            .queues
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        for table_id in table_ids {
+            if queues
+                .get(table_id)
+                .is_some_and(|queue| Arc::strong_count(queue) == 1)
+            {
+                queues.remove(table_id);
+            }
+        }
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.queues
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .len()
+    }
+}
+
+impl Drop for CommitQueueGuard<'_> {
+    fn drop(&mut self) {
+        self.guards.clear();
+        self.queues.release(&self.table_ids);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_commits_are_served_in_order() {
+        let queues = Arc::new(CommitQueues::default());
+        let table_id = TableId::new_random();
+        let order = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        let first = queues
+            .enqueue([table_id], Duration::from_secs(5))
+            .await
+            .unwrap();
+
+        let mut handles = Vec::new();
+        for i in 0..3 {
+            let queues = queues.clone();
+            let order = order.clone();
+            handles.push(tokio::spawn(async move {
+                let _guard = queues
+                    .enqueue([table_id], Duration::from_secs(5))
+                    .await
+                    .unwrap();
+                order.lock().unwrap().push(i);
+            }));
+            // Make sure the commits are queued in the order they are spawned
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+
+        drop(first);
+        for handle in handles {
+            handle.await.unwrap();
+        }
+        assert_eq!(*order.lock().unwrap(), vec![0, 1, 2]);
+        assert_eq!(queues.len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_enqueue_times_out() {
+        let queues = CommitQueues::default();
+        let table_id = TableId::new_random();
+        let other_table_id = TableId::new_random();
+
+        let guard = queues
+            .enqueue([table_id], Duration::from_secs(5))
+            .await
+            .unwrap();
+        let err = queues
+            .enqueue([other_table_id, table_id], Duration::from_millis(50))
+            .await
+            .unwrap_err();
+        assert_eq!(err.error.code, 409);
+        assert_eq!(err.error.r#type, "CommitQueueTimeout");
+
+        // Other tables are not blocked
+        let other = queues
+            .enqueue([other_table_id], Duration::from_millis(50))
+            .await
+            .unwrap();
+        drop(other);
+        drop(guard);
+        assert_eq!(queues.len(), 0);
+    }
+}
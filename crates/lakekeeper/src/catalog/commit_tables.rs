@@ -1,6 +1,9 @@
-use std::str::FromStr as _;
+use std::{collections::HashSet, str::FromStr as _};
 
-use iceberg::{spec::TableMetadata, TableRequirement, TableUpdate};
+use iceberg::{
+    spec::{Operation, SnapshotRetention, TableMetadata},
+    TableRequirement, TableUpdate,
+};
 use iceberg_ext::{
     configs::Location,
     spec::{TableMetadataBuildResult, TableMetadataBuilder},
@@ -86,6 +89,26 @@ pub(super) fn apply_commit(
         })
 }
 
+/// Whether the updates only append snapshots and move branches to them.
+/// Such commits are typical for streaming writers and take the append fast path.
+pub(super) fn is_append_only(updates: &[TableUpdate]) -> bool {
+    let mut added_snapshots = HashSet::new();
+    for update in updates {
+        match update {
+            TableUpdate::AddSnapshot { snapshot }
+                if snapshot.summary().operation == Operation::Append =>
+            {
+                added_snapshots.insert(snapshot.snapshot_id());
+            }
+            TableUpdate::SetSnapshotRef { reference, .. }
+                if matches!(reference.retention, SnapshotRetention::Branch { .. })
+                    && added_snapshots.contains(&reference.snapshot_id) => {}
+            _ => return false,
+        }
+    }
+    !added_snapshots.is_empty()
+}
+
 fn table_update_as_str(update: &TableUpdate) -> &str {
     match update {
         TableUpdate::UpgradeFormatVersion { .. } => "upgrade_format_version",
@@ -111,3 +134,74 @@ fn table_update_as_str(update: &TableUpdate) -> &str {
         TableUpdate::RemoveSchemas { .. } => "remove_schemas",
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use iceberg::spec::{Snapshot, SnapshotReference, Summary, MAIN_BRANCH};
+
+    use super::*;
+
+    fn snapshot(snapshot_id: i64, operation: Operation) -> TableUpdate {
+        TableUpdate::AddSnapshot {
+            snapshot: Snapshot::builder()
+                .with_snapshot_id(snapshot_id)
+                .with_timestamp_ms(1)
+                .with_sequence_number(snapshot_id)
+                .with_schema_id(0)
+                .with_manifest_list(format!("/snap-{snapshot_id}.avro"))
+                .with_summary(Summary {
+                    operation,
+                    additional_properties: HashMap::new(),
+                })
+                .build(),
+        }
+    }
+
+    fn set_ref(snapshot_id: i64, retention: SnapshotRetention) -> TableUpdate {
+        TableUpdate::SetSnapshotRef {
+            ref_name: MAIN_BRANCH.to_string(),
+            reference: SnapshotReference {
+                snapshot_id,
+                retention,
+            },
+        }
+    }
+
+    #[test]
+    fn test_is_append_only() {
+        let branch = SnapshotRetention::Branch {
+            min_snapshots_to_keep: None,
+            max_snapshot_age_ms: None,
+            max_ref_age_ms: None,
+        };
+        let tag = SnapshotRetention::Tag {
+            max_ref_age_ms: None,
+        };
+
+        assert!(is_append_only(&[snapshot(1, Operation::Append)]));
+        assert!(is_append_only(&[
+            snapshot(1, Operation::Append),
+            set_ref(1, branch.clone()),
+        ]));
+        assert!(!is_append_only(&[]));
+        assert!(!is_append_only(&[snapshot(1, Operation::Overwrite)]));
+        // Branches may only be moved to appended snapshots
+        assert!(!is_append_only(&[set_ref(1, branch.clone())]));
+        assert!(!is_append_only(&[
+            snapshot(1, Operation::Append),
+            set_ref(0, branch),
+        ]));
+        assert!(!is_append_only(&[
+            snapshot(1, Operation::Append),
+            set_ref(1, tag),
+        ]));
+        assert!(!is_append_only(&[
+            snapshot(1, Operation::Append),
+            TableUpdate::SetProperties {
+                updates: HashMap::new(),
+            },
+        ]));
+    }
+}
@@ -1,4 +1,4 @@
-use std::str::FromStr;
+use std::{str::FromStr, sync::LazyLock};
 
 use super::{CatalogServer, DEFAULT_PAGE_SIZE};
 use crate::{
@@ -7,16 +7,33 @@ use crate::{
             config::GetConfigQueryParams, ApiContext, CatalogConfig, ErrorModel, PageToken,
             PaginationQuery, Result,
         },
-        management::v1::user::{parse_create_user_request, UserLastUpdatedWith},
+        management::v1::{
+            user::{parse_create_user_request, UserLastUpdatedWith},
+            ListSort,
+        },
     },
     request_metadata::RequestMetadata,
     service::{
         authz::{Authorizer, CatalogProjectAction, CatalogWarehouseAction},
+        response_properties::{response_properties, PropertyResponse},
+        shared_cache::SharedCache,
         Catalog, ProjectId, SecretStore, State, Transaction,
     },
-    CONFIG,
+    WarehouseId, CONFIG,
 };
 
+/// Configuration of warehouses as stored in the catalog, before request specific
+/// properties are added.
+static WAREHOUSE_CONFIG_CACHE: LazyLock<SharedCache<CatalogConfig>> =
+    LazyLock::new(|| SharedCache::new("warehouse-config"));
+
+/// Remove the cached configuration of a warehouse after the warehouse was changed.
+pub(crate) async fn invalidate_warehouse_config(warehouse_id: WarehouseId) {
+    WAREHOUSE_CONFIG_CACHE
+        .invalidate(&warehouse_id.to_string())
+        .await;
+}
+
 #[async_trait::async_trait]
 impl<A: Authorizer + Clone, C: Catalog, S: SecretStore>
     crate::api::iceberg::v1::config::Service<State<A, C, S>> for CatalogServer<C, A, S>
@@ -30,8 +47,11 @@ impl<A: Authorizer + Clone, C: Catalog, S: SecretStore>
 
         maybe_register_user::<C>(&request_metadata, api_context.v1_state.catalog.clone()).await?;
 
-        // Arg takes precedence over auth
-        let warehouse_id = if let Some(query_warehouse) = query.warehouse {
+        // The warehouse of a warehouse-scoped URL takes precedence over the arg
+        let scoped_warehouse = request_metadata.warehouse_slug().cloned();
+        let warehouse_id = if let Some(scoped_warehouse) = &scoped_warehouse {
+            scoped_warehouse.warehouse_id
+        } else if let Some(query_warehouse) = query.warehouse {
             let (project_from_arg, warehouse_from_arg) = parse_warehouse_arg(&query_warehouse);
             let project_id = request_metadata.require_project_id(project_from_arg)?;
             authorizer
@@ -59,24 +79,47 @@ impl<A: Authorizer + Clone, C: Catalog, S: SecretStore>
             )
             .await?;
 
-        // Get config from DB and new token from AuthHandler simultaneously
-        let mut config = C::require_config_for_warehouse(
-            warehouse_id,
-            &request_metadata,
-            api_context.v1_state.catalog,
-        )
-        .await?;
+        let cache_key = warehouse_id.to_string();
+        let mut config = if let Some(config) = WAREHOUSE_CONFIG_CACHE.get(&cache_key).await {
+            config
+        } else {
+            let config = C::require_config_for_warehouse(
+                warehouse_id,
+                &request_metadata,
+                api_context.v1_state.catalog,
+            )
+            .await?;
+            WAREHOUSE_CONFIG_CACHE
+                .insert(cache_key, config.clone(), None)
+                .await;
+            config
+        };
 
-        config
-            .defaults
-            .insert("prefix".to_string(), CONFIG.warehouse_prefix(warehouse_id));
+        // Warehouse-scoped URLs don't use a prefix, the warehouse is part of the URI.
+        // Clients are pointed to the current slug, in case they used an alias.
+        let uri = if let Some(scoped_warehouse) = &scoped_warehouse {
+            format!(
+                "{}/w/{}",
+                request_metadata.base_uri_catalog(),
+                scoped_warehouse.slug
+            )
+        } else {
+            config
+                .defaults
+                .insert("prefix".to_string(), CONFIG.warehouse_prefix(warehouse_id));
+            request_metadata.base_uri_catalog()
+        };
         config
             .defaults
             .insert("rest-page-size".to_string(), DEFAULT_PAGE_SIZE.to_string());
 
-        config
-            .overrides
-            .insert("uri".to_string(), request_metadata.base_uri_catalog());
+        // Injected properties can't change the URI of the catalog
+        config.overrides.extend(response_properties(
+            PropertyResponse::GetConfig,
+            warehouse_id,
+            &request_metadata,
+        ));
+        config.overrides.insert("uri".to_string(), uri);
 
         Ok(config)
     }
@@ -122,6 +165,9 @@ async fn maybe_register_user<D: Catalog>(
     let user = D::list_user(
         Some(vec![user_id.clone()]),
         None,
+        None,
+        None,
+        ListSort::default(),
         PaginationQuery {
             page_token: PageToken::Empty,
             page_size: Some(1),
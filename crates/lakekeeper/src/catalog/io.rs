@@ -3,13 +3,52 @@ use iceberg::{io::FileIO, spec::TableMetadata};
 use iceberg_ext::{catalog::rest::IcebergErrorResponse, configs::Location};
 use serde::Serialize;
 
-use super::compression_codec::CompressionCodec;
+use super::{compression_codec::CompressionCodec, metadata_cache};
 use crate::{
     api::{ErrorModel, Result},
     retry::retry_fn,
-    service::storage::az::ALTERNATIVE_PROTOCOLS as AZURE_ALTERNATIVE_PROTOCOLS,
+    service::storage::{
+        az::ALTERNATIVE_PROTOCOLS as AZURE_ALTERNATIVE_PROTOCOLS,
+        hdfs::{WebHdfsClient, WebHdfsError},
+    },
 };
 
+/// IO for the storage of a warehouse.
+///
+/// Object stores are accessed via iceberg's [`FileIO`]. HDFS is accessed via
+/// its WebHDFS REST API, which only supports the operations in this module.
+#[derive(Debug, Clone)]
+pub enum StorageIo {
+    FileIo(FileIO),
+    WebHdfs(WebHdfsClient),
+}
+
+impl StorageIo {
+    /// Returns the iceberg [`FileIO`] for features that read table files
+    /// via iceberg, such as manifests or data files.
+    ///
+    /// # Errors
+    /// Fails for storages that are not supported by [`FileIO`].
+    pub(crate) fn iceberg_file_io(&self, feature: &'static str) -> Result<&FileIO, IoError> {
+        match self {
+            StorageIo::FileIo(file_io) => Ok(file_io),
+            StorageIo::WebHdfs(_) => Err(IoError::Unsupported(feature)),
+        }
+    }
+}
+
+impl From<FileIO> for StorageIo {
+    fn from(file_io: FileIO) -> Self {
+        StorageIo::FileIo(file_io)
+    }
+}
+
+impl From<WebHdfsClient> for StorageIo {
+    fn from(client: WebHdfsClient) -> Self {
+        StorageIo::WebHdfs(client)
+    }
+}
+
 fn normalize_location(location: &Location) -> String {
     if location.as_str().starts_with("abfs")
         || AZURE_ALTERNATIVE_PROTOCOLS
@@ -23,7 +62,8 @@ fn normalize_location(location: &Location) -> String {
             location.set_scheme_mut("abfss");
             location.to_string()
         }
-    } else if location.scheme().starts_with("s3") {
+    } else if location.scheme().starts_with("s3") || location.scheme() == "oss" {
+        // OSS is accessed via its S3-compatible API
         if location.scheme() == "s3" {
             location.to_string()
         } else {
@@ -36,58 +76,97 @@ fn normalize_location(location: &Location) -> String {
     }
 }
 
+/// Returns the size of the serialized metadata before compression.
+#[tracing::instrument(skip_all, fields(location = %metadata_location))]
 pub(crate) async fn write_metadata_file(
     metadata_location: &Location,
     metadata: impl Serialize,
     compression_codec: CompressionCodec,
-    file_io: &FileIO,
-) -> Result<(), IoError> {
+    file_io: &StorageIo,
+) -> Result<usize, IoError> {
     let metadata_location = normalize_location(metadata_location);
     tracing::debug!("Writing metadata file to {}", metadata_location);
 
-    let metadata_file = file_io
-        .new_output(metadata_location)
-        .map_err(IoError::FileCreation)?;
-
     let buf = serde_json::to_vec(&metadata).map_err(IoError::Serialization)?;
+    let size = buf.len();
 
     let metadata_bytes = compression_codec.compress(buf).await?;
 
-    retry_fn(|| async {
-        metadata_file
-            .write(metadata_bytes.clone().into())
-            .await
-            .map_err(IoError::FileWriterCreation)
-    })
-    .await
+    match file_io {
+        StorageIo::FileIo(file_io) => {
+            let metadata_file = file_io
+                .new_output(metadata_location)
+                .map_err(IoError::FileCreation)?;
+            retry_fn(|| async {
+                metadata_file
+                    .write(metadata_bytes.clone().into())
+                    .await
+                    .map_err(IoError::FileWriterCreation)
+            })
+            .await?;
+        }
+        StorageIo::WebHdfs(client) => {
+            retry_fn(|| async {
+                client
+                    .write(&metadata_location, metadata_bytes.clone())
+                    .await
+                    .map_err(IoError::WebHdfs)
+            })
+            .await?;
+        }
+    }
+    Ok(size)
+}
+
+/// Writes the metadata of a table and adds it to the [`metadata_cache`].
+pub(crate) async fn write_table_metadata_file(
+    metadata_location: &Location,
+    metadata: &TableMetadata,
+    compression_codec: CompressionCodec,
+    file_io: &StorageIo,
+) -> Result<(), IoError> {
+    let size = write_metadata_file(metadata_location, metadata, compression_codec, file_io).await?;
+    metadata_cache::insert(metadata_location, metadata.clone(), size).await;
+    Ok(())
 }
 
-pub(crate) async fn delete_file(file_io: &FileIO, location: &Location) -> Result<(), IoError> {
+#[tracing::instrument(skip_all, fields(%location))]
+pub(crate) async fn delete_file(file_io: &StorageIo, location: &Location) -> Result<(), IoError> {
     let location = normalize_location(location);
 
     retry_fn(|| async {
-        file_io
-            .clone()
-            .delete(location.clone())
-            .await
-            .map_err(IoError::FileDelete)
+        match file_io {
+            StorageIo::FileIo(file_io) => file_io
+                .clone()
+                .delete(location.clone())
+                .await
+                .map_err(IoError::FileDelete),
+            StorageIo::WebHdfs(client) => client
+                .delete(&location, false)
+                .await
+                .map_err(IoError::WebHdfs),
+        }
     })
     .await
 }
 
-pub(crate) async fn read_file(file_io: &FileIO, file: &Location) -> Result<Vec<u8>, IoError> {
+#[tracing::instrument(skip_all, fields(location = %file))]
+pub(crate) async fn read_file(file_io: &StorageIo, file: &Location) -> Result<Vec<u8>, IoError> {
     let file = normalize_location(file);
 
     let content: Vec<_> = retry_fn(|| async {
-        // InputFile isn't clone hence it's here
-        file_io
-            .clone()
-            .new_input(file.clone())
-            .map_err(IoError::FileInput)?
-            .read()
-            .await
-            .map_err(|e| IoError::FileRead(Box::new(e)))
-            .map(Into::into)
+        match file_io {
+            // InputFile isn't clone hence it's here
+            StorageIo::FileIo(file_io) => file_io
+                .clone()
+                .new_input(file.clone())
+                .map_err(IoError::FileInput)?
+                .read()
+                .await
+                .map_err(|e| IoError::FileRead(Box::new(e)))
+                .map(Into::into),
+            StorageIo::WebHdfs(client) => client.read(&file).await.map_err(IoError::WebHdfs),
+        }
     })
     .await?;
 
@@ -100,38 +179,76 @@ pub(crate) async fn read_file(file_io: &FileIO, file: &Location) -> Result<Vec<u
     }
 }
 
+/// Reads the metadata of a table, served from the [`metadata_cache`] if possible.
 pub(crate) async fn read_metadata_file(
-    file_io: &FileIO,
+    file_io: &StorageIo,
     file: &Location,
 ) -> Result<TableMetadata, IoError> {
+    if let Some(metadata) = metadata_cache::get(file).await {
+        return Ok(metadata);
+    }
     let content = read_file(file_io, file).await?;
-    match tokio::task::spawn_blocking(move || {
+    let size = content.len();
+    let metadata: TableMetadata = match tokio::task::spawn_blocking(move || {
         serde_json::from_slice(&content).map_err(IoError::TableMetadataDeserialization)
     })
     .await
     {
-        Ok(result) => result,
-        Err(e) => Err(IoError::FileDecompression(Box::new(e))),
-    }
+        Ok(result) => result?,
+        Err(e) => return Err(IoError::FileDecompression(Box::new(e))),
+    };
+    metadata_cache::insert(file, metadata.clone(), size).await;
+    Ok(metadata)
 }
 
-pub(crate) async fn remove_all(file_io: &FileIO, location: &Location) -> Result<(), IoError> {
+#[tracing::instrument(skip_all, fields(%location))]
+pub(crate) async fn remove_all(file_io: &StorageIo, location: &Location) -> Result<(), IoError> {
     let location = normalize_location(location.clone().with_trailing_slash());
 
     retry_fn(|| async {
-        file_io
-            .clone()
-            .remove_dir_all(location.clone())
-            .await
-            .map_err(IoError::FileRemoveAll)
+        match file_io {
+            StorageIo::FileIo(file_io) => file_io
+                .clone()
+                .remove_dir_all(location.clone())
+                .await
+                .map_err(IoError::FileRemoveAll),
+            StorageIo::WebHdfs(client) => client
+                .delete(&location, true)
+                .await
+                .map_err(IoError::WebHdfs),
+        }
     })
     .await
 }
 
+/// WebHDFS has no paginated recursive listing. All files are listed
+/// upfront and returned in pages of `page_size`.
+async fn list_webhdfs_files<'a>(
+    client: &WebHdfsClient,
+    location: &str,
+    page_size: usize,
+) -> Result<BoxStream<'a, std::result::Result<Vec<ListedFile>, IoError>>, IoError> {
+    let files = retry_fn(|| async { client.list_files(location).await })
+        .await
+        .map_err(IoError::WebHdfs)?
+        .into_iter()
+        .map(|file| ListedFile {
+            path: file.location,
+            last_modified: file.last_modified,
+        })
+        .collect::<Vec<_>>();
+    let pages = files
+        .chunks(page_size.max(1))
+        .map(|page| Ok(page.to_vec()))
+        .collect::<Vec<_>>();
+    Ok(futures::stream::iter(pages).boxed())
+}
+
 pub(crate) const DEFAULT_LIST_LOCATION_PAGE_SIZE: usize = 1000;
 
+#[tracing::instrument(skip_all, fields(%location))]
 pub(crate) async fn list_location<'a>(
-    file_io: &'a FileIO,
+    file_io: &'a StorageIo,
     location: &'a Location,
     page_size: Option<usize>,
 ) -> Result<BoxStream<'a, std::result::Result<Vec<String>, IoError>>, IoError> {
@@ -140,6 +257,16 @@ pub(crate) async fn list_location<'a>(
     tracing::debug!("Listing location: {}", location);
     let size = page_size.unwrap_or(DEFAULT_LIST_LOCATION_PAGE_SIZE);
 
+    let file_io = match file_io {
+        StorageIo::FileIo(file_io) => file_io,
+        StorageIo::WebHdfs(client) => {
+            let entries = list_webhdfs_files(client, &location, size).await?;
+            return Ok(entries
+                .map(|page| page.map(|files| files.into_iter().map(|f| f.path).collect()))
+                .boxed());
+        }
+    };
+
     let entries = retry_fn(|| async {
         file_io
             .list_paginated(location.clone().as_str(), true, size)
@@ -160,6 +287,56 @@ pub(crate) async fn list_location<'a>(
     Ok(entries.boxed())
 }
 
+/// A file returned by [`list_files`].
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ListedFile {
+    pub(crate) path: String,
+    /// `None` if the storage does not report modification times when listing.
+    pub(crate) last_modified: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Recursively lists all files below `location`, including their modification time.
+/// Directories are omitted.
+#[tracing::instrument(skip_all, fields(%location))]
+pub(crate) async fn list_files<'a>(
+    file_io: &'a StorageIo,
+    location: &'a Location,
+    page_size: Option<usize>,
+) -> Result<BoxStream<'a, std::result::Result<Vec<ListedFile>, IoError>>, IoError> {
+    let location = normalize_location(location);
+    let location = format!("{}/", location.trim_end_matches('/'));
+    tracing::debug!("Listing files in location: {}", location);
+    let size = page_size.unwrap_or(DEFAULT_LIST_LOCATION_PAGE_SIZE);
+
+    let file_io = match file_io {
+        StorageIo::FileIo(file_io) => file_io,
+        StorageIo::WebHdfs(client) => return list_webhdfs_files(client, &location, size).await,
+    };
+
+    let entries = retry_fn(|| async {
+        file_io
+            .list_paginated(location.clone().as_str(), true, size)
+            .await
+            .map_err(|e| {
+                tracing::warn!(?e, "Failed to list files in location. Retry three times...");
+                IoError::List(e)
+            })
+    })
+    .await?
+    .map(|res| match res {
+        Ok(entries) => Ok(entries
+            .into_iter()
+            .filter(|it| !it.path().ends_with('/'))
+            .map(|it| ListedFile {
+                path: it.path().to_string(),
+                last_modified: it.metadata().last_modified(),
+            })
+            .collect()),
+        Err(e) => Err(IoError::List(e)),
+    });
+    Ok(entries.boxed())
+}
+
 #[derive(thiserror::Error, Debug, strum::IntoStaticStr)]
 pub enum IoError {
     #[error("Failed to create file. Please check the storage credentials: {}", .0)]
@@ -192,6 +369,10 @@ pub enum IoError {
     FileRemoveAll(#[source] iceberg::Error),
     #[error("Failed to list files in location. Please check the storage credentials: {}", .0)]
     List(#[source] iceberg::Error),
+    #[error("HDFS operation failed. Please check the storage credentials: {}", .0)]
+    WebHdfs(#[source] WebHdfsError),
+    #[error("{0} is not supported for this storage")]
+    Unsupported(&'static str),
 }
 
 impl IoError {
@@ -216,7 +397,13 @@ impl From<IoError> for IcebergErrorResponse {
             | IoError::FileWriterCreation(_)
             | IoError::FileCreation(_)
             | IoError::FileDecompression(_)
-            | IoError::List(_) => ErrorModel::failed_dependency(message, typ, Some(boxed)).into(),
+            | IoError::List(_)
+            | IoError::WebHdfs(_) => {
+                ErrorModel::failed_dependency(message, typ, Some(boxed)).into()
+            }
+            IoError::Unsupported(_) => {
+                ErrorModel::not_implemented(message, typ, Some(boxed)).into()
+            }
             IoError::FileCompression(_) | IoError::Write(_) | IoError::Serialization(_) => {
                 ErrorModel::internal(message, typ, Some(boxed)).into()
             }
@@ -236,7 +423,7 @@ mod tests {
 
     #[allow(dead_code)]
     async fn test_remove_all(cred: StorageCredential, profile: StorageProfile) {
-        async fn list_simple(file_io: &FileIO, location: &Location) -> Option<Vec<String>> {
+        async fn list_simple(file_io: &StorageIo, location: &Location) -> Option<Vec<String>> {
             let list = list_location(file_io, location, Some(10)).await.unwrap();
 
             list.collect::<Vec<_>>()
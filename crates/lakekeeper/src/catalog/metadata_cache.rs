@@ -0,0 +1,115 @@
+//! Parsed table metadata files by their location.
+//!
+//! Metadata files are never modified once written, so entries don't need to be invalidated
+//! when a table changes. Files written by table creations and commits are added right away,
+//! so that reading them later, for example when registering a table or importing its history,
+//! doesn't require a request to the object store. The cache is local to each instance.
+use std::{
+    sync::{Arc, LazyLock},
+    time::Duration,
+};
+
+use axum_prometheus::metrics;
+use iceberg::spec::TableMetadata;
+use iceberg_ext::configs::Location;
+
+use crate::CONFIG;
+
+/// Lookups of metadata files, labelled by their `result` (`hit` or `miss`).
+const METADATA_CACHE_REQUESTS: &str = "lakekeeper_metadata_cache_requests_total";
+
+/// Entries are weighted by the size of the serialized metadata,
+/// so that few large tables can't exhaust the memory of the instance.
+static METADATA_CACHE: LazyLock<moka::future::Cache<String, CachedMetadataFile>> =
+    LazyLock::new(|| {
+        moka::future::Cache::builder()
+            .max_capacity(CONFIG.metadata_cache_max_size_bytes)
+            .time_to_live(Duration::from_secs(CONFIG.metadata_cache_ttl_seconds))
+            .weigher(|_, file: &CachedMetadataFile| file.size)
+            .build()
+    });
+
+#[derive(Debug, Clone)]
+struct CachedMetadataFile {
+    metadata: Arc<TableMetadata>,
+    size: u32,
+}
+
+fn enabled() -> bool {
+    CONFIG.metadata_cache_max_size_bytes > 0
+}
+
+/// Get the metadata of a file if it is cached.
+pub(crate) async fn get(location: &Location) -> Option<TableMetadata> {
+    if !enabled() {
+        return None;
+    }
+    if let Some(file) = METADATA_CACHE.get(location.as_str()).await {
+        metrics::counter!(METADATA_CACHE_REQUESTS, "result" => "hit").increment(1);
+        return Some(Arc::unwrap_or_clone(file.metadata));
+    }
+    metrics::counter!(METADATA_CACHE_REQUESTS, "result" => "miss").increment(1);
+    None
+}
+
+/// Cache the metadata stored at `location`. `size` is the size of the serialized
+/// metadata in bytes.
+pub(crate) async fn insert(location: &Location, metadata: TableMetadata, size: usize) {
+    if !enabled() {
+        return;
+    }
+    METADATA_CACHE
+        .insert(
+            location.to_string(),
+            CachedMetadataFile {
+                metadata: Arc::new(metadata),
+                size: u32::try_from(size).unwrap_or(u32::MAX),
+            },
+        )
+        .await;
+}
+
+/// Remove all entries of this instance.
+pub(crate) fn flush() {
+    METADATA_CACHE.invalidate_all();
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashMap, str::FromStr};
+
+    use iceberg::spec::{
+        FormatVersion, Schema, SortOrder, TableMetadataBuilder, UnboundPartitionSpec,
+    };
+
+    use super::*;
+
+    fn metadata() -> TableMetadata {
+        TableMetadataBuilder::new(
+            Schema::builder().build().unwrap(),
+            UnboundPartitionSpec::builder().build(),
+            SortOrder::unsorted_order(),
+            "s3://bucket/table".to_string(),
+            FormatVersion::V2,
+            HashMap::new(),
+        )
+        .unwrap()
+        .build()
+        .unwrap()
+        .metadata
+    }
+
+    #[tokio::test]
+    async fn test_insert_get_flush() {
+        let location =
+            Location::from_str("s3://bucket/table/metadata/00001-test.metadata.json").unwrap();
+        assert!(get(&location).await.is_none());
+
+        let metadata = metadata();
+        insert(&location, metadata.clone(), 1024).await;
+        assert_eq!(get(&location).await, Some(metadata));
+
+        flush();
+        assert!(get(&location).await.is_none());
+    }
+}
@@ -1,13 +1,18 @@
+pub(crate) mod commit_queue;
 pub(crate) mod commit_tables;
 pub(crate) mod compression_codec;
-mod config;
+pub(crate) mod config;
 pub(crate) mod io;
+pub(crate) mod metadata_cache;
 mod metrics;
 pub(crate) mod namespace;
+mod oauth;
 #[cfg(feature = "s3-signer")]
 mod s3_signer;
+pub(crate) mod scan_planning;
 pub(crate) mod tables;
 pub(crate) mod tabular;
+pub(crate) mod upload_checksum;
 pub(crate) mod views;
 
 use std::{collections::HashMap, fmt::Debug, marker::PhantomData, sync::LazyLock};
@@ -51,6 +56,14 @@ pub struct CatalogServer<C: Catalog, A: Authorizer + Clone, S: SecretStore> {
     secret_store: PhantomData<S>,
 }
 
+/// Remove all shared cache entries of a warehouse after the warehouse was changed,
+/// for example its storage profile or status.
+pub(crate) async fn invalidate_warehouse_caches(warehouse_id: WarehouseId) {
+    config::invalidate_warehouse_config(warehouse_id).await;
+    namespace::invalidate_namespace_ids(warehouse_id).await;
+    tables::invalidate_warehouse_table_metadata(warehouse_id).await;
+}
+
 fn require_warehouse_id(prefix: Option<Prefix>) -> Result<WarehouseId> {
     prefix
         .ok_or_else(|| {
@@ -280,6 +293,7 @@ pub(crate) mod test {
             aws_access_key_id,
             aws_secret_access_key,
             external_id: None,
+            sse_customer_key: None,
         })
         .into();
 
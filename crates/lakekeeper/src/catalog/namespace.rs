@@ -1,4 +1,8 @@
-use std::{collections::HashMap, ops::Deref};
+use std::{
+    collections::HashMap,
+    ops::Deref,
+    sync::{Arc, LazyLock},
+};
 
 use futures::FutureExt;
 use http::StatusCode;
@@ -15,18 +19,26 @@ use crate::{
             GetNamespaceResponse, ListNamespacesQuery, ListNamespacesResponse, NamespaceParameters,
             Prefix, Result, UpdateNamespacePropertiesRequest, UpdateNamespacePropertiesResponse,
         },
-        management::v1::{warehouse::TabularDeleteProfile, TabularType},
+        management::v1::{warehouse::TabularDeleteProfile, DeleteKind, TabularType},
         set_not_found_status_code,
     },
     catalog,
     request_metadata::RequestMetadata,
     service::{
         authz::{Authorizer, CatalogNamespaceAction, CatalogWarehouseAction, NamespaceParent},
+        client_compatibility,
+        default_branch::{
+            invalidate_default_branches, validate_default_branch, DEFAULT_BRANCH_PROPERTY,
+        },
+        property_schemas::{require_valid_properties, PropertyEntityType},
+        quotas::require_namespace_depth_quota,
         secrets::SecretStore,
+        shared_cache::SharedCache,
         task_queue::{
+            tabular_expiration_queue::TabularExpirationPayload,
             tabular_purge_queue::TabularPurgePayload, EntityId, TaskFilter, TaskMetadata,
         },
-        Catalog, GetWarehouseResponse, NamespaceId, State, TabularId, Transaction,
+        Catalog, GetWarehouseResponse, NamespaceId, State, TableId, TabularId, Transaction, ViewId,
     },
     WarehouseId, CONFIG,
 };
@@ -37,6 +49,9 @@ pub const UNSUPPORTED_NAMESPACE_PROPERTIES: &[&str] = &[];
 pub const MAX_NAMESPACE_DEPTH: i32 = 5;
 pub const NAMESPACE_ID_PROPERTY: &str = "namespace_id";
 pub(crate) const MANAGED_ACCESS_PROPERTY: &str = "managed_access";
+/// Overrides the soft-delete expiration of the warehouse for all tables and views in the
+/// namespace and its children. Nested namespaces inherit the value of their closest ancestor.
+pub const SOFT_DELETE_EXPIRATION_SECONDS_PROPERTY: &str = "soft_delete_expiration_seconds";
 
 #[async_trait::async_trait]
 impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
@@ -174,6 +189,12 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             .into_iter()
             .map(|n| (n.namespace_ident, n.protected))
             .unzip();
+        let next_page_token = client_compatibility::next_page_token::<C>(
+            next_page_token,
+            warehouse_id,
+            state.v1_state.catalog,
+        )
+        .await;
         Ok(ListNamespacesResponse {
             next_page_token,
             namespaces,
@@ -232,7 +253,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
                 .await?;
         }
 
-        let mut t = C::Transaction::begin_write(state.v1_state.catalog).await?;
+        let mut t = C::Transaction::begin_write(state.v1_state.catalog.clone()).await?;
         let parent_id = if let Some(namespace_parent) = namespace.parent() {
             let parent_namespace_id =
                 C::namespace_to_id(warehouse_id, &namespace_parent, t.transaction()).await;
@@ -258,9 +279,24 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         // ------------------- BUSINESS LOGIC -------------------
         let namespace_id = NamespaceId::new_random();
         let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
+        require_valid_properties::<C>(
+            &warehouse.project_id,
+            PropertyEntityType::Namespace,
+            properties.iter().flatten(),
+            state.v1_state.catalog.clone(),
+        )
+        .await?;
+        require_namespace_depth_quota::<C>(
+            &warehouse.project_id,
+            namespace.len(),
+            state.v1_state.catalog,
+        )
+        .await?;
 
         let mut namespace_props = NamespaceProperties::try_from_maybe_props(properties.clone())
             .map_err(|e| ErrorModel::bad_request(e.to_string(), e.err_type(), None))?;
+        validate_soft_delete_expiration_property(&namespace_props)?;
+        validate_default_branch_property(&namespace_props)?;
         // Set location if not specified - validate location if specified
         set_namespace_location_property(&mut namespace_props, &warehouse, namespace_id)?;
         remove_managed_namespace_properties(&mut namespace_props);
@@ -282,6 +318,18 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         r.properties
             .as_mut()
             .map(|p| p.insert(NAMESPACE_ID_PROPERTY.to_string(), namespace_id.to_string()));
+
+        state
+            .v1_state
+            .hooks
+            .create_namespace(
+                warehouse_id,
+                namespace_id,
+                Arc::new(r.clone()),
+                Arc::new(request_metadata),
+            )
+            .await;
+
         Ok(r)
     }
 
@@ -386,24 +434,50 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         .await?;
 
         //  ------------------- BUSINESS LOGIC -------------------
-        if flags.recursive {
-            try_recursive_drop(
-                flags,
-                state,
-                warehouse_id,
-                t,
-                namespace_id,
-                &request_metadata,
-            )
-            .await
-        } else {
-            C::drop_namespace(warehouse_id, namespace_id, flags, t.transaction()).await?;
-            authorizer
-                .delete_namespace(&request_metadata, namespace_id)
+        let hooks = state.v1_state.hooks.clone();
+        let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
+        let delete_profile =
+            resolve_tabular_delete_profile::<C>(&warehouse, &parameters.namespace, &mut t).await?;
+        match delete_profile {
+            TabularDeleteProfile::Soft { expiration_seconds } if !flags.force => {
+                soft_drop::<A, C>(
+                    flags,
+                    expiration_seconds,
+                    authorizer,
+                    warehouse_id,
+                    t,
+                    namespace_id,
+                    &request_metadata,
+                )
+                .await?;
+            }
+            _ if flags.recursive => {
+                recursive_drop(
+                    flags,
+                    state,
+                    warehouse_id,
+                    t,
+                    namespace_id,
+                    &request_metadata,
+                )
                 .await?;
-            t.commit().await?;
-            Ok(())
+            }
+            _ => {
+                C::drop_namespace(warehouse_id, namespace_id, flags, t.transaction()).await?;
+                authorizer
+                    .delete_namespace(&request_metadata, namespace_id)
+                    .await?;
+                t.commit().await?;
+            }
         }
+
+        invalidate_namespace_ids(warehouse_id).await;
+        invalidate_default_branches(warehouse_id).await;
+
+        hooks
+            .drop_namespace(warehouse_id, namespace_id, Arc::new(request_metadata))
+            .await;
+        Ok(())
     }
 
     /// Set or remove properties on a namespace
@@ -429,10 +503,12 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         namespace_location_may_not_change(updates.as_ref(), removals.as_ref())?;
         let mut updates = NamespaceProperties::try_from_maybe_props(updates.clone())
             .map_err(|e| ErrorModel::bad_request(e.to_string(), e.err_type(), None))?;
+        validate_soft_delete_expiration_property(&updates)?;
+        validate_default_branch_property(&updates)?;
         remove_managed_namespace_properties(&mut updates);
         //  ------------------- AUTHZ -------------------
         let authorizer = state.v1_state.authz.clone();
-        let mut t = C::Transaction::begin_write(state.v1_state.catalog).await?;
+        let mut t = C::Transaction::begin_write(state.v1_state.catalog.clone()).await?;
         let namespace_id = authorized_namespace_ident_to_id::<C, _>(
             authorizer,
             &request_metadata,
@@ -444,6 +520,14 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         .await?;
 
         //  ------------------- BUSINESS LOGIC -------------------
+        let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
+        require_valid_properties::<C>(
+            &warehouse.project_id,
+            PropertyEntityType::Namespace,
+            updates.inner(),
+            state.v1_state.catalog,
+        )
+        .await?;
         let previous_properties =
             C::get_namespace(warehouse_id, namespace_id, t.transaction()).await?;
         let (new_properties, r) =
@@ -451,11 +535,24 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         C::update_namespace_properties(warehouse_id, namespace_id, new_properties, t.transaction())
             .await?;
         t.commit().await?;
+        invalidate_default_branches(warehouse_id).await;
+
+        state
+            .v1_state
+            .hooks
+            .update_namespace_properties(
+                warehouse_id,
+                namespace_id,
+                Arc::new(r.clone()),
+                Arc::new(request_metadata),
+            )
+            .await;
+
         Ok(r)
     }
 }
 
-async fn try_recursive_drop<A: Authorizer, C: Catalog, S: SecretStore>(
+async fn recursive_drop<A: Authorizer, C: Catalog, S: SecretStore>(
     flags: NamespaceDropFlags,
     state: ApiContext<State<A, C, S>>,
     warehouse_id: WarehouseId,
@@ -463,69 +560,149 @@ async fn try_recursive_drop<A: Authorizer, C: Catalog, S: SecretStore>(
     namespace_id: NamespaceId,
     request_metadata: &RequestMetadata,
 ) -> Result<()> {
-    let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
-    if matches!(
-        warehouse.tabular_delete_profile,
-        TabularDeleteProfile::Hard {}
-    ) || (flags.force
-        && matches!(
-            warehouse.tabular_delete_profile,
-            TabularDeleteProfile::Soft { .. }
-        ))
-    {
-        let drop_info =
-            C::drop_namespace(warehouse_id, namespace_id, flags, t.transaction()).await?;
+    let drop_info = C::drop_namespace(warehouse_id, namespace_id, flags, t.transaction()).await?;
 
-        // cancel pending tasks
-        C::cancel_tabular_expiration(TaskFilter::TaskIds(drop_info.open_tasks), t.transaction())
-            .await?;
+    // cancel pending tasks
+    C::cancel_tabular_expiration(TaskFilter::TaskIds(drop_info.open_tasks), t.transaction())
+        .await?;
 
-        if flags.purge {
-            for (tabular_id, tabular_location) in drop_info.child_tables {
-                let (tabular_id, tabular_type) = match tabular_id {
-                    TabularId::Table(id) => (id, TabularType::Table),
-                    TabularId::View(id) => (id, TabularType::View),
-                };
-                C::queue_tabular_purge(
-                    TaskMetadata {
-                        warehouse_id,
-                        entity_id: EntityId::Tabular(tabular_id),
-                        parent_task_id: None,
-                        schedule_for: None,
-                    },
-                    TabularPurgePayload {
-                        tabular_location,
-                        tabular_type,
-                    },
-                    t.transaction(),
-                )
-                .await?;
-            }
+    if flags.purge {
+        // Files of external tables are owned by another system and are never purged.
+        for (tabular_id, tabular_location) in drop_info
+            .child_tables
+            .iter()
+            .filter(|(id, _)| !drop_info.external_tables.contains(id))
+        {
+            let (tabular_id, tabular_type) = match *tabular_id {
+                TabularId::Table(id) => (id, TabularType::Table),
+                TabularId::View(id) => (id, TabularType::View),
+            };
+            C::queue_tabular_purge(
+                TaskMetadata {
+                    warehouse_id,
+                    entity_id: EntityId::Tabular(tabular_id),
+                    parent_task_id: None,
+                    schedule_for: None,
+                },
+                TabularPurgePayload {
+                    tabular_location: tabular_location.clone(),
+                    tabular_type,
+                },
+                t.transaction(),
+            )
+            .await?;
         }
-        // commit before starting the purge tasks so that we cannot end in the situation where
-        // data is deleted but the transaction is not committed, meaning dangling pointers.
-        t.commit().await?;
+    }
+    // commit before starting the purge tasks so that we cannot end in the situation where
+    // data is deleted but the transaction is not committed, meaning dangling pointers.
+    t.commit().await?;
+
+    // the hierarchy is gone from catalog, we should not return an error to the client if we fail
+    // to delete it from the authorizer.
+    let authorizer = &state.v1_state.authz;
+    for (tabular_id, _) in drop_info.child_tables {
+        let result = match tabular_id {
+            TabularId::Table(id) => authorizer.delete_table(TableId::from(id)).await,
+            TabularId::View(id) => authorizer.delete_view(ViewId::from(id)).await,
+        };
+        result
+            .inspect_err(|err| {
+                tracing::error!(
+                    "Failed to delete {tabular_id} from authorizer: {}",
+                    err.error
+                );
+            })
+            .ok();
+    }
+    for child_namespace_id in drop_info
+        .child_namespaces
+        .into_iter()
+        .chain(std::iter::once(namespace_id))
+    {
+        authorizer
+            .delete_namespace(request_metadata, child_namespace_id)
+            .await
+            .inspect_err(|err| {
+                tracing::error!("Failed to delete namespace from authorizer: {}", err.error);
+            })
+            .ok();
+    }
+    Ok(())
+}
 
+/// Soft-delete the namespace and, if `recursive` is set, its content.
+///
+/// All tables and views dropped as part of the cascade expire together, using the
+/// expiration of the dropped namespace. Until then the namespace can be restored with
+/// the undrop endpoint of the management API. Permissions are kept so that they are
+/// restored as well.
+async fn soft_drop<A: Authorizer, C: Catalog>(
+    flags: NamespaceDropFlags,
+    expiration_seconds: chrono::Duration,
+    authorizer: A,
+    warehouse_id: WarehouseId,
+    mut t: <C as Catalog>::Transaction,
+    namespace_id: NamespaceId,
+    request_metadata: &RequestMetadata,
+) -> Result<()> {
+    let Some(drop_info) =
+        C::mark_namespace_as_deleted(warehouse_id, namespace_id, flags, t.transaction()).await?
+    else {
+        t.commit().await?;
         // namespace is gone from catalog, we should not return an error to the client if we fail to
         // delete it from the authorizer.
-        state
-            .v1_state
-            .authz
+        authorizer
             .delete_namespace(request_metadata, namespace_id)
             .await
             .inspect_err(|err| {
                 tracing::error!("Failed to delete namespace from authorizer: {}", err.error);
             })
             .ok();
-        Ok(())
-    } else {
-        Err(ErrorModel::bad_request(
-            "Cannot recursively delete namespace with soft-deletion without force flag",
-            "NamespaceDeleteNotAllowed",
-            None,
+        return Ok(());
+    };
+
+    let schedule_for = chrono::Utc::now() + expiration_seconds;
+    for (tabular_id, _) in drop_info.child_tables {
+        let purge = flags.purge && !drop_info.external_tables.contains(&tabular_id);
+        let (tabular_id, tabular_type) = match tabular_id {
+            TabularId::Table(id) => (id, TabularType::Table),
+            TabularId::View(id) => (id, TabularType::View),
+        };
+        let _ = C::queue_tabular_expiration(
+            TaskMetadata {
+                warehouse_id,
+                entity_id: EntityId::Tabular(tabular_id),
+                parent_task_id: None,
+                schedule_for: Some(schedule_for),
+            },
+            TabularExpirationPayload {
+                tabular_type,
+                deletion_kind: if purge {
+                    DeleteKind::Purge
+                } else {
+                    DeleteKind::Default
+                },
+            },
+            t.transaction(),
         )
-        .into())
+        .await?;
     }
+    tracing::debug!("Soft-deleted namespace '{namespace_id}'.");
+    t.commit().await?;
+    Ok(())
+}
+
+/// Ids of existing namespaces by `{warehouse_id}:{namespace}`, so that replicas don't
+/// resolve the namespace of every table request.
+static NAMESPACE_ID_CACHE: LazyLock<SharedCache<NamespaceId>> =
+    LazyLock::new(|| SharedCache::new("namespace-id"));
+
+/// Remove the cached namespace ids of a warehouse after namespaces were dropped.
+/// Nested namespaces are dropped with their parent, so all namespaces are invalidated.
+pub(crate) async fn invalidate_namespace_ids(warehouse_id: WarehouseId) {
+    NAMESPACE_ID_CACHE
+        .invalidate(&warehouse_id.to_string())
+        .await;
 }
 
 pub(crate) async fn authorized_namespace_ident_to_id<C: Catalog, A: Authorizer + Clone>(
@@ -540,7 +717,18 @@ pub(crate) async fn authorized_namespace_ident_to_id<C: Catalog, A: Authorizer +
     authorizer
         .require_warehouse_action(metadata, *warehouse_id, CatalogWarehouseAction::CanUse)
         .await?;
-    let namespace_id = C::namespace_to_id(*warehouse_id, namespace, transaction).await; // Cannot fail before authz
+    let cache_key = format!("{warehouse_id}:{}", namespace.to_url_string());
+    let namespace_id = if let Some(namespace_id) = NAMESPACE_ID_CACHE.get(&cache_key).await {
+        Ok(Some(namespace_id))
+    } else {
+        let namespace_id = C::namespace_to_id(*warehouse_id, namespace, transaction).await; // Cannot fail before authz
+        if let Ok(Some(namespace_id)) = &namespace_id {
+            NAMESPACE_ID_CACHE
+                .insert(cache_key, *namespace_id, None)
+                .await;
+        }
+        namespace_id
+    };
     authorizer
         .require_namespace_action(metadata, namespace_id, action)
         .await
@@ -614,12 +802,94 @@ pub(crate) fn validate_namespace_ident(namespace: &NamespaceIdent) -> Result<()>
     Ok(())
 }
 
-fn remove_managed_namespace_properties(namespace_props: &mut NamespaceProperties) {
+fn parse_soft_delete_expiration(value: &str) -> Result<chrono::Duration> {
+    value
+        .parse::<i64>()
+        .ok()
+        .filter(|seconds| *seconds >= 0)
+        .and_then(chrono::Duration::try_seconds)
+        .ok_or_else(|| {
+            ErrorModel::bad_request(
+                format!(
+                    "The property '{SOFT_DELETE_EXPIRATION_SECONDS_PROPERTY}' must be a non-negative number of seconds, got '{value}'."
+                ),
+                "InvalidSoftDeleteExpiration",
+                None,
+            )
+            .into()
+        })
+}
+
+fn validate_soft_delete_expiration_property(namespace_props: &NamespaceProperties) -> Result<()> {
+    namespace_props
+        .get_custom_prop(SOFT_DELETE_EXPIRATION_SECONDS_PROPERTY)
+        .map(|value| parse_soft_delete_expiration(&value))
+        .transpose()
+        .map(|_| ())
+}
+
+fn validate_default_branch_property(namespace_props: &NamespaceProperties) -> Result<()> {
+    namespace_props
+        .get_custom_prop(DEFAULT_BRANCH_PROPERTY)
+        .map_or(Ok(()), |value| validate_default_branch(&value))
+}
+
+/// Determine the delete profile for a tabular in `namespace`.
+///
+/// For warehouses with soft-deletion, the expiration of the warehouse is overridden by the
+/// [`SOFT_DELETE_EXPIRATION_SECONDS_PROPERTY`] of the closest namespace that sets it,
+/// starting at `namespace` itself and walking up to the top-level namespace.
+pub(crate) async fn resolve_tabular_delete_profile<C: Catalog>(
+    warehouse: &GetWarehouseResponse,
+    namespace: &NamespaceIdent,
+    t: &mut C::Transaction,
+) -> Result<TabularDeleteProfile> {
+    if matches!(
+        warehouse.tabular_delete_profile,
+        TabularDeleteProfile::Hard {}
+    ) {
+        return Ok(warehouse.tabular_delete_profile);
+    }
+
+    let ancestors = (1..=namespace.len())
+        .rev()
+        .filter_map(|depth| NamespaceIdent::from_vec(namespace[..depth].to_vec()).ok());
+    for ancestor in ancestors {
+        let Some(namespace_id) =
+            C::namespace_to_id(warehouse.id, &ancestor, t.transaction()).await?
+        else {
+            continue;
+        };
+        let properties = C::get_namespace(warehouse.id, namespace_id, t.transaction())
+            .await?
+            .properties
+            .unwrap_or_default();
+        let Some(value) = properties.get(SOFT_DELETE_EXPIRATION_SECONDS_PROPERTY) else {
+            continue;
+        };
+        match parse_soft_delete_expiration(value) {
+            Ok(expiration_seconds) => {
+                return Ok(TabularDeleteProfile::Soft { expiration_seconds });
+            }
+            Err(e) => {
+                // Values stored before validation was introduced must not block drops.
+                tracing::warn!(
+                    "Ignoring invalid soft-delete expiration of namespace '{namespace_id}': {}",
+                    e.error
+                );
+            }
+        }
+    }
+
+    Ok(warehouse.tabular_delete_profile)
+}
+
+pub(crate) fn remove_managed_namespace_properties(namespace_props: &mut NamespaceProperties) {
     namespace_props.remove_untyped(NAMESPACE_ID_PROPERTY);
     namespace_props.remove_untyped(MANAGED_ACCESS_PROPERTY);
 }
 
-fn set_namespace_location_property(
+pub(crate) fn set_namespace_location_property(
     namespace_props: &mut NamespaceProperties,
     warehouse: &GetWarehouseResponse,
     namespace_id: NamespaceId,
@@ -723,10 +993,13 @@ fn namespace_location_may_not_change(
 #[cfg(test)]
 mod tests {
 
-    use std::{collections::HashSet, hash::RandomState};
+    use std::{
+        collections::{HashMap, HashSet},
+        hash::RandomState,
+    };
 
-    use iceberg::NamespaceIdent;
-    use iceberg_ext::catalog::rest::CreateNamespaceRequest;
+    use iceberg::{NamespaceIdent, TableIdent};
+    use iceberg_ext::catalog::rest::{CreateNamespaceRequest, UpdateNamespacePropertiesRequest};
     use sqlx::PgPool;
 
     use crate::{
@@ -735,11 +1008,13 @@ mod tests {
                 types::{PageToken, Prefix},
                 v1::{
                     namespace::{NamespaceDropFlags, NamespaceService},
-                    NamespaceParameters,
+                    tables::TablesService,
+                    DataAccess, DropParams, NamespaceParameters, TableParameters,
                 },
             },
             management::v1::{
-                namespace::NamespaceManagementService, warehouse::TabularDeleteProfile,
+                namespace::NamespaceManagementService,
+                warehouse::{ListDeletedTabularsQuery, Service as _, TabularDeleteProfile},
                 ApiServer as ManagementApiServer,
             },
             ApiContext,
@@ -1175,6 +1450,134 @@ mod tests {
         }
     }
 
+    #[sqlx::test]
+    async fn test_soft_delete_expiration_is_inherited_from_namespace(pool: PgPool) {
+        let prof = crate::catalog::test::test_io_profile();
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool.clone(),
+            prof,
+            None,
+            AllowAllAuthorizer,
+            TabularDeleteProfile::Soft {
+                expiration_seconds: chrono::Duration::days(7),
+            },
+            Some(UserId::new_unchecked("oidc", "test-user-id")),
+        )
+        .await;
+        let prefix = Some(Prefix(warehouse.warehouse_id.to_string()));
+
+        let e = CatalogServer::create_namespace(
+            prefix.clone(),
+            CreateNamespaceRequest {
+                namespace: NamespaceIdent::new("invalid".to_string()),
+                properties: Some(HashMap::from_iter([(
+                    super::SOFT_DELETE_EXPIRATION_SECONDS_PROPERTY.to_string(),
+                    "-1".to_string(),
+                )])),
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(e.error.code, http::StatusCode::BAD_REQUEST);
+
+        let parent = NamespaceIdent::new("parent".to_string());
+        let child = NamespaceIdent::from_strs(["parent", "child"]).unwrap();
+        for namespace in [&parent, &child] {
+            CatalogServer::create_namespace(
+                prefix.clone(),
+                CreateNamespaceRequest {
+                    namespace: namespace.clone(),
+                    properties: None,
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+            .await
+            .unwrap();
+        }
+
+        let e = CatalogServer::update_namespace_properties(
+            NamespaceParameters {
+                prefix: prefix.clone(),
+                namespace: parent.clone(),
+            },
+            UpdateNamespacePropertiesRequest {
+                removals: None,
+                updates: Some(HashMap::from_iter([(
+                    super::SOFT_DELETE_EXPIRATION_SECONDS_PROPERTY.to_string(),
+                    "one hour".to_string(),
+                )])),
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(e.error.code, http::StatusCode::BAD_REQUEST);
+
+        CatalogServer::update_namespace_properties(
+            NamespaceParameters {
+                prefix: prefix.clone(),
+                namespace: parent.clone(),
+            },
+            UpdateNamespacePropertiesRequest {
+                removals: None,
+                updates: Some(HashMap::from_iter([(
+                    super::SOFT_DELETE_EXPIRATION_SECONDS_PROPERTY.to_string(),
+                    "3600".to_string(),
+                )])),
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+
+        let table = TableIdent::new(child.clone(), "my_table".to_string());
+        CatalogServer::create_table(
+            NamespaceParameters {
+                prefix: prefix.clone(),
+                namespace: child.clone(),
+            },
+            crate::catalog::tables::test::create_request(Some(table.name.clone()), None),
+            DataAccess::not_specified(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        CatalogServer::drop_table(
+            TableParameters {
+                prefix: prefix.clone(),
+                table,
+            },
+            DropParams {
+                purge_requested: false,
+                force: false,
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+
+        let deleted = ManagementApiServer::list_soft_deleted_tabulars(
+            warehouse.warehouse_id,
+            ListDeletedTabularsQuery::default(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap()
+        .tabulars;
+        assert_eq!(deleted.len(), 1);
+        let expiration = deleted[0].expiration_date - deleted[0].deleted_at;
+        assert!(expiration <= chrono::Duration::hours(1));
+        assert!(expiration > chrono::Duration::minutes(59));
+    }
+
     #[test]
     fn test_update_ns_properties() {
         use super::*;
@@ -0,0 +1,155 @@
+//! Emulation of the deprecated `/v1/oauth/tokens` endpoint of the Iceberg REST specification.
+//!
+//! Client credentials are exchanged for a token at the token endpoint of the OpenID provider,
+//! so that clients configured with `credential=<client_id>:<client_secret>` work unchanged.
+use http::StatusCode;
+use iceberg_ext::catalog::rest::{
+    OAuthAccessTokenType, OAuthClientCredentialsRequest, OAuthError, OAuthErrorType,
+    OAuthTokenRequest, OAuthTokenResponse,
+};
+
+use super::CatalogServer;
+use crate::{
+    api::iceberg::v1::{oauth::OAuthErrorResponse, ApiContext},
+    request_metadata::RequestMetadata,
+    service::{
+        authz::Authorizer,
+        client_compatibility::{token_endpoint, HTTP_CLIENT},
+        secrets::SecretStore,
+        Catalog, State,
+    },
+    CONFIG,
+};
+
+/// Scope requested by Iceberg clients if none is configured.
+/// It has no meaning for OpenID providers, which may reject it.
+const ICEBERG_DEFAULT_SCOPE: &str = "catalog";
+
+#[async_trait::async_trait]
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
+    crate::api::iceberg::v1::oauth::Service<State<A, C, S>> for CatalogServer<C, A, S>
+{
+    async fn get_token(
+        _: ApiContext<State<A, C, S>>,
+        _: RequestMetadata,
+        request: OAuthTokenRequest,
+    ) -> std::result::Result<OAuthTokenResponse, OAuthErrorResponse> {
+        match request {
+            OAuthTokenRequest::OAuthClientCredentialsRequest(request) => {
+                client_credentials(request).await
+            }
+            OAuthTokenRequest::OAuthTokenExchangeRequest(_) => Err(OAuthErrorResponse::new(
+                StatusCode::BAD_REQUEST,
+                OAuthErrorType::UnsupportedGrantType,
+                "Token exchange is not supported. Request a new token with client credentials instead.",
+            )),
+        }
+    }
+}
+
+/// Successful token response of the OpenID provider. Parsed leniently, as providers
+/// differ in the capitalization of `token_type`.
+#[derive(Debug, serde::Deserialize)]
+struct ProviderTokenResponse {
+    access_token: String,
+    token_type: String,
+    expires_in: Option<u64>,
+    refresh_token: Option<String>,
+    scope: Option<String>,
+}
+
+async fn client_credentials(
+    request: OAuthClientCredentialsRequest,
+) -> std::result::Result<OAuthTokenResponse, OAuthErrorResponse> {
+    let OAuthClientCredentialsRequest {
+        scope,
+        client_id,
+        client_secret,
+    } = request;
+    let token_endpoint = token_endpoint().await.map_err(|e| server_error(&e.error))?;
+
+    let mut form = vec![
+        ("grant_type", "client_credentials".to_string()),
+        ("client_id", client_id),
+        ("client_secret", client_secret),
+    ];
+    if let Some(scope) = provider_scope(scope) {
+        form.push(("scope", scope));
+    }
+
+    let response = HTTP_CLIENT
+        .post(token_endpoint)
+        .form(&form)
+        .send()
+        .await
+        .map_err(|e| server_error(&e))?;
+    let status = response.status();
+    let body = response.bytes().await.map_err(|e| server_error(&e))?;
+
+    if status.is_success() {
+        let token =
+            serde_json::from_slice::<ProviderTokenResponse>(&body).map_err(|e| server_error(&e))?;
+        if !token.token_type.eq_ignore_ascii_case("bearer") {
+            return Err(server_error(&format!(
+                "Unsupported token type '{}'",
+                token.token_type
+            )));
+        }
+        Ok(OAuthTokenResponse {
+            access_token: token.access_token,
+            token_type: OAuthAccessTokenType::Bearer,
+            expires_in: token.expires_in,
+            issued_token_type: None,
+            refresh_token: token.refresh_token,
+            scope: token.scope,
+        })
+    } else if status.is_client_error() {
+        // Errors of the provider, e.g. invalid credentials, are passed on to the client.
+        let error = serde_json::from_slice::<OAuthError>(&body).unwrap_or(OAuthError {
+            error: OAuthErrorType::InvalidClient,
+            error_description: Some(String::from_utf8_lossy(&body).into_owned()),
+            error_uri: None,
+        });
+        Err(OAuthErrorResponse { status, error })
+    } else {
+        Err(server_error(&format!(
+            "OpenID provider responded with status {status}"
+        )))
+    }
+}
+
+/// Scope to request from the OpenID provider. The default scope of Iceberg clients
+/// is replaced by the scope Lakekeeper requires in tokens.
+fn provider_scope(requested: Option<String>) -> Option<String> {
+    match requested {
+        Some(scope) if scope != ICEBERG_DEFAULT_SCOPE => Some(scope),
+        _ => CONFIG.openid_scope.clone(),
+    }
+}
+
+fn server_error(e: &dyn std::fmt::Display) -> OAuthErrorResponse {
+    tracing::error!("Failed to obtain token from OpenID provider: {e}");
+    OAuthErrorResponse::new(
+        StatusCode::BAD_GATEWAY,
+        OAuthErrorType::ServerError,
+        "Failed to obtain token from OpenID provider",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provider_scope() {
+        assert_eq!(
+            provider_scope(Some("lakekeeper".to_string())),
+            Some("lakekeeper".to_string())
+        );
+        assert_eq!(
+            provider_scope(Some(ICEBERG_DEFAULT_SCOPE.to_string())),
+            CONFIG.openid_scope
+        );
+        assert_eq!(provider_scope(None), CONFIG.openid_scope);
+    }
+}
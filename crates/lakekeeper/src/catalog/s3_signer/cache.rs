@@ -3,10 +3,21 @@ use std::{
     time::{Duration, Instant},
 };
 
-use crate::{service::storage::s3::S3UrlStyleDetectionMode, WarehouseId};
+use crate::{
+    service::storage::{s3::S3UrlStyleDetectionMode, S3AccessPoint},
+    WarehouseId,
+};
+
+/// Settings of a warehouse's storage profile required to parse request URLs.
+#[derive(Debug, Clone)]
+pub(super) struct WarehouseS3UrlSettings {
+    pub(super) url_style: S3UrlStyleDetectionMode,
+    pub(super) bucket: String,
+    pub(super) access_point: Option<S3AccessPoint>,
+}
 
-pub(super) static WAREHOUSE_S3_URL_STYLE_CACHE: LazyLock<
-    moka::future::Cache<WarehouseId, S3UrlStyleDetectionMode>,
+pub(super) static WAREHOUSE_S3_URL_SETTINGS_CACHE: LazyLock<
+    moka::future::Cache<WarehouseId, WarehouseS3UrlSettings>,
 > = LazyLock::new(|| {
     moka::future::Cache::builder()
         .max_capacity(10000)
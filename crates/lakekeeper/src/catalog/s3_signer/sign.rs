@@ -1,12 +1,14 @@
 use std::{collections::HashMap, sync::Arc, time::SystemTime, vec};
 
 use aws_sigv4::{
-    http_request::{sign as aws_sign, SignableBody, SignableRequest, SigningSettings},
-    sign::v4,
+    http_request::{
+        sign as aws_sign, SignableBody, SignableRequest, SigningParams, SigningSettings,
+    },
+    sign::{v4, v4a},
     {self},
 };
 
-use super::{super::CatalogServer, error::SignError};
+use super::{super::CatalogServer, cache::WarehouseS3UrlSettings, error::SignError};
 use crate::{
     api::{
         iceberg::types::Prefix, ApiContext, ErrorModel, IcebergErrorResponse, Result,
@@ -16,15 +18,22 @@ use crate::{
     request_metadata::RequestMetadata,
     service::{
         authz::{Authorizer, CatalogTableAction, CatalogWarehouseAction},
+        labels::require_table_data_access,
+        s3_sign_audit::audit_signing,
         secrets::SecretStore,
         storage::{
-            s3::S3UrlStyleDetectionMode, S3Credential, S3Location, S3Profile, StorageCredential,
+            s3::{S3ServerSideEncryption, S3UrlStyleDetectionMode},
+            S3Credential, S3Location, S3Profile, StorageCredential,
         },
         Catalog, GetTableMetadataResponse, ListFlags, State, TableId, Transaction,
     },
     WarehouseId,
 };
 
+/// Header that charges the request to the requester for buckets with requester-pays enabled.
+const REQUEST_PAYER_HEADER: &str = "x-amz-request-payer";
+const REQUEST_PAYER_REQUESTER: &str = "requester";
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Operation {
     Read,
@@ -65,12 +74,29 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         // Include staged tables as this might be a commit
         let include_staged = true;
 
-        let (parsed_url, operation) = s3_utils::parse_s3_url(
+        let url_settings =
+            s3_url_settings::<C>(state.v1_state.catalog.clone(), warehouse_id).await?;
+        // Access point hosts are always virtual-host style and don't contain the bucket name.
+        let via_access_point = url_settings.access_point.as_ref().is_some_and(|ap| {
+            request_url
+                .host_str()
+                .is_some_and(|host| ap.matches_host(host))
+        });
+        let url_style = if via_access_point {
+            S3UrlStyleDetectionMode::VirtualHost
+        } else {
+            url_settings.url_style
+        };
+
+        let (mut parsed_url, operation) = s3_utils::parse_s3_url(
             &request_url,
-            s3_url_style_detection::<C>(state.v1_state.catalog.clone(), warehouse_id).await?,
+            url_style,
             &request_method,
             request_body.as_deref(),
         )?;
+        if via_access_point {
+            parsed_url = parsed_url.with_bucket(&url_settings.bucket)?;
+        }
 
         let GetTableMetadataResponse {
             table: _,
@@ -81,6 +107,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             metadata_location: _,
             storage_secret_ident,
             storage_profile,
+            external,
         } = if let Some(table_id) = path_table_id.map(Into::into) {
             let metadata_by_id = get_unauthorized_table_metadata_by_id(
                 warehouse_id,
@@ -150,7 +177,25 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
 
         // First check - fail fast if requested table is not allowed.
         // We also need to check later if the path matches the table location.
+        require_table_data_access::<C, A>(
+            &authorizer,
+            &request_metadata,
+            warehouse_id,
+            table_id,
+            state.v1_state.catalog.clone(),
+        )
+        .await?;
         authorize_operation::<A>(operation, &request_metadata, table_id, authorizer).await?;
+        // Files of external tables are owned by another system.
+        if external && !matches!(operation, Operation::Read) {
+            return Err(ErrorModel::forbidden(
+                "External tables are read-only, only read requests are signed.",
+                "ExternalTableReadOnly",
+                None,
+            )
+            .append_detail(format!("Table ID: {table_id}"))
+            .into());
+        }
 
         let extend_err = |mut e: IcebergErrorResponse| {
             e.error = e
@@ -167,6 +212,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             .map_err(|e| extend_err(IcebergErrorResponse::from(e)))?;
 
         validate_region(&request_region, &storage_profile).map_err(extend_err)?;
+        validate_checksum_trailers(&request_headers, &storage_profile).map_err(extend_err)?;
         validate_uri(&parsed_url, &location).map_err(extend_err)?;
 
         // If all is good, we need the storage secret
@@ -190,7 +236,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         })
         .transpose()?;
 
-        sign(
+        let response = sign(
             &storage_profile,
             storage_secret.as_ref(),
             request_body,
@@ -200,38 +246,58 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             request_headers,
         )
         .await
-        .map_err(extend_err)
+        .map_err(extend_err)?;
+
+        audit_signing::<C>(
+            warehouse_id,
+            table_id,
+            &request_method,
+            parsed_url
+                .locations
+                .iter()
+                .map(|l| l.as_normalized_location().to_string())
+                .collect(),
+            &request_metadata,
+            state.v1_state.catalog.clone(),
+        )
+        .await?;
+
+        Ok(response)
     }
 }
 
-async fn s3_url_style_detection<C: Catalog>(
+async fn s3_url_settings<C: Catalog>(
     state: C::State,
     warehouse_id: WarehouseId,
-) -> Result<S3UrlStyleDetectionMode, IcebergErrorResponse> {
-    let t = super::cache::WAREHOUSE_S3_URL_STYLE_CACHE
+) -> Result<WarehouseS3UrlSettings, IcebergErrorResponse> {
+    let t = super::cache::WAREHOUSE_S3_URL_SETTINGS_CACHE
         .try_get_with(warehouse_id, async {
             tracing::trace!("No cache hit for {warehouse_id}");
             let mut tx = C::Transaction::begin_read(state).await?;
             let result = C::require_warehouse(warehouse_id, tx.transaction())
                 .await
                 .map(|w| {
-                    w.storage_profile
-                        .try_into_s3()
-                        .map(|s| s.remote_signing_url_style)
-                        .map_err(|e| {
-                            IcebergErrorResponse::from(ErrorModel::bad_request(
-                                "Warehouse storage profile is not an S3 profile",
-                                "InvalidWarehouse",
-                                Some(Box::new(e)),
-                            ))
-                        })
+                    let s = w.storage_profile.try_into_s3().map_err(|e| {
+                        IcebergErrorResponse::from(ErrorModel::bad_request(
+                            "Warehouse storage profile is not an S3 profile",
+                            "InvalidWarehouse",
+                            Some(Box::new(e)),
+                        ))
+                    })?;
+                    Ok::<_, IcebergErrorResponse>(WarehouseS3UrlSettings {
+                        url_style: s.remote_signing_url_style,
+                        access_point: s.access_point()?,
+                        bucket: s.bucket,
+                    })
                 })?;
             tx.commit().await?;
             result
         })
         .await
         .map_err(|e: Arc<IcebergErrorResponse>| {
-            tracing::debug!("Failed to get warehouse S3 URL style detection mode from cache due to error: '{e:?}'");
+            tracing::debug!(
+                "Failed to get warehouse S3 URL settings from cache due to error: '{e:?}'"
+            );
             IcebergErrorResponse::from(ErrorModel::new(
                 e.error.message.as_str(),
                 e.error.r#type.as_str(),
@@ -251,8 +317,26 @@ async fn sign(
     request_region: &str,
     request_url: &url::Url,
     request_method: &http::Method,
-    request_headers: HashMap<String, Vec<String>>,
+    mut request_headers: HashMap<String, Vec<String>>,
 ) -> Result<S3SignResponse> {
+    if let Some(sse) = storage_profile.server_side_encryption(credentials)? {
+        for (key, value) in sse_headers(&sse, request_method, request_url) {
+            if !request_headers.keys().any(|k| k.eq_ignore_ascii_case(key)) {
+                request_headers.insert(key.to_string(), vec![value]);
+            }
+        }
+    }
+    if storage_profile.requester_pays
+        && !request_headers
+            .keys()
+            .any(|k| k.eq_ignore_ascii_case(REQUEST_PAYER_HEADER))
+    {
+        request_headers.insert(
+            REQUEST_PAYER_HEADER.to_string(),
+            vec![REQUEST_PAYER_REQUESTER.to_string()],
+        );
+    }
+
     let body = request_body.map(std::string::String::into_bytes);
     let signable_body = if let Some(body) = &body {
         SignableBody::Bytes(body)
@@ -275,22 +359,31 @@ async fn sign(
         })?;
     let identity = aws_credentials.into();
     // let identity = credentials.into();
-    let signing_params = v4::SigningParams::builder()
-        .identity(&identity)
-        .region(request_region)
-        .name("s3")
-        .time(SystemTime::now())
-        .settings(sign_settings)
-        .build()
-        .map_err(|e| {
-            ErrorModel::builder()
-                .code(http::StatusCode::INTERNAL_SERVER_ERROR.into())
-                .message("Failed to create signing params".to_string())
-                .r#type("FailedToCreateSigningParams".to_string())
-                .source(Some(Box::new(e)))
-                .build()
-        })?
-        .into();
+    // Multi-Region Access Points route requests to any region and require SigV4A.
+    let is_multi_region_request = storage_profile.access_point()?.is_some_and(|ap| {
+        ap.is_multi_region() && request_url.host_str().is_some_and(|h| ap.matches_host(h))
+    });
+    let signing_params: SigningParams<'_> = if is_multi_region_request {
+        v4a::SigningParams::builder()
+            .identity(&identity)
+            .region_set("*")
+            .name("s3")
+            .time(SystemTime::now())
+            .settings(sign_settings)
+            .build()
+            .map_err(signing_params_error)?
+            .into()
+    } else {
+        v4::SigningParams::builder()
+            .identity(&identity)
+            .region(request_region)
+            .name("s3")
+            .time(SystemTime::now())
+            .settings(sign_settings)
+            .build()
+            .map_err(signing_params_error)?
+            .into()
+    };
 
     let mut headers_vec: Vec<(String, String)> = Vec::new();
 
@@ -345,6 +438,73 @@ async fn sign(
     Ok(sign_response)
 }
 
+fn signing_params_error(e: impl std::error::Error + Send + Sync + 'static) -> ErrorModel {
+    ErrorModel::builder()
+        .code(http::StatusCode::INTERNAL_SERVER_ERROR.into())
+        .message("Failed to create signing params".to_string())
+        .r#type("FailedToCreateSigningParams".to_string())
+        .source(Some(Box::new(e)))
+        .build()
+}
+
+/// Encryption headers S3 requires for the request, so that clients which don't
+/// handle server-side encryption themselves still write encrypted objects.
+///
+/// SSE-KMS headers are only allowed when objects are created, SSE-C headers are
+/// required on every request that reads or writes object data.
+fn sse_headers(
+    sse: &S3ServerSideEncryption,
+    method: &http::Method,
+    url: &url::Url,
+) -> Vec<(&'static str, String)> {
+    let has_query = |name: &str| url.query_pairs().any(|(k, _)| k == name);
+    let creates_object = (*method == http::Method::PUT && !has_query("partNumber"))
+        || (*method == http::Method::POST && has_query("uploads"));
+    let uploads_part = *method == http::Method::PUT && has_query("partNumber");
+    let reads_object = (*method == http::Method::GET || *method == http::Method::HEAD)
+        && !["list-type", "prefix", "delimiter", "uploads", "uploadId"]
+            .iter()
+            .any(|q| has_query(q));
+
+    match sse {
+        S3ServerSideEncryption::Kms {
+            key_id,
+            bucket_key_enabled,
+        } if creates_object => {
+            let mut headers = vec![
+                ("x-amz-server-side-encryption", "aws:kms".to_string()),
+                (
+                    "x-amz-server-side-encryption-aws-kms-key-id",
+                    key_id.clone(),
+                ),
+            ];
+            if *bucket_key_enabled {
+                headers.push((
+                    "x-amz-server-side-encryption-bucket-key-enabled",
+                    "true".to_string(),
+                ));
+            }
+            headers
+        }
+        S3ServerSideEncryption::CustomerKey { key, key_md5 }
+            if creates_object || uploads_part || reads_object =>
+        {
+            vec![
+                (
+                    "x-amz-server-side-encryption-customer-algorithm",
+                    "AES256".to_string(),
+                ),
+                ("x-amz-server-side-encryption-customer-key", key.clone()),
+                (
+                    "x-amz-server-side-encryption-customer-key-MD5",
+                    key_md5.clone(),
+                ),
+            ]
+        }
+        _ => vec![],
+    }
+}
+
 fn urldecode_uri_path_segments(uri: &url::Url) -> Result<url::Url> {
     // We only modify path segments. Iterate over all path segments and unr urlencoding::decode them.
     let mut new_uri = uri.clone();
@@ -390,6 +550,36 @@ fn validate_region(region: &str, storage_profile: &S3Profile) -> Result<()> {
     Ok(())
 }
 
+/// Stores that don't support trailing checksums fail `aws-chunked` uploads with
+/// errors that are hard to trace back. Reject them early with a hint instead.
+fn validate_checksum_trailers(
+    request_headers: &HashMap<String, Vec<String>>,
+    storage_profile: &S3Profile,
+) -> Result<()> {
+    if !storage_profile.quirks.no_checksum_trailers {
+        return Ok(());
+    }
+
+    let uses_trailer = request_headers.iter().any(|(key, values)| {
+        key.eq_ignore_ascii_case("x-amz-trailer")
+            || (key.eq_ignore_ascii_case("x-amz-content-sha256")
+                && values
+                    .iter()
+                    .any(|v| v.starts_with("STREAMING-") && v.ends_with("-TRAILER")))
+    });
+
+    if uses_trailer {
+        return Err(ErrorModel::bad_request(
+            "The storage of this warehouse does not support trailing checksums. Configure the client to only calculate checksums when required, for example by setting `AWS_REQUEST_CHECKSUM_CALCULATION=WHEN_REQUIRED`.",
+            "ChecksumTrailersNotSupported",
+            None,
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
 async fn authorize_operation<A: Authorizer>(
     method: Operation,
     metadata: &RequestMetadata,
@@ -535,6 +725,20 @@ pub(super) mod s3_utils {
         pub(super) port: u16,
     }
 
+    impl ParsedSignRequest {
+        /// Replace the bucket of all locations.
+        /// Requests via access points address objects by key only, the host
+        /// identifies the access point instead of the bucket.
+        pub(super) fn with_bucket(mut self, bucket: &str) -> Result<Self> {
+            self.locations = self
+                .locations
+                .into_iter()
+                .map(|location| S3Location::new(bucket.to_string(), location.key().clone(), None))
+                .collect::<Result<_, _>>()?;
+            Ok(self)
+        }
+    }
+
     /// Represents the top-level S3 Delete request structure
     #[derive(Debug, Deserialize, Serialize, PartialEq, Eq)]
     #[serde(rename = "Delete", rename_all = "PascalCase")]
@@ -1272,6 +1476,29 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_uri_access_point() {
+        for request_uri in [
+            "https://my-ap-123456789012.s3-accesspoint.eu-central-1.amazonaws.com/tables/t1/data.parquet",
+            "https://mfzwi23gnjvgw.mrap.accesspoint.s3-global.amazonaws.com/tables/t1/data.parquet",
+        ] {
+            let (parsed, _) = s3_utils::parse_s3_url(
+                &url::Url::parse(request_uri).unwrap(),
+                S3UrlStyleDetectionMode::VirtualHost,
+                &http::Method::GET,
+                None,
+            )
+            .unwrap();
+            let parsed = parsed.with_bucket("my-bucket").unwrap();
+            assert_eq!(
+                parsed.locations[0].as_normalized_location().to_string(),
+                "s3://my-bucket/tables/t1/data.parquet"
+            );
+            validate_uri(&parsed, "s3://my-bucket/tables/t1").unwrap();
+            validate_uri(&parsed, "s3://my-bucket/tables/t2").unwrap_err();
+        }
+    }
+
     #[test]
     fn test_validate_region() {
         let storage_profile = S3Profile::builder()
@@ -1287,4 +1514,75 @@ mod test {
         let result = validate_region("wrong-region", &storage_profile);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_sse_headers() {
+        let kms = S3ServerSideEncryption::Kms {
+            key_id: "alias/lakekeeper".to_string(),
+            bucket_key_enabled: true,
+        };
+        let object = url::Url::parse("https://bucket.s3.amazonaws.com/table/data.parquet").unwrap();
+        let part = url::Url::parse(
+            "https://bucket.s3.amazonaws.com/table/data.parquet?partNumber=1&uploadId=abc",
+        )
+        .unwrap();
+        let create_upload =
+            url::Url::parse("https://bucket.s3.amazonaws.com/table/data.parquet?uploads").unwrap();
+        let list =
+            url::Url::parse("https://bucket.s3.amazonaws.com/?list-type=2&prefix=table").unwrap();
+
+        assert_eq!(sse_headers(&kms, &http::Method::PUT, &object).len(), 3);
+        assert_eq!(
+            sse_headers(&kms, &http::Method::POST, &create_upload).len(),
+            3
+        );
+        assert!(sse_headers(&kms, &http::Method::PUT, &part).is_empty());
+        assert!(sse_headers(&kms, &http::Method::GET, &object).is_empty());
+
+        let customer_key = S3ServerSideEncryption::CustomerKey {
+            key: "key".to_string(),
+            key_md5: "md5".to_string(),
+        };
+        assert_eq!(
+            sse_headers(&customer_key, &http::Method::GET, &object).len(),
+            3
+        );
+        assert_eq!(
+            sse_headers(&customer_key, &http::Method::PUT, &part).len(),
+            3
+        );
+        assert!(sse_headers(&customer_key, &http::Method::GET, &list).is_empty());
+        assert!(sse_headers(&customer_key, &http::Method::DELETE, &object).is_empty());
+    }
+
+    #[test]
+    fn test_validate_checksum_trailers() {
+        let mut storage_profile = S3Profile::builder()
+            .region("my-region".to_string())
+            .flavor(S3Flavor::S3Compat)
+            .sts_enabled(false)
+            .bucket("should-not-be-used".to_string())
+            .build();
+        let headers = HashMap::from([
+            (
+                "X-Amz-Content-Sha256".to_string(),
+                vec!["STREAMING-UNSIGNED-PAYLOAD-TRAILER".to_string()],
+            ),
+            (
+                "x-amz-trailer".to_string(),
+                vec!["x-amz-checksum-crc32".to_string()],
+            ),
+        ]);
+        let plain_headers = HashMap::from([(
+            "x-amz-content-sha256".to_string(),
+            vec!["UNSIGNED-PAYLOAD".to_string()],
+        )]);
+
+        assert!(validate_checksum_trailers(&headers, &storage_profile).is_ok());
+
+        storage_profile.quirks.no_checksum_trailers = true;
+        let err = validate_checksum_trailers(&headers, &storage_profile).unwrap_err();
+        assert_eq!(err.error.r#type, "ChecksumTrailersNotSupported");
+        assert!(validate_checksum_trailers(&plain_headers, &storage_profile).is_ok());
+    }
 }
@@ -0,0 +1,975 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    str::FromStr as _,
+};
+
+use base64::Engine;
+use futures::{StreamExt, TryStreamExt};
+use http::StatusCode;
+use iceberg::{
+    io::FileIO,
+    spec::{
+        DataContentType, DataFile, Datum, Literal, ManifestContentType, ManifestFile,
+        ManifestStatus, PrimitiveType, SnapshotRef, StructType, TableMetadata, Type,
+    },
+};
+use iceberg_ext::catalog::rest::{
+    ContentFile, ContentType, CountMap, FetchPlanningResult, FetchScanTasksRequest,
+    FetchScanTasksResult, FileScanTask, PlanStatus, PlanTableScanRequest, PlanTableScanResult,
+    ScanTasks, StorageCredential, ValueMap,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    maybe_get_secret, require_warehouse_id,
+    tables::{parse_location, require_not_staged, take_table_metadata},
+    CatalogServer,
+};
+use crate::{
+    api::iceberg::v1::{ApiContext, DataAccess, ErrorModel, Result, TableParameters},
+    request_metadata::RequestMetadata,
+    service::{
+        authz::Authorizer, storage::StoragePermissions, Catalog, ListFlags,
+        LoadTableResponse as CatalogLoadTableResult, ScanPlan, ScanPlanId, SecretStore, State,
+        TableId, Transaction,
+    },
+    WarehouseId, CONFIG,
+};
+
+/// Number of manifests that are read concurrently while resolving file scan tasks.
+const MANIFEST_READ_CONCURRENCY: usize = 8;
+
+/// Opaque plan task handed out to clients.
+///
+/// A plan task references a range of data manifests in the manifest list of the
+/// snapshot the plan was created for. The plan itself is persisted, so that
+/// plan tasks become invalid once the plan is cancelled or expired.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct PlanTask {
+    plan_id: ScanPlanId,
+    offset: usize,
+    limit: usize,
+}
+
+impl PlanTask {
+    fn encode(&self) -> String {
+        // Serializing a struct of primitives cannot fail
+        let json = serde_json::to_vec(self).unwrap_or_default();
+        base64::prelude::BASE64_URL_SAFE_NO_PAD.encode(json)
+    }
+
+    fn decode(plan_task: &str) -> Result<Self> {
+        base64::prelude::BASE64_URL_SAFE_NO_PAD
+            .decode(plan_task)
+            .ok()
+            .and_then(|json| serde_json::from_slice(&json).ok())
+            .ok_or_else(|| no_such_plan_task(plan_task).into())
+    }
+}
+
+/// Everything required to plan a scan or to fetch results for a table.
+struct PlanningContext {
+    warehouse_id: WarehouseId,
+    table_id: TableId,
+    table_metadata: TableMetadata,
+    file_io: FileIO,
+    storage_credentials: Option<Vec<StorageCredential>>,
+}
+
+pub(crate) async fn plan_table_scan<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    parameters: TableParameters,
+    request: PlanTableScanRequest,
+    data_access: DataAccess,
+    state: ApiContext<State<A, C, S>>,
+    request_metadata: RequestMetadata,
+) -> Result<PlanTableScanResult> {
+    // ------------------- VALIDATIONS -------------------
+    validate_plan_request(&request)?;
+
+    // ------------------- AUTHZ -------------------
+    let PlanningContext {
+        warehouse_id,
+        table_id,
+        table_metadata,
+        file_io,
+        storage_credentials,
+    } = load_planning_context(parameters, data_access, &state, &request_metadata).await?;
+
+    // ------------------- BUSINESS LOGIC -------------------
+    let selection = ScanSelection::resolve(&table_metadata, &request)?;
+    let plan_id = ScanPlanId::now_v7();
+
+    // The plan is persisted before planning starts, so that plan tasks as well as
+    // results of plans that complete in the background can reference it.
+    let mut t = C::Transaction::begin_write(state.v1_state.catalog.clone()).await?;
+    C::create_scan_plan(
+        ScanPlan {
+            plan_id,
+            warehouse_id,
+            table_id,
+            snapshot_id: selection.as_ref().map(ScanSelection::snapshot_id),
+            request: request.clone(),
+            status: PlanStatus::Submitted,
+            result: None,
+            error: None,
+            expires_at: chrono::Utc::now() + CONFIG.scan_plan_expiration_seconds,
+        },
+        t.transaction(),
+    )
+    .await?;
+    t.commit().await?;
+
+    let catalog_state = state.v1_state.catalog;
+    let planning = tokio::spawn(async move {
+        let result = match selection {
+            Some(selection) => {
+                plan_scan(&table_metadata, &file_io, &request, &selection, plan_id).await
+            }
+            None => Ok(ScanTasks::default()),
+        };
+        let stored = match &result {
+            Ok(scan_tasks) => Ok(scan_tasks.clone()),
+            Err(e) => Err(ErrorModel::new(
+                e.error.message.clone(),
+                e.error.r#type.clone(),
+                e.error.code,
+                None,
+            )),
+        };
+        if let Err(e) = C::set_scan_plan_result(plan_id, stored, catalog_state).await {
+            tracing::warn!("Failed to store result of scan plan {plan_id}: {}", e.error);
+        }
+        result
+    });
+
+    match tokio::time::timeout(CONFIG.scan_planning_sync_timeout, planning).await {
+        Ok(Ok(Ok(scan_tasks))) => Ok(PlanTableScanResult::Completed {
+            // A plan-id indicates that the server holds state for the client.
+            plan_id: (!scan_tasks.plan_tasks.is_empty()).then(|| plan_id.to_string()),
+            storage_credentials: (!scan_tasks.file_scan_tasks.is_empty())
+                .then_some(storage_credentials)
+                .flatten(),
+            scan_tasks,
+        }),
+        Ok(Ok(Err(e))) => Err(e.into()),
+        Ok(Err(e)) => Err(ErrorModel::internal(
+            "Scan planning failed unexpectedly",
+            "ScanPlanningError",
+            Some(Box::new(e)),
+        )
+        .into()),
+        Err(_) => {
+            tracing::debug!(
+                "Scan plan {plan_id} did not complete in time, continuing in background"
+            );
+            Ok(PlanTableScanResult::Submitted {
+                plan_id: plan_id.to_string(),
+            })
+        }
+    }
+}
+
+pub(crate) async fn fetch_planning_result<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    parameters: TableParameters,
+    plan_id: String,
+    data_access: DataAccess,
+    state: ApiContext<State<A, C, S>>,
+    request_metadata: RequestMetadata,
+) -> Result<FetchPlanningResult> {
+    // ------------------- AUTHZ -------------------
+    let PlanningContext {
+        warehouse_id,
+        table_id,
+        storage_credentials,
+        ..
+    } = load_planning_context(parameters, data_access, &state, &request_metadata).await?;
+
+    // ------------------- BUSINESS LOGIC -------------------
+    let plan_id = parse_plan_id(&plan_id)?;
+    let plan = C::get_scan_plan(warehouse_id, table_id, plan_id, state.v1_state.catalog)
+        .await?
+        .ok_or_else(|| no_such_plan_id(plan_id))?;
+
+    Ok(match plan.status {
+        PlanStatus::Submitted => FetchPlanningResult::Submitted,
+        PlanStatus::Cancelled => FetchPlanningResult::Cancelled,
+        PlanStatus::Failed => FetchPlanningResult::Failed {
+            error: plan.error.unwrap_or_else(|| {
+                ErrorModel::internal("Scan planning failed", "ScanPlanningError", None)
+            }),
+        },
+        PlanStatus::Completed => {
+            let scan_tasks = plan.result.unwrap_or_default();
+            FetchPlanningResult::Completed {
+                storage_credentials: (!scan_tasks.file_scan_tasks.is_empty())
+                    .then_some(storage_credentials)
+                    .flatten(),
+                scan_tasks,
+            }
+        }
+    })
+}
+
+pub(crate) async fn cancel_planning<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    parameters: TableParameters,
+    plan_id: String,
+    state: ApiContext<State<A, C, S>>,
+    request_metadata: RequestMetadata,
+) -> Result<()> {
+    // ------------------- AUTHZ -------------------
+    let TableParameters { prefix, table } = parameters;
+    let warehouse_id = require_warehouse_id(prefix)?;
+    let mut t = C::Transaction::begin_read(state.v1_state.catalog.clone()).await?;
+    let (tabular_details, _) = CatalogServer::<C, A, S>::resolve_and_authorize_table_access(
+        &request_metadata,
+        &table,
+        warehouse_id,
+        ListFlags::default(),
+        state.v1_state.authz,
+        state.v1_state.catalog.clone(),
+        t.transaction(),
+    )
+    .await?;
+    t.commit().await?;
+
+    // ------------------- BUSINESS LOGIC -------------------
+    let plan_id = parse_plan_id(&plan_id)?;
+    C::cancel_scan_plan(
+        warehouse_id,
+        tabular_details.ident,
+        plan_id,
+        state.v1_state.catalog,
+    )
+    .await?
+    .ok_or_else(|| no_such_plan_id(plan_id))?;
+
+    Ok(())
+}
+
+pub(crate) async fn fetch_scan_tasks<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    parameters: TableParameters,
+    request: FetchScanTasksRequest,
+    data_access: DataAccess,
+    state: ApiContext<State<A, C, S>>,
+    request_metadata: RequestMetadata,
+) -> Result<FetchScanTasksResult> {
+    // ------------------- AUTHZ -------------------
+    let PlanningContext {
+        warehouse_id,
+        table_id,
+        table_metadata,
+        file_io,
+        storage_credentials,
+    } = load_planning_context(parameters, data_access, &state, &request_metadata).await?;
+
+    // ------------------- BUSINESS LOGIC -------------------
+    let plan_task = PlanTask::decode(&request.plan_task)?;
+    let plan = C::get_scan_plan(
+        warehouse_id,
+        table_id,
+        plan_task.plan_id,
+        state.v1_state.catalog,
+    )
+    .await?
+    .filter(|plan| plan.status == PlanStatus::Completed)
+    .ok_or_else(|| no_such_plan_task(&request.plan_task))?;
+
+    // The snapshot might have been expired since the plan was created.
+    let selection = ScanSelection::resolve(&table_metadata, &plan.request)
+        .ok()
+        .flatten()
+        .filter(|s| Some(s.snapshot_id()) == plan.snapshot_id)
+        .ok_or_else(|| no_such_plan_task(&request.plan_task))?;
+
+    let manifests = load_manifests(&table_metadata, &file_io, &selection).await?;
+    let data_manifests = manifests
+        .data
+        .into_iter()
+        .skip(plan_task.offset)
+        .take(plan_task.limit)
+        .collect::<Vec<_>>();
+    let scan_tasks = resolve_file_scan_tasks(
+        &table_metadata,
+        &file_io,
+        &plan.request,
+        &selection,
+        &data_manifests,
+        &manifests.deletes,
+    )
+    .await?;
+
+    Ok(FetchScanTasksResult {
+        storage_credentials: (!scan_tasks.file_scan_tasks.is_empty())
+            .then_some(storage_credentials)
+            .flatten(),
+        scan_tasks,
+    })
+}
+
+async fn load_planning_context<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    parameters: TableParameters,
+    data_access: DataAccess,
+    state: &ApiContext<State<A, C, S>>,
+    request_metadata: &RequestMetadata,
+) -> Result<PlanningContext> {
+    let TableParameters { prefix, table } = parameters;
+    let warehouse_id = require_warehouse_id(prefix)?;
+    let list_flags = ListFlags::default();
+
+    let mut t = C::Transaction::begin_read(state.v1_state.catalog.clone()).await?;
+    let (tabular_details, storage_permissions) =
+        CatalogServer::<C, A, S>::resolve_and_authorize_table_access(
+            request_metadata,
+            &table,
+            warehouse_id,
+            list_flags,
+            state.v1_state.authz.clone(),
+            state.v1_state.catalog.clone(),
+            t.transaction(),
+        )
+        .await?;
+    if storage_permissions.is_none() {
+        return Err(ErrorModel::forbidden(
+            "Scan planning requires permission to read table data",
+            "NoStoragePermissions",
+            None,
+        )
+        .into());
+    }
+
+    let mut metadatas = C::load_tables(
+        warehouse_id,
+        vec![tabular_details.ident],
+        list_flags.include_deleted,
+        t.transaction(),
+    )
+    .await?;
+    t.commit().await?;
+    let CatalogLoadTableResult {
+        table_id,
+        namespace_id: _,
+        table_metadata,
+        metadata_location,
+        storage_secret_ident,
+        storage_profile,
+        external: _,
+    } = take_table_metadata(&tabular_details.ident, &table, &mut metadatas)?;
+    require_not_staged(metadata_location.as_ref())?;
+
+    let storage_secret = maybe_get_secret(storage_secret_ident, &state.v1_state.secrets).await?;
+    let file_io = storage_profile
+        .file_io(storage_secret.as_ref())
+        .await?
+        .iceberg_file_io("Scan planning")?
+        .clone();
+
+    // Credentials returned alongside file scan tasks only grant read access,
+    // independent of the permissions of the caller.
+    let storage_credentials = if data_access.requested() {
+        let table_location =
+            parse_location(table_metadata.location(), StatusCode::INTERNAL_SERVER_ERROR)?;
+        let storage_config = storage_profile
+            .generate_table_config(
+                data_access,
+                storage_secret.as_ref(),
+                &table_location,
+                StoragePermissions::Read,
+                request_metadata,
+                warehouse_id,
+                table_id.into(),
+            )
+            .await?;
+        (!storage_config.creds.inner().is_empty()).then(|| {
+            vec![StorageCredential {
+                prefix: table_location.to_string(),
+                config: storage_config.creds.into(),
+            }]
+        })
+    } else {
+        None
+    };
+
+    Ok(PlanningContext {
+        warehouse_id,
+        table_id,
+        table_metadata,
+        file_io,
+        storage_credentials,
+    })
+}
+
+fn validate_plan_request(request: &PlanTableScanRequest) -> Result<()> {
+    let incremental = request.start_snapshot_id.is_some() || request.end_snapshot_id.is_some();
+    if incremental && request.snapshot_id.is_some() {
+        return Err(ErrorModel::bad_request(
+            "Point-in-time (snapshot-id) and incremental (start-snapshot-id, end-snapshot-id) scans are mutually exclusive",
+            "InvalidScanPlanRequest",
+            None,
+        )
+        .into());
+    }
+    if request.start_snapshot_id.is_some() && request.end_snapshot_id.is_none() {
+        return Err(ErrorModel::bad_request(
+            "end-snapshot-id is required when start-snapshot-id is specified",
+            "InvalidScanPlanRequest",
+            None,
+        )
+        .into());
+    }
+    Ok(())
+}
+
+fn parse_plan_id(plan_id: &str) -> Result<ScanPlanId> {
+    ScanPlanId::from_str(plan_id).map_err(|_| {
+        ErrorModel::not_found(
+            format!("Plan '{plan_id}' does not exist"),
+            "NoSuchPlanIdException",
+            None,
+        )
+        .into()
+    })
+}
+
+fn no_such_plan_id(plan_id: ScanPlanId) -> ErrorModel {
+    ErrorModel::not_found(
+        format!("Plan '{plan_id}' does not exist"),
+        "NoSuchPlanIdException",
+        None,
+    )
+}
+
+fn no_such_plan_task(plan_task: &str) -> ErrorModel {
+    ErrorModel::not_found(
+        format!("Plan task '{plan_task}' does not exist"),
+        "NoSuchPlanTaskException",
+        None,
+    )
+}
+
+fn planning_error(message: &str, e: iceberg::Error) -> ErrorModel {
+    ErrorModel::internal(message, "ScanPlanningError", Some(Box::new(e)))
+}
+
+/// Snapshot(s) a scan is planned for.
+#[derive(Debug, Clone)]
+enum ScanSelection {
+    /// Scan all live files of a snapshot.
+    PointInTime { snapshot: SnapshotRef },
+    /// Scan files appended by the snapshots after the start snapshot (exclusive)
+    /// up to and including the end snapshot.
+    Incremental {
+        end_snapshot: SnapshotRef,
+        snapshot_ids: HashSet<i64>,
+    },
+}
+
+impl ScanSelection {
+    /// Returns `Ok(None)` if the table has no snapshot to scan.
+    fn resolve(metadata: &TableMetadata, request: &PlanTableScanRequest) -> Result<Option<Self>> {
+        let find_snapshot = |snapshot_id: i64| {
+            metadata
+                .snapshot_by_id(snapshot_id)
+                .cloned()
+                .ok_or_else(|| {
+                    ErrorModel::bad_request(
+                        format!("Snapshot {snapshot_id} does not exist"),
+                        "SnapshotNotFound",
+                        None,
+                    )
+                })
+        };
+
+        if let Some(end_snapshot_id) = request.end_snapshot_id {
+            let end_snapshot = find_snapshot(end_snapshot_id)?;
+            let mut snapshot_ids = HashSet::new();
+            let mut current = Some(end_snapshot.clone());
+            while let Some(snapshot) = current {
+                if Some(snapshot.snapshot_id()) == request.start_snapshot_id {
+                    break;
+                }
+                snapshot_ids.insert(snapshot.snapshot_id());
+                current = snapshot
+                    .parent_snapshot_id()
+                    .and_then(|id| metadata.snapshot_by_id(id).cloned());
+                if current.is_none() && request.start_snapshot_id.is_some() {
+                    return Err(ErrorModel::bad_request(
+                        format!(
+                            "Start snapshot {} is not an ancestor of end snapshot {end_snapshot_id}",
+                            request.start_snapshot_id.unwrap_or_default()
+                        ),
+                        "InvalidScanPlanRequest",
+                        None,
+                    )
+                    .into());
+                }
+            }
+            return Ok(Some(Self::Incremental {
+                end_snapshot,
+                snapshot_ids,
+            }));
+        }
+
+        let snapshot = match request.snapshot_id {
+            Some(snapshot_id) => Some(find_snapshot(snapshot_id)?),
+            None => metadata.current_snapshot().cloned(),
+        };
+        Ok(snapshot.map(|snapshot| Self::PointInTime { snapshot }))
+    }
+
+    fn snapshot(&self) -> &SnapshotRef {
+        match self {
+            Self::PointInTime { snapshot } => snapshot,
+            Self::Incremental { end_snapshot, .. } => end_snapshot,
+        }
+    }
+
+    fn snapshot_id(&self) -> i64 {
+        self.snapshot().snapshot_id()
+    }
+
+    fn includes_manifest(&self, manifest: &ManifestFile) -> bool {
+        match self {
+            Self::PointInTime { .. } => true,
+            // Only appends are considered for incremental scans
+            Self::Incremental { snapshot_ids, .. } => {
+                manifest.content == ManifestContentType::Data
+                    && snapshot_ids.contains(&manifest.added_snapshot_id)
+            }
+        }
+    }
+
+    fn includes_entry(&self, status: ManifestStatus, snapshot_id: Option<i64>) -> bool {
+        match self {
+            Self::PointInTime { .. } => status != ManifestStatus::Deleted,
+            Self::Incremental { snapshot_ids, .. } => {
+                status == ManifestStatus::Added
+                    && snapshot_id.is_some_and(|id| snapshot_ids.contains(&id))
+            }
+        }
+    }
+}
+
+struct SnapshotManifests {
+    data: Vec<ManifestFile>,
+    deletes: Vec<ManifestFile>,
+}
+
+async fn load_manifests(
+    metadata: &TableMetadata,
+    file_io: &FileIO,
+    selection: &ScanSelection,
+) -> Result<SnapshotManifests> {
+    let manifest_list = selection
+        .snapshot()
+        .load_manifest_list(file_io, metadata)
+        .await
+        .map_err(|e| planning_error("Failed to read manifest list", e))?;
+
+    let (data, deletes) = manifest_list
+        .entries()
+        .iter()
+        .filter(|m| selection.includes_manifest(m))
+        .cloned()
+        .partition(|m| m.content == ManifestContentType::Data);
+
+    Ok(SnapshotManifests { data, deletes })
+}
+
+async fn plan_scan(
+    metadata: &TableMetadata,
+    file_io: &FileIO,
+    request: &PlanTableScanRequest,
+    selection: &ScanSelection,
+    plan_id: ScanPlanId,
+) -> Result<ScanTasks> {
+    let manifests = load_manifests(metadata, file_io, selection).await?;
+    let manifests_per_task = CONFIG.scan_planning_manifests_per_plan_task.max(1);
+
+    if manifests.data.len() <= manifests_per_task {
+        return resolve_file_scan_tasks(
+            metadata,
+            file_io,
+            request,
+            selection,
+            &manifests.data,
+            &manifests.deletes,
+        )
+        .await;
+    }
+
+    let plan_tasks = (0..manifests.data.len())
+        .step_by(manifests_per_task)
+        .map(|offset| {
+            PlanTask {
+                plan_id,
+                offset,
+                limit: manifests_per_task,
+            }
+            .encode()
+        })
+        .collect();
+
+    Ok(ScanTasks {
+        delete_files: vec![],
+        file_scan_tasks: vec![],
+        plan_tasks,
+    })
+}
+
+/// A delete file together with the information required to match it to data files.
+struct DeleteCandidate {
+    file: ContentFile,
+    sequence_number: i64,
+    global: bool,
+}
+
+async fn resolve_file_scan_tasks(
+    metadata: &TableMetadata,
+    file_io: &FileIO,
+    request: &PlanTableScanRequest,
+    selection: &ScanSelection,
+    data_manifests: &[ManifestFile],
+    delete_manifests: &[ManifestFile],
+) -> Result<ScanTasks> {
+    let converter = ContentFileConverter::new(metadata, request, selection)?;
+
+    let read_entries = |manifests: &[ManifestFile]| {
+        futures::stream::iter(manifests.to_vec())
+            .map(|manifest| async move {
+                let spec_id = manifest.partition_spec_id;
+                manifest
+                    .load_manifest(file_io)
+                    .await
+                    .map(|m| (spec_id, m))
+                    .map_err(|e| planning_error("Failed to read manifest", e))
+            })
+            .buffered(MANIFEST_READ_CONCURRENCY)
+            .try_collect::<Vec<_>>()
+    };
+
+    let mut delete_candidates = vec![];
+    for (spec_id, manifest) in read_entries(delete_manifests).await? {
+        for entry in manifest.entries() {
+            if !entry.is_alive() {
+                continue;
+            }
+            let file = converter.convert(entry.data_file(), spec_id, false)?;
+            let global = file.content == ContentType::EqualityDeletes && file.partition.is_empty();
+            delete_candidates.push(DeleteCandidate {
+                file,
+                sequence_number: entry.sequence_number().unwrap_or_default(),
+                global,
+            });
+        }
+    }
+
+    let mut delete_files = vec![];
+    let mut delete_file_indices: HashMap<usize, usize> = HashMap::new();
+    let mut file_scan_tasks = vec![];
+    for (spec_id, manifest) in read_entries(data_manifests).await? {
+        for entry in manifest.entries() {
+            if !selection.includes_entry(entry.status(), entry.snapshot_id()) {
+                continue;
+            }
+            let data_file = converter.convert(entry.data_file(), spec_id, true)?;
+            let data_sequence_number = entry.sequence_number().unwrap_or_default();
+
+            let references = delete_candidates
+                .iter()
+                .enumerate()
+                .filter(|(_, candidate)| applies_to(candidate, &data_file, data_sequence_number))
+                .map(|(i, candidate)| {
+                    *delete_file_indices.entry(i).or_insert_with(|| {
+                        delete_files.push(candidate.file.clone());
+                        delete_files.len() - 1
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            file_scan_tasks.push(FileScanTask {
+                data_file,
+                delete_file_references: (!references.is_empty()).then_some(references),
+                // Filter expressions are not evaluated server-side,
+                // clients apply the original filter.
+                residual_filter: None,
+            });
+        }
+    }
+
+    Ok(ScanTasks {
+        delete_files,
+        file_scan_tasks,
+        plan_tasks: vec![],
+    })
+}
+
+/// Applies the delete file scoping rules of the iceberg spec.
+fn applies_to(
+    delete: &DeleteCandidate,
+    data_file: &ContentFile,
+    data_sequence_number: i64,
+) -> bool {
+    let same_partition =
+        delete.file.spec_id == data_file.spec_id && delete.file.partition == data_file.partition;
+    match delete.file.content {
+        ContentType::PositionDeletes => {
+            delete.sequence_number >= data_sequence_number && same_partition
+        }
+        ContentType::EqualityDeletes => {
+            delete.sequence_number > data_sequence_number && (delete.global || same_partition)
+        }
+        ContentType::Data => false,
+    }
+}
+
+struct ContentFileConverter {
+    partition_types: HashMap<i32, StructType>,
+    /// Field ids to return column statistics for. `None` if no stats are requested.
+    stats_field_ids: Option<HashSet<i32>>,
+}
+
+impl ContentFileConverter {
+    fn new(
+        metadata: &TableMetadata,
+        request: &PlanTableScanRequest,
+        selection: &ScanSelection,
+    ) -> Result<Self> {
+        let schema = if request.use_snapshot_schema {
+            selection
+                .snapshot()
+                .schema_id()
+                .and_then(|id| metadata.schema_by_id(id))
+                .unwrap_or_else(|| metadata.current_schema())
+        } else {
+            metadata.current_schema()
+        };
+
+        let partition_types = metadata
+            .partition_specs_iter()
+            .map(|spec| {
+                spec.partition_type(schema)
+                    .map(|t| (spec.spec_id(), t))
+                    .map_err(|e| planning_error("Failed to determine partition type", e))
+            })
+            .collect::<Result<HashMap<_, _>, _>>()?;
+
+        let stats_field_ids = request
+            .stats_fields
+            .as_ref()
+            .map(|fields| {
+                fields
+                    .iter()
+                    .map(|name| {
+                        let field = if request.case_sensitive {
+                            schema.field_by_name(name)
+                        } else {
+                            schema.field_by_name_case_insensitive(name)
+                        };
+                        field.map(|f| f.id).ok_or_else(|| {
+                            ErrorModel::bad_request(
+                                format!("Stats field '{name}' does not exist in table schema"),
+                                "InvalidScanPlanRequest",
+                                None,
+                            )
+                        })
+                    })
+                    .collect::<Result<HashSet<_>, _>>()
+            })
+            .transpose()?;
+
+        Ok(Self {
+            partition_types,
+            stats_field_ids,
+        })
+    }
+
+    fn convert(&self, file: &DataFile, spec_id: i32, include_stats: bool) -> Result<ContentFile> {
+        let partition_type = self.partition_types.get(&spec_id).ok_or_else(|| {
+            ErrorModel::internal(
+                format!("Partition spec {spec_id} not found in table metadata"),
+                "ScanPlanningError",
+                None,
+            )
+        })?;
+        let partition = file
+            .partition()
+            .iter()
+            .zip(partition_type.fields())
+            .map(|(value, field)| {
+                value.map_or(Ok(serde_json::Value::Null), |v| {
+                    v.clone().try_into_json(&field.field_type)
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| planning_error("Failed to serialize partition values", e))?;
+
+        let stats_field_ids = self.stats_field_ids.as_ref().filter(|_| include_stats);
+        let count_map = |counts: &HashMap<i32, u64>| {
+            stats_field_ids.map(|ids| {
+                let counts = counts
+                    .iter()
+                    .filter(|(id, _)| ids.contains(id))
+                    .collect::<BTreeMap<_, _>>();
+                CountMap {
+                    keys: counts.keys().map(|id| **id).collect(),
+                    values: counts
+                        .values()
+                        .map(|v| i64::try_from(**v).unwrap_or(i64::MAX))
+                        .collect(),
+                }
+            })
+        };
+        let value_map = |bounds: &HashMap<i32, Datum>| {
+            stats_field_ids
+                .map(|ids| {
+                    let bounds = bounds
+                        .iter()
+                        .filter(|(id, _)| ids.contains(id))
+                        .collect::<BTreeMap<_, _>>();
+                    Ok::<_, iceberg::Error>(ValueMap {
+                        keys: bounds.keys().map(|id| **id).collect(),
+                        values: bounds
+                            .values()
+                            .map(|datum| datum_to_json(datum))
+                            .collect::<Result<Vec<_>, _>>()?,
+                    })
+                })
+                .transpose()
+                .map_err(|e| planning_error("Failed to serialize column bounds", e))
+        };
+
+        Ok(ContentFile {
+            content: match file.content_type() {
+                DataContentType::Data => ContentType::Data,
+                DataContentType::PositionDeletes => ContentType::PositionDeletes,
+                DataContentType::EqualityDeletes => ContentType::EqualityDeletes,
+            },
+            file_path: file.file_path().to_string(),
+            file_format: file.file_format().to_string(),
+            spec_id,
+            partition,
+            file_size_in_bytes: i64::try_from(file.file_size_in_bytes()).unwrap_or(i64::MAX),
+            record_count: i64::try_from(file.record_count()).unwrap_or(i64::MAX),
+            key_metadata: file
+                .key_metadata()
+                .map(|k| k.iter().map(|b| format!("{b:02X}")).collect()),
+            split_offsets: (!file.split_offsets().is_empty())
+                .then(|| file.split_offsets().to_vec()),
+            sort_order_id: file.sort_order_id(),
+            column_sizes: count_map(file.column_sizes()),
+            value_counts: count_map(file.value_counts()),
+            null_value_counts: count_map(file.null_value_counts()),
+            nan_value_counts: count_map(file.nan_value_counts()),
+            lower_bounds: value_map(file.lower_bounds())?,
+            upper_bounds: value_map(file.upper_bounds())?,
+            equality_ids: (!file.equality_ids().is_empty()).then(|| file.equality_ids().to_vec()),
+        })
+    }
+}
+
+fn datum_to_json(datum: &Datum) -> iceberg::Result<serde_json::Value> {
+    let r#type: PrimitiveType = datum.data_type().clone();
+    Literal::Primitive(datum.literal().clone()).try_into_json(&Type::Primitive(r#type))
+}
+
+#[cfg(test)]
+mod test {
+    use iceberg_ext::catalog::rest::{ContentType, PlanTableScanRequest};
+
+    use super::{applies_to, validate_plan_request, DeleteCandidate, PlanTask};
+
+    fn content_file(content: ContentType, spec_id: i32, partition: i64) -> super::ContentFile {
+        super::ContentFile {
+            content,
+            file_path: "s3://bucket/file.parquet".to_string(),
+            file_format: "parquet".to_string(),
+            spec_id,
+            partition: vec![serde_json::json!(partition)],
+            file_size_in_bytes: 10,
+            record_count: 1,
+            key_metadata: None,
+            split_offsets: None,
+            sort_order_id: None,
+            column_sizes: None,
+            value_counts: None,
+            null_value_counts: None,
+            nan_value_counts: None,
+            lower_bounds: None,
+            upper_bounds: None,
+            equality_ids: None,
+        }
+    }
+
+    #[test]
+    fn test_plan_task_roundtrip() {
+        let task = PlanTask {
+            plan_id: uuid::Uuid::now_v7(),
+            offset: 16,
+            limit: 16,
+        };
+        let encoded = task.encode();
+        assert_eq!(PlanTask::decode(&encoded).unwrap(), task);
+        assert!(PlanTask::decode("not-a-plan-task").is_err());
+    }
+
+    #[test]
+    fn test_point_in_time_and_incremental_are_exclusive() {
+        let request = PlanTableScanRequest {
+            snapshot_id: Some(1),
+            start_snapshot_id: Some(2),
+            end_snapshot_id: Some(3),
+            ..Default::default()
+        };
+        assert!(validate_plan_request(&request).is_err());
+
+        let request = PlanTableScanRequest {
+            start_snapshot_id: Some(2),
+            ..Default::default()
+        };
+        assert!(validate_plan_request(&request).is_err());
+
+        let request = PlanTableScanRequest {
+            end_snapshot_id: Some(3),
+            ..Default::default()
+        };
+        assert!(validate_plan_request(&request).is_ok());
+    }
+
+    #[test]
+    fn test_delete_files_apply_by_sequence_number_and_partition() {
+        let data_file = content_file(ContentType::Data, 0, 1);
+
+        let position_delete = DeleteCandidate {
+            file: content_file(ContentType::PositionDeletes, 0, 1),
+            sequence_number: 5,
+            global: false,
+        };
+        assert!(applies_to(&position_delete, &data_file, 5));
+        assert!(!applies_to(&position_delete, &data_file, 6));
+        assert!(!applies_to(
+            &position_delete,
+            &content_file(ContentType::Data, 0, 2),
+            1
+        ));
+
+        let equality_delete = DeleteCandidate {
+            file: content_file(ContentType::EqualityDeletes, 0, 1),
+            sequence_number: 5,
+            global: false,
+        };
+        assert!(applies_to(&equality_delete, &data_file, 4));
+        assert!(!applies_to(&equality_delete, &data_file, 5));
+
+        let global_equality_delete = DeleteCandidate {
+            file: content_file(ContentType::EqualityDeletes, 1, 1),
+            sequence_number: 5,
+            global: true,
+        };
+        assert!(applies_to(
+            &global_equality_delete,
+            &content_file(ContentType::Data, 0, 2),
+            4
+        ));
+    }
+}
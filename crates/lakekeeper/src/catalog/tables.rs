@@ -1,9 +1,10 @@
 use std::{
     collections::{HashMap, HashSet},
     str::FromStr as _,
-    sync::Arc,
+    sync::{Arc, LazyLock},
 };
 
+use axum_prometheus::metrics;
 use futures::FutureExt;
 use fxhash::FxHashSet;
 use http::StatusCode;
@@ -16,19 +17,27 @@ use iceberg::{
     NamespaceIdent, TableUpdate,
 };
 use iceberg_ext::{
-    catalog::rest::{LoadCredentialsResponse, StorageCredential},
+    catalog::rest::{
+        FetchPlanningResult, FetchScanTasksRequest, FetchScanTasksResult, LoadCredentialsResponse,
+        PlanTableScanRequest, PlanTableScanResult, StorageCredential,
+    },
     configs::{namespace::NamespaceProperties, Location, ParseFromStr},
 };
 use itertools::Itertools;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use super::{
-    commit_tables::apply_commit,
-    io::{delete_file, read_metadata_file, write_metadata_file},
+    commit_queue::COMMIT_QUEUES,
+    commit_tables::{apply_commit, is_append_only},
+    io::{delete_file, read_metadata_file, write_table_metadata_file, IoError, StorageIo},
     maybe_get_secret,
-    namespace::{authorized_namespace_ident_to_id, validate_namespace_ident},
-    require_warehouse_id, CatalogServer,
+    namespace::{
+        authorized_namespace_ident_to_id, resolve_tabular_delete_profile, validate_namespace_ident,
+    },
+    require_warehouse_id, scan_planning,
+    upload_checksum::verify_upload_checksums,
+    CatalogServer,
 };
 use crate::{
     api::{
@@ -36,42 +45,128 @@ use crate::{
             types::DropParams,
             v1::{
                 ApiContext, CommitTableRequest, CommitTableResponse, CommitTransactionRequest,
-                CreateTableRequest, DataAccess, ErrorModel, ListTablesQuery, ListTablesResponse,
-                LoadTableResult, NamespaceParameters, PaginationQuery, Prefix,
+                CreateTableRequest, DataAccess, ErrorModel, ListTablesInclude, ListTablesQuery,
+                ListTablesResponse, LoadTableResult, NamespaceParameters, PaginationQuery, Prefix,
                 RegisterTableRequest, RenameTableRequest, Result, TableIdent, TableParameters,
             },
         },
-        management::v1::{warehouse::TabularDeleteProfile, DeleteKind, TabularType},
+        management::v1::{
+            table::TableWriterCheckpoint, warehouse::TabularDeleteProfile, DeleteKind, TabularType,
+        },
         set_not_found_status_code,
     },
     catalog::{self, compression_codec::CompressionCodec, tabular::list_entities},
+    config::ViewLineageDropCheck,
     request_metadata::RequestMetadata,
     retry::retry_fn,
     service::{
         authz::{
-            Authorizer, CatalogNamespaceAction, CatalogTableAction, CatalogWarehouseAction,
-            TableUuid,
+            AuthorizationContext, Authorizer, CatalogNamespaceAction, CatalogTableAction,
+            CatalogWarehouseAction, TableUuid,
         },
+        client_compatibility,
         contract_verification::{ContractVerification, ContractVerificationOutcome},
+        default_branch::{apply_default_branch, resolve_default_branch},
+        labels::{is_table_data_access_allowed, require_table_data_access},
+        property_schemas::{get_property_schemas, require_valid_properties, PropertyEntityType},
+        quotas::{get_project_quotas, require_snapshot_quota, require_table_quota},
+        response_properties::{response_properties, PropertyResponse},
         secrets::SecretStore,
+        shared_cache::SharedCache,
         storage::{StorageLocations as _, StoragePermissions, StorageProfile, ValidationError},
+        storage_intent::{self, StorageIntent, StorageIntentKind},
         task_queue::{
+            orphan_file_cleanup_queue::OrphanFileCleanupPayload,
+            pii_detection_queue::PiiDetectionPayload,
+            snapshot_expiration_queue::SnapshotExpirationPayload,
             tabular_expiration_queue::TabularExpirationPayload,
-            tabular_purge_queue::TabularPurgePayload, EntityId, TaskMetadata,
+            tabular_purge_queue::TabularPurgePayload, EntityId, TaskId, TaskMetadata,
         },
         Catalog, CreateTableResponse, GetNamespaceResponse, ListFlags,
-        LoadTableResponse as CatalogLoadTableResult, State, TableCommit, TableCreation, TableId,
-        TabularDetails, TabularId, Transaction, WarehouseStatus,
+        LoadTableResponse as CatalogLoadTableResult, NamespaceId, State, TableCommit,
+        TableCreation, TableHistoryImport, TableId, TabularDetails, TabularId, Transaction,
+        WarehouseStatus,
     },
-    WarehouseId,
+    ProjectId, WarehouseId, CONFIG,
 };
 
 const PROPERTY_METADATA_DELETE_AFTER_COMMIT_ENABLED: &str =
     "write.metadata.delete-after-commit.enabled";
 const PROPERTY_METADATA_DELETE_AFTER_COMMIT_ENABLED_DEFAULT: bool = false;
+/// Upper bound of previous metadata files read when importing the history of a registered table.
+const MAX_IMPORTED_METADATA_FILES: usize = 1000;
 
 pub(crate) const CONCURRENT_UPDATE_ERROR_TYPE: &str = "ConcurrentUpdateError";
 pub(crate) const MAX_RETRIES_ON_CONCURRENT_UPDATE: usize = 2;
+/// Duration of successful commits including retries, labelled by the `path` (`append` or `full`).
+const TABLE_COMMIT_DURATION: &str = "lakekeeper_table_commit_duration_seconds";
+
+/// Metadata of active tables by `{warehouse_id}:{table_id}`, so that replicas don't load
+/// the metadata of hot tables for every request. Metadata can be large, so fewer entries
+/// are kept locally than for other caches.
+static TABLE_METADATA_CACHE: LazyLock<SharedCache<CachedTableMetadata>> =
+    LazyLock::new(|| SharedCache::with_local_capacity("table-metadata", 1_000));
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedTableMetadata {
+    namespace_id: NamespaceId,
+    table_metadata: TableMetadata,
+    metadata_location: String,
+    storage_secret_ident: Option<Uuid>,
+    storage_profile: StorageProfile,
+    external: bool,
+}
+
+impl CachedTableMetadata {
+    fn from_result(table: &CatalogLoadTableResult) -> Option<Self> {
+        Some(Self {
+            namespace_id: table.namespace_id,
+            table_metadata: table.table_metadata.clone(),
+            metadata_location: table.metadata_location.as_ref()?.to_string(),
+            storage_secret_ident: table.storage_secret_ident.map(Into::into),
+            storage_profile: table.storage_profile.clone(),
+            external: table.external,
+        })
+    }
+
+    fn into_result(self, table_id: TableId) -> Option<CatalogLoadTableResult> {
+        let metadata_location = Location::parse_value(&self.metadata_location)
+            .inspect_err(|e| tracing::warn!("Invalid metadata location in cache: {e:?}"))
+            .ok()?;
+        Some(CatalogLoadTableResult {
+            table_id,
+            namespace_id: self.namespace_id,
+            table_metadata: self.table_metadata,
+            metadata_location: Some(metadata_location),
+            storage_secret_ident: self.storage_secret_ident.map(Into::into),
+            storage_profile: self.storage_profile,
+            external: self.external,
+        })
+    }
+}
+
+fn table_metadata_cache_key(warehouse_id: WarehouseId, table_id: TableId) -> String {
+    format!("{warehouse_id}:{table_id}")
+}
+
+/// Remove the cached metadata of tables after they were committed.
+pub(crate) async fn invalidate_table_metadata(
+    warehouse_id: WarehouseId,
+    table_ids: impl IntoIterator<Item = TableId>,
+) {
+    for table_id in table_ids {
+        TABLE_METADATA_CACHE
+            .invalidate(&table_metadata_cache_key(warehouse_id, table_id))
+            .await;
+    }
+}
+
+/// Remove the cached metadata of all tables of a warehouse.
+pub(crate) async fn invalidate_warehouse_table_metadata(warehouse_id: WarehouseId) {
+    TABLE_METADATA_CACHE
+        .invalidate(&warehouse_id.to_string())
+        .await;
+}
 
 #[async_trait::async_trait]
 impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
@@ -93,7 +188,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
 
         // ------------------- AUTHZ -------------------
         let authorizer = state.v1_state.authz;
-        let mut t = C::Transaction::begin_read(state.v1_state.catalog).await?;
+        let mut t = C::Transaction::begin_read(state.v1_state.catalog.clone()).await?;
         let namespace_id = authorized_namespace_ident_to_id::<C, _>(
             authorizer.clone(),
             &request_metadata,
@@ -122,19 +217,41 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
                 &mut t,
             )
             .await?;
+        let stats = match query.include {
+            Some(ListTablesInclude::Stats) => {
+                let mut stats = C::get_table_stats(&table_uuids, t.transaction()).await?;
+                Some(
+                    table_uuids
+                        .iter()
+                        .map(|id| stats.remove(id).unwrap_or_default())
+                        .collect(),
+                )
+            }
+            None => None,
+        };
         t.commit().await?;
         let mut idents = Vec::with_capacity(identifiers.len());
         let mut protection_status = Vec::with_capacity(identifiers.len());
+        let mut external_status = Vec::with_capacity(identifiers.len());
         for ident in identifiers {
             idents.push(ident.table_ident);
             protection_status.push(ident.protected);
+            external_status.push(ident.external);
         }
+        let next_page_token = client_compatibility::next_page_token::<C>(
+            next_page_token,
+            warehouse_id,
+            state.v1_state.catalog,
+        )
+        .await;
 
         Ok(ListTablesResponse {
             next_page_token,
             identifiers: idents,
             table_uuids: return_uuids.then_some(table_uuids.into_iter().map(|u| *u).collect()),
             protection_status: query.return_protection_status.then_some(protection_status),
+            external_status: query.return_external_status.then_some(external_status),
+            stats,
         })
     }
 
@@ -160,7 +277,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
 
         // ------------------- AUTHZ -------------------
         let authorizer = state.v1_state.authz.clone();
-        let mut t = C::Transaction::begin_write(state.v1_state.catalog).await?;
+        let mut t = C::Transaction::begin_write(state.v1_state.catalog.clone()).await?;
         let namespace_id = authorized_namespace_ident_to_id::<C, _>(
             authorizer.clone(),
             &request_metadata,
@@ -172,14 +289,21 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         .await?;
 
         // ------------------- BUSINESS LOGIC -------------------
-        let id = Uuid::now_v7();
-        let tabular_id = TabularId::Table(id);
-        let table_id = TableId::from(id);
+        let table_id = TableId::new_random();
+        let tabular_id = TabularId::Table(*table_id);
 
         let namespace = C::get_namespace(warehouse_id, namespace_id, t.transaction()).await?;
         let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
         let storage_profile = &warehouse.storage_profile;
         require_active_warehouse(warehouse.status)?;
+        require_table_quota::<C>(&warehouse.project_id, state.v1_state.catalog.clone()).await?;
+        require_valid_properties::<C>(
+            &warehouse.project_id,
+            PropertyEntityType::Table,
+            request.properties.iter().flatten(),
+            state.v1_state.catalog.clone(),
+        )
+        .await?;
 
         let table_location = determine_tabular_location(
             &namespace,
@@ -216,6 +340,8 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
                 table_ident: &table,
                 table_metadata,
                 metadata_location: metadata_location.as_ref(),
+                external: false,
+                imported_history: TableHistoryImport::default(),
             },
             t.transaction(),
         )
@@ -251,6 +377,14 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
                     );
                     Err(e)
                 }
+                Err(e @ ValidationError::InvalidLocation { .. })
+                    if storage_profile.has_weak_list_consistency() =>
+                {
+                    tracing::info!(
+                        "Location is not empty yet, listing might be stale. Retrying up to three times.."
+                    );
+                    Err(e)
+                }
                 Ok(()) => {
                     tracing::debug!("Location is empty");
                     Ok(Ok(()))
@@ -265,13 +399,25 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
 
         if let Some(metadata_location) = &metadata_location {
             let compression_codec = CompressionCodec::try_from_metadata(&table_metadata)?;
-            write_metadata_file(
+            let write_intent = StorageIntent::new(
+                warehouse_id,
+                table_id,
+                StorageIntentKind::WriteMetadata,
+                metadata_location.clone(),
+            );
+            storage_intent::record_storage_intents::<C>(
+                std::slice::from_ref(&write_intent),
+                state.v1_state.catalog,
+            )
+            .await?;
+            write_table_metadata_file(
                 metadata_location,
                 &table_metadata,
                 compression_codec,
                 &file_io,
             )
             .await?;
+            C::complete_storage_intents(&[write_intent.intent_id], t.transaction()).await?;
         }
 
         // This requires the storage secret
@@ -303,6 +449,11 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             storage_credentials,
         };
 
+        // Staged tables are analyzed once their creation is committed
+        if metadata_location.is_some() {
+            maybe_queue_pii_detection::<C>(warehouse_id, table_id, t.transaction()).await?;
+        }
+
         authorizer
             .create_table(&request_metadata, TableId::from(*tabular_id), namespace_id)
             .await?;
@@ -347,6 +498,11 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         validate_table_or_view_ident(&table)?;
         let metadata_location =
             parse_location(&request.metadata_location, StatusCode::BAD_REQUEST)?;
+        let adopt_location = request
+            .adopt_location
+            .as_deref()
+            .map(|location| parse_location(location, StatusCode::BAD_REQUEST))
+            .transpose()?;
 
         // ------------------- AUTHZ -------------------
         let authorizer = state.v1_state.authz.clone();
@@ -371,13 +527,61 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         let storage_secret =
             maybe_get_secret(warehouse.storage_secret_id, &state.v1_state.secrets).await?;
         let file_io = storage_profile.file_io(storage_secret.as_ref()).await?;
-        let table_metadata = read_metadata_file(&file_io, &metadata_location).await?;
+        let table_metadata = match read_metadata_file(&file_io, &metadata_location).await {
+            Ok(table_metadata) => table_metadata,
+            Err(e) if request.verify && !matches!(e, IoError::TableMetadataDeserialization(_)) => {
+                return Err(ErrorModel::not_found(
+                    format!("Metadata file '{metadata_location}' does not exist or can't be read"),
+                    "MetadataFileNotFound",
+                    Some(Box::new(e)),
+                )
+                .into());
+            }
+            Err(e) => return Err(e.into()),
+        };
+        // Adopted metadata is written as a new metadata file at the new location.
+        let (table_metadata, metadata_location, adopted_from) =
+            if let Some(adopt_location) = &adopt_location {
+                storage_profile.require_allowed_location(adopt_location)?;
+                let adopted_metadata = rewrite_table_locations(&table_metadata, adopt_location)?;
+                let new_metadata_location = storage_profile.default_metadata_location(
+                    adopt_location,
+                    &CompressionCodec::try_from_metadata(&adopted_metadata)?,
+                    Uuid::now_v7(),
+                    extract_count_from_metadata_location(&metadata_location).map_or(0, |v| v + 1),
+                );
+                (
+                    adopted_metadata,
+                    new_metadata_location,
+                    Some(table_metadata.location().to_string()),
+                )
+            } else {
+                (table_metadata, metadata_location, None)
+            };
         let table_location = parse_location(table_metadata.location(), StatusCode::BAD_REQUEST)?;
+        let mut imported_history = if request.import_history {
+            import_table_history(&file_io, &table_metadata).await
+        } else {
+            TableHistoryImport::default()
+        };
+        if let Some(adopted_from) = &adopted_from {
+            // Previous metadata files are read from the new location, but still
+            // reference the previous location.
+            for log in &mut imported_history.metadata_log {
+                if let Some(rewritten) = rewrite_location_prefix(
+                    &log.metadata_file,
+                    adopted_from,
+                    table_location.as_str(),
+                ) {
+                    log.metadata_file = rewritten;
+                }
+            }
+        }
 
         // Check if we need to handle overwrite
         let mut previous_table_id = None;
 
-        let mut t_write = C::Transaction::begin_write(state.v1_state.catalog).await?;
+        let mut t_write = C::Transaction::begin_write(state.v1_state.catalog.clone()).await?;
         if request.overwrite {
             // Check if table exists
             previous_table_id = C::table_to_id(
@@ -413,12 +617,57 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             }
         }
         t_read.commit().await?;
+        // Overwriting a table doesn't change the number of tables
+        if previous_table_id.is_none() {
+            require_table_quota::<C>(&warehouse.project_id, state.v1_state.catalog.clone()).await?;
+        }
+        if request.verify {
+            require_table_not_registered::<C>(
+                warehouse_id,
+                TableId::from(table_metadata.uuid()),
+                &table_location,
+                previous_table_id,
+                state.v1_state.catalog.clone(),
+            )
+            .await?;
+        }
 
         validate_table_properties(table_metadata.properties().keys())?;
+        require_valid_properties::<C>(
+            &warehouse.project_id,
+            PropertyEntityType::Table,
+            table_metadata.properties(),
+            state.v1_state.catalog.clone(),
+        )
+        .await?;
         storage_profile.require_allowed_location(&table_location)?;
 
         let tabular_id = TableId::from(table_metadata.uuid());
 
+        let write_intent = if adopted_from.is_some() {
+            let write_intent = StorageIntent::new(
+                warehouse_id,
+                tabular_id,
+                StorageIntentKind::WriteMetadata,
+                metadata_location.clone(),
+            );
+            storage_intent::record_storage_intents::<C>(
+                std::slice::from_ref(&write_intent),
+                state.v1_state.catalog.clone(),
+            )
+            .await?;
+            write_table_metadata_file(
+                &metadata_location,
+                &table_metadata,
+                CompressionCodec::try_from_metadata(&table_metadata)?,
+                &file_io,
+            )
+            .await?;
+            Some(write_intent)
+        } else {
+            None
+        };
+
         let CreateTableResponse {
             table_metadata,
             staged_table_id,
@@ -428,17 +677,26 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
                 table_ident: &table,
                 table_metadata,
                 metadata_location: Some(&metadata_location),
+                external: request.external,
+                imported_history,
             },
             t_write.transaction(),
         )
         .await?;
+        if let Some(write_intent) = write_intent {
+            C::complete_storage_intents(&[write_intent.intent_id], t_write.transaction()).await?;
+        }
 
         let config = storage_profile
             .generate_table_config(
                 DataAccess::not_specified(),
                 storage_secret.as_ref(),
                 &table_location,
-                StoragePermissions::ReadWriteDelete,
+                if request.external {
+                    StoragePermissions::Read
+                } else {
+                    StoragePermissions::ReadWriteDelete
+                },
                 &request_metadata,
                 warehouse_id,
                 tabular_id.into(),
@@ -462,6 +720,25 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
                 .await?;
         }
 
+        maybe_queue_pii_detection::<C>(warehouse_id, tabular_id, t_write.transaction()).await?;
+        // Files of external tables are owned by another system, we must not modify them.
+        if !request.external {
+            maybe_queue_snapshot_expiration::<C>(
+                warehouse_id,
+                tabular_id,
+                None,
+                t_write.transaction(),
+            )
+            .await?;
+            maybe_queue_orphan_file_cleanup::<C>(
+                warehouse_id,
+                tabular_id,
+                None,
+                t_write.transaction(),
+            )
+            .await?;
+        }
+
         // Commit the transaction
         t_write.commit().await?;
 
@@ -536,7 +813,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         // ------------------- AUTHZ -------------------
         let authorizer = state.v1_state.authz;
         let catalog = state.v1_state.catalog;
-        let mut t = C::Transaction::begin_read(catalog).await?;
+        let mut t = C::Transaction::begin_read(catalog.clone()).await?;
 
         let (tabular_details, storage_permissions) = Self::resolve_and_authorize_table_access(
             &request_metadata,
@@ -544,19 +821,35 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             warehouse_id,
             list_flags,
             authorizer,
+            catalog.clone(),
             t.transaction(),
         )
         .await?;
 
         // ------------------- BUSINESS LOGIC -------------------
-        let mut metadatas = C::load_tables(
-            warehouse_id,
-            vec![tabular_details.ident],
-            list_flags.include_deleted,
-            t.transaction(),
-        )
-        .await?;
-        t.commit().await?;
+        let cache_key = table_metadata_cache_key(warehouse_id, tabular_details.ident);
+        let cached = TABLE_METADATA_CACHE
+            .get(&cache_key)
+            .await
+            .and_then(|cached| cached.into_result(tabular_details.ident));
+        let loaded = if let Some(cached) = cached {
+            t.commit().await?;
+            cached
+        } else {
+            let mut metadatas = C::load_tables(
+                warehouse_id,
+                vec![tabular_details.ident],
+                list_flags.include_deleted,
+                t.transaction(),
+            )
+            .await?;
+            t.commit().await?;
+            let loaded = take_table_metadata(&tabular_details.ident, &table, &mut metadatas)?;
+            if let Some(cached) = CachedTableMetadata::from_result(&loaded) {
+                TABLE_METADATA_CACHE.insert(cache_key, cached, None).await;
+            }
+            loaded
+        };
         let CatalogLoadTableResult {
             table_id,
             namespace_id: _,
@@ -564,8 +857,14 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             metadata_location,
             storage_secret_ident,
             storage_profile,
-        } = take_table_metadata(&tabular_details.ident, &table, &mut metadatas)?;
+            external: _,
+        } = loaded;
         require_not_staged(metadata_location.as_ref())?;
+        let table_metadata =
+            match resolve_default_branch::<C>(warehouse_id, &table.namespace, catalog).await? {
+                Some(default_branch) => apply_default_branch(table_metadata, &default_branch)?,
+                None => table_metadata,
+            };
 
         let table_location =
             parse_location(table_metadata.location(), StatusCode::INTERNAL_SERVER_ERROR)?;
@@ -601,10 +900,29 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             })
         });
 
+        let mut config: Option<HashMap<String, String>> = storage_config.map(|c| c.config.into());
+        // Long-running scans can refresh short-lived credentials without reloading the table.
+        if storage_credentials.is_some() {
+            if let Some(endpoint) =
+                table_credentials_endpoint(&request_metadata, warehouse_id, &table)
+            {
+                config
+                    .get_or_insert_with(HashMap::new)
+                    .extend(storage_profile.refresh_credentials_config(&endpoint));
+            }
+        }
+        let injected_properties =
+            response_properties(PropertyResponse::LoadTable, warehouse_id, &request_metadata);
+        if !injected_properties.is_empty() {
+            config
+                .get_or_insert_with(HashMap::new)
+                .extend(injected_properties);
+        }
+
         let load_table_result = LoadTableResult {
             metadata_location: metadata_location.as_ref().map(ToString::to_string),
             metadata: table_metadata,
-            config: storage_config.map(|c| c.config.into()),
+            config,
             storage_credentials,
         };
 
@@ -621,7 +939,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         let TableParameters { prefix, table } = parameters;
         let warehouse_id = require_warehouse_id(prefix)?;
 
-        let mut t = C::Transaction::begin_read(state.v1_state.catalog).await?;
+        let mut t = C::Transaction::begin_read(state.v1_state.catalog.clone()).await?;
         let (tabular_details, storage_permissions) = Self::resolve_and_authorize_table_access(
             &request_metadata,
             &table,
@@ -632,6 +950,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
                 include_deleted: false,
             },
             state.v1_state.authz,
+            state.v1_state.catalog,
             t.transaction(),
         )
         .await?;
@@ -641,6 +960,17 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             None,
         ))?;
 
+        // Clients refreshing credentials don't necessarily repeat the access delegation
+        // header of the initial load, so credentials are vended unless asked otherwise.
+        let data_access = if data_access.requested() {
+            data_access
+        } else {
+            DataAccess {
+                vended_credentials: true,
+                remote_signing: false,
+            }
+        };
+
         let (storage_secret_ident, storage_profile) =
             C::load_storage_profile(warehouse_id, tabular_details.ident, t.transaction()).await?;
         let storage_secret =
@@ -742,7 +1072,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         let include_active = true;
 
         let mut t = C::Transaction::begin_write(state.v1_state.catalog).await?;
-        let table_id = C::table_to_id(
+        let table_details = C::resolve_table_ident(
             warehouse_id,
             table,
             ListFlags {
@@ -754,11 +1084,24 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         )
         .await; // We can't fail before AuthZ
 
-        let table_id = authorizer
-            .require_table_action(&request_metadata, table_id, CatalogTableAction::CanDrop)
+        let TabularDetails {
+            ident: table_id,
+            external,
+            ..
+        } = authorizer
+            .require_table_action(
+                &request_metadata,
+                table_details,
+                CatalogTableAction::CanDrop,
+            )
             .await?;
 
         // ------------------- BUSINESS LOGIC -------------------
+        // Files of external tables are owned by another system and are never purged.
+        if purge_requested && external {
+            tracing::debug!("Ignoring purge request for external table '{table_id}'.");
+        }
+        let purge_requested = purge_requested && !external;
 
         let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
 
@@ -769,7 +1112,11 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             .await?
             .into_result()?;
 
-        match warehouse.tabular_delete_profile {
+        check_views_referencing_table::<C>(warehouse_id, table_id, table, force, &mut t).await?;
+
+        let delete_profile =
+            resolve_tabular_delete_profile::<C>(&warehouse, &table.namespace, &mut t).await?;
+        match delete_profile {
             TabularDeleteProfile::Hard {} => {
                 let location = C::drop_table(table_id, force, t.transaction()).await?;
 
@@ -976,15 +1323,68 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         let _ = commit_tables_internal(prefix, request, state, request_metadata).await?;
         Ok(())
     }
+
+    /// Plan a table scan
+    async fn plan_table_scan(
+        parameters: TableParameters,
+        request: PlanTableScanRequest,
+        data_access: DataAccess,
+        state: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<PlanTableScanResult> {
+        scan_planning::plan_table_scan(parameters, request, data_access, state, request_metadata)
+            .await
+    }
+
+    /// Fetch the result of scan planning for a plan-id
+    async fn fetch_planning_result(
+        parameters: TableParameters,
+        plan_id: String,
+        data_access: DataAccess,
+        state: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<FetchPlanningResult> {
+        scan_planning::fetch_planning_result(
+            parameters,
+            plan_id,
+            data_access,
+            state,
+            request_metadata,
+        )
+        .await
+    }
+
+    /// Cancel scan planning for a plan-id
+    async fn cancel_planning(
+        parameters: TableParameters,
+        plan_id: String,
+        state: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        scan_planning::cancel_planning(parameters, plan_id, state, request_metadata).await
+    }
+
+    /// Fetch the file scan tasks for a plan task
+    async fn fetch_scan_tasks(
+        parameters: TableParameters,
+        request: FetchScanTasksRequest,
+        data_access: DataAccess,
+        state: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<FetchScanTasksResult> {
+        scan_planning::fetch_scan_tasks(parameters, request, data_access, state, request_metadata)
+            .await
+    }
 }
 
 impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> CatalogServer<C, A, S> {
-    async fn resolve_and_authorize_table_access(
+    pub(super) async fn resolve_and_authorize_table_access(
         request_metadata: &RequestMetadata,
         table: &TableIdent,
         warehouse_id: WarehouseId,
         list_flags: ListFlags,
         authorizer: A,
+        catalog_state: C::State,
         transaction: <C::Transaction as Transaction<C::State>>::Transaction<'_>,
     ) -> Result<(TabularDetails, Option<StoragePermissions>)> {
         authorizer
@@ -1020,9 +1420,27 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> CatalogServer<C, A, S> {
             ),
         )?;
 
-        let storage_permissions = if write_access {
+        // Label policies take precedence over the permissions of the authorizer.
+        let (read_access, write_access) = if (read_access || write_access)
+            && !is_table_data_access_allowed::<C, A>(
+                &authorizer,
+                request_metadata,
+                warehouse_id,
+                table_id.ident,
+                catalog_state,
+            )
+            .await?
+        {
+            (false, false)
+        } else {
+            (read_access, write_access)
+        };
+
+        // Only read credentials are vended for external tables, their files are owned by
+        // another system.
+        let storage_permissions = if write_access && !table_id.external {
             Some(StoragePermissions::ReadWriteDelete)
-        } else if read_access {
+        } else if read_access || write_access {
             Some(StoragePermissions::Read)
         } else {
             None
@@ -1031,33 +1449,91 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> CatalogServer<C, A, S> {
     }
 }
 
-#[allow(clippy::too_many_lines)]
-async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
-    prefix: Option<Prefix>,
-    request: CommitTransactionRequest,
-    state: ApiContext<State<A, C, S>>,
-    request_metadata: RequestMetadata,
-) -> Result<Vec<CommitContext>> {
-    // ------------------- VALIDATIONS -------------------
-    let warehouse_id = require_warehouse_id(prefix.clone())?;
+/// Validates a `CommitTransactionRequest` before any table is resolved.
+///
+/// A transaction must contain at least one and at most
+/// `CONFIG.max_tables_per_transaction` changes, each change must carry an
+/// identifier, and every table may only appear once.
+fn validate_commit_transaction_request(request: &CommitTransactionRequest) -> Result<()> {
+    if request.table_changes.is_empty() {
+        return Err(ErrorModel::bad_request(
+            "CommitTransactionRequest must contain at least one table change",
+            "EmptyCommitTransaction",
+            None,
+        )
+        .into());
+    }
+
+    if request.table_changes.len() > CONFIG.max_tables_per_transaction {
+        return Err(ErrorModel::bad_request(
+            format!(
+                "CommitTransactionRequest contains {} table changes, at most {} are allowed",
+                request.table_changes.len(),
+                CONFIG.max_tables_per_transaction
+            ),
+            "TooManyTablesInCommitTransaction",
+            None,
+        )
+        .into());
+    }
+
+    let mut identifiers = HashSet::with_capacity(request.table_changes.len());
     for change in &request.table_changes {
         validate_table_updates(&change.updates)?;
-        change
-            .identifier
-            .as_ref()
-            .map(validate_table_or_view_ident)
-            .transpose()?;
-
-        if change.identifier.is_none() {
+        let Some(identifier) = change.identifier.as_ref() else {
             return Err(ErrorModel::bad_request(
                 "Table identifier is required for each change in the CommitTransactionRequest",
                 "TableIdentifierRequiredForCommitTransaction",
                 None,
             )
             .into());
+        };
+        validate_table_or_view_ident(identifier)?;
+
+        if !identifiers.insert(identifier) {
+            return Err(ErrorModel::bad_request(
+                format!(
+                    "Table identifiers must be unique in the CommitTransactionRequest. Table '{}.{}' is changed more than once.",
+                    identifier.namespace.to_url_string(),
+                    identifier.name
+                ),
+                "UniqueTableIdentifiersRequiredForCommitTransaction",
+                None,
+            )
+            .into());
         }
     }
 
+    Ok(())
+}
+
+pub(crate) async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    prefix: Option<Prefix>,
+    request: CommitTransactionRequest,
+    state: ApiContext<State<A, C, S>>,
+    request_metadata: RequestMetadata,
+) -> Result<Vec<CommitContext>> {
+    commit_tables_with_writer_checkpoint(prefix, request, None, state, request_metadata).await
+}
+
+/// Like [`commit_tables_internal`], but additionally marks `writer_checkpoint` as
+/// committed in the same transaction, so that the checkpoint is committed exactly once.
+#[allow(clippy::too_many_lines)]
+pub(crate) async fn commit_tables_with_writer_checkpoint<
+    C: Catalog,
+    A: Authorizer + Clone,
+    S: SecretStore,
+>(
+    prefix: Option<Prefix>,
+    request: CommitTransactionRequest,
+    writer_checkpoint: Option<TableWriterCheckpoint>,
+    state: ApiContext<State<A, C, S>>,
+    request_metadata: RequestMetadata,
+) -> Result<Vec<CommitContext>> {
+    // ------------------- VALIDATIONS -------------------
+    let warehouse_id = require_warehouse_id(prefix.clone())?;
+    validate_commit_transaction_request(&request)?;
+
     // ------------------- AUTHZ -------------------
     let authorizer = state.v1_state.authz.clone();
     authorizer
@@ -1077,7 +1553,6 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
         .iter()
         .filter_map(|change| change.identifier.as_ref())
         .collect::<HashSet<_>>();
-    let n_identifiers = identifiers.len();
     let table_ids = C::table_idents_to_ids(
         warehouse_id,
         identifiers,
@@ -1106,7 +1581,29 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
         })
         .collect::<Vec<_>>();
 
-    let table_uuids = futures::future::try_join_all(authz_checks).await?;
+    let (table_uuids, actor_roles) = futures::future::try_join(
+        futures::future::try_join_all(authz_checks),
+        authorizer.list_actor_roles(&request_metadata),
+    )
+    .await?;
+    futures::future::try_join_all(table_uuids.iter().map(|table_id| {
+        require_table_data_access::<C, A>(
+            &authorizer,
+            &request_metadata,
+            warehouse_id,
+            *table_id,
+            state.v1_state.catalog.clone(),
+        )
+    }))
+    .await?;
+    let decision_id = Uuid::now_v7();
+    tracing::info!(
+        %decision_id,
+        actor = %request_metadata.actor(),
+        action = %CatalogTableAction::CanCommit,
+        tables = ?table_uuids,
+        "Authorized table commit"
+    );
     let table_ids = Arc::new(
         table_ids
             .into_iter()
@@ -1116,34 +1613,35 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
     );
 
     // ------------------- BUSINESS LOGIC -------------------
-
-    if n_identifiers != request.table_changes.len() {
-        return Err(ErrorModel::bad_request(
-            "Table identifiers must be unique in the CommitTransactionRequest",
-            "UniqueTableIdentifiersRequiredForCommitTransaction",
-            None,
+    // Wait for concurrent commits to the same tables instead of conflicting with them
+    let _commit_queue_guard = if CONFIG.commit_queue_max_wait.is_zero() {
+        None
+    } else {
+        Some(
+            COMMIT_QUEUES
+                .enqueue(table_ids.values().copied(), CONFIG.commit_queue_max_wait)
+                .await?,
         )
-        .into());
-    }
+    };
 
-    let mut events = vec![];
-    let mut event_table_ids: Vec<(TableIdent, TableId)> = vec![];
-    let mut updates = vec![];
-    for commit_table_request in &request.table_changes {
-        if let Some(id) = &commit_table_request.identifier {
-            if let Some(uuid) = table_ids.get(id) {
-                events.push(maybe_body_to_json(commit_table_request));
-                event_table_ids.push((id.clone(), *uuid));
-                updates.push(commit_table_request.updates.clone());
-            }
-        }
-    }
+    let commit_path = if CONFIG.enable_append_commit_fast_path
+        && request
+            .table_changes
+            .iter()
+            .all(|change| is_append_only(&change.updates))
+    {
+        "append"
+    } else {
+        "full"
+    };
+    let started = std::time::Instant::now();
 
     // Start the retry loop
     let mut attempt = 0;
     loop {
         let result = try_commit_tables::<C, A, S>(
             &request,
+            writer_checkpoint.as_ref(),
             warehouse_id,
             table_ids.clone(),
             &state,
@@ -1152,7 +1650,16 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
         .await;
 
         match result {
-            Ok(commits) => {
+            Ok((commits, project_id)) => {
+                metrics::histogram!(TABLE_COMMIT_DURATION, "path" => commit_path)
+                    .record(started.elapsed().as_secs_f64());
+
+                let authorization = AuthorizationContext {
+                    decision_id,
+                    action: CatalogTableAction::CanCommit.to_string(),
+                    project_id,
+                    roles: actor_roles,
+                };
                 // Fire hooks
                 state
                     .v1_state
@@ -1162,6 +1669,7 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
                         Arc::new(request),
                         Arc::new(commits.clone()),
                         table_ids,
+                        Arc::new(authorization),
                         Arc::new(request_metadata),
                     )
                     .await;
@@ -1185,17 +1693,23 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
     }
 }
 
-// Extract the core commit logic to a separate function for retry purposes
+// Extract the core commit logic to a separate function for retry purposes.
+// Returns the commits and the project of the warehouse.
 #[allow(clippy::too_many_lines)]
 async fn try_commit_tables<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
     request: &CommitTransactionRequest,
+    writer_checkpoint: Option<&TableWriterCheckpoint>,
     warehouse_id: WarehouseId,
     table_ids: Arc<HashMap<TableIdent, TableId>>,
     state: &ApiContext<State<A, C, S>>,
     include_deleted: bool,
-) -> Result<Vec<CommitContext>> {
+) -> Result<(Vec<CommitContext>, ProjectId)> {
     let mut transaction = C::Transaction::begin_write(state.v1_state.catalog.clone()).await?;
     let warehouse = C::require_warehouse(warehouse_id, transaction.transaction()).await?;
+    let quotas =
+        get_project_quotas::<C>(&warehouse.project_id, state.v1_state.catalog.clone()).await?;
+    let property_schemas =
+        get_property_schemas::<C>(&warehouse.project_id, state.v1_state.catalog.clone()).await?;
 
     // Load old metadata
     let mut previous_metadatas = C::load_tables(
@@ -1206,7 +1720,7 @@ async fn try_commit_tables<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
     )
     .await?;
 
-    let mut expired_metadata_logs: Vec<MetadataLog> = vec![];
+    let mut expired_metadata_logs: Vec<(TableId, MetadataLog)> = vec![];
 
     // Apply changes
     let commits = request
@@ -1223,23 +1737,32 @@ async fn try_commit_tables<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
             let table_id = require_table_id(table_ident, table_ids.get(table_ident).copied())?;
             let previous_table_metadata =
                 take_table_metadata(&table_id, table_ident, &mut previous_metadatas)?;
+            require_not_external(&previous_table_metadata, table_ident)?;
+            for update in &change.updates {
+                if let TableUpdate::SetProperties { updates } = update {
+                    property_schemas.validate(PropertyEntityType::Table, updates)?;
+                }
+            }
             let TableMetadataBuildResult {
                 metadata: new_metadata,
                 changes: _,
-                expired_metadata_logs: mut this_expired,
+                expired_metadata_logs: this_expired,
             } = apply_commit(
                 previous_table_metadata.table_metadata.clone(),
                 previous_table_metadata.metadata_location.as_ref(),
                 &change.requirements,
                 change.updates.clone(),
             )?;
+            require_snapshot_quota(
+                &quotas,
+                &previous_table_metadata.table_metadata,
+                &new_metadata,
+            )?;
 
             let number_expired_metadata_log_entries = this_expired.len();
 
             if get_delete_after_commit_enabled(new_metadata.properties()) {
-                expired_metadata_logs.extend(this_expired);
-            } else {
-                this_expired.clear();
+                expired_metadata_logs.extend(this_expired.into_iter().map(|log| (table_id, log)));
             }
 
             let next_metadata_count = previous_table_metadata
@@ -1284,6 +1807,44 @@ async fn try_commit_tables<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
         transaction.transaction(),
     )
     .await?;
+    if let Some(writer_checkpoint) = writer_checkpoint {
+        C::complete_table_writer_commit(writer_checkpoint, transaction.transaction()).await?;
+    }
+
+    // Columns may have been added or renamed - analyze the new schema
+    for commit in commits.iter().filter(|c| {
+        c.updates
+            .iter()
+            .any(|u| matches!(u, TableUpdate::AddSchema { .. }))
+    }) {
+        maybe_queue_pii_detection::<C>(
+            warehouse_id,
+            TableId::from(commit.new_metadata.uuid()),
+            transaction.transaction(),
+        )
+        .await?;
+    }
+
+    for commit in commits.iter().filter(|c| {
+        c.updates
+            .iter()
+            .any(|u| matches!(u, TableUpdate::AddSnapshot { .. }))
+    }) {
+        maybe_queue_snapshot_expiration::<C>(
+            warehouse_id,
+            TableId::from(commit.new_metadata.uuid()),
+            None,
+            transaction.transaction(),
+        )
+        .await?;
+        maybe_queue_orphan_file_cleanup::<C>(
+            warehouse_id,
+            TableId::from(commit.new_metadata.uuid()),
+            None,
+            transaction.transaction(),
+        )
+        .await?;
+    }
 
     // Check contract verification
     let futures = commits.iter().map(|c| {
@@ -1308,26 +1869,47 @@ async fn try_commit_tables<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
         .storage_profile
         .file_io(storage_secret.as_ref())
         .await?;
+    verify_upload_checksums(&commits, &file_io).await?;
 
-    let write_futures: Vec<_> = commits
+    // Files written by a crashed or failed commit are cleaned up via their intents
+    let write_intents = commits
         .iter()
         .map(|commit| {
-            write_metadata_file(
-                &commit.new_metadata_location,
-                &commit.new_metadata,
-                commit.new_compression_codec,
+            StorageIntent::new(
+                warehouse_id,
+                TableId::from(commit.new_metadata.uuid()),
+                StorageIntentKind::WriteMetadata,
+                commit.new_metadata_location.clone(),
+            )
+        })
+        .collect_vec();
+    storage_intent::record_storage_intents::<C>(&write_intents, state.v1_state.catalog.clone())
+        .await?;
+
+    let write_futures: Vec<_> = commits
+        .iter()
+        .map(|commit| {
+            write_table_metadata_file(
+                &commit.new_metadata_location,
+                &commit.new_metadata,
+                commit.new_compression_codec,
                 &file_io,
             )
         })
         .collect();
     futures::future::try_join_all(write_futures).await?;
 
-    transaction.commit().await?;
+    C::complete_storage_intents(
+        &write_intents.iter().map(|i| i.intent_id).collect_vec(),
+        transaction.transaction(),
+    )
+    .await?;
 
-    // Delete files in parallel - if one delete fails, we still want to delete the rest
-    let expired_locations = expired_metadata_logs
+    // Expired files are deleted after the commit. If the deletion does not happen,
+    // their intents are recovered later.
+    let delete_intents = expired_metadata_logs
         .into_iter()
-        .filter_map(|expired_metadata_log| {
+        .filter_map(|(table_id, expired_metadata_log)| {
             Location::parse_value(&expired_metadata_log.metadata_file)
                 .map_err(|e| {
                     tracing::warn!(
@@ -1337,22 +1919,49 @@ async fn try_commit_tables<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
                     );
                 })
                 .ok()
+                .map(|location| {
+                    StorageIntent::new(
+                        warehouse_id,
+                        table_id,
+                        StorageIntentKind::DeleteFile,
+                        location,
+                    )
+                })
         })
-        .collect::<Vec<_>>();
-    let _ = futures::future::join_all(
-        expired_locations
-            .iter()
-            .map(|location| delete_file(&file_io, location))
-            .collect::<Vec<_>>(),
+        .collect_vec();
+    C::record_storage_intents(&delete_intents, transaction.transaction()).await?;
+
+    transaction.commit().await?;
+    invalidate_table_metadata(
+        warehouse_id,
+        commits.iter().map(|c| TableId::from(c.new_metadata.uuid())),
     )
+    .await;
+
+    // Delete files in parallel - if one delete fails, we still want to delete the rest
+    let deleted = futures::future::join_all(delete_intents.iter().map(|intent| async {
+        delete_file(&file_io, &intent.location)
+            .await
+            .map(|()| intent.intent_id)
+    }))
     .await
     .into_iter()
-    .map(|r| {
+    .filter_map(|r| {
         r.map_err(|e| tracing::warn!("Failed to delete expired metadata file: {:?}", e))
             .ok()
-    });
+    })
+    .collect_vec();
+    if let Err(e) =
+        storage_intent::complete_storage_intents::<C>(&deleted, state.v1_state.catalog.clone())
+            .await
+    {
+        tracing::warn!(
+            "Failed to complete intents of deleted metadata files: {}",
+            e.error
+        );
+    }
 
-    Ok(commits)
+    Ok((commits, warehouse.project_id))
 }
 
 pub(crate) async fn authorized_table_ident_to_id<C: Catalog, A: Authorizer>(
@@ -1374,6 +1983,399 @@ pub(crate) async fn authorized_table_ident_to_id<C: Catalog, A: Authorizer>(
         .map_err(set_not_found_status_code)
 }
 
+/// External tables are read-only for the catalog. Their metadata is updated by
+/// registering the table again with `overwrite`.
+fn require_not_external(table: &CatalogLoadTableResult, table_ident: &TableIdent) -> Result<()> {
+    if table.external {
+        return Err(ErrorModel::bad_request(
+            format!(
+                "Table '{}.{}' is an external table and cannot be modified. Register the table again with `overwrite` to update its metadata.",
+                table_ident.namespace.to_url_string(),
+                table_ident.name
+            ),
+            "ExternalTableNotModifiable",
+            None,
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Absolute URL of the `loadCredentials` endpoint of the table, which vends fresh
+/// credentials without loading the table metadata.
+fn table_credentials_endpoint(
+    request_metadata: &RequestMetadata,
+    warehouse_id: WarehouseId,
+    table: &TableIdent,
+) -> Option<String> {
+    let mut url = url::Url::parse(&request_metadata.base_uri_catalog()).ok()?;
+    url.path_segments_mut().ok()?.pop_if_empty().extend([
+        "v1",
+        &warehouse_id.to_string(),
+        "namespaces",
+        &table.namespace.to_url_string(),
+        "tables",
+        &table.name,
+        "credentials",
+    ]);
+    Some(url.to_string())
+}
+
+/// Recovers metadata and snapshot log entries of a registered table that are no longer
+/// part of its current metadata file, e.g. because of `write.metadata.previous-versions-max`
+/// or snapshot expiration.
+///
+/// The metadata log is followed backwards by reading the oldest known previous metadata
+/// file until no older entries are found. Previous metadata files are frequently cleaned up,
+/// so an unreadable file ends the import instead of failing the registration.
+/// Snapshot log entries are only imported for snapshots that are still part of the table.
+async fn import_table_history(
+    file_io: &StorageIo,
+    table_metadata: &TableMetadata,
+) -> TableHistoryImport {
+    let mut known_metadata_files = table_metadata
+        .metadata_log()
+        .iter()
+        .map(|log| log.metadata_file.clone())
+        .collect::<HashSet<_>>();
+    let mut oldest_snapshot_log_ms = table_metadata
+        .history()
+        .first()
+        .map_or(i64::MAX, |log| log.timestamp_ms);
+    let mut history = TableHistoryImport::default();
+
+    let mut next = table_metadata.metadata_log().first().cloned();
+    let mut n_read = 0;
+    while let Some(MetadataLog { metadata_file, .. }) = next.take() {
+        if n_read >= MAX_IMPORTED_METADATA_FILES {
+            tracing::warn!(
+                "Stopping history import of table {} after reading {n_read} previous metadata files",
+                table_metadata.uuid()
+            );
+            break;
+        }
+        n_read += 1;
+
+        let previous_metadata = match Location::from_str(&metadata_file) {
+            Ok(location) => read_metadata_file(file_io, &location)
+                .await
+                .map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        let previous_metadata = match previous_metadata {
+            Ok(m) => m,
+            Err(e) => {
+                tracing::info!(
+                    "Stopping history import of table {} as previous metadata file '{metadata_file}' could not be read: {e}",
+                    table_metadata.uuid()
+                );
+                break;
+            }
+        };
+
+        // Logs are ordered oldest first, everything found in older files is prepended.
+        let older_metadata_log = previous_metadata
+            .metadata_log()
+            .iter()
+            .filter(|log| known_metadata_files.insert(log.metadata_file.clone()))
+            .cloned()
+            .collect_vec();
+        let older_snapshot_log = previous_metadata
+            .history()
+            .iter()
+            .filter(|log| {
+                log.timestamp_ms < oldest_snapshot_log_ms
+                    && table_metadata.snapshot_by_id(log.snapshot_id).is_some()
+            })
+            .cloned()
+            .collect_vec();
+        if let Some(first) = previous_metadata.history().first() {
+            oldest_snapshot_log_ms = oldest_snapshot_log_ms.min(first.timestamp_ms);
+        }
+
+        next = older_metadata_log.first().cloned();
+        history.metadata_log.splice(0..0, older_metadata_log);
+        history.snapshot_log.splice(0..0, older_snapshot_log);
+    }
+
+    history
+}
+
+/// Rejects the registration of a table that is already part of the warehouse, either with
+/// the same uuid or at the same location. The table replaced by an overwrite is ignored.
+async fn require_table_not_registered<C: Catalog>(
+    warehouse_id: WarehouseId,
+    table_id: TableId,
+    table_location: &Location,
+    replaced_table_id: Option<TableId>,
+    catalog_state: C::State,
+) -> Result<()> {
+    let list_flags = ListFlags {
+        include_active: true,
+        include_staged: true,
+        include_deleted: true,
+    };
+    let by_id =
+        C::get_table_metadata_by_id(warehouse_id, table_id, list_flags, catalog_state.clone())
+            .await?;
+    let by_location = C::get_table_metadata_by_s3_location(
+        warehouse_id,
+        table_location,
+        list_flags,
+        catalog_state,
+    )
+    .await?;
+    if let Some(existing) = by_id
+        .into_iter()
+        .chain(by_location)
+        .find(|existing| Some(existing.table_id) != replaced_table_id)
+    {
+        return Err(ErrorModel::conflict(
+            format!(
+                "Metadata file belongs to table '{}' with id {} at '{}', which is already registered in this warehouse",
+                existing.table.name, existing.table_id, existing.location
+            ),
+            "TableAlreadyRegistered",
+            None,
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Looks up views whose current version references the table before it is dropped.
+/// Depending on `CONFIG.view_lineage_drop_check` a warning is logged or the drop is
+/// rejected unless it is forced.
+async fn check_views_referencing_table<C: Catalog>(
+    warehouse_id: WarehouseId,
+    table_id: TableId,
+    table: &TableIdent,
+    force: bool,
+    t: &mut C::Transaction,
+) -> Result<()> {
+    let check = CONFIG.view_lineage_drop_check;
+    if check == ViewLineageDropCheck::Disabled {
+        return Ok(());
+    }
+    let views = C::list_views_referencing_table(warehouse_id, table_id, t.transaction()).await?;
+    if views.is_empty() {
+        return Ok(());
+    }
+    let view_names = views
+        .iter()
+        .map(|view| format!("{}.{}", view.namespace.join("."), view.name))
+        .join(", ");
+
+    if check == ViewLineageDropCheck::Block && !force {
+        return Err(ErrorModel::conflict(
+            format!(
+                "Table '{}' is referenced by views: {view_names}. Drop or replace the views first, or force the drop.",
+                table.name
+            ),
+            "TableReferencedByViews",
+            None,
+        )
+        .into());
+    }
+    tracing::warn!(
+        "Dropping table {table_id} in warehouse {warehouse_id} that is referenced by views: {view_names}"
+    );
+    Ok(())
+}
+
+/// Rewrites all absolute locations of the metadata that start with its table location,
+/// such as the locations of manifest lists, statistics files, previous metadata files
+/// and `write.*.path` properties, to start with `location` instead.
+///
+/// Manifest lists and manifests are not rewritten. Files they reference must still be
+/// readable at their previous location.
+fn rewrite_table_locations(
+    table_metadata: &TableMetadata,
+    location: &Location,
+) -> Result<TableMetadata> {
+    fn rewrite(value: &mut serde_json::Value, from: &str, to: &str) {
+        match value {
+            serde_json::Value::String(s) => {
+                if let Some(rewritten) = rewrite_location_prefix(s, from, to) {
+                    *s = rewritten;
+                }
+            }
+            serde_json::Value::Array(values) => {
+                values.iter_mut().for_each(|v| rewrite(v, from, to));
+            }
+            serde_json::Value::Object(map) => {
+                map.values_mut().for_each(|v| rewrite(v, from, to));
+            }
+            _ => {}
+        }
+    }
+
+    let mut value = serde_json::to_value(table_metadata).map_err(|e| {
+        ErrorModel::internal(
+            "Failed to serialize table metadata",
+            "TableMetadataSerializationError",
+            Some(Box::new(e)),
+        )
+    })?;
+    rewrite(&mut value, table_metadata.location(), location.as_str());
+    serde_json::from_value(value).map_err(|e| {
+        ErrorModel::bad_request(
+            format!("Table metadata is invalid after adopting it at '{location}'"),
+            "InvalidAdoptedTableMetadata",
+            Some(Box::new(e)),
+        )
+        .into()
+    })
+}
+
+/// Replaces the prefix `from` of a location with `to`.
+/// Returns `None` if the location is not `from` or a location below it.
+fn rewrite_location_prefix(location: &str, from: &str, to: &str) -> Option<String> {
+    let from = from.trim_end_matches('/');
+    let rest = location.strip_prefix(from)?;
+    (rest.is_empty() || rest.starts_with('/'))
+        .then(|| format!("{}{rest}", to.trim_end_matches('/')))
+}
+
+/// Queues a PII detection run for the table if PII detection is enabled.
+pub(crate) async fn maybe_queue_pii_detection<C: Catalog>(
+    warehouse_id: WarehouseId,
+    table_id: TableId,
+    transaction: <C::Transaction as Transaction<C::State>>::Transaction<'_>,
+) -> Result<()> {
+    if CONFIG.enable_pii_detection {
+        C::queue_pii_detection(
+            TaskMetadata {
+                warehouse_id,
+                parent_task_id: None,
+                entity_id: EntityId::Tabular(*table_id),
+                schedule_for: None,
+            },
+            PiiDetectionPayload {},
+            transaction,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Queues the next snapshot expiration run for the table if snapshot expiration is enabled.
+/// Tables that are already scheduled keep their schedule.
+pub(crate) async fn maybe_queue_snapshot_expiration<C: Catalog>(
+    warehouse_id: WarehouseId,
+    table_id: TableId,
+    parent_task_id: Option<TaskId>,
+    transaction: <C::Transaction as Transaction<C::State>>::Transaction<'_>,
+) -> Result<()> {
+    if CONFIG.enable_snapshot_expiration {
+        C::queue_snapshot_expiration(
+            TaskMetadata {
+                warehouse_id,
+                parent_task_id,
+                entity_id: EntityId::Tabular(*table_id),
+                schedule_for: Some(
+                    chrono::Utc::now() + CONFIG.snapshot_expiration_interval_seconds,
+                ),
+            },
+            SnapshotExpirationPayload {},
+            transaction,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Queues the next orphan file cleanup run for the table if orphan file cleanup is enabled.
+/// Tables that are already scheduled keep their schedule.
+pub(crate) async fn maybe_queue_orphan_file_cleanup<C: Catalog>(
+    warehouse_id: WarehouseId,
+    table_id: TableId,
+    parent_task_id: Option<TaskId>,
+    transaction: <C::Transaction as Transaction<C::State>>::Transaction<'_>,
+) -> Result<()> {
+    if CONFIG.enable_orphan_file_cleanup {
+        C::queue_orphan_file_cleanup(
+            TaskMetadata {
+                warehouse_id,
+                parent_task_id,
+                entity_id: EntityId::Tabular(*table_id),
+                schedule_for: Some(
+                    chrono::Utc::now() + CONFIG.orphan_file_cleanup_interval_seconds,
+                ),
+            },
+            OrphanFileCleanupPayload::default(),
+            transaction,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Commits updates to a single table on behalf of the server, i.e. from maintenance tasks.
+///
+/// No authorization checks, contract verifications or hooks are run, and no events
+/// are emitted. The new metadata file is written before returning; the caller is
+/// responsible for committing `transaction`.
+pub(crate) async fn commit_table_maintenance<C: Catalog>(
+    warehouse_id: WarehouseId,
+    table: &CatalogLoadTableResult,
+    updates: Vec<TableUpdate>,
+    file_io: &StorageIo,
+    transaction: <C::Transaction as Transaction<C::State>>::Transaction<'_>,
+) -> Result<TableMetadata> {
+    require_not_staged(table.metadata_location.as_ref())?;
+    let TableMetadataBuildResult {
+        metadata: new_metadata,
+        changes: _,
+        expired_metadata_logs,
+    } = apply_commit(
+        table.table_metadata.clone(),
+        table.metadata_location.as_ref(),
+        &[],
+        updates.clone(),
+    )?;
+
+    let next_metadata_count = table
+        .metadata_location
+        .as_ref()
+        .and_then(extract_count_from_metadata_location)
+        .map_or(0, |v| v + 1);
+    let new_table_location =
+        parse_location(new_metadata.location(), StatusCode::INTERNAL_SERVER_ERROR)?;
+    let new_compression_codec = CompressionCodec::try_from_metadata(&new_metadata)?;
+    let new_metadata_location = table.storage_profile.default_metadata_location(
+        &new_table_location,
+        &new_compression_codec,
+        Uuid::now_v7(),
+        next_metadata_count,
+    );
+    let number_added_metadata_log_entries = (new_metadata.metadata_log().len()
+        + expired_metadata_logs.len())
+    .saturating_sub(table.table_metadata.metadata_log().len());
+
+    let commit = CommitContext {
+        new_metadata,
+        new_metadata_location,
+        new_compression_codec,
+        previous_metadata_location: table.metadata_location.clone(),
+        updates,
+        previous_metadata: table.table_metadata.clone(),
+        number_expired_metadata_log_entries: expired_metadata_logs.len(),
+        number_added_metadata_log_entries,
+    };
+
+    C::commit_table_transaction(warehouse_id, [commit.commit()], transaction).await?;
+    write_table_metadata_file(
+        &commit.new_metadata_location,
+        &commit.new_metadata,
+        commit.new_compression_codec,
+        file_io,
+    )
+    .await?;
+
+    Ok(commit.new_metadata)
+}
+
 pub(crate) fn extract_count_from_metadata_location(location: &Location) -> Option<usize> {
     let last_segment = location
         .as_str()
@@ -1405,13 +2407,23 @@ pub struct CommitContext {
 }
 
 impl CommitContext {
-    fn commit(&self) -> TableCommit {
-        let diffs = calculate_diffs(
-            &self.new_metadata,
-            &self.previous_metadata,
-            self.number_added_metadata_log_entries,
-            self.number_expired_metadata_log_entries,
-        );
+    pub(crate) fn commit(&self) -> TableCommit {
+        let diffs = if CONFIG.enable_append_commit_fast_path && is_append_only(&self.updates) {
+            calculate_append_diffs(
+                &self.updates,
+                &self.new_metadata,
+                &self.previous_metadata,
+                self.number_added_metadata_log_entries,
+                self.number_expired_metadata_log_entries,
+            )
+        } else {
+            calculate_diffs(
+                &self.new_metadata,
+                &self.previous_metadata,
+                self.number_added_metadata_log_entries,
+                self.number_expired_metadata_log_entries,
+            )
+        };
 
         TableCommit {
             diffs,
@@ -1423,6 +2435,66 @@ impl CommitContext {
     }
 }
 
+/// Diffs of a commit that only appends snapshots, see [`is_append_only`].
+/// Derived from the updates instead of comparing all snapshots, schemas, specs,
+/// sort orders and statistics of both metadata versions.
+fn calculate_append_diffs(
+    updates: &[TableUpdate],
+    new_metadata: &TableMetadata,
+    previous_metadata: &TableMetadata,
+    added_metadata_log: usize,
+    expired_metadata_logs: usize,
+) -> TableMetadataDiffs {
+    let added_snapshots = updates
+        .iter()
+        .filter_map(|update| match update {
+            TableUpdate::AddSnapshot { snapshot } => Some(snapshot.snapshot_id()),
+            _ => None,
+        })
+        .collect();
+    let (head_of_snapshot_log_changed, n_removed_snapshot_log) =
+        snapshot_log_diff(new_metadata, previous_metadata);
+
+    TableMetadataDiffs {
+        removed_snapshots: vec![],
+        added_snapshots,
+        removed_schemas: vec![],
+        added_schemas: vec![],
+        new_current_schema_id: None,
+        removed_partition_specs: vec![],
+        added_partition_specs: vec![],
+        default_partition_spec_id: None,
+        removed_sort_orders: vec![],
+        added_sort_orders: vec![],
+        default_sort_order_id: None,
+        head_of_snapshot_log_changed,
+        n_removed_snapshot_log,
+        expired_metadata_logs,
+        added_metadata_log,
+        added_stats: vec![],
+        removed_stats: vec![],
+        added_partition_stats: vec![],
+        removed_partition_stats: vec![],
+    }
+}
+
+/// Whether the newest snapshot log entry changed and how many previous entries were removed.
+fn snapshot_log_diff(
+    new_metadata: &TableMetadata,
+    previous_metadata: &TableMetadata,
+) -> (bool, usize) {
+    let head_of_snapshot_log_changed =
+        previous_metadata.history().last() != new_metadata.history().last();
+
+    let n_removed_snapshot_log = previous_metadata.history().len().saturating_sub(
+        new_metadata
+            .history()
+            .len()
+            .saturating_sub(usize::from(head_of_snapshot_log_changed)),
+    );
+    (head_of_snapshot_log_changed, n_removed_snapshot_log)
+}
+
 #[allow(clippy::too_many_lines)]
 fn calculate_diffs(
     new_metadata: &TableMetadata,
@@ -1507,15 +2579,8 @@ fn calculate_diffs(
         != new_metadata.default_sort_order_id())
     .then_some(new_metadata.default_sort_order_id());
 
-    let head_of_snapshot_log_changed =
-        previous_metadata.history().last() != new_metadata.history().last();
-
-    let n_removed_snapshot_log = previous_metadata.history().len().saturating_sub(
-        new_metadata
-            .history()
-            .len()
-            .saturating_sub(usize::from(head_of_snapshot_log_changed)),
-    );
+    let (head_of_snapshot_log_changed, n_removed_snapshot_log) =
+        snapshot_log_diff(new_metadata, previous_metadata);
 
     let old_stats = previous_metadata
         .statistics_iter()
@@ -1574,7 +2639,7 @@ fn calculate_diffs(
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) struct TableMetadataDiffs {
     pub(crate) removed_snapshots: Vec<i64>,
     pub(crate) added_snapshots: Vec<i64>,
@@ -1699,7 +2764,7 @@ fn require_table_id(table_ident: &TableIdent, table_id: Option<TableId>) -> Resu
     })
 }
 
-fn require_not_staged<T>(metadata_location: Option<&T>) -> Result<()> {
+pub(super) fn require_not_staged<T>(metadata_location: Option<&T>) -> Result<()> {
     if metadata_location.is_none() {
         return Err(ErrorModel::not_found(
             "Table not found or staged.",
@@ -1712,7 +2777,7 @@ fn require_not_staged<T>(metadata_location: Option<&T>) -> Result<()> {
     Ok(())
 }
 
-fn take_table_metadata<T>(
+pub(super) fn take_table_metadata<T>(
     table_id: &TableId,
     table_ident: &TableIdent,
     metadatas: &mut HashMap<TableId, T>,
@@ -1898,15 +2963,17 @@ pub(crate) mod test {
     use http::StatusCode;
     use iceberg::{
         spec::{
-            NestedField, Operation, PrimitiveType, Schema, Snapshot, SnapshotReference,
-            SnapshotRetention, Summary, TableMetadata, Transform, Type, UnboundPartitionField,
-            UnboundPartitionSpec, MAIN_BRANCH, PROPERTY_METADATA_PREVIOUS_VERSIONS_MAX,
+            FormatVersion, NestedField, Operation, PrimitiveType, Schema, Snapshot,
+            SnapshotReference, SnapshotRetention, SortOrder, Summary, TableMetadata,
+            TableMetadataBuilder, Transform, Type, UnboundPartitionField, UnboundPartitionSpec,
+            MAIN_BRANCH, PROPERTY_METADATA_PREVIOUS_VERSIONS_MAX,
         },
-        TableIdent,
+        TableIdent, TableRequirement, TableUpdate,
     };
     use iceberg_ext::{
         catalog::rest::{
             CommitTableRequest, CreateNamespaceResponse, CreateTableRequest, LoadTableResult,
+            UpdateNamespacePropertiesRequest,
         },
         configs::Location,
     };
@@ -1919,8 +2986,8 @@ pub(crate) mod test {
             iceberg::{
                 types::{PageToken, Prefix},
                 v1::{
-                    tables::TablesService as _, DataAccess, DropParams, ListTablesQuery,
-                    NamespaceParameters, TableParameters,
+                    namespace::NamespaceService as _, tables::TablesService as _, DataAccess,
+                    DropParams, ListTablesQuery, NamespaceParameters, TableParameters,
                 },
             },
             management::v1::{
@@ -1929,11 +2996,16 @@ pub(crate) mod test {
             },
             ApiContext,
         },
-        catalog::{tables::validate_table_properties, test::impl_pagination_tests, CatalogServer},
+        catalog::{
+            tables::{table_credentials_endpoint, validate_table_properties},
+            test::impl_pagination_tests,
+            CatalogServer,
+        },
         implementations::postgres::{PostgresCatalog, SecretsState},
         request_metadata::RequestMetadata,
         service::{
             authz::{tests::HidingAuthorizer, AllowAllAuthorizer},
+            default_branch::DEFAULT_BRANCH_PROPERTY,
             State, UserId,
         },
         tests::random_request_metadata,
@@ -1946,6 +3018,52 @@ pub(crate) mod test {
         assert!(validate_table_properties(properties.iter()).is_ok());
     }
 
+    #[test]
+    fn test_table_credentials_endpoint() {
+        let warehouse_id = WarehouseId::from(Uuid::nil());
+        let table = TableIdent::new(
+            iceberg::NamespaceIdent::from_vec(vec!["accounting".to_string(), "tax".to_string()])
+                .unwrap(),
+            "my table".to_string(),
+        );
+        assert_eq!(
+            table_credentials_endpoint(
+                &RequestMetadata::new_unauthenticated(),
+                warehouse_id,
+                &table
+            )
+            .unwrap(),
+            "http://localhost:8181/catalog/v1/00000000-0000-0000-0000-000000000000/namespaces/accounting%1Ftax/tables/my%20table/credentials"
+        );
+    }
+
+    #[test]
+    fn test_rewrite_location_prefix() {
+        let rewritten = super::rewrite_location_prefix(
+            "s3://old/ns/table/metadata/snap-1.avro",
+            "s3://old/ns/table/",
+            "s3://new/table",
+        );
+        assert_eq!(
+            rewritten.as_deref(),
+            Some("s3://new/table/metadata/snap-1.avro")
+        );
+        assert_eq!(
+            super::rewrite_location_prefix("s3://old/ns/table", "s3://old/ns/table", "s3://new")
+                .as_deref(),
+            Some("s3://new")
+        );
+        // Siblings that only share a prefix of the name are not rewritten
+        assert_eq!(
+            super::rewrite_location_prefix(
+                "s3://old/ns/table_2/data.parquet",
+                "s3://old/ns/table",
+                "s3://new"
+            ),
+            None
+        );
+    }
+
     #[test]
     fn test_extract_count_from_metadata_location() {
         let location = Location::from_str("s3://path/to/table/metadata/00000-d0407fb2-1112-4944-bb88-c68ae697e2b4.gz.metadata.json").unwrap();
@@ -2075,7 +3193,269 @@ pub(crate) mod test {
         )
         .await
         .unwrap();
-        assert_table_metadata_are_equal(&table_metadata.metadata, &tab.metadata);
+        assert_table_metadata_are_equal(&table_metadata.metadata, &tab.metadata);
+    }
+
+    #[sqlx::test]
+    async fn test_commit_transaction_across_namespaces(pool: sqlx::PgPool) {
+        let (ctx, ns, ns_params, table_1) = commit_test_setup(pool).await;
+        let ns_2 = crate::catalog::test::create_ns(
+            ctx.clone(),
+            ns_params.prefix.clone().unwrap().into_string(),
+            "ns2".to_string(),
+        )
+        .await;
+        let table_2 = CatalogServer::create_table(
+            NamespaceParameters {
+                prefix: ns_params.prefix.clone(),
+                namespace: ns_2.namespace.clone(),
+            },
+            create_request(Some("tab-2".to_string()), Some(false)),
+            DataAccess::not_specified(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+
+        let set_property = |table: LoadTableResult| {
+            table
+                .metadata
+                .into_builder(table.metadata_location)
+                .set_properties(HashMap::from([("p1".into(), "v1".into())]))
+                .unwrap()
+                .build()
+                .unwrap()
+                .changes
+        };
+        let ident_1 = TableIdent::new(ns.namespace.clone(), "tab-1".to_string());
+        let ident_2 = TableIdent::new(ns_2.namespace.clone(), "tab-2".to_string());
+
+        let commits = super::commit_tables_internal(
+            ns_params.prefix.clone(),
+            super::CommitTransactionRequest {
+                table_changes: vec![
+                    CommitTableRequest {
+                        identifier: Some(ident_1.clone()),
+                        requirements: vec![],
+                        updates: set_property(table_1),
+                    },
+                    CommitTableRequest {
+                        identifier: Some(ident_2.clone()),
+                        requirements: vec![],
+                        updates: set_property(table_2),
+                    },
+                ],
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(commits.len(), 2);
+
+        for ident in [ident_1, ident_2] {
+            let tab = CatalogServer::load_table(
+                TableParameters {
+                    prefix: ns_params.prefix.clone(),
+                    table: ident,
+                },
+                DataAccess::not_specified(),
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+            .await
+            .unwrap();
+            assert_eq!(tab.metadata.properties().get("p1"), Some(&"v1".to_string()));
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_commit_transaction_is_atomic(pool: sqlx::PgPool) {
+        let (ctx, ns, ns_params, table_1) = commit_test_setup(pool).await;
+        let table_2 = CatalogServer::create_table(
+            ns_params.clone(),
+            create_request(Some("tab-2".to_string()), Some(false)),
+            DataAccess::not_specified(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        let ident_1 = TableIdent::new(ns.namespace.clone(), "tab-1".to_string());
+        let ident_2 = TableIdent::new(ns.namespace.clone(), "tab-2".to_string());
+
+        let updates_1 = table_1
+            .metadata
+            .into_builder(table_1.metadata_location)
+            .set_properties(HashMap::from([("p1".into(), "v1".into())]))
+            .unwrap()
+            .build()
+            .unwrap()
+            .changes;
+        let updates_2 = table_2
+            .metadata
+            .into_builder(table_2.metadata_location)
+            .set_properties(HashMap::from([("p1".into(), "v1".into())]))
+            .unwrap()
+            .build()
+            .unwrap()
+            .changes;
+
+        // The second change fails its requirement, the first must not be applied.
+        let err = super::commit_tables_internal(
+            ns_params.prefix.clone(),
+            super::CommitTransactionRequest {
+                table_changes: vec![
+                    CommitTableRequest {
+                        identifier: Some(ident_1.clone()),
+                        requirements: vec![],
+                        updates: updates_1,
+                    },
+                    CommitTableRequest {
+                        identifier: Some(ident_2),
+                        requirements: vec![TableRequirement::UuidMatch {
+                            uuid: Uuid::now_v7(),
+                        }],
+                        updates: updates_2,
+                    },
+                ],
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, StatusCode::CONFLICT.as_u16());
+
+        let tab = CatalogServer::load_table(
+            TableParameters {
+                prefix: ns_params.prefix,
+                table: ident_1,
+            },
+            DataAccess::not_specified(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        assert!(!tab.metadata.properties().contains_key("p1"));
+    }
+
+    #[test]
+    fn test_append_diffs_match_full_diffs() {
+        fn append(snapshot_id: i64, parent: Option<i64>) -> Vec<TableUpdate> {
+            let snapshot = Snapshot::builder()
+                .with_snapshot_id(snapshot_id)
+                .with_parent_snapshot_id(parent)
+                .with_timestamp_ms(chrono::Utc::now().timestamp_millis())
+                .with_sequence_number(snapshot_id)
+                .with_schema_id(0)
+                .with_manifest_list(format!("/snap-{snapshot_id}.avro"))
+                .with_summary(Summary {
+                    operation: Operation::Append,
+                    additional_properties: HashMap::new(),
+                })
+                .build();
+            vec![
+                TableUpdate::AddSnapshot { snapshot },
+                TableUpdate::SetSnapshotRef {
+                    ref_name: MAIN_BRANCH.to_string(),
+                    reference: SnapshotReference {
+                        snapshot_id,
+                        retention: SnapshotRetention::Branch {
+                            min_snapshots_to_keep: None,
+                            max_snapshot_age_ms: None,
+                            max_ref_age_ms: None,
+                        },
+                    },
+                },
+            ]
+        }
+
+        let schema = Schema::builder()
+            .with_fields(vec![NestedField::required(
+                1,
+                "id",
+                Type::Primitive(PrimitiveType::Long),
+            )
+            .into()])
+            .build()
+            .unwrap();
+        let mut previous = TableMetadataBuilder::new(
+            schema,
+            UnboundPartitionSpec::builder().build(),
+            SortOrder::unsorted_order(),
+            "s3://bucket/table".to_string(),
+            FormatVersion::V2,
+            HashMap::new(),
+        )
+        .unwrap()
+        .build()
+        .unwrap()
+        .metadata;
+
+        for (snapshot_id, parent) in [(1, None), (2, Some(1))] {
+            let updates = append(snapshot_id, parent);
+            assert!(super::is_append_only(&updates));
+            let new = TableMetadataBuilder::new_from_metadata(previous.clone(), None);
+            let new = updates
+                .iter()
+                .cloned()
+                .fold(new, |builder, update| update.apply(builder).unwrap())
+                .build()
+                .unwrap()
+                .metadata;
+
+            assert_eq!(
+                super::calculate_append_diffs(&updates, &new, &previous, 0, 0),
+                super::calculate_diffs(&new, &previous, 0, 0)
+            );
+            previous = new;
+        }
+    }
+
+    #[test]
+    fn test_validate_commit_transaction_request() {
+        let err = super::validate_commit_transaction_request(&super::CommitTransactionRequest {
+            table_changes: vec![],
+        })
+        .unwrap_err();
+        assert_eq!(err.error.r#type, "EmptyCommitTransaction");
+
+        let change = || CommitTableRequest {
+            identifier: Some(TableIdent::new(
+                iceberg::NamespaceIdent::new("ns1".to_string()),
+                "tab-1".to_string(),
+            )),
+            requirements: vec![],
+            updates: vec![],
+        };
+        super::validate_commit_transaction_request(&super::CommitTransactionRequest {
+            table_changes: vec![change()],
+        })
+        .unwrap();
+
+        let err = super::validate_commit_transaction_request(&super::CommitTransactionRequest {
+            table_changes: vec![change(), change()],
+        })
+        .unwrap_err();
+        assert_eq!(
+            err.error.r#type,
+            "UniqueTableIdentifiersRequiredForCommitTransaction"
+        );
+
+        let err = super::validate_commit_transaction_request(&super::CommitTransactionRequest {
+            table_changes: vec![CommitTableRequest {
+                identifier: None,
+                ..change()
+            }],
+        })
+        .unwrap_err();
+        assert_eq!(
+            err.error.r#type,
+            "TableIdentifierRequiredForCommitTransaction"
+        );
     }
 
     fn schema() -> Schema {
@@ -2374,6 +3754,116 @@ pub(crate) mod test {
         assert_eq!(tab.metadata, builder.metadata);
     }
 
+    #[sqlx::test]
+    async fn test_load_table_serves_default_branch(pool: PgPool) {
+        let (ctx, ns, ns_params, table) = commit_test_setup(pool).await;
+        let table_parameters = TableParameters {
+            prefix: ns_params.prefix.clone(),
+            table: TableIdent {
+                namespace: ns.namespace.clone(),
+                name: "tab-1".to_string(),
+            },
+        };
+        let last_updated = table.metadata.last_updated_ms();
+        let snapshot = Snapshot::builder()
+            .with_snapshot_id(1)
+            .with_timestamp_ms(last_updated + 1)
+            .with_sequence_number(1)
+            .with_schema_id(0)
+            .with_manifest_list("/snap-1.avro")
+            .with_summary(Summary {
+                operation: Operation::Append,
+                additional_properties: HashMap::new(),
+            })
+            .build();
+        let builder = table
+            .metadata
+            .into_builder(table.metadata_location)
+            .add_snapshot(snapshot)
+            .unwrap()
+            .set_ref(
+                "published",
+                SnapshotReference {
+                    snapshot_id: 1,
+                    retention: SnapshotRetention::Branch {
+                        min_snapshots_to_keep: None,
+                        max_snapshot_age_ms: None,
+                        max_ref_age_ms: None,
+                    },
+                },
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+        let _ = super::commit_tables_internal(
+            ns_params.prefix.clone(),
+            super::CommitTransactionRequest {
+                table_changes: vec![CommitTableRequest {
+                    identifier: Some(table_parameters.table.clone()),
+                    requirements: vec![],
+                    updates: builder.changes,
+                }],
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+
+        let tab = CatalogServer::load_table(
+            table_parameters.clone(),
+            DataAccess::not_specified(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(tab.metadata.current_snapshot_id(), None);
+
+        let e = CatalogServer::update_namespace_properties(
+            ns_params.clone(),
+            UpdateNamespacePropertiesRequest {
+                removals: None,
+                updates: Some(HashMap::from_iter([(
+                    DEFAULT_BRANCH_PROPERTY.to_string(),
+                    " published".to_string(),
+                )])),
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(e.error.code, StatusCode::BAD_REQUEST);
+
+        CatalogServer::update_namespace_properties(
+            ns_params.clone(),
+            UpdateNamespacePropertiesRequest {
+                removals: None,
+                updates: Some(HashMap::from_iter([(
+                    DEFAULT_BRANCH_PROPERTY.to_string(),
+                    "published".to_string(),
+                )])),
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+
+        let tab = CatalogServer::load_table(
+            table_parameters,
+            DataAccess::not_specified(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(tab.metadata.current_snapshot_id(), Some(1));
+        assert_eq!(tab.metadata.refs().get(MAIN_BRANCH).unwrap().snapshot_id, 1);
+        assert_eq!(tab.metadata.refs().get("published").unwrap().snapshot_id, 1);
+    }
+
     #[sqlx::test]
     async fn test_expire_metadata_log(pool: PgPool) {
         let (ctx, ns, ns_params, table) = commit_test_setup(pool).await;
@@ -3113,6 +4603,8 @@ pub(crate) mod test {
                 page_size: Some(11),
                 return_uuids: true,
                 return_protection_status: true,
+                return_external_status: false,
+                include: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -3129,6 +4621,8 @@ pub(crate) mod test {
                 page_size: Some(10),
                 return_uuids: true,
                 return_protection_status: true,
+                return_external_status: false,
+                include: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -3145,6 +4639,8 @@ pub(crate) mod test {
                 page_size: Some(10),
                 return_uuids: true,
                 return_protection_status: true,
+                return_external_status: false,
+                include: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -3162,6 +4658,8 @@ pub(crate) mod test {
                 page_size: Some(6),
                 return_uuids: true,
                 return_protection_status: true,
+                return_external_status: false,
+                include: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -3188,6 +4686,8 @@ pub(crate) mod test {
                 page_size: Some(6),
                 return_uuids: true,
                 return_protection_status: true,
+                return_external_status: false,
+                include: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -3222,6 +4722,8 @@ pub(crate) mod test {
                 page_size: Some(5),
                 return_uuids: true,
                 return_protection_status: true,
+                return_external_status: false,
+                include: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -3249,6 +4751,8 @@ pub(crate) mod test {
                 page_size: Some(6),
                 return_uuids: true,
                 return_protection_status: true,
+                return_external_status: false,
+                include: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -3320,6 +4824,8 @@ pub(crate) mod test {
                 page_size: Some(11),
                 return_uuids: true,
                 return_protection_status: true,
+                return_external_status: false,
+                include: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -3337,6 +4843,8 @@ pub(crate) mod test {
                 page_size: Some(11),
                 return_uuids: true,
                 return_protection_status: true,
+                return_external_status: false,
+                include: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -3574,4 +5082,260 @@ pub(crate) mod test {
         assert_eq!(loaded_table.metadata.uuid(), second_table.metadata.uuid());
         assert_ne!(loaded_table.metadata.uuid(), initial_table.metadata.uuid());
     }
+
+    #[sqlx::test]
+    async fn test_register_external_table(pool: PgPool) {
+        let (ctx, ns, ns_params, _) = table_test_setup(pool).await;
+        let source = CatalogServer::create_table(
+            ns_params.clone(),
+            create_request(Some("source".to_string()), Some(false)),
+            DataAccess::not_specified(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        CatalogServer::drop_table(
+            TableParameters {
+                prefix: ns_params.prefix.clone(),
+                table: TableIdent::new(ns.namespace.clone(), "source".to_string()),
+            },
+            DropParams {
+                purge_requested: false,
+                force: false,
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+
+        let table_ident = TableIdent::new(ns.namespace.clone(), "external".to_string());
+        let registered = CatalogServer::register_table(
+            ns_params.clone(),
+            iceberg_ext::catalog::rest::RegisterTableRequest::builder()
+                .name(table_ident.name.clone())
+                .metadata_location(source.metadata_location.clone().unwrap())
+                .external(true)
+                .build(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+
+        let tables = CatalogServer::list_tables(
+            ns_params.clone(),
+            ListTablesQuery {
+                page_token: PageToken::NotSpecified,
+                page_size: None,
+                return_uuids: false,
+                return_protection_status: false,
+                return_external_status: true,
+                include: None,
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(tables.identifiers, vec![table_ident.clone()]);
+        assert_eq!(tables.external_status, Some(vec![true]));
+        assert_eq!(tables.protection_status, None);
+
+        let updates = registered
+            .metadata
+            .into_builder(registered.metadata_location)
+            .set_properties(HashMap::from([("p1".into(), "v1".into())]))
+            .unwrap()
+            .build()
+            .unwrap()
+            .changes;
+        let e = super::commit_tables_internal(
+            ns_params.prefix.clone(),
+            super::CommitTransactionRequest {
+                table_changes: vec![CommitTableRequest {
+                    identifier: Some(table_ident.clone()),
+                    requirements: vec![],
+                    updates,
+                }],
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .expect_err("Commits to external tables must be rejected");
+        assert_eq!(e.error.r#type, "ExternalTableNotModifiable");
+
+        CatalogServer::drop_table(
+            TableParameters {
+                prefix: ns_params.prefix.clone(),
+                table: table_ident,
+            },
+            DropParams {
+                purge_requested: true,
+                force: false,
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+    }
+
+    #[sqlx::test]
+    async fn test_register_verify_rejects_registered_table(pool: PgPool) {
+        let (ctx, _, ns_params, _) = table_test_setup(pool).await;
+        let source = CatalogServer::create_table(
+            ns_params.clone(),
+            create_request(Some("source".to_string()), Some(false)),
+            DataAccess::not_specified(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+
+        let e = CatalogServer::register_table(
+            ns_params.clone(),
+            iceberg_ext::catalog::rest::RegisterTableRequest::builder()
+                .name("copy".to_string())
+                .metadata_location(source.metadata_location.clone().unwrap())
+                .verify(true)
+                .build(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .expect_err("Metadata of a registered table must be rejected");
+        assert_eq!(e.error.r#type, "TableAlreadyRegistered");
+        assert_eq!(e.error.code, StatusCode::CONFLICT);
+
+        let mut missing_location = source.metadata_location.clone().unwrap();
+        missing_location.push_str(".missing");
+        let e = CatalogServer::register_table(
+            ns_params.clone(),
+            iceberg_ext::catalog::rest::RegisterTableRequest::builder()
+                .name("missing".to_string())
+                .metadata_location(missing_location)
+                .verify(true)
+                .build(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .expect_err("Missing metadata files must be rejected");
+        assert_eq!(e.error.r#type, "MetadataFileNotFound");
+    }
+
+    #[sqlx::test]
+    async fn test_register_table_with_imported_history(pool: PgPool) {
+        let (ctx, ns, ns_params, _) = table_test_setup(pool).await;
+        let source_ident = TableIdent::new(ns.namespace.clone(), "source".to_string());
+        let mut source = CatalogServer::create_table(
+            ns_params.clone(),
+            create_request(Some("source".to_string()), Some(false)),
+            DataAccess::not_specified(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+
+        // Only the latest previous metadata file is kept in the metadata log
+        for i in 0..3 {
+            let updates = source
+                .metadata
+                .into_builder(source.metadata_location)
+                .set_properties(HashMap::from([
+                    (
+                        PROPERTY_METADATA_PREVIOUS_VERSIONS_MAX.to_string(),
+                        "1".to_string(),
+                    ),
+                    ("p1".to_string(), i.to_string()),
+                ]))
+                .unwrap()
+                .build()
+                .unwrap()
+                .changes;
+            super::commit_tables_internal(
+                ns_params.prefix.clone(),
+                super::CommitTransactionRequest {
+                    table_changes: vec![CommitTableRequest {
+                        identifier: Some(source_ident.clone()),
+                        requirements: vec![],
+                        updates,
+                    }],
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+            .await
+            .unwrap();
+            source = CatalogServer::load_table(
+                TableParameters {
+                    prefix: ns_params.prefix.clone(),
+                    table: source_ident.clone(),
+                },
+                DataAccess::not_specified(),
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+            .await
+            .unwrap();
+        }
+        assert_eq!(source.metadata.metadata_log().len(), 1);
+
+        CatalogServer::drop_table(
+            TableParameters {
+                prefix: ns_params.prefix.clone(),
+                table: source_ident,
+            },
+            DropParams {
+                purge_requested: false,
+                force: false,
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+
+        let table_ident = TableIdent::new(ns.namespace.clone(), "imported".to_string());
+        CatalogServer::register_table(
+            ns_params.clone(),
+            iceberg_ext::catalog::rest::RegisterTableRequest::builder()
+                .name(table_ident.name.clone())
+                .metadata_location(source.metadata_location.clone().unwrap())
+                .import_history(true)
+                .build(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+
+        let imported = CatalogServer::load_table(
+            TableParameters {
+                prefix: ns_params.prefix.clone(),
+                table: table_ident,
+            },
+            DataAccess::not_specified(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        // Initial metadata and all three commits except the current one
+        assert_eq!(imported.metadata.metadata_log().len(), 3);
+        assert_eq!(
+            imported.metadata.metadata_log().last(),
+            source.metadata.metadata_log().last()
+        );
+        assert!(imported
+            .metadata
+            .metadata_log()
+            .windows(2)
+            .all(|w| w[0].timestamp_ms <= w[1].timestamp_ms));
+    }
 }
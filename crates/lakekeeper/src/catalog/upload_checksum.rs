@@ -0,0 +1,205 @@
+//! Verification of files uploaded by clients against checksums they provide.
+//!
+//! Clients upload the manifest list of a snapshot before they commit it. A truncated upload
+//! is only noticed when the table is read the next time. To catch it at commit time, clients
+//! add the checksum of the manifest list to the summary of the snapshot, i.e.
+//! `lakekeeper.manifest-list-checksum=sha256:<hex>`. Spark sets it with the
+//! `snapshot-property.lakekeeper.manifest-list-checksum` write option.
+//!
+//! Enforcement is configured with `CONFIG.upload_checksum_enforcement`.
+use std::{fmt::Display, str::FromStr};
+
+use iceberg::{spec::Snapshot, TableUpdate};
+use iceberg_ext::configs::{Location, ParseFromStr as _};
+use md5::Digest as _;
+
+use super::{
+    io::{read_file, StorageIo},
+    tables::CommitContext,
+};
+use crate::{
+    config::UploadChecksumEnforcement,
+    service::{ErrorModel, Result},
+    CONFIG,
+};
+
+/// Snapshot summary property holding the checksum of the manifest list.
+pub(crate) const MANIFEST_LIST_CHECKSUM_PROPERTY: &str = "lakekeeper.manifest-list-checksum";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum_macros::EnumString, strum_macros::Display)]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum ChecksumAlgorithm {
+    Sha256,
+    Md5,
+}
+
+impl ChecksumAlgorithm {
+    /// Lowercase hex digest of `content`.
+    fn digest(self, content: &[u8]) -> String {
+        match self {
+            Self::Sha256 => format!("{:x}", sha2::Sha256::digest(content)),
+            Self::Md5 => format!("{:x}", md5::Md5::digest(content)),
+        }
+    }
+}
+
+/// Checksum in the form `<algorithm>:<hex digest>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checksum {
+    pub algorithm: ChecksumAlgorithm,
+    pub digest: String,
+}
+
+impl FromStr for Checksum {
+    type Err = ErrorModel;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| {
+            ErrorModel::bad_request(
+                format!("Invalid checksum '{s}': {reason}. Expected '<sha256|md5>:<hex digest>'"),
+                "InvalidUploadChecksum",
+                None,
+            )
+        };
+        let (algorithm, digest) = s
+            .split_once(':')
+            .ok_or_else(|| invalid("missing algorithm"))?;
+        let algorithm = ChecksumAlgorithm::from_str(algorithm.trim())
+            .map_err(|_| invalid("unsupported algorithm"))?;
+        let digest = digest.trim().to_ascii_lowercase();
+        if digest.is_empty() || !digest.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(invalid("digest is not hex encoded"));
+        }
+        Ok(Self { algorithm, digest })
+    }
+}
+
+impl Display for Checksum {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.algorithm, self.digest)
+    }
+}
+
+/// Verifies the manifest lists of all snapshots added by the commits against the checksums
+/// in their summaries. Must be called before the commits are persisted.
+pub(crate) async fn verify_upload_checksums(
+    commits: &[CommitContext],
+    file_io: &StorageIo,
+) -> Result<()> {
+    let enforcement = CONFIG.upload_checksum_enforcement;
+    if enforcement == UploadChecksumEnforcement::Disabled {
+        return Ok(());
+    }
+
+    let mut checks = vec![];
+    for snapshot in commits
+        .iter()
+        .flat_map(|commit| &commit.updates)
+        .filter_map(|update| match update {
+            TableUpdate::AddSnapshot { snapshot } => Some(snapshot),
+            _ => None,
+        })
+    {
+        match snapshot_checksum(snapshot)? {
+            Some(checksum) => checks.push(verify_manifest_list(snapshot, checksum, file_io)),
+            None if enforcement == UploadChecksumEnforcement::Required => {
+                return Err(ErrorModel::bad_request(
+                    format!(
+                        "Snapshot {} has no checksum of its manifest list. Set the snapshot summary property '{MANIFEST_LIST_CHECKSUM_PROPERTY}'.",
+                        snapshot.snapshot_id()
+                    ),
+                    "UploadChecksumRequired",
+                    None,
+                )
+                .into());
+            }
+            None => {}
+        }
+    }
+    futures::future::try_join_all(checks).await?;
+    Ok(())
+}
+
+fn snapshot_checksum(snapshot: &Snapshot) -> Result<Option<Checksum>> {
+    snapshot
+        .summary()
+        .additional_properties
+        .get(MANIFEST_LIST_CHECKSUM_PROPERTY)
+        .map(|checksum| Checksum::from_str(checksum).map_err(Into::into))
+        .transpose()
+}
+
+async fn verify_manifest_list(
+    snapshot: &Snapshot,
+    expected: Checksum,
+    file_io: &StorageIo,
+) -> Result<()> {
+    let location = Location::parse_value(snapshot.manifest_list()).map_err(|e| {
+        ErrorModel::bad_request(
+            format!(
+                "Invalid manifest list location of snapshot {}",
+                snapshot.snapshot_id()
+            ),
+            "InvalidManifestListLocation",
+            Some(Box::new(e)),
+        )
+    })?;
+    let content = read_file(file_io, &location).await.map_err(|e| {
+        ErrorModel::bad_request(
+            format!(
+                "Manifest list '{location}' of snapshot {} can't be read to verify its checksum",
+                snapshot.snapshot_id()
+            ),
+            "UploadedFileUnreadable",
+            Some(Box::new(e)),
+        )
+    })?;
+    let actual = expected.algorithm.digest(&content);
+    if actual != expected.digest {
+        return Err(ErrorModel::bad_request(
+            format!(
+                "Manifest list '{location}' of snapshot {} does not match its checksum. Expected {expected}, found {}:{actual} for {} bytes. The upload may be incomplete.",
+                snapshot.snapshot_id(),
+                expected.algorithm,
+                content.len()
+            ),
+            "UploadChecksumMismatch",
+            None,
+        )
+        .into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_checksum() {
+        let checksum = Checksum::from_str("SHA256:ABCDEF0123").unwrap();
+        assert_eq!(checksum.algorithm, ChecksumAlgorithm::Sha256);
+        assert_eq!(checksum.to_string(), "sha256:abcdef0123");
+        assert_eq!(
+            Checksum::from_str("md5:0a").unwrap().algorithm,
+            ChecksumAlgorithm::Md5
+        );
+
+        for invalid in ["abcdef", "crc32:abcd", "sha256:", "sha256:xyz"] {
+            let e = Checksum::from_str(invalid).unwrap_err();
+            assert_eq!(e.r#type, "InvalidUploadChecksum", "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_digest() {
+        assert_eq!(
+            ChecksumAlgorithm::Sha256.digest(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            ChecksumAlgorithm::Md5.digest(b"abc"),
+            "900150983cd24fb0d6963f7d28e17f72"
+        );
+    }
+}
@@ -8,6 +8,7 @@ mod rename;
 
 use std::str::FromStr;
 
+pub(crate) use commit::commit_view;
 pub(crate) use exists::authorized_view_ident_to_id;
 use iceberg_ext::{
     catalog::rest::{ErrorModel, ViewUpdate},
@@ -104,7 +105,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
     }
 }
 
-fn validate_view_properties<'a, I>(properties: I) -> Result<()>
+pub(crate) fn validate_view_properties<'a, I>(properties: I) -> Result<()>
 where
     I: IntoIterator<Item = &'a String>,
 {
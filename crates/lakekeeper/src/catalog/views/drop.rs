@@ -6,7 +6,10 @@ use crate::{
         management::v1::{warehouse::TabularDeleteProfile, DeleteKind, TabularType},
         set_not_found_status_code, ApiContext,
     },
-    catalog::{require_warehouse_id, tables::validate_table_or_view_ident},
+    catalog::{
+        namespace::resolve_tabular_delete_profile, require_warehouse_id,
+        tables::validate_table_or_view_ident,
+    },
     request_metadata::RequestMetadata,
     service::{
         authz::{Authorizer, CatalogViewAction, CatalogWarehouseAction},
@@ -63,7 +66,9 @@ pub(crate) async fn drop_view<C: Catalog, A: Authorizer + Clone, S: SecretStore>
 
     tracing::debug!("Proceeding to delete view");
 
-    match warehouse.tabular_delete_profile {
+    let delete_profile =
+        resolve_tabular_delete_profile::<C>(&warehouse, &view.namespace, &mut t).await?;
+    match delete_profile {
         TabularDeleteProfile::Hard {} => {
             let location = C::drop_view(view_id, force, t.transaction()).await?;
 
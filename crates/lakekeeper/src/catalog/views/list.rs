@@ -13,7 +13,7 @@ use crate::{
     request_metadata::RequestMetadata,
     service::{
         authz::{Authorizer, CatalogNamespaceAction, CatalogViewAction},
-        Catalog, SecretStore, State, Transaction,
+        client_compatibility, Catalog, SecretStore, State, Transaction,
     },
 };
 
@@ -32,7 +32,7 @@ pub(crate) async fn list_views<C: Catalog, A: Authorizer + Clone, S: SecretStore
     let authorizer = state.v1_state.authz;
 
     let mut t: <C as Catalog>::Transaction =
-        C::Transaction::begin_read(state.v1_state.catalog).await?;
+        C::Transaction::begin_read(state.v1_state.catalog.clone()).await?;
 
     let namespace_id = authorized_namespace_ident_to_id::<C, _>(
         authorizer.clone(),
@@ -71,12 +71,22 @@ pub(crate) async fn list_views<C: Catalog, A: Authorizer + Clone, S: SecretStore
         idents.push(ident.table_ident);
         protection_status.push(ident.protected);
     }
+    let next_page_token = client_compatibility::next_page_token::<C>(
+        next_page_token,
+        warehouse_id,
+        state.v1_state.catalog,
+    )
+    .await;
 
     Ok(ListTablesResponse {
         next_page_token,
         identifiers: idents,
         table_uuids: return_uuids.then_some(view_uuids.into_iter().map(|id| *id).collect()),
+        external_status: query
+            .return_external_status
+            .then(|| vec![false; protection_status.len()]),
         protection_status: query.return_protection_status.then_some(protection_status),
+        stats: None,
     })
 }
 
@@ -221,6 +231,8 @@ mod test {
                 page_size: Some(11),
                 return_uuids: true,
                 return_protection_status: true,
+                return_external_status: false,
+                include: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -237,6 +249,8 @@ mod test {
                 page_size: Some(10),
                 return_uuids: true,
                 return_protection_status: true,
+                return_external_status: false,
+                include: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -253,6 +267,8 @@ mod test {
                 page_size: Some(10),
                 return_uuids: true,
                 return_protection_status: true,
+                return_external_status: false,
+                include: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -270,6 +286,8 @@ mod test {
                 page_size: Some(6),
                 return_uuids: true,
                 return_protection_status: true,
+                return_external_status: false,
+                include: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -296,6 +314,8 @@ mod test {
                 page_size: Some(6),
                 return_uuids: true,
                 return_protection_status: true,
+                return_external_status: false,
+                include: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -331,6 +351,8 @@ mod test {
                 page_size: Some(5),
                 return_uuids: true,
                 return_protection_status: true,
+                return_external_status: false,
+                include: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -358,6 +380,8 @@ mod test {
                 page_size: Some(6),
                 return_uuids: true,
                 return_protection_status: true,
+                return_external_status: false,
+                include: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -433,6 +457,8 @@ mod test {
                 page_size: Some(11),
                 return_uuids: true,
                 return_protection_status: true,
+                return_external_status: false,
+                include: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -450,6 +476,8 @@ mod test {
                 page_size: Some(11),
                 return_uuids: true,
                 return_protection_status: true,
+                return_external_status: false,
+                include: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
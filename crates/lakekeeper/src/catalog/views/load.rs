@@ -73,6 +73,13 @@ pub(crate) async fn load_view<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         status,
         tabular_delete_profile: _,
         protected: _,
+        disabled_endpoint_groups: _,
+        client_workarounds: _,
+        default_branch: _,
+        metadata_push_enabled: _,
+        labels: _,
+        created_at: _,
+        updated_at: _,
     } = C::require_warehouse(warehouse_id, t.transaction()).await?;
     require_active_warehouse(status)?;
 
@@ -20,7 +20,15 @@ use serde::{Deserialize, Deserializer, Serialize};
 use url::Url;
 use veil::Redact;
 
-use crate::{ProjectId, WarehouseId};
+use crate::{
+    service::{
+        client_compatibility::ClientWorkaround, endpoint_policy::EndpointGroup,
+        engine_compatibility::Engine, glue_federation::GlueFederatedWarehouse,
+        hms_federation::HmsFederatedWarehouse, rate_limit::RateLimitRule,
+        replica::ReplicaWarehouse, storage_policy::StoragePolicyRule, UserId,
+    },
+    ProjectId, WarehouseId,
+};
 
 const DEFAULT_RESERVED_NAMESPACES: [&str; 3] = ["system", "examples", "information_schema"];
 const DEFAULT_ENCRYPTION_KEY: &str = "<This is unsafe, please set a proper key>";
@@ -89,6 +97,9 @@ pub struct DynAppConfig {
     pub base_uri: Option<url::Url>,
     /// Port under which we serve metrics
     pub metrics_port: u16,
+    /// Maximum number of warehouses that are labeled individually in per-warehouse metrics.
+    /// Metrics of further warehouses are aggregated. `0` disables per-warehouse metrics.
+    pub metrics_max_warehouses: usize,
     /// Port to listen on.
     pub listen_port: u16,
     /// Bind IP the server listens on.
@@ -124,6 +135,15 @@ pub struct DynAppConfig {
         serialize_with = "serialize_reserved_namespaces"
     )]
     pub reserved_namespaces: ReservedNamespaces,
+    /// Endpoint groups that are disabled for the whole deployment, as a comma
+    /// separated list, e.g. `register-table,purge-drops`. Requests to disabled
+    /// endpoints are rejected with `405 Method Not Allowed`.
+    /// Groups can additionally be disabled per warehouse via the management API.
+    #[serde(
+        deserialize_with = "deserialize_endpoint_groups",
+        serialize_with = "serialize_endpoint_groups"
+    )]
+    pub disabled_endpoint_groups: Vec<EndpointGroup>,
     // ------------- STORAGE OPTIONS -------------
     /// If true, can create Warehouses with using System Identities.
     pub(crate) enable_aws_system_credentials: bool,
@@ -140,6 +160,23 @@ pub struct DynAppConfig {
     /// Enable GCP System Identities
     pub(crate) enable_gcp_system_credentials: bool,
 
+    /// Enable Kerberos authentication to HDFS with the credentials of the system
+    pub(crate) enable_hdfs_kerberos_credentials: bool,
+
+    /// Enable warehouses in the local file system of the server.
+    /// Intended for tests and demos only.
+    pub(crate) enable_local_filesystem_storage: bool,
+
+    /// Storage settings that can no longer be used for new warehouses, as a comma separated
+    /// list of `deny-storage-type:<storage-type>`, `deny-credential-type:<storage-type>/<credential-type>`
+    /// and `require-s3-remote-signing`. Existing warehouses keep working and may be updated
+    /// as long as no new violation is introduced.
+    #[serde(
+        deserialize_with = "deserialize_storage_policy",
+        serialize_with = "serialize_storage_policy"
+    )]
+    pub storage_policy: Vec<StoragePolicyRule>,
+
     // ------------- POSTGRES IMPLEMENTATION -------------
     #[redact]
     pub(crate) pg_encryption_key: String,
@@ -208,6 +245,15 @@ pub struct DynAppConfig {
     pub kubernetes_authentication_accept_legacy_serviceaccount: bool,
     /// Claim to use in provided JWT tokens as the subject.
     pub openid_subject_claim: Option<String>,
+    /// Maximum lifetime in seconds of an impersonation started by a server admin.
+    #[serde(
+        deserialize_with = "seconds_to_duration",
+        serialize_with = "duration_to_seconds"
+    )]
+    pub max_impersonation_duration_seconds: chrono::Duration,
+    /// Maximum age in seconds of tokens, measured from their `iat` claim, that are accepted
+    /// by management and permission endpoints that change the server. Unlimited if not set.
+    pub max_token_age_seconds: Option<u64>,
 
     // ------------- AUTHORIZATION - OPENFGA -------------
     #[serde(default)]
@@ -230,6 +276,13 @@ pub struct DynAppConfig {
         serialize_with = "crate::config::serialize_std_duration_as_ms"
     )]
     pub task_poll_interval: std::time::Duration,
+    /// Task queues whose built-in workers are not started, as a comma separated list.
+    /// Tasks of these queues are processed by external workers via the management API.
+    #[serde(
+        deserialize_with = "deserialize_string_list",
+        serialize_with = "serialize_string_list"
+    )]
+    pub disabled_task_queue_workers: Vec<String>,
     // ------------- Tabular -------------
     /// Delay in seconds after which a tabular will be deleted
     #[serde(
@@ -238,6 +291,288 @@ pub struct DynAppConfig {
     )]
     pub default_tabular_expiration_delay_seconds: chrono::Duration,
 
+    // ------------- Scan Planning -------------
+    /// Maximum time to wait for scan planning to complete before responding
+    /// with a `submitted` status. Planning continues in the background.
+    #[serde(
+        deserialize_with = "seconds_to_std_duration",
+        serialize_with = "serialize_std_duration_as_ms"
+    )]
+    pub scan_planning_sync_timeout: Duration,
+    /// Number of data manifests that are resolved by a single plan task.
+    pub scan_planning_manifests_per_plan_task: usize,
+    /// Duration in seconds after which a scan plan and its plan tasks expire.
+    #[serde(
+        deserialize_with = "seconds_to_duration",
+        serialize_with = "duration_to_seconds"
+    )]
+    pub scan_plan_expiration_seconds: chrono::Duration,
+
+    // ------------- Transactions -------------
+    /// Maximum number of tables that can be committed in a single
+    /// `/transactions/commit` request.
+    pub max_tables_per_transaction: usize,
+    /// Maximum time a commit waits for concurrent commits to the same tables on this
+    /// server before it fails with a conflict. Commits to a table are served in the
+    /// order they arrive. `0` disables queueing, concurrent commits are then retried a
+    /// few times before failing.
+    #[serde(
+        deserialize_with = "seconds_to_std_duration",
+        serialize_with = "serialize_std_duration_as_ms"
+    )]
+    pub commit_queue_max_wait: Duration,
+    /// If enabled, commits that only append snapshots skip the comparison of the
+    /// full previous and new table metadata when persisting changes.
+    pub enable_append_commit_fast_path: bool,
+    /// Verification of manifest lists uploaded by clients against the checksum in the
+    /// `lakekeeper.manifest-list-checksum` summary property of their snapshot.
+    pub upload_checksum_enforcement: UploadChecksumEnforcement,
+    /// Check for views referencing a table before it is dropped.
+    pub view_lineage_drop_check: ViewLineageDropCheck,
+
+    // ------------- Remote Signing -------------
+    /// If enabled, every signed S3 request is recorded in the `s3_sign_audit` table.
+    /// Signing fails if the request cannot be recorded.
+    pub enable_s3_sign_audit: bool,
+    /// Time in seconds audit entries of signed requests are kept.
+    #[serde(
+        deserialize_with = "seconds_to_duration",
+        serialize_with = "duration_to_seconds"
+    )]
+    pub s3_sign_audit_retention_seconds: chrono::Duration,
+
+    // ------------- PII Detection -------------
+    /// If enabled, tables are analyzed for columns that likely contain personally
+    /// identifiable information whenever they are created or their schema changes.
+    /// Results are stored as column tag suggestions that need to be reviewed.
+    pub enable_pii_detection: bool,
+
+    // ------------- Snapshot Expiration -------------
+    /// If enabled, snapshots of tables are expired periodically according to the
+    /// retention policy of their warehouse and table.
+    pub enable_snapshot_expiration: bool,
+    /// Time in seconds between two snapshot expiration runs of a table.
+    #[serde(
+        deserialize_with = "seconds_to_duration",
+        serialize_with = "duration_to_seconds"
+    )]
+    pub snapshot_expiration_interval_seconds: chrono::Duration,
+
+    // ------------- Engine Compatibility -------------
+    /// Engines that tables are checked against by the compatibility report,
+    /// as a comma separated list of `<engine>-<version>`, e.g. `trino-440,spark-1.7.1`.
+    #[serde(
+        deserialize_with = "deserialize_engines",
+        serialize_with = "serialize_engines"
+    )]
+    pub compatibility_engines: Vec<Engine>,
+
+    // ------------- Client Compatibility -------------
+    /// Workarounds for known client bugs that are enabled for the whole deployment,
+    /// as a comma separated list, e.g. `omit-empty-next-page-token`.
+    /// Workarounds can additionally be enabled per warehouse via the management API.
+    #[serde(
+        deserialize_with = "deserialize_client_workarounds",
+        serialize_with = "serialize_client_workarounds"
+    )]
+    pub client_workarounds: Vec<ClientWorkaround>,
+
+    // ------------- Orphan File Cleanup -------------
+    /// If enabled, the locations of tables are scanned periodically for files that
+    /// are not referenced by the table metadata.
+    pub enable_orphan_file_cleanup: bool,
+    /// Time in seconds between two orphan file cleanup runs of a table.
+    #[serde(
+        deserialize_with = "seconds_to_duration",
+        serialize_with = "duration_to_seconds"
+    )]
+    pub orphan_file_cleanup_interval_seconds: chrono::Duration,
+
+    // ------------- Catalog Snapshot Export -------------
+    /// If enabled, a logical snapshot of the catalog state of each warehouse is
+    /// periodically exported to the storage of the warehouse.
+    pub enable_catalog_snapshot_export: bool,
+    /// Time in seconds between two catalog snapshot exports of a warehouse.
+    #[serde(
+        deserialize_with = "seconds_to_duration",
+        serialize_with = "duration_to_seconds"
+    )]
+    pub catalog_snapshot_export_interval_seconds: chrono::Duration,
+
+    // ------------- Maintenance -------------
+    /// Minimum number of small data files in a partition to recommend compaction.
+    pub compaction_min_input_files: usize,
+    /// Minimum number of delete files in a partition to recommend compaction.
+    pub compaction_delete_file_threshold: usize,
+
+    // ------------- Entity IDs -------------
+    /// Scheme used to generate the IDs of new warehouses, namespaces, tables and views.
+    pub id_scheme: IdScheme,
+
+    // ------------- Search Index -------------
+    /// External index used by the catalog search. If not set, searches run against Postgres.
+    pub search_index_backend: Option<SearchIndexBackend>,
+    pub search_index_url: Option<Url>,
+    /// Name of the OpenSearch index or Meilisearch index uid.
+    pub search_index_name: String,
+    pub search_index_user: Option<String>,
+    #[redact]
+    pub search_index_password: Option<String>,
+    #[redact]
+    pub search_index_api_key: Option<String>,
+    /// Time in seconds between two full re-syncs of the search index.
+    /// Full syncs repair changes missed while the indexer was unavailable.
+    /// `0` disables full syncs, including the one on startup.
+    #[serde(
+        deserialize_with = "seconds_to_duration",
+        serialize_with = "duration_to_seconds"
+    )]
+    pub search_index_full_sync_interval_seconds: chrono::Duration,
+
+    // ------------- Metadata Push -------------
+    /// Metadata platform that schema, ownership and property changes of tables are pushed to.
+    /// Pushing is enabled per warehouse.
+    pub metadata_push_backend: Option<MetadataPushBackend>,
+    /// Base URL of the DataHub GMS or the OpenMetadata server.
+    pub metadata_push_url: Option<Url>,
+    /// Bearer token to authenticate against the metadata platform.
+    #[redact]
+    pub metadata_push_token: Option<String>,
+    /// Environment (fabric) of DataHub dataset URNs.
+    pub metadata_push_datahub_env: String,
+    /// Name of the OpenMetadata database service that tables are pushed to.
+    pub metadata_push_openmetadata_service: String,
+    /// Maximum number of attempts to push a change. Attempts are spaced
+    /// with exponential backoff.
+    pub metadata_push_max_attempts: u32,
+
+    // ------------- Announcements -------------
+    /// If true, active announcements are added to all responses as
+    /// `lakekeeper-announcement` headers.
+    pub announcement_headers: bool,
+
+    // ------------- Response Properties -------------
+    /// YAML or JSON file with rules that inject additional properties into
+    /// `getConfig` and `loadTable` responses.
+    pub response_property_rules_file: Option<PathBuf>,
+
+    // ------------- Tracing -------------
+    /// OTLP gRPC endpoint that spans are exported to, i.e. `http://localhost:4317`.
+    /// If not set, spans are not exported.
+    pub otlp_traces_endpoint: Option<Url>,
+    /// Service name of exported spans.
+    pub otlp_service_name: String,
+
+    // ------------- Request Log -------------
+    /// If true, a structured line is logged for every request. Can be changed at
+    /// runtime via the management API, which takes precedence over all request log
+    /// settings of the configuration.
+    pub request_log_enabled: bool,
+    /// If true, JSON request bodies of at most 64 KiB are included in request logs.
+    pub request_log_include_request_body: bool,
+    /// If true, tokens, secrets, passwords and credentials are redacted in request logs.
+    pub request_log_redact_credentials: bool,
+    /// Keys of properties whose values are redacted in request logs, as a comma
+    /// separated list. A trailing `*` matches all keys starting with the prefix.
+    #[serde(
+        deserialize_with = "deserialize_string_list",
+        serialize_with = "serialize_string_list"
+    )]
+    pub request_log_redacted_properties: Vec<String>,
+
+    // ------------- Server Events -------------
+    /// If true, lifecycle events of the server are published to the cloud event sinks
+    /// in addition to being recorded in the catalog.
+    pub publish_server_events: bool,
+    /// Time in seconds server events are kept.
+    #[serde(
+        deserialize_with = "seconds_to_duration",
+        serialize_with = "duration_to_seconds"
+    )]
+    pub server_event_retention_seconds: chrono::Duration,
+
+    // ------------- Shared Cache -------------
+    /// Redis that token validation results, `getConfig` responses, table metadata,
+    /// namespace ids and authorization decisions are cached in, shared by all instances.
+    /// Requires the `redis` feature.
+    #[redact]
+    pub redis_url: Option<Url>,
+    /// Time to live of entries in the shared cache. Cached token validation results
+    /// never outlive the expiry of the token.
+    pub shared_cache_ttl_seconds: u64,
+
+    // ------------- Metadata Cache -------------
+    /// Maximum size in bytes of the table metadata files cached in memory by their
+    /// location. Set to 0 to disable the cache.
+    pub metadata_cache_max_size_bytes: u64,
+    /// Time to live of cached table metadata files in seconds.
+    pub metadata_cache_ttl_seconds: u64,
+
+    // ------------- Rate Limits -------------
+    /// Quotas of requests as a comma separated list of `<scope>[.<route-class>]=<requests>/<s|m|h>`,
+    /// e.g. `principal=100/s,warehouse.commit=50/s`. Requests exceeding a quota are rejected
+    /// with `429 Too Many Requests`. Quotas are shared by all instances if `redis_url` is set.
+    #[serde(
+        deserialize_with = "deserialize_rate_limits",
+        serialize_with = "serialize_rate_limits"
+    )]
+    pub rate_limits: Vec<RateLimitRule>,
+
+    // ------------- Read Replica -------------
+    /// Base URL of the Lakekeeper this instance is a read replica of, i.e.
+    /// `https://lakekeeper.eu.example.com`. If set, endpoints of the Iceberg REST API that
+    /// change the catalog are rejected and the warehouses of `replica_warehouses` are
+    /// kept in sync with the primary.
+    pub replica_primary_url: Option<Url>,
+    /// Bearer token to authenticate at the primary. The principal requires the
+    /// `operator` or `admin` role on the primary.
+    #[redact]
+    pub replica_primary_token: Option<String>,
+    /// File the bearer token is read from before every sync, i.e. a projected Kubernetes
+    /// service account token. Takes precedence over `replica_primary_token`.
+    pub replica_primary_token_file: Option<PathBuf>,
+    /// Warehouses to mirror as a comma separated list of
+    /// `<replica-warehouse-id>=<primary-warehouse-id>`.
+    #[serde(
+        deserialize_with = "deserialize_replica_warehouses",
+        serialize_with = "serialize_replica_warehouses"
+    )]
+    pub replica_warehouses: Vec<ReplicaWarehouse>,
+    /// Time in seconds between two syncs of a mirrored warehouse.
+    pub replica_sync_interval_seconds: u64,
+    /// Principal that owns mirrored namespaces, tables and views in the authorizer of the
+    /// replica. Required unless the `allowall` authorizer is used.
+    pub replica_owner: Option<UserId>,
+
+    // ------------- Glue Federation -------------
+    /// Warehouses that mirror an AWS Glue Data Catalog as a comma separated list of
+    /// `<warehouse-id>=<region>[:<catalog-id>]`. Federated warehouses are read-only.
+    /// Glue is accessed with the credentials of the default AWS credential chain.
+    #[serde(
+        deserialize_with = "deserialize_glue_federated_warehouses",
+        serialize_with = "serialize_glue_federated_warehouses"
+    )]
+    pub glue_federated_warehouses: Vec<GlueFederatedWarehouse>,
+    /// Time in seconds between two syncs of a federated warehouse.
+    pub glue_federation_sync_interval_seconds: u64,
+    /// Principal that owns mirrored namespaces and tables in the authorizer.
+    /// Required unless the `allowall` authorizer is used.
+    pub glue_federation_owner: Option<UserId>,
+
+    // ------------- Hive Metastore Federation -------------
+    /// Warehouses that mirror the Iceberg tables of a Hive Metastore as a comma separated
+    /// list of `<warehouse-id>=thrift://<host>[:<port>]`. Federated warehouses are read-only.
+    #[serde(
+        deserialize_with = "deserialize_hms_federated_warehouses",
+        serialize_with = "serialize_hms_federated_warehouses"
+    )]
+    pub hms_federated_warehouses: Vec<HmsFederatedWarehouse>,
+    /// Time in seconds between two syncs of a federated warehouse.
+    pub hms_federation_sync_interval_seconds: u64,
+    /// Principal that owns mirrored namespaces and tables in the authorizer.
+    /// Required unless the `allowall` authorizer is used.
+    pub hms_federation_owner: Option<UserId>,
+
     // ------------- Stats -------------
     /// Interval to wait before writing the latest accumulated endpoint statistics into the database.
     ///
@@ -330,6 +665,203 @@ where
         .serialize(serializer)
 }
 
+fn deserialize_string_list<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let buf = String::deserialize(deserializer)?;
+    Ok(buf
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+fn serialize_string_list<S>(value: &[String], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    value.join(",").serialize(serializer)
+}
+
+fn deserialize_endpoint_groups<'de, D>(deserializer: D) -> Result<Vec<EndpointGroup>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let buf = String::deserialize(deserializer)?;
+    buf.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            EndpointGroup::from_str(s)
+                .map_err(|_| serde::de::Error::custom(format!("Unknown endpoint group '{s}'")))
+        })
+        .collect()
+}
+
+fn serialize_endpoint_groups<S>(value: &[EndpointGroup], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    value.iter().join(",").serialize(serializer)
+}
+
+fn deserialize_rate_limits<'de, D>(deserializer: D) -> Result<Vec<RateLimitRule>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let buf = String::deserialize(deserializer)?;
+    buf.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| RateLimitRule::from_str(s).map_err(serde::de::Error::custom))
+        .collect()
+}
+
+fn serialize_rate_limits<S>(value: &[RateLimitRule], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    value.iter().join(",").serialize(serializer)
+}
+
+fn deserialize_storage_policy<'de, D>(deserializer: D) -> Result<Vec<StoragePolicyRule>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let buf = String::deserialize(deserializer)?;
+    buf.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| StoragePolicyRule::from_str(s).map_err(serde::de::Error::custom))
+        .collect()
+}
+
+fn serialize_storage_policy<S>(
+    value: &[StoragePolicyRule],
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    value.iter().join(",").serialize(serializer)
+}
+
+fn deserialize_replica_warehouses<'de, D>(
+    deserializer: D,
+) -> Result<Vec<ReplicaWarehouse>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let buf = String::deserialize(deserializer)?;
+    buf.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| ReplicaWarehouse::from_str(s).map_err(serde::de::Error::custom))
+        .collect()
+}
+
+fn serialize_replica_warehouses<S>(
+    value: &[ReplicaWarehouse],
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    value.iter().join(",").serialize(serializer)
+}
+
+fn deserialize_glue_federated_warehouses<'de, D>(
+    deserializer: D,
+) -> Result<Vec<GlueFederatedWarehouse>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let buf = String::deserialize(deserializer)?;
+    buf.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| GlueFederatedWarehouse::from_str(s).map_err(serde::de::Error::custom))
+        .collect()
+}
+
+fn serialize_glue_federated_warehouses<S>(
+    value: &[GlueFederatedWarehouse],
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    value.iter().join(",").serialize(serializer)
+}
+
+fn deserialize_hms_federated_warehouses<'de, D>(
+    deserializer: D,
+) -> Result<Vec<HmsFederatedWarehouse>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let buf = String::deserialize(deserializer)?;
+    buf.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| HmsFederatedWarehouse::from_str(s).map_err(serde::de::Error::custom))
+        .collect()
+}
+
+fn serialize_hms_federated_warehouses<S>(
+    value: &[HmsFederatedWarehouse],
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    value.iter().join(",").serialize(serializer)
+}
+
+fn deserialize_client_workarounds<'de, D>(
+    deserializer: D,
+) -> Result<Vec<ClientWorkaround>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let buf = String::deserialize(deserializer)?;
+    buf.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            ClientWorkaround::from_str(s)
+                .map_err(|_| serde::de::Error::custom(format!("Unknown client workaround '{s}'")))
+        })
+        .collect()
+}
+
+fn serialize_client_workarounds<S>(
+    value: &[ClientWorkaround],
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    value.iter().join(",").serialize(serializer)
+}
+
+fn deserialize_engines<'de, D>(deserializer: D) -> Result<Vec<Engine>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let buf = String::deserialize(deserializer)?;
+    crate::service::engine_compatibility::parse_engines(&buf).map_err(serde::de::Error::custom)
+}
+
+fn serialize_engines<S>(value: &[Engine], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    value.iter().join(",").serialize(serializer)
+}
+
 fn deserialize_origin<'de, D>(deserializer: D) -> Result<Option<Vec<HeaderValue>>, D::Error>
 where
     D: Deserializer<'de>,
@@ -431,6 +963,83 @@ pub enum SecretBackend {
     Postgres,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum SearchIndexBackend {
+    #[serde(alias = "opensearch", alias = "OPENSEARCH")]
+    OpenSearch,
+    #[serde(alias = "meilisearch", alias = "MEILISEARCH")]
+    Meilisearch,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum MetadataPushBackend {
+    #[serde(alias = "datahub", alias = "DATAHUB")]
+    DataHub,
+    #[serde(alias = "openmetadata", alias = "OPENMETADATA")]
+    OpenMetadata,
+}
+
+/// Verification of files uploaded by clients before their commit completes.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum UploadChecksumEnforcement {
+    /// Checksums sent by clients are ignored.
+    #[serde(alias = "disabled", alias = "DISABLED")]
+    Disabled,
+    /// Files are verified if clients send a checksum.
+    #[default]
+    #[serde(alias = "verify", alias = "VERIFY")]
+    Verify,
+    /// Commits adding snapshots without a checksum are rejected.
+    #[serde(alias = "required", alias = "REQUIRED")]
+    Required,
+}
+
+/// Behavior when a table is dropped that is referenced by the current version of a view.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum ViewLineageDropCheck {
+    /// Views referencing the table are not looked up.
+    #[serde(alias = "disabled", alias = "DISABLED")]
+    Disabled,
+    /// A warning listing the referencing views is logged, the table is dropped.
+    #[default]
+    #[serde(alias = "warn", alias = "WARN")]
+    Warn,
+    /// The drop is rejected unless it is forced.
+    #[serde(alias = "block", alias = "BLOCK")]
+    Block,
+}
+
+/// ID scheme for new entities. All schemes produce 128 bit IDs that are stored
+/// and exposed as UUIDs.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum IdScheme {
+    /// Time ordered UUIDs (RFC 9562). Keeps B-tree indexes compact.
+    #[default]
+    #[serde(alias = "uuid-v7", alias = "uuidv7", alias = "v7")]
+    UuidV7,
+    /// Fully random UUIDs.
+    #[serde(alias = "uuid-v4", alias = "uuidv4", alias = "v4")]
+    UuidV4,
+    /// ULIDs: 48 bit millisecond timestamp followed by 80 random bits.
+    #[serde(alias = "ulid", alias = "ULID")]
+    Ulid,
+}
+
+impl IdScheme {
+    #[must_use]
+    pub fn generate(self) -> uuid::Uuid {
+        match self {
+            IdScheme::UuidV7 => uuid::Uuid::now_v7(),
+            IdScheme::UuidV4 => uuid::Uuid::new_v4(),
+            IdScheme::Ulid => {
+                let millis = u128::try_from(chrono::Utc::now().timestamp_millis()).unwrap_or(0);
+                let random = rand::random::<u128>() & ((1 << 80) - 1);
+                uuid::Uuid::from_u128(((millis & ((1 << 48) - 1)) << 80) | random)
+            }
+        }
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize, PartialEq, Redact)]
 pub struct KV2Config {
     pub url: Url,
@@ -445,6 +1054,7 @@ impl Default for DynAppConfig {
         Self {
             base_uri: None,
             metrics_port: 9000,
+            metrics_max_warehouses: 100,
             enable_default_project: true,
             use_x_forwarded_headers: true,
             prefix_template: "{warehouse_id}".to_string(),
@@ -453,6 +1063,7 @@ impl Default for DynAppConfig {
                 "system".to_string(),
                 "examples".to_string(),
             ])),
+            disabled_endpoint_groups: vec![],
             pg_encryption_key: DEFAULT_ENCRYPTION_KEY.to_string(),
             pg_database_url_read: None,
             pg_database_url_write: None,
@@ -475,6 +1086,9 @@ impl Default for DynAppConfig {
             s3_enable_direct_system_credentials: false,
             s3_require_external_id_for_system_credentials: true,
             enable_gcp_system_credentials: false,
+            enable_hdfs_kerberos_credentials: false,
+            enable_local_filesystem_storage: false,
+            storage_policy: vec![],
             nats_address: None,
             nats_topic: None,
             nats_creds_file: None,
@@ -493,6 +1107,8 @@ impl Default for DynAppConfig {
             kubernetes_authentication_audience: None,
             kubernetes_authentication_accept_legacy_serviceaccount: false,
             openid_subject_claim: None,
+            max_impersonation_duration_seconds: chrono::Duration::minutes(30),
+            max_token_age_seconds: None,
             listen_port: 8181,
             bind_ip: IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)),
             health_check_frequency_seconds: 10,
@@ -501,7 +1117,82 @@ impl Default for DynAppConfig {
             openfga: None,
             secret_backend: SecretBackend::Postgres,
             task_poll_interval: Duration::from_secs(10),
+            disabled_task_queue_workers: vec![],
             default_tabular_expiration_delay_seconds: chrono::Duration::days(7),
+            scan_planning_sync_timeout: Duration::from_secs(10),
+            scan_planning_manifests_per_plan_task: 16,
+            scan_plan_expiration_seconds: chrono::Duration::hours(1),
+            max_tables_per_transaction: 100,
+            commit_queue_max_wait: Duration::ZERO,
+            enable_append_commit_fast_path: true,
+            upload_checksum_enforcement: UploadChecksumEnforcement::default(),
+            view_lineage_drop_check: ViewLineageDropCheck::default(),
+            enable_s3_sign_audit: false,
+            s3_sign_audit_retention_seconds: chrono::Duration::days(90),
+            enable_pii_detection: false,
+            enable_snapshot_expiration: false,
+            snapshot_expiration_interval_seconds: chrono::Duration::days(1),
+            compatibility_engines: crate::service::engine_compatibility::DEFAULT_ENGINES
+                .iter()
+                .map(|e| Engine::from_str(e).expect("Default engines are valid"))
+                .collect(),
+            client_workarounds: vec![],
+            enable_orphan_file_cleanup: false,
+            orphan_file_cleanup_interval_seconds: chrono::Duration::days(7),
+            enable_catalog_snapshot_export: false,
+            catalog_snapshot_export_interval_seconds: chrono::Duration::days(1),
+            compaction_min_input_files: 5,
+            compaction_delete_file_threshold: 10,
+            id_scheme: IdScheme::default(),
+            search_index_backend: None,
+            search_index_url: None,
+            search_index_name: "lakekeeper-catalog".to_string(),
+            search_index_user: None,
+            search_index_password: None,
+            search_index_api_key: None,
+            search_index_full_sync_interval_seconds: chrono::Duration::days(1),
+            metadata_push_backend: None,
+            metadata_push_url: None,
+            metadata_push_token: None,
+            metadata_push_datahub_env: "PROD".to_string(),
+            metadata_push_openmetadata_service: "lakekeeper".to_string(),
+            metadata_push_max_attempts: 5,
+            announcement_headers: false,
+            response_property_rules_file: None,
+            otlp_traces_endpoint: None,
+            otlp_service_name: "lakekeeper".to_string(),
+            request_log_enabled: false,
+            request_log_include_request_body: false,
+            request_log_redact_credentials: true,
+            request_log_redacted_properties: [
+                "s3.secret-access-key",
+                "s3.session-token",
+                "adls.sas-token.*",
+                "adls.auth.shared-key.account.key",
+                "gcs.oauth2.token",
+            ]
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+            publish_server_events: false,
+            server_event_retention_seconds: chrono::Duration::days(90),
+            redis_url: None,
+            shared_cache_ttl_seconds: 30,
+            metadata_cache_max_size_bytes: 256 * 1024 * 1024,
+            metadata_cache_ttl_seconds: 3600,
+            rate_limits: vec![],
+            replica_primary_url: None,
+            replica_primary_token: None,
+            replica_primary_token_file: None,
+            replica_warehouses: vec![],
+            replica_sync_interval_seconds: 10,
+            replica_owner: None,
+            glue_federated_warehouses: vec![],
+            glue_federation_sync_interval_seconds: 60,
+            glue_federation_owner: None,
+            hms_federated_warehouses: vec![],
+            hms_federation_sync_interval_seconds: 60,
+            hms_federation_owner: None,
             endpoint_stat_flush_interval: Duration::from_secs(30),
             server_id: uuid::Uuid::nil(),
             serve_swagger_ui: true,
@@ -522,6 +1213,37 @@ impl DynAppConfig {
     pub fn authn_enabled(&self) -> bool {
         self.openid_provider_uri.is_some()
     }
+
+    /// Whether this instance is a read replica of another Lakekeeper.
+    pub fn is_read_replica(&self) -> bool {
+        self.replica_primary_url.is_some()
+    }
+
+    /// Whether the warehouse mirrors an AWS Glue Data Catalog.
+    pub fn is_glue_federated_warehouse(&self, warehouse_id: WarehouseId) -> bool {
+        self.glue_federated_warehouses
+            .iter()
+            .any(|w| w.warehouse_id == warehouse_id)
+    }
+
+    /// Whether the warehouse mirrors a Hive Metastore.
+    pub fn is_hms_federated_warehouse(&self, warehouse_id: WarehouseId) -> bool {
+        self.hms_federated_warehouses
+            .iter()
+            .any(|w| w.warehouse_id == warehouse_id)
+    }
+
+    /// Name of the external catalog a federated warehouse mirrors.
+    /// `None` if the warehouse is not federated.
+    pub fn federation_source(&self, warehouse_id: WarehouseId) -> Option<&'static str> {
+        if self.is_glue_federated_warehouse(warehouse_id) {
+            Some("AWS Glue")
+        } else if self.is_hms_federated_warehouse(warehouse_id) {
+            Some("the Hive Metastore")
+        } else {
+            None
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, Serialize, PartialEq)]
@@ -873,6 +1595,271 @@ mod test {
         });
     }
 
+    #[test]
+    fn test_id_scheme() {
+        figment::Jail::expect_with(|jail| {
+            assert_eq!(get_config().id_scheme, IdScheme::UuidV7);
+            jail.set_env("LAKEKEEPER_TEST__ID_SCHEME", "ulid");
+            assert_eq!(get_config().id_scheme, IdScheme::Ulid);
+            jail.set_env("LAKEKEEPER_TEST__ID_SCHEME", "uuid-v4");
+            assert_eq!(get_config().id_scheme, IdScheme::UuidV4);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_upload_checksum_enforcement() {
+        figment::Jail::expect_with(|jail| {
+            assert_eq!(
+                get_config().upload_checksum_enforcement,
+                UploadChecksumEnforcement::Verify
+            );
+            jail.set_env("LAKEKEEPER_TEST__UPLOAD_CHECKSUM_ENFORCEMENT", "required");
+            assert_eq!(
+                get_config().upload_checksum_enforcement,
+                UploadChecksumEnforcement::Required
+            );
+            jail.set_env("LAKEKEEPER_TEST__UPLOAD_CHECKSUM_ENFORCEMENT", "disabled");
+            assert_eq!(
+                get_config().upload_checksum_enforcement,
+                UploadChecksumEnforcement::Disabled
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_view_lineage_drop_check() {
+        figment::Jail::expect_with(|jail| {
+            assert_eq!(
+                get_config().view_lineage_drop_check,
+                ViewLineageDropCheck::Warn
+            );
+            jail.set_env("LAKEKEEPER_TEST__VIEW_LINEAGE_DROP_CHECK", "block");
+            assert_eq!(
+                get_config().view_lineage_drop_check,
+                ViewLineageDropCheck::Block
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_id_scheme_generate() {
+        assert_eq!(IdScheme::UuidV7.generate().get_version_num(), 7);
+        assert_eq!(IdScheme::UuidV4.generate().get_version_num(), 4);
+
+        let before = u128::try_from(chrono::Utc::now().timestamp_millis()).unwrap();
+        let ulid = IdScheme::Ulid.generate().as_u128();
+        let after = u128::try_from(chrono::Utc::now().timestamp_millis()).unwrap();
+        assert!((before..=after).contains(&(ulid >> 80)));
+    }
+
+    #[test]
+    fn test_disabled_task_queue_workers() {
+        figment::Jail::expect_with(|jail| {
+            let config = get_config();
+            assert!(config.disabled_task_queue_workers.is_empty());
+            jail.set_env(
+                "LAKEKEEPER_TEST__DISABLED_TASK_QUEUE_WORKERS",
+                "tabular_purge, orphan_file_cleanup",
+            );
+            let config = get_config();
+            assert_eq!(
+                config.disabled_task_queue_workers,
+                vec!["tabular_purge", "orphan_file_cleanup"]
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_disabled_endpoint_groups() {
+        figment::Jail::expect_with(|jail| {
+            let config = get_config();
+            assert!(config.disabled_endpoint_groups.is_empty());
+            jail.set_env(
+                "LAKEKEEPER_TEST__DISABLED_ENDPOINT_GROUPS",
+                "register-table, purge-drops",
+            );
+            let config = get_config();
+            assert_eq!(
+                config.disabled_endpoint_groups,
+                vec![EndpointGroup::RegisterTable, EndpointGroup::PurgeDrops]
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_rate_limits() {
+        figment::Jail::expect_with(|jail| {
+            let config = get_config();
+            assert!(config.rate_limits.is_empty());
+            jail.set_env(
+                "LAKEKEEPER_TEST__RATE_LIMITS",
+                "principal=100/s, warehouse.commit=600/m",
+            );
+            let config = get_config();
+            assert_eq!(
+                config
+                    .rate_limits
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>(),
+                vec!["principal=100/s", "warehouse.commit=600/m"]
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_storage_policy() {
+        figment::Jail::expect_with(|jail| {
+            let config = get_config();
+            assert!(config.storage_policy.is_empty());
+            jail.set_env(
+                "LAKEKEEPER_TEST__STORAGE_POLICY",
+                "deny-credential-type:s3/access-key, require-s3-remote-signing",
+            );
+            let config = get_config();
+            assert_eq!(
+                config
+                    .storage_policy
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>(),
+                vec![
+                    "deny-credential-type:s3/access-key",
+                    "require-s3-remote-signing"
+                ]
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_replica_warehouses() {
+        figment::Jail::expect_with(|jail| {
+            let config = get_config();
+            assert!(!config.is_read_replica());
+            assert!(config.replica_warehouses.is_empty());
+            jail.set_env(
+                "LAKEKEEPER_TEST__REPLICA_PRIMARY_URL",
+                "https://lakekeeper.eu.example.com",
+            );
+            jail.set_env(
+                "LAKEKEEPER_TEST__REPLICA_WAREHOUSES",
+                "0197a3b5-0000-7000-8000-000000000001=0197a3b5-0000-7000-8000-000000000002",
+            );
+            let config = get_config();
+            assert!(config.is_read_replica());
+            assert_eq!(
+                config.replica_warehouses,
+                vec![ReplicaWarehouse {
+                    replica: WarehouseId::from_str("0197a3b5-0000-7000-8000-000000000001").unwrap(),
+                    primary: WarehouseId::from_str("0197a3b5-0000-7000-8000-000000000002").unwrap(),
+                }]
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_glue_federated_warehouses() {
+        figment::Jail::expect_with(|jail| {
+            let config = get_config();
+            assert!(config.glue_federated_warehouses.is_empty());
+            jail.set_env(
+                "LAKEKEEPER_TEST__GLUE_FEDERATED_WAREHOUSES",
+                "0197a3b5-0000-7000-8000-000000000001=eu-central-1, 0197a3b5-0000-7000-8000-000000000002=us-east-1:123456789012",
+            );
+            let config = get_config();
+            assert_eq!(config.glue_federated_warehouses.len(), 2);
+            assert_eq!(
+                config.glue_federated_warehouses[1].catalog_id.as_deref(),
+                Some("123456789012")
+            );
+            assert!(config.is_glue_federated_warehouse(
+                WarehouseId::from_str("0197a3b5-0000-7000-8000-000000000001").unwrap()
+            ));
+            assert!(!config.is_glue_federated_warehouse(
+                WarehouseId::from_str("0197a3b5-0000-7000-8000-000000000003").unwrap()
+            ));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_hms_federated_warehouses() {
+        figment::Jail::expect_with(|jail| {
+            let config = get_config();
+            assert!(config.hms_federated_warehouses.is_empty());
+            jail.set_env(
+                "LAKEKEEPER_TEST__HMS_FEDERATED_WAREHOUSES",
+                "0197a3b5-0000-7000-8000-000000000001=thrift://metastore:9083",
+            );
+            let config = get_config();
+            assert_eq!(config.hms_federated_warehouses.len(), 1);
+            assert_eq!(config.hms_federated_warehouses[0].host, "metastore");
+            let warehouse_id =
+                WarehouseId::from_str("0197a3b5-0000-7000-8000-000000000001").unwrap();
+            assert!(config.is_hms_federated_warehouse(warehouse_id));
+            assert_eq!(
+                config.federation_source(warehouse_id),
+                Some("the Hive Metastore")
+            );
+            assert_eq!(
+                config.federation_source(
+                    WarehouseId::from_str("0197a3b5-0000-7000-8000-000000000003").unwrap()
+                ),
+                None
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_client_workarounds() {
+        figment::Jail::expect_with(|jail| {
+            let config = get_config();
+            assert!(config.client_workarounds.is_empty());
+            jail.set_env(
+                "LAKEKEEPER_TEST__CLIENT_WORKAROUNDS",
+                "omit-empty-next-page-token,legacy-oauth-tokens-endpoint",
+            );
+            let config = get_config();
+            assert_eq!(
+                config.client_workarounds,
+                vec![
+                    ClientWorkaround::OmitEmptyNextPageToken,
+                    ClientWorkaround::LegacyOauthTokensEndpoint
+                ]
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_request_log_redacted_properties() {
+        figment::Jail::expect_with(|jail| {
+            let config = get_config();
+            assert!(config
+                .request_log_redacted_properties
+                .contains(&"s3.secret-access-key".to_string()));
+            jail.set_env(
+                "LAKEKEEPER_TEST__REQUEST_LOG_REDACTED_PROPERTIES",
+                "my.secret, my-company.*",
+            );
+            let config = get_config();
+            assert_eq!(
+                config.request_log_redacted_properties,
+                vec!["my.secret".to_string(), "my-company.*".to_string()]
+            );
+            Ok(())
+        });
+    }
+
     #[test]
     fn reserved_namespaces_should_contains_default_values() {
         assert!(CONFIG.reserved_namespaces.contains("system"));
@@ -902,6 +1889,21 @@ mod test {
         });
     }
 
+    #[test]
+    fn test_commit_queue_max_wait() {
+        figment::Jail::expect_with(|jail| {
+            let config = get_config();
+            assert!(config.commit_queue_max_wait.is_zero());
+            jail.set_env("LAKEKEEPER_TEST__COMMIT_QUEUE_MAX_WAIT", "500ms");
+            let config = get_config();
+            assert_eq!(
+                config.commit_queue_max_wait,
+                std::time::Duration::from_millis(500)
+            );
+            Ok(())
+        });
+    }
+
     #[test]
     fn test_task_queue_config_legacy_seconds() {
         figment::Jail::expect_with(|jail| {
@@ -1091,6 +2093,49 @@ mod test {
         });
     }
 
+    #[test]
+    fn test_search_index_config() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env("LAKEKEEPER_TEST__SEARCH_INDEX_BACKEND", "meilisearch");
+            jail.set_env("LAKEKEEPER_TEST__SEARCH_INDEX_URL", "http://localhost:7700");
+            jail.set_env(
+                "LAKEKEEPER_TEST__SEARCH_INDEX_FULL_SYNC_INTERVAL_SECONDS",
+                "0",
+            );
+            let config = get_config();
+            assert_eq!(
+                config.search_index_backend,
+                Some(SearchIndexBackend::Meilisearch)
+            );
+            assert_eq!(config.search_index_name, "lakekeeper-catalog");
+            assert_eq!(
+                config.search_index_full_sync_interval_seconds,
+                chrono::Duration::zero()
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_metadata_push_config() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env("LAKEKEEPER_TEST__METADATA_PUSH_BACKEND", "openmetadata");
+            jail.set_env(
+                "LAKEKEEPER_TEST__METADATA_PUSH_URL",
+                "http://localhost:8585/api/",
+            );
+            jail.set_env("LAKEKEEPER_TEST__METADATA_PUSH_MAX_ATTEMPTS", "3");
+            let config = get_config();
+            assert_eq!(
+                config.metadata_push_backend,
+                Some(MetadataPushBackend::OpenMetadata)
+            );
+            assert_eq!(config.metadata_push_max_attempts, 3);
+            assert_eq!(config.metadata_push_openmetadata_service, "lakekeeper");
+            Ok(())
+        });
+    }
+
     #[test]
     fn test_use_x_forwarded_headers() {
         figment::Jail::expect_with(|jail| {
@@ -274,6 +274,7 @@ mod tests {
                 aws_access_key_id: "my access key".to_string(),
                 aws_secret_access_key: "my secret key".to_string(),
                 external_id: None,
+                sse_customer_key: None,
             })
             .into();
 
@@ -297,6 +298,7 @@ mod tests {
                 aws_access_key_id: "my access key".to_string(),
                 aws_secret_access_key: "my secret key".to_string(),
                 external_id: None,
+                sse_customer_key: None,
             })
             .into();
 
@@ -0,0 +1,416 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::{
+    api::{
+        iceberg::v1::{PaginationQuery, MAX_PAGE_SIZE},
+        management::v1::announcement::{
+            Announcement, AnnouncementSeverity, CreateAnnouncementRequest,
+            ListAnnouncementsResponse, UpdateAnnouncementRequest,
+        },
+    },
+    implementations::postgres::{
+        dbutils::DBErrorHandler,
+        pagination::{PaginateToken, V2PaginateToken},
+    },
+    service::Result,
+};
+
+#[derive(sqlx::FromRow, Debug)]
+struct AnnouncementRow {
+    announcement_id: Uuid,
+    title: String,
+    message: Option<String>,
+    severity: AnnouncementSeverity,
+    starts_at: DateTime<Utc>,
+    ends_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+    updated_at: Option<DateTime<Utc>>,
+}
+
+impl From<AnnouncementRow> for Announcement {
+    fn from(
+        AnnouncementRow {
+            announcement_id,
+            title,
+            message,
+            severity,
+            starts_at,
+            ends_at,
+            created_at,
+            updated_at,
+        }: AnnouncementRow,
+    ) -> Self {
+        Self {
+            announcement_id,
+            title,
+            message,
+            severity,
+            starts_at,
+            ends_at,
+            created_at,
+            updated_at,
+        }
+    }
+}
+
+pub(crate) async fn create_announcement<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    announcement_id: Uuid,
+    CreateAnnouncementRequest {
+        title,
+        message,
+        severity,
+        starts_at,
+        ends_at,
+    }: CreateAnnouncementRequest,
+    connection: E,
+) -> Result<Announcement> {
+    let announcement = sqlx::query_as!(
+        AnnouncementRow,
+        r#"
+        INSERT INTO announcement (announcement_id, title, message, severity, starts_at, ends_at)
+        VALUES ($1, $2, $3, $4, COALESCE($5, now()), $6)
+        RETURNING announcement_id, title, message, severity as "severity: AnnouncementSeverity",
+            starts_at, ends_at, created_at, updated_at
+        "#,
+        announcement_id,
+        title,
+        message,
+        severity as _,
+        starts_at,
+        ends_at,
+    )
+    .fetch_one(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error creating announcement"))?;
+
+    Ok(announcement.into())
+}
+
+pub(crate) async fn get_announcement<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    announcement_id: Uuid,
+    connection: E,
+) -> Result<Option<Announcement>> {
+    let announcement = sqlx::query_as!(
+        AnnouncementRow,
+        r#"
+        SELECT announcement_id, title, message, severity as "severity: AnnouncementSeverity",
+            starts_at, ends_at, created_at, updated_at
+        FROM announcement
+        WHERE announcement_id = $1
+        "#,
+        announcement_id,
+    )
+    .fetch_optional(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching announcement"))?;
+
+    Ok(announcement.map(Into::into))
+}
+
+pub(crate) async fn update_announcement<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    announcement_id: Uuid,
+    UpdateAnnouncementRequest {
+        title,
+        message,
+        severity,
+        starts_at,
+        ends_at,
+    }: UpdateAnnouncementRequest,
+    connection: E,
+) -> Result<Option<Announcement>> {
+    let announcement = sqlx::query_as!(
+        AnnouncementRow,
+        r#"
+        UPDATE announcement
+        SET title = $2, message = $3, severity = $4, starts_at = COALESCE($5, starts_at), ends_at = $6
+        WHERE announcement_id = $1
+        RETURNING announcement_id, title, message, severity as "severity: AnnouncementSeverity",
+            starts_at, ends_at, created_at, updated_at
+        "#,
+        announcement_id,
+        title,
+        message,
+        severity as _,
+        starts_at,
+        ends_at,
+    )
+    .fetch_optional(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error updating announcement"))?;
+
+    Ok(announcement.map(Into::into))
+}
+
+pub(crate) async fn delete_announcement<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    announcement_id: Uuid,
+    connection: E,
+) -> Result<Option<()>> {
+    let deleted = sqlx::query!(
+        "DELETE FROM announcement WHERE announcement_id = $1",
+        announcement_id
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error deleting announcement"))?;
+
+    Ok((deleted.rows_affected() > 0).then_some(()))
+}
+
+pub(crate) async fn list_announcements<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    PaginationQuery {
+        page_size,
+        page_token,
+    }: PaginationQuery,
+    connection: E,
+) -> Result<ListAnnouncementsResponse> {
+    let page_size = page_size.map_or(MAX_PAGE_SIZE, |i| i.clamp(1, MAX_PAGE_SIZE));
+
+    let token_id: Option<Uuid> = page_token
+        .as_option()
+        .map(PaginateToken::try_from)
+        .transpose()?
+        .map(PaginateToken::into_id)
+        .transpose()?;
+
+    let announcements: Vec<Announcement> = sqlx::query_as!(
+        AnnouncementRow,
+        r#"
+        SELECT announcement_id, title, message, severity as "severity: AnnouncementSeverity",
+            starts_at, ends_at, created_at, updated_at
+        FROM announcement
+        WHERE announcement_id > $1 OR $1 IS NULL
+        ORDER BY announcement_id ASC
+        LIMIT $2
+        "#,
+        token_id,
+        page_size,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching announcements"))?
+    .into_iter()
+    .map(Announcement::from)
+    .collect();
+
+    let next_page_token = announcements.last().map(|a| {
+        PaginateToken::V2(V2PaginateToken::<Uuid> {
+            id: a.announcement_id,
+        })
+        .to_string()
+    });
+
+    Ok(ListAnnouncementsResponse {
+        announcements,
+        next_page_token,
+    })
+}
+
+pub(crate) async fn list_active_announcements<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    connection: E,
+) -> Result<Vec<Announcement>> {
+    let announcements = sqlx::query_as!(
+        AnnouncementRow,
+        r#"
+        SELECT announcement_id, title, message, severity as "severity: AnnouncementSeverity",
+            starts_at, ends_at, created_at, updated_at
+        FROM announcement
+        WHERE starts_at <= now() AND (ends_at IS NULL OR ends_at > now())
+        ORDER BY severity DESC, starts_at DESC
+        "#,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching active announcements"))?
+    .into_iter()
+    .map(Announcement::from)
+    .collect();
+
+    Ok(announcements)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::api::iceberg::v1::PageToken;
+
+    fn request(title: &str) -> CreateAnnouncementRequest {
+        CreateAnnouncementRequest {
+            title: title.to_string(),
+            message: None,
+            severity: AnnouncementSeverity::Info,
+            starts_at: None,
+            ends_at: None,
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_active_announcements(pool: sqlx::PgPool) {
+        let now = Utc::now();
+        let active = create_announcement(
+            Uuid::now_v7(),
+            CreateAnnouncementRequest {
+                message: Some("The catalog is read-only during the upgrade".to_string()),
+                ends_at: Some(now + chrono::Duration::hours(1)),
+                ..request("Upgrade")
+            },
+            &pool,
+        )
+        .await
+        .unwrap();
+        let critical = create_announcement(
+            Uuid::now_v7(),
+            CreateAnnouncementRequest {
+                severity: AnnouncementSeverity::Critical,
+                ..request("Outage")
+            },
+            &pool,
+        )
+        .await
+        .unwrap();
+        // Upcoming
+        create_announcement(
+            Uuid::now_v7(),
+            CreateAnnouncementRequest {
+                starts_at: Some(now + chrono::Duration::days(1)),
+                ..request("Maintenance")
+            },
+            &pool,
+        )
+        .await
+        .unwrap();
+        // Past
+        create_announcement(
+            Uuid::now_v7(),
+            CreateAnnouncementRequest {
+                starts_at: Some(now - chrono::Duration::days(2)),
+                ends_at: Some(now - chrono::Duration::days(1)),
+                ..request("Migration")
+            },
+            &pool,
+        )
+        .await
+        .unwrap();
+
+        let announcements = list_active_announcements(&pool).await.unwrap();
+        assert_eq!(announcements, vec![critical, active]);
+    }
+
+    #[sqlx::test]
+    async fn test_update_and_delete_announcement(pool: sqlx::PgPool) {
+        let created = create_announcement(Uuid::now_v7(), request("Upgrade"), &pool)
+            .await
+            .unwrap();
+
+        let updated = update_announcement(
+            created.announcement_id,
+            UpdateAnnouncementRequest {
+                title: "Upgrade postponed".to_string(),
+                message: None,
+                severity: AnnouncementSeverity::Warning,
+                starts_at: None,
+                ends_at: Some(created.starts_at + chrono::Duration::hours(2)),
+            },
+            &pool,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(updated.title, "Upgrade postponed");
+        assert_eq!(updated.severity, AnnouncementSeverity::Warning);
+        assert_eq!(updated.starts_at, created.starts_at);
+        assert!(updated.updated_at.is_some());
+
+        assert_eq!(
+            get_announcement(created.announcement_id, &pool)
+                .await
+                .unwrap(),
+            Some(updated)
+        );
+
+        delete_announcement(created.announcement_id, &pool)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(delete_announcement(created.announcement_id, &pool)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(get_announcement(created.announcement_id, &pool)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[sqlx::test]
+    async fn test_list_announcements_paginates(pool: sqlx::PgPool) {
+        let mut ids = vec![];
+        for i in 0..3 {
+            let announcement =
+                create_announcement(Uuid::now_v7(), request(&format!("Notice {i}")), &pool)
+                    .await
+                    .unwrap();
+            ids.push(announcement.announcement_id);
+        }
+
+        let first = list_announcements(
+            PaginationQuery {
+                page_token: PageToken::NotSpecified,
+                page_size: Some(2),
+            },
+            &pool,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            first
+                .announcements
+                .iter()
+                .map(|a| a.announcement_id)
+                .collect::<Vec<_>>(),
+            ids[..2]
+        );
+
+        let second = list_announcements(
+            PaginationQuery {
+                page_token: PageToken::Present(first.next_page_token.unwrap()),
+                page_size: Some(2),
+            },
+            &pool,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            second
+                .announcements
+                .iter()
+                .map(|a| a.announcement_id)
+                .collect::<Vec<_>>(),
+            ids[2..]
+        );
+    }
+}
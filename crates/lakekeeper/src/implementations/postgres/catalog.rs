@@ -3,27 +3,55 @@ use std::collections::{HashMap, HashSet};
 use chrono::Duration;
 use iceberg::spec::ViewMetadata;
 use iceberg_ext::{
-    catalog::rest::{CatalogConfig, ErrorModel},
+    catalog::rest::{CatalogConfig, ErrorModel, ScanTasks, TableStats},
     configs::Location,
 };
 use itertools::Itertools;
 
 use super::{
+    announcement::{
+        create_announcement, delete_announcement, get_announcement, list_active_announcements,
+        list_announcements, update_announcement,
+    },
     bootstrap::{bootstrap, get_validation_data},
+    invitation::{
+        create_project_invitation, list_project_invitations, lock_project_invitation,
+        mark_project_invitation_redeemed, revoke_project_invitation,
+    },
+    label::{
+        get_effective_table_labels, get_label_settings, get_labels, set_label_settings, set_labels,
+    },
     namespace::{
-        create_namespace, drop_namespace, get_namespace, list_namespaces, namespace_to_id,
+        clear_namespace_deleted_at, create_namespace, drop_emptied_namespaces, drop_namespace,
+        get_namespace, list_namespaces, mark_namespace_as_deleted, namespace_to_id,
         update_namespace_properties,
     },
+    recertification::{
+        close_recertification_campaign, create_recertification_campaign,
+        decide_recertification_items, get_recertification_campaign, list_recertification_campaigns,
+        list_recertification_items,
+    },
+    referenced_files::list_referenced_files,
+    request_log::{get_request_log_settings, set_request_log_settings},
     role::{create_role, delete_role, list_roles, update_role},
     tabular::table::{
         drop_table, get_table_metadata_by_id, get_table_metadata_by_s3_location, list_tables,
         load_tables, rename_table, resolve_table_ident, table_idents_to_ids,
     },
     warehouse::{
-        create_project, create_warehouse, delete_project, delete_warehouse,
-        get_config_for_warehouse, get_project, get_warehouse, get_warehouse_by_name, list_projects,
-        list_warehouses, rename_project, rename_warehouse, set_warehouse_deletion_profile,
-        set_warehouse_status, update_storage_profile,
+        clear_project_quota_warning, create_project, create_warehouse, delete_project,
+        delete_warehouse, delete_warehouse_slugs, get_config_for_warehouse, get_project,
+        get_project_quota_usage, get_project_quotas, get_property_schemas, get_warehouse,
+        get_warehouse_by_name, get_warehouse_client_workarounds,
+        get_warehouse_disabled_endpoint_groups, get_warehouse_entity_counts,
+        get_warehouse_event_routing_rules, list_project_quota_warnings, list_projects,
+        list_projects_with_quota_warnings, list_warehouse_slugs, list_warehouses,
+        raise_project_quota_warning, rename_project, rename_warehouse, resolve_warehouse_slug,
+        set_project_quotas, set_property_schemas, set_warehouse_client_workarounds,
+        set_warehouse_default_branch, set_warehouse_deletion_profile,
+        set_warehouse_disabled_endpoint_groups, set_warehouse_event_routing_rules,
+        set_warehouse_metadata_push, set_warehouse_slug, set_warehouse_status,
+        update_storage_profile,
     },
     CatalogState, PostgresTransaction,
 };
@@ -31,44 +59,137 @@ use crate::{
     api::{
         iceberg::v1::{namespace::NamespaceDropFlags, PaginatedMapping, PaginationQuery},
         management::v1::{
+            announcement::{
+                Announcement, CreateAnnouncementRequest, ListAnnouncementsResponse,
+                UpdateAnnouncementRequest,
+            },
+            invitation::{CreateInvitationRequest, ListInvitationsResponse, ProjectInvitation},
             project::{EndpointStatisticsResponse, TimeWindowSelector, WarehouseFilter},
+            recertification::{
+                CreateRecertificationCampaignRequest, ListRecertificationCampaignsResponse,
+                RecertificationCampaign, RecertificationItem, RecertificationItemDecision,
+            },
+            referenced_files::ListReferencedFilesResponse,
             role::{ListRolesResponse, Role, SearchRoleResponse},
-            user::{ListUsersResponse, SearchUserResponse, UserLastUpdatedWith, UserType},
+            search::{CatalogSearchFilter, GlobalSearchMatch, SearchCatalogResult},
+            server_event::ListServerEventsResponse,
+            table::{
+                ColumnTagSuggestion, ColumnTagSuggestionKey, ColumnTagSuggestionStatus,
+                CreateTablePropertyProposalRequest, GetTableTimelineResponse,
+                ListTableSigningsResponse, PreparedTableWriterCommit, TablePropertyProposal,
+                TablePropertyProposalStatus, TableSigning, TableWriter, TableWriterCheckpoint,
+                TableWriterState, TaggedColumn,
+            },
+            task::{DeadLetterTask, ListDeadLetterTasksResponse},
+            task_schedule::ListTaskSchedulesResponse,
+            user::{
+                Impersonation, ListUsersResponse, MergeUserResponse, SearchUserResponse,
+                UserAttributes, UserLastUpdatedWith, UserReferenceReport, UserType,
+            },
             warehouse::{
                 GetTaskQueueConfigResponse, SetTaskQueueConfigRequest, TabularDeleteProfile,
                 WarehouseStatisticsResponse,
             },
-            DeleteWarehouseQuery, ProtectionResponse,
+            DeleteWarehouseQuery, ListSort, ProtectionResponse,
         },
     },
     implementations::postgres::{
+        catalog_import::{
+            create_catalog_import, get_catalog_import, list_catalog_import_tables,
+            record_catalog_import_table, set_catalog_import_status,
+        },
+        catalog_snapshot::{
+            create_catalog_snapshot, delete_expired_catalog_snapshots, get_catalog_snapshot,
+            list_catalog_snapshots, load_catalog_snapshot_state,
+        },
         endpoint_statistics::list::list_statistics,
         namespace::{get_namespace_protected, set_namespace_protected},
         role::search_role,
+        s3_sign_audit::{delete_table_signings, list_table_signings, record_table_signing},
+        search::{global_search, search_catalog},
+        server_event::{delete_server_events, list_server_events, record_server_event},
+        storage_intent::{
+            claim_stale_storage_intents, complete_storage_intents, record_storage_intents,
+        },
         tabular::{
-            clear_tabular_deleted_at, get_tabular_protected, list_tabulars,
+            clear_tabular_deleted_at, get_tabular_protected, get_tabular_version, list_tabulars,
             mark_tabular_as_deleted, set_tabular_protected,
-            table::{commit_table_transaction, create_table, load_storage_profile},
-            view::{create_view, drop_view, list_views, load_view, rename_view, view_ident_to_id},
+            table::{
+                begin_table_writer, cancel_scan_plan, commit_table_transaction,
+                complete_table_writer_commit, create_scan_plan, create_table,
+                create_table_property_proposal, get_column_tags, get_scan_plan, get_table_lineage,
+                get_table_stats, get_table_timeline, get_table_writer, list_column_tag_suggestions,
+                list_table_property_proposals, list_tagged_columns, load_storage_profile,
+                prepare_table_writer_commit, record_table_activity, record_table_lineage,
+                replace_column_tag_suggestions, resolve_table_locations,
+                review_column_tag_suggestions, review_table_property_proposal, set_column_tags,
+                set_scan_plan_result,
+            },
+            view::{
+                create_view, drop_view, get_view_lineage, list_views, list_views_referencing_table,
+                load_view, rename_view, view_id_to_ident, view_ident_to_id,
+            },
+        },
+        task_dead_letter::{
+            get_dead_letter_task, list_dead_letter_tasks, list_task_attempts,
+            requeue_dead_letter_task,
         },
         task_queues::{
-            cancel_tasks, check_task, get_task_queue_config, queue_task_batch,
-            set_task_queue_config, stop_task,
+            cancel_tasks, check_task, get_entity_task, get_task_queue_config, get_task_queue_stats,
+            is_task_lease_valid, lease_task, list_entity_task_log, queue_or_reschedule_task,
+            queue_task_batch, set_task_queue_config, stop_task,
+        },
+        task_schedules::{
+            claim_due_task_schedules, create_task_schedule, delete_task_schedule,
+            get_task_schedule, list_task_schedules, record_task_schedule_fire,
+            set_task_schedule_paused,
+        },
+        usage::{aggregate_warehouse_usage, list_warehouse_usage},
+        user::{
+            create_impersonation, create_or_update_user, delete_user, end_impersonation,
+            get_impersonation, hard_delete_user, is_user_active, list_users, merge_user,
+            record_user_seen, search_user, set_user_active, set_user_attributes,
+        },
+        warehouse::{
+            claim_due_retired_storage_secrets, delete_retired_storage_secrets, get_warehouse_stats,
+            retire_storage_secret, set_warehouse_protection,
         },
-        user::{create_or_update_user, delete_user, list_users, search_user},
-        warehouse::{get_warehouse_stats, set_warehouse_protection},
     },
     request_metadata::RequestMetadata,
     service::{
         authn::UserId,
+        client_compatibility::ClientWorkaround,
+        endpoint_policy::EndpointGroup,
+        event_publisher::routing::EventRoutingRule,
+        labels::{LabelEntity, LabelSettings, Labels},
+        quotas::{ProjectQuotaKind, ProjectQuotaUsage, ProjectQuotaWarning, ProjectQuotas},
+        request_log::RequestLogSettings,
+        server_events::{ServerEvent, ServerEventType},
         storage::StorageProfile,
-        task_queue::{Task, TaskCheckState, TaskFilter, TaskId, TaskInput},
+        storage_intent::StorageIntent,
+        table_lineage::{LineageEdge, LineageTable, TableLineage},
+        table_timeline::TableActivity,
+        task_queue::{
+            catalog_import_queue::{
+                CatalogImport, CatalogImportSource, CatalogImportStatus, CatalogImportTable,
+            },
+            catalog_snapshot_export_queue::{CatalogSnapshot, CatalogSnapshotState},
+            pii_detection_queue::PiiSuggestion,
+            schedule::{TaskSchedule, TaskScheduleInput},
+            EntityId, Task, TaskCheckState, TaskFilter, TaskId, TaskInfo, TaskInput, TaskLogEntry,
+            TaskQueueStats,
+        },
+        usage::WarehouseUsageDay,
+        view_lineage::{ReferencingView, ViewLineageSource},
+        warehouse_metrics::WarehouseEntityCounts,
+        warehouse_slug::{ResolvedWarehouseSlug, WarehouseSlug},
         Catalog, CreateNamespaceRequest, CreateNamespaceResponse, CreateOrUpdateUserResponse,
         CreateTableResponse, GetNamespaceResponse, GetProjectResponse, GetTableMetadataResponse,
         GetWarehouseResponse, ListFlags, ListNamespacesQuery, LoadTableResponse, NamespaceDropInfo,
-        NamespaceId, NamespaceIdent, NamespaceInfo, ProjectId, Result, RoleId, ServerInfo,
-        TableCommit, TableCreation, TableId, TableIdent, TableInfo, TabularId, TabularInfo,
-        Transaction, UndropTabularResponse, ViewCommit, ViewId, WarehouseId, WarehouseStatus,
+        NamespaceId, NamespaceIdent, NamespaceInfo, ProjectId, Result, RoleId, ScanPlan,
+        ScanPlanId, ServerInfo, TableCommit, TableCreation, TableId, TableIdent, TableInfo,
+        TabularId, TabularInfo, TabularVersion, Transaction, UndropTabularResponse, ViewCommit,
+        ViewId, WarehouseId, WarehouseStatus,
     },
     SecretIdent,
 };
@@ -78,6 +199,7 @@ impl Catalog for super::PostgresCatalog {
     type Transaction = PostgresTransaction;
     type State = CatalogState;
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn get_server_info(
         catalog_state: Self::State,
     ) -> std::result::Result<ServerInfo, ErrorModel> {
@@ -85,6 +207,7 @@ impl Catalog for super::PostgresCatalog {
     }
 
     // ---------------- Bootstrap ----------------
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn bootstrap<'a>(
         terms_accepted: bool,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
@@ -92,6 +215,7 @@ impl Catalog for super::PostgresCatalog {
         bootstrap(terms_accepted, &mut **transaction).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn get_warehouse_by_name(
         warehouse_name: &str,
         project_id: &ProjectId,
@@ -100,6 +224,7 @@ impl Catalog for super::PostgresCatalog {
         get_warehouse_by_name(warehouse_name, project_id, catalog_state).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn get_config_for_warehouse(
         warehouse_id: WarehouseId,
         catalog_state: CatalogState,
@@ -108,6 +233,38 @@ impl Catalog for super::PostgresCatalog {
         get_config_for_warehouse(warehouse_id, catalog_state, request_metadata).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_warehouse_disabled_endpoint_groups(
+        warehouse_id: WarehouseId,
+        catalog_state: CatalogState,
+    ) -> Result<Option<Vec<EndpointGroup>>> {
+        get_warehouse_disabled_endpoint_groups(warehouse_id, catalog_state).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_warehouse_client_workarounds(
+        warehouse_id: WarehouseId,
+        catalog_state: CatalogState,
+    ) -> Result<Option<Vec<ClientWorkaround>>> {
+        get_warehouse_client_workarounds(warehouse_id, catalog_state).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_warehouse_entity_counts(
+        catalog_state: CatalogState,
+    ) -> Result<Vec<WarehouseEntityCounts>> {
+        get_warehouse_entity_counts(&catalog_state.read_pool()).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn resolve_warehouse_slug(
+        slug: &str,
+        catalog_state: CatalogState,
+    ) -> Result<Option<ResolvedWarehouseSlug>> {
+        resolve_warehouse_slug(slug, catalog_state).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn list_namespaces<'a>(
         warehouse_id: WarehouseId,
         query: &ListNamespacesQuery,
@@ -116,6 +273,7 @@ impl Catalog for super::PostgresCatalog {
         list_namespaces(warehouse_id, query, transaction).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn create_namespace<'a>(
         warehouse_id: WarehouseId,
         namespace_id: NamespaceId,
@@ -125,6 +283,7 @@ impl Catalog for super::PostgresCatalog {
         create_namespace(warehouse_id, namespace_id, request, transaction).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn get_namespace<'a>(
         warehouse_id: WarehouseId,
         namespace_id: NamespaceId,
@@ -133,6 +292,7 @@ impl Catalog for super::PostgresCatalog {
         get_namespace(warehouse_id, namespace_id, transaction).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn namespace_to_id<'a>(
         warehouse_id: WarehouseId,
         namespace: &NamespaceIdent,
@@ -141,6 +301,7 @@ impl Catalog for super::PostgresCatalog {
         namespace_to_id(warehouse_id, namespace, transaction).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn drop_namespace<'a>(
         warehouse_id: WarehouseId,
         namespace_id: NamespaceId,
@@ -150,6 +311,34 @@ impl Catalog for super::PostgresCatalog {
         drop_namespace(warehouse_id, namespace_id, flags, transaction).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn mark_namespace_as_deleted<'a>(
+        warehouse_id: WarehouseId,
+        namespace_id: NamespaceId,
+        flags: NamespaceDropFlags,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<NamespaceDropInfo>> {
+        mark_namespace_as_deleted(warehouse_id, namespace_id, flags, transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn undrop_namespace<'a>(
+        warehouse_id: WarehouseId,
+        namespace_id: NamespaceId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<TaskId>> {
+        clear_namespace_deleted_at(warehouse_id, namespace_id, transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn drop_emptied_namespaces<'a>(
+        warehouse_id: WarehouseId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<NamespaceId>> {
+        drop_emptied_namespaces(warehouse_id, transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn update_namespace_properties<'a>(
         warehouse_id: WarehouseId,
         namespace_id: NamespaceId,
@@ -159,6 +348,7 @@ impl Catalog for super::PostgresCatalog {
         update_namespace_properties(warehouse_id, namespace_id, properties, transaction).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn create_table<'a>(
         table_creation: TableCreation<'_>,
         transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
@@ -166,6 +356,7 @@ impl Catalog for super::PostgresCatalog {
         create_table(table_creation, transaction).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn list_tables<'a>(
         warehouse_id: WarehouseId,
         namespace: &NamespaceIdent,
@@ -183,6 +374,15 @@ impl Catalog for super::PostgresCatalog {
         .await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_table_stats<'a>(
+        table_ids: &[TableId],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<HashMap<TableId, TableStats>> {
+        get_table_stats(table_ids, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn table_to_id<'a>(
         warehouse_id: WarehouseId,
         table: &TableIdent,
@@ -194,6 +394,7 @@ impl Catalog for super::PostgresCatalog {
             .map(|x| x.map(|x| x.ident))
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn table_idents_to_ids(
         warehouse_id: WarehouseId,
         tables: HashSet<&TableIdent>,
@@ -204,6 +405,7 @@ impl Catalog for super::PostgresCatalog {
     }
 
     // Should also load staged tables but not tables of inactive warehouses
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn load_tables<'a>(
         warehouse_id: WarehouseId,
         tables: impl IntoIterator<Item = TableId> + Send,
@@ -213,6 +415,7 @@ impl Catalog for super::PostgresCatalog {
         load_tables(warehouse_id, tables, include_deleted, transaction).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn get_table_metadata_by_id(
         warehouse_id: WarehouseId,
         table: TableId,
@@ -222,6 +425,7 @@ impl Catalog for super::PostgresCatalog {
         get_table_metadata_by_id(warehouse_id, table, list_flags, catalog_state).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn get_table_metadata_by_s3_location(
         warehouse_id: WarehouseId,
         location: &Location,
@@ -231,6 +435,7 @@ impl Catalog for super::PostgresCatalog {
         get_table_metadata_by_s3_location(warehouse_id, location, list_flags, catalog_state).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn rename_table<'a>(
         warehouse_id: WarehouseId,
         source_id: TableId,
@@ -241,6 +446,7 @@ impl Catalog for super::PostgresCatalog {
         rename_table(warehouse_id, source_id, source, destination, transaction).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn drop_table<'a>(
         table_id: TableId,
         force: bool,
@@ -249,6 +455,7 @@ impl Catalog for super::PostgresCatalog {
         drop_table(table_id, force, transaction).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn undrop_tabulars(
         tabular_ids: &[TableId],
         warehouse_id: WarehouseId,
@@ -262,6 +469,7 @@ impl Catalog for super::PostgresCatalog {
         .await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn mark_tabular_as_deleted(
         table_id: TabularId,
         force: bool,
@@ -270,6 +478,7 @@ impl Catalog for super::PostgresCatalog {
         mark_tabular_as_deleted(table_id, force, None, transaction).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn commit_table_transaction<'a>(
         warehouse_id: WarehouseId,
         commits: impl IntoIterator<Item = TableCommit> + Send,
@@ -279,6 +488,7 @@ impl Catalog for super::PostgresCatalog {
     }
 
     // ---------------- Role Management API ----------------
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn create_role<'a>(
         role_id: RoleId,
         project_id: &ProjectId,
@@ -296,6 +506,7 @@ impl Catalog for super::PostgresCatalog {
         .await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn update_role<'a>(
         role_id: RoleId,
         role_name: &str,
@@ -305,10 +516,12 @@ impl Catalog for super::PostgresCatalog {
         update_role(role_id, role_name, description, &mut **transaction).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn list_roles<'a>(
         filter_project_id: Option<ProjectId>,
         filter_role_id: Option<Vec<RoleId>>,
         filter_name: Option<String>,
+        sort: Option<ListSort>,
         pagination: PaginationQuery,
         catalog_state: Self::State,
     ) -> Result<ListRolesResponse> {
@@ -316,12 +529,14 @@ impl Catalog for super::PostgresCatalog {
             filter_project_id,
             filter_role_id,
             filter_name,
+            sort,
             pagination,
             &catalog_state.read_pool(),
         )
         .await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn delete_role<'a>(
         role_id: RoleId,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
@@ -329,6 +544,7 @@ impl Catalog for super::PostgresCatalog {
         delete_role(role_id, &mut **transaction).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn search_role(
         search_term: &str,
         catalog_state: Self::State,
@@ -336,7 +552,36 @@ impl Catalog for super::PostgresCatalog {
         search_role(search_term, &catalog_state.read_pool()).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn search_catalog(
+        project_id: &ProjectId,
+        filter: &CatalogSearchFilter,
+        limit: i64,
+        catalog_state: Self::State,
+    ) -> Result<Vec<SearchCatalogResult>> {
+        search_catalog(project_id, filter, limit, &catalog_state.read_pool()).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn global_search(
+        project_id: &ProjectId,
+        search: &str,
+        warehouse_id: Option<WarehouseId>,
+        limit: i64,
+        catalog_state: Self::State,
+    ) -> Result<Vec<GlobalSearchMatch>> {
+        global_search(
+            project_id,
+            search,
+            warehouse_id,
+            limit,
+            &catalog_state.read_pool(),
+        )
+        .await
+    }
+
     // ---------------- User Management API ----------------
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn create_or_update_user<'a>(
         user_id: &UserId,
         name: &str,
@@ -356,6 +601,7 @@ impl Catalog for super::PostgresCatalog {
         .await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn search_user(
         search_term: &str,
         catalog_state: Self::State,
@@ -364,21 +610,29 @@ impl Catalog for super::PostgresCatalog {
     }
 
     /// Return Ok(vec[]) if the user does not exist.
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn list_user(
         filter_user_id: Option<Vec<UserId>>,
         filter_name: Option<String>,
+        filter_user_type: Option<UserType>,
+        filter_inactive_for_days: Option<u32>,
+        sort: ListSort,
         pagination: PaginationQuery,
         catalog_state: Self::State,
     ) -> Result<ListUsersResponse> {
         list_users(
             filter_user_id,
             filter_name,
+            filter_user_type,
+            filter_inactive_for_days,
+            sort,
             pagination,
             &catalog_state.read_pool(),
         )
         .await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn delete_user<'a>(
         user_id: UserId,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
@@ -386,6 +640,81 @@ impl Catalog for super::PostgresCatalog {
         delete_user(user_id, &mut **transaction).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn set_user_active<'a>(
+        user_id: UserId,
+        active: bool,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<()>> {
+        set_user_active(user_id, active, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn is_user_active(user_id: &UserId, catalog_state: Self::State) -> Result<bool> {
+        is_user_active(user_id, &catalog_state.read_pool()).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn set_user_attributes<'a>(
+        user_id: &UserId,
+        attributes: &UserAttributes,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<()>> {
+        set_user_attributes(user_id, attributes, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn record_user_seen(
+        user_id: &UserId,
+        min_interval: std::time::Duration,
+        catalog_state: Self::State,
+    ) -> Result<()> {
+        record_user_seen(user_id, min_interval, &catalog_state.write_pool()).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn hard_delete_user<'a>(
+        user_id: UserId,
+        tombstone_id: UserId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<Vec<UserReferenceReport>>> {
+        hard_delete_user(user_id, tombstone_id, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn merge_user<'a>(
+        source_id: UserId,
+        target_id: UserId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<MergeUserResponse>> {
+        merge_user(source_id, target_id, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn create_impersonation<'a>(
+        impersonation: &Impersonation,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        create_impersonation(impersonation, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_impersonation(
+        impersonation_id: uuid::Uuid,
+        catalog_state: Self::State,
+    ) -> Result<Option<Impersonation>> {
+        get_impersonation(impersonation_id, &catalog_state.read_pool()).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn end_impersonation<'a>(
+        impersonation_id: uuid::Uuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<Impersonation>> {
+        end_impersonation(impersonation_id, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn create_warehouse<'a>(
         warehouse_name: String,
         project_id: &ProjectId,
@@ -406,6 +735,7 @@ impl Catalog for super::PostgresCatalog {
     }
 
     // ---------------- Management API ----------------
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn create_project<'a>(
         project_id: &ProjectId,
         project_name: String,
@@ -415,6 +745,7 @@ impl Catalog for super::PostgresCatalog {
     }
 
     /// Delete a project
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn delete_project<'a>(
         project_id: &ProjectId,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
@@ -423,6 +754,7 @@ impl Catalog for super::PostgresCatalog {
     }
 
     /// Get the project metadata
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn get_project<'a>(
         project_id: &ProjectId,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
@@ -430,6 +762,7 @@ impl Catalog for super::PostgresCatalog {
         get_project(project_id, transaction).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn list_projects(
         project_ids: Option<HashSet<ProjectId>>,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
@@ -437,6 +770,7 @@ impl Catalog for super::PostgresCatalog {
         list_projects(project_ids, &mut **transaction).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn get_endpoint_statistics(
         project_id: ProjectId,
         warehouse_id: WarehouseFilter,
@@ -454,6 +788,7 @@ impl Catalog for super::PostgresCatalog {
         .await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn list_warehouses(
         project_id: &ProjectId,
         include_inactive: Option<Vec<WarehouseStatus>>,
@@ -462,6 +797,7 @@ impl Catalog for super::PostgresCatalog {
         list_warehouses(project_id, include_inactive, &mut **transaction).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn get_warehouse<'a>(
         warehouse_id: WarehouseId,
         transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
@@ -469,6 +805,7 @@ impl Catalog for super::PostgresCatalog {
         get_warehouse(warehouse_id, transaction).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn get_warehouse_stats(
         warehouse_id: WarehouseId,
         pagination_query: PaginationQuery,
@@ -477,6 +814,7 @@ impl Catalog for super::PostgresCatalog {
         get_warehouse_stats(state.read_pool(), warehouse_id, pagination_query).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn delete_warehouse<'a>(
         warehouse_id: WarehouseId,
         query: DeleteWarehouseQuery,
@@ -485,6 +823,7 @@ impl Catalog for super::PostgresCatalog {
         delete_warehouse(warehouse_id, query, transaction).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn rename_warehouse<'a>(
         warehouse_id: WarehouseId,
         new_name: &str,
@@ -493,6 +832,7 @@ impl Catalog for super::PostgresCatalog {
         rename_warehouse(warehouse_id, new_name, transaction).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn set_warehouse_deletion_profile<'a>(
         warehouse_id: WarehouseId,
         deletion_profile: &TabularDeleteProfile,
@@ -501,6 +841,144 @@ impl Catalog for super::PostgresCatalog {
         set_warehouse_deletion_profile(warehouse_id, deletion_profile, &mut **transaction).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn set_warehouse_disabled_endpoint_groups<'a>(
+        warehouse_id: WarehouseId,
+        disabled_endpoint_groups: &[EndpointGroup],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_warehouse_disabled_endpoint_groups(
+            warehouse_id,
+            disabled_endpoint_groups,
+            &mut **transaction,
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn set_warehouse_client_workarounds<'a>(
+        warehouse_id: WarehouseId,
+        client_workarounds: &[ClientWorkaround],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_warehouse_client_workarounds(warehouse_id, client_workarounds, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn set_warehouse_default_branch<'a>(
+        warehouse_id: WarehouseId,
+        default_branch: Option<&str>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_warehouse_default_branch(warehouse_id, default_branch, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn set_warehouse_metadata_push<'a>(
+        warehouse_id: WarehouseId,
+        enabled: bool,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_warehouse_metadata_push(warehouse_id, enabled, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_warehouse_event_routing_rules(
+        warehouse_id: WarehouseId,
+        catalog_state: Self::State,
+    ) -> Result<Option<Vec<EventRoutingRule>>> {
+        get_warehouse_event_routing_rules(warehouse_id, &catalog_state.read_pool()).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn set_warehouse_event_routing_rules<'a>(
+        warehouse_id: WarehouseId,
+        rules: &[EventRoutingRule],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_warehouse_event_routing_rules(warehouse_id, rules, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_label_settings<'a>(
+        project_id: &ProjectId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<LabelSettings>> {
+        get_label_settings(project_id, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn set_label_settings<'a>(
+        project_id: &ProjectId,
+        settings: &LabelSettings,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_label_settings(project_id, settings, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_labels<'a>(
+        warehouse_id: WarehouseId,
+        entity: LabelEntity,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<Labels>> {
+        get_labels(warehouse_id, entity, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn set_labels<'a>(
+        warehouse_id: WarehouseId,
+        entity: LabelEntity,
+        labels: &Labels,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_labels(warehouse_id, entity, labels, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_effective_table_labels(
+        warehouse_id: WarehouseId,
+        table_id: TableId,
+        catalog_state: Self::State,
+    ) -> Result<Labels> {
+        get_effective_table_labels(warehouse_id, table_id, &catalog_state.read_pool()).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn list_referenced_files<'a>(
+        warehouse_id: WarehouseId,
+        pagination_query: PaginationQuery,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<ListReferencedFilesResponse> {
+        list_referenced_files(warehouse_id, pagination_query, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn list_warehouse_slugs<'a>(
+        warehouse_id: WarehouseId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<WarehouseSlug>> {
+        list_warehouse_slugs(warehouse_id, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn set_warehouse_slug<'a>(
+        warehouse_id: WarehouseId,
+        slug: &str,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_warehouse_slug(warehouse_id, slug, transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn delete_warehouse_slugs<'a>(
+        warehouse_id: WarehouseId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        delete_warehouse_slugs(warehouse_id, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn rename_project<'a>(
         project_id: &ProjectId,
         new_name: &str,
@@ -509,6 +987,80 @@ impl Catalog for super::PostgresCatalog {
         rename_project(project_id, new_name, transaction).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_project_quotas<'a>(
+        project_id: &ProjectId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<ProjectQuotas>> {
+        get_project_quotas(project_id, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn set_project_quotas<'a>(
+        project_id: &ProjectId,
+        quotas: &ProjectQuotas,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_project_quotas(project_id, quotas, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_project_quota_usage<'a>(
+        project_id: &ProjectId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<ProjectQuotaUsage> {
+        get_project_quota_usage(project_id, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn list_projects_with_quota_warnings(state: Self::State) -> Result<Vec<ProjectId>> {
+        list_projects_with_quota_warnings(&state.read_pool()).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn list_project_quota_warnings<'a>(
+        project_id: &ProjectId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<ProjectQuotaWarning>> {
+        list_project_quota_warnings(project_id, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn raise_project_quota_warning<'a>(
+        project_id: &ProjectId,
+        warning: &ProjectQuotaWarning,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<bool> {
+        raise_project_quota_warning(project_id, warning, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn clear_project_quota_warning<'a>(
+        project_id: &ProjectId,
+        quota: ProjectQuotaKind,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<bool> {
+        clear_project_quota_warning(project_id, quota, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_property_schemas<'a>(
+        project_id: &ProjectId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<Vec<PropertySchema>>> {
+        get_property_schemas(project_id, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn set_property_schemas<'a>(
+        project_id: &ProjectId,
+        schemas: &[PropertySchema],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_property_schemas(project_id, schemas, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn set_warehouse_status<'a>(
         warehouse_id: WarehouseId,
         status: WarehouseStatus,
@@ -517,6 +1069,7 @@ impl Catalog for super::PostgresCatalog {
         set_warehouse_status(warehouse_id, status, transaction).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn update_storage_profile<'a>(
         warehouse_id: WarehouseId,
         storage_profile: StorageProfile,
@@ -532,6 +1085,7 @@ impl Catalog for super::PostgresCatalog {
         .await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn view_to_id<'a>(
         warehouse_id: WarehouseId,
         view: &TableIdent,
@@ -540,6 +1094,16 @@ impl Catalog for super::PostgresCatalog {
         view_ident_to_id(warehouse_id, view, false, &mut **transaction).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn view_id_to_ident<'a>(
+        warehouse_id: WarehouseId,
+        view_id: ViewId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<TableIdent>> {
+        view_id_to_ident(warehouse_id, view_id, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn create_view<'a>(
         namespace_id: NamespaceId,
         view: &TableIdent,
@@ -559,6 +1123,7 @@ impl Catalog for super::PostgresCatalog {
         .await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn load_view<'a>(
         view_id: ViewId,
         include_deleted: bool,
@@ -567,6 +1132,7 @@ impl Catalog for super::PostgresCatalog {
         load_view(view_id, include_deleted, &mut *transaction).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn list_views<'a>(
         warehouse_id: WarehouseId,
         namespace: &NamespaceIdent,
@@ -584,6 +1150,7 @@ impl Catalog for super::PostgresCatalog {
         .await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn update_view_metadata(
         ViewCommit {
             namespace_id,
@@ -608,6 +1175,7 @@ impl Catalog for super::PostgresCatalog {
         .await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn drop_view<'a>(
         view_id: ViewId,
         force: bool,
@@ -616,6 +1184,7 @@ impl Catalog for super::PostgresCatalog {
         drop_view(view_id, force, None, transaction).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn rename_view(
         warehouse_id: WarehouseId,
         source_id: ViewId,
@@ -626,6 +1195,25 @@ impl Catalog for super::PostgresCatalog {
         rename_view(warehouse_id, source_id, source, destination, transaction).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_view_lineage(
+        warehouse_id: WarehouseId,
+        view_id: ViewId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Vec<ViewLineageSource>> {
+        get_view_lineage(warehouse_id, view_id, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn list_views_referencing_table(
+        warehouse_id: WarehouseId,
+        table_id: TableId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Vec<ReferencingView>> {
+        list_views_referencing_table(warehouse_id, table_id, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn list_tabulars(
         warehouse_id: WarehouseId,
         namespace_id: Option<NamespaceId>,
@@ -645,6 +1233,7 @@ impl Catalog for super::PostgresCatalog {
         .await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn load_storage_profile(
         warehouse_id: WarehouseId,
         tabular_id: TableId,
@@ -653,6 +1242,7 @@ impl Catalog for super::PostgresCatalog {
         load_storage_profile(warehouse_id, tabular_id, transaction).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn resolve_table_ident(
         warehouse_id: WarehouseId,
         table: &TableIdent,
@@ -662,6 +1252,7 @@ impl Catalog for super::PostgresCatalog {
         resolve_table_ident(warehouse_id, table, list_flags, &mut **transaction).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn set_tabular_protected(
         tabular_id: TabularId,
         protect: bool,
@@ -670,6 +1261,7 @@ impl Catalog for super::PostgresCatalog {
         set_tabular_protected(tabular_id, protect, transaction).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn get_tabular_protected(
         tabular_id: TabularId,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
@@ -677,6 +1269,16 @@ impl Catalog for super::PostgresCatalog {
         get_tabular_protected(tabular_id, transaction).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_tabular_version(
+        warehouse_id: WarehouseId,
+        tabular_id: TabularId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<TabularVersion>> {
+        get_tabular_version(warehouse_id, tabular_id, transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn set_namespace_protected(
         namespace_id: NamespaceId,
         protect: bool,
@@ -685,6 +1287,7 @@ impl Catalog for super::PostgresCatalog {
         set_namespace_protected(namespace_id, protect, transaction).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn get_namespace_protected(
         namespace_id: NamespaceId,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
@@ -692,6 +1295,7 @@ impl Catalog for super::PostgresCatalog {
         get_namespace_protected(namespace_id, transaction).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn set_warehouse_protected(
         warehouse_id: WarehouseId,
         protect: bool,
@@ -700,6 +1304,7 @@ impl Catalog for super::PostgresCatalog {
         set_warehouse_protection(warehouse_id, protect, transaction).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn pick_new_task(
         queue_name: &str,
         max_time_since_last_heartbeat: Duration,
@@ -713,6 +1318,23 @@ impl Catalog for super::PostgresCatalog {
         .await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn lease_task(
+        queue_name: &str,
+        max_time_since_last_heartbeat: Duration,
+        visibility_timeout: Option<Duration>,
+        state: Self::State,
+    ) -> Result<Option<Task>> {
+        lease_task(
+            &state.write_pool(),
+            queue_name,
+            max_time_since_last_heartbeat,
+            visibility_timeout,
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn record_task_success(
         id: TaskId,
         message: Option<&str>,
@@ -722,6 +1344,7 @@ impl Catalog for super::PostgresCatalog {
             .await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn record_task_failure(
         id: TaskId,
         error_details: &str,
@@ -737,6 +1360,7 @@ impl Catalog for super::PostgresCatalog {
         .await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn enqueue_task_batch(
         queue_name: &'static str,
         tasks: Vec<TaskInput>,
@@ -752,6 +1376,16 @@ impl Catalog for super::PostgresCatalog {
         Ok(queued.into_iter().map(|t| t.task_id).collect())
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn enqueue_or_reschedule_task(
+        queue_name: &'static str,
+        task: TaskInput,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<TaskId>> {
+        queue_or_reschedule_task(transaction, queue_name, task).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn cancel_pending_tasks(
         queue_name: &str,
         filter: TaskFilter,
@@ -761,6 +1395,7 @@ impl Catalog for super::PostgresCatalog {
         cancel_tasks(&mut *transaction, filter, queue_name, force).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn check_and_heartbeat_task(
         task_id: TaskId,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
@@ -768,6 +1403,16 @@ impl Catalog for super::PostgresCatalog {
         check_task(&mut *transaction, task_id).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn is_task_lease_valid(
+        task_id: TaskId,
+        attempt: i32,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<bool> {
+        is_task_lease_valid(&mut *transaction, task_id, attempt).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn stop_task(
         task_id: TaskId,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
@@ -775,6 +1420,7 @@ impl Catalog for super::PostgresCatalog {
         stop_task(&mut *transaction, task_id).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn set_task_queue_config(
         warehouse_id: WarehouseId,
         queue_name: &str,
@@ -784,6 +1430,7 @@ impl Catalog for super::PostgresCatalog {
         set_task_queue_config(transaction, queue_name, warehouse_id, config).await
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn get_task_queue_config(
         warehouse_id: WarehouseId,
         queue_name: &str,
@@ -791,4 +1438,832 @@ impl Catalog for super::PostgresCatalog {
     ) -> Result<Option<GetTaskQueueConfigResponse>> {
         get_task_queue_config(transaction, warehouse_id, queue_name).await
     }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_entity_task(
+        warehouse_id: WarehouseId,
+        entity_id: EntityId,
+        queue_name: &str,
+        catalog_state: Self::State,
+    ) -> Result<Option<TaskInfo>> {
+        get_entity_task(
+            warehouse_id,
+            entity_id,
+            queue_name,
+            &catalog_state.read_pool(),
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn list_entity_task_log(
+        warehouse_id: WarehouseId,
+        entity_id: EntityId,
+        queue_name: &str,
+        limit: i64,
+        catalog_state: Self::State,
+    ) -> Result<Vec<TaskLogEntry>> {
+        list_entity_task_log(
+            warehouse_id,
+            entity_id,
+            queue_name,
+            limit,
+            &catalog_state.read_pool(),
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn create_task_schedule(
+        schedule: TaskScheduleInput,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<TaskSchedule>> {
+        create_task_schedule(schedule, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_task_schedule(
+        warehouse_id: WarehouseId,
+        schedule_id: uuid::Uuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<TaskSchedule>> {
+        get_task_schedule(warehouse_id, schedule_id, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn list_task_schedules(
+        warehouse_id: WarehouseId,
+        queue_name: Option<&str>,
+        pagination_query: PaginationQuery,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<ListTaskSchedulesResponse> {
+        list_task_schedules(
+            warehouse_id,
+            queue_name,
+            pagination_query,
+            &mut **transaction,
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn delete_task_schedule(
+        warehouse_id: WarehouseId,
+        schedule_id: uuid::Uuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<()>> {
+        delete_task_schedule(warehouse_id, schedule_id, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn set_task_schedule_paused(
+        warehouse_id: WarehouseId,
+        schedule_id: uuid::Uuid,
+        paused: bool,
+        next_run_at: Option<chrono::DateTime<chrono::Utc>>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<TaskSchedule>> {
+        set_task_schedule_paused(
+            warehouse_id,
+            schedule_id,
+            paused,
+            next_run_at,
+            &mut **transaction,
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn claim_due_task_schedules(
+        limit: i64,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Vec<TaskSchedule>> {
+        claim_due_task_schedules(limit, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn record_task_schedule_fire(
+        schedule_id: uuid::Uuid,
+        task_id: Option<TaskId>,
+        next_run_at: chrono::DateTime<chrono::Utc>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<()> {
+        record_task_schedule_fire(schedule_id, task_id, next_run_at, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn load_catalog_snapshot_state(
+        warehouse_id: WarehouseId,
+        catalog_state: Self::State,
+    ) -> Result<Option<CatalogSnapshotState>> {
+        load_catalog_snapshot_state(warehouse_id, &catalog_state.read_pool()).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn create_catalog_snapshot(
+        snapshot: &CatalogSnapshot,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<()> {
+        create_catalog_snapshot(snapshot, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_catalog_snapshot(
+        warehouse_id: WarehouseId,
+        snapshot_id: uuid::Uuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<CatalogSnapshot>> {
+        get_catalog_snapshot(warehouse_id, snapshot_id, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn list_catalog_snapshots(
+        warehouse_id: WarehouseId,
+        limit: i64,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Vec<CatalogSnapshot>> {
+        list_catalog_snapshots(warehouse_id, limit, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn delete_expired_catalog_snapshots(
+        warehouse_id: WarehouseId,
+        retain: i64,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Vec<CatalogSnapshot>> {
+        delete_expired_catalog_snapshots(warehouse_id, retain, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn create_catalog_import(
+        import_id: uuid::Uuid,
+        warehouse_id: WarehouseId,
+        source: &CatalogImportSource,
+        created_by: Option<&str>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<()> {
+        create_catalog_import(
+            import_id,
+            warehouse_id,
+            source,
+            created_by,
+            &mut **transaction,
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_catalog_import(
+        warehouse_id: WarehouseId,
+        import_id: uuid::Uuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<CatalogImport>> {
+        get_catalog_import(warehouse_id, import_id, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn set_catalog_import_status(
+        import_id: uuid::Uuid,
+        status: CatalogImportStatus,
+        error: Option<&str>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<()> {
+        set_catalog_import_status(import_id, status, error, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn record_catalog_import_table(
+        import_id: uuid::Uuid,
+        table: &CatalogImportTable,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<()> {
+        record_catalog_import_table(import_id, table, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn list_catalog_import_tables(
+        import_id: uuid::Uuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Vec<CatalogImportTable>> {
+        list_catalog_import_tables(import_id, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn list_dead_letter_tasks(
+        warehouse_id: WarehouseId,
+        queue_name: Option<&str>,
+        pagination_query: PaginationQuery,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<ListDeadLetterTasksResponse> {
+        list_dead_letter_tasks(
+            warehouse_id,
+            queue_name,
+            pagination_query,
+            &mut **transaction,
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_dead_letter_task(
+        warehouse_id: WarehouseId,
+        task_id: TaskId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<DeadLetterTask>> {
+        get_dead_letter_task(warehouse_id, task_id, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn list_task_attempts(
+        warehouse_id: WarehouseId,
+        task_id: TaskId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Vec<TaskLogEntry>> {
+        list_task_attempts(warehouse_id, task_id, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn requeue_dead_letter_task(
+        warehouse_id: WarehouseId,
+        task_id: TaskId,
+        new_task_id: TaskId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<TaskId>> {
+        requeue_dead_letter_task(warehouse_id, task_id, new_task_id, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_task_queue_stats(
+        window: chrono::Duration,
+        catalog_state: Self::State,
+    ) -> Result<Vec<TaskQueueStats>> {
+        get_task_queue_stats(window, &catalog_state.read_pool()).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn create_scan_plan(
+        plan: ScanPlan,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<()> {
+        create_scan_plan(plan, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn set_scan_plan_result(
+        plan_id: ScanPlanId,
+        result: std::result::Result<ScanTasks, ErrorModel>,
+        catalog_state: Self::State,
+    ) -> Result<()> {
+        set_scan_plan_result(plan_id, result, &catalog_state.write_pool()).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_scan_plan(
+        warehouse_id: WarehouseId,
+        table_id: TableId,
+        plan_id: ScanPlanId,
+        catalog_state: Self::State,
+    ) -> Result<Option<ScanPlan>> {
+        get_scan_plan(warehouse_id, table_id, plan_id, &catalog_state.read_pool()).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn cancel_scan_plan(
+        warehouse_id: WarehouseId,
+        table_id: TableId,
+        plan_id: ScanPlanId,
+        catalog_state: Self::State,
+    ) -> Result<Option<()>> {
+        cancel_scan_plan(warehouse_id, table_id, plan_id, &catalog_state.write_pool()).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn set_column_tags(
+        warehouse_id: WarehouseId,
+        table_id: TableId,
+        tags: &HashMap<i32, Vec<String>>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<()> {
+        set_column_tags(warehouse_id, table_id, tags, transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_column_tags(
+        table_id: TableId,
+        catalog_state: Self::State,
+    ) -> Result<HashMap<i32, Vec<String>>> {
+        get_column_tags(table_id, &catalog_state.read_pool()).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn list_tagged_columns(
+        warehouse_id: WarehouseId,
+        tag: &str,
+        catalog_state: Self::State,
+    ) -> Result<Vec<TaggedColumn>> {
+        list_tagged_columns(warehouse_id, tag, &catalog_state.read_pool()).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn replace_column_tag_suggestions(
+        warehouse_id: WarehouseId,
+        table_id: TableId,
+        suggestions: &[PiiSuggestion],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<()> {
+        replace_column_tag_suggestions(warehouse_id, table_id, suggestions, transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn list_column_tag_suggestions(
+        table_id: TableId,
+        status: Option<ColumnTagSuggestionStatus>,
+        catalog_state: Self::State,
+    ) -> Result<Vec<ColumnTagSuggestion>> {
+        list_column_tag_suggestions(table_id, status, &catalog_state.read_pool()).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn review_column_tag_suggestions(
+        warehouse_id: WarehouseId,
+        table_id: TableId,
+        accept: &[ColumnTagSuggestionKey],
+        reject: &[ColumnTagSuggestionKey],
+        reviewed_by: Option<&str>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<u64> {
+        review_column_tag_suggestions(
+            warehouse_id,
+            table_id,
+            accept,
+            reject,
+            reviewed_by,
+            transaction,
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn create_table_property_proposal(
+        warehouse_id: WarehouseId,
+        table_id: TableId,
+        proposal_id: uuid::Uuid,
+        request: &CreateTablePropertyProposalRequest,
+        proposed_by: Option<&str>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<TablePropertyProposal> {
+        create_table_property_proposal(
+            warehouse_id,
+            table_id,
+            proposal_id,
+            request,
+            proposed_by,
+            &mut **transaction,
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn list_table_property_proposals(
+        table_id: TableId,
+        status: Option<TablePropertyProposalStatus>,
+        catalog_state: Self::State,
+    ) -> Result<Vec<TablePropertyProposal>> {
+        list_table_property_proposals(table_id, status, &catalog_state.read_pool()).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn review_table_property_proposal(
+        table_id: TableId,
+        proposal_id: uuid::Uuid,
+        status: TablePropertyProposalStatus,
+        reviewed_by: Option<&str>,
+        review_comment: Option<&str>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<TablePropertyProposal>> {
+        review_table_property_proposal(
+            table_id,
+            proposal_id,
+            status,
+            reviewed_by,
+            review_comment,
+            &mut **transaction,
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn begin_table_writer(
+        warehouse_id: WarehouseId,
+        table_id: TableId,
+        writer_id: &str,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<TableWriterState> {
+        begin_table_writer(warehouse_id, table_id, writer_id, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_table_writer(
+        table_id: TableId,
+        writer_id: &str,
+        catalog_state: Self::State,
+    ) -> Result<Option<TableWriter>> {
+        get_table_writer(table_id, writer_id, &catalog_state.write_pool()).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn prepare_table_writer_commit(
+        table_id: TableId,
+        writer_id: &str,
+        epoch: i64,
+        checkpoint_id: i64,
+        commit: &PreparedTableWriterCommit,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<bool> {
+        prepare_table_writer_commit(
+            table_id,
+            writer_id,
+            epoch,
+            checkpoint_id,
+            commit,
+            &mut **transaction,
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn complete_table_writer_commit(
+        checkpoint: &TableWriterCheckpoint,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<()> {
+        complete_table_writer_commit(checkpoint, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn create_announcement(
+        announcement_id: uuid::Uuid,
+        request: CreateAnnouncementRequest,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Announcement> {
+        create_announcement(announcement_id, request, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_announcement(
+        announcement_id: uuid::Uuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<Announcement>> {
+        get_announcement(announcement_id, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn update_announcement(
+        announcement_id: uuid::Uuid,
+        request: UpdateAnnouncementRequest,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<Announcement>> {
+        update_announcement(announcement_id, request, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn delete_announcement(
+        announcement_id: uuid::Uuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<()>> {
+        delete_announcement(announcement_id, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn list_announcements(
+        pagination_query: PaginationQuery,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<ListAnnouncementsResponse> {
+        list_announcements(pagination_query, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn list_active_announcements(catalog_state: Self::State) -> Result<Vec<Announcement>> {
+        list_active_announcements(&catalog_state.read_pool()).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_request_log_settings(
+        catalog_state: Self::State,
+    ) -> Result<Option<RequestLogSettings>> {
+        get_request_log_settings(&catalog_state.read_pool()).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn set_request_log_settings(
+        settings: RequestLogSettings,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<RequestLogSettings> {
+        set_request_log_settings(settings, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn create_recertification_campaign(
+        campaign_id: uuid::Uuid,
+        project_id: &ProjectId,
+        request: &CreateRecertificationCampaignRequest,
+        created_by: Option<&str>,
+        items: &[RecertificationItem],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<RecertificationCampaign> {
+        create_recertification_campaign(
+            campaign_id,
+            project_id,
+            request,
+            created_by,
+            items,
+            transaction,
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_recertification_campaign(
+        campaign_id: uuid::Uuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<RecertificationCampaign>> {
+        get_recertification_campaign(campaign_id, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn list_recertification_campaigns(
+        project_id: &ProjectId,
+        reviewer: Option<&str>,
+        pagination_query: PaginationQuery,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<ListRecertificationCampaignsResponse> {
+        list_recertification_campaigns(project_id, reviewer, pagination_query, &mut **transaction)
+            .await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn list_recertification_items(
+        campaign_id: uuid::Uuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Vec<RecertificationItem>> {
+        list_recertification_items(campaign_id, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn decide_recertification_items(
+        campaign_id: uuid::Uuid,
+        decisions: &[RecertificationItemDecision],
+        decided_by: Option<&str>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Vec<RecertificationItem>> {
+        decide_recertification_items(campaign_id, decisions, decided_by, transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn close_recertification_campaign(
+        campaign_id: uuid::Uuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Vec<RecertificationItem>> {
+        close_recertification_campaign(campaign_id, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn create_project_invitation(
+        invitation_id: uuid::Uuid,
+        project_id: &ProjectId,
+        request: &CreateInvitationRequest,
+        token_hash: Option<&str>,
+        expires_at: chrono::DateTime<chrono::Utc>,
+        created_by: Option<&str>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<ProjectInvitation> {
+        create_project_invitation(
+            invitation_id,
+            project_id,
+            request,
+            token_hash,
+            expires_at,
+            created_by,
+            transaction,
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn list_project_invitations(
+        project_id: &ProjectId,
+        pagination_query: PaginationQuery,
+        catalog_state: Self::State,
+    ) -> Result<ListInvitationsResponse> {
+        list_project_invitations(project_id, pagination_query, &catalog_state.read_pool()).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn lock_project_invitation(
+        invitation_id: Option<uuid::Uuid>,
+        token_hash: Option<&str>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<ProjectInvitation>> {
+        lock_project_invitation(invitation_id, token_hash, transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn revoke_project_invitation(
+        invitation_id: uuid::Uuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<()> {
+        revoke_project_invitation(invitation_id, transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn mark_project_invitation_redeemed(
+        invitation_id: uuid::Uuid,
+        redeemed_by: &str,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<()> {
+        mark_project_invitation_redeemed(invitation_id, redeemed_by, transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn record_storage_intents(
+        intents: &[StorageIntent],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<()> {
+        record_storage_intents(intents, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn complete_storage_intents(
+        intent_ids: &[uuid::Uuid],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<()> {
+        complete_storage_intents(intent_ids, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn claim_stale_storage_intents(
+        recorded_before: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Vec<StorageIntent>> {
+        claim_stale_storage_intents(recorded_before, limit, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn retire_storage_secret(
+        warehouse_id: WarehouseId,
+        secret_id: SecretIdent,
+        retire_at: chrono::DateTime<chrono::Utc>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<()> {
+        retire_storage_secret(warehouse_id, secret_id, retire_at, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn claim_due_retired_storage_secrets(
+        limit: i64,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Vec<SecretIdent>> {
+        claim_due_retired_storage_secrets(limit, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn delete_retired_storage_secrets(
+        secret_ids: &[SecretIdent],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<()> {
+        delete_retired_storage_secrets(secret_ids, &mut **transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn record_table_signing(
+        warehouse_id: WarehouseId,
+        signing: &TableSigning,
+        catalog_state: Self::State,
+    ) -> Result<()> {
+        record_table_signing(warehouse_id, signing, &catalog_state.write_pool()).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn list_table_signings(
+        warehouse_id: WarehouseId,
+        table_id: TableId,
+        pagination_query: PaginationQuery,
+        catalog_state: Self::State,
+    ) -> Result<ListTableSigningsResponse> {
+        list_table_signings(
+            warehouse_id,
+            table_id,
+            pagination_query,
+            &catalog_state.read_pool(),
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn delete_table_signings(
+        signed_before: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+        catalog_state: Self::State,
+    ) -> Result<u64> {
+        delete_table_signings(signed_before, limit, &catalog_state.write_pool()).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn record_server_event(event: &ServerEvent, catalog_state: Self::State) -> Result<()> {
+        record_server_event(event, &catalog_state.write_pool()).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn list_server_events(
+        event_types: Option<Vec<ServerEventType>>,
+        pagination_query: PaginationQuery,
+        catalog_state: Self::State,
+    ) -> Result<ListServerEventsResponse> {
+        list_server_events(event_types, pagination_query, &catalog_state.read_pool()).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn delete_server_events(
+        created_before: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+        catalog_state: Self::State,
+    ) -> Result<u64> {
+        delete_server_events(created_before, limit, &catalog_state.write_pool()).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn record_table_activity(
+        activity: &TableActivity,
+        catalog_state: Self::State,
+    ) -> Result<()> {
+        record_table_activity(activity, &catalog_state.write_pool()).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_table_timeline(
+        warehouse_id: WarehouseId,
+        table_id: TableId,
+        pagination_query: PaginationQuery,
+        catalog_state: Self::State,
+    ) -> Result<GetTableTimelineResponse> {
+        get_table_timeline(
+            warehouse_id,
+            table_id,
+            pagination_query,
+            &catalog_state.read_pool(),
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn resolve_table_locations(
+        locations: &[String],
+        catalog_state: Self::State,
+    ) -> Result<HashMap<String, LineageTable>> {
+        resolve_table_locations(locations, &catalog_state.read_pool()).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn record_table_lineage(
+        edges: &[LineageEdge],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<()> {
+        record_table_lineage(edges, transaction).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn get_table_lineage(
+        warehouse_id: WarehouseId,
+        table_id: TableId,
+        catalog_state: Self::State,
+    ) -> Result<TableLineage> {
+        get_table_lineage(warehouse_id, table_id, &catalog_state.read_pool()).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn aggregate_warehouse_usage(
+        day: chrono::NaiveDate,
+        update_entity_counts: bool,
+        catalog_state: Self::State,
+    ) -> Result<()> {
+        aggregate_warehouse_usage(day, update_entity_counts, &catalog_state.write_pool()).await
+    }
+
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn list_warehouse_usage(
+        project_id: &ProjectId,
+        warehouse_id: Option<WarehouseId>,
+        start: chrono::NaiveDate,
+        end: chrono::NaiveDate,
+        catalog_state: Self::State,
+    ) -> Result<Vec<WarehouseUsageDay>> {
+        list_warehouse_usage(
+            project_id,
+            warehouse_id,
+            start,
+            end,
+            &catalog_state.read_pool(),
+        )
+        .await
+    }
 }
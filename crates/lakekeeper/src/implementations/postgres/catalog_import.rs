@@ -0,0 +1,282 @@
+use uuid::Uuid;
+
+use crate::{
+    implementations::postgres::dbutils::DBErrorHandler as _,
+    service::{
+        task_queue::catalog_import_queue::{
+            CatalogImport, CatalogImportSource, CatalogImportStatus, CatalogImportTable,
+            CatalogImportTableStatus,
+        },
+        Result,
+    },
+    WarehouseId,
+};
+
+#[derive(sqlx::Type, Debug, Clone, Copy, PartialEq, Eq)]
+#[sqlx(rename_all = "kebab-case", type_name = "catalog_import_status")]
+enum DbCatalogImportStatus {
+    Pending,
+    Running,
+    Succeeded,
+    Failed,
+}
+
+impl From<DbCatalogImportStatus> for CatalogImportStatus {
+    fn from(value: DbCatalogImportStatus) -> Self {
+        match value {
+            DbCatalogImportStatus::Pending => CatalogImportStatus::Pending,
+            DbCatalogImportStatus::Running => CatalogImportStatus::Running,
+            DbCatalogImportStatus::Succeeded => CatalogImportStatus::Succeeded,
+            DbCatalogImportStatus::Failed => CatalogImportStatus::Failed,
+        }
+    }
+}
+
+impl From<CatalogImportStatus> for DbCatalogImportStatus {
+    fn from(value: CatalogImportStatus) -> Self {
+        match value {
+            CatalogImportStatus::Pending => DbCatalogImportStatus::Pending,
+            CatalogImportStatus::Running => DbCatalogImportStatus::Running,
+            CatalogImportStatus::Succeeded => DbCatalogImportStatus::Succeeded,
+            CatalogImportStatus::Failed => DbCatalogImportStatus::Failed,
+        }
+    }
+}
+
+#[derive(sqlx::Type, Debug, Clone, Copy, PartialEq, Eq)]
+#[sqlx(rename_all = "kebab-case", type_name = "catalog_import_table_status")]
+enum DbCatalogImportTableStatus {
+    Imported,
+    Skipped,
+    Failed,
+}
+
+impl From<DbCatalogImportTableStatus> for CatalogImportTableStatus {
+    fn from(value: DbCatalogImportTableStatus) -> Self {
+        match value {
+            DbCatalogImportTableStatus::Imported => CatalogImportTableStatus::Imported,
+            DbCatalogImportTableStatus::Skipped => CatalogImportTableStatus::Skipped,
+            DbCatalogImportTableStatus::Failed => CatalogImportTableStatus::Failed,
+        }
+    }
+}
+
+impl From<CatalogImportTableStatus> for DbCatalogImportTableStatus {
+    fn from(value: CatalogImportTableStatus) -> Self {
+        match value {
+            CatalogImportTableStatus::Imported => DbCatalogImportTableStatus::Imported,
+            CatalogImportTableStatus::Skipped => DbCatalogImportTableStatus::Skipped,
+            CatalogImportTableStatus::Failed => DbCatalogImportTableStatus::Failed,
+        }
+    }
+}
+
+struct CatalogImportRow {
+    import_id: Uuid,
+    warehouse_id: Uuid,
+    source_uri: String,
+    source_warehouse: Option<String>,
+    status: DbCatalogImportStatus,
+    error: Option<String>,
+    created_by: Option<String>,
+    created_at: chrono::DateTime<chrono::Utc>,
+    finished_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<CatalogImportRow> for CatalogImport {
+    fn from(row: CatalogImportRow) -> Self {
+        Self {
+            import_id: row.import_id,
+            warehouse_id: row.warehouse_id.into(),
+            source_uri: row.source_uri,
+            source_warehouse: row.source_warehouse,
+            status: row.status.into(),
+            error: row.error,
+            created_by: row.created_by,
+            created_at: row.created_at,
+            finished_at: row.finished_at,
+        }
+    }
+}
+
+struct CatalogImportTableRow {
+    namespace: Vec<String>,
+    table_name: String,
+    metadata_location: Option<String>,
+    status: DbCatalogImportTableStatus,
+    table_id: Option<Uuid>,
+    error: Option<String>,
+}
+
+impl From<CatalogImportTableRow> for CatalogImportTable {
+    fn from(row: CatalogImportTableRow) -> Self {
+        Self {
+            namespace: row.namespace,
+            name: row.table_name,
+            metadata_location: row.metadata_location,
+            status: row.status.into(),
+            table_id: row.table_id,
+            error: row.error,
+        }
+    }
+}
+
+pub(crate) async fn create_catalog_import<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    import_id: Uuid,
+    warehouse_id: WarehouseId,
+    source: &CatalogImportSource,
+    created_by: Option<&str>,
+    connection: E,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO catalog_import (import_id, warehouse_id, source_uri, source_warehouse, created_by)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+        import_id,
+        *warehouse_id,
+        source.uri.as_str(),
+        source.warehouse,
+        created_by,
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error creating catalog import"))?;
+
+    Ok(())
+}
+
+pub(crate) async fn get_catalog_import<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseId,
+    import_id: Uuid,
+    connection: E,
+) -> Result<Option<CatalogImport>> {
+    let import = sqlx::query_as!(
+        CatalogImportRow,
+        r#"
+        SELECT import_id,
+               warehouse_id,
+               source_uri,
+               source_warehouse,
+               status as "status: DbCatalogImportStatus",
+               error,
+               created_by,
+               created_at,
+               finished_at
+        FROM catalog_import
+        WHERE warehouse_id = $1 AND import_id = $2
+        "#,
+        *warehouse_id,
+        import_id,
+    )
+    .fetch_optional(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error loading catalog import"))?;
+
+    Ok(import.map(Into::into))
+}
+
+pub(crate) async fn set_catalog_import_status<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    import_id: Uuid,
+    status: CatalogImportStatus,
+    error: Option<&str>,
+    connection: E,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        UPDATE catalog_import
+        SET status = $2,
+            error = $3,
+            finished_at = CASE
+                WHEN $2 IN ('succeeded'::catalog_import_status, 'failed'::catalog_import_status)
+                THEN now()
+            END
+        WHERE import_id = $1
+        "#,
+        import_id,
+        DbCatalogImportStatus::from(status) as _,
+        error,
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error updating status of catalog import"))?;
+
+    Ok(())
+}
+
+pub(crate) async fn record_catalog_import_table<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    import_id: Uuid,
+    table: &CatalogImportTable,
+    connection: E,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO catalog_import_table
+            (import_id, namespace, table_name, metadata_location, status, table_id, error)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        ON CONFLICT (import_id, namespace, table_name) DO UPDATE
+        SET metadata_location = EXCLUDED.metadata_location,
+            status = EXCLUDED.status,
+            table_id = EXCLUDED.table_id,
+            error = EXCLUDED.error
+        "#,
+        import_id,
+        &table.namespace,
+        table.name,
+        table.metadata_location,
+        DbCatalogImportTableStatus::from(table.status) as _,
+        table.table_id,
+        table.error,
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error recording table of catalog import"))?;
+
+    Ok(())
+}
+
+pub(crate) async fn list_catalog_import_tables<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    import_id: Uuid,
+    connection: E,
+) -> Result<Vec<CatalogImportTable>> {
+    let tables = sqlx::query_as!(
+        CatalogImportTableRow,
+        r#"
+        SELECT namespace,
+               table_name,
+               metadata_location,
+               status as "status: DbCatalogImportTableStatus",
+               table_id,
+               error
+        FROM catalog_import_table
+        WHERE import_id = $1
+        ORDER BY namespace, table_name
+        "#,
+        import_id,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error listing tables of catalog import"))?;
+
+    Ok(tables.into_iter().map(Into::into).collect())
+}
@@ -0,0 +1,507 @@
+use std::collections::BTreeMap;
+
+use sqlx::{types::Json, PgPool};
+use uuid::Uuid;
+
+use crate::{
+    implementations::postgres::{dbutils::DBErrorHandler as _, tabular::TabularType},
+    service::{
+        task_queue::catalog_snapshot_export_queue::{
+            CatalogSnapshot, CatalogSnapshotKind, CatalogSnapshotNamespace, CatalogSnapshotState,
+            CatalogSnapshotTabular, CatalogSnapshotWarehouse,
+        },
+        Result,
+    },
+    WarehouseId,
+};
+
+#[derive(sqlx::Type, Debug, Clone, Copy, PartialEq, Eq)]
+#[sqlx(rename_all = "kebab-case", type_name = "catalog_snapshot_kind")]
+enum DbCatalogSnapshotKind {
+    Full,
+    Differential,
+}
+
+impl From<DbCatalogSnapshotKind> for CatalogSnapshotKind {
+    fn from(value: DbCatalogSnapshotKind) -> Self {
+        match value {
+            DbCatalogSnapshotKind::Full => CatalogSnapshotKind::Full,
+            DbCatalogSnapshotKind::Differential => CatalogSnapshotKind::Differential,
+        }
+    }
+}
+
+impl From<CatalogSnapshotKind> for DbCatalogSnapshotKind {
+    fn from(value: CatalogSnapshotKind) -> Self {
+        match value {
+            CatalogSnapshotKind::Full => DbCatalogSnapshotKind::Full,
+            CatalogSnapshotKind::Differential => DbCatalogSnapshotKind::Differential,
+        }
+    }
+}
+
+struct CatalogSnapshotRow {
+    snapshot_id: Uuid,
+    warehouse_id: Uuid,
+    kind: DbCatalogSnapshotKind,
+    base_snapshot_id: Option<Uuid>,
+    parent_snapshot_id: Option<Uuid>,
+    location: String,
+    checksum: String,
+    namespace_count: i64,
+    tabular_count: i64,
+    task_id: Option<Uuid>,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<CatalogSnapshotRow> for CatalogSnapshot {
+    fn from(row: CatalogSnapshotRow) -> Self {
+        Self {
+            snapshot_id: row.snapshot_id,
+            warehouse_id: row.warehouse_id.into(),
+            kind: row.kind.into(),
+            base_snapshot_id: row.base_snapshot_id,
+            parent_snapshot_id: row.parent_snapshot_id,
+            location: row.location,
+            checksum: row.checksum,
+            namespace_count: row.namespace_count,
+            tabular_count: row.tabular_count,
+            task_id: row.task_id,
+            created_at: row.created_at,
+        }
+    }
+}
+
+pub(crate) async fn load_catalog_snapshot_state(
+    warehouse_id: WarehouseId,
+    pool: &PgPool,
+) -> Result<Option<CatalogSnapshotState>> {
+    let mut transaction = pool
+        .begin()
+        .await
+        .map_err(|e| e.into_error_model("Error starting transaction"))?;
+    // All queries below see the same state of the database.
+    sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ, READ ONLY")
+        .execute(&mut *transaction)
+        .await
+        .map_err(|e| e.into_error_model("Error setting transaction isolation level"))?;
+
+    let Some(warehouse) = sqlx::query!(
+        r#"
+        SELECT warehouse_name,
+               storage_profile,
+               tabular_expiration_seconds,
+               protected,
+               disabled_endpoint_groups::text[] as "disabled_endpoint_groups!"
+        FROM warehouse
+        WHERE warehouse_id = $1
+        "#,
+        *warehouse_id,
+    )
+    .fetch_optional(&mut *transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error loading warehouse of catalog snapshot"))?
+    else {
+        return Ok(None);
+    };
+
+    let task_queue_configs = sqlx::query!(
+        r#"
+        SELECT queue_name, config
+        FROM task_config
+        WHERE warehouse_id = $1
+        "#,
+        *warehouse_id,
+    )
+    .fetch_all(&mut *transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error loading task queue configs of catalog snapshot"))?;
+
+    let namespaces = sqlx::query!(
+        r#"
+        SELECT namespace_id,
+               namespace_name,
+               namespace_properties as "properties: Json<Option<BTreeMap<String, String>>>",
+               protected,
+               deleted_at IS NOT NULL as "deleted!"
+        FROM namespace
+        WHERE warehouse_id = $1
+        ORDER BY namespace_id
+        "#,
+        *warehouse_id,
+    )
+    .fetch_all(&mut *transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error loading namespaces of catalog snapshot"))?;
+
+    let tabulars = sqlx::query!(
+        r#"
+        SELECT t.tabular_id,
+               t.typ as "typ: TabularType",
+               n.namespace_name,
+               t.name,
+               t.metadata_location,
+               t.protected,
+               t.deleted_at IS NOT NULL as "deleted!"
+        FROM tabular t
+        INNER JOIN namespace n ON n.namespace_id = t.namespace_id
+        WHERE n.warehouse_id = $1
+        ORDER BY t.tabular_id
+        "#,
+        *warehouse_id,
+    )
+    .fetch_all(&mut *transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error loading tabulars of catalog snapshot"))?;
+
+    transaction
+        .commit()
+        .await
+        .map_err(|e| e.into_error_model("Error committing transaction"))?;
+
+    Ok(Some(CatalogSnapshotState {
+        warehouse: CatalogSnapshotWarehouse {
+            name: warehouse.warehouse_name,
+            storage_profile: warehouse.storage_profile,
+            tabular_expiration_seconds: warehouse.tabular_expiration_seconds,
+            protected: warehouse.protected,
+            disabled_endpoint_groups: warehouse.disabled_endpoint_groups,
+        },
+        task_queue_configs: task_queue_configs
+            .into_iter()
+            .map(|r| (r.queue_name, r.config))
+            .collect(),
+        namespaces: namespaces
+            .into_iter()
+            .map(|r| CatalogSnapshotNamespace {
+                namespace_id: r.namespace_id,
+                name: r.namespace_name,
+                properties: r.properties.0.unwrap_or_default(),
+                protected: r.protected,
+                deleted: r.deleted,
+            })
+            .collect(),
+        tabulars: tabulars
+            .into_iter()
+            .map(|r| CatalogSnapshotTabular {
+                tabular_id: r.tabular_id,
+                typ: r.typ.into(),
+                namespace: r.namespace_name,
+                name: r.name,
+                metadata_location: r.metadata_location,
+                protected: r.protected,
+                deleted: r.deleted,
+            })
+            .collect(),
+        access: None,
+    }))
+}
+
+pub(crate) async fn create_catalog_snapshot<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    snapshot: &CatalogSnapshot,
+    connection: E,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO catalog_snapshot
+            (snapshot_id, warehouse_id, kind, base_snapshot_id, parent_snapshot_id, location, checksum, namespace_count, tabular_count, task_id, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+        "#,
+        snapshot.snapshot_id,
+        *snapshot.warehouse_id,
+        DbCatalogSnapshotKind::from(snapshot.kind) as _,
+        snapshot.base_snapshot_id,
+        snapshot.parent_snapshot_id,
+        snapshot.location,
+        snapshot.checksum,
+        snapshot.namespace_count,
+        snapshot.tabular_count,
+        snapshot.task_id,
+        snapshot.created_at,
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error creating catalog snapshot"))?;
+
+    Ok(())
+}
+
+pub(crate) async fn get_catalog_snapshot<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseId,
+    snapshot_id: Uuid,
+    connection: E,
+) -> Result<Option<CatalogSnapshot>> {
+    let snapshot = sqlx::query_as!(
+        CatalogSnapshotRow,
+        r#"
+        SELECT snapshot_id,
+               warehouse_id,
+               kind as "kind: DbCatalogSnapshotKind",
+               base_snapshot_id,
+               parent_snapshot_id,
+               location,
+               checksum,
+               namespace_count,
+               tabular_count,
+               task_id,
+               created_at
+        FROM catalog_snapshot
+        WHERE warehouse_id = $1 AND snapshot_id = $2
+        "#,
+        *warehouse_id,
+        snapshot_id,
+    )
+    .fetch_optional(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error loading catalog snapshot"))?;
+
+    Ok(snapshot.map(Into::into))
+}
+
+pub(crate) async fn list_catalog_snapshots<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseId,
+    limit: i64,
+    connection: E,
+) -> Result<Vec<CatalogSnapshot>> {
+    let snapshots = sqlx::query_as!(
+        CatalogSnapshotRow,
+        r#"
+        SELECT snapshot_id,
+               warehouse_id,
+               kind as "kind: DbCatalogSnapshotKind",
+               base_snapshot_id,
+               parent_snapshot_id,
+               location,
+               checksum,
+               namespace_count,
+               tabular_count,
+               task_id,
+               created_at
+        FROM catalog_snapshot
+        WHERE warehouse_id = $1
+        ORDER BY created_at DESC, snapshot_id DESC
+        LIMIT $2
+        "#,
+        *warehouse_id,
+        limit,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error listing catalog snapshots"))?;
+
+    Ok(snapshots.into_iter().map(Into::into).collect())
+}
+
+/// Deletes all but the `retain` most recent snapshots of the warehouse.
+/// Snapshots that retained differential snapshots are replayed onto are kept.
+pub(crate) async fn delete_expired_catalog_snapshots<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseId,
+    retain: i64,
+    connection: E,
+) -> Result<Vec<CatalogSnapshot>> {
+    let snapshots = sqlx::query_as!(
+        CatalogSnapshotRow,
+        r#"
+        WITH retained AS (
+            SELECT snapshot_id, base_snapshot_id, created_at
+            FROM catalog_snapshot
+            WHERE warehouse_id = $1
+            ORDER BY created_at DESC, snapshot_id DESC
+            LIMIT $2
+        ),
+        required AS (
+            SELECT created_at FROM retained
+            UNION ALL
+            SELECT b.created_at
+            FROM catalog_snapshot b
+            INNER JOIN retained r ON b.snapshot_id = r.base_snapshot_id
+        )
+        DELETE FROM catalog_snapshot
+        WHERE warehouse_id = $1
+          AND created_at < coalesce((SELECT min(created_at) FROM required), 'infinity')
+        RETURNING snapshot_id,
+                  warehouse_id,
+                  kind as "kind: DbCatalogSnapshotKind",
+                  base_snapshot_id,
+                  parent_snapshot_id,
+                  location,
+                  checksum,
+                  namespace_count,
+                  tabular_count,
+                  task_id,
+                  created_at
+        "#,
+        *warehouse_id,
+        retain,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error deleting expired catalog snapshots"))?;
+
+    Ok(snapshots.into_iter().map(Into::into).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::management::v1::TabularType as ApiTabularType,
+        implementations::postgres::{
+            namespace::tests::initialize_namespace, tabular::table::tests::initialize_table,
+            warehouse::test::initialize_warehouse, CatalogState,
+        },
+    };
+
+    #[sqlx::test]
+    async fn test_catalog_snapshots(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let namespace = iceberg::NamespaceIdent::from_vec(vec!["ns".to_string()]).unwrap();
+        initialize_namespace(state.clone(), warehouse_id, &namespace, None).await;
+        let table = initialize_table(
+            warehouse_id,
+            state.clone(),
+            false,
+            Some(namespace.clone()),
+            Some("tab".to_string()),
+        )
+        .await;
+
+        let catalog_state = load_catalog_snapshot_state(warehouse_id, &state.read_pool())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(catalog_state.namespaces.len(), 1);
+        assert_eq!(catalog_state.namespaces[0].name, vec!["ns".to_string()]);
+        assert_eq!(catalog_state.tabulars.len(), 1);
+        assert_eq!(catalog_state.tabulars[0].tabular_id, *table.table_id);
+        assert_eq!(catalog_state.tabulars[0].typ, ApiTabularType::Table);
+        assert_eq!(catalog_state.tabulars[0].namespace, vec!["ns".to_string()]);
+        assert!(!catalog_state.tabulars[0].deleted);
+        assert!(
+            load_catalog_snapshot_state(WarehouseId::new_random(), &state.read_pool())
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        let mut snapshot_ids = vec![];
+        for i in 0..3 {
+            let snapshot = CatalogSnapshot {
+                snapshot_id: Uuid::now_v7(),
+                warehouse_id,
+                kind: CatalogSnapshotKind::Full,
+                base_snapshot_id: None,
+                parent_snapshot_id: None,
+                location: format!("s3://bucket/_lakekeeper/catalog-snapshots/{i}.json"),
+                checksum: "abc".to_string(),
+                namespace_count: 1,
+                tabular_count: 1,
+                task_id: None,
+                created_at: chrono::Utc::now() + chrono::Duration::seconds(i),
+            };
+            create_catalog_snapshot(&snapshot, &state.write_pool())
+                .await
+                .unwrap();
+            snapshot_ids.push(snapshot.snapshot_id);
+        }
+
+        let snapshots = list_catalog_snapshots(warehouse_id, 10, &state.read_pool())
+            .await
+            .unwrap();
+        assert_eq!(
+            snapshots.iter().map(|s| s.snapshot_id).collect::<Vec<_>>(),
+            snapshot_ids.iter().rev().copied().collect::<Vec<_>>()
+        );
+
+        let expired = delete_expired_catalog_snapshots(warehouse_id, 2, &state.write_pool())
+            .await
+            .unwrap();
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].snapshot_id, snapshot_ids[0]);
+        assert!(
+            get_catalog_snapshot(warehouse_id, snapshot_ids[0], &state.read_pool())
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            get_catalog_snapshot(warehouse_id, snapshot_ids[2], &state.read_pool())
+                .await
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_expiry_keeps_base_of_differential_snapshots(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+
+        // full, full, differential, differential
+        let mut snapshots: Vec<CatalogSnapshot> = vec![];
+        for i in 0..4 {
+            let differential = i >= 2;
+            let snapshot = CatalogSnapshot {
+                snapshot_id: Uuid::now_v7(),
+                warehouse_id,
+                kind: if differential {
+                    CatalogSnapshotKind::Differential
+                } else {
+                    CatalogSnapshotKind::Full
+                },
+                base_snapshot_id: differential.then(|| snapshots[1].snapshot_id),
+                parent_snapshot_id: differential.then(|| snapshots[i - 1].snapshot_id),
+                location: format!("s3://bucket/_lakekeeper/catalog-snapshots/{i}.json"),
+                checksum: "abc".to_string(),
+                namespace_count: 0,
+                tabular_count: 0,
+                task_id: None,
+                created_at: chrono::Utc::now()
+                    + chrono::Duration::seconds(i64::try_from(i).unwrap()),
+            };
+            create_catalog_snapshot(&snapshot, &state.write_pool())
+                .await
+                .unwrap();
+            snapshots.push(snapshot);
+        }
+
+        let expired = delete_expired_catalog_snapshots(warehouse_id, 1, &state.write_pool())
+            .await
+            .unwrap();
+        assert_eq!(
+            expired.iter().map(|s| s.snapshot_id).collect::<Vec<_>>(),
+            vec![snapshots[0].snapshot_id]
+        );
+        let remaining = list_catalog_snapshots(warehouse_id, 10, &state.read_pool())
+            .await
+            .unwrap();
+        assert_eq!(remaining.len(), 3);
+        assert_eq!(remaining[0].kind, CatalogSnapshotKind::Differential);
+        assert_eq!(
+            remaining[0].base_snapshot_id,
+            Some(snapshots[1].snapshot_id)
+        );
+        assert_eq!(
+            remaining[0].parent_snapshot_id,
+            Some(snapshots[2].snapshot_id)
+        );
+        assert_eq!(remaining[2].kind, CatalogSnapshotKind::Full);
+    }
+}
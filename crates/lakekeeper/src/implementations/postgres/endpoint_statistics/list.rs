@@ -144,8 +144,8 @@ fn parse_token(token: &str) -> Result<(chrono::DateTime<Utc>, chrono::Duration),
     // with our TryFrom implementation, which requires an std::error::Error. As a result, we created our own
     // Duration type, RoundTrippableDuration, which wraps iso8601::Duration and implements TryFrom<&str> and Display.
     // This approach ensures compatibility and functionality.
-    let PaginateToken::V1(V1PaginateToken { created_at, id }): PaginateToken<iso8601::Duration> =
-        PaginateToken::try_from(token)?;
+    let V1PaginateToken { created_at, id }: V1PaginateToken<iso8601::Duration> =
+        PaginateToken::try_from(token)?.into_v1()?;
 
     Ok((
         created_at,
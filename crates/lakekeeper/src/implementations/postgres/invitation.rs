@@ -0,0 +1,403 @@
+use chrono::{DateTime, Utc};
+use iceberg_ext::catalog::rest::ErrorModel;
+use uuid::Uuid;
+
+use crate::{
+    api::{
+        iceberg::v1::{PaginationQuery, MAX_PAGE_SIZE},
+        management::v1::invitation::{
+            CreateInvitationRequest, InvitationStatus, ListInvitationsResponse, ProjectInvitation,
+        },
+    },
+    implementations::postgres::{
+        dbutils::DBErrorHandler,
+        pagination::{PaginateToken, V2PaginateToken},
+    },
+    service::{authz::ProjectMembership, Result, RoleId},
+    ProjectId,
+};
+
+#[derive(sqlx::FromRow, Debug)]
+struct InvitationRow {
+    invitation_id: Uuid,
+    project_id: String,
+    email: Option<String>,
+    has_token: bool,
+    membership: ProjectMembership,
+    role_ids: Vec<Uuid>,
+    created_by: Option<String>,
+    created_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+    redeemed_at: Option<DateTime<Utc>>,
+    redeemed_by: Option<String>,
+    revoked_at: Option<DateTime<Utc>>,
+}
+
+impl From<InvitationRow> for ProjectInvitation {
+    fn from(
+        InvitationRow {
+            invitation_id,
+            project_id,
+            email,
+            has_token,
+            membership,
+            role_ids,
+            created_by,
+            created_at,
+            expires_at,
+            redeemed_at,
+            redeemed_by,
+            revoked_at,
+        }: InvitationRow,
+    ) -> Self {
+        Self {
+            invitation_id,
+            project_id: ProjectId::from_db_unchecked(project_id),
+            email,
+            has_token,
+            membership,
+            role_ids: role_ids.into_iter().map(RoleId::new).collect(),
+            status: InvitationStatus::of(expires_at, redeemed_at, revoked_at),
+            created_by,
+            created_at,
+            expires_at,
+            redeemed_at,
+            redeemed_by,
+            revoked_at,
+        }
+    }
+}
+
+pub(crate) async fn create_project_invitation(
+    invitation_id: Uuid,
+    project_id: &ProjectId,
+    request: &CreateInvitationRequest,
+    token_hash: Option<&str>,
+    expires_at: DateTime<Utc>,
+    created_by: Option<&str>,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<ProjectInvitation> {
+    let role_ids = request
+        .role_ids
+        .iter()
+        .map(|id| Uuid::from(*id))
+        .collect::<Vec<_>>();
+
+    let invitation = sqlx::query_as!(
+        InvitationRow,
+        r#"
+        INSERT INTO project_invitation
+            (invitation_id, project_id, email, token_hash, membership, role_ids, created_by, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        RETURNING invitation_id, project_id, email, token_hash IS NOT NULL as "has_token!",
+            membership as "membership: ProjectMembership", role_ids, created_by, created_at,
+            expires_at, redeemed_at, redeemed_by, revoked_at
+        "#,
+        invitation_id,
+        project_id,
+        request.email,
+        token_hash,
+        request.membership as _,
+        &role_ids,
+        created_by,
+        expires_at,
+    )
+    .fetch_one(&mut **transaction)
+    .await
+    .map_err(|e| match e {
+        sqlx::Error::Database(db_error) if db_error.is_foreign_key_violation() => {
+            ErrorModel::not_found(
+                format!("Project {project_id} not found"),
+                "ProjectNotFound",
+                Some(Box::new(db_error)),
+            )
+        }
+        _ => e.into_error_model("Error creating project invitation"),
+    })?;
+
+    Ok(invitation.into())
+}
+
+pub(crate) async fn list_project_invitations<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    project_id: &ProjectId,
+    PaginationQuery {
+        page_size,
+        page_token,
+    }: PaginationQuery,
+    connection: E,
+) -> Result<ListInvitationsResponse> {
+    let page_size = page_size.map_or(MAX_PAGE_SIZE, |i| i.clamp(1, MAX_PAGE_SIZE));
+
+    let token_id: Option<Uuid> = page_token
+        .as_option()
+        .map(PaginateToken::try_from)
+        .transpose()?
+        .map(PaginateToken::into_id)
+        .transpose()?;
+
+    let invitations = sqlx::query_as!(
+        InvitationRow,
+        r#"
+        SELECT invitation_id, project_id, email, token_hash IS NOT NULL as "has_token!",
+            membership as "membership: ProjectMembership", role_ids, created_by, created_at,
+            expires_at, redeemed_at, redeemed_by, revoked_at
+        FROM project_invitation
+        WHERE project_id = $1
+            AND (invitation_id > $2 OR $2 IS NULL)
+        ORDER BY invitation_id ASC
+        LIMIT $3
+        "#,
+        project_id,
+        token_id,
+        page_size,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching project invitations"))?
+    .into_iter()
+    .map(ProjectInvitation::from)
+    .collect::<Vec<_>>();
+
+    let next_page_token = invitations.last().map(|i| {
+        PaginateToken::V2(V2PaginateToken::<Uuid> {
+            id: i.invitation_id,
+        })
+        .to_string()
+    });
+
+    Ok(ListInvitationsResponse {
+        invitations,
+        next_page_token,
+    })
+}
+
+pub(crate) async fn lock_project_invitation(
+    invitation_id: Option<Uuid>,
+    token_hash: Option<&str>,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<Option<ProjectInvitation>> {
+    if invitation_id.is_none() && token_hash.is_none() {
+        return Ok(None);
+    }
+
+    let invitation = sqlx::query_as!(
+        InvitationRow,
+        r#"
+        SELECT invitation_id, project_id, email, token_hash IS NOT NULL as "has_token!",
+            membership as "membership: ProjectMembership", role_ids, created_by, created_at,
+            expires_at, redeemed_at, redeemed_by, revoked_at
+        FROM project_invitation
+        WHERE ($1::UUID IS NULL OR invitation_id = $1)
+            AND ($2::TEXT IS NULL OR token_hash = $2)
+        FOR UPDATE
+        "#,
+        invitation_id,
+        token_hash,
+    )
+    .fetch_optional(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching project invitation"))?;
+
+    Ok(invitation.map(Into::into))
+}
+
+pub(crate) async fn revoke_project_invitation(
+    invitation_id: Uuid,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        UPDATE project_invitation
+        SET revoked_at = now()
+        WHERE invitation_id = $1 AND redeemed_at IS NULL AND revoked_at IS NULL
+        "#,
+        invitation_id,
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error revoking project invitation"))?;
+
+    Ok(())
+}
+
+pub(crate) async fn mark_project_invitation_redeemed(
+    invitation_id: Uuid,
+    redeemed_by: &str,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let redeemed = sqlx::query_scalar!(
+        r#"
+        UPDATE project_invitation
+        SET redeemed_at = now(), redeemed_by = $2
+        WHERE invitation_id = $1
+            AND redeemed_at IS NULL
+            AND revoked_at IS NULL
+            AND expires_at > now()
+        RETURNING invitation_id
+        "#,
+        invitation_id,
+        redeemed_by,
+    )
+    .fetch_optional(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error redeeming project invitation"))?;
+
+    if redeemed.is_none() {
+        return Err(ErrorModel::conflict(
+            format!("Invitation {invitation_id} cannot be redeemed"),
+            "InvitationNotPending",
+            None,
+        )
+        .into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        api::management::v1::invitation::hash_invitation_token,
+        implementations::postgres::{warehouse::test::initialize_project, PostgresTransaction},
+        service::Transaction,
+    };
+
+    fn request(email: Option<&str>) -> CreateInvitationRequest {
+        CreateInvitationRequest {
+            email: email.map(ToString::to_string),
+            create_token: false,
+            membership: ProjectMembership::Select,
+            role_ids: vec![RoleId::new_random()],
+            expires_in_seconds: 3600,
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_redeem_invitation_once(pool: sqlx::PgPool) {
+        let (state, project_id) = initialize_project(pool).await;
+        let token_hash = hash_invitation_token("secret");
+
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let invitation = create_project_invitation(
+            Uuid::now_v7(),
+            &project_id,
+            &request(None),
+            Some(&token_hash),
+            Utc::now() + chrono::Duration::hours(1),
+            Some("oidc~admin"),
+            t.transaction(),
+        )
+        .await
+        .unwrap();
+        assert!(invitation.has_token);
+        assert_eq!(invitation.status, InvitationStatus::Pending);
+        assert_eq!(invitation.membership, ProjectMembership::Select);
+
+        let locked = lock_project_invitation(None, Some(&token_hash), t.transaction())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(locked, invitation);
+        assert!(
+            lock_project_invitation(None, Some("other"), t.transaction())
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        mark_project_invitation_redeemed(invitation.invitation_id, "oidc~bob", t.transaction())
+            .await
+            .unwrap();
+        // Invitations can only be redeemed once
+        assert!(mark_project_invitation_redeemed(
+            invitation.invitation_id,
+            "oidc~carol",
+            t.transaction()
+        )
+        .await
+        .is_err());
+
+        let redeemed =
+            lock_project_invitation(Some(invitation.invitation_id), None, t.transaction())
+                .await
+                .unwrap()
+                .unwrap();
+        assert_eq!(redeemed.status, InvitationStatus::Redeemed);
+        assert_eq!(redeemed.redeemed_by.as_deref(), Some("oidc~bob"));
+        t.commit().await.unwrap();
+    }
+
+    #[sqlx::test]
+    async fn test_revoked_and_expired_invitations_cannot_be_redeemed(pool: sqlx::PgPool) {
+        let (state, project_id) = initialize_project(pool).await;
+
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let revoked = create_project_invitation(
+            Uuid::now_v7(),
+            &project_id,
+            &request(Some("bob@example.com")),
+            None,
+            Utc::now() + chrono::Duration::hours(1),
+            None,
+            t.transaction(),
+        )
+        .await
+        .unwrap();
+        assert!(!revoked.has_token);
+        revoke_project_invitation(revoked.invitation_id, t.transaction())
+            .await
+            .unwrap();
+        assert!(mark_project_invitation_redeemed(
+            revoked.invitation_id,
+            "oidc~bob",
+            t.transaction()
+        )
+        .await
+        .is_err());
+
+        let expired = create_project_invitation(
+            Uuid::now_v7(),
+            &project_id,
+            &request(Some("carol@example.com")),
+            None,
+            Utc::now() - chrono::Duration::seconds(1),
+            None,
+            t.transaction(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(expired.status, InvitationStatus::Expired);
+        assert!(mark_project_invitation_redeemed(
+            expired.invitation_id,
+            "oidc~carol",
+            t.transaction()
+        )
+        .await
+        .is_err());
+
+        let listed = list_project_invitations(
+            &project_id,
+            PaginationQuery::empty(),
+            &mut **t.transaction(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            listed
+                .invitations
+                .iter()
+                .map(|i| i.status)
+                .collect::<Vec<_>>(),
+            vec![InvitationStatus::Revoked, InvitationStatus::Expired]
+        );
+        t.commit().await.unwrap();
+    }
+}
@@ -0,0 +1,386 @@
+use iceberg_ext::catalog::rest::ErrorModel;
+use sqlx::types::Json;
+
+use crate::{
+    implementations::postgres::dbutils::DBErrorHandler as _,
+    service::{
+        labels::{LabelEntity, LabelSettings, Labels},
+        ProjectId, Result, TableId,
+    },
+    WarehouseId,
+};
+
+pub(crate) async fn get_label_settings<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    project_id: &ProjectId,
+    connection: E,
+) -> Result<Option<LabelSettings>> {
+    let settings = sqlx::query_scalar!(
+        r#"
+        SELECT label_settings as "label_settings: Json<LabelSettings>"
+        FROM project
+        WHERE project_id = $1
+        "#,
+        project_id
+    )
+    .fetch_optional(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching label settings of project"))?
+    .map(|Json(settings)| settings);
+
+    Ok(settings)
+}
+
+pub(crate) async fn set_label_settings<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    project_id: &ProjectId,
+    settings: &LabelSettings,
+    connection: E,
+) -> Result<()> {
+    let row_count = sqlx::query!(
+        r#"
+        UPDATE project
+        SET label_settings = $1
+        WHERE project_id = $2
+        "#,
+        Json(settings) as _,
+        project_id
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error setting label settings of project"))?
+    .rows_affected();
+
+    if row_count == 0 {
+        return Err(ErrorModel::not_found(
+            format!("Project {project_id} not found"),
+            "ProjectNotFound",
+            None,
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Labels of an active entity of the warehouse.
+pub(crate) async fn get_labels<'e, 'c: 'e, E: sqlx::Executor<'c, Database = sqlx::Postgres>>(
+    warehouse_id: WarehouseId,
+    entity: LabelEntity,
+    connection: E,
+) -> Result<Option<Labels>> {
+    let labels = match entity {
+        LabelEntity::Warehouse => {
+            sqlx::query_scalar!(
+                r#"
+                SELECT labels as "labels: Json<Labels>"
+                FROM warehouse
+                WHERE warehouse_id = $1
+                "#,
+                *warehouse_id
+            )
+            .fetch_optional(connection)
+            .await
+        }
+        LabelEntity::Namespace(namespace_id) => {
+            sqlx::query_scalar!(
+                r#"
+                SELECT labels as "labels: Json<Labels>"
+                FROM namespace
+                WHERE warehouse_id = $1 AND namespace_id = $2 AND deleted_at IS NULL
+                "#,
+                *warehouse_id,
+                *namespace_id
+            )
+            .fetch_optional(connection)
+            .await
+        }
+        LabelEntity::Table(table_id) => {
+            sqlx::query_scalar!(
+                r#"
+                SELECT t.labels as "labels: Json<Labels>"
+                FROM tabular t
+                INNER JOIN namespace n ON t.namespace_id = n.namespace_id
+                WHERE n.warehouse_id = $1 AND t.tabular_id = $2
+                    AND t.typ = 'table' AND t.deleted_at IS NULL
+                "#,
+                *warehouse_id,
+                *table_id
+            )
+            .fetch_optional(connection)
+            .await
+        }
+    }
+    .map_err(|e| e.into_error_model(format!("Error fetching labels of {}", entity.entity_type())))?
+    .map(|Json(labels)| labels);
+
+    Ok(labels)
+}
+
+/// Replace the labels of an active entity of the warehouse.
+pub(crate) async fn set_labels<'e, 'c: 'e, E: sqlx::Executor<'c, Database = sqlx::Postgres>>(
+    warehouse_id: WarehouseId,
+    entity: LabelEntity,
+    labels: &Labels,
+    connection: E,
+) -> Result<()> {
+    let (result, not_found) = match entity {
+        LabelEntity::Warehouse => (
+            sqlx::query!(
+                r#"
+                UPDATE warehouse
+                SET labels = $2
+                WHERE warehouse_id = $1 AND status = 'active'
+                "#,
+                *warehouse_id,
+                Json(labels) as _,
+            )
+            .execute(connection)
+            .await,
+            ("Warehouse not found", "WarehouseNotFound"),
+        ),
+        LabelEntity::Namespace(namespace_id) => (
+            sqlx::query!(
+                r#"
+                UPDATE namespace
+                SET labels = $3
+                WHERE warehouse_id = $1 AND namespace_id = $2 AND deleted_at IS NULL
+                "#,
+                *warehouse_id,
+                *namespace_id,
+                Json(labels) as _,
+            )
+            .execute(connection)
+            .await,
+            ("Namespace not found", "NamespaceNotFound"),
+        ),
+        LabelEntity::Table(table_id) => (
+            sqlx::query!(
+                r#"
+                UPDATE tabular t
+                SET labels = $3
+                FROM namespace n
+                WHERE t.namespace_id = n.namespace_id
+                    AND n.warehouse_id = $1 AND t.tabular_id = $2
+                    AND t.typ = 'table' AND t.deleted_at IS NULL
+                "#,
+                *warehouse_id,
+                *table_id,
+                Json(labels) as _,
+            )
+            .execute(connection)
+            .await,
+            ("Table not found", "TableNotFound"),
+        ),
+    };
+    let row_count = result
+        .map_err(|e| {
+            e.into_error_model(format!("Error setting labels of {}", entity.entity_type()))
+        })?
+        .rows_affected();
+
+    if row_count == 0 {
+        let (message, r#type) = not_found;
+        return Err(ErrorModel::not_found(message, r#type, None).into());
+    }
+
+    Ok(())
+}
+
+/// Labels of a table merged with the labels of its warehouse and all its ancestor namespaces.
+/// If a key is set on several levels, the value of the innermost entity wins.
+pub(crate) async fn get_effective_table_labels<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseId,
+    table_id: TableId,
+    connection: E,
+) -> Result<Labels> {
+    let labels = sqlx::query_scalar!(
+        r#"
+        SELECT jsonb_object_agg(l.key, l.value ORDER BY e.depth) as "labels: Json<Labels>"
+        FROM (
+            SELECT 0 AS depth, w.labels
+            FROM warehouse w
+            WHERE w.warehouse_id = $1
+            UNION ALL
+            SELECT cardinality(pn.namespace_name), pn.labels
+            FROM tabular t
+            INNER JOIN namespace n ON t.namespace_id = n.namespace_id
+            INNER JOIN namespace pn ON pn.warehouse_id = n.warehouse_id
+                AND pn.namespace_name = n.namespace_name[1:cardinality(pn.namespace_name)]
+                AND (pn.deleted_at IS NULL OR pn.namespace_id = n.namespace_id)
+            WHERE n.warehouse_id = $1 AND t.tabular_id = $2
+            UNION ALL
+            SELECT cardinality(n.namespace_name) + 1, t.labels
+            FROM tabular t
+            INNER JOIN namespace n ON t.namespace_id = n.namespace_id
+            WHERE n.warehouse_id = $1 AND t.tabular_id = $2
+        ) e
+        CROSS JOIN LATERAL jsonb_each_text(e.labels) l
+        "#,
+        *warehouse_id,
+        *table_id
+    )
+    .fetch_one(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching effective labels of table"))?;
+
+    Ok(labels.map(|Json(labels)| labels).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        implementations::postgres::{
+            namespace::tests::initialize_namespace,
+            tabular::table::tests::initialize_table,
+            warehouse::{get_warehouse, test::initialize_warehouse},
+            CatalogState,
+        },
+        service::labels::LabelPolicy,
+    };
+
+    fn labels(pairs: &[(&str, &str)]) -> Labels {
+        pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect()
+    }
+
+    #[sqlx::test]
+    async fn test_label_settings(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let mut transaction = pool.begin().await.unwrap();
+        let project_id = get_warehouse(warehouse_id, &mut transaction)
+            .await
+            .unwrap()
+            .unwrap()
+            .project_id;
+        transaction.commit().await.unwrap();
+
+        assert_eq!(
+            get_label_settings(&project_id, &pool).await.unwrap(),
+            Some(LabelSettings::default())
+        );
+        let settings = LabelSettings {
+            definitions: vec![],
+            policies: vec![LabelPolicy {
+                key: "classification".to_string(),
+                values: vec!["pii".to_string()],
+                allowed_roles: vec![],
+            }],
+        };
+        set_label_settings(&project_id, &settings, &pool)
+            .await
+            .unwrap();
+        assert_eq!(
+            get_label_settings(&project_id, &pool).await.unwrap(),
+            Some(settings.clone())
+        );
+
+        let unknown = ProjectId::new_random();
+        assert_eq!(get_label_settings(&unknown, &pool).await.unwrap(), None);
+        let e = set_label_settings(&unknown, &settings, &pool)
+            .await
+            .unwrap_err();
+        assert_eq!(e.error.code, 404);
+    }
+
+    #[sqlx::test]
+    async fn test_effective_table_labels(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let parent = iceberg::NamespaceIdent::new("sales".to_string());
+        let (parent_id, _) = initialize_namespace(state.clone(), warehouse_id, &parent, None).await;
+        let child =
+            iceberg::NamespaceIdent::from_vec(vec!["sales".to_string(), "eu".to_string()]).unwrap();
+        let (child_id, _) = initialize_namespace(state.clone(), warehouse_id, &child, None).await;
+        let table = initialize_table(
+            warehouse_id,
+            state.clone(),
+            false,
+            Some(child.clone()),
+            Some("orders".to_string()),
+        )
+        .await;
+
+        assert_eq!(
+            get_effective_table_labels(warehouse_id, table.table_id, &pool)
+                .await
+                .unwrap(),
+            Labels::new()
+        );
+
+        set_labels(
+            warehouse_id,
+            LabelEntity::Warehouse,
+            &labels(&[("env", "prod"), ("team", "platform")]),
+            &pool,
+        )
+        .await
+        .unwrap();
+        set_labels(
+            warehouse_id,
+            LabelEntity::Namespace(parent_id),
+            &labels(&[("team", "sales"), ("classification", "internal")]),
+            &pool,
+        )
+        .await
+        .unwrap();
+        set_labels(
+            warehouse_id,
+            LabelEntity::Namespace(child_id),
+            &labels(&[("region", "eu")]),
+            &pool,
+        )
+        .await
+        .unwrap();
+        set_labels(
+            warehouse_id,
+            LabelEntity::Table(table.table_id),
+            &labels(&[("classification", "pii")]),
+            &pool,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            get_labels(warehouse_id, LabelEntity::Table(table.table_id), &pool)
+                .await
+                .unwrap(),
+            Some(labels(&[("classification", "pii")]))
+        );
+        assert_eq!(
+            get_effective_table_labels(warehouse_id, table.table_id, &pool)
+                .await
+                .unwrap(),
+            labels(&[
+                ("classification", "pii"),
+                ("env", "prod"),
+                ("region", "eu"),
+                ("team", "sales")
+            ])
+        );
+
+        let unknown = LabelEntity::Table(TableId::new_random());
+        assert_eq!(
+            get_labels(warehouse_id, unknown, &pool).await.unwrap(),
+            None
+        );
+        let e = set_labels(warehouse_id, unknown, &Labels::new(), &pool)
+            .await
+            .unwrap_err();
+        assert_eq!(e.error.r#type, "TableNotFound");
+    }
+}
@@ -0,0 +1,340 @@
+//! Golden database states for migration tests.
+//!
+//! Every directory in `tests/migration-goldens` is named after the last migration of a released
+//! schema and contains:
+//! - `state.sql`: Data inserted into a database migrated up to that version. Ids and timestamps
+//!   are fixed so that the resulting state is deterministic.
+//! - `expected.json`: What the list functions of the catalog return after migrating the state
+//!   to the latest schema.
+//!
+//! Each listing is fetched once in a single page and once page by page with a page size of 1.
+//! Both must match, which catches broken pagination tokens for migrated rows.
+//! Run the tests with `UPDATE_MIGRATION_GOLDENS=1` to rewrite `expected.json` after intentional
+//! changes and review the diff.
+use std::{collections::VecDeque, future::Future, path::PathBuf};
+
+use serde_json::{json, Value};
+use sqlx::Executor;
+
+use super::{migrate, migrate_until};
+use crate::{
+    api::{
+        iceberg::v1::{PageToken, PaginationQuery},
+        management::v1::ListSort,
+    },
+    implementations::postgres::{CatalogState, PostgresCatalog, PostgresTransaction},
+    service::{
+        Catalog, ListFlags, ListNamespacesQuery, NamespaceIdent, TabularIdentOwned, Transaction,
+        WarehouseStatus,
+    },
+};
+
+const GOLDENS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/migration-goldens");
+
+/// Loads `state.sql` of the golden into a database at the golden's schema version
+/// and migrates it to the latest schema.
+async fn load_golden(pool: &sqlx::PgPool, version: i64) -> CatalogState {
+    migrate_until(pool, Some(version)).await.unwrap();
+    let state_sql = std::fs::read_to_string(golden_dir(version).join("state.sql")).unwrap();
+    // `execute` on a plain string uses the simple query protocol, which allows multiple statements
+    pool.execute(state_sql.as_str()).await.unwrap();
+    migrate(pool).await.unwrap();
+    CatalogState::from_pools(pool.clone(), pool.clone())
+}
+
+fn golden_dir(version: i64) -> PathBuf {
+    PathBuf::from(GOLDENS_DIR).join(version.to_string())
+}
+
+fn assert_golden(version: i64, snapshot: &Value) {
+    let path = golden_dir(version).join("expected.json");
+    let actual = format!("{}\n", serde_json::to_string_pretty(snapshot).unwrap());
+    if std::env::var("UPDATE_MIGRATION_GOLDENS").is_ok_and(|v| v == "1") {
+        std::fs::write(&path, &actual).unwrap();
+        return;
+    }
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|e| {
+        panic!(
+            "Failed to read {}: {e}. Run with UPDATE_MIGRATION_GOLDENS=1 to create it.",
+            path.display()
+        )
+    });
+    assert_eq!(
+        expected,
+        actual,
+        "State migrated from {version} differs from {}",
+        path.display()
+    );
+}
+
+/// Fetches all pages of a listing. `fetch` returns the entries of a page
+/// and the token of the next page.
+async fn fetch_pages<F, Fut>(page_size: Option<i64>, mut fetch: F) -> Vec<Value>
+where
+    F: FnMut(PaginationQuery) -> Fut,
+    Fut: Future<Output = (Vec<Value>, Option<String>)>,
+{
+    let mut entries = vec![];
+    let mut page_token = PageToken::NotSpecified;
+    loop {
+        let (page, next_page_token) = fetch(PaginationQuery {
+            page_token: page_token.clone(),
+            page_size,
+        })
+        .await;
+        if page.is_empty() {
+            break;
+        }
+        entries.extend(page);
+        match next_page_token {
+            Some(token) => page_token = PageToken::Present(token),
+            None => break,
+        }
+    }
+    entries
+}
+
+/// Fetches a listing in a single page and page by page, and asserts that both are equal.
+async fn list_paginated<F, Fut>(what: &str, mut fetch: F) -> Vec<Value>
+where
+    F: FnMut(PaginationQuery) -> Fut,
+    Fut: Future<Output = (Vec<Value>, Option<String>)>,
+{
+    let single_page = fetch_pages(None, &mut fetch).await;
+    let paged = fetch_pages(Some(1), &mut fetch).await;
+    assert_eq!(
+        single_page, paged,
+        "Listing {what} page by page differs from a single page"
+    );
+    single_page
+}
+
+async fn snapshot(state: CatalogState) -> Value {
+    let mut t = PostgresTransaction::begin_read(state.clone())
+        .await
+        .unwrap();
+    let mut projects = PostgresCatalog::list_projects(None, t.transaction())
+        .await
+        .unwrap();
+    projects.sort_by(|a, b| a.project_id.cmp(&b.project_id));
+
+    let mut warehouses = vec![];
+    for project in &projects {
+        let mut project_warehouses = PostgresCatalog::list_warehouses(
+            &project.project_id,
+            Some(vec![WarehouseStatus::Active, WarehouseStatus::Inactive]),
+            t.transaction(),
+        )
+        .await
+        .unwrap();
+        project_warehouses.sort_by_key(|w| w.id);
+        warehouses.extend(project_warehouses);
+    }
+    t.commit().await.unwrap();
+
+    let active_warehouses = warehouses
+        .iter()
+        .filter(|w| w.status == WarehouseStatus::Active)
+        .map(|w| w.id)
+        .collect::<Vec<_>>();
+
+    let mut namespaces = vec![];
+    for warehouse_id in &active_warehouses {
+        let mut parents: VecDeque<Option<NamespaceIdent>> = VecDeque::from([None]);
+        let mut warehouse_namespaces = vec![];
+        while let Some(parent) = parents.pop_front() {
+            let children = list_paginated("namespaces", |q| {
+                let state = state.clone();
+                let parent = parent.clone();
+                async move {
+                    let mut t = PostgresTransaction::begin_read(state).await.unwrap();
+                    let page = PostgresCatalog::list_namespaces(
+                        *warehouse_id,
+                        &ListNamespacesQuery {
+                            page_token: q.page_token,
+                            page_size: q.page_size,
+                            parent,
+                            return_uuids: true,
+                            return_protection_status: true,
+                        },
+                        t.transaction(),
+                    )
+                    .await
+                    .unwrap();
+                    t.commit().await.unwrap();
+                    mapping_page(page.into_iter_with_page_tokens().map(|(id, info, token)| {
+                        let entry = json!({
+                            "warehouse-id": warehouse_id,
+                            "namespace-id": id,
+                            "namespace": info.namespace_ident.clone().inner(),
+                            "protected": info.protected,
+                        });
+                        (entry, token)
+                    }))
+                }
+            })
+            .await;
+            for child in &children {
+                let ident = serde_json::from_value::<Vec<String>>(child["namespace"].clone())
+                    .map(NamespaceIdent::from_vec)
+                    .unwrap()
+                    .unwrap();
+                parents.push_back(Some(ident));
+            }
+            warehouse_namespaces.extend(children);
+        }
+        warehouse_namespaces.sort_by(|a, b| {
+            a["namespace-id"]
+                .as_str()
+                .unwrap()
+                .cmp(b["namespace-id"].as_str().unwrap())
+        });
+        namespaces.extend(warehouse_namespaces);
+    }
+
+    let mut tabulars = vec![];
+    for warehouse_id in &active_warehouses {
+        let warehouse_tabulars = list_paginated("tabulars", |q| {
+            let state = state.clone();
+            async move {
+                let mut t = PostgresTransaction::begin_read(state).await.unwrap();
+                let page = PostgresCatalog::list_tabulars(
+                    *warehouse_id,
+                    None,
+                    ListFlags::all(),
+                    t.transaction(),
+                    q,
+                )
+                .await
+                .unwrap();
+                t.commit().await.unwrap();
+                mapping_page(page.into_iter_with_page_tokens().map(|(id, info, token)| {
+                    let (typ, ident) = match &info.table_ident {
+                        TabularIdentOwned::Table(ident) => ("table", ident),
+                        TabularIdentOwned::View(ident) => ("view", ident),
+                    };
+                    let entry = json!({
+                        "warehouse-id": warehouse_id,
+                        "tabular-id": *id,
+                        "type": typ,
+                        "namespace": ident.namespace.clone().inner(),
+                        "name": ident.name,
+                        "protected": info.protected,
+                        "external": info.external,
+                        "deleted": info.deletion_details.is_some(),
+                        "expiration-task-id": info
+                            .deletion_details
+                            .as_ref()
+                            .map(|d| d.expiration_task_id),
+                    });
+                    (entry, token)
+                }))
+            }
+        })
+        .await;
+        tabulars.extend(warehouse_tabulars);
+    }
+
+    let mut roles = vec![];
+    for project in &projects {
+        let project_roles = list_paginated("roles", |q| {
+            let state = state.clone();
+            let project_id = project.project_id.clone();
+            async move {
+                let page =
+                    PostgresCatalog::list_roles(Some(project_id), None, None, None, q, state)
+                        .await
+                        .unwrap();
+                let entries = page
+                    .roles
+                    .into_iter()
+                    .map(|r| {
+                        json!({
+                            "id": r.id,
+                            "name": r.name,
+                            "description": r.description,
+                            "project-id": r.project_id,
+                        })
+                    })
+                    .collect();
+                (entries, page.next_page_token)
+            }
+        })
+        .await;
+        roles.extend(project_roles);
+    }
+
+    let users = list_paginated("users", |q| {
+        let state = state.clone();
+        async move {
+            let page =
+                PostgresCatalog::list_user(None, None, None, None, ListSort::default(), q, state)
+                    .await
+                    .unwrap();
+            let entries = page
+                .users
+                .into_iter()
+                .map(|u| {
+                    json!({
+                        "id": u.id,
+                        "name": u.name,
+                        "email": u.email,
+                        "user-type": u.user_type,
+                        "last-updated-with": u.last_updated_with,
+                    })
+                })
+                .collect();
+            (entries, page.next_page_token)
+        }
+    })
+    .await;
+
+    json!({
+        "projects": projects
+            .iter()
+            .map(|p| json!({"project-id": p.project_id, "project-name": p.name}))
+            .collect::<Vec<_>>(),
+        "warehouses": warehouses
+            .iter()
+            .map(|w| {
+                json!({
+                    "id": w.id,
+                    "name": w.name,
+                    "project-id": w.project_id,
+                    "status": w.status,
+                    "delete-profile": w.tabular_delete_profile,
+                    "protected": w.protected,
+                    "storage-type": serde_json::to_value(&w.storage_profile).unwrap()["type"],
+                    "disabled-endpoint-groups": w.disabled_endpoint_groups,
+                    "client-workarounds": w.client_workarounds,
+                    "default-branch": w.default_branch,
+                    "metadata-push-enabled": w.metadata_push_enabled,
+                })
+            })
+            .collect::<Vec<_>>(),
+        "namespaces": namespaces,
+        "tabulars": tabulars,
+        "roles": roles,
+        "users": users,
+    })
+}
+
+/// Entries of a page of a [`PaginatedMapping`](crate::api::iceberg::v1::PaginatedMapping)
+/// and the token of the next page, which is the token of the last entry.
+fn mapping_page(entries: impl Iterator<Item = (Value, String)>) -> (Vec<Value>, Option<String>) {
+    let mut next_page_token = None;
+    let entries = entries
+        .map(|(entry, token)| {
+            next_page_token = Some(token);
+            entry
+        })
+        .collect();
+    (entries, next_page_token)
+}
+
+#[sqlx::test(migrations = false)]
+async fn test_migrate_golden_20250623114333(pool: sqlx::PgPool) {
+    let version = 20_250_623_114_333;
+    let state = load_golden(&pool, version).await;
+    assert_golden(version, &snapshot(state).await);
+}
@@ -13,17 +13,29 @@ use sqlx::{
 
 use crate::{
     implementations::postgres::{
-        migrations::split_table_metadata::SplitTableMetadataHook, CatalogState, PostgresTransaction,
+        migrations::split_table_metadata::SplitTableMetadataHook,
+        server_event::record_server_event, CatalogState, PostgresTransaction,
+    },
+    service::{
+        server_events::{ServerEvent, ServerEventType},
+        Transaction,
     },
-    service::Transaction,
 };
 
+#[cfg(test)]
+mod golden;
 mod patch_migration_hash;
 mod split_table_metadata;
 
 /// # Errors
 /// Returns an error if the migration fails.
 pub async fn migrate(pool: &sqlx::PgPool) -> anyhow::Result<()> {
+    migrate_until(pool, None).await
+}
+
+/// Applies all migrations up to and including `last_version`, or all migrations if `None`.
+/// Stopping early is only used to set up databases of older schema versions in tests.
+async fn migrate_until(pool: &sqlx::PgPool, last_version: Option<i64>) -> anyhow::Result<()> {
     let migrator = sqlx::migrate!();
     let mut data_migration_hooks = get_data_migrations();
     let mut sha_patches = get_changed_migration_ids();
@@ -45,6 +57,7 @@ pub async fn migrate(pool: &sqlx::PgPool) -> anyhow::Result<()> {
     }
 
     let applied_migrations = run_checks(&migrator, transaction).await?;
+    let mut newly_applied = vec![];
 
     for migration in migrator.iter() {
         tracing::info!(%migration.version, %migration.description, "Current migration");
@@ -54,6 +67,9 @@ pub async fn migrate(pool: &sqlx::PgPool) -> anyhow::Result<()> {
         if migration.migration_type.is_down_migration() {
             continue;
         }
+        if last_version.is_some_and(|last| migration.version > last) {
+            break;
+        }
 
         if let Some(applied_migration) = applied_migrations.get(&migration.version) {
             if migration.checksum != applied_migration.checksum {
@@ -73,6 +89,7 @@ pub async fn migrate(pool: &sqlx::PgPool) -> anyhow::Result<()> {
         } else {
             transaction.apply(&migration).await?;
             tracing::info!(%migration.version, "Applying migration");
+            newly_applied.push((migration.version, migration.description.to_string()));
             if let Some(hook) = data_migration_hooks.remove(&migration.version) {
                 tracing::info!(%migration.version, "Running data migration {}", hook.name());
                 hook.apply(transaction).await?;
@@ -83,6 +100,21 @@ pub async fn migrate(pool: &sqlx::PgPool) -> anyhow::Result<()> {
         }
     }
 
+    // Recorded after all migrations, as earlier migrations predate the event table
+    if last_version.is_some() {
+        newly_applied.clear();
+    }
+    for (version, description) in newly_applied {
+        let event = ServerEvent::new(
+            ServerEventType::MigrationApplied,
+            None,
+            serde_json::json!({ "version": version, "description": description }),
+        );
+        record_server_event(&event, &mut **transaction)
+            .await
+            .map_err(|e| anyhow!(e.error))?;
+    }
+
     // unlock the migrator to allow other migrators to run
     // but do nothing as we already migrated
     if locking {
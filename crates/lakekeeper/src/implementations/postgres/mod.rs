@@ -1,14 +1,29 @@
+mod announcement;
 mod bootstrap;
 mod catalog;
+mod catalog_import;
+mod catalog_snapshot;
 pub(crate) mod dbutils;
 pub mod endpoint_statistics;
+mod invitation;
+mod label;
 pub mod migrations;
 pub(crate) mod namespace;
 mod pagination;
+mod recertification;
+mod referenced_files;
+mod request_log;
 pub(crate) mod role;
+mod s3_sign_audit;
+mod search;
 pub(crate) mod secrets;
+mod server_event;
+mod storage_intent;
 pub mod tabular;
+mod task_dead_letter;
 pub mod task_queues;
+mod task_schedules;
+mod usage;
 pub(crate) mod user;
 pub(crate) mod warehouse;
 
@@ -66,6 +81,7 @@ pub struct PostgresTransaction {
 impl crate::service::Transaction<CatalogState> for PostgresTransaction {
     type Transaction<'a> = &'a mut sqlx::Transaction<'static, sqlx::Postgres>;
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn begin_write(db_state: CatalogState) -> Result<Self> {
         let transaction = db_state
             .write_pool()
@@ -76,6 +92,7 @@ impl crate::service::Transaction<CatalogState> for PostgresTransaction {
         Ok(Self { transaction })
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn begin_read(db_state: CatalogState) -> Result<Self> {
         let mut transaction = db_state
             .read_pool()
@@ -92,6 +109,7 @@ impl crate::service::Transaction<CatalogState> for PostgresTransaction {
         Ok(Self { transaction })
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn commit(self) -> Result<()> {
         self.transaction
             .commit()
@@ -100,6 +118,7 @@ impl crate::service::Transaction<CatalogState> for PostgresTransaction {
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(db.system = "postgresql"))]
     async fn rollback(self) -> Result<()> {
         self.transaction
             .rollback()
@@ -1,6 +1,5 @@
 use std::{collections::HashMap, ops::Deref};
 
-use chrono::Utc;
 use http::StatusCode;
 use iceberg_ext::catalog::rest::IcebergErrorResponse;
 use itertools::{izip, Itertools};
@@ -15,7 +14,7 @@ use crate::{
     },
     catalog::namespace::MAX_NAMESPACE_DEPTH,
     implementations::postgres::{
-        pagination::{PaginateToken, V1PaginateToken},
+        pagination::{PaginateToken, V2PaginateToken},
         tabular::TabularType,
     },
     service::{
@@ -41,6 +40,7 @@ pub(crate) async fn get_namespace(
         FROM namespace n
         INNER JOIN warehouse w ON n.warehouse_id = w.warehouse_id
         WHERE n.warehouse_id = $1 AND n.namespace_id = $2
+        AND n.deleted_at IS NULL
         AND w.status = 'active'
         "#,
         *warehouse_id,
@@ -91,95 +91,85 @@ pub(crate) async fn list_namespaces(
     let parent = parent
         .as_ref()
         .and_then(|p| if p.is_empty() { None } else { Some(p.clone()) });
-    let token = page_token
+    let token_id: Option<Uuid> = page_token
         .as_option()
         .map(PaginateToken::try_from)
+        .transpose()?
+        .map(PaginateToken::into_id)
         .transpose()?;
 
-    let (token_ts, token_id) = token
-        .as_ref()
-        .map(
-            |PaginateToken::V1(V1PaginateToken { created_at, id }): &PaginateToken<Uuid>| {
-                (created_at, id)
-            },
-        )
-        .unzip();
-
-    let namespaces: Vec<(Uuid, Vec<String>, chrono::DateTime<Utc>, bool)> =
-        if let Some(parent) = parent {
-            // If it doesn't fit in a i32 it is way too large. Validation would have failed
-            // already in the catalog.
-            let parent_len: i32 = parent.len().try_into().unwrap_or(MAX_NAMESPACE_DEPTH + 1);
-
-            // Namespace name field is an array.
-            // Get all namespaces where the "name" array has
-            // length(parent) + 1 elements, and the first length(parent)
-            // elements are equal to parent.
-            sqlx::query!(
-                r#"
+    let namespaces: Vec<(Uuid, Vec<String>, bool)> = if let Some(parent) = parent {
+        // If it doesn't fit in a i32 it is way too large. Validation would have failed
+        // already in the catalog.
+        let parent_len: i32 = parent.len().try_into().unwrap_or(MAX_NAMESPACE_DEPTH + 1);
+
+        // Namespace name field is an array.
+        // Get all namespaces where the "name" array has
+        // length(parent) + 1 elements, and the first length(parent)
+        // elements are equal to parent.
+        sqlx::query!(
+            r#"
             SELECT
                 n.namespace_id,
                 "namespace_name" as "namespace_name: Vec<String>",
-                n.created_at,
                 n.protected
             FROM namespace n
             INNER JOIN warehouse w ON n.warehouse_id = w.warehouse_id
             WHERE n.warehouse_id = $1
             AND w.status = 'active'
+            AND n.deleted_at IS NULL
             AND array_length("namespace_name", 1) = $2 + 1
             AND "namespace_name"[1:$2] = $3
             --- PAGINATION
-            AND ((n.created_at > $4 OR $4 IS NULL) OR (n.created_at = $4 AND n.namespace_id > $5))
-            ORDER BY n.created_at, n.namespace_id ASC
-            LIMIT $6
+            AND (n.namespace_id > $4 OR $4 IS NULL)
+            ORDER BY n.namespace_id ASC
+            LIMIT $5
             "#,
-                *warehouse_id,
-                parent_len,
-                &*parent,
-                token_ts,
-                token_id,
-                page_size
-            )
-            .fetch_all(&mut **transaction)
-            .await
-            .map_err(|e| e.into_error_model("Error fetching Namespace"))?
-            .into_iter()
-            .map(|r| (r.namespace_id, r.namespace_name, r.created_at, r.protected))
-            .collect()
-        } else {
-            sqlx::query!(
-                r#"
+            *warehouse_id,
+            parent_len,
+            &*parent,
+            token_id,
+            page_size
+        )
+        .fetch_all(&mut **transaction)
+        .await
+        .map_err(|e| e.into_error_model("Error fetching Namespace"))?
+        .into_iter()
+        .map(|r| (r.namespace_id, r.namespace_name, r.protected))
+        .collect()
+    } else {
+        sqlx::query!(
+            r#"
             SELECT
                 n.namespace_id,
                 "namespace_name" as "namespace_name: Vec<String>",
-                n.created_at,
                 n.protected
             FROM namespace n
             INNER JOIN warehouse w ON n.warehouse_id = w.warehouse_id
             WHERE n.warehouse_id = $1
             AND array_length("namespace_name", 1) = 1
             AND w.status = 'active'
-            AND ((n.created_at > $2 OR $2 IS NULL) OR (n.created_at = $2 AND n.namespace_id > $3))
-            ORDER BY n.created_at, n.namespace_id ASC
-            LIMIT $4
+            AND n.deleted_at IS NULL
+            AND (n.namespace_id > $2 OR $2 IS NULL)
+            ORDER BY n.namespace_id ASC
+            LIMIT $3
             "#,
-                *warehouse_id,
-                token_ts,
-                token_id,
-                page_size
-            )
-            .fetch_all(&mut **transaction)
-            .await
-            .map_err(|e| e.into_error_model("Error fetching Namespace"))?
-            .into_iter()
-            .map(|r| (r.namespace_id, r.namespace_name, r.created_at, r.protected))
-            .collect()
-        };
+            *warehouse_id,
+            token_id,
+            page_size
+        )
+        .fetch_all(&mut **transaction)
+        .await
+        .map_err(|e| e.into_error_model("Error fetching Namespace"))?
+        .into_iter()
+        .map(|r| (r.namespace_id, r.namespace_name, r.protected))
+        .collect()
+    };
 
     // Convert Vec<Vec<String>> to Vec<NamespaceIdent>
     let mut namespace_map: PaginatedMapping<NamespaceId, NamespaceInfo> =
         PaginatedMapping::with_capacity(namespaces.len());
-    for ns_result in namespaces.into_iter().map(|(id, n, ts, protected)| {
+    for ns_result in namespaces.into_iter().map(|(id, n, protected)| {
         NamespaceIdent::from_vec(n.clone())
             .map_err(|e| {
                 IcebergErrorResponse::from(ErrorModel::internal(
@@ -195,19 +185,14 @@ pub(crate) async fn list_namespaces(
                         namespace_ident: n,
                         protected,
                     },
-                    ts,
                 )
             })
     }) {
-        let (id, ns, created_at) = ns_result?;
+        let (id, ns) = ns_result?;
         namespace_map.insert(
             id,
             ns,
-            PaginateToken::V1(V1PaginateToken {
-                id: *id,
-                created_at,
-            })
-            .to_string(),
+            PaginateToken::V2(V2PaginateToken { id: *id }).to_string(),
         );
     }
 
@@ -310,6 +295,7 @@ pub(crate) async fn namespace_to_id(
         FROM namespace n
         INNER JOIN warehouse w ON n.warehouse_id = w.warehouse_id
         WHERE n.warehouse_id = $1 AND namespace_name = $2
+        AND n.deleted_at IS NULL
         AND w.status = 'active'
         "#,
         *warehouse_id,
@@ -330,7 +316,7 @@ pub(crate) async fn namespace_to_id(
 }
 
 #[allow(clippy::too_many_lines)]
-pub(crate) async fn drop_namespace(
+async fn validate_namespace_drop(
     warehouse_id: WarehouseId,
     namespace_id: NamespaceId,
     NamespaceDropFlags {
@@ -344,16 +330,16 @@ pub(crate) async fn drop_namespace(
         WITH namespace_info AS (
             SELECT namespace_name, protected
             FROM namespace
-            WHERE warehouse_id = $1 AND namespace_id = $2
+            WHERE warehouse_id = $1 AND namespace_id = $2 AND deleted_at IS NULL
         ),
         child_namespaces AS (
             SELECT n.protected, n.namespace_id
             FROM namespace n
             INNER JOIN namespace_info ni ON n.namespace_name[1:array_length(ni.namespace_name, 1)] = ni.namespace_name
-            WHERE n.warehouse_id = $1 AND n.namespace_id != $2
+            WHERE n.warehouse_id = $1 AND n.namespace_id != $2 AND n.deleted_at IS NULL
         ),
         tabulars AS (
-            SELECT ta.tabular_id, fs_location, fs_protocol, ta.typ, protected, deleted_at
+            SELECT ta.tabular_id, fs_location, fs_protocol, ta.typ, protected, deleted_at, external
             FROM tabular ta
             WHERE namespace_id = $2 AND metadata_location IS NOT NULL OR (namespace_id = ANY (SELECT namespace_id FROM child_namespaces))
         ),
@@ -369,9 +355,10 @@ pub(crate) async fn drop_namespace(
             ARRAY(SELECT tabular_id FROM tabulars where deleted_at is NULL) AS "child_tabulars!",
             ARRAY(SELECT tabular_id FROM tabulars where deleted_at is not NULL) AS "child_tabulars_deleted!",
             ARRAY(SELECT namespace_id FROM child_namespaces) AS "child_namespaces!",
-            ARRAY(SELECT fs_protocol FROM tabulars) AS "child_tabular_fs_protocol!",
-            ARRAY(SELECT fs_location FROM tabulars) AS "child_tabular_fs_location!",
-            ARRAY(SELECT typ FROM tabulars) AS "child_tabular_typ!: Vec<TabularType>",
+            ARRAY(SELECT fs_protocol FROM tabulars where deleted_at is NULL) AS "child_tabular_fs_protocol!",
+            ARRAY(SELECT fs_location FROM tabulars where deleted_at is NULL) AS "child_tabular_fs_location!",
+            ARRAY(SELECT typ FROM tabulars where deleted_at is NULL) AS "child_tabular_typ!: Vec<TabularType>",
+            ARRAY(SELECT tabular_id FROM tabulars where deleted_at is NULL AND external) AS "child_external_tabulars!",
             ARRAY(SELECT task_id FROM tasks) AS "child_tabular_task_id!: Vec<Uuid>"
 "#,
         *warehouse_id,
@@ -432,6 +419,43 @@ pub(crate) async fn drop_namespace(
         );
     }
 
+    Ok(NamespaceDropInfo {
+        child_namespaces: info.child_namespaces.into_iter().map(Into::into).collect(),
+        child_tables: izip!(
+            info.child_tabulars,
+            info.child_tabular_fs_protocol,
+            info.child_tabular_fs_location,
+            info.child_tabular_typ
+        )
+        .map(|(id, protocol, fs_location, typ)| {
+            (
+                match typ {
+                    TabularType::Table => TabularId::Table(id),
+                    TabularType::View => TabularId::View(id),
+                },
+                join_location(protocol.as_str(), fs_location.as_str()),
+            )
+        })
+        .collect_vec(),
+        external_tables: info
+            .child_external_tabulars
+            .into_iter()
+            .map(TabularId::Table)
+            .collect(),
+        open_tasks: info
+            .child_tabular_task_id
+            .into_iter()
+            .map(TaskId::from)
+            .collect(),
+    })
+}
+
+async fn delete_namespaces(
+    warehouse_id: WarehouseId,
+    namespace_id: NamespaceId,
+    child_namespaces: &[NamespaceId],
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
     // Return 404 not found if namespace does not exist
     let record = sqlx::query!(
         r#"
@@ -444,7 +468,7 @@ pub(crate) async fn drop_namespace(
             )
         "#,
         *warehouse_id,
-        &info.child_namespaces,
+        &child_namespaces.iter().map(|id| **id).collect_vec(),
         *namespace_id,
     )
     .execute(&mut **transaction)
@@ -474,30 +498,294 @@ pub(crate) async fn drop_namespace(
         .into());
     }
 
-    Ok(NamespaceDropInfo {
-        child_namespaces: info.child_namespaces.into_iter().map(Into::into).collect(),
-        child_tables: izip!(
-            info.child_tabulars,
-            info.child_tabular_fs_protocol,
-            info.child_tabular_fs_location,
-            info.child_tabular_typ
+    Ok(())
+}
+
+pub(crate) async fn drop_namespace(
+    warehouse_id: WarehouseId,
+    namespace_id: NamespaceId,
+    flags: NamespaceDropFlags,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<NamespaceDropInfo> {
+    let drop_info = validate_namespace_drop(warehouse_id, namespace_id, flags, transaction).await?;
+    delete_namespaces(
+        warehouse_id,
+        namespace_id,
+        &drop_info.child_namespaces,
+        transaction,
+    )
+    .await?;
+    Ok(drop_info)
+}
+
+pub(crate) async fn mark_namespace_as_deleted(
+    warehouse_id: WarehouseId,
+    namespace_id: NamespaceId,
+    flags: NamespaceDropFlags,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<Option<NamespaceDropInfo>> {
+    let drop_info = validate_namespace_drop(warehouse_id, namespace_id, flags, transaction).await?;
+    let namespace_ids = drop_info
+        .child_namespaces
+        .iter()
+        .map(|id| **id)
+        .chain(std::iter::once(*namespace_id))
+        .collect_vec();
+
+    // Without tables or views there is no expiration that could clean up the namespaces later.
+    let has_tabulars = sqlx::query_scalar!(
+        r#"SELECT EXISTS (SELECT 1 FROM tabular WHERE namespace_id = ANY($1)) AS "exists!""#,
+        &namespace_ids,
+    )
+    .fetch_one(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error checking namespace content"))?;
+
+    if !has_tabulars {
+        delete_namespaces(
+            warehouse_id,
+            namespace_id,
+            &drop_info.child_namespaces,
+            transaction,
         )
-        .map(|(id, protocol, fs_location, typ)| {
-            (
-                match typ {
-                    TabularType::Table => TabularId::Table(id),
-                    TabularType::View => TabularId::View(id),
-                },
-                join_location(protocol.as_str(), fs_location.as_str()),
+        .await?;
+        return Ok(None);
+    }
+
+    // `now()` is the start of the transaction, so namespaces and tabulars share the same
+    // `deleted_at`. This is what ties the tabulars to the namespace drop for a later undrop.
+    let dropped_tabulars = sqlx::query!(
+        r#"
+        UPDATE tabular
+        SET deleted_at = now()
+        WHERE namespace_id = ANY($1) AND deleted_at IS NULL
+        RETURNING tabular_id, typ as "typ: TabularType", fs_protocol, fs_location
+        "#,
+        &namespace_ids,
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error marking tabulars as deleted"))?;
+
+    let record = sqlx::query!(
+        r#"
+        UPDATE namespace
+        SET deleted_at = now()
+        WHERE warehouse_id = $1
+            AND namespace_id = ANY($2)
+            AND deleted_at IS NULL
+            AND warehouse_id IN (
+                SELECT warehouse_id FROM warehouse WHERE status = 'active'
             )
-        })
-        .collect_vec(),
-        open_tasks: info
-            .child_tabular_task_id
+        "#,
+        *warehouse_id,
+        &namespace_ids,
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error marking namespace as deleted"))?;
+
+    if record.rows_affected() == 0 {
+        return Err(ErrorModel::not_found(
+            format!("Namespace {namespace_id} not found in warehouse {warehouse_id}"),
+            "NamespaceNotFound",
+            None,
+        )
+        .into());
+    }
+
+    Ok(Some(NamespaceDropInfo {
+        child_namespaces: drop_info.child_namespaces,
+        child_tables: dropped_tabulars
             .into_iter()
-            .map(TaskId::from)
-            .collect(),
-    })
+            .map(|r| {
+                (
+                    match r.typ {
+                        TabularType::Table => TabularId::Table(r.tabular_id),
+                        TabularType::View => TabularId::View(r.tabular_id),
+                    },
+                    join_location(r.fs_protocol.as_str(), r.fs_location.as_str()),
+                )
+            })
+            .collect_vec(),
+        external_tables: drop_info.external_tables,
+        open_tasks: drop_info.open_tasks,
+    }))
+}
+
+#[allow(clippy::too_many_lines)]
+pub(crate) async fn clear_namespace_deleted_at(
+    warehouse_id: WarehouseId,
+    namespace_id: NamespaceId,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<Vec<TaskId>> {
+    let namespace = sqlx::query!(
+        r#"
+        SELECT namespace_name as "namespace_name: Vec<String>", n.deleted_at as "deleted_at!"
+        FROM namespace n
+        INNER JOIN warehouse w ON n.warehouse_id = w.warehouse_id
+        WHERE n.warehouse_id = $1 AND n.namespace_id = $2
+        AND n.deleted_at IS NOT NULL
+        AND w.status = 'active'
+        "#,
+        *warehouse_id,
+        *namespace_id
+    )
+    .fetch_optional(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching deleted namespace"))?
+    .ok_or_else(|| {
+        ErrorModel::not_found(
+            format!("Deleted namespace {namespace_id} not found in warehouse {warehouse_id}"),
+            "NamespaceNotFound",
+            None,
+        )
+    })?;
+
+    if let Some((_, parent)) = namespace.namespace_name.split_last() {
+        if !parent.is_empty() {
+            let parent_exists = sqlx::query_scalar!(
+                r#"
+                SELECT EXISTS (
+                    SELECT 1 FROM namespace
+                    WHERE warehouse_id = $1 AND namespace_name = $2 AND deleted_at IS NULL
+                ) AS "exists!"
+                "#,
+                *warehouse_id,
+                parent
+            )
+            .fetch_one(&mut **transaction)
+            .await
+            .map_err(|e| e.into_error_model("Error fetching parent namespace"))?;
+
+            if !parent_exists {
+                return Err(ErrorModel::conflict(
+                    format!(
+                        "Parent namespace of {namespace_id} does not exist. Restore the parent namespace first."
+                    ),
+                    "ParentNamespaceNotFound",
+                    None,
+                )
+                .into());
+            }
+        }
+    }
+
+    // Child namespaces dropped together with the namespace share its `deleted_at`.
+    let namespace_ids = sqlx::query_scalar!(
+        r#"
+        UPDATE namespace
+        SET deleted_at = NULL
+        WHERE warehouse_id = $1
+            AND deleted_at = $2
+            AND namespace_name[1:array_length($3::text[], 1)] = $3
+        RETURNING namespace_id
+        "#,
+        *warehouse_id,
+        namespace.deleted_at,
+        &namespace.namespace_name,
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| match &e {
+        sqlx::Error::Database(db_error)
+            if db_error.constraint() == Some("unique_namespace_per_warehouse") =>
+        {
+            ErrorModel::conflict(
+                "A namespace with the same name as a restored namespace already exists.",
+                "NamespaceAlreadyExists",
+                Some(Box::new(e)),
+            )
+        }
+        _ => e.into_error_model("Error marking namespace as undeleted"),
+    })?;
+
+    let tabular_ids = sqlx::query_scalar!(
+        r#"
+        SELECT tabular_id
+        FROM tabular
+        WHERE namespace_id = ANY($1) AND deleted_at = $2
+        "#,
+        &namespace_ids,
+        namespace.deleted_at,
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching deleted tabulars of namespace"))?;
+
+    // Lock the expiration tasks so that no worker picks them up before they are cancelled.
+    let expiration_tasks = sqlx::query!(
+        r#"SELECT task_id, entity_id, status != 'scheduled' as "in_progress!"
+        FROM task
+        WHERE warehouse_id = $2
+            AND entity_type = 'tabular'
+            AND entity_id = ANY($1::uuid[])
+            AND queue_name = 'tabular_expiration'
+        FOR UPDATE"#,
+        &tabular_ids,
+        *warehouse_id,
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error locking tabular expiration tasks".to_string()))?;
+    if let Some(task) = expiration_tasks.iter().find(|t| t.in_progress) {
+        return Err(ErrorModel::conflict(
+            format!(
+                "Tabular {} is currently being expired and cannot be restored.",
+                task.entity_id
+            ),
+            "TabularExpirationRunning",
+            None,
+        )
+        .into());
+    }
+
+    sqlx::query!(
+        r#"
+        UPDATE tabular
+        SET deleted_at = NULL
+        WHERE tabular_id = ANY($1)
+        "#,
+        &tabular_ids,
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error marking tabulars as undeleted"))?;
+
+    Ok(expiration_tasks
+        .into_iter()
+        .map(|t| TaskId::from(t.task_id))
+        .collect())
+}
+
+/// Hard-deletes soft-deleted namespaces of the warehouse once all tables and views
+/// dropped together with them are expired.
+pub(crate) async fn drop_emptied_namespaces(
+    warehouse_id: WarehouseId,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<Vec<NamespaceId>> {
+    let namespace_ids = sqlx::query_scalar!(
+        r#"
+        DELETE FROM namespace n
+        WHERE n.warehouse_id = $1
+            AND n.deleted_at IS NOT NULL
+            AND NOT EXISTS (
+                SELECT 1
+                FROM tabular t
+                INNER JOIN namespace c ON t.namespace_id = c.namespace_id
+                WHERE c.warehouse_id = n.warehouse_id
+                    AND c.deleted_at = n.deleted_at
+                    AND c.namespace_name[1:array_length(n.namespace_name, 1)] = n.namespace_name
+            )
+        RETURNING n.namespace_id
+        "#,
+        *warehouse_id,
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error deleting expired namespaces"))?;
+
+    Ok(namespace_ids.into_iter().map(Into::into).collect())
 }
 
 pub(crate) async fn set_namespace_protected(
@@ -509,7 +797,7 @@ pub(crate) async fn set_namespace_protected(
         r#"
         UPDATE namespace
         SET protected = $1
-        WHERE namespace_id = $2 AND warehouse_id IN (
+        WHERE namespace_id = $2 AND deleted_at IS NULL AND warehouse_id IN (
             SELECT warehouse_id FROM warehouse WHERE status = 'active'
         )
         returning protected, updated_at
@@ -546,7 +834,7 @@ pub(crate) async fn get_namespace_protected(
         r#"
         SELECT protected, updated_at
         FROM namespace
-        WHERE namespace_id = $1 AND warehouse_id IN (
+        WHERE namespace_id = $1 AND deleted_at IS NULL AND warehouse_id IN (
             SELECT warehouse_id FROM warehouse WHERE status = 'active'
         )
         "#,
@@ -593,6 +881,7 @@ pub(crate) async fn update_namespace_properties(
         UPDATE namespace
         SET namespace_properties = $1
         WHERE warehouse_id = $2 AND namespace_id = $3
+        AND deleted_at IS NULL
         AND warehouse_id IN (
             SELECT warehouse_id FROM warehouse WHERE status = 'active'
         )
@@ -1080,6 +1369,128 @@ pub(crate) mod tests {
         assert_eq!(ns.len(), 0);
     }
 
+    #[sqlx::test]
+    async fn test_soft_drop_and_undrop_namespace(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let parent = NamespaceIdent::from_vec(vec!["parent".to_string()]).unwrap();
+        let child =
+            NamespaceIdent::from_vec(vec!["parent".to_string(), "child".to_string()]).unwrap();
+        let (parent_id, _) = initialize_namespace(state.clone(), warehouse_id, &parent, None).await;
+        let _ = initialize_namespace(state.clone(), warehouse_id, &child, None).await;
+        let table = initialize_table(
+            warehouse_id,
+            state.clone(),
+            false,
+            Some(child.clone()),
+            None,
+        )
+        .await;
+
+        let mut transaction = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let drop_info = mark_namespace_as_deleted(
+            warehouse_id,
+            parent_id,
+            NamespaceDropFlags {
+                force: false,
+                purge: false,
+                recursive: true,
+            },
+            transaction.transaction(),
+        )
+        .await
+        .unwrap()
+        .expect("Namespace with content should be soft-deleted");
+        assert_eq!(drop_info.child_namespaces.len(), 1);
+        assert_eq!(
+            drop_info.child_tables.first().map(|(id, _)| *id),
+            Some(TabularId::Table(*table.table_id))
+        );
+        transaction.commit().await.unwrap();
+
+        let mut transaction = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        for namespace in [&parent, &child] {
+            let id = namespace_to_id(warehouse_id, namespace, transaction.transaction())
+                .await
+                .unwrap();
+            assert!(id.is_none());
+        }
+        // The namespace name is free again while the old one is soft-deleted
+        let (recreated_id, _) =
+            initialize_namespace(state.clone(), warehouse_id, &parent, None).await;
+        let e = clear_namespace_deleted_at(warehouse_id, parent_id, transaction.transaction())
+            .await
+            .unwrap_err();
+        assert_eq!(e.error.code, StatusCode::CONFLICT);
+        drop(transaction);
+
+        let mut transaction = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        drop_namespace(
+            warehouse_id,
+            recreated_id,
+            NamespaceDropFlags::default(),
+            transaction.transaction(),
+        )
+        .await
+        .unwrap();
+        let task_ids =
+            clear_namespace_deleted_at(warehouse_id, parent_id, transaction.transaction())
+                .await
+                .unwrap();
+        assert!(task_ids.is_empty());
+        let child_id = namespace_to_id(warehouse_id, &child, transaction.transaction())
+            .await
+            .unwrap()
+            .expect("Child namespace should be restored");
+        let tables = load_tables(
+            warehouse_id,
+            [table.table_id],
+            false,
+            transaction.transaction(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(tables.len(), 1);
+
+        // Once the dropped table is gone, the soft-deleted namespaces are cleaned up
+        mark_namespace_as_deleted(
+            warehouse_id,
+            parent_id,
+            NamespaceDropFlags {
+                force: false,
+                purge: false,
+                recursive: true,
+            },
+            transaction.transaction(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert!(
+            drop_emptied_namespaces(warehouse_id, transaction.transaction())
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        PostgresCatalog::drop_table(table.table_id, true, transaction.transaction())
+            .await
+            .unwrap();
+        let dropped = drop_emptied_namespaces(warehouse_id, transaction.transaction())
+            .await
+            .unwrap();
+        assert_eq!(dropped.len(), 2);
+        assert!(dropped.contains(&parent_id));
+        assert!(dropped.contains(&child_id));
+        transaction.commit().await.unwrap();
+    }
+
     #[sqlx::test]
     async fn test_case_insensitive_but_preserve_case(pool: sqlx::PgPool) {
         let state = CatalogState::from_pools(pool.clone(), pool.clone());
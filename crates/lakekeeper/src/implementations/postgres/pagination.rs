@@ -9,14 +9,68 @@ use iceberg_ext::catalog::rest::ErrorModel;
 #[derive(Debug, PartialEq)]
 pub(crate) enum PaginateToken<T> {
     V1(V1PaginateToken<T>),
+    V2(V2PaginateToken<T>),
+    V3(V3PaginateToken<T>),
 }
 
+/// Token for lists ordered by `(created_at, id)`.
 #[derive(Debug, PartialEq)]
 pub(crate) struct V1PaginateToken<T> {
     pub(crate) created_at: chrono::DateTime<Utc>,
     pub(crate) id: T,
 }
 
+/// Token for lists ordered by a time-ordered id alone, such as UUIDv7 or ULID.
+#[derive(Debug, PartialEq)]
+pub(crate) struct V2PaginateToken<T> {
+    pub(crate) id: T,
+}
+
+/// Token for lists ordered by a sort key selected by the client, with ties ordered by id.
+/// `sort` identifies the order the token was issued for, `key` is the sort key of the last
+/// entry of the page.
+#[derive(Debug, PartialEq)]
+pub(crate) struct V3PaginateToken<T> {
+    pub(crate) sort: String,
+    pub(crate) key: String,
+    pub(crate) id: T,
+}
+
+impl<T> PaginateToken<T> {
+    /// Cursor of lists ordered by id.
+    /// `V1` tokens were issued while the list was ordered by `(created_at, id)`, which does not
+    /// agree with the order of ids that are not time-ordered, so they are rejected. `V3` tokens
+    /// belong to a sorted list and are rejected as in [`Self::into_sorted`].
+    pub(crate) fn into_id(self) -> Result<T, ErrorModel> {
+        match self {
+            PaginateToken::V1(_) => Err(outdated_token_error()),
+            PaginateToken::V2(V2PaginateToken { id }) => Ok(id),
+            PaginateToken::V3(_) => Err(sort_mismatch_error()),
+        }
+    }
+
+    /// Cursor of lists ordered by `(created_at, id)`.
+    pub(crate) fn into_v1(self) -> Result<V1PaginateToken<T>, ErrorModel> {
+        match self {
+            PaginateToken::V1(token) => Ok(token),
+            PaginateToken::V2(_) | PaginateToken::V3(_) => Err(parse_error(None)),
+        }
+    }
+
+    /// Cursor `(key, id)` of lists ordered by the sort `sort`.
+    /// Tokens issued for a different sort are rejected, as their key does not belong to this
+    /// order. For lists ordered by id alone, `sort` is empty and `V2` tokens are accepted with
+    /// an empty key. `V1` tokens are rejected as in [`Self::into_id`].
+    pub(crate) fn into_sorted(self, sort: &str) -> Result<(String, T), ErrorModel> {
+        match self {
+            PaginateToken::V3(token) if token.sort == sort => Ok((token.key, token.id)),
+            PaginateToken::V2(V2PaginateToken { id }) if sort.is_empty() => Ok((String::new(), id)),
+            PaginateToken::V1(_) => Err(outdated_token_error()),
+            _ => Err(sort_mismatch_error()),
+        }
+    }
+}
+
 impl<T> Display for PaginateToken<T>
 where
     T: Display,
@@ -26,6 +80,11 @@ where
             PaginateToken::V1(V1PaginateToken { created_at, id }) => {
                 format!("1&{}&{}", created_at.timestamp_micros(), id)
             }
+            PaginateToken::V2(V2PaginateToken { id }) => format!("2&{id}"),
+            // The key may contain `&`, so it is prefixed with its length in bytes.
+            PaginateToken::V3(V3PaginateToken { sort, key, id }) => {
+                format!("3&{sort}&{}&{key}&{id}", key.len())
+            }
         };
         write!(
             f,
@@ -62,31 +121,75 @@ where
             )
         })?;
 
-        let parts = s.splitn(3, '&').collect::<Vec<_>>();
+        let (version, rest) = s.split_once('&').ok_or(parse_error(None))?;
 
-        match *parts.first().ok_or(parse_error(None))? {
-            "1" => match &parts[1..] {
-                &[ts, id] => {
-                    let created_at = chrono::DateTime::from_timestamp_micros(
-                        ts.parse().map_err(|e| parse_error(Some(Box::new(e))))?,
-                    )
+        match version {
+            "1" => {
+                let (ts, id) = rest.split_once('&').ok_or(parse_error(None))?;
+                let created_at = chrono::DateTime::from_timestamp_micros(
+                    ts.parse().map_err(|e| parse_error(Some(Box::new(e))))?,
+                )
+                .ok_or(parse_error(None))?;
+                Ok(PaginateToken::V1(V1PaginateToken {
+                    created_at,
+                    id: parse_id(id)?,
+                }))
+            }
+            "2" => Ok(PaginateToken::V2(V2PaginateToken {
+                id: parse_id(rest)?,
+            })),
+            "3" => {
+                let (sort, rest) = rest.split_once('&').ok_or(parse_error(None))?;
+                let (key_len, rest) = rest.split_once('&').ok_or(parse_error(None))?;
+                let key_len: usize = key_len
+                    .parse()
+                    .map_err(|e| parse_error(Some(Box::new(e))))?;
+                let key = rest.get(..key_len).ok_or(parse_error(None))?;
+                let id = rest
+                    .get(key_len..)
+                    .and_then(|rest| rest.strip_prefix('&'))
                     .ok_or(parse_error(None))?;
-                    let id = id.parse().map_err(|e| {
-                        parse_error(Some(Box::new(ErrorModel::bad_request(
-                            format!("Pagination id could not be parsed: {e}"),
-                            "PaginationTokenIdParseError".to_string(),
-                            None,
-                        ))))
-                    })?;
-                    Ok(PaginateToken::V1(V1PaginateToken { created_at, id }))
-                }
-                _ => Err(parse_error(None)),
-            },
+                Ok(PaginateToken::V3(V3PaginateToken {
+                    sort: sort.to_string(),
+                    key: key.to_string(),
+                    id: parse_id(id)?,
+                }))
+            }
             _ => Err(parse_error(None)),
         }
     }
 }
 
+fn parse_id<T>(id: &str) -> Result<T, ErrorModel>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Display,
+{
+    id.parse().map_err(|e| {
+        parse_error(Some(Box::new(ErrorModel::bad_request(
+            format!("Pagination id could not be parsed: {e}"),
+            "PaginationTokenIdParseError".to_string(),
+            None,
+        ))))
+    })
+}
+
+fn sort_mismatch_error() -> ErrorModel {
+    ErrorModel::bad_request(
+        "Page token was issued for a different sort order",
+        "PaginateTokenSortMismatch",
+        None,
+    )
+}
+
+fn outdated_token_error() -> ErrorModel {
+    ErrorModel::bad_request(
+        "Page token was issued by an earlier version of Lakekeeper for a different order. Restart the listing without a page token.",
+        "PaginateTokenOutdated",
+        None,
+    )
+}
+
 fn parse_error(source: Option<Box<dyn std::error::Error + Send + Sync + 'static>>) -> ErrorModel {
     ErrorModel::bad_request(
         "Invalid paginate token".to_string(),
@@ -144,6 +247,97 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_v2_paginate_token() {
+        let id = uuid::Uuid::now_v7();
+        let token_str = PaginateToken::V2(V2PaginateToken { id }).to_string();
+        let token: PaginateToken<uuid::Uuid> = PaginateToken::try_from(token_str.as_str()).unwrap();
+        assert_eq!(token, PaginateToken::V2(V2PaginateToken { id }));
+        assert_eq!(token.into_id().unwrap(), id);
+    }
+
+    #[test]
+    fn test_v2_paginate_token_with_ampersand() {
+        let id = "kubernetes/some-name&with&ampersand".to_string();
+        let token_str = PaginateToken::V2(V2PaginateToken { id: id.clone() }).to_string();
+        let token: PaginateToken<String> = PaginateToken::try_from(token_str.as_str()).unwrap();
+        assert_eq!(token, PaginateToken::V2(V2PaginateToken { id }));
+    }
+
+    #[test]
+    fn test_v1_paginate_token_is_rejected_for_id_cursor() {
+        let token_str = PaginateToken::V1(V1PaginateToken {
+            created_at: Utc::now(),
+            id: uuid::Uuid::now_v7(),
+        })
+        .to_string();
+        let token: PaginateToken<uuid::Uuid> = PaginateToken::try_from(token_str.as_str()).unwrap();
+        let err = token.into_id().unwrap_err();
+        assert_eq!(err.r#type, "PaginateTokenOutdated");
+    }
+
+    #[test]
+    fn test_v2_paginate_token_is_rejected_for_timestamp_cursor() {
+        let token_str = PaginateToken::V2(V2PaginateToken {
+            id: uuid::Uuid::now_v7(),
+        })
+        .to_string();
+        let token: PaginateToken<uuid::Uuid> = PaginateToken::try_from(token_str.as_str()).unwrap();
+        assert!(token.into_v1().is_err());
+    }
+
+    #[test]
+    fn test_v3_paginate_token_with_ampersand() {
+        let token = PaginateToken::V3(V3PaginateToken {
+            sort: "name.desc".to_string(),
+            key: "sales&marketing".to_string(),
+            id: "kubernetes/some-name&with&ampersand".to_string(),
+        });
+        let token_str = token.to_string();
+        let parsed: PaginateToken<String> = PaginateToken::try_from(token_str.as_str()).unwrap();
+        assert_eq!(parsed, token);
+        assert_eq!(
+            parsed.into_sorted("name.desc").unwrap(),
+            (
+                "sales&marketing".to_string(),
+                "kubernetes/some-name&with&ampersand".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_v3_paginate_token_is_rejected_for_other_sort() {
+        let id = uuid::Uuid::now_v7();
+        let token = PaginateToken::V3(V3PaginateToken {
+            sort: "name.asc".to_string(),
+            key: "orders".to_string(),
+            id,
+        });
+        let token: PaginateToken<uuid::Uuid> =
+            PaginateToken::try_from(token.to_string().as_str()).unwrap();
+        assert!(token.into_sorted("name.desc").is_err());
+    }
+
+    #[test]
+    fn test_v3_paginate_token_is_rejected_for_id_cursor() {
+        let token = PaginateToken::V3(V3PaginateToken {
+            sort: "name.asc".to_string(),
+            key: "orders".to_string(),
+            id: uuid::Uuid::now_v7(),
+        });
+        let err = token.into_id().unwrap_err();
+        assert_eq!(err.r#type, "PaginateTokenSortMismatch");
+    }
+
+    #[test]
+    fn test_v2_paginate_token_is_accepted_as_unsorted_cursor() {
+        let id = uuid::Uuid::now_v7();
+        let token = PaginateToken::V2(V2PaginateToken { id });
+        assert_eq!(token.into_sorted("").unwrap(), (String::new(), id));
+        let token = PaginateToken::V2(V2PaginateToken { id });
+        assert!(token.into_sorted("name.asc").is_err());
+    }
+
     #[test]
     fn test_paginate_token_with_user_id() {
         let created_at = Utc::now();
@@ -0,0 +1,630 @@
+use chrono::{DateTime, Utc};
+use iceberg_ext::catalog::rest::ErrorModel;
+use uuid::Uuid;
+
+use crate::{
+    api::{
+        iceberg::v1::{PaginationQuery, MAX_PAGE_SIZE},
+        management::v1::recertification::{
+            CreateRecertificationCampaignRequest, ListRecertificationCampaignsResponse,
+            RecertificationCampaign, RecertificationCampaignStatus, RecertificationDecision,
+            RecertificationItem, RecertificationItemDecision,
+        },
+    },
+    implementations::postgres::{
+        dbutils::DBErrorHandler,
+        pagination::{PaginateToken, V2PaginateToken},
+    },
+    service::{authz::RoleAccessKind, Result, RoleId, UserId},
+    ProjectId,
+};
+
+#[derive(sqlx::FromRow, Debug)]
+struct CampaignRow {
+    campaign_id: Uuid,
+    project_id: String,
+    name: String,
+    description: Option<String>,
+    reviewers: Vec<String>,
+    deadline: DateTime<Utc>,
+    auto_revoke_unreviewed: bool,
+    status: RecertificationCampaignStatus,
+    created_by: Option<String>,
+    created_at: DateTime<Utc>,
+    closed_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<CampaignRow> for RecertificationCampaign {
+    type Error = ErrorModel;
+
+    fn try_from(
+        CampaignRow {
+            campaign_id,
+            project_id,
+            name,
+            description,
+            reviewers,
+            deadline,
+            auto_revoke_unreviewed,
+            status,
+            created_by,
+            created_at,
+            closed_at,
+        }: CampaignRow,
+    ) -> std::result::Result<Self, Self::Error> {
+        let reviewers = reviewers
+            .into_iter()
+            .map(UserId::try_from)
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| {
+                ErrorModel::internal(
+                    format!("Invalid reviewer of recertification campaign {campaign_id}"),
+                    "InvalidRecertificationReviewer",
+                    Some(Box::new(e)),
+                )
+            })?;
+
+        Ok(Self {
+            campaign_id,
+            project_id: ProjectId::from_db_unchecked(project_id),
+            name,
+            description,
+            reviewers,
+            deadline,
+            auto_revoke_unreviewed,
+            status,
+            created_by,
+            created_at,
+            closed_at,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow, Debug)]
+struct ItemRow {
+    item_id: Uuid,
+    role_id: Uuid,
+    role_name: String,
+    kind: RoleAccessKind,
+    principal: String,
+    relation: String,
+    object: String,
+    decision: RecertificationDecision,
+    decided_by: Option<String>,
+    decided_at: Option<DateTime<Utc>>,
+    comment: Option<String>,
+}
+
+impl From<ItemRow> for RecertificationItem {
+    fn from(
+        ItemRow {
+            item_id,
+            role_id,
+            role_name,
+            kind,
+            principal,
+            relation,
+            object,
+            decision,
+            decided_by,
+            decided_at,
+            comment,
+        }: ItemRow,
+    ) -> Self {
+        Self {
+            item_id,
+            role_id: RoleId::new(role_id),
+            role_name,
+            kind,
+            principal,
+            relation,
+            object,
+            decision,
+            decided_by,
+            decided_at,
+            comment,
+        }
+    }
+}
+
+pub(crate) async fn create_recertification_campaign(
+    campaign_id: Uuid,
+    project_id: &ProjectId,
+    request: &CreateRecertificationCampaignRequest,
+    created_by: Option<&str>,
+    items: &[RecertificationItem],
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<RecertificationCampaign> {
+    let reviewers = request
+        .reviewers
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+
+    let campaign = sqlx::query_as!(
+        CampaignRow,
+        r#"
+        INSERT INTO recertification_campaign
+            (campaign_id, project_id, name, description, reviewers, deadline, auto_revoke_unreviewed, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        RETURNING campaign_id, project_id, name, description, reviewers, deadline,
+            auto_revoke_unreviewed, status as "status: RecertificationCampaignStatus",
+            created_by, created_at, closed_at
+        "#,
+        campaign_id,
+        project_id,
+        request.name,
+        request.description,
+        &reviewers,
+        request.deadline,
+        request.auto_revoke_unreviewed,
+        created_by,
+    )
+    .fetch_one(&mut **transaction)
+    .await
+    .map_err(|e| match e {
+        sqlx::Error::Database(db_error) if db_error.is_foreign_key_violation() => {
+            ErrorModel::not_found(
+                format!("Project {project_id} not found"),
+                "ProjectNotFound",
+                Some(Box::new(db_error)),
+            )
+        }
+        _ => e.into_error_model("Error creating recertification campaign"),
+    })?;
+
+    if !items.is_empty() {
+        let item_ids = items.iter().map(|i| i.item_id).collect::<Vec<_>>();
+        let role_ids = items
+            .iter()
+            .map(|i| Uuid::from(i.role_id))
+            .collect::<Vec<_>>();
+        let role_names = items
+            .iter()
+            .map(|i| i.role_name.clone())
+            .collect::<Vec<_>>();
+        let kinds = items
+            .iter()
+            .map(|i| match i.kind {
+                RoleAccessKind::Member => "member".to_string(),
+                RoleAccessKind::Grant => "grant".to_string(),
+            })
+            .collect::<Vec<_>>();
+        let principals = items
+            .iter()
+            .map(|i| i.principal.clone())
+            .collect::<Vec<_>>();
+        let relations = items.iter().map(|i| i.relation.clone()).collect::<Vec<_>>();
+        let objects = items.iter().map(|i| i.object.clone()).collect::<Vec<_>>();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO recertification_item
+                (item_id, campaign_id, role_id, role_name, kind, principal, relation, object)
+            SELECT i.item_id, $1, i.role_id, i.role_name, i.kind::recertification_access_kind,
+                i.principal, i.relation, i.object
+            FROM UNNEST($2::UUID[], $3::UUID[], $4::TEXT[], $5::TEXT[], $6::TEXT[], $7::TEXT[], $8::TEXT[])
+                AS i(item_id, role_id, role_name, kind, principal, relation, object)
+            "#,
+            campaign_id,
+            &item_ids,
+            &role_ids,
+            &role_names,
+            &kinds,
+            &principals,
+            &relations,
+            &objects,
+        )
+        .execute(&mut **transaction)
+        .await
+        .map_err(|e| e.into_error_model("Error creating recertification items"))?;
+    }
+
+    RecertificationCampaign::try_from(campaign).map_err(Into::into)
+}
+
+pub(crate) async fn get_recertification_campaign<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    campaign_id: Uuid,
+    connection: E,
+) -> Result<Option<RecertificationCampaign>> {
+    let campaign = sqlx::query_as!(
+        CampaignRow,
+        r#"
+        SELECT campaign_id, project_id, name, description, reviewers, deadline,
+            auto_revoke_unreviewed, status as "status: RecertificationCampaignStatus",
+            created_by, created_at, closed_at
+        FROM recertification_campaign
+        WHERE campaign_id = $1
+        "#,
+        campaign_id,
+    )
+    .fetch_optional(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching recertification campaign"))?;
+
+    campaign
+        .map(RecertificationCampaign::try_from)
+        .transpose()
+        .map_err(Into::into)
+}
+
+pub(crate) async fn list_recertification_campaigns<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    project_id: &ProjectId,
+    reviewer: Option<&str>,
+    PaginationQuery {
+        page_size,
+        page_token,
+    }: PaginationQuery,
+    connection: E,
+) -> Result<ListRecertificationCampaignsResponse> {
+    let page_size = page_size.map_or(MAX_PAGE_SIZE, |i| i.clamp(1, MAX_PAGE_SIZE));
+
+    let token_id: Option<Uuid> = page_token
+        .as_option()
+        .map(PaginateToken::try_from)
+        .transpose()?
+        .map(PaginateToken::into_id)
+        .transpose()?;
+
+    let campaigns = sqlx::query_as!(
+        CampaignRow,
+        r#"
+        SELECT campaign_id, project_id, name, description, reviewers, deadline,
+            auto_revoke_unreviewed, status as "status: RecertificationCampaignStatus",
+            created_by, created_at, closed_at
+        FROM recertification_campaign
+        WHERE project_id = $1
+            AND ($2::TEXT IS NULL OR $2 = ANY(reviewers))
+            AND (campaign_id > $3 OR $3 IS NULL)
+        ORDER BY campaign_id ASC
+        LIMIT $4
+        "#,
+        project_id,
+        reviewer,
+        token_id,
+        page_size,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching recertification campaigns"))?
+    .into_iter()
+    .map(RecertificationCampaign::try_from)
+    .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    let next_page_token = campaigns
+        .last()
+        .map(|c| PaginateToken::V2(V2PaginateToken::<Uuid> { id: c.campaign_id }).to_string());
+
+    Ok(ListRecertificationCampaignsResponse {
+        campaigns,
+        next_page_token,
+    })
+}
+
+pub(crate) async fn list_recertification_items<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    campaign_id: Uuid,
+    connection: E,
+) -> Result<Vec<RecertificationItem>> {
+    let items = sqlx::query_as!(
+        ItemRow,
+        r#"
+        SELECT item_id, role_id, role_name, kind as "kind: RoleAccessKind", principal,
+            relation, object, decision as "decision: RecertificationDecision",
+            decided_by, decided_at, comment
+        FROM recertification_item
+        WHERE campaign_id = $1
+        ORDER BY role_name, item_id
+        "#,
+        campaign_id,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching recertification items"))?
+    .into_iter()
+    .map(RecertificationItem::from)
+    .collect();
+
+    Ok(items)
+}
+
+pub(crate) async fn decide_recertification_items(
+    campaign_id: Uuid,
+    decisions: &[RecertificationItemDecision],
+    decided_by: Option<&str>,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<Vec<RecertificationItem>> {
+    let item_ids = decisions.iter().map(|d| d.item_id).collect::<Vec<_>>();
+    let outcomes = decisions
+        .iter()
+        .map(|d| {
+            RecertificationDecision::from(d.decision)
+                .as_str()
+                .to_string()
+        })
+        .collect::<Vec<_>>();
+    let comments = decisions
+        .iter()
+        .map(|d| d.comment.clone())
+        .collect::<Vec<_>>();
+
+    let items = sqlx::query_as!(
+        ItemRow,
+        r#"
+        UPDATE recertification_item i
+        SET decision = d.decision::recertification_decision,
+            decided_by = $5,
+            decided_at = now(),
+            comment = d.comment
+        FROM UNNEST($2::UUID[], $3::TEXT[], $4::TEXT[]) AS d(item_id, decision, comment)
+        WHERE i.campaign_id = $1
+          AND i.item_id = d.item_id
+          AND i.decision = 'pending'
+        RETURNING i.item_id, i.role_id, i.role_name, i.kind as "kind: RoleAccessKind",
+            i.principal, i.relation, i.object, i.decision as "decision: RecertificationDecision",
+            i.decided_by, i.decided_at, i.comment
+        "#,
+        campaign_id,
+        &item_ids,
+        &outcomes,
+        &comments,
+        decided_by,
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error deciding on recertification items"))?
+    .into_iter()
+    .map(RecertificationItem::from)
+    .collect();
+
+    Ok(items)
+}
+
+pub(crate) async fn close_recertification_campaign(
+    campaign_id: Uuid,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<Vec<RecertificationItem>> {
+    let auto_revoke = sqlx::query_scalar!(
+        r#"
+        UPDATE recertification_campaign
+        SET status = 'closed', closed_at = now()
+        WHERE campaign_id = $1 AND status = 'open'
+        RETURNING auto_revoke_unreviewed
+        "#,
+        campaign_id,
+    )
+    .fetch_optional(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error closing recertification campaign"))?
+    .ok_or_else(|| {
+        ErrorModel::conflict(
+            format!("Campaign {campaign_id} does not exist or is already closed"),
+            "RecertificationCampaignClosed",
+            None,
+        )
+    })?;
+
+    if !auto_revoke {
+        return Ok(vec![]);
+    }
+
+    let items = sqlx::query_as!(
+        ItemRow,
+        r#"
+        UPDATE recertification_item
+        SET decision = 'auto-revoked', decided_at = now()
+        WHERE campaign_id = $1 AND decision = 'pending'
+        RETURNING item_id, role_id, role_name, kind as "kind: RoleAccessKind", principal,
+            relation, object, decision as "decision: RecertificationDecision",
+            decided_by, decided_at, comment
+        "#,
+        campaign_id,
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error auto-revoking recertification items"))?
+    .into_iter()
+    .map(RecertificationItem::from)
+    .collect();
+
+    Ok(items)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        api::management::v1::recertification::ReviewDecision,
+        implementations::postgres::{warehouse::test::initialize_project, PostgresTransaction},
+        service::Transaction,
+    };
+
+    fn request(auto_revoke_unreviewed: bool) -> CreateRecertificationCampaignRequest {
+        CreateRecertificationCampaignRequest {
+            name: "Quarterly review".to_string(),
+            description: None,
+            reviewers: vec![UserId::try_from("oidc~alice").unwrap()],
+            deadline: Utc::now() + chrono::Duration::days(14),
+            auto_revoke_unreviewed,
+            role_ids: None,
+        }
+    }
+
+    fn item(role_id: RoleId, principal: &str) -> RecertificationItem {
+        RecertificationItem {
+            item_id: Uuid::now_v7(),
+            role_id,
+            role_name: "Analysts".to_string(),
+            kind: RoleAccessKind::Member,
+            principal: principal.to_string(),
+            relation: "assignee".to_string(),
+            object: format!("role:{role_id}"),
+            decision: RecertificationDecision::Pending,
+            decided_by: None,
+            decided_at: None,
+            comment: None,
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_review_and_close_campaign(pool: sqlx::PgPool) {
+        let (state, project_id) = initialize_project(pool).await;
+        let role_id = RoleId::new_random();
+        let items = vec![
+            item(role_id, "user:oidc~bob"),
+            item(role_id, "user:oidc~carol"),
+            item(role_id, "user:oidc~dave"),
+        ];
+
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let campaign = create_recertification_campaign(
+            Uuid::now_v7(),
+            &project_id,
+            &request(true),
+            Some("oidc~admin"),
+            &items,
+            t.transaction(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(campaign.status, RecertificationCampaignStatus::Open);
+        assert_eq!(campaign.reviewers, request(true).reviewers);
+
+        let decided = decide_recertification_items(
+            campaign.campaign_id,
+            &[
+                RecertificationItemDecision {
+                    item_id: items[0].item_id,
+                    decision: ReviewDecision::Approve,
+                    comment: None,
+                },
+                RecertificationItemDecision {
+                    item_id: items[1].item_id,
+                    decision: ReviewDecision::Revoke,
+                    comment: Some("Left the team".to_string()),
+                },
+            ],
+            Some("oidc~alice"),
+            t.transaction(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(decided.len(), 2);
+
+        // Items can only be decided once
+        let decided_again = decide_recertification_items(
+            campaign.campaign_id,
+            &[RecertificationItemDecision {
+                item_id: items[0].item_id,
+                decision: ReviewDecision::Revoke,
+                comment: None,
+            }],
+            Some("oidc~alice"),
+            t.transaction(),
+        )
+        .await
+        .unwrap();
+        assert!(decided_again.is_empty());
+
+        let auto_revoked = close_recertification_campaign(campaign.campaign_id, t.transaction())
+            .await
+            .unwrap();
+        assert_eq!(
+            auto_revoked.iter().map(|i| i.item_id).collect::<Vec<_>>(),
+            vec![items[2].item_id]
+        );
+        assert!(
+            close_recertification_campaign(campaign.campaign_id, t.transaction())
+                .await
+                .is_err()
+        );
+
+        let items = list_recertification_items(campaign.campaign_id, &mut **t.transaction())
+            .await
+            .unwrap();
+        assert_eq!(
+            items.iter().map(|i| i.decision).collect::<Vec<_>>(),
+            vec![
+                RecertificationDecision::Approved,
+                RecertificationDecision::Revoked,
+                RecertificationDecision::AutoRevoked
+            ]
+        );
+        assert_eq!(items[1].comment.as_deref(), Some("Left the team"));
+        assert_eq!(items[1].decided_by.as_deref(), Some("oidc~alice"));
+
+        let campaign = get_recertification_campaign(campaign.campaign_id, &mut **t.transaction())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(campaign.status, RecertificationCampaignStatus::Closed);
+        assert!(campaign.closed_at.is_some());
+        t.commit().await.unwrap();
+    }
+
+    #[sqlx::test]
+    async fn test_list_campaigns_of_reviewer(pool: sqlx::PgPool) {
+        let (state, project_id) = initialize_project(pool).await;
+
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let reviewed = create_recertification_campaign(
+            Uuid::now_v7(),
+            &project_id,
+            &request(false),
+            None,
+            &[],
+            t.transaction(),
+        )
+        .await
+        .unwrap();
+        create_recertification_campaign(
+            Uuid::now_v7(),
+            &project_id,
+            &CreateRecertificationCampaignRequest {
+                reviewers: vec![UserId::try_from("oidc~bob").unwrap()],
+                ..request(false)
+            },
+            None,
+            &[],
+            t.transaction(),
+        )
+        .await
+        .unwrap();
+        t.commit().await.unwrap();
+
+        let all = list_recertification_campaigns(
+            &project_id,
+            None,
+            PaginationQuery::empty(),
+            &state.read_pool(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(all.campaigns.len(), 2);
+
+        let of_alice = list_recertification_campaigns(
+            &project_id,
+            Some("oidc~alice"),
+            PaginationQuery::empty(),
+            &state.read_pool(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(of_alice.campaigns, vec![reviewed]);
+    }
+}
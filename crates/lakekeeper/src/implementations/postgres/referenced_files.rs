@@ -0,0 +1,246 @@
+use chrono::Utc;
+use iceberg_ext::catalog::rest::ErrorModel;
+use uuid::Uuid;
+
+use crate::{
+    api::{
+        iceberg::v1::{PaginationQuery, MAX_PAGE_SIZE},
+        management::v1::referenced_files::{
+            ListReferencedFilesResponse, ReferencedFile, ReferencedFileKind, TabularReferencedFiles,
+        },
+    },
+    implementations::postgres::{
+        dbutils::DBErrorHandler as _,
+        pagination::{PaginateToken, V1PaginateToken},
+        tabular::TabularType,
+    },
+    service::{storage::join_location, Result},
+    WarehouseId,
+};
+
+fn file_kind(kind: &str) -> Result<ReferencedFileKind> {
+    Ok(match kind {
+        "metadata" => ReferencedFileKind::Metadata,
+        "previous-metadata" => ReferencedFileKind::PreviousMetadata,
+        "manifest-list" => ReferencedFileKind::ManifestList,
+        "statistics" => ReferencedFileKind::Statistics,
+        "partition-statistics" => ReferencedFileKind::PartitionStatistics,
+        _ => {
+            return Err(ErrorModel::internal(
+                format!("Unknown kind of referenced file: '{kind}'"),
+                "UnexpectedReferencedFileKind",
+                None,
+            )
+            .into())
+        }
+    })
+}
+
+/// Lists the files referenced by a page of tabulars of the warehouse.
+///
+/// All files of a page are fetched in a single statement, so that each page is a consistent
+/// snapshot. The page token is a [`V1PaginateToken`] whose `created_at` is the start of
+/// the listing.
+pub(crate) async fn list_referenced_files<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseId,
+    PaginationQuery {
+        page_size,
+        page_token,
+    }: PaginationQuery,
+    connection: E,
+) -> Result<ListReferencedFilesResponse> {
+    let page_size = page_size.map_or(MAX_PAGE_SIZE, |i| i.clamp(1, MAX_PAGE_SIZE));
+
+    let token = page_token
+        .as_option()
+        .map(PaginateToken::<Uuid>::try_from)
+        .transpose()?
+        .map(PaginateToken::into_v1)
+        .transpose()?;
+    let (listing_started_at, token_id) = match token {
+        Some(V1PaginateToken { created_at, id }) => (created_at, Some(id)),
+        None => (Utc::now(), None),
+    };
+
+    let rows = sqlx::query!(
+        r#"
+        WITH page AS (
+            SELECT t.tabular_id, t.typ, t.name, n.namespace_name, t.metadata_location,
+                t.fs_protocol, t.fs_location, t.deleted_at
+            FROM tabular t
+            INNER JOIN namespace n ON t.namespace_id = n.namespace_id
+            WHERE n.warehouse_id = $1
+                AND t.metadata_location IS NOT NULL
+                AND (t.tabular_id > $2 OR $2 IS NULL)
+            ORDER BY t.tabular_id
+            LIMIT $3
+        )
+        SELECT p.tabular_id,
+            p.typ as "typ: TabularType",
+            p.name,
+            p.namespace_name,
+            p.fs_protocol,
+            p.fs_location,
+            p.deleted_at IS NOT NULL AS "deleted!",
+            f.kind AS "kind!",
+            f.file_location AS "file_location!"
+        FROM page p
+        CROSS JOIN LATERAL (
+            SELECT 0 AS ord, 'metadata' AS kind, p.metadata_location AS file_location
+            UNION ALL
+            SELECT 1, 'previous-metadata', ml.metadata_file
+            FROM table_metadata_log ml WHERE ml.table_id = p.tabular_id
+            UNION ALL
+            SELECT 2, 'manifest-list', s.manifest_list
+            FROM table_snapshot s WHERE s.table_id = p.tabular_id
+            UNION ALL
+            SELECT 3, 'statistics', ts.statistics_path
+            FROM table_statistics ts WHERE ts.table_id = p.tabular_id
+            UNION ALL
+            SELECT 4, 'partition-statistics', ps.statistics_path
+            FROM partition_statistics ps WHERE ps.table_id = p.tabular_id
+        ) f
+        ORDER BY p.tabular_id, f.ord, f.file_location
+        "#,
+        *warehouse_id,
+        token_id,
+        page_size,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching referenced files of warehouse"))?;
+
+    let mut tabulars: Vec<TabularReferencedFiles> = vec![];
+    for row in rows {
+        let file = ReferencedFile {
+            kind: file_kind(&row.kind)?,
+            location: row.file_location,
+        };
+        match tabulars.last_mut() {
+            Some(tabular) if tabular.tabular_id == row.tabular_id => tabular.files.push(file),
+            _ => tabulars.push(TabularReferencedFiles {
+                tabular_id: row.tabular_id,
+                typ: row.typ.into(),
+                namespace: row.namespace_name,
+                name: row.name,
+                location: join_location(&row.fs_protocol, &row.fs_location),
+                deleted: row.deleted,
+                files: vec![file],
+            }),
+        }
+    }
+
+    let next_page_token = tabulars.last().map(|t| {
+        PaginateToken::V1(V1PaginateToken {
+            created_at: listing_started_at,
+            id: t.tabular_id,
+        })
+        .to_string()
+    });
+
+    Ok(ListReferencedFilesResponse {
+        listing_started_at,
+        tabulars,
+        next_page_token,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        api::iceberg::v1::PageToken,
+        implementations::postgres::{
+            namespace::tests::initialize_namespace, tabular::table::tests::initialize_table,
+            warehouse::test::initialize_warehouse, CatalogState,
+        },
+    };
+
+    #[sqlx::test]
+    async fn test_list_referenced_files(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let namespace = iceberg::NamespaceIdent::new("ns".to_string());
+        initialize_namespace(state.clone(), warehouse_id, &namespace, None).await;
+        let first = initialize_table(
+            warehouse_id,
+            state.clone(),
+            false,
+            Some(namespace.clone()),
+            Some("t1".to_string()),
+        )
+        .await;
+        let second = initialize_table(
+            warehouse_id,
+            state.clone(),
+            false,
+            Some(namespace.clone()),
+            Some("t2".to_string()),
+        )
+        .await;
+
+        let all = list_referenced_files(
+            warehouse_id,
+            PaginationQuery {
+                page_token: PageToken::NotSpecified,
+                page_size: None,
+            },
+            &pool,
+        )
+        .await
+        .unwrap();
+        assert_eq!(all.tabulars.len(), 2);
+        for tabular in &all.tabulars {
+            assert_eq!(tabular.namespace, vec!["ns".to_string()]);
+            assert!(!tabular.deleted);
+            assert_eq!(tabular.files[0].kind, ReferencedFileKind::Metadata);
+            assert!(tabular
+                .files
+                .iter()
+                .any(|f| f.kind == ReferencedFileKind::ManifestList && f.location == "a.txt"));
+        }
+        let mut ids = vec![*first.table_id, *second.table_id];
+        ids.sort();
+        assert_eq!(
+            all.tabulars
+                .iter()
+                .map(|t| t.tabular_id)
+                .collect::<Vec<_>>(),
+            ids
+        );
+
+        // Pages carry the start of the listing
+        let first_page = list_referenced_files(
+            warehouse_id,
+            PaginationQuery {
+                page_token: PageToken::NotSpecified,
+                page_size: Some(1),
+            },
+            &pool,
+        )
+        .await
+        .unwrap();
+        let second_page = list_referenced_files(
+            warehouse_id,
+            PaginationQuery {
+                page_token: PageToken::Present(first_page.next_page_token.unwrap()),
+                page_size: Some(1),
+            },
+            &pool,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            second_page.listing_started_at.timestamp_micros(),
+            first_page.listing_started_at.timestamp_micros()
+        );
+        assert_eq!(
+            [first_page.tabulars, second_page.tabulars].concat(),
+            all.tabulars
+        );
+    }
+}
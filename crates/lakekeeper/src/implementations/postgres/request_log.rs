@@ -0,0 +1,98 @@
+use crate::{
+    implementations::postgres::dbutils::DBErrorHandler,
+    service::{request_log::RequestLogSettings, Result},
+};
+
+pub(crate) async fn get_request_log_settings<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    connection: E,
+) -> Result<Option<RequestLogSettings>> {
+    let settings = sqlx::query_as!(
+        RequestLogSettings,
+        r#"
+        SELECT enabled, include_request_body, redact_credentials, redacted_properties
+        FROM request_log_settings
+        "#,
+    )
+    .fetch_optional(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching request log settings"))?;
+
+    Ok(settings)
+}
+
+pub(crate) async fn set_request_log_settings<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    RequestLogSettings {
+        enabled,
+        include_request_body,
+        redact_credentials,
+        redacted_properties,
+    }: RequestLogSettings,
+    connection: E,
+) -> Result<RequestLogSettings> {
+    let settings = sqlx::query_as!(
+        RequestLogSettings,
+        r#"
+        INSERT INTO request_log_settings
+            (single_row, enabled, include_request_body, redact_credentials, redacted_properties)
+        VALUES (true, $1, $2, $3, $4)
+        ON CONFLICT (single_row)
+        DO UPDATE SET enabled = EXCLUDED.enabled,
+            include_request_body = EXCLUDED.include_request_body,
+            redact_credentials = EXCLUDED.redact_credentials,
+            redacted_properties = EXCLUDED.redacted_properties
+        RETURNING enabled, include_request_body, redact_credentials, redacted_properties
+        "#,
+        enabled,
+        include_request_body,
+        redact_credentials,
+        &redacted_properties,
+    )
+    .fetch_one(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error setting request log settings"))?;
+
+    Ok(settings)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[sqlx::test]
+    async fn test_request_log_settings(pool: sqlx::PgPool) {
+        assert!(get_request_log_settings(&pool).await.unwrap().is_none());
+
+        let settings = RequestLogSettings {
+            enabled: true,
+            include_request_body: false,
+            redact_credentials: true,
+            redacted_properties: vec!["my.secret.property".to_string()],
+        };
+        assert_eq!(
+            set_request_log_settings(settings.clone(), &pool)
+                .await
+                .unwrap(),
+            settings
+        );
+
+        let disabled = RequestLogSettings {
+            enabled: false,
+            ..settings
+        };
+        set_request_log_settings(disabled.clone(), &pool)
+            .await
+            .unwrap();
+        assert_eq!(
+            get_request_log_settings(&pool).await.unwrap(),
+            Some(disabled)
+        );
+    }
+}
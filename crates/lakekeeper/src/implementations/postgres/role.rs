@@ -4,11 +4,14 @@ use uuid::Uuid;
 use crate::{
     api::{
         iceberg::v1::{PaginationQuery, MAX_PAGE_SIZE},
-        management::v1::role::{ListRolesResponse, Role, SearchRoleResponse},
+        management::v1::{
+            role::{ListRolesResponse, Role, SearchRoleResponse},
+            ListSort,
+        },
     },
     implementations::postgres::{
         dbutils::DBErrorHandler,
-        pagination::{PaginateToken, V1PaginateToken},
+        pagination::{PaginateToken, V2PaginateToken, V3PaginateToken},
     },
     service::{Result, RoleId},
     ProjectId,
@@ -152,6 +155,7 @@ pub(crate) async fn list_roles<'e, 'c: 'e, E: sqlx::Executor<'c, Database = sqlx
     filter_project_id: Option<ProjectId>,
     filter_role_id: Option<Vec<RoleId>>,
     filter_name: Option<String>,
+    sort: Option<ListSort>,
     PaginationQuery {
         page_size,
         page_token,
@@ -160,23 +164,17 @@ pub(crate) async fn list_roles<'e, 'c: 'e, E: sqlx::Executor<'c, Database = sqlx
 ) -> Result<ListRolesResponse> {
     let page_size = page_size.map_or(MAX_PAGE_SIZE, |i| i.clamp(1, MAX_PAGE_SIZE));
     let filter_name = filter_name.unwrap_or_default();
+    let sort_name = sort.as_ref().map(ToString::to_string).unwrap_or_default();
 
-    let token = page_token
+    let (token_key, token_id): (_, Option<Uuid>) = page_token
         .as_option()
         .map(PaginateToken::try_from)
-        .transpose()?;
-
-    let (token_ts, token_id) = token
-        .as_ref()
-        .map(
-            |PaginateToken::V1(V1PaginateToken { created_at, id }): &PaginateToken<Uuid>| {
-                (created_at, id)
-            },
-        )
+        .transpose()?
+        .map(|token| token.into_sorted(&sort_name))
+        .transpose()?
         .unzip();
 
-    let roles: Vec<Role> = sqlx::query_as!(
-        RoleRow,
+    let rows = sqlx::query!(
         r#"
         SELECT
             id,
@@ -184,15 +182,30 @@ pub(crate) async fn list_roles<'e, 'c: 'e, E: sqlx::Executor<'c, Database = sqlx
             description,
             project_id,
             created_at,
-            updated_at
-        FROM role r
-        WHERE ($1 OR project_id = $2)
-            AND ($3 OR id = any($4))
-            AND ($5 OR name ILIKE ('%' || $6 || '%'))
-            --- PAGINATION
-            AND ((r.created_at > $7 OR $7 IS NULL) OR (r.created_at = $7 AND r.id > $8))
-        ORDER BY r.created_at, r.id ASC
-        LIMIT $9
+            updated_at,
+            sort_key as "sort_key!"
+        FROM (
+            SELECT r.*,
+                CASE $7::text
+                    WHEN 'name' THEN lower(r.name)
+                    WHEN 'created-at' THEN to_char(r.created_at AT TIME ZONE 'UTC', 'YYYY-MM-DD"T"HH24:MI:SS.US')
+                    WHEN 'updated-at' THEN to_char(coalesce(r.updated_at, r.created_at) AT TIME ZONE 'UTC', 'YYYY-MM-DD"T"HH24:MI:SS.US')
+                    ELSE ''
+                END COLLATE "C" AS sort_key
+            FROM role r
+            WHERE ($1 OR r.project_id = $2)
+                AND ($3 OR r.id = any($4))
+                AND ($5 OR r.name ILIKE ('%' || $6 || '%'))
+        ) r
+        --- PAGINATION
+        WHERE $9::text IS NULL
+            OR CASE WHEN $8 THEN (r.sort_key, r.id) < ($9, $10) ELSE (r.sort_key, r.id) > ($9, $10) END
+        ORDER BY
+            CASE WHEN $8 THEN r.sort_key END DESC,
+            CASE WHEN $8 THEN r.id END DESC,
+            r.sort_key ASC,
+            r.id ASC
+        LIMIT $11
         "#,
         filter_project_id.is_none(),
         &filter_project_id.unwrap_or(ProjectId::new_random()),
@@ -204,24 +217,42 @@ pub(crate) async fn list_roles<'e, 'c: 'e, E: sqlx::Executor<'c, Database = sqlx
             .collect::<Vec<uuid::Uuid>>() as Vec<Uuid>,
         filter_name.is_empty(),
         filter_name.to_string(),
-        token_ts,
+        sort.map(|s| s.by.to_string()),
+        sort.is_some_and(ListSort::is_desc),
+        token_key,
         token_id,
         page_size,
     )
     .fetch_all(connection)
     .await
-    .map_err(|e| e.into_error_model("Error fetching roles".to_string()))?
-    .into_iter()
-    .map(Role::from)
-    .collect();
+    .map_err(|e| e.into_error_model("Error fetching roles".to_string()))?;
+
+    let next_page_token = rows.last().map(|r| {
+        if sort.is_some() {
+            PaginateToken::V3(V3PaginateToken {
+                sort: sort_name.clone(),
+                key: r.sort_key.clone(),
+                id: r.id,
+            })
+            .to_string()
+        } else {
+            PaginateToken::V2(V2PaginateToken::<Uuid> { id: r.id }).to_string()
+        }
+    });
 
-    let next_page_token = roles.last().map(|r| {
-        PaginateToken::V1(V1PaginateToken::<Uuid> {
-            created_at: r.created_at,
-            id: r.id.into(),
+    let roles = rows
+        .into_iter()
+        .map(|r| {
+            Role::from(RoleRow {
+                id: r.id,
+                name: r.name,
+                description: r.description,
+                project_id: r.project_id,
+                created_at: r.created_at,
+                updated_at: r.updated_at,
+            })
         })
-        .to_string()
-    });
+        .collect();
 
     Ok(ListRolesResponse {
         roles,
@@ -252,7 +283,10 @@ pub(crate) async fn delete_role<'e, 'c: 'e, E: sqlx::Executor<'c, Database = sql
 mod test {
     use super::*;
     use crate::{
-        api::iceberg::v1::PageToken,
+        api::{
+            iceberg::v1::PageToken,
+            management::v1::{SortBy, SortOrder},
+        },
         implementations::postgres::{CatalogState, PostgresCatalog, PostgresTransaction},
         service::{Catalog, Transaction},
     };
@@ -473,6 +507,7 @@ mod test {
             None,
             None,
             None,
+            None,
             PaginationQuery {
                 page_size: Some(10),
                 page_token: PageToken::Empty,
@@ -488,6 +523,7 @@ mod test {
             Some(project1_id),
             None,
             None,
+            None,
             PaginationQuery {
                 page_size: Some(10),
                 page_token: PageToken::Empty,
@@ -505,6 +541,7 @@ mod test {
             None,
             Some(vec![role2_id]),
             None,
+            None,
             PaginationQuery {
                 page_size: Some(10),
                 page_token: PageToken::Empty,
@@ -522,6 +559,7 @@ mod test {
             None,
             None,
             Some("Role 1".to_string()),
+            None,
             PaginationQuery {
                 page_size: Some(10),
                 page_token: PageToken::Empty,
@@ -570,6 +608,7 @@ mod test {
             None,
             None,
             None,
+            None,
             PaginationQuery {
                 page_size: Some(10),
                 page_token: PageToken::Empty,
@@ -584,6 +623,7 @@ mod test {
             None,
             None,
             None,
+            None,
             PaginationQuery {
                 page_size: Some(5),
                 page_token: PageToken::Empty,
@@ -603,6 +643,7 @@ mod test {
             None,
             None,
             None,
+            None,
             PaginationQuery {
                 page_size: Some(5),
                 page_token: roles.next_page_token.into(),
@@ -621,6 +662,7 @@ mod test {
             None,
             None,
             None,
+            None,
             PaginationQuery {
                 page_size: Some(5),
                 page_token: roles.next_page_token.into(),
@@ -633,6 +675,89 @@ mod test {
         assert!(roles.next_page_token.is_none());
     }
 
+    #[sqlx::test]
+    async fn test_sort_roles(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let project_id = ProjectId::new_random();
+
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        PostgresCatalog::create_project(
+            &project_id,
+            format!("Project {project_id}"),
+            t.transaction(),
+        )
+        .await
+        .unwrap();
+        t.commit().await.unwrap();
+
+        for name in ["beta", "Alpha", "gamma", "delta", "Epsilon"] {
+            create_role(
+                RoleId::new_random(),
+                &project_id,
+                name,
+                None,
+                &state.write_pool(),
+            )
+            .await
+            .unwrap();
+        }
+
+        let sort = Some(ListSort {
+            by: SortBy::Name,
+            order: SortOrder::Asc,
+        });
+        let mut names = vec![];
+        let mut page_token = PageToken::Empty;
+        loop {
+            let roles = list_roles(
+                None,
+                None,
+                None,
+                sort,
+                PaginationQuery {
+                    page_size: Some(2),
+                    page_token,
+                },
+                &state.read_pool(),
+            )
+            .await
+            .unwrap();
+            if roles.roles.is_empty() {
+                break;
+            }
+            names.extend(roles.roles.into_iter().map(|r| r.name));
+            page_token = roles.next_page_token.into();
+        }
+        assert_eq!(names, vec!["Alpha", "beta", "delta", "Epsilon", "gamma"]);
+
+        let roles = list_roles(
+            None,
+            None,
+            None,
+            Some(ListSort {
+                by: SortBy::CreatedAt,
+                order: SortOrder::Desc,
+            }),
+            PaginationQuery {
+                page_size: Some(10),
+                page_token: PageToken::Empty,
+            },
+            &state.read_pool(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            roles
+                .roles
+                .iter()
+                .map(|r| r.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Epsilon", "delta", "gamma", "Alpha", "beta"]
+        );
+    }
+
     #[sqlx::test]
     async fn test_delete_role(pool: sqlx::PgPool) {
         let state = CatalogState::from_pools(pool.clone(), pool.clone());
@@ -672,6 +797,7 @@ mod test {
             None,
             None,
             None,
+            None,
             PaginationQuery {
                 page_size: Some(10),
                 page_token: PageToken::Empty,
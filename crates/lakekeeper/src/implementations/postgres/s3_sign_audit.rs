@@ -0,0 +1,199 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::{
+    api::{
+        iceberg::v1::{PaginationQuery, MAX_PAGE_SIZE},
+        management::v1::table::{ListTableSigningsResponse, TableSigning},
+    },
+    implementations::postgres::{
+        dbutils::DBErrorHandler,
+        pagination::{PaginateToken, V2PaginateToken},
+    },
+    service::{Result, TableId},
+    WarehouseId,
+};
+
+pub(crate) async fn record_table_signing<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseId,
+    signing: &TableSigning,
+    connection: E,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO s3_sign_audit (s3_sign_audit_id, warehouse_id, table_id, request_id,
+            principal, assumed_role_id, method, locations, signed_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        "#,
+        signing.signing_id,
+        *warehouse_id,
+        signing.table_id,
+        signing.request_id,
+        signing.principal,
+        signing.assumed_role_id,
+        signing.method,
+        &signing.locations,
+        signing.signed_at,
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error recording table signing"))?;
+
+    Ok(())
+}
+
+pub(crate) async fn list_table_signings<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseId,
+    table_id: TableId,
+    PaginationQuery {
+        page_size,
+        page_token,
+    }: PaginationQuery,
+    connection: E,
+) -> Result<ListTableSigningsResponse> {
+    let page_size = page_size.map_or(MAX_PAGE_SIZE, |i| i.clamp(1, MAX_PAGE_SIZE));
+
+    let token_id: Option<Uuid> = page_token
+        .as_option()
+        .map(PaginateToken::try_from)
+        .transpose()?
+        .map(PaginateToken::into_id);
+
+    let signings: Vec<TableSigning> = sqlx::query_as!(
+        TableSigning,
+        r#"
+        SELECT s3_sign_audit_id as signing_id, table_id, request_id, principal,
+            assumed_role_id, method, locations, signed_at
+        FROM s3_sign_audit
+        WHERE warehouse_id = $1 AND table_id = $2
+            AND (s3_sign_audit_id < $3 OR $3 IS NULL)
+        ORDER BY s3_sign_audit_id DESC
+        LIMIT $4
+        "#,
+        *warehouse_id,
+        *table_id,
+        token_id,
+        page_size,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching table signings"))?;
+
+    let next_page_token = signings
+        .last()
+        .map(|s| PaginateToken::V2(V2PaginateToken::<Uuid> { id: s.signing_id }).to_string());
+
+    Ok(ListTableSigningsResponse {
+        signings,
+        next_page_token,
+    })
+}
+
+pub(crate) async fn delete_table_signings<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    signed_before: DateTime<Utc>,
+    limit: i64,
+    connection: E,
+) -> Result<u64> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM s3_sign_audit
+        WHERE s3_sign_audit_id IN (
+            SELECT s3_sign_audit_id FROM s3_sign_audit
+            WHERE signed_at < $1
+            ORDER BY signed_at
+            LIMIT $2
+        )
+        "#,
+        signed_before,
+        limit,
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error deleting table signings"))?;
+
+    Ok(result.rows_affected())
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::Duration;
+
+    use super::*;
+    use crate::{
+        api::iceberg::v1::PageToken,
+        implementations::postgres::{warehouse::test::initialize_warehouse, CatalogState},
+    };
+
+    fn signing(table_id: TableId, signed_at: DateTime<Utc>) -> TableSigning {
+        TableSigning {
+            signing_id: Uuid::now_v7(),
+            table_id: *table_id,
+            request_id: Uuid::now_v7(),
+            principal: Some("oidc~alice".to_string()),
+            assumed_role_id: None,
+            method: "GET".to_string(),
+            locations: vec!["s3://bucket/table/data/00000.parquet".to_string()],
+            signed_at,
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_table_signings(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let table_id = TableId::new_random();
+        let other_table_id = TableId::new_random();
+
+        // Rounded to microseconds to match the precision of Postgres
+        let now = DateTime::from_timestamp_micros(Utc::now().timestamp_micros()).unwrap();
+        let old = signing(table_id, now - Duration::days(10));
+        let first = signing(table_id, now);
+        let second = signing(table_id, now);
+        for s in [&old, &first, &second, &signing(other_table_id, now)] {
+            record_table_signing(warehouse_id, s, &pool).await.unwrap();
+        }
+
+        let page = list_table_signings(
+            warehouse_id,
+            table_id,
+            PaginationQuery {
+                page_size: Some(2),
+                page_token: PageToken::NotSpecified,
+            },
+            &pool,
+        )
+        .await
+        .unwrap();
+        assert_eq!(page.signings, vec![second, first]);
+
+        let page = list_table_signings(
+            warehouse_id,
+            table_id,
+            PaginationQuery {
+                page_size: Some(2),
+                page_token: PageToken::Present(page.next_page_token.unwrap()),
+            },
+            &pool,
+        )
+        .await
+        .unwrap();
+        assert_eq!(page.signings, vec![old]);
+
+        let deleted = delete_table_signings(now - Duration::days(1), 100, &pool)
+            .await
+            .unwrap();
+        assert_eq!(deleted, 1);
+    }
+}
@@ -0,0 +1,530 @@
+use sqlx::types::Json;
+
+use super::dbutils::DBErrorHandler;
+use crate::{
+    api::{
+        management::v1::search::{
+            CatalogSearchFilter, GlobalSearchField, GlobalSearchMatch, SearchCatalogResult,
+            SearchEntityType, CERTIFICATION_PROPERTY, OWNER_PROPERTY,
+        },
+        Result,
+    },
+    service::labels::Labels,
+    ProjectId, WarehouseId,
+};
+
+/// Escapes `LIKE` wildcards so that the search term is matched literally.
+fn escape_like(search: &str) -> String {
+    search
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+pub(crate) async fn search_catalog<'e, 'c: 'e, E: sqlx::Executor<'c, Database = sqlx::Postgres>>(
+    project_id: &ProjectId,
+    filter: &CatalogSearchFilter,
+    limit: i64,
+    connection: E,
+) -> Result<Vec<SearchCatalogResult>> {
+    let entity_types = filter.entity_types.as_ref().map(|types| {
+        types
+            .iter()
+            .map(|t| t.as_str().to_string())
+            .collect::<Vec<_>>()
+    });
+    let warehouse_ids = filter
+        .warehouse_ids
+        .as_ref()
+        .map(|ids| ids.iter().map(|id| **id).collect::<Vec<_>>());
+    let search = filter.search.as_deref().map(escape_like);
+    // Selectors with a value are matched by containment, selectors without one by key.
+    let (label_values, label_keys) = match &filter.labels {
+        Some(selectors) => {
+            let values = selectors
+                .iter()
+                .filter_map(|s| s.value.as_ref().map(|v| (s.key.clone(), v.clone())))
+                .collect::<Labels>();
+            let keys = selectors
+                .iter()
+                .filter(|s| s.value.is_none())
+                .map(|s| s.key.clone())
+                .collect::<Vec<_>>();
+            (Some(Json(values)), Some(keys))
+        }
+        None => (None, None),
+    };
+
+    let rows = sqlx::query!(
+        r#"
+        WITH wh AS (
+            SELECT warehouse_id, warehouse_name
+            FROM warehouse
+            WHERE project_id = $1
+                AND status = 'active'
+                AND ($3::uuid[] IS NULL OR warehouse_id = ANY($3))
+        ),
+        entities AS (
+            SELECT 'namespace' AS entity_type,
+                n.namespace_id AS id,
+                n.warehouse_id,
+                wh.warehouse_name,
+                n.namespace_name[1:array_length(n.namespace_name, 1) - 1] AS parent,
+                n.namespace_name[array_length(n.namespace_name, 1)] AS name,
+                n.namespace_properties ->> $11 AS owner,
+                n.namespace_properties ->> $12 AS certification,
+                ARRAY[]::text[] AS tags,
+                n.labels,
+                coalesce(n.updated_at, n.created_at) AS last_modified
+            FROM namespace n
+            JOIN wh ON wh.warehouse_id = n.warehouse_id
+            WHERE n.deleted_at IS NULL
+                AND ($2::text[] IS NULL OR 'namespace' = ANY($2))
+            UNION ALL
+            SELECT t.typ::text,
+                t.tabular_id,
+                n.warehouse_id,
+                wh.warehouse_name,
+                n.namespace_name,
+                t.name,
+                coalesce(
+                    (SELECT value FROM table_properties p WHERE p.table_id = t.tabular_id AND p.key = $11),
+                    (SELECT value FROM view_properties p WHERE p.view_id = t.tabular_id AND p.key = $11)
+                ),
+                coalesce(
+                    (SELECT value FROM table_properties p WHERE p.table_id = t.tabular_id AND p.key = $12),
+                    (SELECT value FROM view_properties p WHERE p.view_id = t.tabular_id AND p.key = $12)
+                ),
+                coalesce(
+                    (SELECT array_agg(DISTINCT ct.tag ORDER BY ct.tag) FROM column_tag ct WHERE ct.table_id = t.tabular_id),
+                    ARRAY[]::text[]
+                ),
+                t.labels,
+                coalesce(t.updated_at, t.created_at)
+            FROM tabular t
+            JOIN namespace n ON n.namespace_id = t.namespace_id
+            JOIN wh ON wh.warehouse_id = n.warehouse_id
+            WHERE t.deleted_at IS NULL
+                AND t.metadata_location IS NOT NULL
+                AND ($2::text[] IS NULL OR t.typ::text = ANY($2))
+        )
+        SELECT entity_type as "entity_type!",
+            id as "id!",
+            warehouse_id as "warehouse_id!",
+            warehouse_name as "warehouse_name!",
+            parent as "parent!",
+            name as "name!",
+            owner,
+            certification,
+            tags as "tags!",
+            last_modified as "last_modified!"
+        FROM entities
+        WHERE ($4::text[] IS NULL OR tags && $4)
+            AND ($5::text[] IS NULL OR owner = ANY($5))
+            AND ($6::text[] IS NULL OR certification = ANY($6))
+            AND ($7::timestamptz IS NULL OR last_modified >= $7)
+            AND ($8::timestamptz IS NULL OR last_modified < $8)
+            AND ($9::text IS NULL OR lower(name COLLATE "C") LIKE '%' || lower($9) || '%')
+            AND ($13::uuid[] IS NULL OR id = ANY($13))
+            AND ($14::jsonb IS NULL OR labels @> $14)
+            AND ($15::text[] IS NULL OR labels ?& $15)
+        ORDER BY last_modified DESC, id
+        LIMIT $10
+        "#,
+        project_id.to_string(),
+        entity_types.as_deref(),
+        warehouse_ids.as_deref(),
+        filter.tags.as_deref(),
+        filter.owners.as_deref(),
+        filter.certifications.as_deref(),
+        filter.modified_after,
+        filter.modified_before,
+        search,
+        limit,
+        OWNER_PROPERTY,
+        CERTIFICATION_PROPERTY,
+        filter.ids.as_deref(),
+        label_values as _,
+        label_keys.as_deref(),
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error searching catalog".to_string()))?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            Some(SearchCatalogResult {
+                entity_type: entity_type(&row.entity_type)?,
+                id: row.id,
+                warehouse_id: WarehouseId::from(row.warehouse_id),
+                warehouse_name: row.warehouse_name,
+                namespace: row.parent,
+                name: row.name,
+                owner: row.owner,
+                certification: row.certification,
+                tags: row.tags,
+                last_modified: row.last_modified,
+            })
+        })
+        .collect())
+}
+
+fn entity_type(entity_type: &str) -> Option<SearchEntityType> {
+    match entity_type {
+        "namespace" => Some(SearchEntityType::Namespace),
+        "table" => Some(SearchEntityType::Table),
+        "view" => Some(SearchEntityType::View),
+        _ => None,
+    }
+}
+
+/// Fuzzy search over the names, comments and property values of namespaces, tables and views
+/// of all active warehouses of a project, backed by trigram indexes.
+///
+/// Values match if they contain the search term or a word similar to it. Only the best
+/// match per entity is returned, best matches first.
+pub(crate) async fn global_search<'e, 'c: 'e, E: sqlx::Executor<'c, Database = sqlx::Postgres>>(
+    project_id: &ProjectId,
+    search: &str,
+    warehouse_id: Option<WarehouseId>,
+    limit: i64,
+    connection: E,
+) -> Result<Vec<GlobalSearchMatch>> {
+    let search = search.to_lowercase();
+    let pattern = format!("%{}%", escape_like(&search));
+
+    let rows = sqlx::query!(
+        r#"
+        WITH wh AS (
+            SELECT warehouse_id
+            FROM warehouse
+            WHERE project_id = $1
+                AND status = 'active'
+                AND ($4::uuid IS NULL OR warehouse_id = $4)
+        ),
+        matches AS (
+            SELECT 'namespace' AS entity_type,
+                n.namespace_id AS id,
+                'name' AS matched_field,
+                n.namespace_name[array_length(n.namespace_name, 1)] AS matched_value,
+                word_similarity($2, lower(n.namespace_name[array_length(n.namespace_name, 1)] collate "C")) AS score
+            FROM namespace n
+            JOIN wh ON wh.warehouse_id = n.warehouse_id
+            WHERE n.deleted_at IS NULL
+                AND ($2 <% lower(n.namespace_name[array_length(n.namespace_name, 1)] collate "C")
+                    OR lower(n.namespace_name[array_length(n.namespace_name, 1)] collate "C") LIKE $3)
+            UNION ALL
+            SELECT 'namespace',
+                n.namespace_id,
+                CASE WHEN p.key = 'comment' THEN 'comment' ELSE 'property' END,
+                p.value,
+                word_similarity($2, lower(p.value collate "C"))
+            FROM namespace n
+            JOIN wh ON wh.warehouse_id = n.warehouse_id
+            CROSS JOIN LATERAL jsonb_each_text(n.namespace_properties) p
+            WHERE n.deleted_at IS NULL
+                AND ($2 <% lower(p.value collate "C") OR lower(p.value collate "C") LIKE $3)
+            UNION ALL
+            SELECT t.typ::text,
+                t.tabular_id,
+                'name',
+                t.name,
+                word_similarity($2, lower(t.name collate "C"))
+            FROM tabular t
+            JOIN namespace n ON n.namespace_id = t.namespace_id
+            JOIN wh ON wh.warehouse_id = n.warehouse_id
+            WHERE t.deleted_at IS NULL
+                AND t.metadata_location IS NOT NULL
+                AND ($2 <% lower(t.name collate "C") OR lower(t.name collate "C") LIKE $3)
+            UNION ALL
+            SELECT t.typ::text,
+                t.tabular_id,
+                CASE WHEN p.key = 'comment' THEN 'comment' ELSE 'property' END,
+                p.value,
+                word_similarity($2, lower(p.value collate "C"))
+            FROM (
+                SELECT table_id AS tabular_id, key, value FROM table_properties
+                UNION ALL
+                SELECT view_id, key, value FROM view_properties
+            ) p
+            JOIN tabular t ON t.tabular_id = p.tabular_id
+            JOIN namespace n ON n.namespace_id = t.namespace_id
+            JOIN wh ON wh.warehouse_id = n.warehouse_id
+            WHERE t.deleted_at IS NULL
+                AND t.metadata_location IS NOT NULL
+                AND ($2 <% lower(p.value collate "C") OR lower(p.value collate "C") LIKE $3)
+        ),
+        best AS (
+            SELECT DISTINCT ON (id) entity_type, id, matched_field, matched_value, score
+            FROM matches
+            ORDER BY id, score DESC, matched_field = 'name' DESC
+        )
+        SELECT entity_type as "entity_type!",
+            id as "id!",
+            matched_field as "matched_field!",
+            matched_value as "matched_value!",
+            score as "score!"
+        FROM best
+        ORDER BY score DESC, matched_field = 'name' DESC, id
+        LIMIT $5
+        "#,
+        project_id.to_string(),
+        search,
+        pattern,
+        warehouse_id.map(|id| *id),
+        limit,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error searching catalog".to_string()))?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let matched_field = match row.matched_field.as_str() {
+                "name" => GlobalSearchField::Name,
+                "comment" => GlobalSearchField::Comment,
+                _ => GlobalSearchField::Property,
+            };
+            Some(GlobalSearchMatch {
+                entity_type: entity_type(&row.entity_type)?,
+                id: row.id,
+                matched_field,
+                matched_value: row.matched_value,
+                score: row.score,
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::{
+        implementations::postgres::{
+            label::set_labels,
+            namespace::tests::initialize_namespace,
+            tabular::table::{set_column_tags, tests::initialize_table},
+            warehouse::test::initialize_warehouse,
+            CatalogState,
+        },
+        service::labels::{parse_label_selectors, LabelEntity},
+    };
+
+    #[test]
+    fn test_escape_like() {
+        assert_eq!(escape_like("my_table%"), "my\\_table\\%");
+        assert_eq!(escape_like("a\\b"), "a\\\\b");
+    }
+
+    #[sqlx::test]
+    async fn test_search_catalog(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let project_id = ProjectId::from(uuid::Uuid::nil());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let namespace = iceberg::NamespaceIdent::from_vec(vec!["sales".to_string()]).unwrap();
+        initialize_namespace(state.clone(), warehouse_id, &namespace, None).await;
+        let orders = initialize_table(
+            warehouse_id,
+            state.clone(),
+            false,
+            Some(namespace.clone()),
+            Some("Orders".to_string()),
+        )
+        .await;
+        initialize_table(
+            warehouse_id,
+            state.clone(),
+            false,
+            Some(namespace.clone()),
+            Some("customers".to_string()),
+        )
+        .await;
+
+        let mut transaction = state.write_pool().begin().await.unwrap();
+        set_column_tags(
+            warehouse_id,
+            orders.table_id,
+            &HashMap::from([(1, vec!["pii".to_string()])]),
+            &mut transaction,
+        )
+        .await
+        .unwrap();
+        transaction.commit().await.unwrap();
+
+        let all = search_catalog(
+            &project_id,
+            &CatalogSearchFilter::default(),
+            100,
+            &state.read_pool(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(all.len(), 3);
+        assert!(all.iter().all(|r| r.warehouse_id == warehouse_id));
+
+        let by_name = search_catalog(
+            &project_id,
+            &CatalogSearchFilter {
+                search: Some("ORD".to_string()),
+                ..Default::default()
+            },
+            100,
+            &state.read_pool(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(by_name.len(), 1);
+        assert_eq!(by_name[0].id, *orders.table_id);
+        assert_eq!(by_name[0].namespace, vec!["sales".to_string()]);
+        assert_eq!(by_name[0].tags, vec!["pii".to_string()]);
+
+        let by_tag = search_catalog(
+            &project_id,
+            &CatalogSearchFilter {
+                tags: Some(vec!["pii".to_string()]),
+                ..Default::default()
+            },
+            100,
+            &state.read_pool(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(by_tag.len(), 1);
+
+        let namespaces = search_catalog(
+            &project_id,
+            &CatalogSearchFilter {
+                entity_types: Some(vec![SearchEntityType::Namespace]),
+                ..Default::default()
+            },
+            100,
+            &state.read_pool(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(namespaces.len(), 1);
+        assert_eq!(namespaces[0].name, "sales");
+        assert!(namespaces[0].namespace.is_empty());
+
+        let limited = search_catalog(
+            &project_id,
+            &CatalogSearchFilter::default(),
+            2,
+            &state.read_pool(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(limited.len(), 2);
+
+        let by_id = search_catalog(
+            &project_id,
+            &CatalogSearchFilter {
+                ids: Some(vec![*orders.table_id, uuid::Uuid::now_v7()]),
+                ..Default::default()
+            },
+            100,
+            &state.read_pool(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(by_id.len(), 1);
+        assert_eq!(by_id[0].id, *orders.table_id);
+
+        set_labels(
+            warehouse_id,
+            LabelEntity::Table(orders.table_id),
+            &Labels::from([
+                ("classification".to_string(), "pii".to_string()),
+                ("team".to_string(), "sales".to_string()),
+            ]),
+            &pool,
+        )
+        .await
+        .unwrap();
+        for (selectors, expected) in [
+            ("classification=pii,team", 1),
+            ("classification=public", 0),
+            ("team", 1),
+            ("tier", 0),
+        ] {
+            let by_label = search_catalog(
+                &project_id,
+                &CatalogSearchFilter {
+                    labels: Some(parse_label_selectors(selectors).unwrap()),
+                    ..Default::default()
+                },
+                100,
+                &state.read_pool(),
+            )
+            .await
+            .unwrap();
+            assert_eq!(by_label.len(), expected, "{selectors}");
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_global_search(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let project_id = ProjectId::from(uuid::Uuid::nil());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let namespace = iceberg::NamespaceIdent::from_vec(vec!["sales".to_string()]).unwrap();
+        let (namespace_id, _) = initialize_namespace(
+            state.clone(),
+            warehouse_id,
+            &namespace,
+            Some(HashMap::from([(
+                "comment".to_string(),
+                "Customer invoices".to_string(),
+            )])),
+        )
+        .await;
+        let orders = initialize_table(
+            warehouse_id,
+            state.clone(),
+            false,
+            Some(namespace.clone()),
+            Some("Orders".to_string()),
+        )
+        .await;
+
+        // Substring and misspelled names match
+        for search in ["ORD", "ordr"] {
+            let matches = global_search(&project_id, search, None, 10, &pool)
+                .await
+                .unwrap();
+            assert_eq!(matches.len(), 1, "{search}");
+            assert_eq!(matches[0].id, *orders.table_id);
+            assert_eq!(matches[0].entity_type, SearchEntityType::Table);
+            assert_eq!(matches[0].matched_field, GlobalSearchField::Name);
+            assert_eq!(matches[0].matched_value, "Orders");
+        }
+
+        let matches = global_search(&project_id, "invoice", Some(warehouse_id), 10, &pool)
+            .await
+            .unwrap();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].id, *namespace_id);
+        assert_eq!(matches[0].entity_type, SearchEntityType::Namespace);
+        assert_eq!(matches[0].matched_field, GlobalSearchField::Comment);
+        assert_eq!(matches[0].matched_value, "Customer invoices");
+
+        let matches = global_search(
+            &project_id,
+            "orders",
+            Some(WarehouseId::new_random()),
+            10,
+            &pool,
+        )
+        .await
+        .unwrap();
+        assert!(matches.is_empty());
+        assert!(global_search(&project_id, "zzzzzz", None, 10, &pool)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -186,6 +186,7 @@ mod tests {
             aws_access_key_id: "my access key".to_string(),
             aws_secret_access_key: "my secret key".to_string(),
             external_id: None,
+            sse_customer_key: None,
         })
         .into();
 
@@ -207,6 +208,7 @@ mod tests {
             aws_access_key_id: "my access key".to_string(),
             aws_secret_access_key: "my secret key".to_string(),
             external_id: None,
+            sse_customer_key: None,
         })
         .into();
 
@@ -0,0 +1,197 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::{
+    api::{
+        iceberg::v1::{PaginationQuery, MAX_PAGE_SIZE},
+        management::v1::server_event::ListServerEventsResponse,
+    },
+    implementations::postgres::{
+        dbutils::DBErrorHandler,
+        pagination::{PaginateToken, V2PaginateToken},
+    },
+    service::{
+        server_events::{ServerEvent, ServerEventType},
+        Result,
+    },
+};
+
+pub(crate) async fn record_server_event<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    event: &ServerEvent,
+    connection: E,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO server_event (server_event_id, event_type, instance_id, hostname,
+            principal, details, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+        event.server_event_id,
+        event.event_type as _,
+        event.instance_id,
+        event.hostname,
+        event.principal,
+        event.details,
+        event.created_at,
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error recording server event"))?;
+
+    Ok(())
+}
+
+pub(crate) async fn list_server_events<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    event_types: Option<Vec<ServerEventType>>,
+    PaginationQuery {
+        page_size,
+        page_token,
+    }: PaginationQuery,
+    connection: E,
+) -> Result<ListServerEventsResponse> {
+    let page_size = page_size.map_or(MAX_PAGE_SIZE, |i| i.clamp(1, MAX_PAGE_SIZE));
+
+    let token_id: Option<Uuid> = page_token
+        .as_option()
+        .map(PaginateToken::try_from)
+        .transpose()?
+        .map(PaginateToken::into_id)
+        .transpose()?;
+
+    let events: Vec<ServerEvent> = sqlx::query_as!(
+        ServerEvent,
+        r#"
+        SELECT server_event_id, event_type as "event_type: ServerEventType", instance_id,
+            hostname, principal, details, created_at
+        FROM server_event
+        WHERE (event_type = ANY($1::server_event_type[]) OR $1 IS NULL)
+            AND (server_event_id < $2 OR $2 IS NULL)
+        ORDER BY server_event_id DESC
+        LIMIT $3
+        "#,
+        event_types as Option<Vec<ServerEventType>>,
+        token_id,
+        page_size,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching server events"))?;
+
+    let next_page_token = events.last().map(|e| {
+        PaginateToken::V2(V2PaginateToken::<Uuid> {
+            id: e.server_event_id,
+        })
+        .to_string()
+    });
+
+    Ok(ListServerEventsResponse {
+        events,
+        next_page_token,
+    })
+}
+
+pub(crate) async fn delete_server_events<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    created_before: DateTime<Utc>,
+    limit: i64,
+    connection: E,
+) -> Result<u64> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM server_event
+        WHERE server_event_id IN (
+            SELECT server_event_id FROM server_event
+            WHERE created_at < $1
+            ORDER BY created_at
+            LIMIT $2
+        )
+        "#,
+        created_before,
+        limit,
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error deleting server events"))?;
+
+    Ok(result.rows_affected())
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::Duration;
+
+    use super::*;
+    use crate::api::iceberg::v1::PageToken;
+
+    fn event(event_type: ServerEventType, created_at: DateTime<Utc>) -> ServerEvent {
+        ServerEvent {
+            created_at,
+            ..ServerEvent::new(
+                event_type,
+                None,
+                serde_json::json!({"version": env!("CARGO_PKG_VERSION")}),
+            )
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_server_events(pool: sqlx::PgPool) {
+        // Rounded to microseconds to match the precision of Postgres
+        let now = DateTime::from_timestamp_micros(Utc::now().timestamp_micros()).unwrap();
+        let old = event(ServerEventType::Startup, now - Duration::days(100));
+        let crashed = event(ServerEventType::TaskWorkerCrashed, now);
+        let shutdown = event(ServerEventType::Shutdown, now);
+        for e in [&old, &crashed, &shutdown] {
+            record_server_event(e, &pool).await.unwrap();
+        }
+
+        let page = list_server_events(
+            None,
+            PaginationQuery {
+                page_size: Some(2),
+                page_token: PageToken::NotSpecified,
+            },
+            &pool,
+        )
+        .await
+        .unwrap();
+        assert_eq!(page.events, vec![shutdown.clone(), crashed.clone()]);
+
+        let page = list_server_events(
+            None,
+            PaginationQuery {
+                page_size: Some(2),
+                page_token: PageToken::Present(page.next_page_token.unwrap()),
+            },
+            &pool,
+        )
+        .await
+        .unwrap();
+        assert_eq!(page.events, vec![old]);
+
+        let page = list_server_events(
+            Some(vec![ServerEventType::TaskWorkerCrashed]),
+            PaginationQuery::empty(),
+            &pool,
+        )
+        .await
+        .unwrap();
+        assert_eq!(page.events, vec![crashed]);
+
+        let deleted = delete_server_events(now - Duration::days(90), 100, &pool)
+            .await
+            .unwrap();
+        assert_eq!(deleted, 1);
+    }
+}
@@ -0,0 +1,220 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use iceberg_ext::{catalog::rest::ErrorModel, configs::Location};
+use uuid::Uuid;
+
+use crate::{
+    implementations::postgres::dbutils::DBErrorHandler,
+    service::{
+        storage_intent::{StorageIntent, StorageIntentKind},
+        Result,
+    },
+};
+
+pub(crate) async fn record_storage_intents<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    intents: &[StorageIntent],
+    connection: E,
+) -> Result<()> {
+    if intents.is_empty() {
+        return Ok(());
+    }
+
+    let mut intent_ids = Vec::with_capacity(intents.len());
+    let mut warehouse_ids = Vec::with_capacity(intents.len());
+    let mut table_ids = Vec::with_capacity(intents.len());
+    let mut kinds = Vec::with_capacity(intents.len());
+    let mut locations = Vec::with_capacity(intents.len());
+    for intent in intents {
+        intent_ids.push(intent.intent_id);
+        warehouse_ids.push(*intent.warehouse_id);
+        table_ids.push(*intent.table_id);
+        kinds.push(intent.kind);
+        locations.push(intent.location.to_string());
+    }
+
+    sqlx::query!(
+        r#"
+        INSERT INTO storage_intent (storage_intent_id, warehouse_id, table_id, kind, location)
+        SELECT * FROM UNNEST(
+            $1::UUID[],
+            $2::UUID[],
+            $3::UUID[],
+            $4::storage_intent_kind[],
+            $5::TEXT[]
+        )
+        "#,
+        &intent_ids,
+        &warehouse_ids,
+        &table_ids,
+        &kinds as &[StorageIntentKind],
+        &locations,
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error recording storage intents"))?;
+
+    Ok(())
+}
+
+pub(crate) async fn complete_storage_intents<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    intent_ids: &[Uuid],
+    connection: E,
+) -> Result<()> {
+    if intent_ids.is_empty() {
+        return Ok(());
+    }
+
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM storage_intent
+        WHERE storage_intent_id = ANY($1::UUID[])
+        "#,
+        intent_ids,
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error completing storage intents"))?;
+
+    if result.rows_affected() != intent_ids.len() as u64 {
+        return Err(ErrorModel::conflict(
+            "Storage intents have been recovered concurrently, files they refer to may have been deleted. Please retry.",
+            "StorageIntentRecovered",
+            None,
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn claim_stale_storage_intents<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    recorded_before: DateTime<Utc>,
+    limit: i64,
+    connection: E,
+) -> Result<Vec<StorageIntent>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT storage_intent_id, warehouse_id, table_id, kind as "kind: StorageIntentKind", location
+        FROM storage_intent
+        WHERE created_at < $1
+        ORDER BY created_at, storage_intent_id
+        LIMIT $2
+        FOR UPDATE SKIP LOCKED
+        "#,
+        recorded_before,
+        limit,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error claiming stale storage intents"))?;
+
+    rows.into_iter()
+        .map(|row| {
+            let location = Location::from_str(&row.location).map_err(|e| {
+                ErrorModel::internal(
+                    format!(
+                        "Invalid location '{}' of storage intent {}",
+                        row.location, row.storage_intent_id
+                    ),
+                    "InvalidStorageIntentLocation",
+                    Some(Box::new(e)),
+                )
+            })?;
+            Ok(StorageIntent {
+                intent_id: row.storage_intent_id,
+                warehouse_id: row.warehouse_id.into(),
+                table_id: row.table_id.into(),
+                kind: row.kind,
+                location,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::Duration;
+
+    use super::*;
+    use crate::implementations::postgres::{
+        tabular::table::tests::initialize_table, warehouse::test::initialize_warehouse,
+        CatalogState,
+    };
+
+    #[sqlx::test]
+    async fn test_storage_intent_lifecycle(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let table = initialize_table(warehouse_id, state.clone(), false, None, None).await;
+
+        let write = StorageIntent::new(
+            warehouse_id,
+            table.table_id,
+            StorageIntentKind::WriteMetadata,
+            Location::from_str("s3://bucket/table/metadata/00001.metadata.json").unwrap(),
+        );
+        let delete = StorageIntent::new(
+            warehouse_id,
+            table.table_id,
+            StorageIntentKind::DeleteFile,
+            Location::from_str("s3://bucket/table/metadata/00000.metadata.json").unwrap(),
+        );
+        record_storage_intents(&[write.clone(), delete.clone()], &pool)
+            .await
+            .unwrap();
+
+        // Fresh intents are not stale yet
+        let claimed = claim_stale_storage_intents(Utc::now() - Duration::minutes(1), 10, &pool)
+            .await
+            .unwrap();
+        assert!(claimed.is_empty());
+
+        let mut transaction = pool.begin().await.unwrap();
+        let claimed = claim_stale_storage_intents(Utc::now(), 10, &mut *transaction)
+            .await
+            .unwrap();
+        assert_eq!(claimed, vec![write.clone(), delete.clone()]);
+
+        // Claimed intents are locked for other recoveries
+        let mut other = pool.begin().await.unwrap();
+        assert!(claim_stale_storage_intents(Utc::now(), 10, &mut *other)
+            .await
+            .unwrap()
+            .is_empty());
+        other.rollback().await.unwrap();
+
+        complete_storage_intents(&[delete.intent_id], &mut *transaction)
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+
+        // Completing an intent that no longer exists conflicts
+        let mut transaction = pool.begin().await.unwrap();
+        let err = complete_storage_intents(&[write.intent_id, delete.intent_id], &mut *transaction)
+            .await
+            .unwrap_err();
+        assert_eq!(err.error.code, 409);
+        transaction.rollback().await.unwrap();
+
+        complete_storage_intents(&[write.intent_id], &pool)
+            .await
+            .unwrap();
+        assert!(claim_stale_storage_intents(Utc::now(), 10, &pool)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
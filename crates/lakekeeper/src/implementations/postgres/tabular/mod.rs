@@ -20,12 +20,13 @@ use crate::{
         management::v1::ProtectionResponse,
     },
     catalog::tables::CONCURRENT_UPDATE_ERROR_TYPE,
-    implementations::postgres::pagination::{PaginateToken, V1PaginateToken},
+    implementations::postgres::pagination::{PaginateToken, V2PaginateToken},
     service::{
         storage::{join_location, split_location},
         task_queue::TaskId,
         DeletionDetails, ErrorModel, NamespaceId, Result, TableId, TableIdent, TabularId,
-        TabularIdentBorrowed, TabularIdentOwned, TabularInfo, UndropTabularResponse,
+        TabularIdentBorrowed, TabularIdentOwned, TabularInfo, TabularVersion,
+        UndropTabularResponse,
     },
     WarehouseId,
 };
@@ -125,12 +126,44 @@ pub(crate) async fn get_tabular_protected(
     })
 }
 
+pub(crate) async fn get_tabular_version(
+    warehouse_id: WarehouseId,
+    tabular_id: TabularId,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<Option<TabularVersion>> {
+    let typ = TabularType::from(tabular_id);
+    let row = sqlx::query!(
+        r#"
+        SELECT t.metadata_location, t.deleted_at IS NOT NULL AS "deleted!"
+        FROM tabular t
+        INNER JOIN namespace n ON t.namespace_id = n.namespace_id
+        WHERE n.warehouse_id = $1 AND t.tabular_id = $2 AND t.typ = $3
+        "#,
+        *warehouse_id,
+        *tabular_id,
+        typ as _,
+    )
+    .fetch_optional(&mut **transaction)
+    .await
+    .map_err(|e| {
+        e.into_error_model(format!(
+            "Error getting version of {} {tabular_id}",
+            tabular_id.typ_str()
+        ))
+    })?;
+
+    Ok(row.map(|r| TabularVersion {
+        metadata_location: r.metadata_location,
+        deleted: r.deleted,
+    }))
+}
+
 pub(crate) async fn tabular_ident_to_id<'a, 'e, 'c: 'e, E>(
     warehouse_id: WarehouseId,
     table: &TabularIdentBorrowed<'a>,
     list_flags: crate::service::ListFlags,
     transaction: E,
-) -> Result<Option<(TabularId, String)>>
+) -> Result<Option<(TabularId, String, bool)>>
 where
     E: 'e + sqlx::Executor<'c, Database = sqlx::Postgres>,
 {
@@ -139,12 +172,13 @@ where
 
     let rows = sqlx::query!(
         r#"
-        SELECT t.tabular_id, t.typ as "typ: TabularType", fs_protocol, fs_location
+        SELECT t.tabular_id, t.typ as "typ: TabularType", fs_protocol, fs_location, t.external
         FROM tabular t
         INNER JOIN namespace n ON t.namespace_id = n.namespace_id
         INNER JOIN warehouse w ON n.warehouse_id = w.warehouse_id
         WHERE n.namespace_name = $1 AND t.name = $2
         AND n.warehouse_id = $3
+        AND n.deleted_at IS NULL
         AND w.status = 'active'
         AND t.typ = $4
         AND (t.deleted_at IS NULL OR $5)
@@ -162,8 +196,8 @@ where
     .map(|r| {
         let location = join_location(&r.fs_protocol, &r.fs_location);
         Some(match r.typ {
-            TabularType::Table => (TabularId::Table(r.tabular_id), location),
-            TabularType::View => (TabularId::View(r.tabular_id), location),
+            TabularType::Table => (TabularId::Table(r.tabular_id), location, r.external),
+            TabularType::View => (TabularId::View(r.tabular_id), location, r.external),
         })
     });
 
@@ -233,6 +267,7 @@ where
         INNER JOIN namespace n ON t.namespace_id = n.namespace_id
         INNER JOIN warehouse w ON n.warehouse_id = w.warehouse_id
         WHERE w.status = 'active' and n."warehouse_id" = $1
+            AND n.deleted_at is NULL
             AND (t.deleted_at is NULL OR $2)
             AND (t.metadata_location is not NULL OR $3) "#,
         *warehouse_id,
@@ -339,6 +374,7 @@ pub(crate) struct CreateTabular<'a> {
     pub(crate) typ: TabularType,
     pub(crate) metadata_location: Option<&'a Location>,
     pub(crate) location: &'a Location,
+    pub(crate) external: bool,
 }
 
 pub(crate) fn get_partial_fs_locations(location: &Location) -> Result<Vec<String>> {
@@ -362,6 +398,7 @@ pub(crate) async fn create_tabular(
         typ,
         metadata_location,
         location,
+        external,
     }: CreateTabular<'_>,
     transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
 ) -> Result<Uuid> {
@@ -370,8 +407,8 @@ pub(crate) async fn create_tabular(
 
     let tabular_id = sqlx::query_scalar!(
         r#"
-        INSERT INTO tabular (tabular_id, name, namespace_id, typ, metadata_location, fs_protocol, fs_location)
-        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        INSERT INTO tabular (tabular_id, name, namespace_id, typ, metadata_location, fs_protocol, fs_location, external)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
         RETURNING tabular_id
         "#,
         id,
@@ -380,7 +417,8 @@ pub(crate) async fn create_tabular(
         typ as _,
         metadata_location.map(iceberg_ext::configs::Location::as_str),
         fs_protocol,
-        fs_location
+        fs_location,
+        external
     )
     .fetch_one(&mut **transaction)
     .await
@@ -440,21 +478,14 @@ where
         .page_size
         .map_or(MAX_PAGE_SIZE, |i| i.clamp(1, MAX_PAGE_SIZE));
 
-    let token = pagination_query
+    let token_id: Option<Uuid> = pagination_query
         .page_token
         .as_option()
         .map(PaginateToken::try_from)
+        .transpose()?
+        .map(PaginateToken::into_id)
         .transpose()?;
 
-    let (token_ts, token_id) = token
-        .as_ref()
-        .map(
-            |PaginateToken::V1(V1PaginateToken { created_at, id }): &PaginateToken<Uuid>| {
-                (created_at, id)
-            },
-        )
-        .unzip();
-
     let tables = sqlx::query!(
         r#"
         SELECT
@@ -466,7 +497,8 @@ where
             t.deleted_at,
             tt.scheduled_for as "cleanup_at?",
             tt.task_id as "cleanup_task_id?",
-            t.protected
+            t.protected,
+            t.external
         FROM tabular t
         INNER JOIN namespace n ON t.namespace_id = n.namespace_id
         INNER JOIN warehouse w ON n.warehouse_id = w.warehouse_id
@@ -474,16 +506,16 @@ where
         WHERE (tt.queue_name = 'tabular_expiration' OR tt.queue_name is NULL)
             AND n.warehouse_id = $1
             AND (namespace_name = $2 OR $2 IS NULL)
-            AND (n.namespace_id = $10 OR $10 IS NULL)
+            AND (n.namespace_id = $9 OR $9 IS NULL)
             AND w.status = 'active'
             AND (t.typ = $3 OR $3 IS NULL)
             -- active tables are tables that are not staged and not deleted
             AND ((t.deleted_at IS NOT NULL OR t.metadata_location IS NULL) OR $4)
             AND (t.deleted_at IS NULL OR $5)
             AND (t.metadata_location IS NOT NULL OR $6)
-            AND ((t.created_at > $7 OR $7 IS NULL) OR (t.created_at = $7 AND t.tabular_id > $8))
-            ORDER BY t.created_at, t.tabular_id ASC
-            LIMIT $9
+            AND (t.tabular_id > $7 OR $7 IS NULL)
+            ORDER BY t.tabular_id ASC
+            LIMIT $8
         "#,
         *warehouse_id,
         namespace.as_deref().map(|n| n.as_ref().as_slice()),
@@ -491,7 +523,6 @@ where
         list_flags.include_active,
         list_flags.include_deleted,
         list_flags.include_staged,
-        token_ts,
         token_id,
         page_size,
         namespace_id.map(|n| *n),
@@ -532,9 +563,9 @@ where
                         table_ident: TabularIdentOwned::Table(TableIdent { namespace, name }),
                         deletion_details,
                         protected: table.protected,
+                        external: table.external,
                     },
-                    PaginateToken::V1(V1PaginateToken {
-                        created_at: table.created_at,
+                    PaginateToken::V2(V2PaginateToken {
                         id: table.tabular_id,
                     })
                     .to_string(),
@@ -547,9 +578,9 @@ where
                         table_ident: TabularIdentOwned::View(TableIdent { namespace, name }),
                         deletion_details,
                         protected: table.protected,
+                        external: table.external,
                     },
-                    PaginateToken::V1(V1PaginateToken {
-                        created_at: table.created_at,
+                    PaginateToken::V2(V2PaginateToken {
                         id: table.tabular_id,
                     })
                     .to_string(),
@@ -612,7 +643,7 @@ pub(crate) async fn rename_tabular(
             WITH ns_id AS (
                 SELECT namespace_id
                 FROM namespace
-                WHERE warehouse_id = $2 AND namespace_name = $3
+                WHERE warehouse_id = $2 AND namespace_name = $3 AND deleted_at IS NULL
             )
             UPDATE tabular ti
             SET name = $1, namespace_id = ns_id.namespace_id
@@ -684,6 +715,58 @@ pub(crate) async fn clear_tabular_deleted_at(
     warehouse_id: WarehouseId,
     transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
 ) -> Result<Vec<UndropTabularResponse>> {
+    let in_deleted_namespace = sqlx::query_scalar!(
+        r#"SELECT t.tabular_id
+        FROM tabular t
+        INNER JOIN namespace n ON t.namespace_id = n.namespace_id
+        WHERE t.tabular_id = ANY($1::uuid[])
+            AND n.warehouse_id = $2
+            AND n.deleted_at IS NOT NULL
+        LIMIT 1"#,
+        tabular_ids,
+        *warehouse_id,
+    )
+    .fetch_optional(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching namespaces of tabulars".to_string()))?;
+    if let Some(tabular_id) = in_deleted_namespace {
+        return Err(ErrorModel::conflict(
+            format!(
+                "Tabular {tabular_id} belongs to a dropped namespace. Undrop the namespace instead."
+            ),
+            "NamespaceDropped",
+            None,
+        )
+        .into());
+    }
+
+    // Lock the expiration tasks so that no worker picks them up before they are cancelled.
+    let expiration_tasks = sqlx::query!(
+        r#"SELECT entity_id, status != 'scheduled' as "in_progress!"
+        FROM task
+        WHERE warehouse_id = $2
+            AND entity_type = 'tabular'
+            AND entity_id = ANY($1::uuid[])
+            AND queue_name = 'tabular_expiration'
+        FOR UPDATE"#,
+        tabular_ids,
+        *warehouse_id,
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error locking tabular expiration tasks".to_string()))?;
+    if let Some(task) = expiration_tasks.iter().find(|t| t.in_progress) {
+        return Err(ErrorModel::conflict(
+            format!(
+                "Tabular {} is currently being expired and cannot be restored.",
+                task.entity_id
+            ),
+            "TabularExpirationRunning",
+            None,
+        )
+        .into());
+    }
+
     let undrop_tabular_informations = sqlx::query!(
         r#"WITH validation AS (
                 SELECT NOT EXISTS (
@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+
+use iceberg::spec::Schema;
+use sqlx::types::Json;
+
+use crate::{
+    api::management::v1::table::TaggedColumn,
+    implementations::postgres::dbutils::DBErrorHandler as _,
+    service::{Result, TableId},
+    WarehouseId,
+};
+
+pub(crate) async fn set_column_tags(
+    warehouse_id: WarehouseId,
+    table_id: TableId,
+    tags: &HashMap<i32, Vec<String>>,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let field_ids = tags.keys().copied().collect::<Vec<_>>();
+    sqlx::query!(
+        r#"DELETE FROM column_tag WHERE table_id = $1 AND field_id = ANY($2::INT[])"#,
+        *table_id,
+        &field_ids,
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error deleting column tags".to_string()))?;
+
+    let (field_ids, tags): (Vec<i32>, Vec<String>) = tags
+        .iter()
+        .flat_map(|(field_id, tags)| tags.iter().map(|tag| (*field_id, tag.clone())))
+        .unzip();
+    if field_ids.is_empty() {
+        return Ok(());
+    }
+
+    sqlx::query!(
+        r#"INSERT INTO column_tag (warehouse_id, table_id, field_id, tag)
+           SELECT $1, $2, UNNEST($3::INT[]), UNNEST($4::TEXT[])
+           ON CONFLICT DO NOTHING"#,
+        *warehouse_id,
+        *table_id,
+        &field_ids,
+        &tags,
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error inserting column tags".to_string()))?;
+
+    Ok(())
+}
+
+pub(crate) async fn get_column_tags<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    table_id: TableId,
+    connection: E,
+) -> Result<HashMap<i32, Vec<String>>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT field_id, array_agg(tag ORDER BY tag) as "tags!"
+        FROM column_tag
+        WHERE table_id = $1
+        GROUP BY field_id
+        "#,
+        *table_id,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching column tags".to_string()))?;
+
+    Ok(rows.into_iter().map(|r| (r.field_id, r.tags)).collect())
+}
+
+pub(crate) async fn list_tagged_columns<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseId,
+    tag: &str,
+    connection: E,
+) -> Result<Vec<TaggedColumn>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT ct.table_id,
+               ct.field_id,
+               t.name as "table_name",
+               n.namespace_name,
+               ts.schema as "schema: Json<Schema>"
+        FROM column_tag ct
+        INNER JOIN tabular t ON t.tabular_id = ct.table_id
+        INNER JOIN namespace n ON n.namespace_id = t.namespace_id
+        INNER JOIN table_current_schema tcs ON tcs.table_id = ct.table_id
+        INNER JOIN table_schema ts ON ts.table_id = tcs.table_id AND ts.schema_id = tcs.schema_id
+        WHERE ct.warehouse_id = $1 AND ct.tag = $2 AND t.deleted_at IS NULL
+        ORDER BY n.namespace_name, t.name, ct.field_id
+        "#,
+        *warehouse_id,
+        tag,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error listing tagged columns".to_string()))?;
+
+    // Field ids are stable across schema evolution - resolve them against the
+    // current schema to obtain the current column name.
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let column_name = row.schema.name_by_field_id(row.field_id)?.to_string();
+            Some(TaggedColumn {
+                table_id: row.table_id.into(),
+                namespace: row.namespace_name,
+                table_name: row.table_name,
+                field_id: row.field_id,
+                column_name,
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::implementations::postgres::{
+        namespace::tests::initialize_namespace, tabular::table::tests::initialize_table,
+        warehouse::test::initialize_warehouse, CatalogState,
+    };
+
+    #[sqlx::test]
+    async fn test_column_tags(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let namespace = iceberg::NamespaceIdent::from_vec(vec!["ns".to_string()]).unwrap();
+        initialize_namespace(state.clone(), warehouse_id, &namespace, None).await;
+        let table = initialize_table(
+            warehouse_id,
+            state.clone(),
+            false,
+            Some(namespace.clone()),
+            Some("tab".to_string()),
+        )
+        .await;
+        let table_id = table.table_id;
+
+        let mut transaction = state.write_pool().begin().await.unwrap();
+        set_column_tags(
+            warehouse_id,
+            table_id,
+            &HashMap::from([(1, vec!["financial".to_string(), "pii".to_string()])]),
+            &mut transaction,
+        )
+        .await
+        .unwrap();
+        transaction.commit().await.unwrap();
+
+        let tags = get_column_tags(table_id, &state.read_pool()).await.unwrap();
+        assert_eq!(
+            tags,
+            HashMap::from([(1, vec!["financial".to_string(), "pii".to_string()])])
+        );
+
+        let columns = list_tagged_columns(warehouse_id, "pii", &state.read_pool())
+            .await
+            .unwrap();
+        assert_eq!(columns.len(), 1);
+        assert_eq!(columns[0].table_id, table_id);
+        assert_eq!(columns[0].field_id, 1);
+        assert_eq!(columns[0].column_name, "id");
+        assert_eq!(columns[0].namespace, vec!["ns".to_string()]);
+        assert_eq!(columns[0].table_name, "tab");
+
+        // Replacing with an empty list removes all tags of the field
+        let mut transaction = state.write_pool().begin().await.unwrap();
+        set_column_tags(
+            warehouse_id,
+            table_id,
+            &HashMap::from([(1, vec![])]),
+            &mut transaction,
+        )
+        .await
+        .unwrap();
+        transaction.commit().await.unwrap();
+
+        assert!(get_column_tags(table_id, &state.read_pool())
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(list_tagged_columns(warehouse_id, "pii", &state.read_pool())
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,324 @@
+use iceberg::spec::Schema;
+use sqlx::types::Json;
+
+use crate::{
+    api::management::v1::table::{
+        ColumnTagSuggestion, ColumnTagSuggestionKey, ColumnTagSuggestionStatus,
+    },
+    implementations::postgres::dbutils::DBErrorHandler as _,
+    service::{task_queue::pii_detection_queue::PiiSuggestion, Result, TableId},
+    WarehouseId,
+};
+
+#[derive(sqlx::Type, Debug, Clone, Copy, PartialEq, Eq)]
+#[sqlx(rename_all = "kebab-case", type_name = "column_tag_suggestion_status")]
+enum DbColumnTagSuggestionStatus {
+    Pending,
+    Accepted,
+    Rejected,
+}
+
+impl From<DbColumnTagSuggestionStatus> for ColumnTagSuggestionStatus {
+    fn from(value: DbColumnTagSuggestionStatus) -> Self {
+        match value {
+            DbColumnTagSuggestionStatus::Pending => ColumnTagSuggestionStatus::Pending,
+            DbColumnTagSuggestionStatus::Accepted => ColumnTagSuggestionStatus::Accepted,
+            DbColumnTagSuggestionStatus::Rejected => ColumnTagSuggestionStatus::Rejected,
+        }
+    }
+}
+
+impl From<ColumnTagSuggestionStatus> for DbColumnTagSuggestionStatus {
+    fn from(value: ColumnTagSuggestionStatus) -> Self {
+        match value {
+            ColumnTagSuggestionStatus::Pending => DbColumnTagSuggestionStatus::Pending,
+            ColumnTagSuggestionStatus::Accepted => DbColumnTagSuggestionStatus::Accepted,
+            ColumnTagSuggestionStatus::Rejected => DbColumnTagSuggestionStatus::Rejected,
+        }
+    }
+}
+
+pub(crate) async fn replace_column_tag_suggestions(
+    warehouse_id: WarehouseId,
+    table_id: TableId,
+    suggestions: &[PiiSuggestion],
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    sqlx::query!(
+        r#"DELETE FROM column_tag_suggestion WHERE table_id = $1 AND status = 'pending'"#,
+        *table_id,
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error deleting column tag suggestions".to_string()))?;
+
+    if suggestions.is_empty() {
+        return Ok(());
+    }
+
+    let field_ids = suggestions.iter().map(|s| s.field_id).collect::<Vec<_>>();
+    let tags = suggestions
+        .iter()
+        .map(|s| s.tag.clone())
+        .collect::<Vec<_>>();
+    let confidences = suggestions.iter().map(|s| s.confidence).collect::<Vec<_>>();
+    let reasons = suggestions
+        .iter()
+        .map(|s| s.reason.clone())
+        .collect::<Vec<_>>();
+
+    // Reviewed suggestions take precedence over new ones
+    sqlx::query!(
+        r#"INSERT INTO column_tag_suggestion (warehouse_id, table_id, field_id, tag, confidence, reason)
+           SELECT $1, $2, UNNEST($3::INT[]), UNNEST($4::TEXT[]), UNNEST($5::FLOAT8[]), UNNEST($6::TEXT[])
+           ON CONFLICT DO NOTHING"#,
+        *warehouse_id,
+        *table_id,
+        &field_ids,
+        &tags,
+        &confidences,
+        &reasons,
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error inserting column tag suggestions".to_string()))?;
+
+    Ok(())
+}
+
+pub(crate) async fn list_column_tag_suggestions<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    table_id: TableId,
+    status: Option<ColumnTagSuggestionStatus>,
+    connection: E,
+) -> Result<Vec<ColumnTagSuggestion>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT s.field_id,
+               s.tag,
+               s.confidence,
+               s.reason,
+               s.status as "status: DbColumnTagSuggestionStatus",
+               s.reviewed_by,
+               ts.schema as "schema: Json<Schema>"
+        FROM column_tag_suggestion s
+        INNER JOIN table_current_schema tcs ON tcs.table_id = s.table_id
+        INNER JOIN table_schema ts ON ts.table_id = tcs.table_id AND ts.schema_id = tcs.schema_id
+        WHERE s.table_id = $1 AND ($2::column_tag_suggestion_status IS NULL OR s.status = $2)
+        ORDER BY s.field_id, s.tag
+        "#,
+        *table_id,
+        status.map(DbColumnTagSuggestionStatus::from) as Option<DbColumnTagSuggestionStatus>,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error listing column tag suggestions".to_string()))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| ColumnTagSuggestion {
+            column_name: row
+                .schema
+                .name_by_field_id(row.field_id)
+                .map(ToString::to_string),
+            field_id: row.field_id,
+            tag: row.tag,
+            confidence: row.confidence,
+            reason: row.reason,
+            status: row.status.into(),
+            reviewed_by: row.reviewed_by,
+        })
+        .collect())
+}
+
+pub(crate) async fn review_column_tag_suggestions(
+    warehouse_id: WarehouseId,
+    table_id: TableId,
+    accept: &[ColumnTagSuggestionKey],
+    reject: &[ColumnTagSuggestionKey],
+    reviewed_by: Option<&str>,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<u64> {
+    let (field_ids, tags): (Vec<i32>, Vec<String>) = accept
+        .iter()
+        .chain(reject)
+        .map(|k| (k.field_id, k.tag.clone()))
+        .unzip();
+    let statuses = [
+        vec!["accepted".to_string(); accept.len()],
+        vec!["rejected".to_string(); reject.len()],
+    ]
+    .concat();
+
+    let reviewed = sqlx::query!(
+        r#"
+        UPDATE column_tag_suggestion s
+        SET status = d.status::column_tag_suggestion_status, reviewed_by = $5
+        FROM UNNEST($2::INT[], $3::TEXT[], $4::TEXT[]) AS d(field_id, tag, status)
+        WHERE s.table_id = $1
+          AND s.field_id = d.field_id
+          AND s.tag = d.tag
+          AND s.status = 'pending'
+        "#,
+        *table_id,
+        &field_ids,
+        &tags,
+        &statuses,
+        reviewed_by,
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error reviewing column tag suggestions".to_string()))?
+    .rows_affected();
+
+    if !accept.is_empty() {
+        let (field_ids, tags): (Vec<i32>, Vec<String>) =
+            accept.iter().map(|k| (k.field_id, k.tag.clone())).unzip();
+        sqlx::query!(
+            r#"INSERT INTO column_tag (warehouse_id, table_id, field_id, tag)
+               SELECT $1, $2, UNNEST($3::INT[]), UNNEST($4::TEXT[])
+               ON CONFLICT DO NOTHING"#,
+            *warehouse_id,
+            *table_id,
+            &field_ids,
+            &tags,
+        )
+        .execute(&mut **transaction)
+        .await
+        .map_err(|e| e.into_error_model("Error inserting column tags".to_string()))?;
+    }
+
+    Ok(reviewed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::implementations::postgres::{
+        namespace::tests::initialize_namespace,
+        tabular::table::{get_column_tags, tests::initialize_table},
+        warehouse::test::initialize_warehouse,
+        CatalogState,
+    };
+
+    fn suggestion(field_id: i32, tag: &str) -> PiiSuggestion {
+        PiiSuggestion {
+            field_id,
+            tag: tag.to_string(),
+            confidence: 0.8,
+            reason: "test".to_string(),
+        }
+    }
+
+    fn key(field_id: i32, tag: &str) -> ColumnTagSuggestionKey {
+        ColumnTagSuggestionKey {
+            field_id,
+            tag: tag.to_string(),
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_column_tag_suggestions(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let namespace = iceberg::NamespaceIdent::from_vec(vec!["ns".to_string()]).unwrap();
+        initialize_namespace(state.clone(), warehouse_id, &namespace, None).await;
+        let table = initialize_table(
+            warehouse_id,
+            state.clone(),
+            false,
+            Some(namespace.clone()),
+            Some("tab".to_string()),
+        )
+        .await;
+        let table_id = table.table_id;
+
+        let mut transaction = state.write_pool().begin().await.unwrap();
+        replace_column_tag_suggestions(
+            warehouse_id,
+            table_id,
+            &[suggestion(2, "pii:name"), suggestion(2, "pii:email")],
+            &mut transaction,
+        )
+        .await
+        .unwrap();
+        transaction.commit().await.unwrap();
+
+        let suggestions = list_column_tag_suggestions(table_id, None, &state.read_pool())
+            .await
+            .unwrap();
+        assert_eq!(suggestions.len(), 2);
+        assert_eq!(suggestions[0].tag, "pii:email");
+        assert_eq!(suggestions[0].column_name.as_deref(), Some("name"));
+        assert_eq!(suggestions[0].status, ColumnTagSuggestionStatus::Pending);
+
+        let mut transaction = state.write_pool().begin().await.unwrap();
+        let reviewed = review_column_tag_suggestions(
+            warehouse_id,
+            table_id,
+            &[key(2, "pii:name")],
+            &[key(2, "pii:email"), key(1, "pii:unknown")],
+            Some("oidc~reviewer"),
+            &mut transaction,
+        )
+        .await
+        .unwrap();
+        transaction.commit().await.unwrap();
+        assert_eq!(reviewed, 2);
+
+        // Accepted suggestions become column tags
+        let tags = get_column_tags(table_id, &state.read_pool()).await.unwrap();
+        assert_eq!(tags.get(&2), Some(&vec!["pii:name".to_string()]));
+
+        // Reviewed suggestions are kept when the analyzer runs again
+        let mut transaction = state.write_pool().begin().await.unwrap();
+        replace_column_tag_suggestions(
+            warehouse_id,
+            table_id,
+            &[suggestion(2, "pii:email"), suggestion(2, "pii:phone")],
+            &mut transaction,
+        )
+        .await
+        .unwrap();
+        transaction.commit().await.unwrap();
+
+        let rejected = list_column_tag_suggestions(
+            table_id,
+            Some(ColumnTagSuggestionStatus::Rejected),
+            &state.read_pool(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(rejected.len(), 1);
+        assert_eq!(rejected[0].tag, "pii:email");
+        assert_eq!(rejected[0].reviewed_by.as_deref(), Some("oidc~reviewer"));
+
+        let pending = list_column_tag_suggestions(
+            table_id,
+            Some(ColumnTagSuggestionStatus::Pending),
+            &state.read_pool(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].tag, "pii:phone");
+
+        // Already reviewed suggestions can't be reviewed again
+        let mut transaction = state.write_pool().begin().await.unwrap();
+        let reviewed = review_column_tag_suggestions(
+            warehouse_id,
+            table_id,
+            &[],
+            &[key(2, "pii:name")],
+            None,
+            &mut transaction,
+        )
+        .await
+        .unwrap();
+        transaction.commit().await.unwrap();
+        assert_eq!(reviewed, 0);
+    }
+}
@@ -12,6 +12,7 @@ use crate::{
         dbutils::DBErrorHandler,
         tabular::table::{
             common::{self, expire_metadata_log_entries, remove_snapshot_log_entries},
+            stats::total_data_size_bytes,
             DbTableFormatVersion, TableUpdates, MAX_PARAMETERS,
         },
     },
@@ -64,7 +65,8 @@ pub(crate) async fn commit_table_transaction(
     }
 
     // Update tabular (metadata location, fs_location, fs_protocol) and top level table metadata
-    // (format_version, last_column_id, last_sequence_number, last_updated_ms, last_partition_id)
+    // (format_version, last_column_id, last_sequence_number, last_updated_ms, last_partition_id,
+    // total_data_size_bytes)
     let (mut query_meta_update, mut query_meta_location_update) =
         build_table_and_tabular_update_queries(location_metadata_pairs)?;
 
@@ -124,7 +126,8 @@ fn build_table_and_tabular_update_queries(
             last_column_id = c."last_column_id",
             last_sequence_number = c."last_sequence_number",
             last_updated_ms = c."last_updated_ms",
-            last_partition_id = c."last_partition_id"
+            last_partition_id = c."last_partition_id",
+            total_data_size_bytes = c."total_data_size_bytes"
         FROM (VALUES
         "#,
     );
@@ -164,6 +167,8 @@ fn build_table_and_tabular_update_queries(
         query_builder_table.push_bind(new_metadata.last_updated_ms());
         query_builder_table.push(", ");
         query_builder_table.push_bind(new_metadata.last_partition_id());
+        query_builder_table.push(", ");
+        query_builder_table.push_bind(total_data_size_bytes(&new_metadata));
         query_builder_table.push(")");
 
         query_builder_tabular.push("(");
@@ -185,7 +190,7 @@ fn build_table_and_tabular_update_queries(
     }
 
     query_builder_table
-        .push(") as c(table_id, table_format_version, last_column_id, last_sequence_number, last_updated_ms, last_partition_id) WHERE c.table_id = t.table_id");
+        .push(") as c(table_id, table_format_version, last_column_id, last_sequence_number, last_updated_ms, last_partition_id, total_data_size_bytes) WHERE c.table_id = t.table_id");
     query_builder_tabular.push(
         ") as c(table_id, new_metadata_location, fs_location, fs_protocol, old_metadata_location) WHERE c.table_id = t.tabular_id AND t.typ = 'table' AND t.metadata_location IS NOT DISTINCT FROM c.old_metadata_location",
     );
@@ -5,6 +5,7 @@ use iceberg::{
     TableIdent,
 };
 use iceberg_ext::{catalog::rest::ErrorModel, configs::Location};
+use itertools::Itertools as _;
 use sqlx::{Postgres, Transaction};
 use uuid::Uuid;
 
@@ -14,7 +15,7 @@ use crate::{
         dbutils::DBErrorHandler,
         tabular::{
             create_tabular,
-            table::{common, DbTableFormatVersion},
+            table::{common, stats::total_data_size_bytes, DbTableFormatVersion},
             CreateTabular, TabularType,
         },
     },
@@ -27,6 +28,8 @@ pub(crate) async fn create_table(
         table_ident,
         table_metadata,
         metadata_location,
+        external,
+        imported_history,
     }: TableCreation<'_>,
     transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
 ) -> api::Result<CreateTableResponse> {
@@ -49,6 +52,7 @@ pub(crate) async fn create_table(
             typ: TabularType::Table,
             metadata_location,
             location: &location,
+            external,
         },
         transaction,
     )
@@ -74,7 +78,17 @@ pub(crate) async fn create_table(
 
     common::insert_snapshots(tabular_id, table_metadata.snapshots(), transaction).await?;
     common::insert_snapshot_refs(&table_metadata, transaction).await?;
-    common::insert_snapshot_log(table_metadata.history().iter(), transaction, tabular_id).await?;
+    common::insert_snapshot_log(
+        imported_history
+            .snapshot_log
+            .iter()
+            .chain(table_metadata.history())
+            .collect_vec()
+            .into_iter(),
+        transaction,
+        tabular_id,
+    )
+    .await?;
 
     common::insert_sort_orders(table_metadata.sort_orders_iter(), transaction, tabular_id).await?;
     common::set_default_sort_order(
@@ -88,7 +102,12 @@ pub(crate) async fn create_table(
 
     common::insert_metadata_log(
         tabular_id,
-        table_metadata.metadata_log().iter().cloned(),
+        imported_history
+            .metadata_log
+            .into_iter()
+            .chain(table_metadata.metadata_log().iter().cloned())
+            .collect_vec()
+            .into_iter(),
         transaction,
     )
     .await?;
@@ -159,10 +178,11 @@ async fn insert_table(
                              last_column_id,
                              last_sequence_number,
                              last_updated_ms,
-                             last_partition_id
+                             last_partition_id,
+                             total_data_size_bytes
                              )
         (
-            SELECT $1, $2, $3, $4, $5, $6
+            SELECT $1, $2, $3, $4, $5, $6, $7
             WHERE EXISTS (SELECT 1
                 FROM active_tables
                 WHERE active_tables.table_id = $1))
@@ -176,7 +196,8 @@ async fn insert_table(
         table_metadata.last_column_id(),
         table_metadata.last_sequence_number(),
         table_metadata.last_updated_ms(),
-        table_metadata.last_partition_id()
+        table_metadata.last_partition_id(),
+        total_data_size_bytes(table_metadata)
     )
     .fetch_one(&mut **transaction)
     .await
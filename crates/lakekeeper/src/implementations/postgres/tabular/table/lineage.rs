@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+
+use crate::{
+    implementations::postgres::dbutils::DBErrorHandler as _,
+    service::{
+        storage::split_location,
+        table_lineage::{LineageEdge, LineageJob, LineageNeighbor, LineageTable, TableLineage},
+        Result, TableId,
+    },
+    WarehouseId,
+};
+
+pub(crate) async fn resolve_table_locations<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    locations: &[String],
+    connection: E,
+) -> Result<HashMap<String, LineageTable>> {
+    let mut protocols = Vec::with_capacity(locations.len());
+    let mut fs_locations = Vec::with_capacity(locations.len());
+    for location in locations {
+        let (protocol, fs_location) = split_location(location)?;
+        protocols.push(protocol.to_string());
+        fs_locations.push(fs_location.to_string());
+    }
+
+    // Table locations are unique across warehouses, see `create_tabular`.
+    let rows = sqlx::query!(
+        r#"
+        SELECT l.fs_protocol as "fs_protocol!", l.fs_location as "fs_location!",
+            t.tabular_id, n.warehouse_id
+        FROM UNNEST($1::text[], $2::text[]) AS l(fs_protocol, fs_location)
+        INNER JOIN tabular t ON t.fs_protocol = l.fs_protocol
+            AND t.fs_location IN (l.fs_location, l.fs_location || '/')
+        INNER JOIN namespace n ON n.namespace_id = t.namespace_id
+        WHERE t.typ = 'table' AND t.deleted_at IS NULL AND t.metadata_location IS NOT NULL
+        "#,
+        &protocols,
+        &fs_locations,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error resolving table locations"))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            (
+                format!("{}://{}", row.fs_protocol, row.fs_location),
+                LineageTable {
+                    warehouse_id: row.warehouse_id.into(),
+                    table_id: row.tabular_id.into(),
+                },
+            )
+        })
+        .collect())
+}
+
+pub(crate) async fn record_table_lineage(
+    edges: &[LineageEdge],
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    for edge in edges {
+        // Events may arrive out of order, an older run doesn't replace a newer one.
+        sqlx::query!(
+            r#"
+            INSERT INTO table_lineage (upstream_namespace, upstream_name, downstream_namespace,
+                downstream_name, job_namespace, job_name, upstream_table_id, downstream_table_id,
+                last_run_id, last_event_time)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            ON CONFLICT (upstream_namespace, upstream_name, downstream_namespace, downstream_name,
+                job_namespace, job_name)
+            DO UPDATE SET upstream_table_id = EXCLUDED.upstream_table_id,
+                downstream_table_id = EXCLUDED.downstream_table_id,
+                last_run_id = EXCLUDED.last_run_id,
+                last_event_time = EXCLUDED.last_event_time
+            WHERE table_lineage.last_event_time <= EXCLUDED.last_event_time
+            "#,
+            edge.upstream.namespace,
+            edge.upstream.name,
+            edge.downstream.namespace,
+            edge.downstream.name,
+            edge.job.namespace,
+            edge.job.name,
+            edge.upstream_table.map(|t| *t.table_id),
+            edge.downstream_table.map(|t| *t.table_id),
+            edge.run_id,
+            edge.event_time,
+        )
+        .execute(&mut **transaction)
+        .await
+        .map_err(|e| e.into_error_model("Error recording table lineage"))?;
+    }
+    Ok(())
+}
+
+pub(crate) async fn get_table_lineage<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseId,
+    table_id: TableId,
+    connection: E,
+) -> Result<TableLineage> {
+    // Neighbors are only resolved to tables that are not dropped.
+    let rows = sqlx::query!(
+        r#"
+        WITH edges AS (
+            SELECT true AS is_upstream, l.upstream_namespace AS dataset_namespace,
+                l.upstream_name AS dataset_name, l.upstream_table_id AS neighbor_table_id,
+                l.job_namespace, l.job_name, l.last_run_id, l.last_event_time
+            FROM table_lineage l
+            WHERE l.downstream_table_id = $2
+            UNION ALL
+            SELECT false, l.downstream_namespace, l.downstream_name, l.downstream_table_id,
+                l.job_namespace, l.job_name, l.last_run_id, l.last_event_time
+            FROM table_lineage l
+            WHERE l.upstream_table_id = $2
+        )
+        SELECT e.is_upstream as "is_upstream!", e.dataset_namespace as "dataset_namespace!",
+            e.dataset_name as "dataset_name!", t.tabular_id as "table_id?",
+            n.warehouse_id as "warehouse_id?", e.job_namespace as "job_namespace!",
+            e.job_name as "job_name!", e.last_run_id as "last_run_id!",
+            e.last_event_time as "last_event_time!"
+        FROM edges e
+        INNER JOIN tabular ft ON ft.tabular_id = $2
+        INNER JOIN namespace fns ON fns.namespace_id = ft.namespace_id AND fns.warehouse_id = $1
+        LEFT JOIN tabular t ON t.tabular_id = e.neighbor_table_id AND t.deleted_at IS NULL
+        LEFT JOIN namespace n ON n.namespace_id = t.namespace_id
+        ORDER BY e.last_event_time DESC, e.dataset_namespace, e.dataset_name
+        "#,
+        *warehouse_id,
+        *table_id,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching table lineage"))?;
+
+    let mut lineage = TableLineage {
+        upstream: vec![],
+        downstream: vec![],
+    };
+    for row in rows {
+        let neighbor = LineageNeighbor {
+            dataset_namespace: row.dataset_namespace,
+            dataset_name: row.dataset_name,
+            warehouse_id: row.warehouse_id.map(WarehouseId::from),
+            table_id: row.table_id.map(TableId::from),
+            job: LineageJob {
+                namespace: row.job_namespace,
+                name: row.job_name,
+            },
+            last_run_id: row.last_run_id,
+            last_event_time: row.last_event_time,
+        };
+        if row.is_upstream {
+            lineage.upstream.push(neighbor);
+        } else {
+            lineage.downstream.push(neighbor);
+        }
+    }
+    Ok(lineage)
+}
@@ -1,6 +1,14 @@
+mod column_tag;
+mod column_tag_suggestion;
 mod commit;
 mod common;
 mod create;
+mod lineage;
+mod property_proposal;
+mod scan_plan;
+mod stats;
+mod timeline;
+mod writer;
 
 use std::{
     collections::{HashMap, HashSet},
@@ -10,6 +18,10 @@ use std::{
     sync::Arc,
 };
 
+pub(crate) use column_tag::{get_column_tags, list_tagged_columns, set_column_tags};
+pub(crate) use column_tag_suggestion::{
+    list_column_tag_suggestions, replace_column_tag_suggestions, review_column_tag_suggestions,
+};
 pub(crate) use commit::commit_table_transaction;
 pub(crate) use create::create_table;
 use http::StatusCode;
@@ -21,8 +33,20 @@ use iceberg::{
     TableUpdate,
 };
 use iceberg_ext::{configs::Location, spec::TableMetadata, NamespaceIdent};
+pub(crate) use lineage::{get_table_lineage, record_table_lineage, resolve_table_locations};
+pub(crate) use property_proposal::{
+    create_table_property_proposal, list_table_property_proposals, review_table_property_proposal,
+};
+pub(crate) use scan_plan::{
+    cancel_scan_plan, create_scan_plan, get_scan_plan, set_scan_plan_result,
+};
 use sqlx::types::Json;
+pub(crate) use stats::get_table_stats;
+pub(crate) use timeline::{get_table_timeline, record_table_activity};
 use uuid::Uuid;
+pub(crate) use writer::{
+    begin_table_writer, complete_table_writer_commit, get_table_writer, prepare_table_writer_commit,
+};
 
 use super::get_partial_fs_locations;
 use crate::{
@@ -61,10 +85,11 @@ where
         catalog_state,
     )
     .await?
-    .map(|(id, location)| match id {
+    .map(|(id, location, external)| match id {
         TabularId::Table(tab) => Ok(TabularDetails {
             ident: tab.into(),
             location,
+            external,
         }),
         TabularId::View(_) => Err(ErrorModel::builder()
             .code(StatusCode::INTERNAL_SERVER_ERROR.into())
@@ -219,6 +244,7 @@ struct TableQueryStruct {
     table_stats_file_footer_size_in_bytes: Option<Vec<i64>>,
     table_stats_key_metadata: Option<Vec<Option<String>>>,
     table_stats_blob_metadata: Option<Vec<Json<Vec<BlobMetadata>>>>,
+    external: bool,
 }
 
 impl TableQueryStruct {
@@ -512,7 +538,8 @@ pub(crate) async fn load_tables(
             tstat.file_size_in_bytes_s as "table_stats_file_size_in_bytes",
             tstat.file_footer_size_in_bytes_s as "table_stats_file_footer_size_in_bytes",
             tstat.key_metadatas as "table_stats_key_metadata: Vec<Option<String>>",
-            tstat.blob_metadatas as "table_stats_blob_metadata: Vec<Json<Vec<BlobMetadata>>>"
+            tstat.blob_metadatas as "table_stats_blob_metadata: Vec<Json<Vec<BlobMetadata>>>",
+            ti.external
         FROM "table" t
         INNER JOIN tabular ti ON t.table_id = ti.tabular_id
         INNER JOIN namespace n ON ti.namespace_id = n.namespace_id
@@ -617,6 +644,7 @@ pub(crate) async fn load_tables(
         let namespace_id = table.namespace_id.into();
         let storage_secret_ident = table.storage_secret_id.map(SecretIdent::from);
         let storage_profile = table.storage_profile.deref().clone();
+        let external = table.external;
 
         let Some(table_metadata) = table.into_table_metadata()? else {
             tracing::warn!(
@@ -635,6 +663,7 @@ pub(crate) async fn load_tables(
                 metadata_location,
                 storage_secret_ident,
                 storage_profile,
+                external,
             },
         );
     }
@@ -672,7 +701,8 @@ pub(crate) async fn get_table_metadata_by_id(
             t."metadata" as "metadata: Json<TableMetadata>",
             ti."metadata_location",
             w.storage_profile as "storage_profile: Json<StorageProfile>",
-            w."storage_secret_id"
+            w."storage_secret_id",
+            ti.external
         FROM "table" t
         INNER JOIN tabular ti ON t.table_id = ti.tabular_id
         INNER JOIN namespace n ON ti.namespace_id = n.namespace_id
@@ -716,6 +746,7 @@ pub(crate) async fn get_table_metadata_by_id(
         metadata_location: table.metadata_location,
         storage_secret_ident: table.storage_secret_id.map(SecretIdent::from),
         storage_profile: table.storage_profile.deref().clone(),
+        external: table.external,
     }))
 }
 
@@ -741,7 +772,8 @@ pub(crate) async fn get_table_metadata_by_s3_location(
              t."metadata" as "metadata: Json<TableMetadata>",
              ti."metadata_location",
              w.storage_profile as "storage_profile: Json<StorageProfile>",
-             w."storage_secret_id"
+             w."storage_secret_id",
+             ti.external
          FROM "table" t
          INNER JOIN tabular ti ON t.table_id = ti.tabular_id
          INNER JOIN namespace n ON ti.namespace_id = n.namespace_id
@@ -792,6 +824,7 @@ pub(crate) async fn get_table_metadata_by_s3_location(
         metadata_location: table.metadata_location,
         storage_secret_ident: table.storage_secret_id.map(SecretIdent::from),
         storage_profile: table.storage_profile.deref().clone(),
+        external: table.external,
     }))
 }
 
@@ -882,10 +915,11 @@ pub(crate) mod tests {
             PostgresCatalog,
         },
         service::{
+            table_lineage::{LineageDataset, LineageEdge, LineageJob},
             task_queue::{
                 tabular_expiration_queue::TabularExpirationPayload, EntityId, TaskMetadata,
             },
-            Catalog, ListFlags, NamespaceId, TableCreation,
+            Catalog, ListFlags, NamespaceId, TableCreation, TableHistoryImport,
         },
     };
 
@@ -1038,6 +1072,8 @@ pub(crate) mod tests {
             table_ident: &table_ident,
             table_metadata,
             metadata_location: metadata_location.as_ref(),
+            external: false,
+            imported_history: TableHistoryImport::default(),
         };
         let mut transaction = state.write_pool().begin().await.unwrap();
         let _create_result = create_table(create, &mut transaction).await.unwrap();
@@ -1079,6 +1115,8 @@ pub(crate) mod tests {
             table_ident: &table_ident,
             table_metadata,
             metadata_location: metadata_location.as_ref(),
+            external: false,
+            imported_history: TableHistoryImport::default(),
         };
 
         let create_result = create_table(request.clone(), &mut transaction)
@@ -1147,6 +1185,8 @@ pub(crate) mod tests {
             table_ident: &table_ident,
             table_metadata,
             metadata_location: metadata_location.as_ref(),
+            external: false,
+            imported_history: TableHistoryImport::default(),
         };
 
         let _create_result = create_table(request.clone(), &mut transaction)
@@ -1192,6 +1232,8 @@ pub(crate) mod tests {
             table_ident: &table_ident,
             table_metadata,
             metadata_location: metadata_location.as_ref(),
+            external: false,
+            imported_history: TableHistoryImport::default(),
         };
         let mut transaction = pool.begin().await.unwrap();
         let create_result = create_table(request, &mut transaction).await.unwrap();
@@ -1752,6 +1794,72 @@ pub(crate) mod tests {
         .is_none());
     }
 
+    #[sqlx::test]
+    async fn test_table_lineage(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let table = initialize_table(warehouse_id, state.clone(), false, None, None).await;
+        let location = get_table_metadata_by_id(
+            warehouse_id,
+            table.table_id,
+            ListFlags::default(),
+            state.clone(),
+        )
+        .await
+        .unwrap()
+        .unwrap()
+        .location;
+
+        let (bucket, path) = location
+            .strip_prefix("s3://")
+            .unwrap()
+            .split_once('/')
+            .unwrap();
+        let input = LineageDataset {
+            namespace: format!("s3a://{bucket}"),
+            name: format!("{path}/"),
+        };
+        let tables = resolve_table_locations(&[input.location().unwrap()], &pool)
+            .await
+            .unwrap();
+        let resolved = tables.get(&input.location().unwrap()).copied();
+        assert_eq!(resolved.map(|t| t.table_id), Some(table.table_id));
+
+        let edge = LineageEdge {
+            upstream: input,
+            upstream_table: resolved,
+            downstream: LineageDataset {
+                namespace: "kafka://broker:9092".to_string(),
+                name: "orders".to_string(),
+            },
+            downstream_table: None,
+            job: LineageJob {
+                namespace: "spark".to_string(),
+                name: "export_orders".to_string(),
+            },
+            run_id: Uuid::now_v7(),
+            event_time: chrono::Utc::now(),
+        };
+        let mut transaction = pool.begin().await.unwrap();
+        record_table_lineage(&[edge.clone()], &mut transaction)
+            .await
+            .unwrap();
+        // Recording the same edge again updates it
+        record_table_lineage(&[edge], &mut transaction)
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+
+        let lineage = get_table_lineage(warehouse_id, table.table_id, &pool)
+            .await
+            .unwrap();
+        assert!(lineage.upstream.is_empty());
+        assert_eq!(lineage.downstream.len(), 1);
+        assert_eq!(lineage.downstream[0].dataset_name, "orders");
+        assert_eq!(lineage.downstream[0].table_id, None);
+    }
+
     #[sqlx::test]
     async fn test_cannot_get_table_of_inactive_warehouse(pool: sqlx::PgPool) {
         let state = CatalogState::from_pools(pool.clone(), pool.clone());
@@ -0,0 +1,301 @@
+use std::collections::HashMap;
+
+use sqlx::types::Json;
+use uuid::Uuid;
+
+use crate::{
+    api::management::v1::table::{
+        CreateTablePropertyProposalRequest, TablePropertyProposal, TablePropertyProposalStatus,
+    },
+    implementations::postgres::dbutils::DBErrorHandler as _,
+    service::{Result, TableId},
+    WarehouseId,
+};
+
+#[derive(sqlx::Type, Debug, Clone, Copy, PartialEq, Eq)]
+#[sqlx(
+    rename_all = "kebab-case",
+    type_name = "table_property_proposal_status"
+)]
+enum DbTablePropertyProposalStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl From<DbTablePropertyProposalStatus> for TablePropertyProposalStatus {
+    fn from(value: DbTablePropertyProposalStatus) -> Self {
+        match value {
+            DbTablePropertyProposalStatus::Pending => TablePropertyProposalStatus::Pending,
+            DbTablePropertyProposalStatus::Approved => TablePropertyProposalStatus::Approved,
+            DbTablePropertyProposalStatus::Rejected => TablePropertyProposalStatus::Rejected,
+        }
+    }
+}
+
+impl From<TablePropertyProposalStatus> for DbTablePropertyProposalStatus {
+    fn from(value: TablePropertyProposalStatus) -> Self {
+        match value {
+            TablePropertyProposalStatus::Pending => DbTablePropertyProposalStatus::Pending,
+            TablePropertyProposalStatus::Approved => DbTablePropertyProposalStatus::Approved,
+            TablePropertyProposalStatus::Rejected => DbTablePropertyProposalStatus::Rejected,
+        }
+    }
+}
+
+struct TablePropertyProposalRow {
+    proposal_id: Uuid,
+    set_properties: Json<HashMap<String, String>>,
+    remove_properties: Vec<String>,
+    reason: Option<String>,
+    status: DbTablePropertyProposalStatus,
+    proposed_by: Option<String>,
+    created_at: chrono::DateTime<chrono::Utc>,
+    reviewed_by: Option<String>,
+    reviewed_at: Option<chrono::DateTime<chrono::Utc>>,
+    review_comment: Option<String>,
+}
+
+impl From<TablePropertyProposalRow> for TablePropertyProposal {
+    fn from(row: TablePropertyProposalRow) -> Self {
+        Self {
+            proposal_id: row.proposal_id,
+            set_properties: row.set_properties.0,
+            remove_properties: row.remove_properties,
+            reason: row.reason,
+            status: row.status.into(),
+            proposed_by: row.proposed_by,
+            created_at: row.created_at,
+            reviewed_by: row.reviewed_by,
+            reviewed_at: row.reviewed_at,
+            review_comment: row.review_comment,
+        }
+    }
+}
+
+pub(crate) async fn create_table_property_proposal<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseId,
+    table_id: TableId,
+    proposal_id: Uuid,
+    request: &CreateTablePropertyProposalRequest,
+    proposed_by: Option<&str>,
+    connection: E,
+) -> Result<TablePropertyProposal> {
+    let proposal = sqlx::query_as!(
+        TablePropertyProposalRow,
+        r#"
+        INSERT INTO table_property_proposal
+            (proposal_id, warehouse_id, table_id, set_properties, remove_properties, reason, proposed_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING proposal_id,
+                  set_properties as "set_properties: Json<HashMap<String, String>>",
+                  remove_properties,
+                  reason,
+                  status as "status: DbTablePropertyProposalStatus",
+                  proposed_by,
+                  created_at,
+                  reviewed_by,
+                  reviewed_at,
+                  review_comment
+        "#,
+        proposal_id,
+        *warehouse_id,
+        *table_id,
+        Json(&request.set_properties) as _,
+        &request.remove_properties,
+        request.reason,
+        proposed_by,
+    )
+    .fetch_one(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error creating table property proposal"))?;
+
+    Ok(proposal.into())
+}
+
+pub(crate) async fn list_table_property_proposals<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    table_id: TableId,
+    status: Option<TablePropertyProposalStatus>,
+    connection: E,
+) -> Result<Vec<TablePropertyProposal>> {
+    let proposals = sqlx::query_as!(
+        TablePropertyProposalRow,
+        r#"
+        SELECT proposal_id,
+               set_properties as "set_properties: Json<HashMap<String, String>>",
+               remove_properties,
+               reason,
+               status as "status: DbTablePropertyProposalStatus",
+               proposed_by,
+               created_at,
+               reviewed_by,
+               reviewed_at,
+               review_comment
+        FROM table_property_proposal
+        WHERE table_id = $1 AND ($2::table_property_proposal_status IS NULL OR status = $2)
+        ORDER BY created_at DESC, proposal_id DESC
+        "#,
+        *table_id,
+        status.map(DbTablePropertyProposalStatus::from) as Option<DbTablePropertyProposalStatus>,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error listing table property proposals"))?;
+
+    Ok(proposals.into_iter().map(Into::into).collect())
+}
+
+pub(crate) async fn review_table_property_proposal<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    table_id: TableId,
+    proposal_id: Uuid,
+    status: TablePropertyProposalStatus,
+    reviewed_by: Option<&str>,
+    review_comment: Option<&str>,
+    connection: E,
+) -> Result<Option<TablePropertyProposal>> {
+    let proposal = sqlx::query_as!(
+        TablePropertyProposalRow,
+        r#"
+        UPDATE table_property_proposal
+        SET status = $3, reviewed_by = $4, reviewed_at = now(), review_comment = $5
+        WHERE table_id = $1 AND proposal_id = $2 AND status = 'pending'
+        RETURNING proposal_id,
+                  set_properties as "set_properties: Json<HashMap<String, String>>",
+                  remove_properties,
+                  reason,
+                  status as "status: DbTablePropertyProposalStatus",
+                  proposed_by,
+                  created_at,
+                  reviewed_by,
+                  reviewed_at,
+                  review_comment
+        "#,
+        *table_id,
+        proposal_id,
+        DbTablePropertyProposalStatus::from(status) as _,
+        reviewed_by,
+        review_comment,
+    )
+    .fetch_optional(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error reviewing table property proposal"))?;
+
+    Ok(proposal.map(Into::into))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::implementations::postgres::{
+        namespace::tests::initialize_namespace, tabular::table::tests::initialize_table,
+        warehouse::test::initialize_warehouse, CatalogState,
+    };
+
+    #[sqlx::test]
+    async fn test_table_property_proposals(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let namespace = iceberg::NamespaceIdent::from_vec(vec!["ns".to_string()]).unwrap();
+        initialize_namespace(state.clone(), warehouse_id, &namespace, None).await;
+        let table = initialize_table(
+            warehouse_id,
+            state.clone(),
+            false,
+            Some(namespace.clone()),
+            Some("tab".to_string()),
+        )
+        .await;
+        let table_id = table.table_id;
+
+        let request = CreateTablePropertyProposalRequest {
+            set_properties: HashMap::from([(
+                "history.expire.max-snapshot-age-ms".to_string(),
+                "86400000".to_string(),
+            )]),
+            remove_properties: vec!["owner".to_string()],
+            reason: Some("Shorter retention".to_string()),
+        };
+        let mut proposal_ids = vec![];
+        for _ in 0..2 {
+            let mut transaction = state.write_pool().begin().await.unwrap();
+            let proposal = create_table_property_proposal(
+                warehouse_id,
+                table_id,
+                Uuid::now_v7(),
+                &request,
+                Some("oidc~proposer"),
+                &mut *transaction,
+            )
+            .await
+            .unwrap();
+            transaction.commit().await.unwrap();
+            assert_eq!(proposal.status, TablePropertyProposalStatus::Pending);
+            assert_eq!(proposal.set_properties, request.set_properties);
+            assert_eq!(proposal.proposed_by.as_deref(), Some("oidc~proposer"));
+            proposal_ids.push(proposal.proposal_id);
+        }
+
+        let proposals = list_table_property_proposals(table_id, None, &state.read_pool())
+            .await
+            .unwrap();
+        assert_eq!(
+            proposals.iter().map(|p| p.proposal_id).collect::<Vec<_>>(),
+            proposal_ids.iter().rev().copied().collect::<Vec<_>>()
+        );
+
+        let mut transaction = state.write_pool().begin().await.unwrap();
+        let reviewed = review_table_property_proposal(
+            table_id,
+            proposal_ids[0],
+            TablePropertyProposalStatus::Approved,
+            Some("oidc~owner"),
+            Some("LGTM"),
+            &mut *transaction,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        transaction.commit().await.unwrap();
+        assert_eq!(reviewed.status, TablePropertyProposalStatus::Approved);
+        assert_eq!(reviewed.reviewed_by.as_deref(), Some("oidc~owner"));
+        assert_eq!(reviewed.review_comment.as_deref(), Some("LGTM"));
+        assert!(reviewed.reviewed_at.is_some());
+
+        // Reviewed proposals can't be reviewed again
+        let mut transaction = state.write_pool().begin().await.unwrap();
+        let reviewed = review_table_property_proposal(
+            table_id,
+            proposal_ids[0],
+            TablePropertyProposalStatus::Rejected,
+            None,
+            None,
+            &mut *transaction,
+        )
+        .await
+        .unwrap();
+        transaction.commit().await.unwrap();
+        assert!(reviewed.is_none());
+
+        let pending = list_table_property_proposals(
+            table_id,
+            Some(TablePropertyProposalStatus::Pending),
+            &state.read_pool(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].proposal_id, proposal_ids[1]);
+    }
+}
@@ -0,0 +1,227 @@
+use iceberg_ext::catalog::rest::{PlanStatus, ScanTasks};
+use sqlx::types::Json;
+
+use crate::{
+    implementations::postgres::dbutils::DBErrorHandler as _,
+    service::{ErrorModel, Result, ScanPlan, ScanPlanId, TableId},
+    WarehouseId,
+};
+
+#[derive(sqlx::Type, Debug, Clone, Copy, PartialEq, Eq)]
+#[sqlx(rename_all = "kebab-case", type_name = "scan_plan_status")]
+enum DbScanPlanStatus {
+    Submitted,
+    Completed,
+    Cancelled,
+    Failed,
+}
+
+impl From<DbScanPlanStatus> for PlanStatus {
+    fn from(value: DbScanPlanStatus) -> Self {
+        match value {
+            DbScanPlanStatus::Submitted => PlanStatus::Submitted,
+            DbScanPlanStatus::Completed => PlanStatus::Completed,
+            DbScanPlanStatus::Cancelled => PlanStatus::Cancelled,
+            DbScanPlanStatus::Failed => PlanStatus::Failed,
+        }
+    }
+}
+
+impl From<PlanStatus> for DbScanPlanStatus {
+    fn from(value: PlanStatus) -> Self {
+        match value {
+            PlanStatus::Submitted => DbScanPlanStatus::Submitted,
+            PlanStatus::Completed => DbScanPlanStatus::Completed,
+            PlanStatus::Cancelled => DbScanPlanStatus::Cancelled,
+            PlanStatus::Failed => DbScanPlanStatus::Failed,
+        }
+    }
+}
+
+pub(crate) async fn create_scan_plan<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    plan: ScanPlan,
+    connection: E,
+) -> Result<()> {
+    let ScanPlan {
+        plan_id,
+        warehouse_id,
+        table_id,
+        snapshot_id,
+        request,
+        status,
+        result,
+        error,
+        expires_at,
+    } = plan;
+    let request = serde_json::to_value(&request).map_err(|e| {
+        ErrorModel::internal(
+            "Error serializing scan plan request",
+            "ScanPlanSerializationError",
+            Some(Box::new(e)),
+        )
+    })?;
+    let result = result.map(serde_json::to_value).transpose().map_err(|e| {
+        ErrorModel::internal(
+            "Error serializing scan plan result",
+            "ScanPlanSerializationError",
+            Some(Box::new(e)),
+        )
+    })?;
+    let error = error.map(serde_json::to_value).transpose().map_err(|e| {
+        ErrorModel::internal(
+            "Error serializing scan plan error",
+            "ScanPlanSerializationError",
+            Some(Box::new(e)),
+        )
+    })?;
+
+    sqlx::query!(
+        r#"
+        WITH cleanup AS (
+            DELETE FROM scan_plan WHERE expires_at < now()
+        )
+        INSERT INTO scan_plan (plan_id, warehouse_id, table_id, snapshot_id, request, status, result, error, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        "#,
+        plan_id,
+        *warehouse_id,
+        *table_id,
+        snapshot_id,
+        request,
+        DbScanPlanStatus::from(status) as _,
+        result,
+        error,
+        expires_at,
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error creating scan plan".to_string()))?;
+
+    Ok(())
+}
+
+pub(crate) async fn set_scan_plan_result<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    plan_id: ScanPlanId,
+    result: std::result::Result<ScanTasks, ErrorModel>,
+    connection: E,
+) -> Result<()> {
+    let (status, result, error) = match result {
+        Ok(scan_tasks) => (
+            DbScanPlanStatus::Completed,
+            Some(serde_json::to_value(scan_tasks).map_err(|e| {
+                ErrorModel::internal(
+                    "Error serializing scan plan result",
+                    "ScanPlanSerializationError",
+                    Some(Box::new(e)),
+                )
+            })?),
+            None,
+        ),
+        Err(error) => (
+            DbScanPlanStatus::Failed,
+            None,
+            Some(serde_json::to_value(error).map_err(|e| {
+                ErrorModel::internal(
+                    "Error serializing scan plan error",
+                    "ScanPlanSerializationError",
+                    Some(Box::new(e)),
+                )
+            })?),
+        ),
+    };
+
+    sqlx::query!(
+        r#"
+        UPDATE scan_plan
+        SET status = $2, result = $3, error = $4
+        WHERE plan_id = $1 AND status = 'submitted'
+        "#,
+        plan_id,
+        status as _,
+        result,
+        error,
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error storing scan plan result".to_string()))?;
+
+    Ok(())
+}
+
+pub(crate) async fn get_scan_plan<'c, 'e: 'c, E: sqlx::Executor<'c, Database = sqlx::Postgres>>(
+    warehouse_id: WarehouseId,
+    table_id: TableId,
+    plan_id: ScanPlanId,
+    connection: E,
+) -> Result<Option<ScanPlan>> {
+    let row = sqlx::query!(
+        r#"
+        SELECT plan_id,
+               snapshot_id,
+               request as "request: Json<iceberg_ext::catalog::rest::PlanTableScanRequest>",
+               status as "status: DbScanPlanStatus",
+               result as "result: Json<ScanTasks>",
+               error as "error: Json<ErrorModel>",
+               expires_at
+        FROM scan_plan
+        WHERE plan_id = $1 AND warehouse_id = $2 AND table_id = $3 AND expires_at > now()
+        "#,
+        plan_id,
+        *warehouse_id,
+        *table_id,
+    )
+    .fetch_optional(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching scan plan".to_string()))?;
+
+    Ok(row.map(|row| ScanPlan {
+        plan_id: row.plan_id,
+        warehouse_id,
+        table_id,
+        snapshot_id: row.snapshot_id,
+        request: row.request.0,
+        status: row.status.into(),
+        result: row.result.map(|r| r.0),
+        error: row.error.map(|e| e.0),
+        expires_at: row.expires_at,
+    }))
+}
+
+pub(crate) async fn cancel_scan_plan<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseId,
+    table_id: TableId,
+    plan_id: ScanPlanId,
+    connection: E,
+) -> Result<Option<()>> {
+    let row = sqlx::query!(
+        r#"
+        UPDATE scan_plan
+        SET status = 'cancelled', result = NULL
+        WHERE plan_id = $1 AND warehouse_id = $2 AND table_id = $3 AND expires_at > now()
+        "#,
+        plan_id,
+        *warehouse_id,
+        *table_id,
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error cancelling scan plan".to_string()))?;
+
+    if row.rows_affected() == 0 {
+        return Ok(None);
+    }
+
+    Ok(Some(()))
+}
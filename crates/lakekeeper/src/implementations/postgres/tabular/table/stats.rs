@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+
+use iceberg::spec::TableMetadata;
+use iceberg_ext::catalog::rest::TableStats;
+
+use crate::{
+    implementations::postgres::dbutils::DBErrorHandler as _,
+    service::{Result, TableId},
+};
+
+const TOTAL_FILES_SIZE_SUMMARY_PROPERTY: &str = "total-files-size";
+
+/// Total size of the files of the current snapshot, as reported by its summary.
+/// Tables without a current snapshot are empty.
+pub(super) fn total_data_size_bytes(metadata: &TableMetadata) -> Option<i64> {
+    match metadata.current_snapshot() {
+        Some(snapshot) => snapshot
+            .summary()
+            .additional_properties
+            .get(TOTAL_FILES_SIZE_SUMMARY_PROPERTY)
+            .and_then(|size| size.parse().ok()),
+        None => Some(0),
+    }
+}
+
+pub(crate) async fn get_table_stats<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    table_ids: &[TableId],
+    connection: E,
+) -> Result<HashMap<TableId, TableStats>> {
+    let table_ids = table_ids.iter().map(|id| **id).collect::<Vec<_>>();
+    let rows = sqlx::query!(
+        r#"
+        SELECT t.table_id,
+            t.last_updated_ms,
+            t.total_data_size_bytes,
+            s.last_snapshot_timestamp_ms,
+            s.snapshot_count as "snapshot_count!",
+            coalesce(jsonb_array_length(ts.schema -> 'fields'), 0)::bigint as "column_count!"
+        FROM "table" t
+        CROSS JOIN LATERAL (
+            SELECT max(timestamp_ms) AS last_snapshot_timestamp_ms, count(*) AS snapshot_count
+            FROM table_snapshot
+            WHERE table_id = t.table_id
+        ) s
+        LEFT JOIN table_current_schema cs ON cs.table_id = t.table_id
+        LEFT JOIN table_schema ts ON ts.table_id = cs.table_id AND ts.schema_id = cs.schema_id
+        WHERE t.table_id = ANY($1)
+        "#,
+        &table_ids,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching table statistics"))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            (
+                TableId::from(row.table_id),
+                TableStats {
+                    last_updated_ms: row.last_updated_ms,
+                    last_snapshot_timestamp_ms: row.last_snapshot_timestamp_ms,
+                    snapshot_count: row.snapshot_count,
+                    column_count: row.column_count,
+                    total_data_size_bytes: row.total_data_size_bytes,
+                },
+            )
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::implementations::postgres::{
+        tabular::table::tests::initialize_table, warehouse::test::initialize_warehouse,
+        CatalogState,
+    };
+
+    #[sqlx::test]
+    async fn test_get_table_stats(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let table = initialize_table(warehouse_id, state.clone(), false, None, None).await;
+
+        let unknown = TableId::new_random();
+        let stats = get_table_stats(&[table.table_id, unknown], &pool)
+            .await
+            .unwrap();
+        assert_eq!(stats.len(), 1);
+        let stats = &stats[&table.table_id];
+        assert_eq!(stats.snapshot_count, 1);
+        assert!(stats.last_snapshot_timestamp_ms.is_some());
+        assert!(stats.last_updated_ms.is_some());
+        assert_eq!(stats.column_count, 2);
+        // The snapshot of the test table is only referenced by a tag, so the table has
+        // no current snapshot.
+        assert_eq!(stats.total_data_size_bytes, Some(0));
+    }
+}
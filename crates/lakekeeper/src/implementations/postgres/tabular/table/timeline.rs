@@ -0,0 +1,155 @@
+use chrono::{DateTime, Utc};
+
+use crate::{
+    api::{
+        iceberg::v1::{PaginationQuery, MAX_PAGE_SIZE},
+        management::v1::table::GetTableTimelineResponse,
+    },
+    implementations::postgres::{
+        dbutils::DBErrorHandler as _,
+        pagination::{PaginateToken, V1PaginateToken},
+    },
+    service::{
+        table_timeline::{TableActivity, TableTimelineEvent, TableTimelineEventType},
+        Result, TableId,
+    },
+    WarehouseId,
+};
+
+pub(crate) async fn record_table_activity<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    activity: &TableActivity,
+    connection: E,
+) -> Result<()> {
+    // The warehouse is taken from the table. Nothing is recorded if the table is gone.
+    sqlx::query!(
+        r#"
+        INSERT INTO table_activity (activity_id, warehouse_id, table_id, event_type, principal,
+            details, created_at)
+        SELECT $1, n.warehouse_id, t.table_id, $3, $4, $5, $6
+        FROM "table" t
+        INNER JOIN tabular ta ON ta.tabular_id = t.table_id
+        INNER JOIN namespace n ON n.namespace_id = ta.namespace_id
+        WHERE t.table_id = $2
+        "#,
+        activity.activity_id,
+        *activity.table_id,
+        activity.event_type as _,
+        activity.principal,
+        activity.details,
+        activity.created_at,
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error recording table activity"))?;
+
+    Ok(())
+}
+
+pub(crate) async fn get_table_timeline<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseId,
+    table_id: TableId,
+    PaginationQuery {
+        page_size,
+        page_token,
+    }: PaginationQuery,
+    connection: E,
+) -> Result<GetTableTimelineResponse> {
+    let page_size = page_size.map_or(MAX_PAGE_SIZE, |i| i.clamp(1, MAX_PAGE_SIZE));
+
+    let token = page_token
+        .as_option()
+        .map(PaginateToken::<String>::try_from)
+        .transpose()?
+        .map(PaginateToken::into_v1)
+        .transpose()?;
+    let (token_ts, token_id): (Option<DateTime<Utc>>, Option<String>) = token
+        .map(|V1PaginateToken { created_at, id }| (created_at, id))
+        .unzip();
+
+    // Events of all sources get an id that is unique within the timeline,
+    // so that events with the same timestamp are paginated consistently.
+    let rows = sqlx::query!(
+        r#"
+        WITH events AS (
+            SELECT 'commit'::table_timeline_event_type AS event_type,
+                to_timestamp(s.timestamp_ms / 1000.0) AS event_timestamp,
+                NULL::text AS principal,
+                jsonb_build_object(
+                    'snapshot-id', s.snapshot_id,
+                    'sequence-number', s.sequence_number,
+                    'operation', s.summary->>'operation'
+                ) AS details,
+                'commit-' || s.snapshot_id::text AS event_id
+            FROM table_snapshot s
+            WHERE s.table_id = $2
+            UNION ALL
+            SELECT a.event_type, a.created_at, a.principal, a.details,
+                'activity-' || a.activity_id::text
+            FROM table_activity a
+            WHERE a.warehouse_id = $1 AND a.table_id = $2
+            UNION ALL
+            SELECT 'maintenance-run'::table_timeline_event_type,
+                coalesce(l.started_at, l.created_at),
+                NULL::text,
+                jsonb_build_object(
+                    'queue-name', l.queue_name,
+                    'task-id', l.task_id,
+                    'attempt', l.attempt,
+                    'status', l.status::text,
+                    'message', l.message,
+                    'duration-ms', (extract(epoch FROM l.duration) * 1000)::bigint
+                ),
+                'task-' || l.task_id::text || '-' || l.attempt::text
+            FROM task_log l
+            WHERE l.warehouse_id = $1 AND l.entity_type = 'tabular' AND l.entity_id = $2
+        )
+        SELECT event_type as "event_type!: TableTimelineEventType",
+            event_timestamp as "event_timestamp!",
+            principal,
+            details as "details!",
+            event_id as "event_id!"
+        FROM events
+        WHERE $3::timestamptz IS NULL OR (event_timestamp, event_id) < ($3, $4::text)
+        ORDER BY event_timestamp DESC, event_id DESC
+        LIMIT $5
+        "#,
+        *warehouse_id,
+        *table_id,
+        token_ts,
+        token_id,
+        page_size,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching table timeline"))?;
+
+    let next_page_token = rows.last().map(|r| {
+        PaginateToken::V1(V1PaginateToken {
+            created_at: r.event_timestamp,
+            id: r.event_id.clone(),
+        })
+        .to_string()
+    });
+    let events = rows
+        .into_iter()
+        .map(|r| TableTimelineEvent {
+            event_type: r.event_type,
+            timestamp: r.event_timestamp,
+            principal: r.principal,
+            details: r.details,
+        })
+        .collect();
+
+    Ok(GetTableTimelineResponse {
+        events,
+        next_page_token,
+    })
+}
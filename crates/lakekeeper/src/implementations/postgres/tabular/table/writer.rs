@@ -0,0 +1,255 @@
+use iceberg_ext::catalog::rest::ErrorModel;
+use sqlx::types::Json;
+
+use crate::{
+    api::management::v1::table::{
+        PreparedTableWriterCommit, TableWriter, TableWriterCheckpoint, TableWriterState,
+        TABLE_WRITER_CONFLICT_ERROR_TYPE,
+    },
+    implementations::postgres::dbutils::DBErrorHandler as _,
+    service::{Result, TableId},
+    WarehouseId,
+};
+
+pub(crate) async fn begin_table_writer<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseId,
+    table_id: TableId,
+    writer_id: &str,
+    connection: E,
+) -> Result<TableWriterState> {
+    let row = sqlx::query!(
+        r#"
+        INSERT INTO table_writer (table_id, writer_id, warehouse_id, epoch)
+        VALUES ($1, $2, $3, 1)
+        ON CONFLICT (table_id, writer_id)
+        DO UPDATE SET epoch = table_writer.epoch + 1
+        RETURNING epoch, committed_checkpoint, prepared_checkpoint
+        "#,
+        *table_id,
+        writer_id,
+        *warehouse_id,
+    )
+    .fetch_one(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error beginning table writer"))?;
+
+    Ok(TableWriterState {
+        epoch: row.epoch,
+        committed_checkpoint: row.committed_checkpoint,
+        prepared_checkpoint: row.prepared_checkpoint,
+    })
+}
+
+pub(crate) async fn get_table_writer<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    table_id: TableId,
+    writer_id: &str,
+    connection: E,
+) -> Result<Option<TableWriter>> {
+    let row = sqlx::query!(
+        r#"
+        SELECT epoch,
+               committed_checkpoint,
+               prepared_checkpoint,
+               prepared_commit as "prepared_commit: Json<PreparedTableWriterCommit>"
+        FROM table_writer
+        WHERE table_id = $1 AND writer_id = $2
+        "#,
+        *table_id,
+        writer_id,
+    )
+    .fetch_optional(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error loading table writer"))?;
+
+    Ok(row.map(|row| TableWriter {
+        state: TableWriterState {
+            epoch: row.epoch,
+            committed_checkpoint: row.committed_checkpoint,
+            prepared_checkpoint: row.prepared_checkpoint,
+        },
+        prepared_commit: row.prepared_commit.map(|c| c.0),
+    }))
+}
+
+/// Returns `false` if the writer does not exist or `epoch` is not its current epoch.
+pub(crate) async fn prepare_table_writer_commit<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    table_id: TableId,
+    writer_id: &str,
+    epoch: i64,
+    checkpoint_id: i64,
+    commit: &PreparedTableWriterCommit,
+    connection: E,
+) -> Result<bool> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE table_writer
+        SET prepared_checkpoint = $4, prepared_commit = $5
+        WHERE table_id = $1 AND writer_id = $2 AND epoch = $3
+        "#,
+        *table_id,
+        writer_id,
+        epoch,
+        checkpoint_id,
+        Json(commit) as _,
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error preparing table writer commit"))?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+pub(crate) async fn complete_table_writer_commit<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    checkpoint: &TableWriterCheckpoint,
+    connection: E,
+) -> Result<()> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE table_writer
+        SET committed_checkpoint = $4, prepared_checkpoint = NULL, prepared_commit = NULL
+        WHERE table_id = $1 AND writer_id = $2 AND epoch = $3
+            AND (committed_checkpoint IS NULL OR committed_checkpoint < $4)
+        "#,
+        *checkpoint.table_id,
+        checkpoint.writer_id,
+        checkpoint.epoch,
+        checkpoint.checkpoint_id,
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error completing table writer commit"))?;
+
+    if result.rows_affected() != 1 {
+        return Err(ErrorModel::conflict(
+            format!(
+                "Writer {} of table {} has been fenced or checkpoint {} has already been committed",
+                checkpoint.writer_id, checkpoint.table_id, checkpoint.checkpoint_id
+            ),
+            TABLE_WRITER_CONFLICT_ERROR_TYPE,
+            None,
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use iceberg::TableUpdate;
+
+    use super::*;
+    use crate::implementations::postgres::{
+        tabular::table::tests::initialize_table, warehouse::test::initialize_warehouse,
+        CatalogState,
+    };
+
+    #[sqlx::test]
+    async fn test_table_writer_lifecycle(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let table_id = initialize_table(warehouse_id, state.clone(), false, None, None)
+            .await
+            .table_id;
+
+        let first = begin_table_writer(warehouse_id, table_id, "job-1", &pool)
+            .await
+            .unwrap();
+        assert_eq!(
+            first,
+            TableWriterState {
+                epoch: 1,
+                committed_checkpoint: None,
+                prepared_checkpoint: None,
+            }
+        );
+
+        let commit = PreparedTableWriterCommit {
+            requirements: vec![],
+            updates: vec![TableUpdate::RemoveProperties {
+                removals: vec!["foo".to_string()],
+            }],
+        };
+        assert!(
+            prepare_table_writer_commit(table_id, "job-1", 1, 10, &commit, &pool)
+                .await
+                .unwrap()
+        );
+
+        // A restarted writer fences the previous instance and sees the prepared checkpoint
+        let second = begin_table_writer(warehouse_id, table_id, "job-1", &pool)
+            .await
+            .unwrap();
+        assert_eq!(second.epoch, 2);
+        assert_eq!(second.prepared_checkpoint, Some(10));
+        assert!(
+            !prepare_table_writer_commit(table_id, "job-1", 1, 11, &commit, &pool)
+                .await
+                .unwrap()
+        );
+        let writer = get_table_writer(table_id, "job-1", &pool)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(writer.prepared_commit, Some(commit));
+
+        let mut checkpoint = TableWriterCheckpoint {
+            table_id,
+            writer_id: "job-1".to_string(),
+            epoch: 1,
+            checkpoint_id: 10,
+        };
+        let err = complete_table_writer_commit(&checkpoint, &pool)
+            .await
+            .unwrap_err();
+        assert_eq!(err.error.r#type, TABLE_WRITER_CONFLICT_ERROR_TYPE);
+
+        checkpoint.epoch = 2;
+        complete_table_writer_commit(&checkpoint, &pool)
+            .await
+            .unwrap();
+        let writer = get_table_writer(table_id, "job-1", &pool)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            writer,
+            TableWriter {
+                state: TableWriterState {
+                    epoch: 2,
+                    committed_checkpoint: Some(10),
+                    prepared_checkpoint: None,
+                },
+                prepared_commit: None,
+            }
+        );
+
+        // Checkpoints are committed at most once
+        let err = complete_table_writer_commit(&checkpoint, &pool)
+            .await
+            .unwrap_err();
+        assert_eq!(err.error.code, 409);
+
+        // Writers are independent of each other
+        assert!(get_table_writer(table_id, "job-2", &pool)
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
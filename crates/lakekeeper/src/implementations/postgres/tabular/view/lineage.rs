@@ -0,0 +1,123 @@
+use iceberg::TableIdent;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::{
+    implementations::postgres::{dbutils::DBErrorHandler as _, tabular},
+    service::{
+        view_lineage::{ReferencingView, ViewLineageSource},
+        Result, TableId, ViewId,
+    },
+    WarehouseId,
+};
+
+pub(super) async fn insert_view_version_lineage(
+    view_id: Uuid,
+    version_id: i32,
+    sources: &[TableIdent],
+    transaction: &mut Transaction<'_, Postgres>,
+) -> Result<()> {
+    for source in sources {
+        sqlx::query!(
+            r#"
+            INSERT INTO view_lineage (view_id, view_version_id, source_namespace, source_name)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT DO NOTHING
+            "#,
+            view_id,
+            i64::from(version_id),
+            &source.namespace.clone().inner(),
+            source.name.as_str(),
+        )
+        .execute(&mut **transaction)
+        .await
+        .map_err(|e| e.into_error_model("Error inserting view lineage"))?;
+    }
+    Ok(())
+}
+
+pub(crate) async fn get_view_lineage<'e, 'c: 'e, E>(
+    warehouse_id: WarehouseId,
+    view_id: ViewId,
+    connection: E,
+) -> Result<Vec<ViewLineageSource>>
+where
+    E: 'e + sqlx::Executor<'c, Database = sqlx::Postgres>,
+{
+    // Sources are resolved by name, tables of other warehouses are never matched.
+    let rows = sqlx::query!(
+        r#"
+        SELECT l.source_namespace, l.source_name, t.tabular_id as "table_id?"
+        FROM current_view_metadata_version c
+        INNER JOIN view_lineage l ON l.view_id = c.view_id AND l.view_version_id = c.version_id
+        INNER JOIN tabular v ON v.tabular_id = c.view_id
+        INNER JOIN namespace vn ON vn.namespace_id = v.namespace_id
+        LEFT JOIN namespace n ON n.warehouse_id = vn.warehouse_id
+            AND n.namespace_name = l.source_namespace
+            AND n.deleted_at IS NULL
+        LEFT JOIN tabular t ON t.namespace_id = n.namespace_id
+            AND t.name = l.source_name
+            AND t.typ = 'table'
+            AND t.deleted_at IS NULL
+            AND t.metadata_location IS NOT NULL
+        WHERE vn.warehouse_id = $1 AND c.view_id = $2
+        ORDER BY l.source_namespace, l.source_name
+        "#,
+        *warehouse_id,
+        *view_id,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching view lineage"))?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(ViewLineageSource {
+                namespace: tabular::try_parse_namespace_ident(row.source_namespace)?,
+                name: row.source_name,
+                table_id: row.table_id.map(TableId::from),
+            })
+        })
+        .collect()
+}
+
+pub(crate) async fn list_views_referencing_table<'e, 'c: 'e, E>(
+    warehouse_id: WarehouseId,
+    table_id: TableId,
+    connection: E,
+) -> Result<Vec<ReferencingView>>
+where
+    E: 'e + sqlx::Executor<'c, Database = sqlx::Postgres>,
+{
+    let rows = sqlx::query!(
+        r#"
+        SELECT v.tabular_id as view_id, vn.namespace_name as view_namespace, v.name as view_name
+        FROM tabular t
+        INNER JOIN namespace n ON n.namespace_id = t.namespace_id
+        INNER JOIN view_lineage l ON l.source_namespace = n.namespace_name
+            AND l.source_name = t.name
+        INNER JOIN current_view_metadata_version c ON c.view_id = l.view_id
+            AND c.version_id = l.view_version_id
+        INNER JOIN tabular v ON v.tabular_id = l.view_id AND v.deleted_at IS NULL
+        INNER JOIN namespace vn ON vn.namespace_id = v.namespace_id
+            AND vn.warehouse_id = n.warehouse_id
+        WHERE n.warehouse_id = $1 AND t.tabular_id = $2
+        ORDER BY vn.namespace_name, v.name
+        "#,
+        *warehouse_id,
+        *table_id,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching views referencing table"))?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(ReferencingView {
+                view_id: row.view_id,
+                namespace: tabular::try_parse_namespace_ident(row.view_namespace)?,
+                name: row.view_name,
+            })
+        })
+        .collect()
+}
@@ -1,3 +1,4 @@
+mod lineage;
 mod load;
 
 use std::{collections::HashMap, default::Default};
@@ -9,6 +10,7 @@ use iceberg::{
     NamespaceIdent,
 };
 use iceberg_ext::configs::Location;
+pub(crate) use lineage::{get_view_lineage, list_views_referencing_table};
 pub(crate) use load::load_view;
 use serde::Deserialize;
 use sqlx::{FromRow, Postgres, Transaction};
@@ -25,7 +27,8 @@ use crate::{
         },
     },
     service::{
-        ErrorModel, ListFlags, NamespaceId, Result, TableIdent, TableInfo, TabularInfo, ViewId,
+        view_lineage::view_version_sources, ErrorModel, ListFlags, NamespaceId, Result, TableIdent,
+        TableInfo, TabularInfo, ViewId,
     },
     WarehouseId,
 };
@@ -50,7 +53,7 @@ where
         catalog_state,
     )
     .await?
-    .map(|(id, _)| match id {
+    .map(|(id, _, _)| match id {
         TabularId::Table(_) => Err(ErrorModel::builder()
             .code(StatusCode::INTERNAL_SERVER_ERROR.into())
             .message("DB returned a table when filtering for views.".to_string())
@@ -62,6 +65,43 @@ where
     .transpose()
 }
 
+pub(crate) async fn view_id_to_ident<'e, 'c: 'e, E>(
+    warehouse_id: WarehouseId,
+    view_id: ViewId,
+    catalog_state: E,
+) -> Result<Option<TableIdent>>
+where
+    E: 'e + sqlx::Executor<'c, Database = sqlx::Postgres>,
+{
+    let view = sqlx::query!(
+        r#"
+        SELECT n.namespace_name, t.name
+        FROM tabular t
+        INNER JOIN namespace n ON t.namespace_id = n.namespace_id
+        INNER JOIN warehouse w ON n.warehouse_id = w.warehouse_id
+        WHERE n.warehouse_id = $1 AND t.tabular_id = $2
+        AND t.typ = 'view'
+        AND w.status = 'active'
+        AND n.deleted_at IS NULL
+        AND t.deleted_at IS NULL
+        AND t.metadata_location IS NOT NULL
+        "#,
+        *warehouse_id,
+        *view_id
+    )
+    .fetch_optional(catalog_state)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching view".to_string()))?;
+
+    view.map(|view| {
+        Ok(TableIdent {
+            namespace: tabular::try_parse_namespace_ident(view.namespace_name)?,
+            name: view.name,
+        })
+    })
+    .transpose()
+}
+
 pub(crate) async fn create_view(
     namespace_id: NamespaceId,
     metadata_location: &Location,
@@ -92,6 +132,7 @@ pub(crate) async fn create_view(
             typ: TabularType::View,
             metadata_location: Some(metadata_location),
             location,
+            external: false,
         },
         &mut *transaction,
     )
@@ -318,7 +359,7 @@ async fn create_view_version(
         r#"
         SELECT namespace_id
         FROM namespace
-        WHERE namespace_name = $1
+        WHERE namespace_name = $1 AND deleted_at IS NULL
         "#,
         &default_ns
     )
@@ -387,6 +428,13 @@ async fn create_view_version(
     for rep in view_version.representations().iter() {
         insert_representation(rep, transaction, insert_response).await?;
     }
+    lineage::insert_view_version_lineage(
+        view_id,
+        version_id,
+        &view_version_sources(&view_version),
+        transaction,
+    )
+    .await?;
 
     tracing::debug!(
         "Inserted version: '{}' view metadata version for '{}'",
@@ -535,7 +583,7 @@ pub(crate) mod tests {
         api::{iceberg::v1::PaginationQuery, management::v1::DeleteKind},
         implementations::postgres::{
             namespace::tests::initialize_namespace,
-            tabular::{mark_tabular_as_deleted, view::load_view},
+            tabular::{mark_tabular_as_deleted, table::tests::initialize_table, view::load_view},
             warehouse::test::initialize_warehouse,
             CatalogState, PostgresCatalog,
         },
@@ -893,6 +941,46 @@ pub(crate) mod tests {
         assert_eq!(e.error.code, 404);
     }
 
+    #[sqlx::test]
+    async fn view_lineage_of_current_version(pool: sqlx::PgPool) {
+        let (state, metadata, warehouse_id, _, _, _) = prepare_view(pool).await;
+        let view_id = ViewId::from(metadata.uuid());
+
+        // The view references `spark_demo.my_table`, which doesn't exist yet
+        let lineage = super::get_view_lineage(warehouse_id, view_id, &state.read_pool())
+            .await
+            .unwrap();
+        assert_eq!(lineage.len(), 1);
+        assert_eq!(lineage[0].namespace.clone().inner(), vec!["spark_demo"]);
+        assert_eq!(lineage[0].name, "my_table");
+        assert_eq!(lineage[0].table_id, None);
+
+        // Identifiers are matched case-insensitively
+        let namespace = NamespaceIdent::from_vec(vec!["SPARK_DEMO".to_string()]).unwrap();
+        initialize_namespace(state.clone(), warehouse_id, &namespace, None).await;
+        let table = initialize_table(
+            warehouse_id,
+            state.clone(),
+            false,
+            Some(namespace),
+            Some("My_Table".to_string()),
+        )
+        .await;
+
+        let lineage = super::get_view_lineage(warehouse_id, view_id, &state.read_pool())
+            .await
+            .unwrap();
+        assert_eq!(lineage[0].table_id, Some(table.table_id));
+
+        let views =
+            super::list_views_referencing_table(warehouse_id, table.table_id, &state.read_pool())
+                .await
+                .unwrap();
+        assert_eq!(views.len(), 1);
+        assert_eq!(views[0].view_id, *view_id);
+        assert_eq!(views[0].name, "myview");
+    }
+
     async fn prepare_view(
         pool: PgPool,
     ) -> (
@@ -0,0 +1,442 @@
+use chrono::{DateTime, Utc};
+use iceberg_ext::catalog::rest::ErrorModel;
+use uuid::Uuid;
+
+use super::task_queues::EntityType;
+use crate::{
+    api::{
+        iceberg::v1::{PaginationQuery, MAX_PAGE_SIZE},
+        management::v1::task::{DeadLetterTask, ListDeadLetterTasksResponse, TaskEntityType},
+    },
+    implementations::postgres::{
+        dbutils::DBErrorHandler,
+        pagination::{PaginateToken, V1PaginateToken},
+    },
+    service::{
+        task_queue::{TaskId, TaskLogEntry, TaskOutcome, TaskStatus},
+        Result,
+    },
+    WarehouseId,
+};
+
+#[derive(sqlx::FromRow, Debug)]
+struct DeadLetterTaskRow {
+    task_id: Uuid,
+    warehouse_id: Uuid,
+    queue_name: String,
+    task_data: serde_json::Value,
+    entity_id: Uuid,
+    entity_type: EntityType,
+    parent_task_id: Option<Uuid>,
+    attempt: i32,
+    last_error: Option<String>,
+    task_created_at: DateTime<Utc>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<DeadLetterTaskRow> for DeadLetterTask {
+    fn from(row: DeadLetterTaskRow) -> Self {
+        Self {
+            task_id: row.task_id,
+            warehouse_id: row.warehouse_id.into(),
+            queue_name: row.queue_name,
+            entity_type: match row.entity_type {
+                EntityType::Tabular => TaskEntityType::Tabular,
+                EntityType::Warehouse => TaskEntityType::Warehouse,
+            },
+            entity_id: row.entity_id,
+            parent_task_id: row.parent_task_id,
+            attempt: row.attempt,
+            last_error: row.last_error,
+            payload: row.task_data,
+            enqueued_at: row.task_created_at,
+            dead_lettered_at: row.created_at,
+        }
+    }
+}
+
+pub(crate) async fn list_dead_letter_tasks<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseId,
+    queue_name: Option<&str>,
+    PaginationQuery {
+        page_size,
+        page_token,
+    }: PaginationQuery,
+    connection: E,
+) -> Result<ListDeadLetterTasksResponse> {
+    let page_size = page_size.map_or(MAX_PAGE_SIZE, |i| i.clamp(1, MAX_PAGE_SIZE));
+
+    let token = page_token
+        .as_option()
+        .map(PaginateToken::try_from)
+        .transpose()?
+        .map(PaginateToken::into_v1)
+        .transpose()?;
+
+    let (token_ts, token_id): (_, Option<Uuid>) = token
+        .map(|V1PaginateToken { created_at, id }| (created_at, id))
+        .unzip();
+
+    let tasks: Vec<DeadLetterTask> = sqlx::query_as!(
+        DeadLetterTaskRow,
+        r#"
+        SELECT task_id, warehouse_id, queue_name, task_data, entity_id,
+            entity_type as "entity_type: EntityType", parent_task_id, attempt, last_error,
+            task_created_at, created_at
+        FROM task_dead_letter d
+        WHERE warehouse_id = $1
+            AND ($2::text IS NULL OR queue_name = $2)
+            --- PAGINATION
+            AND ((d.created_at > $3 OR $3 IS NULL) OR (d.created_at = $3 AND d.task_id > $4))
+        ORDER BY d.created_at, d.task_id ASC
+        LIMIT $5
+        "#,
+        *warehouse_id,
+        queue_name,
+        token_ts,
+        token_id,
+        page_size,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching dead-letter tasks"))?
+    .into_iter()
+    .map(DeadLetterTask::from)
+    .collect();
+
+    let next_page_token = tasks.last().map(|t| {
+        PaginateToken::V1(V1PaginateToken::<Uuid> {
+            created_at: t.dead_lettered_at,
+            id: t.task_id,
+        })
+        .to_string()
+    });
+
+    Ok(ListDeadLetterTasksResponse {
+        tasks,
+        next_page_token,
+    })
+}
+
+pub(crate) async fn get_dead_letter_task<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseId,
+    task_id: TaskId,
+    connection: E,
+) -> Result<Option<DeadLetterTask>> {
+    let row = sqlx::query_as!(
+        DeadLetterTaskRow,
+        r#"
+        SELECT task_id, warehouse_id, queue_name, task_data, entity_id,
+            entity_type as "entity_type: EntityType", parent_task_id, attempt, last_error,
+            task_created_at, created_at
+        FROM task_dead_letter
+        WHERE warehouse_id = $1 AND task_id = $2
+        "#,
+        *warehouse_id,
+        *task_id,
+    )
+    .fetch_optional(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching dead-letter task"))?;
+
+    Ok(row.map(DeadLetterTask::from))
+}
+
+/// All finished attempts of a task, oldest first.
+pub(crate) async fn list_task_attempts<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseId,
+    task_id: TaskId,
+    connection: E,
+) -> Result<Vec<TaskLogEntry>> {
+    Ok(sqlx::query!(
+        r#"SELECT task_id,
+                  attempt,
+                  status as "status: TaskOutcome",
+                  started_at,
+                  (extract(epoch from duration) * 1000)::bigint as duration_ms,
+                  message,
+                  created_at
+        FROM task_log
+        WHERE warehouse_id = $1 AND task_id = $2
+        ORDER BY attempt ASC"#,
+        *warehouse_id,
+        *task_id,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model(format!("Failed to list attempts of task {task_id}")))?
+    .into_iter()
+    .map(|row| TaskLogEntry {
+        task_id: row.task_id.into(),
+        attempt: row.attempt,
+        status: row.status,
+        started_at: row.started_at,
+        duration_ms: row.duration_ms,
+        message: row.message,
+        created_at: row.created_at,
+    })
+    .collect())
+}
+
+/// Moves a dead-lettered task back into its queue as a new task with `new_task_id`.
+/// Returns `None` if the dead-lettered task does not exist.
+pub(crate) async fn requeue_dead_letter_task<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseId,
+    task_id: TaskId,
+    new_task_id: TaskId,
+    connection: E,
+) -> Result<Option<TaskId>> {
+    let new_task_id = sqlx::query_scalar!(
+        r#"
+        WITH dead_letter AS (
+            DELETE FROM task_dead_letter
+            WHERE warehouse_id = $1 AND task_id = $2
+            RETURNING warehouse_id, queue_name, task_data, entity_id, entity_type, parent_task_id
+        )
+        INSERT INTO task (task_id, warehouse_id, queue_name, status, parent_task_id, task_data, entity_type, entity_id)
+        SELECT $3, warehouse_id, queue_name, $4, parent_task_id, task_data, entity_type, entity_id
+        FROM dead_letter
+        RETURNING task_id
+        "#,
+        *warehouse_id,
+        *task_id,
+        *new_task_id,
+        TaskStatus::Scheduled as _,
+    )
+    .fetch_optional(connection)
+    .await
+    .map_err(|e| match e {
+        sqlx::Error::Database(db_error) if db_error.is_unique_violation() => {
+            ErrorModel::conflict(
+                format!(
+                    "Cannot requeue task {task_id}: another task for the same entity is already in the queue"
+                ),
+                "TaskAlreadyQueued",
+                Some(Box::new(db_error)),
+            )
+        }
+        _ => e.into_error_model("Error requeueing dead-letter task"),
+    })?;
+
+    Ok(new_task_id.map(TaskId::from))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::iceberg::v1::PageToken,
+        implementations::postgres::task_queues::{
+            pick_task, queue_task, record_failure, test::setup,
+        },
+        service::task_queue::{EntityId, DEFAULT_MAX_TIME_SINCE_LAST_HEARTBEAT},
+    };
+
+    /// Fails the only task of the `test` queue until it is dead-lettered.
+    async fn fail_until_dead_lettered(
+        pool: &sqlx::PgPool,
+        warehouse_id: WarehouseId,
+        max_retries: i32,
+    ) -> TaskId {
+        let mut conn = pool.acquire().await.unwrap();
+        for _ in 0..max_retries {
+            let task = pick_task(pool, "test", DEFAULT_MAX_TIME_SINCE_LAST_HEARTBEAT)
+                .await
+                .unwrap()
+                .unwrap();
+            record_failure(&mut conn, task.task_id, max_retries, "boom")
+                .await
+                .unwrap();
+        }
+        assert!(
+            pick_task(pool, "test", DEFAULT_MAX_TIME_SINCE_LAST_HEARTBEAT)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        let mut tasks =
+            list_dead_letter_tasks(warehouse_id, Some("test"), PaginationQuery::empty(), pool)
+                .await
+                .unwrap()
+                .tasks;
+        TaskId::from(tasks.pop().unwrap().task_id)
+    }
+
+    #[sqlx::test]
+    async fn test_exhausted_tasks_are_dead_lettered_and_requeued(pool: sqlx::PgPool) {
+        let warehouse_id = setup(pool.clone()).await;
+        let entity_id = Uuid::now_v7();
+        let mut conn = pool.acquire().await.unwrap();
+        let original_id = queue_task(
+            &mut conn,
+            "test",
+            None,
+            EntityId::Tabular(entity_id),
+            warehouse_id,
+            None,
+            Some(serde_json::json!({"key": "value"})),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        let task_id = fail_until_dead_lettered(&pool, warehouse_id, 2).await;
+        assert_eq!(task_id, original_id);
+
+        let task = get_dead_letter_task(warehouse_id, task_id, &pool)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(task.attempt, 2);
+        assert_eq!(task.entity_id, entity_id);
+        assert_eq!(task.last_error.as_deref(), Some("boom"));
+        assert_eq!(task.payload, serde_json::json!({"key": "value"}));
+
+        let attempts = list_task_attempts(warehouse_id, task_id, &pool)
+            .await
+            .unwrap();
+        assert_eq!(
+            attempts.iter().map(|a| a.attempt).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert!(attempts
+            .iter()
+            .all(|a| matches!(a.status, TaskOutcome::Failed)));
+
+        let new_task_id =
+            requeue_dead_letter_task(warehouse_id, task_id, TaskId::from(Uuid::now_v7()), &pool)
+                .await
+                .unwrap()
+                .unwrap();
+        assert!(get_dead_letter_task(warehouse_id, task_id, &pool)
+            .await
+            .unwrap()
+            .is_none());
+
+        let task = pick_task(&pool, "test", DEFAULT_MAX_TIME_SINCE_LAST_HEARTBEAT)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(task.task_id, new_task_id);
+        assert_eq!(task.attempt, 1);
+        assert_eq!(task.state, serde_json::json!({"key": "value"}));
+
+        // Requeueing twice is not possible
+        assert!(requeue_dead_letter_task(
+            warehouse_id,
+            task_id,
+            TaskId::from(Uuid::now_v7()),
+            &pool
+        )
+        .await
+        .unwrap()
+        .is_none());
+    }
+
+    #[sqlx::test]
+    async fn test_requeue_conflicts_with_queued_task(pool: sqlx::PgPool) {
+        let warehouse_id = setup(pool.clone()).await;
+        let entity_id = EntityId::Tabular(Uuid::now_v7());
+        let mut conn = pool.acquire().await.unwrap();
+        queue_task(&mut conn, "test", None, entity_id, warehouse_id, None, None)
+            .await
+            .unwrap()
+            .unwrap();
+        let task_id = fail_until_dead_lettered(&pool, warehouse_id, 1).await;
+
+        queue_task(&mut conn, "test", None, entity_id, warehouse_id, None, None)
+            .await
+            .unwrap()
+            .unwrap();
+        let err =
+            requeue_dead_letter_task(warehouse_id, task_id, TaskId::from(Uuid::now_v7()), &pool)
+                .await
+                .unwrap_err();
+        assert_eq!(err.error.code, http::StatusCode::CONFLICT.as_u16());
+
+        // The failed requeue keeps the task in the dead-letter queue
+        assert!(get_dead_letter_task(warehouse_id, task_id, &pool)
+            .await
+            .unwrap()
+            .is_some());
+    }
+
+    #[sqlx::test]
+    async fn test_list_dead_letter_tasks_paginates(pool: sqlx::PgPool) {
+        let warehouse_id = setup(pool.clone()).await;
+        let mut conn = pool.acquire().await.unwrap();
+        for _ in 0..3 {
+            queue_task(
+                &mut conn,
+                "test",
+                None,
+                EntityId::Tabular(Uuid::now_v7()),
+                warehouse_id,
+                None,
+                None,
+            )
+            .await
+            .unwrap()
+            .unwrap();
+            let task = pick_task(&pool, "test", DEFAULT_MAX_TIME_SINCE_LAST_HEARTBEAT)
+                .await
+                .unwrap()
+                .unwrap();
+            record_failure(&mut conn, task.task_id, 1, "boom")
+                .await
+                .unwrap();
+        }
+
+        let first = list_dead_letter_tasks(
+            warehouse_id,
+            None,
+            PaginationQuery {
+                page_token: PageToken::Empty,
+                page_size: Some(2),
+            },
+            &pool,
+        )
+        .await
+        .unwrap();
+        assert_eq!(first.tasks.len(), 2);
+        let second = list_dead_letter_tasks(
+            warehouse_id,
+            None,
+            PaginationQuery {
+                page_token: PageToken::Present(first.next_page_token.unwrap()),
+                page_size: Some(2),
+            },
+            &pool,
+        )
+        .await
+        .unwrap();
+        assert_eq!(second.tasks.len(), 1);
+
+        assert!(list_dead_letter_tasks(
+            warehouse_id,
+            Some("other"),
+            PaginationQuery::empty(),
+            &pool
+        )
+        .await
+        .unwrap()
+        .tasks
+        .is_empty());
+    }
+}
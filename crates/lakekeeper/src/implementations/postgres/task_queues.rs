@@ -21,14 +21,16 @@ pub(crate) struct InsertResult {
 
 #[derive(Debug, sqlx::Type, Clone, Copy)]
 #[sqlx(type_name = "entity_type", rename_all = "kebab-case")]
-enum EntityType {
+pub(super) enum EntityType {
     Tabular,
+    Warehouse,
 }
 
 impl From<EntityId> for EntityType {
     fn from(entity_id: EntityId) -> Self {
         match entity_id {
             EntityId::Tabular(_) => Self::Tabular,
+            EntityId::Warehouse(_) => Self::Warehouse,
         }
     }
 }
@@ -124,6 +126,7 @@ pub(crate) async fn queue_task_batch(
                 #[cfg(test)]
                 entity_id: match record.entity_type {
                     EntityType::Tabular => EntityId::Tabular(record.entity_id),
+                    EntityType::Warehouse => EntityId::Warehouse(record.entity_id),
                 },
             })
             .collect_vec()
@@ -131,23 +134,89 @@ pub(crate) async fn queue_task_batch(
     .map_err(|e| e.into_error_model("failed queueing tasks"))?)
 }
 
+/// Queues a task or, if a task of the same entity is already scheduled in the queue,
+/// replaces its schedule and payload. Returns `None` if the task of the entity is running.
+pub(crate) async fn queue_or_reschedule_task(
+    conn: &mut PgConnection,
+    queue_name: &'static str,
+    task: TaskInput,
+) -> Result<Option<TaskId>, IcebergErrorResponse> {
+    let TaskInput {
+        task_metadata:
+            TaskMetadata {
+                warehouse_id,
+                parent_task_id,
+                entity_id,
+                schedule_for,
+            },
+        payload,
+    } = task;
+
+    Ok(sqlx::query_scalar!(
+        r#"INSERT INTO task(
+                task_id,
+                queue_name,
+                status,
+                parent_task_id,
+                warehouse_id,
+                scheduled_for,
+                task_data,
+                entity_id,
+                entity_type)
+        VALUES ($1, $2, $3, $4, $5, coalesce($6, now()), $7, $8, $9)
+        ON CONFLICT (warehouse_id, entity_type, entity_id, queue_name) DO UPDATE
+        SET scheduled_for = EXCLUDED.scheduled_for, task_data = EXCLUDED.task_data
+        WHERE task.status = $3
+        RETURNING task_id"#,
+        Uuid::now_v7(),
+        queue_name,
+        TaskStatus::Scheduled as _,
+        parent_task_id.as_deref().copied(),
+        *warehouse_id,
+        schedule_for,
+        payload,
+        entity_id.to_uuid(),
+        EntityType::from(entity_id) as _,
+    )
+    .fetch_optional(conn)
+    .await
+    .map_err(|e| e.into_error_model("failed rescheduling task"))?
+    .map(TaskId::from))
+}
+
+fn to_pg_interval(duration: chrono::Duration) -> Result<PgInterval, IcebergErrorResponse> {
+    Ok(PgInterval {
+        months: 0,
+        days: 0,
+        microseconds: duration.num_microseconds().ok_or(ErrorModel::internal(
+            "Could not convert max_age into microseconds. Integer overflow, this is a bug.",
+            "InternalError",
+            None,
+        ))?,
+    })
+}
+
 #[tracing::instrument]
 pub(crate) async fn pick_task(
     pool: &PgPool,
     queue_name: &str,
     max_time_since_last_heartbeat: chrono::Duration,
 ) -> Result<Option<Task>, IcebergErrorResponse> {
-    let max_time_since_last_heartbeat = PgInterval {
-        months: 0,
-        days: 0,
-        microseconds: max_time_since_last_heartbeat.num_microseconds().ok_or(
-            ErrorModel::internal(
-                "Could not convert max_age into microseconds. Integer overflow, this is a bug.",
-                "InternalError",
-                None,
-            ),
-        )?,
-    };
+    lease_task(pool, queue_name, max_time_since_last_heartbeat, None).await
+}
+
+/// Picks the next task of `queue_name`. If `visibility_timeout` is set, the task becomes
+/// available to other workers once no heartbeat was received for that duration,
+/// regardless of the `max_time_since_last_heartbeat` configured for the queue.
+#[tracing::instrument]
+pub(crate) async fn lease_task(
+    pool: &PgPool,
+    queue_name: &str,
+    max_time_since_last_heartbeat: chrono::Duration,
+    visibility_timeout: Option<chrono::Duration>,
+) -> Result<Option<Task>, IcebergErrorResponse> {
+    let max_time_since_last_heartbeat = to_pg_interval(max_time_since_last_heartbeat)?;
+    let visibility_timeout = visibility_timeout.map(to_pg_interval).transpose()?;
     let x = sqlx::query!(
         r#"WITH updated_task AS (
         SELECT task_id, t.warehouse_id, config
@@ -157,7 +226,8 @@ pub(crate) async fn pick_task(
                    AND tc.warehouse_id = t.warehouse_id
         WHERE (status = $3 AND t.queue_name = $1
                    AND scheduled_for < now() AT TIME ZONE 'UTC')
-           OR (status = $4 AND (now() - last_heartbeat_at) > COALESCE(tc.max_time_since_last_heartbeat, $2))
+           OR (status = $4 AND t.queue_name = $1
+                   AND (now() - last_heartbeat_at) > COALESCE(t.visibility_timeout, tc.max_time_since_last_heartbeat, $2))
         -- FOR UPDATE locks the row we select here, SKIP LOCKED makes us not wait for rows other
         -- transactions locked, this is our queue right there.
         FOR UPDATE OF t SKIP LOCKED
@@ -167,7 +237,8 @@ pub(crate) async fn pick_task(
     SET status = $4,
         picked_up_at = now() AT TIME ZONE 'UTC',
         last_heartbeat_at = now() AT TIME ZONE 'UTC',
-        attempt = task.attempt + 1
+        attempt = task.attempt + 1,
+        visibility_timeout = $5
     FROM updated_task
     WHERE task.task_id = updated_task.task_id
     RETURNING
@@ -188,6 +259,7 @@ pub(crate) async fn pick_task(
         max_time_since_last_heartbeat,
         TaskStatus::Scheduled as _,
         TaskStatus::Running as _,
+        visibility_timeout,
     )
     .fetch_optional(pool)
     .await
@@ -203,6 +275,7 @@ pub(crate) async fn pick_task(
                 warehouse_id: task.warehouse_id.into(),
                 entity_id: match task.entity_type {
                     EntityType::Tabular => EntityId::Tabular(task.entity_id),
+                    EntityType::Warehouse => EntityId::Warehouse(task.entity_id),
                 },
                 parent_task_id: task.parent_task_id.map(TaskId::from),
                 schedule_for: Some(task.scheduled_for),
@@ -265,16 +338,25 @@ pub(crate) async fn record_success(
     Ok(())
 }
 
+/// Maximum delay between two attempts of a failed task.
+const MAX_RETRY_BACKOFF: chrono::Duration = chrono::Duration::days(1);
+
+/// Records a failed attempt. Tasks are retried until the `max_attempts` of the queue
+/// (falling back to `max_retries`) are exhausted, then moved to the dead-letter queue.
 pub(crate) async fn record_failure(
     conn: &mut PgConnection,
     task_id: TaskId,
     max_retries: i32,
     details: &str,
 ) -> Result<(), IcebergErrorResponse> {
-    let should_fail = sqlx::query_scalar!(
+    let policy = sqlx::query!(
         r#"
-        SELECT attempt >= $1 as "should_fail!"
-        FROM task
+        SELECT t.attempt >= COALESCE(tc.max_attempts, $1) as "should_fail!",
+               tc.retry_backoff
+        FROM task t
+        LEFT JOIN task_config tc
+            ON tc.queue_name = t.queue_name
+                   AND tc.warehouse_id = t.warehouse_id
         WHERE task_id = $2
         "#,
         max_retries,
@@ -282,15 +364,22 @@ pub(crate) async fn record_failure(
     )
     .fetch_optional(&mut *conn)
     .await
-    .map_err(|e| e.into_error_model("failed to check if task should fail"))?
-    .unwrap_or(false);
+    .map_err(|e| e.into_error_model("failed to check if task should fail"))?;
+    let Some(policy) = policy else {
+        return Ok(());
+    };
 
-    if should_fail {
+    if policy.should_fail {
         sqlx::query!(
             r#"
             WITH history as (
-                INSERT INTO task_log(task_id, warehouse_id, queue_name, task_data, status, entity_id, entity_type, attempt, started_at, duration)
-                SELECT task_id, warehouse_id, queue_name, task_data, $2, entity_id, entity_type, attempt, picked_up_at, now() - picked_up_at
+                INSERT INTO task_log(task_id, warehouse_id, queue_name, task_data, status, entity_id, entity_type, message, attempt, started_at, duration)
+                SELECT task_id, warehouse_id, queue_name, task_data, $2, entity_id, entity_type, $3, attempt, picked_up_at, now() - picked_up_at
+                FROM task WHERE task_id = $1
+            ),
+            dead_letter as (
+                INSERT INTO task_dead_letter(task_id, warehouse_id, queue_name, task_data, entity_id, entity_type, parent_task_id, attempt, last_error, task_created_at)
+                SELECT task_id, warehouse_id, queue_name, task_data, entity_id, entity_type, parent_task_id, attempt, $3, created_at
                 FROM task WHERE task_id = $1
             )
             DELETE FROM task
@@ -298,11 +387,13 @@ pub(crate) async fn record_failure(
             "#,
             *task_id,
             TaskOutcome::Failed as _,
+            details,
         )
             .execute(conn)
             .await
-            .map_err(|e| e.into_error_model("failed to log and delete failed task"))?;
+            .map_err(|e| e.into_error_model("failed to move failed task to dead-letter queue"))?;
     } else {
+        let max_retry_backoff = to_pg_interval(MAX_RETRY_BACKOFF)?;
         sqlx::query!(
             r#"
             WITH task_log as (
@@ -311,13 +402,20 @@ pub(crate) async fn record_failure(
                 FROM task WHERE task_id = $1
             )
             UPDATE task
-            SET status = $3
+            SET status = $3,
+                -- Exponential backoff: the delay doubles with every attempt
+                scheduled_for = CASE
+                    WHEN $5::interval IS NULL THEN scheduled_for
+                    ELSE now() + LEAST($5::interval * power(2, GREATEST(attempt - 1, 0)), $6::interval)
+                END
             WHERE task_id = $1
             "#,
             *task_id,
             details,
             TaskStatus::Scheduled as _,
             TaskOutcome::Failed as _,
+            policy.retry_backoff,
+            max_retry_backoff,
         )
             .execute(conn)
             .await
@@ -334,7 +432,7 @@ pub(crate) async fn get_task_queue_config(
 ) -> crate::api::Result<Option<GetTaskQueueConfigResponse>> {
     let result = sqlx::query!(
         r#"
-        SELECT config, max_time_since_last_heartbeat
+        SELECT config, max_time_since_last_heartbeat, max_attempts, retry_backoff
         FROM task_config
         WHERE warehouse_id = $1 AND queue_name = $2
         "#,
@@ -358,6 +456,8 @@ pub(crate) async fn get_task_queue_config(
         max_seconds_since_last_heartbeat: result
             .max_time_since_last_heartbeat
             .map(|x| x.microseconds / 1_000_000),
+        max_attempts: result.max_attempts,
+        retry_backoff_seconds: result.retry_backoff.map(|x| x.microseconds / 1_000_000),
     }))
 }
 
@@ -384,17 +484,26 @@ pub(crate) async fn set_task_queue_config(
         } else {
             None
         };
+    let retry_backoff = config
+        .retry_backoff_seconds
+        .map(|seconds| to_pg_interval(chrono::Duration::seconds(seconds)))
+        .transpose()?;
     sqlx::query!(
         r#"
-        INSERT INTO task_config (queue_name, warehouse_id, config, max_time_since_last_heartbeat)
-        VALUES ($1, $2, $3, $4)
+        INSERT INTO task_config (queue_name, warehouse_id, config, max_time_since_last_heartbeat, max_attempts, retry_backoff)
+        VALUES ($1, $2, $3, $4, $5, $6)
         ON CONFLICT (queue_name, warehouse_id) DO UPDATE
-        SET config = $3, max_time_since_last_heartbeat = COALESCE($4, task_config.max_time_since_last_heartbeat )
+        SET config = $3,
+            max_time_since_last_heartbeat = COALESCE($4, task_config.max_time_since_last_heartbeat ),
+            max_attempts = COALESCE($5, task_config.max_attempts),
+            retry_backoff = COALESCE($6, task_config.retry_backoff)
         "#,
         queue_name,
         *warehouse_id,
         serialized,
-        max_time_since_last_heartbeat
+        max_time_since_last_heartbeat,
+        config.max_attempts,
+        retry_backoff,
     )
     .execute(transaction)
     .await
@@ -451,10 +560,173 @@ pub(crate) async fn check_task(
     }))
 }
 
+/// Locks the task and returns whether it is still leased with the given `attempt`.
+/// A lease is lost once the task finished, was rescheduled or picked up again.
+pub(crate) async fn is_task_lease_valid(
+    transaction: &mut PgConnection,
+    task_id: TaskId,
+    attempt: i32,
+) -> crate::api::Result<bool> {
+    Ok(sqlx::query_scalar!(
+        r#"SELECT task_id FROM task
+        WHERE task_id = $1 AND attempt = $2 AND status != $3
+        FOR UPDATE"#,
+        *task_id,
+        attempt,
+        TaskStatus::Scheduled as _,
+    )
+    .fetch_optional(transaction)
+    .await
+    .map_err(|e| {
+        tracing::error!(?e, "Failed to check task lease");
+        e.into_error_model(format!("Failed to check lease of task {task_id}"))
+    })?
+    .is_some())
+}
+
 use crate::service::task_queue::{
-    EntityId, TaskCheckState, TaskId, TaskInput, TaskMetadata, TaskOutcome,
+    EntityId, TaskCheckState, TaskId, TaskInfo, TaskInput, TaskLogEntry, TaskMetadata, TaskOutcome,
+    TaskQueueStats,
 };
 
+pub(crate) async fn get_entity_task<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseId,
+    entity_id: EntityId,
+    queue_name: &str,
+    connection: E,
+) -> crate::api::Result<Option<TaskInfo>> {
+    Ok(sqlx::query!(
+        r#"SELECT task_id, status as "status: TaskStatus", scheduled_for, picked_up_at, attempt
+        FROM task
+        WHERE warehouse_id = $1 AND entity_type = $2 AND entity_id = $3 AND queue_name = $4"#,
+        *warehouse_id,
+        EntityType::from(entity_id) as _,
+        entity_id.to_uuid(),
+        queue_name,
+    )
+    .fetch_optional(connection)
+    .await
+    .map_err(|e| e.into_error_model(format!("Failed to get task of {queue_name}")))?
+    .map(|row| TaskInfo {
+        task_id: row.task_id,
+        status: row.status,
+        scheduled_for: row.scheduled_for,
+        picked_up_at: row.picked_up_at,
+        attempt: row.attempt,
+    }))
+}
+
+pub(crate) async fn list_entity_task_log<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseId,
+    entity_id: EntityId,
+    queue_name: &str,
+    limit: i64,
+    connection: E,
+) -> crate::api::Result<Vec<TaskLogEntry>> {
+    Ok(sqlx::query!(
+        r#"SELECT task_id,
+                  attempt,
+                  status as "status: TaskOutcome",
+                  started_at,
+                  (extract(epoch from duration) * 1000)::bigint as duration_ms,
+                  message,
+                  created_at
+        FROM task_log
+        WHERE warehouse_id = $1 AND entity_type = $2 AND entity_id = $3 AND queue_name = $4
+        ORDER BY created_at DESC, attempt DESC
+        LIMIT $5"#,
+        *warehouse_id,
+        EntityType::from(entity_id) as _,
+        entity_id.to_uuid(),
+        queue_name,
+        limit,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model(format!("Failed to list task log of {queue_name}")))?
+    .into_iter()
+    .map(|row| TaskLogEntry {
+        task_id: row.task_id.into(),
+        attempt: row.attempt,
+        status: row.status,
+        started_at: row.started_at,
+        duration_ms: row.duration_ms,
+        message: row.message,
+        created_at: row.created_at,
+    })
+    .collect())
+}
+
+/// Aggregates the statistics of all queues that have tasks, finished attempts
+/// within `window` or dead-lettered tasks.
+pub(crate) async fn get_task_queue_stats<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    window: chrono::Duration,
+    connection: E,
+) -> crate::api::Result<Vec<TaskQueueStats>> {
+    Ok(sqlx::query!(
+        r#"WITH tasks AS (
+            SELECT queue_name,
+                   count(*) FILTER (WHERE status = 'scheduled') as scheduled,
+                   count(*) FILTER (WHERE status = 'scheduled' AND scheduled_for <= now()) as due,
+                   count(*) FILTER (WHERE status != 'scheduled') as in_flight,
+                   min(scheduled_for) FILTER (WHERE status = 'scheduled' AND scheduled_for <= now()) as oldest_due_at
+            FROM task
+            GROUP BY queue_name
+        ), finished AS (
+            SELECT queue_name,
+                   count(*) FILTER (WHERE status = 'failed') as failed,
+                   count(*) FILTER (WHERE status = 'success') as succeeded
+            FROM task_log
+            WHERE created_at > now() - $1::interval
+            GROUP BY queue_name
+        ), dead_lettered AS (
+            SELECT queue_name, count(*) as dead_lettered
+            FROM task_dead_letter
+            GROUP BY queue_name
+        )
+        SELECT COALESCE(t.queue_name, f.queue_name, d.queue_name) as "queue_name!",
+               COALESCE(t.scheduled, 0) as "scheduled!",
+               COALESCE(t.due, 0) as "due!",
+               COALESCE(t.in_flight, 0) as "in_flight!",
+               t.oldest_due_at,
+               COALESCE(f.failed, 0) as "failed!",
+               COALESCE(f.succeeded, 0) as "succeeded!",
+               COALESCE(d.dead_lettered, 0) as "dead_lettered!"
+        FROM tasks t
+        FULL OUTER JOIN finished f ON f.queue_name = t.queue_name
+        FULL OUTER JOIN dead_lettered d ON d.queue_name = COALESCE(t.queue_name, f.queue_name)
+        ORDER BY 1"#,
+        to_pg_interval(window)?,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Failed to get task queue statistics"))?
+    .into_iter()
+    .map(|row| TaskQueueStats {
+        queue_name: row.queue_name,
+        scheduled: row.scheduled,
+        due: row.due,
+        in_flight: row.in_flight,
+        oldest_due_at: row.oldest_due_at,
+        failed: row.failed,
+        succeeded: row.succeeded,
+        dead_lettered: row.dead_lettered,
+    })
+    .collect())
+}
+
 /// Cancel pending tasks for a warehouse
 /// If `task_ids` are provided in `filter` which are not pending, they are ignored
 pub(crate) async fn cancel_tasks(
@@ -562,7 +834,7 @@ pub(crate) async fn cancel_tasks(
 }
 
 #[cfg(test)]
-mod test {
+pub(crate) mod test {
     use chrono::{DateTime, Utc};
     use sqlx::PgPool;
     use uuid::Uuid;
@@ -579,7 +851,7 @@ mod test {
         WarehouseId,
     };
 
-    async fn queue_task(
+    pub(crate) async fn queue_task(
         conn: &mut PgConnection,
         queue_name: &'static str,
         parent_task_id: Option<TaskId>,
@@ -1018,6 +1290,93 @@ mod test {
         assert_eq!(&task.queue_name, "test");
     }
 
+    #[sqlx::test]
+    async fn test_leased_tasks_use_visibility_timeout(pool: PgPool) {
+        let mut conn = pool.acquire().await.unwrap();
+        let warehouse_id = setup(pool.clone()).await;
+        let id = queue_task(
+            &mut conn,
+            "test",
+            None,
+            EntityId::Tabular(Uuid::now_v7()),
+            warehouse_id,
+            None,
+            None,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        let task = lease_task(
+            &pool,
+            "test",
+            chrono::Duration::hours(1),
+            Some(chrono::Duration::milliseconds(500)),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(task.task_id, id);
+        assert_eq!(task.attempt, 1);
+
+        // Other queues must not pick up the stale task
+        tokio::time::sleep(std::time::Duration::from_millis(600)).await;
+        assert!(pick_task(&pool, "other", chrono::Duration::hours(1))
+            .await
+            .unwrap()
+            .is_none());
+
+        let task = pick_task(&pool, "test", chrono::Duration::hours(1))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(task.task_id, id);
+        assert_eq!(task.attempt, 2);
+
+        // The built-in worker does not set a visibility timeout, the queue default applies again
+        tokio::time::sleep(std::time::Duration::from_millis(600)).await;
+        assert!(pick_task(&pool, "test", chrono::Duration::hours(1))
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[sqlx::test]
+    async fn test_task_lease_is_lost_after_reschedule(pool: PgPool) {
+        let mut conn = pool.acquire().await.unwrap();
+        let warehouse_id = setup(pool.clone()).await;
+        let id = queue_task(
+            &mut conn,
+            "test",
+            None,
+            EntityId::Tabular(Uuid::now_v7()),
+            warehouse_id,
+            None,
+            None,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        assert!(!is_task_lease_valid(&mut conn, id, 1).await.unwrap());
+
+        let task = lease_task(&pool, "test", chrono::Duration::hours(1), None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(is_task_lease_valid(&mut conn, id, task.attempt)
+            .await
+            .unwrap());
+        assert!(!is_task_lease_valid(&mut conn, id, task.attempt + 1)
+            .await
+            .unwrap());
+
+        record_failure(&mut conn, id, 5, "failed").await.unwrap();
+        assert!(!is_task_lease_valid(&mut conn, id, task.attempt)
+            .await
+            .unwrap());
+    }
+
     #[sqlx::test]
     async fn test_multiple_tasks(pool: PgPool) {
         let mut conn = pool.acquire().await.unwrap();
@@ -1316,6 +1675,8 @@ mod test {
         let config = SetTaskQueueConfigRequest {
             queue_config: QueueConfig(serde_json::json!({"max_attempts": 5})),
             max_seconds_since_last_heartbeat: Some(3600),
+            max_attempts: None,
+            retry_backoff_seconds: None,
         };
 
         set_task_queue_config(&mut conn, queue_name, warehouse_id, config)
@@ -1344,6 +1705,8 @@ mod test {
         let config = SetTaskQueueConfigRequest {
             queue_config: QueueConfig(serde_json::json!({"max_attempts": 5})),
             max_seconds_since_last_heartbeat: Some(3600),
+            max_attempts: None,
+            retry_backoff_seconds: None,
         };
 
         set_task_queue_config(&mut conn, queue_name, warehouse_id, config)
@@ -1395,4 +1758,207 @@ mod test {
         assert_eq!(task.config, None);
         assert_eq!(task.state, other_payload);
     }
+
+    #[sqlx::test]
+    async fn test_entity_task_and_log(pool: PgPool) {
+        let mut conn = pool.acquire().await.unwrap();
+        let warehouse_id = setup(pool.clone()).await;
+        let entity_id = EntityId::Tabular(Uuid::now_v7());
+
+        assert!(get_entity_task(warehouse_id, entity_id, "test", &pool)
+            .await
+            .unwrap()
+            .is_none());
+
+        let id = queue_task(&mut conn, "test", None, entity_id, warehouse_id, None, None)
+            .await
+            .unwrap()
+            .unwrap();
+        let info = get_entity_task(warehouse_id, entity_id, "test", &pool)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(info.task_id, *id);
+        assert_eq!(info.status, TaskStatus::Scheduled);
+        assert_eq!(info.attempt, 0);
+        assert!(info.picked_up_at.is_none());
+        // Other queues of the same entity are not returned
+        assert!(get_entity_task(warehouse_id, entity_id, "other", &pool)
+            .await
+            .unwrap()
+            .is_none());
+
+        pick_task(&pool, "test", DEFAULT_MAX_TIME_SINCE_LAST_HEARTBEAT)
+            .await
+            .unwrap()
+            .unwrap();
+        record_failure(&mut conn, id, 5, "first failed")
+            .await
+            .unwrap();
+        pick_task(&pool, "test", DEFAULT_MAX_TIME_SINCE_LAST_HEARTBEAT)
+            .await
+            .unwrap()
+            .unwrap();
+        record_success(id, &mut conn, Some("done")).await.unwrap();
+
+        assert!(get_entity_task(warehouse_id, entity_id, "test", &pool)
+            .await
+            .unwrap()
+            .is_none());
+        let log = list_entity_task_log(warehouse_id, entity_id, "test", 10, &pool)
+            .await
+            .unwrap();
+        assert_eq!(log.len(), 2);
+        assert_eq!(log[0].task_id, id);
+        assert_eq!(log[0].attempt, 2);
+        assert_eq!(log[0].status, TaskOutcome::Success);
+        assert_eq!(log[0].message.as_deref(), Some("done"));
+        assert!(log[0].duration_ms.is_some());
+        assert_eq!(log[1].attempt, 1);
+        assert_eq!(log[1].status, TaskOutcome::Failed);
+        assert_eq!(log[1].message.as_deref(), Some("first failed"));
+
+        let log = list_entity_task_log(warehouse_id, entity_id, "test", 1, &pool)
+            .await
+            .unwrap();
+        assert_eq!(log.len(), 1);
+        assert_eq!(log[0].attempt, 2);
+    }
+
+    #[sqlx::test]
+    async fn test_queue_or_reschedule_task(pool: PgPool) {
+        let mut conn = pool.acquire().await.unwrap();
+        let warehouse_id = setup(pool.clone()).await;
+        let entity_id = EntityId::Tabular(Uuid::now_v7());
+        let task = |schedule_for: Option<DateTime<Utc>>, payload: serde_json::Value| TaskInput {
+            task_metadata: TaskMetadata {
+                warehouse_id,
+                parent_task_id: None,
+                entity_id,
+                schedule_for,
+            },
+            payload,
+        };
+
+        let later = Utc::now() + chrono::Duration::hours(1);
+        let id = queue_or_reschedule_task(
+            &mut conn,
+            "test",
+            task(Some(later), serde_json::json!({"run": 1})),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert!(
+            pick_task(&pool, "test", DEFAULT_MAX_TIME_SINCE_LAST_HEARTBEAT)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        // A scheduled task is moved and keeps its id
+        let rescheduled =
+            queue_or_reschedule_task(&mut conn, "test", task(None, serde_json::json!({"run": 2})))
+                .await
+                .unwrap()
+                .unwrap();
+        assert_eq!(rescheduled, id);
+        let picked = pick_task(&pool, "test", DEFAULT_MAX_TIME_SINCE_LAST_HEARTBEAT)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(picked.task_id, id);
+        assert_eq!(picked.state, serde_json::json!({"run": 2}));
+
+        // A running task is not touched
+        assert!(queue_or_reschedule_task(
+            &mut conn,
+            "test",
+            task(Some(later), serde_json::json!({"run": 3}))
+        )
+        .await
+        .unwrap()
+        .is_none());
+        let info = get_entity_task(warehouse_id, entity_id, "test", &pool)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(info.status, TaskStatus::Running);
+    }
+
+    #[sqlx::test]
+    async fn test_task_queue_stats(pool: PgPool) {
+        let mut conn = pool.acquire().await.unwrap();
+        let warehouse_id = setup(pool.clone()).await;
+        let window = chrono::Duration::hours(1);
+
+        assert!(get_task_queue_stats(window, &pool)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let later = Utc::now() + chrono::Duration::hours(1);
+        for schedule_for in [None, None, Some(later)] {
+            queue_task(
+                &mut conn,
+                "test",
+                None,
+                EntityId::Tabular(Uuid::now_v7()),
+                warehouse_id,
+                schedule_for,
+                None,
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        }
+        let failed = pick_task(&pool, "test", DEFAULT_MAX_TIME_SINCE_LAST_HEARTBEAT)
+            .await
+            .unwrap()
+            .unwrap();
+        record_failure(&mut conn, failed.task_id, 5, "failed")
+            .await
+            .unwrap();
+        let succeeded = pick_task(&pool, "test", DEFAULT_MAX_TIME_SINCE_LAST_HEARTBEAT)
+            .await
+            .unwrap()
+            .unwrap();
+        record_success(succeeded.task_id, &mut conn, None)
+            .await
+            .unwrap();
+        // The retried task is running again
+        pick_task(&pool, "test", DEFAULT_MAX_TIME_SINCE_LAST_HEARTBEAT)
+            .await
+            .unwrap()
+            .unwrap();
+        queue_task(
+            &mut conn,
+            "other",
+            None,
+            EntityId::Tabular(Uuid::now_v7()),
+            warehouse_id,
+            None,
+            None,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        let stats = get_task_queue_stats(window, &pool).await.unwrap();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].queue_name, "other");
+        assert_eq!(stats[0].scheduled, 1);
+        assert_eq!(stats[0].due, 1);
+        assert!(stats[0].oldest_due_at.is_some());
+
+        let test = &stats[1];
+        assert_eq!(test.queue_name, "test");
+        assert_eq!(test.scheduled, 1);
+        assert_eq!(test.due, 0);
+        assert!(test.oldest_due_at.is_none());
+        assert_eq!(test.in_flight, 1);
+        assert_eq!(test.failed, 1);
+        assert_eq!(test.succeeded, 1);
+        assert_eq!(test.dead_lettered, 0);
+    }
 }
@@ -0,0 +1,452 @@
+use chrono::{DateTime, Utc};
+use iceberg_ext::catalog::rest::ErrorModel;
+use uuid::Uuid;
+
+use crate::{
+    api::{
+        iceberg::v1::{PaginationQuery, MAX_PAGE_SIZE},
+        management::v1::task_schedule::ListTaskSchedulesResponse,
+    },
+    implementations::postgres::{
+        dbutils::DBErrorHandler,
+        pagination::{PaginateToken, V2PaginateToken},
+    },
+    service::{
+        task_queue::{
+            schedule::{TaskSchedule, TaskScheduleInput},
+            TaskId,
+        },
+        Result,
+    },
+    WarehouseId,
+};
+
+#[derive(sqlx::FromRow, Debug)]
+struct TaskScheduleRow {
+    schedule_id: Uuid,
+    warehouse_id: Uuid,
+    queue_name: String,
+    entity_id: Uuid,
+    cron: String,
+    jitter_seconds: i32,
+    payload: serde_json::Value,
+    paused: bool,
+    next_run_at: DateTime<Utc>,
+    last_fired_at: Option<DateTime<Utc>>,
+    last_task_id: Option<Uuid>,
+    last_fire_skipped: bool,
+    created_at: DateTime<Utc>,
+    updated_at: Option<DateTime<Utc>>,
+}
+
+impl From<TaskScheduleRow> for TaskSchedule {
+    fn from(row: TaskScheduleRow) -> Self {
+        Self {
+            schedule_id: row.schedule_id,
+            warehouse_id: row.warehouse_id.into(),
+            queue_name: row.queue_name,
+            tabular_id: row.entity_id,
+            cron: row.cron,
+            jitter_seconds: row.jitter_seconds,
+            payload: row.payload,
+            paused: row.paused,
+            next_run_at: row.next_run_at,
+            last_fired_at: row.last_fired_at,
+            last_task_id: row.last_task_id,
+            last_fire_skipped: row.last_fire_skipped,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+pub(crate) async fn create_task_schedule<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    schedule: TaskScheduleInput,
+    connection: E,
+) -> Result<Option<TaskSchedule>> {
+    let TaskScheduleInput {
+        schedule_id,
+        warehouse_id,
+        queue_name,
+        tabular_id,
+        cron,
+        jitter_seconds,
+        payload,
+        paused,
+        next_run_at,
+    } = schedule;
+
+    let row = sqlx::query_as!(
+        TaskScheduleRow,
+        r#"
+        INSERT INTO task_schedule (schedule_id, warehouse_id, queue_name, entity_type, entity_id, cron, jitter_seconds, payload, paused, next_run_at)
+        SELECT $1, n.warehouse_id, $3, 'tabular', t.tabular_id, $5, $6, $7, $8, $9
+        FROM tabular t
+        JOIN namespace n ON t.namespace_id = n.namespace_id
+        WHERE n.warehouse_id = $2 AND t.tabular_id = $4 AND t.deleted_at IS NULL
+        RETURNING schedule_id, warehouse_id, queue_name, entity_id, cron, jitter_seconds, payload, paused,
+            next_run_at, last_fired_at, last_task_id, last_fire_skipped, created_at, updated_at
+        "#,
+        schedule_id,
+        *warehouse_id,
+        queue_name,
+        tabular_id,
+        cron,
+        jitter_seconds,
+        payload,
+        paused,
+        next_run_at,
+    )
+    .fetch_optional(connection)
+    .await
+    .map_err(|e| match e {
+        sqlx::Error::Database(db_error) if db_error.is_unique_violation() => {
+            ErrorModel::conflict(
+                format!(
+                    "A schedule for tabular {tabular_id} in queue '{queue_name}' already exists"
+                ),
+                "TaskScheduleAlreadyExists",
+                Some(Box::new(db_error)),
+            )
+        }
+        _ => e.into_error_model("Error creating task schedule"),
+    })?;
+
+    Ok(row.map(TaskSchedule::from))
+}
+
+pub(crate) async fn get_task_schedule<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseId,
+    schedule_id: Uuid,
+    connection: E,
+) -> Result<Option<TaskSchedule>> {
+    let row = sqlx::query_as!(
+        TaskScheduleRow,
+        r#"
+        SELECT schedule_id, warehouse_id, queue_name, entity_id, cron, jitter_seconds, payload, paused,
+            next_run_at, last_fired_at, last_task_id, last_fire_skipped, created_at, updated_at
+        FROM task_schedule
+        WHERE warehouse_id = $1 AND schedule_id = $2
+        "#,
+        *warehouse_id,
+        schedule_id,
+    )
+    .fetch_optional(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching task schedule"))?;
+
+    Ok(row.map(TaskSchedule::from))
+}
+
+pub(crate) async fn list_task_schedules<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseId,
+    queue_name: Option<&str>,
+    PaginationQuery {
+        page_size,
+        page_token,
+    }: PaginationQuery,
+    connection: E,
+) -> Result<ListTaskSchedulesResponse> {
+    let page_size = page_size.map_or(MAX_PAGE_SIZE, |i| i.clamp(1, MAX_PAGE_SIZE));
+
+    let token_id: Option<Uuid> = page_token
+        .as_option()
+        .map(PaginateToken::try_from)
+        .transpose()?
+        .map(PaginateToken::into_id)
+        .transpose()?;
+
+    let schedules: Vec<TaskSchedule> = sqlx::query_as!(
+        TaskScheduleRow,
+        r#"
+        SELECT schedule_id, warehouse_id, queue_name, entity_id, cron, jitter_seconds, payload, paused,
+            next_run_at, last_fired_at, last_task_id, last_fire_skipped, created_at, updated_at
+        FROM task_schedule s
+        WHERE warehouse_id = $1
+            AND ($2::text IS NULL OR queue_name = $2)
+            --- PAGINATION
+            AND (s.schedule_id > $3 OR $3 IS NULL)
+        ORDER BY s.schedule_id ASC
+        LIMIT $4
+        "#,
+        *warehouse_id,
+        queue_name,
+        token_id,
+        page_size,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching task schedules"))?
+    .into_iter()
+    .map(TaskSchedule::from)
+    .collect();
+
+    let next_page_token = schedules
+        .last()
+        .map(|s| PaginateToken::V2(V2PaginateToken::<Uuid> { id: s.schedule_id }).to_string());
+
+    Ok(ListTaskSchedulesResponse {
+        schedules,
+        next_page_token,
+    })
+}
+
+pub(crate) async fn delete_task_schedule<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseId,
+    schedule_id: Uuid,
+    connection: E,
+) -> Result<Option<()>> {
+    let deleted = sqlx::query!(
+        r#"
+        DELETE FROM task_schedule
+        WHERE warehouse_id = $1 AND schedule_id = $2
+        RETURNING schedule_id
+        "#,
+        *warehouse_id,
+        schedule_id,
+    )
+    .fetch_optional(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error deleting task schedule"))?;
+
+    Ok(deleted.map(|_| ()))
+}
+
+pub(crate) async fn set_task_schedule_paused<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseId,
+    schedule_id: Uuid,
+    paused: bool,
+    next_run_at: Option<DateTime<Utc>>,
+    connection: E,
+) -> Result<Option<TaskSchedule>> {
+    let row = sqlx::query_as!(
+        TaskScheduleRow,
+        r#"
+        UPDATE task_schedule
+        SET paused = $3, next_run_at = coalesce($4, next_run_at)
+        WHERE warehouse_id = $1 AND schedule_id = $2
+        RETURNING schedule_id, warehouse_id, queue_name, entity_id, cron, jitter_seconds, payload, paused,
+            next_run_at, last_fired_at, last_task_id, last_fire_skipped, created_at, updated_at
+        "#,
+        *warehouse_id,
+        schedule_id,
+        paused,
+        next_run_at,
+    )
+    .fetch_optional(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error updating task schedule"))?;
+
+    Ok(row.map(TaskSchedule::from))
+}
+
+pub(crate) async fn claim_due_task_schedules<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    limit: i64,
+    connection: E,
+) -> Result<Vec<TaskSchedule>> {
+    let rows = sqlx::query_as!(
+        TaskScheduleRow,
+        r#"
+        SELECT s.schedule_id, s.warehouse_id, s.queue_name, s.entity_id, s.cron, s.jitter_seconds,
+            s.payload, s.paused, s.next_run_at, s.last_fired_at, s.last_task_id, s.last_fire_skipped,
+            s.created_at, s.updated_at
+        FROM task_schedule s
+        JOIN warehouse w ON s.warehouse_id = w.warehouse_id AND w.status = 'active'
+        WHERE NOT s.paused AND s.next_run_at <= now()
+        ORDER BY s.next_run_at
+        LIMIT $1
+        FOR UPDATE OF s SKIP LOCKED
+        "#,
+        limit,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error claiming due task schedules"))?;
+
+    Ok(rows.into_iter().map(TaskSchedule::from).collect())
+}
+
+pub(crate) async fn record_task_schedule_fire<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    schedule_id: Uuid,
+    task_id: Option<TaskId>,
+    next_run_at: DateTime<Utc>,
+    connection: E,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        UPDATE task_schedule
+        SET last_fired_at = now(),
+            last_task_id = $2,
+            last_fire_skipped = $2::uuid IS NULL,
+            next_run_at = $3
+        WHERE schedule_id = $1
+        "#,
+        schedule_id,
+        task_id.map(|id| *id),
+        next_run_at,
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error recording task schedule run"))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+    use crate::{
+        api::iceberg::v1::PageToken,
+        implementations::postgres::{
+            tabular::table::tests::initialize_table, warehouse::test::initialize_warehouse,
+            CatalogState,
+        },
+    };
+
+    fn input(
+        warehouse_id: WarehouseId,
+        tabular_id: Uuid,
+        next_run_at: DateTime<Utc>,
+    ) -> TaskScheduleInput {
+        TaskScheduleInput {
+            schedule_id: Uuid::now_v7(),
+            warehouse_id,
+            queue_name: "tabular_purge".to_string(),
+            tabular_id,
+            cron: "@daily".to_string(),
+            jitter_seconds: 0,
+            payload: serde_json::json!({}),
+            paused: false,
+            next_run_at,
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_task_schedule_lifecycle(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let table = initialize_table(warehouse_id, state.clone(), false, None, None).await;
+        let tabular_id = *table.table_id;
+
+        // Unknown tabulars are not found
+        let missing = create_task_schedule(input(warehouse_id, Uuid::now_v7(), Utc::now()), &pool)
+            .await
+            .unwrap();
+        assert!(missing.is_none());
+
+        let due = create_task_schedule(
+            input(warehouse_id, tabular_id, Utc::now() - Duration::minutes(1)),
+            &pool,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(due.tabular_id, tabular_id);
+        assert!(!due.paused);
+
+        // One schedule per tabular and queue
+        let err = create_task_schedule(input(warehouse_id, tabular_id, Utc::now()), &pool)
+            .await
+            .unwrap_err();
+        assert_eq!(err.error.code, 409);
+
+        let mut transaction = pool.begin().await.unwrap();
+        let claimed = claim_due_task_schedules(10, &mut *transaction)
+            .await
+            .unwrap();
+        assert_eq!(claimed, vec![due.clone()]);
+
+        // Claimed schedules are locked for other schedulers
+        let mut other = pool.begin().await.unwrap();
+        assert!(claim_due_task_schedules(10, &mut *other)
+            .await
+            .unwrap()
+            .is_empty());
+        other.rollback().await.unwrap();
+
+        let next_run_at = Utc::now() + Duration::days(1);
+        record_task_schedule_fire(due.schedule_id, None, next_run_at, &mut *transaction)
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+
+        let fired = get_task_schedule(warehouse_id, due.schedule_id, &pool)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(fired.last_fired_at.is_some());
+        assert!(fired.last_fire_skipped);
+        assert_eq!(fired.next_run_at.timestamp(), next_run_at.timestamp());
+
+        let paused = set_task_schedule_paused(warehouse_id, due.schedule_id, true, None, &pool)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(paused.paused);
+        assert_eq!(paused.next_run_at, fired.next_run_at);
+
+        let listed = list_task_schedules(
+            warehouse_id,
+            Some("tabular_purge"),
+            PaginationQuery {
+                page_token: PageToken::NotSpecified,
+                page_size: Some(10),
+            },
+            &pool,
+        )
+        .await
+        .unwrap();
+        assert_eq!(listed.schedules, vec![paused]);
+        let other_queue = list_task_schedules(
+            warehouse_id,
+            Some("tabular_expiration"),
+            PaginationQuery {
+                page_token: PageToken::NotSpecified,
+                page_size: Some(10),
+            },
+            &pool,
+        )
+        .await
+        .unwrap();
+        assert!(other_queue.schedules.is_empty());
+
+        assert!(delete_task_schedule(warehouse_id, due.schedule_id, &pool)
+            .await
+            .unwrap()
+            .is_some());
+        assert!(delete_task_schedule(warehouse_id, due.schedule_id, &pool)
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,139 @@
+use chrono::NaiveDate;
+
+use crate::{
+    implementations::postgres::dbutils::DBErrorHandler,
+    service::{usage::WarehouseUsageDay, Result},
+    ProjectId, WarehouseId,
+};
+
+pub(crate) async fn aggregate_warehouse_usage<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    day: NaiveDate,
+    update_entity_counts: bool,
+    connection: E,
+) -> Result<()> {
+    // Endpoint statistics are stored with the end of their hour as timestamp.
+    sqlx::query!(
+        r#"
+        INSERT INTO warehouse_usage (warehouse_id, day, api_calls, sign_requests, tables, views, snapshots)
+        SELECT
+            w.warehouse_id,
+            $1,
+            coalesce(es.api_calls, 0),
+            coalesce(es.sign_requests, 0),
+            (SELECT count(*)
+                FROM tabular t
+                INNER JOIN namespace n ON t.namespace_id = n.namespace_id
+                WHERE n.warehouse_id = w.warehouse_id
+                AND t.typ = 'table'
+                AND t.deleted_at IS NULL),
+            (SELECT count(*)
+                FROM tabular t
+                INNER JOIN namespace n ON t.namespace_id = n.namespace_id
+                WHERE n.warehouse_id = w.warehouse_id
+                AND t.typ = 'view'
+                AND t.deleted_at IS NULL),
+            (SELECT count(*)
+                FROM table_snapshot s
+                INNER JOIN tabular t ON s.table_id = t.tabular_id
+                INNER JOIN namespace n ON t.namespace_id = n.namespace_id
+                WHERE n.warehouse_id = w.warehouse_id
+                AND t.deleted_at IS NULL)
+        FROM warehouse w
+        LEFT JOIN (
+            SELECT
+                warehouse_id,
+                sum(count)::bigint AS api_calls,
+                (sum(count) FILTER (
+                    WHERE matched_path IN ('sign-s3-request-global', 'sign-s3-request-prefix', 'sign-s3-request-tabular')
+                ))::bigint AS sign_requests
+            FROM endpoint_statistics
+            WHERE timestamp > $1::date::timestamp AT TIME ZONE 'UTC'
+            AND timestamp <= ($1::date + 1)::timestamp AT TIME ZONE 'UTC'
+            GROUP BY warehouse_id
+        ) es ON es.warehouse_id = w.warehouse_id
+        ON CONFLICT (warehouse_id, day) DO UPDATE SET
+            api_calls = excluded.api_calls,
+            sign_requests = excluded.sign_requests,
+            tables = CASE WHEN $2 THEN excluded.tables ELSE warehouse_usage.tables END,
+            views = CASE WHEN $2 THEN excluded.views ELSE warehouse_usage.views END,
+            snapshots = CASE WHEN $2 THEN excluded.snapshots ELSE warehouse_usage.snapshots END
+        "#,
+        day,
+        update_entity_counts,
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error aggregating warehouse usage"))?;
+
+    Ok(())
+}
+
+pub(crate) async fn list_warehouse_usage<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    project_id: &ProjectId,
+    warehouse_id: Option<WarehouseId>,
+    start: NaiveDate,
+    end: NaiveDate,
+    connection: E,
+) -> Result<Vec<WarehouseUsageDay>> {
+    let usage = sqlx::query_as!(
+        WarehouseUsageDay,
+        r#"
+        SELECT u.warehouse_id, w.warehouse_name, u.day, u.api_calls, u.sign_requests,
+            u.tables, u.views, u.snapshots
+        FROM warehouse_usage u
+        INNER JOIN warehouse w ON u.warehouse_id = w.warehouse_id
+        WHERE w.project_id = $1
+            AND (u.warehouse_id = $2 OR $2 IS NULL)
+            AND u.day >= $3 AND u.day <= $4
+        ORDER BY u.warehouse_id, u.day
+        "#,
+        project_id,
+        warehouse_id.map(|id| *id),
+        start,
+        end,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching warehouse usage"))?;
+
+    Ok(usage)
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::Utc;
+
+    use super::*;
+    use crate::implementations::postgres::{warehouse::test::initialize_warehouse, CatalogState};
+
+    #[sqlx::test]
+    async fn test_aggregate_warehouse_usage(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let project_id = ProjectId::from(uuid::Uuid::new_v4());
+        let warehouse_id = initialize_warehouse(state, None, Some(&project_id), None, true).await;
+        let today = Utc::now().date_naive();
+
+        aggregate_warehouse_usage(today, true, &pool).await.unwrap();
+        // Aggregating again replaces the previous result
+        aggregate_warehouse_usage(today, false, &pool)
+            .await
+            .unwrap();
+
+        let usage = list_warehouse_usage(&project_id, Some(warehouse_id), today, today, &pool)
+            .await
+            .unwrap();
+        assert_eq!(usage.len(), 1);
+        assert_eq!(usage[0].warehouse_id, *warehouse_id);
+        assert_eq!(usage[0].day, today);
+        assert_eq!(usage[0].api_calls, 0);
+        assert_eq!(usage[0].tables, 0);
+    }
+}
@@ -1,13 +1,20 @@
+use sqlx::{types::Json, PgConnection};
+
 use super::dbutils::DBErrorHandler;
 use crate::{
     api::{
-        iceberg::v1::{PaginationQuery, MAX_PAGE_SIZE},
-        management::v1::user::{
-            ListUsersResponse, SearchUser, SearchUserResponse, User, UserLastUpdatedWith, UserType,
+        iceberg::v1::{PageToken, PaginationQuery, MAX_PAGE_SIZE},
+        management::v1::{
+            user::{
+                Impersonation, ListUsersResponse, MergeUserResponse, SearchUser,
+                SearchUserResponse, User, UserAttributes, UserLastUpdatedWith, UserReferenceKind,
+                UserReferenceReport, UserType,
+            },
+            ListSort,
         },
     },
-    implementations::postgres::pagination::{PaginateToken, V1PaginateToken},
-    service::{CreateOrUpdateUserResponse, Result, UserId},
+    implementations::postgres::pagination::{PaginateToken, V1PaginateToken, V3PaginateToken},
+    service::{CreateOrUpdateUserResponse, ErrorModel, Result, UserId},
 };
 
 #[derive(sqlx::Type, Debug, Clone, Copy)]
@@ -50,6 +57,9 @@ struct UserRow {
     email: Option<String>,
     last_updated_with: DbUserLastUpdatedWith,
     user_type: DbUserType,
+    active: bool,
+    last_seen_at: Option<chrono::DateTime<chrono::Utc>>,
+    attributes: Json<UserAttributes>,
     created_at: chrono::DateTime<chrono::Utc>,
     updated_at: Option<chrono::DateTime<chrono::Utc>>,
 }
@@ -64,6 +74,9 @@ impl TryFrom<UserRow> for User {
             email,
             last_updated_with,
             user_type,
+            active,
+            last_seen_at,
+            attributes: Json(attributes),
             created_at,
             updated_at,
         }: UserRow,
@@ -80,6 +93,9 @@ impl TryFrom<UserRow> for User {
                 }
                 DbUserLastUpdatedWith::UpdateEndpoint => UserLastUpdatedWith::UpdateEndpoint,
             },
+            active,
+            last_seen_at,
+            attributes,
             created_at,
             updated_at,
         })
@@ -89,6 +105,9 @@ impl TryFrom<UserRow> for User {
 pub(crate) async fn list_users<'e, 'c: 'e, E: sqlx::Executor<'c, Database = sqlx::Postgres>>(
     filter_user_id: Option<Vec<UserId>>,
     filter_name: Option<String>,
+    filter_user_type: Option<UserType>,
+    filter_inactive_for_days: Option<u32>,
+    sort: ListSort,
     PaginationQuery {
         page_token,
         page_size,
@@ -97,19 +116,25 @@ pub(crate) async fn list_users<'e, 'c: 'e, E: sqlx::Executor<'c, Database = sqlx
 ) -> Result<ListUsersResponse> {
     let page_size = page_size.map_or(MAX_PAGE_SIZE, |i| i.clamp(1, MAX_PAGE_SIZE));
     let filter_name = filter_name.unwrap_or_default();
+    let sort_name = sort.to_string();
 
     let token = page_token
         .as_option()
-        .map(PaginateToken::try_from)
+        .map(PaginateToken::<String>::try_from)
+        .transpose()?
+        .map(|token| match token {
+            // Issued before users could be sorted, when they were ordered by creation only.
+            PaginateToken::V1(V1PaginateToken { created_at, id })
+                if sort == ListSort::default() =>
+            {
+                Ok((created_at.format("%Y-%m-%dT%H:%M:%S%.6f").to_string(), id))
+            }
+            token => token.into_sorted(&sort_name),
+        })
         .transpose()?;
+    let (token_key, token_id) = token.unzip();
 
-    let (token_ts, token_id): (_, Option<&String>) = token
-        .as_ref()
-        .map(|PaginateToken::V1(V1PaginateToken { created_at, id })| (created_at, id))
-        .unzip();
-
-    let users: Vec<User> = sqlx::query_as!(
-        UserRow,
+    let rows = sqlx::query!(
         r#"
         SELECT
             id,
@@ -117,16 +142,35 @@ pub(crate) async fn list_users<'e, 'c: 'e, E: sqlx::Executor<'c, Database = sqlx
             last_updated_with as "last_updated_with: DbUserLastUpdatedWith",
             user_type as "user_type: DbUserType",
             email,
+            active,
+            last_seen_at,
+            attributes as "attributes: Json<UserAttributes>",
             created_at,
-            updated_at
-        FROM users u
-        where (deleted_at is null)
-            AND ($1 OR name ILIKE ('%' || $2 || '%'))
-            AND ($3 OR id = any($4))
-            --- PAGINATION
-            AND ((u.created_at > $5 OR $5 IS NULL) OR (u.created_at = $5 AND u.id > $6))
-        ORDER BY u.created_at, u.id ASC
-        LIMIT $7
+            updated_at,
+            sort_key as "sort_key!"
+        FROM (
+            SELECT u.*,
+                CASE $7::text
+                    WHEN 'name' THEN lower(u.name)
+                    WHEN 'updated-at' THEN to_char(coalesce(u.updated_at, u.created_at) AT TIME ZONE 'UTC', 'YYYY-MM-DD"T"HH24:MI:SS.US')
+                    ELSE to_char(u.created_at AT TIME ZONE 'UTC', 'YYYY-MM-DD"T"HH24:MI:SS.US')
+                END COLLATE "C" AS sort_key
+            FROM users u
+            WHERE (u.deleted_at is null)
+                AND ($1 OR u.name ILIKE ('%' || $2 || '%'))
+                AND ($3 OR u.id = any($4))
+                AND ($5::user_type IS NULL OR u.user_type = $5)
+                AND ($11::int IS NULL OR coalesce(u.last_seen_at, u.created_at) < now() - make_interval(days => $11))
+        ) u
+        --- PAGINATION
+        WHERE $8::text IS NULL
+            OR CASE WHEN $6 THEN (u.sort_key, u.id) < ($8, $9) ELSE (u.sort_key, u.id) > ($8, $9) END
+        ORDER BY
+            CASE WHEN $6 THEN u.sort_key END DESC,
+            CASE WHEN $6 THEN u.id END DESC,
+            u.sort_key ASC,
+            u.id ASC
+        LIMIT $10
         "#,
         filter_name.is_empty(),
         filter_name.to_string(),
@@ -136,25 +180,45 @@ pub(crate) async fn list_users<'e, 'c: 'e, E: sqlx::Executor<'c, Database = sqlx
             .into_iter()
             .map(|u| u.to_string())
             .collect::<Vec<String>>() as Vec<String>,
-        token_ts,
+        filter_user_type.map(DbUserType::from) as Option<DbUserType>,
+        sort.is_desc(),
+        sort.by.to_string(),
+        token_key,
         token_id,
         page_size,
+        filter_inactive_for_days.map(|d| i32::try_from(d).unwrap_or(i32::MAX)),
     )
     .fetch_all(connection)
     .await
-    .map_err(|e| e.into_error_model("Error fetching users".to_string()))?
-    .into_iter()
-    .map(User::try_from)
-    .collect::<Result<_>>()?;
+    .map_err(|e| e.into_error_model("Error fetching users".to_string()))?;
 
-    let next_page_token = users.last().map(|u| {
-        PaginateToken::V1(V1PaginateToken {
-            created_at: u.created_at,
-            id: u.id.clone(),
+    let next_page_token = rows.last().map(|r| {
+        PaginateToken::V3(V3PaginateToken {
+            sort: sort_name.clone(),
+            key: r.sort_key.clone(),
+            id: r.id.clone(),
         })
         .to_string()
     });
 
+    let users = rows
+        .into_iter()
+        .map(|r| {
+            User::try_from(UserRow {
+                id: r.id,
+                name: r.name,
+                email: r.email,
+                last_updated_with: r.last_updated_with,
+                user_type: r.user_type,
+                active: r.active,
+                last_seen_at: r.last_seen_at,
+                attributes: r.attributes,
+                created_at: r.created_at,
+                updated_at: r.updated_at,
+            })
+        })
+        .collect::<Result<_>>()?;
+
     Ok(ListUsersResponse {
         users,
         next_page_token,
@@ -170,7 +234,8 @@ pub(crate) async fn delete_user<'c, 'e: 'c, E: sqlx::Executor<'c, Database = sql
         UPDATE users
         SET deleted_at = now(),
             name = 'Deleted User',
-            email = null
+            email = null,
+            attributes = '{}'
         WHERE id = $1
         "#,
         id.to_string(),
@@ -186,6 +251,358 @@ pub(crate) async fn delete_user<'c, 'e: 'c, E: sqlx::Executor<'c, Database = sql
     Ok(Some(()))
 }
 
+/// Activates or deactivates the user. Deleted users cannot be changed.
+pub(crate) async fn set_user_active<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    id: UserId,
+    active: bool,
+    connection: E,
+) -> Result<Option<()>> {
+    let row = sqlx::query!(
+        r#"
+        UPDATE users
+        SET active = $2
+        WHERE id = $1 AND deleted_at IS NULL
+        "#,
+        id.to_string(),
+        active,
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error changing activation of user".to_string()))?;
+
+    if row.rows_affected() == 0 {
+        return Ok(None);
+    }
+
+    Ok(Some(()))
+}
+
+/// Returns false only for users that exist and are deactivated.
+/// Users don't need to be provisioned to access the catalog, so unknown users are active.
+pub(crate) async fn is_user_active<'e, 'c: 'e, E: sqlx::Executor<'c, Database = sqlx::Postgres>>(
+    id: &UserId,
+    connection: E,
+) -> Result<bool> {
+    let active = sqlx::query_scalar!(
+        r#"SELECT active FROM users WHERE id = $1 AND deleted_at IS NULL"#,
+        id.to_string(),
+    )
+    .fetch_optional(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error checking if user is active".to_string()))?;
+
+    Ok(active.unwrap_or(true))
+}
+
+/// Replaces all attributes of the user. Returns Ok(None) if the user does not exist.
+pub(crate) async fn set_user_attributes<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    id: &UserId,
+    attributes: &UserAttributes,
+    connection: E,
+) -> Result<Option<()>> {
+    let row = sqlx::query!(
+        r#"
+        UPDATE users
+        SET attributes = $2
+        WHERE id = $1 AND deleted_at IS NULL
+        "#,
+        id.to_string(),
+        Json(attributes) as _,
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error setting attributes of user".to_string()))?;
+
+    if row.rows_affected() == 0 {
+        return Ok(None);
+    }
+
+    Ok(Some(()))
+}
+
+/// Sets the last-seen time of the user to now, unless it was set within `min_interval`.
+/// Users that are not provisioned are ignored.
+pub(crate) async fn record_user_seen<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    id: &UserId,
+    min_interval: std::time::Duration,
+    connection: E,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        UPDATE users
+        SET last_seen_at = now()
+        WHERE id = $1
+            AND (last_seen_at IS NULL OR last_seen_at < now() - make_interval(secs => $2))
+        "#,
+        id.to_string(),
+        f64::from(u32::try_from(min_interval.as_secs()).unwrap_or(u32::MAX)),
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error recording last-seen time of user".to_string()))?;
+
+    Ok(())
+}
+
+/// A column that stores the id of a user.
+struct UserReferenceColumn {
+    kind: UserReferenceKind,
+    table: &'static str,
+    column: &'static str,
+    /// SQL prepended to the user id, for columns that store a prefixed principal.
+    prefix: &'static str,
+    /// Additional assignments applied when the user is anonymized.
+    anonymize: &'static str,
+}
+
+impl UserReferenceColumn {
+    const fn new(kind: UserReferenceKind, table: &'static str, column: &'static str) -> Self {
+        Self {
+            kind,
+            table,
+            column,
+            prefix: "",
+            anonymize: "",
+        }
+    }
+}
+
+/// All columns that reference users. Used to re-point records on hard-deletion and merge.
+const USER_REFERENCE_COLUMNS: &[UserReferenceColumn] = &[
+    UserReferenceColumn {
+        anonymize: ", reason = null",
+        ..UserReferenceColumn::new(
+            UserReferenceKind::ImpersonatedUser,
+            "user_impersonation",
+            "user_id",
+        )
+    },
+    UserReferenceColumn::new(
+        UserReferenceKind::Impersonator,
+        "user_impersonation",
+        "impersonator_id",
+    ),
+    UserReferenceColumn::new(
+        UserReferenceKind::ColumnTagSuggestionReviewer,
+        "column_tag_suggestion",
+        "reviewed_by",
+    ),
+    UserReferenceColumn::new(
+        UserReferenceKind::TablePropertyProposer,
+        "table_property_proposal",
+        "proposed_by",
+    ),
+    UserReferenceColumn::new(
+        UserReferenceKind::TablePropertyReviewer,
+        "table_property_proposal",
+        "reviewed_by",
+    ),
+    UserReferenceColumn::new(
+        UserReferenceKind::RecertificationCampaignCreator,
+        "recertification_campaign",
+        "created_by",
+    ),
+    UserReferenceColumn {
+        prefix: "'user:' || ",
+        ..UserReferenceColumn::new(
+            UserReferenceKind::RecertificationItemPrincipal,
+            "recertification_item",
+            "principal",
+        )
+    },
+    UserReferenceColumn::new(
+        UserReferenceKind::RecertificationItemDecider,
+        "recertification_item",
+        "decided_by",
+    ),
+    UserReferenceColumn::new(
+        UserReferenceKind::InvitationCreator,
+        "project_invitation",
+        "created_by",
+    ),
+    UserReferenceColumn::new(
+        UserReferenceKind::InvitationRedeemer,
+        "project_invitation",
+        "redeemed_by",
+    ),
+    UserReferenceColumn::new(
+        UserReferenceKind::CatalogImportCreator,
+        "catalog_import",
+        "created_by",
+    ),
+    UserReferenceColumn::new(
+        UserReferenceKind::S3SignAuditPrincipal,
+        "s3_sign_audit",
+        "principal",
+    ),
+    UserReferenceColumn::new(
+        UserReferenceKind::ServerEventPrincipal,
+        "server_event",
+        "principal",
+    ),
+    UserReferenceColumn::new(
+        UserReferenceKind::TableActivityPrincipal,
+        "table_activity",
+        "principal",
+    ),
+];
+
+/// Re-points every [`USER_REFERENCE_COLUMNS`] entry from user `from` to user `to`.
+async fn repoint_user_references(
+    from: &str,
+    to: &str,
+    anonymize: bool,
+    conn: &mut PgConnection,
+) -> Result<Vec<UserReferenceReport>> {
+    let mut references = Vec::with_capacity(USER_REFERENCE_COLUMNS.len());
+    for UserReferenceColumn {
+        kind,
+        table,
+        column,
+        prefix,
+        anonymize: anonymize_assignments,
+    } in USER_REFERENCE_COLUMNS
+    {
+        let assignments = if anonymize {
+            *anonymize_assignments
+        } else {
+            ""
+        };
+        let result = sqlx::query(&format!(
+            "UPDATE {table} SET {column} = {prefix}$2{assignments} WHERE {column} = {prefix}$1"
+        ))
+        .bind(from)
+        .bind(to)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| e.into_error_model(format!("Error re-pointing user in {table}.{column}")))?;
+        references.push(UserReferenceReport {
+            kind: *kind,
+            count: i64::try_from(result.rows_affected()).unwrap_or(i64::MAX),
+        });
+    }
+    Ok(references)
+}
+
+/// Replaces all references to the user with `tombstone_id` and removes the user.
+///
+/// The tombstone is stored as a deleted user without name or email so that
+/// historical records keep a valid, but anonymous, principal.
+pub(crate) async fn hard_delete_user(
+    id: UserId,
+    tombstone_id: UserId,
+    conn: &mut PgConnection,
+) -> Result<Option<Vec<UserReferenceReport>>> {
+    let id = id.to_string();
+    let tombstone_id = tombstone_id.to_string();
+
+    let tombstone = sqlx::query!(
+        r#"
+        INSERT INTO users (id, name, email, user_type, last_updated_with, deleted_at)
+        SELECT $2, 'Deleted User', null, user_type, last_updated_with, now()
+        FROM users
+        WHERE id = $1
+        RETURNING id
+        "#,
+        id,
+        tombstone_id,
+    )
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| e.into_error_model("Error creating tombstone user".to_string()))?;
+    if tombstone.is_none() {
+        return Ok(None);
+    }
+
+    let references = repoint_user_references(&id, &tombstone_id, true, &mut *conn).await?;
+
+    sqlx::query!("DELETE FROM users WHERE id = $1", id)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| e.into_error_model("Error deleting user".to_string()))?;
+
+    Ok(Some(references))
+}
+
+/// Re-points all references of the source user to the target user and soft-deletes the source.
+/// The target keeps its name, email and activation. Attributes of the source are added
+/// unless the target has an attribute with the same key.
+///
+/// Returns Ok(None) if one of the users does not exist.
+pub(crate) async fn merge_user(
+    source_id: UserId,
+    target_id: UserId,
+    conn: &mut PgConnection,
+) -> Result<Option<MergeUserResponse>> {
+    let source = source_id.to_string();
+    let target = target_id.to_string();
+
+    let users = sqlx::query_scalar!(
+        r#"
+        SELECT id FROM users
+        WHERE id = ANY($1) AND deleted_at IS NULL
+        FOR UPDATE
+        "#,
+        &[source.clone(), target.clone()] as &[String],
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.into_error_model("Error locking users to merge".to_string()))?;
+    if users.len() != 2 {
+        return Ok(None);
+    }
+
+    let references = repoint_user_references(&source, &target, false, &mut *conn).await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE users t
+        SET last_seen_at = greatest(t.last_seen_at, s.last_seen_at),
+            attributes = s.attributes || t.attributes
+        FROM users s
+        WHERE t.id = $2 AND s.id = $1
+        "#,
+        source,
+        target,
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| e.into_error_model("Error merging last-seen time and attributes".to_string()))?;
+
+    delete_user(source_id, &mut *conn).await?;
+
+    let user = list_users(
+        Some(vec![target_id]),
+        None,
+        None,
+        None,
+        ListSort::default(),
+        PaginationQuery {
+            page_token: PageToken::NotSpecified,
+            page_size: Some(1),
+        },
+        &mut *conn,
+    )
+    .await?
+    .users
+    .pop()
+    .ok_or_else(|| ErrorModel::internal("Merged user not found", "MergedUserNotFound", None))?;
+
+    Ok(Some(MergeUserResponse { user, references }))
+}
+
 pub(crate) async fn create_or_update_user<
     'c,
     'e: 'c,
@@ -211,7 +628,7 @@ pub(crate) async fn create_or_update_user<
         VALUES ($1, $2, $3, $4, $5)
         ON CONFLICT (id)
         DO UPDATE SET name = $2, email = $3, last_updated_with = $4, user_type = $5, deleted_at = null
-        returning (xmax = 0) AS created, id, name, email, created_at, updated_at, last_updated_with as "last_updated_with: DbUserLastUpdatedWith", user_type as "user_type: DbUserType"
+        returning (xmax = 0) AS created, id, name, email, active, last_seen_at, attributes as "attributes: Json<UserAttributes>", created_at, updated_at, last_updated_with as "last_updated_with: DbUserLastUpdatedWith", user_type as "user_type: DbUserType"
         "#,
         id.to_string(),
         name,
@@ -229,6 +646,9 @@ pub(crate) async fn create_or_update_user<
         email: user.email,
         user_type: user.user_type,
         last_updated_with: user.last_updated_with,
+        active: user.active,
+        last_seen_at: user.last_seen_at,
+        attributes: user.attributes,
         created_at: user.created_at,
         updated_at: user.updated_at,
     };
@@ -246,7 +666,8 @@ pub(crate) async fn search_user<'e, 'c: 'e, E: sqlx::Executor<'c, Database = sql
 ) -> Result<SearchUserResponse> {
     let users = sqlx::query!(
         r#"
-        SELECT id, name, email, (name || ' ' || email) <-> $1 AS dist, user_type as "user_type: DbUserType"
+        SELECT id, name, email, (name || ' ' || email) <-> $1 AS dist, user_type as "user_type: DbUserType",
+            attributes as "attributes: Json<UserAttributes>"
         FROM users
         ORDER BY dist ASC
         LIMIT 10
@@ -263,16 +684,133 @@ pub(crate) async fn search_user<'e, 'c: 'e, E: sqlx::Executor<'c, Database = sql
         name: row.name,
         user_type: row.user_type.into(),
         email: row.email,
+        attributes: row.attributes.0,
     }))
     .collect::<Result<_>>()?;
 
     Ok(SearchUserResponse { users })
 }
 
+#[derive(sqlx::FromRow, Debug)]
+struct ImpersonationRow {
+    impersonation_id: uuid::Uuid,
+    user_id: String,
+    impersonator_id: String,
+    reason: Option<String>,
+    created_at: chrono::DateTime<chrono::Utc>,
+    expires_at: chrono::DateTime<chrono::Utc>,
+    ended_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl TryFrom<ImpersonationRow> for Impersonation {
+    type Error = crate::service::IcebergErrorResponse;
+
+    fn try_from(
+        ImpersonationRow {
+            impersonation_id,
+            user_id,
+            impersonator_id,
+            reason,
+            created_at,
+            expires_at,
+            ended_at,
+        }: ImpersonationRow,
+    ) -> Result<Self> {
+        Ok(Impersonation {
+            impersonation_id,
+            user_id: user_id.try_into()?,
+            impersonator_id: impersonator_id.try_into()?,
+            reason,
+            created_at,
+            expires_at,
+            ended_at,
+        })
+    }
+}
+
+pub(crate) async fn create_impersonation<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    impersonation: &Impersonation,
+    connection: E,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO user_impersonation (impersonation_id, user_id, impersonator_id, reason, created_at, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+        impersonation.impersonation_id,
+        impersonation.user_id.to_string(),
+        impersonation.impersonator_id.to_string(),
+        impersonation.reason,
+        impersonation.created_at,
+        impersonation.expires_at,
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error creating impersonation".to_string()))?;
+
+    Ok(())
+}
+
+pub(crate) async fn get_impersonation<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    impersonation_id: uuid::Uuid,
+    connection: E,
+) -> Result<Option<Impersonation>> {
+    sqlx::query_as!(
+        ImpersonationRow,
+        r#"
+        SELECT impersonation_id, user_id, impersonator_id, reason, created_at, expires_at, ended_at
+        FROM user_impersonation
+        WHERE impersonation_id = $1
+        "#,
+        impersonation_id,
+    )
+    .fetch_optional(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching impersonation".to_string()))?
+    .map(Impersonation::try_from)
+    .transpose()
+}
+
+pub(crate) async fn end_impersonation<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    impersonation_id: uuid::Uuid,
+    connection: E,
+) -> Result<Option<Impersonation>> {
+    sqlx::query_as!(
+        ImpersonationRow,
+        r#"
+        UPDATE user_impersonation
+        SET ended_at = now()
+        WHERE impersonation_id = $1 AND ended_at IS NULL AND expires_at > now()
+        RETURNING impersonation_id, user_id, impersonator_id, reason, created_at, expires_at, ended_at
+        "#,
+        impersonation_id,
+    )
+    .fetch_optional(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error ending impersonation".to_string()))?
+    .map(Impersonation::try_from)
+    .transpose()
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::{api::iceberg::types::PageToken, implementations::postgres::CatalogState};
+    use crate::{
+        api::management::v1::{SortBy, SortOrder},
+        implementations::postgres::CatalogState,
+    };
 
     #[sqlx::test]
     async fn test_create_or_update_user(pool: sqlx::PgPool) {
@@ -295,6 +833,9 @@ mod test {
         let users = list_users(
             None,
             None,
+            None,
+            None,
+            ListSort::default(),
             PaginationQuery {
                 page_token: PageToken::NotSpecified,
                 page_size: Some(10),
@@ -326,6 +867,9 @@ mod test {
         let users = list_users(
             None,
             None,
+            None,
+            None,
+            ListSort::default(),
             PaginationQuery {
                 page_token: PageToken::NotSpecified,
                 page_size: Some(10),
@@ -368,6 +912,70 @@ mod test {
         assert_eq!(search_result.users[0].user_type, UserType::Application);
     }
 
+    #[sqlx::test]
+    async fn test_user_attributes(pool: sqlx::PgPool) {
+        let user_id = UserId::new_unchecked("oidc", "test_user_1");
+        let attributes = UserAttributes::from([
+            ("team".to_string(), "data-platform".to_string()),
+            ("cost-center".to_string(), "4711".to_string()),
+        ]);
+        assert!(set_user_attributes(&user_id, &attributes, &pool)
+            .await
+            .unwrap()
+            .is_none());
+
+        create_or_update_user(
+            &user_id,
+            "Test User 1",
+            None,
+            UserLastUpdatedWith::CreateEndpoint,
+            UserType::Human,
+            &pool,
+        )
+        .await
+        .unwrap();
+        set_user_attributes(&user_id, &attributes, &pool)
+            .await
+            .unwrap()
+            .unwrap();
+
+        // Updating the user keeps its attributes
+        let user = create_or_update_user(
+            &user_id,
+            "Test User 1 Updated",
+            None,
+            UserLastUpdatedWith::UpdateEndpoint,
+            UserType::Human,
+            &pool,
+        )
+        .await
+        .unwrap();
+        let CreateOrUpdateUserResponse::Updated(user) = user else {
+            panic!("User should have been updated");
+        };
+        assert_eq!(user.attributes, attributes);
+
+        let search_result = search_user("Test", &pool).await.unwrap();
+        assert_eq!(search_result.users[0].attributes, attributes);
+
+        // Deleting the user removes its attributes
+        delete_user(user_id.clone(), &pool).await.unwrap();
+        let user = create_or_update_user(
+            &user_id,
+            "Test User 1",
+            None,
+            UserLastUpdatedWith::CreateEndpoint,
+            UserType::Human,
+            &pool,
+        )
+        .await
+        .unwrap();
+        let CreateOrUpdateUserResponse::Updated(user) = user else {
+            panic!("User should have been updated");
+        };
+        assert!(user.attributes.is_empty());
+    }
+
     #[sqlx::test]
     async fn test_delete_user(pool: sqlx::PgPool) {
         let state = CatalogState::from_pools(pool.clone(), pool.clone());
@@ -393,6 +1001,9 @@ mod test {
         let users = list_users(
             None,
             None,
+            None,
+            None,
+            ListSort::default(),
             PaginationQuery {
                 page_token: PageToken::NotSpecified,
                 page_size: Some(10),
@@ -412,6 +1023,130 @@ mod test {
         assert_eq!(result, None);
     }
 
+    #[sqlx::test]
+    async fn test_deactivate_user(pool: sqlx::PgPool) {
+        let user_id = UserId::new_unchecked("oidc", "test_user_1");
+        // Users that are not provisioned are active
+        assert!(is_user_active(&user_id, &pool).await.unwrap());
+        assert_eq!(
+            set_user_active(user_id.clone(), false, &pool)
+                .await
+                .unwrap(),
+            None
+        );
+
+        create_or_update_user(
+            &user_id,
+            "Test User 1",
+            None,
+            UserLastUpdatedWith::CreateEndpoint,
+            UserType::Human,
+            &pool,
+        )
+        .await
+        .unwrap();
+        assert!(is_user_active(&user_id, &pool).await.unwrap());
+
+        set_user_active(user_id.clone(), false, &pool)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!is_user_active(&user_id, &pool).await.unwrap());
+
+        // Updating the user does not re-activate it
+        let user = create_or_update_user(
+            &user_id,
+            "Test User 1 Updated",
+            None,
+            UserLastUpdatedWith::UpdateEndpoint,
+            UserType::Human,
+            &pool,
+        )
+        .await
+        .unwrap();
+        let CreateOrUpdateUserResponse::Updated(user) = user else {
+            panic!("User should have been updated");
+        };
+        assert!(!user.active);
+
+        set_user_active(user_id.clone(), true, &pool)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(is_user_active(&user_id, &pool).await.unwrap());
+    }
+
+    #[sqlx::test]
+    async fn test_last_seen_user(pool: sqlx::PgPool) {
+        let min_interval = std::time::Duration::from_secs(300);
+        for i in 0..2 {
+            create_or_update_user(
+                &UserId::new_unchecked("oidc", &format!("test_user_{i}")),
+                &format!("test user {i}"),
+                None,
+                UserLastUpdatedWith::CreateEndpoint,
+                UserType::Application,
+                &pool,
+            )
+            .await
+            .unwrap();
+        }
+        let seen_id = UserId::new_unchecked("oidc", "test_user_0");
+        let stale_id = UserId::new_unchecked("oidc", "test_user_1");
+        sqlx::query!(
+            "UPDATE users SET created_at = now() - interval '30 days' WHERE id = $1",
+            stale_id.to_string()
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        record_user_seen(&seen_id, min_interval, &pool)
+            .await
+            .unwrap();
+        // Unknown users are ignored
+        record_user_seen(
+            &UserId::new_unchecked("oidc", "unknown"),
+            min_interval,
+            &pool,
+        )
+        .await
+        .unwrap();
+
+        let list = |inactive_for_days| {
+            list_users(
+                None,
+                None,
+                None,
+                inactive_for_days,
+                ListSort::default(),
+                PaginationQuery {
+                    page_token: PageToken::NotSpecified,
+                    page_size: Some(10),
+                },
+                &pool,
+            )
+        };
+        let users = list(None).await.unwrap().users;
+        let seen = users.iter().find(|u| u.id == seen_id).unwrap();
+        let last_seen_at = seen.last_seen_at.unwrap();
+        // Being seen is not an update of the user
+        assert!(seen.updated_at.is_none());
+
+        // Rate limited
+        record_user_seen(&seen_id, min_interval, &pool)
+            .await
+            .unwrap();
+        let users = list(None).await.unwrap().users;
+        let seen = users.iter().find(|u| u.id == seen_id).unwrap();
+        assert_eq!(seen.last_seen_at, Some(last_seen_at));
+
+        let stale = list(Some(7)).await.unwrap().users;
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].id, stale_id);
+        assert!(stale[0].last_seen_at.is_none());
+    }
+
     #[sqlx::test]
     async fn test_paginate_user(pool: sqlx::PgPool) {
         let state = CatalogState::from_pools(pool.clone(), pool.clone());
@@ -433,6 +1168,9 @@ mod test {
         let users = list_users(
             None,
             None,
+            None,
+            None,
+            ListSort::default(),
             PaginationQuery {
                 page_token: PageToken::NotSpecified,
                 page_size: Some(10),
@@ -447,6 +1185,9 @@ mod test {
         let users = list_users(
             None,
             None,
+            None,
+            None,
+            ListSort::default(),
             PaginationQuery {
                 page_token: PageToken::NotSpecified,
                 page_size: Some(5),
@@ -467,6 +1208,9 @@ mod test {
         let users = list_users(
             None,
             None,
+            None,
+            None,
+            ListSort::default(),
             PaginationQuery {
                 page_token: users.next_page_token.into(),
                 page_size: Some(5),
@@ -490,6 +1234,9 @@ mod test {
         let users = list_users(
             None,
             None,
+            None,
+            None,
+            ListSort::default(),
             PaginationQuery {
                 page_token: users.next_page_token.into(),
                 page_size: Some(5),
@@ -501,4 +1248,304 @@ mod test {
         assert_eq!(users.users.len(), 0);
         assert!(users.next_page_token.is_none());
     }
+
+    #[sqlx::test]
+    async fn test_sort_and_filter_users(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        for (i, name) in ["bob", "Alice", "carol", "dave"].iter().enumerate() {
+            create_or_update_user(
+                &UserId::new_unchecked("oidc", &format!("test_user_{i}")),
+                name,
+                None,
+                UserLastUpdatedWith::CreateEndpoint,
+                if i == 3 {
+                    UserType::Application
+                } else {
+                    UserType::Human
+                },
+                &state.read_write.write_pool,
+            )
+            .await
+            .unwrap();
+        }
+        let sort = ListSort {
+            by: SortBy::Name,
+            order: SortOrder::Desc,
+        };
+
+        let mut names = vec![];
+        let mut page_token = PageToken::NotSpecified;
+        loop {
+            let users = list_users(
+                None,
+                None,
+                Some(UserType::Human),
+                None,
+                sort,
+                PaginationQuery {
+                    page_token,
+                    page_size: Some(2),
+                },
+                &state.read_write.read_pool,
+            )
+            .await
+            .unwrap();
+            if users.users.is_empty() {
+                break;
+            }
+            names.extend(users.users.into_iter().map(|u| u.name));
+            page_token = users.next_page_token.into();
+        }
+        assert_eq!(names, vec!["carol", "bob", "Alice"]);
+
+        // Tokens of one sort cannot be used with another
+        let users = list_users(
+            None,
+            None,
+            None,
+            None,
+            sort,
+            PaginationQuery {
+                page_token: PageToken::NotSpecified,
+                page_size: Some(1),
+            },
+            &state.read_write.read_pool,
+        )
+        .await
+        .unwrap();
+        let e = list_users(
+            None,
+            None,
+            None,
+            None,
+            ListSort::default(),
+            PaginationQuery {
+                page_token: users.next_page_token.into(),
+                page_size: Some(1),
+            },
+            &state.read_write.read_pool,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(e.error.code, 400);
+    }
+
+    #[sqlx::test]
+    async fn test_impersonation_lifecycle(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+
+        let user_id = UserId::new_unchecked("oidc", "impersonated_user");
+        let admin_id = UserId::new_unchecked("oidc", "admin_user");
+        create_or_update_user(
+            &user_id,
+            "Impersonated User",
+            None,
+            UserLastUpdatedWith::CreateEndpoint,
+            UserType::Human,
+            &state.read_write.write_pool,
+        )
+        .await
+        .unwrap();
+
+        let now = chrono::Utc::now();
+        let impersonation = Impersonation {
+            impersonation_id: uuid::Uuid::now_v7(),
+            user_id: user_id.clone(),
+            impersonator_id: admin_id.clone(),
+            reason: Some("Debugging".to_string()),
+            created_at: now,
+            expires_at: now + chrono::Duration::minutes(5),
+            ended_at: None,
+        };
+        create_impersonation(&impersonation, &state.read_write.write_pool)
+            .await
+            .unwrap();
+
+        let loaded = get_impersonation(impersonation.impersonation_id, &state.read_write.read_pool)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(loaded.user_id, user_id);
+        assert_eq!(loaded.impersonator_id, admin_id);
+        assert!(loaded.is_active());
+
+        let ended = end_impersonation(impersonation.impersonation_id, &state.read_write.write_pool)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!ended.is_active());
+
+        // Ending twice is not possible
+        let ended = end_impersonation(impersonation.impersonation_id, &state.read_write.write_pool)
+            .await
+            .unwrap();
+        assert!(ended.is_none());
+
+        let missing = get_impersonation(uuid::Uuid::now_v7(), &state.read_write.read_pool)
+            .await
+            .unwrap();
+        assert!(missing.is_none());
+    }
+
+    #[sqlx::test]
+    async fn test_merge_user(pool: sqlx::PgPool) {
+        let source_id = UserId::new_unchecked("oidc", "old_subject");
+        let target_id = UserId::new_unchecked("oidc", "new_subject");
+        let other_id = UserId::new_unchecked("oidc", "other_user");
+        for (user_id, name) in [
+            (&source_id, "Old User"),
+            (&target_id, "New User"),
+            (&other_id, "Other User"),
+        ] {
+            create_or_update_user(
+                user_id,
+                name,
+                None,
+                UserLastUpdatedWith::CreateEndpoint,
+                UserType::Human,
+                &pool,
+            )
+            .await
+            .unwrap();
+        }
+        record_user_seen(&source_id, std::time::Duration::ZERO, &pool)
+            .await
+            .unwrap();
+
+        let now = chrono::Utc::now();
+        let impersonation = Impersonation {
+            impersonation_id: uuid::Uuid::now_v7(),
+            user_id: other_id.clone(),
+            impersonator_id: source_id.clone(),
+            reason: Some("Debugging".to_string()),
+            created_at: now,
+            expires_at: now + chrono::Duration::minutes(5),
+            ended_at: None,
+        };
+        create_impersonation(&impersonation, &pool).await.unwrap();
+        sqlx::query!(
+            r#"
+            INSERT INTO server_event (server_event_id, event_type, instance_id, principal)
+            VALUES ($1, 'settings-changed', $1, $2)
+            "#,
+            uuid::Uuid::now_v7(),
+            source_id.to_string(),
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let mut conn = pool.acquire().await.unwrap();
+        let merged = merge_user(source_id.clone(), target_id.clone(), &mut conn)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(merged.user.id, target_id);
+        assert_eq!(merged.user.name, "New User");
+        assert!(merged.user.last_seen_at.is_some());
+        let count = |kind| {
+            merged
+                .references
+                .iter()
+                .find(|r| r.kind == kind)
+                .unwrap()
+                .count
+        };
+        assert_eq!(count(UserReferenceKind::Impersonator), 1);
+        assert_eq!(count(UserReferenceKind::ImpersonatedUser), 0);
+        assert_eq!(count(UserReferenceKind::ServerEventPrincipal), 1);
+
+        let loaded = get_impersonation(impersonation.impersonation_id, &pool)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(loaded.impersonator_id, target_id);
+        assert_eq!(loaded.user_id, other_id);
+
+        // The source user is soft-deleted and cannot be merged again
+        let users = list_users(
+            None,
+            None,
+            None,
+            None,
+            ListSort::default(),
+            PaginationQuery {
+                page_token: PageToken::NotSpecified,
+                page_size: Some(10),
+            },
+            &pool,
+        )
+        .await
+        .unwrap();
+        assert!(users.users.iter().all(|u| u.id != source_id));
+        assert!(merge_user(source_id, target_id, &mut conn)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[sqlx::test]
+    async fn test_hard_delete_user(pool: sqlx::PgPool) {
+        let user_id = UserId::new_unchecked("oidc", "gdpr_user");
+        let admin_id = UserId::new_unchecked("oidc", "admin_user");
+        create_or_update_user(
+            &user_id,
+            "GDPR User",
+            Some("gdpr@example.com"),
+            UserLastUpdatedWith::CreateEndpoint,
+            UserType::Human,
+            &pool,
+        )
+        .await
+        .unwrap();
+
+        let now = chrono::Utc::now();
+        let impersonation = Impersonation {
+            impersonation_id: uuid::Uuid::now_v7(),
+            user_id: user_id.clone(),
+            impersonator_id: admin_id.clone(),
+            reason: Some("Ticket of GDPR User".to_string()),
+            created_at: now,
+            expires_at: now + chrono::Duration::minutes(5),
+            ended_at: None,
+        };
+        create_impersonation(&impersonation, &pool).await.unwrap();
+
+        let tombstone_id = UserId::new_tombstone();
+        let mut conn = pool.acquire().await.unwrap();
+        let references = hard_delete_user(user_id.clone(), tombstone_id.clone(), &mut conn)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(references.len(), USER_REFERENCE_COLUMNS.len());
+        let count = |kind| references.iter().find(|r| r.kind == kind).unwrap().count;
+        assert_eq!(count(UserReferenceKind::ImpersonatedUser), 1);
+        assert_eq!(count(UserReferenceKind::Impersonator), 0);
+        assert_eq!(count(UserReferenceKind::ColumnTagSuggestionReviewer), 0);
+
+        let loaded = get_impersonation(impersonation.impersonation_id, &pool)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(loaded.user_id, tombstone_id);
+        assert_eq!(loaded.impersonator_id, admin_id);
+        assert!(loaded.reason.is_none());
+
+        let users = sqlx::query!("SELECT id, name, email FROM users")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert_eq!(users.len(), 1);
+        assert_eq!(users[0].id, tombstone_id.to_string());
+        assert_eq!(users[0].name, "Deleted User");
+        assert!(users[0].email.is_none());
+
+        // The user is gone for good
+        assert!(
+            hard_delete_user(user_id, UserId::new_tombstone(), &mut conn)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
 }
@@ -5,17 +5,31 @@ use sqlx::{types::Json, PgPool};
 use super::{dbutils::DBErrorHandler as _, CatalogState};
 use crate::{
     api::{
-        iceberg::v1::{PaginationQuery, MAX_PAGE_SIZE},
+        iceberg::{
+            read_only_endpoints_without, supported_endpoints_without,
+            v1::{PaginationQuery, MAX_PAGE_SIZE},
+        },
         management::v1::{
             warehouse::{TabularDeleteProfile, WarehouseStatistics, WarehouseStatisticsResponse},
             DeleteWarehouseQuery, ProtectionResponse,
         },
-        CatalogConfig, ErrorModel, Result,
+        CatalogConfig, ErrorModel, IcebergErrorResponse, Result,
     },
     implementations::postgres::pagination::{PaginateToken, V1PaginateToken},
     request_metadata::RequestMetadata,
-    service::{storage::StorageProfile, GetProjectResponse, GetWarehouseResponse, WarehouseStatus},
-    ProjectId, SecretIdent, WarehouseId,
+    service::{
+        client_compatibility::ClientWorkaround,
+        endpoint_policy::EndpointGroup,
+        event_publisher::routing::EventRoutingRule,
+        labels::Labels,
+        property_schemas::PropertySchema,
+        quotas::{ProjectQuotaKind, ProjectQuotaUsage, ProjectQuotaWarning, ProjectQuotas},
+        storage::StorageProfile,
+        warehouse_metrics::WarehouseEntityCounts,
+        warehouse_slug::{ResolvedWarehouseSlug, WarehouseSlug},
+        GetProjectResponse, GetWarehouseResponse, WarehouseStatus,
+    },
+    ProjectId, SecretIdent, WarehouseId, CONFIG,
 };
 
 pub(super) async fn get_warehouse_by_name(
@@ -88,7 +102,8 @@ pub(super) async fn get_config_for_warehouse(
             SELECT
                 storage_profile as "storage_profile: Json<StorageProfile>",
                 tabular_delete_mode as "tabular_delete_mode: DbTabularDeleteProfile",
-                tabular_expiration_seconds
+                tabular_expiration_seconds,
+                disabled_endpoint_groups as "disabled_endpoint_groups: Vec<EndpointGroup>"
             FROM warehouse
             WHERE warehouse_id = $1
             AND status = 'active'
@@ -104,494 +119,443 @@ pub(super) async fn get_config_for_warehouse(
             row.tabular_delete_mode,
             row.tabular_expiration_seconds,
         )?;
-        Ok(Some(row.storage_profile.generate_catalog_config(
+        let mut config = row.storage_profile.generate_catalog_config(
             warehouse_id,
             request_metadata,
             delete_profile,
-        )))
+        );
+        let read_only = CONFIG.federation_source(warehouse_id).is_some();
+        if read_only || !row.disabled_endpoint_groups.is_empty() {
+            let disabled = CONFIG
+                .disabled_endpoint_groups
+                .iter()
+                .chain(&row.disabled_endpoint_groups)
+                .copied()
+                .collect::<Vec<_>>();
+            config.endpoints = if read_only {
+                read_only_endpoints_without(&disabled)
+            } else {
+                supported_endpoints_without(&disabled)
+            };
+        }
+        Ok(Some(config))
     } else {
         Ok(None)
     }
 }
 
-pub(crate) async fn create_warehouse(
-    warehouse_name: String,
-    project_id: &ProjectId,
-    storage_profile: StorageProfile,
-    tabular_delete_profile: TabularDeleteProfile,
-    storage_secret_id: Option<SecretIdent>,
-    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
-) -> Result<WarehouseId> {
-    let storage_profile_ser = serde_json::to_value(storage_profile).map_err(|e| {
-        ErrorModel::internal(
-            "Error serializing storage profile",
-            "StorageProfileSerializationError",
-            Some(Box::new(e)),
-        )
-    })?;
-
-    let num_secs = tabular_delete_profile
-        .expiration_seconds()
-        .map(|dur| dur.num_seconds());
-    let prof = DbTabularDeleteProfile::from(tabular_delete_profile);
+pub(super) async fn get_warehouse_disabled_endpoint_groups(
+    warehouse_id: WarehouseId,
+    catalog_state: CatalogState,
+) -> Result<Option<Vec<EndpointGroup>>> {
+    sqlx::query_scalar!(
+        r#"
+            SELECT
+                disabled_endpoint_groups as "disabled_endpoint_groups: Vec<EndpointGroup>"
+            FROM warehouse
+            WHERE warehouse_id = $1
+            "#,
+        *warehouse_id
+    )
+    .fetch_optional(&catalog_state.read_pool())
+    .await
+    .map_err(|e| map_select_warehouse_err(e).into())
+}
 
-    let warehouse_id = sqlx::query_scalar!(
-        r#"WITH
-            whi AS (INSERT INTO warehouse (
-                                   warehouse_name,
-                                   project_id,
-                                   storage_profile,
-                                   storage_secret_id,
-                                   status,
-                                   tabular_expiration_seconds,
-                                   tabular_delete_mode)
-                                VALUES ($1, $2, $3, $4, 'active', $5, $6)
-                                RETURNING warehouse_id),
-            whs AS (INSERT INTO warehouse_statistics (number_of_views,
-                                                      number_of_tables,
-                                                      warehouse_id)
-                     VALUES (0, 0, (SELECT warehouse_id FROM whi)))
-            SELECT warehouse_id FROM whi"#,
-        warehouse_name,
-        project_id,
-        storage_profile_ser,
-        storage_secret_id.map(|id| id.into_uuid()),
-        num_secs,
-        prof as _
+pub(super) async fn get_warehouse_client_workarounds(
+    warehouse_id: WarehouseId,
+    catalog_state: CatalogState,
+) -> Result<Option<Vec<ClientWorkaround>>> {
+    sqlx::query_scalar!(
+        r#"
+            SELECT
+                client_workarounds as "client_workarounds: Vec<ClientWorkaround>"
+            FROM warehouse
+            WHERE warehouse_id = $1
+            "#,
+        *warehouse_id
     )
-    .fetch_one(&mut **transaction)
+    .fetch_optional(&catalog_state.read_pool())
     .await
-    .map_err(|e| match &e {
-        sqlx::Error::Database(db_err) => match db_err.constraint() {
-            // ToDo: Get constraint name from const
-            Some("unique_warehouse_name_in_project") => ErrorModel::conflict(
-                "Warehouse with this name already exists in the project.",
-                "WarehouseNameAlreadyExists",
-                Some(Box::new(e)),
-            ),
-            Some("warehouse_project_id_fk") => {
-                ErrorModel::not_found("Project not found", "ProjectNotFound", Some(Box::new(e)))
-            }
-            _ => e.into_error_model("Error creating Warehouse"),
-        },
-        _ => e.into_error_model("Error creating Warehouse"),
-    })?;
+    .map_err(|e| map_select_warehouse_err(e).into())
+}
 
-    Ok(warehouse_id.into())
+pub(super) async fn get_warehouse_entity_counts<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    connection: E,
+) -> Result<Vec<WarehouseEntityCounts>> {
+    Ok(sqlx::query!(
+        r#"WITH namespaces AS (
+            SELECT warehouse_id,
+                   count(*) FILTER (WHERE deleted_at IS NULL) as active,
+                   count(*) FILTER (WHERE deleted_at IS NOT NULL) as deleted
+            FROM namespace
+            GROUP BY warehouse_id
+        ), tabulars AS (
+            SELECT n.warehouse_id,
+                   count(*) FILTER (WHERE t.typ = 'table' AND t.deleted_at IS NULL) as tables,
+                   count(*) FILTER (WHERE t.typ = 'view' AND t.deleted_at IS NULL) as views,
+                   count(*) FILTER (WHERE t.deleted_at IS NOT NULL) as deleted
+            FROM tabular t
+            INNER JOIN namespace n ON n.namespace_id = t.namespace_id
+            GROUP BY n.warehouse_id
+        )
+        SELECT w.project_id,
+               w.warehouse_id,
+               COALESCE(n.active, 0) as "namespaces!",
+               COALESCE(t.tables, 0) as "tables!",
+               COALESCE(t.views, 0) as "views!",
+               COALESCE(n.deleted, 0) + COALESCE(t.deleted, 0) as "pending_soft_deletes!"
+        FROM warehouse w
+        LEFT JOIN namespaces n ON n.warehouse_id = w.warehouse_id
+        LEFT JOIN tabulars t ON t.warehouse_id = w.warehouse_id
+        WHERE w.status = 'active'
+        ORDER BY w.project_id, w.warehouse_id"#,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching warehouse entity counts"))?
+    .into_iter()
+    .map(|row| WarehouseEntityCounts {
+        project_id: ProjectId::from_db_unchecked(row.project_id),
+        warehouse_id: row.warehouse_id.into(),
+        namespaces: row.namespaces,
+        tables: row.tables,
+        views: row.views,
+        pending_soft_deletes: row.pending_soft_deletes,
+    })
+    .collect())
 }
 
-pub(crate) async fn rename_project(
-    project_id: &ProjectId,
-    new_name: &str,
-    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+pub(super) async fn set_warehouse_client_workarounds<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseId,
+    client_workarounds: &[ClientWorkaround],
+    connection: E,
 ) -> Result<()> {
     let row_count = sqlx::query!(
-        "UPDATE project
-            SET project_name = $1
-            WHERE project_id = $2",
-        new_name,
-        project_id
+        r#"
+            UPDATE warehouse
+            SET client_workarounds = $1
+            WHERE warehouse_id = $2
+            AND status = 'active'
+            "#,
+        client_workarounds as _,
+        *warehouse_id
     )
-    .execute(&mut **transaction)
+    .execute(connection)
     .await
-    .map_err(|e| e.into_error_model("Error renaming project"))?
+    .map_err(|e| e.into_error_model("Error setting client workarounds of warehouse"))?
     .rows_affected();
 
     if row_count == 0 {
-        return Err(ErrorModel::not_found("Project not found", "ProjectNotFound", None).into());
+        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
     }
 
     Ok(())
 }
 
-pub(crate) async fn create_project(
-    project_id: &ProjectId,
-    project_name: String,
-    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+pub(super) async fn set_warehouse_default_branch<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseId,
+    default_branch: Option<&str>,
+    connection: E,
 ) -> Result<()> {
-    let Some(_project_id) = sqlx::query_scalar!(
+    let row_count = sqlx::query!(
         r#"
-        INSERT INTO project (project_name, project_id)
-        VALUES ($1, $2)
-        ON CONFLICT DO NOTHING
-        RETURNING project_id
-        "#,
-        project_name,
-        project_id
+            UPDATE warehouse
+            SET default_branch = $1
+            WHERE warehouse_id = $2
+            AND status = 'active'
+            "#,
+        default_branch,
+        *warehouse_id
     )
-    .fetch_optional(&mut **transaction)
+    .execute(connection)
     .await
-    .map_err(|e| e.into_error_model("Error creating Project"))?
-    else {
-        return Err(ErrorModel::conflict(
-            "Project with this id already exists",
-            "ProjectIdAlreadyExists",
-            None,
-        )
-        .into());
-    };
+    .map_err(|e| e.into_error_model("Error setting default branch of warehouse"))?
+    .rows_affected();
+
+    if row_count == 0 {
+        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
+    }
 
     Ok(())
 }
 
-pub(crate) async fn get_project(
-    project_id: &ProjectId,
-    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
-) -> Result<Option<GetProjectResponse>> {
-    let project = sqlx::query!(
+pub(super) async fn set_warehouse_metadata_push<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseId,
+    enabled: bool,
+    connection: E,
+) -> Result<()> {
+    let row_count = sqlx::query!(
         r#"
-        SELECT
-            project_name,
-            project_id
-        FROM project
-        WHERE project_id = $1
-        "#,
-        project_id
+            UPDATE warehouse
+            SET metadata_push_enabled = $1
+            WHERE warehouse_id = $2
+            AND status = 'active'
+            "#,
+        enabled,
+        *warehouse_id
     )
-    .fetch_optional(&mut **transaction)
+    .execute(connection)
     .await
-    .map_err(|e| {
-        ErrorModel::internal(
-            "Error fetching project",
-            "ProjectFetchError",
-            Some(Box::new(e)),
-        )
-    })?;
-
-    if let Some(project) = project {
-        Ok(Some(GetProjectResponse {
-            project_id: ProjectId::from_db_unchecked(project.project_id),
-            name: project.project_name,
-        }))
-    } else {
-        Ok(None)
-    }
-}
-
-pub(crate) async fn delete_project(
-    project_id: &ProjectId,
-    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
-) -> Result<()> {
-    let row_count = sqlx::query_scalar!(r#"DELETE FROM project WHERE project_id = $1"#, project_id)
-        .execute(&mut **transaction)
-        .await
-        .map_err(|e| match &e {
-            sqlx::Error::Database(db_error) => {
-                if db_error.is_foreign_key_violation() {
-                    ErrorModel::conflict(
-                        "Project is not empty",
-                        "ProjectNotEmpty",
-                        Some(Box::new(e)),
-                    )
-                } else {
-                    e.into_error_model("Error deleting project")
-                }
-            }
-            _ => e.into_error_model("Error deleting project"),
-        })?
-        .rows_affected();
+    .map_err(|e| e.into_error_model("Error setting metadata push of warehouse"))?
+    .rows_affected();
 
     if row_count == 0 {
-        return Err(ErrorModel::not_found("Project not found", "ProjectNotFound", None).into());
+        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
     }
 
     Ok(())
 }
 
-pub(crate) async fn list_warehouses<
-    'e,
-    'c: 'e,
+pub(super) async fn get_warehouse_event_routing_rules<
+    'c,
+    'e: 'c,
     E: sqlx::Executor<'c, Database = sqlx::Postgres>,
 >(
-    project_id: &ProjectId,
-    include_status: Option<Vec<WarehouseStatus>>,
-    catalog_state: E,
-) -> Result<Vec<GetWarehouseResponse>> {
-    #[derive(sqlx::FromRow, Debug, PartialEq)]
-    struct WarehouseRecord {
-        warehouse_id: uuid::Uuid,
-        warehouse_name: String,
-        storage_profile: Json<StorageProfile>,
-        storage_secret_id: Option<uuid::Uuid>,
-        status: WarehouseStatus,
-        tabular_delete_mode: DbTabularDeleteProfile,
-        tabular_expiration_seconds: Option<i64>,
-        protected: bool,
-    }
-
-    let include_status = include_status.unwrap_or_else(|| vec![WarehouseStatus::Active]);
-    let warehouses = sqlx::query_as!(
-        WarehouseRecord,
+    warehouse_id: WarehouseId,
+    connection: E,
+) -> Result<Option<Vec<EventRoutingRule>>> {
+    let rules = sqlx::query_scalar!(
         r#"
-            SELECT 
-                warehouse_id,
-                warehouse_name,
-                storage_profile as "storage_profile: Json<StorageProfile>",
-                storage_secret_id,
-                status AS "status: WarehouseStatus",
-                tabular_delete_mode as "tabular_delete_mode: DbTabularDeleteProfile",
-                tabular_expiration_seconds,
-                protected
-            FROM warehouse
-            WHERE project_id = $1
-            AND status = ANY($2)
-            "#,
-        project_id,
-        include_status as Vec<WarehouseStatus>
+        SELECT event_routing_rules as "event_routing_rules: Json<Vec<EventRoutingRule>>"
+        FROM warehouse
+        WHERE warehouse_id = $1
+        "#,
+        *warehouse_id
     )
-    .fetch_all(catalog_state)
+    .fetch_optional(connection)
     .await
-    .map_err(|e| e.into_error_model("Error fetching warehouses"))?;
-
-    warehouses
-        .into_iter()
-        .map(|warehouse| {
-            let tabular_delete_profile = db_to_api_tabular_delete_profile(
-                warehouse.tabular_delete_mode,
-                warehouse.tabular_expiration_seconds,
-            )?;
+    .map_err(|e| e.into_error_model("Error fetching event routing rules of warehouse"))?
+    .map(|Json(rules)| rules);
 
-            Ok(GetWarehouseResponse {
-                id: warehouse.warehouse_id.into(),
-                name: warehouse.warehouse_name,
-                project_id: project_id.clone(),
-                storage_profile: warehouse.storage_profile.deref().clone(),
-                storage_secret_id: warehouse.storage_secret_id.map(std::convert::Into::into),
-                status: warehouse.status,
-                tabular_delete_profile,
-                protected: warehouse.protected,
-            })
-        })
-        .collect::<Result<Vec<_>>>()
+    Ok(rules)
 }
 
-pub(crate) async fn get_warehouse(
+pub(super) async fn set_warehouse_event_routing_rules<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
     warehouse_id: WarehouseId,
-    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
-) -> Result<Option<GetWarehouseResponse>> {
-    let warehouse = sqlx::query!(
+    rules: &[EventRoutingRule],
+    connection: E,
+) -> Result<()> {
+    let row_count = sqlx::query!(
         r#"
-        SELECT 
-            warehouse_name,
-            project_id,
-            storage_profile as "storage_profile: Json<StorageProfile>",
-            storage_secret_id,
-            status AS "status: WarehouseStatus",
-            tabular_delete_mode as "tabular_delete_mode: DbTabularDeleteProfile",
-            tabular_expiration_seconds,
-            protected
-        FROM warehouse
-        WHERE warehouse_id = $1
+        UPDATE warehouse
+        SET event_routing_rules = $1
+        WHERE warehouse_id = $2
+        AND status = 'active'
         "#,
+        Json(rules) as _,
         *warehouse_id
     )
-    .fetch_optional(&mut **transaction)
+    .execute(connection)
     .await
-    .map_err(map_select_warehouse_err)?;
+    .map_err(|e| e.into_error_model("Error setting event routing rules of warehouse"))?
+    .rows_affected();
 
-    if let Some(warehouse) = warehouse {
-        let tabular_delete_profile = db_to_api_tabular_delete_profile(
-            warehouse.tabular_delete_mode,
-            warehouse.tabular_expiration_seconds,
-        )?;
+    if row_count == 0 {
+        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
+    }
 
-        Ok(Some(GetWarehouseResponse {
-            id: warehouse_id,
-            name: warehouse.warehouse_name,
-            project_id: ProjectId::from_db_unchecked(warehouse.project_id),
-            storage_profile: warehouse.storage_profile.deref().clone(),
-            storage_secret_id: warehouse.storage_secret_id.map(std::convert::Into::into),
-            status: warehouse.status,
-            tabular_delete_profile,
-            protected: warehouse.protected,
-        }))
-    } else {
-        Ok(None)
+    Ok(())
+}
+
+pub(super) async fn set_warehouse_disabled_endpoint_groups<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseId,
+    disabled_endpoint_groups: &[EndpointGroup],
+    connection: E,
+) -> Result<()> {
+    let row_count = sqlx::query!(
+        r#"
+            UPDATE warehouse
+            SET disabled_endpoint_groups = $1
+            WHERE warehouse_id = $2
+            AND status = 'active'
+            "#,
+        disabled_endpoint_groups as _,
+        *warehouse_id
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error setting disabled endpoint groups of warehouse"))?
+    .rows_affected();
+
+    if row_count == 0 {
+        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
     }
+
+    Ok(())
 }
 
-pub(crate) async fn list_projects<'e, 'c: 'e, E: sqlx::Executor<'c, Database = sqlx::Postgres>>(
-    project_ids: Option<HashSet<ProjectId>>,
+pub(super) async fn resolve_warehouse_slug(
+    slug: &str,
+    catalog_state: CatalogState,
+) -> Result<Option<ResolvedWarehouseSlug>> {
+    let row = sqlx::query!(
+        r#"
+            SELECT s.warehouse_id, c.slug as current_slug
+            FROM warehouse_slug s
+            INNER JOIN warehouse_slug c
+                ON c.warehouse_id = s.warehouse_id AND c.is_current
+            WHERE s.slug = $1
+            "#,
+        slug
+    )
+    .fetch_optional(&catalog_state.read_pool())
+    .await
+    .map_err(|e| e.into_error_model("Error resolving warehouse slug"))?;
+
+    Ok(row.map(|row| ResolvedWarehouseSlug {
+        warehouse_id: row.warehouse_id.into(),
+        slug: row.current_slug,
+    }))
+}
+
+pub(super) async fn list_warehouse_slugs<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseId,
     connection: E,
-) -> Result<Vec<GetProjectResponse>> {
-    let return_all = project_ids.is_none();
-    let projects = sqlx::query!(
+) -> Result<Vec<WarehouseSlug>> {
+    let slugs = sqlx::query_as!(
+        WarehouseSlug,
         r#"
-        SELECT project_id, project_name FROM project WHERE project_id = ANY($1) or $2
-        "#,
-        project_ids
-            .map(|ids| ids.into_iter().map(|i| i.to_string()).collect::<Vec<_>>())
-            .unwrap_or_default() as Vec<String>,
-        return_all
+            SELECT slug, is_current, created_at
+            FROM warehouse_slug
+            WHERE warehouse_id = $1
+            ORDER BY is_current DESC, created_at DESC, slug
+            "#,
+        *warehouse_id
     )
     .fetch_all(connection)
     .await
-    .map_err(|e| e.into_error_model("Error fetching projects"))?;
+    .map_err(|e| e.into_error_model("Error listing warehouse slugs"))?;
 
-    Ok(projects
-        .into_iter()
-        .map(|project| GetProjectResponse {
-            project_id: ProjectId::from_db_unchecked(project.project_id),
-            name: project.project_name,
-        })
-        .collect())
+    Ok(slugs)
 }
 
-pub(crate) async fn delete_warehouse(
+pub(super) async fn set_warehouse_slug(
     warehouse_id: WarehouseId,
-    DeleteWarehouseQuery { force }: DeleteWarehouseQuery,
+    slug: &str,
     transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
 ) -> Result<()> {
-    let unfinished_task_counts_per_queue = sqlx::query!(
-        r#"WITH running_tasks as (SELECT task_id, queue_name, status from task WHERE warehouse_id = $1),
-                deletes as (DELETE FROM task where warehouse_id = $1)
-            SELECT COUNT(task_id) as "task_count!", queue_name FROM running_tasks GROUP BY queue_name"#,
-        *warehouse_id,
-    ).fetch_all(&mut **transaction).await.map_err(|e| e.into_error_model("Error deleting tasks for warehouse"))?;
-    if !unfinished_task_counts_per_queue.is_empty() {
-        let task_descriptions = unfinished_task_counts_per_queue
-            .iter()
-            .map(|row| format!("{} in queue '{}'", row.task_count, row.queue_name))
-            .collect::<Vec<_>>()
-            .join(", ");
-
-        return Err(ErrorModel::conflict(
-            format!(
-                "Warehouse has unfinished tasks: {task_descriptions}. Retry once they are done."
-            ),
-            "WarehouseHasUnfinishedTasks",
-            None,
-        )
-        .into());
+    let owner = sqlx::query_scalar!(
+        r#"SELECT warehouse_id FROM warehouse_slug WHERE slug = $1"#,
+        slug
+    )
+    .fetch_optional(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error checking warehouse slug"))?;
+    if owner.is_some_and(|owner| owner != *warehouse_id) {
+        return Err(slug_conflict(slug));
     }
 
-    let protected = sqlx::query_scalar!(
-        r#"WITH delete_info as (
-               SELECT protected FROM warehouse w WHERE w.warehouse_id = $1
-           ),
-           deleted as (DELETE FROM warehouse WHERE warehouse_id = $1 AND (not protected OR $2))
-           SELECT protected as "protected!" FROM delete_info"#,
+    sqlx::query!(
+        r#"
+            UPDATE warehouse_slug
+            SET is_current = false
+            WHERE warehouse_id = $1 AND is_current AND slug <> $2
+            "#,
         *warehouse_id,
-        force
+        slug
     )
-    .fetch_one(&mut **transaction)
+    .execute(&mut **transaction)
     .await
-    .map_err(|e| match &e {
-        sqlx::Error::RowNotFound => ErrorModel::not_found(
-            format!("Warehouse '{warehouse_id}' not found"),
-            "WarehouseNotFound",
-            Some(Box::new(e)),
-        ),
-        sqlx::Error::Database(db_error) => {
-            if db_error.is_foreign_key_violation() {
-                ErrorModel::conflict(
-                    "Warehouse is not empty",
-                    "WarehouseNotEmpty",
-                    Some(Box::new(e)),
-                )
-            } else {
-                e.into_error_model("Error deleting warehouse")
-            }
-        }
-        _ => e.into_error_model("Error deleting warehouse"),
-    })?;
-
-    if protected && !force {
-        return Err(
-            ErrorModel::conflict("Warehouse is protected", "WarehouseProtected", None).into(),
-        );
-    }
+    .map_err(|e| e.into_error_model("Error replacing warehouse slug"))?;
 
-    Ok(())
-}
-
-pub(crate) async fn rename_warehouse(
-    warehouse_id: WarehouseId,
-    new_name: &str,
-    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
-) -> Result<()> {
+    // A concurrent request might have claimed the slug for another warehouse
+    // in the meantime, in which case no row is affected.
     let row_count = sqlx::query!(
-        "UPDATE warehouse
-            SET warehouse_name = $1
-            WHERE warehouse_id = $2
-            AND status = 'active'",
-        new_name,
+        r#"
+            INSERT INTO warehouse_slug (slug, warehouse_id, is_current)
+            VALUES ($1, $2, true)
+            ON CONFLICT (slug) DO UPDATE SET is_current = true
+            WHERE warehouse_slug.warehouse_id = excluded.warehouse_id
+            "#,
+        slug,
         *warehouse_id
     )
     .execute(&mut **transaction)
     .await
-    .map_err(|e| e.into_error_model("Error renaming warehouse"))?
+    .map_err(|e| match e {
+        sqlx::Error::Database(db_error) if db_error.is_foreign_key_violation() => {
+            ErrorModel::not_found(
+                "Warehouse not found",
+                "WarehouseNotFound",
+                Some(Box::new(db_error)),
+            )
+        }
+        e => e.into_error_model("Error setting warehouse slug"),
+    })?
     .rows_affected();
 
     if row_count == 0 {
-        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
+        return Err(slug_conflict(slug));
     }
 
     Ok(())
 }
 
-pub(crate) async fn set_warehouse_status(
+fn slug_conflict(slug: &str) -> IcebergErrorResponse {
+    ErrorModel::conflict(
+        format!("Slug '{slug}' is already used by another warehouse"),
+        "WarehouseSlugAlreadyExists",
+        None,
+    )
+    .into()
+}
+
+pub(super) async fn delete_warehouse_slugs<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
     warehouse_id: WarehouseId,
-    status: WarehouseStatus,
-    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    connection: E,
 ) -> Result<()> {
-    let row_count = sqlx::query!(
-        "UPDATE warehouse
-            SET status = $1
-            WHERE warehouse_id = $2",
-        status as _,
+    sqlx::query!(
+        r#"DELETE FROM warehouse_slug WHERE warehouse_id = $1"#,
         *warehouse_id
     )
-    .execute(&mut **transaction)
+    .execute(connection)
     .await
-    .map_err(|e| e.into_error_model("Error setting warehouse status"))?
-    .rows_affected();
-
-    if row_count == 0 {
-        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
-    }
+    .map_err(|e| e.into_error_model("Error deleting warehouse slugs"))?;
 
     Ok(())
 }
 
-pub(crate) async fn set_warehouse_protection(
-    warehouse_id: WarehouseId,
-    protected: bool,
-    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
-) -> Result<ProtectionResponse> {
-    let row = sqlx::query!(
-        "UPDATE warehouse
-            SET protected = $1
-            WHERE warehouse_id = $2
-            returning protected, updated_at",
-        protected,
-        *warehouse_id
-    )
-    .fetch_one(&mut **transaction)
-    .await
-    .map_err(|e| {
-        if let sqlx::Error::RowNotFound = e {
-            return ErrorModel::not_found(
-                format!("Warehouse '{warehouse_id}' not found"),
-                "WarehouseNotFound",
-                Some(Box::new(e)),
-            );
-        }
-        e.into_error_model("Error setting warehouse protection")
-    })?;
-
-    Ok(ProtectionResponse {
-        protected: row.protected,
-        updated_at: row.updated_at,
-    })
-}
-
-pub(crate) async fn update_storage_profile(
-    warehouse_id: WarehouseId,
+pub(crate) async fn create_warehouse(
+    warehouse_name: String,
+    project_id: &ProjectId,
     storage_profile: StorageProfile,
+    tabular_delete_profile: TabularDeleteProfile,
     storage_secret_id: Option<SecretIdent>,
     transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
-) -> Result<()> {
+) -> Result<WarehouseId> {
     let storage_profile_ser = serde_json::to_value(storage_profile).map_err(|e| {
         ErrorModel::internal(
             "Error serializing storage profile",
@@ -600,489 +564,1840 @@ pub(crate) async fn update_storage_profile(
         )
     })?;
 
-    let row_count = sqlx::query!(
-        r#"
-            UPDATE warehouse
-            SET storage_profile = $1, storage_secret_id = $2
-            WHERE warehouse_id = $3
-            AND status = 'active'
-        "#,
+    let num_secs = tabular_delete_profile
+        .expiration_seconds()
+        .map(|dur| dur.num_seconds());
+    let prof = DbTabularDeleteProfile::from(tabular_delete_profile);
+
+    let warehouse_id = sqlx::query_scalar!(
+        r#"WITH
+            whi AS (INSERT INTO warehouse (
+                                   warehouse_id,
+                                   warehouse_name,
+                                   project_id,
+                                   storage_profile,
+                                   storage_secret_id,
+                                   status,
+                                   tabular_expiration_seconds,
+                                   tabular_delete_mode)
+                                VALUES ($7, $1, $2, $3, $4, 'active', $5, $6)
+                                RETURNING warehouse_id),
+            whs AS (INSERT INTO warehouse_statistics (number_of_views,
+                                                      number_of_tables,
+                                                      warehouse_id)
+                     VALUES (0, 0, (SELECT warehouse_id FROM whi)))
+            SELECT warehouse_id FROM whi"#,
+        warehouse_name,
+        project_id,
         storage_profile_ser,
         storage_secret_id.map(|id| id.into_uuid()),
-        *warehouse_id
+        num_secs,
+        prof as _,
+        *WarehouseId::new_random(),
+    )
+    .fetch_one(&mut **transaction)
+    .await
+    .map_err(|e| match &e {
+        sqlx::Error::Database(db_err) => match db_err.constraint() {
+            // ToDo: Get constraint name from const
+            Some("unique_warehouse_name_in_project") => ErrorModel::conflict(
+                "Warehouse with this name already exists in the project.",
+                "WarehouseNameAlreadyExists",
+                Some(Box::new(e)),
+            ),
+            Some("warehouse_project_id_fk") => {
+                ErrorModel::not_found("Project not found", "ProjectNotFound", Some(Box::new(e)))
+            }
+            _ => e.into_error_model("Error creating Warehouse"),
+        },
+        _ => e.into_error_model("Error creating Warehouse"),
+    })?;
+
+    Ok(warehouse_id.into())
+}
+
+pub(crate) async fn rename_project(
+    project_id: &ProjectId,
+    new_name: &str,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let row_count = sqlx::query!(
+        "UPDATE project
+            SET project_name = $1
+            WHERE project_id = $2",
+        new_name,
+        project_id
     )
     .execute(&mut **transaction)
     .await
-    .map_err(|e| e.into_error_model("Error updating storage profile"))?
+    .map_err(|e| e.into_error_model("Error renaming project"))?
     .rows_affected();
 
     if row_count == 0 {
-        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
+        return Err(ErrorModel::not_found("Project not found", "ProjectNotFound", None).into());
     }
 
     Ok(())
 }
 
-fn map_select_warehouse_err(e: sqlx::Error) -> ErrorModel {
-    ErrorModel::internal(
-        "Error fetching warehouse",
-        "WarehouseFetchError",
-        Some(Box::new(e)),
+pub(super) async fn get_project_quotas<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    project_id: &ProjectId,
+    connection: E,
+) -> Result<Option<ProjectQuotas>> {
+    let quotas = sqlx::query!(
+        r#"
+        SELECT max_warehouses, max_tables, max_namespace_depth, max_snapshots_per_table,
+            quota_warning_threshold_percent
+        FROM project
+        WHERE project_id = $1
+        "#,
+        project_id
     )
-}
+    .fetch_optional(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching project quotas"))?
+    .map(|row| ProjectQuotas {
+        max_warehouses: row.max_warehouses,
+        max_tables: row.max_tables,
+        max_namespace_depth: row.max_namespace_depth,
+        max_snapshots_per_table: row.max_snapshots_per_table,
+        warning_threshold_percent: row.quota_warning_threshold_percent,
+    });
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
-#[sqlx(type_name = "tabular_delete_mode", rename_all = "kebab-case")]
-enum DbTabularDeleteProfile {
-    Soft,
-    Hard,
+    Ok(quotas)
 }
 
-impl From<TabularDeleteProfile> for DbTabularDeleteProfile {
-    fn from(value: TabularDeleteProfile) -> Self {
-        match value {
-            TabularDeleteProfile::Soft { .. } => DbTabularDeleteProfile::Soft,
-            TabularDeleteProfile::Hard {} => DbTabularDeleteProfile::Hard,
-        }
+pub(super) async fn set_project_quotas<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    project_id: &ProjectId,
+    quotas: &ProjectQuotas,
+    connection: E,
+) -> Result<()> {
+    let row_count = sqlx::query!(
+        r#"
+        UPDATE project
+        SET max_warehouses = $1,
+            max_tables = $2,
+            max_namespace_depth = $3,
+            max_snapshots_per_table = $4,
+            quota_warning_threshold_percent = $5
+        WHERE project_id = $6
+        "#,
+        quotas.max_warehouses,
+        quotas.max_tables,
+        quotas.max_namespace_depth,
+        quotas.max_snapshots_per_table,
+        quotas.warning_threshold_percent,
+        project_id
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error setting project quotas"))?
+    .rows_affected();
+
+    if row_count == 0 {
+        return Err(ErrorModel::not_found("Project not found", "ProjectNotFound", None).into());
     }
+
+    Ok(())
 }
 
-/// Convert a database tabular delete profile to the API tabular delete profile
-fn db_to_api_tabular_delete_profile(
-    mode: DbTabularDeleteProfile,
-    expiration_seconds: Option<i64>,
-) -> Result<TabularDeleteProfile> {
-    match mode {
-        DbTabularDeleteProfile::Soft => {
-            let seconds = expiration_seconds.ok_or(ErrorModel::internal(
-                "Tabular expiration seconds not found",
-                "TabularExpirationSecondsNotFound",
-                None,
-            ))?;
-            Ok(TabularDeleteProfile::Soft {
-                expiration_seconds: chrono::Duration::seconds(seconds),
-            })
-        }
-        DbTabularDeleteProfile::Hard => Ok(TabularDeleteProfile::Hard {}),
-    }
+pub(super) async fn get_property_schemas<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    project_id: &ProjectId,
+    connection: E,
+) -> Result<Option<Vec<PropertySchema>>> {
+    let schemas = sqlx::query_scalar!(
+        r#"
+        SELECT property_schemas as "property_schemas: Json<Vec<PropertySchema>>"
+        FROM project
+        WHERE project_id = $1
+        "#,
+        project_id
+    )
+    .fetch_optional(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching property schemas of project"))?
+    .map(|Json(schemas)| schemas);
+
+    Ok(schemas)
 }
 
-pub(crate) async fn get_warehouse_stats(
-    conn: PgPool,
-    warehouse_ident: WarehouseId,
-    PaginationQuery {
-        page_size,
-        page_token,
-    }: PaginationQuery,
-) -> crate::api::Result<WarehouseStatisticsResponse> {
-    let page_size = page_size.map_or(MAX_PAGE_SIZE, |i| i.clamp(1, MAX_PAGE_SIZE));
+pub(super) async fn set_property_schemas<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    project_id: &ProjectId,
+    schemas: &[PropertySchema],
+    connection: E,
+) -> Result<()> {
+    let row_count = sqlx::query!(
+        r#"
+        UPDATE project
+        SET property_schemas = $1
+        WHERE project_id = $2
+        "#,
+        Json(schemas) as _,
+        project_id
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error setting property schemas of project"))?
+    .rows_affected();
 
-    let token = page_token
-        .as_option()
-        .map(PaginateToken::try_from)
-        .transpose()?;
+    if row_count == 0 {
+        return Err(ErrorModel::not_found("Project not found", "ProjectNotFound", None).into());
+    }
 
-    let (token_ts, _): (_, Option<String>) = token
-        .map(|PaginateToken::V1(V1PaginateToken { created_at, id })| (created_at, id))
-        .unzip();
+    Ok(())
+}
 
-    let stats = sqlx::query!(
+pub(super) async fn get_project_quota_usage<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    project_id: &ProjectId,
+    connection: E,
+) -> Result<ProjectQuotaUsage> {
+    let usage = sqlx::query!(
         r#"
         SELECT
-            number_of_views as "number_of_views!",
-            number_of_tables as "number_of_tables!",
-            created_at as "created_at!",
-            updated_at,
-            timestamp as "timestamp!"
-        FROM (
-            (SELECT number_of_views, number_of_tables, created_at, updated_at, timestamp
-            FROM warehouse_statistics
-            WHERE warehouse_id = $1
-            AND (timestamp < $2 OR $2 IS NULL))
+            (SELECT count(*) FROM warehouse WHERE project_id = $1) AS "warehouses!",
+            (SELECT count(*)
+                FROM tabular t
+                INNER JOIN namespace n ON t.namespace_id = n.namespace_id
+                INNER JOIN warehouse w ON n.warehouse_id = w.warehouse_id
+                WHERE w.project_id = $1
+                AND t.typ = 'table'
+                AND t.deleted_at IS NULL) AS "tables!"
+        "#,
+        project_id
+    )
+    .fetch_one(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching project quota usage"))?;
 
-            UNION ALL
+    Ok(ProjectQuotaUsage {
+        warehouses: usage.warehouses,
+        tables: usage.tables,
+    })
+}
 
-            (SELECT number_of_views, number_of_tables, created_at, updated_at, timestamp
-            FROM warehouse_statistics_history
-            WHERE warehouse_id = $1
-            AND (timestamp < $2 OR $2 IS NULL))
-        ) AS ww
-        ORDER BY timestamp DESC
-        LIMIT $3
+pub(super) async fn list_projects_with_quota_warnings<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    connection: E,
+) -> Result<Vec<ProjectId>> {
+    let project_ids = sqlx::query_scalar!(
+        r#"
+        SELECT project_id FROM project WHERE quota_warning_threshold_percent IS NOT NULL
+        UNION
+        SELECT project_id FROM project_quota_warning
+        "#
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching projects with quota warnings"))?;
+
+    Ok(project_ids
+        .into_iter()
+        .flatten()
+        .map(ProjectId::from_db_unchecked)
+        .collect())
+}
+
+pub(super) async fn list_project_quota_warnings<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    project_id: &ProjectId,
+    connection: E,
+) -> Result<Vec<ProjectQuotaWarning>> {
+    let warnings = sqlx::query_as!(
+        ProjectQuotaWarning,
+        r#"
+        SELECT quota as "quota: ProjectQuotaKind", usage, "limit", raised_at
+        FROM project_quota_warning
+        WHERE project_id = $1
+        ORDER BY quota
         "#,
-        warehouse_ident.0,
-        token_ts,
-        page_size
+        project_id
     )
-    .fetch_all(&conn)
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching project quota warnings"))?;
+
+    Ok(warnings)
+}
+
+pub(super) async fn raise_project_quota_warning<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    project_id: &ProjectId,
+    warning: &ProjectQuotaWarning,
+    connection: E,
+) -> Result<bool> {
+    let row_count = sqlx::query!(
+        r#"
+        INSERT INTO project_quota_warning (project_id, quota, usage, "limit", raised_at)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (project_id, quota) DO NOTHING
+        "#,
+        project_id,
+        warning.quota as _,
+        warning.usage,
+        warning.limit,
+        warning.raised_at,
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error raising project quota warning"))?
+    .rows_affected();
+
+    Ok(row_count > 0)
+}
+
+pub(super) async fn clear_project_quota_warning<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    project_id: &ProjectId,
+    quota: ProjectQuotaKind,
+    connection: E,
+) -> Result<bool> {
+    let row_count = sqlx::query!(
+        r#"
+        DELETE FROM project_quota_warning
+        WHERE project_id = $1 AND quota = $2
+        "#,
+        project_id,
+        quota as _,
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error clearing project quota warning"))?
+    .rows_affected();
+
+    Ok(row_count > 0)
+}
+
+pub(crate) async fn create_project(
+    project_id: &ProjectId,
+    project_name: String,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let Some(_project_id) = sqlx::query_scalar!(
+        r#"
+        INSERT INTO project (project_name, project_id)
+        VALUES ($1, $2)
+        ON CONFLICT DO NOTHING
+        RETURNING project_id
+        "#,
+        project_name,
+        project_id
+    )
+    .fetch_optional(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error creating Project"))?
+    else {
+        return Err(ErrorModel::conflict(
+            "Project with this id already exists",
+            "ProjectIdAlreadyExists",
+            None,
+        )
+        .into());
+    };
+
+    Ok(())
+}
+
+pub(crate) async fn get_project(
+    project_id: &ProjectId,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<Option<GetProjectResponse>> {
+    let project = sqlx::query!(
+        r#"
+        SELECT
+            project_name,
+            project_id
+        FROM project
+        WHERE project_id = $1
+        "#,
+        project_id
+    )
+    .fetch_optional(&mut **transaction)
     .await
     .map_err(|e| {
-        tracing::error!(error=?e, "Error fetching warehouse stats");
-        e.into_error_model("failed to get stats")
+        ErrorModel::internal(
+            "Error fetching project",
+            "ProjectFetchError",
+            Some(Box::new(e)),
+        )
     })?;
 
-    let next_page_token = stats.last().map(|s| {
-        PaginateToken::V1(V1PaginateToken {
-            created_at: s.timestamp,
-            id: String::new(),
+    if let Some(project) = project {
+        Ok(Some(GetProjectResponse {
+            project_id: ProjectId::from_db_unchecked(project.project_id),
+            name: project.project_name,
+        }))
+    } else {
+        Ok(None)
+    }
+}
+
+pub(crate) async fn delete_project(
+    project_id: &ProjectId,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let row_count = sqlx::query_scalar!(r#"DELETE FROM project WHERE project_id = $1"#, project_id)
+        .execute(&mut **transaction)
+        .await
+        .map_err(|e| match &e {
+            sqlx::Error::Database(db_error) => {
+                if db_error.is_foreign_key_violation() {
+                    ErrorModel::conflict(
+                        "Project is not empty",
+                        "ProjectNotEmpty",
+                        Some(Box::new(e)),
+                    )
+                } else {
+                    e.into_error_model("Error deleting project")
+                }
+            }
+            _ => e.into_error_model("Error deleting project"),
+        })?
+        .rows_affected();
+
+    if row_count == 0 {
+        return Err(ErrorModel::not_found("Project not found", "ProjectNotFound", None).into());
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn list_warehouses<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    project_id: &ProjectId,
+    include_status: Option<Vec<WarehouseStatus>>,
+    catalog_state: E,
+) -> Result<Vec<GetWarehouseResponse>> {
+    #[derive(sqlx::FromRow, Debug, PartialEq)]
+    struct WarehouseRecord {
+        warehouse_id: uuid::Uuid,
+        warehouse_name: String,
+        storage_profile: Json<StorageProfile>,
+        storage_secret_id: Option<uuid::Uuid>,
+        status: WarehouseStatus,
+        tabular_delete_mode: DbTabularDeleteProfile,
+        tabular_expiration_seconds: Option<i64>,
+        protected: bool,
+        disabled_endpoint_groups: Vec<EndpointGroup>,
+        client_workarounds: Vec<ClientWorkaround>,
+        default_branch: Option<String>,
+        metadata_push_enabled: bool,
+        labels: Json<Labels>,
+        created_at: chrono::DateTime<chrono::Utc>,
+        updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    }
+
+    let include_status = include_status.unwrap_or_else(|| vec![WarehouseStatus::Active]);
+    let warehouses = sqlx::query_as!(
+        WarehouseRecord,
+        r#"
+            SELECT 
+                warehouse_id,
+                warehouse_name,
+                storage_profile as "storage_profile: Json<StorageProfile>",
+                storage_secret_id,
+                status AS "status: WarehouseStatus",
+                tabular_delete_mode as "tabular_delete_mode: DbTabularDeleteProfile",
+                tabular_expiration_seconds,
+                protected,
+                disabled_endpoint_groups as "disabled_endpoint_groups: Vec<EndpointGroup>",
+                client_workarounds as "client_workarounds: Vec<ClientWorkaround>",
+                default_branch,
+                metadata_push_enabled,
+                labels as "labels: Json<Labels>",
+                created_at,
+                updated_at
+            FROM warehouse
+            WHERE project_id = $1
+            AND status = ANY($2)
+            "#,
+        project_id,
+        include_status as Vec<WarehouseStatus>
+    )
+    .fetch_all(catalog_state)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching warehouses"))?;
+
+    warehouses
+        .into_iter()
+        .map(|warehouse| {
+            let tabular_delete_profile = db_to_api_tabular_delete_profile(
+                warehouse.tabular_delete_mode,
+                warehouse.tabular_expiration_seconds,
+            )?;
+
+            Ok(GetWarehouseResponse {
+                id: warehouse.warehouse_id.into(),
+                name: warehouse.warehouse_name,
+                project_id: project_id.clone(),
+                storage_profile: warehouse.storage_profile.deref().clone(),
+                storage_secret_id: warehouse.storage_secret_id.map(std::convert::Into::into),
+                status: warehouse.status,
+                tabular_delete_profile,
+                protected: warehouse.protected,
+                disabled_endpoint_groups: warehouse.disabled_endpoint_groups,
+                client_workarounds: warehouse.client_workarounds,
+                default_branch: warehouse.default_branch,
+                metadata_push_enabled: warehouse.metadata_push_enabled,
+                labels: warehouse.labels.0,
+                created_at: warehouse.created_at,
+                updated_at: warehouse.updated_at,
+            })
         })
-        .to_string()
-    });
+        .collect::<Result<Vec<_>>>()
+}
+
+pub(crate) async fn get_warehouse(
+    warehouse_id: WarehouseId,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<Option<GetWarehouseResponse>> {
+    let warehouse = sqlx::query!(
+        r#"
+        SELECT 
+            warehouse_name,
+            project_id,
+            storage_profile as "storage_profile: Json<StorageProfile>",
+            storage_secret_id,
+            status AS "status: WarehouseStatus",
+            tabular_delete_mode as "tabular_delete_mode: DbTabularDeleteProfile",
+            tabular_expiration_seconds,
+            protected,
+            disabled_endpoint_groups as "disabled_endpoint_groups: Vec<EndpointGroup>",
+            client_workarounds as "client_workarounds: Vec<ClientWorkaround>",
+            default_branch,
+            metadata_push_enabled,
+            labels as "labels: Json<Labels>",
+            created_at,
+            updated_at
+        FROM warehouse
+        WHERE warehouse_id = $1
+        "#,
+        *warehouse_id
+    )
+    .fetch_optional(&mut **transaction)
+    .await
+    .map_err(map_select_warehouse_err)?;
+
+    if let Some(warehouse) = warehouse {
+        let tabular_delete_profile = db_to_api_tabular_delete_profile(
+            warehouse.tabular_delete_mode,
+            warehouse.tabular_expiration_seconds,
+        )?;
+
+        Ok(Some(GetWarehouseResponse {
+            id: warehouse_id,
+            name: warehouse.warehouse_name,
+            project_id: ProjectId::from_db_unchecked(warehouse.project_id),
+            storage_profile: warehouse.storage_profile.deref().clone(),
+            storage_secret_id: warehouse.storage_secret_id.map(std::convert::Into::into),
+            status: warehouse.status,
+            tabular_delete_profile,
+            protected: warehouse.protected,
+            disabled_endpoint_groups: warehouse.disabled_endpoint_groups,
+            client_workarounds: warehouse.client_workarounds,
+            default_branch: warehouse.default_branch,
+            metadata_push_enabled: warehouse.metadata_push_enabled,
+            labels: warehouse.labels.0,
+            created_at: warehouse.created_at,
+            updated_at: warehouse.updated_at,
+        }))
+    } else {
+        Ok(None)
+    }
+}
+
+pub(crate) async fn list_projects<'e, 'c: 'e, E: sqlx::Executor<'c, Database = sqlx::Postgres>>(
+    project_ids: Option<HashSet<ProjectId>>,
+    connection: E,
+) -> Result<Vec<GetProjectResponse>> {
+    let return_all = project_ids.is_none();
+    let projects = sqlx::query!(
+        r#"
+        SELECT project_id, project_name FROM project WHERE project_id = ANY($1) or $2
+        "#,
+        project_ids
+            .map(|ids| ids.into_iter().map(|i| i.to_string()).collect::<Vec<_>>())
+            .unwrap_or_default() as Vec<String>,
+        return_all
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching projects"))?;
+
+    Ok(projects
+        .into_iter()
+        .map(|project| GetProjectResponse {
+            project_id: ProjectId::from_db_unchecked(project.project_id),
+            name: project.project_name,
+        })
+        .collect())
+}
+
+pub(crate) async fn delete_warehouse(
+    warehouse_id: WarehouseId,
+    DeleteWarehouseQuery { force }: DeleteWarehouseQuery,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let unfinished_task_counts_per_queue = sqlx::query!(
+        r#"WITH running_tasks as (SELECT task_id, queue_name, status from task WHERE warehouse_id = $1),
+                deletes as (DELETE FROM task where warehouse_id = $1)
+            SELECT COUNT(task_id) as "task_count!", queue_name FROM running_tasks GROUP BY queue_name"#,
+        *warehouse_id,
+    ).fetch_all(&mut **transaction).await.map_err(|e| e.into_error_model("Error deleting tasks for warehouse"))?;
+    if !unfinished_task_counts_per_queue.is_empty() {
+        let task_descriptions = unfinished_task_counts_per_queue
+            .iter()
+            .map(|row| format!("{} in queue '{}'", row.task_count, row.queue_name))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        return Err(ErrorModel::conflict(
+            format!(
+                "Warehouse has unfinished tasks: {task_descriptions}. Retry once they are done."
+            ),
+            "WarehouseHasUnfinishedTasks",
+            None,
+        )
+        .into());
+    }
+
+    let protected = sqlx::query_scalar!(
+        r#"WITH delete_info as (
+               SELECT protected FROM warehouse w WHERE w.warehouse_id = $1
+           ),
+           deleted as (DELETE FROM warehouse WHERE warehouse_id = $1 AND (not protected OR $2))
+           SELECT protected as "protected!" FROM delete_info"#,
+        *warehouse_id,
+        force
+    )
+    .fetch_one(&mut **transaction)
+    .await
+    .map_err(|e| match &e {
+        sqlx::Error::RowNotFound => ErrorModel::not_found(
+            format!("Warehouse '{warehouse_id}' not found"),
+            "WarehouseNotFound",
+            Some(Box::new(e)),
+        ),
+        sqlx::Error::Database(db_error) => {
+            if db_error.is_foreign_key_violation() {
+                ErrorModel::conflict(
+                    "Warehouse is not empty",
+                    "WarehouseNotEmpty",
+                    Some(Box::new(e)),
+                )
+            } else {
+                e.into_error_model("Error deleting warehouse")
+            }
+        }
+        _ => e.into_error_model("Error deleting warehouse"),
+    })?;
+
+    if protected && !force {
+        return Err(
+            ErrorModel::conflict("Warehouse is protected", "WarehouseProtected", None).into(),
+        );
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn rename_warehouse(
+    warehouse_id: WarehouseId,
+    new_name: &str,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let row_count = sqlx::query!(
+        "UPDATE warehouse
+            SET warehouse_name = $1
+            WHERE warehouse_id = $2
+            AND status = 'active'",
+        new_name,
+        *warehouse_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error renaming warehouse"))?
+    .rows_affected();
+
+    if row_count == 0 {
+        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn set_warehouse_status(
+    warehouse_id: WarehouseId,
+    status: WarehouseStatus,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let row_count = sqlx::query!(
+        "UPDATE warehouse
+            SET status = $1
+            WHERE warehouse_id = $2",
+        status as _,
+        *warehouse_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error setting warehouse status"))?
+    .rows_affected();
+
+    if row_count == 0 {
+        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn set_warehouse_protection(
+    warehouse_id: WarehouseId,
+    protected: bool,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<ProtectionResponse> {
+    let row = sqlx::query!(
+        "UPDATE warehouse
+            SET protected = $1
+            WHERE warehouse_id = $2
+            returning protected, updated_at",
+        protected,
+        *warehouse_id
+    )
+    .fetch_one(&mut **transaction)
+    .await
+    .map_err(|e| {
+        if let sqlx::Error::RowNotFound = e {
+            return ErrorModel::not_found(
+                format!("Warehouse '{warehouse_id}' not found"),
+                "WarehouseNotFound",
+                Some(Box::new(e)),
+            );
+        }
+        e.into_error_model("Error setting warehouse protection")
+    })?;
+
+    Ok(ProtectionResponse {
+        protected: row.protected,
+        updated_at: row.updated_at,
+    })
+}
+
+pub(crate) async fn update_storage_profile(
+    warehouse_id: WarehouseId,
+    storage_profile: StorageProfile,
+    storage_secret_id: Option<SecretIdent>,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let storage_profile_ser = serde_json::to_value(storage_profile).map_err(|e| {
+        ErrorModel::internal(
+            "Error serializing storage profile",
+            "StorageProfileSerializationError",
+            Some(Box::new(e)),
+        )
+    })?;
+
+    let row_count = sqlx::query!(
+        r#"
+            UPDATE warehouse
+            SET storage_profile = $1, storage_secret_id = $2
+            WHERE warehouse_id = $3
+            AND status = 'active'
+        "#,
+        storage_profile_ser,
+        storage_secret_id.map(|id| id.into_uuid()),
+        *warehouse_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error updating storage profile"))?
+    .rows_affected();
+
+    if row_count == 0 {
+        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn retire_storage_secret<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseId,
+    secret_id: SecretIdent,
+    retire_at: chrono::DateTime<chrono::Utc>,
+    connection: E,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO retired_storage_secret (secret_id, warehouse_id, retire_at)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (secret_id) DO UPDATE SET retire_at = LEAST(retired_storage_secret.retire_at, EXCLUDED.retire_at)
+        "#,
+        secret_id.into_uuid(),
+        *warehouse_id,
+        retire_at,
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error retiring storage secret"))?;
+
+    Ok(())
+}
+
+pub(crate) async fn claim_due_retired_storage_secrets<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    limit: i64,
+    connection: E,
+) -> Result<Vec<SecretIdent>> {
+    let secret_ids = sqlx::query_scalar!(
+        r#"
+        SELECT secret_id
+        FROM retired_storage_secret
+        WHERE retire_at <= now()
+        ORDER BY retire_at
+        LIMIT $1
+        FOR UPDATE SKIP LOCKED
+        "#,
+        limit,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error claiming retired storage secrets"))?;
+
+    Ok(secret_ids.into_iter().map(SecretIdent::from).collect())
+}
+
+pub(crate) async fn delete_retired_storage_secrets<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    secret_ids: &[SecretIdent],
+    connection: E,
+) -> Result<()> {
+    if secret_ids.is_empty() {
+        return Ok(());
+    }
+
+    sqlx::query!(
+        r#"
+        DELETE FROM retired_storage_secret
+        WHERE secret_id = ANY($1::UUID[])
+        "#,
+        &secret_ids
+            .iter()
+            .map(SecretIdent::into_uuid)
+            .collect::<Vec<_>>(),
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error deleting retired storage secrets"))?;
+
+    Ok(())
+}
+
+fn map_select_warehouse_err(e: sqlx::Error) -> ErrorModel {
+    ErrorModel::internal(
+        "Error fetching warehouse",
+        "WarehouseFetchError",
+        Some(Box::new(e)),
+    )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "tabular_delete_mode", rename_all = "kebab-case")]
+enum DbTabularDeleteProfile {
+    Soft,
+    Hard,
+}
+
+impl From<TabularDeleteProfile> for DbTabularDeleteProfile {
+    fn from(value: TabularDeleteProfile) -> Self {
+        match value {
+            TabularDeleteProfile::Soft { .. } => DbTabularDeleteProfile::Soft,
+            TabularDeleteProfile::Hard {} => DbTabularDeleteProfile::Hard,
+        }
+    }
+}
+
+/// Convert a database tabular delete profile to the API tabular delete profile
+fn db_to_api_tabular_delete_profile(
+    mode: DbTabularDeleteProfile,
+    expiration_seconds: Option<i64>,
+) -> Result<TabularDeleteProfile> {
+    match mode {
+        DbTabularDeleteProfile::Soft => {
+            let seconds = expiration_seconds.ok_or(ErrorModel::internal(
+                "Tabular expiration seconds not found",
+                "TabularExpirationSecondsNotFound",
+                None,
+            ))?;
+            Ok(TabularDeleteProfile::Soft {
+                expiration_seconds: chrono::Duration::seconds(seconds),
+            })
+        }
+        DbTabularDeleteProfile::Hard => Ok(TabularDeleteProfile::Hard {}),
+    }
+}
+
+pub(crate) async fn get_warehouse_stats(
+    conn: PgPool,
+    warehouse_ident: WarehouseId,
+    PaginationQuery {
+        page_size,
+        page_token,
+    }: PaginationQuery,
+) -> crate::api::Result<WarehouseStatisticsResponse> {
+    let page_size = page_size.map_or(MAX_PAGE_SIZE, |i| i.clamp(1, MAX_PAGE_SIZE));
+
+    let token = page_token
+        .as_option()
+        .map(PaginateToken::try_from)
+        .transpose()?
+        .map(PaginateToken::into_v1)
+        .transpose()?;
+
+    let (token_ts, _): (_, Option<String>) = token
+        .map(|V1PaginateToken { created_at, id }| (created_at, id))
+        .unzip();
+
+    let stats = sqlx::query!(
+        r#"
+        SELECT
+            number_of_views as "number_of_views!",
+            number_of_tables as "number_of_tables!",
+            created_at as "created_at!",
+            updated_at,
+            timestamp as "timestamp!"
+        FROM (
+            (SELECT number_of_views, number_of_tables, created_at, updated_at, timestamp
+            FROM warehouse_statistics
+            WHERE warehouse_id = $1
+            AND (timestamp < $2 OR $2 IS NULL))
+
+            UNION ALL
+
+            (SELECT number_of_views, number_of_tables, created_at, updated_at, timestamp
+            FROM warehouse_statistics_history
+            WHERE warehouse_id = $1
+            AND (timestamp < $2 OR $2 IS NULL))
+        ) AS ww
+        ORDER BY timestamp DESC
+        LIMIT $3
+        "#,
+        warehouse_ident.0,
+        token_ts,
+        page_size
+    )
+    .fetch_all(&conn)
+    .await
+    .map_err(|e| {
+        tracing::error!(error=?e, "Error fetching warehouse stats");
+        e.into_error_model("failed to get stats")
+    })?;
+
+    let next_page_token = stats.last().map(|s| {
+        PaginateToken::V1(V1PaginateToken {
+            created_at: s.timestamp,
+            id: String::new(),
+        })
+        .to_string()
+    });
+
+    let stats = stats
+        .into_iter()
+        .map(|s| WarehouseStatistics {
+            number_of_tables: s.number_of_tables,
+            number_of_views: s.number_of_views,
+            timestamp: s.timestamp,
+            updated_at: s.updated_at.unwrap_or(s.created_at),
+        })
+        .collect();
+    Ok(WarehouseStatisticsResponse {
+        warehouse_ident: *warehouse_ident,
+        stats,
+        next_page_token,
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use chrono::SubsecRound as _;
+    use http::StatusCode;
+
+    use super::*;
+    use crate::{
+        api::iceberg::types::PageToken,
+        implementations::postgres::{PostgresCatalog, PostgresTransaction},
+        service::{
+            property_schemas::{PropertyEntityType, PropertyType},
+            storage::{S3Flavor, S3Profile},
+            Catalog as _, Transaction,
+        },
+    };
+
+    pub(crate) async fn initialize_project(pool: sqlx::PgPool) -> (CatalogState, ProjectId) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let project_id = ProjectId::new_random();
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        PostgresCatalog::create_project(
+            &project_id,
+            format!("Project {project_id}"),
+            t.transaction(),
+        )
+        .await
+        .unwrap();
+        t.commit().await.unwrap();
+        (state, project_id)
+    }
+
+    pub(crate) async fn initialize_warehouse(
+        state: CatalogState,
+        storage_profile: Option<StorageProfile>,
+        project_id: Option<&ProjectId>,
+        secret_id: Option<SecretIdent>,
+        create_project: bool,
+    ) -> crate::WarehouseId {
+        let project_id = project_id.map_or(
+            ProjectId::from(uuid::Uuid::nil()),
+            std::borrow::ToOwned::to_owned,
+        );
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+
+        if create_project {
+            PostgresCatalog::create_project(
+                &project_id,
+                format!("Project {project_id}"),
+                t.transaction(),
+            )
+            .await
+            .unwrap();
+        }
+
+        let storage_profile = storage_profile.unwrap_or(StorageProfile::S3(
+            S3Profile::builder()
+                .bucket("test_bucket".to_string())
+                .region("us-east-1".to_string())
+                .flavor(S3Flavor::S3Compat)
+                .sts_enabled(false)
+                .build(),
+        ));
+
+        let warehouse_id = PostgresCatalog::create_warehouse(
+            "test_warehouse".to_string(),
+            &project_id,
+            storage_profile,
+            TabularDeleteProfile::Soft {
+                expiration_seconds: chrono::Duration::seconds(5),
+            },
+            secret_id,
+            t.transaction(),
+        )
+        .await
+        .unwrap();
+
+        t.commit().await.unwrap();
+        warehouse_id
+    }
+
+    #[sqlx::test]
+    async fn test_get_warehouse_by_name(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+
+        let fetched_warehouse_id = PostgresCatalog::get_warehouse_by_name(
+            "test_warehouse",
+            &ProjectId::from(uuid::Uuid::nil()),
+            state.clone(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(Some(warehouse_id), fetched_warehouse_id);
+    }
+
+    #[sqlx::test]
+    async fn test_list_projects(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+
+        let project_id_1 = ProjectId::from(uuid::Uuid::new_v4());
+        initialize_warehouse(state.clone(), None, Some(&project_id_1), None, true).await;
+
+        let mut trx = PostgresTransaction::begin_read(state.clone())
+            .await
+            .unwrap();
+
+        let projects = PostgresCatalog::list_projects(None, trx.transaction())
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|p| p.project_id)
+            .collect::<Vec<_>>();
+        trx.commit().await.unwrap();
+        assert_eq!(projects.len(), 1);
+        assert!(projects.contains(&project_id_1));
+
+        let project_id_2 = ProjectId::from(uuid::Uuid::new_v4());
+        initialize_warehouse(state.clone(), None, Some(&project_id_2), None, true).await;
+
+        let mut trx = PostgresTransaction::begin_read(state.clone())
+            .await
+            .unwrap();
+
+        let projects = PostgresCatalog::list_projects(None, trx.transaction())
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|p| p.project_id)
+            .collect::<Vec<_>>();
+        trx.commit().await.unwrap();
+        assert_eq!(projects.len(), 2);
+        assert!(projects.contains(&project_id_1));
+        assert!(projects.contains(&project_id_2));
+        let mut trx = PostgresTransaction::begin_read(state).await.unwrap();
+
+        let projects = PostgresCatalog::list_projects(
+            Some(HashSet::from_iter(vec![project_id_1.clone()])),
+            trx.transaction(),
+        )
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|p| p.project_id)
+        .collect::<Vec<_>>();
+        trx.commit().await.unwrap();
+
+        assert_eq!(projects.len(), 1);
+        assert!(projects.contains(&project_id_1));
+    }
+
+    #[sqlx::test]
+    async fn test_list_warehouses(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let project_id = ProjectId::from(uuid::Uuid::new_v4());
+        let warehouse_id_1 =
+            initialize_warehouse(state.clone(), None, Some(&project_id), None, true).await;
+        let mut trx = PostgresTransaction::begin_read(state).await.unwrap();
+
+        let warehouses = PostgresCatalog::list_warehouses(&project_id, None, trx.transaction())
+            .await
+            .unwrap();
+        trx.commit().await.unwrap();
+        assert_eq!(warehouses.len(), 1);
+        // Check ids
+        assert!(warehouses.iter().any(|w| w.id == warehouse_id_1));
+    }
+
+    #[sqlx::test]
+    async fn test_list_warehouses_active_filter(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let project_id = ProjectId::from(uuid::Uuid::new_v4());
+        let warehouse_id_1 =
+            initialize_warehouse(state.clone(), None, Some(&project_id), None, true).await;
+
+        // Rename warehouse 1
+        let mut transaction = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        PostgresCatalog::rename_warehouse(warehouse_id_1, "new_name", transaction.transaction())
+            .await
+            .unwrap();
+        PostgresCatalog::set_warehouse_status(
+            warehouse_id_1,
+            WarehouseStatus::Inactive,
+            transaction.transaction(),
+        )
+        .await
+        .unwrap();
+        transaction.commit().await.unwrap();
+
+        // Create warehouse 2
+        let warehouse_id_2 =
+            initialize_warehouse(state.clone(), None, Some(&project_id), None, false).await;
+        let mut trx = PostgresTransaction::begin_read(state.clone())
+            .await
+            .unwrap();
+
+        // Assert active whs
+        let warehouses = PostgresCatalog::list_warehouses(
+            &project_id,
+            Some(vec![WarehouseStatus::Active, WarehouseStatus::Inactive]),
+            trx.transaction(),
+        )
+        .await
+        .unwrap();
+        trx.commit().await.unwrap();
+        assert_eq!(warehouses.len(), 2);
+        assert!(warehouses.iter().any(|w| w.id == warehouse_id_1));
+        assert!(warehouses.iter().any(|w| w.id == warehouse_id_2));
+
+        // Assert only active whs
+        let mut trx = PostgresTransaction::begin_read(state).await.unwrap();
+
+        let warehouses = PostgresCatalog::list_warehouses(&project_id, None, trx.transaction())
+            .await
+            .unwrap();
+        trx.commit().await.unwrap();
+        assert_eq!(warehouses.len(), 1);
+        assert!(warehouses.iter().any(|w| w.id == warehouse_id_2));
+    }
+
+    #[sqlx::test]
+    async fn test_rename_warehouse(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let project_id = ProjectId::from(uuid::Uuid::new_v4());
+        let warehouse_id =
+            initialize_warehouse(state.clone(), None, Some(&project_id), None, true).await;
+
+        let mut transaction = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        PostgresCatalog::rename_warehouse(warehouse_id, "new_name", transaction.transaction())
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+
+        let mut read_transaction = PostgresTransaction::begin_read(state.clone())
+            .await
+            .unwrap();
+        let warehouse =
+            PostgresCatalog::get_warehouse(warehouse_id, read_transaction.transaction())
+                .await
+                .unwrap();
+        assert_eq!(warehouse.unwrap().name, "new_name");
+    }
+
+    #[sqlx::test]
+    async fn test_rename_project(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let project_id = ProjectId::from(uuid::Uuid::new_v4());
+        {
+            let mut t = PostgresTransaction::begin_write(state.clone())
+                .await
+                .unwrap();
+            PostgresCatalog::create_project(&project_id, "old_name".to_string(), t.transaction())
+                .await
+                .unwrap();
+            t.commit().await.unwrap();
+        }
 
-    let stats = stats
-        .into_iter()
-        .map(|s| WarehouseStatistics {
-            number_of_tables: s.number_of_tables,
-            number_of_views: s.number_of_views,
-            timestamp: s.timestamp,
-            updated_at: s.updated_at.unwrap_or(s.created_at),
-        })
-        .collect();
-    Ok(WarehouseStatisticsResponse {
-        warehouse_ident: *warehouse_ident,
-        stats,
-        next_page_token,
-    })
-}
+        {
+            let mut t = PostgresTransaction::begin_write(state.clone())
+                .await
+                .unwrap();
+            PostgresCatalog::rename_project(&project_id, "new_name", t.transaction())
+                .await
+                .unwrap();
+            t.commit().await.unwrap();
+        }
 
-#[cfg(test)]
-pub(crate) mod test {
-    use http::StatusCode;
+        let mut read_transaction = PostgresTransaction::begin_read(state.clone())
+            .await
+            .unwrap();
+        let project = PostgresCatalog::get_project(&project_id, read_transaction.transaction())
+            .await
+            .unwrap();
+        assert_eq!(project.unwrap().name, "new_name");
+    }
 
-    use super::*;
-    use crate::{
-        api::iceberg::types::PageToken,
-        implementations::postgres::{PostgresCatalog, PostgresTransaction},
-        service::{
-            storage::{S3Flavor, S3Profile},
-            Catalog as _, Transaction,
-        },
-    };
+    #[sqlx::test]
+    async fn test_project_quotas(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let project_id = ProjectId::from(uuid::Uuid::new_v4());
+        initialize_warehouse(state.clone(), None, Some(&project_id), None, true).await;
 
-    pub(crate) async fn initialize_warehouse(
-        state: CatalogState,
-        storage_profile: Option<StorageProfile>,
-        project_id: Option<&ProjectId>,
-        secret_id: Option<SecretIdent>,
-        create_project: bool,
-    ) -> crate::WarehouseId {
-        let project_id = project_id.map_or(
-            ProjectId::from(uuid::Uuid::nil()),
-            std::borrow::ToOwned::to_owned,
-        );
         let mut t = PostgresTransaction::begin_write(state.clone())
             .await
             .unwrap();
+        let quotas = PostgresCatalog::get_project_quotas(&project_id, t.transaction())
+            .await
+            .unwrap();
+        assert_eq!(quotas, Some(ProjectQuotas::default()));
+
+        let new_quotas = ProjectQuotas {
+            max_warehouses: Some(2),
+            max_tables: Some(100),
+            max_namespace_depth: None,
+            max_snapshots_per_table: Some(50),
+            warning_threshold_percent: Some(80),
+        };
+        PostgresCatalog::set_project_quotas(&project_id, &new_quotas, t.transaction())
+            .await
+            .unwrap();
+        let quotas = PostgresCatalog::get_project_quotas(&project_id, t.transaction())
+            .await
+            .unwrap();
+        assert_eq!(quotas, Some(new_quotas));
 
-        if create_project {
-            PostgresCatalog::create_project(
-                &project_id,
-                format!("Project {project_id}"),
-                t.transaction(),
-            )
+        let usage = PostgresCatalog::get_project_quota_usage(&project_id, t.transaction())
             .await
             .unwrap();
-        }
+        assert_eq!(
+            usage,
+            ProjectQuotaUsage {
+                warehouses: 1,
+                tables: 0
+            }
+        );
 
-        let storage_profile = storage_profile.unwrap_or(StorageProfile::S3(
-            S3Profile::builder()
-                .bucket("test_bucket".to_string())
-                .region("us-east-1".to_string())
-                .flavor(S3Flavor::S3Compat)
-                .sts_enabled(false)
-                .build(),
-        ));
+        let missing_project = ProjectId::from(uuid::Uuid::new_v4());
+        assert!(
+            PostgresCatalog::get_project_quotas(&missing_project, t.transaction())
+                .await
+                .unwrap()
+                .is_none()
+        );
+        let err =
+            PostgresCatalog::set_project_quotas(&missing_project, &new_quotas, t.transaction())
+                .await
+                .unwrap_err();
+        assert_eq!(err.error.code, StatusCode::NOT_FOUND);
+        t.commit().await.unwrap();
 
-        let warehouse_id = PostgresCatalog::create_warehouse(
-            "test_warehouse".to_string(),
-            &project_id,
-            storage_profile,
-            TabularDeleteProfile::Soft {
-                expiration_seconds: chrono::Duration::seconds(5),
+        let projects = PostgresCatalog::list_projects_with_quota_warnings(state)
+            .await
+            .unwrap();
+        assert_eq!(projects, vec![project_id]);
+    }
+
+    #[sqlx::test]
+    async fn test_property_schemas(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let project_id = ProjectId::from(uuid::Uuid::new_v4());
+        initialize_warehouse(state.clone(), None, Some(&project_id), None, true).await;
+
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let schemas = PostgresCatalog::get_property_schemas(&project_id, t.transaction())
+            .await
+            .unwrap();
+        assert_eq!(schemas, Some(vec![]));
+
+        let new_schemas = vec![PropertySchema {
+            key: "owner.team".to_string(),
+            entity_types: vec![PropertyEntityType::Namespace, PropertyEntityType::Table],
+            description: Some("Team owning the data".to_string()),
+            r#type: PropertyType::Enum {
+                values: vec!["analytics".to_string(), "platform".to_string()],
             },
-            secret_id,
-            t.transaction(),
-        )
-        .await
-        .unwrap();
+        }];
+        PostgresCatalog::set_property_schemas(&project_id, &new_schemas, t.transaction())
+            .await
+            .unwrap();
+        let schemas = PostgresCatalog::get_property_schemas(&project_id, t.transaction())
+            .await
+            .unwrap();
+        assert_eq!(schemas, Some(new_schemas.clone()));
 
+        let missing_project = ProjectId::from(uuid::Uuid::new_v4());
+        assert!(
+            PostgresCatalog::get_property_schemas(&missing_project, t.transaction())
+                .await
+                .unwrap()
+                .is_none()
+        );
+        let err =
+            PostgresCatalog::set_property_schemas(&missing_project, &new_schemas, t.transaction())
+                .await
+                .unwrap_err();
+        assert_eq!(err.error.code, StatusCode::NOT_FOUND);
         t.commit().await.unwrap();
-        warehouse_id
     }
 
     #[sqlx::test]
-    async fn test_get_warehouse_by_name(pool: sqlx::PgPool) {
+    async fn test_project_quota_warnings(pool: sqlx::PgPool) {
         let state = CatalogState::from_pools(pool.clone(), pool.clone());
-        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
-
-        let fetched_warehouse_id = PostgresCatalog::get_warehouse_by_name(
-            "test_warehouse",
-            &ProjectId::from(uuid::Uuid::nil()),
-            state.clone(),
+        let project_id = ProjectId::from(uuid::Uuid::new_v4());
+        initialize_warehouse(state.clone(), None, Some(&project_id), None, true).await;
+
+        let warning = ProjectQuotaWarning {
+            quota: ProjectQuotaKind::Tables,
+            usage: 80,
+            limit: 100,
+            raised_at: chrono::Utc::now().trunc_subsecs(6),
+        };
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        assert!(PostgresCatalog::raise_project_quota_warning(
+            &project_id,
+            &warning,
+            t.transaction()
         )
         .await
-        .unwrap();
+        .unwrap());
+        // Raising an active warning again has no effect
+        assert!(!PostgresCatalog::raise_project_quota_warning(
+            &project_id,
+            &warning,
+            t.transaction()
+        )
+        .await
+        .unwrap());
+        let warnings = PostgresCatalog::list_project_quota_warnings(&project_id, t.transaction())
+            .await
+            .unwrap();
+        assert_eq!(warnings, vec![warning]);
+        t.commit().await.unwrap();
 
-        assert_eq!(Some(warehouse_id), fetched_warehouse_id);
+        // Projects with active warnings are checked even without threshold
+        let projects = PostgresCatalog::list_projects_with_quota_warnings(state.clone())
+            .await
+            .unwrap();
+        assert_eq!(projects, vec![project_id.clone()]);
+
+        let mut t = PostgresTransaction::begin_write(state).await.unwrap();
+        assert!(PostgresCatalog::clear_project_quota_warning(
+            &project_id,
+            ProjectQuotaKind::Tables,
+            t.transaction()
+        )
+        .await
+        .unwrap());
+        assert!(!PostgresCatalog::clear_project_quota_warning(
+            &project_id,
+            ProjectQuotaKind::Tables,
+            t.transaction()
+        )
+        .await
+        .unwrap());
+        t.commit().await.unwrap();
     }
 
     #[sqlx::test]
-    async fn test_list_projects(pool: sqlx::PgPool) {
+    async fn test_same_project_id(pool: sqlx::PgPool) {
         let state = CatalogState::from_pools(pool.clone(), pool.clone());
-
-        let project_id_1 = ProjectId::from(uuid::Uuid::new_v4());
-        initialize_warehouse(state.clone(), None, Some(&project_id_1), None, true).await;
-
-        let mut trx = PostgresTransaction::begin_read(state.clone())
+        let project_id = ProjectId::from(uuid::Uuid::new_v4());
+        let mut t = PostgresTransaction::begin_write(state.clone())
             .await
             .unwrap();
-
-        let projects = PostgresCatalog::list_projects(None, trx.transaction())
+        PostgresCatalog::create_project(&project_id, "old_name".to_string(), t.transaction())
             .await
-            .unwrap()
-            .into_iter()
-            .map(|p| p.project_id)
-            .collect::<Vec<_>>();
-        trx.commit().await.unwrap();
-        assert_eq!(projects.len(), 1);
-        assert!(projects.contains(&project_id_1));
-
-        let project_id_2 = ProjectId::from(uuid::Uuid::new_v4());
-        initialize_warehouse(state.clone(), None, Some(&project_id_2), None, true).await;
+            .unwrap();
+        let err =
+            PostgresCatalog::create_project(&project_id, "other_name".to_string(), t.transaction())
+                .await
+                .unwrap_err();
+        assert_eq!(err.error.code, StatusCode::CONFLICT);
+        t.commit().await.unwrap();
+    }
 
-        let mut trx = PostgresTransaction::begin_read(state.clone())
+    #[sqlx::test]
+    async fn test_cannot_drop_protected_warehouse(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let project_id = ProjectId::from(uuid::Uuid::new_v4());
+        let warehouse_id =
+            initialize_warehouse(state.clone(), None, Some(&project_id), None, true).await;
+        let mut trx = PostgresTransaction::begin_write(state.clone())
             .await
             .unwrap();
-
-        let projects = PostgresCatalog::list_projects(None, trx.transaction())
+        set_warehouse_protection(warehouse_id, true, trx.transaction())
             .await
-            .unwrap()
-            .into_iter()
-            .map(|p| p.project_id)
-            .collect::<Vec<_>>();
-        trx.commit().await.unwrap();
-        assert_eq!(projects.len(), 2);
-        assert!(projects.contains(&project_id_1));
-        assert!(projects.contains(&project_id_2));
-        let mut trx = PostgresTransaction::begin_read(state).await.unwrap();
-
-        let projects = PostgresCatalog::list_projects(
-            Some(HashSet::from_iter(vec![project_id_1.clone()])),
+            .unwrap();
+        let e = delete_warehouse(
+            warehouse_id,
+            DeleteWarehouseQuery { force: false },
             trx.transaction(),
         )
         .await
-        .unwrap()
-        .into_iter()
-        .map(|p| p.project_id)
-        .collect::<Vec<_>>();
-        trx.commit().await.unwrap();
+        .unwrap_err();
+        assert_eq!(e.error.code, StatusCode::CONFLICT);
+        set_warehouse_protection(warehouse_id, false, trx.transaction())
+            .await
+            .unwrap();
+        delete_warehouse(
+            warehouse_id,
+            DeleteWarehouseQuery { force: false },
+            trx.transaction(),
+        )
+        .await
+        .unwrap();
 
-        assert_eq!(projects.len(), 1);
-        assert!(projects.contains(&project_id_1));
+        trx.commit().await.unwrap();
     }
 
     #[sqlx::test]
-    async fn test_list_warehouses(pool: sqlx::PgPool) {
+    async fn test_can_force_drop_protected_warehouse(pool: sqlx::PgPool) {
         let state = CatalogState::from_pools(pool.clone(), pool.clone());
         let project_id = ProjectId::from(uuid::Uuid::new_v4());
-        let warehouse_id_1 =
+        let warehouse_id =
             initialize_warehouse(state.clone(), None, Some(&project_id), None, true).await;
-        let mut trx = PostgresTransaction::begin_read(state).await.unwrap();
+        let mut trx = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        set_warehouse_protection(warehouse_id, true, trx.transaction())
+            .await
+            .unwrap();
+        delete_warehouse(
+            warehouse_id,
+            DeleteWarehouseQuery { force: true },
+            trx.transaction(),
+        )
+        .await
+        .unwrap();
 
-        let warehouses = PostgresCatalog::list_warehouses(&project_id, None, trx.transaction())
-            .await
-            .unwrap();
         trx.commit().await.unwrap();
-        assert_eq!(warehouses.len(), 1);
-        // Check ids
-        assert!(warehouses.iter().any(|w| w.id == warehouse_id_1));
     }
 
     #[sqlx::test]
-    async fn test_list_warehouses_active_filter(pool: sqlx::PgPool) {
+    async fn test_set_disabled_endpoint_groups(pool: sqlx::PgPool) {
         let state = CatalogState::from_pools(pool.clone(), pool.clone());
         let project_id = ProjectId::from(uuid::Uuid::new_v4());
-        let warehouse_id_1 =
+        let warehouse_id =
             initialize_warehouse(state.clone(), None, Some(&project_id), None, true).await;
-
-        // Rename warehouse 1
-        let mut transaction = PostgresTransaction::begin_write(state.clone())
+        let groups = get_warehouse_disabled_endpoint_groups(warehouse_id, state.clone())
             .await
             .unwrap();
-        PostgresCatalog::rename_warehouse(warehouse_id_1, "new_name", transaction.transaction())
+        assert_eq!(groups, Some(vec![]));
+
+        let mut trx = PostgresTransaction::begin_write(state.clone())
             .await
             .unwrap();
-        PostgresCatalog::set_warehouse_status(
-            warehouse_id_1,
-            WarehouseStatus::Inactive,
-            transaction.transaction(),
+        set_warehouse_disabled_endpoint_groups(
+            warehouse_id,
+            &[EndpointGroup::RegisterTable, EndpointGroup::Views],
+            &mut **trx.transaction(),
         )
         .await
         .unwrap();
-        transaction.commit().await.unwrap();
+        trx.commit().await.unwrap();
 
-        // Create warehouse 2
-        let warehouse_id_2 =
-            initialize_warehouse(state.clone(), None, Some(&project_id), None, false).await;
-        let mut trx = PostgresTransaction::begin_read(state.clone())
+        let groups = get_warehouse_disabled_endpoint_groups(warehouse_id, state.clone())
             .await
             .unwrap();
+        assert_eq!(
+            groups,
+            Some(vec![EndpointGroup::RegisterTable, EndpointGroup::Views])
+        );
 
-        // Assert active whs
-        let warehouses = PostgresCatalog::list_warehouses(
-            &project_id,
-            Some(vec![WarehouseStatus::Active, WarehouseStatus::Inactive]),
-            trx.transaction(),
-        )
-        .await
-        .unwrap();
+        let mut trx = PostgresTransaction::begin_read(state.clone())
+            .await
+            .unwrap();
+        let warehouse = get_warehouse(warehouse_id, trx.transaction())
+            .await
+            .unwrap()
+            .unwrap();
         trx.commit().await.unwrap();
-        assert_eq!(warehouses.len(), 2);
-        assert!(warehouses.iter().any(|w| w.id == warehouse_id_1));
-        assert!(warehouses.iter().any(|w| w.id == warehouse_id_2));
+        assert_eq!(
+            warehouse.disabled_endpoint_groups,
+            vec![EndpointGroup::RegisterTable, EndpointGroup::Views]
+        );
 
-        // Assert only active whs
-        let mut trx = PostgresTransaction::begin_read(state).await.unwrap();
+        let missing =
+            get_warehouse_disabled_endpoint_groups(uuid::Uuid::now_v7().into(), state.clone())
+                .await
+                .unwrap();
+        assert!(missing.is_none());
+    }
 
-        let warehouses = PostgresCatalog::list_warehouses(&project_id, None, trx.transaction())
-            .await
+    #[sqlx::test]
+    async fn test_warehouse_entity_counts(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+
+        let counts = get_warehouse_entity_counts(&pool).await.unwrap();
+        let counts = counts
+            .into_iter()
+            .find(|c| c.warehouse_id == warehouse_id)
             .unwrap();
-        trx.commit().await.unwrap();
-        assert_eq!(warehouses.len(), 1);
-        assert!(warehouses.iter().any(|w| w.id == warehouse_id_2));
+        assert_eq!(counts.namespaces, 0);
+        assert_eq!(counts.tables, 0);
+        assert_eq!(counts.views, 0);
+        assert_eq!(counts.pending_soft_deletes, 0);
     }
 
     #[sqlx::test]
-    async fn test_rename_warehouse(pool: sqlx::PgPool) {
+    async fn test_set_client_workarounds(pool: sqlx::PgPool) {
         let state = CatalogState::from_pools(pool.clone(), pool.clone());
-        let project_id = ProjectId::from(uuid::Uuid::new_v4());
-        let warehouse_id =
-            initialize_warehouse(state.clone(), None, Some(&project_id), None, true).await;
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let workarounds = get_warehouse_client_workarounds(warehouse_id, state.clone())
+            .await
+            .unwrap();
+        assert_eq!(workarounds, Some(vec![]));
 
-        let mut transaction = PostgresTransaction::begin_write(state.clone())
+        let mut trx = PostgresTransaction::begin_write(state.clone())
             .await
             .unwrap();
-        PostgresCatalog::rename_warehouse(warehouse_id, "new_name", transaction.transaction())
+        set_warehouse_client_workarounds(
+            warehouse_id,
+            &[ClientWorkaround::OmitEmptyNextPageToken],
+            &mut **trx.transaction(),
+        )
+        .await
+        .unwrap();
+        trx.commit().await.unwrap();
+
+        let mut trx = PostgresTransaction::begin_read(state.clone())
             .await
             .unwrap();
-        transaction.commit().await.unwrap();
+        let warehouse = get_warehouse(warehouse_id, trx.transaction())
+            .await
+            .unwrap()
+            .unwrap();
+        trx.commit().await.unwrap();
+        assert_eq!(
+            warehouse.client_workarounds,
+            vec![ClientWorkaround::OmitEmptyNextPageToken]
+        );
 
-        let mut read_transaction = PostgresTransaction::begin_read(state.clone())
+        let missing = get_warehouse_client_workarounds(uuid::Uuid::now_v7().into(), state.clone())
             .await
             .unwrap();
-        let warehouse =
-            PostgresCatalog::get_warehouse(warehouse_id, read_transaction.transaction())
-                .await
-                .unwrap();
-        assert_eq!(warehouse.unwrap().name, "new_name");
+        assert!(missing.is_none());
     }
 
     #[sqlx::test]
-    async fn test_rename_project(pool: sqlx::PgPool) {
+    async fn test_set_metadata_push(pool: sqlx::PgPool) {
         let state = CatalogState::from_pools(pool.clone(), pool.clone());
-        let project_id = ProjectId::from(uuid::Uuid::new_v4());
-        {
-            let mut t = PostgresTransaction::begin_write(state.clone())
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+
+        for enabled in [true, false] {
+            let mut trx = PostgresTransaction::begin_write(state.clone())
                 .await
                 .unwrap();
-            PostgresCatalog::create_project(&project_id, "old_name".to_string(), t.transaction())
+            set_warehouse_metadata_push(warehouse_id, enabled, &mut **trx.transaction())
                 .await
                 .unwrap();
-            t.commit().await.unwrap();
-        }
+            trx.commit().await.unwrap();
 
-        {
-            let mut t = PostgresTransaction::begin_write(state.clone())
+            let mut trx = PostgresTransaction::begin_read(state.clone())
                 .await
                 .unwrap();
-            PostgresCatalog::rename_project(&project_id, "new_name", t.transaction())
+            let warehouse = get_warehouse(warehouse_id, trx.transaction())
                 .await
+                .unwrap()
                 .unwrap();
-            t.commit().await.unwrap();
+            trx.commit().await.unwrap();
+            assert_eq!(warehouse.metadata_push_enabled, enabled);
         }
 
-        let mut read_transaction = PostgresTransaction::begin_read(state.clone())
-            .await
-            .unwrap();
-        let project = PostgresCatalog::get_project(&project_id, read_transaction.transaction())
+        let mut trx = PostgresTransaction::begin_write(state.clone())
             .await
             .unwrap();
-        assert_eq!(project.unwrap().name, "new_name");
+        let e = set_warehouse_metadata_push(
+            uuid::Uuid::now_v7().into(),
+            true,
+            &mut **trx.transaction(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(e.error.code, StatusCode::NOT_FOUND);
     }
 
     #[sqlx::test]
-    async fn test_same_project_id(pool: sqlx::PgPool) {
+    async fn test_set_event_routing_rules(pool: sqlx::PgPool) {
         let state = CatalogState::from_pools(pool.clone(), pool.clone());
-        let project_id = ProjectId::from(uuid::Uuid::new_v4());
-        let mut t = PostgresTransaction::begin_write(state.clone())
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+
+        let rules = get_warehouse_event_routing_rules(warehouse_id, &pool)
             .await
             .unwrap();
-        PostgresCatalog::create_project(&project_id, "old_name".to_string(), t.transaction())
+        assert_eq!(rules, Some(vec![]));
+
+        let rules = vec![EventRoutingRule {
+            namespace_prefix: Some(vec!["prod".to_string()]),
+            event_types: vec!["updateTable".to_string()],
+            sinks: vec!["kafka-publisher".to_string()],
+            topic: Some("prod-commits".to_string()),
+        }];
+        let mut trx = PostgresTransaction::begin_write(state.clone())
             .await
             .unwrap();
-        let err =
-            PostgresCatalog::create_project(&project_id, "other_name".to_string(), t.transaction())
-                .await
-                .unwrap_err();
-        assert_eq!(err.error.code, StatusCode::CONFLICT);
-        t.commit().await.unwrap();
+        set_warehouse_event_routing_rules(warehouse_id, &rules, &mut **trx.transaction())
+            .await
+            .unwrap();
+        trx.commit().await.unwrap();
+
+        let loaded = get_warehouse_event_routing_rules(warehouse_id, &pool)
+            .await
+            .unwrap();
+        assert_eq!(loaded, Some(rules));
+        let missing = get_warehouse_event_routing_rules(uuid::Uuid::now_v7().into(), &pool)
+            .await
+            .unwrap();
+        assert_eq!(missing, None);
     }
 
     #[sqlx::test]
-    async fn test_cannot_drop_protected_warehouse(pool: sqlx::PgPool) {
+    async fn test_set_default_branch(pool: sqlx::PgPool) {
         let state = CatalogState::from_pools(pool.clone(), pool.clone());
-        let project_id = ProjectId::from(uuid::Uuid::new_v4());
-        let warehouse_id =
-            initialize_warehouse(state.clone(), None, Some(&project_id), None, true).await;
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+
+        for default_branch in [Some("published"), None] {
+            let mut trx = PostgresTransaction::begin_write(state.clone())
+                .await
+                .unwrap();
+            set_warehouse_default_branch(warehouse_id, default_branch, &mut **trx.transaction())
+                .await
+                .unwrap();
+            trx.commit().await.unwrap();
+
+            let mut trx = PostgresTransaction::begin_read(state.clone())
+                .await
+                .unwrap();
+            let warehouse = get_warehouse(warehouse_id, trx.transaction())
+                .await
+                .unwrap()
+                .unwrap();
+            trx.commit().await.unwrap();
+            assert_eq!(warehouse.default_branch.as_deref(), default_branch);
+        }
+
         let mut trx = PostgresTransaction::begin_write(state.clone())
             .await
             .unwrap();
-        set_warehouse_protection(warehouse_id, true, trx.transaction())
-            .await
-            .unwrap();
-        let e = delete_warehouse(
-            warehouse_id,
-            DeleteWarehouseQuery { force: false },
-            trx.transaction(),
+        let e = set_warehouse_default_branch(
+            uuid::Uuid::now_v7().into(),
+            Some("published"),
+            &mut **trx.transaction(),
         )
         .await
         .unwrap_err();
-        assert_eq!(e.error.code, StatusCode::CONFLICT);
-        set_warehouse_protection(warehouse_id, false, trx.transaction())
-            .await
-            .unwrap();
-        delete_warehouse(
-            warehouse_id,
-            DeleteWarehouseQuery { force: false },
-            trx.transaction(),
-        )
-        .await
-        .unwrap();
-
-        trx.commit().await.unwrap();
+        assert_eq!(e.error.code, StatusCode::NOT_FOUND);
     }
 
     #[sqlx::test]
-    async fn test_can_force_drop_protected_warehouse(pool: sqlx::PgPool) {
+    async fn test_warehouse_slugs(pool: sqlx::PgPool) {
         let state = CatalogState::from_pools(pool.clone(), pool.clone());
         let project_id = ProjectId::from(uuid::Uuid::new_v4());
         let warehouse_id =
             initialize_warehouse(state.clone(), None, Some(&project_id), None, true).await;
-        let mut trx = PostgresTransaction::begin_write(state.clone())
+        assert!(resolve_warehouse_slug("sales", state.clone())
+            .await
+            .unwrap()
+            .is_none());
+
+        for slug in ["sales", "sales-eu"] {
+            let mut trx = PostgresTransaction::begin_write(state.clone())
+                .await
+                .unwrap();
+            set_warehouse_slug(warehouse_id, slug, trx.transaction())
+                .await
+                .unwrap();
+            trx.commit().await.unwrap();
+        }
+
+        // The replaced slug keeps resolving to the warehouse
+        for slug in ["sales", "sales-eu"] {
+            let resolved = resolve_warehouse_slug(slug, state.clone())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(resolved.warehouse_id, warehouse_id);
+            assert_eq!(resolved.slug, "sales-eu");
+        }
+
+        let mut trx = PostgresTransaction::begin_read(state.clone())
             .await
             .unwrap();
-        set_warehouse_protection(warehouse_id, true, trx.transaction())
+        let slugs = list_warehouse_slugs(warehouse_id, &mut **trx.transaction())
             .await
             .unwrap();
-        delete_warehouse(
-            warehouse_id,
-            DeleteWarehouseQuery { force: true },
+        trx.commit().await.unwrap();
+        assert_eq!(
+            slugs
+                .iter()
+                .map(|s| (s.slug.as_str(), s.is_current))
+                .collect::<Vec<_>>(),
+            vec![("sales-eu", true), ("sales", false)]
+        );
+
+        // Aliases can't be used by other warehouses
+        let mut trx = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let other_warehouse_id = PostgresCatalog::create_warehouse(
+            "other_warehouse".to_string(),
+            &project_id,
+            StorageProfile::S3(
+                S3Profile::builder()
+                    .bucket("test_bucket".to_string())
+                    .region("us-east-1".to_string())
+                    .flavor(S3Flavor::S3Compat)
+                    .sts_enabled(false)
+                    .build(),
+            ),
+            TabularDeleteProfile::Hard {},
+            None,
             trx.transaction(),
         )
         .await
         .unwrap();
+        let err = set_warehouse_slug(other_warehouse_id, "sales", trx.transaction())
+            .await
+            .unwrap_err();
+        assert_eq!(err.error.code, StatusCode::CONFLICT);
+        trx.rollback().await.unwrap();
+
+        // Switching back to an alias makes it current again
+        let mut trx = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        set_warehouse_slug(warehouse_id, "sales", trx.transaction())
+            .await
+            .unwrap();
+        trx.commit().await.unwrap();
+        let resolved = resolve_warehouse_slug("sales-eu", state.clone())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(resolved.slug, "sales");
 
+        let mut trx = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        delete_warehouse_slugs(warehouse_id, &mut **trx.transaction())
+            .await
+            .unwrap();
         trx.commit().await.unwrap();
+        assert!(resolve_warehouse_slug("sales", state.clone())
+            .await
+            .unwrap()
+            .is_none());
     }
 
     #[sqlx::test]
@@ -1184,6 +2499,76 @@ pub(crate) mod test {
         assert!(stats.next_page_token.is_none());
     }
 
+    #[sqlx::test]
+    async fn test_retired_storage_secrets(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let due = SecretIdent::from(uuid::Uuid::now_v7());
+        let pending = SecretIdent::from(uuid::Uuid::now_v7());
+
+        retire_storage_secret(
+            warehouse_id,
+            due,
+            chrono::Utc::now() - chrono::Duration::seconds(1),
+            &pool,
+        )
+        .await
+        .unwrap();
+        retire_storage_secret(
+            warehouse_id,
+            pending,
+            chrono::Utc::now() + chrono::Duration::hours(1),
+            &pool,
+        )
+        .await
+        .unwrap();
+
+        let mut trx = pool.begin().await.unwrap();
+        let claimed = claim_due_retired_storage_secrets(10, &mut *trx)
+            .await
+            .unwrap();
+        assert_eq!(claimed, vec![due]);
+
+        // Claimed secrets are locked for other workers
+        let mut other = pool.begin().await.unwrap();
+        assert!(claim_due_retired_storage_secrets(10, &mut *other)
+            .await
+            .unwrap()
+            .is_empty());
+        other.rollback().await.unwrap();
+
+        delete_retired_storage_secrets(&claimed, &mut *trx)
+            .await
+            .unwrap();
+        trx.commit().await.unwrap();
+        assert!(claim_due_retired_storage_secrets(10, &pool)
+            .await
+            .unwrap()
+            .is_empty());
+
+        // Retiring a secret again never postpones its deletion
+        retire_storage_secret(
+            warehouse_id,
+            pending,
+            chrono::Utc::now() + chrono::Duration::hours(2),
+            &pool,
+        )
+        .await
+        .unwrap();
+        retire_storage_secret(
+            warehouse_id,
+            pending,
+            chrono::Utc::now() - chrono::Duration::seconds(1),
+            &pool,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            claim_due_retired_storage_secrets(10, &pool).await.unwrap(),
+            vec![pending]
+        );
+    }
+
     #[sqlx::test]
     async fn test_delete_non_existing_warehouse(pool: sqlx::PgPool) {
         let state = CatalogState::from_pools(pool.clone(), pool.clone());
@@ -34,6 +34,8 @@ pub use utoipa;
 
 #[cfg(feature = "router")]
 pub mod metrics;
+#[cfg(feature = "otel")]
+pub mod otel;
 mod retry;
 #[cfg(feature = "router")]
 pub(crate) mod tracing;
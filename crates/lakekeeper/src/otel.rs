@@ -0,0 +1,91 @@
+//! Export of spans via OpenTelemetry (OTLP).
+//!
+//! If `CONFIG.otlp_traces_endpoint` is set, spans of REST handlers, catalog queries,
+//! storage operations and authorization checks are exported. Request spans continue
+//! the trace of the W3C `traceparent` header of incoming requests, so that catalog
+//! calls show up inside the traces of query engines.
+
+use http::HeaderMap;
+use opentelemetry::{global, propagation::Extractor, trace::TracerProvider as _, KeyValue};
+use opentelemetry_otlp::WithExportConfig as _;
+use opentelemetry_sdk::{
+    propagation::TraceContextPropagator,
+    runtime,
+    trace::{Tracer, TracerProvider},
+    Resource,
+};
+use tracing::{Span, Subscriber};
+use tracing_opentelemetry::{OpenTelemetryLayer, OpenTelemetrySpanExt as _};
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::CONFIG;
+
+/// Flushes pending spans when dropped. Must be kept alive until the process exits.
+#[derive(Debug)]
+pub struct OtlpTracingGuard {
+    provider: TracerProvider,
+}
+
+impl Drop for OtlpTracingGuard {
+    fn drop(&mut self) {
+        if let Err(e) = self.provider.shutdown() {
+            tracing::warn!("Failed to flush OTLP spans: {e}");
+        }
+    }
+}
+
+/// Layer that exports spans to `CONFIG.otlp_traces_endpoint`.
+/// Returns `None` if no endpoint is configured.
+///
+/// # Errors
+/// Fails if the exporter cannot be created.
+pub fn otlp_tracing_layer<S>(
+) -> anyhow::Result<Option<(OpenTelemetryLayer<S, Tracer>, OtlpTracingGuard)>>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    let Some(endpoint) = &CONFIG.otlp_traces_endpoint else {
+        return Ok(None);
+    };
+
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_tonic()
+        .with_endpoint(endpoint.as_str())
+        .build()?;
+    let provider = TracerProvider::builder()
+        .with_batch_exporter(exporter, runtime::Tokio)
+        .with_resource(Resource::new([
+            KeyValue::new("service.name", CONFIG.otlp_service_name.clone()),
+            KeyValue::new("service.version", env!("CARGO_PKG_VERSION")),
+        ]))
+        .build();
+    let tracer = provider.tracer("lakekeeper");
+
+    global::set_text_map_propagator(TraceContextPropagator::new());
+    global::set_tracer_provider(provider.clone());
+
+    Ok(Some((
+        tracing_opentelemetry::layer().with_tracer(tracer),
+        OtlpTracingGuard { provider },
+    )))
+}
+
+/// Continue the trace of the `traceparent` header, if any.
+/// Without an exporter the propagator is a no-op.
+pub(crate) fn set_remote_parent(span: &Span, headers: &HeaderMap) {
+    let context =
+        global::get_text_map_propagator(|propagator| propagator.extract(&HeaderExtractor(headers)));
+    span.set_parent(context);
+}
+
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|v| v.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(http::HeaderName::as_str).collect()
+    }
+}
@@ -11,7 +11,7 @@ use limes::Authentication;
 use uuid::Uuid;
 
 use crate::{
-    service::{authn::Actor, TabularId},
+    service::{authn::Actor, warehouse_slug::ResolvedWarehouseSlug, TabularId},
     ProjectId, WarehouseId, CONFIG, DEFAULT_PROJECT_ID,
 };
 
@@ -32,8 +32,11 @@ pub struct RequestMetadata {
     authentication: Option<Authentication>,
     base_url: String,
     actor: Actor,
+    impersonated_by: Option<crate::service::UserId>,
     matched_path: Option<Arc<str>>,
     request_method: Method,
+    warehouse_slug: Option<ResolvedWarehouseSlug>,
+    headers: Arc<HeaderMap>,
 }
 
 impl RequestMetadata {
@@ -48,6 +51,25 @@ impl RequestMetadata {
         self
     }
 
+    /// Act as `user_id` for the remainder of the request.
+    /// The authenticated principal is retained as `impersonated_by`.
+    pub fn set_impersonation(
+        &mut self,
+        user_id: crate::service::UserId,
+        impersonated_by: crate::service::UserId,
+    ) -> &mut Self {
+        self.actor = Actor::Principal(user_id);
+        self.impersonated_by = Some(impersonated_by);
+        self
+    }
+
+    /// ID of the admin impersonating the user of this request, if any.
+    /// `user_id()` and `actor()` refer to the impersonated user.
+    #[must_use]
+    pub fn impersonated_by(&self) -> Option<&crate::service::UserId> {
+        self.impersonated_by.as_ref()
+    }
+
     /// ID of the user performing the request.
     /// This returns the underlying user-id, even if a role is assumed.
     /// Please use `actor()` to get the full actor for `AuthZ` decisions.
@@ -69,6 +91,50 @@ impl RequestMetadata {
         &self.request_method
     }
 
+    /// Warehouse of a request to a warehouse-scoped catalog URL
+    /// (`/catalog/w/{slug}/v1/...`).
+    #[must_use]
+    pub fn warehouse_slug(&self) -> Option<&ResolvedWarehouseSlug> {
+        self.warehouse_slug.as_ref()
+    }
+
+    /// Headers of the request. Empty for internal actions.
+    #[must_use]
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// Metadata for actions the catalog performs on its own, such as background tasks,
+    /// rather than on behalf of a request.
+    #[must_use]
+    pub(crate) fn new_internal() -> Self {
+        Self {
+            request_id: Uuid::now_v7(),
+            project_id: None,
+            authentication: None,
+            base_url: CONFIG
+                .base_uri
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_default(),
+            actor: Actor::Anonymous,
+            impersonated_by: None,
+            matched_path: None,
+            request_method: Method::default(),
+            warehouse_slug: None,
+            headers: Arc::default(),
+        }
+    }
+
+    /// Metadata for internal actions that create entities owned by `actor`.
+    #[must_use]
+    pub(crate) fn new_internal_as(actor: Actor) -> Self {
+        Self {
+            actor,
+            ..Self::new_internal()
+        }
+    }
+
     #[cfg(any(test, feature = "test-utils"))]
     #[must_use]
     pub fn new_unauthenticated() -> Self {
@@ -78,8 +144,11 @@ impl RequestMetadata {
             authentication: None,
             base_url: "http://localhost:8181".to_string(),
             actor: Actor::Anonymous,
+            impersonated_by: None,
             matched_path: None,
             request_method: Method::default(),
+            warehouse_slug: None,
+            headers: Arc::default(),
         }
     }
 
@@ -105,8 +174,11 @@ impl RequestMetadata {
             ),
             base_url: "http://localhost:8181".to_string(),
             actor: Actor::Principal(user_id),
+            impersonated_by: None,
             matched_path: None,
             request_method: Method::default(),
+            warehouse_slug: None,
+            headers: Arc::default(),
             project_id: None,
         }
     }
@@ -126,9 +198,12 @@ impl RequestMetadata {
             authentication,
             base_url: base_url.unwrap_or_else(|| "http://localhost:8181".to_string()),
             actor,
+            impersonated_by: None,
             project_id,
             matched_path,
             request_method,
+            warehouse_slug: None,
+            headers: Arc::default(),
         }
     }
 
@@ -258,15 +333,27 @@ pub(crate) async fn create_request_metadata_with_trace_and_project_fn(
         .cloned()
         .map(|mp| Arc::from(mp.as_str()));
     let request_method = request.method().clone();
+    if let Some(matched_path) = &matched_path {
+        let span = tracing::Span::current();
+        span.record("http.route", matched_path.as_ref());
+        span.record("otel.name", format!("{request_method} {matched_path}"));
+    }
+    let warehouse_slug = request.extensions().get::<ResolvedWarehouseSlug>().cloned();
+    // Credentials are not retained, as the metadata is passed on to hooks and tasks.
+    let mut headers = headers;
+    headers.remove(http::header::AUTHORIZATION);
 
     request.extensions_mut().insert(RequestMetadata {
         request_id,
         authentication: None,
         base_url: base_uri,
         actor: Actor::Anonymous,
+        impersonated_by: None,
         project_id,
         matched_path,
         request_method,
+        warehouse_slug,
+        headers: Arc::new(headers),
     });
     next.run(request).await
 }
@@ -14,11 +14,28 @@ use crate::{
             EndpointStatisticsMessage, EndpointStatisticsSink, EndpointStatisticsTracker, FlushMode,
         },
         event_publisher::{
-            CloudEventBackend, CloudEventsMessage, CloudEventsPublisher,
-            CloudEventsPublisherBackgroundTask,
+            routing::CatalogEventRoutingRules, CloudEventBackend, CloudEventsMessage,
+            CloudEventsPublisher, CloudEventsPublisherBackgroundTask,
         },
         health::ServiceHealthProvider,
+        metadata_push::{MetadataPushBackgroundTask, MetadataPushTarget, MetadataPusher},
+        quotas::quota_warning_worker,
+        replica::replica_sync_worker,
+        response_properties::RESPONSE_PROPERTY_RULES,
+        s3_sign_audit::s3_sign_audit_cleanup_worker,
+        search_index::{SearchIndex, SearchIndexer, SearchIndexerBackgroundTask},
+        secret_retirement::retired_secret_cleanup_worker,
+        server_events::{
+            emit_server_event, server_event_cleanup_worker, ServerEvent, ServerEventRecorder,
+            ServerEventType, ServerEventsMessage,
+        },
+        shared_cache,
+        storage_intent::storage_intent_recovery_worker,
+        table_timeline::TableTimelineRecorder,
         task_queue::TaskQueueRegistry,
+        usage::usage_aggregation_worker,
+        warehouse_metrics::warehouse_metrics_worker,
+        watch::WatchNotifier,
         Catalog, EndpointStatisticsTrackerTx, SecretStore, ServerInfo,
     },
     CONFIG,
@@ -64,6 +81,14 @@ pub struct ServeConfiguration<
     /// Emitting cloud events is always registered.
     #[builder(default)]
     pub additional_endpoint_hooks: Option<EndpointHookCollection>,
+    /// External index for the catalog search. If set, entity changes are streamed into
+    /// the index and searches query it before falling back to the catalog.
+    #[builder(default)]
+    pub search_index: Option<Arc<dyn SearchIndex>>,
+    /// External metadata platform that table metadata of warehouses with metadata push
+    /// enabled is pushed to.
+    #[builder(default)]
+    pub metadata_push_target: Option<Arc<dyn MetadataPushTarget>>,
     /// Additional background services / futures to await.
     /// If any of these futures fail, the service will gracefully shut down and exit.
     #[builder(default)]
@@ -93,6 +118,8 @@ pub async fn serve<C: Catalog, S: SecretStore, A: Authorizer, N: Authenticator +
         enable_built_in_task_queues: enable_built_in_queues,
         register_additional_task_queues_fn,
         additional_endpoint_hooks,
+        search_index,
+        metadata_push_target,
         additional_background_services,
     } = config;
 
@@ -105,6 +132,18 @@ pub async fn serve<C: Catalog, S: SecretStore, A: Authorizer, N: Authenticator +
     let server_info = C::get_server_info(catalog_state.clone()).await?;
     validate_server_info(&server_info)?;
 
+    // Load response property rules on startup to fail early on invalid rules
+    let response_property_rules = std::sync::LazyLock::force(&RESPONSE_PROPERTY_RULES);
+    if !response_property_rules.is_empty() {
+        tracing::info!(
+            "Loaded {} response property rules",
+            response_property_rules.len()
+        );
+    }
+
+    // Connect shared caches before the first request is served
+    shared_cache::connect().await?;
+
     // Health checks
     let health_provider = ServiceHealthProvider::new(
         vec![
@@ -120,8 +159,19 @@ pub async fn serve<C: Catalog, S: SecretStore, A: Authorizer, N: Authenticator +
     let cloud_events_background_task = CloudEventsPublisherBackgroundTask {
         source: cloud_events_rx,
         sinks: cloud_event_sinks,
+        routing_rules: Some(Arc::new(CatalogEventRoutingRules::<C>::new(
+            catalog_state.clone(),
+        ))),
     };
 
+    // Server events, recorded in the catalog and optionally published as cloud events
+    let (server_event_recorder, server_events_tx) = ServerEventRecorder::<C>::new(
+        catalog_state.clone(),
+        CONFIG
+            .publish_server_events
+            .then(|| CloudEventsPublisher::new(cloud_events_tx.clone())),
+    );
+
     // Metrics server
     let (layer, metrics_future) =
         crate::metrics::get_axum_layer_and_install_recorder(CONFIG.metrics_port).map_err(|e| {
@@ -144,6 +194,38 @@ pub async fn serve<C: Catalog, S: SecretStore, A: Authorizer, N: Authenticator +
     // Endpoint Hooks
     let mut hooks = additional_endpoint_hooks.unwrap_or(EndpointHookCollection::new(vec![]));
     hooks.append(Arc::new(CloudEventsPublisher::new(cloud_events_tx.clone())));
+    hooks.append(Arc::new(WatchNotifier));
+    hooks.append(Arc::new(TableTimelineRecorder::<C>::new(
+        catalog_state.clone(),
+    )));
+
+    // Search index
+    let search_indexer_background_task = search_index.clone().map(|index| {
+        let (search_index_tx, search_index_rx) = tokio::sync::mpsc::channel(1000);
+        hooks.append(Arc::new(SearchIndexer::new(search_index_tx)));
+        SearchIndexerBackgroundTask::<C> {
+            source: search_index_rx,
+            index,
+            catalog_state: catalog_state.clone(),
+            full_sync_interval: CONFIG
+                .search_index_full_sync_interval_seconds
+                .to_std()
+                .ok()
+                .filter(|interval| !interval.is_zero()),
+        }
+    });
+
+    // Metadata push
+    let metadata_push_background_task = metadata_push_target.map(|target| {
+        let (metadata_push_tx, metadata_push_rx) = tokio::sync::mpsc::channel(1000);
+        hooks.append(Arc::new(MetadataPusher::new(metadata_push_tx)));
+        MetadataPushBackgroundTask::<C> {
+            source: metadata_push_rx,
+            target,
+            catalog_state: catalog_state.clone(),
+            max_attempts: CONFIG.metadata_push_max_attempts,
+        }
+    });
 
     // Task queues
     let mut task_queue_registry = TaskQueueRegistry::new();
@@ -172,6 +254,7 @@ pub async fn serve<C: Catalog, S: SecretStore, A: Authorizer, N: Authenticator +
         endpoint_statistics_tracker_tx: endpoint_statistics_tracker_tx.clone(),
         hooks,
         registered_task_queues: task_queue_registry.registered_task_queues(),
+        search_index,
     })?;
 
     if let Some(modify_router_fn) = modify_router_fn {
@@ -189,6 +272,85 @@ pub async fn serve<C: Catalog, S: SecretStore, A: Authorizer, N: Authenticator +
         }
     });
     let stats_handle = tokio::task::spawn(tracker.run());
+    let server_events_handle = tokio::task::spawn(server_event_recorder.run());
+    // Stops once the router, and with it the `SearchIndexer` hook, is dropped.
+    if let Some(search_indexer_background_task) = search_indexer_background_task {
+        tokio::task::spawn(async move {
+            match search_indexer_background_task.run().await {
+                Ok(()) => tracing::info!("Exiting search indexer task"),
+                Err(e) => tracing::error!("Search indexer task failed: {e}"),
+            }
+        });
+    }
+    // Stops once the router, and with it the `MetadataPusher` hook, is dropped.
+    if let Some(metadata_push_background_task) = metadata_push_background_task {
+        tokio::task::spawn(async move {
+            match metadata_push_background_task.run().await {
+                Ok(()) => tracing::info!("Exiting metadata push task"),
+                Err(e) => tracing::error!("Metadata push task failed: {e}"),
+            }
+        });
+    }
+
+    // Cleans up after pointer updates that were interrupted, e.g. by a crash.
+    tokio::task::spawn(storage_intent_recovery_worker::<C, S>(
+        catalog_state.clone(),
+        secrets_state.clone(),
+    ));
+    // Deletes storage secrets whose grace period after a credential rotation ended.
+    tokio::task::spawn(retired_secret_cleanup_worker::<C, S>(
+        catalog_state.clone(),
+        secrets_state.clone(),
+    ));
+    // Entries are also deleted if auditing has been disabled since they were recorded.
+    tokio::task::spawn(s3_sign_audit_cleanup_worker::<C>(catalog_state.clone()));
+    if CONFIG.metrics_max_warehouses > 0 {
+        tokio::task::spawn(warehouse_metrics_worker::<C>(catalog_state.clone()));
+    }
+    tokio::task::spawn(server_event_cleanup_worker::<C>(catalog_state.clone()));
+    // Raises and clears warnings of projects approaching their quotas.
+    tokio::task::spawn(quota_warning_worker::<C>(
+        catalog_state.clone(),
+        CloudEventsPublisher::new(cloud_events_tx.clone()),
+    ));
+    // Rolls the endpoint statistics up into daily usage per warehouse.
+    tokio::task::spawn(usage_aggregation_worker::<C>(catalog_state.clone()));
+    // Mirrors warehouses of the primary if this instance is a read replica.
+    tokio::task::spawn(replica_sync_worker::<C, A, S>(
+        catalog_state.clone(),
+        authorizer.clone(),
+        secrets_state.clone(),
+    ));
+    // Mirrors AWS Glue Data Catalogs into federated warehouses, if any are configured.
+    #[cfg(feature = "glue-federation")]
+    tokio::task::spawn(crate::service::glue_federation::glue_federation_worker::<
+        C,
+        A,
+        S,
+    >(
+        catalog_state.clone(),
+        authorizer.clone(),
+        secrets_state.clone(),
+    ));
+    #[cfg(not(feature = "glue-federation"))]
+    if !CONFIG.glue_federated_warehouses.is_empty() {
+        tracing::warn!("Federated warehouses are configured, but Lakekeeper was built without the `glue-federation` feature. They are not synced.");
+    }
+    // Mirrors Hive Metastores into federated warehouses, if any are configured.
+    #[cfg(feature = "hms-federation")]
+    tokio::task::spawn(crate::service::hms_federation::hms_federation_worker::<
+        C,
+        A,
+        S,
+    >(
+        catalog_state.clone(),
+        authorizer.clone(),
+        secrets_state.clone(),
+    ));
+    #[cfg(not(feature = "hms-federation"))]
+    if !CONFIG.hms_federated_warehouses.is_empty() {
+        tracing::warn!("Federated warehouses are configured, but Lakekeeper was built without the `hms-federation` feature. They are not synced.");
+    }
 
     let task_runner = task_queue_registry.task_queues_runner();
 
@@ -204,19 +366,37 @@ pub async fn serve<C: Catalog, S: SecretStore, A: Authorizer, N: Authenticator +
         additional_services_futures.push(service);
     }
 
-    tokio::select!(
-        () = task_runner.run_queue_workers(true) => tracing::error!("Task queues failed."),
-        err = service_serve(listener, router) => tracing::error!("Service failed: {err:?}"),
-        _ = metrics_future => tracing::error!("Metrics server failed"),
-        Some(_) = health_handles_stream.next() => tracing::error!("Health check thread failed."),
-        Some(_) = additional_services_futures.next() => tracing::error!("An additional background service finished unexpectedly."),
+    emit_server_event(ServerEvent::new(
+        ServerEventType::Startup,
+        None,
+        serde_json::json!({
+            "version": env!("CARGO_PKG_VERSION"),
+            "bind-addr": bind_addr.to_string(),
+        }),
+    ));
+
+    let reason = tokio::select!(
+        () = task_runner.run_queue_workers(true) => "Task queues failed.".to_string(),
+        err = service_serve(listener, router) => format!("Service failed: {err:?}"),
+        _ = metrics_future => "Metrics server failed".to_string(),
+        Some(_) = health_handles_stream.next() => "Health check thread failed.".to_string(),
+        Some(_) = additional_services_futures.next() => "An additional background service finished unexpectedly.".to_string(),
     );
+    tracing::error!("{reason}");
+    emit_server_event(ServerEvent::new(
+        ServerEventType::Shutdown,
+        None,
+        serde_json::json!({ "reason": reason }),
+    ));
 
     tracing::debug!("Sending shutdown signal to threads");
     cancellation_token.cancel();
     endpoint_statistics_tracker_tx
         .send(EndpointStatisticsMessage::Shutdown)
         .await?;
+    // Server events are published as cloud events, so the recorder stops first.
+    server_events_tx.send(ServerEventsMessage::Shutdown).await?;
+    server_events_handle.await?;
     cloud_events_tx.send(CloudEventsMessage::Shutdown).await?;
 
     // Wait for queues to finish processing
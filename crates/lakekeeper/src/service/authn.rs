@@ -1,4 +1,4 @@
-use std::{fmt::Debug, str::FromStr};
+use std::{fmt::Debug, str::FromStr, sync::LazyLock, time::Duration};
 
 use axum::{
     extract::{Request, State},
@@ -11,10 +11,17 @@ use axum_extra::{
 };
 use http::{HeaderMap, StatusCode};
 use iceberg_ext::catalog::rest::{ErrorModel, IcebergErrorResponse};
-use limes::{format_subject, parse_subject, Authenticator, AuthenticatorEnum, Subject};
+use limes::{
+    format_subject, parse_subject, Authentication, Authenticator, AuthenticatorEnum, PrincipalType,
+    Subject,
+};
 use serde::{Deserialize, Serialize};
 
-use super::{authz::Authorizer, RoleId};
+use super::{
+    authz::{Authorizer, CatalogServerAction},
+    shared_cache::{self, SharedCache},
+    Catalog, RoleId,
+};
 use crate::{
     api::{self},
     request_metadata::RequestMetadata,
@@ -23,6 +30,31 @@ use crate::{
 
 pub const IDP_SEPARATOR: char = '~';
 pub const ASSUME_ROLE_HEADER: &str = "x-assume-role";
+pub const IMPERSONATION_HEADER: &str = "x-impersonation-id";
+
+/// Validated tokens, keyed by the hash of the token.
+static AUTHENTICATION_CACHE: LazyLock<SharedCache<CachedAuthentication>> =
+    LazyLock::new(|| SharedCache::new("authn"));
+
+/// Whether users are active, keyed by user id.
+static USER_ACTIVE_CACHE: LazyLock<SharedCache<bool>> =
+    LazyLock::new(|| SharedCache::new("user-active"));
+
+/// Minimum time between two updates of the last-seen time of a user.
+const LAST_SEEN_UPDATE_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Users whose last-seen time was recorded by this instance within [`LAST_SEEN_UPDATE_INTERVAL`].
+static LAST_SEEN_RECORDED: LazyLock<moka::future::Cache<String, ()>> = LazyLock::new(|| {
+    moka::future::Cache::builder()
+        .max_capacity(10_000)
+        .time_to_live(LAST_SEEN_UPDATE_INTERVAL)
+        .build()
+});
+
+/// Remove the cached activation of a user after it was activated or deactivated.
+pub(crate) async fn invalidate_user_active(user_id: &UserId) {
+    USER_ACTIVE_CACHE.invalidate(&user_id.to_string()).await;
+}
 
 #[derive(
     Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, strum_macros::Display,
@@ -41,9 +73,16 @@ pub enum Actor {
     },
 }
 
-#[derive(Debug, Clone)]
-pub(crate) struct AuthMiddlewareState<T: Authenticator, A: Authorizer> {
+#[derive(Clone)]
+pub(crate) struct AuthMiddlewareState<T: Authenticator, C: Catalog, A: Authorizer> {
     pub authenticator: T,
+    pub catalog_state: C::State,
+    pub authorizer: A,
+}
+
+#[derive(Clone)]
+pub(crate) struct ImpersonationMiddlewareState<C: Catalog, A: Authorizer> {
+    pub catalog_state: C::State,
     pub authorizer: A,
 }
 
@@ -52,6 +91,7 @@ pub struct UserId(Subject);
 
 const OIDC_IDP_ID: &str = "oidc";
 const K8S_IDP_ID: &str = "kubernetes";
+const TOMBSTONE_IDP_ID: &str = "lakekeeper";
 
 #[derive(Debug, Clone)]
 pub enum BuiltInAuthenticators {
@@ -59,6 +99,9 @@ pub enum BuiltInAuthenticators {
     Chain(limes::AuthenticatorChain<AuthenticatorEnum>),
 }
 
+/// Interval in which the keys of the `OpenID` provider are reloaded.
+pub(crate) const JWKS_REFRESH_INTERVAL: Duration = Duration::from_secs(3600);
+
 /// Get the default authenticator configuration from the environment.
 ///
 /// # Errors
@@ -110,12 +153,10 @@ pub async fn get_default_authenticator_from_config() -> anyhow::Result<Option<Bu
     };
 
     let authn_oidc = if let Some(uri) = CONFIG.openid_provider_uri.clone() {
-        let mut authenticator = limes::jwks::JWKSWebAuthenticator::new(
-            uri.as_ref(),
-            Some(std::time::Duration::from_secs(3600)),
-        )
-        .await?
-        .set_idp_id(OIDC_IDP_ID);
+        let mut authenticator =
+            limes::jwks::JWKSWebAuthenticator::new(uri.as_ref(), Some(JWKS_REFRESH_INTERVAL))
+                .await?
+                .set_idp_id(OIDC_IDP_ID);
         if let Some(aud) = &CONFIG.openid_audience {
             tracing::debug!("Setting accepted audiences: {aud:?}");
             authenticator = authenticator.set_accepted_audiences(aud.clone());
@@ -181,8 +222,8 @@ pub async fn get_default_authenticator_from_config() -> anyhow::Result<Option<Bu
 /// Use a limes [`Authenticator`] to Authenticate a request.
 ///
 /// This middleware needs to run after [`create_request_metadata_with_trace_and_project_fn`](crate::request_metadata::create_request_metadata_with_trace_and_project_fn).
-pub(crate) async fn auth_middleware_fn<T: Authenticator, A: Authorizer>(
-    State(state): State<AuthMiddlewareState<T, A>>,
+pub(crate) async fn auth_middleware_fn<T: Authenticator, C: Catalog, A: Authorizer>(
+    State(state): State<AuthMiddlewareState<T, C, A>>,
     authorization: Option<TypedHeader<Authorization<Bearer>>>,
     headers: HeaderMap,
     mut request: Request,
@@ -195,12 +236,8 @@ pub(crate) async fn auth_middleware_fn<T: Authenticator, A: Authorizer>(
         return (StatusCode::UNAUTHORIZED, "Missing authorization header").into_response();
     };
 
-    let authentication = match authenticator.authenticate(authorization.token()).await {
-        Ok(principal) => principal,
-        Err(e) => {
-            tracing::debug!("Failed to authenticate: {}", e);
-            return (StatusCode::UNAUTHORIZED, "Failed to authenticate").into_response();
-        }
+    let Some(authentication) = authenticate(authenticator, authorization.token()).await else {
+        return (StatusCode::UNAUTHORIZED, "Failed to authenticate").into_response();
     };
     let user_id = match UserId::try_new(authentication.subject().clone()) {
         Ok(user_id) => user_id,
@@ -216,6 +253,20 @@ pub(crate) async fn auth_middleware_fn<T: Authenticator, A: Authorizer>(
                 .into_response();
         }
     };
+    match is_user_active::<C>(&user_id, state.catalog_state.clone()).await {
+        Ok(true) => {}
+        Ok(false) => {
+            tracing::debug!("Rejecting token of deactivated user {user_id}");
+            return IcebergErrorResponse::from(ErrorModel::unauthorized(
+                "User is deactivated",
+                "UserDeactivated",
+                None,
+            ))
+            .into_response();
+        }
+        Err(e) => return e.into_response(),
+    }
+    record_user_seen::<C>(&user_id, state.catalog_state.clone()).await;
     let role_id = match extract_role_id(&headers) {
         Ok(role_id) => role_id,
         Err(e) => return e.into_response(),
@@ -240,6 +291,225 @@ pub(crate) async fn auth_middleware_fn<T: Authenticator, A: Authorizer>(
     next.run(request).await
 }
 
+/// Validate a token, using the result of a previous validation if it is cached.
+async fn authenticate<T: Authenticator>(authenticator: &T, token: &str) -> Option<Authentication> {
+    let key = shared_cache::hash_key(token);
+    if let Some(cached) = AUTHENTICATION_CACHE.get(&key).await {
+        match Authentication::try_from(cached) {
+            Ok(authentication) => return Some(authentication),
+            Err(e) => tracing::debug!("Ignoring invalid cached authentication: {}", e.message),
+        }
+    }
+
+    match authenticator.authenticate(token).await {
+        Ok(authentication) => {
+            if shared_cache::is_enabled() {
+                let cached = CachedAuthentication::from(&authentication);
+                let ttl = cached.time_to_expiry();
+                AUTHENTICATION_CACHE.insert(key, cached, ttl).await;
+            }
+            Some(authentication)
+        }
+        Err(e) => {
+            tracing::debug!("Failed to authenticate: {}", e);
+            None
+        }
+    }
+}
+
+/// Check if the user is active, using the cached result if present.
+///
+/// Without a shared cache backend nothing is cached, so every authenticated
+/// request costs an additional query against the catalog database. A
+/// per-instance cache is not used, as deactivations on other instances would
+/// not be picked up until its entries expire.
+async fn is_user_active<C: Catalog>(
+    user_id: &UserId,
+    catalog_state: C::State,
+) -> Result<bool, IcebergErrorResponse> {
+    let key = user_id.to_string();
+    if let Some(active) = USER_ACTIVE_CACHE.get(&key).await {
+        return Ok(active);
+    }
+    let active = C::is_user_active(user_id, catalog_state).await?;
+    USER_ACTIVE_CACHE.insert(key, active, None).await;
+    Ok(active)
+}
+
+/// Update the last-seen time of the user in the background, at most once per
+/// [`LAST_SEEN_UPDATE_INTERVAL`] and instance.
+async fn record_user_seen<C: Catalog>(user_id: &UserId, catalog_state: C::State) {
+    let key = user_id.to_string();
+    if LAST_SEEN_RECORDED.contains_key(&key) {
+        return;
+    }
+    LAST_SEEN_RECORDED.insert(key, ()).await;
+    let user_id = user_id.clone();
+    tokio::spawn(async move {
+        if let Err(e) =
+            C::record_user_seen(&user_id, LAST_SEEN_UPDATE_INTERVAL, catalog_state).await
+        {
+            tracing::warn!(
+                "Failed to record last-seen time of user {user_id}: {}",
+                e.error
+            );
+        }
+    });
+}
+
+/// Serializable form of an [`Authentication`] for the shared cache.
+/// The token header is not cached.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct CachedAuthentication {
+    subject: String,
+    claims: serde_json::Value,
+    name: Option<String>,
+    email: Option<String>,
+    human: Option<bool>,
+}
+
+impl CachedAuthentication {
+    /// Time until the `exp` claim of the token, if present.
+    fn time_to_expiry(&self) -> Option<Duration> {
+        let expires_at = self.claims.get("exp")?.as_i64()?;
+        let seconds = expires_at.saturating_sub(chrono::Utc::now().timestamp());
+        Some(Duration::from_secs(u64::try_from(seconds).unwrap_or(0)))
+    }
+}
+
+impl From<&Authentication> for CachedAuthentication {
+    fn from(authentication: &Authentication) -> Self {
+        Self {
+            subject: format_subject(authentication.subject(), Some(IDP_SEPARATOR)),
+            claims: authentication.claims().clone(),
+            name: authentication.full_name().map(ToString::to_string),
+            email: authentication.email().map(ToString::to_string),
+            human: authentication
+                .principal_type()
+                .map(|t| matches!(t, PrincipalType::Human)),
+        }
+    }
+}
+
+impl TryFrom<CachedAuthentication> for Authentication {
+    type Error = ErrorModel;
+
+    fn try_from(cached: CachedAuthentication) -> Result<Self, Self::Error> {
+        let user_id = UserId::try_from(cached.subject.as_str())?;
+        Ok(Authentication::builder()
+            .token_header(None)
+            .claims(cached.claims)
+            .subject(user_id.into())
+            .name(cached.name)
+            .email(cached.email)
+            .principal_type(cached.human.map(|human| {
+                if human {
+                    PrincipalType::Human
+                } else {
+                    PrincipalType::Application
+                }
+            }))
+            .build())
+    }
+}
+
+/// Switch the actor of a request to the impersonated user if the `x-impersonation-id` header is set.
+///
+/// Only the admin that started the impersonation can use it, and only as long as
+/// the impersonation is neither stopped nor expired.
+/// This middleware needs to run after [`auth_middleware_fn`].
+pub(crate) async fn impersonation_middleware_fn<C: Catalog, A: Authorizer>(
+    State(state): State<ImpersonationMiddlewareState<C, A>>,
+    headers: HeaderMap,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let impersonation_id = match extract_impersonation_id(&headers) {
+        Ok(Some(impersonation_id)) => impersonation_id,
+        Ok(None) => return next.run(request).await,
+        Err(e) => return e.into_response(),
+    };
+    let Some(request_metadata) = request.extensions_mut().get_mut::<RequestMetadata>() else {
+        return next.run(request).await;
+    };
+
+    let impersonator_id = match request_metadata.actor() {
+        Actor::Principal(user_id) => user_id.clone(),
+        Actor::Role { .. } => {
+            return IcebergErrorResponse::from(ErrorModel::bad_request(
+                format!("`{IMPERSONATION_HEADER}` cannot be combined with `{ASSUME_ROLE_HEADER}`"),
+                "ImpersonationWithAssumedRole",
+                None,
+            ))
+            .into_response();
+        }
+        Actor::Anonymous => {
+            return IcebergErrorResponse::from(ErrorModel::unauthorized(
+                "Impersonation requires an authenticated user",
+                "ImpersonationWithoutUser",
+                None,
+            ))
+            .into_response();
+        }
+    };
+
+    let impersonation = match C::get_impersonation(impersonation_id, state.catalog_state).await {
+        Ok(impersonation) => impersonation,
+        Err(e) => return e.into_response(),
+    };
+    let Some(impersonation) =
+        impersonation.filter(|i| i.is_active() && i.impersonator_id == impersonator_id)
+    else {
+        return IcebergErrorResponse::from(ErrorModel::forbidden(
+            format!("Impersonation {impersonation_id} does not exist or is no longer active"),
+            "ImpersonationNotActive",
+            None,
+        ))
+        .into_response();
+    };
+
+    // Impersonation rights might have been revoked since the impersonation was started.
+    if let Err(e) = state
+        .authorizer
+        .require_server_action(request_metadata, CatalogServerAction::CanUpdateUsers)
+        .await
+    {
+        return e.into_response();
+    }
+
+    tracing::info!(
+        impersonation_id = %impersonation_id,
+        impersonator = %impersonator_id,
+        user = %impersonation.user_id,
+        "Executing request with impersonation"
+    );
+    request_metadata.set_impersonation(impersonation.user_id, impersonator_id);
+
+    next.run(request).await
+}
+
+fn extract_impersonation_id(
+    headers: &HeaderMap,
+) -> Result<Option<uuid::Uuid>, IcebergErrorResponse> {
+    headers
+        .get(IMPERSONATION_HEADER)
+        .map(|impersonation_id| {
+            impersonation_id
+                .to_str()
+                .ok()
+                .and_then(|s| uuid::Uuid::from_str(s).ok())
+                .ok_or_else(|| {
+                    ErrorModel::bad_request(
+                        "Failed to parse Impersonation-ID",
+                        "InvalidImpersonationIdError",
+                        None,
+                    )
+                    .into()
+                })
+        })
+        .transpose()
+}
+
 fn extract_role_id(headers: &HeaderMap) -> Result<Option<RoleId>, IcebergErrorResponse> {
     if let Some(role_id) = headers.get(ASSUME_ROLE_HEADER) {
         let role_id = role_id.to_str().map_err(|e| {
@@ -274,6 +544,15 @@ impl UserId {
         Ok(Self(subject))
     }
 
+    /// Anonymized principal that takes over the historical records of a hard-deleted user.
+    #[must_use]
+    pub fn new_tombstone() -> Self {
+        Self(Subject::new(
+            Some(TOMBSTONE_IDP_ID.to_string()),
+            format!("deleted-{}", uuid::Uuid::now_v7()),
+        ))
+    }
+
     #[cfg(test)]
     #[must_use]
     pub fn new_unchecked(idp_id: &str, sub: &str) -> Self {
@@ -466,6 +745,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_cached_authentication_expiry() {
+        let cached = CachedAuthentication {
+            subject: "oidc~123".to_string(),
+            claims: serde_json::json!({"exp": chrono::Utc::now().timestamp() + 60}),
+            name: None,
+            email: None,
+            human: Some(true),
+        };
+        let ttl = cached.time_to_expiry().unwrap();
+        assert!(ttl <= Duration::from_secs(60) && ttl >= Duration::from_secs(58));
+
+        let expired = CachedAuthentication {
+            claims: serde_json::json!({"exp": chrono::Utc::now().timestamp() - 60}),
+            ..cached.clone()
+        };
+        assert_eq!(expired.time_to_expiry(), Some(Duration::ZERO));
+
+        let without_exp = CachedAuthentication {
+            claims: serde_json::json!({}),
+            ..cached
+        };
+        assert_eq!(without_exp.time_to_expiry(), None);
+    }
+
     #[test]
     /// Test special cases:
     /// * empty idp (must not work)
@@ -568,6 +872,27 @@ mod tests {
         assert_eq!(role_id, RoleId::new(this_role_id));
     }
 
+    #[test]
+    fn test_extract_impersonation_id() {
+        let headers = HeaderMap::new();
+        assert_eq!(extract_impersonation_id(&headers).unwrap(), None);
+
+        let mut headers = HeaderMap::new();
+        let impersonation_id = Uuid::now_v7();
+        headers.insert(
+            "X-Impersonation-Id",
+            impersonation_id.to_string().parse().unwrap(),
+        );
+        assert_eq!(
+            extract_impersonation_id(&headers).unwrap(),
+            Some(impersonation_id)
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert(IMPERSONATION_HEADER, "not-a-uuid".parse().unwrap());
+        assert!(extract_impersonation_id(&headers).is_err());
+    }
+
     #[test]
     fn test_actor_serde_principal() {
         let actor = Actor::Principal(UserId::try_from("oidc~123").unwrap());
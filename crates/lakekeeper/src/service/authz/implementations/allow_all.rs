@@ -10,7 +10,8 @@ use crate::{
         authz::{
             Authorizer, CatalogNamespaceAction, CatalogProjectAction, CatalogRoleAction,
             CatalogServerAction, CatalogTableAction, CatalogUserAction, CatalogViewAction,
-            CatalogWarehouseAction, ListProjectsResponse, NamespaceParent,
+            CatalogWarehouseAction, GrantObject, ListProjectsResponse, NamespaceParent,
+            ObjectGrant, ProjectMembership, RoleAccessEntry,
         },
         health::{Health, HealthExt},
         Actor, Catalog, NamespaceId, ProjectId, RoleId, SecretStore, State, TableId, ViewId,
@@ -149,6 +150,15 @@ impl Authorizer for AllowAllAuthorizer {
         Ok(())
     }
 
+    async fn merge_user(
+        &self,
+        _metadata: &RequestMetadata,
+        _source_user_id: UserId,
+        _target_user_id: UserId,
+    ) -> Result<()> {
+        Ok(())
+    }
+
     async fn create_role(
         &self,
         _metadata: &RequestMetadata,
@@ -162,6 +172,36 @@ impl Authorizer for AllowAllAuthorizer {
         Ok(())
     }
 
+    async fn list_role_access(&self, _role_id: RoleId) -> Result<Vec<RoleAccessEntry>> {
+        Ok(vec![])
+    }
+
+    async fn revoke_role_access(&self, _role_id: RoleId, _entry: &RoleAccessEntry) -> Result<()> {
+        Ok(())
+    }
+
+    async fn list_object_grants(&self, _objects: &[GrantObject]) -> Result<Vec<ObjectGrant>> {
+        Ok(vec![])
+    }
+
+    async fn write_object_grants(&self, _grants: &[ObjectGrant]) -> Result<()> {
+        Ok(())
+    }
+
+    async fn delete_object_grants(&self, _grants: &[ObjectGrant]) -> Result<()> {
+        Ok(())
+    }
+
+    async fn add_project_member(
+        &self,
+        _project_id: &ProjectId,
+        _user_id: &UserId,
+        _membership: ProjectMembership,
+        _roles: &[RoleId],
+    ) -> Result<()> {
+        Ok(())
+    }
+
     async fn create_project(
         &self,
         _metadata: &RequestMetadata,
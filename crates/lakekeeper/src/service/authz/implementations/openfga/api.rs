@@ -40,6 +40,7 @@ use crate::{
         authz::implementations::openfga::{
             entities::OpenFgaEntity, OpenFGAAuthorizer, OpenFGAError, OpenFGAResult,
         },
+        table_timeline::{record_table_activities, TableActivity, TableTimelineEventType},
         Actor, Catalog, NamespaceId, Result, RoleId, SecretStore, State, TableId, ViewId,
     },
     ProjectId, WarehouseId,
@@ -1161,6 +1162,12 @@ async fn update_table_assignments_by_id<C: Catalog, S: SecretStore>(
     Json(request): Json<UpdateTableAssignmentsRequest>,
 ) -> Result<StatusCode> {
     let authorizer = api_context.v1_state.authz;
+    let activity = TableActivity::new(
+        table_id,
+        TableTimelineEventType::GrantsChanged,
+        &metadata,
+        serde_json::to_value(&request).unwrap_or_default(),
+    );
     checked_write(
         authorizer,
         metadata.actor(),
@@ -1169,6 +1176,7 @@ async fn update_table_assignments_by_id<C: Catalog, S: SecretStore>(
         &table_id.to_openfga(),
     )
     .await?;
+    record_table_activities::<C>(vec![activity], api_context.v1_state.catalog);
 
     Ok(StatusCode::NO_CONTENT)
 }
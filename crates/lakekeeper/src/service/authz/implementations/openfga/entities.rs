@@ -6,9 +6,12 @@ use super::RoleAssignee;
 use crate::{
     service::{
         authn::{Actor, UserId},
-        authz::implementations::{
-            openfga::{OpenFGAError, OpenFGAResult},
-            FgaType,
+        authz::{
+            implementations::{
+                openfga::{OpenFGAError, OpenFGAResult},
+                FgaType,
+            },
+            GrantObject,
         },
         NamespaceId, RoleId, TableId, ViewId,
     },
@@ -237,6 +240,26 @@ impl OpenFgaEntity for ViewId {
     }
 }
 
+impl OpenFgaEntity for GrantObject {
+    fn to_openfga(&self) -> String {
+        match self {
+            GrantObject::Warehouse(id) => WarehouseId::from(*id).to_openfga(),
+            GrantObject::Namespace(id) => NamespaceId::from(*id).to_openfga(),
+            GrantObject::Table(id) => TableId::from(*id).to_openfga(),
+            GrantObject::View(id) => ViewId::from(*id).to_openfga(),
+        }
+    }
+
+    fn openfga_type(&self) -> FgaType {
+        match self {
+            GrantObject::Warehouse(_) => FgaType::Warehouse,
+            GrantObject::Namespace(_) => FgaType::Namespace,
+            GrantObject::Table(_) => FgaType::Table,
+            GrantObject::View(_) => FgaType::View,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
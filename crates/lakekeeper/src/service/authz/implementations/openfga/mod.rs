@@ -6,6 +6,7 @@ use std::{
 };
 
 use axum::Router;
+use futures::{StreamExt as _, TryStreamExt as _};
 use openfga_client::{
     client::{
         CheckRequestTupleKey, ReadRequestTupleKey, ReadResponse, Tuple, TupleKey,
@@ -21,9 +22,11 @@ use crate::{
         authn::Actor,
         authz::{
             Authorizer, CatalogNamespaceAction, CatalogProjectAction, CatalogServerAction,
-            CatalogTableAction, CatalogViewAction, CatalogWarehouseAction, ErrorModel,
-            ListProjectsResponse, Result,
+            CatalogTableAction, CatalogViewAction, CatalogWarehouseAction, ErrorModel, GrantObject,
+            ListProjectsResponse, ObjectGrant, ProjectMembership, Result, RoleAccessEntry,
+            RoleAccessKind,
         },
+        shared_cache::SharedCache,
         NamespaceId, TableId,
     },
     ProjectId, WarehouseId, CONFIG,
@@ -50,8 +53,8 @@ pub(crate) use migration::migrate;
 pub(crate) use models::{OpenFgaType, RoleAssignee};
 use openfga_client::client::BasicOpenFgaClient;
 use relations::{
-    NamespaceRelation, ProjectRelation, RoleRelation, ServerRelation, TableRelation, ViewRelation,
-    WarehouseRelation,
+    NamespaceRelation, ProjectRelation, ReducedRelation as _, RoleRelation, ServerRelation,
+    TableRelation, UserOrRole, ViewRelation, WarehouseRelation,
 };
 use tokio::sync::RwLock;
 use utoipa::OpenApi;
@@ -70,6 +73,8 @@ use crate::{
 };
 
 const MAX_TUPLES_PER_WRITE: i32 = 100;
+/// Maximum number of concurrent reads when listing or checking grants of many objects.
+const MAX_CONCURRENT_READS: usize = 16;
 
 static AUTH_CONFIG: LazyLock<crate::config::OpenFGAConfig> =
     LazyLock::new(|| CONFIG.openfga.clone().expect("OpenFGAConfig not found"));
@@ -93,6 +98,13 @@ static CONFIGURED_MODEL_VERSION: LazyLock<Option<AuthorizationModelVersion>> = L
 pub(crate) static OPENFGA_SERVER: LazyLock<String> =
     LazyLock::new(|| format!("server:{}", CONFIG.server_id));
 
+// Written tuples can only grant access and deleted tuples can only revoke access,
+// so positive and negative decisions are invalidated separately.
+static ALLOWED_CHECKS: LazyLock<SharedCache<bool>> =
+    LazyLock::new(|| SharedCache::new("openfga-allowed"));
+static DENIED_CHECKS: LazyLock<SharedCache<bool>> =
+    LazyLock::new(|| SharedCache::new("openfga-denied"));
+
 #[derive(Clone, Debug)]
 pub struct OpenFGAAuthorizer {
     client: BasicOpenFgaClient,
@@ -204,6 +216,24 @@ impl Authorizer for OpenFGAAuthorizer {
         Ok(metadata.actor().is_authenticated())
     }
 
+    async fn list_actor_roles(&self, metadata: &RequestMetadata) -> Result<Vec<RoleId>> {
+        match metadata.actor() {
+            // Requests of an assumed role are authorized with this role only
+            Actor::Role { assumed_role, .. } => Ok(vec![*assumed_role]),
+            Actor::Anonymous => Ok(vec![]),
+            actor @ Actor::Principal(_) => self
+                .list_objects(
+                    FgaType::Role.to_string(),
+                    RoleRelation::Assignee.to_string(),
+                    actor.to_openfga(),
+                )
+                .await?
+                .iter()
+                .map(|r| RoleId::parse_from_openfga(r).map_err(Into::into))
+                .collect(),
+        }
+    }
+
     async fn is_allowed_role_action(
         &self,
         metadata: &RequestMetadata,
@@ -371,6 +401,16 @@ impl Authorizer for OpenFGAAuthorizer {
         self.delete_all_relations(&user_id).await
     }
 
+    async fn merge_user(
+        &self,
+        _metadata: &RequestMetadata,
+        source_user_id: UserId,
+        target_user_id: UserId,
+    ) -> Result<()> {
+        self.move_user_relations(&source_user_id, &target_user_id)
+            .await
+    }
+
     async fn create_role(
         &self,
         metadata: &RequestMetadata,
@@ -407,6 +447,207 @@ impl Authorizer for OpenFGAAuthorizer {
         self.delete_all_relations(&role_id).await
     }
 
+    async fn list_role_access(&self, role_id: RoleId) -> Result<Vec<RoleAccessEntry>> {
+        let members = self
+            .read_all(ReadRequestTupleKey {
+                user: String::new(),
+                relation: RoleRelation::Assignee.to_string(),
+                object: role_id.to_openfga(),
+            })
+            .await?
+            .into_iter()
+            .filter_map(|t| t.key)
+            .map(|t| RoleAccessEntry {
+                kind: RoleAccessKind::Member,
+                principal: t.user,
+                relation: t.relation,
+                object: t.object,
+            });
+
+        let assignee = RoleAssignee::from_role(role_id).to_openfga();
+        let grants = futures::future::try_join_all(FgaType::Role.user_of().iter().map(|o| {
+            self.read_all(ReadRequestTupleKey {
+                user: assignee.clone(),
+                relation: String::new(),
+                object: format!("{o}:"),
+            })
+        }))
+        .await?
+        .into_iter()
+        .flatten()
+        .filter_map(|t| t.key)
+        .map(|t| RoleAccessEntry {
+            kind: RoleAccessKind::Grant,
+            principal: t.user,
+            relation: t.relation,
+            object: t.object,
+        });
+
+        Ok(members.chain(grants).collect())
+    }
+
+    async fn revoke_role_access(&self, role_id: RoleId, entry: &RoleAccessEntry) -> Result<()> {
+        let belongs_to_role = match entry.kind {
+            RoleAccessKind::Member => entry.object == role_id.to_openfga(),
+            RoleAccessKind::Grant => {
+                entry.principal == RoleAssignee::from_role(role_id).to_openfga()
+            }
+        };
+        if !belongs_to_role {
+            return Err(ErrorModel::bad_request(
+                format!("Access entry does not belong to role {role_id}"),
+                "RoleAccessEntryMismatch",
+                None,
+            )
+            .into());
+        }
+
+        // Deleting a tuple that does not exist fails in OpenFGA
+        let exists = !self
+            .read(
+                1,
+                ReadRequestTupleKey {
+                    user: entry.principal.clone(),
+                    relation: entry.relation.clone(),
+                    object: entry.object.clone(),
+                },
+                None,
+            )
+            .await?
+            .tuples
+            .is_empty();
+        if exists {
+            self.write(
+                None,
+                Some(vec![TupleKeyWithoutCondition {
+                    user: entry.principal.clone(),
+                    relation: entry.relation.clone(),
+                    object: entry.object.clone(),
+                }]),
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn list_object_grants(&self, objects: &[GrantObject]) -> Result<Vec<ObjectGrant>> {
+        let tuples = futures::stream::iter(objects.iter().map(|object| async move {
+            let tuples = self
+                .read_all(ReadRequestTupleKey {
+                    user: String::new(),
+                    relation: String::new(),
+                    object: object.to_openfga(),
+                })
+                .await?;
+            OpenFGAResult::Ok((*object, tuples))
+        }))
+        .buffered(MAX_CONCURRENT_READS)
+        .try_collect::<Vec<_>>()
+        .await?;
+
+        // Users of parent relations are objects, not users or roles
+        Ok(tuples
+            .into_iter()
+            .flat_map(|(object, tuples)| {
+                tuples
+                    .into_iter()
+                    .filter_map(|t| t.key)
+                    .filter_map(move |t| {
+                        let principal = UserOrRole::parse_from_openfga(&t.user).ok()?;
+                        Some(ObjectGrant {
+                            object,
+                            relation: t.relation,
+                            principal: principal.into(),
+                        })
+                    })
+            })
+            .collect())
+    }
+
+    async fn write_object_grants(&self, grants: &[ObjectGrant]) -> Result<()> {
+        // Writing a tuple that already exists fails in OpenFGA
+        let missing = self
+            .filter_existing_grants(grants, false)
+            .await?
+            .into_iter()
+            .map(|t| TupleKey {
+                user: t.user,
+                relation: t.relation,
+                object: t.object,
+                condition: None,
+            })
+            .collect::<Vec<_>>();
+        for chunk in missing.chunks(MAX_TUPLES_PER_WRITE.unsigned_abs() as usize) {
+            self.write(Some(chunk.to_vec()), None).await?;
+        }
+        Ok(())
+    }
+
+    async fn delete_object_grants(&self, grants: &[ObjectGrant]) -> Result<()> {
+        // Deleting a tuple that does not exist fails in OpenFGA
+        let existing = self.filter_existing_grants(grants, true).await?;
+        for chunk in existing.chunks(MAX_TUPLES_PER_WRITE.unsigned_abs() as usize) {
+            self.write(None, Some(chunk.to_vec())).await?;
+        }
+        Ok(())
+    }
+
+    async fn add_project_member(
+        &self,
+        project_id: &ProjectId,
+        user_id: &UserId,
+        membership: ProjectMembership,
+        roles: &[RoleId],
+    ) -> Result<()> {
+        let user = user_id.to_openfga();
+        let membership = TupleKeyWithoutCondition {
+            user: user.clone(),
+            relation: membership.to_openfga().to_string(),
+            object: project_id.to_openfga(),
+        };
+        let assignments = roles.iter().map(|role_id| TupleKeyWithoutCondition {
+            user: user.clone(),
+            relation: RoleRelation::Assignee.to_string(),
+            object: role_id.to_openfga(),
+        });
+
+        // Writing a tuple that already exists fails in OpenFGA
+        let missing = futures::stream::iter(std::iter::once(membership).chain(assignments).map(
+            |tuple| async move {
+                let exists = !self
+                    .read(
+                        1,
+                        ReadRequestTupleKey {
+                            user: tuple.user.clone(),
+                            relation: tuple.relation.clone(),
+                            object: tuple.object.clone(),
+                        },
+                        None,
+                    )
+                    .await?
+                    .tuples
+                    .is_empty();
+                OpenFGAResult::Ok((!exists).then_some(TupleKey {
+                    user: tuple.user,
+                    relation: tuple.relation,
+                    object: tuple.object,
+                    condition: None,
+                }))
+            },
+        ))
+        .buffered(MAX_CONCURRENT_READS)
+        .try_collect::<Vec<_>>()
+        .await?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+        if missing.is_empty() {
+            return Ok(());
+        }
+        self.write(Some(missing), None).await.map_err(Into::into)
+    }
+
     async fn create_project(
         &self,
         metadata: &RequestMetadata,
@@ -680,9 +921,19 @@ impl OpenFGAAuthorizer {
         writes: impl Into<Option<Vec<TupleKey>>>,
         deletes: impl Into<Option<Vec<TupleKeyWithoutCondition>>>,
     ) -> OpenFGAResult<()> {
+        let writes: Option<Vec<TupleKey>> = writes.into();
+        let deletes: Option<Vec<TupleKeyWithoutCondition>> = deletes.into();
+        let has_writes = writes.as_ref().is_some_and(|w| !w.is_empty());
+        let has_deletes = deletes.as_ref().is_some_and(|d| !d.is_empty());
         self.client.write(writes, deletes).await.inspect_err(|e| {
             tracing::error!("Failed to write to OpenFGA: {e}");
         })?;
+        if has_writes {
+            DENIED_CHECKS.invalidate_all().await;
+        }
+        if has_deletes {
+            ALLOWED_CHECKS.invalidate_all().await;
+        }
         Ok(())
     }
 
@@ -714,15 +965,33 @@ impl OpenFGAAuthorizer {
             .map_err(Into::into)
     }
 
-    /// A convenience wrapper around check
+    /// A convenience wrapper around check. Decisions are cached in the shared cache.
     async fn check(&self, tuple_key: impl Into<CheckRequestTupleKey>) -> OpenFGAResult<bool> {
-        self.client
+        let tuple_key = tuple_key.into();
+        let key = format!(
+            "{}|{}|{}",
+            tuple_key.user, tuple_key.relation, tuple_key.object
+        );
+        if ALLOWED_CHECKS.get(&key).await.is_some() {
+            return Ok(true);
+        }
+        if DENIED_CHECKS.get(&key).await.is_some() {
+            return Ok(false);
+        }
+
+        let allowed = self
+            .client
             .check(tuple_key, None, None, false)
             .await
             .inspect_err(|e| {
                 tracing::error!("Failed to check with OpenFGA: {e}");
-            })
-            .map_err(Into::into)
+            })?;
+        if allowed {
+            ALLOWED_CHECKS.insert(key, true, None).await;
+        } else {
+            DENIED_CHECKS.insert(key, false, None).await;
+        }
+        Ok(allowed)
     }
 
     async fn require_action(
@@ -820,6 +1089,40 @@ impl OpenFGAAuthorizer {
         Ok(())
     }
 
+    /// Tuples of the grants that exist in `OpenFGA` if `exists` is true,
+    /// or that don't exist if `exists` is false.
+    async fn filter_existing_grants(
+        &self,
+        grants: &[ObjectGrant],
+        exists: bool,
+    ) -> Result<Vec<TupleKeyWithoutCondition>> {
+        let tuples = futures::stream::iter(grants.iter().map(|grant| async move {
+            let tuple = TupleKeyWithoutCondition {
+                user: UserOrRole::from(&grant.principal).to_openfga(),
+                relation: grant.relation.clone(),
+                object: grant.object.to_openfga(),
+            };
+            let found = !self
+                .read(
+                    1,
+                    ReadRequestTupleKey {
+                        user: tuple.user.clone(),
+                        relation: tuple.relation.clone(),
+                        object: tuple.object.clone(),
+                    },
+                    None,
+                )
+                .await?
+                .tuples
+                .is_empty();
+            OpenFGAResult::Ok((found == exists).then_some(tuple))
+        }))
+        .buffered(MAX_CONCURRENT_READS)
+        .try_collect::<Vec<_>>()
+        .await?;
+        Ok(tuples.into_iter().flatten().collect())
+    }
+
     async fn delete_all_relations(&self, object: &impl OpenFgaEntity) -> Result<()> {
         let object_openfga = object.to_openfga();
         let (own_relations, user_relations) = futures::join!(
@@ -890,14 +1193,66 @@ impl OpenFGAAuthorizer {
         Ok(())
     }
 
+    /// Transfer all relations of the `source` user to the `target` user.
+    /// Relations that the target already has are only removed from the source.
+    async fn move_user_relations(&self, source: &UserId, target: &UserId) -> Result<()> {
+        let source_fga = source.to_openfga();
+        let target_fga = target.to_openfga();
+
+        for object_type in FgaType::User.user_of() {
+            let read_relations = |user: &str| {
+                self.read_all(ReadRequestTupleKey {
+                    user: user.to_string(),
+                    relation: String::new(),
+                    object: format!("{object_type}:"),
+                })
+            };
+            let existing = read_relations(&target_fga)
+                .await?
+                .into_iter()
+                .filter_map(|t| t.key)
+                .map(|k| (k.relation, k.object))
+                .collect::<HashSet<_>>();
+            let keys = read_relations(&source_fga)
+                .await?
+                .into_iter()
+                .filter_map(|t| t.key)
+                .collect::<Vec<_>>();
+
+            // Writes and deletes of one request count towards the same limit
+            for chunk in keys.chunks(MAX_TUPLES_PER_WRITE.unsigned_abs() as usize / 2) {
+                let writes = chunk
+                    .iter()
+                    .filter(|k| !existing.contains(&(k.relation.clone(), k.object.clone())))
+                    .map(|k| TupleKey {
+                        user: target_fga.clone(),
+                        ..k.clone()
+                    })
+                    .collect::<Vec<_>>();
+                let deletes = chunk
+                    .iter()
+                    .map(|k| TupleKeyWithoutCondition {
+                        user: k.user.clone(),
+                        relation: k.relation.clone(),
+                        object: k.object.clone(),
+                    })
+                    .collect::<Vec<_>>();
+                self.write(writes, deletes).await?;
+            }
+        }
+
+        Ok(())
+    }
+
     async fn delete_own_relations(&self, object: &impl OpenFgaEntity) -> Result<()> {
         let object_openfga = object.to_openfga();
         self.client
             .delete_relations_to_object(&object_openfga)
             .await
             .inspect_err(|e| tracing::error!("Failed to delete relations to {object_openfga}: {e}"))
-            .map_err(OpenFGAError::from)
-            .map_err(Into::into)
+            .map_err(OpenFGAError::from)?;
+        ALLOWED_CHECKS.invalidate_all().await;
+        Ok(())
     }
 
     /// A convenience wrapper around `client.list_objects`
@@ -12,6 +12,7 @@ use crate::service::{
     authz::{
         implementations::FgaType, CatalogNamespaceAction, CatalogProjectAction, CatalogRoleAction,
         CatalogServerAction, CatalogTableAction, CatalogViewAction, CatalogWarehouseAction,
+        GrantPrincipal, ProjectMembership,
     },
     Actor, RoleId,
 };
@@ -84,6 +85,24 @@ impl From<RoleId> for UserOrRole {
     }
 }
 
+impl From<UserOrRole> for GrantPrincipal {
+    fn from(user_or_role: UserOrRole) -> Self {
+        match user_or_role {
+            UserOrRole::User(user) => GrantPrincipal::User(user),
+            UserOrRole::Role(role) => GrantPrincipal::Role(*role.role()),
+        }
+    }
+}
+
+impl From<&GrantPrincipal> for UserOrRole {
+    fn from(principal: &GrantPrincipal) -> Self {
+        match principal {
+            GrantPrincipal::User(user) => UserOrRole::User(user.clone()),
+            GrantPrincipal::Role(role) => UserOrRole::Role(RoleAssignee::from_role(*role)),
+        }
+    }
+}
+
 impl ParseOpenFgaEntity for UserOrRole {
     fn try_from_openfga_id(r#type: FgaType, id: &str) -> OpenFGAResult<Self> {
         match r#type {
@@ -361,6 +380,16 @@ impl ReducedRelation for CatalogServerAction {
             CatalogServerAction::CanDeleteUsers => ServerRelation::CanDeleteUsers,
             CatalogServerAction::CanListUsers => ServerRelation::CanListAllProjects,
             CatalogServerAction::CanProvisionUsers => ServerRelation::CanProvisionUsers,
+            // Operators are meant for machines, such as external task workers.
+            CatalogServerAction::CanRunTasks => ServerRelation::Operator,
+            // Granted to admins and operators.
+            CatalogServerAction::CanManageAnnouncements
+            | CatalogServerAction::CanConfigureRequestLog
+            | CatalogServerAction::CanFlushMetadataCache
+            | CatalogServerAction::CanConfigureLogging
+            | CatalogServerAction::CanManageProjectQuotas => ServerRelation::CanCreateProject,
+            CatalogServerAction::CanGetTaskQueueStats
+            | CatalogServerAction::CanListServerEvents => ServerRelation::CanListAllProjects,
         }
     }
 }
@@ -606,6 +635,24 @@ impl ReducedRelation for CatalogProjectAction {
             CatalogProjectAction::CanCreateRole => ProjectRelation::CanCreateRole,
             CatalogProjectAction::CanListRoles => ProjectRelation::CanListRoles,
             CatalogProjectAction::CanSearchRoles => ProjectRelation::CanSearchRoles,
+            CatalogProjectAction::CanManageAccessReviews => ProjectRelation::CanReadAssignments,
+            CatalogProjectAction::CanManagePropertySchemas => ProjectRelation::CanRename,
+            CatalogProjectAction::CanManageInvitations => ProjectRelation::CanGrantSecurityAdmin,
+            CatalogProjectAction::CanManageLabels => ProjectRelation::CanGrantSecurityAdmin,
+        }
+    }
+}
+
+impl ReducedRelation for ProjectMembership {
+    type OpenFgaRelation = ProjectRelation;
+
+    fn to_openfga(&self) -> Self::OpenFgaRelation {
+        match self {
+            ProjectMembership::Describe => ProjectRelation::Describe,
+            ProjectMembership::Select => ProjectRelation::Select,
+            ProjectMembership::Create => ProjectRelation::Create,
+            ProjectMembership::Modify => ProjectRelation::Modify,
+            ProjectMembership::DataAdmin => ProjectRelation::DataAdmin,
         }
     }
 }
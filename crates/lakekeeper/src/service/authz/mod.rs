@@ -41,6 +41,22 @@ pub enum CatalogServerAction {
     CanListUsers,
     /// Can provision user
     CanProvisionUsers,
+    /// Can lease and process tasks of all task queues as an external worker.
+    CanRunTasks,
+    /// Can create, update and delete server-wide announcements.
+    CanManageAnnouncements,
+    /// Can read statistics of all task queues on this server.
+    CanGetTaskQueueStats,
+    /// Can read and change the request log settings of this server.
+    CanConfigureRequestLog,
+    /// Can list lifecycle events of all instances of this server.
+    CanListServerEvents,
+    /// Can flush the table metadata cache of this server.
+    CanFlushMetadataCache,
+    /// Can change log levels and trace targets of this server.
+    CanConfigureLogging,
+    /// Can change the quotas of any project.
+    CanManageProjectQuotas,
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, strum_macros::Display, EnumIter, EnumString)]
@@ -55,6 +71,10 @@ pub enum CatalogProjectAction {
     CanCreateRole,
     CanListRoles,
     CanSearchRoles,
+    CanManageAccessReviews,
+    CanManagePropertySchemas,
+    CanManageInvitations,
+    CanManageLabels,
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, strum_macros::Display, EnumIter, EnumString)]
@@ -150,12 +170,164 @@ pub enum ListProjectsResponse {
     All,
 }
 
+/// Kind of access a role entry of an access review refers to.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize, utoipa::ToSchema,
+)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(feature = "sqlx-postgres", derive(sqlx::Type))]
+#[cfg_attr(
+    feature = "sqlx-postgres",
+    sqlx(type_name = "recertification_access_kind", rename_all = "kebab-case")
+)]
+pub enum RoleAccessKind {
+    /// A user or role that is assigned to the role.
+    Member,
+    /// A privilege that is granted to the role on an object.
+    Grant,
+}
+
+/// A single member or grant of a role.
+/// `principal`, `relation` and `object` are identifiers of the authorizer.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RoleAccessEntry {
+    pub kind: RoleAccessKind,
+    pub principal: String,
+    pub relation: String,
+    pub object: String,
+}
+
+/// Catalog object that privileges can be granted on.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+    utoipa::ToSchema,
+)]
+#[serde(tag = "type", content = "id", rename_all = "kebab-case")]
+pub enum GrantObject {
+    Warehouse(uuid::Uuid),
+    Namespace(uuid::Uuid),
+    Table(uuid::Uuid),
+    View(uuid::Uuid),
+}
+
+impl GrantObject {
+    #[must_use]
+    pub fn id(&self) -> uuid::Uuid {
+        match self {
+            GrantObject::Warehouse(id)
+            | GrantObject::Namespace(id)
+            | GrantObject::Table(id)
+            | GrantObject::View(id) => *id,
+        }
+    }
+}
+
+impl std::fmt::Display for GrantObject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GrantObject::Warehouse(id) => write!(f, "warehouse {id}"),
+            GrantObject::Namespace(id) => write!(f, "namespace {id}"),
+            GrantObject::Table(id) => write!(f, "table {id}"),
+            GrantObject::View(id) => write!(f, "view {id}"),
+        }
+    }
+}
+
+/// User or role that a privilege is granted to.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+#[serde(tag = "type", content = "id", rename_all = "kebab-case")]
+pub enum GrantPrincipal {
+    #[schema(value_type = String)]
+    User(UserId),
+    #[schema(value_type = uuid::Uuid)]
+    Role(RoleId),
+}
+
+impl std::fmt::Display for GrantPrincipal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GrantPrincipal::User(user_id) => write!(f, "user {user_id}"),
+            GrantPrincipal::Role(role_id) => write!(f, "role {role_id}"),
+        }
+    }
+}
+
+/// A privilege that is granted directly to a user or role on a catalog object.
+/// `relation` is an identifier of the authorizer.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ObjectGrant {
+    pub object: GrantObject,
+    pub relation: String,
+    pub principal: GrantPrincipal,
+}
+
+/// Privilege on a project that is granted to users who redeem an invitation.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+    strum_macros::Display,
+    utoipa::ToSchema,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+#[cfg_attr(feature = "sqlx-postgres", derive(sqlx::Type))]
+#[cfg_attr(
+    feature = "sqlx-postgres",
+    sqlx(type_name = "project_membership", rename_all = "kebab-case")
+)]
+pub enum ProjectMembership {
+    /// Can see the project and its warehouses.
+    #[default]
+    Describe,
+    /// Can read data of all warehouses of the project.
+    Select,
+    /// Can create warehouses and objects inside them.
+    Create,
+    /// Can modify all objects of the project.
+    Modify,
+    /// Can manage all warehouses of the project, without granting privileges.
+    DataAdmin,
+}
+
 #[derive(Debug, Clone)]
 pub enum NamespaceParent {
     Warehouse(WarehouseId),
     Namespace(NamespaceId),
 }
 
+/// Authorization of a request that changed the catalog.
+///
+/// Attached to the events of the change, so that consumers can verify that
+/// it was authorized without querying the catalog or the authorizer.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AuthorizationContext {
+    /// Identifies the decision in the logs of the server.
+    pub decision_id: uuid::Uuid,
+    /// Action that was granted on all affected entities.
+    pub action: String,
+    /// Project of the affected entities.
+    pub project_id: ProjectId,
+    /// Roles of the actor. Only the assumed role if the actor assumed a role.
+    pub roles: Vec<RoleId>,
+}
+
 #[async_trait::async_trait]
 /// Interface to provide AuthZ functions to the catalog.
 /// The provided `Actor` argument of all methods except `check_actor`
@@ -189,6 +361,15 @@ where
     /// Search users
     async fn can_search_users(&self, metadata: &RequestMetadata) -> Result<bool>;
 
+    /// Roles the actor of the request is assigned to.
+    /// Authorizers without role assignments return the assumed role, if any.
+    async fn list_actor_roles(&self, metadata: &RequestMetadata) -> Result<Vec<RoleId>> {
+        Ok(match metadata.actor() {
+            Actor::Role { assumed_role, .. } => vec![*assumed_role],
+            Actor::Principal(_) | Actor::Anonymous => vec![],
+        })
+    }
+
     /// Return Ok(true) if the action is allowed, otherwise return Ok(false).
     /// Return Err for internal errors.
     async fn is_allowed_user_action(
@@ -269,6 +450,15 @@ where
     /// Hook that is called when a user is deleted.
     async fn delete_user(&self, metadata: &RequestMetadata, user_id: UserId) -> Result<()>;
 
+    /// Hook that is called when a user is merged into another user.
+    /// All permissions of `source_user_id` are transferred to `target_user_id`.
+    async fn merge_user(
+        &self,
+        metadata: &RequestMetadata,
+        source_user_id: UserId,
+        target_user_id: UserId,
+    ) -> Result<()>;
+
     /// Hook that is called when a new project is created.
     /// This is used to set up the initial permissions for the project.
     async fn create_role(
@@ -282,6 +472,38 @@ where
     /// This is used to clean up permissions for the role.
     async fn delete_role(&self, metadata: &RequestMetadata, role_id: RoleId) -> Result<()>;
 
+    /// List the members of a role and the privileges granted to it.
+    /// Used to generate the items of access reviews.
+    async fn list_role_access(&self, role_id: RoleId) -> Result<Vec<RoleAccessEntry>>;
+
+    /// Revoke a member or grant of a role as returned by [`Authorizer::list_role_access`].
+    /// Entries that no longer exist are ignored.
+    async fn revoke_role_access(&self, role_id: RoleId, entry: &RoleAccessEntry) -> Result<()>;
+
+    /// List the privileges granted directly to users and roles on the given objects.
+    /// Relations between objects, such as the parent of a table, are not included.
+    /// Used to snapshot the grants of a warehouse.
+    async fn list_object_grants(&self, objects: &[GrantObject]) -> Result<Vec<ObjectGrant>>;
+
+    /// Write grants as returned by [`Authorizer::list_object_grants`].
+    /// Grants that already exist are ignored.
+    async fn write_object_grants(&self, grants: &[ObjectGrant]) -> Result<()>;
+
+    /// Delete grants as returned by [`Authorizer::list_object_grants`].
+    /// Grants that no longer exist are ignored.
+    async fn delete_object_grants(&self, grants: &[ObjectGrant]) -> Result<()>;
+
+    /// Hook that is called when a user redeems an invitation to a project.
+    /// Grants `membership` on the project and assigns the user to `roles` of the project.
+    /// Privileges and assignments the user already has are kept.
+    async fn add_project_member(
+        &self,
+        project_id: &ProjectId,
+        user_id: &UserId,
+        membership: ProjectMembership,
+        roles: &[RoleId],
+    ) -> Result<()>;
+
     /// Hook that is called when a new project is created.
     /// This is used to set up the initial permissions for the project.
     async fn create_project(
@@ -368,6 +590,7 @@ where
         }
     }
 
+    #[tracing::instrument(skip_all, fields(%action))]
     async fn require_user_action(
         &self,
         metadata: &RequestMetadata,
@@ -389,6 +612,7 @@ where
         }
     }
 
+    #[tracing::instrument(skip_all, fields(%action))]
     async fn require_role_action(
         &self,
         metadata: &RequestMetadata,
@@ -410,6 +634,7 @@ where
         }
     }
 
+    #[tracing::instrument(skip_all, fields(%action))]
     async fn require_server_action(
         &self,
         metadata: &RequestMetadata,
@@ -428,6 +653,7 @@ where
         }
     }
 
+    #[tracing::instrument(skip_all, fields(%action))]
     async fn require_project_action(
         &self,
         metadata: &RequestMetadata,
@@ -450,6 +676,7 @@ where
         }
     }
 
+    #[tracing::instrument(skip_all, fields(%action))]
     async fn require_warehouse_action(
         &self,
         metadata: &RequestMetadata,
@@ -472,6 +699,7 @@ where
         }
     }
 
+    #[tracing::instrument(skip_all, fields(%action))]
     async fn require_namespace_action(
         &self,
         metadata: &RequestMetadata,
@@ -511,6 +739,7 @@ where
         }
     }
 
+    #[tracing::instrument(skip_all, fields(%action))]
     async fn require_table_action<T: TableUuid + Send>(
         &self,
         metadata: &RequestMetadata,
@@ -545,6 +774,7 @@ where
         }
     }
 
+    #[tracing::instrument(skip_all, fields(%action))]
     async fn require_view_action(
         &self,
         metadata: &RequestMetadata,
@@ -864,6 +1094,15 @@ pub(crate) mod tests {
             Ok(())
         }
 
+        async fn merge_user(
+            &self,
+            _metadata: &RequestMetadata,
+            _source_user_id: UserId,
+            _target_user_id: UserId,
+        ) -> Result<()> {
+            Ok(())
+        }
+
         async fn create_role(
             &self,
             _metadata: &RequestMetadata,
@@ -877,6 +1116,40 @@ pub(crate) mod tests {
             Ok(())
         }
 
+        async fn list_role_access(&self, _role_id: RoleId) -> Result<Vec<RoleAccessEntry>> {
+            Ok(vec![])
+        }
+
+        async fn revoke_role_access(
+            &self,
+            _role_id: RoleId,
+            _entry: &RoleAccessEntry,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn list_object_grants(&self, _objects: &[GrantObject]) -> Result<Vec<ObjectGrant>> {
+            Ok(vec![])
+        }
+
+        async fn write_object_grants(&self, _grants: &[ObjectGrant]) -> Result<()> {
+            Ok(())
+        }
+
+        async fn delete_object_grants(&self, _grants: &[ObjectGrant]) -> Result<()> {
+            Ok(())
+        }
+
+        async fn add_project_member(
+            &self,
+            _project_id: &ProjectId,
+            _user_id: &UserId,
+            _membership: ProjectMembership,
+            _roles: &[RoleId],
+        ) -> Result<()> {
+            Ok(())
+        }
+
         async fn create_project(
             &self,
             _metadata: &RequestMetadata,
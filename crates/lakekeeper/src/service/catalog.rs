@@ -4,18 +4,35 @@ use std::{
 };
 
 use iceberg::{
-    spec::{TableMetadata, ViewMetadata},
+    spec::{MetadataLog, SnapshotLog, TableMetadata, ViewMetadata},
     TableUpdate,
 };
 pub use iceberg_ext::catalog::rest::{CommitTableResponse, CreateTableRequest};
 use iceberg_ext::{
-    catalog::rest::{CatalogConfig, ErrorModel},
+    catalog::rest::{
+        CatalogConfig, ErrorModel, PlanStatus, PlanTableScanRequest, ScanTasks, TableStats,
+    },
     configs::Location,
 };
 
 use super::{
-    authz::TableUuid, storage::StorageProfile, NamespaceId, ProjectId, RoleId, TableId,
-    TabularDetails, ViewId, WarehouseId, WarehouseStatus,
+    authz::TableUuid,
+    client_compatibility::ClientWorkaround,
+    endpoint_policy::EndpointGroup,
+    event_publisher::routing::EventRoutingRule,
+    labels::{LabelEntity, LabelSettings, Labels},
+    property_schemas::PropertySchema,
+    quotas::{ProjectQuotaKind, ProjectQuotaUsage, ProjectQuotaWarning, ProjectQuotas},
+    request_log::RequestLogSettings,
+    server_events::{ServerEvent, ServerEventType},
+    storage::StorageProfile,
+    table_lineage::{LineageEdge, LineageTable, TableLineage},
+    table_timeline::TableActivity,
+    usage::WarehouseUsageDay,
+    view_lineage::{ReferencingView, ViewLineageSource},
+    warehouse_metrics::WarehouseEntityCounts,
+    warehouse_slug::{ResolvedWarehouseSlug, WarehouseSlug},
+    NamespaceId, ProjectId, RoleId, TableId, TabularDetails, ViewId, WarehouseId, WarehouseStatus,
 };
 pub use crate::api::iceberg::v1::{
     CreateNamespaceRequest, CreateNamespaceResponse, ListNamespacesQuery, NamespaceIdent, Result,
@@ -25,14 +42,38 @@ use crate::{
     api::{
         iceberg::v1::{namespace::NamespaceDropFlags, PaginatedMapping, PaginationQuery},
         management::v1::{
+            announcement::{
+                Announcement, CreateAnnouncementRequest, ListAnnouncementsResponse,
+                UpdateAnnouncementRequest,
+            },
+            invitation::{CreateInvitationRequest, ListInvitationsResponse, ProjectInvitation},
             project::{EndpointStatisticsResponse, TimeWindowSelector, WarehouseFilter},
+            recertification::{
+                CreateRecertificationCampaignRequest, ListRecertificationCampaignsResponse,
+                RecertificationCampaign, RecertificationItem, RecertificationItemDecision,
+            },
+            referenced_files::ListReferencedFilesResponse,
             role::{ListRolesResponse, Role, SearchRoleResponse},
-            user::{ListUsersResponse, SearchUserResponse, User, UserLastUpdatedWith, UserType},
+            search::{CatalogSearchFilter, GlobalSearchMatch, SearchCatalogResult},
+            server_event::ListServerEventsResponse,
+            table::{
+                ColumnTagSuggestion, ColumnTagSuggestionKey, ColumnTagSuggestionStatus,
+                CreateTablePropertyProposalRequest, GetTableTimelineResponse,
+                ListTableSigningsResponse, PreparedTableWriterCommit, TablePropertyProposal,
+                TablePropertyProposalStatus, TableSigning, TableWriter, TableWriterCheckpoint,
+                TableWriterState, TaggedColumn,
+            },
+            task::{DeadLetterTask, ListDeadLetterTasksResponse},
+            task_schedule::ListTaskSchedulesResponse,
+            user::{
+                Impersonation, ListUsersResponse, MergeUserResponse, SearchUserResponse, User,
+                UserAttributes, UserLastUpdatedWith, UserReferenceReport, UserType,
+            },
             warehouse::{
                 GetTaskQueueConfigResponse, SetTaskQueueConfigRequest, TabularDeleteProfile,
                 WarehouseStatisticsResponse,
             },
-            DeleteWarehouseQuery, ProtectionResponse,
+            DeleteWarehouseQuery, ListSort, ProtectionResponse,
         },
     },
     catalog::tables::TableMetadataDiffs,
@@ -40,11 +81,31 @@ use crate::{
     service::{
         authn::UserId,
         health::HealthExt,
+        storage_intent::StorageIntent,
         tabular_idents::{TabularId, TabularIdentOwned},
         task_queue::{
-            tabular_expiration_queue, tabular_expiration_queue::TabularExpirationPayload,
-            tabular_purge_queue, tabular_purge_queue::TabularPurgePayload, Status, Task,
-            TaskCheckState, TaskFilter, TaskId, TaskInput, TaskMetadata,
+            catalog_import_queue,
+            catalog_import_queue::{
+                CatalogImport, CatalogImportPayload, CatalogImportSource, CatalogImportStatus,
+                CatalogImportTable,
+            },
+            catalog_snapshot_export_queue,
+            catalog_snapshot_export_queue::{
+                CatalogSnapshot, CatalogSnapshotExportPayload, CatalogSnapshotState,
+            },
+            orphan_file_cleanup_queue,
+            orphan_file_cleanup_queue::OrphanFileCleanupPayload,
+            pii_detection_queue,
+            pii_detection_queue::{PiiDetectionPayload, PiiSuggestion},
+            schedule::{TaskSchedule, TaskScheduleInput},
+            snapshot_expiration_queue,
+            snapshot_expiration_queue::SnapshotExpirationPayload,
+            tabular_expiration_queue,
+            tabular_expiration_queue::TabularExpirationPayload,
+            tabular_purge_queue,
+            tabular_purge_queue::TabularPurgePayload,
+            EntityId, Status, Task, TaskCheckState, TaskFilter, TaskId, TaskInfo, TaskInput,
+            TaskLogEntry, TaskMetadata, TaskQueueStats,
         },
     },
     SecretIdent,
@@ -99,6 +160,15 @@ pub struct LoadTableResponse {
     pub metadata_location: Option<Location>,
     pub storage_secret_ident: Option<SecretIdent>,
     pub storage_profile: StorageProfile,
+    pub external: bool,
+}
+
+/// Version of a table or view, which changes with every commit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TabularVersion {
+    /// `None` for staged tables
+    pub metadata_location: Option<String>,
+    pub deleted: bool,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -111,6 +181,7 @@ pub struct GetTableMetadataResponse {
     pub metadata_location: Option<String>,
     pub storage_secret_ident: Option<SecretIdent>,
     pub storage_profile: StorageProfile,
+    pub external: bool,
 }
 
 impl TableUuid for GetTableMetadataResponse {
@@ -143,6 +214,20 @@ pub struct GetWarehouseResponse {
     pub tabular_delete_profile: TabularDeleteProfile,
     /// Whether the warehouse is protected from being deleted.
     pub protected: bool,
+    /// Endpoint groups that are disabled for the warehouse.
+    pub disabled_endpoint_groups: Vec<EndpointGroup>,
+    /// Client workarounds that are enabled for the warehouse.
+    pub client_workarounds: Vec<ClientWorkaround>,
+    /// Branch that is served as `main` when loading tables of the warehouse.
+    pub default_branch: Option<String>,
+    /// Whether table changes of the warehouse are pushed to the external metadata platform.
+    pub metadata_push_enabled: bool,
+    /// Labels of the warehouse.
+    pub labels: Labels,
+    /// Time the warehouse was created.
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Time the warehouse was last updated.
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Debug, Clone)]
@@ -179,6 +264,20 @@ pub struct TableCreation<'c> {
     pub(crate) table_ident: &'c TableIdent,
     pub(crate) metadata_location: Option<&'c Location>,
     pub(crate) table_metadata: TableMetadata,
+    /// External tables are registered tables whose files are owned by another system.
+    /// They are never purged, not maintained and only read credentials are vended for them.
+    pub(crate) external: bool,
+    /// History of a registered table recovered from previous metadata files.
+    pub(crate) imported_history: TableHistoryImport,
+}
+
+/// Metadata and snapshot log entries of a registered table which are older than
+/// the entries of its current metadata. Both logs are ordered oldest first and are
+/// stored before the entries of the current metadata.
+#[derive(Debug, Clone, Default)]
+pub struct TableHistoryImport {
+    pub(crate) metadata_log: Vec<MetadataLog>,
+    pub(crate) snapshot_log: Vec<SnapshotLog>,
 }
 
 #[derive(Debug, Clone)]
@@ -218,6 +317,8 @@ pub struct NamespaceInfo {
 pub struct NamespaceDropInfo {
     pub child_namespaces: Vec<NamespaceId>,
     pub child_tables: Vec<(TabularId, String)>,
+    /// Subset of `child_tables` that are external tables and must not be purged.
+    pub external_tables: HashSet<TabularId>,
     pub open_tasks: Vec<TaskId>,
 }
 
@@ -226,6 +327,8 @@ pub struct TableInfo {
     pub table_ident: TableIdent,
     pub deletion_details: Option<DeletionDetails>,
     pub protected: bool,
+    /// Whether the table is external. Always `false` for views.
+    pub external: bool,
 }
 
 #[derive(Debug, PartialEq)]
@@ -233,6 +336,8 @@ pub struct TabularInfo {
     pub table_ident: TabularIdentOwned,
     pub deletion_details: Option<DeletionDetails>,
     pub protected: bool,
+    /// Whether the table is external. Always `false` for views.
+    pub external: bool,
 }
 
 impl TabularInfo {
@@ -245,6 +350,7 @@ impl TabularInfo {
             table_ident: self.table_ident.into_table()?,
             deletion_details: self.deletion_details,
             protected: self.protected,
+            external: self.external,
         })
     }
 
@@ -257,6 +363,7 @@ impl TabularInfo {
             table_ident: self.table_ident.into_view()?,
             deletion_details: self.deletion_details,
             protected: self.protected,
+            external: self.external,
         })
     }
 }
@@ -317,6 +424,36 @@ where
         request_metadata: &RequestMetadata,
     ) -> Result<Option<CatalogConfig>>;
 
+    /// Endpoint groups that are disabled for the warehouse.
+    ///
+    /// Return Ok(None) if the warehouse does not exist.
+    async fn get_warehouse_disabled_endpoint_groups(
+        warehouse_id: WarehouseId,
+        catalog_state: Self::State,
+    ) -> Result<Option<Vec<EndpointGroup>>>;
+
+    /// Client workarounds that are enabled for the warehouse.
+    ///
+    /// Return Ok(None) if the warehouse does not exist.
+    async fn get_warehouse_client_workarounds(
+        warehouse_id: WarehouseId,
+        catalog_state: Self::State,
+    ) -> Result<Option<Vec<ClientWorkaround>>>;
+
+    /// Number of namespaces, tables, views and pending soft-deletes of all active warehouses.
+    async fn get_warehouse_entity_counts(
+        catalog_state: Self::State,
+    ) -> Result<Vec<WarehouseEntityCounts>>;
+
+    /// Resolve a slug of a warehouse-scoped URL to its warehouse.
+    /// Replaced slugs resolve to the warehouse they were replaced in.
+    ///
+    /// Return Ok(None) if no warehouse uses the slug.
+    async fn resolve_warehouse_slug(
+        slug: &str,
+        catalog_state: Self::State,
+    ) -> Result<Option<ResolvedWarehouseSlug>>;
+
     /// Wrapper around get_config_for_warehouse that returns
     /// not found error if the warehouse does not exist.
     async fn require_config_for_warehouse(
@@ -375,6 +512,37 @@ where
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
     ) -> Result<NamespaceDropInfo>;
 
+    /// Soft-delete a namespace, its child namespaces and all tables and views they contain.
+    /// The dropped tables and views are returned in `child_tables` so that their expiration
+    /// can be scheduled.
+    ///
+    /// If neither the namespace nor its children contain any tables or views, nothing would
+    /// ever expire them, so the namespaces are deleted immediately and `None` is returned.
+    async fn mark_namespace_as_deleted<'a>(
+        warehouse_id: WarehouseId,
+        namespace_id: NamespaceId,
+        flags: NamespaceDropFlags,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<NamespaceDropInfo>>;
+
+    /// Undrop a soft-deleted namespace.
+    ///
+    /// Restores the namespace, the child namespaces dropped together with it and the
+    /// tables and views that were dropped as part of the same cascade.
+    /// Returns the ids of the expiration tasks of the restored tables and views.
+    async fn undrop_namespace<'a>(
+        warehouse_id: WarehouseId,
+        namespace_id: NamespaceId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<TaskId>>;
+
+    /// Hard-delete soft-deleted namespaces that no longer contain any tables or views.
+    /// Returns the ids of the deleted namespaces.
+    async fn drop_emptied_namespaces<'a>(
+        warehouse_id: WarehouseId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<NamespaceId>>;
+
     /// Update the properties of a namespace.
     ///
     /// The properties are the final key-value properties that should
@@ -399,6 +567,12 @@ where
         pagination_query: PaginationQuery,
     ) -> Result<PaginatedMapping<TableId, TableInfo>>;
 
+    /// Statistics of the given tables. Tables that don't exist are omitted.
+    async fn get_table_stats<'a>(
+        table_ids: &[TableId],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<HashMap<TableId, TableStats>>;
+
     /// Return Err only on unexpected errors, not if the table does not exist.
     /// If include_staged is true, also return staged tables.
     /// If the table does not exist, return Ok(None).
@@ -516,6 +690,8 @@ where
         filter_project_id: Option<ProjectId>,
         filter_role_id: Option<Vec<RoleId>>,
         filter_name: Option<String>,
+        // If None, roles are ordered by id
+        sort: Option<ListSort>,
         pagination: PaginationQuery,
         catalog_state: Self::State,
     ) -> Result<ListRolesResponse>;
@@ -531,6 +707,26 @@ where
         catalog_state: Self::State,
     ) -> Result<SearchRoleResponse>;
 
+    /// Search namespaces, tables and views of all active warehouses of a project.
+    /// Returns at most `limit` entities, most recently modified first.
+    async fn search_catalog(
+        project_id: &ProjectId,
+        filter: &CatalogSearchFilter,
+        limit: i64,
+        catalog_state: Self::State,
+    ) -> Result<Vec<SearchCatalogResult>>;
+
+    /// Fuzzy search over the names, comments and property values of namespaces, tables
+    /// and views of all active warehouses of a project.
+    /// Returns at most `limit` matches, one per entity, best matches first.
+    async fn global_search(
+        project_id: &ProjectId,
+        search: &str,
+        warehouse_id: Option<WarehouseId>,
+        limit: i64,
+        catalog_state: Self::State,
+    ) -> Result<Vec<GlobalSearchMatch>>;
+
     // ---------------- User Management API ----------------
     async fn create_or_update_user<'a>(
         user_id: &UserId,
@@ -548,9 +744,14 @@ where
     ) -> Result<SearchUserResponse>;
 
     /// Return Ok(vec[]) if the user does not exist.
+    /// `filter_inactive_for_days` only returns users that have not been seen for the given
+    /// number of days. Users that were never seen are inactive since their creation.
     async fn list_user(
         filter_user_id: Option<Vec<UserId>>,
         filter_name: Option<String>,
+        filter_user_type: Option<UserType>,
+        filter_inactive_for_days: Option<u32>,
+        sort: ListSort,
         pagination: PaginationQuery,
         catalog_state: Self::State,
     ) -> Result<ListUsersResponse>;
@@ -560,6 +761,69 @@ where
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
     ) -> Result<Option<()>>;
 
+    /// Activate or deactivate a user.
+    /// Return Ok(None) if the user does not exist.
+    async fn set_user_active<'a>(
+        user_id: UserId,
+        active: bool,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<()>>;
+
+    /// Return false if the user exists and is deactivated.
+    /// Users that are not provisioned are active.
+    async fn is_user_active(user_id: &UserId, catalog_state: Self::State) -> Result<bool>;
+
+    /// Replace all attributes of the user.
+    /// Return Ok(None) if the user does not exist.
+    async fn set_user_attributes<'a>(
+        user_id: &UserId,
+        attributes: &UserAttributes,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<()>>;
+
+    /// Record that the user was authenticated now.
+    /// Skipped if the user was seen within `min_interval` or is not provisioned.
+    async fn record_user_seen(
+        user_id: &UserId,
+        min_interval: std::time::Duration,
+        catalog_state: Self::State,
+    ) -> Result<()>;
+
+    /// Re-point all records referencing the user to `tombstone_id` and remove the user.
+    /// Return Ok(None) if the user does not exist.
+    async fn hard_delete_user<'a>(
+        user_id: UserId,
+        tombstone_id: UserId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<Vec<UserReferenceReport>>>;
+
+    /// Re-point all records referencing `source_id` to `target_id` and soft-delete the source.
+    /// Return Ok(None) if one of the users does not exist.
+    async fn merge_user<'a>(
+        source_id: UserId,
+        target_id: UserId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<MergeUserResponse>>;
+
+    async fn create_impersonation<'a>(
+        impersonation: &Impersonation,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Return Ok(None) if the impersonation does not exist.
+    /// Ended and expired impersonations are returned as well.
+    async fn get_impersonation(
+        impersonation_id: uuid::Uuid,
+        catalog_state: Self::State,
+    ) -> Result<Option<Impersonation>>;
+
+    /// End an active impersonation.
+    /// Return Ok(None) if no active impersonation with this id exists.
+    async fn end_impersonation<'a>(
+        impersonation_id: uuid::Uuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<Impersonation>>;
+
     // ---------------- Warehouse Management API ----------------
 
     /// Create a warehouse.
@@ -670,6 +934,118 @@ where
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
     ) -> Result<()>;
 
+    /// Set the endpoint groups that are disabled for a warehouse.
+    async fn set_warehouse_disabled_endpoint_groups<'a>(
+        warehouse_id: WarehouseId,
+        disabled_endpoint_groups: &[EndpointGroup],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Set the client workarounds that are enabled for a warehouse.
+    async fn set_warehouse_client_workarounds<'a>(
+        warehouse_id: WarehouseId,
+        client_workarounds: &[ClientWorkaround],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Set the branch that `loadTable` serves as `main` for tables of a warehouse.
+    /// `None` serves the `main` branch of tables.
+    async fn set_warehouse_default_branch<'a>(
+        warehouse_id: WarehouseId,
+        default_branch: Option<&str>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Enable or disable pushing table changes of a warehouse to the
+    /// external metadata platform.
+    async fn set_warehouse_metadata_push<'a>(
+        warehouse_id: WarehouseId,
+        enabled: bool,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Rules routing cloud events of a warehouse to specific sinks and topics.
+    /// Returns `None` if the warehouse does not exist.
+    async fn get_warehouse_event_routing_rules(
+        warehouse_id: WarehouseId,
+        catalog_state: Self::State,
+    ) -> Result<Option<Vec<EventRoutingRule>>>;
+
+    /// Replace the event routing rules of a warehouse.
+    async fn set_warehouse_event_routing_rules<'a>(
+        warehouse_id: WarehouseId,
+        rules: &[EventRoutingRule],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Label definitions and policies of a project. `None` if the project does not exist.
+    async fn get_label_settings<'a>(
+        project_id: &ProjectId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<LabelSettings>>;
+
+    /// Replace the label definitions and policies of a project.
+    async fn set_label_settings<'a>(
+        project_id: &ProjectId,
+        settings: &LabelSettings,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Labels of a warehouse, or of a namespace or table in the warehouse.
+    /// `None` if the entity does not exist in the warehouse.
+    async fn get_labels<'a>(
+        warehouse_id: WarehouseId,
+        entity: LabelEntity,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<Labels>>;
+
+    /// Replace the labels of a warehouse, or of a namespace or table in the warehouse.
+    async fn set_labels<'a>(
+        warehouse_id: WarehouseId,
+        entity: LabelEntity,
+        labels: &Labels,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Labels of a table including those inherited from its warehouse and namespaces.
+    /// Labels of nested entities take precedence.
+    async fn get_effective_table_labels(
+        warehouse_id: WarehouseId,
+        table_id: TableId,
+        catalog_state: Self::State,
+    ) -> Result<Labels>;
+
+    /// Files referenced by a page of tables and views of a warehouse, including
+    /// soft-deleted ones. All files of a page are read consistently.
+    async fn list_referenced_files<'a>(
+        warehouse_id: WarehouseId,
+        pagination_query: PaginationQuery,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<ListReferencedFilesResponse>;
+
+    /// Slugs of a warehouse, the current slug first, followed by
+    /// replaced slugs from newest to oldest.
+    async fn list_warehouse_slugs<'a>(
+        warehouse_id: WarehouseId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<WarehouseSlug>>;
+
+    /// Set the current slug of a warehouse. The previous slug remains an alias
+    /// of the warehouse.
+    ///
+    /// Fails if the slug is used by another warehouse.
+    async fn set_warehouse_slug<'a>(
+        warehouse_id: WarehouseId,
+        slug: &str,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Delete all slugs of a warehouse, including aliases.
+    async fn delete_warehouse_slugs<'a>(
+        warehouse_id: WarehouseId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
     /// Rename a project.
     async fn rename_project<'a>(
         project_id: &ProjectId,
@@ -677,6 +1053,63 @@ where
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
     ) -> Result<()>;
 
+    /// Quotas of a project. Returns `None` if the project does not exist.
+    async fn get_project_quotas<'a>(
+        project_id: &ProjectId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<ProjectQuotas>>;
+
+    /// Replace the quotas of a project.
+    async fn set_project_quotas<'a>(
+        project_id: &ProjectId,
+        quotas: &ProjectQuotas,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Resources of a project that are limited by its quotas.
+    async fn get_project_quota_usage<'a>(
+        project_id: &ProjectId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<ProjectQuotaUsage>;
+
+    /// Projects with a quota warning threshold or active quota warnings.
+    async fn list_projects_with_quota_warnings(state: Self::State) -> Result<Vec<ProjectId>>;
+
+    /// Active quota warnings of a project.
+    async fn list_project_quota_warnings<'a>(
+        project_id: &ProjectId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<ProjectQuotaWarning>>;
+
+    /// Store a raised quota warning.
+    /// Returns `false` if the warning was already active.
+    async fn raise_project_quota_warning<'a>(
+        project_id: &ProjectId,
+        warning: &ProjectQuotaWarning,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<bool>;
+
+    /// Remove a quota warning.
+    /// Returns `false` if the warning was not active.
+    async fn clear_project_quota_warning<'a>(
+        project_id: &ProjectId,
+        quota: ProjectQuotaKind,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<bool>;
+
+    /// Custom property schemas of a project. Returns `None` if the project does not exist.
+    async fn get_property_schemas<'a>(
+        project_id: &ProjectId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<Vec<PropertySchema>>>;
+
+    /// Replace the custom property schemas of a project.
+    async fn set_property_schemas<'a>(
+        project_id: &ProjectId,
+        schemas: &[PropertySchema],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
     /// Set the status of a warehouse.
     async fn set_warehouse_status<'a>(
         warehouse_id: WarehouseId,
@@ -703,6 +1136,14 @@ where
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
     ) -> Result<Option<ViewId>>;
 
+    /// Resolve the identifier of an active view in the warehouse by its id.
+    /// If the view does not exist, return Ok(None).
+    async fn view_id_to_ident<'a>(
+        warehouse_id: WarehouseId,
+        view_id: ViewId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<TableIdent>>;
+
     async fn create_view<'a>(
         namespace_id: NamespaceId,
         view: &TableIdent,
@@ -747,6 +1188,21 @@ where
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
     ) -> Result<()>;
 
+    /// Source tables of the current version of a view, resolved within the warehouse.
+    async fn get_view_lineage(
+        warehouse_id: WarehouseId,
+        view_id: ViewId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Vec<ViewLineageSource>>;
+
+    /// Views of the warehouse whose current version references the table.
+    /// Soft-deleted views are not returned.
+    async fn list_views_referencing_table(
+        warehouse_id: WarehouseId,
+        table_id: TableId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Vec<ReferencingView>>;
+
     async fn list_tabulars(
         warehouse_id: WarehouseId,
         namespace_id: Option<NamespaceId>, // Filter by namespace
@@ -779,6 +1235,14 @@ where
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
     ) -> Result<ProtectionResponse>;
 
+    /// Get the current version of a table or view, including soft-deleted ones.
+    /// Returns `None` if the tabular does not exist in the warehouse.
+    async fn get_tabular_version(
+        warehouse_id: WarehouseId,
+        tabular_id: TabularId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<TabularVersion>>;
+
     async fn set_namespace_protected(
         namespace_id: NamespaceId,
         protect: bool,
@@ -802,6 +1266,14 @@ where
         max_time_since_last_heartbeat: chrono::Duration,
         state: Self::State,
     ) -> Result<Option<Task>>;
+    /// Pick a task for an external worker. If `visibility_timeout` is set, the task is
+    /// handed to another worker once no heartbeat was received for that duration.
+    async fn lease_task(
+        queue_name: &str,
+        max_time_since_last_heartbeat: chrono::Duration,
+        visibility_timeout: Option<chrono::Duration>,
+        state: Self::State,
+    ) -> Result<Option<Task>>;
     async fn record_task_success(
         id: TaskId,
         message: Option<&str>,
@@ -887,6 +1359,17 @@ where
                 .map(|v| v.into_iter().next())?,
         )
     }
+
+    /// Enqueue a single task to a task queue. If a task of the same entity is already
+    /// pending in the queue, its schedule and payload are replaced by the ones of `task`.
+    ///
+    /// Returns `None` if the task of the entity is currently running.
+    async fn enqueue_or_reschedule_task(
+        queue_name: &'static str,
+        task: TaskInput,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<TaskId>>;
+
     async fn cancel_pending_tasks(
         queue_name: &str,
         filter: TaskFilter,
@@ -954,40 +1437,813 @@ where
         .await
     }
 
-    /// Checks task state and sends a hearbeat.
-    ///
-    /// This is used to send a heartbeat and check whether this task should continue to run.
-    async fn check_and_heartbeat_task(
-        task_id: TaskId,
-        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
-    ) -> Result<Option<TaskCheckState>>;
-
-    /// Sends a stop signal to the task.
-    ///
-    /// This does by no means guarantee that the task will be actually stop. It is up to the task
-    /// handler to decide if it can stop.
-    async fn stop_task(
-        task_id: TaskId,
+    #[tracing::instrument(skip(transaction))]
+    async fn queue_pii_detection(
+        task_metadata: TaskMetadata,
+        payload: PiiDetectionPayload,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
-    ) -> Result<()>;
+    ) -> Result<Option<TaskId>> {
+        Self::enqueue_task(
+            pii_detection_queue::QUEUE_NAME,
+            TaskInput {
+                task_metadata,
+                payload: serde_json::to_value(&payload).map_err(|e| {
+                    ErrorModel::internal(
+                        format!("Failed to serialize task payload: {e}"),
+                        "TaskPayloadSerializationError",
+                        Some(Box::new(e)),
+                    )
+                })?,
+            },
+            transaction,
+        )
+        .await
+    }
 
-    async fn set_task_queue_config(
-        warehouse_id: WarehouseId,
-        queue_name: &str,
-        config: SetTaskQueueConfigRequest,
+    #[tracing::instrument(skip(transaction))]
+    async fn queue_snapshot_expiration(
+        task_metadata: TaskMetadata,
+        payload: SnapshotExpirationPayload,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
-    ) -> Result<()>;
+    ) -> Result<Option<TaskId>> {
+        Self::enqueue_task(
+            snapshot_expiration_queue::QUEUE_NAME,
+            TaskInput {
+                task_metadata,
+                payload: serde_json::to_value(&payload).map_err(|e| {
+                    ErrorModel::internal(
+                        format!("Failed to serialize task payload: {e}"),
+                        "TaskPayloadSerializationError",
+                        Some(Box::new(e)),
+                    )
+                })?,
+            },
+            transaction,
+        )
+        .await
+    }
 
-    async fn get_task_queue_config(
-        warehouse_id: WarehouseId,
-        queue_name: &str,
+    #[tracing::instrument(skip(transaction))]
+    async fn queue_orphan_file_cleanup(
+        task_metadata: TaskMetadata,
+        payload: OrphanFileCleanupPayload,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
-    ) -> Result<Option<GetTaskQueueConfigResponse>>;
-}
-
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub struct ListFlags {
-    pub include_active: bool,
+    ) -> Result<Option<TaskId>> {
+        Self::enqueue_task(
+            orphan_file_cleanup_queue::QUEUE_NAME,
+            TaskInput {
+                task_metadata,
+                payload: serde_json::to_value(&payload).map_err(|e| {
+                    ErrorModel::internal(
+                        format!("Failed to serialize task payload: {e}"),
+                        "TaskPayloadSerializationError",
+                        Some(Box::new(e)),
+                    )
+                })?,
+            },
+            transaction,
+        )
+        .await
+    }
+
+    /// Runs the orphan file cleanup of an entity at `task_metadata.schedule_for`,
+    /// replacing a pending run. Returns `None` if a run is in progress.
+    #[tracing::instrument(skip(transaction))]
+    async fn reschedule_orphan_file_cleanup(
+        task_metadata: TaskMetadata,
+        payload: OrphanFileCleanupPayload,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<TaskId>> {
+        Self::enqueue_or_reschedule_task(
+            orphan_file_cleanup_queue::QUEUE_NAME,
+            TaskInput {
+                task_metadata,
+                payload: serde_json::to_value(&payload).map_err(|e| {
+                    ErrorModel::internal(
+                        format!("Failed to serialize task payload: {e}"),
+                        "TaskPayloadSerializationError",
+                        Some(Box::new(e)),
+                    )
+                })?,
+            },
+            transaction,
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip(transaction))]
+    async fn queue_catalog_snapshot_export(
+        task_metadata: TaskMetadata,
+        payload: CatalogSnapshotExportPayload,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<TaskId>> {
+        Self::enqueue_task(
+            catalog_snapshot_export_queue::QUEUE_NAME,
+            TaskInput {
+                task_metadata,
+                payload: serde_json::to_value(&payload).map_err(|e| {
+                    ErrorModel::internal(
+                        format!("Failed to serialize task payload: {e}"),
+                        "TaskPayloadSerializationError",
+                        Some(Box::new(e)),
+                    )
+                })?,
+            },
+            transaction,
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip(transaction))]
+    async fn queue_catalog_import(
+        task_metadata: TaskMetadata,
+        payload: CatalogImportPayload,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<TaskId>> {
+        Self::enqueue_task(
+            catalog_import_queue::QUEUE_NAME,
+            TaskInput {
+                task_metadata,
+                payload: serde_json::to_value(&payload).map_err(|e| {
+                    ErrorModel::internal(
+                        format!("Failed to serialize task payload: {e}"),
+                        "TaskPayloadSerializationError",
+                        Some(Box::new(e)),
+                    )
+                })?,
+            },
+            transaction,
+        )
+        .await
+    }
+
+    /// Runs the catalog snapshot export of a warehouse at `task_metadata.schedule_for`,
+    /// replacing a pending run. Returns `None` if an export is in progress.
+    #[tracing::instrument(skip(transaction))]
+    async fn reschedule_catalog_snapshot_export(
+        task_metadata: TaskMetadata,
+        payload: CatalogSnapshotExportPayload,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<TaskId>> {
+        Self::enqueue_or_reschedule_task(
+            catalog_snapshot_export_queue::QUEUE_NAME,
+            TaskInput {
+                task_metadata,
+                payload: serde_json::to_value(&payload).map_err(|e| {
+                    ErrorModel::internal(
+                        format!("Failed to serialize task payload: {e}"),
+                        "TaskPayloadSerializationError",
+                        Some(Box::new(e)),
+                    )
+                })?,
+            },
+            transaction,
+        )
+        .await
+    }
+
+    /// Checks task state and sends a hearbeat.
+    ///
+    /// This is used to send a heartbeat and check whether this task should continue to run.
+    async fn check_and_heartbeat_task(
+        task_id: TaskId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<TaskCheckState>>;
+
+    /// Check whether `attempt` of the task is still running and lock the task for the
+    /// remainder of the transaction.
+    async fn is_task_lease_valid(
+        task_id: TaskId,
+        attempt: i32,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<bool>;
+
+    /// Sends a stop signal to the task.
+    ///
+    /// This does by no means guarantee that the task will be actually stop. It is up to the task
+    /// handler to decide if it can stop.
+    async fn stop_task(
+        task_id: TaskId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<()>;
+
+    async fn set_task_queue_config(
+        warehouse_id: WarehouseId,
+        queue_name: &str,
+        config: SetTaskQueueConfigRequest,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<()>;
+
+    async fn get_task_queue_config(
+        warehouse_id: WarehouseId,
+        queue_name: &str,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<GetTaskQueueConfigResponse>>;
+
+    /// Get the scheduled or running task of an entity in a queue.
+    async fn get_entity_task(
+        warehouse_id: WarehouseId,
+        entity_id: EntityId,
+        queue_name: &str,
+        catalog_state: Self::State,
+    ) -> Result<Option<TaskInfo>>;
+
+    /// List the `limit` most recent finished attempts of the tasks of an entity
+    /// in a queue, newest first.
+    async fn list_entity_task_log(
+        warehouse_id: WarehouseId,
+        entity_id: EntityId,
+        queue_name: &str,
+        limit: i64,
+        catalog_state: Self::State,
+    ) -> Result<Vec<TaskLogEntry>>;
+
+    // ---------------- Task Schedules ----------------
+    /// Returns `None` if the tabular does not exist in the warehouse.
+    async fn create_task_schedule(
+        schedule: TaskScheduleInput,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<TaskSchedule>>;
+
+    async fn get_task_schedule(
+        warehouse_id: WarehouseId,
+        schedule_id: uuid::Uuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<TaskSchedule>>;
+
+    async fn list_task_schedules(
+        warehouse_id: WarehouseId,
+        queue_name: Option<&str>,
+        pagination_query: PaginationQuery,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<ListTaskSchedulesResponse>;
+
+    /// Returns `None` if the schedule does not exist.
+    async fn delete_task_schedule(
+        warehouse_id: WarehouseId,
+        schedule_id: uuid::Uuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<()>>;
+
+    /// Pause or resume a schedule. If `next_run_at` is `None`, the next run is kept.
+    /// Returns `None` if the schedule does not exist.
+    async fn set_task_schedule_paused(
+        warehouse_id: WarehouseId,
+        schedule_id: uuid::Uuid,
+        paused: bool,
+        next_run_at: Option<chrono::DateTime<chrono::Utc>>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<TaskSchedule>>;
+
+    /// Lock up to `limit` schedules of active warehouses that are due.
+    /// Schedules locked by other transactions are skipped.
+    async fn claim_due_task_schedules(
+        limit: i64,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Vec<TaskSchedule>>;
+
+    /// Record that a schedule fired. `task_id` is `None` if the run was skipped.
+    async fn record_task_schedule_fire(
+        schedule_id: uuid::Uuid,
+        task_id: Option<TaskId>,
+        next_run_at: chrono::DateTime<chrono::Utc>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<()>;
+
+    // ---------------- Catalog Snapshots ----------------
+    /// Read the catalog state of a warehouse in a single consistent snapshot of the database.
+    /// Returns `None` if the warehouse does not exist.
+    async fn load_catalog_snapshot_state(
+        warehouse_id: WarehouseId,
+        catalog_state: Self::State,
+    ) -> Result<Option<CatalogSnapshotState>>;
+
+    async fn create_catalog_snapshot(
+        snapshot: &CatalogSnapshot,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<()>;
+
+    async fn get_catalog_snapshot(
+        warehouse_id: WarehouseId,
+        snapshot_id: uuid::Uuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<CatalogSnapshot>>;
+
+    /// Most recent snapshots of a warehouse, newest first.
+    async fn list_catalog_snapshots(
+        warehouse_id: WarehouseId,
+        limit: i64,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Vec<CatalogSnapshot>>;
+
+    /// Delete all but the `retain` most recent snapshots of a warehouse.
+    /// Returns the deleted snapshots.
+    async fn delete_expired_catalog_snapshots(
+        warehouse_id: WarehouseId,
+        retain: i64,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Vec<CatalogSnapshot>>;
+
+    // ---------------- Catalog Imports ----------------
+    async fn create_catalog_import(
+        import_id: uuid::Uuid,
+        warehouse_id: WarehouseId,
+        source: &CatalogImportSource,
+        created_by: Option<&str>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<()>;
+
+    /// Returns `None` if the import does not exist.
+    async fn get_catalog_import(
+        warehouse_id: WarehouseId,
+        import_id: uuid::Uuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<CatalogImport>>;
+
+    /// Sets the status of an import. Succeeded and failed imports are marked as finished.
+    async fn set_catalog_import_status(
+        import_id: uuid::Uuid,
+        status: CatalogImportStatus,
+        error: Option<&str>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<()>;
+
+    /// Records the outcome of a table, replacing the outcome of a previous attempt.
+    async fn record_catalog_import_table(
+        import_id: uuid::Uuid,
+        table: &CatalogImportTable,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<()>;
+
+    /// Tables of an import, ordered by namespace and name.
+    async fn list_catalog_import_tables(
+        import_id: uuid::Uuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Vec<CatalogImportTable>>;
+
+    // ---------------- Dead-Letter Queue ----------------
+    async fn list_dead_letter_tasks(
+        warehouse_id: WarehouseId,
+        queue_name: Option<&str>,
+        pagination_query: PaginationQuery,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<ListDeadLetterTasksResponse>;
+
+    async fn get_dead_letter_task(
+        warehouse_id: WarehouseId,
+        task_id: TaskId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<DeadLetterTask>>;
+
+    /// All finished attempts of a task, oldest first.
+    async fn list_task_attempts(
+        warehouse_id: WarehouseId,
+        task_id: TaskId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Vec<TaskLogEntry>>;
+
+    /// Move a dead-lettered task back into its queue as a new task with `new_task_id`.
+    /// Returns `None` if the dead-lettered task does not exist.
+    async fn requeue_dead_letter_task(
+        warehouse_id: WarehouseId,
+        task_id: TaskId,
+        new_task_id: TaskId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<TaskId>>;
+
+    /// Statistics of all queues that have tasks, attempts finished within `window`
+    /// or dead-lettered tasks, ordered by queue name.
+    async fn get_task_queue_stats(
+        window: chrono::Duration,
+        catalog_state: Self::State,
+    ) -> Result<Vec<TaskQueueStats>>;
+
+    // ---------------- Scan Planning ----------------
+    async fn create_scan_plan(
+        plan: ScanPlan,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<()>;
+
+    /// Store the outcome of a submitted scan plan.
+    /// Must not modify plans that are no longer in the `submitted` state.
+    async fn set_scan_plan_result(
+        plan_id: ScanPlanId,
+        result: std::result::Result<ScanTasks, ErrorModel>,
+        catalog_state: Self::State,
+    ) -> Result<()>;
+
+    /// Returns Ok(None) if the plan does not exist or is expired.
+    async fn get_scan_plan(
+        warehouse_id: WarehouseId,
+        table_id: TableId,
+        plan_id: ScanPlanId,
+        catalog_state: Self::State,
+    ) -> Result<Option<ScanPlan>>;
+
+    /// Returns Ok(None) if the plan does not exist or is expired.
+    async fn cancel_scan_plan(
+        warehouse_id: WarehouseId,
+        table_id: TableId,
+        plan_id: ScanPlanId,
+        catalog_state: Self::State,
+    ) -> Result<Option<()>>;
+
+    // ---------------- Column Tags ----------------
+    /// Replace the tags of the given fields of a table.
+    /// Fields mapped to an empty list lose all their tags.
+    async fn set_column_tags(
+        warehouse_id: WarehouseId,
+        table_id: TableId,
+        tags: &HashMap<i32, Vec<String>>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<()>;
+
+    /// Get the tags of all fields of a table, keyed by field id.
+    async fn get_column_tags(
+        table_id: TableId,
+        catalog_state: Self::State,
+    ) -> Result<HashMap<i32, Vec<String>>>;
+
+    /// List all columns of active tables in a warehouse that carry `tag`.
+    /// Columns that are not part of the current schema of their table are skipped.
+    async fn list_tagged_columns(
+        warehouse_id: WarehouseId,
+        tag: &str,
+        catalog_state: Self::State,
+    ) -> Result<Vec<TaggedColumn>>;
+
+    // ---------------- Column Tag Suggestions ----------------
+    /// Replace all pending tag suggestions of a table.
+    /// Suggestions that have already been reviewed are kept and not suggested again.
+    async fn replace_column_tag_suggestions(
+        warehouse_id: WarehouseId,
+        table_id: TableId,
+        suggestions: &[PiiSuggestion],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<()>;
+
+    /// List the tag suggestions of a table, optionally filtered by status.
+    async fn list_column_tag_suggestions(
+        table_id: TableId,
+        status: Option<ColumnTagSuggestionStatus>,
+        catalog_state: Self::State,
+    ) -> Result<Vec<ColumnTagSuggestion>>;
+
+    /// Accept or reject pending tag suggestions of a table. Accepted suggestions
+    /// are added to the column tags.
+    /// Returns the number of reviewed suggestions. Suggestions that don't exist
+    /// or are not pending are not counted.
+    async fn review_column_tag_suggestions(
+        warehouse_id: WarehouseId,
+        table_id: TableId,
+        accept: &[ColumnTagSuggestionKey],
+        reject: &[ColumnTagSuggestionKey],
+        reviewed_by: Option<&str>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<u64>;
+
+    // ---------------- Table Property Proposals ----------------
+    async fn create_table_property_proposal(
+        warehouse_id: WarehouseId,
+        table_id: TableId,
+        proposal_id: uuid::Uuid,
+        request: &CreateTablePropertyProposalRequest,
+        proposed_by: Option<&str>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<TablePropertyProposal>;
+
+    /// List the property proposals of a table, newest first, optionally filtered by status.
+    async fn list_table_property_proposals(
+        table_id: TableId,
+        status: Option<TablePropertyProposalStatus>,
+        catalog_state: Self::State,
+    ) -> Result<Vec<TablePropertyProposal>>;
+
+    /// Set the status of a pending proposal of a table and lock it until the
+    /// transaction ends.
+    /// Returns `None` if the proposal does not exist or is not pending.
+    async fn review_table_property_proposal(
+        table_id: TableId,
+        proposal_id: uuid::Uuid,
+        status: TablePropertyProposalStatus,
+        reviewed_by: Option<&str>,
+        review_comment: Option<&str>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<TablePropertyProposal>>;
+
+    // ---------------- Table Writers ----------------
+    /// Create a writer of a table or start a new epoch of an existing writer.
+    /// Requests of the previous epoch are fenced from now on.
+    async fn begin_table_writer(
+        warehouse_id: WarehouseId,
+        table_id: TableId,
+        writer_id: &str,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<TableWriterState>;
+
+    /// Returns `None` if the writer does not exist.
+    async fn get_table_writer(
+        table_id: TableId,
+        writer_id: &str,
+        catalog_state: Self::State,
+    ) -> Result<Option<TableWriter>>;
+
+    /// Store the changes of a checkpoint, replacing a previously prepared checkpoint.
+    /// Returns `false` if the writer does not exist or `epoch` is not its current epoch.
+    async fn prepare_table_writer_commit(
+        table_id: TableId,
+        writer_id: &str,
+        epoch: i64,
+        checkpoint_id: i64,
+        commit: &PreparedTableWriterCommit,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<bool>;
+
+    /// Mark a checkpoint as committed and clear the prepared checkpoint.
+    /// Must be called in the transaction that commits the changes of the checkpoint.
+    /// Fails with a conflict if the epoch is not current or a checkpoint with the
+    /// same or a higher id has already been committed.
+    async fn complete_table_writer_commit(
+        checkpoint: &TableWriterCheckpoint,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<()>;
+
+    // ---------------- Announcements ----------------
+    async fn create_announcement(
+        announcement_id: uuid::Uuid,
+        request: CreateAnnouncementRequest,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Announcement>;
+
+    /// Returns `None` if the announcement does not exist.
+    async fn get_announcement(
+        announcement_id: uuid::Uuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<Announcement>>;
+
+    /// Returns `None` if the announcement does not exist.
+    async fn update_announcement(
+        announcement_id: uuid::Uuid,
+        request: UpdateAnnouncementRequest,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<Announcement>>;
+
+    /// Returns `None` if the announcement does not exist.
+    async fn delete_announcement(
+        announcement_id: uuid::Uuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<()>>;
+
+    async fn list_announcements(
+        pagination_query: PaginationQuery,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<ListAnnouncementsResponse>;
+
+    /// Announcements that started and did not end yet, most severe first.
+    async fn list_active_announcements(catalog_state: Self::State) -> Result<Vec<Announcement>>;
+
+    // ---------------- Request Log ----------------
+    /// Returns `None` if the settings were never changed via the management API.
+    async fn get_request_log_settings(
+        catalog_state: Self::State,
+    ) -> Result<Option<RequestLogSettings>>;
+
+    async fn set_request_log_settings(
+        settings: RequestLogSettings,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<RequestLogSettings>;
+
+    // ---------------- Access Recertification ----------------
+    async fn create_recertification_campaign(
+        campaign_id: uuid::Uuid,
+        project_id: &ProjectId,
+        request: &CreateRecertificationCampaignRequest,
+        created_by: Option<&str>,
+        items: &[RecertificationItem],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<RecertificationCampaign>;
+
+    /// Returns `None` if the campaign does not exist.
+    async fn get_recertification_campaign(
+        campaign_id: uuid::Uuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<RecertificationCampaign>>;
+
+    /// If `reviewer` is set, only campaigns the user is a reviewer of are returned.
+    async fn list_recertification_campaigns(
+        project_id: &ProjectId,
+        reviewer: Option<&str>,
+        pagination_query: PaginationQuery,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<ListRecertificationCampaignsResponse>;
+
+    async fn list_recertification_items(
+        campaign_id: uuid::Uuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Vec<RecertificationItem>>;
+
+    /// Records the decisions on pending items of the campaign.
+    /// Returns the decided items. Items that are not pending or belong to
+    /// a different campaign are not returned.
+    async fn decide_recertification_items(
+        campaign_id: uuid::Uuid,
+        decisions: &[RecertificationItemDecision],
+        decided_by: Option<&str>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Vec<RecertificationItem>>;
+
+    /// Closes the campaign. If the campaign auto-revokes unreviewed items, pending
+    /// items are marked as auto-revoked and returned.
+    async fn close_recertification_campaign(
+        campaign_id: uuid::Uuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Vec<RecertificationItem>>;
+
+    // ---------------- Project Invitations ----------------
+    async fn create_project_invitation(
+        invitation_id: uuid::Uuid,
+        project_id: &ProjectId,
+        request: &CreateInvitationRequest,
+        token_hash: Option<&str>,
+        expires_at: chrono::DateTime<chrono::Utc>,
+        created_by: Option<&str>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<ProjectInvitation>;
+
+    async fn list_project_invitations(
+        project_id: &ProjectId,
+        pagination_query: PaginationQuery,
+        catalog_state: Self::State,
+    ) -> Result<ListInvitationsResponse>;
+
+    /// Lock the invitation with the given id and / or link token hash for the
+    /// rest of the transaction. Returns `None` if no invitation matches.
+    async fn lock_project_invitation(
+        invitation_id: Option<uuid::Uuid>,
+        token_hash: Option<&str>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<ProjectInvitation>>;
+
+    /// Invitations that are already redeemed or revoked are left unchanged.
+    async fn revoke_project_invitation(
+        invitation_id: uuid::Uuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<()>;
+
+    /// Fails with a conflict if the invitation is redeemed, revoked or expired.
+    async fn mark_project_invitation_redeemed(
+        invitation_id: uuid::Uuid,
+        redeemed_by: &str,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<()>;
+
+    // ---------------- Storage Intents ----------------
+    async fn record_storage_intents(
+        intents: &[StorageIntent],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<()>;
+
+    /// Removes the intents. Fails with a conflict if any of them no longer exists,
+    /// i.e. if it has been recovered in the meantime.
+    async fn complete_storage_intents(
+        intent_ids: &[uuid::Uuid],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<()>;
+
+    /// Lock up to `limit` intents that were recorded before `recorded_before`, oldest first.
+    /// Intents locked by other transactions are skipped.
+    async fn claim_stale_storage_intents(
+        recorded_before: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Vec<StorageIntent>>;
+
+    // ---------------- Retired Storage Secrets ----------------
+    /// Schedule the deletion of a storage secret that is no longer used by the warehouse.
+    /// If the secret is already scheduled, the earlier point in time wins.
+    async fn retire_storage_secret(
+        warehouse_id: WarehouseId,
+        secret_id: SecretIdent,
+        retire_at: chrono::DateTime<chrono::Utc>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<()>;
+
+    /// Lock up to `limit` retired secrets that are due for deletion.
+    /// Secrets locked by other transactions are skipped.
+    async fn claim_due_retired_storage_secrets(
+        limit: i64,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Vec<SecretIdent>>;
+
+    /// Remove retired secrets after they have been deleted from the secret store.
+    async fn delete_retired_storage_secrets(
+        secret_ids: &[SecretIdent],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<()>;
+
+    // ---------------- Remote Signing Audit ----------------
+    async fn record_table_signing(
+        warehouse_id: WarehouseId,
+        signing: &TableSigning,
+        catalog_state: Self::State,
+    ) -> Result<()>;
+
+    /// Signings of the table, newest first.
+    async fn list_table_signings(
+        warehouse_id: WarehouseId,
+        table_id: TableId,
+        pagination_query: PaginationQuery,
+        catalog_state: Self::State,
+    ) -> Result<ListTableSigningsResponse>;
+
+    /// Delete up to `limit` signings that were recorded before `signed_before`.
+    /// Returns the number of deleted signings.
+    async fn delete_table_signings(
+        signed_before: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+        catalog_state: Self::State,
+    ) -> Result<u64>;
+
+    // ---------------- Server Events ----------------
+    async fn record_server_event(event: &ServerEvent, catalog_state: Self::State) -> Result<()>;
+
+    /// Events of all instances, newest first. If `event_types` is set, only events
+    /// of these types are returned.
+    async fn list_server_events(
+        event_types: Option<Vec<ServerEventType>>,
+        pagination_query: PaginationQuery,
+        catalog_state: Self::State,
+    ) -> Result<ListServerEventsResponse>;
+
+    /// Delete up to `limit` events that were emitted before `created_before`.
+    /// Returns the number of deleted events.
+    async fn delete_server_events(
+        created_before: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+        catalog_state: Self::State,
+    ) -> Result<u64>;
+
+    // ---------------- Table Timeline ----------------
+    /// Record an activity of a table. Activities of tables that no longer exist are ignored.
+    async fn record_table_activity(
+        activity: &TableActivity,
+        catalog_state: Self::State,
+    ) -> Result<()>;
+
+    /// Commits, finished maintenance tasks and recorded activities of a table, newest first.
+    async fn get_table_timeline(
+        warehouse_id: WarehouseId,
+        table_id: TableId,
+        pagination_query: PaginationQuery,
+        catalog_state: Self::State,
+    ) -> Result<GetTableTimelineResponse>;
+
+    // ---------------- Table Lineage ----------------
+    /// Tables at the given locations, keyed by location. Locations without a table
+    /// are not returned. Soft-deleted tables are not returned.
+    async fn resolve_table_locations(
+        locations: &[String],
+        catalog_state: Self::State,
+    ) -> Result<HashMap<String, LineageTable>>;
+
+    /// Insert lineage edges or update the last run of existing edges.
+    async fn record_table_lineage(
+        edges: &[LineageEdge],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<()>;
+
+    /// Datasets read by jobs writing the table and datasets written by jobs reading it.
+    async fn get_table_lineage(
+        warehouse_id: WarehouseId,
+        table_id: TableId,
+        catalog_state: Self::State,
+    ) -> Result<TableLineage>;
+
+    // ---------------- Usage Statistics ----------------
+    /// Aggregate the endpoint statistics of all warehouses for `day` (UTC) and store them
+    /// together with the current number of tables, views and snapshots. If usage for the
+    /// day was aggregated before, the number of entities is only updated if
+    /// `update_entity_counts` is set.
+    async fn aggregate_warehouse_usage(
+        day: chrono::NaiveDate,
+        update_entity_counts: bool,
+        catalog_state: Self::State,
+    ) -> Result<()>;
+
+    /// Daily usage of the warehouses of a project between `start` and `end` (inclusive),
+    /// ordered by warehouse and day.
+    async fn list_warehouse_usage(
+        project_id: &ProjectId,
+        warehouse_id: Option<WarehouseId>,
+        start: chrono::NaiveDate,
+        end: chrono::NaiveDate,
+        catalog_state: Self::State,
+    ) -> Result<Vec<WarehouseUsageDay>>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ListFlags {
+    pub include_active: bool,
     pub include_staged: bool,
     pub include_deleted: bool,
 }
@@ -1048,6 +2304,22 @@ pub struct ViewMetadataWithLocation {
     pub metadata: ViewMetadata,
 }
 
+pub type ScanPlanId = uuid::Uuid;
+
+#[derive(Debug)]
+pub struct ScanPlan {
+    pub plan_id: ScanPlanId,
+    pub warehouse_id: WarehouseId,
+    pub table_id: TableId,
+    /// Snapshot the plan was created for. `None` if the table has no snapshots.
+    pub snapshot_id: Option<i64>,
+    pub request: PlanTableScanRequest,
+    pub status: PlanStatus,
+    pub result: Option<ScanTasks>,
+    pub error: Option<ErrorModel>,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct DeletionDetails {
     pub expiration_task_id: uuid::Uuid,
@@ -0,0 +1,263 @@
+//! Workarounds for known bugs of Iceberg REST clients.
+//!
+//! Workarounds are disabled by default and can be enabled for the whole deployment via
+//! `CONFIG.client_workarounds` or for individual warehouses via the management API.
+//! Each workaround is named after the behavior it changes, so that it is explicit which
+//! interop fixes are active and they can be removed once affected clients are gone.
+use std::{
+    sync::{Arc, LazyLock},
+    time::Duration,
+};
+
+use iceberg_ext::catalog::rest::ErrorModel;
+use url::Url;
+
+use crate::{
+    service::{Catalog, Result},
+    WarehouseId, CONFIG,
+};
+
+/// Workarounds of warehouses are cached, as they are checked on catalog requests.
+static WAREHOUSE_WORKAROUNDS_CACHE: LazyLock<
+    moka::future::Cache<WarehouseId, Arc<Vec<ClientWorkaround>>>,
+> = LazyLock::new(|| {
+    moka::future::Cache::builder()
+        .max_capacity(10_000)
+        .time_to_live(Duration::from_secs(30))
+        .build()
+});
+
+static TOKEN_ENDPOINT_CACHE: LazyLock<moka::future::Cache<(), Url>> = LazyLock::new(|| {
+    moka::future::Cache::builder()
+        .max_capacity(1)
+        .time_to_live(Duration::from_secs(60 * 60))
+        .build()
+});
+
+pub(crate) static HTTP_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .expect("Failed to create HTTP client")
+});
+
+/// Workaround for a known client bug.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    strum_macros::Display,
+    strum_macros::EnumString,
+    strum_macros::EnumIter,
+    serde::Serialize,
+    serde::Deserialize,
+    utoipa::ToSchema,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[cfg_attr(
+    feature = "sqlx",
+    sqlx(type_name = "client_workaround", rename_all = "kebab-case")
+)]
+pub enum ClientWorkaround {
+    /// Omit `next-page-token` from list responses instead of returning an empty token.
+    /// For clients that fail to parse an empty `next-page-token`.
+    OmitEmptyNextPageToken,
+    /// Serve the deprecated `/v1/oauth/tokens` endpoint, exchanging client credentials for
+    /// a token at the OpenID provider. For clients that require the endpoint to obtain
+    /// tokens. Token requests are not bound to a warehouse, so this workaround is only
+    /// effective if enabled for the whole deployment.
+    LegacyOauthTokensEndpoint,
+}
+
+impl ClientWorkaround {
+    /// Returns true if the workaround is enabled for the whole deployment.
+    #[must_use]
+    pub fn is_enabled_globally(self) -> bool {
+        CONFIG.client_workarounds.contains(&self)
+    }
+}
+
+/// Returns true if the workaround is enabled for the deployment or the warehouse.
+///
+/// If the workarounds of the warehouse cannot be loaded, the workaround is considered disabled.
+pub(crate) async fn is_enabled<C: Catalog>(
+    workaround: ClientWorkaround,
+    warehouse_id: WarehouseId,
+    catalog_state: C::State,
+) -> bool {
+    if workaround.is_enabled_globally() {
+        return true;
+    }
+
+    let enabled = WAREHOUSE_WORKAROUNDS_CACHE
+        .try_get_with(warehouse_id, async {
+            C::get_warehouse_client_workarounds(warehouse_id, catalog_state)
+                .await
+                .map(|w| Arc::new(w.unwrap_or_default()))
+        })
+        .await;
+    match enabled {
+        Ok(enabled) => enabled.contains(&workaround),
+        Err(e) => {
+            tracing::warn!(error=?e, "Failed to load client workarounds of warehouse {warehouse_id}");
+            false
+        }
+    }
+}
+
+/// Remove cached workarounds of a warehouse after they were changed.
+pub(crate) async fn invalidate_warehouse_client_workarounds(warehouse_id: WarehouseId) {
+    WAREHOUSE_WORKAROUNDS_CACHE.invalidate(&warehouse_id).await;
+}
+
+/// Next page token of a list response, with [`ClientWorkaround::OmitEmptyNextPageToken`] applied.
+pub(crate) async fn next_page_token<C: Catalog>(
+    next_page_token: Option<String>,
+    warehouse_id: WarehouseId,
+    catalog_state: C::State,
+) -> Option<String> {
+    match next_page_token {
+        // Only look up the workarounds of the warehouse if they could change the result.
+        Some(token) if token.is_empty() => {
+            if is_enabled::<C>(
+                ClientWorkaround::OmitEmptyNextPageToken,
+                warehouse_id,
+                catalog_state,
+            )
+            .await
+            {
+                None
+            } else {
+                Some(token)
+            }
+        }
+        token => token,
+    }
+}
+
+/// Returns true if the deprecated token endpoint should be served, see
+/// [`ClientWorkaround::LegacyOauthTokensEndpoint`].
+pub(crate) fn serve_legacy_oauth_tokens_endpoint() -> bool {
+    if !ClientWorkaround::LegacyOauthTokensEndpoint.is_enabled_globally() {
+        return false;
+    }
+    if CONFIG.openid_provider_uri.is_none() {
+        tracing::warn!(
+            "Client workaround '{}' is enabled, but no OpenID provider is configured. Not serving the legacy token endpoint.",
+            ClientWorkaround::LegacyOauthTokensEndpoint
+        );
+        return false;
+    }
+    true
+}
+
+/// Token endpoint of the OpenID provider, used by
+/// [`ClientWorkaround::LegacyOauthTokensEndpoint`].
+pub(crate) async fn token_endpoint() -> Result<Url> {
+    TOKEN_ENDPOINT_CACHE
+        .try_get_with((), discover_token_endpoint())
+        .await
+        .map_err(|e| {
+            ErrorModel::internal(
+                "Failed to discover token endpoint of OpenID provider",
+                "TokenEndpointDiscoveryFailed",
+                Some(Box::new(e)),
+            )
+            .into()
+        })
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct OpenIdConfiguration {
+    token_endpoint: Url,
+}
+
+async fn discover_token_endpoint() -> std::result::Result<Url, ErrorModel> {
+    let provider_uri = CONFIG.openid_provider_uri.as_ref().ok_or_else(|| {
+        ErrorModel::internal(
+            "No OpenID provider configured",
+            "OpenIdProviderNotConfigured",
+            None,
+        )
+    })?;
+    let discovery_url = openid_configuration_url(provider_uri)?;
+
+    let configuration = HTTP_CLIENT
+        .get(discovery_url)
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|e| {
+            ErrorModel::internal(
+                "Failed to fetch OpenID configuration",
+                "TokenEndpointDiscoveryFailed",
+                Some(Box::new(e)),
+            )
+        })?
+        .json::<OpenIdConfiguration>()
+        .await
+        .map_err(|e| {
+            ErrorModel::internal(
+                "Failed to parse OpenID configuration",
+                "TokenEndpointDiscoveryFailed",
+                Some(Box::new(e)),
+            )
+        })?;
+    Ok(configuration.token_endpoint)
+}
+
+fn openid_configuration_url(provider_uri: &Url) -> std::result::Result<Url, ErrorModel> {
+    let mut url = provider_uri.clone();
+    url.path_segments_mut()
+        .map_err(|()| {
+            ErrorModel::internal(
+                format!("Invalid OpenID provider URI '{provider_uri}'"),
+                "InvalidOpenIdProviderUri",
+                None,
+            )
+        })?
+        .pop_if_empty()
+        .extend([".well-known", "openid-configuration"]);
+    Ok(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_workaround_names() {
+        assert_eq!(
+            ClientWorkaround::from_str("omit-empty-next-page-token").unwrap(),
+            ClientWorkaround::OmitEmptyNextPageToken
+        );
+        assert_eq!(
+            serde_json::to_value(ClientWorkaround::LegacyOauthTokensEndpoint).unwrap(),
+            serde_json::json!("legacy-oauth-tokens-endpoint")
+        );
+    }
+
+    #[test]
+    fn test_openid_configuration_url() {
+        for provider_uri in [
+            "https://idp.example.com/realms/iceberg",
+            "https://idp.example.com/realms/iceberg/",
+        ] {
+            assert_eq!(
+                openid_configuration_url(&Url::parse(provider_uri).unwrap())
+                    .unwrap()
+                    .as_str(),
+                "https://idp.example.com/realms/iceberg/.well-known/openid-configuration"
+            );
+        }
+    }
+}
@@ -0,0 +1,140 @@
+//! Default branch served by `loadTable`.
+//!
+//! Warehouses and namespaces can configure a branch that is served in place of `main` to
+//! clients loading a table. This enables blue/green publishing: writers commit to a staging
+//! branch, and readers switch to it once the branch is swapped behind the scenes, without
+//! changing their queries. Namespaces inherit the branch of their closest ancestor that sets
+//! [`DEFAULT_BRANCH_PROPERTY`], falling back to the branch of the warehouse.
+use std::sync::LazyLock;
+
+use iceberg::{
+    spec::{
+        SnapshotReference, SnapshotRetention, TableMetadata, TableMetadataBuilder, MAIN_BRANCH,
+    },
+    NamespaceIdent,
+};
+
+use crate::{
+    api::ErrorModel,
+    service::{shared_cache::SharedCache, Catalog, Result, Transaction},
+    WarehouseId,
+};
+
+/// Namespace property that overrides the default branch of the warehouse for all tables
+/// in the namespace and its children.
+pub const DEFAULT_BRANCH_PROPERTY: &str = "default_branch";
+
+/// Resolved branches are cached, as they are required on every `loadTable`.
+static DEFAULT_BRANCH_CACHE: LazyLock<SharedCache<Option<String>>> =
+    LazyLock::new(|| SharedCache::new("default-branch"));
+
+/// Validate the name of a default branch.
+///
+/// # Errors
+/// If the name is empty or contains leading or trailing whitespace.
+pub fn validate_default_branch(default_branch: &str) -> Result<()> {
+    if default_branch.is_empty() || default_branch.trim() != default_branch {
+        return Err(ErrorModel::bad_request(
+            format!(
+                "Default branch must be a non-empty name without surrounding whitespace, got '{default_branch}'."
+            ),
+            "InvalidDefaultBranch",
+            None,
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Determine the branch served as `main` for tables in `namespace`.
+///
+/// Returns `None` if tables are served as stored.
+pub(crate) async fn resolve_default_branch<C: Catalog>(
+    warehouse_id: WarehouseId,
+    namespace: &NamespaceIdent,
+    catalog_state: C::State,
+) -> Result<Option<String>> {
+    DEFAULT_BRANCH_CACHE
+        .try_get_with(
+            format!("{warehouse_id}:{}", namespace.to_url_string()),
+            load_default_branch::<C>(warehouse_id, namespace, catalog_state),
+        )
+        .await
+}
+
+async fn load_default_branch<C: Catalog>(
+    warehouse_id: WarehouseId,
+    namespace: &NamespaceIdent,
+    catalog_state: C::State,
+) -> Result<Option<String>> {
+    let mut t = C::Transaction::begin_read(catalog_state).await?;
+    let ancestors = (1..=namespace.len())
+        .rev()
+        .filter_map(|depth| NamespaceIdent::from_vec(namespace[..depth].to_vec()).ok());
+    for ancestor in ancestors {
+        let Some(namespace_id) =
+            C::namespace_to_id(warehouse_id, &ancestor, t.transaction()).await?
+        else {
+            continue;
+        };
+        let properties = C::get_namespace(warehouse_id, namespace_id, t.transaction())
+            .await?
+            .properties
+            .unwrap_or_default();
+        if let Some(default_branch) = properties.get(DEFAULT_BRANCH_PROPERTY) {
+            t.commit().await?;
+            return Ok(Some(default_branch.clone()).filter(|b| b != MAIN_BRANCH));
+        }
+    }
+    let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
+    t.commit().await?;
+    Ok(warehouse.default_branch.filter(|b| b != MAIN_BRANCH))
+}
+
+/// Remove resolved branches of a warehouse on all instances after the default branch of
+/// the warehouse or one of its namespaces changed.
+pub(crate) async fn invalidate_default_branches(warehouse_id: WarehouseId) {
+    DEFAULT_BRANCH_CACHE
+        .invalidate(&warehouse_id.to_string())
+        .await;
+}
+
+/// Serve the head of `default_branch` as `main` of the table.
+///
+/// Tables without the branch are returned unchanged, so that readers keep seeing `main`
+/// until the branch is created.
+pub(crate) fn apply_default_branch(
+    metadata: TableMetadata,
+    default_branch: &str,
+) -> Result<TableMetadata> {
+    let Some(target) = metadata.refs().get(default_branch) else {
+        return Ok(metadata);
+    };
+    let main = metadata.refs().get(MAIN_BRANCH);
+    if main.is_some_and(|main| main.snapshot_id == target.snapshot_id) {
+        return Ok(metadata);
+    }
+    let reference = SnapshotReference {
+        snapshot_id: target.snapshot_id,
+        retention: main.map_or(
+            SnapshotRetention::Branch {
+                min_snapshots_to_keep: None,
+                max_snapshot_age_ms: None,
+                max_ref_age_ms: None,
+            },
+            |main| main.retention.clone(),
+        ),
+    };
+    TableMetadataBuilder::new_from_metadata(metadata, None)
+        .set_ref(MAIN_BRANCH, reference)
+        .and_then(TableMetadataBuilder::build)
+        .map(|result| result.metadata)
+        .map_err(|e| {
+            ErrorModel::internal(
+                format!("Failed to serve branch '{default_branch}' as '{MAIN_BRANCH}'"),
+                "DefaultBranchError",
+                Some(Box::new(e)),
+            )
+            .into()
+        })
+}
@@ -23,11 +23,17 @@ use crate::{
             types::DropParams,
             v1::{DataAccess, NamespaceParameters, TableParameters, ViewParameters},
         },
-        management::v1::warehouse::UndropTabularsRequest,
+        management::v1::{
+            user::Impersonation, view::RollbackViewResponse, warehouse::UndropTabularsRequest,
+        },
         RequestMetadata,
     },
     catalog::tables::CommitContext,
-    service::{TableId, UndropTabularResponse, ViewId},
+    service::{
+        authz::AuthorizationContext, maintenance::CompactionRecommendation,
+        CreateNamespaceResponse, NamespaceId, TableId, UndropTabularResponse,
+        UpdateNamespacePropertiesResponse, ViewId,
+    },
     WarehouseId,
 };
 
@@ -75,12 +81,79 @@ pub struct ViewCommit {
 }
 
 impl EndpointHookCollection {
+    pub(crate) async fn create_namespace(
+        &self,
+        warehouse_id: WarehouseId,
+        namespace_id: NamespaceId,
+        response: Arc<CreateNamespaceResponse>,
+        request_metadata: Arc<RequestMetadata>,
+    ) {
+        futures::future::join_all(self.0.iter().map(|hook| {
+            hook.create_namespace(
+                warehouse_id,
+                namespace_id,
+                response.clone(),
+                request_metadata.clone(),
+            )
+            .map_err(|e| {
+                tracing::warn!(
+                    "Hook '{}' encountered error on create_namespace: {e:?}",
+                    hook.to_string()
+                );
+            })
+        }))
+        .await;
+    }
+
+    pub(crate) async fn drop_namespace(
+        &self,
+        warehouse_id: WarehouseId,
+        namespace_id: NamespaceId,
+        request_metadata: Arc<RequestMetadata>,
+    ) {
+        futures::future::join_all(self.0.iter().map(|hook| {
+            hook.drop_namespace(warehouse_id, namespace_id, request_metadata.clone())
+                .map_err(|e| {
+                    tracing::warn!(
+                        "Hook '{}' encountered error on drop_namespace: {e:?}",
+                        hook.to_string()
+                    );
+                })
+        }))
+        .await;
+    }
+
+    pub(crate) async fn update_namespace_properties(
+        &self,
+        warehouse_id: WarehouseId,
+        namespace_id: NamespaceId,
+        response: Arc<UpdateNamespacePropertiesResponse>,
+        request_metadata: Arc<RequestMetadata>,
+    ) {
+        futures::future::join_all(self.0.iter().map(|hook| {
+            hook.update_namespace_properties(
+                warehouse_id,
+                namespace_id,
+                response.clone(),
+                request_metadata.clone(),
+            )
+            .map_err(|e| {
+                tracing::warn!(
+                    "Hook '{}' encountered error on update_namespace_properties: {e:?}",
+                    hook.to_string()
+                );
+            })
+        }))
+        .await;
+    }
+
     pub(crate) async fn commit_transaction(
         &self,
         warehouse_id: WarehouseId,
         request: Arc<CommitTransactionRequest>,
         commits: Arc<Vec<CommitContext>>,
         table_ident_map: Arc<HashMap<TableIdent, TableId>>,
+        authorization: Arc<AuthorizationContext>,
         request_metadata: Arc<RequestMetadata>,
     ) {
         futures::future::join_all(self.0.iter().map(|hook| {
@@ -89,6 +162,7 @@ impl EndpointHookCollection {
                 request.clone(),
                 commits.clone(),
                 table_ident_map.clone(),
+                authorization.clone(),
                 request_metadata.clone(),
             )
             .map_err(|e| {
@@ -319,6 +393,32 @@ impl EndpointHookCollection {
         .await;
     }
 
+    pub(crate) async fn rollback_view(
+        &self,
+        warehouse_id: WarehouseId,
+        view_id: ViewId,
+        view: Arc<TableIdent>,
+        rollback: Arc<RollbackViewResponse>,
+        request_metadata: Arc<RequestMetadata>,
+    ) {
+        futures::future::join_all(self.0.iter().map(|hook| {
+            hook.rollback_view(
+                warehouse_id,
+                view_id,
+                view.clone(),
+                rollback.clone(),
+                request_metadata.clone(),
+            )
+            .map_err(|e| {
+                tracing::warn!(
+                    "Hook '{}' encountered error on rollback_view: {e:?}",
+                    hook.to_string()
+                );
+            })
+        }))
+        .await;
+    }
+
     pub(crate) async fn undrop_tabular(
         &self,
         warehouse_id: WarehouseId,
@@ -342,6 +442,66 @@ impl EndpointHookCollection {
         }))
         .await;
     }
+
+    pub(crate) async fn start_impersonation(
+        &self,
+        impersonation: Arc<Impersonation>,
+        request_metadata: Arc<RequestMetadata>,
+    ) {
+        futures::future::join_all(self.0.iter().map(|hook| {
+            hook.start_impersonation(impersonation.clone(), request_metadata.clone())
+                .map_err(|e| {
+                    tracing::warn!(
+                        "Hook '{}' encountered error on start_impersonation: {e:?}",
+                        hook.to_string()
+                    );
+                })
+        }))
+        .await;
+    }
+
+    pub(crate) async fn stop_impersonation(
+        &self,
+        impersonation: Arc<Impersonation>,
+        request_metadata: Arc<RequestMetadata>,
+    ) {
+        futures::future::join_all(self.0.iter().map(|hook| {
+            hook.stop_impersonation(impersonation.clone(), request_metadata.clone())
+                .map_err(|e| {
+                    tracing::warn!(
+                        "Hook '{}' encountered error on stop_impersonation: {e:?}",
+                        hook.to_string()
+                    );
+                })
+        }))
+        .await;
+    }
+
+    pub(crate) async fn compaction_requested(
+        &self,
+        warehouse_id: WarehouseId,
+        table_id: TableId,
+        table: Arc<TableIdent>,
+        recommendations: Arc<Vec<CompactionRecommendation>>,
+        request_metadata: Arc<RequestMetadata>,
+    ) {
+        futures::future::join_all(self.0.iter().map(|hook| {
+            hook.compaction_requested(
+                warehouse_id,
+                table_id,
+                table.clone(),
+                recommendations.clone(),
+                request_metadata.clone(),
+            )
+            .map_err(|e| {
+                tracing::warn!(
+                    "Hook '{}' encountered error on compaction_requested: {e:?}",
+                    hook.to_string()
+                );
+            })
+        }))
+        .await;
+    }
 }
 
 /// `EndpointHook` is a trait that allows for custom hooks to be executed within the context of
@@ -361,12 +521,42 @@ impl EndpointHookCollection {
 /// that the request is not blocked by a hook failure.
 #[async_trait::async_trait]
 pub trait EndpointHook: Send + Sync + Debug + Display {
+    async fn create_namespace(
+        &self,
+        _warehouse_id: WarehouseId,
+        _namespace_id: NamespaceId,
+        _response: Arc<CreateNamespaceResponse>,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn drop_namespace(
+        &self,
+        _warehouse_id: WarehouseId,
+        _namespace_id: NamespaceId,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn update_namespace_properties(
+        &self,
+        _warehouse_id: WarehouseId,
+        _namespace_id: NamespaceId,
+        _response: Arc<UpdateNamespacePropertiesResponse>,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
     async fn commit_transaction(
         &self,
         _warehouse_id: WarehouseId,
         _request: Arc<CommitTransactionRequest>,
         _commits: Arc<Vec<CommitContext>>,
         _table_ident_map: Arc<HashMap<TableIdent, TableId>>,
+        _authorization: Arc<AuthorizationContext>,
         _request_metadata: Arc<RequestMetadata>,
     ) -> anyhow::Result<()> {
         Ok(())
@@ -466,6 +656,19 @@ pub trait EndpointHook: Send + Sync + Debug + Display {
         Ok(())
     }
 
+    /// Called after the current version of a view was rolled back via the management API.
+    /// The commit itself is additionally reported via `commit_view`.
+    async fn rollback_view(
+        &self,
+        _warehouse_id: WarehouseId,
+        _view_id: ViewId,
+        _view: Arc<TableIdent>,
+        _rollback: Arc<RollbackViewResponse>,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
     async fn undrop_tabular(
         &self,
         _warehouse_id: WarehouseId,
@@ -475,4 +678,33 @@ pub trait EndpointHook: Send + Sync + Debug + Display {
     ) -> anyhow::Result<()> {
         Ok(())
     }
+
+    async fn start_impersonation(
+        &self,
+        _impersonation: Arc<Impersonation>,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn stop_impersonation(
+        &self,
+        _impersonation: Arc<Impersonation>,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// Called when compaction of partitions of a table is requested via the management API.
+    /// Hooks can use the listed files to trigger external compaction jobs.
+    async fn compaction_requested(
+        &self,
+        _warehouse_id: WarehouseId,
+        _table_id: TableId,
+        _table: Arc<TableIdent>,
+        _recommendations: Arc<Vec<CompactionRecommendation>>,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
 }
@@ -0,0 +1,339 @@
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::{Arc, LazyLock},
+    time::Duration,
+};
+
+use axum::{
+    extract::{Path, Query, Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use http::Uri;
+use iceberg_ext::catalog::rest::{ErrorModel, IcebergErrorResponse};
+use uuid::Uuid;
+
+use crate::{
+    api::{
+        endpoints::{CatalogV1Endpoint, Endpoint, ManagementV1Endpoint, SignEndpoint},
+        iceberg::v1::DropParams,
+    },
+    request_metadata::RequestMetadata,
+    service::Catalog,
+    WarehouseId, CONFIG,
+};
+
+/// Disabled endpoint groups of warehouses are cached for the policy middleware,
+/// so that catalog requests don't require an additional database query.
+static WAREHOUSE_DISABLED_GROUPS_CACHE: LazyLock<
+    moka::future::Cache<WarehouseId, Arc<Vec<EndpointGroup>>>,
+> = LazyLock::new(|| {
+    moka::future::Cache::builder()
+        .max_capacity(10_000)
+        .time_to_live(Duration::from_secs(30))
+        .build()
+});
+
+/// Group of endpoints that can be disabled as a whole, either for the
+/// whole deployment or for individual warehouses.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    strum_macros::Display,
+    strum_macros::EnumString,
+    strum_macros::EnumIter,
+    serde::Serialize,
+    serde::Deserialize,
+    utoipa::ToSchema,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[cfg_attr(
+    feature = "sqlx",
+    sqlx(type_name = "endpoint_group", rename_all = "kebab-case")
+)]
+pub enum EndpointGroup {
+    /// Registering existing metadata files as new tables.
+    RegisterTable,
+    /// Dropping tables or views with `purgeRequested` set.
+    PurgeDrops,
+    /// All endpoints of the Iceberg REST view API.
+    Views,
+    /// Server side scan planning.
+    ScanPlanning,
+    /// Multi-table transactions.
+    Transactions,
+    /// S3 remote signing.
+    RemoteSigning,
+}
+
+impl EndpointGroup {
+    /// Group the endpoint belongs to regardless of the request parameters.
+    #[must_use]
+    pub fn of_endpoint(endpoint: Endpoint) -> Option<Self> {
+        match endpoint {
+            Endpoint::CatalogV1(e) => match e {
+                CatalogV1Endpoint::RegisterTable => Some(Self::RegisterTable),
+                CatalogV1Endpoint::CreateView
+                | CatalogV1Endpoint::ListViews
+                | CatalogV1Endpoint::LoadView
+                | CatalogV1Endpoint::ReplaceView
+                | CatalogV1Endpoint::DropView
+                | CatalogV1Endpoint::ViewExists
+                | CatalogV1Endpoint::RenameView => Some(Self::Views),
+                CatalogV1Endpoint::PlanTableScan
+                | CatalogV1Endpoint::FetchPlanningResult
+                | CatalogV1Endpoint::CancelPlanning
+                | CatalogV1Endpoint::FetchScanTasks => Some(Self::ScanPlanning),
+                CatalogV1Endpoint::CommitTransaction => Some(Self::Transactions),
+                _ => None,
+            },
+            Endpoint::Sign(
+                SignEndpoint::S3RequestGlobal
+                | SignEndpoint::S3RequestPrefix
+                | SignEndpoint::S3RequestTabular,
+            ) => Some(Self::RemoteSigning),
+            // Imports register the tables of another catalog or warehouse.
+            Endpoint::ManagementV1(
+                ManagementV1Endpoint::ImportCatalog | ManagementV1Endpoint::ImportWarehouse,
+            ) => Some(Self::RegisterTable),
+            Endpoint::ManagementV1(_) | Endpoint::PermissionV1(_) => None,
+        }
+    }
+
+    /// All groups a concrete request falls into.
+    fn of_request(endpoint: Endpoint, uri: &Uri) -> Vec<Self> {
+        let mut groups = Self::of_endpoint(endpoint).into_iter().collect::<Vec<_>>();
+        let is_drop = matches!(
+            endpoint,
+            Endpoint::CatalogV1(CatalogV1Endpoint::DropTable | CatalogV1Endpoint::DropView)
+        );
+        if is_drop
+            && Query::<DropParams>::try_from_uri(uri)
+                .is_ok_and(|Query(params)| params.purge_requested)
+        {
+            groups.push(Self::PurgeDrops);
+        }
+        groups
+    }
+}
+
+/// Returns true if the endpoint is not disabled as a whole for the given groups.
+#[must_use]
+pub fn is_endpoint_enabled(endpoint: Endpoint, disabled: &[EndpointGroup]) -> bool {
+    !EndpointGroup::of_endpoint(endpoint).is_some_and(|group| disabled.contains(&group))
+        && !(CONFIG.is_read_replica() && is_write_endpoint(endpoint))
+}
+
+/// Endpoints of the Iceberg REST API that change the catalog, imports, restores and repairs.
+/// They are rejected by read replicas and for federated warehouses.
+#[must_use]
+pub fn is_write_endpoint(endpoint: Endpoint) -> bool {
+    matches!(
+        endpoint,
+        Endpoint::ManagementV1(
+            ManagementV1Endpoint::ImportCatalog
+                | ManagementV1Endpoint::ImportWarehouse
+                | ManagementV1Endpoint::RestoreCatalogSnapshot
+                | ManagementV1Endpoint::RepairTable
+        ) | Endpoint::CatalogV1(
+            CatalogV1Endpoint::CreateNamespace
+                | CatalogV1Endpoint::DropNamespace
+                | CatalogV1Endpoint::UpdateNamespaceProperties
+                | CatalogV1Endpoint::CreateTable
+                | CatalogV1Endpoint::UpdateTable
+                | CatalogV1Endpoint::DropTable
+                | CatalogV1Endpoint::RenameTable
+                | CatalogV1Endpoint::RegisterTable
+                | CatalogV1Endpoint::CommitTransaction
+                | CatalogV1Endpoint::CreateView
+                | CatalogV1Endpoint::ReplaceView
+                | CatalogV1Endpoint::DropView
+                | CatalogV1Endpoint::RenameView
+        )
+    )
+}
+
+/// Remove cached disabled groups of a warehouse after they were changed.
+pub(crate) async fn invalidate_warehouse_disabled_groups(warehouse_id: WarehouseId) {
+    WAREHOUSE_DISABLED_GROUPS_CACHE
+        .invalidate(&warehouse_id)
+        .await;
+}
+
+/// Middleware rejecting requests to endpoint groups that are disabled.
+///
+/// Groups disabled for the whole deployment via configuration and writes to read replicas
+/// or federated warehouses are rejected with `405 Method Not Allowed`, groups disabled for
+/// the warehouse of the request are rejected with `403 Forbidden`.
+pub(crate) async fn endpoint_policy_middleware_fn<C: Catalog>(
+    State(catalog_state): State<C::State>,
+    Path(path_params): Path<HashMap<String, String>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let endpoint = request.extensions().get::<RequestMetadata>().and_then(|m| {
+        m.matched_path().and_then(|matched_path| {
+            Endpoint::from_method_and_matched_path(m.request_method(), matched_path)
+        })
+    });
+    let Some(endpoint) = endpoint else {
+        return next.run(request).await;
+    };
+
+    if CONFIG.is_read_replica() && is_write_endpoint(endpoint) {
+        return IcebergErrorResponse::from(ErrorModel::not_allowed(
+            "This Lakekeeper is a read replica. Changes must be sent to the primary.",
+            "ReadReplica",
+            None,
+        ))
+        .into_response();
+    }
+    if is_write_endpoint(endpoint) {
+        if let Some((warehouse_id, source)) = warehouse_id_from_path(&path_params)
+            .and_then(|id| CONFIG.federation_source(id).map(|source| (id, source)))
+        {
+            return IcebergErrorResponse::from(ErrorModel::not_allowed(
+                format!("Warehouse {warehouse_id} is federated from {source} and read-only. Changes must be made in {source}."),
+                "FederatedWarehouse",
+                None,
+            ))
+            .into_response();
+        }
+    }
+
+    let groups = EndpointGroup::of_request(endpoint, request.uri());
+    if groups.is_empty() {
+        return next.run(request).await;
+    }
+
+    if let Some(group) = groups
+        .iter()
+        .find(|g| CONFIG.disabled_endpoint_groups.contains(g))
+    {
+        return IcebergErrorResponse::from(ErrorModel::not_allowed(
+            format!("Endpoint group '{group}' is disabled for this deployment"),
+            "EndpointDisabled",
+            None,
+        ))
+        .into_response();
+    }
+
+    let Some(warehouse_id) = warehouse_id_from_path(&path_params) else {
+        return next.run(request).await;
+    };
+
+    let disabled = WAREHOUSE_DISABLED_GROUPS_CACHE
+        .try_get_with(warehouse_id, async {
+            C::get_warehouse_disabled_endpoint_groups(warehouse_id, catalog_state)
+                .await
+                .map(|groups| Arc::new(groups.unwrap_or_default()))
+        })
+        .await;
+    match disabled {
+        Ok(disabled) => {
+            if let Some(group) = groups.iter().find(|g| disabled.contains(g)) {
+                return IcebergErrorResponse::from(ErrorModel::forbidden(
+                    format!("Endpoint group '{group}' is disabled for warehouse {warehouse_id}"),
+                    "EndpointDisabledForWarehouse",
+                    None,
+                ))
+                .into_response();
+            }
+        }
+        Err(e) => {
+            tracing::warn!(error=?e, "Failed to load disabled endpoint groups of warehouse {warehouse_id}");
+        }
+    }
+
+    next.run(request).await
+}
+
+/// Warehouse of a request, taken from the `warehouse_id` or `prefix` path parameter.
+pub(crate) fn warehouse_id_from_path(path_params: &HashMap<String, String>) -> Option<WarehouseId> {
+    path_params
+        .get("warehouse_id")
+        .or_else(|| path_params.get("prefix"))
+        .and_then(|s| Uuid::from_str(s).ok())
+        .map(WarehouseId::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_purge_drop_group() {
+        let drop_table = Endpoint::CatalogV1(CatalogV1Endpoint::DropTable);
+        let uri =
+            Uri::from_str("/catalog/v1/wh/namespaces/ns/tables/t?purgeRequested=false").unwrap();
+        assert!(EndpointGroup::of_request(drop_table, &uri).is_empty());
+
+        let uri =
+            Uri::from_str("/catalog/v1/wh/namespaces/ns/tables/t?purgeRequested=True").unwrap();
+        assert_eq!(
+            EndpointGroup::of_request(drop_table, &uri),
+            vec![EndpointGroup::PurgeDrops]
+        );
+
+        let drop_view = Endpoint::CatalogV1(CatalogV1Endpoint::DropView);
+        let uri =
+            Uri::from_str("/catalog/v1/wh/namespaces/ns/views/v?purgeRequested=true").unwrap();
+        assert_eq!(
+            EndpointGroup::of_request(drop_view, &uri),
+            vec![EndpointGroup::Views, EndpointGroup::PurgeDrops]
+        );
+    }
+
+    #[test]
+    fn test_endpoint_enabled() {
+        let register = Endpoint::CatalogV1(CatalogV1Endpoint::RegisterTable);
+        assert!(is_endpoint_enabled(register, &[]));
+        assert!(!is_endpoint_enabled(
+            register,
+            &[EndpointGroup::RegisterTable]
+        ));
+        assert!(is_endpoint_enabled(
+            Endpoint::CatalogV1(CatalogV1Endpoint::DropTable),
+            &[EndpointGroup::PurgeDrops]
+        ));
+    }
+
+    #[test]
+    fn test_write_endpoints() {
+        assert!(is_write_endpoint(Endpoint::CatalogV1(
+            CatalogV1Endpoint::CommitTransaction
+        )));
+        assert!(is_write_endpoint(Endpoint::CatalogV1(
+            CatalogV1Endpoint::DropView
+        )));
+        assert!(is_write_endpoint(Endpoint::ManagementV1(
+            ManagementV1Endpoint::ImportCatalog
+        )));
+        assert!(is_write_endpoint(Endpoint::ManagementV1(
+            ManagementV1Endpoint::ImportWarehouse
+        )));
+        assert!(is_write_endpoint(Endpoint::ManagementV1(
+            ManagementV1Endpoint::RestoreCatalogSnapshot
+        )));
+        assert!(is_write_endpoint(Endpoint::ManagementV1(
+            ManagementV1Endpoint::RepairTable
+        )));
+        for read in [
+            CatalogV1Endpoint::LoadTable,
+            CatalogV1Endpoint::LoadCredentials,
+            CatalogV1Endpoint::PlanTableScan,
+            CatalogV1Endpoint::ReportMetrics,
+        ] {
+            assert!(!is_write_endpoint(Endpoint::CatalogV1(read)));
+        }
+    }
+}
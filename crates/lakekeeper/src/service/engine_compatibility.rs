@@ -0,0 +1,386 @@
+//! Checks table metadata against known limitations of query engines.
+//!
+//! Engines are identified by `<kind>-<version>`, e.g. `trino-440` or `spark-1.7.1`.
+//! For Spark, the version is the version of the Iceberg Spark runtime, as most
+//! limitations depend on it rather than on the Spark version.
+use std::{fmt::Display, str::FromStr};
+
+use iceberg::spec::{NestedFieldRef, PrimitiveType, TableMetadata, Type};
+use serde::{Deserialize, Serialize};
+
+/// Engines checked if neither the request nor the server configuration specify engines.
+pub const DEFAULT_ENGINES: &[&str] = &["trino-475", "spark-1.9.0"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum EngineKind {
+    Trino,
+    Spark,
+}
+
+/// A query engine in a specific version.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Engine {
+    pub kind: EngineKind,
+    version: [u32; 3],
+}
+
+impl Engine {
+    fn at_least(&self, version: [u32; 3]) -> bool {
+        self.version >= version
+    }
+}
+
+impl FromStr for Engine {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "Invalid engine '{s}'. Expected '<engine>-<version>', e.g. 'trino-440' or 'spark-1.7.1'"
+            )
+        };
+        let (kind, version) = s.trim().split_once('-').ok_or_else(invalid)?;
+        let kind = EngineKind::from_str(&kind.to_lowercase()).map_err(|_| invalid())?;
+        let parts = version
+            .split('.')
+            .map(str::parse::<u32>)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| invalid())?;
+        if parts.is_empty() || parts.len() > 3 {
+            return Err(invalid());
+        }
+        let mut version = [0; 3];
+        version[..parts.len()].copy_from_slice(&parts);
+        Ok(Self { kind, version })
+    }
+}
+
+impl TryFrom<String> for Engine {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::from_str(&value)
+    }
+}
+
+impl From<Engine> for String {
+    fn from(engine: Engine) -> Self {
+        engine.to_string()
+    }
+}
+
+impl Display for Engine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let [major, minor, patch] = self.version;
+        match self.kind {
+            EngineKind::Trino => write!(f, "{}-{major}", self.kind),
+            EngineKind::Spark => write!(f, "{}-{major}.{minor}.{patch}", self.kind),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum CompatibilityIssueSeverity {
+    /// The engine can use the table with restrictions.
+    Warning,
+    /// The engine fails to read or write the table.
+    Error,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum CompatibilityIssueCode {
+    FormatVersion,
+    NanosecondTimestamp,
+    TimeType,
+    UuidType,
+    ColumnDefault,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct CompatibilityIssue {
+    pub code: CompatibilityIssueCode,
+    pub severity: CompatibilityIssueSeverity,
+    pub message: String,
+    /// What to do about the issue
+    pub recommendation: String,
+    /// Id of the affected column for column level issues
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field_id: Option<i32>,
+    /// Full name of the affected column for column level issues
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field_name: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct EngineCompatibility {
+    /// Engine in the format `<engine>-<version>`
+    pub engine: String,
+    /// `false` if there is at least one issue with severity `error`
+    pub compatible: bool,
+    pub issues: Vec<CompatibilityIssue>,
+}
+
+/// A feature of a table that is not supported by all engines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TableFeature {
+    code: CompatibilityIssueCode,
+    description: String,
+    field: Option<(i32, String)>,
+}
+
+enum Support {
+    Supported,
+    Degraded(&'static str),
+    Unsupported(&'static str),
+}
+
+/// Known support of `engine` for a feature.
+fn support(code: CompatibilityIssueCode, engine: &Engine) -> Support {
+    use CompatibilityIssueCode as C;
+    use EngineKind as E;
+
+    match (code, engine.kind) {
+        (C::FormatVersion, E::Spark) if engine.at_least([1, 8, 0]) => Support::Supported,
+        (C::FormatVersion, E::Spark) => Support::Unsupported(
+            "Upgrade the Iceberg Spark runtime to 1.8.0 or later to read format version 3 tables.",
+        ),
+        (C::FormatVersion, E::Trino) => {
+            Support::Unsupported("Keep tables that are used from Trino on format version 2.")
+        }
+        (C::NanosecondTimestamp, _) => Support::Unsupported(
+            "Use a microsecond precision timestamp type for columns that are read by this engine.",
+        ),
+        (C::TimeType, E::Spark) => Support::Unsupported(
+            "Spark has no time type. Store the time of day as a long or string instead.",
+        ),
+        (C::UuidType, E::Spark) => Support::Degraded(
+            "Spark reads uuid columns as strings. Filters on them are not pushed down.",
+        ),
+        (C::ColumnDefault, E::Spark) if engine.at_least([1, 8, 0]) => Support::Supported,
+        (C::ColumnDefault, _) => Support::Unsupported(
+            "Default values are ignored by this engine. Remove the defaults or upgrade the engine.",
+        ),
+        (C::TimeType | C::UuidType, E::Trino) => Support::Supported,
+    }
+}
+
+/// Checks the current metadata of a table against `engines`.
+pub fn check_table(metadata: &TableMetadata, engines: &[Engine]) -> Vec<EngineCompatibility> {
+    let features = table_features(metadata);
+    engines
+        .iter()
+        .map(|engine| {
+            let issues = features
+                .iter()
+                .filter_map(|feature| {
+                    let (severity, recommendation) = match support(feature.code, engine) {
+                        Support::Supported => return None,
+                        Support::Degraded(r) => (CompatibilityIssueSeverity::Warning, r),
+                        Support::Unsupported(r) => (CompatibilityIssueSeverity::Error, r),
+                    };
+                    Some(CompatibilityIssue {
+                        code: feature.code,
+                        severity,
+                        message: format!(
+                            "{} is not fully supported by {engine}",
+                            feature.description
+                        ),
+                        recommendation: recommendation.to_string(),
+                        field_id: feature.field.as_ref().map(|(id, _)| *id),
+                        field_name: feature.field.as_ref().map(|(_, name)| name.clone()),
+                    })
+                })
+                .collect::<Vec<_>>();
+            EngineCompatibility {
+                engine: engine.to_string(),
+                compatible: !issues
+                    .iter()
+                    .any(|i| i.severity == CompatibilityIssueSeverity::Error),
+                issues,
+            }
+        })
+        .collect()
+}
+
+fn table_features(metadata: &TableMetadata) -> Vec<TableFeature> {
+    let mut features = Vec::new();
+    let format_version = metadata.format_version() as u8;
+    if format_version >= 3 {
+        features.push(TableFeature {
+            code: CompatibilityIssueCode::FormatVersion,
+            description: format!("Format version {format_version}"),
+            field: None,
+        });
+    }
+
+    let schema = metadata.current_schema();
+    for field in all_fields(schema.as_struct().fields()) {
+        let field_ref = || {
+            Some((
+                field.id,
+                schema
+                    .name_by_field_id(field.id)
+                    .unwrap_or(&field.name)
+                    .to_string(),
+            ))
+        };
+        if let Type::Primitive(primitive) = field.field_type.as_ref() {
+            let code = match primitive {
+                PrimitiveType::TimestampNs | PrimitiveType::TimestamptzNs => {
+                    Some(CompatibilityIssueCode::NanosecondTimestamp)
+                }
+                PrimitiveType::Time => Some(CompatibilityIssueCode::TimeType),
+                PrimitiveType::Uuid => Some(CompatibilityIssueCode::UuidType),
+                _ => None,
+            };
+            if let Some(code) = code {
+                features.push(TableFeature {
+                    code,
+                    description: format!("Type '{primitive}' of column '{}'", field.name),
+                    field: field_ref(),
+                });
+            }
+        }
+        if field.initial_default.is_some() || field.write_default.is_some() {
+            features.push(TableFeature {
+                code: CompatibilityIssueCode::ColumnDefault,
+                description: format!("Default value of column '{}'", field.name),
+                field: field_ref(),
+            });
+        }
+    }
+    features
+}
+
+/// All fields of a schema, including nested fields, list elements and map entries.
+fn all_fields(fields: &[NestedFieldRef]) -> Vec<&NestedFieldRef> {
+    let mut result = Vec::new();
+    for field in fields {
+        result.push(field);
+        match field.field_type.as_ref() {
+            Type::Primitive(_) => {}
+            Type::Struct(s) => result.extend(all_fields(s.fields())),
+            Type::List(list) => {
+                result.extend(all_fields(std::slice::from_ref(&list.element_field)))
+            }
+            Type::Map(map) => {
+                result.extend(all_fields(std::slice::from_ref(&map.key_field)));
+                result.extend(all_fields(std::slice::from_ref(&map.value_field)));
+            }
+        }
+    }
+    result
+}
+
+/// Parses a comma separated list of engines.
+///
+/// # Errors
+/// If any of the engines is invalid.
+pub fn parse_engines(engines: &str) -> Result<Vec<Engine>, String> {
+    engines
+        .split(',')
+        .filter(|e| !e.trim().is_empty())
+        .map(Engine::from_str)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use iceberg::spec::{
+        FormatVersion, Literal, NestedField, PrimitiveType, Schema, SortOrder, StructType,
+        TableMetadataBuilder, Type, UnboundPartitionSpec,
+    };
+
+    use super::*;
+
+    fn metadata(schema: Schema) -> TableMetadata {
+        TableMetadataBuilder::new(
+            schema,
+            UnboundPartitionSpec::builder().build(),
+            SortOrder::unsorted_order(),
+            "s3://bucket/table".to_string(),
+            FormatVersion::V2,
+            HashMap::new(),
+        )
+        .unwrap()
+        .build()
+        .unwrap()
+        .metadata
+    }
+
+    #[test]
+    fn test_parse_engines() {
+        let engines = parse_engines("trino-440, Spark-1.7").unwrap();
+        assert_eq!(engines[0].kind, EngineKind::Trino);
+        assert_eq!(engines[0].to_string(), "trino-440");
+        assert_eq!(engines[1].kind, EngineKind::Spark);
+        assert_eq!(engines[1].to_string(), "spark-1.7.0");
+        assert!(parse_engines("presto-1").is_err());
+        assert!(parse_engines("spark").is_err());
+        assert!(parse_engines("spark-1.x").is_err());
+        assert!(parse_engines("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_check_table() {
+        let schema = Schema::builder()
+            .with_fields(vec![
+                NestedField::required(1, "id", Type::Primitive(PrimitiveType::Uuid)).into(),
+                NestedField::optional(
+                    2,
+                    "info",
+                    Type::Struct(StructType::new(vec![NestedField::optional(
+                        3,
+                        "opened_at",
+                        Type::Primitive(PrimitiveType::Time),
+                    )
+                    .into()])),
+                )
+                .into(),
+                NestedField::optional(4, "region", Type::Primitive(PrimitiveType::String))
+                    .with_initial_default(Literal::string("eu"))
+                    .into(),
+            ])
+            .build()
+            .unwrap();
+        let engines = parse_engines("trino-440,spark-1.7.1,spark-1.9.0").unwrap();
+        let report = check_table(&metadata(schema), &engines);
+
+        let trino = &report[0];
+        assert!(!trino.compatible);
+        assert_eq!(trino.issues.len(), 1);
+        assert_eq!(trino.issues[0].code, CompatibilityIssueCode::ColumnDefault);
+        assert_eq!(trino.issues[0].field_name.as_deref(), Some("region"));
+
+        let spark_old = &report[1];
+        assert!(!spark_old.compatible);
+        let codes = spark_old.issues.iter().map(|i| i.code).collect::<Vec<_>>();
+        assert_eq!(
+            codes,
+            vec![
+                CompatibilityIssueCode::UuidType,
+                CompatibilityIssueCode::TimeType,
+                CompatibilityIssueCode::ColumnDefault
+            ]
+        );
+        assert_eq!(
+            spark_old.issues[0].severity,
+            CompatibilityIssueSeverity::Warning
+        );
+        assert_eq!(
+            spark_old.issues[1].field_name.as_deref(),
+            Some("info.opened_at")
+        );
+
+        let spark_new = &report[2];
+        assert_eq!(spark_new.issues.len(), 2);
+    }
+}
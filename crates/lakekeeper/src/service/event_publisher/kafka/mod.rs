@@ -122,6 +122,10 @@ impl std::fmt::Debug for KafkaBackend {
 #[async_trait]
 impl CloudEventBackend for KafkaBackend {
     async fn publish(&self, event: Event) -> anyhow::Result<()> {
+        self.publish_to_topic(event, &self.topic).await
+    }
+
+    async fn publish_to_topic(&self, event: Event, topic: &str) -> anyhow::Result<()> {
         let key: String = match event.extension("tabular-id") {
             Some(extension_value) => extension_value.to_string(),
             None => String::new(),
@@ -130,7 +134,7 @@ impl CloudEventBackend for KafkaBackend {
         let delivery_status = self
             .producer
             .send(
-                FutureRecord::to(&self.topic)
+                FutureRecord::to(topic)
                     .message_record(&message_record)
                     .key(&key[..]),
                 Duration::from_secs(1),
@@ -144,8 +148,7 @@ impl CloudEventBackend for KafkaBackend {
                 timestamp,
             }) => {
                 tracing::debug!(
-                    "CloudEvents event sent via kafka to topic: {}, partition: {partition}, offset: {offset}, timestamp: {timestamp:?}",
-                    &self.topic,
+                    "CloudEvents event sent via kafka to topic: {topic}, partition: {partition}, offset: {offset}, timestamp: {timestamp:?}",
                 );
                 Ok(())
             }
@@ -18,6 +18,7 @@ use iceberg_ext::{
     },
     configs::Location,
 };
+use itertools::Itertools;
 use uuid::Uuid;
 
 use super::{TableId, UndropTabularResponse, ViewId, WarehouseId};
@@ -27,12 +28,18 @@ use crate::{
             types::{DropParams, Prefix},
             v1::{DataAccess, NamespaceParameters, TableParameters, ViewParameters},
         },
-        management::v1::warehouse::UndropTabularsRequest,
+        management::v1::{
+            user::Impersonation, view::RollbackViewResponse, warehouse::UndropTabularsRequest,
+        },
         RequestMetadata,
     },
     catalog::tables::{maybe_body_to_json, CommitContext},
     service::{
+        authn::Actor,
+        authz::AuthorizationContext,
         endpoint_hooks::{EndpointHook, ViewCommit},
+        event_publisher::routing::{route_event, EventRoutingRuleSource},
+        maintenance::CompactionRecommendation,
         tabular_idents::TabularId,
     },
     CONFIG,
@@ -42,6 +49,7 @@ use crate::{
 pub mod kafka;
 #[cfg(feature = "nats")]
 pub mod nats;
+pub mod routing;
 
 /// Builds the default cloud event backends from the configuration.
 ///
@@ -86,49 +94,83 @@ impl EndpointHook for CloudEventsPublisher {
         request: Arc<CommitTransactionRequest>,
         _commits: Arc<Vec<CommitContext>>,
         table_ident_map: Arc<HashMap<TableIdent, TableId>>,
+        authorization: Arc<AuthorizationContext>,
         request_metadata: Arc<RequestMetadata>,
     ) -> anyhow::Result<()> {
-        let mut events = vec![];
-        let mut event_table_ids: Vec<(TableIdent, TableId)> = vec![];
-        let mut updates = vec![];
-        for commit_table_request in &request.table_changes {
-            if let Some(id) = &commit_table_request.identifier {
-                if let Some(uuid) = table_ident_map.get(id) {
-                    events.push(maybe_body_to_json(commit_table_request));
-                    event_table_ids.push((id.clone(), *uuid));
-                    updates.push(commit_table_request.updates.clone());
-                }
-            }
-        }
-        let number_of_events = events.len();
-        let mut futs = Vec::with_capacity(number_of_events);
-        for (event_sequence_number, (body, (table_ident, table_id))) in
-            events.into_iter().zip(event_table_ids).enumerate()
-        {
-            futs.push(
-                self.publish(
+        let actor = serde_json::to_string(request_metadata.actor())
+            .map_err(|e| anyhow::anyhow!(e).context("Failed to serialize actor"))?;
+        let impersonated_by = request_metadata.impersonated_by().map(ToString::to_string);
+
+        let changes = request
+            .table_changes
+            .iter()
+            .filter_map(|change| {
+                let ident = change.identifier.as_ref()?;
+                table_ident_map
+                    .get(ident)
+                    .map(|table_id| (ident, *table_id, change))
+            })
+            .collect::<Vec<_>>();
+
+        // Single table commits keep emitting `updateTable` events. Transactions
+        // spanning multiple tables emit a single aggregated `commitTransaction`
+        // event so that consumers observe them atomically.
+        if let [(table_ident, table_id, change)] = changes.as_slice() {
+            return self
+                .publish(
                     Uuid::now_v7(),
                     "updateTable",
-                    body,
+                    maybe_body_to_json(change),
                     EventMetadata {
-                        tabular_id: TabularId::Table(*table_id),
-                        warehouse_id,
-                        name: table_ident.name,
+                        tabular_id: Some(TabularId::Table(**table_id)),
+                        warehouse_id: Some(warehouse_id),
+                        name: table_ident.name.clone(),
                         namespace: table_ident.namespace.to_url_string(),
                         prefix: String::new(),
-                        num_events: number_of_events,
-                        sequence_number: event_sequence_number,
+                        num_events: 1,
+                        sequence_number: 0,
                         trace_id: request_metadata.request_id(),
-                        actor: serde_json::to_string(request_metadata.actor())
-                            .map_err(|e| anyhow::anyhow!(e).context("Failed to serialize actor"))?,
+                        actor,
+                        impersonated_by,
+                        authorization: Some(authorization.as_ref().clone()),
                     },
-                ),
-            );
+                )
+                .await
+                .context("Failed to publish `updateTable` event");
         }
-        futures::future::try_join_all(futs)
-            .await
-            .context("Failed to publish `updateTable` event")?;
-        Ok(())
+
+        let table_changes = changes
+            .iter()
+            .map(|(table_ident, table_id, change)| {
+                serde_json::json!({
+                    "table-id": **table_id,
+                    "identifier": table_ident,
+                    "requirements": maybe_body_to_json(&change.requirements),
+                    "updates": maybe_body_to_json(&change.updates),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        self.publish(
+            Uuid::now_v7(),
+            "commitTransaction",
+            serde_json::json!({ "table-changes": table_changes }),
+            EventMetadata {
+                tabular_id: None,
+                warehouse_id: Some(warehouse_id),
+                name: String::new(),
+                namespace: String::new(),
+                prefix: String::new(),
+                num_events: 1,
+                sequence_number: 0,
+                trace_id: request_metadata.request_id(),
+                actor,
+                impersonated_by,
+                authorization: Some(authorization.as_ref().clone()),
+            },
+        )
+        .await
+        .context("Failed to publish `commitTransaction` event")
     }
 
     async fn drop_table(
@@ -144,8 +186,8 @@ impl EndpointHook for CloudEventsPublisher {
             "dropTable",
             serde_json::Value::Null,
             EventMetadata {
-                tabular_id: TabularId::Table(*table_ident_uuid),
-                warehouse_id,
+                tabular_id: Some(TabularId::Table(*table_ident_uuid)),
+                warehouse_id: Some(warehouse_id),
                 name: table.name,
                 namespace: table.namespace.to_url_string(),
                 prefix: prefix.map(Prefix::into_string).unwrap_or_default(),
@@ -154,6 +196,8 @@ impl EndpointHook for CloudEventsPublisher {
                 trace_id: request_metadata.request_id(),
                 actor: serde_json::to_string(request_metadata.actor())
                     .map_err(|e| anyhow::anyhow!(e).context("Failed to serialize actor"))?,
+                impersonated_by: request_metadata.impersonated_by().map(ToString::to_string),
+                authorization: None,
             },
         )
         .await
@@ -174,8 +218,8 @@ impl EndpointHook for CloudEventsPublisher {
             "registerTable",
             serde_json::Value::Null,
             EventMetadata {
-                tabular_id: TabularId::Table(metadata.uuid()),
-                warehouse_id,
+                tabular_id: Some(TabularId::Table(metadata.uuid())),
+                warehouse_id: Some(warehouse_id),
                 name: request.name.clone(),
                 namespace: namespace.to_url_string(),
                 prefix: prefix.map(Prefix::into_string).unwrap_or_default(),
@@ -184,6 +228,8 @@ impl EndpointHook for CloudEventsPublisher {
                 trace_id: request_metadata.request_id(),
                 actor: serde_json::to_string(request_metadata.actor())
                     .map_err(|e| anyhow::anyhow!(e).context("Failed to serialize actor"))?,
+                impersonated_by: request_metadata.impersonated_by().map(ToString::to_string),
+                authorization: None,
             },
         )
         .await
@@ -206,8 +252,8 @@ impl EndpointHook for CloudEventsPublisher {
             "createTable",
             serde_json::Value::Null,
             EventMetadata {
-                tabular_id: TabularId::Table(metadata.uuid()),
-                warehouse_id,
+                tabular_id: Some(TabularId::Table(metadata.uuid())),
+                warehouse_id: Some(warehouse_id),
                 name: request.name.clone(),
                 namespace: namespace.to_url_string(),
                 prefix: prefix.map(Prefix::into_string).unwrap_or_default(),
@@ -216,6 +262,8 @@ impl EndpointHook for CloudEventsPublisher {
                 trace_id: request_metadata.request_id(),
                 actor: serde_json::to_string(request_metadata.actor())
                     .map_err(|e| anyhow::anyhow!(e).context("Failed to serialize actor"))?,
+                impersonated_by: request_metadata.impersonated_by().map(ToString::to_string),
+                authorization: None,
             },
         )
         .await
@@ -235,8 +283,8 @@ impl EndpointHook for CloudEventsPublisher {
             "renameTable",
             serde_json::Value::Null,
             EventMetadata {
-                tabular_id: TabularId::Table(*table_ident_uuid),
-                warehouse_id,
+                tabular_id: Some(TabularId::Table(*table_ident_uuid)),
+                warehouse_id: Some(warehouse_id),
                 name: request.source.name.clone(),
                 namespace: request.source.namespace.to_url_string(),
                 prefix: String::new(),
@@ -245,6 +293,8 @@ impl EndpointHook for CloudEventsPublisher {
                 trace_id: request_metadata.request_id(),
                 actor: serde_json::to_string(request_metadata.actor())
                     .map_err(|e| anyhow::anyhow!(e).context("Failed to serialize actor"))?,
+                impersonated_by: request_metadata.impersonated_by().map(ToString::to_string),
+                authorization: None,
             },
         )
         .await
@@ -267,8 +317,8 @@ impl EndpointHook for CloudEventsPublisher {
             "createView",
             maybe_body_to_json(&request),
             EventMetadata {
-                tabular_id: TabularId::View(metadata.uuid()),
-                warehouse_id,
+                tabular_id: Some(TabularId::View(metadata.uuid())),
+                warehouse_id: Some(warehouse_id),
                 name: request.name.clone(),
                 namespace: parameters.namespace.to_url_string(),
                 prefix: parameters
@@ -280,6 +330,8 @@ impl EndpointHook for CloudEventsPublisher {
                 trace_id: request_metadata.request_id(),
                 actor: serde_json::to_string(request_metadata.actor())
                     .map_err(|e| anyhow::anyhow!(e).context("Failed to serialize actor"))?,
+                impersonated_by: request_metadata.impersonated_by().map(ToString::to_string),
+                authorization: None,
             },
         )
         .await
@@ -301,8 +353,8 @@ impl EndpointHook for CloudEventsPublisher {
             "updateView",
             maybe_body_to_json(request),
             EventMetadata {
-                tabular_id: TabularId::View(metadata.new_metadata.uuid()),
-                warehouse_id,
+                tabular_id: Some(TabularId::View(metadata.new_metadata.uuid())),
+                warehouse_id: Some(warehouse_id),
                 name: parameters.view.name,
                 namespace: parameters.view.namespace.to_url_string(),
                 prefix: parameters
@@ -314,6 +366,8 @@ impl EndpointHook for CloudEventsPublisher {
                 trace_id: request_metadata.request_id(),
                 actor: serde_json::to_string(request_metadata.actor())
                     .map_err(|e| anyhow::anyhow!(e).context("Failed to serialize actor"))?,
+                impersonated_by: request_metadata.impersonated_by().map(ToString::to_string),
+                authorization: None,
             },
         )
         .await
@@ -334,8 +388,8 @@ impl EndpointHook for CloudEventsPublisher {
             "dropView",
             serde_json::Value::Null,
             EventMetadata {
-                tabular_id: TabularId::View(*view_ident_uuid),
-                warehouse_id,
+                tabular_id: Some(TabularId::View(*view_ident_uuid)),
+                warehouse_id: Some(warehouse_id),
                 name: parameters.view.name,
                 namespace: parameters.view.namespace.to_url_string(),
                 prefix: parameters
@@ -347,6 +401,8 @@ impl EndpointHook for CloudEventsPublisher {
                 trace_id: request_metadata.request_id(),
                 actor: serde_json::to_string(request_metadata.actor())
                     .map_err(|e| anyhow::anyhow!(e).context("Failed to serialize actor"))?,
+                impersonated_by: request_metadata.impersonated_by().map(ToString::to_string),
+                authorization: None,
             },
         )
         .await
@@ -366,8 +422,8 @@ impl EndpointHook for CloudEventsPublisher {
             "renameView",
             serde_json::Value::Null,
             EventMetadata {
-                tabular_id: TabularId::View(*view_ident_uuid),
-                warehouse_id,
+                tabular_id: Some(TabularId::View(*view_ident_uuid)),
+                warehouse_id: Some(warehouse_id),
                 name: request.source.name.clone(),
                 namespace: request.source.namespace.to_url_string(),
                 prefix: String::new(),
@@ -376,6 +432,8 @@ impl EndpointHook for CloudEventsPublisher {
                 trace_id: request_metadata.request_id(),
                 actor: serde_json::to_string(request_metadata.actor())
                     .map_err(|e| anyhow::anyhow!(e).context("Failed to serialize actor"))?,
+                impersonated_by: request_metadata.impersonated_by().map(ToString::to_string),
+                authorization: None,
             },
         )
         .await
@@ -383,6 +441,38 @@ impl EndpointHook for CloudEventsPublisher {
         Ok(())
     }
 
+    async fn rollback_view(
+        &self,
+        warehouse_id: WarehouseId,
+        view_id: ViewId,
+        view: Arc<TableIdent>,
+        rollback: Arc<RollbackViewResponse>,
+        request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        self.publish(
+            Uuid::now_v7(),
+            "rollbackView",
+            maybe_body_to_json(rollback),
+            EventMetadata {
+                tabular_id: Some(TabularId::View(*view_id)),
+                warehouse_id: Some(warehouse_id),
+                name: view.name.clone(),
+                namespace: view.namespace.to_url_string(),
+                prefix: String::new(),
+                num_events: 1,
+                sequence_number: 0,
+                trace_id: request_metadata.request_id(),
+                actor: serde_json::to_string(request_metadata.actor())
+                    .map_err(|e| anyhow::anyhow!(e).context("Failed to serialize actor"))?,
+                impersonated_by: request_metadata.impersonated_by().map(ToString::to_string),
+                authorization: None,
+            },
+        )
+        .await
+        .context("Failed to publish `rollbackView` event")?;
+        Ok(())
+    }
+
     async fn undrop_tabular(
         &self,
         warehouse_id: WarehouseId,
@@ -399,8 +489,8 @@ impl EndpointHook for CloudEventsPublisher {
                     "undropTabulars",
                     serde_json::Value::Null,
                     EventMetadata {
-                        tabular_id: TabularId::from(utr.table_ident),
-                        warehouse_id,
+                        tabular_id: Some(TabularId::from(utr.table_ident)),
+                        warehouse_id: Some(warehouse_id),
                         name: utr.name.clone(),
                         namespace: utr.namespace.to_url_string(),
                         prefix: String::new(),
@@ -409,6 +499,10 @@ impl EndpointHook for CloudEventsPublisher {
                         trace_id: request_metadata.request_id(),
                         actor: serde_json::to_string(request_metadata.actor())
                             .map_err(|e| anyhow::anyhow!(e).context("Failed to serialize actor"))?,
+                        impersonated_by: request_metadata
+                            .impersonated_by()
+                            .map(ToString::to_string),
+                        authorization: None,
                     },
                 ),
             );
@@ -422,6 +516,66 @@ impl EndpointHook for CloudEventsPublisher {
             .context("Failed to publish `undropTabulars` event")?;
         Ok(())
     }
+
+    async fn start_impersonation(
+        &self,
+        impersonation: Arc<Impersonation>,
+        request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        self.publish_impersonation_event("startImpersonation", &impersonation, &request_metadata)
+            .await
+            .context("Failed to publish `startImpersonation` event")
+    }
+
+    async fn stop_impersonation(
+        &self,
+        impersonation: Arc<Impersonation>,
+        request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        self.publish_impersonation_event("stopImpersonation", &impersonation, &request_metadata)
+            .await
+            .context("Failed to publish `stopImpersonation` event")
+    }
+
+    async fn compaction_requested(
+        &self,
+        warehouse_id: WarehouseId,
+        table_id: TableId,
+        table: Arc<TableIdent>,
+        recommendations: Arc<Vec<CompactionRecommendation>>,
+        request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        let num_events = recommendations.len();
+        let actor = serde_json::to_string(request_metadata.actor())
+            .map_err(|e| anyhow::anyhow!(e).context("Failed to serialize actor"))?;
+        futures::future::try_join_all(recommendations.iter().enumerate().map(
+            |(idx, recommendation)| {
+                self.publish(
+                    Uuid::now_v7(),
+                    "compactionRequested",
+                    maybe_body_to_json(recommendation),
+                    EventMetadata {
+                        tabular_id: Some(TabularId::Table(*table_id)),
+                        warehouse_id: Some(warehouse_id),
+                        name: table.name.clone(),
+                        namespace: table.namespace.to_url_string(),
+                        prefix: String::new(),
+                        num_events,
+                        sequence_number: idx,
+                        trace_id: request_metadata.request_id(),
+                        actor: actor.clone(),
+                        impersonated_by: request_metadata
+                            .impersonated_by()
+                            .map(ToString::to_string),
+                        authorization: None,
+                    },
+                )
+            },
+        ))
+        .await
+        .context("Failed to publish `compactionRequested` event")?;
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -479,10 +633,50 @@ impl CloudEventsPublisher {
     }
 }
 
+impl CloudEventsPublisher {
+    async fn publish_impersonation_event(
+        &self,
+        typ: &str,
+        impersonation: &Impersonation,
+        request_metadata: &RequestMetadata,
+    ) -> anyhow::Result<()> {
+        // The impersonator is the actor of both start and stop events,
+        // independent of whether the request itself was impersonated.
+        let actor = Actor::Principal(
+            request_metadata
+                .impersonated_by()
+                .or(request_metadata.user_id())
+                .unwrap_or(&impersonation.impersonator_id)
+                .clone(),
+        );
+        self.publish(
+            Uuid::now_v7(),
+            typ,
+            maybe_body_to_json(impersonation),
+            EventMetadata {
+                tabular_id: None,
+                warehouse_id: None,
+                name: impersonation.user_id.to_string(),
+                namespace: String::new(),
+                prefix: String::new(),
+                num_events: 1,
+                sequence_number: 0,
+                trace_id: request_metadata.request_id(),
+                actor: serde_json::to_string(&actor)
+                    .map_err(|e| anyhow::anyhow!(e).context("Failed to serialize actor"))?,
+                impersonated_by: None,
+                authorization: None,
+            },
+        )
+        .await
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct EventMetadata {
-    pub tabular_id: TabularId,
-    pub warehouse_id: WarehouseId,
+    /// Tabular the event refers to. `None` for events not related to a tabular.
+    pub tabular_id: Option<TabularId>,
+    pub warehouse_id: Option<WarehouseId>,
     pub name: String,
     pub namespace: String,
     pub prefix: String,
@@ -490,6 +684,10 @@ pub struct EventMetadata {
     pub sequence_number: usize,
     pub trace_id: Uuid,
     pub actor: String,
+    /// Server admin impersonating the actor, if any.
+    pub impersonated_by: Option<String>,
+    /// Authorization of the change, if the event describes one.
+    pub authorization: Option<AuthorizationContext>,
 }
 
 #[derive(Debug)]
@@ -511,6 +709,8 @@ pub enum CloudEventsMessage {
 pub struct CloudEventsPublisherBackgroundTask {
     pub source: tokio::sync::mpsc::Receiver<CloudEventsMessage>,
     pub sinks: Vec<Arc<dyn CloudEventBackend + Sync + Send>>,
+    /// Routing rules of warehouses. If not set, all events are published to all sinks.
+    pub routing_rules: Option<Arc<dyn EventRoutingRuleSource>>,
 }
 
 impl CloudEventsPublisherBackgroundTask {
@@ -534,8 +734,11 @@ impl CloudEventsPublisherBackgroundTask {
                         .map(|os| os.to_string_lossy().to_string())
                         .unwrap_or("hostname-unavailable".into())
                 ))
-                .ty(typ)
+                .ty(typ.clone())
                 .data("application/json", data);
+            let routes = self
+                .routes(metadata.warehouse_id, &typ, &metadata.namespace)
+                .await;
 
             let EventMetadata {
                 tabular_id,
@@ -547,12 +750,37 @@ impl CloudEventsPublisherBackgroundTask {
                 sequence_number,
                 trace_id,
                 actor,
+                impersonated_by,
+                authorization,
             } = metadata;
             // TODO: this could be more elegant with a proc macro to give us IntoIter for EventMetadata
+            let mut event_builder = event_builder;
+            if let Some(tabular_id) = tabular_id {
+                event_builder = event_builder
+                    .extension("tabular-type", tabular_id.typ_str())
+                    .extension("tabular-id", tabular_id.to_string());
+            }
+            if let Some(warehouse_id) = warehouse_id {
+                event_builder = event_builder.extension("warehouse-id", warehouse_id.to_string());
+            }
+            if let Some(impersonated_by) = impersonated_by {
+                event_builder = event_builder.extension("impersonated-by", impersonated_by);
+            }
+            if let Some(authorization) = authorization {
+                event_builder = event_builder
+                    .extension("authz-decision-id", authorization.decision_id.to_string())
+                    .extension("authz-action", authorization.action)
+                    .extension("project-id", authorization.project_id.to_string())
+                    .extension(
+                        "actor-roles",
+                        authorization
+                            .roles
+                            .iter()
+                            .map(ToString::to_string)
+                            .join(","),
+                    );
+            }
             let event = event_builder
-                .extension("tabular-type", tabular_id.typ_str())
-                .extension("tabular-id", tabular_id.to_string())
-                .extension("warehouse-id", warehouse_id.to_string())
                 .extension("name", name.to_string())
                 .extension("namespace", namespace.to_string())
                 .extension("prefix", prefix.to_string())
@@ -566,10 +794,15 @@ impl CloudEventsPublisherBackgroundTask {
                 .extension("actor", actor)
                 .build()?;
 
-            let publish_futures = self.sinks.iter().map(|sink| {
+            let publish_futures = routes.into_iter().map(|(idx, topic)| {
+                let sink = &self.sinks[idx];
                 let event = event.clone();
                 async move {
-                    if let Err(e) = sink.publish(event).await {
+                    let result = match topic {
+                        Some(topic) => sink.publish_to_topic(event, &topic).await,
+                        None => sink.publish(event).await,
+                    };
+                    if let Err(e) = result {
                         tracing::warn!(
                             "Failed to emit event with id: '{}' on sink: '{}' due to: '{}'.",
                             id,
@@ -587,11 +820,50 @@ impl CloudEventsPublisherBackgroundTask {
 
         Ok(())
     }
+
+    /// Sinks and topics to publish an event to according to the routing rules of its
+    /// warehouse. Falls back to all sinks if the rules can't be loaded.
+    async fn routes(
+        &self,
+        warehouse_id: Option<WarehouseId>,
+        typ: &str,
+        namespace: &str,
+    ) -> Vec<(usize, Option<String>)> {
+        let all_sinks = || (0..self.sinks.len()).map(|idx| (idx, None)).collect();
+        let (Some(routing_rules), Some(warehouse_id)) = (&self.routing_rules, warehouse_id) else {
+            return all_sinks();
+        };
+        match routing_rules.routing_rules(warehouse_id).await {
+            Ok(rules) => {
+                let sink_names = self
+                    .sinks
+                    .iter()
+                    .map(|sink| sink.name())
+                    .collect::<Vec<_>>();
+                route_event(&rules, &sink_names, typ, namespace)
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to load event routing rules of warehouse {warehouse_id}, publishing to all sinks: {}",
+                    e.error
+                );
+                all_sinks()
+            }
+        }
+    }
 }
 
 #[async_trait]
 pub trait CloudEventBackend: Debug {
     async fn publish(&self, event: Event) -> anyhow::Result<()>;
+
+    /// Publish the event to `topic` instead of the configured topic.
+    /// Backends without topics publish as usual.
+    async fn publish_to_topic(&self, event: Event, topic: &str) -> anyhow::Result<()> {
+        let _ = topic;
+        self.publish(event).await
+    }
+
     fn name(&self) -> &str;
 }
 
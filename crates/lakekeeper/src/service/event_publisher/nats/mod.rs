@@ -63,9 +63,13 @@ pub struct NatsBackend {
 #[async_trait]
 impl CloudEventBackend for NatsBackend {
     async fn publish(&self, event: Event) -> anyhow::Result<()> {
+        self.publish_to_topic(event, &self.topic).await
+    }
+
+    async fn publish_to_topic(&self, event: Event, topic: &str) -> anyhow::Result<()> {
         Ok(self
             .client
-            .publish(self.topic.clone(), serde_json::to_vec(&event)?.into())
+            .publish(topic.to_string(), serde_json::to_vec(&event)?.into())
             .await?)
     }
 
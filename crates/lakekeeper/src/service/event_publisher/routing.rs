@@ -0,0 +1,318 @@
+//! Routing of cloud events to individual sinks and topics.
+//!
+//! Warehouses can configure rules that fan events out to specific sinks and topics depending
+//! on the namespace and type of the event, for example to publish commits in `prod` to a
+//! dedicated Kafka topic. Events that match no rule are published to all sinks with their
+//! configured topics. Rules are cached for a short time, so changes apply to all instances
+//! without a restart.
+use std::{
+    fmt::Debug,
+    marker::PhantomData,
+    sync::{Arc, LazyLock},
+    time::Duration,
+};
+
+use iceberg_ext::catalog::rest::ErrorModel;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    service::{Catalog, Result},
+    WarehouseId,
+};
+
+/// Maximum number of routing rules per warehouse.
+pub const MAX_EVENT_ROUTING_RULES: usize = 100;
+
+static EVENT_ROUTING_RULES_CACHE: LazyLock<
+    moka::future::Cache<WarehouseId, Arc<Vec<EventRoutingRule>>>,
+> = LazyLock::new(|| {
+    moka::future::Cache::builder()
+        .max_capacity(10_000)
+        .time_to_live(Duration::from_secs(30))
+        .build()
+});
+
+/// Publishes matching events of a warehouse to specific sinks and topics.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct EventRoutingRule {
+    /// Namespace the event must belong to, including its children, for example `["prod"]`.
+    /// Events without a namespace, such as multi-table transactions, don't match.
+    /// Matches all events if not set.
+    #[serde(default)]
+    pub namespace_prefix: Option<Vec<String>>,
+    /// Types of events the rule applies to, for example `updateTable` or `dropTable`.
+    /// Applies to all types if empty.
+    #[serde(default)]
+    pub event_types: Vec<String>,
+    /// Names of the sinks matching events are published to, for example `kafka-publisher`.
+    /// Publishes to all sinks if empty.
+    #[serde(default)]
+    pub sinks: Vec<String>,
+    /// Kafka topic or NATS subject to publish to instead of the configured one.
+    /// Sinks without topics ignore it.
+    #[serde(default)]
+    pub topic: Option<String>,
+}
+
+impl EventRoutingRule {
+    fn matches(&self, typ: &str, namespace: &[&str]) -> bool {
+        if !self.event_types.is_empty() && !self.event_types.iter().any(|t| t == typ) {
+            return false;
+        }
+        match &self.namespace_prefix {
+            Some(prefix) => {
+                !namespace.is_empty()
+                    && prefix.len() <= namespace.len()
+                    && prefix.iter().zip(namespace).all(|(p, n)| p == n)
+            }
+            None => true,
+        }
+    }
+}
+
+/// Validate the routing rules of a warehouse.
+///
+/// # Errors
+/// If there are too many rules or a rule contains empty values.
+pub fn validate_event_routing_rules(rules: &[EventRoutingRule]) -> Result<()> {
+    if rules.len() > MAX_EVENT_ROUTING_RULES {
+        return Err(invalid_rule(format!(
+            "At most {MAX_EVENT_ROUTING_RULES} event routing rules are allowed per warehouse, got {}.",
+            rules.len()
+        )));
+    }
+    for (idx, rule) in rules.iter().enumerate() {
+        if rule
+            .namespace_prefix
+            .as_ref()
+            .is_some_and(|prefix| prefix.is_empty() || prefix.iter().any(String::is_empty))
+        {
+            return Err(invalid_rule(format!(
+                "Namespace prefix of rule {idx} must consist of non-empty parts."
+            )));
+        }
+        if rule.event_types.iter().any(String::is_empty) {
+            return Err(invalid_rule(format!(
+                "Event types of rule {idx} must not be empty."
+            )));
+        }
+        if rule.sinks.iter().any(String::is_empty) {
+            return Err(invalid_rule(format!(
+                "Sink names of rule {idx} must not be empty."
+            )));
+        }
+        if rule.topic.as_ref().is_some_and(|t| t.trim().is_empty()) {
+            return Err(invalid_rule(format!(
+                "Topic of rule {idx} must not be empty."
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn invalid_rule(message: String) -> crate::api::IcebergErrorResponse {
+    ErrorModel::bad_request(message, "InvalidEventRoutingRule", None).into()
+}
+
+/// Sinks and topics an event is published to, as pairs of the index in `sink_names`
+/// and the topic overriding the configured one. Events matching rules are only published
+/// to the sinks of these rules.
+///
+/// `namespace` is the namespace of the event with parts separated by the unit separator,
+/// as used in the `namespace` extension of events.
+#[must_use]
+pub fn route_event(
+    rules: &[EventRoutingRule],
+    sink_names: &[&str],
+    typ: &str,
+    namespace: &str,
+) -> Vec<(usize, Option<String>)> {
+    let namespace = namespace
+        .split('\u{1f}')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>();
+
+    let matching_rules = rules
+        .iter()
+        .filter(|rule| rule.matches(typ, &namespace))
+        .collect::<Vec<_>>();
+    if matching_rules.is_empty() {
+        return (0..sink_names.len()).map(|idx| (idx, None)).collect();
+    }
+
+    let mut routes: Vec<(usize, Option<String>)> = vec![];
+    for rule in matching_rules {
+        for (idx, name) in sink_names.iter().enumerate() {
+            if !rule.sinks.is_empty() && !rule.sinks.iter().any(|sink| sink == name) {
+                continue;
+            }
+            let route = (idx, rule.topic.clone());
+            if !routes.contains(&route) {
+                routes.push(route);
+            }
+        }
+    }
+    routes
+}
+
+/// Source of the routing rules of warehouses for the cloud events background task.
+#[async_trait::async_trait]
+pub trait EventRoutingRuleSource: Debug + Send + Sync + 'static {
+    async fn routing_rules(&self, warehouse_id: WarehouseId) -> Result<Arc<Vec<EventRoutingRule>>>;
+}
+
+/// Loads routing rules from the catalog.
+pub struct CatalogEventRoutingRules<C: Catalog> {
+    catalog_state: C::State,
+    _catalog: PhantomData<C>,
+}
+
+impl<C: Catalog> CatalogEventRoutingRules<C> {
+    #[must_use]
+    pub fn new(catalog_state: C::State) -> Self {
+        Self {
+            catalog_state,
+            _catalog: PhantomData,
+        }
+    }
+}
+
+impl<C: Catalog> Debug for CatalogEventRoutingRules<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CatalogEventRoutingRules")
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait::async_trait]
+impl<C: Catalog> EventRoutingRuleSource for CatalogEventRoutingRules<C> {
+    async fn routing_rules(&self, warehouse_id: WarehouseId) -> Result<Arc<Vec<EventRoutingRule>>> {
+        EVENT_ROUTING_RULES_CACHE
+            .try_get_with(warehouse_id, async {
+                C::get_warehouse_event_routing_rules(warehouse_id, self.catalog_state.clone())
+                    .await
+                    .map(Arc::new)
+            })
+            .await
+            .map_err(|e| {
+                ErrorModel::new(
+                    e.error.message.as_str(),
+                    e.error.r#type.as_str(),
+                    e.error.code,
+                    None,
+                )
+                .into()
+            })
+    }
+}
+
+/// Drop the cached routing rules of a warehouse after they changed.
+pub(crate) async fn invalidate_event_routing_rules(warehouse_id: WarehouseId) {
+    EVENT_ROUTING_RULES_CACHE.invalidate(&warehouse_id).await;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SINKS: &[&str] = &["kafka-publisher", "nats-publisher"];
+
+    fn rule(
+        namespace_prefix: Option<&[&str]>,
+        event_types: &[&str],
+        sinks: &[&str],
+    ) -> EventRoutingRule {
+        EventRoutingRule {
+            namespace_prefix: namespace_prefix
+                .map(|prefix| prefix.iter().map(ToString::to_string).collect()),
+            event_types: event_types.iter().map(ToString::to_string).collect(),
+            sinks: sinks.iter().map(ToString::to_string).collect(),
+            topic: None,
+        }
+    }
+
+    #[test]
+    fn test_unmatched_events_are_published_to_all_sinks() {
+        let rules = vec![rule(
+            Some(&["prod"]),
+            &["updateTable"],
+            &["kafka-publisher"],
+        )];
+        assert_eq!(
+            route_event(&rules, SINKS, "updateTable", "dev"),
+            vec![(0, None), (1, None)]
+        );
+        assert_eq!(
+            route_event(&[], SINKS, "dropTable", "prod"),
+            vec![(0, None), (1, None)]
+        );
+    }
+
+    #[test]
+    fn test_route_by_namespace_prefix_and_type() {
+        let mut commits = rule(Some(&["prod"]), &["updateTable"], &["kafka-publisher"]);
+        commits.topic = Some("prod-commits".to_string());
+        let drops = rule(None, &["dropTable", "dropView"], &["nats-publisher"]);
+        let rules = vec![commits, drops];
+
+        assert_eq!(
+            route_event(&rules, SINKS, "updateTable", "prod\u{1f}sales"),
+            vec![(0, Some("prod-commits".to_string()))]
+        );
+        assert_eq!(
+            route_event(&rules, SINKS, "dropTable", "prod\u{1f}sales"),
+            vec![(1, None)]
+        );
+        // `production` does not start with the namespace `prod`
+        assert_eq!(
+            route_event(&rules, SINKS, "updateTable", "production"),
+            vec![(0, None), (1, None)]
+        );
+        // Multi-table transactions have no namespace
+        assert_eq!(
+            route_event(&rules, SINKS, "updateTable", ""),
+            vec![(0, None), (1, None)]
+        );
+    }
+
+    #[test]
+    fn test_matching_rules_are_fanned_out() {
+        let mut topic_a = rule(Some(&["prod"]), &[], &[]);
+        topic_a.topic = Some("a".to_string());
+        let mut topic_b = rule(None, &["updateTable"], &["kafka-publisher"]);
+        topic_b.topic = Some("b".to_string());
+        let rules = vec![topic_a.clone(), topic_b, topic_a];
+
+        assert_eq!(
+            route_event(&rules, SINKS, "updateTable", "prod"),
+            vec![
+                (0, Some("a".to_string())),
+                (1, Some("a".to_string())),
+                (0, Some("b".to_string()))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_matching_rule_with_unknown_sink_drops_event() {
+        let rules = vec![rule(None, &["dropTable"], &["webhook"])];
+        assert!(route_event(&rules, SINKS, "dropTable", "prod").is_empty());
+    }
+
+    #[test]
+    fn test_validate_event_routing_rules() {
+        assert!(validate_event_routing_rules(&[rule(Some(&["prod"]), &[], &[])]).is_ok());
+        assert!(validate_event_routing_rules(&[rule(Some(&[]), &[], &[])]).is_err());
+        assert!(validate_event_routing_rules(&[rule(None, &[""], &[])]).is_err());
+        assert!(validate_event_routing_rules(&[rule(None, &[], &[""])]).is_err());
+        let mut empty_topic = rule(None, &[], &[]);
+        empty_topic.topic = Some(" ".to_string());
+        assert!(validate_event_routing_rules(&[empty_topic]).is_err());
+        assert!(validate_event_routing_rules(&vec![
+            rule(None, &[], &[]);
+            MAX_EVENT_ROUTING_RULES + 1
+        ])
+        .is_err());
+    }
+}
@@ -0,0 +1,379 @@
+//! Read-only warehouses that mirror the Iceberg tables of an external catalog.
+//!
+//! Connectors such as [`super::glue_federation`] and [`super::hms_federation`] list the
+//! databases and tables of their catalog. Databases are mirrored as namespaces, and Iceberg
+//! tables as tables pointing to the metadata file the external catalog references. Tables
+//! keep the uuid of their metadata as id. Only the metadata of tables whose metadata
+//! location changed is read again.
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use uuid::Uuid;
+
+use crate::{
+    api::management::v1::TabularType,
+    service::task_queue::catalog_snapshot_export_queue::{
+        CatalogSnapshotNamespace, CatalogSnapshotState, CatalogSnapshotTabular,
+    },
+};
+
+/// Value of the `table_type` parameter of Iceberg tables in Glue and the Hive Metastore.
+const ICEBERG_TABLE_TYPE: &str = "ICEBERG";
+const TABLE_TYPE_PARAMETER: &str = "table_type";
+const METADATA_LOCATION_PARAMETER: &str = "metadata_location";
+/// Namespace property holding the location of a database.
+pub(crate) const LOCATION_PROPERTY: &str = "location";
+
+/// Database of an external catalog and its Iceberg tables.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FederatedDatabase {
+    pub(crate) name: String,
+    pub(crate) properties: BTreeMap<String, String>,
+    pub(crate) tables: Vec<FederatedTable>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FederatedTable {
+    pub(crate) name: String,
+    pub(crate) metadata_location: String,
+}
+
+impl FederatedDatabase {
+    /// Database with its parameters as properties and `location` as `location` property.
+    /// `tables` are the names and parameters of all tables, only Iceberg tables are kept.
+    pub(crate) fn new(
+        name: String,
+        mut properties: BTreeMap<String, String>,
+        location: Option<String>,
+        tables: impl IntoIterator<Item = (String, HashMap<String, String>)>,
+    ) -> Self {
+        if let Some(location) = location {
+            properties.insert(LOCATION_PROPERTY.to_string(), location);
+        }
+        let tables = tables
+            .into_iter()
+            .filter_map(|(name, parameters)| {
+                iceberg_metadata_location(&parameters).map(|metadata_location| FederatedTable {
+                    name,
+                    metadata_location: metadata_location.to_string(),
+                })
+            })
+            .collect();
+        Self {
+            name,
+            properties,
+            tables,
+        }
+    }
+}
+
+/// Location of the current metadata file if the parameters belong to an Iceberg table.
+fn iceberg_metadata_location(parameters: &HashMap<String, String>) -> Option<&str> {
+    let is_iceberg = parameters
+        .get(TABLE_TYPE_PARAMETER)
+        .is_some_and(|t| t.eq_ignore_ascii_case(ICEBERG_TABLE_TYPE));
+    if !is_iceberg {
+        return None;
+    }
+    parameters
+        .get(METADATA_LOCATION_PARAMETER)
+        .map(String::as_str)
+}
+
+/// Id of the table at `metadata_location`, if it is already known.
+///
+/// Tables whose metadata location did not change keep their id, so their metadata does
+/// not need to be read.
+pub(crate) fn known_table_id(
+    current: &CatalogSnapshotState,
+    database: &str,
+    table: &FederatedTable,
+) -> Option<Uuid> {
+    current
+        .tabulars
+        .iter()
+        .find(|t| {
+            t.typ == TabularType::Table
+                && t.namespace.len() == 1
+                && t.namespace[0] == database
+                && t.name == table.name
+                && t.metadata_location.as_deref() == Some(table.metadata_location.as_str())
+        })
+        .map(|t| t.tabular_id)
+}
+
+/// State of the federated warehouse that mirrors `databases`.
+///
+/// `table_ids` maps the metadata locations of tables to the uuid of their metadata.
+/// Tables without id are not mirrored. Namespaces keep the id they have in `current`.
+pub(crate) fn target_state(
+    source: &str,
+    current: &CatalogSnapshotState,
+    databases: &[FederatedDatabase],
+    table_ids: &HashMap<String, Uuid>,
+) -> CatalogSnapshotState {
+    let namespace_ids = current
+        .namespaces
+        .iter()
+        .filter(|n| !n.deleted)
+        .map(|n| (n.name.clone(), n.namespace_id))
+        .collect::<HashMap<_, _>>();
+
+    let mut namespaces = Vec::with_capacity(databases.len());
+    let mut tabulars = Vec::new();
+    let mut seen_table_ids = HashSet::new();
+    for database in databases {
+        let name = vec![database.name.clone()];
+        namespaces.push(CatalogSnapshotNamespace {
+            namespace_id: namespace_ids
+                .get(&name)
+                .copied()
+                .unwrap_or_else(Uuid::now_v7),
+            name: name.clone(),
+            properties: database.properties.clone(),
+            protected: false,
+            deleted: false,
+        });
+
+        for table in &database.tables {
+            let Some(table_id) = table_ids.get(&table.metadata_location) else {
+                continue;
+            };
+            // Tables can be registered multiple times, ids must be unique.
+            if !seen_table_ids.insert(*table_id) {
+                tracing::warn!(
+                    "Table {}.{} of {source} is not mirrored: Table {table_id} is already registered under another name.",
+                    database.name,
+                    table.name
+                );
+                continue;
+            }
+            tabulars.push(CatalogSnapshotTabular {
+                tabular_id: *table_id,
+                typ: TabularType::Table,
+                namespace: name.clone(),
+                name: table.name.clone(),
+                metadata_location: Some(table.metadata_location.clone()),
+                protected: false,
+                deleted: false,
+            });
+        }
+    }
+
+    CatalogSnapshotState {
+        warehouse: current.warehouse.clone(),
+        task_queue_configs: current.task_queue_configs.clone(),
+        namespaces,
+        tabulars,
+        access: None,
+    }
+}
+
+#[cfg(any(feature = "glue-federation", feature = "hms-federation"))]
+pub(crate) use sync::sync_federated_warehouse;
+
+#[cfg(any(feature = "glue-federation", feature = "hms-federation"))]
+mod sync {
+    use std::{collections::HashMap, str::FromStr};
+
+    use iceberg_ext::{catalog::rest::ErrorModel, configs::Location};
+
+    use super::{known_table_id, target_state, FederatedDatabase};
+    use crate::{
+        catalog::{io::read_metadata_file, maybe_get_secret},
+        request_metadata::RequestMetadata,
+        service::{
+            authz::Authorizer,
+            mirror::{apply_catalog_state, MirrorOptions},
+            Catalog, Result, SecretStore, Transaction,
+        },
+        WarehouseId,
+    };
+
+    /// Mirror `databases` of the external catalog `source` into the federated warehouse.
+    pub(crate) async fn sync_federated_warehouse<C: Catalog, A: Authorizer, S: SecretStore>(
+        warehouse_id: WarehouseId,
+        source: &str,
+        databases: &[FederatedDatabase],
+        catalog_state: C::State,
+        authorizer: &A,
+        secret_store: &S,
+        owner: &RequestMetadata,
+    ) -> Result<()> {
+        let current = C::load_catalog_snapshot_state(warehouse_id, catalog_state.clone())
+            .await?
+            .ok_or_else(|| {
+                ErrorModel::not_found(
+                    format!("Federated warehouse {warehouse_id} not found"),
+                    "WarehouseNotFound",
+                    None,
+                )
+            })?;
+
+        // Ids of new tables are the uuids of their metadata. Metadata files are cached,
+        // so they are not read again when the changes are applied.
+        let mut t = C::Transaction::begin_read(catalog_state.clone()).await?;
+        let federated_warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
+        t.commit().await?;
+        let storage_secret =
+            maybe_get_secret(federated_warehouse.storage_secret_id, secret_store).await?;
+        let file_io = federated_warehouse
+            .storage_profile
+            .file_io(storage_secret.as_ref())
+            .await?;
+        let mut table_ids = HashMap::new();
+        for database in databases {
+            for table in &database.tables {
+                if let Some(id) = known_table_id(&current, &database.name, table) {
+                    table_ids.insert(table.metadata_location.clone(), id);
+                    continue;
+                }
+                let metadata = match Location::from_str(&table.metadata_location) {
+                    Ok(location) => read_metadata_file(&file_io, &location)
+                        .await
+                        .map_err(|e| e.to_string()),
+                    Err(e) => Err(e.to_string()),
+                };
+                match metadata {
+                    Ok(metadata) => {
+                        table_ids.insert(table.metadata_location.clone(), metadata.uuid());
+                    }
+                    // Other tables are still mirrored, the failed ones are retried with the next sync.
+                    Err(e) => tracing::warn!(
+                        "Failed to read metadata of table {}.{} of federated warehouse {warehouse_id}: {e}",
+                        database.name,
+                        table.name,
+                    ),
+                }
+            }
+        }
+
+        let target = target_state(source, &current, databases, &table_ids);
+        let plan = apply_catalog_state::<C, A, S>(
+            warehouse_id,
+            &target,
+            catalog_state,
+            authorizer,
+            secret_store,
+            owner,
+            MirrorOptions::MIRROR,
+        )
+        .await?;
+        if !plan.is_empty() {
+            tracing::info!(
+                created_namespaces = plan.create_namespaces.len(),
+                updated_namespaces = plan.update_namespaces.len(),
+                dropped_namespaces = plan.drop_namespaces.len(),
+                created_tables = plan.create_tabulars.len(),
+                updated_tables = plan.update_tabulars.len(),
+                dropped_tables = plan.drop_tabulars.len(),
+                "Synced federated warehouse {warehouse_id} from {source}"
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::service::task_queue::catalog_snapshot_export_queue::CatalogSnapshotWarehouse;
+
+    fn table(name: &str, metadata_location: &str) -> FederatedTable {
+        FederatedTable {
+            name: name.to_string(),
+            metadata_location: metadata_location.to_string(),
+        }
+    }
+
+    fn empty_state() -> CatalogSnapshotState {
+        CatalogSnapshotState {
+            warehouse: CatalogSnapshotWarehouse {
+                name: "federated".to_string(),
+                storage_profile: serde_json::json!({}),
+                tabular_expiration_seconds: None,
+                protected: false,
+                disabled_endpoint_groups: vec![],
+            },
+            task_queue_configs: BTreeMap::new(),
+            namespaces: vec![],
+            tabulars: vec![],
+            access: None,
+        }
+    }
+
+    #[test]
+    fn test_iceberg_metadata_location() {
+        let parameters = HashMap::from([
+            (TABLE_TYPE_PARAMETER.to_string(), "iceberg".to_string()),
+            (
+                METADATA_LOCATION_PARAMETER.to_string(),
+                "s3://bucket/t/metadata/00001.metadata.json".to_string(),
+            ),
+        ]);
+        assert_eq!(
+            iceberg_metadata_location(&parameters),
+            Some("s3://bucket/t/metadata/00001.metadata.json")
+        );
+        let parameters = HashMap::from([("classification".to_string(), "csv".to_string())]);
+        assert_eq!(iceberg_metadata_location(&parameters), None);
+    }
+
+    #[test]
+    fn test_target_state() {
+        let database = FederatedDatabase {
+            name: "sales".to_string(),
+            properties: BTreeMap::from([(
+                LOCATION_PROPERTY.to_string(),
+                "s3://bucket/sales".to_string(),
+            )]),
+            tables: vec![
+                table(
+                    "orders",
+                    "s3://bucket/sales/orders/metadata/00001.metadata.json",
+                ),
+                table(
+                    "orders_copy",
+                    "s3://bucket/sales/orders/metadata/00001.metadata.json",
+                ),
+                table(
+                    "unreadable",
+                    "s3://bucket/sales/unreadable/metadata/00001.metadata.json",
+                ),
+            ],
+        };
+        let table_id = Uuid::now_v7();
+        let table_ids = HashMap::from([(
+            "s3://bucket/sales/orders/metadata/00001.metadata.json".to_string(),
+            table_id,
+        )]);
+
+        let state = target_state("Glue", &empty_state(), &[database.clone()], &table_ids);
+        assert_eq!(state.namespaces.len(), 1);
+        assert_eq!(state.namespaces[0].name, vec!["sales".to_string()]);
+        assert_eq!(state.namespaces[0].properties, database.properties);
+        assert_eq!(state.tabulars.len(), 1);
+        assert_eq!(state.tabulars[0].tabular_id, table_id);
+        assert_eq!(state.tabulars[0].name, "orders");
+
+        // Namespaces keep their id
+        let next = target_state(
+            "Glue",
+            &state,
+            &[FederatedDatabase {
+                tables: vec![],
+                ..database.clone()
+            }],
+            &HashMap::new(),
+        );
+        assert_eq!(
+            next.namespaces[0].namespace_id,
+            state.namespaces[0].namespace_id
+        );
+        assert!(next.tabulars.is_empty());
+        assert_eq!(
+            known_table_id(&state, "sales", &database.tables[0]),
+            Some(table_id)
+        );
+        assert_eq!(known_table_id(&state, "sales", &database.tables[2]), None);
+    }
+}
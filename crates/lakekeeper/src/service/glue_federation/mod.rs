@@ -0,0 +1,185 @@
+//! Federation of AWS Glue Data Catalogs.
+//!
+//! A federated warehouse is a read-only view of a Glue Data Catalog, which allows to
+//! migrate clients from Glue to Lakekeeper gradually. Databases of Glue are periodically
+//! mirrored as namespaces, and Iceberg tables registered in Glue as tables pointing to the
+//! metadata file Glue references. Tables and metadata files are cached in the catalog, so
+//! that listing and loading does not hit Glue.
+//!
+//! All endpoints that change a federated warehouse are rejected - writes go to Glue, or
+//! to warehouses managed by Lakekeeper. Mirrored entities are created below the federated
+//! warehouse and owned by `glue_federation_owner`.
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+    str::FromStr,
+};
+
+use serde::Deserialize;
+
+#[cfg(feature = "glue-federation")]
+mod sync;
+#[cfg(feature = "glue-federation")]
+pub use sync::glue_federation_worker;
+
+use crate::{service::federation::FederatedDatabase, WarehouseId};
+
+/// A warehouse of Lakekeeper and the Glue Data Catalog it mirrors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlueFederatedWarehouse {
+    pub warehouse_id: WarehouseId,
+    /// AWS region of the Glue Data Catalog, i.e. `eu-central-1`
+    pub region: String,
+    /// Id of the Glue Data Catalog, which is the AWS account id.
+    /// Defaults to the account of the credentials.
+    pub catalog_id: Option<String>,
+}
+
+impl FromStr for GlueFederatedWarehouse {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (warehouse_id, catalog) = s.split_once('=').ok_or_else(|| {
+            format!(
+                "Invalid federated warehouse '{s}'. Expected '<warehouse-id>=<region>[:<catalog-id>]'."
+            )
+        })?;
+        let warehouse_id = WarehouseId::from_str(warehouse_id.trim()).map_err(|_| {
+            format!("Invalid warehouse id '{warehouse_id}' in federated warehouse '{s}'.")
+        })?;
+        let (region, catalog_id) = match catalog.trim().split_once(':') {
+            Some((region, catalog_id)) => (region, Some(catalog_id.to_string())),
+            None => (catalog.trim(), None),
+        };
+        if region.is_empty() || catalog_id.as_deref().is_some_and(str::is_empty) {
+            return Err(format!(
+                "Invalid federated warehouse '{s}'. Expected '<warehouse-id>=<region>[:<catalog-id>]'."
+            ));
+        }
+        Ok(Self {
+            warehouse_id,
+            region: region.to_string(),
+            catalog_id,
+        })
+    }
+}
+
+impl Display for GlueFederatedWarehouse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={}", self.warehouse_id, self.region)?;
+        if let Some(catalog_id) = &self.catalog_id {
+            write!(f, ":{catalog_id}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Database of the Glue Data Catalog, as returned by `GetDatabases`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub(crate) struct GlueDatabase {
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) location_uri: Option<String>,
+    #[serde(default)]
+    pub(crate) parameters: BTreeMap<String, String>,
+}
+
+/// Table of the Glue Data Catalog, as returned by `GetTables`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub(crate) struct GlueTable {
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) parameters: HashMap<String, String>,
+}
+
+impl GlueDatabase {
+    /// The database and its Iceberg tables. The `LocationUri` of the database becomes
+    /// the `location` property of the namespace.
+    pub(crate) fn into_federated(self, tables: Vec<GlueTable>) -> FederatedDatabase {
+        FederatedDatabase::new(
+            self.name,
+            self.parameters,
+            self.location_uri,
+            tables.into_iter().map(|t| (t.name, t.parameters)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::service::federation::{FederatedTable, LOCATION_PROPERTY};
+
+    #[test]
+    fn test_glue_federated_warehouse_from_str() {
+        let id = Uuid::now_v7();
+        let warehouse = GlueFederatedWarehouse::from_str(&format!("{id} = eu-central-1")).unwrap();
+        assert_eq!(warehouse.warehouse_id, WarehouseId::from(id));
+        assert_eq!(warehouse.region, "eu-central-1");
+        assert_eq!(warehouse.catalog_id, None);
+        assert_eq!(warehouse.to_string(), format!("{id}=eu-central-1"));
+
+        let warehouse =
+            GlueFederatedWarehouse::from_str(&format!("{id}=us-east-1:123456789012")).unwrap();
+        assert_eq!(warehouse.catalog_id.as_deref(), Some("123456789012"));
+        assert_eq!(
+            warehouse.to_string(),
+            format!("{id}=us-east-1:123456789012")
+        );
+
+        assert!(GlueFederatedWarehouse::from_str("eu-central-1").is_err());
+        assert!(GlueFederatedWarehouse::from_str(&format!("{id}=")).is_err());
+        assert!(GlueFederatedWarehouse::from_str(&format!("{id}=us-east-1:")).is_err());
+    }
+
+    #[test]
+    fn test_into_federated() {
+        let database = GlueDatabase {
+            name: "sales".to_string(),
+            location_uri: Some("s3://bucket/sales".to_string()),
+            parameters: BTreeMap::from([("owner".to_string(), "data-team".to_string())]),
+        };
+        let tables = vec![
+            GlueTable {
+                name: "orders".to_string(),
+                parameters: HashMap::from([
+                    ("table_type".to_string(), "ICEBERG".to_string()),
+                    (
+                        "metadata_location".to_string(),
+                        "s3://bucket/sales/orders/metadata/00001.metadata.json".to_string(),
+                    ),
+                ]),
+            },
+            GlueTable {
+                name: "raw".to_string(),
+                parameters: HashMap::from([("classification".to_string(), "csv".to_string())]),
+            },
+        ];
+
+        let database = database.into_federated(tables);
+        assert_eq!(database.name, "sales");
+        assert_eq!(
+            database
+                .properties
+                .get(LOCATION_PROPERTY)
+                .map(String::as_str),
+            Some("s3://bucket/sales")
+        );
+        assert_eq!(
+            database.properties.get("owner").map(String::as_str),
+            Some("data-team")
+        );
+        assert_eq!(
+            database.tables,
+            vec![FederatedTable {
+                name: "orders".to_string(),
+                metadata_location: "s3://bucket/sales/orders/metadata/00001.metadata.json"
+                    .to_string(),
+            }]
+        );
+    }
+}
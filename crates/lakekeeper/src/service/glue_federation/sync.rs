@@ -0,0 +1,323 @@
+use std::time::{Duration, SystemTime};
+
+use aws_config::BehaviorVersion;
+use aws_credential_types::{provider::ProvideCredentials as _, Credentials};
+use aws_sigv4::{
+    http_request::{
+        sign as aws_sign, SignableBody, SignableRequest, SigningParams, SigningSettings,
+    },
+    sign::v4,
+};
+use axum_prometheus::metrics;
+use iceberg_ext::catalog::rest::ErrorModel;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use super::{GlueDatabase, GlueFederatedWarehouse, GlueTable};
+use crate::{
+    request_metadata::RequestMetadata,
+    service::{
+        authz::Authorizer, federation::sync_federated_warehouse, mirror::mirror_owner, Catalog,
+        Result, SecretStore,
+    },
+    CONFIG,
+};
+
+/// Unix timestamp of the last successful sync, labelled by the federated `warehouse`.
+const GLUE_LAST_SYNC: &str = "lakekeeper_glue_federation_last_sync_timestamp_seconds";
+/// Failed syncs, labelled by the federated `warehouse`.
+const GLUE_SYNC_ERRORS: &str = "lakekeeper_glue_federation_sync_errors_total";
+
+const GLUE_REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+const GLUE_PAGE_SIZE: i32 = 100;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct GetDatabasesRequest<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    catalog_id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_token: Option<String>,
+    max_results: i32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct GetDatabasesResponse {
+    #[serde(default)]
+    database_list: Vec<GlueDatabase>,
+    next_token: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct GetTablesRequest<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    catalog_id: Option<&'a str>,
+    database_name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_token: Option<String>,
+    max_results: i32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct GetTablesResponse {
+    #[serde(default)]
+    table_list: Vec<GlueTable>,
+    next_token: Option<String>,
+}
+
+/// Infinitely running worker that keeps federated warehouses in sync with Glue.
+/// Returns immediately if no federated warehouses are configured.
+pub async fn glue_federation_worker<C: Catalog, A: Authorizer, S: SecretStore>(
+    catalog_state: C::State,
+    authorizer: A,
+    secret_store: S,
+) {
+    if CONFIG.glue_federated_warehouses.is_empty() {
+        return;
+    }
+    let Some(owner) = mirror_owner(CONFIG.glue_federation_owner.as_ref()) else {
+        tracing::error!(
+            "Federated warehouses are not synced: `LAKEKEEPER__GLUE_FEDERATION_OWNER` must be set unless the `allowall` authorizer is used."
+        );
+        return;
+    };
+    metrics::describe_gauge!(
+        GLUE_LAST_SYNC,
+        metrics::Unit::Seconds,
+        "Time of the last successful sync of the warehouse from Glue"
+    );
+    metrics::describe_counter!(GLUE_SYNC_ERRORS, "Failed syncs of the warehouse from Glue");
+
+    let client = reqwest::Client::builder()
+        .timeout(GLUE_REQUEST_TIMEOUT)
+        .build()
+        .expect("Failed to create HTTP client");
+    let interval = Duration::from_secs(CONFIG.glue_federation_sync_interval_seconds.max(1));
+    loop {
+        for warehouse in &CONFIG.glue_federated_warehouses {
+            let labels = [("warehouse", warehouse.warehouse_id.to_string())];
+            match sync_warehouse::<C, A, S>(
+                &client,
+                warehouse,
+                catalog_state.clone(),
+                &authorizer,
+                &secret_store,
+                &owner,
+            )
+            .await
+            {
+                Ok(()) => {
+                    #[allow(clippy::cast_precision_loss)]
+                    metrics::gauge!(GLUE_LAST_SYNC, &labels)
+                        .set(chrono::Utc::now().timestamp() as f64);
+                }
+                Err(e) => {
+                    metrics::counter!(GLUE_SYNC_ERRORS, &labels).increment(1);
+                    tracing::error!(
+                        ?e,
+                        "Failed to sync federated warehouse {} from Glue in {}: {}",
+                        warehouse.warehouse_id,
+                        warehouse.region,
+                        e.error
+                    );
+                }
+            }
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
+
+/// Mirror the current state of Glue into the federated warehouse.
+async fn sync_warehouse<C: Catalog, A: Authorizer, S: SecretStore>(
+    client: &reqwest::Client,
+    warehouse: &GlueFederatedWarehouse,
+    catalog_state: C::State,
+    authorizer: &A,
+    secret_store: &S,
+    owner: &RequestMetadata,
+) -> Result<()> {
+    let glue = GlueClient::new(client.clone(), warehouse).await?;
+    let mut databases = Vec::new();
+    for database in glue.get_databases().await? {
+        let tables = glue.get_tables(&database.name).await?;
+        databases.push(database.into_federated(tables));
+    }
+
+    sync_federated_warehouse::<C, A, S>(
+        warehouse.warehouse_id,
+        &format!("Glue in {}", warehouse.region),
+        &databases,
+        catalog_state,
+        authorizer,
+        secret_store,
+        owner,
+    )
+    .await
+}
+
+/// Minimal client of the Glue JSON API, signing requests with the credentials of the
+/// default AWS credential chain.
+struct GlueClient<'a> {
+    client: reqwest::Client,
+    warehouse: &'a GlueFederatedWarehouse,
+    credentials: Credentials,
+}
+
+impl<'a> GlueClient<'a> {
+    async fn new(client: reqwest::Client, warehouse: &'a GlueFederatedWarehouse) -> Result<Self> {
+        let sdk_config = aws_config::from_env()
+            .region(aws_config::Region::new(warehouse.region.clone()))
+            .behavior_version(BehaviorVersion::latest())
+            .load()
+            .await;
+        let provider = sdk_config.credentials_provider().ok_or_else(|| {
+            ErrorModel::internal(
+                "No AWS credentials available to access Glue",
+                "GlueCredentialsError",
+                None,
+            )
+        })?;
+        let credentials = provider.provide_credentials().await.map_err(|e| {
+            ErrorModel::internal(
+                "Failed to obtain AWS credentials to access Glue",
+                "GlueCredentialsError",
+                Some(Box::new(e)),
+            )
+        })?;
+        Ok(Self {
+            client,
+            warehouse,
+            credentials,
+        })
+    }
+
+    async fn get_databases(&self) -> Result<Vec<GlueDatabase>> {
+        let mut databases = Vec::new();
+        let mut next_token = None;
+        loop {
+            let response: GetDatabasesResponse = self
+                .call(
+                    "GetDatabases",
+                    &GetDatabasesRequest {
+                        catalog_id: self.warehouse.catalog_id.as_deref(),
+                        next_token,
+                        max_results: GLUE_PAGE_SIZE,
+                    },
+                )
+                .await?;
+            databases.extend(response.database_list);
+            next_token = response.next_token.filter(|t| !t.is_empty());
+            if next_token.is_none() {
+                return Ok(databases);
+            }
+        }
+    }
+
+    async fn get_tables(&self, database_name: &str) -> Result<Vec<GlueTable>> {
+        let mut tables = Vec::new();
+        let mut next_token = None;
+        loop {
+            let response: GetTablesResponse = self
+                .call(
+                    "GetTables",
+                    &GetTablesRequest {
+                        catalog_id: self.warehouse.catalog_id.as_deref(),
+                        database_name,
+                        next_token,
+                        max_results: GLUE_PAGE_SIZE,
+                    },
+                )
+                .await?;
+            tables.extend(response.table_list);
+            next_token = response.next_token.filter(|t| !t.is_empty());
+            if next_token.is_none() {
+                return Ok(tables);
+            }
+        }
+    }
+
+    async fn call<T: Serialize, R: DeserializeOwned>(
+        &self,
+        operation: &str,
+        body: &T,
+    ) -> Result<R> {
+        let url = format!("https://glue.{}.amazonaws.com/", self.warehouse.region);
+        let body = serde_json::to_vec(body).map_err(|e| {
+            ErrorModel::internal(
+                format!("Failed to serialize Glue {operation} request"),
+                "GlueRequestError",
+                Some(Box::new(e)),
+            )
+        })?;
+        let target = format!("AWSGlue.{operation}");
+        let headers = [
+            ("content-type", "application/x-amz-json-1.1"),
+            ("x-amz-target", target.as_str()),
+        ];
+
+        let identity = self.credentials.clone().into();
+        let signing_params: SigningParams<'_> = v4::SigningParams::builder()
+            .identity(&identity)
+            .region(&self.warehouse.region)
+            .name("glue")
+            .time(SystemTime::now())
+            .settings(SigningSettings::default())
+            .build()
+            .map_err(|e| {
+                ErrorModel::internal(
+                    "Failed to create signing params for Glue",
+                    "GlueRequestError",
+                    Some(Box::new(e)),
+                )
+            })?
+            .into();
+        let signable_request = SignableRequest::new(
+            "POST",
+            &url,
+            headers.iter().copied(),
+            SignableBody::Bytes(&body),
+        )
+        .map_err(|e| {
+            ErrorModel::internal(
+                "Glue request is not signable",
+                "GlueRequestError",
+                Some(Box::new(e)),
+            )
+        })?;
+        let (signing_instructions, _signature) = aws_sign(signable_request, &signing_params)
+            .map_err(|e| {
+                ErrorModel::internal(
+                    "Failed to sign Glue request",
+                    "GlueRequestError",
+                    Some(Box::new(e)),
+                )
+            })?
+            .into_parts();
+
+        let mut request = self.client.post(&url).body(body);
+        for (key, value) in headers.into_iter().chain(signing_instructions.headers()) {
+            request = request.header(key, value);
+        }
+        let response = request
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|e| {
+                ErrorModel::internal(
+                    format!("Glue {operation} request failed"),
+                    "GlueUnavailable",
+                    Some(Box::new(e)),
+                )
+            })?;
+        response.json().await.map_err(|e| {
+            ErrorModel::internal(
+                format!("Failed to parse Glue {operation} response"),
+                "GlueResponseInvalid",
+                Some(Box::new(e)),
+            )
+            .into()
+        })
+    }
+}
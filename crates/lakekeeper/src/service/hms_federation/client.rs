@@ -0,0 +1,380 @@
+//! Minimal blocking client of the thrift API of the Hive Metastore.
+//!
+//! Only the calls required to list databases and tables are implemented. Fields of the
+//! returned structs that are not needed are skipped, so the client works with all
+//! versions of the Hive Metastore that use the binary protocol without SASL.
+use std::{
+    collections::HashMap,
+    net::{TcpStream, ToSocketAddrs},
+    time::Duration,
+};
+
+use thrift::{
+    protocol::{
+        TBinaryInputProtocol, TBinaryOutputProtocol, TFieldIdentifier, TInputProtocol,
+        TListIdentifier, TMessageIdentifier, TMessageType, TOutputProtocol, TStructIdentifier,
+        TType,
+    },
+    transport::{TBufferedReadTransport, TBufferedWriteTransport, TIoChannel, TTcpChannel},
+};
+
+use super::{HmsDatabase, HmsFederatedWarehouse, HmsTable};
+
+const HMS_TIMEOUT: Duration = Duration::from_secs(60);
+/// Number of tables requested with a single `get_table_objects_by_name` call.
+const HMS_TABLE_BATCH_SIZE: usize = 100;
+
+#[derive(Debug, thiserror::Error)]
+pub(super) enum HmsError {
+    #[error("Failed to connect to Hive Metastore at {address}: {source}")]
+    Connect {
+        address: String,
+        source: std::io::Error,
+    },
+    #[error("Hive Metastore thrift error: {0}")]
+    Thrift(#[from] thrift::Error),
+    #[error("Hive Metastore call {method} failed: {message}")]
+    Exception {
+        method: &'static str,
+        message: String,
+    },
+    #[error("Hive Metastore returned an invalid response to {method}: {message}")]
+    InvalidResponse {
+        method: &'static str,
+        message: String,
+    },
+}
+
+/// Databases of the Hive Metastore and their tables.
+/// Blocks until all tables are read.
+pub(super) fn read_databases(
+    warehouse: &HmsFederatedWarehouse,
+) -> Result<Vec<(HmsDatabase, Vec<HmsTable>)>, HmsError> {
+    let mut client = HmsClient::connect(warehouse)?;
+    let mut databases = Vec::new();
+    for database_name in client.get_all_databases()? {
+        let database = client.get_database(&database_name)?;
+        let table_names = client.get_all_tables(&database_name)?;
+        let mut tables = Vec::with_capacity(table_names.len());
+        for chunk in table_names.chunks(HMS_TABLE_BATCH_SIZE) {
+            tables.extend(client.get_table_objects_by_name(&database_name, chunk)?);
+        }
+        databases.push((database, tables));
+    }
+    Ok(databases)
+}
+
+struct HmsClient {
+    i_prot: Box<dyn TInputProtocol + Send>,
+    o_prot: Box<dyn TOutputProtocol + Send>,
+    sequence_number: i32,
+}
+
+impl HmsClient {
+    fn connect(warehouse: &HmsFederatedWarehouse) -> Result<Self, HmsError> {
+        let address = format!("{}:{}", warehouse.host, warehouse.port);
+        let connect_error = |source| HmsError::Connect {
+            address: address.clone(),
+            source,
+        };
+        let mut last_error = None;
+        let mut stream = None;
+        for socket_address in address.to_socket_addrs().map_err(connect_error)? {
+            match TcpStream::connect_timeout(&socket_address, HMS_TIMEOUT) {
+                Ok(s) => {
+                    stream = Some(s);
+                    break;
+                }
+                Err(e) => last_error = Some(e),
+            }
+        }
+        let stream = stream.ok_or_else(|| {
+            connect_error(last_error.unwrap_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    "Host did not resolve to any address",
+                )
+            }))
+        })?;
+        stream
+            .set_read_timeout(Some(HMS_TIMEOUT))
+            .and_then(|()| stream.set_write_timeout(Some(HMS_TIMEOUT)))
+            .map_err(connect_error)?;
+
+        let (i_chan, o_chan) = TTcpChannel::with_stream(stream).split()?;
+        Ok(Self {
+            i_prot: Box::new(TBinaryInputProtocol::new(
+                TBufferedReadTransport::new(i_chan),
+                true,
+            )),
+            o_prot: Box::new(TBinaryOutputProtocol::new(
+                TBufferedWriteTransport::new(o_chan),
+                true,
+            )),
+            sequence_number: 0,
+        })
+    }
+
+    fn get_all_databases(&mut self) -> Result<Vec<String>, HmsError> {
+        self.call("get_all_databases", |_| Ok(()), read_string_list)
+    }
+
+    fn get_database(&mut self, name: &str) -> Result<HmsDatabase, HmsError> {
+        self.call(
+            "get_database",
+            |o| write_string_field(o, "name", 1, name),
+            read_database,
+        )
+    }
+
+    fn get_all_tables(&mut self, database_name: &str) -> Result<Vec<String>, HmsError> {
+        self.call(
+            "get_all_tables",
+            |o| write_string_field(o, "db_name", 1, database_name),
+            read_string_list,
+        )
+    }
+
+    fn get_table_objects_by_name(
+        &mut self,
+        database_name: &str,
+        table_names: &[String],
+    ) -> Result<Vec<HmsTable>, HmsError> {
+        self.call(
+            "get_table_objects_by_name",
+            |o| {
+                write_string_field(o, "dbname", 1, database_name)?;
+                o.write_field_begin(&TFieldIdentifier::new("tbl_names", TType::List, 2))?;
+                o.write_list_begin(&TListIdentifier::new(
+                    TType::String,
+                    i32::try_from(table_names.len()).unwrap_or(i32::MAX),
+                ))?;
+                for name in table_names {
+                    o.write_string(name)?;
+                }
+                o.write_list_end()?;
+                o.write_field_end()
+            },
+            |i| {
+                let list = i.read_list_begin()?;
+                let tables = (0..list.size)
+                    .map(|_| read_table(i))
+                    .collect::<thrift::Result<Vec<_>>>()?;
+                i.read_list_end()?;
+                Ok(tables)
+            },
+        )
+    }
+
+    /// Call `method` with the fields written by `write_args` and read the result with
+    /// `read_success`. Exceptions declared by the method are returned as
+    /// [`HmsError::Exception`].
+    fn call<T>(
+        &mut self,
+        method: &'static str,
+        write_args: impl FnOnce(&mut dyn TOutputProtocol) -> thrift::Result<()>,
+        mut read_success: impl FnMut(&mut dyn TInputProtocol) -> thrift::Result<T>,
+    ) -> Result<T, HmsError> {
+        self.sequence_number = self.sequence_number.wrapping_add(1);
+        let o: &mut dyn TOutputProtocol = &mut *self.o_prot;
+        o.write_message_begin(&TMessageIdentifier::new(
+            method,
+            TMessageType::Call,
+            self.sequence_number,
+        ))?;
+        o.write_struct_begin(&TStructIdentifier::new(format!("{method}_args")))?;
+        write_args(&mut *o)?;
+        o.write_field_stop()?;
+        o.write_struct_end()?;
+        o.write_message_end()?;
+        o.flush()?;
+
+        let i: &mut dyn TInputProtocol = &mut *self.i_prot;
+        let message = i.read_message_begin()?;
+        if message.message_type == TMessageType::Exception {
+            let e = thrift::Error::read_application_error_from_in_protocol(i)?;
+            i.read_message_end()?;
+            return Err(thrift::Error::Application(e).into());
+        }
+        if message.name != method || message.sequence_number != self.sequence_number {
+            return Err(HmsError::InvalidResponse {
+                method,
+                message: format!(
+                    "Expected reply {} to {method}, got {} to {}",
+                    self.sequence_number, message.sequence_number, message.name
+                ),
+            });
+        }
+
+        let mut success = None;
+        let mut exception = None;
+        i.read_struct_begin()?;
+        loop {
+            let field = i.read_field_begin()?;
+            match (field.id, field.field_type) {
+                (_, TType::Stop) => break,
+                (Some(0), _) => success = Some(read_success(&mut *i)?),
+                // All declared exceptions of the metastore have a message as first field.
+                (Some(_), TType::Struct) => exception = Some(read_exception_message(i)?),
+                (_, field_type) => i.skip(field_type)?,
+            }
+            i.read_field_end()?;
+        }
+        i.read_struct_end()?;
+        i.read_message_end()?;
+
+        match (success, exception) {
+            (_, Some(message)) => Err(HmsError::Exception { method, message }),
+            (Some(success), None) => Ok(success),
+            (None, None) => Err(HmsError::InvalidResponse {
+                method,
+                message: "Reply contains neither a result nor an exception".to_string(),
+            }),
+        }
+    }
+}
+
+fn write_string_field(
+    o: &mut dyn TOutputProtocol,
+    name: &str,
+    id: i16,
+    value: &str,
+) -> thrift::Result<()> {
+    o.write_field_begin(&TFieldIdentifier::new(name, TType::String, id))?;
+    o.write_string(value)?;
+    o.write_field_end()
+}
+
+/// Reads the fields of a struct, calling `read_field` with the id and type of each field.
+/// `read_field` must consume the value of the field.
+fn read_struct(
+    i: &mut dyn TInputProtocol,
+    mut read_field: impl FnMut(&mut dyn TInputProtocol, Option<i16>, TType) -> thrift::Result<()>,
+) -> thrift::Result<()> {
+    i.read_struct_begin()?;
+    loop {
+        let field = i.read_field_begin()?;
+        if field.field_type == TType::Stop {
+            break;
+        }
+        read_field(&mut *i, field.id, field.field_type)?;
+        i.read_field_end()?;
+    }
+    i.read_struct_end()
+}
+
+fn read_string_list(i: &mut dyn TInputProtocol) -> thrift::Result<Vec<String>> {
+    let list = i.read_list_begin()?;
+    let values = (0..list.size)
+        .map(|_| i.read_string())
+        .collect::<thrift::Result<Vec<_>>>()?;
+    i.read_list_end()?;
+    Ok(values)
+}
+
+fn read_string_map(i: &mut dyn TInputProtocol) -> thrift::Result<HashMap<String, String>> {
+    let map = i.read_map_begin()?;
+    let mut entries = HashMap::new();
+    for _ in 0..map.size {
+        let key = i.read_string()?;
+        entries.insert(key, i.read_string()?);
+    }
+    i.read_map_end()?;
+    Ok(entries)
+}
+
+fn read_exception_message(i: &mut dyn TInputProtocol) -> thrift::Result<String> {
+    let mut message = String::new();
+    read_struct(i, |i, id, field_type| match (id, field_type) {
+        (Some(1), TType::String) => {
+            message = i.read_string()?;
+            Ok(())
+        }
+        _ => i.skip(field_type),
+    })?;
+    Ok(message)
+}
+
+/// `Database` struct: `1: name`, `3: locationUri`, `4: parameters`.
+fn read_database(i: &mut dyn TInputProtocol) -> thrift::Result<HmsDatabase> {
+    let mut database = HmsDatabase::default();
+    read_struct(i, |i, id, field_type| match (id, field_type) {
+        (Some(1), TType::String) => {
+            database.name = i.read_string()?;
+            Ok(())
+        }
+        (Some(3), TType::String) => {
+            database.location_uri = Some(i.read_string()?).filter(|l| !l.is_empty());
+            Ok(())
+        }
+        (Some(4), TType::Map) => {
+            database.parameters = read_string_map(i)?.into_iter().collect();
+            Ok(())
+        }
+        _ => i.skip(field_type),
+    })?;
+    Ok(database)
+}
+
+/// `Table` struct: `1: tableName`, `9: parameters`.
+fn read_table(i: &mut dyn TInputProtocol) -> thrift::Result<HmsTable> {
+    let mut table = HmsTable::default();
+    read_struct(i, |i, id, field_type| match (id, field_type) {
+        (Some(1), TType::String) => {
+            table.name = i.read_string()?;
+            Ok(())
+        }
+        (Some(9), TType::Map) => {
+            table.parameters = read_string_map(i)?;
+            Ok(())
+        }
+        _ => i.skip(field_type),
+    })?;
+    Ok(table)
+}
+
+#[cfg(test)]
+mod test {
+    use thrift::protocol::TMapIdentifier;
+
+    use super::*;
+
+    #[test]
+    fn test_read_table_skips_unknown_fields() {
+        let mut buf = Vec::new();
+        let mut o = TBinaryOutputProtocol::new(&mut buf, true);
+        o.write_struct_begin(&TStructIdentifier::new("Table"))
+            .unwrap();
+        write_string_field(&mut o, "tableName", 1, "events").unwrap();
+        write_string_field(&mut o, "dbName", 2, "legacy").unwrap();
+        // Nested structs such as the storage descriptor are skipped
+        o.write_field_begin(&TFieldIdentifier::new("sd", TType::Struct, 7))
+            .unwrap();
+        o.write_struct_begin(&TStructIdentifier::new("StorageDescriptor"))
+            .unwrap();
+        write_string_field(&mut o, "location", 2, "s3://bucket/legacy/events").unwrap();
+        o.write_field_stop().unwrap();
+        o.write_struct_end().unwrap();
+        o.write_field_end().unwrap();
+        o.write_field_begin(&TFieldIdentifier::new("parameters", TType::Map, 9))
+            .unwrap();
+        o.write_map_begin(&TMapIdentifier::new(TType::String, TType::String, 1))
+            .unwrap();
+        o.write_string("table_type").unwrap();
+        o.write_string("ICEBERG").unwrap();
+        o.write_map_end().unwrap();
+        o.write_field_end().unwrap();
+        o.write_field_stop().unwrap();
+        o.write_struct_end().unwrap();
+        o.flush().unwrap();
+        drop(o);
+
+        let mut i = TBinaryInputProtocol::new(buf.as_slice(), true);
+        let table = read_table(&mut i).unwrap();
+        assert_eq!(table.name, "events");
+        assert_eq!(
+            table.parameters,
+            HashMap::from([("table_type".to_string(), "ICEBERG".to_string())])
+        );
+    }
+}
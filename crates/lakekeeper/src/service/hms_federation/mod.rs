@@ -0,0 +1,180 @@
+//! Federation of Hive Metastores.
+//!
+//! A federated warehouse is a read-only view of the Iceberg tables registered in a Hive
+//! Metastore (HMS), which allows engines that only speak the Iceberg REST protocol to read
+//! tables of legacy HMS deployments during a migration. Databases of the HMS are
+//! periodically mirrored as namespaces, and Iceberg tables as tables pointing to the
+//! metadata file the HMS references. Other tables of the HMS are ignored.
+//!
+//! The HMS is accessed via its thrift API without authentication, SASL and Kerberos
+//! secured metastores are not supported. All endpoints that change a federated warehouse
+//! are rejected. Mirrored entities are created below the federated warehouse and owned
+//! by `hms_federation_owner`.
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+    str::FromStr,
+};
+
+#[cfg(feature = "hms-federation")]
+mod client;
+#[cfg(feature = "hms-federation")]
+mod sync;
+#[cfg(feature = "hms-federation")]
+pub use sync::hms_federation_worker;
+
+use crate::{service::federation::FederatedDatabase, WarehouseId};
+
+const THRIFT_SCHEME: &str = "thrift://";
+const DEFAULT_HMS_PORT: u16 = 9083;
+
+/// A warehouse of Lakekeeper and the Hive Metastore it mirrors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HmsFederatedWarehouse {
+    pub warehouse_id: WarehouseId,
+    /// Host of the thrift API of the Hive Metastore
+    pub host: String,
+    /// Port of the thrift API of the Hive Metastore. Defaults to 9083.
+    pub port: u16,
+}
+
+impl FromStr for HmsFederatedWarehouse {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "Invalid federated warehouse '{s}'. Expected '<warehouse-id>=thrift://<host>[:<port>]'."
+            )
+        };
+        let (warehouse_id, uri) = s.split_once('=').ok_or_else(invalid)?;
+        let warehouse_id = WarehouseId::from_str(warehouse_id.trim()).map_err(|_| {
+            format!("Invalid warehouse id '{warehouse_id}' in federated warehouse '{s}'.")
+        })?;
+        let uri = uri.trim();
+        let address = uri.strip_prefix(THRIFT_SCHEME).unwrap_or(uri);
+        let (host, port) = match address.rsplit_once(':') {
+            Some((host, port)) => (host, port.parse::<u16>().map_err(|_| invalid())?),
+            None => (address, DEFAULT_HMS_PORT),
+        };
+        if host.is_empty() || host.contains(['/', ':']) {
+            return Err(invalid());
+        }
+        Ok(Self {
+            warehouse_id,
+            host: host.to_string(),
+            port,
+        })
+    }
+}
+
+impl Display for HmsFederatedWarehouse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}={THRIFT_SCHEME}{}:{}",
+            self.warehouse_id, self.host, self.port
+        )
+    }
+}
+
+/// Database of the Hive Metastore, as returned by `get_database`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct HmsDatabase {
+    pub(crate) name: String,
+    pub(crate) location_uri: Option<String>,
+    pub(crate) parameters: BTreeMap<String, String>,
+}
+
+/// Table of the Hive Metastore, as returned by `get_table_objects_by_name`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct HmsTable {
+    pub(crate) name: String,
+    pub(crate) parameters: HashMap<String, String>,
+}
+
+impl HmsDatabase {
+    /// The database and its Iceberg tables. The `locationUri` of the database becomes
+    /// the `location` property of the namespace.
+    pub(crate) fn into_federated(self, tables: Vec<HmsTable>) -> FederatedDatabase {
+        FederatedDatabase::new(
+            self.name,
+            self.parameters,
+            self.location_uri,
+            tables.into_iter().map(|t| (t.name, t.parameters)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::service::federation::{FederatedTable, LOCATION_PROPERTY};
+
+    #[test]
+    fn test_hms_federated_warehouse_from_str() {
+        let id = Uuid::now_v7();
+        let warehouse =
+            HmsFederatedWarehouse::from_str(&format!("{id} = thrift://metastore:9083")).unwrap();
+        assert_eq!(warehouse.warehouse_id, WarehouseId::from(id));
+        assert_eq!(warehouse.host, "metastore");
+        assert_eq!(warehouse.port, 9083);
+        assert_eq!(
+            warehouse.to_string(),
+            format!("{id}=thrift://metastore:9083")
+        );
+
+        let warehouse = HmsFederatedWarehouse::from_str(&format!("{id}=hms.internal")).unwrap();
+        assert_eq!(warehouse.host, "hms.internal");
+        assert_eq!(warehouse.port, DEFAULT_HMS_PORT);
+
+        assert!(HmsFederatedWarehouse::from_str("thrift://metastore:9083").is_err());
+        assert!(HmsFederatedWarehouse::from_str(&format!("{id}=")).is_err());
+        assert!(HmsFederatedWarehouse::from_str(&format!("{id}=thrift://metastore:port")).is_err());
+        assert!(HmsFederatedWarehouse::from_str(&format!("{id}=http://metastore:9083")).is_err());
+    }
+
+    #[test]
+    fn test_into_federated() {
+        let database = HmsDatabase {
+            name: "legacy".to_string(),
+            location_uri: Some("hdfs://namenode/warehouse/legacy.db".to_string()),
+            parameters: BTreeMap::new(),
+        };
+        let tables = vec![
+            HmsTable {
+                name: "events".to_string(),
+                parameters: HashMap::from([
+                    ("table_type".to_string(), "ICEBERG".to_string()),
+                    (
+                        "metadata_location".to_string(),
+                        "s3://bucket/legacy/events/metadata/00003.metadata.json".to_string(),
+                    ),
+                ]),
+            },
+            HmsTable {
+                name: "raw_events".to_string(),
+                parameters: HashMap::from([("EXTERNAL".to_string(), "TRUE".to_string())]),
+            },
+        ];
+
+        let database = database.into_federated(tables);
+        assert_eq!(
+            database
+                .properties
+                .get(LOCATION_PROPERTY)
+                .map(String::as_str),
+            Some("hdfs://namenode/warehouse/legacy.db")
+        );
+        assert_eq!(
+            database.tables,
+            vec![FederatedTable {
+                name: "events".to_string(),
+                metadata_location: "s3://bucket/legacy/events/metadata/00003.metadata.json"
+                    .to_string(),
+            }]
+        );
+    }
+}
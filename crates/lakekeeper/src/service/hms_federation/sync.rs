@@ -0,0 +1,122 @@
+use std::time::Duration;
+
+use axum_prometheus::metrics;
+use iceberg_ext::catalog::rest::ErrorModel;
+
+use super::{client::read_databases, HmsFederatedWarehouse};
+use crate::{
+    request_metadata::RequestMetadata,
+    service::{
+        authz::Authorizer, federation::sync_federated_warehouse, mirror::mirror_owner, Catalog,
+        Result, SecretStore,
+    },
+    CONFIG,
+};
+
+/// Unix timestamp of the last successful sync, labelled by the federated `warehouse`.
+const HMS_LAST_SYNC: &str = "lakekeeper_hms_federation_last_sync_timestamp_seconds";
+/// Failed syncs, labelled by the federated `warehouse`.
+const HMS_SYNC_ERRORS: &str = "lakekeeper_hms_federation_sync_errors_total";
+
+/// Infinitely running worker that keeps federated warehouses in sync with Hive Metastores.
+/// Returns immediately if no federated warehouses are configured.
+pub async fn hms_federation_worker<C: Catalog, A: Authorizer, S: SecretStore>(
+    catalog_state: C::State,
+    authorizer: A,
+    secret_store: S,
+) {
+    if CONFIG.hms_federated_warehouses.is_empty() {
+        return;
+    }
+    let Some(owner) = mirror_owner(CONFIG.hms_federation_owner.as_ref()) else {
+        tracing::error!(
+            "Federated warehouses are not synced: `LAKEKEEPER__HMS_FEDERATION_OWNER` must be set unless the `allowall` authorizer is used."
+        );
+        return;
+    };
+    metrics::describe_gauge!(
+        HMS_LAST_SYNC,
+        metrics::Unit::Seconds,
+        "Time of the last successful sync of the warehouse from the Hive Metastore"
+    );
+    metrics::describe_counter!(
+        HMS_SYNC_ERRORS,
+        "Failed syncs of the warehouse from the Hive Metastore"
+    );
+
+    let interval = Duration::from_secs(CONFIG.hms_federation_sync_interval_seconds.max(1));
+    loop {
+        for warehouse in &CONFIG.hms_federated_warehouses {
+            let labels = [("warehouse", warehouse.warehouse_id.to_string())];
+            match sync_warehouse::<C, A, S>(
+                warehouse,
+                catalog_state.clone(),
+                &authorizer,
+                &secret_store,
+                &owner,
+            )
+            .await
+            {
+                Ok(()) => {
+                    #[allow(clippy::cast_precision_loss)]
+                    metrics::gauge!(HMS_LAST_SYNC, &labels)
+                        .set(chrono::Utc::now().timestamp() as f64);
+                }
+                Err(e) => {
+                    metrics::counter!(HMS_SYNC_ERRORS, &labels).increment(1);
+                    tracing::error!(
+                        ?e,
+                        "Failed to sync federated warehouse {} from Hive Metastore {}:{}: {}",
+                        warehouse.warehouse_id,
+                        warehouse.host,
+                        warehouse.port,
+                        e.error
+                    );
+                }
+            }
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
+
+/// Mirror the current state of the Hive Metastore into the federated warehouse.
+async fn sync_warehouse<C: Catalog, A: Authorizer, S: SecretStore>(
+    warehouse: &HmsFederatedWarehouse,
+    catalog_state: C::State,
+    authorizer: &A,
+    secret_store: &S,
+    owner: &RequestMetadata,
+) -> Result<()> {
+    // The thrift client is blocking.
+    let hms = warehouse.clone();
+    let databases = tokio::task::spawn_blocking(move || read_databases(&hms))
+        .await
+        .map_err(|e| {
+            ErrorModel::internal(
+                "Reading the Hive Metastore panicked",
+                "HmsUnavailable",
+                Some(Box::new(e)),
+            )
+        })?
+        .map_err(|e| {
+            ErrorModel::internal(
+                "Failed to read the Hive Metastore",
+                "HmsUnavailable",
+                Some(Box::new(e)),
+            )
+        })?
+        .into_iter()
+        .map(|(database, tables)| database.into_federated(tables))
+        .collect::<Vec<_>>();
+
+    sync_federated_warehouse::<C, A, S>(
+        warehouse.warehouse_id,
+        &format!("Hive Metastore {}:{}", warehouse.host, warehouse.port),
+        &databases,
+        catalog_state,
+        authorizer,
+        secret_store,
+        owner,
+    )
+    .await
+}
@@ -0,0 +1,603 @@
+//! Labels of warehouses, namespaces and tables.
+//!
+//! Labels are key-value pairs that are managed independently of the properties of an entity,
+//! so they can be changed without committing to a table. Projects can define label keys with
+//! a description and a pattern their values must match. Keys that are not defined are not
+//! restricted.
+//!
+//! Label policies make labels usable as conditions of authorization: tables that carry a
+//! label covered by a policy, directly or inherited from their namespaces or warehouse, only
+//! grant access to their data to actors that assume one of the allowed roles. Other actors
+//! are not vended storage credentials, don't get requests signed and can't commit.
+use std::{
+    collections::{BTreeMap, HashSet},
+    str::FromStr,
+    sync::LazyLock,
+};
+
+use lazy_regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::{ErrorModel, IcebergErrorResponse},
+    request_metadata::RequestMetadata,
+    service::{
+        authz::Authorizer, shared_cache::SharedCache, Catalog, NamespaceId, Result, RoleId,
+        TableId, Transaction,
+    },
+    WarehouseId,
+};
+
+/// Labels of an entity by key.
+pub type Labels = BTreeMap<String, String>;
+
+/// Maximum number of labels of a single entity.
+pub const MAX_LABELS_PER_ENTITY: usize = 64;
+/// Maximum number of characters of a label key.
+pub const MAX_LABEL_KEY_LENGTH: usize = 128;
+/// Maximum number of characters of a label value.
+pub const MAX_LABEL_VALUE_LENGTH: usize = 256;
+/// Maximum number of label policies per project.
+pub const MAX_LABEL_POLICIES: usize = 100;
+
+const INVALID_LABEL_ERROR_TYPE: &str = "InvalidLabel";
+const INVALID_LABEL_SETTINGS_ERROR_TYPE: &str = "InvalidLabelSettings";
+
+/// Policies are checked whenever the data of a table is accessed, so they are cached.
+static LABEL_POLICIES_CACHE: LazyLock<SharedCache<Vec<LabelPolicy>>> =
+    LazyLock::new(|| SharedCache::new("label-policies"));
+
+/// Entities labels can be set on.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    strum_macros::Display,
+    utoipa::ToSchema,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum LabelEntityType {
+    Warehouse,
+    Namespace,
+    Table,
+}
+
+/// Entity whose labels are read or written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LabelEntity {
+    Warehouse,
+    Namespace(NamespaceId),
+    Table(TableId),
+}
+
+impl LabelEntity {
+    #[must_use]
+    pub fn entity_type(self) -> LabelEntityType {
+        match self {
+            LabelEntity::Warehouse => LabelEntityType::Warehouse,
+            LabelEntity::Namespace(_) => LabelEntityType::Namespace,
+            LabelEntity::Table(_) => LabelEntityType::Table,
+        }
+    }
+}
+
+/// Definition of a label key and the values it accepts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct LabelDefinition {
+    /// Key of the label, for example `classification`
+    pub key: String,
+    /// Description of the label for users of the catalog
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Regular expression the whole value must match
+    #[serde(default)]
+    pub pattern: Option<String>,
+    /// Entities the label can be set on. All entities if empty.
+    #[serde(default)]
+    pub entity_types: Vec<LabelEntityType>,
+}
+
+/// Restricts access to the data of tables carrying a label to actors that assume one of the
+/// allowed roles. Labels of namespaces and warehouses are inherited by their tables.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct LabelPolicy {
+    /// Key of the label, for example `classification`
+    pub key: String,
+    /// Values of the label the policy applies to, for example `["pii"]`.
+    /// Applies to all values if empty.
+    #[serde(default)]
+    pub values: Vec<String>,
+    /// Roles that keep access to the data of labeled tables.
+    /// If empty, the data of labeled tables can't be accessed through the catalog.
+    #[serde(default)]
+    #[schema(value_type = Vec<uuid::Uuid>)]
+    pub allowed_roles: Vec<RoleId>,
+}
+
+impl LabelPolicy {
+    fn applies_to(&self, labels: &Labels) -> bool {
+        labels
+            .get(&self.key)
+            .is_some_and(|value| self.values.is_empty() || self.values.contains(value))
+    }
+}
+
+/// Label definitions and policies of a project.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct LabelSettings {
+    #[serde(default)]
+    pub definitions: Vec<LabelDefinition>,
+    #[serde(default)]
+    pub policies: Vec<LabelPolicy>,
+}
+
+/// Condition on the labels of an entity. `key` requires the label to be set,
+/// `key=value` requires it to have the value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LabelSelector {
+    pub key: String,
+    pub value: Option<String>,
+}
+
+impl LabelSelector {
+    fn matches(&self, labels: &Labels) -> bool {
+        match &self.value {
+            Some(value) => labels.get(&self.key) == Some(value),
+            None => labels.contains_key(&self.key),
+        }
+    }
+}
+
+impl FromStr for LabelSelector {
+    type Err = IcebergErrorResponse;
+
+    fn from_str(selector: &str) -> Result<Self> {
+        let (key, value) = match selector.split_once('=') {
+            Some((key, value)) => (key, Some(value.to_string())),
+            None => (selector, None),
+        };
+        check_key(key).map_err(|reason| {
+            ErrorModel::bad_request(
+                format!("Invalid label selector '{selector}': {reason}"),
+                "InvalidLabelSelector",
+                None,
+            )
+        })?;
+        Ok(Self {
+            key: key.to_string(),
+            value,
+        })
+    }
+}
+
+/// Parse comma separated label selectors, for example `team=sales,pii`.
+///
+/// # Errors
+/// If a selector has an invalid key.
+pub fn parse_label_selectors(selectors: &str) -> Result<Vec<LabelSelector>> {
+    selectors
+        .split(',')
+        .filter(|s| !s.is_empty())
+        .map(LabelSelector::from_str)
+        .collect()
+}
+
+/// Whether the labels match all selectors.
+#[must_use]
+pub fn matches_label_selectors(selectors: &[LabelSelector], labels: &Labels) -> bool {
+    selectors.iter().all(|selector| selector.matches(labels))
+}
+
+fn check_key(key: &str) -> std::result::Result<(), String> {
+    if key.chars().count() > MAX_LABEL_KEY_LENGTH
+        || !lazy_regex::regex_is_match!(r"^[A-Za-z0-9][A-Za-z0-9._/-]*$", key)
+    {
+        return Err(format!(
+            "Keys must start with a letter or digit, consist of letters, digits, `.`, `_`, `/` and `-`, and have at most {MAX_LABEL_KEY_LENGTH} characters."
+        ));
+    }
+    Ok(())
+}
+
+fn check_value(value: &str) -> std::result::Result<(), String> {
+    if value.chars().count() > MAX_LABEL_VALUE_LENGTH {
+        return Err(format!(
+            "Values must have at most {MAX_LABEL_VALUE_LENGTH} characters."
+        ));
+    }
+    if value.contains(',') || value.chars().any(char::is_control) {
+        return Err("Values must not contain commas or control characters.".to_string());
+    }
+    Ok(())
+}
+
+fn compile_pattern(key: &str, pattern: &str) -> Result<Regex> {
+    Regex::new(&format!("^(?:{pattern})$")).map_err(|e| {
+        invalid_settings(format!(
+            "Pattern of label '{key}' is not a valid regular expression: {e}"
+        ))
+    })
+}
+
+fn invalid_settings(message: String) -> IcebergErrorResponse {
+    ErrorModel::bad_request(message, INVALID_LABEL_SETTINGS_ERROR_TYPE, None).into()
+}
+
+/// Validate the label settings of a project before they are stored.
+///
+/// # Errors
+/// If keys or values are invalid, a key is defined twice, a pattern is not a valid
+/// regular expression or there are too many policies.
+pub fn validate_label_settings(settings: &LabelSettings) -> Result<()> {
+    let mut keys = HashSet::new();
+    for definition in &settings.definitions {
+        let key = &definition.key;
+        check_key(key).map_err(|reason| invalid_settings(format!("Label '{key}': {reason}")))?;
+        if !keys.insert(key.as_str()) {
+            return Err(invalid_settings(format!(
+                "Label '{key}' is defined more than once."
+            )));
+        }
+        if let Some(pattern) = &definition.pattern {
+            compile_pattern(key, pattern)?;
+        }
+    }
+
+    if settings.policies.len() > MAX_LABEL_POLICIES {
+        return Err(invalid_settings(format!(
+            "At most {MAX_LABEL_POLICIES} label policies are allowed per project, got {}.",
+            settings.policies.len()
+        )));
+    }
+    for (idx, policy) in settings.policies.iter().enumerate() {
+        check_key(&policy.key)
+            .and_then(|()| policy.values.iter().try_for_each(|v| check_value(v)))
+            .map_err(|reason| invalid_settings(format!("Label policy {idx}: {reason}")))?;
+    }
+    Ok(())
+}
+
+/// Validate labels written to an entity against the label definitions of its project.
+///
+/// # Errors
+/// `400 Bad Request` listing every invalid label.
+pub fn validate_labels(
+    settings: &LabelSettings,
+    entity_type: LabelEntityType,
+    labels: &Labels,
+) -> Result<()> {
+    if labels.len() > MAX_LABELS_PER_ENTITY {
+        return Err(ErrorModel::bad_request(
+            format!(
+                "At most {MAX_LABELS_PER_ENTITY} labels are allowed per {entity_type}, got {}.",
+                labels.len()
+            ),
+            INVALID_LABEL_ERROR_TYPE,
+            None,
+        )
+        .into());
+    }
+
+    let mut violations = vec![];
+    for (key, value) in labels {
+        if let Err(reason) = check_key(key).and_then(|()| check_value(value)) {
+            violations.push(format!("Label '{key}': {reason}"));
+            continue;
+        }
+        let Some(definition) = settings.definitions.iter().find(|d| &d.key == key) else {
+            continue;
+        };
+        if !definition.entity_types.is_empty() && !definition.entity_types.contains(&entity_type) {
+            violations.push(format!("Label '{key}' can't be set on a {entity_type}."));
+            continue;
+        }
+        if let Some(pattern) = &definition.pattern {
+            if !compile_pattern(key, pattern)?.is_match(value) {
+                violations.push(format!(
+                    "Label '{key}' must match the pattern `{pattern}`, got '{value}'."
+                ));
+            }
+        }
+    }
+
+    match violations.len() {
+        0 => Ok(()),
+        1 => Err(
+            ErrorModel::bad_request(violations.remove(0), INVALID_LABEL_ERROR_TYPE, None).into(),
+        ),
+        n => Err(ErrorModel::bad_request(
+            format!("{n} labels are invalid."),
+            INVALID_LABEL_ERROR_TYPE,
+            None,
+        )
+        .append_details(violations)
+        .into()),
+    }
+}
+
+/// Whether replacing the labels `old` by `new` adds, removes or changes a label that is
+/// covered by a policy. Such changes alter who can access data and require the permission
+/// to manage labels of the project.
+#[must_use]
+pub fn changes_policy_labels(policies: &[LabelPolicy], old: &Labels, new: &Labels) -> bool {
+    policies
+        .iter()
+        .any(|policy| old.get(&policy.key) != new.get(&policy.key))
+}
+
+fn policies_allow(policies: &[&LabelPolicy], roles: &[RoleId]) -> bool {
+    policies
+        .iter()
+        .all(|policy| policy.allowed_roles.iter().any(|role| roles.contains(role)))
+}
+
+/// Label policies of the project of a warehouse.
+async fn warehouse_label_policies<C: Catalog>(
+    warehouse_id: WarehouseId,
+    catalog_state: C::State,
+) -> Result<Vec<LabelPolicy>> {
+    LABEL_POLICIES_CACHE
+        .try_get_with(warehouse_id.to_string(), async {
+            let mut t = C::Transaction::begin_read(catalog_state).await?;
+            let settings = match C::get_warehouse(warehouse_id, t.transaction()).await? {
+                Some(warehouse) => {
+                    C::get_label_settings(&warehouse.project_id, t.transaction()).await?
+                }
+                None => None,
+            };
+            t.commit().await?;
+            Ok(settings.map(|s| s.policies).unwrap_or_default())
+        })
+        .await
+}
+
+/// Whether label policies allow the actor of the request to access the data of a table.
+pub(crate) async fn is_table_data_access_allowed<C: Catalog, A: Authorizer>(
+    authorizer: &A,
+    request_metadata: &RequestMetadata,
+    warehouse_id: WarehouseId,
+    table_id: TableId,
+    catalog_state: C::State,
+) -> Result<bool> {
+    let policies = warehouse_label_policies::<C>(warehouse_id, catalog_state.clone()).await?;
+    if policies.is_empty() {
+        return Ok(true);
+    }
+
+    let labels = C::get_effective_table_labels(warehouse_id, table_id, catalog_state).await?;
+    let applicable = policies
+        .iter()
+        .filter(|policy| policy.applies_to(&labels))
+        .collect::<Vec<_>>();
+    if applicable.is_empty() {
+        return Ok(true);
+    }
+
+    let roles = authorizer.list_actor_roles(request_metadata).await?;
+    Ok(policies_allow(&applicable, &roles))
+}
+
+/// Ensure that label policies allow the actor of the request to access the data of a table.
+pub(crate) async fn require_table_data_access<C: Catalog, A: Authorizer>(
+    authorizer: &A,
+    request_metadata: &RequestMetadata,
+    warehouse_id: WarehouseId,
+    table_id: TableId,
+    catalog_state: C::State,
+) -> Result<()> {
+    if is_table_data_access_allowed::<C, A>(
+        authorizer,
+        request_metadata,
+        warehouse_id,
+        table_id,
+        catalog_state,
+    )
+    .await?
+    {
+        Ok(())
+    } else {
+        let actor = request_metadata.actor();
+        Err(ErrorModel::forbidden(
+            format!("Access to the data of table {table_id} is restricted by a label policy for {actor}"),
+            "LabelPolicyForbidden",
+            None,
+        )
+        .into())
+    }
+}
+
+/// Remove all cached label policies on all instances after the settings of a project changed.
+pub(crate) async fn invalidate_label_policies() {
+    LABEL_POLICIES_CACHE.invalidate_all().await;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn labels(pairs: &[(&str, &str)]) -> Labels {
+        pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect()
+    }
+
+    fn definition(key: &str, pattern: Option<&str>) -> LabelDefinition {
+        LabelDefinition {
+            key: key.to_string(),
+            description: None,
+            pattern: pattern.map(ToString::to_string),
+            entity_types: vec![],
+        }
+    }
+
+    fn policy(key: &str, values: &[&str], allowed_roles: Vec<RoleId>) -> LabelPolicy {
+        LabelPolicy {
+            key: key.to_string(),
+            values: values.iter().map(ToString::to_string).collect(),
+            allowed_roles,
+        }
+    }
+
+    #[test]
+    fn test_deserialize_label_settings() {
+        let settings: LabelSettings = serde_json::from_value(serde_json::json!({
+            "definitions": [{"key": "classification", "pattern": "public|internal|pii"}],
+            "policies": [{
+                "key": "classification",
+                "values": ["pii"],
+                "allowed-roles": ["00000000-0000-0000-0000-000000000001"]
+            }]
+        }))
+        .unwrap();
+        assert_eq!(
+            settings.definitions[0],
+            definition("classification", Some("public|internal|pii"))
+        );
+        assert_eq!(settings.policies[0].allowed_roles.len(), 1);
+        assert_eq!(
+            serde_json::from_value::<LabelSettings>(serde_json::json!({})).unwrap(),
+            LabelSettings::default()
+        );
+    }
+
+    #[test]
+    fn test_validate_label_settings() {
+        validate_label_settings(&LabelSettings::default()).unwrap();
+        let duplicate = LabelSettings {
+            definitions: vec![definition("team", None), definition("team", None)],
+            policies: vec![],
+        };
+        let e = validate_label_settings(&duplicate).unwrap_err();
+        assert_eq!(e.error.r#type, INVALID_LABEL_SETTINGS_ERROR_TYPE);
+        let invalid_pattern = LabelSettings {
+            definitions: vec![definition("team", Some("[a-z"))],
+            policies: vec![],
+        };
+        assert!(validate_label_settings(&invalid_pattern).is_err());
+        let invalid_key = LabelSettings {
+            definitions: vec![definition("-team", None)],
+            policies: vec![],
+        };
+        assert!(validate_label_settings(&invalid_key).is_err());
+        let invalid_policy_value = LabelSettings {
+            definitions: vec![],
+            policies: vec![policy("classification", &["a,b"], vec![])],
+        };
+        assert!(validate_label_settings(&invalid_policy_value).is_err());
+    }
+
+    #[test]
+    fn test_validate_labels() {
+        let settings = LabelSettings {
+            definitions: vec![
+                definition("team", Some("[a-z-]+")),
+                LabelDefinition {
+                    entity_types: vec![LabelEntityType::Table],
+                    ..definition("tier", None)
+                },
+            ],
+            policies: vec![],
+        };
+        validate_labels(
+            &settings,
+            LabelEntityType::Table,
+            &labels(&[("team", "data-eng"), ("tier", "gold"), ("undefined", "x")]),
+        )
+        .unwrap();
+
+        let e = validate_labels(
+            &settings,
+            LabelEntityType::Table,
+            &labels(&[("team", "Data Eng")]),
+        )
+        .unwrap_err();
+        assert_eq!(
+            e.error.message,
+            "Label 'team' must match the pattern `[a-z-]+`, got 'Data Eng'."
+        );
+        let e = validate_labels(
+            &settings,
+            LabelEntityType::Namespace,
+            &labels(&[("tier", "gold"), ("bad key", "x")]),
+        )
+        .unwrap_err();
+        assert_eq!(e.error.stack.len(), 2);
+        assert!(validate_labels(
+            &LabelSettings::default(),
+            LabelEntityType::Warehouse,
+            &labels(&[("team", "a,b")]),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_label_selectors() {
+        let selectors = parse_label_selectors("team=sales,pii").unwrap();
+        assert_eq!(
+            selectors,
+            vec![
+                LabelSelector {
+                    key: "team".to_string(),
+                    value: Some("sales".to_string())
+                },
+                LabelSelector {
+                    key: "pii".to_string(),
+                    value: None
+                }
+            ]
+        );
+        assert!(matches_label_selectors(
+            &selectors,
+            &labels(&[("team", "sales"), ("pii", "true")])
+        ));
+        assert!(!matches_label_selectors(
+            &selectors,
+            &labels(&[("team", "marketing"), ("pii", "true")])
+        ));
+        assert!(!matches_label_selectors(
+            &selectors,
+            &labels(&[("team", "sales")])
+        ));
+        assert!(matches_label_selectors(&[], &Labels::new()));
+        assert!(parse_label_selectors("=sales").is_err());
+    }
+
+    #[test]
+    fn test_policies() {
+        let admin = RoleId::new_random();
+        let analyst = RoleId::new_random();
+        let pii = policy("classification", &["pii"], vec![admin]);
+        let quarantine = policy("quarantine", &[], vec![]);
+
+        assert!(pii.applies_to(&labels(&[("classification", "pii")])));
+        assert!(!pii.applies_to(&labels(&[("classification", "public")])));
+        assert!(quarantine.applies_to(&labels(&[("quarantine", "")])));
+
+        assert!(policies_allow(&[&pii], &[analyst, admin]));
+        assert!(!policies_allow(&[&pii], &[analyst]));
+        assert!(!policies_allow(&[&pii, &quarantine], &[admin]));
+        assert!(policies_allow(&[], &[]));
+
+        let policies = [pii];
+        assert!(changes_policy_labels(
+            &policies,
+            &labels(&[("classification", "pii")]),
+            &labels(&[])
+        ));
+        assert!(!changes_policy_labels(
+            &policies,
+            &labels(&[("classification", "pii"), ("team", "a")]),
+            &labels(&[("classification", "pii")])
+        ));
+    }
+}
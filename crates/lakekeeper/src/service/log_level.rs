@@ -0,0 +1,445 @@
+//! Log levels that can be changed at runtime.
+//!
+//! The log filter of the process is built from `RUST_LOG` at startup. Via the management API
+//! operators raise or lower the level globally or for single modules, optionally for a limited
+//! time, and enable debug tracing for requests of single principals or warehouses. Changes
+//! apply to the instance serving the management request and are lost on restart.
+//!
+//! Targeted tracing marks the `request` span of matching requests with `debug_trace=true`.
+//! While trace targets are active, the filter contains the directive
+//! `[request{debug_trace=true}]=<level>`, so that all events inside such requests are logged.
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::{LazyLock, OnceLock, RwLock},
+    time::Duration,
+};
+
+use axum::{
+    extract::{Path, Request},
+    middleware::Next,
+    response::Response,
+};
+use chrono::{DateTime, Utc};
+use iceberg_ext::catalog::rest::ErrorModel;
+use serde::{Deserialize, Serialize};
+use tracing_subscriber::{
+    filter::{Directive, LevelFilter},
+    reload, EnvFilter, Registry,
+};
+use uuid::Uuid;
+
+use crate::{
+    request_metadata::RequestMetadata, service::endpoint_policy::warehouse_id_from_path,
+    WarehouseId,
+};
+
+/// Field of the `request` span that marks requests matching a trace target.
+pub const DEBUG_TRACE_FIELD: &str = "debug_trace";
+/// Upper bound for the duration of temporary changes.
+pub const MAX_LOG_LEVEL_DURATION: Duration = Duration::from_secs(24 * 60 * 60);
+
+static FILTER_HANDLE: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+static LOG_LEVEL_STATE: LazyLock<RwLock<LogLevelState>> = LazyLock::new(RwLock::default);
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    strum_macros::Display,
+    strum_macros::EnumString,
+    utoipa::ToSchema,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum LogLevel {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+/// Level of a module, or the default level if no target is set.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct LogLevelOverride {
+    /// Module path the level applies to, such as `lakekeeper::implementations::postgres`
+    pub target: Option<String>,
+    pub level: LogLevel,
+    /// Time the override is reverted. Kept until restart if not set.
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Requests of a principal, a warehouse or a principal in a warehouse that are traced.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TraceTarget {
+    pub trace_target_id: Uuid,
+    pub principal: Option<String>,
+    pub warehouse_id: Option<Uuid>,
+    pub level: LogLevel,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl TraceTarget {
+    fn matches(&self, principal: Option<&str>, warehouse_id: Option<WarehouseId>) -> bool {
+        self.principal
+            .as_deref()
+            .map_or(true, |p| Some(p) == principal)
+            && self
+                .warehouse_id
+                .map_or(true, |w| Some(w) == warehouse_id.map(|id| *id))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct LogLevelStatus {
+    /// Directives of `RUST_LOG` at startup
+    pub startup_directives: String,
+    /// Filter directives currently in effect
+    pub effective_directives: String,
+    pub overrides: Vec<LogLevelOverride>,
+    pub trace_targets: Vec<TraceTarget>,
+}
+
+#[derive(Debug, Default)]
+struct LogLevelState {
+    startup_directives: String,
+    overrides: Vec<LogLevelOverride>,
+    trace_targets: Vec<TraceTarget>,
+}
+
+impl LogLevelState {
+    fn directives(&self) -> String {
+        let mut directives = vec![];
+        if !self.startup_directives.is_empty() {
+            directives.push(self.startup_directives.clone());
+        }
+        // Later directives for the same target take precedence
+        directives.extend(self.overrides.iter().map(|o| match &o.target {
+            Some(target) => format!("{target}={}", o.level),
+            None => o.level.to_string(),
+        }));
+        if let Some(level) = self.trace_targets.iter().map(|t| t.level).max() {
+            directives.push(format!("[request{{{DEBUG_TRACE_FIELD}=true}}]={level}"));
+        }
+        directives.join(",")
+    }
+
+    /// Removes expired overrides and targets. Returns true if any was removed.
+    fn remove_expired(&mut self, now: DateTime<Utc>) -> bool {
+        let before = self.overrides.len() + self.trace_targets.len();
+        self.overrides
+            .retain(|o| o.expires_at.map_or(true, |expires_at| expires_at > now));
+        self.trace_targets.retain(|t| t.expires_at > now);
+        before != self.overrides.len() + self.trace_targets.len()
+    }
+
+    fn status(&self) -> LogLevelStatus {
+        LogLevelStatus {
+            startup_directives: self.startup_directives.clone(),
+            effective_directives: self.directives(),
+            overrides: self.overrides.clone(),
+            trace_targets: self.trace_targets.clone(),
+        }
+    }
+}
+
+fn build_filter(directives: &str) -> EnvFilter {
+    EnvFilter::builder()
+        .with_default_directive(LevelFilter::INFO.into())
+        .parse_lossy(directives)
+}
+
+/// Global log filter of the process. Must be the first layer of the subscriber so
+/// that its level can be changed at runtime.
+#[must_use]
+pub fn env_filter_layer() -> reload::Layer<EnvFilter, Registry> {
+    let startup_directives = std::env::var(EnvFilter::DEFAULT_ENV).unwrap_or_default();
+    let filter = build_filter(&startup_directives);
+    LOG_LEVEL_STATE
+        .write()
+        .expect("Log level state lock poisoned")
+        .startup_directives = startup_directives;
+    let (layer, handle) = reload::Layer::new(filter);
+    if FILTER_HANDLE.set(handle).is_err() {
+        tracing::warn!("Log filter layer was created twice, only the first one is reloadable");
+    }
+    layer
+}
+
+fn reload_handle() -> Result<&'static reload::Handle<EnvFilter, Registry>, ErrorModel> {
+    FILTER_HANDLE.get().ok_or_else(|| {
+        ErrorModel::not_implemented(
+            "Log levels can't be changed at runtime, the log filter of this process is not reloadable",
+            "LogLevelNotReloadable",
+            None,
+        )
+    })
+}
+
+fn apply(state: &LogLevelState) -> Result<(), ErrorModel> {
+    let directives = state.directives();
+    reload_handle()?
+        .reload(build_filter(&directives))
+        .map_err(|e| {
+            ErrorModel::internal(
+                "Failed to reload log filter",
+                "LogLevelReloadFailed",
+                Some(Box::new(e)),
+            )
+        })?;
+    tracing::info!("Log filter changed to '{directives}'");
+    Ok(())
+}
+
+/// Reverts the change once `expires_at` has passed.
+fn schedule_expiry(expires_at: DateTime<Utc>) {
+    let delay = (expires_at - Utc::now()).to_std().unwrap_or_default();
+    tokio::spawn(async move {
+        tokio::time::sleep(delay).await;
+        let mut state = LOG_LEVEL_STATE
+            .write()
+            .expect("Log level state lock poisoned");
+        if state.remove_expired(Utc::now()) {
+            if let Err(e) = apply(&state) {
+                tracing::error!(?e, "Failed to revert expired log level: {}", e.message);
+            }
+        }
+    });
+}
+
+fn expiry(duration_seconds: Option<u64>) -> Result<Option<DateTime<Utc>>, ErrorModel> {
+    let Some(duration_seconds) = duration_seconds else {
+        return Ok(None);
+    };
+    let duration = Duration::from_secs(duration_seconds);
+    if duration.is_zero() || duration > MAX_LOG_LEVEL_DURATION {
+        return Err(ErrorModel::bad_request(
+            format!(
+                "Duration must be between 1 and {} seconds",
+                MAX_LOG_LEVEL_DURATION.as_secs()
+            ),
+            "InvalidLogLevelDuration",
+            None,
+        ));
+    }
+    Ok(chrono::Duration::from_std(duration)
+        .ok()
+        .map(|duration| Utc::now() + duration))
+}
+
+fn validate_target(target: &str) -> Result<(), ErrorModel> {
+    let valid = !target.is_empty()
+        && target
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':' || c == '-')
+        && Directive::from_str(&format!("{target}=info")).is_ok();
+    if valid {
+        Ok(())
+    } else {
+        Err(ErrorModel::bad_request(
+            format!("Invalid log target '{target}'. Expected a module path such as 'lakekeeper::implementations::postgres'"),
+            "InvalidLogTarget",
+            None,
+        ))
+    }
+}
+
+/// Current levels and trace targets of this instance.
+pub(crate) fn log_level_status() -> LogLevelStatus {
+    let mut state = LOG_LEVEL_STATE
+        .write()
+        .expect("Log level state lock poisoned");
+    // Expired entries are normally removed by their timer
+    state.remove_expired(Utc::now());
+    state.status()
+}
+
+/// Sets the level of `target`, or the default level if no target is given.
+/// Replaces a previous override of the same target.
+pub(crate) fn set_log_level(
+    target: Option<String>,
+    level: LogLevel,
+    duration_seconds: Option<u64>,
+) -> Result<LogLevelStatus, ErrorModel> {
+    if let Some(target) = &target {
+        validate_target(target)?;
+    }
+    let expires_at = expiry(duration_seconds)?;
+    reload_handle()?;
+
+    let mut state = LOG_LEVEL_STATE
+        .write()
+        .expect("Log level state lock poisoned");
+    state.overrides.retain(|o| o.target != target);
+    state.overrides.push(LogLevelOverride {
+        target,
+        level,
+        expires_at,
+    });
+    apply(&state)?;
+    if let Some(expires_at) = expires_at {
+        schedule_expiry(expires_at);
+    }
+    Ok(state.status())
+}
+
+/// Traces requests matching the principal and warehouse at `level` for `duration_seconds`.
+pub(crate) fn add_trace_target(
+    principal: Option<String>,
+    warehouse_id: Option<WarehouseId>,
+    level: LogLevel,
+    duration_seconds: u64,
+) -> Result<LogLevelStatus, ErrorModel> {
+    if principal.is_none() && warehouse_id.is_none() {
+        return Err(ErrorModel::bad_request(
+            "A trace target requires a principal, a warehouse or both",
+            "InvalidTraceTarget",
+            None,
+        ));
+    }
+    let expires_at = expiry(Some(duration_seconds))?.unwrap_or_else(Utc::now);
+    reload_handle()?;
+
+    let mut state = LOG_LEVEL_STATE
+        .write()
+        .expect("Log level state lock poisoned");
+    state.trace_targets.push(TraceTarget {
+        trace_target_id: Uuid::now_v7(),
+        principal,
+        warehouse_id: warehouse_id.map(|id| *id),
+        level,
+        expires_at,
+    });
+    apply(&state)?;
+    schedule_expiry(expires_at);
+    Ok(state.status())
+}
+
+/// Removes all overrides and trace targets, restoring the levels of `RUST_LOG`.
+pub(crate) fn reset_log_levels() -> Result<LogLevelStatus, ErrorModel> {
+    reload_handle()?;
+    let mut state = LOG_LEVEL_STATE
+        .write()
+        .expect("Log level state lock poisoned");
+    state.overrides.clear();
+    state.trace_targets.clear();
+    apply(&state)?;
+    Ok(state.status())
+}
+
+/// Middleware marking requests that match a trace target.
+///
+/// This middleware needs to run after [`auth_middleware_fn`](crate::service::authn::auth_middleware_fn).
+pub(crate) async fn debug_trace_middleware_fn(
+    Path(path_params): Path<HashMap<String, String>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let traced = {
+        let state = LOG_LEVEL_STATE
+            .read()
+            .expect("Log level state lock poisoned");
+        !state.trace_targets.is_empty()
+            && request
+                .extensions()
+                .get::<RequestMetadata>()
+                .is_some_and(|metadata| {
+                    let principal = metadata.user_id().map(ToString::to_string);
+                    let warehouse_id = warehouse_id_from_path(&path_params);
+                    let now = Utc::now();
+                    state.trace_targets.iter().any(|t| {
+                        t.expires_at > now && t.matches(principal.as_deref(), warehouse_id)
+                    })
+                })
+    };
+    if traced {
+        tracing::Span::current().record(DEBUG_TRACE_FIELD, true);
+    }
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_directives() {
+        let mut state = LogLevelState {
+            startup_directives: "info,sqlx=warn".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(state.directives(), "info,sqlx=warn");
+
+        let now = Utc::now();
+        state.overrides.push(LogLevelOverride {
+            target: Some("lakekeeper::implementations::postgres".to_string()),
+            level: LogLevel::Debug,
+            expires_at: Some(now + chrono::Duration::minutes(10)),
+        });
+        state.overrides.push(LogLevelOverride {
+            target: None,
+            level: LogLevel::Warn,
+            expires_at: None,
+        });
+        state.trace_targets.push(TraceTarget {
+            trace_target_id: Uuid::now_v7(),
+            principal: Some("oidc~user".to_string()),
+            warehouse_id: None,
+            level: LogLevel::Trace,
+            expires_at: now + chrono::Duration::minutes(10),
+        });
+        let directives = state.directives();
+        assert_eq!(
+            directives,
+            "info,sqlx=warn,lakekeeper::implementations::postgres=debug,warn,[request{debug_trace=true}]=trace"
+        );
+        for directive in directives.split(',') {
+            assert!(Directive::from_str(directive).is_ok(), "{directive}");
+        }
+
+        assert!(!state.remove_expired(now));
+        assert!(state.remove_expired(now + chrono::Duration::minutes(11)));
+        assert_eq!(state.directives(), "info,sqlx=warn,warn");
+    }
+
+    #[test]
+    fn test_trace_target_matches() {
+        let warehouse_id = WarehouseId::new_random();
+        let target = TraceTarget {
+            trace_target_id: Uuid::now_v7(),
+            principal: Some("oidc~user".to_string()),
+            warehouse_id: Some(*warehouse_id),
+            level: LogLevel::Debug,
+            expires_at: Utc::now(),
+        };
+        assert!(target.matches(Some("oidc~user"), Some(warehouse_id)));
+        assert!(!target.matches(Some("oidc~user"), None));
+        assert!(!target.matches(Some("oidc~other"), Some(warehouse_id)));
+
+        let warehouse_only = TraceTarget {
+            principal: None,
+            ..target
+        };
+        assert!(warehouse_only.matches(None, Some(warehouse_id)));
+    }
+
+    #[test]
+    fn test_validate_target() {
+        assert!(validate_target("lakekeeper::implementations::postgres").is_ok());
+        assert!(validate_target("sqlx").is_ok());
+        assert!(validate_target("").is_err());
+        assert!(validate_target("lakekeeper=debug").is_err());
+        assert!(validate_target("[request]").is_err());
+    }
+}
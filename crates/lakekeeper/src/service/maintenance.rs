@@ -0,0 +1,369 @@
+//! Analyzes the data files of tables to recommend maintenance operations.
+//!
+//! Lakekeeper does not rewrite data files itself. Instead, partitions that would benefit
+//! from compaction are reported and can be handed to external compaction jobs via
+//! endpoint hooks, i.e. as `compactionRequested` cloud events.
+//!
+//! The thresholds mirror the defaults of Iceberg's `rewrite_data_files` procedure:
+//! A data file is small if it is smaller than 75% of the target file size of the table.
+//! A partition is recommended for compaction if it contains at least
+//! `compaction_min_input_files` small files, or if at least `compaction_delete_file_threshold`
+//! delete files apply to it.
+use std::collections::BTreeMap;
+
+use futures::{StreamExt, TryStreamExt};
+use iceberg::{
+    io::FileIO,
+    spec::{DataContentType, StructType, TableMetadata},
+};
+use iceberg_ext::catalog::rest::ErrorModel;
+use serde::{Deserialize, Serialize};
+
+use crate::{api::Result, CONFIG};
+
+/// Table property holding the target size of data files.
+pub const TARGET_FILE_SIZE_PROPERTY: &str = "write.target-file-size-bytes";
+/// Default of `write.target-file-size-bytes` in Iceberg: 512 MiB.
+pub const DEFAULT_TARGET_FILE_SIZE_BYTES: u64 = 512 * 1024 * 1024;
+/// Maximum number of file paths listed per recommendation.
+pub const MAX_FILES_PER_RECOMMENDATION: usize = 10_000;
+const MANIFEST_READ_CONCURRENCY: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum CompactionReason {
+    /// The partition contains many data files smaller than the minimum file size.
+    SmallFiles,
+    /// Many delete files apply to the partition.
+    DeleteFiles,
+}
+
+/// A partition of the current snapshot that should be compacted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct CompactionRecommendation {
+    pub spec_id: i32,
+    /// Partition values in the order of the fields of the partition spec.
+    /// Empty for unpartitioned tables.
+    pub partition: Vec<serde_json::Value>,
+    pub reasons: Vec<CompactionReason>,
+    pub data_files: usize,
+    pub small_files: usize,
+    pub small_files_size_bytes: u64,
+    pub delete_files: usize,
+    /// Small data files and delete files that should be rewritten.
+    pub files: Vec<String>,
+    /// More files should be rewritten than are listed in `files`.
+    pub files_truncated: bool,
+}
+
+/// Result of analyzing the current snapshot of a table.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct MaintenanceAnalysis {
+    /// Analyzed snapshot. `None` if the table has no snapshot.
+    pub snapshot_id: Option<i64>,
+    pub target_file_size_bytes: u64,
+    /// Data files smaller than this are considered small.
+    pub min_file_size_bytes: u64,
+    pub data_files: usize,
+    pub small_files: usize,
+    pub delete_files: usize,
+    pub recommendations: Vec<CompactionRecommendation>,
+}
+
+/// Thresholds for compaction recommendations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompactionThresholds {
+    pub min_file_size_bytes: u64,
+    pub min_input_files: usize,
+    pub delete_file_threshold: usize,
+}
+
+impl CompactionThresholds {
+    /// Thresholds for a table, respecting its `write.target-file-size-bytes` property.
+    #[must_use]
+    pub fn for_table(metadata: &TableMetadata) -> Self {
+        let target_file_size_bytes = target_file_size_bytes(metadata);
+        Self {
+            min_file_size_bytes: target_file_size_bytes / 4 * 3,
+            min_input_files: CONFIG.compaction_min_input_files.max(1),
+            delete_file_threshold: CONFIG.compaction_delete_file_threshold.max(1),
+        }
+    }
+}
+
+/// A live data or delete file of a snapshot.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnalyzedFile {
+    pub spec_id: i32,
+    pub partition: Vec<serde_json::Value>,
+    pub path: String,
+    pub size_bytes: u64,
+    pub is_delete: bool,
+}
+
+#[must_use]
+pub fn target_file_size_bytes(metadata: &TableMetadata) -> u64 {
+    metadata
+        .properties()
+        .get(TARGET_FILE_SIZE_PROPERTY)
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(DEFAULT_TARGET_FILE_SIZE_BYTES)
+}
+
+/// Reads the manifests of the current snapshot and recommends partitions for compaction.
+///
+/// # Errors
+/// Fails if the manifest list or a manifest cannot be read.
+pub async fn analyze_table(
+    metadata: &TableMetadata,
+    file_io: &FileIO,
+) -> Result<MaintenanceAnalysis> {
+    let thresholds = CompactionThresholds::for_table(metadata);
+    let files = match metadata.current_snapshot() {
+        Some(snapshot) => {
+            let manifest_list = snapshot
+                .load_manifest_list(file_io, metadata)
+                .await
+                .map_err(|e| io_error("Failed to read manifest list", e))?;
+            let partition_types = metadata
+                .partition_specs_iter()
+                .map(|spec| {
+                    spec.partition_type(metadata.current_schema())
+                        .map(|t| (spec.spec_id(), t))
+                        .map_err(|e| io_error("Failed to determine partition type", e))
+                })
+                .collect::<Result<BTreeMap<_, _>, _>>()?;
+
+            futures::stream::iter(manifest_list.entries().to_vec())
+                .map(|manifest| async move {
+                    let spec_id = manifest.partition_spec_id;
+                    manifest
+                        .load_manifest(file_io)
+                        .await
+                        .map(|m| (spec_id, m))
+                        .map_err(|e| io_error("Failed to read manifest", e))
+                })
+                .buffered(MANIFEST_READ_CONCURRENCY)
+                .try_collect::<Vec<_>>()
+                .await?
+                .into_iter()
+                .map(|(spec_id, manifest)| {
+                    let partition_type = partition_types.get(&spec_id);
+                    manifest
+                        .entries()
+                        .iter()
+                        .filter(|entry| entry.is_alive())
+                        .map(|entry| {
+                            let file = entry.data_file();
+                            Ok(AnalyzedFile {
+                                spec_id,
+                                partition: partition_values(file.partition(), partition_type)?,
+                                path: file.file_path().to_string(),
+                                size_bytes: file.file_size_in_bytes(),
+                                is_delete: file.content_type() != DataContentType::Data,
+                            })
+                        })
+                        .collect::<Result<Vec<_>>>()
+                })
+                .collect::<Result<Vec<_>>>()?
+                .into_iter()
+                .flatten()
+                .collect()
+        }
+        None => vec![],
+    };
+
+    let mut analysis = recommend(files, thresholds);
+    analysis.snapshot_id = metadata.current_snapshot_id();
+    analysis.target_file_size_bytes = target_file_size_bytes(metadata);
+    Ok(analysis)
+}
+
+/// Groups files by partition and recommends partitions exceeding the thresholds,
+/// partitions with the most small files first.
+#[must_use]
+pub fn recommend(
+    files: Vec<AnalyzedFile>,
+    thresholds: CompactionThresholds,
+) -> MaintenanceAnalysis {
+    let mut partitions = BTreeMap::<(i32, String), Vec<AnalyzedFile>>::new();
+    for file in files {
+        let key = (
+            file.spec_id,
+            serde_json::Value::Array(file.partition.clone()).to_string(),
+        );
+        partitions.entry(key).or_default().push(file);
+    }
+
+    let mut analysis = MaintenanceAnalysis {
+        snapshot_id: None,
+        target_file_size_bytes: 0,
+        min_file_size_bytes: thresholds.min_file_size_bytes,
+        data_files: 0,
+        small_files: 0,
+        delete_files: 0,
+        recommendations: vec![],
+    };
+    for ((spec_id, _), files) in partitions {
+        let partition = files[0].partition.clone();
+        let (deletes, data): (Vec<_>, Vec<_>) = files.into_iter().partition(|f| f.is_delete);
+        let small = data
+            .iter()
+            .filter(|f| f.size_bytes < thresholds.min_file_size_bytes)
+            .collect::<Vec<_>>();
+        analysis.data_files += data.len();
+        analysis.small_files += small.len();
+        analysis.delete_files += deletes.len();
+
+        let mut reasons = vec![];
+        // A single small file can't be compacted into fewer files
+        if small.len() >= thresholds.min_input_files && small.len() > 1 {
+            reasons.push(CompactionReason::SmallFiles);
+        }
+        if deletes.len() >= thresholds.delete_file_threshold {
+            reasons.push(CompactionReason::DeleteFiles);
+        }
+        if reasons.is_empty() {
+            continue;
+        }
+
+        let files = small
+            .iter()
+            .map(|f| f.path.clone())
+            .chain(deletes.iter().map(|f| f.path.clone()))
+            .collect::<Vec<_>>();
+        analysis.recommendations.push(CompactionRecommendation {
+            spec_id,
+            partition,
+            reasons,
+            data_files: data.len(),
+            small_files: small.len(),
+            small_files_size_bytes: small.iter().map(|f| f.size_bytes).sum(),
+            delete_files: deletes.len(),
+            files_truncated: files.len() > MAX_FILES_PER_RECOMMENDATION,
+            files: files
+                .into_iter()
+                .take(MAX_FILES_PER_RECOMMENDATION)
+                .collect(),
+        });
+    }
+    analysis.recommendations.sort_by(|a, b| {
+        b.small_files
+            .cmp(&a.small_files)
+            .then_with(|| b.delete_files.cmp(&a.delete_files))
+    });
+    analysis
+}
+
+fn partition_values(
+    partition: &iceberg::spec::Struct,
+    partition_type: Option<&StructType>,
+) -> Result<Vec<serde_json::Value>> {
+    let Some(partition_type) = partition_type else {
+        return Ok(vec![]);
+    };
+    partition
+        .iter()
+        .zip(partition_type.fields())
+        .map(|(value, field)| {
+            value.map_or(Ok(serde_json::Value::Null), |v| {
+                v.clone().try_into_json(&field.field_type)
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| io_error("Failed to serialize partition values", e).into())
+}
+
+fn io_error(message: &str, e: iceberg::Error) -> ErrorModel {
+    ErrorModel::internal(message, "MaintenanceAnalysisError", Some(Box::new(e)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn file(partition: i64, path: &str, size_bytes: u64, is_delete: bool) -> AnalyzedFile {
+        AnalyzedFile {
+            spec_id: 0,
+            partition: vec![serde_json::json!(partition)],
+            path: path.to_string(),
+            size_bytes,
+            is_delete,
+        }
+    }
+
+    const THRESHOLDS: CompactionThresholds = CompactionThresholds {
+        min_file_size_bytes: 100,
+        min_input_files: 3,
+        delete_file_threshold: 2,
+    };
+
+    #[test]
+    fn test_recommend_small_files() {
+        let files = vec![
+            file(1, "a", 10, false),
+            file(1, "b", 20, false),
+            file(1, "c", 30, false),
+            file(1, "big", 1000, false),
+            file(2, "d", 10, false),
+            file(2, "e", 10, false),
+        ];
+        let analysis = recommend(files, THRESHOLDS);
+        assert_eq!(analysis.data_files, 6);
+        assert_eq!(analysis.small_files, 5);
+        assert_eq!(analysis.recommendations.len(), 1);
+        let recommendation = &analysis.recommendations[0];
+        assert_eq!(recommendation.partition, vec![serde_json::json!(1)]);
+        assert_eq!(recommendation.reasons, vec![CompactionReason::SmallFiles]);
+        assert_eq!(recommendation.data_files, 4);
+        assert_eq!(recommendation.small_files, 3);
+        assert_eq!(recommendation.small_files_size_bytes, 60);
+        assert_eq!(recommendation.files, vec!["a", "b", "c"]);
+        assert!(!recommendation.files_truncated);
+    }
+
+    #[test]
+    fn test_recommend_delete_files() {
+        let files = vec![
+            file(1, "a", 1000, false),
+            file(1, "del-1", 10, true),
+            file(1, "del-2", 10, true),
+        ];
+        let analysis = recommend(files, THRESHOLDS);
+        assert_eq!(analysis.small_files, 0);
+        assert_eq!(analysis.delete_files, 2);
+        assert_eq!(analysis.recommendations.len(), 1);
+        assert_eq!(
+            analysis.recommendations[0].reasons,
+            vec![CompactionReason::DeleteFiles]
+        );
+        assert_eq!(analysis.recommendations[0].files, vec!["del-1", "del-2"]);
+    }
+
+    #[test]
+    fn test_single_small_file_is_not_recommended() {
+        let thresholds = CompactionThresholds {
+            min_input_files: 1,
+            ..THRESHOLDS
+        };
+        let analysis = recommend(vec![file(1, "a", 10, false)], thresholds);
+        assert!(analysis.recommendations.is_empty());
+    }
+
+    #[test]
+    fn test_recommendations_sorted_by_small_files() {
+        let mut files = (0..3)
+            .map(|i| file(1, &format!("p1-{i}"), 10, false))
+            .collect::<Vec<_>>();
+        files.extend((0..5).map(|i| file(2, &format!("p2-{i}"), 10, false)));
+        let analysis = recommend(files, THRESHOLDS);
+        assert_eq!(analysis.recommendations.len(), 2);
+        assert_eq!(
+            analysis.recommendations[0].partition,
+            vec![serde_json::json!(2)]
+        );
+    }
+}
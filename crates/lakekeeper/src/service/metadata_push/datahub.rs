@@ -0,0 +1,223 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use serde_json::{json, Value};
+use url::Url;
+
+use super::{error_for_status, ColumnType, MetadataPushTarget, PushedColumn, PushedTable};
+
+const PLATFORM_URN: &str = "urn:li:dataPlatform:iceberg";
+
+/// Pushes tables as datasets of the `iceberg` platform to the DataHub GMS.
+#[derive(Debug, Clone)]
+pub struct DataHubTarget {
+    client: reqwest::Client,
+    url: Url,
+    token: Option<String>,
+    env: String,
+}
+
+impl DataHubTarget {
+    #[must_use]
+    pub fn new(url: Url, token: Option<String>, env: String) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+            url,
+            token,
+            env,
+        }
+    }
+
+    fn dataset_urn(&self, table: &PushedTable) -> String {
+        format!(
+            "urn:li:dataset:({PLATFORM_URN},{},{})",
+            table.qualified_name(),
+            self.env
+        )
+    }
+
+    async fn ingest_aspect(
+        &self,
+        urn: &str,
+        aspect_name: &str,
+        aspect: &Value,
+    ) -> anyhow::Result<()> {
+        let url = self
+            .url
+            .join("aspects?action=ingestProposal")
+            .context("Invalid DataHub URL")?;
+        let request = self
+            .client
+            .post(url)
+            .header("X-RestLi-Protocol-Version", "2.0.0")
+            .json(&proposal(urn, aspect_name, aspect));
+        let request = match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        };
+        let response = request
+            .send()
+            .await
+            .with_context(|| format!("Failed to send `{aspect_name}` of {urn} to DataHub"))?;
+        error_for_status("DataHub", response).await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl MetadataPushTarget for DataHubTarget {
+    async fn push_table(&self, table: &PushedTable) -> anyhow::Result<()> {
+        let urn = self.dataset_urn(table);
+        self.ingest_aspect(&urn, "datasetProperties", &dataset_properties(table))
+            .await?;
+        self.ingest_aspect(&urn, "schemaMetadata", &schema_metadata(table))
+            .await?;
+        // Owners assigned in DataHub are kept for tables without an `owner` property
+        if let Some(owner) = &table.owner {
+            self.ingest_aspect(&urn, "ownership", &ownership(owner))
+                .await?;
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "datahub"
+    }
+}
+
+fn proposal(urn: &str, aspect_name: &str, aspect: &Value) -> Value {
+    json!({
+        "proposal": {
+            "entityType": "dataset",
+            "entityUrn": urn,
+            "changeType": "UPSERT",
+            "aspectName": aspect_name,
+            "aspect": {
+                "contentType": "application/json",
+                "value": aspect.to_string(),
+            },
+        }
+    })
+}
+
+fn dataset_properties(table: &PushedTable) -> Value {
+    json!({
+        "name": table.name,
+        "qualifiedName": table.qualified_name(),
+        "description": table.description,
+        "customProperties": table.properties,
+        "uri": table.location,
+    })
+}
+
+fn schema_metadata(table: &PushedTable) -> Value {
+    let mut fields = vec![];
+    add_schema_fields(&table.columns, &mut fields);
+    json!({
+        "schemaName": table.qualified_name(),
+        "platform": PLATFORM_URN,
+        "version": table.schema_id,
+        "hash": "",
+        "platformSchema": {"com.linkedin.schema.OtherSchema": {"rawSchema": ""}},
+        "fields": fields,
+    })
+}
+
+/// Nested columns are added as separate fields with a dotted path.
+fn add_schema_fields(columns: &[PushedColumn], fields: &mut Vec<Value>) {
+    for column in columns {
+        fields.push(json!({
+            "fieldPath": column.path.join("."),
+            "nativeDataType": column.native_type,
+            "type": {"type": {field_type(column.data_type): {}}},
+            "nullable": column.nullable,
+            "description": column.doc,
+        }));
+        add_schema_fields(&column.children, fields);
+    }
+}
+
+fn field_type(data_type: ColumnType) -> &'static str {
+    match data_type {
+        ColumnType::Boolean => "com.linkedin.schema.BooleanType",
+        ColumnType::Int
+        | ColumnType::Long
+        | ColumnType::Float
+        | ColumnType::Double
+        | ColumnType::Decimal => "com.linkedin.schema.NumberType",
+        ColumnType::Date => "com.linkedin.schema.DateType",
+        ColumnType::Time | ColumnType::Timestamp => "com.linkedin.schema.TimeType",
+        ColumnType::String | ColumnType::Uuid => "com.linkedin.schema.StringType",
+        ColumnType::Binary => "com.linkedin.schema.BytesType",
+        ColumnType::Struct => "com.linkedin.schema.RecordType",
+        ColumnType::List => "com.linkedin.schema.ArrayType",
+        ColumnType::Map => "com.linkedin.schema.MapType",
+    }
+}
+
+/// Owners that are not URNs are treated as DataHub users.
+fn ownership(owner: &str) -> Value {
+    let owner_urn = if owner.starts_with("urn:li:") {
+        owner.to_string()
+    } else {
+        format!("urn:li:corpuser:{owner}")
+    };
+    json!({
+        "owners": [{"owner": owner_urn, "type": "DATAOWNER"}],
+        "lastModified": {
+            "time": chrono::Utc::now().timestamp_millis(),
+            "actor": "urn:li:corpuser:lakekeeper",
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::metadata_push::test::pushed_table;
+
+    #[test]
+    fn test_dataset_urn() {
+        let target = DataHubTarget::new(
+            "http://localhost:8080".parse().unwrap(),
+            None,
+            "PROD".to_string(),
+        );
+        assert_eq!(
+            target.dataset_urn(&pushed_table()),
+            "urn:li:dataset:(urn:li:dataPlatform:iceberg,analytics.sales.eu.orders,PROD)"
+        );
+    }
+
+    #[test]
+    fn test_schema_metadata_flattens_nested_fields() {
+        let schema = schema_metadata(&pushed_table());
+        let fields = schema["fields"].as_array().unwrap();
+        let paths = fields
+            .iter()
+            .map(|f| f["fieldPath"].as_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(paths, vec!["id", "amount", "items", "items.sku"]);
+        assert_eq!(
+            fields[1]["type"],
+            json!({"type": {"com.linkedin.schema.NumberType": {}}})
+        );
+        assert_eq!(fields[1]["description"], "Amount in EUR");
+        assert_eq!(fields[0]["nullable"], false);
+    }
+
+    #[test]
+    fn test_ownership_keeps_urns() {
+        assert_eq!(
+            ownership("alice")["owners"][0]["owner"],
+            "urn:li:corpuser:alice"
+        );
+        assert_eq!(
+            ownership("urn:li:corpGroup:sales")["owners"][0]["owner"],
+            "urn:li:corpGroup:sales"
+        );
+    }
+}
@@ -0,0 +1,598 @@
+//! Optional push of table metadata to DataHub or OpenMetadata.
+//!
+//! The [`MetadataPusher`] hook forwards created tables and commits that change the schema or
+//! the properties of a table, which includes its owner. The [`MetadataPushBackgroundTask`] drops
+//! changes of warehouses without metadata push enabled and pushes the remaining ones with a
+//! bounded number of retries.
+
+pub mod datahub;
+pub mod openmetadata;
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::{Debug, Display},
+    sync::Arc,
+    time::Duration,
+};
+
+use iceberg::{
+    spec::{NestedFieldRef, PrimitiveType, TableMetadata, Type},
+    TableIdent,
+};
+use iceberg_ext::{
+    catalog::rest::{CommitTransactionRequest, CreateTableRequest, RegisterTableRequest},
+    configs::Location,
+};
+
+use crate::{
+    api::{
+        iceberg::v1::{DataAccess, NamespaceParameters},
+        management::v1::search::OWNER_PROPERTY,
+        RequestMetadata,
+    },
+    catalog::tables::CommitContext,
+    config::MetadataPushBackend,
+    service::{
+        authz::AuthorizationContext, endpoint_hooks::EndpointHook, Catalog, TableId, Transaction,
+    },
+    WarehouseId, CONFIG,
+};
+
+/// Table property pushed as the description of the table.
+const DESCRIPTION_PROPERTY: &str = "comment";
+/// Number of changes processed together. Changes of the same table within a batch are merged.
+const BATCH_SIZE: usize = 100;
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// External metadata platform that tables are pushed to.
+#[async_trait::async_trait]
+pub trait MetadataPushTarget: Debug + Send + Sync + 'static {
+    /// Create or replace the table including its columns, owner and properties.
+    async fn push_table(&self, table: &PushedTable) -> anyhow::Result<()>;
+
+    fn name(&self) -> &'static str;
+}
+
+/// Build the metadata push target configured via `LAKEKEEPER__METADATA_PUSH_*`.
+/// Returns `None` if no backend is configured.
+///
+/// # Errors
+/// - If a backend is configured but `LAKEKEEPER__METADATA_PUSH_URL` is not set.
+pub fn build_metadata_push_target_from_config(
+) -> anyhow::Result<Option<Arc<dyn MetadataPushTarget>>> {
+    let Some(backend) = CONFIG.metadata_push_backend else {
+        tracing::info!("No metadata platform configured. Table metadata is not pushed.");
+        return Ok(None);
+    };
+    let url = CONFIG.metadata_push_url.clone().ok_or_else(|| {
+        anyhow::anyhow!(
+            "`LAKEKEEPER__METADATA_PUSH_URL` is required if a metadata push backend is set"
+        )
+    })?;
+    let token = CONFIG.metadata_push_token.clone();
+
+    let target: Arc<dyn MetadataPushTarget> = match backend {
+        MetadataPushBackend::DataHub => Arc::new(datahub::DataHubTarget::new(
+            url.clone(),
+            token,
+            CONFIG.metadata_push_datahub_env.clone(),
+        )),
+        MetadataPushBackend::OpenMetadata => Arc::new(openmetadata::OpenMetadataTarget::new(
+            url.clone(),
+            token,
+            CONFIG.metadata_push_openmetadata_service.clone(),
+        )),
+    };
+    tracing::info!(
+        "Pushing table metadata of enabled warehouses to {} at {url}",
+        target.name()
+    );
+    Ok(Some(target))
+}
+
+/// A table as pushed to the metadata platform.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PushedTable {
+    pub table_id: TableId,
+    pub warehouse_name: String,
+    pub namespace: Vec<String>,
+    pub name: String,
+    pub location: String,
+    /// Value of the `comment` property
+    pub description: Option<String>,
+    /// Value of the `owner` property
+    pub owner: Option<String>,
+    pub properties: BTreeMap<String, String>,
+    pub schema_id: i32,
+    pub columns: Vec<PushedColumn>,
+}
+
+impl PushedTable {
+    #[must_use]
+    pub fn new(
+        warehouse_name: String,
+        table_id: TableId,
+        table_ident: &TableIdent,
+        metadata: &TableMetadata,
+    ) -> Self {
+        let properties = metadata
+            .properties()
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect::<BTreeMap<_, _>>();
+        let schema = metadata.current_schema();
+        Self {
+            table_id,
+            warehouse_name,
+            namespace: table_ident.namespace.clone().inner(),
+            name: table_ident.name.clone(),
+            location: metadata.location().to_string(),
+            description: properties.get(DESCRIPTION_PROPERTY).cloned(),
+            owner: properties.get(OWNER_PROPERTY).cloned(),
+            properties,
+            schema_id: schema.schema_id(),
+            columns: schema
+                .as_struct()
+                .fields()
+                .iter()
+                .map(|field| PushedColumn::new(field, &[]))
+                .collect(),
+        }
+    }
+
+    /// Name of the table including warehouse and namespace, separated by dots.
+    #[must_use]
+    pub fn qualified_name(&self) -> String {
+        std::iter::once(self.warehouse_name.as_str())
+            .chain(self.namespace.iter().map(String::as_str))
+            .chain(std::iter::once(self.name.as_str()))
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+}
+
+/// A column of a [`PushedTable`]. Fields of structs, including structs in lists,
+/// are pushed as children.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PushedColumn {
+    /// Names of the parent columns followed by the name of the column
+    pub path: Vec<String>,
+    pub data_type: ColumnType,
+    /// Type of list elements
+    pub element_type: Option<ColumnType>,
+    /// Iceberg type, i.e. `decimal(10,2)`
+    pub native_type: String,
+    pub nullable: bool,
+    pub doc: Option<String>,
+    pub children: Vec<PushedColumn>,
+}
+
+impl PushedColumn {
+    fn new(field: &NestedFieldRef, parent_path: &[String]) -> Self {
+        let mut path = parent_path.to_vec();
+        path.push(field.name.clone());
+        let (element_type, nested_fields) = match field.field_type.as_ref() {
+            Type::Struct(s) => (None, s.fields()),
+            Type::List(list) => (
+                Some(ColumnType::of(&list.element_field.field_type)),
+                match list.element_field.field_type.as_ref() {
+                    Type::Struct(s) => s.fields(),
+                    _ => &[][..],
+                },
+            ),
+            Type::Primitive(_) | Type::Map(_) => (None, &[][..]),
+        };
+        Self {
+            children: nested_fields
+                .iter()
+                .map(|child| Self::new(child, &path))
+                .collect(),
+            path,
+            data_type: ColumnType::of(&field.field_type),
+            element_type,
+            native_type: field.field_type.to_string(),
+            nullable: !field.required,
+            doc: field.doc.clone(),
+        }
+    }
+
+    #[must_use]
+    pub fn name(&self) -> &str {
+        self.path.last().map_or("", String::as_str)
+    }
+}
+
+/// Type of a column, as understood by both DataHub and OpenMetadata.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnType {
+    Boolean,
+    Int,
+    Long,
+    Float,
+    Double,
+    Decimal,
+    Date,
+    Time,
+    Timestamp,
+    String,
+    Uuid,
+    Binary,
+    Struct,
+    List,
+    Map,
+}
+
+impl ColumnType {
+    #[must_use]
+    pub fn of(typ: &Type) -> Self {
+        match typ {
+            Type::Primitive(primitive) => match primitive {
+                PrimitiveType::Boolean => Self::Boolean,
+                PrimitiveType::Int => Self::Int,
+                PrimitiveType::Long => Self::Long,
+                PrimitiveType::Float => Self::Float,
+                PrimitiveType::Double => Self::Double,
+                PrimitiveType::Decimal { .. } => Self::Decimal,
+                PrimitiveType::Date => Self::Date,
+                PrimitiveType::Time => Self::Time,
+                PrimitiveType::Timestamp
+                | PrimitiveType::Timestamptz
+                | PrimitiveType::TimestampNs
+                | PrimitiveType::TimestamptzNs => Self::Timestamp,
+                PrimitiveType::String => Self::String,
+                PrimitiveType::Uuid => Self::Uuid,
+                PrimitiveType::Fixed(_) | PrimitiveType::Binary => Self::Binary,
+            },
+            Type::Struct(_) => Self::Struct,
+            Type::List(_) => Self::List,
+            Type::Map(_) => Self::Map,
+        }
+    }
+}
+
+/// Whether a commit changed metadata that is pushed.
+fn is_pushed_change(previous: &TableMetadata, new: &TableMetadata) -> bool {
+    previous.current_schema_id() != new.current_schema_id()
+        || previous.properties() != new.properties()
+}
+
+/// A table that was created or changed.
+#[derive(Debug, Clone)]
+pub struct TableMetadataChange {
+    pub warehouse_id: WarehouseId,
+    pub table_id: TableId,
+    pub table_ident: TableIdent,
+    pub metadata: Arc<TableMetadata>,
+}
+
+/// Endpoint hook forwarding table changes to the [`MetadataPushBackgroundTask`].
+#[derive(Debug, Clone)]
+pub struct MetadataPusher {
+    tx: tokio::sync::mpsc::Sender<TableMetadataChange>,
+    timeout: tokio::time::Duration,
+}
+
+impl Display for MetadataPusher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "MetadataPusher")
+    }
+}
+
+impl MetadataPusher {
+    #[must_use]
+    pub fn new(tx: tokio::sync::mpsc::Sender<TableMetadataChange>) -> Self {
+        Self {
+            tx,
+            timeout: tokio::time::Duration::from_millis(50),
+        }
+    }
+
+    async fn send(&self, change: TableMetadataChange) -> anyhow::Result<()> {
+        self.tx
+            .send_timeout(change, self.timeout)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to queue metadata push: {e}"))
+    }
+}
+
+#[async_trait::async_trait]
+impl EndpointHook for MetadataPusher {
+    async fn commit_transaction(
+        &self,
+        warehouse_id: WarehouseId,
+        _request: Arc<CommitTransactionRequest>,
+        commits: Arc<Vec<CommitContext>>,
+        table_ident_map: Arc<HashMap<TableIdent, TableId>>,
+        _authorization: Arc<AuthorizationContext>,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        let idents = table_ident_map
+            .iter()
+            .map(|(ident, id)| (**id, ident))
+            .collect::<HashMap<_, _>>();
+        for commit in commits.iter() {
+            if !is_pushed_change(&commit.previous_metadata, &commit.new_metadata) {
+                continue;
+            }
+            let table_id = TableId::from(commit.new_metadata.uuid());
+            let Some(table_ident) = idents.get(&*table_id) else {
+                continue;
+            };
+            self.send(TableMetadataChange {
+                warehouse_id,
+                table_id,
+                table_ident: (*table_ident).clone(),
+                metadata: Arc::new(commit.new_metadata.clone()),
+            })
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn register_table(
+        &self,
+        warehouse_id: WarehouseId,
+        parameters: NamespaceParameters,
+        request: Arc<RegisterTableRequest>,
+        metadata: Arc<TableMetadata>,
+        _metadata_location: Arc<Location>,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        self.send(TableMetadataChange {
+            warehouse_id,
+            table_id: TableId::from(metadata.uuid()),
+            table_ident: TableIdent::new(parameters.namespace, request.name.clone()),
+            metadata,
+        })
+        .await
+    }
+
+    async fn create_table(
+        &self,
+        warehouse_id: WarehouseId,
+        parameters: NamespaceParameters,
+        request: Arc<CreateTableRequest>,
+        metadata: Arc<TableMetadata>,
+        metadata_location: Option<Arc<Location>>,
+        _data_access: DataAccess,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        // Staged tables are pushed once they are committed
+        if metadata_location.is_none() {
+            return Ok(());
+        }
+        self.send(TableMetadataChange {
+            warehouse_id,
+            table_id: TableId::from(metadata.uuid()),
+            table_ident: TableIdent::new(parameters.namespace, request.name.clone()),
+            metadata,
+        })
+        .await
+    }
+}
+
+/// Pushes [`TableMetadataChange`]s of warehouses with metadata push enabled.
+pub struct MetadataPushBackgroundTask<C: Catalog> {
+    pub source: tokio::sync::mpsc::Receiver<TableMetadataChange>,
+    pub target: Arc<dyn MetadataPushTarget>,
+    pub catalog_state: C::State,
+    /// Attempts per table before a change is dropped
+    pub max_attempts: u32,
+}
+
+impl<C: Catalog> MetadataPushBackgroundTask<C> {
+    /// Runs until all senders are dropped.
+    ///
+    /// # Errors
+    /// Never returns an error. Changes that could not be pushed are logged and dropped.
+    pub async fn run(mut self) -> anyhow::Result<()> {
+        while let Some(change) = self.source.recv().await {
+            let mut changes = vec![change];
+            while changes.len() < BATCH_SIZE {
+                match self.source.try_recv() {
+                    Ok(change) => changes.push(change),
+                    Err(_) => break,
+                }
+            }
+            if let Err(e) = self.push_changes(changes).await {
+                tracing::warn!(
+                    "Failed to push table metadata to {}: {e:?}",
+                    self.target.name()
+                );
+            }
+        }
+        Ok(())
+    }
+
+    async fn push_changes(&self, changes: Vec<TableMetadataChange>) -> anyhow::Result<()> {
+        // Only the latest change of a table is pushed
+        let mut latest = HashMap::<TableId, TableMetadataChange>::new();
+        let mut order = vec![];
+        for change in changes {
+            if latest.insert(change.table_id, change.clone()).is_none() {
+                order.push(change.table_id);
+            }
+        }
+
+        let mut warehouse_names = HashMap::<WarehouseId, Option<String>>::new();
+        for table_id in order {
+            let Some(change) = latest.remove(&table_id) else {
+                continue;
+            };
+            let warehouse_name = match warehouse_names.get(&change.warehouse_id) {
+                Some(name) => name.clone(),
+                None => {
+                    let name = self.enabled_warehouse_name(change.warehouse_id).await?;
+                    warehouse_names.insert(change.warehouse_id, name.clone());
+                    name
+                }
+            };
+            let Some(warehouse_name) = warehouse_name else {
+                continue;
+            };
+
+            let table = PushedTable::new(
+                warehouse_name,
+                change.table_id,
+                &change.table_ident,
+                &change.metadata,
+            );
+            if let Err(e) = self.push_with_retries(&table).await {
+                tracing::warn!(
+                    "Failed to push table {} ({}) to {}: {e:?}",
+                    table.qualified_name(),
+                    table.table_id,
+                    self.target.name()
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Name of the warehouse if it is active and has metadata push enabled.
+    async fn enabled_warehouse_name(
+        &self,
+        warehouse_id: WarehouseId,
+    ) -> anyhow::Result<Option<String>> {
+        let mut t = C::Transaction::begin_read(self.catalog_state.clone())
+            .await
+            .map_err(|e| anyhow::anyhow!(e.error))?;
+        let warehouse = C::get_warehouse(warehouse_id, t.transaction())
+            .await
+            .map_err(|e| anyhow::anyhow!(e.error))?;
+        t.commit().await.map_err(|e| anyhow::anyhow!(e.error))?;
+        Ok(warehouse
+            .filter(|w| w.metadata_push_enabled)
+            .map(|w| w.name))
+    }
+
+    async fn push_with_retries(&self, table: &PushedTable) -> anyhow::Result<()> {
+        let mut backoff = INITIAL_BACKOFF;
+        let mut attempt = 1;
+        loop {
+            match self.target.push_table(table).await {
+                Ok(()) => return Ok(()),
+                Err(e) if attempt < self.max_attempts => {
+                    tracing::debug!(
+                        "Attempt {attempt} to push table {} failed, retrying in {backoff:?}: {e:?}",
+                        table.table_id
+                    );
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(MAX_BACKOFF);
+                    attempt += 1;
+                }
+                Err(e) => return Err(e.context(format!("Giving up after {attempt} attempts"))),
+            }
+        }
+    }
+}
+
+/// Turns a response with an error status into an error including the response body.
+async fn error_for_status(
+    platform: &str,
+    response: reqwest::Response,
+) -> anyhow::Result<reqwest::Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body = response.text().await.unwrap_or_default();
+    anyhow::bail!("{platform} responded with status {status}: {body}")
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use iceberg::{
+        spec::{
+            FormatVersion, ListType, NestedField, Schema, SortOrder, StructType,
+            TableMetadataBuilder, UnboundPartitionSpec,
+        },
+        NamespaceIdent,
+    };
+
+    use super::*;
+
+    pub(crate) fn pushed_table() -> PushedTable {
+        let schema = Schema::builder()
+            .with_fields(vec![
+                NestedField::required(1, "id", Type::Primitive(PrimitiveType::Long)).into(),
+                NestedField::optional(
+                    2,
+                    "amount",
+                    Type::Primitive(PrimitiveType::Decimal {
+                        precision: 10,
+                        scale: 2,
+                    }),
+                )
+                .with_doc("Amount in EUR")
+                .into(),
+                NestedField::optional(
+                    3,
+                    "items",
+                    Type::List(ListType::new(
+                        NestedField::list_element(
+                            4,
+                            Type::Struct(StructType::new(vec![NestedField::optional(
+                                5,
+                                "sku",
+                                Type::Primitive(PrimitiveType::String),
+                            )
+                            .into()])),
+                            true,
+                        )
+                        .into(),
+                    )),
+                )
+                .into(),
+            ])
+            .build()
+            .unwrap();
+        let metadata = TableMetadataBuilder::new(
+            schema,
+            UnboundPartitionSpec::builder().build(),
+            SortOrder::unsorted_order(),
+            "s3://bucket/wh/sales/orders".to_string(),
+            FormatVersion::V2,
+            HashMap::from([
+                ("owner".to_string(), "alice".to_string()),
+                ("comment".to_string(), "All orders".to_string()),
+            ]),
+        )
+        .unwrap()
+        .build()
+        .unwrap()
+        .metadata;
+
+        PushedTable::new(
+            "analytics".to_string(),
+            TableId::from(metadata.uuid()),
+            &TableIdent::new(
+                NamespaceIdent::from_strs(["sales", "eu"]).unwrap(),
+                "orders".to_string(),
+            ),
+            &metadata,
+        )
+    }
+
+    #[test]
+    fn test_pushed_table_from_metadata() {
+        let table = pushed_table();
+        assert_eq!(table.qualified_name(), "analytics.sales.eu.orders");
+        assert_eq!(table.owner.as_deref(), Some("alice"));
+        assert_eq!(table.description.as_deref(), Some("All orders"));
+        assert_eq!(table.location, "s3://bucket/wh/sales/orders");
+
+        let [id, amount, items] = table.columns.as_slice() else {
+            panic!("Expected three columns, got {:?}", table.columns);
+        };
+        assert_eq!(id.data_type, ColumnType::Long);
+        assert!(!id.nullable);
+        assert_eq!(amount.data_type, ColumnType::Decimal);
+        assert_eq!(amount.doc.as_deref(), Some("Amount in EUR"));
+        assert_eq!(items.data_type, ColumnType::List);
+        assert_eq!(items.element_type, Some(ColumnType::Struct));
+        assert_eq!(items.children.len(), 1);
+        assert_eq!(items.children[0].path, vec!["items", "sku"]);
+    }
+}
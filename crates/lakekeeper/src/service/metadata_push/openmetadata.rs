@@ -0,0 +1,213 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use serde_json::{json, Value};
+use url::Url;
+
+use super::{error_for_status, ColumnType, MetadataPushTarget, PushedColumn, PushedTable};
+
+/// Pushes tables to a database service of OpenMetadata.
+///
+/// Warehouses are pushed as databases, namespaces as database schemas. OpenMetadata has no
+/// free-form table properties, so only the description and the owner are pushed from them.
+#[derive(Debug, Clone)]
+pub struct OpenMetadataTarget {
+    client: reqwest::Client,
+    url: Url,
+    token: Option<String>,
+    service: String,
+}
+
+impl OpenMetadataTarget {
+    #[must_use]
+    pub fn new(url: Url, token: Option<String>, service: String) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+            url,
+            token,
+            service,
+        }
+    }
+
+    fn request(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+    ) -> anyhow::Result<reqwest::RequestBuilder> {
+        let url = self
+            .url
+            .join(path)
+            .with_context(|| format!("Invalid OpenMetadata path '{path}'"))?;
+        let request = self.client.request(method, url);
+        Ok(match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        })
+    }
+
+    /// Create or update an entity. `PUT` is idempotent for all entities used here.
+    async fn put(&self, path: &str, body: &Value) -> anyhow::Result<()> {
+        let response = self
+            .request(reqwest::Method::PUT, path)?
+            .json(body)
+            .send()
+            .await
+            .with_context(|| format!("Failed to send request to OpenMetadata '{path}'"))?;
+        error_for_status("OpenMetadata", response).await?;
+        Ok(())
+    }
+
+    /// Reference to the user or team with the given name, if any exists.
+    async fn owner_reference(&self, owner: &str) -> anyhow::Result<Option<Value>> {
+        for (path, typ) in [("v1/users/name/", "user"), ("v1/teams/name/", "team")] {
+            let response = self
+                .request(
+                    reqwest::Method::GET,
+                    &format!("{path}{}", urlencoding::encode(owner)),
+                )?
+                .send()
+                .await
+                .context("Failed to look up owner in OpenMetadata")?;
+            if response.status() == reqwest::StatusCode::NOT_FOUND {
+                continue;
+            }
+            let entity: Value = error_for_status("OpenMetadata", response)
+                .await?
+                .json()
+                .await
+                .context("Failed to parse OpenMetadata owner")?;
+            return Ok(Some(json!({"id": entity["id"], "type": typ})));
+        }
+        Ok(None)
+    }
+}
+
+#[async_trait::async_trait]
+impl MetadataPushTarget for OpenMetadataTarget {
+    async fn push_table(&self, table: &PushedTable) -> anyhow::Result<()> {
+        let schema_name = table.namespace.join(".");
+        let database_fqn = fqn(&[&self.service, &table.warehouse_name]);
+        let schema_fqn = fqn(&[&self.service, &table.warehouse_name, &schema_name]);
+        self.put(
+            "v1/databases",
+            &json!({"name": table.warehouse_name, "service": self.service}),
+        )
+        .await?;
+        self.put(
+            "v1/databaseSchemas",
+            &json!({"name": schema_name, "database": database_fqn}),
+        )
+        .await?;
+
+        let mut body = create_table(table, &schema_fqn);
+        if let Some(owner) = &table.owner {
+            match self.owner_reference(owner).await? {
+                Some(reference) => body["owners"] = json!([reference]),
+                None => tracing::debug!(
+                    "Owner '{owner}' of table {} is neither a user nor a team in OpenMetadata",
+                    table.table_id
+                ),
+            }
+        }
+        self.put("v1/tables", &body).await
+    }
+
+    fn name(&self) -> &'static str {
+        "openmetadata"
+    }
+}
+
+/// Fully qualified name from its parts. Parts containing dots are quoted.
+fn fqn(parts: &[&str]) -> String {
+    parts
+        .iter()
+        .map(|part| {
+            if part.contains('.') {
+                format!("\"{part}\"")
+            } else {
+                (*part).to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+fn create_table(table: &PushedTable, schema_fqn: &str) -> Value {
+    json!({
+        "name": table.name,
+        "databaseSchema": schema_fqn,
+        "tableType": "Iceberg",
+        "description": table.description,
+        "locationPath": table.location,
+        "columns": table.columns.iter().map(column).collect::<Vec<_>>(),
+    })
+}
+
+fn column(column: &PushedColumn) -> Value {
+    let mut value = json!({
+        "name": column.name(),
+        "dataType": data_type(column.data_type),
+        "dataTypeDisplay": column.native_type,
+        "description": column.doc,
+        "constraint": if column.nullable { "NULL" } else { "NOT_NULL" },
+    });
+    if let Some(element_type) = column.element_type {
+        value["arrayDataType"] = json!(data_type(element_type));
+    }
+    if !column.children.is_empty() {
+        value["children"] = json!(column.children.iter().map(self::column).collect::<Vec<_>>());
+    }
+    value
+}
+
+fn data_type(data_type: ColumnType) -> &'static str {
+    match data_type {
+        ColumnType::Boolean => "BOOLEAN",
+        ColumnType::Int => "INT",
+        ColumnType::Long => "BIGINT",
+        ColumnType::Float => "FLOAT",
+        ColumnType::Double => "DOUBLE",
+        ColumnType::Decimal => "DECIMAL",
+        ColumnType::Date => "DATE",
+        ColumnType::Time => "TIME",
+        ColumnType::Timestamp => "TIMESTAMP",
+        ColumnType::String => "STRING",
+        ColumnType::Uuid => "UUID",
+        ColumnType::Binary => "BLOB",
+        ColumnType::Struct => "STRUCT",
+        ColumnType::List => "ARRAY",
+        ColumnType::Map => "MAP",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::metadata_push::test::pushed_table;
+
+    #[test]
+    fn test_fqn_quotes_parts_with_dots() {
+        assert_eq!(fqn(&["lakekeeper", "analytics"]), "lakekeeper.analytics");
+        assert_eq!(
+            fqn(&["lakekeeper", "analytics", "sales.eu"]),
+            "lakekeeper.analytics.\"sales.eu\""
+        );
+    }
+
+    #[test]
+    fn test_create_table_nests_struct_fields() {
+        let body = create_table(&pushed_table(), "lakekeeper.analytics.\"sales.eu\"");
+        assert_eq!(body["name"], "orders");
+        assert_eq!(body["description"], "All orders");
+        let columns = body["columns"].as_array().unwrap();
+        assert_eq!(columns.len(), 3);
+        assert_eq!(columns[0]["dataType"], "BIGINT");
+        assert_eq!(columns[0]["constraint"], "NOT_NULL");
+        assert_eq!(columns[2]["dataType"], "ARRAY");
+        assert_eq!(columns[2]["arrayDataType"], "STRUCT");
+        assert_eq!(columns[2]["children"][0]["name"], "sku");
+    }
+}
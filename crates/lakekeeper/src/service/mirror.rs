@@ -0,0 +1,796 @@
+//! Mirroring of catalog state into a local warehouse.
+//!
+//! Used by read replicas, federated warehouses and the restore of catalog snapshots: The desired
+//! state of a warehouse - in the format of catalog snapshots - is compared to its current state,
+//! and the differences are applied in a single transaction. Tables and views point to metadata
+//! files written by someone else, which are read with the storage profile and credential of the
+//! local warehouse.
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
+
+use iceberg::{
+    spec::{TableMetadata, ViewMetadata},
+    NamespaceIdent, TableIdent,
+};
+use iceberg_ext::{
+    catalog::rest::{CreateNamespaceRequest, ErrorModel},
+    configs::Location,
+};
+
+use crate::{
+    api::{iceberg::v1::namespace::NamespaceDropFlags, management::v1::TabularType},
+    catalog::{
+        compression_codec::CompressionCodec,
+        io::{read_file, read_metadata_file, StorageIo},
+        maybe_get_secret,
+        namespace::invalidate_namespace_ids,
+        tables::{invalidate_table_metadata, CommitContext},
+    },
+    config::AuthZBackend,
+    request_metadata::RequestMetadata,
+    service::{
+        authn::Actor,
+        authz::{Authorizer, NamespaceParent},
+        default_branch::invalidate_default_branches,
+        task_queue::catalog_snapshot_export_queue::{
+            CatalogSnapshotNamespace, CatalogSnapshotState, CatalogSnapshotTabular,
+        },
+        watch, Catalog, NamespaceId, Result, SecretStore, TableCreation, TableHistoryImport,
+        TableId, Transaction, UserId, ViewCommit, ViewId,
+    },
+    WarehouseId, CONFIG,
+};
+
+/// Owner of mirrored entities in the authorizer.
+/// `None` if no owner is configured and the authorizer requires one.
+pub(crate) fn mirror_owner(owner: Option<&UserId>) -> Option<RequestMetadata> {
+    match owner {
+        Some(owner) => Some(RequestMetadata::new_internal_as(Actor::Principal(
+            owner.clone(),
+        ))),
+        None if matches!(CONFIG.authz_backend, AuthZBackend::AllowAll) => {
+            Some(RequestMetadata::new_internal())
+        }
+        None => None,
+    }
+}
+
+/// How the target state is applied to the local warehouse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct MirrorOptions {
+    /// Create tables as external tables, whose files are owned by the source of the mirror.
+    pub(crate) external_tables: bool,
+    /// Keep soft-deleted tables, views and namespaces that are soft-deleted in the target
+    /// as well, instead of dropping them.
+    pub(crate) keep_soft_deleted: bool,
+}
+
+impl MirrorOptions {
+    /// Options of read replicas and federated warehouses.
+    pub(crate) const MIRROR: Self = Self {
+        external_tables: true,
+        keep_soft_deleted: false,
+    };
+}
+
+/// Changes that bring a local warehouse to a target state.
+///
+/// Soft-deleted entities of the target are removed from the local warehouse, staged tables
+/// are not mirrored.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct MirrorPlan {
+    /// Parents before their children
+    pub(crate) create_namespaces: Vec<CatalogSnapshotNamespace>,
+    /// Namespaces whose properties changed
+    pub(crate) update_namespaces: Vec<CatalogSnapshotNamespace>,
+    /// Children before their parents
+    pub(crate) drop_namespaces: Vec<CatalogSnapshotNamespace>,
+    pub(crate) create_tabulars: Vec<CatalogSnapshotTabular>,
+    /// Tables and views that were committed to or renamed,
+    /// as `(current state, target state)`
+    pub(crate) update_tabulars: Vec<(CatalogSnapshotTabular, CatalogSnapshotTabular)>,
+    pub(crate) drop_tabulars: Vec<CatalogSnapshotTabular>,
+}
+
+impl MirrorPlan {
+    pub(crate) fn between(current: &CatalogSnapshotState, target: &CatalogSnapshotState) -> Self {
+        let target_namespaces = target
+            .namespaces
+            .iter()
+            .filter(|n| !n.deleted)
+            .map(|n| (n.namespace_id, n))
+            .collect::<HashMap<_, _>>();
+        let current_namespaces = current
+            .namespaces
+            .iter()
+            .map(|n| (n.namespace_id, n))
+            .collect::<HashMap<_, _>>();
+        let target_tabulars = target
+            .tabulars
+            .iter()
+            .filter(|t| !t.deleted && t.metadata_location.is_some())
+            .map(|t| (t.tabular_id, t))
+            .collect::<HashMap<_, _>>();
+        let current_tabulars = current
+            .tabulars
+            .iter()
+            .map(|t| (t.tabular_id, t))
+            .collect::<HashMap<_, _>>();
+
+        let mut plan = Self::default();
+        for namespace in target_namespaces.values() {
+            match current_namespaces.get(&namespace.namespace_id) {
+                None => plan.create_namespaces.push((*namespace).clone()),
+                Some(existing) if existing.properties != namespace.properties => {
+                    plan.update_namespaces.push((*namespace).clone());
+                }
+                Some(_) => {}
+            }
+        }
+        plan.drop_namespaces = current_namespaces
+            .values()
+            .filter(|n| !target_namespaces.contains_key(&n.namespace_id))
+            .map(|n| (*n).clone())
+            .collect();
+        for tabular in target_tabulars.values() {
+            match current_tabulars.get(&tabular.tabular_id) {
+                None => plan.create_tabulars.push((*tabular).clone()),
+                Some(existing)
+                    if existing.metadata_location != tabular.metadata_location
+                        || existing.namespace != tabular.namespace
+                        || existing.name != tabular.name =>
+                {
+                    plan.update_tabulars
+                        .push(((*existing).clone(), (*tabular).clone()));
+                }
+                Some(_) => {}
+            }
+        }
+        plan.drop_tabulars = current_tabulars
+            .values()
+            .filter(|t| !target_tabulars.contains_key(&t.tabular_id))
+            .map(|t| (*t).clone())
+            .collect();
+
+        plan.create_namespaces
+            .sort_by(|a, b| a.name.len().cmp(&b.name.len()).then(a.name.cmp(&b.name)));
+        plan.update_namespaces.sort_by(|a, b| a.name.cmp(&b.name));
+        plan.drop_namespaces
+            .sort_by(|a, b| b.name.len().cmp(&a.name.len()).then(a.name.cmp(&b.name)));
+        plan.create_tabulars.sort_by_key(|t| t.tabular_id);
+        plan.update_tabulars.sort_by_key(|(t, _)| t.tabular_id);
+        plan.drop_tabulars.sort_by_key(|t| t.tabular_id);
+        plan
+    }
+
+    pub(crate) fn with_options(
+        current: &CatalogSnapshotState,
+        target: &CatalogSnapshotState,
+        options: MirrorOptions,
+    ) -> Self {
+        let mut plan = Self::between(current, target);
+        if options.keep_soft_deleted {
+            let deleted_namespaces = target
+                .namespaces
+                .iter()
+                .filter(|n| n.deleted)
+                .map(|n| n.namespace_id)
+                .collect::<HashSet<_>>();
+            let deleted_tabulars = target
+                .tabulars
+                .iter()
+                .filter(|t| t.deleted)
+                .map(|t| t.tabular_id)
+                .collect::<HashSet<_>>();
+            plan.drop_namespaces
+                .retain(|n| !(n.deleted && deleted_namespaces.contains(&n.namespace_id)));
+            plan.drop_tabulars
+                .retain(|t| !(t.deleted && deleted_tabulars.contains(&t.tabular_id)));
+        }
+        plan
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.create_namespaces.is_empty()
+            && self.update_namespaces.is_empty()
+            && self.drop_namespaces.is_empty()
+            && self.create_tabulars.is_empty()
+            && self.update_tabulars.is_empty()
+            && self.drop_tabulars.is_empty()
+    }
+}
+
+enum TabularMetadata {
+    Table(TableMetadata),
+    View(ViewMetadata),
+}
+
+/// Bring the local warehouse `warehouse_id` to the `target` state in a single transaction.
+/// Returns the applied changes. Tables and views whose metadata cannot be read are skipped
+/// and retried with the next call.
+#[allow(clippy::too_many_lines)]
+pub(crate) async fn apply_catalog_state<C: Catalog, A: Authorizer, S: SecretStore>(
+    warehouse_id: WarehouseId,
+    target: &CatalogSnapshotState,
+    catalog_state: C::State,
+    authorizer: &A,
+    secret_store: &S,
+    owner: &RequestMetadata,
+    options: MirrorOptions,
+) -> Result<MirrorPlan> {
+    let current = C::load_catalog_snapshot_state(warehouse_id, catalog_state.clone())
+        .await?
+        .ok_or_else(|| {
+            ErrorModel::not_found(
+                format!("Warehouse {warehouse_id} not found"),
+                "WarehouseNotFound",
+                None,
+            )
+        })?;
+    let mut plan = MirrorPlan::with_options(&current, target, options);
+    if plan.is_empty() {
+        return Ok(plan);
+    }
+
+    // Metadata files are read before the transaction starts, so that it stays short.
+    let mut t = C::Transaction::begin_read(catalog_state.clone()).await?;
+    let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
+    t.commit().await?;
+    let storage_secret = maybe_get_secret(warehouse.storage_secret_id, secret_store).await?;
+    let file_io = warehouse
+        .storage_profile
+        .file_io(storage_secret.as_ref())
+        .await?;
+    let mut metadata = HashMap::new();
+    let changed = plan.create_tabulars.iter().chain(
+        plan.update_tabulars
+            .iter()
+            .filter(|(current, target)| current.metadata_location != target.metadata_location)
+            .map(|(_, target)| target),
+    );
+    for tabular in changed {
+        match read_tabular_metadata(&file_io, tabular).await {
+            Ok(m) => {
+                metadata.insert(tabular.tabular_id, m);
+            }
+            // Other tabulars are still mirrored, the failed ones are retried with the next sync.
+            Err(e) => tracing::warn!(
+                "Failed to read metadata of {} {} of mirrored warehouse {warehouse_id}: {}",
+                tabular.typ,
+                tabular.tabular_id,
+                e.error
+            ),
+        }
+    }
+    plan.create_tabulars
+        .retain(|t| metadata.contains_key(&t.tabular_id));
+    plan.update_tabulars.retain(|(current, target)| {
+        current.metadata_location == target.metadata_location
+            || metadata.contains_key(&target.tabular_id)
+    });
+
+    // Namespace ids are part of the target state, so they are the same on both sides.
+    let namespace_ids = target
+        .namespaces
+        .iter()
+        .filter(|n| !n.deleted)
+        .map(|n| (n.name.clone(), NamespaceId::from(n.namespace_id)))
+        .collect::<HashMap<_, _>>();
+    let namespace_id = |name: &Vec<String>| {
+        namespace_ids.get(name).copied().ok_or_else(|| {
+            ErrorModel::internal(
+                format!("Namespace {name:?} of the target state not found"),
+                "MirrorNamespaceNotFound",
+                None,
+            )
+        })
+    };
+
+    let mut t = C::Transaction::begin_write(catalog_state).await?;
+    // Dropped first, so that new tables and views can take over their names.
+    for tabular in &plan.drop_tabulars {
+        match tabular.typ {
+            TabularType::Table => {
+                C::drop_table(TableId::from(tabular.tabular_id), true, t.transaction()).await?;
+            }
+            TabularType::View => {
+                C::drop_view(ViewId::from(tabular.tabular_id), true, t.transaction()).await?;
+            }
+        }
+    }
+
+    for namespace in &plan.create_namespaces {
+        let id = NamespaceId::from(namespace.namespace_id);
+        C::create_namespace(
+            warehouse_id,
+            id,
+            CreateNamespaceRequest {
+                namespace: namespace_ident(&namespace.name)?,
+                properties: Some(namespace.properties.clone().into_iter().collect()),
+            },
+            t.transaction(),
+        )
+        .await?;
+        let parent = match namespace.name.split_last() {
+            Some((_, parent)) if !parent.is_empty() => {
+                NamespaceParent::Namespace(namespace_id(&parent.to_vec())?)
+            }
+            _ => NamespaceParent::Warehouse(warehouse_id),
+        };
+        authorizer.create_namespace(owner, id, parent).await?;
+    }
+    for namespace in &plan.update_namespaces {
+        C::update_namespace_properties(
+            warehouse_id,
+            NamespaceId::from(namespace.namespace_id),
+            namespace.properties.clone().into_iter().collect(),
+            t.transaction(),
+        )
+        .await?;
+    }
+
+    for (current, target) in &plan.update_tabulars {
+        let source = tabular_ident(current)?;
+        let destination = tabular_ident(target)?;
+        if source != destination {
+            match target.typ {
+                TabularType::Table => {
+                    C::rename_table(
+                        warehouse_id,
+                        TableId::from(target.tabular_id),
+                        &source,
+                        &destination,
+                        t.transaction(),
+                    )
+                    .await?;
+                }
+                TabularType::View => {
+                    C::rename_view(
+                        warehouse_id,
+                        ViewId::from(target.tabular_id),
+                        &source,
+                        &destination,
+                        t.transaction(),
+                    )
+                    .await?;
+                }
+            }
+        }
+        let Some(new_metadata) = metadata.remove(&target.tabular_id) else {
+            continue;
+        };
+        let new_metadata_location = metadata_location(target)?;
+        match new_metadata {
+            TabularMetadata::Table(new_metadata) => {
+                let table_id = TableId::from(target.tabular_id);
+                let previous = C::load_tables(warehouse_id, [table_id], true, t.transaction())
+                    .await?
+                    .remove(&table_id)
+                    .ok_or_else(|| {
+                        ErrorModel::internal(
+                            format!(
+                                "Table {table_id} of mirrored warehouse {warehouse_id} not found"
+                            ),
+                            "MirrorTableNotFound",
+                            None,
+                        )
+                    })?;
+                let (number_added_metadata_log_entries, number_expired_metadata_log_entries) =
+                    metadata_log_changes(&previous.table_metadata, &new_metadata);
+                let commit = CommitContext {
+                    new_compression_codec: CompressionCodec::try_from_metadata(&new_metadata)?,
+                    new_metadata,
+                    new_metadata_location,
+                    previous_metadata: previous.table_metadata,
+                    previous_metadata_location: previous.metadata_location,
+                    updates: vec![],
+                    number_expired_metadata_log_entries,
+                    number_added_metadata_log_entries,
+                };
+                C::commit_table_transaction(warehouse_id, [commit.commit()], t.transaction())
+                    .await?;
+            }
+            TabularMetadata::View(new_metadata) => {
+                let previous_metadata_location = current
+                    .metadata_location
+                    .as_deref()
+                    .map(Location::from_str)
+                    .transpose()
+                    .map_err(|e| {
+                        ErrorModel::internal(
+                            "Invalid metadata location of view",
+                            "InvalidMetadataLocation",
+                            Some(Box::new(e)),
+                        )
+                    })?;
+                let location = view_location(&new_metadata)?;
+                C::update_view_metadata(
+                    ViewCommit {
+                        namespace_id: namespace_id(&target.namespace)?,
+                        view_id: ViewId::from(target.tabular_id),
+                        view_ident: &destination,
+                        new_metadata_location: &new_metadata_location,
+                        previous_metadata_location: previous_metadata_location
+                            .as_ref()
+                            .unwrap_or(&new_metadata_location),
+                        metadata: new_metadata,
+                        new_location: &location,
+                    },
+                    t.transaction(),
+                )
+                .await?;
+            }
+        }
+    }
+
+    for tabular in &plan.create_tabulars {
+        let Some(new_metadata) = metadata.remove(&tabular.tabular_id) else {
+            continue;
+        };
+        let ident = tabular_ident(tabular)?;
+        let parent = namespace_id(&tabular.namespace)?;
+        let metadata_location = metadata_location(tabular)?;
+        match new_metadata {
+            TabularMetadata::Table(table_metadata) => {
+                C::create_table(
+                    TableCreation {
+                        namespace_id: parent,
+                        table_ident: &ident,
+                        metadata_location: Some(&metadata_location),
+                        table_metadata,
+                        external: options.external_tables,
+                        imported_history: TableHistoryImport::default(),
+                    },
+                    t.transaction(),
+                )
+                .await?;
+                authorizer
+                    .create_table(owner, TableId::from(tabular.tabular_id), parent)
+                    .await?;
+            }
+            TabularMetadata::View(view_metadata) => {
+                let location = view_location(&view_metadata)?;
+                C::create_view(
+                    parent,
+                    &ident,
+                    view_metadata,
+                    &metadata_location,
+                    &location,
+                    t.transaction(),
+                )
+                .await?;
+                authorizer
+                    .create_view(owner, ViewId::from(tabular.tabular_id), parent)
+                    .await?;
+            }
+        }
+    }
+
+    for namespace in &plan.drop_namespaces {
+        C::drop_namespace(
+            warehouse_id,
+            NamespaceId::from(namespace.namespace_id),
+            NamespaceDropFlags {
+                force: true,
+                purge: false,
+                recursive: false,
+            },
+            t.transaction(),
+        )
+        .await?;
+    }
+    t.commit().await?;
+
+    for tabular in &plan.drop_tabulars {
+        let result = match tabular.typ {
+            TabularType::Table => {
+                authorizer
+                    .delete_table(TableId::from(tabular.tabular_id))
+                    .await
+            }
+            TabularType::View => {
+                authorizer
+                    .delete_view(ViewId::from(tabular.tabular_id))
+                    .await
+            }
+        };
+        if let Err(e) = result {
+            tracing::warn!(
+                "Failed to remove dropped {} {} from the authorizer: {}",
+                tabular.typ,
+                tabular.tabular_id,
+                e.error
+            );
+        }
+    }
+    for namespace in &plan.drop_namespaces {
+        if let Err(e) = authorizer
+            .delete_namespace(owner, NamespaceId::from(namespace.namespace_id))
+            .await
+        {
+            tracing::warn!(
+                "Failed to remove dropped namespace {} from the authorizer: {}",
+                namespace.namespace_id,
+                e.error
+            );
+        }
+    }
+
+    invalidate_namespace_ids(warehouse_id).await;
+    if !plan.update_namespaces.is_empty() {
+        invalidate_default_branches(warehouse_id).await;
+    }
+    let changed_tabulars = plan
+        .update_tabulars
+        .iter()
+        .map(|(_, t)| t)
+        .chain(&plan.drop_tabulars)
+        .chain(&plan.create_tabulars)
+        .map(|t| t.tabular_id)
+        .collect::<HashSet<_>>();
+    invalidate_table_metadata(
+        warehouse_id,
+        changed_tabulars.iter().copied().map(TableId::from),
+    )
+    .await;
+    watch::notify(warehouse_id, changed_tabulars.iter().copied());
+
+    Ok(plan)
+}
+
+async fn read_tabular_metadata(
+    file_io: &StorageIo,
+    tabular: &CatalogSnapshotTabular,
+) -> Result<TabularMetadata> {
+    let location = metadata_location(tabular)?;
+    match tabular.typ {
+        TabularType::Table => Ok(TabularMetadata::Table(
+            read_metadata_file(file_io, &location).await?,
+        )),
+        TabularType::View => {
+            let content = read_file(file_io, &location).await?;
+            let metadata = serde_json::from_slice(&content).map_err(|e| {
+                ErrorModel::internal(
+                    format!("Failed to parse view metadata file {location}"),
+                    "ViewMetadataDeserializationError",
+                    Some(Box::new(e)),
+                )
+            })?;
+            Ok(TabularMetadata::View(metadata))
+        }
+    }
+}
+
+/// Number of metadata log entries added and expired since the previous metadata.
+fn metadata_log_changes(previous: &TableMetadata, new: &TableMetadata) -> (usize, usize) {
+    let previous_files = previous
+        .metadata_log()
+        .iter()
+        .map(|l| l.metadata_file.as_str())
+        .collect::<HashSet<_>>();
+    let new_files = new
+        .metadata_log()
+        .iter()
+        .map(|l| l.metadata_file.as_str())
+        .collect::<HashSet<_>>();
+    let added = new_files.difference(&previous_files).count();
+    let expired = previous_files.difference(&new_files).count();
+    (added, expired)
+}
+
+fn metadata_location(tabular: &CatalogSnapshotTabular) -> Result<Location> {
+    let location = tabular.metadata_location.as_deref().unwrap_or_default();
+    Location::from_str(location).map_err(|e| {
+        ErrorModel::internal(
+            format!(
+                "Invalid metadata location '{location}' of {} {}",
+                tabular.typ, tabular.tabular_id
+            ),
+            "InvalidMetadataLocation",
+            Some(Box::new(e)),
+        )
+        .into()
+    })
+}
+
+fn view_location(metadata: &ViewMetadata) -> Result<Location> {
+    Location::from_str(metadata.location()).map_err(|e| {
+        ErrorModel::internal(
+            format!("Invalid location of view {}", metadata.uuid()),
+            "InvalidViewLocation",
+            Some(Box::new(e)),
+        )
+        .into()
+    })
+}
+
+fn namespace_ident(name: &[String]) -> Result<NamespaceIdent> {
+    NamespaceIdent::from_vec(name.to_vec()).map_err(|e| {
+        ErrorModel::internal(
+            format!("Invalid namespace {name:?} of the target state"),
+            "InvalidNamespace",
+            Some(Box::new(e)),
+        )
+        .into()
+    })
+}
+
+fn tabular_ident(tabular: &CatalogSnapshotTabular) -> Result<TableIdent> {
+    Ok(TableIdent::new(
+        namespace_ident(&tabular.namespace)?,
+        tabular.name.clone(),
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::service::task_queue::catalog_snapshot_export_queue::CatalogSnapshotWarehouse;
+
+    fn state(
+        namespaces: Vec<CatalogSnapshotNamespace>,
+        tabulars: Vec<CatalogSnapshotTabular>,
+    ) -> CatalogSnapshotState {
+        CatalogSnapshotState {
+            warehouse: CatalogSnapshotWarehouse {
+                name: "warehouse".to_string(),
+                storage_profile: serde_json::json!({}),
+                tabular_expiration_seconds: None,
+                protected: false,
+                disabled_endpoint_groups: vec![],
+            },
+            task_queue_configs: BTreeMap::new(),
+            namespaces,
+            tabulars,
+            access: None,
+        }
+    }
+
+    fn namespace(id: u128, name: &[&str]) -> CatalogSnapshotNamespace {
+        CatalogSnapshotNamespace {
+            namespace_id: Uuid::from_u128(id),
+            name: name.iter().map(ToString::to_string).collect(),
+            properties: BTreeMap::new(),
+            protected: false,
+            deleted: false,
+        }
+    }
+
+    fn table(id: u128, namespace: &[&str], name: &str, version: u8) -> CatalogSnapshotTabular {
+        CatalogSnapshotTabular {
+            tabular_id: Uuid::from_u128(id),
+            typ: TabularType::Table,
+            namespace: namespace.iter().map(ToString::to_string).collect(),
+            name: name.to_string(),
+            metadata_location: Some(format!(
+                "s3://bucket/{name}/metadata/0000{version}.metadata.json"
+            )),
+            protected: false,
+            deleted: false,
+        }
+    }
+
+    #[test]
+    fn test_plan_is_empty_if_in_sync() {
+        let target = state(vec![namespace(1, &["a"])], vec![table(10, &["a"], "t", 1)]);
+        assert!(MirrorPlan::between(&target, &target).is_empty());
+    }
+
+    #[test]
+    fn test_plan_orders_namespaces() {
+        let current = state(
+            vec![namespace(3, &["old"]), namespace(4, &["old", "child"])],
+            vec![],
+        );
+        let target = state(
+            vec![namespace(2, &["a", "b"]), namespace(1, &["a"])],
+            vec![],
+        );
+        let plan = MirrorPlan::between(&current, &target);
+        assert_eq!(
+            plan.create_namespaces
+                .iter()
+                .map(|n| n.namespace_id)
+                .collect::<Vec<_>>(),
+            vec![Uuid::from_u128(1), Uuid::from_u128(2)]
+        );
+        assert_eq!(
+            plan.drop_namespaces
+                .iter()
+                .map(|n| n.namespace_id)
+                .collect::<Vec<_>>(),
+            vec![Uuid::from_u128(4), Uuid::from_u128(3)]
+        );
+    }
+
+    #[test]
+    fn test_plan_tabulars() {
+        let mut staged = table(13, &["a"], "staged", 1);
+        staged.metadata_location = None;
+        let mut soft_deleted = table(14, &["a"], "deleted", 1);
+        soft_deleted.deleted = true;
+        let current = state(
+            vec![namespace(1, &["a"])],
+            vec![
+                table(10, &["a"], "committed", 1),
+                table(11, &["a"], "renamed", 1),
+                table(12, &["a"], "dropped", 1),
+                table(14, &["a"], "deleted", 1),
+                table(15, &["a"], "unchanged", 1),
+            ],
+        );
+        let target = state(
+            vec![namespace(1, &["a"])],
+            vec![
+                table(10, &["a"], "committed", 2),
+                table(11, &["a"], "new_name", 1),
+                staged,
+                soft_deleted,
+                table(15, &["a"], "unchanged", 1),
+                table(16, &["a"], "created", 1),
+            ],
+        );
+        let plan = MirrorPlan::between(&current, &target);
+        assert!(plan.create_namespaces.is_empty());
+        assert_eq!(
+            plan.create_tabulars
+                .iter()
+                .map(|t| t.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["created"]
+        );
+        assert_eq!(
+            plan.update_tabulars
+                .iter()
+                .map(|(_, t)| t.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["committed", "new_name"]
+        );
+        assert_eq!(
+            plan.drop_tabulars
+                .iter()
+                .map(|t| t.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["dropped", "deleted"]
+        );
+    }
+
+    #[test]
+    fn test_plan_keeps_soft_deleted() {
+        let mut deleted = table(10, &["a"], "deleted", 1);
+        deleted.deleted = true;
+        let current = state(vec![namespace(1, &["a"])], vec![deleted.clone()]);
+        let target = state(vec![namespace(1, &["a"])], vec![deleted.clone()]);
+
+        let plan = MirrorPlan::with_options(&current, &target, MirrorOptions::MIRROR);
+        assert_eq!(plan.drop_tabulars, vec![deleted]);
+
+        let plan = MirrorPlan::with_options(
+            &current,
+            &target,
+            MirrorOptions {
+                external_tables: false,
+                keep_soft_deleted: true,
+            },
+        );
+        assert!(plan.is_empty());
+    }
+
+    #[test]
+    fn test_plan_updates_namespace_properties() {
+        let current = state(vec![namespace(1, &["a"])], vec![]);
+        let mut changed = namespace(1, &["a"]);
+        changed
+            .properties
+            .insert("owner".to_string(), "data-team".to_string());
+        let target = state(vec![changed.clone()], vec![]);
+        let plan = MirrorPlan::between(&current, &target);
+        assert_eq!(plan.update_namespaces, vec![changed]);
+    }
+}
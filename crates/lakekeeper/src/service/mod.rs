@@ -1,17 +1,50 @@
 pub mod authn;
 pub mod authz;
 mod catalog;
+pub mod client_compatibility;
 pub mod contract_verification;
+pub mod default_branch;
 pub mod endpoint_hooks;
+pub mod endpoint_policy;
 pub mod endpoint_statistics;
+pub mod engine_compatibility;
 pub mod event_publisher;
+mod federation;
+pub mod glue_federation;
 pub mod health;
+pub mod hms_federation;
+pub mod labels;
+pub mod log_level;
+pub mod maintenance;
+pub mod metadata_push;
+pub(crate) mod mirror;
+pub mod property_schemas;
+pub mod quotas;
+pub mod rate_limit;
+pub mod replica;
+pub mod request_log;
+pub mod response_properties;
+pub mod s3_sign_audit;
+pub mod search_index;
+pub mod secret_retirement;
 pub mod secrets;
+pub mod server_events;
+pub mod shared_cache;
 pub mod storage;
+pub mod storage_intent;
+pub mod storage_policy;
+pub mod table_lineage;
+pub mod table_timeline;
 mod tabular_idents;
 pub mod task_queue;
+pub mod token_cache;
+pub mod usage;
+pub mod view_lineage;
+pub mod warehouse_metrics;
+pub mod warehouse_slug;
+pub mod watch;
 
-use std::{ops::Deref, str::FromStr};
+use std::{ops::Deref, str::FromStr, sync::Arc};
 
 pub use authn::{Actor, UserId};
 pub use catalog::{
@@ -20,9 +53,10 @@ pub use catalog::{
     DropFlags, GetNamespaceResponse, GetProjectResponse, GetStorageConfigResponse,
     GetTableMetadataResponse, GetWarehouseResponse, ListFlags, ListNamespacesQuery,
     ListNamespacesResponse, LoadTableResponse, NamespaceDropInfo, NamespaceIdent, NamespaceInfo,
-    Result, ServerInfo, TableCommit, TableCreation, TableIdent, TableInfo, TabularInfo,
-    Transaction, UndropTabularResponse, UpdateNamespacePropertiesRequest,
-    UpdateNamespacePropertiesResponse, ViewCommit, ViewMetadataWithLocation,
+    Result, ScanPlan, ScanPlanId, ServerInfo, TableCommit, TableCreation, TableHistoryImport,
+    TableIdent, TableInfo, TabularInfo, TabularVersion, Transaction, UndropTabularResponse,
+    UpdateNamespacePropertiesRequest, UpdateNamespacePropertiesResponse, ViewCommit,
+    ViewMetadataWithLocation,
 };
 pub use endpoint_statistics::EndpointStatisticsTrackerTx;
 use http::StatusCode;
@@ -36,7 +70,10 @@ use self::authz::Authorizer;
 pub use crate::api::{ErrorModel, IcebergErrorResponse};
 use crate::{
     api::{iceberg::v1::Prefix, ThreadSafe as ServiceState},
-    service::{contract_verification::ContractVerifiers, endpoint_hooks::EndpointHookCollection},
+    service::{
+        contract_verification::ContractVerifiers, endpoint_hooks::EndpointHookCollection,
+        search_index::SearchIndex,
+    },
 };
 
 // ---------------- State ----------------
@@ -48,6 +85,8 @@ pub struct State<A: Authorizer + Clone, C: Catalog, S: SecretStore> {
     pub contract_verifiers: ContractVerifiers,
     pub hooks: EndpointHookCollection,
     pub registered_task_queues: RegisteredTaskQueues,
+    /// External index used by the catalog search, if configured
+    pub search_index: Option<Arc<dyn SearchIndex>>,
 }
 
 impl<A: Authorizer + Clone, C: Catalog, S: SecretStore> ServiceState for State<A, C, S> {}
@@ -83,30 +122,34 @@ pub struct TableId(uuid::Uuid);
 pub struct WarehouseId(pub(crate) uuid::Uuid);
 
 impl NamespaceId {
+    /// Generates a new ID using the configured [`IdScheme`](crate::config::IdScheme).
     #[must_use]
     pub fn new_random() -> Self {
-        Self(uuid::Uuid::now_v7())
+        Self(crate::CONFIG.id_scheme.generate())
     }
 }
 
 impl TableId {
+    /// Generates a new ID using the configured [`IdScheme`](crate::config::IdScheme).
     #[must_use]
     pub fn new_random() -> Self {
-        Self(uuid::Uuid::now_v7())
+        Self(crate::CONFIG.id_scheme.generate())
     }
 }
 
 impl WarehouseId {
+    /// Generates a new ID using the configured [`IdScheme`](crate::config::IdScheme).
     #[must_use]
     pub fn new_random() -> Self {
-        Self(uuid::Uuid::now_v7())
+        Self(crate::CONFIG.id_scheme.generate())
     }
 }
 
 impl ViewId {
+    /// Generates a new ID using the configured [`IdScheme`](crate::config::IdScheme).
     #[must_use]
     pub fn new_random() -> Self {
-        Self(uuid::Uuid::now_v7())
+        Self(crate::CONFIG.id_scheme.generate())
     }
 }
 
@@ -492,4 +535,5 @@ impl TryFrom<Prefix> for WarehouseId {
 pub struct TabularDetails {
     pub ident: TableId,
     pub location: String,
+    pub external: bool,
 }
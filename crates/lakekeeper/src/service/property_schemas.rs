@@ -0,0 +1,530 @@
+//! Registry of typed custom properties.
+//!
+//! Projects can declare custom properties of namespaces and tables together with their
+//! type: a string, optionally restricted by a pattern or length, one of a fixed set of
+//! values, a number within bounds, or a boolean. Values of declared properties are
+//! validated whenever they are written - when namespaces and tables are created or
+//! registered and when their properties are updated. Properties that are not declared are
+//! not restricted, and existing values are not checked when the registry changes.
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    sync::LazyLock,
+};
+
+use lazy_regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::{ErrorModel, IcebergErrorResponse},
+    service::{shared_cache::SharedCache, Catalog, Result, Transaction},
+    ProjectId,
+};
+
+/// Properties are validated on every commit, so the schemas are cached.
+static PROPERTY_SCHEMAS_CACHE: LazyLock<SharedCache<Vec<PropertySchema>>> =
+    LazyLock::new(|| SharedCache::new("property-schemas"));
+
+const INVALID_SCHEMA_ERROR_TYPE: &str = "InvalidPropertySchema";
+const INVALID_VALUE_ERROR_TYPE: &str = "InvalidPropertyValue";
+
+/// Entities custom properties can be declared for.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    strum_macros::Display,
+    utoipa::ToSchema,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum PropertyEntityType {
+    Namespace,
+    Table,
+}
+
+/// Declaration of a custom property and the values it accepts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct PropertySchema {
+    /// Key of the property, for example `owner.team`
+    pub key: String,
+    /// Entities the declaration applies to
+    pub entity_types: Vec<PropertyEntityType>,
+    /// Description of the property for users of the catalog
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(flatten)]
+    pub r#type: PropertyType,
+}
+
+/// Type of a custom property. Property values are always strings, the type
+/// restricts which strings are accepted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum PropertyType {
+    String {
+        /// Regular expression the whole value must match
+        #[serde(default)]
+        pattern: Option<String>,
+        /// Maximum number of characters
+        #[serde(default, rename = "max-length")]
+        max_length: Option<usize>,
+    },
+    Enum {
+        /// Accepted values
+        values: Vec<String>,
+    },
+    Number {
+        /// Smallest accepted value
+        #[serde(default)]
+        minimum: Option<f64>,
+        /// Largest accepted value
+        #[serde(default)]
+        maximum: Option<f64>,
+        /// Only accept integers. Default: false
+        #[serde(default)]
+        integer: bool,
+    },
+    /// `true` or `false`
+    Bool,
+}
+
+/// The declared properties of a project, ready for validation.
+#[derive(Debug, Default)]
+pub(crate) struct PropertySchemaRegistry {
+    schemas: HashMap<String, PropertySchema>,
+    /// Compiled, anchored `pattern` of string properties by key
+    patterns: HashMap<String, Regex>,
+}
+
+impl PropertySchemaRegistry {
+    /// Build the registry from validated schemas.
+    ///
+    /// # Errors
+    /// If a schema is invalid.
+    pub(crate) fn try_new(schemas: Vec<PropertySchema>) -> Result<Self> {
+        validate_property_schemas(&schemas)?;
+        let mut patterns = HashMap::new();
+        for schema in &schemas {
+            if let PropertyType::String {
+                pattern: Some(pattern),
+                ..
+            } = &schema.r#type
+            {
+                patterns.insert(schema.key.clone(), compile_pattern(&schema.key, pattern)?);
+            }
+        }
+        Ok(Self {
+            schemas: schemas.into_iter().map(|s| (s.key.clone(), s)).collect(),
+            patterns,
+        })
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.schemas.is_empty()
+    }
+
+    /// Validate the values of properties written to an entity.
+    ///
+    /// # Errors
+    /// `400 Bad Request` listing every value that does not match its declaration.
+    pub(crate) fn validate<'a>(
+        &self,
+        entity_type: PropertyEntityType,
+        properties: impl IntoIterator<Item = (&'a String, &'a String)>,
+    ) -> Result<()> {
+        if self.is_empty() {
+            return Ok(());
+        }
+        let mut violations = properties
+            .into_iter()
+            .filter_map(|(key, value)| {
+                let schema = self.schemas.get(key)?;
+                if !schema.entity_types.contains(&entity_type) {
+                    return None;
+                }
+                self.check_value(schema, value).err().map(|reason| {
+                    format!("Property '{key}' of {entity_type} {reason}, got '{value}'.")
+                })
+            })
+            .collect::<Vec<_>>();
+        violations.sort();
+
+        match violations.len() {
+            0 => Ok(()),
+            1 => Err(
+                ErrorModel::bad_request(violations.remove(0), INVALID_VALUE_ERROR_TYPE, None)
+                    .into(),
+            ),
+            n => Err(ErrorModel::bad_request(
+                format!("{n} properties do not match the property schemas of the project."),
+                INVALID_VALUE_ERROR_TYPE,
+                None,
+            )
+            .append_details(violations)
+            .into()),
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn check_value(&self, schema: &PropertySchema, value: &str) -> std::result::Result<(), String> {
+        match &schema.r#type {
+            PropertyType::String {
+                pattern,
+                max_length,
+            } => {
+                if let Some(max_length) = max_length {
+                    if value.chars().count() > *max_length {
+                        return Err(format!("must have at most {max_length} characters"));
+                    }
+                }
+                let regex = self.patterns.get(&schema.key);
+                if let (Some(pattern), Some(regex)) = (pattern, regex) {
+                    if !regex.is_match(value) {
+                        return Err(format!("must match the pattern `{pattern}`"));
+                    }
+                }
+                Ok(())
+            }
+            PropertyType::Enum { values } => {
+                if values.iter().any(|v| v == value) {
+                    Ok(())
+                } else {
+                    Err(format!("must be one of {}", quoted_list(values)))
+                }
+            }
+            PropertyType::Number {
+                minimum,
+                maximum,
+                integer,
+            } => {
+                let number = if *integer {
+                    value
+                        .parse::<i64>()
+                        .map(|n| n as f64)
+                        .map_err(|_| "must be an integer".to_string())?
+                } else {
+                    value
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|n| n.is_finite())
+                        .ok_or_else(|| "must be a number".to_string())?
+                };
+                if let Some(minimum) = minimum.filter(|m| number < *m) {
+                    return Err(format!("must be at least {minimum}"));
+                }
+                if let Some(maximum) = maximum.filter(|m| number > *m) {
+                    return Err(format!("must be at most {maximum}"));
+                }
+                Ok(())
+            }
+            PropertyType::Bool => {
+                if matches!(value, "true" | "false") {
+                    Ok(())
+                } else {
+                    Err("must be `true` or `false`".to_string())
+                }
+            }
+        }
+    }
+}
+
+fn quoted_list(values: &[impl Display]) -> String {
+    values
+        .iter()
+        .map(|v| format!("'{v}'"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn compile_pattern(key: &str, pattern: &str) -> Result<Regex> {
+    Regex::new(&format!("^(?:{pattern})$")).map_err(|e| {
+        ErrorModel::bad_request(
+            format!("Pattern of property '{key}' is not a valid regular expression: {e}"),
+            INVALID_SCHEMA_ERROR_TYPE,
+            None,
+        )
+        .into()
+    })
+}
+
+fn invalid_schema(message: String) -> IcebergErrorResponse {
+    ErrorModel::bad_request(message, INVALID_SCHEMA_ERROR_TYPE, None).into()
+}
+
+/// Validate property schemas before they are stored.
+///
+/// # Errors
+/// If keys are empty or declared twice, a schema applies to no entity, or its type
+/// accepts no value.
+pub fn validate_property_schemas(schemas: &[PropertySchema]) -> Result<()> {
+    let mut keys = HashSet::new();
+    for schema in schemas {
+        let key = &schema.key;
+        if key.trim().is_empty() {
+            return Err(invalid_schema(
+                "Property keys must not be empty.".to_string(),
+            ));
+        }
+        if !keys.insert(key.as_str()) {
+            return Err(invalid_schema(format!(
+                "Property '{key}' is declared more than once."
+            )));
+        }
+        if schema.entity_types.is_empty() {
+            return Err(invalid_schema(format!(
+                "Property '{key}' must apply to at least one entity type."
+            )));
+        }
+        match &schema.r#type {
+            PropertyType::String { pattern, .. } => {
+                if let Some(pattern) = pattern {
+                    compile_pattern(key, pattern)?;
+                }
+            }
+            PropertyType::Enum { values } => {
+                if values.is_empty() {
+                    return Err(invalid_schema(format!(
+                        "Enum property '{key}' must declare at least one value."
+                    )));
+                }
+                if values.iter().collect::<HashSet<_>>().len() != values.len() {
+                    return Err(invalid_schema(format!(
+                        "Enum property '{key}' declares a value more than once."
+                    )));
+                }
+            }
+            PropertyType::Number {
+                minimum, maximum, ..
+            } => {
+                if minimum.is_some_and(|m| !m.is_finite())
+                    || maximum.is_some_and(|m| !m.is_finite())
+                {
+                    return Err(invalid_schema(format!(
+                        "Bounds of property '{key}' must be finite numbers."
+                    )));
+                }
+                if let (Some(minimum), Some(maximum)) = (minimum, maximum) {
+                    if minimum > maximum {
+                        return Err(invalid_schema(format!(
+                            "Minimum of property '{key}' is larger than its maximum."
+                        )));
+                    }
+                }
+            }
+            PropertyType::Bool => {}
+        }
+    }
+    Ok(())
+}
+
+/// Property schemas of a project. Projects without schemas accept any value.
+pub(crate) async fn get_property_schemas<C: Catalog>(
+    project_id: &ProjectId,
+    catalog_state: C::State,
+) -> Result<PropertySchemaRegistry> {
+    let schemas = PROPERTY_SCHEMAS_CACHE
+        .try_get_with(project_id.to_string(), async {
+            let mut t = C::Transaction::begin_read(catalog_state).await?;
+            let schemas = C::get_property_schemas(project_id, t.transaction()).await?;
+            t.commit().await?;
+            Ok::<_, IcebergErrorResponse>(schemas.unwrap_or_default())
+        })
+        .await?;
+    PropertySchemaRegistry::try_new(schemas)
+}
+
+/// Ensure that properties written to an entity match the property schemas of the project.
+pub(crate) async fn require_valid_properties<'a, C: Catalog>(
+    project_id: &ProjectId,
+    entity_type: PropertyEntityType,
+    properties: impl IntoIterator<Item = (&'a String, &'a String)>,
+    catalog_state: C::State,
+) -> Result<()> {
+    get_property_schemas::<C>(project_id, catalog_state)
+        .await?
+        .validate(entity_type, properties)
+}
+
+/// Remove the cached property schemas of a project on all instances after they changed.
+pub(crate) async fn invalidate_property_schemas(project_id: &ProjectId) {
+    PROPERTY_SCHEMAS_CACHE
+        .invalidate(&project_id.to_string())
+        .await;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn schema(key: &str, r#type: PropertyType) -> PropertySchema {
+        PropertySchema {
+            key: key.to_string(),
+            entity_types: vec![PropertyEntityType::Namespace, PropertyEntityType::Table],
+            description: None,
+            r#type,
+        }
+    }
+
+    fn validate(registry: &PropertySchemaRegistry, key: &str, value: &str) -> Result<()> {
+        registry.validate(
+            PropertyEntityType::Table,
+            [(&key.to_string(), &value.to_string())],
+        )
+    }
+
+    #[test]
+    fn test_deserialize_property_schema() {
+        let schema: PropertySchema = serde_json::from_value(serde_json::json!({
+            "key": "owner.team",
+            "entity-types": ["namespace", "table"],
+            "type": "string",
+            "pattern": "[a-z-]+",
+            "max-length": 32
+        }))
+        .unwrap();
+        assert_eq!(
+            schema.r#type,
+            PropertyType::String {
+                pattern: Some("[a-z-]+".to_string()),
+                max_length: Some(32),
+            }
+        );
+
+        let schema: PropertySchema = serde_json::from_value(serde_json::json!({
+            "key": "pii",
+            "entity-types": ["table"],
+            "type": "bool"
+        }))
+        .unwrap();
+        assert_eq!(schema.r#type, PropertyType::Bool);
+    }
+
+    #[test]
+    fn test_validate_property_schemas() {
+        validate_property_schemas(&[]).unwrap();
+        validate_property_schemas(&[schema(
+            "owner.team",
+            PropertyType::String {
+                pattern: Some("[a-z-]+".to_string()),
+                max_length: None,
+            },
+        )])
+        .unwrap();
+
+        let duplicate = [
+            schema("pii", PropertyType::Bool),
+            schema("pii", PropertyType::Bool),
+        ];
+        let e = validate_property_schemas(&duplicate).unwrap_err();
+        assert_eq!(e.error.r#type, INVALID_SCHEMA_ERROR_TYPE);
+
+        let invalid_pattern = schema(
+            "owner.team",
+            PropertyType::String {
+                pattern: Some("[a-z".to_string()),
+                max_length: None,
+            },
+        );
+        assert!(validate_property_schemas(&[invalid_pattern]).is_err());
+
+        let empty_enum = schema("tier", PropertyType::Enum { values: vec![] });
+        assert!(validate_property_schemas(&[empty_enum]).is_err());
+
+        let inverted_bounds = schema(
+            "retention.days",
+            PropertyType::Number {
+                minimum: Some(10.0),
+                maximum: Some(1.0),
+                integer: true,
+            },
+        );
+        assert!(validate_property_schemas(&[inverted_bounds]).is_err());
+
+        let no_entity = PropertySchema {
+            entity_types: vec![],
+            ..schema("pii", PropertyType::Bool)
+        };
+        assert!(validate_property_schemas(&[no_entity]).is_err());
+    }
+
+    #[test]
+    fn test_validate_properties() {
+        let registry = PropertySchemaRegistry::try_new(vec![
+            schema(
+                "owner.team",
+                PropertyType::String {
+                    pattern: Some("[a-z-]+".to_string()),
+                    max_length: Some(8),
+                },
+            ),
+            schema(
+                "tier",
+                PropertyType::Enum {
+                    values: vec!["gold".to_string(), "silver".to_string()],
+                },
+            ),
+            schema(
+                "retention.days",
+                PropertyType::Number {
+                    minimum: Some(1.0),
+                    maximum: Some(365.0),
+                    integer: true,
+                },
+            ),
+            PropertySchema {
+                entity_types: vec![PropertyEntityType::Namespace],
+                ..schema("pii", PropertyType::Bool)
+            },
+        ])
+        .unwrap();
+
+        validate(&registry, "owner.team", "data-eng").unwrap();
+        validate(&registry, "tier", "gold").unwrap();
+        validate(&registry, "retention.days", "30").unwrap();
+        validate(&registry, "undeclared", "anything").unwrap();
+        // Declared for namespaces only
+        validate(&registry, "pii", "maybe").unwrap();
+        registry
+            .validate(
+                PropertyEntityType::Namespace,
+                [(&"pii".to_string(), &"true".to_string())],
+            )
+            .unwrap();
+
+        let e = validate(&registry, "owner.team", "Data-Eng").unwrap_err();
+        assert_eq!(e.error.r#type, INVALID_VALUE_ERROR_TYPE);
+        assert_eq!(
+            e.error.message,
+            "Property 'owner.team' of table must match the pattern `[a-z-]+`, got 'Data-Eng'."
+        );
+        // The pattern must match the whole value
+        assert!(validate(&registry, "owner.team", "eng 1").is_err());
+        assert!(validate(&registry, "owner.team", "engineering").is_err());
+        let e = validate(&registry, "tier", "bronze").unwrap_err();
+        assert_eq!(
+            e.error.message,
+            "Property 'tier' of table must be one of 'gold', 'silver', got 'bronze'."
+        );
+        assert!(validate(&registry, "retention.days", "0").is_err());
+        assert!(validate(&registry, "retention.days", "1.5").is_err());
+        assert!(validate(&registry, "retention.days", "ten").is_err());
+
+        let e = registry
+            .validate(
+                PropertyEntityType::Namespace,
+                [
+                    (&"pii".to_string(), &"yes".to_string()),
+                    (&"tier".to_string(), &"bronze".to_string()),
+                ],
+            )
+            .unwrap_err();
+        assert_eq!(e.error.stack.len(), 2);
+    }
+}
@@ -0,0 +1,561 @@
+//! Limits of the resources a project can use.
+//!
+//! Quotas cap the number of warehouses and tables of a project, the depth of its namespaces
+//! and the number of snapshots per table. They are checked when warehouses, namespaces and
+//! tables are created and when tables are committed. Counts are read before the entity is
+//! created, so concurrent requests can exceed a quota by the number of requests in flight.
+//!
+//! Projects can set a warning threshold for the quotas of warehouses and tables. The
+//! [`quota_warning_worker`] raises a warning once the usage reaches the threshold and
+//! clears it once the usage dropped sufficiently below it. Raised and cleared warnings are
+//! logged with the `lakekeeper::quota_warning` target and published as cloud events.
+use std::{sync::LazyLock, time::Duration};
+
+use chrono::{DateTime, Utc};
+use iceberg::spec::TableMetadata;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    api::{ErrorModel, IcebergErrorResponse},
+    service::{
+        authn::Actor,
+        event_publisher::{CloudEventsPublisher, EventMetadata},
+        shared_cache::SharedCache,
+        Catalog, Result, Transaction,
+    },
+    ProjectId,
+};
+
+/// Quotas are read on every commit, so they are cached.
+static PROJECT_QUOTAS_CACHE: LazyLock<SharedCache<ProjectQuotas>> =
+    LazyLock::new(|| SharedCache::new("project-quotas"));
+
+const QUOTA_EXCEEDED_ERROR_TYPE: &str = "ProjectQuotaExceeded";
+/// Usage must drop this many percent of the limit below the warning threshold before a
+/// warning is cleared, so that usage around the threshold does not raise it repeatedly.
+const WARNING_HYSTERESIS_PERCENT: i64 = 5;
+const WARNING_CHECK_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ProjectQuotas {
+    /// Maximum number of warehouses of the project. `null` for no limit.
+    #[serde(default)]
+    pub max_warehouses: Option<i64>,
+    /// Maximum number of tables in all warehouses of the project, excluding
+    /// soft-deleted tables. `null` for no limit.
+    #[serde(default)]
+    pub max_tables: Option<i64>,
+    /// Maximum depth of namespaces. `null` for no limit.
+    #[serde(default)]
+    pub max_namespace_depth: Option<i64>,
+    /// Maximum number of snapshots of a single table. Commits that remove snapshots
+    /// are accepted even if the table exceeds the limit. `null` for no limit.
+    #[serde(default)]
+    pub max_snapshots_per_table: Option<i64>,
+    /// Usage of `max-warehouses` and `max-tables` in percent at which a warning is raised
+    /// before requests are rejected. `null` for no warnings.
+    #[serde(default)]
+    pub warning_threshold_percent: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ProjectQuotaUsage {
+    /// Number of warehouses of the project
+    pub warehouses: i64,
+    /// Number of tables in all warehouses of the project, excluding soft-deleted tables
+    pub tables: i64,
+}
+
+/// Quotas that warnings can be raised for.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    strum_macros::Display,
+    utoipa::ToSchema,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+#[cfg_attr(feature = "sqlx-postgres", derive(sqlx::Type))]
+#[cfg_attr(
+    feature = "sqlx-postgres",
+    sqlx(type_name = "project_quota_kind", rename_all = "kebab-case")
+)]
+pub enum ProjectQuotaKind {
+    Warehouses,
+    Tables,
+}
+
+impl ProjectQuotaKind {
+    fn limit(self, quotas: &ProjectQuotas) -> Option<i64> {
+        match self {
+            ProjectQuotaKind::Warehouses => quotas.max_warehouses,
+            ProjectQuotaKind::Tables => quotas.max_tables,
+        }
+    }
+
+    fn usage(self, usage: &ProjectQuotaUsage) -> i64 {
+        match self {
+            ProjectQuotaKind::Warehouses => usage.warehouses,
+            ProjectQuotaKind::Tables => usage.tables,
+        }
+    }
+}
+
+/// A quota whose usage reached the warning threshold of the project.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ProjectQuotaWarning {
+    pub quota: ProjectQuotaKind,
+    /// Usage when the warning was raised
+    pub usage: i64,
+    /// Limit when the warning was raised
+    pub limit: i64,
+    pub raised_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum QuotaWarningChange {
+    Raise {
+        quota: ProjectQuotaKind,
+        usage: i64,
+        limit: i64,
+    },
+    Clear {
+        quota: ProjectQuotaKind,
+        usage: i64,
+    },
+}
+
+/// Validate quotas before they are stored.
+///
+/// # Errors
+/// If a limit is negative or the namespace depth is zero.
+pub fn validate_project_quotas(quotas: &ProjectQuotas) -> Result<()> {
+    let ProjectQuotas {
+        max_warehouses,
+        max_tables,
+        max_namespace_depth,
+        max_snapshots_per_table,
+        warning_threshold_percent,
+    } = quotas;
+    let negative = [
+        ("max-warehouses", max_warehouses),
+        ("max-tables", max_tables),
+        ("max-snapshots-per-table", max_snapshots_per_table),
+    ]
+    .into_iter()
+    .find(|(_, limit)| limit.is_some_and(|l| l < 0));
+    if let Some((name, _)) = negative {
+        return Err(ErrorModel::bad_request(
+            format!("Quota `{name}` must not be negative."),
+            "InvalidProjectQuotas",
+            None,
+        )
+        .into());
+    }
+    if max_namespace_depth.is_some_and(|d| d < 1) {
+        return Err(ErrorModel::bad_request(
+            "Quota `max-namespace-depth` must be at least 1.",
+            "InvalidProjectQuotas",
+            None,
+        )
+        .into());
+    }
+    if warning_threshold_percent.is_some_and(|p| !(1..=100).contains(&p)) {
+        return Err(ErrorModel::bad_request(
+            "`warning-threshold-percent` must be between 1 and 100.",
+            "InvalidProjectQuotas",
+            None,
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Quotas of a project. Projects without quotas are not limited.
+pub(crate) async fn get_project_quotas<C: Catalog>(
+    project_id: &ProjectId,
+    catalog_state: C::State,
+) -> Result<ProjectQuotas> {
+    PROJECT_QUOTAS_CACHE
+        .try_get_with(project_id.to_string(), async {
+            let mut t = C::Transaction::begin_read(catalog_state).await?;
+            let quotas = C::get_project_quotas(project_id, t.transaction()).await?;
+            t.commit().await?;
+            Ok(quotas.unwrap_or_default())
+        })
+        .await
+}
+
+/// Remove the cached quotas of a project on all instances after they changed.
+pub(crate) async fn invalidate_project_quotas(project_id: &ProjectId) {
+    PROJECT_QUOTAS_CACHE
+        .invalidate(&project_id.to_string())
+        .await;
+}
+
+async fn get_usage<C: Catalog>(
+    project_id: &ProjectId,
+    catalog_state: C::State,
+) -> Result<ProjectQuotaUsage> {
+    let mut t = C::Transaction::begin_read(catalog_state).await?;
+    let usage = C::get_project_quota_usage(project_id, t.transaction()).await?;
+    t.commit().await?;
+    Ok(usage)
+}
+
+fn quota_exceeded(message: String) -> IcebergErrorResponse {
+    ErrorModel::forbidden(message, QUOTA_EXCEEDED_ERROR_TYPE, None).into()
+}
+
+/// Ensure that another warehouse can be created in the project.
+pub(crate) async fn require_warehouse_quota<C: Catalog>(
+    project_id: &ProjectId,
+    catalog_state: C::State,
+) -> Result<()> {
+    let quotas = get_project_quotas::<C>(project_id, catalog_state.clone()).await?;
+    let Some(max_warehouses) = quotas.max_warehouses else {
+        return Ok(());
+    };
+    let usage = get_usage::<C>(project_id, catalog_state).await?;
+    if usage.warehouses >= max_warehouses {
+        return Err(quota_exceeded(format!(
+            "Project {project_id} reached its quota of {max_warehouses} warehouses."
+        )));
+    }
+    Ok(())
+}
+
+/// Ensure that another table can be created in the project.
+pub(crate) async fn require_table_quota<C: Catalog>(
+    project_id: &ProjectId,
+    catalog_state: C::State,
+) -> Result<()> {
+    let quotas = get_project_quotas::<C>(project_id, catalog_state.clone()).await?;
+    let Some(max_tables) = quotas.max_tables else {
+        return Ok(());
+    };
+    let usage = get_usage::<C>(project_id, catalog_state).await?;
+    if usage.tables >= max_tables {
+        return Err(quota_exceeded(format!(
+            "Project {project_id} reached its quota of {max_tables} tables."
+        )));
+    }
+    Ok(())
+}
+
+/// Ensure that a namespace of `depth` levels can be created in the project.
+pub(crate) async fn require_namespace_depth_quota<C: Catalog>(
+    project_id: &ProjectId,
+    depth: usize,
+    catalog_state: C::State,
+) -> Result<()> {
+    let quotas = get_project_quotas::<C>(project_id, catalog_state).await?;
+    if let Some(max_depth) = quotas.max_namespace_depth {
+        if i64::try_from(depth).unwrap_or(i64::MAX) > max_depth {
+            return Err(quota_exceeded(format!(
+                "Namespaces of project {project_id} can have at most {max_depth} levels, got {depth}."
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Ensure that a commit does not add snapshots beyond the quota of the project.
+pub(crate) fn require_snapshot_quota(
+    quotas: &ProjectQuotas,
+    previous: &TableMetadata,
+    new: &TableMetadata,
+) -> Result<()> {
+    let Some(max_snapshots) = quotas.max_snapshots_per_table else {
+        return Ok(());
+    };
+    let previous_snapshots = previous.snapshots().len();
+    let new_snapshots = new.snapshots().len();
+    if new_snapshots > previous_snapshots
+        && i64::try_from(new_snapshots).unwrap_or(i64::MAX) > max_snapshots
+    {
+        return Err(quota_exceeded(format!(
+            "Table {} would have {new_snapshots} snapshots, which exceeds the quota of {max_snapshots} snapshots per table. Expire snapshots before committing new ones.",
+            new.uuid()
+        )));
+    }
+    Ok(())
+}
+
+/// Warnings to raise and to clear, given the warnings that are currently `active`.
+///
+/// A warning is raised once the usage reaches `warning-threshold-percent` of the limit, and
+/// cleared once it dropped another [`WARNING_HYSTERESIS_PERCENT`] of the limit - but at
+/// least one - below the threshold, or the limit or threshold were removed.
+pub(crate) fn evaluate_quota_warnings(
+    quotas: &ProjectQuotas,
+    usage: &ProjectQuotaUsage,
+    active: &[ProjectQuotaKind],
+) -> Vec<QuotaWarningChange> {
+    let mut changes = Vec::new();
+    for quota in [ProjectQuotaKind::Warehouses, ProjectQuotaKind::Tables] {
+        let current = quota.usage(usage);
+        let is_active = active.contains(&quota);
+        let (Some(limit), Some(percent)) = (
+            quota.limit(quotas).filter(|l| *l > 0),
+            quotas.warning_threshold_percent,
+        ) else {
+            if is_active {
+                changes.push(QuotaWarningChange::Clear {
+                    quota,
+                    usage: current,
+                });
+            }
+            continue;
+        };
+        let threshold = (limit.saturating_mul(percent) + 99) / 100;
+        let margin = (limit.saturating_mul(WARNING_HYSTERESIS_PERCENT) / 100).max(1);
+        if !is_active && current >= threshold {
+            changes.push(QuotaWarningChange::Raise {
+                quota,
+                usage: current,
+                limit,
+            });
+        } else if is_active && current < threshold - margin {
+            changes.push(QuotaWarningChange::Clear {
+                quota,
+                usage: current,
+            });
+        }
+    }
+    changes
+}
+
+/// Infinitely running worker that raises and clears quota warnings of all projects.
+/// Warnings are stored in the catalog, so each change is only published by one instance.
+pub async fn quota_warning_worker<C: Catalog>(
+    catalog_state: C::State,
+    publisher: CloudEventsPublisher,
+) {
+    loop {
+        if let Err(e) = check_quota_warnings::<C>(catalog_state.clone(), &publisher).await {
+            tracing::error!(?e, "Failed to check quota warnings: {}", e.error);
+        }
+        tokio::time::sleep(WARNING_CHECK_INTERVAL).await;
+    }
+}
+
+async fn check_quota_warnings<C: Catalog>(
+    catalog_state: C::State,
+    publisher: &CloudEventsPublisher,
+) -> Result<()> {
+    let project_ids = C::list_projects_with_quota_warnings(catalog_state.clone()).await?;
+    for project_id in project_ids {
+        let mut t = C::Transaction::begin_write(catalog_state.clone()).await?;
+        let Some(quotas) = C::get_project_quotas(&project_id, t.transaction()).await? else {
+            continue;
+        };
+        let usage = C::get_project_quota_usage(&project_id, t.transaction()).await?;
+        let active = C::list_project_quota_warnings(&project_id, t.transaction())
+            .await?
+            .into_iter()
+            .map(|w| w.quota)
+            .collect::<Vec<_>>();
+        let mut changed = Vec::new();
+        for change in evaluate_quota_warnings(&quotas, &usage, &active) {
+            let applied = match change {
+                QuotaWarningChange::Raise {
+                    quota,
+                    usage,
+                    limit,
+                } => {
+                    let warning = ProjectQuotaWarning {
+                        quota,
+                        usage,
+                        limit,
+                        raised_at: Utc::now(),
+                    };
+                    C::raise_project_quota_warning(&project_id, &warning, t.transaction()).await?
+                }
+                QuotaWarningChange::Clear { quota, .. } => {
+                    C::clear_project_quota_warning(&project_id, quota, t.transaction()).await?
+                }
+            };
+            if applied {
+                changed.push(change);
+            }
+        }
+        t.commit().await?;
+
+        for change in changed {
+            publish_quota_warning_change(publisher, &project_id, &quotas, change).await;
+        }
+    }
+    Ok(())
+}
+
+async fn publish_quota_warning_change(
+    publisher: &CloudEventsPublisher,
+    project_id: &ProjectId,
+    quotas: &ProjectQuotas,
+    change: QuotaWarningChange,
+) {
+    let (typ, quota, usage) = match change {
+        QuotaWarningChange::Raise { quota, usage, .. } => {
+            tracing::warn!(
+                target: "lakekeeper::quota_warning",
+                %project_id,
+                %quota,
+                usage,
+                limit = quota.limit(quotas),
+                "Project quota warning raised"
+            );
+            ("projectQuotaWarningRaised", quota, usage)
+        }
+        QuotaWarningChange::Clear { quota, usage } => {
+            tracing::info!(
+                target: "lakekeeper::quota_warning",
+                %project_id,
+                %quota,
+                usage,
+                limit = quota.limit(quotas),
+                "Project quota warning cleared"
+            );
+            ("projectQuotaWarningCleared", quota, usage)
+        }
+    };
+    let id = Uuid::now_v7();
+    let data = serde_json::json!({
+        "project-id": project_id,
+        "quota": quota,
+        "usage": usage,
+        "limit": quota.limit(quotas),
+        "warning-threshold-percent": quotas.warning_threshold_percent,
+    });
+    let metadata = EventMetadata {
+        tabular_id: None,
+        warehouse_id: None,
+        name: project_id.to_string(),
+        namespace: String::new(),
+        prefix: String::new(),
+        num_events: 1,
+        sequence_number: 0,
+        trace_id: id,
+        actor: serde_json::to_string(&Actor::Anonymous).unwrap_or_default(),
+        impersonated_by: None,
+        authorization: None,
+    };
+    if let Err(e) = publisher.publish(id, typ, data, metadata).await {
+        tracing::warn!("Failed to publish `{typ}` event: {e}");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_validate_project_quotas() {
+        validate_project_quotas(&ProjectQuotas::default()).unwrap();
+        validate_project_quotas(&ProjectQuotas {
+            max_warehouses: Some(0),
+            max_tables: Some(100),
+            max_namespace_depth: Some(1),
+            max_snapshots_per_table: Some(1000),
+            warning_threshold_percent: Some(80),
+        })
+        .unwrap();
+
+        let e = validate_project_quotas(&ProjectQuotas {
+            max_tables: Some(-1),
+            ..Default::default()
+        })
+        .unwrap_err();
+        assert_eq!(e.error.r#type, "InvalidProjectQuotas");
+
+        let e = validate_project_quotas(&ProjectQuotas {
+            max_namespace_depth: Some(0),
+            ..Default::default()
+        })
+        .unwrap_err();
+        assert_eq!(e.error.r#type, "InvalidProjectQuotas");
+
+        let e = validate_project_quotas(&ProjectQuotas {
+            warning_threshold_percent: Some(0),
+            ..Default::default()
+        })
+        .unwrap_err();
+        assert_eq!(e.error.r#type, "InvalidProjectQuotas");
+    }
+
+    #[test]
+    fn test_evaluate_quota_warnings_with_hysteresis() {
+        let quotas = ProjectQuotas {
+            max_tables: Some(100),
+            warning_threshold_percent: Some(80),
+            ..Default::default()
+        };
+        let usage = |tables| ProjectQuotaUsage {
+            warehouses: 1,
+            tables,
+        };
+        assert!(evaluate_quota_warnings(&quotas, &usage(79), &[]).is_empty());
+        assert_eq!(
+            evaluate_quota_warnings(&quotas, &usage(80), &[]),
+            vec![QuotaWarningChange::Raise {
+                quota: ProjectQuotaKind::Tables,
+                usage: 80,
+                limit: 100
+            }]
+        );
+        // Active warnings are neither raised again nor cleared right below the threshold
+        let active = [ProjectQuotaKind::Tables];
+        assert!(evaluate_quota_warnings(&quotas, &usage(90), &active).is_empty());
+        assert!(evaluate_quota_warnings(&quotas, &usage(75), &active).is_empty());
+        assert_eq!(
+            evaluate_quota_warnings(&quotas, &usage(74), &active),
+            vec![QuotaWarningChange::Clear {
+                quota: ProjectQuotaKind::Tables,
+                usage: 74
+            }]
+        );
+    }
+
+    #[test]
+    fn test_evaluate_quota_warnings_without_threshold() {
+        let quotas = ProjectQuotas {
+            max_warehouses: Some(2),
+            ..Default::default()
+        };
+        let usage = ProjectQuotaUsage {
+            warehouses: 2,
+            tables: 0,
+        };
+        assert!(evaluate_quota_warnings(&quotas, &usage, &[]).is_empty());
+        // Warnings are cleared once the threshold is removed
+        assert_eq!(
+            evaluate_quota_warnings(&quotas, &usage, &[ProjectQuotaKind::Warehouses]),
+            vec![QuotaWarningChange::Clear {
+                quota: ProjectQuotaKind::Warehouses,
+                usage: 2
+            }]
+        );
+    }
+
+    #[test]
+    fn test_project_quotas_serde() {
+        let quotas: ProjectQuotas =
+            serde_json::from_value(serde_json::json!({"max-tables": 10})).unwrap();
+        assert_eq!(
+            quotas,
+            ProjectQuotas {
+                max_tables: Some(10),
+                ..Default::default()
+            }
+        );
+    }
+}
@@ -0,0 +1,414 @@
+//! Token bucket rate limiting of requests per principal, project or warehouse.
+//!
+//! Rules are configured via `LAKEKEEPER__RATE_LIMITS`. Buckets are kept in memory of each
+//! instance or, if Redis is configured for the shared cache, in Redis, so that all instances
+//! share the same quota.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    str::FromStr,
+    sync::{Arc, LazyLock, Mutex},
+    time::{Duration, Instant},
+};
+
+use axum::{
+    extract::{Path, Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use http::{header, HeaderValue, StatusCode};
+use iceberg_ext::catalog::rest::{ErrorModel, IcebergErrorResponse};
+
+use crate::{
+    api::endpoints::Endpoint,
+    request_metadata::RequestMetadata,
+    service::{endpoint_policy::warehouse_id_from_path, Catalog, Transaction},
+    ProjectId, WarehouseId, CONFIG,
+};
+
+/// Buckets of the current instance. Idle buckets are full and can be dropped.
+static LOCAL_BUCKETS: LazyLock<moka::sync::Cache<String, Arc<Mutex<TokenBucket>>>> =
+    LazyLock::new(|| {
+        moka::sync::Cache::builder()
+            .max_capacity(100_000)
+            .time_to_idle(Duration::from_secs(3600))
+            .build()
+    });
+
+/// Projects of warehouses are cached, as warehouses never move between projects.
+static WAREHOUSE_PROJECT_CACHE: LazyLock<moka::future::Cache<WarehouseId, Option<ProjectId>>> =
+    LazyLock::new(|| {
+        moka::future::Cache::builder()
+            .max_capacity(10_000)
+            .time_to_live(Duration::from_secs(3600))
+            .build()
+    });
+
+/// Entity whose requests share a quota.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum_macros::Display, strum_macros::EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub enum RateLimitScope {
+    /// The authenticated user. Requests without authentication are not limited.
+    Principal,
+    /// The project of the warehouse, or the project of the request for other endpoints.
+    Project,
+    /// The warehouse of the request. Requests without warehouse are not limited.
+    Warehouse,
+}
+
+/// Class of endpoints a rule can be restricted to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum_macros::Display, strum_macros::EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub enum RouteClass {
+    /// `GET` and `HEAD` requests of the Iceberg REST API.
+    Read,
+    /// All other requests of the Iceberg REST API, except commits.
+    Write,
+    /// Table and view commits and multi-table transactions.
+    Commit,
+    /// S3 remote signing.
+    Sign,
+    /// Management and permission API.
+    Management,
+}
+
+impl RouteClass {
+    #[must_use]
+    pub fn of_endpoint(endpoint: Endpoint) -> Self {
+        use crate::api::endpoints::CatalogV1Endpoint;
+
+        match endpoint {
+            Endpoint::CatalogV1(
+                CatalogV1Endpoint::UpdateTable
+                | CatalogV1Endpoint::CommitTransaction
+                | CatalogV1Endpoint::ReplaceView,
+            ) => Self::Commit,
+            Endpoint::CatalogV1(_) => {
+                if matches!(endpoint.method(), http::Method::GET | http::Method::HEAD) {
+                    Self::Read
+                } else {
+                    Self::Write
+                }
+            }
+            Endpoint::Sign(_) => Self::Sign,
+            Endpoint::ManagementV1(_) | Endpoint::PermissionV1(_) => Self::Management,
+        }
+    }
+}
+
+/// A quota of `requests` per `period` for each entity of the scope, i.e. `principal.commit=5/s`.
+/// Unused requests are not carried over to later periods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitRule {
+    pub scope: RateLimitScope,
+    /// If set, the rule only applies to requests of this class.
+    pub route_class: Option<RouteClass>,
+    pub requests: u32,
+    pub period: Duration,
+}
+
+impl FromStr for RateLimitRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "Invalid rate limit `{s}`. Expected format: `<scope>[.<route-class>]=<requests>/<s|m|h>`"
+            )
+        };
+        let (target, quota) = s.split_once('=').ok_or_else(invalid)?;
+        let (scope, route_class) = match target.trim().split_once('.') {
+            Some((scope, route_class)) => (
+                scope,
+                Some(RouteClass::from_str(route_class).map_err(|_| {
+                    format!("Unknown route class `{route_class}` in rate limit `{s}`")
+                })?),
+            ),
+            None => (target.trim(), None),
+        };
+        let scope = RateLimitScope::from_str(scope)
+            .map_err(|_| format!("Unknown scope `{scope}` in rate limit `{s}`"))?;
+        let (requests, period) = quota.trim().split_once('/').ok_or_else(invalid)?;
+        let requests = requests
+            .parse::<u32>()
+            .ok()
+            .filter(|r| *r > 0)
+            .ok_or_else(invalid)?;
+        let period = match period {
+            "s" => Duration::from_secs(1),
+            "m" => Duration::from_secs(60),
+            "h" => Duration::from_secs(3600),
+            _ => return Err(invalid()),
+        };
+        Ok(Self {
+            scope,
+            route_class,
+            requests,
+            period,
+        })
+    }
+}
+
+impl Display for RateLimitRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.scope)?;
+        if let Some(route_class) = self.route_class {
+            write!(f, ".{route_class}")?;
+        }
+        let period = match self.period.as_secs() {
+            1 => "s",
+            60 => "m",
+            _ => "h",
+        };
+        write!(f, "={}/{period}", self.requests)
+    }
+}
+
+impl RateLimitRule {
+    fn tokens_per_second(&self) -> f64 {
+        f64::from(self.requests) / self.period.as_secs_f64()
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    fn full(rule: &RateLimitRule, now: Instant) -> Self {
+        Self {
+            tokens: f64::from(rule.requests),
+            updated: now,
+        }
+    }
+
+    /// Takes a token and returns `None`, or returns the time until a token is available.
+    fn try_acquire(&mut self, rule: &RateLimitRule, now: Instant) -> Option<Duration> {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens =
+            (self.tokens + elapsed * rule.tokens_per_second()).min(f64::from(rule.requests));
+        self.updated = now;
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            None
+        } else {
+            Some(Duration::from_secs_f64(
+                (1.0 - self.tokens) / rule.tokens_per_second(),
+            ))
+        }
+    }
+}
+
+/// Middleware rejecting requests that exceed a configured rate limit with
+/// `429 Too Many Requests` and a `Retry-After` header.
+pub(crate) async fn rate_limit_middleware_fn<C: Catalog>(
+    State(catalog_state): State<C::State>,
+    Path(path_params): Path<HashMap<String, String>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if CONFIG.rate_limits.is_empty() {
+        return next.run(request).await;
+    }
+    let Some(metadata) = request.extensions().get::<RequestMetadata>().cloned() else {
+        return next.run(request).await;
+    };
+
+    let route_class = metadata
+        .matched_path()
+        .and_then(|p| Endpoint::from_method_and_matched_path(metadata.request_method(), p))
+        .map(RouteClass::of_endpoint);
+    let warehouse_id = warehouse_id_from_path(&path_params);
+    let principal = metadata.user_id().map(ToString::to_string);
+    let mut project_id = None;
+
+    for rule in &CONFIG.rate_limits {
+        if rule.route_class.is_some_and(|c| Some(c) != route_class) {
+            continue;
+        }
+        let subject = match rule.scope {
+            RateLimitScope::Principal => principal.clone(),
+            RateLimitScope::Warehouse => warehouse_id.map(|w| w.to_string()),
+            RateLimitScope::Project => {
+                if project_id.is_none() {
+                    project_id = Some(
+                        project_of_request::<C>(&metadata, warehouse_id, catalog_state.clone())
+                            .await,
+                    );
+                }
+                project_id.clone().flatten().map(|p| p.to_string())
+            }
+        };
+        let Some(subject) = subject else {
+            continue;
+        };
+        if let Some(retry_after) = acquire(rule, &subject).await {
+            return too_many_requests(rule, retry_after);
+        }
+    }
+
+    next.run(request).await
+}
+
+async fn acquire(rule: &RateLimitRule, subject: &str) -> Option<Duration> {
+    let key = format!("{rule}:{subject}");
+    #[cfg(feature = "redis")]
+    if let Some(connection) = crate::service::shared_cache::redis_connection() {
+        match acquire_redis(connection, rule, &key).await {
+            Ok(retry_after) => return retry_after,
+            Err(e) => {
+                tracing::warn!("Failed to apply rate limit in Redis, using local bucket: {e}")
+            }
+        }
+    }
+
+    let now = Instant::now();
+    let bucket = LOCAL_BUCKETS.get_with(key, || Arc::new(Mutex::new(TokenBucket::full(rule, now))));
+    let mut bucket = bucket.lock().expect("Rate limit bucket poisoned");
+    bucket.try_acquire(rule, now)
+}
+
+/// Same algorithm as [`TokenBucket::try_acquire`], using the clock of Redis.
+/// Returns the milliseconds until a token is available, or 0 if a token was taken.
+#[cfg(feature = "redis")]
+const TOKEN_BUCKET_SCRIPT: &str = r"
+local capacity = tonumber(ARGV[1])
+local rate = tonumber(ARGV[2])
+local time = redis.call('TIME')
+local now = tonumber(time[1]) * 1000 + math.floor(tonumber(time[2]) / 1000)
+local bucket = redis.call('HMGET', KEYS[1], 'tokens', 'updated')
+local tokens = tonumber(bucket[1]) or capacity
+local updated = tonumber(bucket[2]) or now
+tokens = math.min(capacity, tokens + math.max(0, now - updated) * rate)
+local retry_after = 0
+if tokens >= 1 then
+  tokens = tokens - 1
+else
+  retry_after = math.ceil((1 - tokens) / rate)
+end
+redis.call('HSET', KEYS[1], 'tokens', tostring(tokens), 'updated', now)
+redis.call('PEXPIRE', KEYS[1], math.ceil(capacity / rate) + 1000)
+return retry_after
+";
+
+#[cfg(feature = "redis")]
+async fn acquire_redis(
+    mut connection: redis::aio::ConnectionManager,
+    rule: &RateLimitRule,
+    key: &str,
+) -> redis::RedisResult<Option<Duration>> {
+    let tokens_per_ms = rule.tokens_per_second() / 1000.0;
+    let retry_after_ms: u64 = redis::cmd("EVAL")
+        .arg(TOKEN_BUCKET_SCRIPT)
+        .arg(1)
+        .arg(format!("lakekeeper:rate-limit:{key}"))
+        .arg(rule.requests)
+        .arg(tokens_per_ms)
+        .query_async(&mut connection)
+        .await?;
+    Ok((retry_after_ms > 0).then(|| Duration::from_millis(retry_after_ms)))
+}
+
+async fn project_of_request<C: Catalog>(
+    metadata: &RequestMetadata,
+    warehouse_id: Option<WarehouseId>,
+    catalog_state: C::State,
+) -> Option<ProjectId> {
+    let Some(warehouse_id) = warehouse_id else {
+        return metadata.preferred_project_id();
+    };
+    WAREHOUSE_PROJECT_CACHE
+        .try_get_with(warehouse_id, async {
+            let mut transaction = C::Transaction::begin_read(catalog_state).await?;
+            let warehouse = C::get_warehouse(warehouse_id, transaction.transaction()).await?;
+            transaction.commit().await?;
+            Ok::<_, IcebergErrorResponse>(warehouse.map(|w| w.project_id))
+        })
+        .await
+        .inspect_err(|e| {
+            tracing::warn!(error=?e, "Failed to load project of warehouse {warehouse_id}");
+        })
+        .ok()
+        .flatten()
+}
+
+fn too_many_requests(rule: &RateLimitRule, retry_after: Duration) -> Response {
+    let retry_after_secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+    let mut response = IcebergErrorResponse::from(ErrorModel::new(
+        format!("Rate limit `{rule}` exceeded. Retry after {retry_after_secs} seconds."),
+        "RateLimitExceeded",
+        StatusCode::TOO_MANY_REQUESTS.as_u16(),
+        None,
+    ))
+    .into_response();
+    response
+        .headers_mut()
+        .insert(header::RETRY_AFTER, HeaderValue::from(retry_after_secs));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rule() {
+        let rule = RateLimitRule::from_str("principal.commit=5/s").unwrap();
+        assert_eq!(
+            rule,
+            RateLimitRule {
+                scope: RateLimitScope::Principal,
+                route_class: Some(RouteClass::Commit),
+                requests: 5,
+                period: Duration::from_secs(1),
+            }
+        );
+        assert_eq!(rule.to_string(), "principal.commit=5/s");
+
+        let rule = RateLimitRule::from_str("warehouse=6000/m").unwrap();
+        assert_eq!(rule.route_class, None);
+        assert_eq!(rule.period, Duration::from_secs(60));
+        assert_eq!(rule.to_string(), "warehouse=6000/m");
+
+        for invalid in [
+            "principal",
+            "principal=5",
+            "principal=0/s",
+            "principal=5/d",
+            "user=5/s",
+            "principal.delete=5/s",
+        ] {
+            assert!(RateLimitRule::from_str(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_token_bucket() {
+        let rule = RateLimitRule::from_str("principal=2/s").unwrap();
+        let start = Instant::now();
+        let mut bucket = TokenBucket::full(&rule, start);
+        assert!(bucket.try_acquire(&rule, start).is_none());
+        assert!(bucket.try_acquire(&rule, start).is_none());
+        let retry_after = bucket.try_acquire(&rule, start).unwrap();
+        assert_eq!(retry_after, Duration::from_millis(500));
+
+        // Tokens are refilled over time, but never exceed the quota
+        let later = start + Duration::from_millis(500);
+        assert!(bucket.try_acquire(&rule, later).is_none());
+        assert!(bucket.try_acquire(&rule, later).is_some());
+        let much_later = start + Duration::from_secs(60);
+        assert!(bucket.try_acquire(&rule, much_later).is_none());
+        assert!(bucket.try_acquire(&rule, much_later).is_none());
+        assert!(bucket.try_acquire(&rule, much_later).is_some());
+    }
+
+    #[test]
+    fn test_too_many_requests_response() {
+        let rule = RateLimitRule::from_str("project=1/m").unwrap();
+        let response = too_many_requests(&rule, Duration::from_millis(1500));
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers().get(header::RETRY_AFTER).unwrap(), "2");
+    }
+}
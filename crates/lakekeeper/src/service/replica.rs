@@ -0,0 +1,240 @@
+//! Read replicas of another Lakekeeper.
+//!
+//! A replica mirrors warehouses of a primary Lakekeeper, so that the catalog API can be
+//! served close to its clients, for example in another region. It periodically pulls the
+//! current state of each mirrored warehouse from the primary - the same state catalog
+//! snapshots contain - and applies the differences to its own catalog. Namespaces, tables
+//! and views keep their ids, and tables and views point to the metadata files written by the
+//! primary, which are read with the storage profile and credential of the replica's warehouse.
+//!
+//! Replicas reject all endpoints of the Iceberg REST API that change the catalog.
+//! Permissions are managed by the authorizer of the replica: Mirrored entities are created
+//! below the mirrored warehouse and owned by `replica_owner`.
+use std::{fmt::Display, str::FromStr, time::Duration};
+
+use axum_prometheus::metrics;
+use iceberg_ext::catalog::rest::ErrorModel;
+
+use crate::{
+    api::management::v1::replication::ReplicationStateResponse,
+    request_metadata::RequestMetadata,
+    service::{
+        authz::Authorizer,
+        mirror::{apply_catalog_state, mirror_owner, MirrorOptions},
+        task_queue::catalog_snapshot_export_queue::CatalogSnapshotState,
+        Catalog, Result, SecretStore,
+    },
+    WarehouseId, CONFIG,
+};
+
+/// Unix timestamp of the last successful sync, labelled by the `warehouse` of the replica.
+const REPLICA_LAST_SYNC: &str = "lakekeeper_replica_last_sync_timestamp_seconds";
+/// Failed syncs, labelled by the `warehouse` of the replica.
+const REPLICA_SYNC_ERRORS: &str = "lakekeeper_replica_sync_errors_total";
+
+const PRIMARY_REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// A warehouse of the replica and the warehouse of the primary it mirrors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReplicaWarehouse {
+    pub replica: WarehouseId,
+    pub primary: WarehouseId,
+}
+
+impl FromStr for ReplicaWarehouse {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (replica, primary) = s.split_once('=').ok_or_else(|| {
+            format!("Invalid replica warehouse '{s}'. Expected '<replica-warehouse-id>=<primary-warehouse-id>'.")
+        })?;
+        let parse = |id: &str| {
+            WarehouseId::from_str(id.trim())
+                .map_err(|_| format!("Invalid warehouse id '{id}' in replica warehouse '{s}'."))
+        };
+        Ok(Self {
+            replica: parse(replica)?,
+            primary: parse(primary)?,
+        })
+    }
+}
+
+impl Display for ReplicaWarehouse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={}", self.replica, self.primary)
+    }
+}
+
+/// Infinitely running worker that keeps the mirrored warehouses in sync with the primary.
+/// Returns immediately if this instance is not a read replica.
+pub async fn replica_sync_worker<C: Catalog, A: Authorizer, S: SecretStore>(
+    catalog_state: C::State,
+    authorizer: A,
+    secret_store: S,
+) {
+    let Some(primary_url) = CONFIG.replica_primary_url.clone() else {
+        return;
+    };
+    let Some(owner) = mirror_owner(CONFIG.replica_owner.as_ref()) else {
+        tracing::error!(
+                "Replica warehouses are not synced: `LAKEKEEPER__REPLICA_OWNER` must be set unless the `allowall` authorizer is used."
+        );
+        return;
+    };
+    if CONFIG.replica_warehouses.is_empty() {
+        tracing::warn!("This instance is a read replica of {primary_url}, but no replica warehouses are configured.");
+        return;
+    }
+    metrics::describe_gauge!(
+        REPLICA_LAST_SYNC,
+        metrics::Unit::Seconds,
+        "Time of the last successful sync of the warehouse from the primary"
+    );
+    metrics::describe_counter!(REPLICA_SYNC_ERRORS, "Failed syncs of the warehouse");
+
+    let client = reqwest::Client::builder()
+        .timeout(PRIMARY_REQUEST_TIMEOUT)
+        .build()
+        .expect("Failed to create HTTP client");
+    let interval = Duration::from_secs(CONFIG.replica_sync_interval_seconds.max(1));
+    loop {
+        for warehouse in &CONFIG.replica_warehouses {
+            let labels = [("warehouse", warehouse.replica.to_string())];
+            match sync_warehouse::<C, A, S>(
+                &client,
+                &primary_url,
+                *warehouse,
+                catalog_state.clone(),
+                &authorizer,
+                &secret_store,
+                &owner,
+            )
+            .await
+            {
+                Ok(()) => {
+                    #[allow(clippy::cast_precision_loss)]
+                    metrics::gauge!(REPLICA_LAST_SYNC, &labels)
+                        .set(chrono::Utc::now().timestamp() as f64);
+                }
+                Err(e) => {
+                    metrics::counter!(REPLICA_SYNC_ERRORS, &labels).increment(1);
+                    tracing::error!(
+                        ?e,
+                        "Failed to sync replica warehouse {} from primary warehouse {}: {}",
+                        warehouse.replica,
+                        warehouse.primary,
+                        e.error
+                    );
+                }
+            }
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
+
+async fn fetch_primary_state(
+    client: &reqwest::Client,
+    primary_url: &url::Url,
+    warehouse_id: WarehouseId,
+) -> Result<CatalogSnapshotState> {
+    let url = format!(
+        "{}/management/v1/warehouse/{warehouse_id}/replication-state",
+        primary_url.as_str().trim_end_matches('/')
+    );
+    let token = match &CONFIG.replica_primary_token_file {
+        Some(path) => Some(
+            tokio::fs::read_to_string(path)
+                .await
+                .map_err(|e| {
+                    ErrorModel::internal(
+                        format!("Failed to read token file {}", path.display()),
+                        "ReplicaTokenFileError",
+                        Some(Box::new(e)),
+                    )
+                })?
+                .trim()
+                .to_string(),
+        ),
+        None => CONFIG.replica_primary_token.clone(),
+    };
+    let mut request = client.get(&url);
+    if let Some(token) = token {
+        request = request.bearer_auth(token);
+    }
+    let response = request
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|e| {
+            ErrorModel::internal(
+                format!("Failed to load the state of warehouse {warehouse_id} from the primary"),
+                "ReplicaPrimaryUnavailable",
+                Some(Box::new(e)),
+            )
+        })?;
+    let response: ReplicationStateResponse = response.json().await.map_err(|e| {
+        ErrorModel::internal(
+            format!("Failed to parse the state of warehouse {warehouse_id} from the primary"),
+            "ReplicaPrimaryResponseInvalid",
+            Some(Box::new(e)),
+        )
+    })?;
+    Ok(response.state)
+}
+
+/// Apply the changes of the primary to a warehouse of the replica in a single transaction.
+async fn sync_warehouse<C: Catalog, A: Authorizer, S: SecretStore>(
+    client: &reqwest::Client,
+    primary_url: &url::Url,
+    warehouse: ReplicaWarehouse,
+    catalog_state: C::State,
+    authorizer: &A,
+    secret_store: &S,
+    owner: &RequestMetadata,
+) -> Result<()> {
+    let primary = fetch_primary_state(client, primary_url, warehouse.primary).await?;
+    let plan = apply_catalog_state::<C, A, S>(
+        warehouse.replica,
+        &primary,
+        catalog_state,
+        authorizer,
+        secret_store,
+        owner,
+        MirrorOptions::MIRROR,
+    )
+    .await?;
+    if !plan.is_empty() {
+        tracing::info!(
+            created_namespaces = plan.create_namespaces.len(),
+            updated_namespaces = plan.update_namespaces.len(),
+            dropped_namespaces = plan.drop_namespaces.len(),
+            created_tabulars = plan.create_tabulars.len(),
+            updated_tabulars = plan.update_tabulars.len(),
+            dropped_tabulars = plan.drop_tabulars.len(),
+            "Synced replica warehouse {} from primary warehouse {}",
+            warehouse.replica,
+            warehouse.primary
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[test]
+    fn test_replica_warehouse_from_str() {
+        let id_1 = Uuid::now_v7();
+        let id_2 = Uuid::now_v7();
+        let warehouse = ReplicaWarehouse::from_str(&format!("{id_1} = {id_2}")).unwrap();
+        assert_eq!(warehouse.replica, WarehouseId::from(id_1));
+        assert_eq!(warehouse.primary, WarehouseId::from(id_2));
+        assert_eq!(warehouse.to_string(), format!("{id_1}={id_2}"));
+
+        assert!(ReplicaWarehouse::from_str(&id_1.to_string()).is_err());
+        assert!(ReplicaWarehouse::from_str(&format!("{id_1}=not-a-uuid")).is_err());
+    }
+}
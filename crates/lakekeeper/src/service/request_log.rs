@@ -0,0 +1,336 @@
+//! Structured log line per request.
+//!
+//! Request logs are written as events of the `lakekeeper::request_log` target and contain the
+//! principal, the route template, the ids of the addressed entities, the response status and
+//! the latency. Settings are initialized from the configuration and can be changed at runtime
+//! via the management API.
+use std::{
+    collections::HashMap,
+    sync::{Arc, LazyLock},
+    time::{Duration, Instant},
+};
+
+use axum::{
+    body::Body,
+    extract::{Path, Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use http::header;
+use iceberg_ext::catalog::rest::{ErrorModel, IcebergErrorResponse};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    request_metadata::RequestMetadata,
+    service::{authn::Actor, Catalog},
+    CONFIG,
+};
+
+/// Request bodies larger than this are not included in request logs.
+const MAX_LOGGED_BODY_SIZE: usize = 64 * 1024;
+const REDACTED: &str = "REDACTED";
+
+/// Settings are cached for the middleware, so that requests don't require an
+/// additional database query. Changes made on other instances apply after the TTL.
+static SETTINGS_CACHE: LazyLock<moka::future::Cache<(), Arc<RequestLogSettings>>> =
+    LazyLock::new(|| {
+        moka::future::Cache::builder()
+            .max_capacity(1)
+            .time_to_live(Duration::from_secs(30))
+            .build()
+    });
+
+/// Server-wide settings of request logging.
+/// Omitted fields of requests default to the values of the configuration.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case", default = "RequestLogSettings::from_config")]
+pub struct RequestLogSettings {
+    /// Log a line for every request
+    pub enabled: bool,
+    /// Include JSON request bodies of at most 64 KiB
+    pub include_request_body: bool,
+    /// Redact tokens, secrets, passwords and credentials in query parameters and request bodies
+    pub redact_credentials: bool,
+    /// Keys of properties whose values are redacted in query parameters and request bodies.
+    /// A trailing `*` matches all keys starting with the prefix.
+    pub redacted_properties: Vec<String>,
+}
+
+impl RequestLogSettings {
+    /// Settings that apply until they are changed via the management API.
+    #[must_use]
+    pub fn from_config() -> Self {
+        Self {
+            enabled: CONFIG.request_log_enabled,
+            include_request_body: CONFIG.request_log_include_request_body,
+            redact_credentials: CONFIG.request_log_redact_credentials,
+            redacted_properties: CONFIG.request_log_redacted_properties.clone(),
+        }
+    }
+
+    fn is_redacted(&self, key: &str) -> bool {
+        (self.redact_credentials && is_credential_key(key))
+            || self
+                .redacted_properties
+                .iter()
+                .any(|pattern| match pattern.strip_suffix('*') {
+                    Some(prefix) => key.starts_with(prefix),
+                    None => key == pattern,
+                })
+    }
+
+    fn redact_query(&self, query: &str) -> String {
+        let mut serializer = url::form_urlencoded::Serializer::new(String::new());
+        for (key, value) in url::form_urlencoded::parse(query.as_bytes()) {
+            if self.is_redacted(&key) {
+                serializer.append_pair(&key, REDACTED);
+            } else {
+                serializer.append_pair(&key, &value);
+            }
+        }
+        serializer.finish()
+    }
+
+    fn redact_json(&self, value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::Object(map) => {
+                for (key, value) in map.iter_mut() {
+                    if self.is_redacted(key) {
+                        *value = serde_json::Value::String(REDACTED.to_string());
+                    } else {
+                        self.redact_json(value);
+                    }
+                }
+            }
+            serde_json::Value::Array(values) => {
+                values.iter_mut().for_each(|value| self.redact_json(value));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns true if the key of a field or query parameter likely holds a credential.
+/// Page tokens are not considered credentials.
+fn is_credential_key(key: &str) -> bool {
+    let key = key.to_ascii_lowercase().replace(['-', '_', '.'], "");
+    (key.ends_with("token") && !key.ends_with("pagetoken"))
+        || ["secret", "password", "credential", "privatekey"]
+            .iter()
+            .any(|s| key.contains(s))
+}
+
+/// Current settings, falling back to the configuration if they cannot be loaded.
+pub(crate) async fn request_log_settings<C: Catalog>(
+    catalog_state: C::State,
+) -> Arc<RequestLogSettings> {
+    let settings = SETTINGS_CACHE
+        .try_get_with((), async {
+            C::get_request_log_settings(catalog_state)
+                .await
+                .map(|s| Arc::new(s.unwrap_or_else(RequestLogSettings::from_config)))
+        })
+        .await;
+    settings.unwrap_or_else(|e| {
+        tracing::warn!(error=?e, "Failed to load request log settings");
+        Arc::new(RequestLogSettings::from_config())
+    })
+}
+
+/// Remove the cached settings after they were changed.
+pub(crate) fn invalidate_request_log_settings() {
+    SETTINGS_CACHE.invalidate_all();
+}
+
+/// Middleware logging one structured line per request if request logging is enabled.
+pub(crate) async fn request_log_middleware_fn<C: Catalog>(
+    State(catalog_state): State<C::State>,
+    Path(path_params): Path<HashMap<String, String>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let settings = request_log_settings::<C>(catalog_state).await;
+    if !settings.enabled {
+        return next.run(request).await;
+    }
+
+    let metadata = request.extensions().get::<RequestMetadata>().cloned();
+    let method = request.method().clone();
+    let query = request.uri().query().map(|q| settings.redact_query(q));
+    let (request, body) = if settings.include_request_body {
+        match read_json_body(request, &settings).await {
+            Ok(request_and_body) => request_and_body,
+            Err(response) => return response,
+        }
+    } else {
+        (request, None)
+    };
+
+    let start = Instant::now();
+    let response = next.run(request).await;
+    let latency_ms = u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX);
+
+    let entity_ids = serde_json::to_string(&path_params).unwrap_or_default();
+    let request_id = metadata.as_ref().map(|m| m.request_id().to_string());
+    let principal = metadata
+        .as_ref()
+        .and_then(RequestMetadata::user_id)
+        .map(ToString::to_string);
+    let assumed_role = metadata.as_ref().and_then(|m| match m.actor() {
+        Actor::Role { assumed_role, .. } => Some(assumed_role.to_string()),
+        Actor::Principal(_) | Actor::Anonymous => None,
+    });
+    let impersonated_by = metadata
+        .as_ref()
+        .and_then(RequestMetadata::impersonated_by)
+        .map(ToString::to_string);
+    tracing::info!(
+        target: "lakekeeper::request_log",
+        request_id = request_id.as_deref(),
+        principal = principal.as_deref(),
+        assumed_role = assumed_role.as_deref(),
+        impersonated_by = impersonated_by.as_deref(),
+        method = %method,
+        route = metadata.as_ref().and_then(RequestMetadata::matched_path),
+        entity_ids = entity_ids.as_str(),
+        query = query.as_deref(),
+        request_body = body.as_deref(),
+        status = response.status().as_u16(),
+        latency_ms,
+        "Request"
+    );
+
+    response
+}
+
+/// Buffers JSON bodies of at most [`MAX_LOGGED_BODY_SIZE`] bytes and returns them redacted.
+/// Other bodies are passed on without being read.
+async fn read_json_body(
+    request: Request,
+    settings: &RequestLogSettings,
+) -> std::result::Result<(Request, Option<String>), Response> {
+    let is_json = request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/json"));
+    let is_small = request
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<usize>().ok())
+        .is_some_and(|length| length <= MAX_LOGGED_BODY_SIZE);
+    if !is_json || !is_small {
+        return Ok((request, None));
+    }
+
+    let (parts, body) = request.into_parts();
+    let bytes = axum::body::to_bytes(body, MAX_LOGGED_BODY_SIZE)
+        .await
+        .map_err(|e| {
+            IcebergErrorResponse::from(ErrorModel::bad_request(
+                "Failed to read request body",
+                "InvalidRequestBody",
+                Some(Box::new(e)),
+            ))
+            .into_response()
+        })?;
+    let logged = serde_json::from_slice::<serde_json::Value>(&bytes)
+        .ok()
+        .map(|mut value| {
+            settings.redact_json(&mut value);
+            value.to_string()
+        });
+    Ok((Request::from_parts(parts, Body::from(bytes)), logged))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn settings() -> RequestLogSettings {
+        RequestLogSettings {
+            enabled: true,
+            include_request_body: true,
+            redact_credentials: true,
+            redacted_properties: vec![
+                "s3.secret-access-key".to_string(),
+                "adls.sas-token.*".to_string(),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_credential_keys() {
+        for key in [
+            "access_token",
+            "refresh-token",
+            "client_secret",
+            "aws-secret-access-key",
+            "password",
+            "storage-credential",
+        ] {
+            assert!(is_credential_key(key), "{key}");
+        }
+        for key in ["pageToken", "next-page-token", "name", "location"] {
+            assert!(!is_credential_key(key), "{key}");
+        }
+    }
+
+    #[test]
+    fn test_redact_json() {
+        let mut body = serde_json::json!({
+            "name": "my_table",
+            "properties": {
+                "owner": "alice",
+                "s3.secret-access-key": "secret",
+                "adls.sas-token.account.dfs.core.windows.net": "sas",
+            },
+            "updates": [{"action": "set-properties", "updates": {"s3.secret-access-key": "secret"}}],
+            "storage-credential": {"type": "s3", "aws-access-key-id": "id"},
+        });
+        settings().redact_json(&mut body);
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "name": "my_table",
+                "properties": {
+                    "owner": "alice",
+                    "s3.secret-access-key": REDACTED,
+                    "adls.sas-token.account.dfs.core.windows.net": REDACTED,
+                },
+                "updates": [{"action": "set-properties", "updates": {"s3.secret-access-key": REDACTED}}],
+                "storage-credential": REDACTED,
+            })
+        );
+
+        let mut body = serde_json::json!({"client_secret": "secret"});
+        RequestLogSettings {
+            redact_credentials: false,
+            ..settings()
+        }
+        .redact_json(&mut body);
+        assert_eq!(body, serde_json::json!({"client_secret": "secret"}));
+    }
+
+    #[test]
+    fn test_redact_query() {
+        assert_eq!(
+            settings().redact_query("pageToken=abc&token=xyz&purgeRequested=true"),
+            "pageToken=abc&token=REDACTED&purgeRequested=true"
+        );
+    }
+
+    #[test]
+    fn test_omitted_fields_default_to_config() {
+        let settings: RequestLogSettings =
+            serde_json::from_value(serde_json::json!({"enabled": true})).unwrap();
+        assert_eq!(
+            settings,
+            RequestLogSettings {
+                enabled: true,
+                ..RequestLogSettings::from_config()
+            }
+        );
+    }
+}
@@ -0,0 +1,308 @@
+use std::{collections::HashMap, path::Path, str::FromStr, sync::LazyLock};
+
+use http::{header, HeaderMap, HeaderName};
+use lazy_regex::Regex;
+use serde::{Deserialize, Deserializer};
+
+use crate::{
+    request_metadata::RequestMetadata,
+    service::{authn::Actor, RoleId, UserId},
+    WarehouseId, CONFIG,
+};
+
+/// Rules loaded from `LAKEKEEPER__RESPONSE_PROPERTY_RULES_FILE`.
+/// Empty if no file is configured.
+pub static RESPONSE_PROPERTY_RULES: LazyLock<Vec<ResponsePropertyRule>> = LazyLock::new(|| {
+    CONFIG
+        .response_property_rules_file
+        .as_deref()
+        .map(|path| load_response_property_rules(path).expect("Valid response property rules"))
+        .unwrap_or_default()
+});
+
+/// Response that properties are injected into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PropertyResponse {
+    /// `overrides` of `GET /catalog/v1/config`
+    GetConfig,
+    /// `config` of `GET /catalog/v1/{prefix}/namespaces/{namespace}/tables/{table}`
+    LoadTable,
+}
+
+/// Properties that are added to responses if the condition of the rule matches.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ResponsePropertyRule {
+    /// Name of the rule, used for logging.
+    pub name: String,
+    /// Responses the rule applies to. Applies to all responses if empty.
+    #[serde(default)]
+    pub responses: Vec<PropertyResponse>,
+    /// Condition of the rule. The rule always applies if not set.
+    #[serde(default, rename = "when")]
+    pub condition: Option<RuleCondition>,
+    pub properties: HashMap<String, String>,
+}
+
+/// Condition of a [`ResponsePropertyRule`].
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub enum RuleCondition {
+    /// All conditions match.
+    All(Vec<RuleCondition>),
+    /// At least one of the conditions matches.
+    Any(Vec<RuleCondition>),
+    Not(Box<RuleCondition>),
+    /// The `User-Agent` header of the request matches the regex.
+    UserAgent(#[serde(deserialize_with = "deserialize_from_str")] Regex),
+    /// A header of the request matches the regex. Engines can identify themselves
+    /// by sending custom headers, e.g. `header.x-engine` for Iceberg REST clients.
+    #[serde(rename_all = "kebab-case")]
+    Header {
+        #[serde(deserialize_with = "deserialize_from_str")]
+        name: HeaderName,
+        #[serde(deserialize_with = "deserialize_from_str")]
+        pattern: Regex,
+    },
+    /// The request is performed by one of the users.
+    /// Also matches if the user assumed a role.
+    Principal(Vec<UserId>),
+    /// The request is performed with one of the roles assumed.
+    Role(Vec<RoleId>),
+    /// The request targets one of the warehouses.
+    Warehouse(Vec<WarehouseId>),
+}
+
+/// Request that rules are evaluated against.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RuleContext<'a> {
+    pub(crate) warehouse_id: WarehouseId,
+    pub(crate) actor: &'a Actor,
+    pub(crate) headers: &'a HeaderMap,
+}
+
+impl ResponsePropertyRule {
+    fn applies_to(&self, response: PropertyResponse, context: RuleContext<'_>) -> bool {
+        (self.responses.is_empty() || self.responses.contains(&response))
+            && self
+                .condition
+                .as_ref()
+                .map_or(true, |condition| condition.matches(context))
+    }
+}
+
+impl RuleCondition {
+    fn matches(&self, context: RuleContext<'_>) -> bool {
+        match self {
+            RuleCondition::All(conditions) => conditions.iter().all(|c| c.matches(context)),
+            RuleCondition::Any(conditions) => conditions.iter().any(|c| c.matches(context)),
+            RuleCondition::Not(condition) => !condition.matches(context),
+            RuleCondition::UserAgent(pattern) => {
+                header_matches(context.headers, &header::USER_AGENT, pattern)
+            }
+            RuleCondition::Header { name, pattern } => {
+                header_matches(context.headers, name, pattern)
+            }
+            RuleCondition::Principal(users) => match context.actor {
+                Actor::Principal(user_id)
+                | Actor::Role {
+                    principal: user_id, ..
+                } => users.contains(user_id),
+                Actor::Anonymous => false,
+            },
+            RuleCondition::Role(roles) => match context.actor {
+                Actor::Role { assumed_role, .. } => roles.contains(assumed_role),
+                Actor::Principal(_) | Actor::Anonymous => false,
+            },
+            RuleCondition::Warehouse(warehouses) => warehouses.contains(&context.warehouse_id),
+        }
+    }
+}
+
+fn header_matches(headers: &HeaderMap, name: &HeaderName, pattern: &Regex) -> bool {
+    headers
+        .get_all(name)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .any(|value| pattern.is_match(value))
+}
+
+/// Properties to inject into a response to the request.
+///
+/// Rules are applied in order, so properties of later rules take precedence.
+pub(crate) fn response_properties(
+    response: PropertyResponse,
+    warehouse_id: WarehouseId,
+    request_metadata: &RequestMetadata,
+) -> HashMap<String, String> {
+    evaluate_rules(
+        &RESPONSE_PROPERTY_RULES,
+        response,
+        RuleContext {
+            warehouse_id,
+            actor: request_metadata.actor(),
+            headers: request_metadata.headers(),
+        },
+    )
+}
+
+fn evaluate_rules(
+    rules: &[ResponsePropertyRule],
+    response: PropertyResponse,
+    context: RuleContext<'_>,
+) -> HashMap<String, String> {
+    let mut properties = HashMap::new();
+    for rule in rules.iter().filter(|r| r.applies_to(response, context)) {
+        tracing::trace!(
+            "Response property rule '{}' applies to {response:?} of warehouse {}",
+            rule.name,
+            context.warehouse_id
+        );
+        properties.extend(rule.properties.clone());
+    }
+    properties
+}
+
+/// Load rules from a YAML or JSON file.
+///
+/// # Errors
+/// If the file cannot be read or contains invalid rules.
+pub fn load_response_property_rules(path: &Path) -> anyhow::Result<Vec<ResponsePropertyRule>> {
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        anyhow::anyhow!(e).context(format!(
+            "Failed to read response property rules from {}",
+            path.display()
+        ))
+    })?;
+    serde_yaml::from_str(&content).map_err(|e| {
+        anyhow::anyhow!(e).context(format!(
+            "Failed to parse response property rules in {}",
+            path.display()
+        ))
+    })
+}
+
+fn deserialize_from_str<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    let buf = String::deserialize(deserializer)?;
+    T::from_str(&buf).map_err(serde::de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use http::HeaderValue;
+
+    use super::*;
+
+    const RULES: &str = r#"
+- name: defaults
+  properties:
+    client.region: eu-central-1
+- name: s3-acceleration-for-external-clients
+  responses: [load-table]
+  when:
+    all:
+      - header:
+          name: x-network
+          pattern: ^external$
+      - not:
+          user-agent: ^Trino
+  properties:
+    s3.endpoint: https://s3-accelerate.amazonaws.com
+- name: etl-user
+  responses: [get-config]
+  when:
+    any:
+      - principal: [oidc~etl]
+      - role: [00000000-0000-0000-0000-000000000001]
+  properties:
+    client.region: us-east-1
+"#;
+
+    fn context<'a>(actor: &'a Actor, headers: &'a HeaderMap) -> RuleContext<'a> {
+        RuleContext {
+            warehouse_id: WarehouseId::from(uuid::Uuid::nil()),
+            actor,
+            headers,
+        }
+    }
+
+    #[test]
+    fn test_evaluate_rules() {
+        let rules: Vec<ResponsePropertyRule> = serde_yaml::from_str(RULES).unwrap();
+        let anonymous = Actor::Anonymous;
+        let mut headers = HeaderMap::new();
+
+        let properties = evaluate_rules(
+            &rules,
+            PropertyResponse::LoadTable,
+            context(&anonymous, &headers),
+        );
+        assert_eq!(
+            properties,
+            HashMap::from([("client.region".to_string(), "eu-central-1".to_string())])
+        );
+
+        headers.insert("x-network", HeaderValue::from_static("external"));
+        headers.insert(
+            header::USER_AGENT,
+            HeaderValue::from_static("PyIceberg/0.9.1"),
+        );
+        let properties = evaluate_rules(
+            &rules,
+            PropertyResponse::LoadTable,
+            context(&anonymous, &headers),
+        );
+        assert_eq!(
+            properties.get("s3.endpoint").map(String::as_str),
+            Some("https://s3-accelerate.amazonaws.com")
+        );
+
+        headers.insert(header::USER_AGENT, HeaderValue::from_static("Trino/475"));
+        let properties = evaluate_rules(
+            &rules,
+            PropertyResponse::LoadTable,
+            context(&anonymous, &headers),
+        );
+        assert!(!properties.contains_key("s3.endpoint"));
+    }
+
+    #[test]
+    fn test_later_rules_take_precedence() {
+        let rules: Vec<ResponsePropertyRule> = serde_yaml::from_str(RULES).unwrap();
+        let headers = HeaderMap::new();
+        let etl = Actor::Principal(UserId::try_from("oidc~etl").unwrap());
+        let role = Actor::Role {
+            principal: UserId::try_from("oidc~other").unwrap(),
+            assumed_role: RoleId::from_str("00000000-0000-0000-0000-000000000001").unwrap(),
+        };
+
+        for actor in [&etl, &role] {
+            let properties = evaluate_rules(
+                &rules,
+                PropertyResponse::GetConfig,
+                context(actor, &headers),
+            );
+            assert_eq!(
+                properties.get("client.region").map(String::as_str),
+                Some("us-east-1")
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        for rules in [
+            "- name: x\n  properties: {}\n  when:\n    user-agent: '('",
+            "- name: x\n  properties: {}\n  when:\n    unknown: x",
+            "- name: x\n  properties: {}\n  responses: [create-table]",
+        ] {
+            serde_yaml::from_str::<Vec<ResponsePropertyRule>>(rules).unwrap_err();
+        }
+    }
+}
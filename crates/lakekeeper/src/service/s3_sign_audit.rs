@@ -0,0 +1,89 @@
+//! Auditing of requests signed by the S3 remote signer.
+//!
+//! Every signed request is logged with the `lakekeeper::s3_sign_audit` target.
+//! If `CONFIG.enable_s3_sign_audit` is set, signed requests are additionally recorded
+//! in the catalog, where they are kept for `CONFIG.s3_sign_audit_retention_seconds`.
+
+use std::time::Duration;
+
+use super::{Catalog, Result};
+use crate::CONFIG;
+#[cfg(feature = "s3-signer")]
+use crate::{
+    api::management::v1::table::TableSigning,
+    request_metadata::RequestMetadata,
+    service::{authn::Actor, TableId},
+    WarehouseId,
+};
+
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const MAX_SIGNINGS_PER_CLEANUP: i64 = 10_000;
+
+/// Logs a signed request and records it if auditing is enabled.
+///
+/// # Errors
+/// Fails if the request cannot be recorded. It must not be signed in this case.
+#[cfg(feature = "s3-signer")]
+pub(crate) async fn audit_signing<C: Catalog>(
+    warehouse_id: WarehouseId,
+    table_id: TableId,
+    method: &http::Method,
+    locations: Vec<String>,
+    request_metadata: &RequestMetadata,
+    catalog_state: C::State,
+) -> Result<()> {
+    let assumed_role_id = match request_metadata.actor() {
+        Actor::Role { assumed_role, .. } => Some(**assumed_role),
+        Actor::Principal(_) | Actor::Anonymous => None,
+    };
+    let signing = TableSigning {
+        signing_id: uuid::Uuid::now_v7(),
+        table_id: *table_id,
+        request_id: request_metadata.request_id(),
+        principal: request_metadata.user_id().map(ToString::to_string),
+        assumed_role_id,
+        method: method.to_string(),
+        locations,
+        signed_at: chrono::Utc::now(),
+    };
+
+    tracing::info!(
+        target: "lakekeeper::s3_sign_audit",
+        %warehouse_id,
+        table_id = %signing.table_id,
+        principal = signing.principal.as_deref().unwrap_or("anonymous"),
+        assumed_role_id = ?signing.assumed_role_id,
+        method = %signing.method,
+        locations = ?signing.locations,
+        "Signed S3 request"
+    );
+
+    if CONFIG.enable_s3_sign_audit {
+        C::record_table_signing(warehouse_id, &signing, catalog_state).await?;
+    }
+    Ok(())
+}
+
+/// Infinitely running worker that deletes recorded signings once their retention ended.
+pub async fn s3_sign_audit_cleanup_worker<C: Catalog>(catalog_state: C::State) {
+    loop {
+        match delete_expired_signings::<C>(catalog_state.clone()).await {
+            Ok(n) if n >= u64::try_from(MAX_SIGNINGS_PER_CLEANUP).unwrap_or(0) => continue,
+            Ok(0) => {}
+            Ok(n) => tracing::info!("Deleted {n} expired S3 signing audit entries"),
+            Err(e) => {
+                tracing::error!(
+                    ?e,
+                    "Failed to delete expired S3 signing audit entries: {}",
+                    e.error
+                );
+            }
+        }
+        tokio::time::sleep(CLEANUP_INTERVAL).await;
+    }
+}
+
+async fn delete_expired_signings<C: Catalog>(catalog_state: C::State) -> Result<u64> {
+    let signed_before = chrono::Utc::now() - CONFIG.s3_sign_audit_retention_seconds;
+    C::delete_table_signings(signed_before, MAX_SIGNINGS_PER_CLEANUP, catalog_state).await
+}
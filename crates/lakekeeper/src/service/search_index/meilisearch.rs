@@ -0,0 +1,295 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use serde_json::json;
+use url::Url;
+use uuid::Uuid;
+
+use super::{SearchDocument, SearchIndex};
+use crate::{api::management::v1::search::CatalogSearchFilter, ProjectId};
+
+/// Search index backed by Meilisearch.
+///
+/// Meilisearch processes writes asynchronously. Changes become searchable shortly
+/// after they are acknowledged.
+#[derive(Debug, Clone)]
+pub struct MeilisearchIndex {
+    client: reqwest::Client,
+    url: Url,
+    index_uid: String,
+    api_key: Option<String>,
+}
+
+impl MeilisearchIndex {
+    #[must_use]
+    pub fn new(url: Url, index_uid: String, api_key: Option<String>) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+            url,
+            index_uid,
+            api_key,
+        }
+    }
+
+    async fn send(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: &serde_json::Value,
+    ) -> anyhow::Result<serde_json::Value> {
+        let url = self
+            .url
+            .join(path)
+            .with_context(|| format!("Invalid Meilisearch path '{path}'"))?;
+        let request = self.client.request(method, url).json(body);
+        let request = match &self.api_key {
+            Some(api_key) => request.bearer_auth(api_key),
+            None => request,
+        };
+        let response = request
+            .send()
+            .await
+            .with_context(|| format!("Failed to send request to Meilisearch at '{path}'"))?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("Meilisearch responded with status {status}: {body}");
+        }
+        response
+            .json()
+            .await
+            .context("Failed to parse Meilisearch response")
+    }
+}
+
+#[async_trait::async_trait]
+impl SearchIndex for MeilisearchIndex {
+    async fn initialize(&self) -> anyhow::Result<()> {
+        // Creating an existing index fails asynchronously and leaves it untouched.
+        self.send(
+            reqwest::Method::POST,
+            "indexes",
+            &json!({"uid": self.index_uid, "primaryKey": "id"}),
+        )
+        .await?;
+        self.send(
+            reqwest::Method::PATCH,
+            &format!("indexes/{}/settings", self.index_uid),
+            &json!({
+                "searchableAttributes": ["name", "namespace"],
+                "filterableAttributes": [
+                    "id",
+                    "project_id",
+                    "warehouse_id",
+                    "entity_type",
+                    "owner",
+                    "certification",
+                    "tags",
+                    "last_modified"
+                ],
+                "sortableAttributes": ["last_modified"],
+                // The catalog search requests more hits than the default limit of 1000.
+                "pagination": {"maxTotalHits": 10000}
+            }),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn upsert(&self, documents: &[SearchDocument]) -> anyhow::Result<()> {
+        if documents.is_empty() {
+            return Ok(());
+        }
+        self.send(
+            reqwest::Method::POST,
+            &format!("indexes/{}/documents", self.index_uid),
+            &serde_json::to_value(documents)?,
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn delete(&self, ids: &[Uuid]) -> anyhow::Result<()> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+        self.send(
+            reqwest::Method::POST,
+            &format!("indexes/{}/documents/delete-batch", self.index_uid),
+            &serde_json::to_value(ids)?,
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn search(
+        &self,
+        project_id: &ProjectId,
+        filter: &CatalogSearchFilter,
+        limit: usize,
+    ) -> anyhow::Result<Vec<Uuid>> {
+        let response = self
+            .send(
+                reqwest::Method::POST,
+                &format!("indexes/{}/search", self.index_uid),
+                &search_query(project_id, filter, limit),
+            )
+            .await?;
+
+        response["hits"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|hit| {
+                hit["id"]
+                    .as_str()
+                    .and_then(|id| Uuid::parse_str(id).ok())
+                    .ok_or_else(|| anyhow::anyhow!("Meilisearch returned invalid id: {hit}"))
+            })
+            .collect()
+    }
+
+    fn name(&self) -> &'static str {
+        "meilisearch"
+    }
+}
+
+/// Quotes a value for a Meilisearch filter expression.
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn any_of<T: AsRef<str>>(attribute: &str, values: &[T]) -> String {
+    let values = values
+        .iter()
+        .map(|v| quote(v.as_ref()))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("{attribute} IN [{values}]")
+}
+
+fn search_query(
+    project_id: &ProjectId,
+    filter: &CatalogSearchFilter,
+    limit: usize,
+) -> serde_json::Value {
+    let CatalogSearchFilter {
+        search,
+        entity_types,
+        warehouse_ids,
+        tags,
+        owners,
+        certifications,
+        // Labels are not indexed, label filters are answered by the catalog.
+        labels: _,
+        modified_after,
+        modified_before,
+        ids,
+    } = filter;
+
+    // Elements of the top-level array are combined with AND.
+    let mut filters = vec![format!("project_id = {}", quote(project_id))];
+    if let Some(entity_types) = entity_types {
+        let entity_types = entity_types.iter().map(|t| t.as_str()).collect::<Vec<_>>();
+        filters.push(any_of("entity_type", &entity_types));
+    }
+    if let Some(warehouse_ids) = warehouse_ids {
+        let warehouse_ids = warehouse_ids
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        filters.push(any_of("warehouse_id", &warehouse_ids));
+    }
+    if let Some(tags) = tags {
+        filters.push(any_of("tags", tags));
+    }
+    if let Some(owners) = owners {
+        filters.push(any_of("owner", owners));
+    }
+    if let Some(certifications) = certifications {
+        filters.push(any_of("certification", certifications));
+    }
+    if let Some(ids) = ids {
+        let ids = ids.iter().map(ToString::to_string).collect::<Vec<_>>();
+        filters.push(any_of("id", &ids));
+    }
+    if let Some(modified_after) = modified_after {
+        filters.push(format!(
+            "last_modified >= {}",
+            modified_after.timestamp_millis()
+        ));
+    }
+    if let Some(modified_before) = modified_before {
+        filters.push(format!(
+            "last_modified < {}",
+            modified_before.timestamp_millis()
+        ));
+    }
+
+    let mut query = json!({
+        "q": search.as_deref().unwrap_or_default(),
+        "limit": limit,
+        "filter": filters,
+        "attributesToRetrieve": ["id"],
+    });
+    if search.is_none() {
+        query["sort"] = json!(["last_modified:desc"]);
+    }
+    query
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::management::v1::search::SearchEntityType;
+
+    #[test]
+    fn test_quote_escapes_filter_values() {
+        assert_eq!(quote("plain"), "\"plain\"");
+        assert_eq!(quote("a\"b\\c"), "\"a\\\"b\\\\c\"");
+    }
+
+    #[test]
+    fn test_search_query() {
+        let project_id = ProjectId::from(Uuid::nil());
+        let query = search_query(
+            &project_id,
+            &CatalogSearchFilter {
+                entity_types: Some(vec![SearchEntityType::Table, SearchEntityType::View]),
+                owners: Some(vec!["data\"team".to_string()]),
+                modified_before: Some(chrono::DateTime::from_timestamp_millis(2000).unwrap()),
+                ..Default::default()
+            },
+            10,
+        );
+        assert_eq!(
+            query,
+            json!({
+                "q": "",
+                "limit": 10,
+                "filter": [
+                    format!("project_id = \"{project_id}\""),
+                    "entity_type IN [\"table\", \"view\"]",
+                    "owner IN [\"data\\\"team\"]",
+                    "last_modified < 2000",
+                ],
+                "attributesToRetrieve": ["id"],
+                "sort": ["last_modified:desc"],
+            })
+        );
+
+        let query = search_query(
+            &project_id,
+            &CatalogSearchFilter {
+                search: Some("orders".to_string()),
+                ..Default::default()
+            },
+            10,
+        );
+        assert_eq!(query["q"], "orders");
+        assert!(query.get("sort").is_none());
+    }
+}
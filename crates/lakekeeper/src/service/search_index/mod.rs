@@ -0,0 +1,590 @@
+//! Optional external index for the catalog search.
+//!
+//! Postgres remains the source of truth: The [`SearchIndexer`] hook only forwards the ids of
+//! changed entities. The [`SearchIndexerBackgroundTask`] reloads them from the catalog and
+//! writes them to the index. Search results of the index are re-validated against the catalog
+//! before they are returned, so stale index entries never leak deleted entities.
+
+pub mod meilisearch;
+pub mod opensearch;
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{Debug, Display},
+    sync::Arc,
+};
+
+use iceberg::{
+    spec::{TableMetadata, ViewMetadata},
+    TableIdent,
+};
+use iceberg_ext::{
+    catalog::rest::{
+        CommitTransactionRequest, CommitViewRequest, CreateTableRequest, CreateViewRequest,
+        RegisterTableRequest, RenameTableRequest,
+    },
+    configs::Location,
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{
+    api::{
+        iceberg::{
+            types::DropParams,
+            v1::{DataAccess, NamespaceParameters, TableParameters, ViewParameters},
+        },
+        management::v1::{
+            search::{CatalogSearchFilter, SearchCatalogResult, SearchEntityType},
+            warehouse::UndropTabularsRequest,
+        },
+        RequestMetadata,
+    },
+    catalog::tables::CommitContext,
+    config::SearchIndexBackend,
+    service::{
+        authz::AuthorizationContext,
+        endpoint_hooks::{EndpointHook, ViewCommit},
+        Catalog, CreateNamespaceResponse, NamespaceId, TableId, Transaction, UndropTabularResponse,
+        UpdateNamespacePropertiesResponse, ViewId,
+    },
+    ProjectId, WarehouseId, CONFIG,
+};
+
+/// Number of documents written to the index in a single request.
+const BATCH_SIZE: usize = 500;
+
+/// External full-text index of namespaces, tables and views.
+#[async_trait::async_trait]
+pub trait SearchIndex: Debug + Send + Sync + 'static {
+    /// Create the index if it does not exist yet.
+    async fn initialize(&self) -> anyhow::Result<()>;
+
+    /// Insert or replace documents.
+    async fn upsert(&self, documents: &[SearchDocument]) -> anyhow::Result<()>;
+
+    /// Delete documents by id. Ids that are not indexed are ignored.
+    async fn delete(&self, ids: &[Uuid]) -> anyhow::Result<()>;
+
+    /// Ids of entities matching the filter, best match first.
+    /// If `filter.search` is not set, the most recently modified entities come first.
+    async fn search(
+        &self,
+        project_id: &ProjectId,
+        filter: &CatalogSearchFilter,
+        limit: usize,
+    ) -> anyhow::Result<Vec<Uuid>>;
+
+    fn name(&self) -> &'static str;
+}
+
+/// Build the search index configured via `LAKEKEEPER__SEARCH_INDEX_*`.
+/// Returns `None` if no backend is configured.
+///
+/// # Errors
+/// - If a backend is configured but `LAKEKEEPER__SEARCH_INDEX_URL` is not set.
+pub fn build_search_index_from_config() -> anyhow::Result<Option<Arc<dyn SearchIndex>>> {
+    let Some(backend) = CONFIG.search_index_backend else {
+        tracing::info!("No search index configured. Catalog searches run against the catalog.");
+        return Ok(None);
+    };
+    let url = CONFIG.search_index_url.clone().ok_or_else(|| {
+        anyhow::anyhow!("`LAKEKEEPER__SEARCH_INDEX_URL` is required if a search index is set")
+    })?;
+    let index_name = CONFIG.search_index_name.clone();
+
+    let index: Arc<dyn SearchIndex> = match backend {
+        SearchIndexBackend::OpenSearch => Arc::new(opensearch::OpenSearchIndex::new(
+            url.clone(),
+            index_name.clone(),
+            CONFIG
+                .search_index_user
+                .clone()
+                .zip(CONFIG.search_index_password.clone()),
+        )),
+        SearchIndexBackend::Meilisearch => Arc::new(meilisearch::MeilisearchIndex::new(
+            url.clone(),
+            index_name.clone(),
+            CONFIG.search_index_api_key.clone(),
+        )),
+    };
+    tracing::info!(
+        "Using {} index '{index_name}' at {url} for catalog searches",
+        index.name()
+    );
+    Ok(Some(index))
+}
+
+/// A namespace, table or view as stored in the search index.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SearchDocument {
+    pub id: Uuid,
+    pub project_id: String,
+    pub warehouse_id: Uuid,
+    pub entity_type: SearchEntityType,
+    /// Namespace containing the entity. For namespaces, this is the parent namespace.
+    pub namespace: Vec<String>,
+    pub name: String,
+    pub owner: Option<String>,
+    pub certification: Option<String>,
+    pub tags: Vec<String>,
+    /// Milliseconds since epoch
+    pub last_modified: i64,
+}
+
+impl SearchDocument {
+    #[must_use]
+    pub fn new(project_id: &ProjectId, result: SearchCatalogResult) -> Self {
+        Self {
+            id: result.id,
+            project_id: project_id.to_string(),
+            warehouse_id: *result.warehouse_id,
+            entity_type: result.entity_type,
+            namespace: result.namespace,
+            name: result.name,
+            owner: result.owner,
+            certification: result.certification,
+            tags: result.tags,
+            last_modified: result.last_modified.timestamp_millis(),
+        }
+    }
+}
+
+/// Entities of a warehouse that changed and need to be re-indexed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchIndexUpdate {
+    pub warehouse_id: WarehouseId,
+    pub ids: Vec<Uuid>,
+}
+
+/// Endpoint hook forwarding changed entities to the [`SearchIndexerBackgroundTask`].
+#[derive(Debug, Clone)]
+pub struct SearchIndexer {
+    tx: tokio::sync::mpsc::Sender<SearchIndexUpdate>,
+    timeout: tokio::time::Duration,
+}
+
+impl Display for SearchIndexer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SearchIndexer")
+    }
+}
+
+impl SearchIndexer {
+    #[must_use]
+    pub fn new(tx: tokio::sync::mpsc::Sender<SearchIndexUpdate>) -> Self {
+        Self {
+            tx,
+            timeout: tokio::time::Duration::from_millis(50),
+        }
+    }
+
+    async fn send(&self, warehouse_id: WarehouseId, ids: Vec<Uuid>) -> anyhow::Result<()> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+        self.tx
+            .send_timeout(SearchIndexUpdate { warehouse_id, ids }, self.timeout)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to queue search index update: {e}"))
+    }
+}
+
+#[async_trait::async_trait]
+impl EndpointHook for SearchIndexer {
+    async fn create_namespace(
+        &self,
+        warehouse_id: WarehouseId,
+        namespace_id: NamespaceId,
+        _response: Arc<CreateNamespaceResponse>,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        self.send(warehouse_id, vec![*namespace_id]).await
+    }
+
+    async fn drop_namespace(
+        &self,
+        warehouse_id: WarehouseId,
+        namespace_id: NamespaceId,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        self.send(warehouse_id, vec![*namespace_id]).await
+    }
+
+    async fn update_namespace_properties(
+        &self,
+        warehouse_id: WarehouseId,
+        namespace_id: NamespaceId,
+        _response: Arc<UpdateNamespacePropertiesResponse>,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        self.send(warehouse_id, vec![*namespace_id]).await
+    }
+
+    async fn commit_transaction(
+        &self,
+        warehouse_id: WarehouseId,
+        _request: Arc<CommitTransactionRequest>,
+        _commits: Arc<Vec<CommitContext>>,
+        table_ident_map: Arc<HashMap<TableIdent, TableId>>,
+        _authorization: Arc<AuthorizationContext>,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        self.send(
+            warehouse_id,
+            table_ident_map.values().map(|id| **id).collect(),
+        )
+        .await
+    }
+
+    async fn drop_table(
+        &self,
+        warehouse_id: WarehouseId,
+        _parameters: TableParameters,
+        _drop_params: DropParams,
+        table_id: TableId,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        self.send(warehouse_id, vec![*table_id]).await
+    }
+
+    async fn register_table(
+        &self,
+        warehouse_id: WarehouseId,
+        _parameters: NamespaceParameters,
+        _request: Arc<RegisterTableRequest>,
+        metadata: Arc<TableMetadata>,
+        _metadata_location: Arc<Location>,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        self.send(warehouse_id, vec![metadata.uuid()]).await
+    }
+
+    async fn create_table(
+        &self,
+        warehouse_id: WarehouseId,
+        _parameters: NamespaceParameters,
+        _request: Arc<CreateTableRequest>,
+        metadata: Arc<TableMetadata>,
+        _metadata_location: Option<Arc<Location>>,
+        _data_access: DataAccess,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        self.send(warehouse_id, vec![metadata.uuid()]).await
+    }
+
+    async fn rename_table(
+        &self,
+        warehouse_id: WarehouseId,
+        table_id: TableId,
+        _request: Arc<RenameTableRequest>,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        self.send(warehouse_id, vec![*table_id]).await
+    }
+
+    async fn create_view(
+        &self,
+        warehouse_id: WarehouseId,
+        _parameters: NamespaceParameters,
+        _request: Arc<CreateViewRequest>,
+        metadata: Arc<ViewMetadata>,
+        _metadata_location: Arc<Location>,
+        _data_access: DataAccess,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        self.send(warehouse_id, vec![metadata.uuid()]).await
+    }
+
+    async fn commit_view(
+        &self,
+        warehouse_id: WarehouseId,
+        _parameters: ViewParameters,
+        _request: Arc<CommitViewRequest>,
+        view_commit: Arc<ViewCommit>,
+        _data_access: DataAccess,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        self.send(warehouse_id, vec![view_commit.new_metadata.uuid()])
+            .await
+    }
+
+    async fn drop_view(
+        &self,
+        warehouse_id: WarehouseId,
+        _parameters: ViewParameters,
+        _drop_params: DropParams,
+        view_id: ViewId,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        self.send(warehouse_id, vec![*view_id]).await
+    }
+
+    async fn rename_view(
+        &self,
+        warehouse_id: WarehouseId,
+        view_id: ViewId,
+        _request: Arc<RenameTableRequest>,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        self.send(warehouse_id, vec![*view_id]).await
+    }
+
+    async fn undrop_tabular(
+        &self,
+        warehouse_id: WarehouseId,
+        _request: Arc<UndropTabularsRequest>,
+        responses: Arc<Vec<UndropTabularResponse>>,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        self.send(
+            warehouse_id,
+            responses.iter().map(|r| *r.table_ident).collect(),
+        )
+        .await
+    }
+}
+
+/// Reloads entities from the catalog and writes them to the index.
+/// Entities that no longer exist in the catalog are removed from the index.
+pub(crate) async fn reindex_entities<C: Catalog>(
+    index: &dyn SearchIndex,
+    catalog_state: C::State,
+    project_id: &ProjectId,
+    warehouse_id: Option<WarehouseId>,
+    ids: Vec<Uuid>,
+) -> anyhow::Result<()> {
+    let found = C::search_catalog(
+        project_id,
+        &CatalogSearchFilter {
+            warehouse_ids: warehouse_id.map(|id| vec![id]),
+            ids: Some(ids.clone()),
+            ..Default::default()
+        },
+        i64::try_from(ids.len()).unwrap_or(i64::MAX),
+        catalog_state,
+    )
+    .await
+    .map_err(|e| anyhow::anyhow!(e.error))?;
+
+    let found_ids = found.iter().map(|r| r.id).collect::<HashSet<_>>();
+    let missing = ids
+        .into_iter()
+        .filter(|id| !found_ids.contains(id))
+        .collect::<Vec<_>>();
+    let documents = found
+        .into_iter()
+        .map(|r| SearchDocument::new(project_id, r))
+        .collect::<Vec<_>>();
+
+    if !documents.is_empty() {
+        index.upsert(&documents).await?;
+    }
+    if !missing.is_empty() {
+        index.delete(&missing).await?;
+    }
+    Ok(())
+}
+
+/// Applies [`SearchIndexUpdate`]s and periodically re-syncs the whole catalog.
+///
+/// Children of dropped namespaces are not reported individually. They are removed
+/// from the index when a search returns them but the catalog doesn't.
+pub struct SearchIndexerBackgroundTask<C: Catalog> {
+    pub source: tokio::sync::mpsc::Receiver<SearchIndexUpdate>,
+    pub index: Arc<dyn SearchIndex>,
+    pub catalog_state: C::State,
+    /// `None` disables full syncs
+    pub full_sync_interval: Option<std::time::Duration>,
+}
+
+impl<C: Catalog> SearchIndexerBackgroundTask<C> {
+    /// Runs until all senders are dropped.
+    ///
+    /// # Errors
+    /// Never returns an error. Failures are logged and the affected entities are
+    /// repaired by the next full sync.
+    pub async fn run(mut self) -> anyhow::Result<()> {
+        if let Err(e) = self.index.initialize().await {
+            tracing::error!(
+                "Failed to initialize {} search index: {e:?}",
+                self.index.name()
+            );
+        }
+
+        let mut full_sync = self.full_sync_interval.map(|interval| {
+            let mut interval = tokio::time::interval(interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            interval
+        });
+
+        loop {
+            let next_full_sync = async {
+                match full_sync.as_mut() {
+                    Some(interval) => interval.tick().await,
+                    None => std::future::pending().await,
+                }
+            };
+            tokio::select! {
+                update = self.source.recv() => {
+                    let Some(update) = update else {
+                        break;
+                    };
+                    let mut updates = vec![update];
+                    while updates.len() < BATCH_SIZE {
+                        match self.source.try_recv() {
+                            Ok(update) => updates.push(update),
+                            Err(_) => break,
+                        }
+                    }
+                    if let Err(e) = self.apply_updates(updates).await {
+                        tracing::warn!("Failed to update search index: {e:?}");
+                    }
+                }
+                _ = next_full_sync => {
+                    if let Err(e) = self.full_sync().await {
+                        tracing::warn!("Failed to sync search index: {e:?}");
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn apply_updates(&self, updates: Vec<SearchIndexUpdate>) -> anyhow::Result<()> {
+        let mut by_warehouse = HashMap::<WarehouseId, HashSet<Uuid>>::new();
+        for SearchIndexUpdate { warehouse_id, ids } in updates {
+            by_warehouse.entry(warehouse_id).or_default().extend(ids);
+        }
+
+        for (warehouse_id, ids) in by_warehouse {
+            let ids = ids.into_iter().collect::<Vec<_>>();
+            match self.project_of_warehouse(warehouse_id).await? {
+                Some(project_id) => {
+                    reindex_entities::<C>(
+                        self.index.as_ref(),
+                        self.catalog_state.clone(),
+                        &project_id,
+                        Some(warehouse_id),
+                        ids,
+                    )
+                    .await?;
+                }
+                None => self.index.delete(&ids).await?,
+            }
+        }
+        Ok(())
+    }
+
+    async fn project_of_warehouse(
+        &self,
+        warehouse_id: WarehouseId,
+    ) -> anyhow::Result<Option<ProjectId>> {
+        let mut t = C::Transaction::begin_read(self.catalog_state.clone())
+            .await
+            .map_err(|e| anyhow::anyhow!(e.error))?;
+        let warehouse = C::get_warehouse(warehouse_id, t.transaction())
+            .await
+            .map_err(|e| anyhow::anyhow!(e.error))?;
+        t.commit().await.map_err(|e| anyhow::anyhow!(e.error))?;
+        Ok(warehouse.map(|w| w.project_id))
+    }
+
+    /// Re-index all entities of all active warehouses.
+    async fn full_sync(&self) -> anyhow::Result<()> {
+        let mut t = C::Transaction::begin_read(self.catalog_state.clone())
+            .await
+            .map_err(|e| anyhow::anyhow!(e.error))?;
+        let projects = C::list_projects(None, t.transaction())
+            .await
+            .map_err(|e| anyhow::anyhow!(e.error))?;
+        let mut warehouses = Vec::new();
+        for project in projects {
+            warehouses.extend(
+                C::list_warehouses(&project.project_id, None, t.transaction())
+                    .await
+                    .map_err(|e| anyhow::anyhow!(e.error))?
+                    .into_iter()
+                    .map(|w| (project.project_id.clone(), w.id)),
+            );
+        }
+        t.commit().await.map_err(|e| anyhow::anyhow!(e.error))?;
+
+        let mut indexed = 0;
+        for (project_id, warehouse_id) in warehouses {
+            indexed += self.sync_warehouse(&project_id, warehouse_id).await?;
+        }
+        tracing::info!(
+            "Synced {indexed} entities to {} search index",
+            self.index.name()
+        );
+        Ok(())
+    }
+
+    /// Pages through the warehouse from the most recently modified entity backwards.
+    async fn sync_warehouse(
+        &self,
+        project_id: &ProjectId,
+        warehouse_id: WarehouseId,
+    ) -> anyhow::Result<usize> {
+        let batch_size = i64::try_from(BATCH_SIZE).unwrap_or(i64::MAX);
+        let filter = CatalogSearchFilter {
+            warehouse_ids: Some(vec![warehouse_id]),
+            ..Default::default()
+        };
+        let mut modified_before = None;
+        let mut indexed = 0;
+
+        loop {
+            let mut page = C::search_catalog(
+                project_id,
+                &CatalogSearchFilter {
+                    modified_before,
+                    ..filter.clone()
+                },
+                batch_size,
+                self.catalog_state.clone(),
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!(e.error))?;
+            let is_last_page = page.len() < BATCH_SIZE;
+
+            // The next page starts strictly before the last timestamp of this page.
+            // Load all entities sharing that timestamp so none are skipped.
+            let last_modified = page.last().map(|r| r.last_modified);
+            if let Some(last_modified) = last_modified.filter(|_| !is_last_page) {
+                page.extend(
+                    C::search_catalog(
+                        project_id,
+                        &CatalogSearchFilter {
+                            modified_after: Some(last_modified),
+                            modified_before: Some(
+                                last_modified + chrono::Duration::microseconds(1),
+                            ),
+                            ..filter.clone()
+                        },
+                        i64::MAX,
+                        self.catalog_state.clone(),
+                    )
+                    .await
+                    .map_err(|e| anyhow::anyhow!(e.error))?,
+                );
+            }
+
+            let documents = page
+                .into_iter()
+                .map(|r| SearchDocument::new(project_id, r))
+                .collect::<Vec<_>>();
+            for chunk in documents.chunks(BATCH_SIZE) {
+                self.index.upsert(chunk).await?;
+            }
+            indexed += documents.len();
+
+            if is_last_page {
+                return Ok(indexed);
+            }
+            modified_before = last_modified;
+        }
+    }
+}
@@ -0,0 +1,317 @@
+use std::{fmt::Write as _, time::Duration};
+
+use anyhow::Context;
+use serde_json::json;
+use url::Url;
+use uuid::Uuid;
+
+use super::{SearchDocument, SearchIndex};
+use crate::{api::management::v1::search::CatalogSearchFilter, ProjectId};
+
+/// Search index backed by `OpenSearch` (or Elasticsearch, which shares the used API).
+#[derive(Debug, Clone)]
+pub struct OpenSearchIndex {
+    client: reqwest::Client,
+    url: Url,
+    index_name: String,
+    basic_auth: Option<(String, String)>,
+}
+
+impl OpenSearchIndex {
+    #[must_use]
+    pub fn new(url: Url, index_name: String, basic_auth: Option<(String, String)>) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+            url,
+            index_name,
+            basic_auth,
+        }
+    }
+
+    fn request(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+    ) -> anyhow::Result<reqwest::RequestBuilder> {
+        let url = self
+            .url
+            .join(path)
+            .with_context(|| format!("Invalid OpenSearch path '{path}'"))?;
+        let request = self.client.request(method, url);
+        Ok(match &self.basic_auth {
+            Some((user, password)) => request.basic_auth(user, Some(password)),
+            None => request,
+        })
+    }
+
+    async fn bulk(&self, body: String) -> anyhow::Result<()> {
+        let response = self
+            .request(reqwest::Method::POST, "_bulk")?
+            .header(reqwest::header::CONTENT_TYPE, "application/x-ndjson")
+            .body(body)
+            .send()
+            .await
+            .context("Failed to send bulk request to OpenSearch")?;
+        let response = error_for_status(response).await?;
+        let response: serde_json::Value = response
+            .json()
+            .await
+            .context("Failed to parse OpenSearch bulk response")?;
+
+        // Deleting a missing document is reported as `not_found` without an error.
+        let errors = response["items"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|item| item.as_object()?.values().next()?.get("error"))
+            .collect::<Vec<_>>();
+        if let Some(first) = errors.first() {
+            anyhow::bail!(
+                "OpenSearch bulk request failed for {} documents. First error: {first}",
+                errors.len()
+            );
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl SearchIndex for OpenSearchIndex {
+    async fn initialize(&self) -> anyhow::Result<()> {
+        let exists = self
+            .request(reqwest::Method::HEAD, &self.index_name)?
+            .send()
+            .await
+            .context("Failed to check if OpenSearch index exists")?;
+        if exists.status().is_success() {
+            return Ok(());
+        }
+
+        let response = self
+            .request(reqwest::Method::PUT, &self.index_name)?
+            .json(&index_mappings())
+            .send()
+            .await
+            .context("Failed to create OpenSearch index")?;
+        error_for_status(response).await?;
+        tracing::info!("Created OpenSearch index '{}'", self.index_name);
+        Ok(())
+    }
+
+    async fn upsert(&self, documents: &[SearchDocument]) -> anyhow::Result<()> {
+        if documents.is_empty() {
+            return Ok(());
+        }
+        let mut body = String::new();
+        for document in documents {
+            let action = json!({"index": {"_index": self.index_name, "_id": document.id}});
+            writeln!(body, "{action}")?;
+            writeln!(body, "{}", serde_json::to_string(document)?)?;
+        }
+        self.bulk(body).await
+    }
+
+    async fn delete(&self, ids: &[Uuid]) -> anyhow::Result<()> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+        let mut body = String::new();
+        for id in ids {
+            let action = json!({"delete": {"_index": self.index_name, "_id": id}});
+            writeln!(body, "{action}")?;
+        }
+        self.bulk(body).await
+    }
+
+    async fn search(
+        &self,
+        project_id: &ProjectId,
+        filter: &CatalogSearchFilter,
+        limit: usize,
+    ) -> anyhow::Result<Vec<Uuid>> {
+        let response = self
+            .request(
+                reqwest::Method::POST,
+                &format!("{}/_search", self.index_name),
+            )?
+            .json(&search_query(project_id, filter, limit))
+            .send()
+            .await
+            .context("Failed to send search request to OpenSearch")?;
+        let response: serde_json::Value = error_for_status(response)
+            .await?
+            .json()
+            .await
+            .context("Failed to parse OpenSearch search response")?;
+
+        response["hits"]["hits"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|hit| {
+                hit["_id"]
+                    .as_str()
+                    .and_then(|id| Uuid::parse_str(id).ok())
+                    .ok_or_else(|| anyhow::anyhow!("OpenSearch returned invalid id: {hit}"))
+            })
+            .collect()
+    }
+
+    fn name(&self) -> &'static str {
+        "opensearch"
+    }
+}
+
+async fn error_for_status(response: reqwest::Response) -> anyhow::Result<reqwest::Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body = response.text().await.unwrap_or_default();
+    anyhow::bail!("OpenSearch responded with status {status}: {body}")
+}
+
+fn index_mappings() -> serde_json::Value {
+    json!({
+        "mappings": {
+            "properties": {
+                "id": {"type": "keyword"},
+                "project_id": {"type": "keyword"},
+                "warehouse_id": {"type": "keyword"},
+                "entity_type": {"type": "keyword"},
+                "namespace": {"type": "text"},
+                "name": {"type": "text", "fields": {"keyword": {"type": "keyword"}}},
+                "owner": {"type": "keyword"},
+                "certification": {"type": "keyword"},
+                "tags": {"type": "keyword"},
+                "last_modified": {"type": "date", "format": "epoch_millis"}
+            }
+        }
+    })
+}
+
+fn search_query(
+    project_id: &ProjectId,
+    filter: &CatalogSearchFilter,
+    limit: usize,
+) -> serde_json::Value {
+    let CatalogSearchFilter {
+        search,
+        entity_types,
+        warehouse_ids,
+        tags,
+        owners,
+        certifications,
+        // Labels are not indexed, label filters are answered by the catalog.
+        labels: _,
+        modified_after,
+        modified_before,
+        ids,
+    } = filter;
+
+    let mut filters = vec![json!({"term": {"project_id": project_id.to_string()}})];
+    if let Some(entity_types) = entity_types {
+        let entity_types = entity_types.iter().map(|t| t.as_str()).collect::<Vec<_>>();
+        filters.push(json!({"terms": {"entity_type": entity_types}}));
+    }
+    if let Some(warehouse_ids) = warehouse_ids {
+        let warehouse_ids = warehouse_ids
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        filters.push(json!({"terms": {"warehouse_id": warehouse_ids}}));
+    }
+    if let Some(tags) = tags {
+        filters.push(json!({"terms": {"tags": tags}}));
+    }
+    if let Some(owners) = owners {
+        filters.push(json!({"terms": {"owner": owners}}));
+    }
+    if let Some(certifications) = certifications {
+        filters.push(json!({"terms": {"certification": certifications}}));
+    }
+    if let Some(ids) = ids {
+        filters.push(json!({"terms": {"id": ids}}));
+    }
+    let mut range = serde_json::Map::new();
+    if let Some(modified_after) = modified_after {
+        range.insert("gte".to_string(), modified_after.timestamp_millis().into());
+    }
+    if let Some(modified_before) = modified_before {
+        range.insert("lt".to_string(), modified_before.timestamp_millis().into());
+    }
+    if !range.is_empty() {
+        filters.push(json!({"range": {"last_modified": range}}));
+    }
+
+    let mut query = json!({
+        "size": limit,
+        "_source": false,
+        "query": {"bool": {"filter": filters}},
+    });
+    if let Some(search) = search {
+        query["query"]["bool"]["must"] = json!({
+            "multi_match": {
+                "query": search,
+                "fields": ["name^3", "namespace"],
+                "fuzziness": "AUTO",
+            }
+        });
+    } else {
+        query["sort"] = json!([{"last_modified": "desc"}, {"id": "asc"}]);
+    }
+    query
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::management::v1::search::SearchEntityType;
+
+    #[test]
+    fn test_search_query_without_search_sorts_by_last_modified() {
+        let project_id = ProjectId::from(Uuid::nil());
+        let query = search_query(
+            &project_id,
+            &CatalogSearchFilter {
+                entity_types: Some(vec![SearchEntityType::Table]),
+                modified_after: Some(chrono::DateTime::from_timestamp_millis(1000).unwrap()),
+                ..Default::default()
+            },
+            10,
+        );
+        assert_eq!(
+            query,
+            json!({
+                "size": 10,
+                "_source": false,
+                "query": {"bool": {"filter": [
+                    {"term": {"project_id": project_id.to_string()}},
+                    {"terms": {"entity_type": ["table"]}},
+                    {"range": {"last_modified": {"gte": 1000}}},
+                ]}},
+                "sort": [{"last_modified": "desc"}, {"id": "asc"}],
+            })
+        );
+    }
+
+    #[test]
+    fn test_search_query_with_search_ranks_by_relevance() {
+        let query = search_query(
+            &ProjectId::from(Uuid::nil()),
+            &CatalogSearchFilter {
+                search: Some("orders".to_string()),
+                ..Default::default()
+            },
+            10,
+        );
+        assert_eq!(
+            query["query"]["bool"]["must"]["multi_match"]["query"],
+            "orders"
+        );
+        assert!(query.get("sort").is_none());
+    }
+}
@@ -0,0 +1,57 @@
+//! Deletion of storage secrets that were replaced by a credential rotation.
+//!
+//! Requests that loaded a warehouse before the rotation, such as in-flight remote-signing
+//! requests, still look up the previous secret. It is therefore kept until its grace period
+//! ends and only deleted from the secret store afterwards.
+
+use std::time::Duration;
+
+use super::{Catalog, Result, SecretStore, Transaction};
+
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(60);
+const MAX_SECRETS_PER_CLEANUP: i64 = 100;
+
+/// Infinitely running worker that deletes retired secrets once they are due.
+pub async fn retired_secret_cleanup_worker<C: Catalog, S: SecretStore>(
+    catalog_state: C::State,
+    secret_store: S,
+) {
+    loop {
+        match delete_due_retired_secrets::<C, S>(catalog_state.clone(), &secret_store).await {
+            Ok(n) if n >= usize::try_from(MAX_SECRETS_PER_CLEANUP).unwrap_or(0) => continue,
+            Ok(0) => {}
+            Ok(n) => tracing::info!("Deleted {n} retired storage secrets"),
+            Err(e) => {
+                tracing::error!(?e, "Failed to delete retired storage secrets: {}", e.error);
+            }
+        }
+        tokio::time::sleep(CLEANUP_INTERVAL).await;
+    }
+}
+
+/// Deletes due secrets from the secret store.
+/// Secrets that cannot be deleted are retried later.
+/// Returns the number of deleted secrets.
+async fn delete_due_retired_secrets<C: Catalog, S: SecretStore>(
+    catalog_state: C::State,
+    secret_store: &S,
+) -> Result<usize> {
+    let mut t = C::Transaction::begin_write(catalog_state).await?;
+    let due =
+        C::claim_due_retired_storage_secrets(MAX_SECRETS_PER_CLEANUP, t.transaction()).await?;
+
+    let mut deleted = Vec::with_capacity(due.len());
+    for secret_id in due {
+        match secret_store.delete_secret(&secret_id).await {
+            Ok(()) => deleted.push(secret_id),
+            Err(e) => tracing::warn!(
+                "Failed to delete retired storage secret {secret_id}: {}",
+                e.error
+            ),
+        }
+    }
+
+    C::delete_retired_storage_secrets(&deleted, t.transaction()).await?;
+    t.commit().await?;
+    Ok(deleted.len())
+}
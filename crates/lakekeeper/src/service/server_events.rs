@@ -0,0 +1,250 @@
+//! Lifecycle events of server instances.
+//!
+//! Events such as startups, shutdowns, applied migrations, crashed task workers and changed
+//! runtime settings are logged with the `lakekeeper::server_event` target and recorded in the
+//! catalog, where operators can list them via the management API to correlate incidents with
+//! changes of the servers. If `CONFIG.publish_server_events` is set, they are additionally
+//! published to the cloud event sinks.
+use std::{
+    sync::{LazyLock, OnceLock},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{
+    authn::{Actor, UserId},
+    event_publisher::{CloudEventsPublisher, EventMetadata},
+    Catalog, Result,
+};
+use crate::CONFIG;
+
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const MAX_EVENTS_PER_CLEANUP: i64 = 10_000;
+
+/// Identifies this process in server events, as several instances share the catalog.
+pub static INSTANCE_ID: LazyLock<Uuid> = LazyLock::new(Uuid::now_v7);
+
+static SERVER_EVENTS_TX: OnceLock<tokio::sync::mpsc::Sender<ServerEventsMessage>> = OnceLock::new();
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    strum_macros::Display,
+    utoipa::ToSchema,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+#[cfg_attr(feature = "sqlx-postgres", derive(sqlx::Type))]
+#[cfg_attr(
+    feature = "sqlx-postgres",
+    sqlx(type_name = "server_event_type", rename_all = "kebab-case")
+)]
+pub enum ServerEventType {
+    /// An instance started serving requests
+    Startup,
+    /// An instance stopped serving requests
+    Shutdown,
+    /// A database migration was applied
+    MigrationApplied,
+    /// A built-in task queue worker panicked or stopped and was restarted
+    TaskWorkerCrashed,
+    /// Runtime settings were changed via the management API
+    SettingsChanged,
+}
+
+impl ServerEventType {
+    /// Type of the cloud event, following the naming of catalog events.
+    fn cloud_event_type(self) -> &'static str {
+        match self {
+            ServerEventType::Startup => "serverStartup",
+            ServerEventType::Shutdown => "serverShutdown",
+            ServerEventType::MigrationApplied => "migrationApplied",
+            ServerEventType::TaskWorkerCrashed => "taskWorkerCrashed",
+            ServerEventType::SettingsChanged => "settingsChanged",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ServerEvent {
+    pub server_event_id: Uuid,
+    pub event_type: ServerEventType,
+    /// Process that emitted the event. Changes on every restart.
+    pub instance_id: Uuid,
+    /// Host name of the instance, such as the name of the pod
+    pub hostname: Option<String>,
+    /// Principal that caused the event, if any
+    pub principal: Option<String>,
+    /// Event specific details
+    pub details: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ServerEvent {
+    #[must_use]
+    pub fn new(
+        event_type: ServerEventType,
+        principal: Option<&UserId>,
+        details: serde_json::Value,
+    ) -> Self {
+        Self {
+            server_event_id: Uuid::now_v7(),
+            event_type,
+            instance_id: *INSTANCE_ID,
+            hostname: hostname::get()
+                .ok()
+                .map(|os| os.to_string_lossy().to_string()),
+            principal: principal.map(ToString::to_string),
+            details,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ServerEventsMessage {
+    Event(ServerEvent),
+    Shutdown,
+}
+
+/// Logs the event and passes it on to be recorded if this process serves requests.
+/// Never blocks, events are dropped if the recorder falls behind.
+pub(crate) fn emit_server_event(event: ServerEvent) {
+    tracing::info!(
+        target: "lakekeeper::server_event",
+        server_event_id = %event.server_event_id,
+        event_type = %event.event_type,
+        instance_id = %event.instance_id,
+        principal = event.principal.as_deref(),
+        details = %event.details,
+        "Server event"
+    );
+    if let Some(tx) = SERVER_EVENTS_TX.get() {
+        if let Err(e) = tx.try_send(ServerEventsMessage::Event(event)) {
+            tracing::warn!("Failed to record server event: {e}");
+        }
+    }
+}
+
+/// Records server events in the catalog and optionally publishes them as cloud events.
+pub struct ServerEventRecorder<C: Catalog> {
+    pub source: tokio::sync::mpsc::Receiver<ServerEventsMessage>,
+    pub catalog_state: C::State,
+    pub publisher: Option<CloudEventsPublisher>,
+}
+
+impl<C: Catalog> ServerEventRecorder<C> {
+    /// Creates the recorder of all events emitted by this process.
+    /// Only the first recorder of a process receives events.
+    #[must_use]
+    pub fn new(
+        catalog_state: C::State,
+        publisher: Option<CloudEventsPublisher>,
+    ) -> (Self, tokio::sync::mpsc::Sender<ServerEventsMessage>) {
+        let (tx, source) = tokio::sync::mpsc::channel(1000);
+        if SERVER_EVENTS_TX.set(tx.clone()).is_err() {
+            tracing::warn!("Server events are already recorded by another recorder");
+        }
+        (
+            Self {
+                source,
+                catalog_state,
+                publisher,
+            },
+            tx,
+        )
+    }
+
+    /// Runs until a [`ServerEventsMessage::Shutdown`] is received.
+    pub async fn run(mut self) {
+        while let Some(ServerEventsMessage::Event(event)) = self.source.recv().await {
+            if let Err(e) = C::record_server_event(&event, self.catalog_state.clone()).await {
+                tracing::warn!(?e, "Failed to record server event: {}", e.error);
+            }
+            if let Some(publisher) = &self.publisher {
+                publish_server_event(publisher, event).await;
+            }
+        }
+        tracing::info!("Exiting server event recorder");
+    }
+}
+
+async fn publish_server_event(publisher: &CloudEventsPublisher, event: ServerEvent) {
+    let actor = event
+        .principal
+        .as_deref()
+        .and_then(|p| UserId::try_from(p).ok())
+        .map_or(Actor::Anonymous, Actor::Principal);
+    let metadata = EventMetadata {
+        tabular_id: None,
+        warehouse_id: None,
+        name: event.instance_id.to_string(),
+        namespace: String::new(),
+        prefix: String::new(),
+        num_events: 1,
+        sequence_number: 0,
+        trace_id: event.server_event_id,
+        actor: serde_json::to_string(&actor).unwrap_or_default(),
+        impersonated_by: None,
+        authorization: None,
+    };
+    let typ = event.event_type.cloud_event_type();
+    let data = serde_json::to_value(&event).unwrap_or_default();
+    if let Err(e) = publisher
+        .publish(event.server_event_id, typ, data, metadata)
+        .await
+    {
+        tracing::warn!("Failed to publish `{typ}` event: {e}");
+    }
+}
+
+/// Infinitely running worker that deletes server events once their retention ended.
+pub async fn server_event_cleanup_worker<C: Catalog>(catalog_state: C::State) {
+    loop {
+        match delete_expired_events::<C>(catalog_state.clone()).await {
+            Ok(n) if n >= u64::try_from(MAX_EVENTS_PER_CLEANUP).unwrap_or(0) => continue,
+            Ok(0) => {}
+            Ok(n) => tracing::info!("Deleted {n} expired server events"),
+            Err(e) => {
+                tracing::error!(?e, "Failed to delete expired server events: {}", e.error);
+            }
+        }
+        tokio::time::sleep(CLEANUP_INTERVAL).await;
+    }
+}
+
+async fn delete_expired_events<C: Catalog>(catalog_state: C::State) -> Result<u64> {
+    let created_before = Utc::now() - CONFIG.server_event_retention_seconds;
+    C::delete_server_events(created_before, MAX_EVENTS_PER_CLEANUP, catalog_state).await
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_server_event_serialization() {
+        let event = ServerEvent::new(
+            ServerEventType::TaskWorkerCrashed,
+            None,
+            serde_json::json!({"queue-name": "tabular_expiration"}),
+        );
+        let value = serde_json::to_value(&event).unwrap();
+        assert_eq!(value["event-type"], "task-worker-crashed");
+        assert_eq!(value["instance-id"], INSTANCE_ID.to_string());
+        assert_eq!(value["details"]["queue-name"], "tabular_expiration");
+        assert_eq!(
+            ServerEventType::TaskWorkerCrashed.to_string(),
+            "task-worker-crashed"
+        );
+    }
+}
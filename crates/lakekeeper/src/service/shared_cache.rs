@@ -0,0 +1,427 @@
+//! Caches shared between all instances of Lakekeeper.
+//!
+//! Entries are kept in a local cache and in a [`CacheBackend`] shared by all instances.
+//! Lakekeeper ships with a Redis backend, which is used if `LAKEKEEPER__REDIS_URL` is set.
+//! Invalidations are removed from the backend and delivered to all instances, which drop
+//! their local entries. The caches are only used if a backend is configured, as per-instance
+//! caches of these results would be inconsistent between instances.
+use std::{
+    collections::HashMap,
+    sync::{Arc, LazyLock, OnceLock, RwLock},
+    time::{Duration, Instant},
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+use sha2::Digest;
+
+use crate::CONFIG;
+
+#[cfg(feature = "redis")]
+const KEY_PREFIX: &str = "lakekeeper:cache";
+#[cfg(feature = "redis")]
+const INVALIDATION_CHANNEL: &str = "lakekeeper:cache:invalidate";
+const LOCAL_MAX_CAPACITY: u64 = 10_000;
+
+static BACKEND: OnceLock<Arc<dyn CacheBackend>> = OnceLock::new();
+#[cfg(feature = "redis")]
+static REDIS: OnceLock<redis::aio::ConnectionManager> = OnceLock::new();
+
+type InvalidateLocalFn = Box<dyn Fn(&str) + Send + Sync>;
+
+/// Local invalidation of all shared caches by name, used for invalidations of other instances.
+static LOCAL_CACHES: LazyLock<RwLock<HashMap<&'static str, InvalidateLocalFn>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Storage of cache entries shared between all instances.
+///
+/// Entries are addressed by the name of the cache and a key. Backends are responsible
+/// for delivering invalidations to all instances, which must call [`invalidate_local`].
+/// Errors are not returned but logged, as a failing backend must not fail requests.
+#[async_trait::async_trait]
+pub trait CacheBackend: Send + Sync + std::fmt::Debug + 'static {
+    /// Returns the serialized entry and its remaining time to live.
+    async fn get(&self, cache: &str, key: &str) -> Option<(Vec<u8>, Duration)>;
+
+    /// Stores the serialized entry for the given time to live.
+    async fn set(&self, cache: &str, key: &str, value: Vec<u8>, ttl: Duration);
+
+    /// Removes the entry of the key and all entries with keys starting with `{key}:`,
+    /// and notifies all instances. An empty key removes all entries of the cache.
+    async fn invalidate(&self, cache: &str, key: &str);
+}
+
+/// Use a custom backend for shared caches.
+/// Must be called before [`connect`], as the backend can only be set once.
+///
+/// # Errors
+/// If a backend is already set.
+pub fn set_backend(backend: Arc<dyn CacheBackend>) -> anyhow::Result<()> {
+    BACKEND
+        .set(backend)
+        .map_err(|_| anyhow::anyhow!("Shared cache backend is already set"))
+}
+
+/// Returns true if a backend is configured and shared caches are used.
+pub(crate) fn is_enabled() -> bool {
+    BACKEND.get().is_some()
+}
+
+fn backend() -> Option<&'static Arc<dyn CacheBackend>> {
+    BACKEND.get()
+}
+
+/// Connection to Redis, if the Redis backend is used.
+#[cfg(feature = "redis")]
+pub(crate) fn redis_connection() -> Option<redis::aio::ConnectionManager> {
+    REDIS.get().cloned()
+}
+
+/// Hex encoded SHA-256 of a value, so that secrets such as tokens are not used as keys.
+pub(crate) fn hash_key(value: &str) -> String {
+    format!("{:x}", sha2::Sha256::digest(value.as_bytes()))
+}
+
+/// Drop local entries of a cache after another instance invalidated them.
+/// An empty key drops all entries of the cache.
+pub fn invalidate_local(cache: &str, key: &str) {
+    let caches = LOCAL_CACHES.read().expect("Shared cache registry poisoned");
+    if let Some(invalidate) = caches.get(cache) {
+        invalidate(key);
+    }
+}
+
+/// A cache whose entries and invalidations are shared between instances.
+/// Invalidating a key also invalidates all keys starting with `{key}:`.
+pub(crate) struct SharedCache<V> {
+    name: &'static str,
+    local: moka::future::Cache<String, (V, Instant)>,
+}
+
+impl<V> SharedCache<V>
+where
+    V: Clone + Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    pub(crate) fn new(name: &'static str) -> Self {
+        Self::with_local_capacity(name, LOCAL_MAX_CAPACITY)
+    }
+
+    /// Cache holding at most `capacity` entries locally, used for large values.
+    pub(crate) fn with_local_capacity(name: &'static str, capacity: u64) -> Self {
+        let local = moka::future::Cache::builder()
+            .max_capacity(capacity)
+            .time_to_live(shared_cache_ttl())
+            .support_invalidation_closures()
+            .build();
+        let invalidate_local = local.clone();
+        LOCAL_CACHES
+            .write()
+            .expect("Shared cache registry poisoned")
+            .insert(
+                name,
+                Box::new(move |prefix: &str| invalidate_local_entries(&invalidate_local, prefix)),
+            );
+        Self { name, local }
+    }
+
+    /// Returns the entry from the local cache or from the backend.
+    pub(crate) async fn get(&self, key: &str) -> Option<V> {
+        let backend = backend()?;
+        if let Some((value, expires_at)) = self.local.get(key).await {
+            if expires_at > Instant::now() {
+                return Some(value);
+            }
+            self.local.invalidate(key).await;
+        }
+
+        let (value, ttl) = backend.get(self.name, key).await?;
+        let value: V = serde_json::from_slice(&value)
+            .inspect_err(|e| tracing::warn!("Invalid entry in shared cache {}: {e}", self.name))
+            .ok()?;
+        self.local
+            .insert(key.to_string(), (value.clone(), Instant::now() + ttl))
+            .await;
+        Some(value)
+    }
+
+    /// Returns the cached entry, or loads and caches it with `init`.
+    /// Without a backend, every call loads the entry.
+    pub(crate) async fn try_get_with<E>(
+        &self,
+        key: String,
+        init: impl std::future::Future<Output = Result<V, E>>,
+    ) -> Result<V, E> {
+        if let Some(value) = self.get(&key).await {
+            return Ok(value);
+        }
+        let value = init.await?;
+        self.insert(key, value.clone(), None).await;
+        Ok(value)
+    }
+
+    /// Insert an entry that expires after the configured TTL or the given TTL, whichever
+    /// is shorter.
+    pub(crate) async fn insert(&self, key: String, value: V, max_ttl: Option<Duration>) {
+        let Some(backend) = backend() else {
+            return;
+        };
+        let ttl = max_ttl.map_or(shared_cache_ttl(), |t| t.min(shared_cache_ttl()));
+        if ttl.is_zero() {
+            return;
+        }
+        match serde_json::to_vec(&value) {
+            Ok(serialized) => backend.set(self.name, &key, serialized, ttl).await,
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to serialize entry of shared cache {}: {e}",
+                    self.name
+                );
+                return;
+            }
+        }
+        self.local.insert(key, (value, Instant::now() + ttl)).await;
+    }
+
+    /// Remove the entry of the key and all entries with keys starting with `{key}:`
+    /// on all instances.
+    pub(crate) async fn invalidate(&self, key: &str) {
+        let Some(backend) = backend() else {
+            return;
+        };
+        invalidate_local_entries(&self.local, key);
+        backend.invalidate(self.name, key).await;
+    }
+
+    /// Remove all entries on all instances.
+    pub(crate) async fn invalidate_all(&self) {
+        let Some(backend) = backend() else {
+            return;
+        };
+        self.local.invalidate_all();
+        backend.invalidate(self.name, "").await;
+    }
+}
+
+fn shared_cache_ttl() -> Duration {
+    Duration::from_secs(CONFIG.shared_cache_ttl_seconds)
+}
+
+fn invalidate_local_entries<V: Clone + Send + Sync + 'static>(
+    local: &moka::future::Cache<String, (V, Instant)>,
+    prefix: &str,
+) {
+    if prefix.is_empty() {
+        local.invalidate_all();
+        return;
+    }
+    let prefix = prefix.to_string();
+    let result = local.invalidate_entries_if(move |key, _| {
+        key.strip_prefix(prefix.as_str())
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with(':'))
+    });
+    if let Err(e) = result {
+        tracing::warn!("Failed to invalidate local cache entries: {e}");
+        local.invalidate_all();
+    }
+}
+
+/// Connect to Redis and listen for invalidations of other instances.
+/// Shared caches stay disabled if `LAKEKEEPER__REDIS_URL` is not set and no
+/// custom backend was set.
+///
+/// # Errors
+/// If Redis cannot be reached, or if the `redis` feature is not enabled but Redis is configured.
+#[cfg(feature = "redis")]
+pub async fn connect() -> anyhow::Result<()> {
+    use futures::StreamExt;
+
+    let Some(redis_url) = CONFIG.redis_url.as_ref() else {
+        if is_enabled() {
+            tracing::info!("Running with shared caches in a custom backend.");
+        } else {
+            tracing::info!("Running without shared caches.");
+        }
+        return Ok(());
+    };
+    let client = redis::Client::open(redis_url.as_str())?;
+    let connection = redis::aio::ConnectionManager::new(client.clone()).await?;
+    let mut pubsub = client.get_async_pubsub().await?;
+    pubsub.subscribe(INVALIDATION_CHANNEL).await?;
+    if REDIS.set(connection.clone()).is_err() {
+        tracing::warn!("Shared caches are already connected");
+        return Ok(());
+    }
+    set_backend(Arc::new(RedisCacheBackend { connection }))?;
+
+    tokio::task::spawn(async move {
+        let mut messages = pubsub.into_on_message();
+        while let Some(message) = messages.next().await {
+            match message.get_payload::<String>() {
+                Ok(payload) => {
+                    let (cache, key) = payload.split_once(':').unwrap_or((&payload, ""));
+                    invalidate_local(cache, key);
+                }
+                Err(e) => tracing::warn!("Received invalid cache invalidation: {e}"),
+            }
+        }
+        tracing::error!(
+            "Cache invalidation subscription ended, local caches expire after their TTL"
+        );
+    });
+    tracing::info!("Running with shared caches in Redis.");
+    Ok(())
+}
+
+/// Connect to Redis and listen for invalidations of other instances.
+/// Shared caches stay disabled if `LAKEKEEPER__REDIS_URL` is not set and no
+/// custom backend was set.
+///
+/// # Errors
+/// If the `redis` feature is not enabled but Redis is configured.
+#[cfg(not(feature = "redis"))]
+#[allow(clippy::unused_async)]
+pub async fn connect() -> anyhow::Result<()> {
+    if CONFIG.redis_url.is_some() {
+        anyhow::bail!(
+            "`LAKEKEEPER__REDIS_URL` is set, but Lakekeeper was built without the `redis` feature"
+        );
+    }
+    if is_enabled() {
+        tracing::info!("Running with shared caches in a custom backend.");
+    } else {
+        tracing::info!("Running without shared caches.");
+    }
+    Ok(())
+}
+
+/// Entries are stored as `lakekeeper:cache:{cache name}:{key}`.
+/// Invalidations are published as `{cache name}:{key}`.
+#[cfg(feature = "redis")]
+#[derive(Clone)]
+struct RedisCacheBackend {
+    connection: redis::aio::ConnectionManager,
+}
+
+#[cfg(feature = "redis")]
+impl std::fmt::Debug for RedisCacheBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedisCacheBackend").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "redis")]
+#[async_trait::async_trait]
+impl CacheBackend for RedisCacheBackend {
+    async fn get(&self, cache: &str, key: &str) -> Option<(Vec<u8>, Duration)> {
+        let redis_key = format!("{KEY_PREFIX}:{cache}:{key}");
+        let (value, ttl_ms): (Option<Vec<u8>>, i64) = redis::pipe()
+            .get(&redis_key)
+            .pttl(&redis_key)
+            .query_async(&mut self.connection.clone())
+            .await
+            .inspect_err(|e| tracing::warn!("Failed to read from shared cache {cache}: {e}"))
+            .ok()?;
+        let ttl = Duration::from_millis(u64::try_from(ttl_ms).ok()?);
+        Some((value?, ttl))
+    }
+
+    async fn set(&self, cache: &str, key: &str, value: Vec<u8>, ttl: Duration) {
+        use redis::AsyncCommands;
+
+        let ttl_ms = u64::try_from(ttl.as_millis()).unwrap_or(u64::MAX);
+        let result = self
+            .connection
+            .clone()
+            .pset_ex::<_, _, ()>(format!("{KEY_PREFIX}:{cache}:{key}"), value, ttl_ms)
+            .await;
+        if let Err(e) = result {
+            tracing::warn!("Failed to write to shared cache {cache}: {e}");
+        }
+    }
+
+    async fn invalidate(&self, cache: &str, key: &str) {
+        use futures::StreamExt;
+        use redis::AsyncCommands;
+
+        let (mut keys, pattern) = if key.is_empty() {
+            (vec![], format!("{KEY_PREFIX}:{cache}:*"))
+        } else {
+            let escaped = key
+                .chars()
+                .flat_map(|c| match c {
+                    '*' | '?' | '[' | ']' | '\\' => vec!['\\', c],
+                    c => vec![c],
+                })
+                .collect::<String>();
+            (
+                vec![format!("{KEY_PREFIX}:{cache}:{key}")],
+                format!("{KEY_PREFIX}:{cache}:{escaped}:*"),
+            )
+        };
+        let mut scan_connection = self.connection.clone();
+        match scan_connection.scan_match::<_, String>(&pattern).await {
+            Ok(matching) => keys.extend(matching.collect::<Vec<_>>().await),
+            Err(e) => tracing::error!("Failed to invalidate shared cache {cache}: {e}"),
+        }
+        let mut connection = self.connection.clone();
+        for chunk in keys.chunks(500) {
+            if let Err(e) = connection.del::<_, ()>(chunk).await {
+                tracing::error!("Failed to invalidate shared cache {cache}: {e}");
+            }
+        }
+        if let Err(e) = connection
+            .publish::<_, _, ()>(INVALIDATION_CHANNEL, format!("{cache}:{key}"))
+            .await
+        {
+            tracing::error!("Failed to publish invalidation of shared cache {cache}: {e}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_local_invalidation() {
+        let local = moka::future::Cache::builder()
+            .support_invalidation_closures()
+            .build();
+        let expires_at = Instant::now() + Duration::from_secs(60);
+        local.insert("a".to_string(), (0, expires_at)).await;
+        local.insert("a:1".to_string(), (1, expires_at)).await;
+        local.insert("a:2".to_string(), (2, expires_at)).await;
+        local.insert("ab:1".to_string(), (3, expires_at)).await;
+
+        invalidate_local_entries(&local, "a");
+        local.run_pending_tasks().await;
+        assert!(local.get("a").await.is_none());
+        assert!(local.get("a:1").await.is_none());
+        assert!(local.get("a:2").await.is_none());
+        assert_eq!(local.get("ab:1").await.map(|(v, _)| v), Some(3));
+
+        invalidate_local_entries(&local, "");
+        local.run_pending_tasks().await;
+        assert!(local.get("ab:1").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_local_by_name() {
+        let cache = SharedCache::<u32>::new("test-invalidate-local");
+        let expires_at = Instant::now() + Duration::from_secs(60);
+        cache.local.insert("a:1".to_string(), (1, expires_at)).await;
+        cache.local.insert("b:1".to_string(), (2, expires_at)).await;
+
+        invalidate_local("test-invalidate-local", "a");
+        invalidate_local("unknown-cache", "b");
+        cache.local.run_pending_tasks().await;
+        assert!(cache.local.get("a:1").await.is_none());
+        assert_eq!(cache.local.get("b:1").await.map(|(v, _)| v), Some(2));
+    }
+
+    #[test]
+    fn test_hash_key_hides_value() {
+        let key = hash_key("my-secret-token");
+        assert_eq!(key.len(), 64);
+        assert!(!key.contains("secret"));
+    }
+}
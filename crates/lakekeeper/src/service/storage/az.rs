@@ -58,6 +58,14 @@ pub struct AdlsProfile {
     pub host: Option<String>,
     /// The validity of the sas token in seconds. Default: 3600.
     pub sas_token_validity_seconds: Option<u64>,
+    /// Only vend user delegation SAS tokens, which are signed with a key obtained via Entra ID
+    /// instead of the storage account key.
+    /// If the storage credential is a shared access key, the delegation key is requested with
+    /// the managed identity of the Lakekeeper server, which requires
+    /// `LAKEKEEPER__ENABLE_AZURE_SYSTEM_CREDENTIALS`. The account key is then only used for
+    /// Lakekeeper's own access. Default: false.
+    #[serde(default)]
+    pub user_delegation_sas: bool,
     /// Allow alternative protocols such as `wasbs://` in locations.
     /// This is disabled by default. We do not recommend to use this setting
     /// except for migration of old tables via the register endpoint.
@@ -74,6 +82,7 @@ static HTTP_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(reqwest::Client::n
 static HTTP_CLIENT_ARC: LazyLock<Arc<reqwest::Client>> =
     LazyLock::new(|| Arc::new(HTTP_CLIENT.clone()));
 
+const DEFAULT_SAS_TOKEN_VALIDITY_SECONDS: u64 = 3600;
 const MAX_SAS_TOKEN_VALIDITY_SECONDS: u64 = 7 * 24 * 60 * 60;
 const MAX_SAS_TOKEN_VALIDITY_SECONDS_I64: i64 = 7 * 24 * 60 * 60;
 
@@ -230,29 +239,20 @@ impl AdlsProfile {
                 )
                 .await?
             }
+            AzCredential::SharedAccessKey { .. } if self.user_delegation_sas => {
+                self.sas_via_system_identity(table_location, permissions)
+                    .await?
+            }
             AzCredential::SharedAccessKey { key } => self.sas(
                 table_location,
                 permissions,
                 OffsetDateTime::now_utc()
-                    .saturating_sub(time::Duration::minutes(5))
-                    .saturating_add(time::Duration::days(7)),
+                    .saturating_add(time::Duration::seconds(self.sas_validity_seconds())),
                 azure_core::auth::Secret::new(key.to_string()),
             )?,
             AzCredential::AzureSystemIdentity {} => {
-                let identity: Arc<DefaultAzureCredential> = self.get_system_identity()?;
-                self.sas_via_delegation_key(
-                    table_location,
-                    StorageCredentials::token_credential(identity),
-                    permissions,
-                )
-                .await
-                .map_err(|e| {
-                    tracing::debug!("Failed to get azure system identity token: {e}",);
-                    CredentialsError::ShortTermCredential {
-                        reason: "Failed to get azure system identity token".to_string(),
-                        source: Some(Box::new(e)),
-                    }
-                })?
+                self.sas_via_system_identity(table_location, permissions)
+                    .await?
             }
         };
 
@@ -372,6 +372,37 @@ impl AdlsProfile {
         Ok(builder.build()?)
     }
 
+    /// Validity of vended SAS tokens in seconds, limited to the maximum of 7 days.
+    fn sas_validity_seconds(&self) -> i64 {
+        i64::try_from(
+            self.sas_token_validity_seconds
+                .unwrap_or(DEFAULT_SAS_TOKEN_VALIDITY_SECONDS),
+        )
+        .unwrap_or(MAX_SAS_TOKEN_VALIDITY_SECONDS_I64)
+        .clamp(0, MAX_SAS_TOKEN_VALIDITY_SECONDS_I64)
+    }
+
+    async fn sas_via_system_identity(
+        &self,
+        path: &Location,
+        permissions: StoragePermissions,
+    ) -> Result<String, CredentialsError> {
+        let identity: Arc<DefaultAzureCredential> = self.get_system_identity()?;
+        self.sas_via_delegation_key(
+            path,
+            StorageCredentials::token_credential(identity),
+            permissions,
+        )
+        .await
+        .map_err(|e| {
+            tracing::debug!("Failed to get azure system identity token: {e}",);
+            CredentialsError::ShortTermCredential {
+                reason: "Failed to get azure system identity token".to_string(),
+                source: Some(Box::new(e)),
+            }
+        })
+    }
+
     async fn sas_via_delegation_key(
         &self,
         path: &Location,
@@ -382,12 +413,10 @@ impl AdlsProfile {
 
         // allow for some clock drift
         let start = time::OffsetDateTime::now_utc() - time::Duration::minutes(5);
-        let max_validity_seconds = MAX_SAS_TOKEN_VALIDITY_SECONDS_I64;
-        // account for the 5 minutes offset from above
-        let sas_token_validity_seconds = self.sas_token_validity_seconds.unwrap_or(3600) + 300;
-        let clamped_validity_seconds = i64::try_from(sas_token_validity_seconds)
-            .unwrap_or(max_validity_seconds)
-            .clamp(0, max_validity_seconds);
+        // account for the 5 minutes offset from above, the delegation key
+        // may be valid for at most 7 days.
+        let clamped_validity_seconds =
+            (self.sas_validity_seconds() + 300).min(MAX_SAS_TOKEN_VALIDITY_SECONDS_I64);
 
         let delegation_key = client
             .get_user_deligation_key(
@@ -967,8 +996,10 @@ pub(crate) mod test {
             az::{
                 normalize_host, reduce_scheme_string, validate_account_name,
                 validate_filesystem_name, validate_path_segment, DEFAULT_AUTHORITY_HOST,
+                MAX_SAS_TOKEN_VALIDITY_SECONDS,
             },
-            AdlsLocation, AdlsProfile, StorageLocations, StorageProfile,
+            AdlsLocation, AdlsProfile, AzCredential, StorageLocations, StoragePermissions,
+            StorageProfile,
         },
         tabular_idents::TabularId,
         NamespaceId,
@@ -1017,6 +1048,7 @@ pub(crate) mod test {
                 authority_host: None,
                 host: None,
                 sas_token_validity_seconds: None,
+                user_delegation_sas: false,
                 allow_alternative_protocols: false,
             }
         }
@@ -1102,6 +1134,7 @@ pub(crate) mod test {
             authority_host: None,
             host: None,
             sas_token_validity_seconds: None,
+            user_delegation_sas: false,
             allow_alternative_protocols: false,
         };
 
@@ -1130,6 +1163,62 @@ pub(crate) mod test {
         );
     }
 
+    #[tokio::test]
+    async fn test_sas_validity_and_user_delegation() {
+        let mut profile = AdlsProfile {
+            filesystem: "filesystem".to_string(),
+            key_prefix: None,
+            account_name: "account".to_string(),
+            authority_host: None,
+            host: None,
+            sas_token_validity_seconds: Some(600),
+            user_delegation_sas: false,
+            allow_alternative_protocols: false,
+        };
+        let credential = AzCredential::SharedAccessKey {
+            key: "dGVzdC1hY2NvdW50LWtleQ==".to_string(),
+        };
+        let location =
+            Location::from_str("abfss://filesystem@account.dfs.core.windows.net/ns/table").unwrap();
+        let data_access = crate::api::iceberg::v1::DataAccess {
+            vended_credentials: true,
+            remote_signing: false,
+        };
+
+        assert_eq!(profile.sas_validity_seconds(), 600);
+        let config = profile
+            .generate_table_config(
+                data_access,
+                &location,
+                &credential,
+                StoragePermissions::Read,
+            )
+            .await
+            .unwrap();
+        assert!(config
+            .creds
+            .get_custom_prop("adls.sas-token.account.dfs.core.windows.net")
+            .is_some());
+
+        profile.sas_token_validity_seconds = None;
+        assert_eq!(profile.sas_validity_seconds(), 3600);
+        profile.sas_token_validity_seconds = Some(MAX_SAS_TOKEN_VALIDITY_SECONDS + 1);
+        assert!(profile.normalize().is_err());
+
+        // User delegation SAS for shared keys requires the identity of the server
+        profile.sas_token_validity_seconds = None;
+        profile.user_delegation_sas = true;
+        assert!(profile
+            .generate_table_config(
+                data_access,
+                &location,
+                &credential,
+                StoragePermissions::Read
+            )
+            .await
+            .is_err());
+    }
+
     #[test]
     fn test_parse_adls_location() {
         let cases = vec![
@@ -1332,6 +1421,7 @@ pub(crate) mod test {
             authority_host: None,
             host: None,
             sas_token_validity_seconds: None,
+            user_delegation_sas: false,
             allow_alternative_protocols: true,
         };
 
@@ -1351,6 +1441,7 @@ pub(crate) mod test {
             authority_host: None,
             host: None,
             sas_token_validity_seconds: None,
+            user_delegation_sas: false,
             allow_alternative_protocols: false,
         };
 
@@ -1383,6 +1474,7 @@ mod is_overlapping_location_tests {
             authority_host: authority_host.map(|url| url.parse().unwrap()),
             key_prefix: key_prefix.map(ToString::to_string),
             sas_token_validity_seconds: None,
+            user_delegation_sas: false,
             allow_alternative_protocols: false,
         }
     }
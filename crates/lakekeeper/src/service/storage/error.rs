@@ -111,6 +111,8 @@ pub enum FileIoError {
     FileIoCreationFailed(#[from] iceberg::Error),
     #[error(transparent)]
     Credentials(#[from] CredentialsError),
+    #[error("Storage type `{0}` is disabled in this Lakekeeper deployment")]
+    StorageDisabled(StorageType),
 }
 
 impl From<FileIoError> for IcebergErrorResponse {
@@ -129,6 +131,10 @@ impl From<FileIoError> for IcebergErrorResponse {
             )
             .into(),
             FileIoError::Credentials(cred_e) => cred_e.into(),
+            FileIoError::StorageDisabled(_) => {
+                ErrorModel::bad_request(err.to_string(), "StorageTypeDisabled", Some(Box::new(err)))
+                    .into()
+            }
         }
     }
 }
@@ -8,7 +8,8 @@ use std::{
 
 use base64::Engine;
 use google_cloud_auth::{
-    token::DefaultTokenSourceProvider, token_source::TokenSource as GCloudAuthTokenSource,
+    credentials::CredentialsFile, token::DefaultTokenSourceProvider,
+    token_source::TokenSource as GCloudAuthTokenSource,
 };
 use google_cloud_token::{TokenSource as GCloudTokenSource, TokenSourceProvider as _};
 use iceberg::io::{GCS_DISABLE_CONFIG_LOAD, GCS_DISABLE_VM_METADATA};
@@ -57,7 +58,8 @@ pub struct GcsProfile {
 #[serde(tag = "credential-type", rename_all = "kebab-case")]
 /// GCS Credentials
 ///
-/// Currently only supports Service Account Key
+/// Supports Service Account Keys, the identity of the system Lakekeeper runs as,
+/// and external accounts (Workload Identity Federation).
 /// Example of a key:
 /// ```json
 ///     {
@@ -87,6 +89,17 @@ pub enum GcsCredential {
     /// This can be a Compute Engine default service account or a user-assigned service account.
     #[schema(title = "GcsCredentialSystemIdentity")]
     GcpSystemIdentity {},
+
+    /// External Account (Workload Identity Federation)
+    ///
+    /// Credential configuration of a workload identity pool, as generated by
+    /// `gcloud iam workload-identity-pools create-cred-config`. The subject token is
+    /// read from the configured `credential_source`, for example a projected Kubernetes
+    /// service account token, and exchanged for a Google access token.
+    /// Requires GCP system identities to be enabled, as the credential source is
+    /// read from the environment Lakekeeper runs in.
+    #[schema(title = "GcsCredentialExternalAccount")]
+    ExternalAccount { config: GcsExternalAccountConfig },
 }
 
 #[derive(Redact, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
@@ -105,6 +118,72 @@ pub struct GcsServiceKey {
     pub universe_domain: String,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct GcsExternalAccountConfig {
+    /// Must be `external_account`.
+    pub r#type: String,
+    /// Audience of the workload identity pool provider, e.g.
+    /// `//iam.googleapis.com/projects/<number>/locations/global/workloadIdentityPools/<pool>/providers/<provider>`.
+    pub audience: String,
+    /// Type of the subject token, e.g. `urn:ietf:params:oauth:token-type:jwt`.
+    pub subject_token_type: String,
+    /// Security token service endpoint. Must point to `sts.googleapis.com`.
+    pub token_url: String,
+    /// URL to impersonate a service account with the federated token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service_account_impersonation_url: Option<String>,
+    /// Source of the subject token, either a `file` or a `url`.
+    #[schema(value_type = Object)]
+    pub credential_source: serde_json::Value,
+    /// Project used for quota and billing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quota_project_id: Option<String>,
+    /// Project number of workforce pools.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workforce_pool_user_project: Option<String>,
+}
+
+impl GcsExternalAccountConfig {
+    const TYPE: &'static str = "external_account";
+
+    /// Validate the external account configuration.
+    ///
+    /// # Errors
+    /// - Fails if GCP system identities are disabled.
+    /// - Fails if the type is not `external_account`.
+    /// - Fails if the token url does not point to the Google STS.
+    /// - Fails if the credential source is neither a file nor a url.
+    pub(crate) fn validate(&self) -> Result<(), CredentialsError> {
+        if !CONFIG.enable_gcp_system_credentials {
+            return Err(CredentialsError::Misconfiguration(
+                "GCP external account credentials require GCP system identities, which are disabled in this Lakekeeper deployment."
+                    .to_string(),
+            ));
+        }
+        if self.r#type != Self::TYPE {
+            return Err(CredentialsError::Misconfiguration(format!(
+                "Expected credential type '{}', got '{}'.",
+                Self::TYPE,
+                self.r#type
+            )));
+        }
+        let token_url = Url::parse(&self.token_url)
+            .map_err(|e| CredentialsError::Misconfiguration(format!("Invalid token_url: {e}")))?;
+        if token_url.scheme() != "https" || token_url.host_str() != STS_URL.host_str() {
+            return Err(CredentialsError::Misconfiguration(format!(
+                "token_url must point to '{STS_URL_STR}'."
+            )));
+        }
+        let source = self.credential_source.as_object();
+        if !source.is_some_and(|s| s.contains_key("file") || s.contains_key("url")) {
+            return Err(CredentialsError::Misconfiguration(
+                "credential_source must specify either a 'file' or a 'url'.".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
 pub(crate) enum TokenSource {
     GAuth(Arc<dyn GCloudAuthTokenSource>),
     Token(Arc<dyn GCloudTokenSource>),
@@ -158,6 +237,20 @@ impl GcsProfile {
                     .with_prop(GCS_DISABLE_VM_METADATA, "false")
                     .with_prop(GCS_DISABLE_CONFIG_LOAD, "false");
             }
+            GcsCredential::ExternalAccount { config } => {
+                config.validate()?;
+                builder = builder
+                    .with_prop(
+                        iceberg::io::GCS_CREDENTIALS_JSON,
+                        base64::prelude::BASE64_STANDARD.encode(
+                            serde_json::to_string(config)
+                                .map_err(CredentialsError::from)?
+                                .as_bytes(),
+                        ),
+                    )
+                    .with_prop(GCS_DISABLE_VM_METADATA, "true")
+                    .with_prop(GCS_DISABLE_CONFIG_LOAD, "true");
+            }
         }
 
         Ok(builder.build()?)
@@ -273,6 +366,28 @@ impl GcsProfile {
                 })?;
                 (TokenSource::Token(tsp.token_source()), tsp.project_id)
             }
+            GcsCredential::ExternalAccount { config: account } => {
+                account.validate()?;
+                let credentials_file = CredentialsFile::try_from(account)?;
+                let source = google_cloud_auth::project::create_token_source_from_credentials(
+                    &credentials_file,
+                    &config,
+                )
+                .await
+                .map_err(|e| {
+                    tracing::error!(
+                        "Failed to create gcp token source from external account: {:?}",
+                        e
+                    );
+                    CredentialsError::Misconfiguration(
+                        "Failed to create gcp token source from external account".to_string(),
+                    )
+                })?;
+                (
+                    TokenSource::GAuth(source.into()),
+                    account.quota_project_id.clone(),
+                )
+            }
         })
     }
 
@@ -469,7 +584,7 @@ fn validate_bucket_name(bucket: &str) -> Result<(), ValidationError> {
 pub(crate) mod test {
     use needs_env_var::needs_env_var;
 
-    use crate::service::storage::gcs::validate_bucket_name;
+    use crate::service::storage::gcs::{validate_bucket_name, GcsCredential};
 
     // Bucket names: Your bucket names must meet the following requirements:
     //
@@ -503,6 +618,35 @@ pub(crate) mod test {
         assert!(validate_bucket_name("a".repeat(64).as_str()).is_err()); // More than 63 characters
     }
 
+    #[test]
+    fn test_external_account_config_deserializes_gcloud_config() {
+        let config: GcsCredential = serde_json::from_value(serde_json::json!({
+            "credential-type": "external-account",
+            "config": {
+                "type": "external_account",
+                "audience": "//iam.googleapis.com/projects/123/locations/global/workloadIdentityPools/pool/providers/gke",
+                "subject_token_type": "urn:ietf:params:oauth:token-type:jwt",
+                "token_url": "https://sts.googleapis.com/v1/token",
+                "credential_source": {
+                    "file": "/var/run/service-account/token",
+                    "format": {"type": "text"}
+                }
+            }
+        }))
+        .unwrap();
+        let GcsCredential::ExternalAccount { config } = config else {
+            panic!("Expected external account credential");
+        };
+        assert!(config.service_account_impersonation_url.is_none());
+
+        let file = google_cloud_auth::credentials::CredentialsFile::try_from(&config).unwrap();
+        assert_eq!(file.tp, "external_account");
+        assert_eq!(file.audience, Some(config.audience.clone()));
+
+        // GCP system identities are disabled by default
+        assert!(config.validate().is_err());
+    }
+
     #[needs_env_var(TEST_GCS = 1)]
     pub(crate) mod cloud_tests {
         use crate::{
@@ -3,7 +3,11 @@ use iceberg_ext::configs::Location;
 use serde::{Deserialize, Serialize};
 
 use super::{TokenSource, HTTP_CLIENT, STS_URL};
-use crate::service::storage::{error::TableConfigError, gcs::GcsServiceKey, StoragePermissions};
+use crate::service::storage::{
+    error::{CredentialsError, TableConfigError},
+    gcs::{GcsExternalAccountConfig, GcsServiceKey},
+    StoragePermissions,
+};
 
 pub(crate) async fn downscope(
     token_source: TokenSource,
@@ -211,3 +215,15 @@ impl From<&GcsServiceKey> for CredentialsFile {
         }
     }
 }
+
+impl TryFrom<&GcsExternalAccountConfig> for CredentialsFile {
+    type Error = CredentialsError;
+
+    fn try_from(config: &GcsExternalAccountConfig) -> Result<Self, Self::Error> {
+        // `CredentialsFile` mirrors the credential configuration file generated by gcloud,
+        // so the configuration can be passed through as is.
+        serde_json::to_value(config)
+            .and_then(serde_json::from_value)
+            .map_err(CredentialsError::from)
+    }
+}
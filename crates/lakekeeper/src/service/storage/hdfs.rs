@@ -0,0 +1,768 @@
+#![allow(clippy::module_name_repetitions)]
+
+use std::{collections::HashMap, str::FromStr, sync::LazyLock};
+
+use iceberg_ext::configs::{table::TableProperties, Location};
+use reqwest::{Method, StatusCode};
+use serde::{Deserialize, Serialize};
+use url::Url;
+use veil::Redact;
+
+use super::StorageType;
+use crate::{
+    api::{
+        iceberg::{supported_endpoints, v1::DataAccess},
+        CatalogConfig,
+    },
+    service::storage::{
+        error::{CredentialsError, FileIoError, TableConfigError, UpdateError, ValidationError},
+        TableConfig,
+    },
+    WarehouseId, CONFIG,
+};
+
+// Redirects to data nodes are followed manually, as the body of a write
+// has to be sent to the data node.
+static HTTP_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .expect("failed to build WebHDFS http client")
+});
+const HDFS_SCHEME: &str = "hdfs";
+const WEBHDFS_PATH: &str = "/webhdfs/v1";
+
+#[derive(Debug, Eq, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct HdfsProfile {
+    /// Authority of `hdfs://` locations: The name service of an HA cluster
+    /// or `host:port` of the name node.
+    pub name_service: String,
+    /// URL of the WebHDFS REST API of the name node, e.g. `http://namenode:9870`,
+    /// or of an HttpFS gateway, e.g. `http://httpfs:14000`.
+    #[schema(value_type = String)]
+    pub webhdfs_url: Url,
+    /// Directory in the file system to use.
+    pub key_prefix: Option<String>,
+}
+
+#[derive(Redact, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(tag = "credential-type", rename_all = "kebab-case")]
+pub enum HdfsCredential {
+    /// Pseudo authentication for clusters without Kerberos.
+    /// Requests are made as `user-name`.
+    #[serde(rename_all = "kebab-case")]
+    #[schema(title = "HdfsCredentialSimple")]
+    Simple { user_name: String },
+    /// Hadoop delegation token, e.g. obtained with `hdfs fetchdt`.
+    #[serde(rename_all = "kebab-case")]
+    #[schema(title = "HdfsCredentialDelegationToken")]
+    DelegationToken {
+        #[redact]
+        token: String,
+    },
+    /// Kerberos authentication via SPNEGO.
+    ///
+    /// Uses the Kerberos credentials of the system Lakekeeper runs as, i.e. its credential cache
+    /// (`KRB5CCNAME`) or client keytab (`KRB5_CLIENT_KTNAME`). Requires Lakekeeper to be built
+    /// with the `kerberos` feature and HDFS Kerberos credentials to be enabled.
+    #[serde(rename_all = "kebab-case")]
+    #[schema(title = "HdfsCredentialKerberos")]
+    Kerberos {
+        /// Principal to authenticate as. Defaults to the default principal of the
+        /// credential cache or keytab.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        principal: Option<String>,
+    },
+}
+
+impl HdfsCredential {
+    fn validate(&self) -> Result<(), CredentialsError> {
+        if let HdfsCredential::Kerberos { .. } = self {
+            if !CONFIG.enable_hdfs_kerberos_credentials {
+                return Err(CredentialsError::Misconfiguration(
+                    "HDFS Kerberos credentials are disabled in this Lakekeeper deployment."
+                        .to_string(),
+                ));
+            }
+            if !cfg!(feature = "kerberos") {
+                return Err(CredentialsError::UnsupportedCredential(
+                    "HDFS Kerberos credentials require Lakekeeper to be built with the `kerberos` feature."
+                        .to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl HdfsProfile {
+    /// Create a new WebHDFS client for the profile.
+    ///
+    /// # Errors
+    /// Fails if the credential is not supported by this Lakekeeper deployment.
+    pub fn file_io(&self, credential: &HdfsCredential) -> Result<WebHdfsClient, FileIoError> {
+        credential.validate()?;
+        let mut endpoint = self.webhdfs_url.clone();
+        endpoint.set_path(&format!(
+            "{}{WEBHDFS_PATH}",
+            endpoint.path().trim_end_matches('/')
+        ));
+
+        Ok(WebHdfsClient {
+            endpoint,
+            name_service: self.name_service.clone(),
+            credential: credential.clone(),
+        })
+    }
+
+    /// Validate the HDFS profile.
+    ///
+    /// # Errors
+    /// - Fails if the name service or WebHDFS url is invalid.
+    /// - Fails if the key prefix contains empty, `.` or `..` segments.
+    /// - Fails if the credential is not supported by this Lakekeeper deployment.
+    pub(super) fn normalize(
+        &mut self,
+        credential: Option<&HdfsCredential>,
+    ) -> Result<(), ValidationError> {
+        self.normalize_name_service()?;
+        self.normalize_webhdfs_url()?;
+        self.normalize_key_prefix()?;
+        if let Some(credential) = credential {
+            credential.validate()?;
+        }
+
+        Ok(())
+    }
+
+    /// Update the profile with the other profile.
+    /// `name_service` and `key_prefix` must be the same, the WebHDFS url may change,
+    /// e.g. to point to a different name node or gateway.
+    ///
+    /// # Errors
+    /// Fails if the `name_service` or `key_prefix` is different.
+    pub fn update_with(self, other: Self) -> Result<Self, UpdateError> {
+        if self.name_service != other.name_service {
+            return Err(UpdateError::ImmutableField("name_service".to_string()));
+        }
+
+        if self.key_prefix != other.key_prefix {
+            return Err(UpdateError::ImmutableField("key_prefix".to_string()));
+        }
+
+        Ok(other)
+    }
+
+    #[must_use]
+    #[allow(clippy::unused_self)]
+    pub fn generate_catalog_config(&self, _: WarehouseId) -> CatalogConfig {
+        CatalogConfig {
+            defaults: HashMap::with_capacity(0),
+            overrides: HashMap::with_capacity(0),
+            endpoints: supported_endpoints().to_vec(),
+        }
+    }
+
+    /// Base Location for this storage profile.
+    ///
+    /// # Errors
+    /// Can fail for un-normalized profiles
+    pub fn base_location(&self) -> Result<Location, ValidationError> {
+        let prefix: Vec<String> = self
+            .key_prefix
+            .as_ref()
+            .map(|s| s.split('/').map(std::borrow::ToOwned::to_owned).collect())
+            .unwrap_or_default();
+        Location::from_str(&format!("{HDFS_SCHEME}://{}/", self.name_service))
+            .map(|mut l| {
+                l.extend(prefix.iter());
+                l
+            })
+            .map_err(|e| ValidationError::InvalidLocation {
+                reason: "Invalid HDFS location.".to_string(),
+                location: format!("{HDFS_SCHEME}://{}/", self.name_service),
+                source: Some(e.into()),
+                storage_type: StorageType::Hdfs,
+            })
+    }
+
+    /// Generate the table configuration for HDFS.
+    /// Lakekeeper does not vend credentials for HDFS. Clients access the
+    /// file system with their own Hadoop configuration.
+    #[allow(clippy::unused_self, clippy::unnecessary_wraps)]
+    pub(crate) fn generate_table_config(
+        &self,
+        data_access: DataAccess,
+    ) -> Result<TableConfig, TableConfigError> {
+        if data_access.vended_credentials || data_access.remote_signing {
+            tracing::debug!(
+                "Vended credentials and remote signing are not available for HDFS, returning an empty table config."
+            );
+        }
+        Ok(TableConfig {
+            config: TableProperties::default(),
+            creds: TableProperties::default(),
+        })
+    }
+
+    #[must_use]
+    /// Check whether the location of this storage profile is overlapping
+    /// with the given storage profile.
+    pub fn is_overlapping_location(&self, other: &Self) -> bool {
+        // Different file systems never overlap
+        if self.name_service != other.name_service {
+            return false;
+        }
+
+        if self.key_prefix == other.key_prefix {
+            return true;
+        }
+
+        match (&self.key_prefix, &other.key_prefix) {
+            (Some(key_prefix), Some(other_key_prefix)) => {
+                let kp1 = format!("{key_prefix}/");
+                let kp2 = format!("{other_key_prefix}/");
+                kp1.starts_with(&kp2) || kp2.starts_with(&kp1)
+            }
+            // If either has no key prefix, it can access the entire file system
+            (None, _) | (_, None) => true,
+        }
+    }
+
+    fn normalize_name_service(&mut self) -> Result<(), ValidationError> {
+        self.name_service = self.name_service.trim().to_lowercase();
+        if self.name_service.is_empty()
+            || !self
+                .name_service
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | ':'))
+        {
+            return Err(ValidationError::InvalidProfile {
+                source: None,
+                reason: "`name-service` must be a name service or `host:port` of the name node and can consist only of letters, numbers, dots (.), hyphens (-), underscores (_) and colons (:).".to_string(),
+                entity: "NameService".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    fn normalize_webhdfs_url(&mut self) -> Result<(), ValidationError> {
+        let url = &mut self.webhdfs_url;
+        if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() {
+            return Err(ValidationError::InvalidProfile {
+                source: None,
+                reason: "`webhdfs-url` must be a http or https url with a host.".to_string(),
+                entity: "WebHdfsUrl".to_string(),
+            });
+        }
+        if url.query().is_some() || url.fragment().is_some() {
+            return Err(ValidationError::InvalidProfile {
+                source: None,
+                reason: "`webhdfs-url` must not contain a query or fragment.".to_string(),
+                entity: "WebHdfsUrl".to_string(),
+            });
+        }
+        // The REST API path is appended by Lakekeeper
+        let path = url.path().trim_end_matches('/');
+        let path = path.strip_suffix(WEBHDFS_PATH).unwrap_or(path).to_string();
+        url.set_path(&path);
+        Ok(())
+    }
+
+    fn normalize_key_prefix(&mut self) -> Result<(), ValidationError> {
+        if let Some(key_prefix) = self.key_prefix.as_mut() {
+            *key_prefix = key_prefix.trim_matches('/').to_string();
+        }
+
+        if let Some(key_prefix) = self.key_prefix.as_ref() {
+            if key_prefix.is_empty() {
+                self.key_prefix = None;
+            }
+        }
+
+        if let Some(key_prefix) = self.key_prefix.as_ref() {
+            if key_prefix
+                .split('/')
+                .any(|segment| segment.is_empty() || segment == "." || segment == "..")
+            {
+                return Err(ValidationError::InvalidProfile {
+                    source: None,
+                    reason: "Storage Profile `key_prefix` must not contain empty, `.` or `..` path segments.".to_string(),
+                    entity: "key_prefix".to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum WebHdfsError {
+    #[error("Location '{location}' does not belong to HDFS name service '{name_service}'")]
+    InvalidLocation {
+        location: String,
+        name_service: String,
+    },
+    #[error("WebHDFS request failed: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("WebHDFS request failed with status {status}: {exception}: {message}")]
+    Remote {
+        status: StatusCode,
+        exception: String,
+        message: String,
+    },
+    #[error("Unexpected WebHDFS response: {0}")]
+    UnexpectedResponse(String),
+    #[error("Kerberos authentication failed: {0}")]
+    Kerberos(String),
+}
+
+/// A file listed by [`WebHdfsClient::list_files`].
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct WebHdfsFile {
+    pub(crate) location: String,
+    pub(crate) last_modified: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Client for the WebHDFS REST API, which is also served by HttpFS gateways.
+#[derive(Debug, Clone)]
+pub struct WebHdfsClient {
+    /// `<webhdfs-url>/webhdfs/v1`
+    endpoint: Url,
+    name_service: String,
+    credential: HdfsCredential,
+}
+
+#[derive(Debug, Deserialize)]
+struct RemoteExceptionResponse {
+    #[serde(rename = "RemoteException")]
+    remote_exception: RemoteException,
+}
+
+#[derive(Debug, Deserialize)]
+struct RemoteException {
+    exception: String,
+    #[serde(default)]
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BooleanResponse {
+    boolean: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListStatusResponse {
+    #[serde(rename = "FileStatuses")]
+    file_statuses: FileStatuses,
+}
+
+#[derive(Debug, Deserialize)]
+struct FileStatuses {
+    #[serde(rename = "FileStatus")]
+    file_status: Vec<FileStatus>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FileStatus {
+    path_suffix: String,
+    #[serde(rename = "type")]
+    typ: FileType,
+    modification_time: i64,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "UPPERCASE")]
+enum FileType {
+    File,
+    Directory,
+    Symlink,
+}
+
+impl WebHdfsClient {
+    /// Read the file at `location`.
+    pub(crate) async fn read(&self, location: &str) -> Result<Vec<u8>, WebHdfsError> {
+        let path = self.path(location)?;
+        let response = self.send(Method::GET, &path, "OPEN", &[]).await?;
+        // The name node redirects to a data node, HttpFS returns the content directly.
+        let response = match redirect_location(&response)? {
+            Some(data_node) => HTTP_CLIENT.get(data_node).send().await?,
+            None => response,
+        };
+        Ok(check_status(response).await?.bytes().await?.to_vec())
+    }
+
+    /// Write `content` to `location`, replacing an existing file.
+    /// Parent directories are created.
+    pub(crate) async fn write(&self, location: &str, content: Vec<u8>) -> Result<(), WebHdfsError> {
+        let path = self.path(location)?;
+        let response = self
+            .send(Method::PUT, &path, "CREATE", &[("overwrite", "true")])
+            .await?;
+        let data_node = redirect_location(&response)?.ok_or_else(|| {
+            WebHdfsError::UnexpectedResponse(format!(
+                "Expected a redirect to a data node for CREATE, got status {}",
+                response.status()
+            ))
+        })?;
+        let response = HTTP_CLIENT
+            .put(data_node)
+            .header(reqwest::header::CONTENT_TYPE, "application/octet-stream")
+            .body(content)
+            .send()
+            .await?;
+        check_status(response).await?;
+        Ok(())
+    }
+
+    /// Delete the file or directory at `location`.
+    /// Deleting a location that does not exist succeeds.
+    pub(crate) async fn delete(&self, location: &str, recursive: bool) -> Result<(), WebHdfsError> {
+        let path = self.path(location)?;
+        let recursive = if recursive { "true" } else { "false" };
+        let response = self
+            .send(Method::DELETE, &path, "DELETE", &[("recursive", recursive)])
+            .await?;
+        let deleted: BooleanResponse = check_status(response).await?.json().await?;
+        if !deleted.boolean {
+            tracing::debug!("Nothing to delete at {location}");
+        }
+        Ok(())
+    }
+
+    /// Recursively list all files below `location`. Directories are omitted.
+    pub(crate) async fn list_files(
+        &self,
+        location: &str,
+    ) -> Result<Vec<WebHdfsFile>, WebHdfsError> {
+        let root =
+            Url::parse(location.trim_end_matches('/')).map_err(|_| self.invalid(location))?;
+        let mut directories = vec![root];
+        let mut files = Vec::new();
+
+        while let Some(directory) = directories.pop() {
+            let path = self.path(directory.as_str())?;
+            let response = self.send(Method::GET, &path, "LISTSTATUS", &[]).await?;
+            if response.status() == StatusCode::NOT_FOUND {
+                continue;
+            }
+            let statuses: ListStatusResponse = check_status(response).await?.json().await?;
+
+            for status in statuses.file_statuses.file_status {
+                let mut child = directory.clone();
+                // An empty suffix is returned if `location` is a file
+                if !status.path_suffix.is_empty() {
+                    child
+                        .path_segments_mut()
+                        .map_err(|()| self.invalid(location))?
+                        .pop_if_empty()
+                        .push(&status.path_suffix);
+                }
+                match status.typ {
+                    FileType::Directory => directories.push(child),
+                    FileType::File | FileType::Symlink => files.push(WebHdfsFile {
+                        location: child.to_string(),
+                        last_modified: chrono::DateTime::from_timestamp_millis(
+                            status.modification_time,
+                        ),
+                    }),
+                }
+            }
+        }
+
+        files.sort_by(|a, b| a.location.cmp(&b.location));
+        Ok(files)
+    }
+
+    /// Path of an `hdfs://` location in the file system of this client.
+    fn path(&self, location: &str) -> Result<String, WebHdfsError> {
+        let url = Url::parse(location).map_err(|_| self.invalid(location))?;
+        if url.scheme() != HDFS_SCHEME || !url.authority().eq_ignore_ascii_case(&self.name_service)
+        {
+            return Err(self.invalid(location));
+        }
+        match url.path() {
+            "" => Ok("/".to_string()),
+            path => Ok(path.to_string()),
+        }
+    }
+
+    fn invalid(&self, location: &str) -> WebHdfsError {
+        WebHdfsError::InvalidLocation {
+            location: location.to_string(),
+            name_service: self.name_service.clone(),
+        }
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        op: &str,
+        params: &[(&str, &str)],
+    ) -> Result<reqwest::Response, WebHdfsError> {
+        let mut url = self.endpoint.clone();
+        url.set_path(&format!("{}{path}", self.endpoint.path()));
+        {
+            let mut query = url.query_pairs_mut();
+            query.append_pair("op", op);
+            for (key, value) in params {
+                query.append_pair(key, value);
+            }
+            match &self.credential {
+                HdfsCredential::Simple { user_name } => {
+                    query.append_pair("user.name", user_name);
+                }
+                HdfsCredential::DelegationToken { token } => {
+                    query.append_pair("delegation", token);
+                }
+                HdfsCredential::Kerberos { .. } => {}
+            }
+        }
+
+        let mut request = HTTP_CLIENT.request(method, url);
+        if let HdfsCredential::Kerberos { principal } = &self.credential {
+            let host = self.endpoint.host_str().unwrap_or_default().to_string();
+            let principal = principal.clone();
+            // Acquiring a service ticket may block on the KDC.
+            let token =
+                tokio::task::spawn_blocking(move || spnego_token(&host, principal.as_deref()))
+                    .await
+                    .map_err(|e| WebHdfsError::Kerberos(e.to_string()))??;
+            request = request.header(reqwest::header::AUTHORIZATION, format!("Negotiate {token}"));
+        }
+
+        Ok(request.send().await?)
+    }
+}
+
+fn redirect_location(response: &reqwest::Response) -> Result<Option<Url>, WebHdfsError> {
+    if !response.status().is_redirection() {
+        return Ok(None);
+    }
+    let location = response
+        .headers()
+        .get(reqwest::header::LOCATION)
+        .and_then(|l| l.to_str().ok())
+        .ok_or_else(|| {
+            WebHdfsError::UnexpectedResponse("Redirect without a location header".to_string())
+        })?;
+    Url::parse(location)
+        .map(Some)
+        .map_err(|e| WebHdfsError::UnexpectedResponse(format!("Invalid redirect location: {e}")))
+}
+
+async fn check_status(response: reqwest::Response) -> Result<reqwest::Response, WebHdfsError> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body = response.text().await.unwrap_or_default();
+    Err(parse_remote_exception(status, &body))
+}
+
+fn parse_remote_exception(status: StatusCode, body: &str) -> WebHdfsError {
+    match serde_json::from_str::<RemoteExceptionResponse>(body) {
+        Ok(RemoteExceptionResponse {
+            remote_exception: RemoteException { exception, message },
+        }) => WebHdfsError::Remote {
+            status,
+            exception,
+            message,
+        },
+        Err(_) => WebHdfsError::Remote {
+            status,
+            exception: "Unknown".to_string(),
+            message: body.chars().take(500).collect(),
+        },
+    }
+}
+
+/// Base64 encoded SPNEGO token for the `HTTP` service of `host`.
+#[cfg(feature = "kerberos")]
+fn spnego_token(host: &str, principal: Option<&str>) -> Result<String, WebHdfsError> {
+    use base64::Engine as _;
+    use libgssapi::{
+        context::{ClientCtx, CtxFlags},
+        credential::{Cred, CredUsage},
+        name::Name,
+        oid::{OidSet, GSS_MECH_KRB5, GSS_NT_HOSTBASED_SERVICE, GSS_NT_KRB5_PRINCIPAL},
+    };
+
+    let kerberos_error = |e: libgssapi::error::Error| WebHdfsError::Kerberos(e.to_string());
+
+    let mut mechs = OidSet::new().map_err(kerberos_error)?;
+    mechs.add(&GSS_MECH_KRB5).map_err(kerberos_error)?;
+    let client = principal
+        .map(|p| Name::new(p.as_bytes(), Some(&GSS_NT_KRB5_PRINCIPAL)))
+        .transpose()
+        .map_err(kerberos_error)?;
+    let credential = Cred::acquire(client.as_ref(), None, CredUsage::Initiate, Some(&mechs))
+        .map_err(kerberos_error)?;
+    let service = Name::new(
+        format!("HTTP@{host}").as_bytes(),
+        Some(&GSS_NT_HOSTBASED_SERVICE),
+    )
+    .map_err(kerberos_error)?;
+
+    let mut context = ClientCtx::new(
+        Some(credential),
+        service,
+        CtxFlags::GSS_C_MUTUAL_FLAG,
+        Some(&GSS_MECH_KRB5),
+    );
+    let token = context
+        .step(None, None)
+        .map_err(kerberos_error)?
+        .ok_or_else(|| WebHdfsError::Kerberos("No SPNEGO token was generated".to_string()))?;
+    Ok(base64::prelude::BASE64_STANDARD.encode(&*token))
+}
+
+#[cfg(not(feature = "kerberos"))]
+fn spnego_token(_host: &str, _principal: Option<&str>) -> Result<String, WebHdfsError> {
+    Err(WebHdfsError::Kerberos(
+        "Lakekeeper was built without the `kerberos` feature".to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn profile() -> HdfsProfile {
+        HdfsProfile {
+            name_service: "MyCluster".to_string(),
+            webhdfs_url: "http://namenode:9870/webhdfs/v1/".parse().unwrap(),
+            key_prefix: Some("/warehouse/lakekeeper/".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_normalize() {
+        let mut profile = profile();
+        profile.normalize(None).unwrap();
+        assert_eq!(profile.name_service, "mycluster");
+        assert_eq!(profile.webhdfs_url.as_str(), "http://namenode:9870/");
+        assert_eq!(profile.key_prefix.as_deref(), Some("warehouse/lakekeeper"));
+        assert_eq!(
+            profile.base_location().unwrap().to_string(),
+            "hdfs://mycluster/warehouse/lakekeeper"
+        );
+
+        let mut invalid = HdfsProfile {
+            key_prefix: Some("warehouse/../other".to_string()),
+            ..profile.clone()
+        };
+        invalid.normalize(None).unwrap_err();
+
+        let mut invalid = HdfsProfile {
+            name_service: "my cluster".to_string(),
+            ..profile.clone()
+        };
+        invalid.normalize(None).unwrap_err();
+
+        let mut invalid = HdfsProfile {
+            webhdfs_url: "ftp://namenode:9870".parse().unwrap(),
+            ..profile
+        };
+        invalid.normalize(None).unwrap_err();
+    }
+
+    #[test]
+    fn test_update_keeps_location() {
+        let mut profile = profile();
+        profile.normalize(None).unwrap();
+
+        let moved = HdfsProfile {
+            webhdfs_url: "https://httpfs:14000".parse().unwrap(),
+            ..profile.clone()
+        };
+        assert_eq!(profile.clone().update_with(moved.clone()).unwrap(), moved);
+
+        let other_prefix = HdfsProfile {
+            key_prefix: Some("other".to_string()),
+            ..profile.clone()
+        };
+        profile.update_with(other_prefix).unwrap_err();
+    }
+
+    #[test]
+    fn test_is_overlapping_location() {
+        let mut profile = profile();
+        profile.normalize(None).unwrap();
+
+        let nested = HdfsProfile {
+            key_prefix: Some("warehouse/lakekeeper/nested".to_string()),
+            ..profile.clone()
+        };
+        let sibling = HdfsProfile {
+            key_prefix: Some("warehouse/lakekeeper-2".to_string()),
+            ..profile.clone()
+        };
+        let other_cluster = HdfsProfile {
+            name_service: "othercluster".to_string(),
+            ..profile.clone()
+        };
+        assert!(profile.is_overlapping_location(&nested));
+        assert!(!profile.is_overlapping_location(&sibling));
+        assert!(!profile.is_overlapping_location(&other_cluster));
+    }
+
+    #[test]
+    fn test_client_paths() {
+        let mut profile = profile();
+        profile.normalize(None).unwrap();
+        let client = profile
+            .file_io(&HdfsCredential::Simple {
+                user_name: "hive".to_string(),
+            })
+            .unwrap();
+        assert_eq!(client.endpoint.as_str(), "http://namenode:9870/webhdfs/v1");
+        assert_eq!(
+            client
+                .path("hdfs://mycluster/warehouse/lakekeeper/t/metadata/00000.metadata.json")
+                .unwrap(),
+            "/warehouse/lakekeeper/t/metadata/00000.metadata.json"
+        );
+        client
+            .path("hdfs://othercluster/warehouse/lakekeeper/t")
+            .unwrap_err();
+        client
+            .path("s3://mycluster/warehouse/lakekeeper/t")
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_parse_responses() {
+        let error = parse_remote_exception(
+            StatusCode::NOT_FOUND,
+            r#"{"RemoteException":{"exception":"FileNotFoundException","javaClassName":"java.io.FileNotFoundException","message":"File does not exist: /foo"}}"#,
+        );
+        assert!(matches!(
+            error,
+            WebHdfsError::Remote { ref exception, .. } if exception == "FileNotFoundException"
+        ));
+
+        let list: ListStatusResponse = serde_json::from_str(
+            r#"{"FileStatuses":{"FileStatus":[
+                {"accessTime":0,"blockSize":0,"group":"supergroup","length":0,"modificationTime":1320173277227,"owner":"webuser","pathSuffix":"metadata","permission":"777","replication":0,"type":"DIRECTORY"},
+                {"accessTime":1320171722771,"blockSize":33554432,"group":"supergroup","length":24930,"modificationTime":1320171722771,"owner":"webuser","pathSuffix":"a.patch","permission":"644","replication":1,"type":"FILE"}
+            ]}}"#,
+        )
+        .unwrap();
+        let statuses = list.file_statuses.file_status;
+        assert_eq!(statuses.len(), 2);
+        assert_eq!(statuses[0].typ, FileType::Directory);
+        assert_eq!(statuses[1].path_suffix, "a.patch");
+    }
+
+    #[test]
+    fn test_redact_delegation_token() {
+        let credential = HdfsCredential::DelegationToken {
+            token: "HAAEaGl2ZQRoaXZl".to_string(),
+        };
+        assert!(!format!("{credential:?}").contains("HAAEaGl2ZQRoaXZl"));
+    }
+}
@@ -0,0 +1,252 @@
+#![allow(clippy::module_name_repetitions)]
+
+use std::{collections::HashMap, str::FromStr};
+
+use iceberg::io::{FileIO, FileIOBuilder};
+use iceberg_ext::configs::{table::TableProperties, Location};
+use serde::{Deserialize, Serialize};
+
+use super::StorageType;
+use crate::{
+    api::{
+        iceberg::{supported_endpoints, v1::DataAccess},
+        CatalogConfig,
+    },
+    service::storage::{
+        error::{CredentialsError, FileIoError, TableConfigError, UpdateError, ValidationError},
+        StorageCredential, TableConfig,
+    },
+    WarehouseId, CONFIG,
+};
+
+const FILE_SCHEME: &str = "file";
+
+#[derive(Debug, Eq, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct LocalProfile {
+    /// Absolute path of the directory to use in the file system of the Lakekeeper server,
+    /// e.g. `/var/lib/lakekeeper/warehouse`.
+    /// Query engines must be able to access the directory under the same path.
+    pub path: String,
+}
+
+impl LocalProfile {
+    /// Create a new `FileIO` for the local file system.
+    ///
+    /// # Errors
+    /// Fails if local filesystem storage is disabled in this deployment.
+    pub fn file_io(&self) -> Result<FileIO, FileIoError> {
+        require_local_filesystem_storage_enabled()?;
+        Ok(FileIOBuilder::new(FILE_SCHEME).build()?)
+    }
+
+    /// Validate the local profile.
+    ///
+    /// # Errors
+    /// - Fails if local filesystem storage is disabled in this deployment.
+    /// - Fails if the path is not absolute or contains `.` or `..` segments.
+    /// - Fails if a credential is provided.
+    pub(super) fn normalize(
+        &mut self,
+        credential: Option<&StorageCredential>,
+    ) -> Result<(), ValidationError> {
+        require_local_filesystem_storage_enabled()?;
+        if credential.is_some() {
+            return Err(CredentialsError::UnsupportedCredential(
+                "Local filesystem storage does not use credentials.".to_string(),
+            )
+            .into());
+        }
+        self.normalize_path()
+    }
+
+    /// Update the profile with the other profile.
+    ///
+    /// # Errors
+    /// Fails if the `path` is different.
+    pub fn update_with(self, other: Self) -> Result<Self, UpdateError> {
+        if self.path != other.path {
+            return Err(UpdateError::ImmutableField("path".to_string()));
+        }
+
+        Ok(other)
+    }
+
+    #[must_use]
+    #[allow(clippy::unused_self)]
+    pub fn generate_catalog_config(&self, _: WarehouseId) -> CatalogConfig {
+        CatalogConfig {
+            defaults: HashMap::with_capacity(0),
+            overrides: HashMap::with_capacity(0),
+            endpoints: supported_endpoints().to_vec(),
+        }
+    }
+
+    /// Base Location for this storage profile.
+    ///
+    /// # Errors
+    /// Can fail for un-normalized profiles
+    pub fn base_location(&self) -> Result<Location, ValidationError> {
+        let location = format!("{FILE_SCHEME}://{}/", self.path);
+        Location::from_str(&location).map_err(|e| ValidationError::InvalidLocation {
+            reason: "Invalid local filesystem location.".to_string(),
+            location,
+            source: Some(e.into()),
+            storage_type: StorageType::Local,
+        })
+    }
+
+    /// Generate the table configuration for the local file system.
+    /// Query engines access the file system directly, there are no credentials to vend.
+    #[allow(clippy::unused_self, clippy::unnecessary_wraps)]
+    pub(crate) fn generate_table_config(
+        &self,
+        data_access: DataAccess,
+    ) -> Result<TableConfig, TableConfigError> {
+        if data_access.vended_credentials || data_access.remote_signing {
+            tracing::debug!(
+                "Vended credentials and remote signing are not available for local filesystem storage, returning an empty table config."
+            );
+        }
+        Ok(TableConfig {
+            config: TableProperties::default(),
+            creds: TableProperties::default(),
+        })
+    }
+
+    #[must_use]
+    /// Check whether the location of this storage profile is overlapping
+    /// with the given storage profile.
+    pub fn is_overlapping_location(&self, other: &Self) -> bool {
+        let p1 = format!("{}/", self.path);
+        let p2 = format!("{}/", other.path);
+        p1.starts_with(&p2) || p2.starts_with(&p1)
+    }
+
+    fn normalize_path(&mut self) -> Result<(), ValidationError> {
+        let path = self.path.trim();
+        let Some(relative_path) = path.strip_prefix('/') else {
+            return Err(ValidationError::InvalidProfile {
+                source: None,
+                reason: "Storage Profile `path` must be an absolute path.".to_string(),
+                entity: "Path".to_string(),
+            });
+        };
+        let relative_path = relative_path.trim_end_matches('/');
+
+        if relative_path.is_empty() {
+            return Err(ValidationError::InvalidProfile {
+                source: None,
+                reason: "Storage Profile `path` must not be the root directory.".to_string(),
+                entity: "Path".to_string(),
+            });
+        }
+
+        for segment in relative_path.split('/') {
+            if segment.is_empty() || segment == "." || segment == ".." {
+                return Err(ValidationError::InvalidProfile {
+                    source: None,
+                    reason:
+                        "Storage Profile `path` must not contain empty, `.` or `..` path segments."
+                            .to_string(),
+                    entity: "Path".to_string(),
+                });
+            }
+            if !segment
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+            {
+                return Err(ValidationError::InvalidProfile {
+                    source: None,
+                    reason: "Storage Profile `path` can consist only of letters, numbers, dots (.), hyphens (-), underscores (_) and slashes (/).".to_string(),
+                    entity: "Path".to_string(),
+                });
+            }
+        }
+
+        self.path = format!("/{relative_path}");
+        Ok(())
+    }
+}
+
+fn require_local_filesystem_storage_enabled() -> Result<(), FileIoError> {
+    if CONFIG.enable_local_filesystem_storage {
+        Ok(())
+    } else {
+        Err(FileIoError::StorageDisabled(StorageType::Local))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_normalize_path() {
+        let mut profile = LocalProfile {
+            path: " /var/lib/lakekeeper/warehouse/ ".to_string(),
+        };
+        profile.normalize_path().unwrap();
+        assert_eq!(profile.path, "/var/lib/lakekeeper/warehouse");
+        assert_eq!(
+            profile.base_location().unwrap().to_string(),
+            "file:///var/lib/lakekeeper/warehouse/"
+        );
+
+        for path in [
+            "",
+            "/",
+            "var/lib/lakekeeper",
+            "/var/lib/../etc",
+            "/var/./lib",
+            "/var//lib",
+            "/var/lib/lake keeper",
+        ] {
+            let mut profile = LocalProfile {
+                path: path.to_string(),
+            };
+            profile.normalize_path().unwrap_err();
+        }
+    }
+
+    #[test]
+    fn test_update_keeps_path() {
+        let profile = LocalProfile {
+            path: "/data/warehouse".to_string(),
+        };
+        assert_eq!(
+            profile.clone().update_with(profile.clone()).unwrap(),
+            profile
+        );
+        profile
+            .update_with(LocalProfile {
+                path: "/data/other".to_string(),
+            })
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_is_overlapping_location() {
+        let profile = LocalProfile {
+            path: "/data/warehouse".to_string(),
+        };
+        let nested = LocalProfile {
+            path: "/data/warehouse/nested".to_string(),
+        };
+        let sibling = LocalProfile {
+            path: "/data/warehouse-2".to_string(),
+        };
+        assert!(profile.is_overlapping_location(&nested));
+        assert!(nested.is_overlapping_location(&profile));
+        assert!(!profile.is_overlapping_location(&sibling));
+    }
+
+    #[test]
+    fn test_disabled_by_default() {
+        let mut profile = LocalProfile {
+            path: "/data/warehouse".to_string(),
+        };
+        profile.normalize(None).unwrap_err();
+        profile.file_io().unwrap_err();
+    }
+}
@@ -0,0 +1,624 @@
+#![allow(clippy::module_name_repetitions)]
+
+use std::{collections::HashMap, str::FromStr, sync::LazyLock};
+
+use iceberg_ext::configs::{
+    table::{client, custom, oss, s3, TableProperties},
+    Location,
+};
+use serde::{Deserialize, Serialize};
+use url::Url;
+use veil::Redact;
+
+use super::StorageType;
+use crate::{
+    api::{
+        iceberg::{supported_endpoints, v1::DataAccess},
+        CatalogConfig,
+    },
+    service::storage::{
+        error::{CredentialsError, FileIoError, TableConfigError, UpdateError, ValidationError},
+        StoragePermissions, TableConfig,
+    },
+    WarehouseId,
+};
+
+mod sts;
+
+static HTTP_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(reqwest::Client::new);
+const OSS_SCHEME: &str = "oss";
+const MIN_STS_TOKEN_VALIDITY_SECONDS: u64 = 900;
+const MAX_STS_TOKEN_VALIDITY_SECONDS: u64 = 43200;
+
+#[derive(Debug, Eq, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct OssProfile {
+    /// Name of the OSS bucket
+    pub bucket: String,
+    /// Region ID of the bucket, e.g. `cn-hangzhou`.
+    pub region: String,
+    /// Endpoint of the S3-compatible OSS API.
+    /// Defaults to the public endpoint of the region: `https://oss-<region>.aliyuncs.com`.
+    /// OSS only supports virtual-hosted style requests, the bucket is added by clients.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub endpoint: Option<Url>,
+    /// Subpath in the bucket to use.
+    pub key_prefix: Option<String>,
+    /// Vend temporary credentials obtained via Aliyun STS `AssumeRole`.
+    #[serde(default)]
+    pub sts_enabled: bool,
+    /// ARN of the RAM role to assume for vended credentials,
+    /// e.g. `acs:ram::1234567890123456:role/lakekeeper-oss`.
+    /// Required if `sts-enabled` is true.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sts_role_arn: Option<String>,
+    /// Endpoint of Aliyun STS.
+    /// Defaults to the endpoint of the region: `https://sts.<region>.aliyuncs.com`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub sts_endpoint: Option<Url>,
+    /// The validity of the STS tokens in seconds. Must be between 900 and
+    /// the maximum session duration of the role (at most 43200). Default is 3600.
+    #[serde(default = "fn_3600")]
+    pub sts_token_validity_seconds: u64,
+}
+
+#[derive(Redact, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(tag = "credential-type", rename_all = "kebab-case")]
+pub enum OssCredential {
+    /// AccessKey pair of a RAM user.
+    #[serde(rename_all = "kebab-case")]
+    #[schema(title = "OssCredentialAccessKey")]
+    AccessKey {
+        access_key_id: String,
+        #[redact]
+        access_key_secret: String,
+    },
+}
+
+impl OssCredential {
+    fn access_key(&self) -> (&str, &str) {
+        match self {
+            OssCredential::AccessKey {
+                access_key_id,
+                access_key_secret,
+            } => (access_key_id, access_key_secret),
+        }
+    }
+}
+
+impl OssProfile {
+    /// Create a new `FileIO` for OSS, using its S3-compatible API.
+    ///
+    /// # Errors
+    /// Fails if the `FileIO` cannot be created.
+    pub fn file_io(&self, credential: &OssCredential) -> Result<iceberg::io::FileIO, FileIoError> {
+        let (access_key_id, access_key_secret) = credential.access_key();
+        Ok(iceberg::io::FileIOBuilder::new("s3")
+            .with_client(HTTP_CLIENT.clone())
+            .with_prop(iceberg::io::S3_REGION, self.region.clone())
+            .with_prop(iceberg::io::S3_ENDPOINT, self.endpoint().to_string())
+            .with_prop(iceberg::io::S3_PATH_STYLE_ACCESS, "false")
+            .with_prop(iceberg::io::S3_ACCESS_KEY_ID, access_key_id)
+            .with_prop(iceberg::io::S3_SECRET_ACCESS_KEY, access_key_secret)
+            .with_prop(iceberg::io::S3_DISABLE_CONFIG_LOAD, "true")
+            .with_prop(iceberg::io::S3_DISABLE_EC2_METADATA, "true")
+            .build()?)
+    }
+
+    /// Validate the OSS profile.
+    ///
+    /// # Errors
+    /// - Fails if the bucket name, region or endpoints are invalid.
+    /// - Fails if STS is enabled without a valid role ARN.
+    pub(super) fn normalize(&mut self) -> Result<(), ValidationError> {
+        validate_bucket_name(&self.bucket)?;
+        self.normalize_region()?;
+        self.normalize_key_prefix()?;
+        if let Some(endpoint) = self.endpoint.as_mut() {
+            normalize_endpoint(endpoint, "endpoint")?;
+        }
+        if let Some(sts_endpoint) = self.sts_endpoint.as_mut() {
+            normalize_endpoint(sts_endpoint, "sts-endpoint")?;
+        }
+        self.normalize_sts()
+    }
+
+    /// Update the profile with the other profile.
+    /// `bucket`, `region` and `key_prefix` must be the same.
+    ///
+    /// # Errors
+    /// Fails if the `bucket`, `region` or `key_prefix` is different.
+    pub fn update_with(self, other: Self) -> Result<Self, UpdateError> {
+        if self.bucket != other.bucket {
+            return Err(UpdateError::ImmutableField("bucket".to_string()));
+        }
+
+        if self.region != other.region {
+            return Err(UpdateError::ImmutableField("region".to_string()));
+        }
+
+        if self.key_prefix != other.key_prefix {
+            return Err(UpdateError::ImmutableField("key_prefix".to_string()));
+        }
+
+        Ok(other)
+    }
+
+    #[must_use]
+    #[allow(clippy::unused_self)]
+    pub fn generate_catalog_config(&self, _: WarehouseId) -> CatalogConfig {
+        CatalogConfig {
+            defaults: HashMap::with_capacity(0),
+            overrides: HashMap::with_capacity(0),
+            endpoints: supported_endpoints().to_vec(),
+        }
+    }
+
+    /// Base Location for this storage profile.
+    ///
+    /// # Errors
+    /// Can fail for un-normalized profiles
+    pub fn base_location(&self) -> Result<Location, ValidationError> {
+        let prefix: Vec<String> = self
+            .key_prefix
+            .as_ref()
+            .map(|s| s.split('/').map(std::borrow::ToOwned::to_owned).collect())
+            .unwrap_or_default();
+        Location::from_str(&format!("{OSS_SCHEME}://{}/", self.bucket))
+            .map(|mut l| {
+                l.extend(prefix.iter());
+                l
+            })
+            .map_err(|e| ValidationError::InvalidLocation {
+                reason: "Invalid OSS location.".to_string(),
+                location: format!("{OSS_SCHEME}://{}/", self.bucket),
+                source: Some(e.into()),
+                storage_type: StorageType::Oss,
+            })
+    }
+
+    /// Generate the table configuration for OSS.
+    ///
+    /// Vended credentials are returned for Iceberg's `OSSFileIO` (`client.*`)
+    /// as well as for clients using the S3-compatible API (`s3.*`).
+    ///
+    /// # Errors
+    /// Fails if the credentials cannot be obtained from Aliyun STS.
+    pub(crate) async fn generate_table_config(
+        &self,
+        data_access: DataAccess,
+        credential: &OssCredential,
+        table_location: &Location,
+        storage_permissions: StoragePermissions,
+    ) -> Result<TableConfig, TableConfigError> {
+        let mut config = TableProperties::default();
+        let mut creds = TableProperties::default();
+
+        let endpoint = self.endpoint();
+        config.insert(&oss::Endpoint(endpoint.clone()));
+        config.insert(&s3::Endpoint(endpoint));
+        config.insert(&s3::Region(self.region.clone()));
+        config.insert(&client::Region(self.region.clone()));
+        config.insert(&s3::PathStyleAccess(false));
+        config.insert(&custom::CustomConfig {
+            key: "s3.force-virtual-addressing".to_string(),
+            value: "true".to_string(),
+        });
+
+        if !self.sts_enabled {
+            if data_access.vended_credentials || data_access.remote_signing {
+                tracing::debug!(
+                    "STS is disabled for the OSS storage profile and remote signing is not available, returning a table config without credentials."
+                );
+            }
+            return Ok(TableConfig { config, creds });
+        }
+
+        let sts_credentials = sts::assume_role(
+            &self.sts_endpoint(),
+            credential,
+            self.sts_role_arn.as_deref().ok_or_else(|| {
+                TableConfigError::Misconfiguration(
+                    "`sts-role-arn` is required for OSS Storage Profiles if STS is enabled."
+                        .to_string(),
+                )
+            })?,
+            &self.sts_policy(table_location, storage_permissions)?,
+            self.sts_token_validity_seconds,
+        )
+        .await?;
+
+        for props in [&mut config, &mut creds] {
+            props.insert(&client::AccessKeyId(sts_credentials.access_key_id.clone()));
+            props.insert(&client::AccessKeySecret(
+                sts_credentials.access_key_secret.clone(),
+            ));
+            props.insert(&client::SecurityToken(
+                sts_credentials.security_token.clone(),
+            ));
+            props.insert(&s3::AccessKeyId(sts_credentials.access_key_id.clone()));
+            props.insert(&s3::SecretAccessKey(
+                sts_credentials.access_key_secret.clone(),
+            ));
+            props.insert(&s3::SessionToken(sts_credentials.security_token.clone()));
+        }
+
+        match chrono::DateTime::parse_from_rfc3339(&sts_credentials.expiration) {
+            Ok(expiration) => {
+                creds.insert(&custom::CustomConfig {
+                    key: "s3.session-token-expires-at-ms".to_string(),
+                    value: expiration.timestamp_millis().to_string(),
+                });
+            }
+            Err(e) => tracing::debug!(
+                "Failed to parse expiration `{}` of Aliyun STS credentials: {e}",
+                sts_credentials.expiration
+            ),
+        }
+
+        Ok(TableConfig { config, creds })
+    }
+
+    #[must_use]
+    /// Check whether the location of this storage profile is overlapping
+    /// with the given storage profile.
+    pub fn is_overlapping_location(&self, other: &Self) -> bool {
+        // Different bucket means no overlap
+        if self.bucket != other.bucket {
+            return false;
+        }
+
+        if self.key_prefix == other.key_prefix {
+            return true;
+        }
+
+        match (&self.key_prefix, &other.key_prefix) {
+            (Some(key_prefix), Some(other_key_prefix)) => {
+                let kp1 = format!("{key_prefix}/");
+                let kp2 = format!("{other_key_prefix}/");
+                kp1.starts_with(&kp2) || kp2.starts_with(&kp1)
+            }
+            // If either has no key prefix, it can access the entire bucket
+            (None, _) | (_, None) => true,
+        }
+    }
+
+    fn endpoint(&self) -> Url {
+        self.endpoint.clone().unwrap_or_else(|| {
+            Url::parse(&format!("https://oss-{}.aliyuncs.com", self.region))
+                .expect("Normalized region is a valid host")
+        })
+    }
+
+    fn sts_endpoint(&self) -> Url {
+        self.sts_endpoint.clone().unwrap_or_else(|| {
+            Url::parse(&format!("https://sts.{}.aliyuncs.com", self.region))
+                .expect("Normalized region is a valid host")
+        })
+    }
+
+    /// RAM policy that restricts the assumed role to the table location.
+    fn sts_policy(
+        &self,
+        table_location: &Location,
+        storage_permissions: StoragePermissions,
+    ) -> Result<String, CredentialsError> {
+        let key = table_location
+            .as_str()
+            .strip_prefix(&format!("{OSS_SCHEME}://{}/", self.bucket))
+            .ok_or_else(|| CredentialsError::ShortTermCredential {
+                reason: format!(
+                    "Could not generate downscoped policy for temporary credentials as location `{table_location}` is not in bucket `{}`.",
+                    self.bucket
+                ),
+                source: None,
+            })?;
+        let key = format!("{}/", key.trim_end_matches('/'));
+
+        let actions = match storage_permissions {
+            StoragePermissions::Read => vec!["oss:GetObject"],
+            StoragePermissions::ReadWrite => vec![
+                "oss:GetObject",
+                "oss:PutObject",
+                "oss:AbortMultipartUpload",
+                "oss:ListParts",
+            ],
+            StoragePermissions::ReadWriteDelete => vec![
+                "oss:GetObject",
+                "oss:PutObject",
+                "oss:AbortMultipartUpload",
+                "oss:ListParts",
+                "oss:DeleteObject",
+            ],
+        };
+
+        Ok(serde_json::json!({
+            "Version": "1",
+            "Statement": [
+                {
+                    "Effect": "Allow",
+                    "Action": actions,
+                    "Resource": [format!("acs:oss:*:*:{}/{key}*", self.bucket)]
+                },
+                {
+                    "Effect": "Allow",
+                    "Action": ["oss:ListObjects"],
+                    "Resource": [format!("acs:oss:*:*:{}", self.bucket)],
+                    "Condition": {
+                        "StringLike": {
+                            "oss:Prefix": [format!("{key}*")]
+                        }
+                    }
+                }
+            ]
+        })
+        .to_string())
+    }
+
+    fn normalize_region(&mut self) -> Result<(), ValidationError> {
+        self.region = self.region.trim().to_lowercase();
+        if self.region.is_empty()
+            || self.region.len() > 64
+            || !self
+                .region
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-')
+        {
+            return Err(ValidationError::InvalidProfile {
+                source: None,
+                reason: "`region` must be an Aliyun region ID such as `cn-hangzhou`.".to_string(),
+                entity: "Region".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    fn normalize_key_prefix(&mut self) -> Result<(), ValidationError> {
+        if let Some(key_prefix) = self.key_prefix.as_mut() {
+            *key_prefix = key_prefix.trim_matches('/').to_string();
+        }
+
+        if let Some(key_prefix) = self.key_prefix.as_ref() {
+            if key_prefix.is_empty() {
+                self.key_prefix = None;
+            }
+        }
+
+        // OSS supports a max of 1023 chars and we need some buffer for tables.
+        if let Some(key_prefix) = self.key_prefix.as_ref() {
+            if key_prefix.len() > 896 {
+                return Err(ValidationError::InvalidProfile {
+                    source: None,
+                    reason: "Storage Profile `key_prefix` must be less than 896 characters."
+                        .to_string(),
+                    entity: "key_prefix".to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn normalize_sts(&mut self) -> Result<(), ValidationError> {
+        if let Some(sts_role_arn) = self.sts_role_arn.as_mut() {
+            *sts_role_arn = sts_role_arn.trim().to_string();
+            if sts_role_arn.is_empty() {
+                self.sts_role_arn = None;
+            }
+        }
+
+        if let Some(sts_role_arn) = self.sts_role_arn.as_ref() {
+            if !sts_role_arn.starts_with("acs:ram::") || !sts_role_arn.contains(":role/") {
+                return Err(ValidationError::InvalidProfile {
+                    source: None,
+                    reason: "`sts-role-arn` must be the ARN of a RAM role, e.g. `acs:ram::1234567890123456:role/lakekeeper-oss`.".to_string(),
+                    entity: "StsRoleArn".to_string(),
+                });
+            }
+        }
+
+        if self.sts_enabled && self.sts_role_arn.is_none() {
+            return Err(ValidationError::InvalidProfile {
+                source: None,
+                reason: "`sts-role-arn` is required if `sts-enabled` is true.".to_string(),
+                entity: "StsRoleArn".to_string(),
+            });
+        }
+
+        if !(MIN_STS_TOKEN_VALIDITY_SECONDS..=MAX_STS_TOKEN_VALIDITY_SECONDS)
+            .contains(&self.sts_token_validity_seconds)
+        {
+            return Err(ValidationError::InvalidProfile {
+                source: None,
+                reason: format!(
+                    "`sts-token-validity-seconds` must be between {MIN_STS_TOKEN_VALIDITY_SECONDS} and {MAX_STS_TOKEN_VALIDITY_SECONDS}."
+                ),
+                entity: "StsTokenValiditySeconds".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+fn normalize_endpoint(endpoint: &mut Url, field: &str) -> Result<(), ValidationError> {
+    if !matches!(endpoint.scheme(), "http" | "https") || endpoint.host_str().is_none() {
+        return Err(ValidationError::InvalidProfile {
+            source: None,
+            reason: format!("Storage Profile `{field}` must be a http or https url with a host."),
+            entity: field.to_string(),
+        });
+    }
+    if endpoint.path() != "/" || endpoint.query().is_some() || endpoint.fragment().is_some() {
+        return Err(ValidationError::InvalidProfile {
+            source: None,
+            reason: format!(
+                "Storage Profile `{field}` must not contain a path, query or fragment."
+            ),
+            entity: field.to_string(),
+        });
+    }
+    Ok(())
+}
+
+fn validate_bucket_name(bucket: &str) -> Result<(), ValidationError> {
+    // Bucket names must be between 3 (min) and 63 (max) characters long.
+    if bucket.len() < 3 || bucket.len() > 63 {
+        return Err(ValidationError::InvalidProfile {
+            source: None,
+            reason: "`bucket` must be between 3 and 63 characters long.".to_string(),
+            entity: "BucketName".to_string(),
+        });
+    }
+
+    // Bucket names can consist only of lowercase letters, numbers and hyphens (-).
+    if !bucket
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    {
+        return Err(ValidationError::InvalidProfile {
+            source: None,
+            reason: "Bucket name can consist only of lowercase letters, numbers and hyphens (-)."
+                .to_string(),
+            entity: "BucketName".to_string(),
+        });
+    }
+
+    // Bucket names must begin and end with a letter or number.
+    if bucket.starts_with('-') || bucket.ends_with('-') {
+        return Err(ValidationError::InvalidProfile {
+            source: None,
+            reason: "Bucket name must begin and end with a letter or number.".to_string(),
+            entity: "BucketName".to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+fn fn_3600() -> u64 {
+    3600
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn profile() -> OssProfile {
+        OssProfile {
+            bucket: "lakehouse".to_string(),
+            region: "cn-hangzhou".to_string(),
+            endpoint: None,
+            key_prefix: Some("/warehouse/".to_string()),
+            sts_enabled: true,
+            sts_role_arn: Some("acs:ram::1234567890123456:role/lakekeeper".to_string()),
+            sts_endpoint: None,
+            sts_token_validity_seconds: 3600,
+        }
+    }
+
+    #[test]
+    fn test_normalize() {
+        let mut profile = profile();
+        profile.normalize().unwrap();
+        assert_eq!(profile.key_prefix.as_deref(), Some("warehouse"));
+        assert_eq!(
+            profile.base_location().unwrap().to_string(),
+            "oss://lakehouse/warehouse"
+        );
+        assert_eq!(
+            profile.endpoint().as_str(),
+            "https://oss-cn-hangzhou.aliyuncs.com/"
+        );
+        assert_eq!(
+            profile.sts_endpoint().as_str(),
+            "https://sts.cn-hangzhou.aliyuncs.com/"
+        );
+
+        let mut no_role = OssProfile {
+            sts_role_arn: None,
+            ..profile.clone()
+        };
+        no_role.normalize().unwrap_err();
+
+        let mut invalid_validity = OssProfile {
+            sts_token_validity_seconds: 60,
+            ..profile.clone()
+        };
+        invalid_validity.normalize().unwrap_err();
+
+        let mut endpoint_with_bucket = OssProfile {
+            endpoint: Some(Url::parse("https://oss-cn-hangzhou.aliyuncs.com/lakehouse").unwrap()),
+            ..profile
+        };
+        endpoint_with_bucket.normalize().unwrap_err();
+    }
+
+    #[test]
+    fn test_valid_bucket_names() {
+        assert!(validate_bucket_name("lakehouse").is_ok());
+        assert!(validate_bucket_name("lake-house-123").is_ok());
+
+        assert!(validate_bucket_name("Lakehouse").is_err());
+        assert!(validate_bucket_name("lake.house").is_err());
+        assert!(validate_bucket_name("lake_house").is_err());
+        assert!(validate_bucket_name("-lakehouse").is_err());
+        assert!(validate_bucket_name("lakehouse-").is_err());
+        assert!(validate_bucket_name("ab").is_err());
+        assert!(validate_bucket_name("a".repeat(64).as_str()).is_err());
+    }
+
+    #[test]
+    fn test_sts_policy() {
+        let mut profile = profile();
+        profile.normalize().unwrap();
+        let location = Location::from_str("oss://lakehouse/warehouse/ns/table").unwrap();
+
+        let policy: serde_json::Value = serde_json::from_str(
+            &profile
+                .sts_policy(&location, StoragePermissions::Read)
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            policy,
+            serde_json::json!({
+                "Version": "1",
+                "Statement": [
+                    {
+                        "Effect": "Allow",
+                        "Action": ["oss:GetObject"],
+                        "Resource": ["acs:oss:*:*:lakehouse/warehouse/ns/table/*"]
+                    },
+                    {
+                        "Effect": "Allow",
+                        "Action": ["oss:ListObjects"],
+                        "Resource": ["acs:oss:*:*:lakehouse"],
+                        "Condition": {
+                            "StringLike": {
+                                "oss:Prefix": ["warehouse/ns/table/*"]
+                            }
+                        }
+                    }
+                ]
+            })
+        );
+
+        let other_bucket = Location::from_str("oss://other/warehouse/ns/table").unwrap();
+        profile
+            .sts_policy(&other_bucket, StoragePermissions::Read)
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_credential_deserialization() {
+        let credential: OssCredential = serde_json::from_value(serde_json::json!({
+            "credential-type": "access-key",
+            "access-key-id": "LTAI5tExample",
+            "access-key-secret": "wJalrXUtnFEMI"
+        }))
+        .unwrap();
+        assert_eq!(credential.access_key(), ("LTAI5tExample", "wJalrXUtnFEMI"));
+        assert!(!format!("{credential:?}").contains("wJalrXUtnFEMI"));
+    }
+}
@@ -0,0 +1,212 @@
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use percent_encoding::{AsciiSet, NON_ALPHANUMERIC};
+use serde::Deserialize;
+use sha1::Sha1;
+use url::Url;
+
+use super::{OssCredential, HTTP_CLIENT};
+use crate::service::storage::error::{CredentialsError, TableConfigError};
+
+const STS_API_VERSION: &str = "2015-04-01";
+const ROLE_SESSION_NAME: &str = "lakekeeper";
+
+/// Characters that are not percent-encoded in signed Aliyun RPC requests (RFC 3986).
+const RFC3986: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+/// Assume a RAM role via Aliyun STS, restricted by `policy`.
+pub(super) async fn assume_role(
+    sts_endpoint: &Url,
+    credential: &OssCredential,
+    role_arn: &str,
+    policy: &str,
+    duration_seconds: u64,
+) -> Result<StsCredentials, TableConfigError> {
+    let (access_key_id, access_key_secret) = credential.access_key();
+    let nonce = uuid::Uuid::new_v4().to_string();
+    let timestamp = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    let duration_seconds = duration_seconds.to_string();
+
+    let params = [
+        ("AccessKeyId", access_key_id),
+        ("Action", "AssumeRole"),
+        ("DurationSeconds", duration_seconds.as_str()),
+        ("Format", "JSON"),
+        ("Policy", policy),
+        ("RoleArn", role_arn),
+        ("RoleSessionName", ROLE_SESSION_NAME),
+        ("SignatureMethod", "HMAC-SHA1"),
+        ("SignatureNonce", nonce.as_str()),
+        ("SignatureVersion", "1.0"),
+        ("Timestamp", timestamp.as_str()),
+        ("Version", STS_API_VERSION),
+    ];
+    let query = canonicalized_query(&params);
+    let signature = sign(access_key_secret, "GET", &query)?;
+
+    let mut url = sts_endpoint.clone();
+    url.set_query(Some(&format!(
+        "{query}&Signature={}",
+        percent_encode(&signature)
+    )));
+
+    let response = HTTP_CLIENT.get(url).send().await.map_err(|e| {
+        tracing::error!("Failed to send AssumeRole request to Aliyun STS: {e:?}");
+        TableConfigError::FailedDependency(
+            "Failed to send AssumeRole request to Aliyun STS".to_string(),
+        )
+    })?;
+    let status = response.status();
+    let body = response.text().await.map_err(|e| {
+        tracing::error!("Failed to read AssumeRole response of Aliyun STS: {e:?}");
+        TableConfigError::FailedDependency(
+            "Failed to read AssumeRole response of Aliyun STS".to_string(),
+        )
+    })?;
+
+    if !status.is_success() {
+        let error = serde_json::from_str::<StsErrorResponse>(&body).ok();
+        tracing::info!(
+            "Aliyun STS AssumeRole for role `{role_arn}` failed with status {status}: {body}"
+        );
+        return Err(CredentialsError::ShortTermCredential {
+            reason: match error {
+                Some(StsErrorResponse { code, message }) => {
+                    format!("Aliyun STS AssumeRole failed with `{code}`: {message}")
+                }
+                None => format!("Aliyun STS AssumeRole failed with status {status}"),
+            },
+            source: None,
+        }
+        .into());
+    }
+
+    serde_json::from_str::<AssumeRoleResponse>(&body)
+        .map(|r| r.credentials)
+        .map_err(|e| {
+            tracing::error!("Failed to parse AssumeRole response of Aliyun STS: {e:?}");
+            TableConfigError::FailedDependency(
+                "Failed to parse AssumeRole response of Aliyun STS".to_string(),
+            )
+        })
+}
+
+#[derive(Deserialize, veil::Redact)]
+#[serde(rename_all = "PascalCase")]
+pub(super) struct StsCredentials {
+    pub(super) access_key_id: String,
+    #[redact]
+    pub(super) access_key_secret: String,
+    #[redact(partial)]
+    pub(super) security_token: String,
+    pub(super) expiration: String,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct AssumeRoleResponse {
+    credentials: StsCredentials,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct StsErrorResponse {
+    code: String,
+    message: String,
+}
+
+fn percent_encode(value: &str) -> String {
+    percent_encoding::utf8_percent_encode(value, RFC3986).to_string()
+}
+
+/// Sorted and percent-encoded query parameters of a signed request.
+fn canonicalized_query(params: &[(&str, &str)]) -> String {
+    let mut params = params.to_vec();
+    params.sort_unstable_by_key(|(key, _)| *key);
+    params
+        .iter()
+        .map(|(key, value)| format!("{}={}", percent_encode(key), percent_encode(value)))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// Signature of an Aliyun RPC request (signature version 1.0).
+fn sign(
+    access_key_secret: &str,
+    method: &str,
+    canonicalized_query: &str,
+) -> Result<String, CredentialsError> {
+    let string_to_sign = format!(
+        "{method}&{}&{}",
+        percent_encode("/"),
+        percent_encode(canonicalized_query)
+    );
+    let mut mac = Hmac::<Sha1>::new_from_slice(format!("{access_key_secret}&").as_bytes())
+        .map_err(|e| CredentialsError::ShortTermCredential {
+            reason: "Failed to sign Aliyun STS request".to_string(),
+            source: Some(Box::new(e)),
+        })?;
+    mac.update(string_to_sign.as_bytes());
+    Ok(base64::prelude::BASE64_STANDARD.encode(mac.finalize().into_bytes()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_percent_encode() {
+        assert_eq!(percent_encode("a-b_c.d~e"), "a-b_c.d~e");
+        assert_eq!(percent_encode("a b*c/d:e"), "a%20b%2Ac%2Fd%3Ae");
+    }
+
+    // Example request of the Aliyun signature documentation
+    #[test]
+    fn test_sign() {
+        let query = canonicalized_query(&[
+            ("Version", "2014-05-26"),
+            ("Action", "DescribeRegions"),
+            ("Format", "XML"),
+            ("AccessKeyId", "testid"),
+            ("SignatureMethod", "HMAC-SHA1"),
+            ("SignatureNonce", "3ee8c1b8-83d3-44af-a94f-4e0ad82fd6cf"),
+            ("SignatureVersion", "1.0"),
+            ("Timestamp", "2016-02-23T12:46:24Z"),
+        ]);
+        assert_eq!(
+            query,
+            "AccessKeyId=testid&Action=DescribeRegions&Format=XML&SignatureMethod=HMAC-SHA1&SignatureNonce=3ee8c1b8-83d3-44af-a94f-4e0ad82fd6cf&SignatureVersion=1.0&Timestamp=2016-02-23T12%3A46%3A24Z&Version=2014-05-26"
+        );
+        assert_eq!(
+            sign("testsecret", "GET", &query).unwrap(),
+            "OLeaidS1JvxuMvnyHOwuJ+uX5qY="
+        );
+    }
+
+    #[test]
+    fn test_deserialize_assume_role_response() {
+        let response: AssumeRoleResponse = serde_json::from_value(serde_json::json!({
+            "RequestId": "6894B13B-6D71-4EF5-88FA-F32781734A7F",
+            "AssumedRoleUser": {
+                "Arn": "acs:ram::123456789012****:role/lakekeeper/lakekeeper",
+                "AssumedRoleId": "34458433936495****:lakekeeper"
+            },
+            "Credentials": {
+                "SecurityToken": "CAIS...",
+                "AccessKeyId": "STS.L4aBSCSJVMuKg5U1****",
+                "AccessKeySecret": "wyLTSmsyPGP1ohvvw8xYgB29dlGI8KMiH2pK****",
+                "Expiration": "2015-04-09T11:52:19Z"
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            response.credentials.access_key_id,
+            "STS.L4aBSCSJVMuKg5U1****"
+        );
+        assert_eq!(response.credentials.expiration, "2015-04-09T11:52:19Z");
+    }
+}
@@ -1,13 +1,22 @@
 #![allow(clippy::module_name_repetitions)]
 
-use std::{collections::HashMap, str::FromStr, sync::LazyLock};
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::LazyLock,
+    time::{Duration, Instant},
+};
 
 use aws_config::{identity::IdentityCache, BehaviorVersion, SdkConfig};
 use aws_sdk_sts::config::{ProvideCredentials as _, SharedIdentityCache};
+use axum_prometheus::metrics;
+use base64::Engine as _;
 use iceberg_ext::configs::{
     table::{client, custom, s3, TableProperties},
     ConfigProperty, Location,
 };
+use itertools::Itertools as _;
+use md5::Digest as _;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use veil::Redact;
@@ -25,14 +34,14 @@ use crate::{
             error::{
                 CredentialsError, FileIoError, TableConfigError, UpdateError, ValidationError,
             },
-            StoragePermissions, TableConfig,
+            S3AccessPoint, StoragePermissions, TableConfig,
         },
         TabularId,
     },
     WarehouseId, CONFIG,
 };
 
-static S3_HTTP_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(reqwest::Client::new);
+pub(super) static S3_HTTP_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(reqwest::Client::new);
 static STS_HTTP_CLIENT: LazyLock<aws_sdk_sts::config::SharedHttpClient> = LazyLock::new(|| {
     aws_smithy_http_client::Builder::new()
         .tls_provider(aws_smithy_http_client::tls::Provider::Rustls(
@@ -42,6 +51,13 @@ static STS_HTTP_CLIENT: LazyLock<aws_sdk_sts::config::SharedHttpClient> = LazyLo
 });
 static AWS_IDENTITY_CACHE: LazyLock<SharedIdentityCache> =
     LazyLock::new(|| IdentityCache::lazy().build());
+/// Maximum lifetime of temporary credentials issued by the Cloudflare R2 API (7 days).
+const R2_MAX_TOKEN_VALIDITY_SECONDS: u64 = 7 * 24 * 60 * 60;
+/// Vended credentials that are reused for profiles with `sts-credentials-cache-seconds`.
+static STS_CREDENTIALS_CACHE: LazyLock<
+    moka::sync::Cache<StsCredentialsCacheKey, CachedStsCredentials>,
+> = LazyLock::new(|| moka::sync::Cache::builder().max_capacity(10_000).build());
+const STS_CREDENTIALS_CACHE_REQUESTS: &str = "lakekeeper_sts_credentials_cache_requests_total";
 
 #[derive(
     Debug,
@@ -84,10 +100,19 @@ pub struct S3Profile {
     #[builder(default, setter(strip_option))]
     pub sts_role_arn: Option<String>,
     pub sts_enabled: bool,
-    /// The validity of the sts tokens in seconds. Default is 3600
+    /// The validity of the sts tokens in seconds. Default is 3600.
+    /// For Cloudflare R2 this is the TTL of the minted temporary credentials (at most 7 days).
     #[builder(default = 3600)]
     #[serde(default = "fn_3600")]
     pub sts_token_validity_seconds: u64,
+    /// Seconds for which vended STS credentials are reused for further requests of the
+    /// same principal to the same table with the same permissions, instead of requesting
+    /// new credentials from the STS endpoint. Must be at most half of
+    /// `sts-token-validity-seconds`, so that reused credentials remain valid for at least
+    /// half of their lifetime. Default: 0 (disabled).
+    #[serde(default)]
+    #[builder(default)]
+    pub sts_credentials_cache_seconds: u64,
     /// S3 flavor to use.
     /// Defaults to AWS
     #[serde(default)]
@@ -140,6 +165,120 @@ pub struct S3Profile {
     #[serde(default)]
     #[builder(default, setter(strip_option))]
     pub aws_kms_key_arn: Option<String>,
+    /// ID or ARN of the KMS key used to encrypt objects written by Lakekeeper
+    /// and clients (SSE-KMS). If not set, the default encryption of the bucket applies.
+    /// Can not be combined with a customer provided key (SSE-C) in the storage credential.
+    #[serde(default)]
+    #[builder(default, setter(strip_option))]
+    pub sse_kms_key_id: Option<String>,
+    /// Use an S3 Bucket Key for SSE-KMS to reduce the number of requests to KMS.
+    /// Only applies if `sse-kms-key-id` is set.
+    #[serde(default)]
+    #[builder(default)]
+    pub sse_bucket_key_enabled: bool,
+    /// Behavioral differences of the S3-compatible storage that Lakekeeper should
+    /// work around. Only supported for the `s3-compat` flavor.
+    /// Use the storage probe endpoint of the warehouse to get suggested values.
+    #[serde(default)]
+    #[builder(default)]
+    pub quirks: S3Quirks,
+    /// ARN of an S3 Access Point or Multi-Region Access Point attached to `bucket`.
+    /// Only supported for the AWS flavor.
+    /// If set, clients are instructed to route requests for `bucket` through the access point
+    /// and remote signing accepts requests addressed to it. Table locations keep using
+    /// `s3://<bucket>/...`.
+    /// Example: `arn:aws:s3:eu-central-1:123456789012:accesspoint/my-access-point`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[builder(default, setter(strip_option))]
+    pub access_point_arn: Option<String>,
+    /// The bucket has requester-pays enabled. Remote signing adds the
+    /// `x-amz-request-payer: requester` header to signed requests and vended
+    /// credentials instruct clients to send it, so that requests are billed to the
+    /// account of the credentials instead of the bucket owner.
+    #[serde(default)]
+    #[builder(default)]
+    pub requester_pays: bool,
+    /// Additional locations outside of `bucket` and `key-prefix` in which namespaces and
+    /// tables may be placed, for example `s3://other-bucket/some/prefix`.
+    /// The locations must be accessible with the endpoint, region and credential of this profile.
+    /// Vended credentials and remote signing are scoped to the table location as usual.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[builder(default)]
+    pub additional_allowed_locations: Vec<String>,
+}
+
+/// Known deviations of S3-compatible object stores from AWS S3 behavior.
+/// All flags default to `false`, which matches AWS S3.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct S3Quirks {
+    /// The store does not support object tagging.
+    /// If set, clients are instructed not to tag objects on write.
+    #[serde(default)]
+    pub no_tagging: bool,
+    /// The store does not support trailing checksums (`aws-chunked` uploads with
+    /// `x-amz-trailer`), which recent AWS SDKs send by default.
+    /// If set, remote signing rejects such requests with an actionable error
+    /// instead of letting the upload fail at the storage.
+    #[serde(default)]
+    pub no_checksum_trailers: bool,
+    /// Listings may not immediately reflect recently written or deleted objects.
+    /// If set, Lakekeeper retries checks for empty locations before failing.
+    #[serde(default)]
+    pub weak_list_consistency: bool,
+}
+
+impl S3Quirks {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// Server-side encryption applied to objects of a storage profile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum S3ServerSideEncryption {
+    /// SSE-KMS with the given key.
+    Kms {
+        key_id: String,
+        bucket_key_enabled: bool,
+    },
+    /// SSE-C with a customer provided key. Key and its MD5 digest are base64 encoded.
+    CustomerKey { key: String, key_md5: String },
+}
+
+impl S3ServerSideEncryption {
+    /// Value of the `s3.sse.type` property understood by Iceberg `FileIO`s.
+    pub(crate) fn sse_type(&self) -> &'static str {
+        match self {
+            Self::Kms { .. } => "kms",
+            Self::CustomerKey { .. } => "custom",
+        }
+    }
+
+    /// Value of the `s3.sse.key` property understood by Iceberg `FileIO`s.
+    pub(crate) fn sse_key(&self) -> &str {
+        match self {
+            Self::Kms { key_id, .. } => key_id,
+            Self::CustomerKey { key, .. } => key,
+        }
+    }
+
+    fn from_customer_key(key: &str) -> Result<Self, CredentialsError> {
+        let decoded = base64::prelude::BASE64_STANDARD
+            .decode(key)
+            .ok()
+            .filter(|k| k.len() == 32)
+            .ok_or_else(|| {
+                CredentialsError::Misconfiguration(
+                    "`sse-customer-key` must be a base64 encoded 256-bit key.".to_string(),
+                )
+            })?;
+        Ok(Self::CustomerKey {
+            key: key.to_string(),
+            key_md5: base64::prelude::BASE64_STANDARD.encode(md5::Md5::digest(decoded)),
+        })
+    }
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default, ToSchema)]
@@ -162,7 +301,9 @@ pub enum S3Flavor {
     Aws,
     #[serde(alias = "minio")]
     S3Compat,
-    // CloudflareR2,
+    /// Cloudflare R2. Requires a `cloudflare-r2` credential. Temporary
+    /// credentials are minted through the Cloudflare API instead of STS.
+    CloudflareR2,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
@@ -185,6 +326,9 @@ pub struct S3AccessKeyCredential {
     pub aws_secret_access_key: String,
     #[redact(partial)]
     pub external_id: Option<String>,
+    /// Base64 encoded 256-bit customer provided key used to encrypt objects (SSE-C).
+    #[redact]
+    pub sse_customer_key: Option<String>,
 }
 
 #[derive(Redact, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
@@ -193,6 +337,9 @@ pub struct S3AccessKeyCredential {
 pub struct S3AwsSystemIdentityCredential {
     #[redact(partial)]
     pub external_id: Option<String>,
+    /// Base64 encoded 256-bit customer provided key used to encrypt objects (SSE-C).
+    #[redact]
+    pub sse_customer_key: Option<String>,
 }
 
 #[derive(Redact, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
@@ -220,6 +367,38 @@ enum STSCapableCredential {
     AwsSystemIdentity(S3AwsSystemIdentityCredential),
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct StsCredentialsCacheKey {
+    profile_fingerprint: u64,
+    tabular_id: TabularId,
+    table_location: String,
+    actor: String,
+    storage_permissions: StoragePermissions,
+}
+
+#[derive(Debug, Clone)]
+struct CachedStsCredentials {
+    credentials: aws_sdk_sts::types::Credentials,
+    cached_until: Instant,
+}
+
+impl S3Credential {
+    /// Customer provided key for SSE-C, if configured.
+    #[must_use]
+    pub fn sse_customer_key(&self) -> Option<&str> {
+        match self {
+            S3Credential::AccessKey(S3AccessKeyCredential {
+                sse_customer_key, ..
+            })
+            | S3Credential::AwsSystemIdentity(S3AwsSystemIdentityCredential {
+                sse_customer_key,
+                ..
+            }) => sse_customer_key.as_deref(),
+            S3Credential::CloudflareR2(_) => None,
+        }
+    }
+}
+
 impl STSCapableCredential {
     /// Get the external ID for the credential.
     /// Returns `None` if the credential is not an access key.
@@ -229,6 +408,7 @@ impl STSCapableCredential {
             STSCapableCredential::AccessKey(S3AccessKeyCredential { external_id, .. })
             | STSCapableCredential::AwsSystemIdentity(S3AwsSystemIdentityCredential {
                 external_id,
+                ..
             }) => external_id.as_deref(),
         }
     }
@@ -254,14 +434,31 @@ impl S3Profile {
     /// Check whether the location of this storage profile is overlapping
     /// with the given storage profile.
     pub fn is_overlapping_location(&self, other: &Self) -> bool {
-        // Different bucket, region, or endpoint means no overlap
-        if self.bucket != other.bucket
-            || self.region != other.region
-            || self.endpoint != other.endpoint
+        // Different region or endpoint means no overlap
+        if self.region != other.region || self.endpoint != other.endpoint {
+            return false;
+        }
+
+        if self.bucket == other.bucket && self.is_overlapping_key_prefix(other) {
+            return true;
+        }
+
+        if self.additional_allowed_locations.is_empty()
+            && other.additional_allowed_locations.is_empty()
         {
             return false;
         }
 
+        // Additional allowed locations may overlap with any location of the other profile
+        let other_locations = other.allowed_locations().collect_vec();
+        self.allowed_locations().any(|location| {
+            other_locations
+                .iter()
+                .any(|o| location.is_sublocation_of(o) || o.is_sublocation_of(&location))
+        })
+    }
+
+    fn is_overlapping_key_prefix(&self, other: &Self) -> bool {
         // If key prefixes are identical, they overlap
         if self.key_prefix == other.key_prefix {
             return true;
@@ -279,6 +476,57 @@ impl S3Profile {
         }
     }
 
+    /// Base location followed by all additional allowed locations.
+    /// Locations that can't be parsed are skipped, they are rejected during normalization.
+    fn allowed_locations(&self) -> impl Iterator<Item = Location> + '_ {
+        self.base_location()
+            .ok()
+            .map(S3Location::into_normalized_location)
+            .into_iter()
+            .chain(self.additional_allowed_locations())
+    }
+
+    /// Additional locations in which namespaces and tables may be placed.
+    pub(super) fn additional_allowed_locations(&self) -> impl Iterator<Item = Location> + '_ {
+        self.additional_allowed_locations
+            .iter()
+            .filter_map(|l| Location::from_str(l).ok())
+    }
+
+    /// Access point configured for this profile, if any.
+    ///
+    /// # Errors
+    /// Fails if `access_point_arn` is not a valid access point ARN.
+    pub fn access_point(&self) -> Result<Option<S3AccessPoint>, ValidationError> {
+        self.access_point_arn
+            .as_deref()
+            .map(str::parse::<S3AccessPoint>)
+            .transpose()
+    }
+
+    /// Server-side encryption to apply to objects, if configured.
+    ///
+    /// # Errors
+    /// Fails if both SSE-KMS and SSE-C are configured or if the customer key is invalid.
+    pub(crate) fn server_side_encryption(
+        &self,
+        credential: Option<&S3Credential>,
+    ) -> Result<Option<S3ServerSideEncryption>, CredentialsError> {
+        let customer_key = credential.and_then(S3Credential::sse_customer_key);
+        match (&self.sse_kms_key_id, customer_key) {
+            (Some(_), Some(_)) => Err(CredentialsError::Misconfiguration(
+                "SSE-KMS (`sse-kms-key-id`) and SSE-C (`sse-customer-key`) can not be combined."
+                    .to_string(),
+            )),
+            (Some(key_id), None) => Ok(Some(S3ServerSideEncryption::Kms {
+                key_id: key_id.clone(),
+                bucket_key_enabled: self.sse_bucket_key_enabled,
+            })),
+            (None, Some(key)) => S3ServerSideEncryption::from_customer_key(key).map(Some),
+            (None, None) => Ok(None),
+        }
+    }
+
     /// Create a new `FileIO` instance for S3.
     ///
     /// # Errors
@@ -307,6 +555,15 @@ impl S3Profile {
             self.path_style_access.unwrap_or_default(),
         );
 
+        if let Some(sse) = self.server_side_encryption(credential)? {
+            builder = builder
+                .with_prop(iceberg::io::S3_SSE_TYPE, sse.sse_type())
+                .with_prop(iceberg::io::S3_SSE_KEY, sse.sse_key());
+            if let S3ServerSideEncryption::CustomerKey { key_md5, .. } = &sse {
+                builder = builder.with_prop(iceberg::io::S3_SSE_MD5, key_md5);
+            }
+        }
+
         let credentials = self
             .get_aws_credentials_with_assumed_role(credential)
             .await?;
@@ -343,6 +600,12 @@ impl S3Profile {
     /// - Fails if the key prefix is too long.
     /// - Fails if the region or endpoint is missing.
     /// - Fails if the endpoint is not a valid URL.
+    /// - Fails if the KMS key or the customer provided key for server-side encryption is invalid.
+    /// - Fails if quirks are set for the AWS flavor.
+    /// - Fails if the Cloudflare R2 flavor is used without a Cloudflare R2 credential
+    ///   or with a token validity the Cloudflare API does not accept.
+    /// - Fails if the access point ARN is invalid or not usable with this profile.
+    /// - Fails if an additional allowed location is not a valid S3 location.
     pub(super) fn normalize(
         &mut self,
         s3_credential: Option<&S3Credential>,
@@ -356,6 +619,12 @@ impl S3Profile {
 
         if let Some(S3Credential::CloudflareR2(cloudflare_r2_credential)) = s3_credential {
             self.normalize_r2(cloudflare_r2_credential)?;
+        } else if matches!(self.flavor, S3Flavor::CloudflareR2) {
+            return Err(ValidationError::InvalidProfile {
+                source: None,
+                reason: "Storage Profiles with Cloudflare R2 flavor require a `cloudflare-r2` credential.".to_string(),
+                entity: "flavor".to_string(),
+            });
         }
 
         if self.sts_enabled
@@ -370,6 +639,28 @@ impl S3Profile {
             });
         }
 
+        if self.sts_credentials_cache_seconds.saturating_mul(2) > self.sts_token_validity_seconds {
+            return Err(ValidationError::InvalidProfile {
+                source: None,
+                reason: "`sts-credentials-cache-seconds` must be at most half of `sts-token-validity-seconds`.".to_string(),
+                entity: "sts-credentials-cache-seconds".to_string(),
+            });
+        }
+
+        self.normalize_sse_kms_key_id()?;
+        self.server_side_encryption(s3_credential)?;
+        self.normalize_access_point_arn()?;
+        self.normalize_additional_allowed_locations()?;
+
+        if matches!(self.flavor, S3Flavor::Aws) && !self.quirks.is_empty() {
+            return Err(ValidationError::InvalidProfile {
+                source: None,
+                reason: "`quirks` are only supported for Storage Profiles with S3-Compat flavor."
+                    .to_string(),
+                entity: "quirks".to_string(),
+            });
+        }
+
         Ok(())
     }
 
@@ -479,53 +770,54 @@ impl S3Profile {
             config.insert(&s3::Endpoint(endpoint.clone()));
         }
 
+        if let Some(access_point_arn) = &self.access_point_arn {
+            config.insert(&custom::CustomConfig {
+                key: format!("s3.access-points.{}", self.bucket),
+                value: access_point_arn.clone(),
+            });
+        }
+
+        if let Some(sse) = self.server_side_encryption(s3_credential)? {
+            config.insert(&s3::SseType(sse.sse_type().to_string()));
+            config.insert(&s3::SseKey(sse.sse_key().to_string()));
+            if let S3ServerSideEncryption::CustomerKey { key_md5, .. } = &sse {
+                config.insert(&s3::SseMd5(key_md5.clone()));
+            }
+        }
+
+        if self.requester_pays {
+            config.insert(&s3::RequesterPays(true));
+        }
+
+        if self.quirks.no_tagging {
+            for key in [
+                "s3.write.table-tag-enabled",
+                "s3.write.namespace-tag-enabled",
+            ] {
+                config.insert(&custom::CustomConfig {
+                    key: key.to_string(),
+                    value: "false".to_string(),
+                });
+            }
+        }
+
         if vended_credentials {
             if self.sts_enabled | matches!(s3_credential, Some(S3Credential::CloudflareR2(..))) {
                 let aws_sdk_sts::types::Credentials {
                     access_key_id,
                     secret_access_key,
                     session_token,
-                    expiration: _,
+                    expiration,
                     ..
-                } = match s3_credential.cloned() {
-                    Some(S3Credential::CloudflareR2(c)) => {
-                        self.get_cloudflare_r2_temporary_credentials(
-                            table_location,
-                            c,
-                            storage_permissions,
-                        )
-                        .await?
-                    }
-                    c @ (Some(
-                        S3Credential::AccessKey(..) | S3Credential::AwsSystemIdentity(..),
+                } = self
+                    .cached_sts_credentials(
+                        s3_credential,
+                        table_location,
+                        storage_permissions,
+                        request_metadata,
+                        tabular_id,
                     )
-                    | None) => {
-                        let c_sts = match c {
-                            None => None,
-                            Some(S3Credential::AccessKey(k)) => {
-                                Some(STSCapableCredential::AccessKey(k))
-                            }
-                            Some(S3Credential::AwsSystemIdentity(k)) => {
-                                Some(STSCapableCredential::AwsSystemIdentity(k))
-                            }
-                            Some(S3Credential::CloudflareR2(..)) => unreachable!(),
-                        };
-
-                        if let Some(arn) =
-                            self.sts_role_arn.as_ref().or(self.assume_role_arn.as_ref())
-                        {
-                            self.get_aws_sts_token(table_location, c_sts, arn, storage_permissions)
-                                .await?
-                        } else if S3Flavor::S3Compat == self.flavor {
-                            self.get_s3_compat_sts_token(table_location, c_sts, storage_permissions)
-                                .await?
-                        } else {
-                            return Err(TableConfigError::Misconfiguration(
-                                "Either `sts-role-arn` or `assume-role-arn` is required for Storage Profiles with AWS flavor if STS is enabled.".to_string(),
-                            ));
-                        }
-                    }
-                };
+                    .await?;
 
                 config.insert(&s3::AccessKeyId(access_key_id.clone()));
                 config.insert(&s3::SecretAccessKey(secret_access_key.clone()));
@@ -533,6 +825,17 @@ impl S3Profile {
                 creds.insert(&s3::AccessKeyId(access_key_id));
                 creds.insert(&s3::SecretAccessKey(secret_access_key));
                 creds.insert(&s3::SessionToken(session_token));
+                match expiration.to_millis() {
+                    Ok(expires_at_ms) => {
+                        creds.insert(&custom::CustomConfig {
+                            key: "s3.session-token-expires-at-ms".to_string(),
+                            value: expires_at_ms.to_string(),
+                        });
+                    }
+                    Err(e) => tracing::debug!(
+                        "Failed to convert expiration of vended S3 credentials: {e}"
+                    ),
+                }
             } else {
                 push_fsspec_fileio_with_s3v4restsigner(&mut config);
                 remote_signing = true;
@@ -550,6 +853,113 @@ impl S3Profile {
         Ok(TableConfig { creds, config })
     }
 
+    /// Vended credentials for the table. Reuses credentials previously vended to the same
+    /// principal if `sts_credentials_cache_seconds` is set.
+    async fn cached_sts_credentials(
+        &self,
+        s3_credential: Option<&S3Credential>,
+        table_location: &Location,
+        storage_permissions: StoragePermissions,
+        request_metadata: &RequestMetadata,
+        tabular_id: TabularId,
+    ) -> Result<aws_sdk_sts::types::Credentials, TableConfigError> {
+        let cache_key = self.sts_credentials_cache_key(
+            s3_credential,
+            table_location,
+            storage_permissions,
+            request_metadata,
+            tabular_id,
+        );
+
+        if let Some(cache_key) = &cache_key {
+            if let Some(cached) = STS_CREDENTIALS_CACHE.get(cache_key) {
+                if cached.cached_until > Instant::now() {
+                    metrics::counter!(STS_CREDENTIALS_CACHE_REQUESTS, "result" => "hit")
+                        .increment(1);
+                    return Ok(cached.credentials);
+                }
+                STS_CREDENTIALS_CACHE.invalidate(cache_key);
+            }
+            metrics::counter!(STS_CREDENTIALS_CACHE_REQUESTS, "result" => "miss").increment(1);
+        }
+
+        let credentials = self
+            .sts_credentials(s3_credential, table_location, storage_permissions)
+            .await?;
+
+        if let Some(cache_key) = cache_key {
+            STS_CREDENTIALS_CACHE.insert(
+                cache_key,
+                CachedStsCredentials {
+                    credentials: credentials.clone(),
+                    cached_until: Instant::now()
+                        + Duration::from_secs(self.sts_credentials_cache_seconds),
+                },
+            );
+        }
+        Ok(credentials)
+    }
+
+    fn sts_credentials_cache_key(
+        &self,
+        s3_credential: Option<&S3Credential>,
+        table_location: &Location,
+        storage_permissions: StoragePermissions,
+        request_metadata: &RequestMetadata,
+        tabular_id: TabularId,
+    ) -> Option<StsCredentialsCacheKey> {
+        if self.sts_credentials_cache_seconds == 0 {
+            return None;
+        }
+        // Changes to the profile or credential must not serve credentials of the previous version.
+        let profile = serde_json::to_vec(&(self, s3_credential)).ok()?;
+        Some(StsCredentialsCacheKey {
+            profile_fingerprint: fxhash::hash64(&profile),
+            tabular_id,
+            table_location: table_location.to_string(),
+            actor: request_metadata.actor().to_string(),
+            storage_permissions,
+        })
+    }
+
+    async fn sts_credentials(
+        &self,
+        s3_credential: Option<&S3Credential>,
+        table_location: &Location,
+        storage_permissions: StoragePermissions,
+    ) -> Result<aws_sdk_sts::types::Credentials, TableConfigError> {
+        let credentials = match s3_credential.cloned() {
+            Some(S3Credential::CloudflareR2(c)) => {
+                self.get_cloudflare_r2_temporary_credentials(table_location, c, storage_permissions)
+                    .await?
+            }
+            c
+            @ (Some(S3Credential::AccessKey(..) | S3Credential::AwsSystemIdentity(..)) | None) => {
+                let c_sts = match c {
+                    None => None,
+                    Some(S3Credential::AccessKey(k)) => Some(STSCapableCredential::AccessKey(k)),
+                    Some(S3Credential::AwsSystemIdentity(k)) => {
+                        Some(STSCapableCredential::AwsSystemIdentity(k))
+                    }
+                    Some(S3Credential::CloudflareR2(..)) => unreachable!(),
+                };
+
+                if let Some(arn) = self.sts_role_arn.as_ref().or(self.assume_role_arn.as_ref()) {
+                    self.get_aws_sts_token(table_location, c_sts, arn, storage_permissions)
+                        .await?
+                } else if S3Flavor::S3Compat == self.flavor {
+                    self.get_s3_compat_sts_token(table_location, c_sts, storage_permissions)
+                        .await?
+                } else {
+                    return Err(TableConfigError::Misconfiguration(
+                        "Either `sts-role-arn` or `assume-role-arn` is required for Storage Profiles with AWS flavor if STS is enabled.".to_string(),
+                    ));
+                }
+            }
+        };
+        Ok(credentials)
+    }
+
     async fn get_cloudflare_r2_temporary_credentials(
         &self,
         table_location: &Location,
@@ -786,6 +1196,7 @@ impl S3Profile {
                 aws_access_key_id,
                 aws_secret_access_key,
                 external_id: _,
+                sse_customer_key: _,
             })) => {
                 let aws_credentials = aws_credential_types::Credentials::new(
                     aws_access_key_id,
@@ -798,6 +1209,7 @@ impl S3Profile {
             }
             Some(STSCapableCredential::AwsSystemIdentity(S3AwsSystemIdentityCredential {
                 external_id: _,
+                sse_customer_key: _,
             })) => aws_config::from_env(),
             None => aws_config::from_env().no_credentials(),
         }
@@ -856,6 +1268,7 @@ impl S3Profile {
                         aws_access_key_id,
                         aws_secret_access_key,
                         external_id: _,
+                        sse_customer_key: _,
                     })
                     | S3Credential::CloudflareR2(S3CloudflareR2Credential {
                         access_key_id: aws_access_key_id,
@@ -925,6 +1338,23 @@ impl S3Profile {
         );
         let key = format!("{}/", table_location.key().join("/"));
 
+        // Requests through an access point are authorized against the access point ARN,
+        // objects are addressed as `<access-point-arn>/object/<key>`.
+        let access_point_arn = self.access_point_arn.as_deref();
+        let object_resources = std::iter::once(bucket_arn.clone())
+            .chain(access_point_arn.map(|arn| format!("{arn}/object")))
+            .flat_map(|prefix| {
+                [
+                    format!("\"{prefix}/{key}\""),
+                    format!("\"{prefix}/{key}*\""),
+                ]
+            })
+            .join(",");
+        let list_resources = std::iter::once(bucket_arn.as_str())
+            .chain(access_point_arn)
+            .map(|arn| format!("\"{arn}\""))
+            .join(",");
+
         let mut statements = format!(
             r#"
             {{
@@ -933,16 +1363,13 @@ impl S3Profile {
                 "Action": [
                     {}
                 ],
-                "Resource": [
-                    "{bucket_arn}/{key}",
-                    "{bucket_arn}/{key}*"
-                ]
+                "Resource": [{object_resources}]
             }},
             {{
                 "Sid": "ListBucketForFolder",
                 "Effect": "Allow",
                 "Action": "s3:ListBucket",
-                "Resource": "{bucket_arn}",
+                "Resource": [{list_resources}],
                 "Condition": {{
                     "StringLike": {{
                         "s3:prefix": "{key}*"
@@ -955,7 +1382,20 @@ impl S3Profile {
         .replace('\n', "")
         .replace(' ', "");
 
-        if let Some(kms_key_arn) = self.aws_kms_key_arn.as_ref() {
+        // IAM policies can only reference KMS keys by key ARN, not by alias.
+        let sse_kms_key_arn = self
+            .sse_kms_key_id
+            .as_deref()
+            .filter(|k| k.starts_with("arn:") && k.contains(":key/"));
+        let kms_key_arns = self
+            .aws_kms_key_arn
+            .as_deref()
+            .into_iter()
+            .chain(sse_kms_key_arn)
+            .unique()
+            .map(|arn| format!("\"{arn}\""))
+            .join(",");
+        if !kms_key_arns.is_empty() {
             statements = format!(
                 r#"
                 {statements},
@@ -966,7 +1406,7 @@ impl S3Profile {
                         "kms:Decrypt",
                         "kms:GenerateDataKey"
                     ],
-                    "Resource": "{kms_key_arn}"
+                    "Resource": [{kms_key_arns}]
                 }}"#
             );
         }
@@ -1049,6 +1489,118 @@ impl S3Profile {
         Ok(())
     }
 
+    fn normalize_sse_kms_key_id(&mut self) -> Result<(), ValidationError> {
+        let Some(key_id) = self.sse_kms_key_id.as_mut() else {
+            return Ok(());
+        };
+        *key_id = key_id.trim().to_string();
+
+        let invalid = |reason: String| ValidationError::InvalidProfile {
+            source: None,
+            reason,
+            entity: "SseKmsKeyId".to_string(),
+        };
+        if key_id.is_empty() {
+            return Err(invalid(
+                "Storage Profile `sse-kms-key-id` must not be empty.".to_string(),
+            ));
+        }
+        // S3-compatible stores use their own key names, e.g. MinIO KES.
+        if !matches!(self.flavor, S3Flavor::Aws) {
+            return Ok(());
+        }
+
+        if let Some(arn) = key_id.strip_prefix("arn:") {
+            // <partition>:kms:<region>:<account>:key/<id> or alias/<name>
+            let parts = arn.splitn(5, ':').collect::<Vec<_>>();
+            let is_valid = parts.len() == 5
+                && parts[1] == "kms"
+                && (parts[4].starts_with("key/") || parts[4].starts_with("alias/"));
+            if !is_valid {
+                return Err(invalid(format!(
+                    "Storage Profile `sse-kms-key-id` is not a valid KMS key or alias ARN: {key_id}"
+                )));
+            }
+            if parts[2] != self.region {
+                return Err(invalid(format!(
+                    "KMS key `{key_id}` must be in the region of the bucket ({}).",
+                    self.region
+                )));
+            }
+        } else if !(key_id.starts_with("alias/")
+            || key_id.starts_with("mrk-")
+            || uuid::Uuid::parse_str(key_id).is_ok())
+        {
+            return Err(invalid(format!(
+                "Storage Profile `sse-kms-key-id` must be a KMS key ID, key ARN, alias name or alias ARN. Got: {key_id}"
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn normalize_access_point_arn(&mut self) -> Result<(), ValidationError> {
+        if let Some(arn) = self.access_point_arn.as_mut() {
+            *arn = arn.trim().to_string();
+            if arn.is_empty() {
+                self.access_point_arn = None;
+            }
+        }
+        let Some(access_point) = self.access_point()? else {
+            return Ok(());
+        };
+
+        let invalid = |reason: String| ValidationError::InvalidProfile {
+            source: None,
+            reason,
+            entity: "access-point-arn".to_string(),
+        };
+        if !matches!(self.flavor, S3Flavor::Aws) {
+            return Err(invalid(
+                "`access-point-arn` is only supported for Storage Profiles with AWS flavor."
+                    .to_string(),
+            ));
+        }
+        if self.path_style_access == Some(true) {
+            return Err(invalid(
+                "Access points do not support path style access. Unset `path-style-access`."
+                    .to_string(),
+            ));
+        }
+        if let Some(region) = access_point.region() {
+            if region != self.region {
+                return Err(invalid(format!(
+                    "Access point `{}` must be in the region of the bucket ({}).",
+                    access_point.arn(),
+                    self.region
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn normalize_additional_allowed_locations(&mut self) -> Result<(), ValidationError> {
+        let mut normalized = Vec::with_capacity(self.additional_allowed_locations.len());
+        for location in &self.additional_allowed_locations {
+            let location = S3Location::try_from_str(location.trim_end_matches('/'), false)
+                .map_err(|e| ValidationError::InvalidProfile {
+                    source: Some(Box::new(e)),
+                    reason: format!(
+                        "Storage Profile `additional-allowed-locations` contains an invalid S3 location: '{location}'"
+                    ),
+                    entity: "additional-allowed-locations".to_string(),
+                })?
+                .into_normalized_location()
+                .to_string();
+            if !normalized.contains(&location) {
+                normalized.push(location);
+            }
+        }
+        self.additional_allowed_locations = normalized;
+        Ok(())
+    }
+
     fn normalize_assume_role_arn(&mut self) {
         if let Some(assume_role_arn) = self.assume_role_arn.as_ref() {
             if assume_role_arn.is_empty() {
@@ -1073,7 +1625,17 @@ impl S3Profile {
         self.assume_role_arn = None;
         self.sts_role_arn = None;
         self.sts_enabled = true;
-        self.flavor = S3Flavor::S3Compat;
+        self.flavor = S3Flavor::CloudflareR2;
+
+        if !(1..=R2_MAX_TOKEN_VALIDITY_SECONDS).contains(&self.sts_token_validity_seconds) {
+            return Err(ValidationError::InvalidProfile {
+                source: None,
+                reason: format!(
+                    "Parameter `sts-token-validity-seconds` must be between 1 and {R2_MAX_TOKEN_VALIDITY_SECONDS} for Cloudflare R2."
+                ),
+                entity: "sts-token-validity-seconds".to_string(),
+            });
+        }
 
         // If an endpoint is specified and ends with the bucket, remove the bucket from the endpoint.
         // This is common as in the UI, cloudflare shows the S3 API with the bucket name at the end.
@@ -1397,6 +1959,78 @@ pub(crate) mod test {
         NamespaceId,
     };
 
+    #[test]
+    fn test_quirks_require_s3_compat_flavor() {
+        let quirks = S3Quirks {
+            no_tagging: true,
+            ..S3Quirks::default()
+        };
+        let mut profile = S3Profile::builder()
+            .bucket("test-bucket".to_string())
+            .region("us-east-1".to_string())
+            .sts_enabled(false)
+            .flavor(S3Flavor::Aws)
+            .quirks(quirks)
+            .build();
+        assert!(profile.normalize(None).is_err());
+
+        profile.flavor = S3Flavor::S3Compat;
+        profile.normalize(None).unwrap();
+
+        let deserialized: S3Quirks =
+            serde_json::from_value(serde_json::json!({"no-tagging": true})).unwrap();
+        assert_eq!(deserialized, quirks);
+    }
+
+    #[test]
+    fn test_normalize_additional_allowed_locations() {
+        let mut profile = S3Profile::builder()
+            .bucket("test-bucket".to_string())
+            .region("us-east-1".to_string())
+            .sts_enabled(false)
+            .additional_allowed_locations(vec![
+                "s3://other-bucket/prefix/".to_string(),
+                "s3://other-bucket/prefix".to_string(),
+            ])
+            .build();
+        profile.normalize(None).unwrap();
+        assert_eq!(
+            profile.additional_allowed_locations,
+            vec!["s3://other-bucket/prefix".to_string()]
+        );
+
+        profile.additional_allowed_locations = vec!["abfss://fs@account/prefix".to_string()];
+        assert!(profile.normalize(None).is_err());
+        profile.additional_allowed_locations = vec!["s3://Invalid_Bucket/prefix".to_string()];
+        assert!(profile.normalize(None).is_err());
+    }
+
+    #[test]
+    fn test_additional_allowed_locations_overlap() {
+        let profile = S3Profile::builder()
+            .bucket("bucket-a".to_string())
+            .region("us-east-1".to_string())
+            .sts_enabled(false)
+            .additional_allowed_locations(vec!["s3://bucket-b/shared".to_string()])
+            .build();
+        let other = S3Profile::builder()
+            .bucket("bucket-b".to_string())
+            .region("us-east-1".to_string())
+            .sts_enabled(false)
+            .key_prefix("shared/warehouse".to_string())
+            .build();
+        assert!(profile.is_overlapping_location(&other));
+        assert!(other.is_overlapping_location(&profile));
+
+        let other = S3Profile::builder()
+            .bucket("bucket-b".to_string())
+            .region("us-east-1".to_string())
+            .sts_enabled(false)
+            .key_prefix("private".to_string())
+            .build();
+        assert!(!profile.is_overlapping_location(&other));
+    }
+
     #[test]
     fn test_deserialize_flavor() {
         let flavor: S3Flavor = serde_json::from_value(serde_json::json!("aws")).unwrap();
@@ -1404,6 +2038,116 @@ pub(crate) mod test {
 
         let flavor: S3Flavor = serde_json::from_value(serde_json::json!("s3-compat")).unwrap();
         assert_eq!(flavor, S3Flavor::S3Compat);
+
+        let flavor: S3Flavor = serde_json::from_value(serde_json::json!("cloudflare-r2")).unwrap();
+        assert_eq!(flavor, S3Flavor::CloudflareR2);
+    }
+
+    #[test]
+    fn test_normalize_r2_flavor() {
+        let credential = S3Credential::CloudflareR2(S3CloudflareR2Credential {
+            account_id: "account".to_string(),
+            access_key_id: "key".to_string(),
+            secret_access_key: "secret".to_string(),
+            token: "token".to_string(),
+        });
+        let mut profile = S3Profile::builder()
+            .bucket("test-bucket".to_string())
+            .region("auto".to_string())
+            .endpoint("https://account.r2.cloudflarestorage.com".parse().unwrap())
+            .sts_enabled(false)
+            .assume_role_arn("arn:aws:iam::123456789012:role/foo".to_string())
+            .build();
+        profile.normalize(Some(&credential)).unwrap();
+        assert_eq!(profile.flavor, S3Flavor::CloudflareR2);
+        assert!(profile.sts_enabled);
+        assert!(profile.assume_role_arn.is_none());
+
+        profile.sts_token_validity_seconds = R2_MAX_TOKEN_VALIDITY_SECONDS + 1;
+        assert!(profile.normalize(Some(&credential)).is_err());
+
+        // The R2 flavor is only usable together with an R2 credential
+        profile.sts_token_validity_seconds = 3600;
+        assert!(profile.normalize(None).is_err());
+    }
+
+    #[test]
+    fn test_validate_sts_credentials_cache_seconds() {
+        let mut profile = S3Profile::builder()
+            .bucket("test-bucket".to_string())
+            .region("us-east-1".to_string())
+            .sts_enabled(true)
+            .sts_role_arn("arn:aws:iam::123456789012:role/foo".to_string())
+            .sts_credentials_cache_seconds(1800)
+            .build();
+        profile.normalize(None).unwrap();
+
+        profile.sts_credentials_cache_seconds = 1801;
+        assert!(profile.normalize(None).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sts_credentials_are_reused() {
+        let profile = S3Profile::builder()
+            .bucket("test-bucket".to_string())
+            .region("us-east-1".to_string())
+            .flavor(S3Flavor::S3Compat)
+            // Requests to the STS endpoint fail
+            .endpoint("http://localhost:1".parse().unwrap())
+            .sts_enabled(true)
+            .sts_credentials_cache_seconds(60)
+            .build();
+        let location = Location::from_str("s3://test-bucket/table").unwrap();
+        let request_metadata = RequestMetadata::new_unauthenticated();
+        let tabular_id = TabularId::Table(uuid::Uuid::now_v7());
+
+        let cache_key = profile
+            .sts_credentials_cache_key(
+                None,
+                &location,
+                StoragePermissions::Read,
+                &request_metadata,
+                tabular_id,
+            )
+            .unwrap();
+        let credentials = aws_sdk_sts::types::Credentials::builder()
+            .access_key_id("access-key")
+            .secret_access_key("secret")
+            .session_token("token")
+            .expiration(aws_sdk_sts::primitives::DateTime::from_secs(4_000_000_000))
+            .build()
+            .unwrap();
+        STS_CREDENTIALS_CACHE.insert(
+            cache_key,
+            CachedStsCredentials {
+                credentials: credentials.clone(),
+                cached_until: Instant::now() + Duration::from_secs(60),
+            },
+        );
+
+        let cached = profile
+            .cached_sts_credentials(
+                None,
+                &location,
+                StoragePermissions::Read,
+                &request_metadata,
+                tabular_id,
+            )
+            .await
+            .unwrap();
+        assert_eq!(cached, credentials);
+
+        // Other permissions are not served from the cache
+        assert!(profile
+            .cached_sts_credentials(
+                None,
+                &location,
+                StoragePermissions::ReadWrite,
+                &request_metadata,
+                tabular_id,
+            )
+            .await
+            .is_err());
     }
 
     #[test]
@@ -1454,6 +2198,7 @@ pub(crate) mod test {
             aws_access_key_id: "foo".to_string(),
             aws_secret_access_key: "bar".to_string(),
             external_id: None,
+            sse_customer_key: None,
         });
         assert_eq!(credential, expected);
     }
@@ -1473,6 +2218,7 @@ pub(crate) mod test {
             aws_access_key_id: "foo".to_string(),
             aws_secret_access_key: "bar".to_string(),
             external_id: Some("baz".to_string()),
+            sse_customer_key: None,
         });
         assert_eq!(credential, expected);
     }
@@ -1485,8 +2231,10 @@ pub(crate) mod test {
             }
         );
         let credential: S3Credential = serde_json::from_value(secret).unwrap();
-        let expected =
-            S3Credential::AwsSystemIdentity(S3AwsSystemIdentityCredential { external_id: None });
+        let expected = S3Credential::AwsSystemIdentity(S3AwsSystemIdentityCredential {
+            external_id: None,
+            sse_customer_key: None,
+        });
         assert_eq!(credential, expected);
     }
 
@@ -1501,6 +2249,7 @@ pub(crate) mod test {
         let credential: S3Credential = serde_json::from_value(secret).unwrap();
         let expected = S3Credential::AwsSystemIdentity(S3AwsSystemIdentityCredential {
             external_id: Some("baz".to_string()),
+            sse_customer_key: None,
         });
         assert_eq!(credential, expected);
     }
@@ -1573,8 +2322,15 @@ pub(crate) mod test {
             allow_alternative_protocols: Some(false),
             remote_signing_url_style: S3UrlStyleDetectionMode::Auto,
             sts_token_validity_seconds: 3600,
+            sts_credentials_cache_seconds: 0,
             push_s3_delete_disabled: false,
             aws_kms_key_arn: None,
+            sse_kms_key_id: None,
+            sse_bucket_key_enabled: false,
+            quirks: S3Quirks::default(),
+            access_point_arn: None,
+            requester_pays: false,
+            additional_allowed_locations: vec![],
         };
         let sp: StorageProfile = profile.clone().into();
 
@@ -1617,8 +2373,15 @@ pub(crate) mod test {
             allow_alternative_protocols: Some(false),
             remote_signing_url_style: S3UrlStyleDetectionMode::Auto,
             sts_token_validity_seconds: 3600,
+            sts_credentials_cache_seconds: 0,
             push_s3_delete_disabled: false,
             aws_kms_key_arn: None,
+            sse_kms_key_id: None,
+            sse_bucket_key_enabled: false,
+            quirks: S3Quirks::default(),
+            access_point_arn: None,
+            requester_pays: false,
+            additional_allowed_locations: vec![],
         };
 
         let namespace_location = Location::from_str("s3://test-bucket/foo/").unwrap();
@@ -1674,13 +2437,21 @@ pub(crate) mod test {
                 remote_signing_url_style:
                     crate::service::storage::s3::S3UrlStyleDetectionMode::Auto,
                 sts_token_validity_seconds: 3600,
+                sts_credentials_cache_seconds: 0,
                 push_s3_delete_disabled: false,
                 aws_kms_key_arn: None,
+                sse_kms_key_id: None,
+                sse_bucket_key_enabled: false,
+                quirks: S3Quirks::default(),
+                access_point_arn: None,
+                requester_pays: false,
+                additional_allowed_locations: vec![],
             };
             let cred = S3Credential::AccessKey(S3AccessKeyCredential {
                 aws_access_key_id: TEST_ACCESS_KEY.clone(),
                 aws_secret_access_key: TEST_SECRET_KEY.clone(),
                 external_id: None,
+                sse_customer_key: None,
             });
 
             (profile, cred)
@@ -1730,13 +2501,21 @@ pub(crate) mod test {
                 remote_signing_url_style:
                     crate::service::storage::s3::S3UrlStyleDetectionMode::Auto,
                 sts_token_validity_seconds: 3600,
+                sts_credentials_cache_seconds: 0,
                 push_s3_delete_disabled: false,
                 aws_kms_key_arn: None,
+                sse_kms_key_id: None,
+                sse_bucket_key_enabled: false,
+                quirks: S3Quirks::default(),
+                access_point_arn: None,
+                requester_pays: false,
+                additional_allowed_locations: vec![],
             };
             let cred = S3Credential::AccessKey(S3AccessKeyCredential {
                 aws_access_key_id: std::env::var("AWS_S3_ACCESS_KEY_ID").unwrap(),
                 aws_secret_access_key: std::env::var("AWS_S3_SECRET_ACCESS_KEY").unwrap(),
                 external_id: None,
+                sse_customer_key: None,
             });
 
             (profile, cred)
@@ -1785,13 +2564,21 @@ pub(crate) mod test {
                 remote_signing_url_style:
                     crate::service::storage::s3::S3UrlStyleDetectionMode::Auto,
                 sts_token_validity_seconds: 3600,
+                sts_credentials_cache_seconds: 0,
                 push_s3_delete_disabled: false,
                 aws_kms_key_arn: Some(std::env::var("AWS_S3_KMS_ARN").unwrap()),
+                sse_kms_key_id: None,
+                sse_bucket_key_enabled: false,
+                quirks: S3Quirks::default(),
+                access_point_arn: None,
+                requester_pays: false,
+                additional_allowed_locations: vec![],
             };
             let cred = S3Credential::AccessKey(S3AccessKeyCredential {
                 aws_access_key_id: std::env::var("AWS_S3_ACCESS_KEY_ID").unwrap(),
                 aws_secret_access_key: std::env::var("AWS_S3_SECRET_ACCESS_KEY").unwrap(),
                 external_id: None,
+                sse_customer_key: None,
             });
 
             (profile, cred)
@@ -1845,8 +2632,15 @@ pub(crate) mod test {
                 remote_signing_url_style:
                     crate::service::storage::s3::S3UrlStyleDetectionMode::Auto,
                 sts_token_validity_seconds: 3600,
+                sts_credentials_cache_seconds: 0,
                 push_s3_delete_disabled: false,
                 aws_kms_key_arn: None,
+                sse_kms_key_id: None,
+                sse_bucket_key_enabled: false,
+                quirks: S3Quirks::default(),
+                access_point_arn: None,
+                requester_pays: false,
+                additional_allowed_locations: vec![],
             };
             let cred = S3Credential::CloudflareR2(S3CloudflareR2Credential {
                 access_key_id: std::env::var("LAKEKEEPER_TEST__R2_ACCESS_KEY_ID").unwrap(),
@@ -1955,6 +2749,198 @@ pub(crate) mod test {
         let _ = serde_json::from_str::<serde_json::Value>(&policy).unwrap();
     }
 
+    #[test]
+    fn policy_string_contains_sse_kms_key() {
+        let table_location = "s3://bucket-name/path/to/table";
+        let key_arn = "arn:aws:kms:us-east-1:123456789012:key/1234abcd-12ab-34cd-56ef-1234567890ab";
+        let profile = S3Profile::builder()
+            .bucket("bucket-name".to_string())
+            .region("us-east-1".to_string())
+            .flavor(S3Flavor::Aws)
+            .sts_enabled(true)
+            .sse_kms_key_id(key_arn.to_string())
+            .build();
+        let policy = profile
+            .get_aws_policy_string(
+                &table_location.parse().unwrap(),
+                StoragePermissions::ReadWrite,
+            )
+            .unwrap();
+        let policy = serde_json::from_str::<serde_json::Value>(&policy).unwrap();
+        assert_eq!(
+            policy["Statement"][2]["Resource"],
+            serde_json::json!([key_arn])
+        );
+    }
+
+    #[test]
+    fn policy_string_contains_access_point() {
+        let table_location = "s3://bucket-name/path/to/table";
+        let ap_arn = "arn:aws:s3:us-east-1:123456789012:accesspoint/my-ap";
+        let profile = S3Profile::builder()
+            .bucket("bucket-name".to_string())
+            .region("us-east-1".to_string())
+            .flavor(S3Flavor::Aws)
+            .sts_enabled(true)
+            .access_point_arn(ap_arn.to_string())
+            .build();
+        let policy = profile
+            .get_aws_policy_string(&table_location.parse().unwrap(), StoragePermissions::Read)
+            .unwrap();
+        let policy = serde_json::from_str::<serde_json::Value>(&policy).unwrap();
+        assert_eq!(
+            policy["Statement"][0]["Resource"],
+            serde_json::json!([
+                "arn:aws:s3:::bucket-name/path/to/table/",
+                "arn:aws:s3:::bucket-name/path/to/table/*",
+                format!("{ap_arn}/object/path/to/table/"),
+                format!("{ap_arn}/object/path/to/table/*"),
+            ])
+        );
+        assert_eq!(
+            policy["Statement"][1]["Resource"],
+            serde_json::json!(["arn:aws:s3:::bucket-name", ap_arn])
+        );
+    }
+
+    #[test]
+    fn test_access_point_validation() {
+        let mut profile = S3Profile::builder()
+            .bucket("test-bucket".to_string())
+            .region("eu-central-1".to_string())
+            .flavor(S3Flavor::Aws)
+            .sts_enabled(false)
+            .access_point_arn(" ".to_string())
+            .build();
+        profile.normalize(None).unwrap();
+        assert_eq!(profile.access_point_arn, None);
+
+        profile.access_point_arn =
+            Some("arn:aws:s3:eu-central-1:123456789012:accesspoint/my-ap".to_string());
+        profile.normalize(None).unwrap();
+
+        profile.access_point_arn =
+            Some("arn:aws:s3::123456789012:accesspoint/mfzwi23gnjvgw.mrap".to_string());
+        profile.normalize(None).unwrap();
+
+        // Region mismatch
+        let mut other_region = profile.clone();
+        other_region.access_point_arn =
+            Some("arn:aws:s3:us-east-1:123456789012:accesspoint/my-ap".to_string());
+        assert!(other_region.normalize(None).is_err());
+
+        // Path style access
+        let mut path_style = profile.clone();
+        path_style.path_style_access = Some(true);
+        assert!(path_style.normalize(None).is_err());
+
+        // S3 compatible stores
+        let mut s3_compat = profile.clone();
+        s3_compat.flavor = S3Flavor::S3Compat;
+        assert!(s3_compat.normalize(None).is_err());
+    }
+
+    #[test]
+    fn test_server_side_encryption() {
+        let mut profile = S3Profile::builder()
+            .bucket("test-bucket".to_string())
+            .region("eu-central-1".to_string())
+            .flavor(S3Flavor::Aws)
+            .sts_enabled(false)
+            .sse_kms_key_id(" alias/lakekeeper ".to_string())
+            .sse_bucket_key_enabled(true)
+            .build();
+        profile.normalize(None).unwrap();
+        assert_eq!(
+            profile.server_side_encryption(None).unwrap(),
+            Some(S3ServerSideEncryption::Kms {
+                key_id: "alias/lakekeeper".to_string(),
+                bucket_key_enabled: true,
+            })
+        );
+
+        profile.sse_kms_key_id =
+            Some("arn:aws:kms:us-east-1:123456789012:alias/lakekeeper".to_string());
+        assert!(profile.normalize(None).is_err());
+        profile.sse_kms_key_id = Some("not-a-key".to_string());
+        assert!(profile.normalize(None).is_err());
+
+        // 32 zero bytes
+        let customer_key = "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=";
+        let credential = S3Credential::AccessKey(S3AccessKeyCredential {
+            aws_access_key_id: "foo".to_string(),
+            aws_secret_access_key: "bar".to_string(),
+            external_id: None,
+            sse_customer_key: Some(customer_key.to_string()),
+        });
+        assert!(profile.server_side_encryption(Some(&credential)).is_err());
+
+        profile.sse_kms_key_id = None;
+        assert_eq!(
+            profile.server_side_encryption(Some(&credential)).unwrap(),
+            Some(S3ServerSideEncryption::CustomerKey {
+                key: customer_key.to_string(),
+                key_md5: "cLyPS3KoaSFGi/joRB3OUQ==".to_string(),
+            })
+        );
+
+        let credential = S3Credential::AwsSystemIdentity(S3AwsSystemIdentityCredential {
+            external_id: None,
+            sse_customer_key: Some("dG9vLXNob3J0".to_string()),
+        });
+        assert!(profile.normalize(Some(&credential)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_requester_pays_table_config() {
+        let mut profile = S3Profile::builder()
+            .bucket("test-bucket".to_string())
+            .region("eu-central-1".to_string())
+            .flavor(S3Flavor::Aws)
+            .sts_enabled(false)
+            .build();
+        let location = Location::from_str("s3://test-bucket/table").unwrap();
+        let request_metadata = RequestMetadata::new_unauthenticated();
+        let warehouse_id = WarehouseId::new_random();
+        let tabular_id = TabularId::Table(uuid::Uuid::now_v7());
+        let data_access = DataAccess {
+            vended_credentials: false,
+            remote_signing: true,
+        };
+
+        let config = profile
+            .generate_table_config(
+                data_access,
+                None,
+                &location,
+                StoragePermissions::Read,
+                &request_metadata,
+                warehouse_id,
+                tabular_id,
+            )
+            .await
+            .unwrap();
+        assert_eq!(config.config.get_prop_opt::<s3::RequesterPays>(), None);
+
+        profile.requester_pays = true;
+        let config = profile
+            .generate_table_config(
+                data_access,
+                None,
+                &location,
+                StoragePermissions::Read,
+                &request_metadata,
+                warehouse_id,
+                tabular_id,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            config.config.get_prop_opt::<s3::RequesterPays>(),
+            Some(true)
+        );
+    }
+
     #[test]
     fn test_parse_s3_location_invalid_proto() {
         S3Location::try_from_str("adls://test-bucket/foo/", false).unwrap_err();
@@ -2007,8 +2993,15 @@ mod is_overlapping_location_tests {
             allow_alternative_protocols: None,
             remote_signing_url_style: S3UrlStyleDetectionMode::Auto,
             sts_token_validity_seconds: 3600,
+            sts_credentials_cache_seconds: 0,
             push_s3_delete_disabled: true,
             aws_kms_key_arn: None,
+            sse_kms_key_id: None,
+            sse_bucket_key_enabled: false,
+            quirks: S3Quirks::default(),
+            access_point_arn: None,
+            requester_pays: false,
+            additional_allowed_locations: vec![],
         }
     }
 
@@ -0,0 +1,173 @@
+use std::str::FromStr;
+
+use super::ValidationError;
+
+const MULTI_REGION_ALIAS_SUFFIX: &str = ".mrap";
+
+/// An S3 Access Point or Multi-Region Access Point, identified by its ARN.
+///
+/// Standard access points: `arn:aws:s3:<region>:<account-id>:accesspoint/<name>`
+/// Multi-Region access points: `arn:aws:s3::<account-id>:accesspoint/<alias>.mrap`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct S3AccessPoint {
+    arn: String,
+    region: Option<String>,
+    account_id: String,
+    name: String,
+}
+
+impl S3AccessPoint {
+    #[must_use]
+    pub fn arn(&self) -> &str {
+        &self.arn
+    }
+
+    /// Region of the access point. `None` for Multi-Region Access Points.
+    #[must_use]
+    pub fn region(&self) -> Option<&str> {
+        self.region.as_deref()
+    }
+
+    #[must_use]
+    pub fn is_multi_region(&self) -> bool {
+        self.region.is_none()
+    }
+
+    /// Check whether requests to `host` are addressed to this access point.
+    ///
+    /// Standard access points are reachable via
+    /// `<name>-<account-id>.s3-accesspoint[-fips][.dualstack].<region>.<domain>`,
+    /// Multi-Region Access Points via `<alias>.accesspoint.s3-global.<domain>`.
+    #[must_use]
+    pub fn matches_host(&self, host: &str) -> bool {
+        let host = host.to_lowercase();
+        match &self.region {
+            Some(region) => host
+                .strip_prefix(&format!("{}-{}.", self.name, self.account_id))
+                .is_some_and(|endpoint| {
+                    endpoint.starts_with("s3-accesspoint")
+                        && endpoint.split('.').any(|label| label == region)
+                }),
+            None => host
+                .strip_prefix(&self.name)
+                .is_some_and(|endpoint| endpoint.starts_with(".accesspoint.s3-global.")),
+        }
+    }
+}
+
+impl FromStr for S3AccessPoint {
+    type Err = ValidationError;
+
+    fn from_str(arn: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: String| ValidationError::InvalidProfile {
+            source: None,
+            reason,
+            entity: "access-point-arn".to_string(),
+        };
+
+        // arn:<partition>:s3:<region>:<account-id>:accesspoint/<name>
+        let parts = arn.splitn(6, ':').collect::<Vec<_>>();
+        let [prefix, partition, service, region, account_id, resource] = parts[..] else {
+            return Err(invalid(format!(
+                "`access-point-arn` is not a valid S3 access point ARN: {arn}"
+            )));
+        };
+        let name = resource.strip_prefix("accesspoint/").unwrap_or_default();
+
+        if prefix != "arn" || partition.is_empty() || service != "s3" || name.is_empty() {
+            return Err(invalid(format!(
+                "`access-point-arn` is not a valid S3 access point ARN: {arn}"
+            )));
+        }
+        if account_id.len() != 12 || !account_id.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid(format!(
+                "`access-point-arn` must contain a 12 digit account ID. Got: {account_id}"
+            )));
+        }
+        if !name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '.')
+        {
+            return Err(invalid(format!(
+                "Access point name must only contain lowercase letters, numbers, hyphens and periods. Got: {name}"
+            )));
+        }
+
+        let is_multi_region = name.ends_with(MULTI_REGION_ALIAS_SUFFIX);
+        if is_multi_region != region.is_empty() {
+            return Err(invalid(format!(
+                "`access-point-arn` must specify a region for standard access points and no region for Multi-Region Access Points (alias ending in `{MULTI_REGION_ALIAS_SUFFIX}`). Got: {arn}"
+            )));
+        }
+
+        Ok(Self {
+            arn: arn.to_string(),
+            region: (!region.is_empty()).then(|| region.to_string()),
+            account_id: account_id.to_string(),
+            name: name.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_access_point_arn() {
+        let ap: S3AccessPoint = "arn:aws:s3:eu-central-1:123456789012:accesspoint/my-ap"
+            .parse()
+            .unwrap();
+        assert_eq!(ap.region(), Some("eu-central-1"));
+        assert!(!ap.is_multi_region());
+
+        let mrap: S3AccessPoint = "arn:aws:s3::123456789012:accesspoint/mfzwi23gnjvgw.mrap"
+            .parse()
+            .unwrap();
+        assert_eq!(mrap.region(), None);
+        assert!(mrap.is_multi_region());
+
+        for invalid in [
+            "my-ap",
+            "arn:aws:s3:::my-bucket",
+            "arn:aws:kms:eu-central-1:123456789012:accesspoint/my-ap",
+            "arn:aws:s3:eu-central-1:1234:accesspoint/my-ap",
+            "arn:aws:s3:eu-central-1:123456789012:accesspoint/",
+            "arn:aws:s3:eu-central-1:123456789012:accesspoint/My_AP",
+            "arn:aws:s3::123456789012:accesspoint/my-ap",
+            "arn:aws:s3:eu-central-1:123456789012:accesspoint/mfzwi23gnjvgw.mrap",
+        ] {
+            assert!(
+                invalid.parse::<S3AccessPoint>().is_err(),
+                "{invalid} should be invalid"
+            );
+        }
+    }
+
+    #[test]
+    fn test_matches_host() {
+        let ap: S3AccessPoint = "arn:aws:s3:eu-central-1:123456789012:accesspoint/my-ap"
+            .parse()
+            .unwrap();
+        for host in [
+            "my-ap-123456789012.s3-accesspoint.eu-central-1.amazonaws.com",
+            "my-ap-123456789012.s3-accesspoint.dualstack.eu-central-1.amazonaws.com",
+            "my-ap-123456789012.s3-accesspoint-fips.eu-central-1.amazonaws.com",
+        ] {
+            assert!(ap.matches_host(host), "{host} should match");
+        }
+        for host in [
+            "my-ap-123456789012.s3-accesspoint.us-east-1.amazonaws.com",
+            "other-ap-123456789012.s3-accesspoint.eu-central-1.amazonaws.com",
+            "my-ap.s3.eu-central-1.amazonaws.com",
+        ] {
+            assert!(!ap.matches_host(host), "{host} should not match");
+        }
+
+        let mrap: S3AccessPoint = "arn:aws:s3::123456789012:accesspoint/mfzwi23gnjvgw.mrap"
+            .parse()
+            .unwrap();
+        assert!(mrap.matches_host("mfzwi23gnjvgw.mrap.accesspoint.s3-global.amazonaws.com"));
+        assert!(!mrap.matches_host("mfzwi23gnjvgw.s3.eu-central-1.amazonaws.com"));
+    }
+}
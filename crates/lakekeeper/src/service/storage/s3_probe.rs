@@ -0,0 +1,290 @@
+use std::time::Duration;
+
+use futures::StreamExt;
+use iceberg_ext::configs::Location;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    s3::{S3Credential, S3Profile, S3Quirks, S3_HTTP_CLIENT},
+    NamespaceId, StorageProfile, ValidationError,
+};
+use crate::catalog::{
+    compression_codec::CompressionCodec,
+    io::{delete_file, list_location, remove_all, write_metadata_file, StorageIo},
+};
+
+/// Number of write / delete rounds used to detect stale listings.
+const LIST_CONSISTENCY_PROBE_ROUNDS: usize = 3;
+const VENDOR_PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Vendor of an S3-compatible object store, as detected from its responses.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    utoipa::ToSchema,
+    strum_macros::Display,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum S3Vendor {
+    Aws,
+    Minio,
+    Ceph,
+    Seaweedfs,
+    Garage,
+    Unknown,
+}
+
+impl S3Vendor {
+    fn from_response_headers(headers: &http::HeaderMap) -> Self {
+        let server = headers
+            .get(http::header::SERVER)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default()
+            .to_lowercase();
+
+        if server.contains("minio") {
+            Self::Minio
+        } else if server.contains("seaweedfs") {
+            Self::Seaweedfs
+        } else if server.contains("garage") {
+            Self::Garage
+        } else if server.contains("ceph") || server.contains("rgw") {
+            Self::Ceph
+        } else if server.contains("amazons3") {
+            Self::Aws
+        } else if headers
+            .get("x-amz-request-id")
+            .and_then(|v| v.to_str().ok())
+            // RGW request ids look like `tx000001a2b3c4d5e6f7a8b-0065f1e2d3-1a2b-default`
+            .is_some_and(|id| id.starts_with("tx") && id.matches('-').count() >= 2)
+        {
+            Self::Ceph
+        } else {
+            Self::Unknown
+        }
+    }
+
+    /// Quirks that are known for the vendor, independent of the deployment.
+    #[must_use]
+    pub fn known_quirks(self) -> S3Quirks {
+        match self {
+            Self::Aws | Self::Minio | Self::Unknown => S3Quirks::default(),
+            // Trailing checksums are only supported by recent RGW releases (Squid and later).
+            Self::Ceph => S3Quirks {
+                no_checksum_trailers: true,
+                ..S3Quirks::default()
+            },
+            // Listings are served from filer metadata that is cached per S3 gateway.
+            Self::Seaweedfs => S3Quirks {
+                no_checksum_trailers: true,
+                weak_list_consistency: true,
+                ..S3Quirks::default()
+            },
+            Self::Garage => S3Quirks {
+                no_tagging: true,
+                no_checksum_trailers: true,
+                ..S3Quirks::default()
+            },
+        }
+    }
+}
+
+/// Result of probing the storage of an S3 profile.
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct S3QuirksProbe {
+    /// Detected vendor of the object store.
+    pub vendor: S3Vendor,
+    /// Value of the `Server` header returned by the object store, if any.
+    pub server: Option<String>,
+    /// Whether written and deleted objects were immediately reflected in listings.
+    pub list_after_write_consistent: bool,
+    /// Quirks that should be configured for the storage profile.
+    pub suggested_quirks: S3Quirks,
+}
+
+impl S3Profile {
+    /// Probe the object store of this profile and suggest the quirks to configure.
+    ///
+    /// The vendor is detected from an unauthenticated request to the bucket.
+    /// List-after-write consistency is tested by writing and deleting objects
+    /// in a random location below the key prefix, which is removed afterwards.
+    ///
+    /// # Errors
+    /// Fails if objects cannot be written, listed or deleted with the given credential.
+    pub async fn probe_quirks(
+        &self,
+        credential: Option<&S3Credential>,
+    ) -> Result<S3QuirksProbe, ValidationError> {
+        let (vendor, server) = self.detect_vendor().await;
+
+        let storage_profile = StorageProfile::S3(self.clone());
+        let file_io: StorageIo = self.file_io(credential).await?.into();
+        let probe_location =
+            storage_profile.default_namespace_location(NamespaceId::new_random())?;
+        let consistency =
+            is_list_after_write_consistent(&file_io, &probe_location, &storage_profile).await;
+
+        if let Err(e) = remove_all(&file_io, &probe_location).await {
+            tracing::warn!("Failed to clean up probe location {probe_location}: {e}");
+        }
+        let list_after_write_consistent = consistency?;
+
+        let mut suggested_quirks = vendor.known_quirks();
+        suggested_quirks.weak_list_consistency |= !list_after_write_consistent;
+
+        Ok(S3QuirksProbe {
+            vendor,
+            server,
+            list_after_write_consistent,
+            suggested_quirks,
+        })
+    }
+
+    async fn detect_vendor(&self) -> (S3Vendor, Option<String>) {
+        let Some(endpoint) = &self.endpoint else {
+            return (S3Vendor::Aws, None);
+        };
+        let url = match endpoint.join(&self.bucket) {
+            Ok(url) => url,
+            Err(e) => {
+                tracing::debug!("Failed to build vendor probe URL for endpoint {endpoint}: {e}");
+                return (S3Vendor::Unknown, None);
+            }
+        };
+
+        match S3_HTTP_CLIENT
+            .head(url)
+            .timeout(VENDOR_PROBE_TIMEOUT)
+            .send()
+            .await
+        {
+            Ok(response) => {
+                let server = response
+                    .headers()
+                    .get(http::header::SERVER)
+                    .and_then(|v| v.to_str().ok())
+                    .map(ToString::to_string);
+                (S3Vendor::from_response_headers(response.headers()), server)
+            }
+            Err(e) => {
+                tracing::info!("Failed to detect vendor of S3 endpoint {endpoint}: {e}");
+                (S3Vendor::Unknown, None)
+            }
+        }
+    }
+}
+
+async fn is_list_after_write_consistent(
+    file_io: &StorageIo,
+    location: &Location,
+    storage_profile: &StorageProfile,
+) -> Result<bool, ValidationError> {
+    let io_error = |e| ValidationError::IoOperationFailed(e, Box::new(storage_profile.clone()));
+    let mut consistent = true;
+
+    for round in 0..LIST_CONSISTENCY_PROBE_ROUNDS {
+        let file_name = format!("probe-{round}.json");
+        let mut file = location.clone();
+        file.push(&file_name);
+
+        write_metadata_file(&file, "probe", CompressionCodec::None, file_io)
+            .await
+            .map_err(io_error)?;
+        if !is_listed(file_io, location, &file_name)
+            .await
+            .map_err(io_error)?
+        {
+            tracing::debug!("Written object {file} is missing in listing of {location}");
+            consistent = false;
+        }
+
+        delete_file(file_io, &file).await.map_err(io_error)?;
+        if is_listed(file_io, location, &file_name)
+            .await
+            .map_err(io_error)?
+        {
+            tracing::debug!("Deleted object {file} is still present in listing of {location}");
+            consistent = false;
+        }
+    }
+
+    Ok(consistent)
+}
+
+async fn is_listed(
+    file_io: &StorageIo,
+    location: &Location,
+    file_name: &str,
+) -> Result<bool, crate::catalog::io::IoError> {
+    let mut entry_stream = list_location(file_io, location, None).await?;
+    while let Some(entries) = entry_stream.next().await {
+        if entries?.iter().any(|entry| entry.ends_with(file_name)) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(entries: &[(&'static str, &'static str)]) -> http::HeaderMap {
+        entries
+            .iter()
+            .map(|(k, v)| {
+                (
+                    http::HeaderName::from_static(k),
+                    http::HeaderValue::from_static(v),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_vendor_from_headers() {
+        assert_eq!(
+            S3Vendor::from_response_headers(&headers(&[("server", "MinIO")])),
+            S3Vendor::Minio
+        );
+        assert_eq!(
+            S3Vendor::from_response_headers(&headers(&[("server", "SeaweedFS S3")])),
+            S3Vendor::Seaweedfs
+        );
+        assert_eq!(
+            S3Vendor::from_response_headers(&headers(&[("server", "Garage/v1.0.1")])),
+            S3Vendor::Garage
+        );
+        assert_eq!(
+            S3Vendor::from_response_headers(&headers(&[("server", "AmazonS3")])),
+            S3Vendor::Aws
+        );
+        assert_eq!(
+            S3Vendor::from_response_headers(&headers(&[(
+                "x-amz-request-id",
+                "tx00000a1b2c3d4e5f6a7b8-0065f1e2d3-1a2b-default"
+            )])),
+            S3Vendor::Ceph
+        );
+        assert_eq!(
+            S3Vendor::from_response_headers(&headers(&[("x-amz-request-id", "17C2A4F8E9B0D1A2")])),
+            S3Vendor::Unknown
+        );
+    }
+
+    #[test]
+    fn test_known_quirks() {
+        assert!(S3Vendor::Minio.known_quirks().is_empty());
+        assert!(S3Vendor::Aws.known_quirks().is_empty());
+        assert!(S3Vendor::Ceph.known_quirks().no_checksum_trailers);
+        assert!(S3Vendor::Garage.known_quirks().no_tagging);
+        assert!(S3Vendor::Seaweedfs.known_quirks().weak_list_consistency);
+    }
+}
@@ -0,0 +1,182 @@
+//! Intent log for storage operations that accompany pointer updates of tables.
+//!
+//! New metadata files are written before the transaction that updates the metadata
+//! location of a table commits, expired metadata files are deleted afterwards. A crash
+//! in between leaves files behind that no table references. To make these two-phase
+//! updates crash-safe, an intent is recorded before the storage work starts:
+//!
+//! 1. Write intents are recorded in their own transaction before the file is written
+//!    and are completed in the transaction that updates the pointer.
+//! 2. Delete intents are recorded in the transaction that updates the pointer and are
+//!    completed once the files are deleted.
+//!
+//! Intents that are still open after [`STORAGE_INTENT_GRACE_PERIOD`] are recovered on
+//! startup and periodically afterwards by deleting the affected files.
+//! Recovery locks the intents, so a pointer update that completes a recovered
+//! write intent fails instead of referencing a deleted file.
+
+use std::{collections::HashMap, time::Duration};
+
+use iceberg_ext::configs::Location;
+use itertools::Itertools as _;
+use uuid::Uuid;
+
+use super::{Catalog, Result, SecretStore, TableId, Transaction};
+use crate::{
+    catalog::{io::delete_file, maybe_get_secret},
+    WarehouseId,
+};
+
+/// Minimum age of an open intent before it is recovered.
+/// Operations that are still in progress must finish within this period.
+pub const STORAGE_INTENT_GRACE_PERIOD: Duration = Duration::from_secs(15 * 60);
+const RECOVERY_INTERVAL: Duration = Duration::from_secs(5 * 60);
+const MAX_INTENTS_PER_RECOVERY: i64 = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "sqlx-postgres", derive(sqlx::Type))]
+#[cfg_attr(
+    feature = "sqlx-postgres",
+    sqlx(type_name = "storage_intent_kind", rename_all = "kebab-case")
+)]
+pub enum StorageIntentKind {
+    /// A metadata file is written for a pointer update that did not commit yet.
+    WriteMetadata,
+    /// A file is no longer referenced after a committed pointer update.
+    DeleteFile,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StorageIntent {
+    pub intent_id: Uuid,
+    pub warehouse_id: WarehouseId,
+    pub table_id: TableId,
+    pub kind: StorageIntentKind,
+    pub location: Location,
+}
+
+impl StorageIntent {
+    #[must_use]
+    pub fn new(
+        warehouse_id: WarehouseId,
+        table_id: TableId,
+        kind: StorageIntentKind,
+        location: Location,
+    ) -> Self {
+        Self {
+            intent_id: Uuid::now_v7(),
+            warehouse_id,
+            table_id,
+            kind,
+            location,
+        }
+    }
+}
+
+/// Records intents in a transaction of their own, so that they persist even if the
+/// operation they belong to fails.
+///
+/// # Errors
+/// Fails if the intents cannot be stored.
+pub(crate) async fn record_storage_intents<C: Catalog>(
+    intents: &[StorageIntent],
+    catalog_state: C::State,
+) -> Result<()> {
+    if intents.is_empty() {
+        return Ok(());
+    }
+    let mut t = C::Transaction::begin_write(catalog_state).await?;
+    C::record_storage_intents(intents, t.transaction()).await?;
+    t.commit().await
+}
+
+/// Completes intents in a transaction of their own, i.e. after the storage work of an
+/// already committed pointer update is done.
+///
+/// # Errors
+/// Fails if the intents cannot be completed.
+pub(crate) async fn complete_storage_intents<C: Catalog>(
+    intent_ids: &[Uuid],
+    catalog_state: C::State,
+) -> Result<()> {
+    if intent_ids.is_empty() {
+        return Ok(());
+    }
+    let mut t = C::Transaction::begin_write(catalog_state).await?;
+    C::complete_storage_intents(intent_ids, t.transaction()).await?;
+    t.commit().await
+}
+
+/// Infinitely running worker that recovers stale intents.
+/// The first recovery runs immediately, i.e. on startup.
+pub async fn storage_intent_recovery_worker<C: Catalog, S: SecretStore>(
+    catalog_state: C::State,
+    secret_store: S,
+) {
+    loop {
+        match recover_storage_intents::<C, S>(catalog_state.clone(), &secret_store).await {
+            Ok(n) if n >= usize::try_from(MAX_INTENTS_PER_RECOVERY).unwrap_or(0) => continue,
+            Ok(0) => {}
+            Ok(n) => tracing::info!("Recovered {n} storage intents"),
+            Err(e) => {
+                tracing::error!(?e, "Failed to recover storage intents: {}", e.error);
+            }
+        }
+        tokio::time::sleep(RECOVERY_INTERVAL).await;
+    }
+}
+
+/// Deletes the files of stale intents and completes them.
+/// Intents whose files cannot be deleted stay open and are retried later.
+/// Returns the number of completed intents.
+async fn recover_storage_intents<C: Catalog, S: SecretStore>(
+    catalog_state: C::State,
+    secret_store: &S,
+) -> Result<usize> {
+    let recorded_before = chrono::Utc::now()
+        - chrono::Duration::from_std(STORAGE_INTENT_GRACE_PERIOD)
+            .unwrap_or(chrono::Duration::minutes(15));
+
+    let mut t = C::Transaction::begin_write(catalog_state).await?;
+    let intents =
+        C::claim_stale_storage_intents(recorded_before, MAX_INTENTS_PER_RECOVERY, t.transaction())
+            .await?;
+    let mut completed = Vec::with_capacity(intents.len());
+
+    let by_warehouse: HashMap<WarehouseId, Vec<StorageIntent>> = intents
+        .into_iter()
+        .into_group_map_by(|intent| intent.warehouse_id);
+
+    for (warehouse_id, intents) in by_warehouse {
+        let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
+        let storage_secret = maybe_get_secret(warehouse.storage_secret_id, secret_store).await?;
+        let file_io = warehouse
+            .storage_profile
+            .file_io(storage_secret.as_ref())
+            .await?;
+
+        for intent in intents {
+            match delete_file(&file_io, &intent.location).await {
+                Ok(()) => {
+                    tracing::debug!(
+                        "Recovered {:?} intent of table {} by deleting '{}'",
+                        intent.kind,
+                        intent.table_id,
+                        intent.location
+                    );
+                    completed.push(intent.intent_id);
+                }
+                Err(e) => tracing::warn!(
+                    "Failed to delete '{}' of {:?} intent of table {}: {e}",
+                    intent.location,
+                    intent.kind,
+                    intent.table_id
+                ),
+            }
+        }
+    }
+
+    C::complete_storage_intents(&completed, t.transaction()).await?;
+    t.commit().await?;
+    Ok(completed.len())
+}
@@ -0,0 +1,333 @@
+//! Server policy that deprecates storage profile types and settings.
+//!
+//! Rules are configured via `LAKEKEEPER__STORAGE_POLICY`. They are enforced when a warehouse
+//! is created or its storage is changed. Existing warehouses that violate a rule keep working,
+//! but the violations are reported by the management API so that they can be migrated.
+use std::{fmt::Display, str::FromStr};
+
+use iceberg_ext::catalog::rest::ErrorModel;
+use itertools::Itertools;
+
+use crate::{
+    service::storage::{StorageCredential, StorageProfile, StorageType},
+    WarehouseId, CONFIG,
+};
+
+/// Known `credential-type`s per storage type.
+const CREDENTIAL_TYPES: &[(StorageType, &str)] = &[
+    (StorageType::S3, "access-key"),
+    (StorageType::S3, "aws-system-identity"),
+    (StorageType::S3, "cloudflare-r2"),
+    (StorageType::Adls, "client-credentials"),
+    (StorageType::Adls, "shared-access-key"),
+    (StorageType::Adls, "azure-system-identity"),
+    (StorageType::Gcs, "service-account-key"),
+    (StorageType::Gcs, "gcp-system-identity"),
+    (StorageType::Gcs, "external-account"),
+    (StorageType::Hdfs, "simple"),
+    (StorageType::Hdfs, "delegation-token"),
+    (StorageType::Hdfs, "kerberos"),
+    (StorageType::Oss, "access-key"),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StoragePolicyRule {
+    /// `deny-storage-type:<storage-type>`
+    DenyStorageType(StorageType),
+    /// `deny-credential-type:<storage-type>/<credential-type>`
+    DenyCredentialType {
+        storage_type: StorageType,
+        credential_type: &'static str,
+    },
+    /// `require-s3-remote-signing`: S3 profiles must not vend STS credentials.
+    RequireS3RemoteSigning,
+}
+
+impl FromStr for StoragePolicyRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_storage_type = |storage_type: &str| {
+            StorageType::from_str(storage_type).map_err(|_| {
+                format!("Unknown storage type `{storage_type}` in storage policy `{s}`")
+            })
+        };
+        match s.split_once(':') {
+            None if s == "require-s3-remote-signing" => Ok(Self::RequireS3RemoteSigning),
+            Some(("deny-storage-type", storage_type)) => {
+                Ok(Self::DenyStorageType(parse_storage_type(storage_type)?))
+            }
+            Some(("deny-credential-type", target)) => {
+                let (storage_type, credential_type) = target.split_once('/').ok_or_else(|| {
+                    format!(
+                        "Invalid storage policy `{s}`. Expected format: `deny-credential-type:<storage-type>/<credential-type>`"
+                    )
+                })?;
+                let storage_type = parse_storage_type(storage_type)?;
+                let (storage_type, credential_type) = CREDENTIAL_TYPES
+                    .iter()
+                    .find(|(t, c)| *t == storage_type && *c == credential_type)
+                    .ok_or_else(|| {
+                        format!(
+                            "Unknown credential type `{credential_type}` for storage type `{storage_type}` in storage policy `{s}`"
+                        )
+                    })?;
+                Ok(Self::DenyCredentialType {
+                    storage_type: *storage_type,
+                    credential_type,
+                })
+            }
+            _ => Err(format!(
+                "Invalid storage policy `{s}`. Expected one of `deny-storage-type:<storage-type>`, `deny-credential-type:<storage-type>/<credential-type>` or `require-s3-remote-signing`"
+            )),
+        }
+    }
+}
+
+impl Display for StoragePolicyRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DenyStorageType(storage_type) => write!(f, "deny-storage-type:{storage_type}"),
+            Self::DenyCredentialType {
+                storage_type,
+                credential_type,
+            } => write!(f, "deny-credential-type:{storage_type}/{credential_type}"),
+            Self::RequireS3RemoteSigning => write!(f, "require-s3-remote-signing"),
+        }
+    }
+}
+
+impl StoragePolicyRule {
+    fn is_violated_by(
+        &self,
+        profile: &StorageProfile,
+        credential: Option<&StorageCredential>,
+    ) -> bool {
+        match self {
+            Self::DenyStorageType(storage_type) => profile.storage_type() == *storage_type,
+            Self::DenyCredentialType {
+                storage_type,
+                credential_type,
+            } => credential.is_some_and(|c| {
+                c.storage_type() == *storage_type && c.credential_type() == *credential_type
+            }),
+            Self::RequireS3RemoteSigning => {
+                matches!(profile, StorageProfile::S3(s3) if s3.sts_enabled)
+            }
+        }
+    }
+
+    fn violation_message(&self) -> String {
+        match self {
+            Self::DenyStorageType(storage_type) => {
+                format!("Storage profiles of type `{storage_type}` are deprecated by the storage policy of the server")
+            }
+            Self::DenyCredentialType {
+                storage_type,
+                credential_type,
+            } => format!(
+                "Storage credentials of type `{credential_type}` for `{storage_type}` are deprecated by the storage policy of the server"
+            ),
+            Self::RequireS3RemoteSigning => "S3 storage profiles must use remote signing instead of STS (`sts-enabled: false`) by the storage policy of the server".to_string(),
+        }
+    }
+}
+
+/// Whether any rule of the storage policy depends on the storage credential.
+/// If not, the credential of a warehouse does not need to be loaded to evaluate the policy.
+#[must_use]
+pub fn storage_policy_checks_credentials() -> bool {
+    CONFIG
+        .storage_policy
+        .iter()
+        .any(|rule| matches!(rule, StoragePolicyRule::DenyCredentialType { .. }))
+}
+
+/// Messages for all rules of the storage policy that are violated by a storage configuration.
+/// Credential rules are only evaluated if `credential` is given.
+#[must_use]
+pub fn storage_policy_violations(
+    profile: &StorageProfile,
+    credential: Option<&StorageCredential>,
+) -> Vec<String> {
+    violated_rules(&CONFIG.storage_policy, profile, credential)
+        .into_iter()
+        .map(StoragePolicyRule::violation_message)
+        .collect()
+}
+
+/// Rejects storage configurations that violate the storage policy.
+///
+/// If `current` is the storage configuration a warehouse is using today, rules it
+/// already violates are grandfathered and only logged, so that existing warehouses can
+/// still be updated as long as no new violation is introduced.
+///
+/// # Errors
+/// Fails with `400 StoragePolicyViolation` if the configuration violates a rule that is
+/// not grandfathered.
+pub fn require_storage_policy(
+    warehouse_id: Option<WarehouseId>,
+    profile: &StorageProfile,
+    credential: Option<&StorageCredential>,
+    current: Option<(&StorageProfile, Option<&StorageCredential>)>,
+) -> crate::api::Result<()> {
+    let grandfathered = check_storage_policy(&CONFIG.storage_policy, profile, credential, current)?;
+    if !grandfathered.is_empty() {
+        tracing::warn!(
+            ?warehouse_id,
+            "Warehouse uses storage settings that are deprecated by the storage policy: {}",
+            grandfathered.iter().join(", ")
+        );
+    }
+    Ok(())
+}
+
+fn violated_rules<'a>(
+    rules: &'a [StoragePolicyRule],
+    profile: &StorageProfile,
+    credential: Option<&StorageCredential>,
+) -> Vec<&'a StoragePolicyRule> {
+    rules
+        .iter()
+        .filter(|rule| rule.is_violated_by(profile, credential))
+        .collect()
+}
+
+/// Returns the grandfathered rules that are violated by the new configuration.
+fn check_storage_policy<'a>(
+    rules: &'a [StoragePolicyRule],
+    profile: &StorageProfile,
+    credential: Option<&StorageCredential>,
+    current: Option<(&StorageProfile, Option<&StorageCredential>)>,
+) -> Result<Vec<&'a StoragePolicyRule>, ErrorModel> {
+    let (grandfathered, new): (Vec<_>, Vec<_>) = violated_rules(rules, profile, credential)
+        .into_iter()
+        .partition(|rule| {
+            current.is_some_and(|(profile, credential)| rule.is_violated_by(profile, credential))
+        });
+
+    match new.as_slice() {
+        [] => Ok(grandfathered),
+        [rule] => Err(ErrorModel::bad_request(
+            format!("{}.", rule.violation_message()),
+            "StoragePolicyViolation",
+            None,
+        )),
+        rules => Err(ErrorModel::bad_request(
+            format!(
+                "Storage configuration violates {} rules of the storage policy of the server.",
+                rules.len()
+            ),
+            "StoragePolicyViolation",
+            None,
+        )
+        .append_details(rules.iter().map(|rule| rule.violation_message()))),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::service::storage::{S3Flavor, S3Profile};
+
+    fn s3_profile(sts_enabled: bool) -> StorageProfile {
+        S3Profile::builder()
+            .bucket("my-bucket".to_string())
+            .region("us-east-1".to_string())
+            .sts_enabled(sts_enabled)
+            .flavor(S3Flavor::Aws)
+            .build()
+            .into()
+    }
+
+    fn access_key() -> StorageCredential {
+        serde_json::from_value(serde_json::json!({
+            "type": "s3",
+            "credential-type": "access-key",
+            "aws-access-key-id": "key",
+            "aws-secret-access-key": "secret"
+        }))
+        .unwrap()
+    }
+
+    fn rules(rules: &[&str]) -> Vec<StoragePolicyRule> {
+        rules.iter().map(|r| r.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_parse_rules() {
+        for rule in [
+            "deny-storage-type:hdfs",
+            "deny-credential-type:s3/access-key",
+            "deny-credential-type:adls/shared-access-key",
+            "require-s3-remote-signing",
+        ] {
+            assert_eq!(rule.parse::<StoragePolicyRule>().unwrap().to_string(), rule);
+        }
+        for rule in [
+            "deny-storage-type:ftp",
+            "deny-credential-type:access-key",
+            "deny-credential-type:gcs/access-key",
+            "require-remote-signing",
+        ] {
+            assert!(rule.parse::<StoragePolicyRule>().is_err(), "{rule}");
+        }
+        assert!(matches!(
+            "deny-credential-type:s3/access-key".parse::<StoragePolicyRule>(),
+            Ok(StoragePolicyRule::DenyCredentialType {
+                storage_type: StorageType::S3,
+                credential_type: "access-key"
+            })
+        ));
+        assert_eq!(access_key().credential_type(), "access-key");
+    }
+
+    #[test]
+    fn test_new_configuration_is_rejected() {
+        let rules = rules(&[
+            "deny-credential-type:s3/access-key",
+            "require-s3-remote-signing",
+        ]);
+        let credential = access_key();
+
+        let error =
+            check_storage_policy(&rules, &s3_profile(false), Some(&credential), None).unwrap_err();
+        assert_eq!(error.r#type, "StoragePolicyViolation");
+        assert!(error.message.contains("access-key"), "{}", error.message);
+
+        let error =
+            check_storage_policy(&rules, &s3_profile(true), Some(&credential), None).unwrap_err();
+        assert_eq!(error.stack.len(), 2);
+
+        assert!(check_storage_policy(&rules, &s3_profile(false), None, None)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_existing_violations_are_grandfathered() {
+        let rules = rules(&[
+            "deny-credential-type:s3/access-key",
+            "require-s3-remote-signing",
+        ]);
+        let credential = access_key();
+        let current = (&s3_profile(true), Some(&credential));
+
+        // Rotating the access key keeps both existing violations
+        let grandfathered =
+            check_storage_policy(&rules, &s3_profile(true), Some(&credential), Some(current))
+                .unwrap();
+        assert_eq!(grandfathered.len(), 2);
+
+        // A warehouse that already uses remote signing must not enable STS again
+        let current = (&s3_profile(false), Some(&credential));
+        let error =
+            check_storage_policy(&rules, &s3_profile(true), Some(&credential), Some(current))
+                .unwrap_err();
+        assert!(
+            error.message.contains("remote signing"),
+            "{}",
+            error.message
+        );
+    }
+}
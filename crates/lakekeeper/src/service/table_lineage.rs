@@ -0,0 +1,224 @@
+//! Lineage between tables reported by engines.
+//!
+//! Engines send [OpenLineage](https://openlineage.io) run events to
+//! `POST /management/v1/lineage`. Every input and output dataset of a completed run forms an
+//! edge, which is kept per job and updated by later runs. Datasets are matched to tables by
+//! location, following the OpenLineage naming of object stores: the namespace holds the scheme
+//! and bucket (`s3://bucket`), the name holds the path (`warehouse/ns/table`).
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::TableId;
+use crate::WarehouseId;
+
+/// Run event of the OpenLineage specification. Facets and unknown fields are ignored.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LineageRunEvent {
+    /// Lineage is only recorded for events of type `COMPLETE`
+    #[serde(default)]
+    pub event_type: Option<LineageEventType>,
+    pub event_time: DateTime<Utc>,
+    pub run: LineageRun,
+    pub job: LineageJob,
+    #[serde(default)]
+    pub inputs: Vec<LineageDataset>,
+    #[serde(default)]
+    pub outputs: Vec<LineageDataset>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum LineageEventType {
+    Start,
+    Running,
+    Complete,
+    Abort,
+    Fail,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LineageRun {
+    pub run_id: Uuid,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct LineageJob {
+    pub namespace: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Deserialize, utoipa::ToSchema)]
+pub struct LineageDataset {
+    pub namespace: String,
+    pub name: String,
+}
+
+impl LineageDataset {
+    /// Storage location of the dataset, if its namespace has a scheme.
+    /// Aliases of the `s3` scheme used by Hadoop are replaced by `s3`.
+    #[must_use]
+    pub fn location(&self) -> Option<String> {
+        let (scheme, authority) = self.namespace.split_once("://")?;
+        let scheme = match scheme.to_lowercase().as_str() {
+            "s3a" | "s3n" => "s3".to_string(),
+            scheme => scheme.to_string(),
+        };
+        let authority = authority.trim_end_matches('/');
+        let path = self.name.trim_matches('/');
+        if authority.is_empty() {
+            return None;
+        }
+        if path.is_empty() {
+            Some(format!("{scheme}://{authority}"))
+        } else {
+            Some(format!("{scheme}://{authority}/{path}"))
+        }
+    }
+}
+
+/// Table a dataset location was resolved to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineageTable {
+    pub warehouse_id: WarehouseId,
+    pub table_id: TableId,
+}
+
+/// Edge from an input to an output dataset of a job, with the tables they were resolved to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineageEdge {
+    pub upstream: LineageDataset,
+    pub upstream_table: Option<LineageTable>,
+    pub downstream: LineageDataset,
+    pub downstream_table: Option<LineageTable>,
+    pub job: LineageJob,
+    pub run_id: Uuid,
+    pub event_time: DateTime<Utc>,
+}
+
+impl LineageRunEvent {
+    /// Whether the event reports the final inputs and outputs of a run.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.event_type == Some(LineageEventType::Complete)
+    }
+
+    /// Edges between all inputs and outputs of the run. Edges of which neither side is a table
+    /// of this catalog are not returned.
+    #[must_use]
+    pub fn edges(
+        &self,
+        resolve: impl Fn(&LineageDataset) -> Option<LineageTable>,
+    ) -> Vec<LineageEdge> {
+        let mut edges = vec![];
+        for upstream in &self.inputs {
+            let upstream_table = resolve(upstream);
+            for downstream in &self.outputs {
+                let downstream_table = resolve(downstream);
+                if upstream == downstream
+                    || (upstream_table.is_none() && downstream_table.is_none())
+                {
+                    continue;
+                }
+                edges.push(LineageEdge {
+                    upstream: upstream.clone(),
+                    upstream_table,
+                    downstream: downstream.clone(),
+                    downstream_table,
+                    job: self.job.clone(),
+                    run_id: self.run.run_id,
+                    event_time: self.event_time,
+                });
+            }
+        }
+        edges
+    }
+}
+
+/// Dataset read or written together with a table by a job.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct LineageNeighbor {
+    /// OpenLineage namespace of the dataset
+    pub dataset_namespace: String,
+    /// OpenLineage name of the dataset
+    pub dataset_name: String,
+    /// Warehouse of the table the dataset was resolved to
+    #[schema(value_type = Option<uuid::Uuid>)]
+    pub warehouse_id: Option<WarehouseId>,
+    /// Table the dataset was resolved to. Not set for datasets outside of the catalog
+    /// and for dropped tables.
+    #[schema(value_type = Option<uuid::Uuid>)]
+    pub table_id: Option<TableId>,
+    pub job: LineageJob,
+    /// Last run of the job that reported the edge
+    pub last_run_id: Uuid,
+    pub last_event_time: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableLineage {
+    /// Datasets the table is derived from
+    pub upstream: Vec<LineageNeighbor>,
+    /// Datasets derived from the table
+    pub downstream: Vec<LineageNeighbor>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn dataset(namespace: &str, name: &str) -> LineageDataset {
+        LineageDataset {
+            namespace: namespace.to_string(),
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_dataset_location() {
+        assert_eq!(
+            dataset("s3a://bucket/", "/warehouse/ns/t/").location(),
+            Some("s3://bucket/warehouse/ns/t".to_string())
+        );
+        assert_eq!(
+            dataset("abfss://container@account.dfs.core.windows.net", "wh/t").location(),
+            Some("abfss://container@account.dfs.core.windows.net/wh/t".to_string())
+        );
+        assert_eq!(dataset("spark", "ns.t").location(), None);
+    }
+
+    #[test]
+    fn test_edges_skip_datasets_outside_of_catalog() {
+        let event: LineageRunEvent = serde_json::from_value(serde_json::json!({
+            "eventType": "COMPLETE",
+            "eventTime": "2025-07-01T10:00:00Z",
+            "run": {"runId": "0197c3a2-5c7e-7d2a-8f7e-1a2b3c4d5e6f", "facets": {}},
+            "job": {"namespace": "spark", "name": "daily_orders"},
+            "inputs": [
+                {"namespace": "s3://bucket", "name": "wh/orders"},
+                {"namespace": "kafka://broker:9092", "name": "clicks"}
+            ],
+            "outputs": [{"namespace": "s3://bucket", "name": "wh/daily"}],
+            "producer": "https://github.com/OpenLineage/OpenLineage/tree/1.30.0/integration/spark",
+            "schemaURL": "https://openlineage.io/spec/2-0-2/OpenLineage.json#/$defs/RunEvent"
+        }))
+        .unwrap();
+        assert!(event.is_complete());
+
+        let table = LineageTable {
+            warehouse_id: WarehouseId::from(Uuid::now_v7()),
+            table_id: TableId::new_random(),
+        };
+        let edges = event.edges(|d| (d.name == "wh/orders").then_some(table));
+        assert_eq!(edges.len(), 1);
+        assert_eq!(edges[0].upstream, dataset("s3://bucket", "wh/orders"));
+        assert_eq!(edges[0].upstream_table, Some(table));
+        assert_eq!(edges[0].downstream_table, None);
+    }
+}
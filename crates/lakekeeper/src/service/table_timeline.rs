@@ -0,0 +1,273 @@
+//! Activity timeline of tables.
+//!
+//! The history of a table is spread over its snapshots, the task log and changes that leave no
+//! trace in the table metadata. Property changes, changed grants, drops and undrops are recorded
+//! as table activities, and the timeline merges them with the commits and maintenance runs of the
+//! table in chronological order.
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+    sync::Arc,
+};
+
+use chrono::{DateTime, Utc};
+use iceberg::TableIdent;
+use iceberg_ext::catalog::rest::CommitTransactionRequest;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{
+    authz::AuthorizationContext, endpoint_hooks::EndpointHook, Catalog, TableId,
+    UndropTabularResponse,
+};
+use crate::{
+    api::{
+        iceberg::{types::DropParams, v1::TableParameters},
+        management::v1::warehouse::UndropTabularsRequest,
+        RequestMetadata,
+    },
+    catalog::tables::CommitContext,
+    WarehouseId,
+};
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    strum_macros::Display,
+    utoipa::ToSchema,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+#[cfg_attr(feature = "sqlx-postgres", derive(sqlx::Type))]
+#[cfg_attr(
+    feature = "sqlx-postgres",
+    sqlx(type_name = "table_timeline_event_type", rename_all = "kebab-case")
+)]
+pub enum TableTimelineEventType {
+    /// A snapshot was committed to the table
+    Commit,
+    /// Table properties were set or removed
+    PropertiesChanged,
+    /// Privileges on the table were granted or revoked
+    GrantsChanged,
+    /// A maintenance task of the table finished
+    MaintenanceRun,
+    /// The table was dropped
+    Dropped,
+    /// The soft-deleted table was restored
+    Undropped,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableTimelineEvent {
+    pub event_type: TableTimelineEventType,
+    pub timestamp: DateTime<Utc>,
+    /// Principal that caused the event, if known
+    pub principal: Option<String>,
+    /// Event specific details, such as the snapshot id and operation of commits
+    /// or the queue name and status of maintenance runs
+    pub details: serde_json::Value,
+}
+
+/// Change of a table that is not part of its metadata or the task log.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableActivity {
+    pub activity_id: Uuid,
+    pub table_id: TableId,
+    /// Recorded activities are never of type `Commit` or `MaintenanceRun`
+    pub event_type: TableTimelineEventType,
+    pub principal: Option<String>,
+    pub details: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TableActivity {
+    #[must_use]
+    pub fn new(
+        table_id: TableId,
+        event_type: TableTimelineEventType,
+        request_metadata: &RequestMetadata,
+        details: serde_json::Value,
+    ) -> Self {
+        Self {
+            activity_id: Uuid::now_v7(),
+            table_id,
+            event_type,
+            principal: request_metadata.user_id().map(ToString::to_string),
+            details,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// Records activities in the background, so that requests are not delayed.
+/// Failures are logged, the timeline is not authoritative.
+pub(crate) fn record_table_activities<C: Catalog>(
+    activities: Vec<TableActivity>,
+    catalog_state: C::State,
+) {
+    if activities.is_empty() {
+        return;
+    }
+    tokio::spawn(async move {
+        for activity in activities {
+            if let Err(e) = C::record_table_activity(&activity, catalog_state.clone()).await {
+                tracing::warn!(
+                    ?e,
+                    "Failed to record {} of table {}: {}",
+                    activity.event_type,
+                    activity.table_id,
+                    e.error
+                );
+            }
+        }
+    });
+}
+
+/// Property changes between two versions of the table metadata.
+fn property_changes(commit: &CommitContext) -> Option<serde_json::Value> {
+    let previous = commit.previous_metadata.properties();
+    let current = commit.new_metadata.properties();
+    let set = current
+        .iter()
+        .filter(|(k, v)| previous.get(*k) != Some(*v))
+        .collect::<BTreeMap<_, _>>();
+    let mut removed = previous
+        .keys()
+        .filter(|k| !current.contains_key(*k))
+        .collect::<Vec<_>>();
+    removed.sort();
+    if set.is_empty() && removed.is_empty() {
+        return None;
+    }
+    Some(serde_json::json!({ "set": set, "removed": removed }))
+}
+
+/// Endpoint hook recording the table activities that are part of the timeline.
+#[derive(Clone)]
+pub struct TableTimelineRecorder<C: Catalog> {
+    catalog_state: C::State,
+}
+
+impl<C: Catalog> std::fmt::Debug for TableTimelineRecorder<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TableTimelineRecorder")
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C: Catalog> TableTimelineRecorder<C> {
+    #[must_use]
+    pub fn new(catalog_state: C::State) -> Self {
+        Self { catalog_state }
+    }
+}
+
+impl<C: Catalog> Display for TableTimelineRecorder<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "TableTimelineRecorder")
+    }
+}
+
+#[async_trait::async_trait]
+impl<C: Catalog> EndpointHook for TableTimelineRecorder<C> {
+    async fn commit_transaction(
+        &self,
+        _warehouse_id: WarehouseId,
+        _request: Arc<CommitTransactionRequest>,
+        commits: Arc<Vec<CommitContext>>,
+        _table_ident_map: Arc<HashMap<TableIdent, TableId>>,
+        _authorization: Arc<AuthorizationContext>,
+        request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        let activities = commits
+            .iter()
+            .filter_map(|commit| {
+                property_changes(commit).map(|details| {
+                    TableActivity::new(
+                        TableId::from(commit.new_metadata.uuid()),
+                        TableTimelineEventType::PropertiesChanged,
+                        &request_metadata,
+                        details,
+                    )
+                })
+            })
+            .collect();
+        record_table_activities::<C>(activities, self.catalog_state.clone());
+        Ok(())
+    }
+
+    async fn drop_table(
+        &self,
+        _warehouse_id: WarehouseId,
+        _parameters: TableParameters,
+        drop_params: DropParams,
+        table_id: TableId,
+        request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        let activity = TableActivity::new(
+            table_id,
+            TableTimelineEventType::Dropped,
+            &request_metadata,
+            serde_json::json!({
+                "purge-requested": drop_params.purge_requested,
+                "force": drop_params.force,
+            }),
+        );
+        record_table_activities::<C>(vec![activity], self.catalog_state.clone());
+        Ok(())
+    }
+
+    async fn undrop_tabular(
+        &self,
+        _warehouse_id: WarehouseId,
+        _request: Arc<UndropTabularsRequest>,
+        responses: Arc<Vec<UndropTabularResponse>>,
+        request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        // Views are undropped by the same endpoint. Only activities of tables are stored.
+        let activities = responses
+            .iter()
+            .map(|response| {
+                TableActivity::new(
+                    response.table_ident,
+                    TableTimelineEventType::Undropped,
+                    &request_metadata,
+                    serde_json::json!({ "cancelled-expiration-task-id": *response.task_id }),
+                )
+            })
+            .collect();
+        record_table_activities::<C>(activities, self.catalog_state.clone());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_event_type_names_match_database_enum() {
+        // Variants are stored as `table_timeline_event_type` with the same names.
+        for (event_type, name) in [
+            (TableTimelineEventType::Commit, "commit"),
+            (
+                TableTimelineEventType::PropertiesChanged,
+                "properties-changed",
+            ),
+            (TableTimelineEventType::GrantsChanged, "grants-changed"),
+            (TableTimelineEventType::MaintenanceRun, "maintenance-run"),
+            (TableTimelineEventType::Dropped, "dropped"),
+            (TableTimelineEventType::Undropped, "undropped"),
+        ] {
+            assert_eq!(event_type.to_string(), name);
+            assert_eq!(serde_json::to_value(event_type).unwrap(), name);
+        }
+    }
+}
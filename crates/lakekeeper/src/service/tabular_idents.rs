@@ -81,6 +81,12 @@ pub enum TabularIdentOwned {
 }
 
 impl TabularIdentOwned {
+    pub(crate) fn inner(&self) -> &TableIdent {
+        match self {
+            TabularIdentOwned::Table(ident) | TabularIdentOwned::View(ident) => ident,
+        }
+    }
+
     pub(crate) fn into_inner(self) -> TableIdent {
         match self {
             TabularIdentOwned::Table(ident) | TabularIdentOwned::View(ident) => ident,
@@ -0,0 +1,1115 @@
+//! One-shot import of the namespaces and tables of another Iceberg REST catalog.
+//!
+//! The source catalog is crawled via its REST API: namespaces are listed recursively and
+//! the metadata location of each table is loaded. Namespaces are created in the local
+//! warehouse, and tables are registered by their metadata location - like `registerTable`
+//! does - with the user that started the import as owner. The outcome is recorded per table.
+//! Views are not imported.
+//!
+//! Tables that already exist with the same metadata location are skipped, so an import
+//! that failed, for example because the source became unavailable, can be retried.
+use std::{collections::HashMap, str::FromStr as _, sync::LazyLock, time::Duration};
+
+use chrono::{DateTime, Utc};
+use iceberg::{spec::ViewMetadata, NamespaceIdent, TableIdent};
+use iceberg_ext::{
+    catalog::rest::{CreateNamespaceRequest, ErrorModel},
+    configs::{namespace::NamespaceProperties, ConfigProperty as _, Location},
+};
+use rand::RngCore as _;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tracing::Instrument;
+use utoipa::{PartialSchema, ToSchema};
+use uuid::Uuid;
+
+use super::{
+    QueueApiConfig, QueueConfig, Task, TaskCheckState, TaskId,
+    DEFAULT_MAX_TIME_SINCE_LAST_HEARTBEAT,
+};
+use crate::{
+    api::Result,
+    catalog::{
+        io::{read_file, read_metadata_file, StorageIo},
+        maybe_get_secret,
+        namespace::{
+            invalidate_namespace_ids, remove_managed_namespace_properties,
+            set_namespace_location_property, validate_namespace_ident,
+            validate_namespace_properties_keys,
+        },
+        tables::{
+            maybe_queue_orphan_file_cleanup, maybe_queue_pii_detection,
+            maybe_queue_snapshot_expiration, require_active_warehouse,
+            validate_table_or_view_ident, validate_table_properties,
+        },
+        views::validate_view_properties,
+    },
+    request_metadata::RequestMetadata,
+    service::{
+        authz::{Authorizer, CatalogNamespaceAction, CatalogWarehouseAction, NamespaceParent},
+        mirror::mirror_owner,
+        property_schemas::{require_valid_properties, PropertyEntityType},
+        quotas::{require_namespace_depth_quota, require_table_quota},
+        secrets::SecretInStorage,
+        Catalog, GetWarehouseResponse, ListFlags, NamespaceId, SecretStore, TableCreation,
+        TableHistoryImport, TableId, Transaction, UserId, ViewId,
+    },
+    WarehouseId, CONFIG,
+};
+
+pub(crate) const QUEUE_NAME: &str = "catalog_import";
+pub(crate) static API_CONFIG: LazyLock<QueueApiConfig> = LazyLock::new(|| QueueApiConfig {
+    queue_name: QUEUE_NAME,
+    utoipa_type_name: CatalogImportQueueConfig::name(),
+    utoipa_schema: CatalogImportQueueConfig::schema(),
+});
+
+const SOURCE_REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+/// Scope requested with client credentials if none is configured.
+const DEFAULT_SCOPE: &str = "catalog";
+/// Separator of namespace levels in the `parent` query parameter and in paths.
+const NAMESPACE_SEPARATOR: char = '\u{1f}';
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, ToSchema)]
+pub(crate) struct CatalogImportQueueConfig {}
+
+impl QueueConfig for CatalogImportQueueConfig {}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CatalogImportPayload {
+    pub(crate) source: CatalogImportSource,
+    /// Secret holding the [`CatalogImportCredential`], deleted when the import succeeded.
+    pub(crate) credential_secret_id: Option<Uuid>,
+    /// Owner of imported namespaces and tables
+    pub(crate) owner: Option<UserId>,
+    pub(crate) external: bool,
+}
+
+/// The Iceberg REST catalog an import reads from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct CatalogImportSource {
+    /// Base URI of the Iceberg REST API of the source catalog, without the `/v1` suffix.
+    #[schema(value_type = String)]
+    pub uri: url::Url,
+    /// Warehouse of the source catalog. Passed to its `/v1/config` endpoint.
+    pub warehouse: Option<String>,
+}
+
+/// Credential for the source catalog of an import.
+/// Kept in the secret store until the import succeeded.
+#[derive(Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct CatalogImportCredential {
+    /// Bearer token sent to the source catalog
+    pub token: Option<String>,
+    /// `<client_id>:<client_secret>` exchanged for a token with the OAuth2
+    /// client credentials flow
+    pub credential: Option<String>,
+    /// Token endpoint for `credential`. Defaults to `<uri>/v1/oauth/tokens`.
+    #[schema(value_type = Option<String>)]
+    pub oauth2_server_uri: Option<url::Url>,
+    /// Scope requested with `credential`. Default: `catalog`
+    pub scope: Option<String>,
+}
+
+impl SecretInStorage for CatalogImportCredential {}
+
+impl std::fmt::Debug for CatalogImportCredential {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CatalogImportCredential")
+            .field("token", &self.token.as_ref().map(|_| "<redacted>"))
+            .field(
+                "credential",
+                &self.credential.as_ref().map(|_| "<redacted>"),
+            )
+            .field("oauth2_server_uri", &self.oauth2_server_uri)
+            .field("scope", &self.scope)
+            .finish()
+    }
+}
+
+impl CatalogImportCredential {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.token.is_none() && self.credential.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum CatalogImportStatus {
+    /// Waiting for a worker
+    Pending,
+    Running,
+    Succeeded,
+    /// The last attempt failed. Failed imports are retried by the task queue.
+    Failed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum CatalogImportTableStatus {
+    /// The table was registered
+    Imported,
+    /// The table already exists with the same metadata location,
+    /// or it has no metadata location in the source catalog
+    Skipped,
+    Failed,
+}
+
+/// An import of another Iceberg REST catalog into a warehouse.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct CatalogImport {
+    /// Id of the import, which is also the id of its task
+    pub import_id: Uuid,
+    #[schema(value_type = uuid::Uuid)]
+    pub warehouse_id: WarehouseId,
+    pub source_uri: String,
+    pub source_warehouse: Option<String>,
+    pub status: CatalogImportStatus,
+    /// Error of the last failed attempt
+    pub error: Option<String>,
+    pub created_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// Outcome of the import of a single table.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct CatalogImportTable {
+    pub namespace: Vec<String>,
+    pub name: String,
+    /// Metadata location in the source catalog
+    pub metadata_location: Option<String>,
+    pub status: CatalogImportTableStatus,
+    /// Id of the table in the warehouse
+    pub table_id: Option<Uuid>,
+    /// Reason why the table was skipped or failed
+    pub error: Option<String>,
+}
+
+pub(crate) async fn catalog_import_worker<C: Catalog, A: Authorizer, S: SecretStore>(
+    catalog_state: C::State,
+    authorizer: A,
+    secret_store: S,
+    poll_interval: std::time::Duration,
+) {
+    loop {
+        let task = match C::pick_new_task(
+            QUEUE_NAME,
+            DEFAULT_MAX_TIME_SINCE_LAST_HEARTBEAT,
+            catalog_state.clone(),
+        )
+        .await
+        {
+            Ok(task) => task,
+            Err(err) => {
+                tracing::error!("Failed to fetch catalog import task: {:?}", err);
+                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                continue;
+            }
+        };
+
+        let Some(task) = task else {
+            let jitter = { rand::rng().next_u64() % 500 };
+            tokio::time::sleep(poll_interval + Duration::from_millis(jitter)).await;
+            continue;
+        };
+        let payload = match task.task_state::<CatalogImportPayload>() {
+            Ok(payload) => payload,
+            Err(err) => {
+                tracing::error!("Failed to deserialize task state: {:?}", err);
+                continue;
+            }
+        };
+        let config = match task.task_config::<CatalogImportQueueConfig>() {
+            Ok(config) => config,
+            Err(err) => {
+                tracing::error!("Failed to deserialize task config: {:?}", err);
+                continue;
+            }
+        }
+        .unwrap_or_default();
+
+        let span = tracing::debug_span!(
+            "catalog_import",
+            warehouse_id = %task.task_metadata.warehouse_id,
+            source = %payload.source.uri,
+            queue_name = %task.queue_name,
+            task = ?task,
+        );
+
+        instrumented_import::<C, A, S>(
+            catalog_state.clone(),
+            &authorizer,
+            &secret_store,
+            &task,
+            &payload,
+            &config,
+        )
+        .instrument(span.or_current())
+        .await;
+    }
+}
+
+async fn instrumented_import<C: Catalog, A: Authorizer, S: SecretStore>(
+    catalog_state: C::State,
+    authorizer: &A,
+    secret_store: &S,
+    task: &Task,
+    payload: &CatalogImportPayload,
+    config: &CatalogImportQueueConfig,
+) {
+    match import::<C, A, S>(
+        catalog_state.clone(),
+        authorizer,
+        secret_store,
+        task,
+        payload,
+    )
+    .await
+    {
+        Ok(Some(summary)) => {
+            tracing::info!(
+                imported = summary.imported,
+                skipped = summary.skipped,
+                failed = summary.failed,
+                "Imported catalog {} into warehouse {}",
+                payload.source.uri,
+                task.task_metadata.warehouse_id
+            );
+        }
+        Ok(None) => {}
+        Err(err) => {
+            tracing::error!(
+                "Failed to import catalog {}: {}",
+                payload.source.uri,
+                err.error
+            );
+            if let Err(e) = set_status::<C>(
+                catalog_state.clone(),
+                task.task_id,
+                CatalogImportStatus::Failed,
+                Some(err.error.message.as_str()),
+            )
+            .await
+            {
+                tracing::error!("Failed to record status of catalog import: {}", e.error);
+            }
+            super::record_error_with_catalog::<C>(
+                catalog_state,
+                &format!("Failed to import catalog: '{:?}'", err.error),
+                config.max_retries(),
+                task.task_id,
+            )
+            .await;
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct ImportSummary {
+    imported: usize,
+    skipped: usize,
+    failed: usize,
+}
+
+/// Returns `None` if the task was asked to stop.
+async fn import<C: Catalog, A: Authorizer, S: SecretStore>(
+    catalog_state: C::State,
+    authorizer: &A,
+    secret_store: &S,
+    task: &Task,
+    payload: &CatalogImportPayload,
+) -> Result<Option<ImportSummary>> {
+    let warehouse_id = task.task_metadata.warehouse_id;
+    set_status::<C>(
+        catalog_state.clone(),
+        task.task_id,
+        CatalogImportStatus::Running,
+        None,
+    )
+    .await?;
+
+    let owner = mirror_owner(payload.owner.as_ref()).ok_or_else(|| {
+        ErrorModel::internal(
+            "Imports require an authenticated user unless the `allowall` authorizer is used",
+            "CatalogImportOwnerMissing",
+            None,
+        )
+    })?;
+    let credential = match payload.credential_secret_id {
+        Some(secret_id) => Some(
+            secret_store
+                .get_secret_by_id::<CatalogImportCredential>(secret_id.into())
+                .await?
+                .secret,
+        ),
+        None => None,
+    };
+    let source = SourceCatalog::connect(&payload.source, credential.as_ref()).await?;
+
+    let mut t = C::Transaction::begin_read(catalog_state.clone()).await?;
+    let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
+    t.commit().await?;
+    require_active_warehouse(warehouse.status)?;
+    let storage_secret = maybe_get_secret(warehouse.storage_secret_id, secret_store).await?;
+    let file_io = warehouse
+        .storage_profile
+        .file_io(storage_secret.as_ref())
+        .await?;
+
+    let importer = Importer::<C, A> {
+        catalog_state: catalog_state.clone(),
+        authorizer,
+        warehouse: &warehouse,
+        file_io: &file_io,
+        owner: &owner,
+        external: payload.external,
+    };
+    let mut summary = ImportSummary::default();
+    let mut namespace_ids = HashMap::new();
+    let mut pending = source.list_namespaces(None).await?;
+    while let Some(namespace) = pending.pop() {
+        if !heartbeat::<C>(catalog_state.clone(), task.task_id).await? {
+            return Ok(None);
+        }
+        pending.extend(source.list_namespaces(Some(namespace.as_slice())).await?);
+        let tables = source.list_tables(&namespace).await?;
+
+        // Properties of the source are not imported, as its locations
+        // usually don't belong to the warehouse.
+        let namespace_id = importer
+            .ensure_namespace(&namespace, HashMap::new(), &namespace_ids)
+            .await
+            .map_err(|e| e.error.message);
+        if let Ok(namespace_id) = namespace_id {
+            namespace_ids.insert(namespace.clone(), namespace_id);
+        }
+        for name in tables {
+            let outcome = match &namespace_id {
+                Ok(namespace_id) => {
+                    let metadata_location = source.load_metadata_location(&namespace, &name).await;
+                    importer
+                        .import_table(*namespace_id, &namespace, name, metadata_location)
+                        .await
+                }
+                Err(e) => CatalogImportTable {
+                    namespace: namespace.clone(),
+                    name,
+                    metadata_location: None,
+                    status: CatalogImportTableStatus::Failed,
+                    table_id: None,
+                    error: Some(format!("Failed to create namespace: {e}")),
+                },
+            };
+            match outcome.status {
+                CatalogImportTableStatus::Imported => summary.imported += 1,
+                CatalogImportTableStatus::Skipped => summary.skipped += 1,
+                CatalogImportTableStatus::Failed => summary.failed += 1,
+            }
+            let mut t = C::Transaction::begin_write(catalog_state.clone()).await?;
+            C::record_catalog_import_table(*task.task_id, &outcome, t.transaction()).await?;
+            t.commit().await?;
+        }
+    }
+    invalidate_namespace_ids(warehouse_id).await;
+
+    let mut t = C::Transaction::begin_write(catalog_state).await?;
+    C::set_catalog_import_status(
+        *task.task_id,
+        CatalogImportStatus::Succeeded,
+        None,
+        t.transaction(),
+    )
+    .await?;
+    C::retrying_record_task_success(task.task_id, None, t.transaction()).await;
+    t.commit().await?;
+
+    if let Some(secret_id) = payload.credential_secret_id {
+        if let Err(e) = secret_store.delete_secret(&secret_id.into()).await {
+            tracing::warn!(
+                "Failed to delete credential of catalog import {}: {}",
+                task.task_id,
+                e.error
+            );
+        }
+    }
+
+    Ok(Some(summary))
+}
+
+async fn set_status<C: Catalog>(
+    catalog_state: C::State,
+    task_id: TaskId,
+    status: CatalogImportStatus,
+    error: Option<&str>,
+) -> Result<()> {
+    let mut t = C::Transaction::begin_write(catalog_state).await?;
+    C::set_catalog_import_status(*task_id, status, error, t.transaction()).await?;
+    t.commit().await
+}
+
+/// Sends a heartbeat. Returns `false` if the task should stop.
+async fn heartbeat<C: Catalog>(catalog_state: C::State, task_id: TaskId) -> Result<bool> {
+    let mut trx = C::Transaction::begin_write(catalog_state).await?;
+    let check = C::check_and_heartbeat_task(task_id, trx.transaction()).await?;
+    trx.commit().await?;
+    if matches!(check, Some(TaskCheckState::Continue)) {
+        Ok(true)
+    } else {
+        tracing::info!("Catalog import task {task_id} was asked to stop");
+        Ok(false)
+    }
+}
+
+/// Creates namespaces and registers tables and views in the warehouse of an import.
+pub(crate) struct Importer<'a, C: Catalog, A: Authorizer> {
+    pub(crate) catalog_state: C::State,
+    pub(crate) authorizer: &'a A,
+    pub(crate) warehouse: &'a GetWarehouseResponse,
+    pub(crate) file_io: &'a StorageIo,
+    pub(crate) owner: &'a RequestMetadata,
+    pub(crate) external: bool,
+}
+
+impl<C: Catalog, A: Authorizer> Importer<'_, C, A> {
+    /// Id of the namespace, which is created with `properties` if it doesn't exist yet.
+    /// Namespaces must be imported top-down, so parents are in `namespace_ids` unless
+    /// their creation failed. A `location` outside of the warehouse is replaced by the
+    /// default location.
+    pub(crate) async fn ensure_namespace(
+        &self,
+        namespace: &[String],
+        properties: HashMap<String, String>,
+        namespace_ids: &HashMap<Vec<String>, NamespaceId>,
+    ) -> Result<NamespaceId> {
+        let warehouse_id = self.warehouse.id;
+        let ident = namespace_ident(namespace)?;
+        validate_namespace_ident(&ident)?;
+        validate_namespace_properties_keys(properties.keys())?;
+        if CONFIG
+            .reserved_namespaces
+            .contains(&namespace[0].to_lowercase())
+        {
+            return Err(ErrorModel::bad_request(
+                "Namespace is reserved for internal use.",
+                "ReservedNamespace",
+                None,
+            )
+            .into());
+        }
+
+        let mut t = C::Transaction::begin_write(self.catalog_state.clone()).await?;
+        if let Some(namespace_id) =
+            C::namespace_to_id(warehouse_id, &ident, t.transaction()).await?
+        {
+            t.commit().await?;
+            return Ok(namespace_id);
+        }
+
+        let parent = match namespace.split_last() {
+            Some((_, parent)) if !parent.is_empty() => {
+                let parent_id = namespace_ids.get(parent).copied().ok_or_else(|| {
+                    ErrorModel::not_found(
+                        format!("Parent namespace {parent:?} was not imported"),
+                        "NamespaceNotFound",
+                        None,
+                    )
+                })?;
+                self.authorizer
+                    .require_namespace_action(
+                        self.owner,
+                        Ok(Some(parent_id)),
+                        CatalogNamespaceAction::CanCreateNamespace,
+                    )
+                    .await?;
+                NamespaceParent::Namespace(parent_id)
+            }
+            _ => {
+                self.authorizer
+                    .require_warehouse_action(
+                        self.owner,
+                        warehouse_id,
+                        CatalogWarehouseAction::CanCreateNamespace,
+                    )
+                    .await?;
+                NamespaceParent::Warehouse(warehouse_id)
+            }
+        };
+        require_namespace_depth_quota::<C>(
+            &self.warehouse.project_id,
+            namespace.len(),
+            self.catalog_state.clone(),
+        )
+        .await?;
+
+        let namespace_id = NamespaceId::new_random();
+        let mut properties = NamespaceProperties::from_props_unchecked(properties);
+        remove_managed_namespace_properties(&mut properties);
+        if properties.get_location().is_some_and(|location| {
+            self.warehouse
+                .storage_profile
+                .require_allowed_location(&location)
+                .is_err()
+        }) {
+            properties.remove_untyped(Location::KEY);
+        }
+        require_valid_properties::<C>(
+            &self.warehouse.project_id,
+            PropertyEntityType::Namespace,
+            properties.inner(),
+            self.catalog_state.clone(),
+        )
+        .await?;
+        set_namespace_location_property(&mut properties, self.warehouse, namespace_id)?;
+        C::create_namespace(
+            warehouse_id,
+            namespace_id,
+            CreateNamespaceRequest {
+                namespace: ident,
+                properties: Some(properties.into()),
+            },
+            t.transaction(),
+        )
+        .await?;
+        self.authorizer
+            .create_namespace(self.owner, namespace_id, parent)
+            .await?;
+        t.commit().await?;
+        Ok(namespace_id)
+    }
+
+    /// Registers a table by the location of its metadata file, as loaded from the source.
+    pub(crate) async fn import_table(
+        &self,
+        namespace_id: NamespaceId,
+        namespace: &[String],
+        name: String,
+        metadata_location: Result<Option<String>>,
+    ) -> CatalogImportTable {
+        let mut outcome = CatalogImportTable {
+            namespace: namespace.to_vec(),
+            name,
+            metadata_location: None,
+            status: CatalogImportTableStatus::Failed,
+            table_id: None,
+            error: None,
+        };
+        let result = self
+            .register_table(namespace_id, metadata_location, &mut outcome)
+            .await;
+        record_result(&mut outcome, result, "table");
+        outcome
+    }
+
+    /// Registers a view by the location of its metadata file.
+    pub(crate) async fn import_view(
+        &self,
+        namespace_id: NamespaceId,
+        namespace: &[String],
+        name: String,
+        metadata_location: String,
+    ) -> CatalogImportTable {
+        let mut outcome = CatalogImportTable {
+            namespace: namespace.to_vec(),
+            name,
+            metadata_location: Some(metadata_location),
+            status: CatalogImportTableStatus::Failed,
+            table_id: None,
+            error: None,
+        };
+        let result = self.register_view(namespace_id, &mut outcome).await;
+        record_result(&mut outcome, result, "view");
+        outcome
+    }
+
+    async fn register_table(
+        &self,
+        namespace_id: NamespaceId,
+        metadata_location: Result<Option<String>>,
+        outcome: &mut CatalogImportTable,
+    ) -> Result<CatalogImportTableStatus> {
+        let warehouse_id = self.warehouse.id;
+        let ident = TableIdent::new(namespace_ident(&outcome.namespace)?, outcome.name.clone());
+        validate_table_or_view_ident(&ident)?;
+
+        let Some(metadata_location) = metadata_location? else {
+            outcome.error = Some("The table has no metadata location in the source".to_string());
+            return Ok(CatalogImportTableStatus::Skipped);
+        };
+        outcome.metadata_location = Some(metadata_location.clone());
+        let metadata_location = parse_metadata_location(&metadata_location)?;
+
+        self.authorizer
+            .require_namespace_action(
+                self.owner,
+                Ok(Some(namespace_id)),
+                CatalogNamespaceAction::CanCreateTable,
+            )
+            .await?;
+
+        let mut t = C::Transaction::begin_read(self.catalog_state.clone()).await?;
+        let existing =
+            C::table_to_id(warehouse_id, &ident, ListFlags::default(), t.transaction()).await?;
+        if let Some(table_id) = existing {
+            let existing = C::load_tables(warehouse_id, [table_id], false, t.transaction())
+                .await?
+                .remove(&table_id);
+            t.commit().await?;
+            outcome.table_id = Some(*table_id);
+            if existing.is_some_and(|e| e.metadata_location.as_ref() == Some(&metadata_location)) {
+                outcome.error = Some("The table is already registered".to_string());
+                return Ok(CatalogImportTableStatus::Skipped);
+            }
+            return Err(ErrorModel::conflict(
+                "A table with the same name and a different metadata location exists",
+                "TableAlreadyExists",
+                None,
+            )
+            .into());
+        }
+        t.commit().await?;
+
+        let storage_profile = &self.warehouse.storage_profile;
+        storage_profile.require_allowed_location(&metadata_location)?;
+        let table_metadata = read_metadata_file(self.file_io, &metadata_location).await?;
+        let table_location = Location::from_str(table_metadata.location()).map_err(|e| {
+            ErrorModel::bad_request(
+                format!("Invalid table location '{}'", table_metadata.location()),
+                "InvalidTableLocation",
+                Some(Box::new(e)),
+            )
+        })?;
+        storage_profile.require_allowed_location(&table_location)?;
+        validate_table_properties(table_metadata.properties().keys())?;
+        require_valid_properties::<C>(
+            &self.warehouse.project_id,
+            PropertyEntityType::Table,
+            table_metadata.properties(),
+            self.catalog_state.clone(),
+        )
+        .await?;
+        require_table_quota::<C>(&self.warehouse.project_id, self.catalog_state.clone()).await?;
+
+        let table_id = TableId::from(table_metadata.uuid());
+        let mut t = C::Transaction::begin_write(self.catalog_state.clone()).await?;
+        C::create_table(
+            TableCreation {
+                namespace_id,
+                table_ident: &ident,
+                metadata_location: Some(&metadata_location),
+                table_metadata,
+                external: self.external,
+                imported_history: TableHistoryImport::default(),
+            },
+            t.transaction(),
+        )
+        .await?;
+        self.authorizer
+            .create_table(self.owner, table_id, namespace_id)
+            .await?;
+        maybe_queue_pii_detection::<C>(warehouse_id, table_id, t.transaction()).await?;
+        // Files of external tables are owned by another system, we must not modify them.
+        if !self.external {
+            maybe_queue_snapshot_expiration::<C>(warehouse_id, table_id, None, t.transaction())
+                .await?;
+            maybe_queue_orphan_file_cleanup::<C>(warehouse_id, table_id, None, t.transaction())
+                .await?;
+        }
+        t.commit().await?;
+
+        outcome.table_id = Some(*table_id);
+        Ok(CatalogImportTableStatus::Imported)
+    }
+
+    async fn register_view(
+        &self,
+        namespace_id: NamespaceId,
+        outcome: &mut CatalogImportTable,
+    ) -> Result<CatalogImportTableStatus> {
+        let warehouse_id = self.warehouse.id;
+        let ident = TableIdent::new(namespace_ident(&outcome.namespace)?, outcome.name.clone());
+        validate_table_or_view_ident(&ident)?;
+        let metadata_location =
+            parse_metadata_location(outcome.metadata_location.as_deref().unwrap_or_default())?;
+
+        self.authorizer
+            .require_namespace_action(
+                self.owner,
+                Ok(Some(namespace_id)),
+                CatalogNamespaceAction::CanCreateView,
+            )
+            .await?;
+
+        let mut t = C::Transaction::begin_read(self.catalog_state.clone()).await?;
+        if let Some(view_id) = C::view_to_id(warehouse_id, &ident, t.transaction()).await? {
+            let existing = C::load_view(view_id, false, t.transaction()).await?;
+            t.commit().await?;
+            outcome.table_id = Some(*view_id);
+            if existing.metadata_location == metadata_location.to_string() {
+                outcome.error = Some("The view is already registered".to_string());
+                return Ok(CatalogImportTableStatus::Skipped);
+            }
+            return Err(ErrorModel::conflict(
+                "A view with the same name and a different metadata location exists",
+                "ViewAlreadyExists",
+                None,
+            )
+            .into());
+        }
+        t.commit().await?;
+
+        let storage_profile = &self.warehouse.storage_profile;
+        storage_profile.require_allowed_location(&metadata_location)?;
+        let content = read_file(self.file_io, &metadata_location).await?;
+        let view_metadata: ViewMetadata = serde_json::from_slice(&content).map_err(|e| {
+            ErrorModel::bad_request(
+                format!("Failed to parse view metadata '{metadata_location}'"),
+                "InvalidViewMetadata",
+                Some(Box::new(e)),
+            )
+        })?;
+        let view_location = Location::from_str(view_metadata.location()).map_err(|e| {
+            ErrorModel::bad_request(
+                format!("Invalid view location '{}'", view_metadata.location()),
+                "InvalidViewLocation",
+                Some(Box::new(e)),
+            )
+        })?;
+        storage_profile.require_allowed_location(&view_location)?;
+        validate_view_properties(view_metadata.properties().keys())?;
+
+        let view_id = ViewId::from(view_metadata.uuid());
+        let mut t = C::Transaction::begin_write(self.catalog_state.clone()).await?;
+        C::create_view(
+            namespace_id,
+            &ident,
+            view_metadata,
+            &metadata_location,
+            &view_location,
+            t.transaction(),
+        )
+        .await?;
+        self.authorizer
+            .create_view(self.owner, view_id, namespace_id)
+            .await?;
+        t.commit().await?;
+
+        outcome.table_id = Some(*view_id);
+        Ok(CatalogImportTableStatus::Imported)
+    }
+}
+
+fn record_result(
+    outcome: &mut CatalogImportTable,
+    result: Result<CatalogImportTableStatus>,
+    kind: &str,
+) {
+    match result {
+        Ok(status) => outcome.status = status,
+        Err(e) => {
+            tracing::debug!(
+                "Failed to import {kind} {:?}.{}: {}",
+                outcome.namespace,
+                outcome.name,
+                e.error
+            );
+            outcome.error = Some(e.error.message);
+        }
+    }
+}
+
+fn parse_metadata_location(metadata_location: &str) -> Result<Location> {
+    Location::from_str(metadata_location).map_err(|e| {
+        ErrorModel::bad_request(
+            format!("Invalid metadata location '{metadata_location}'"),
+            "InvalidMetadataLocation",
+            Some(Box::new(e)),
+        )
+        .into()
+    })
+}
+
+pub(crate) fn namespace_ident(namespace: &[String]) -> Result<NamespaceIdent> {
+    NamespaceIdent::from_vec(namespace.to_vec()).map_err(|e| {
+        ErrorModel::bad_request(
+            format!("Invalid namespace {namespace:?}"),
+            "InvalidNamespace",
+            Some(Box::new(e)),
+        )
+        .into()
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct SourceConfig {
+    #[serde(default)]
+    defaults: HashMap<String, String>,
+    #[serde(default)]
+    overrides: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct SourceNamespaces {
+    namespaces: Vec<Vec<String>>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SourceTableIdentifier {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct SourceTables {
+    #[serde(default)]
+    identifiers: Vec<SourceTableIdentifier>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct SourceTable {
+    metadata_location: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SourceToken {
+    access_token: String,
+}
+
+/// Client of the Iceberg REST API of the source catalog.
+struct SourceCatalog {
+    client: reqwest::Client,
+    /// `<uri>/v1[/<prefix>]`
+    base: url::Url,
+    token: Option<String>,
+}
+
+impl SourceCatalog {
+    async fn connect(
+        source: &CatalogImportSource,
+        credential: Option<&CatalogImportCredential>,
+    ) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(SOURCE_REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| {
+                ErrorModel::internal(
+                    "Failed to create HTTP client",
+                    "HttpClientError",
+                    Some(Box::new(e)),
+                )
+            })?;
+        let mut catalog = Self {
+            client,
+            base: join_path(&source.uri, ["v1"]),
+            token: None,
+        };
+        catalog.token = match credential {
+            Some(CatalogImportCredential {
+                token: Some(token), ..
+            }) => Some(token.clone()),
+            Some(CatalogImportCredential {
+                credential: Some(client_credential),
+                oauth2_server_uri,
+                scope,
+                ..
+            }) => Some(
+                catalog
+                    .fetch_token(
+                        oauth2_server_uri
+                            .clone()
+                            .unwrap_or_else(|| join_path(&catalog.base, ["oauth", "tokens"])),
+                        client_credential,
+                        scope.as_deref().unwrap_or(DEFAULT_SCOPE),
+                    )
+                    .await?,
+            ),
+            _ => None,
+        };
+
+        let query = source
+            .warehouse
+            .as_deref()
+            .map(|w| vec![("warehouse", w.to_string())])
+            .unwrap_or_default();
+        let config = catalog
+            .get::<SourceConfig>(join_path(&catalog.base, ["config"]), &query)
+            .await?;
+        if let Some(prefix) = config
+            .overrides
+            .get("prefix")
+            .or_else(|| config.defaults.get("prefix"))
+        {
+            catalog.base = join_path(&catalog.base, prefix.split('/').filter(|s| !s.is_empty()));
+        }
+        Ok(catalog)
+    }
+
+    async fn fetch_token(
+        &self,
+        token_endpoint: url::Url,
+        client_credential: &str,
+        scope: &str,
+    ) -> Result<String> {
+        let (client_id, client_secret) = client_credential.split_once(':').ok_or_else(|| {
+            ErrorModel::bad_request(
+                "Credential must have the format `<client_id>:<client_secret>`",
+                "InvalidCredential",
+                None,
+            )
+        })?;
+        let form = [
+            ("grant_type", "client_credentials"),
+            ("client_id", client_id),
+            ("client_secret", client_secret),
+            ("scope", scope),
+        ];
+        let token = self
+            .client
+            .post(token_endpoint)
+            .form(&form)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|e| source_error("Failed to obtain a token for the source catalog", e))?
+            .json::<SourceToken>()
+            .await
+            .map_err(|e| source_error("Failed to parse the token of the source catalog", e))?;
+        Ok(token.access_token)
+    }
+
+    async fn get<T: DeserializeOwned>(&self, url: url::Url, query: &[(&str, String)]) -> Result<T> {
+        let mut request = self.client.get(url.clone()).query(query);
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        request
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|e| source_error(&format!("Request to source catalog {url} failed"), e))?
+            .json::<T>()
+            .await
+            .map_err(|e| source_error(&format!("Invalid response of source catalog {url}"), e))
+    }
+
+    /// Direct children of `parent`, or all top-level namespaces.
+    async fn list_namespaces(&self, parent: Option<&[String]>) -> Result<Vec<Vec<String>>> {
+        let url = join_path(&self.base, ["namespaces"]);
+        let mut namespaces = vec![];
+        let mut page_token = None;
+        loop {
+            let mut query = vec![];
+            if let Some(parent) = parent {
+                query.push(("parent", encode_namespace(parent)));
+            }
+            if let Some(page_token) = page_token {
+                query.push(("pageToken", page_token));
+            }
+            let page = self.get::<SourceNamespaces>(url.clone(), &query).await?;
+            namespaces.extend(page.namespaces);
+            page_token = page.next_page_token.filter(|t| !t.is_empty());
+            if page_token.is_none() {
+                break;
+            }
+        }
+        // Some catalogs ignore `parent` for flat namespaces.
+        namespaces.retain(|n| match parent {
+            Some(parent) => n.len() == parent.len() + 1 && n.starts_with(parent),
+            None => n.len() == 1,
+        });
+        Ok(namespaces)
+    }
+
+    async fn list_tables(&self, namespace: &[String]) -> Result<Vec<String>> {
+        let url = join_path(
+            &self.base,
+            ["namespaces", &encode_namespace(namespace), "tables"],
+        );
+        let mut tables = vec![];
+        let mut page_token = None;
+        loop {
+            let query = page_token
+                .map(|t| vec![("pageToken", t)])
+                .unwrap_or_default();
+            let page = self.get::<SourceTables>(url.clone(), &query).await?;
+            tables.extend(page.identifiers.into_iter().map(|i| i.name));
+            page_token = page.next_page_token.filter(|t| !t.is_empty());
+            if page_token.is_none() {
+                break;
+            }
+        }
+        Ok(tables)
+    }
+
+    async fn load_metadata_location(
+        &self,
+        namespace: &[String],
+        table: &str,
+    ) -> Result<Option<String>> {
+        let url = join_path(
+            &self.base,
+            ["namespaces", &encode_namespace(namespace), "tables", table],
+        );
+        let table = self
+            .get::<SourceTable>(url, &[("snapshots", "refs".to_string())])
+            .await?;
+        Ok(table.metadata_location)
+    }
+}
+
+fn encode_namespace(namespace: &[String]) -> String {
+    namespace.join(&NAMESPACE_SEPARATOR.to_string())
+}
+
+/// Appends percent-encoded path segments to `base`.
+fn join_path<'a>(base: &url::Url, segments: impl IntoIterator<Item = &'a str>) -> url::Url {
+    let mut url = base.clone();
+    if let Ok(mut path) = url.path_segments_mut() {
+        path.pop_if_empty().extend(segments);
+    }
+    url
+}
+
+fn source_error(message: &str, e: reqwest::Error) -> crate::api::IcebergErrorResponse {
+    ErrorModel::internal(message, "CatalogImportSourceError", Some(Box::new(e))).into()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_join_path() {
+        let base = url::Url::parse("https://catalog.example.com/api/catalog/").unwrap();
+        let base = join_path(&base, ["v1"]);
+        assert_eq!(base.as_str(), "https://catalog.example.com/api/catalog/v1");
+
+        let namespace = encode_namespace(&["sales".to_string(), "eu west".to_string()]);
+        let url = join_path(&base, ["namespaces", &namespace, "tables", "orders"]);
+        assert_eq!(
+            url.as_str(),
+            "https://catalog.example.com/api/catalog/v1/namespaces/sales%1Feu%20west/tables/orders"
+        );
+    }
+
+    #[test]
+    fn test_credential_debug_is_redacted() {
+        let credential = CatalogImportCredential {
+            token: Some("secret-token".to_string()),
+            credential: Some("client:secret".to_string()),
+            oauth2_server_uri: None,
+            scope: Some("catalog".to_string()),
+        };
+        let debug = format!("{credential:?}");
+        assert!(!debug.contains("secret-token"));
+        assert!(!debug.contains("client:secret"));
+        assert!(debug.contains("<redacted>"));
+    }
+}
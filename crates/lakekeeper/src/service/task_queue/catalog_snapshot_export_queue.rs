@@ -0,0 +1,1615 @@
+//! Periodic export of the logical catalog state of a warehouse for disaster recovery.
+//!
+//! Each run reads the warehouse configuration, task queue configurations, namespaces and
+//! the metadata pointers of all tables and views in a single consistent read, adds the
+//! grants on these entities and the users they are granted to, and writes them as a
+//! checksummed manifest below the base location of the warehouse. A restored catalog can
+//! then be verified against any exported snapshot, and a warehouse can be restored to the
+//! state of a snapshot.
+//!
+//! Full snapshots contain the complete state. Differential snapshots only contain the
+//! changes since the previous snapshot and are replayed onto their full base snapshot
+//! to obtain the state at the time of the export.
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    str::FromStr as _,
+    sync::LazyLock,
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use iceberg_ext::{catalog::rest::ErrorModel, configs::Location};
+use rand::RngCore as _;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest as _, Sha256};
+use tracing::Instrument;
+use utoipa::{PartialSchema, ToSchema};
+use uuid::Uuid;
+
+use super::{
+    delete_files, EntityId, QueueApiConfig, QueueConfig, Task, TaskId, TaskMetadata,
+    DEFAULT_MAX_TIME_SINCE_LAST_HEARTBEAT,
+};
+use crate::{
+    api::{
+        iceberg::v1::{PageToken, PaginationQuery},
+        management::v1::{user::UserType, ListSort, TabularType},
+        IcebergErrorResponse, Result,
+    },
+    catalog::{
+        compression_codec::CompressionCodec,
+        io::{read_file, write_metadata_file, StorageIo},
+        maybe_get_secret,
+    },
+    service::{
+        authn::UserId,
+        authz::{Authorizer, GrantObject, GrantPrincipal, ObjectGrant},
+        storage::StorageProfile,
+        Catalog, SecretStore, Transaction,
+    },
+    WarehouseId, CONFIG,
+};
+
+pub(crate) const QUEUE_NAME: &str = "catalog_snapshot_export";
+pub(crate) static API_CONFIG: LazyLock<QueueApiConfig> = LazyLock::new(|| QueueApiConfig {
+    queue_name: QUEUE_NAME,
+    utoipa_type_name: CatalogSnapshotExportQueueConfig::name(),
+    utoipa_schema: CatalogSnapshotExportQueueConfig::schema(),
+});
+
+/// Directory below the base location of a warehouse that manifests are written to.
+const SNAPSHOT_DIRECTORY: [&str; 2] = ["_lakekeeper", "catalog-snapshots"];
+const MANIFEST_FORMAT_VERSION: u8 = 1;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(crate) struct CatalogSnapshotExportPayload {}
+
+/// Maximum number of manifests read to replay a differential snapshot.
+/// Protects against cycles in corrupted manifests.
+const MAX_REPLAYED_SNAPSHOTS: usize = 1000;
+
+/// Catalog snapshot export settings of a warehouse.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct CatalogSnapshotExportQueueConfig {
+    /// Number of snapshots kept. Older snapshots and their manifests are deleted
+    /// after each export. The full base snapshot of a retained differential snapshot
+    /// is kept as well. Default: 30
+    #[serde(default = "default_max_retained_snapshots")]
+    pub(crate) max_retained_snapshots: u32,
+    /// Number of differential snapshots exported between two full snapshots.
+    /// `0` exports a full snapshot on every run. Default: 6
+    #[serde(default = "default_max_differential_snapshots")]
+    pub(crate) max_differential_snapshots: u32,
+}
+
+fn default_max_retained_snapshots() -> u32 {
+    30
+}
+
+fn default_max_differential_snapshots() -> u32 {
+    6
+}
+
+impl Default for CatalogSnapshotExportQueueConfig {
+    fn default() -> Self {
+        Self {
+            max_retained_snapshots: default_max_retained_snapshots(),
+            max_differential_snapshots: default_max_differential_snapshots(),
+        }
+    }
+}
+
+impl QueueConfig for CatalogSnapshotExportQueueConfig {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum CatalogSnapshotKind {
+    /// Contains the complete catalog state
+    Full,
+    /// Contains the changes since the previous snapshot
+    Differential,
+}
+
+/// An exported snapshot of the catalog state of a warehouse.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct CatalogSnapshot {
+    pub snapshot_id: Uuid,
+    #[schema(value_type = uuid::Uuid)]
+    pub warehouse_id: WarehouseId,
+    pub kind: CatalogSnapshotKind,
+    /// Full snapshot that a differential snapshot is replayed onto
+    pub base_snapshot_id: Option<Uuid>,
+    /// Previous snapshot whose state a differential snapshot contains the changes of
+    pub parent_snapshot_id: Option<Uuid>,
+    /// Location of the manifest
+    pub location: String,
+    /// Hex encoded SHA-256 checksum of the manifest
+    pub checksum: String,
+    /// Number of namespaces in the catalog state, including soft-deleted ones
+    pub namespace_count: i64,
+    /// Number of tables and views in the catalog state, including soft-deleted ones
+    pub tabular_count: i64,
+    /// Task that exported the snapshot
+    pub task_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Catalog state of a warehouse as stored in a snapshot manifest.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct CatalogSnapshotState {
+    pub warehouse: CatalogSnapshotWarehouse,
+    /// Task queue configurations by queue name
+    pub task_queue_configs: BTreeMap<String, serde_json::Value>,
+    /// Namespaces including soft-deleted ones, ordered by id
+    pub namespaces: Vec<CatalogSnapshotNamespace>,
+    /// Tables and views including soft-deleted ones, ordered by id
+    pub tabulars: Vec<CatalogSnapshotTabular>,
+    /// Grants on the warehouse, its namespaces, tables and views.
+    /// `None` for snapshots that were exported before grants were included.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access: Option<CatalogSnapshotAccess>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct CatalogSnapshotWarehouse {
+    pub name: String,
+    pub storage_profile: serde_json::Value,
+    /// `None` if tabulars are deleted immediately
+    pub tabular_expiration_seconds: Option<i64>,
+    pub protected: bool,
+    pub disabled_endpoint_groups: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct CatalogSnapshotNamespace {
+    pub namespace_id: Uuid,
+    pub name: Vec<String>,
+    pub properties: BTreeMap<String, String>,
+    pub protected: bool,
+    pub deleted: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct CatalogSnapshotTabular {
+    pub tabular_id: Uuid,
+    pub typ: TabularType,
+    pub namespace: Vec<String>,
+    pub name: String,
+    /// `None` for staged tables
+    pub metadata_location: Option<String>,
+    pub protected: bool,
+    pub deleted: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct CatalogSnapshotAccess {
+    /// Privileges granted directly to users and roles, ordered by object
+    pub grants: Vec<ObjectGrant>,
+    /// Users that privileges are granted to, ordered by id
+    pub users: Vec<CatalogSnapshotUser>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct CatalogSnapshotUser {
+    #[schema(value_type = String)]
+    pub id: UserId,
+    pub name: String,
+    pub email: Option<String>,
+    pub user_type: UserType,
+}
+
+/// Content of a manifest file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct CatalogSnapshotManifest {
+    pub(crate) format_version: u8,
+    pub(crate) snapshot_id: Uuid,
+    pub(crate) warehouse_id: WarehouseId,
+    pub(crate) created_at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub(crate) content: CatalogSnapshotContent,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub(crate) enum CatalogSnapshotContent {
+    Full {
+        state: CatalogSnapshotState,
+    },
+    #[serde(rename_all = "kebab-case")]
+    Differential {
+        base_snapshot_id: Uuid,
+        parent_snapshot_id: Uuid,
+        changes: CatalogSnapshotChanges,
+    },
+}
+
+impl CatalogSnapshotContent {
+    pub(crate) fn kind(&self) -> CatalogSnapshotKind {
+        match self {
+            Self::Full { .. } => CatalogSnapshotKind::Full,
+            Self::Differential { .. } => CatalogSnapshotKind::Differential,
+        }
+    }
+}
+
+/// Changes of the catalog state between two snapshots.
+/// Namespaces, tables and views are identified by id.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct CatalogSnapshotChanges {
+    /// New warehouse settings, `None` if unchanged
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) warehouse: Option<CatalogSnapshotWarehouse>,
+    /// New grants and users, `None` if unchanged
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) access: Option<CatalogSnapshotAccess>,
+    /// Added or changed task queue configurations by queue name
+    pub(crate) upserted_task_queue_configs: BTreeMap<String, serde_json::Value>,
+    pub(crate) removed_task_queue_configs: Vec<String>,
+    /// Added or changed namespaces
+    pub(crate) upserted_namespaces: Vec<CatalogSnapshotNamespace>,
+    pub(crate) removed_namespaces: Vec<Uuid>,
+    /// Added or changed tables and views
+    pub(crate) upserted_tabulars: Vec<CatalogSnapshotTabular>,
+    pub(crate) removed_tabulars: Vec<Uuid>,
+}
+
+impl CatalogSnapshotChanges {
+    /// Changes that turn `previous` into `current`.
+    pub(crate) fn between(previous: &CatalogSnapshotState, current: &CatalogSnapshotState) -> Self {
+        let (upserted_namespaces, removed_namespaces) =
+            upserts_and_removals(&previous.namespaces, &current.namespaces, |n| {
+                n.namespace_id
+            });
+        let (upserted_tabulars, removed_tabulars) =
+            upserts_and_removals(&previous.tabulars, &current.tabulars, |t| t.tabular_id);
+        Self {
+            warehouse: (previous.warehouse != current.warehouse).then(|| current.warehouse.clone()),
+            access: (previous.access != current.access)
+                .then(|| current.access.clone())
+                .flatten(),
+            upserted_task_queue_configs: current
+                .task_queue_configs
+                .iter()
+                .filter(|(queue_name, config)| {
+                    previous.task_queue_configs.get(*queue_name) != Some(*config)
+                })
+                .map(|(queue_name, config)| (queue_name.clone(), config.clone()))
+                .collect(),
+            removed_task_queue_configs: previous
+                .task_queue_configs
+                .keys()
+                .filter(|queue_name| !current.task_queue_configs.contains_key(*queue_name))
+                .cloned()
+                .collect(),
+            upserted_namespaces,
+            removed_namespaces,
+            upserted_tabulars,
+            removed_tabulars,
+        }
+    }
+
+    /// Applies the changes to the state of the previous snapshot.
+    pub(crate) fn apply(self, state: &mut CatalogSnapshotState) {
+        if let Some(warehouse) = self.warehouse {
+            state.warehouse = warehouse;
+        }
+        if let Some(access) = self.access {
+            state.access = Some(access);
+        }
+        for queue_name in &self.removed_task_queue_configs {
+            state.task_queue_configs.remove(queue_name);
+        }
+        state
+            .task_queue_configs
+            .extend(self.upserted_task_queue_configs);
+        apply_upserts_and_removals(
+            &mut state.namespaces,
+            self.upserted_namespaces,
+            &self.removed_namespaces,
+            |n| n.namespace_id,
+        );
+        apply_upserts_and_removals(
+            &mut state.tabulars,
+            self.upserted_tabulars,
+            &self.removed_tabulars,
+            |t| t.tabular_id,
+        );
+    }
+}
+
+fn upserts_and_removals<T: Clone + PartialEq>(
+    previous: &[T],
+    current: &[T],
+    id: impl Fn(&T) -> Uuid,
+) -> (Vec<T>, Vec<Uuid>) {
+    let previous_by_id = previous
+        .iter()
+        .map(|e| (id(e), e))
+        .collect::<BTreeMap<_, _>>();
+    let current_ids = current.iter().map(&id).collect::<BTreeSet<_>>();
+    let upserted = current
+        .iter()
+        .filter(|e| previous_by_id.get(&id(e)) != Some(e))
+        .cloned()
+        .collect();
+    let removed = previous_by_id
+        .keys()
+        .filter(|entity_id| !current_ids.contains(*entity_id))
+        .copied()
+        .collect();
+    (upserted, removed)
+}
+
+fn apply_upserts_and_removals<T>(
+    entities: &mut Vec<T>,
+    upserted: Vec<T>,
+    removed: &[Uuid],
+    id: impl Fn(&T) -> Uuid,
+) {
+    let mut by_id = std::mem::take(entities)
+        .into_iter()
+        .map(|e| (id(&e), e))
+        .collect::<BTreeMap<_, _>>();
+    for entity_id in removed {
+        by_id.remove(entity_id);
+    }
+    by_id.extend(upserted.into_iter().map(|e| (id(&e), e)));
+    // Snapshot states are ordered by id
+    *entities = by_id.into_values().collect();
+}
+
+/// A manifest file: the manifest and the checksum of its serialized form.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct CatalogSnapshotFile {
+    pub(crate) checksum: String,
+    pub(crate) manifest: CatalogSnapshotManifest,
+}
+
+impl CatalogSnapshotFile {
+    pub(crate) fn new(manifest: CatalogSnapshotManifest) -> Result<Self> {
+        Ok(Self {
+            checksum: manifest_checksum(&manifest)?,
+            manifest,
+        })
+    }
+
+    /// Whether the manifest still matches the checksum it was written with.
+    pub(crate) fn checksum_valid(&self) -> Result<bool> {
+        Ok(manifest_checksum(&self.manifest)? == self.checksum)
+    }
+}
+
+fn manifest_checksum(manifest: &CatalogSnapshotManifest) -> Result<String> {
+    let serialized = serde_json::to_vec(manifest).map_err(|e| {
+        ErrorModel::internal(
+            "Failed to serialize catalog snapshot manifest",
+            "CatalogSnapshotSerializationError",
+            Some(Box::new(e)),
+        )
+    })?;
+    Ok(format!("{:x}", Sha256::digest(serialized)))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum CatalogSnapshotEntityType {
+    Warehouse,
+    TaskQueueConfig,
+    Namespace,
+    Table,
+    View,
+    Grant,
+    User,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum CatalogSnapshotDifferenceKind {
+    /// Contained in the snapshot but not in the catalog
+    Missing,
+    /// Contained in the catalog but not in the snapshot
+    Unexpected,
+    /// An attribute differs between snapshot and catalog
+    Changed,
+}
+
+/// A difference between a snapshot and the current catalog state.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct CatalogSnapshotDifference {
+    pub entity_type: CatalogSnapshotEntityType,
+    pub entity_id: Option<Uuid>,
+    /// Qualified name of the entity, the queue name for task queue configs
+    /// or a description of the grant
+    pub name: String,
+    pub kind: CatalogSnapshotDifferenceKind,
+    /// Attribute that changed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attribute: Option<String>,
+    /// Value in the snapshot
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<serde_json::Value>,
+    /// Value in the catalog
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual: Option<serde_json::Value>,
+}
+
+impl CatalogSnapshotDifference {
+    pub(crate) fn presence(
+        entity_type: CatalogSnapshotEntityType,
+        entity_id: Option<Uuid>,
+        name: String,
+        kind: CatalogSnapshotDifferenceKind,
+    ) -> Self {
+        Self {
+            entity_type,
+            entity_id,
+            name,
+            kind,
+            attribute: None,
+            expected: None,
+            actual: None,
+        }
+    }
+}
+
+/// Collects changed attributes of a single entity.
+struct AttributeDiff<'a> {
+    differences: &'a mut Vec<CatalogSnapshotDifference>,
+    entity_type: CatalogSnapshotEntityType,
+    entity_id: Option<Uuid>,
+    name: String,
+}
+
+impl AttributeDiff<'_> {
+    fn compare<T: Serialize + PartialEq>(&mut self, attribute: &str, expected: &T, actual: &T) {
+        if expected != actual {
+            self.differences.push(CatalogSnapshotDifference {
+                entity_type: self.entity_type,
+                entity_id: self.entity_id,
+                name: self.name.clone(),
+                kind: CatalogSnapshotDifferenceKind::Changed,
+                attribute: Some(attribute.to_string()),
+                expected: serde_json::to_value(expected).ok(),
+                actual: serde_json::to_value(actual).ok(),
+            });
+        }
+    }
+}
+
+/// Compares the catalog state stored in a snapshot with the current state.
+/// Namespaces, tables and views are matched by id.
+pub(crate) fn diff_catalog_state(
+    expected: &CatalogSnapshotState,
+    actual: &CatalogSnapshotState,
+) -> Vec<CatalogSnapshotDifference> {
+    let mut differences = Vec::new();
+    diff_warehouse(&mut differences, expected, actual);
+    diff_task_queue_configs(&mut differences, expected, actual);
+    diff_namespaces(&mut differences, expected, actual);
+    diff_tabulars(&mut differences, expected, actual);
+    diff_access(&mut differences, expected, actual);
+    differences
+}
+
+fn diff_warehouse(
+    differences: &mut Vec<CatalogSnapshotDifference>,
+    expected: &CatalogSnapshotState,
+    actual: &CatalogSnapshotState,
+) {
+    let mut warehouse = AttributeDiff {
+        differences,
+        entity_type: CatalogSnapshotEntityType::Warehouse,
+        entity_id: None,
+        name: expected.warehouse.name.clone(),
+    };
+    warehouse.compare("name", &expected.warehouse.name, &actual.warehouse.name);
+    warehouse.compare(
+        "storage-profile",
+        &expected.warehouse.storage_profile,
+        &actual.warehouse.storage_profile,
+    );
+    warehouse.compare(
+        "tabular-expiration-seconds",
+        &expected.warehouse.tabular_expiration_seconds,
+        &actual.warehouse.tabular_expiration_seconds,
+    );
+    warehouse.compare(
+        "protected",
+        &expected.warehouse.protected,
+        &actual.warehouse.protected,
+    );
+    warehouse.compare(
+        "disabled-endpoint-groups",
+        &expected.warehouse.disabled_endpoint_groups,
+        &actual.warehouse.disabled_endpoint_groups,
+    );
+}
+
+fn diff_task_queue_configs(
+    differences: &mut Vec<CatalogSnapshotDifference>,
+    expected: &CatalogSnapshotState,
+    actual: &CatalogSnapshotState,
+) {
+    let queue_names = expected
+        .task_queue_configs
+        .keys()
+        .chain(actual.task_queue_configs.keys())
+        .collect::<BTreeSet<_>>();
+    for queue_name in queue_names {
+        let entity_type = CatalogSnapshotEntityType::TaskQueueConfig;
+        match (
+            expected.task_queue_configs.get(queue_name),
+            actual.task_queue_configs.get(queue_name),
+        ) {
+            (Some(expected), Some(actual)) => AttributeDiff {
+                differences: &mut *differences,
+                entity_type,
+                entity_id: None,
+                name: queue_name.clone(),
+            }
+            .compare("config", expected, actual),
+            (Some(_), None) => differences.push(CatalogSnapshotDifference::presence(
+                entity_type,
+                None,
+                queue_name.clone(),
+                CatalogSnapshotDifferenceKind::Missing,
+            )),
+            (None, Some(_)) => differences.push(CatalogSnapshotDifference::presence(
+                entity_type,
+                None,
+                queue_name.clone(),
+                CatalogSnapshotDifferenceKind::Unexpected,
+            )),
+            (None, None) => {}
+        }
+    }
+}
+
+fn diff_namespaces(
+    differences: &mut Vec<CatalogSnapshotDifference>,
+    expected: &CatalogSnapshotState,
+    actual: &CatalogSnapshotState,
+) {
+    let actual_namespaces = actual
+        .namespaces
+        .iter()
+        .map(|n| (n.namespace_id, n))
+        .collect::<BTreeMap<_, _>>();
+    for namespace in &expected.namespaces {
+        let entity_type = CatalogSnapshotEntityType::Namespace;
+        let name = namespace.name.join(".");
+        let Some(current) = actual_namespaces.get(&namespace.namespace_id) else {
+            differences.push(CatalogSnapshotDifference::presence(
+                entity_type,
+                Some(namespace.namespace_id),
+                name,
+                CatalogSnapshotDifferenceKind::Missing,
+            ));
+            continue;
+        };
+        let mut diff = AttributeDiff {
+            differences: &mut *differences,
+            entity_type,
+            entity_id: Some(namespace.namespace_id),
+            name,
+        };
+        diff.compare("name", &namespace.name, &current.name);
+        diff.compare("properties", &namespace.properties, &current.properties);
+        diff.compare("protected", &namespace.protected, &current.protected);
+        diff.compare("deleted", &namespace.deleted, &current.deleted);
+    }
+    let expected_namespaces = expected
+        .namespaces
+        .iter()
+        .map(|n| n.namespace_id)
+        .collect::<BTreeSet<_>>();
+    for namespace in &actual.namespaces {
+        if !expected_namespaces.contains(&namespace.namespace_id) {
+            differences.push(CatalogSnapshotDifference::presence(
+                CatalogSnapshotEntityType::Namespace,
+                Some(namespace.namespace_id),
+                namespace.name.join("."),
+                CatalogSnapshotDifferenceKind::Unexpected,
+            ));
+        }
+    }
+}
+
+fn diff_tabulars(
+    differences: &mut Vec<CatalogSnapshotDifference>,
+    expected: &CatalogSnapshotState,
+    actual: &CatalogSnapshotState,
+) {
+    let actual_tabulars = actual
+        .tabulars
+        .iter()
+        .map(|t| (t.tabular_id, t))
+        .collect::<BTreeMap<_, _>>();
+    for tabular in &expected.tabulars {
+        let Some(current) = actual_tabulars.get(&tabular.tabular_id) else {
+            differences.push(CatalogSnapshotDifference::presence(
+                tabular.entity_type(),
+                Some(tabular.tabular_id),
+                tabular.qualified_name(),
+                CatalogSnapshotDifferenceKind::Missing,
+            ));
+            continue;
+        };
+        let mut diff = AttributeDiff {
+            differences: &mut *differences,
+            entity_type: tabular.entity_type(),
+            entity_id: Some(tabular.tabular_id),
+            name: tabular.qualified_name(),
+        };
+        diff.compare("namespace", &tabular.namespace, &current.namespace);
+        diff.compare("name", &tabular.name, &current.name);
+        diff.compare(
+            "metadata-location",
+            &tabular.metadata_location,
+            &current.metadata_location,
+        );
+        diff.compare("protected", &tabular.protected, &current.protected);
+        diff.compare("deleted", &tabular.deleted, &current.deleted);
+    }
+    let expected_tabulars = expected
+        .tabulars
+        .iter()
+        .map(|t| t.tabular_id)
+        .collect::<BTreeSet<_>>();
+    for tabular in &actual.tabulars {
+        if !expected_tabulars.contains(&tabular.tabular_id) {
+            differences.push(CatalogSnapshotDifference::presence(
+                tabular.entity_type(),
+                Some(tabular.tabular_id),
+                tabular.qualified_name(),
+                CatalogSnapshotDifferenceKind::Unexpected,
+            ));
+        }
+    }
+}
+
+fn diff_access(
+    differences: &mut Vec<CatalogSnapshotDifference>,
+    expected: &CatalogSnapshotState,
+    actual: &CatalogSnapshotState,
+) {
+    // Grants can only be compared if both states include them
+    let (Some(expected), Some(actual)) = (&expected.access, &actual.access) else {
+        return;
+    };
+
+    let actual_grants = actual.grants.iter().map(grant_key).collect::<BTreeSet<_>>();
+    for grant in &expected.grants {
+        if !actual_grants.contains(&grant_key(grant)) {
+            differences.push(CatalogSnapshotDifference::presence(
+                CatalogSnapshotEntityType::Grant,
+                Some(grant.object.id()),
+                grant_name(grant),
+                CatalogSnapshotDifferenceKind::Missing,
+            ));
+        }
+    }
+    let expected_grants = expected
+        .grants
+        .iter()
+        .map(grant_key)
+        .collect::<BTreeSet<_>>();
+    for grant in &actual.grants {
+        if !expected_grants.contains(&grant_key(grant)) {
+            differences.push(CatalogSnapshotDifference::presence(
+                CatalogSnapshotEntityType::Grant,
+                Some(grant.object.id()),
+                grant_name(grant),
+                CatalogSnapshotDifferenceKind::Unexpected,
+            ));
+        }
+    }
+
+    let actual_users = actual
+        .users
+        .iter()
+        .map(|u| (u.id.to_string(), u))
+        .collect::<BTreeMap<_, _>>();
+    for user in &expected.users {
+        let name = user.id.to_string();
+        let Some(current) = actual_users.get(&name) else {
+            differences.push(CatalogSnapshotDifference::presence(
+                CatalogSnapshotEntityType::User,
+                None,
+                name,
+                CatalogSnapshotDifferenceKind::Missing,
+            ));
+            continue;
+        };
+        let mut diff = AttributeDiff {
+            differences: &mut *differences,
+            entity_type: CatalogSnapshotEntityType::User,
+            entity_id: None,
+            name,
+        };
+        diff.compare("name", &user.name, &current.name);
+        diff.compare("email", &user.email, &current.email);
+        diff.compare("user-type", &user.user_type, &current.user_type);
+    }
+    let expected_users = expected
+        .users
+        .iter()
+        .map(|u| u.id.to_string())
+        .collect::<BTreeSet<_>>();
+    for user in &actual.users {
+        let name = user.id.to_string();
+        if !expected_users.contains(&name) {
+            differences.push(CatalogSnapshotDifference::presence(
+                CatalogSnapshotEntityType::User,
+                None,
+                name,
+                CatalogSnapshotDifferenceKind::Unexpected,
+            ));
+        }
+    }
+}
+
+/// Identifies a grant. Grants are ordered by this key in snapshots.
+pub(crate) fn grant_key(grant: &ObjectGrant) -> (GrantObject, String, String) {
+    (
+        grant.object,
+        grant.relation.clone(),
+        grant.principal.to_string(),
+    )
+}
+
+pub(crate) fn grant_name(grant: &ObjectGrant) -> String {
+    format!(
+        "{} on {} to {}",
+        grant.relation, grant.object, grant.principal
+    )
+}
+
+impl CatalogSnapshotTabular {
+    pub(crate) fn entity_type(&self) -> CatalogSnapshotEntityType {
+        match self.typ {
+            TabularType::Table => CatalogSnapshotEntityType::Table,
+            TabularType::View => CatalogSnapshotEntityType::View,
+        }
+    }
+
+    pub(crate) fn qualified_name(&self) -> String {
+        format!("{}.{}", self.namespace.join("."), self.name)
+    }
+
+    pub(crate) fn grant_object(&self) -> GrantObject {
+        match self.typ {
+            TabularType::Table => GrantObject::Table(self.tabular_id),
+            TabularType::View => GrantObject::View(self.tabular_id),
+        }
+    }
+}
+
+/// Grants on the warehouse and on all namespaces, tables and views of `state`,
+/// and the users they are granted to.
+pub(crate) async fn load_catalog_snapshot_access<C: Catalog, A: Authorizer>(
+    warehouse_id: WarehouseId,
+    state: &CatalogSnapshotState,
+    authorizer: &A,
+    catalog_state: C::State,
+) -> Result<CatalogSnapshotAccess> {
+    let objects = std::iter::once(GrantObject::Warehouse(*warehouse_id))
+        .chain(
+            state
+                .namespaces
+                .iter()
+                .map(|n| GrantObject::Namespace(n.namespace_id)),
+        )
+        .chain(
+            state
+                .tabulars
+                .iter()
+                .map(CatalogSnapshotTabular::grant_object),
+        )
+        .collect::<Vec<_>>();
+    let mut grants = authorizer.list_object_grants(&objects).await?;
+    grants.sort_by_cached_key(grant_key);
+
+    let user_ids = grants
+        .iter()
+        .filter_map(|g| match &g.principal {
+            GrantPrincipal::User(user_id) => Some((user_id.to_string(), user_id.clone())),
+            GrantPrincipal::Role(_) => None,
+        })
+        .collect::<BTreeMap<_, _>>()
+        .into_values()
+        .collect();
+    let users = load_catalog_snapshot_users::<C>(user_ids, catalog_state).await?;
+    Ok(CatalogSnapshotAccess { grants, users })
+}
+
+/// Users with the given ids that exist in the catalog, ordered by id.
+pub(crate) async fn load_catalog_snapshot_users<C: Catalog>(
+    user_ids: Vec<UserId>,
+    catalog_state: C::State,
+) -> Result<Vec<CatalogSnapshotUser>> {
+    let mut users = Vec::new();
+    if user_ids.is_empty() {
+        return Ok(users);
+    }
+    let mut page_token = PageToken::Empty;
+    loop {
+        let page = C::list_user(
+            Some(user_ids.clone()),
+            None,
+            None,
+            None,
+            ListSort::default(),
+            PaginationQuery {
+                page_token,
+                page_size: None,
+            },
+            catalog_state.clone(),
+        )
+        .await?;
+        let done = page.users.is_empty();
+        users.extend(page.users.into_iter().map(|u| CatalogSnapshotUser {
+            id: u.id,
+            name: u.name,
+            email: u.email,
+            user_type: u.user_type,
+        }));
+        match page.next_page_token {
+            Some(token) if !done => page_token = PageToken::Present(token),
+            _ => break,
+        }
+    }
+    users.sort_by_cached_key(|u| u.id.to_string());
+    Ok(users)
+}
+
+/// Location of the manifest of a snapshot below the base location of the warehouse.
+pub(crate) fn catalog_snapshot_location(
+    storage_profile: &StorageProfile,
+    snapshot_id: Uuid,
+) -> Result<Location> {
+    let mut location = storage_profile.base_location()?;
+    location
+        .without_trailing_slash()
+        .extend(SNAPSHOT_DIRECTORY)
+        .push(&format!("{snapshot_id}.json"));
+    Ok(location)
+}
+
+pub(crate) async fn read_catalog_snapshot(
+    file_io: &StorageIo,
+    location: &Location,
+) -> Result<CatalogSnapshotFile> {
+    let content = read_file(file_io, location).await?;
+    Ok(serde_json::from_slice(&content).map_err(|e| {
+        ErrorModel::internal(
+            format!("Failed to parse catalog snapshot manifest at {location}"),
+            "CatalogSnapshotManifestInvalid",
+            Some(Box::new(e)),
+        )
+    })?)
+}
+
+/// Catalog state at the time of a snapshot.
+#[derive(Debug)]
+pub(crate) struct ReplayedCatalogSnapshot {
+    pub(crate) snapshot_id: Uuid,
+    pub(crate) kind: CatalogSnapshotKind,
+    pub(crate) created_at: DateTime<Utc>,
+    /// Checksum of the manifest of the snapshot
+    pub(crate) checksum: String,
+    pub(crate) state: CatalogSnapshotState,
+    /// Snapshots whose manifests were replayed, starting with the full base snapshot
+    pub(crate) replayed_snapshots: Vec<Uuid>,
+    /// All replayed manifests match their checksums
+    pub(crate) checksums_valid: bool,
+}
+
+/// Replays a differential snapshot onto its full base snapshot.
+/// The manifests of previous snapshots are read from their default location.
+/// Full snapshots are returned as is.
+pub(crate) async fn replay_catalog_snapshot(
+    file: CatalogSnapshotFile,
+    file_io: &StorageIo,
+    storage_profile: &StorageProfile,
+) -> Result<ReplayedCatalogSnapshot> {
+    let snapshot_id = file.manifest.snapshot_id;
+    let warehouse_id = file.manifest.warehouse_id;
+    let kind = file.manifest.content.kind();
+    let created_at = file.manifest.created_at;
+    let checksum = file.checksum.clone();
+    let mut checksums_valid = file.checksum_valid()?;
+
+    // Differential snapshots, newest first
+    let mut differentials = Vec::new();
+    let mut current = file;
+    let (base_snapshot_id, mut state) = loop {
+        match current.manifest.content {
+            CatalogSnapshotContent::Full { state } => break (current.manifest.snapshot_id, state),
+            CatalogSnapshotContent::Differential {
+                base_snapshot_id,
+                parent_snapshot_id,
+                changes,
+            } => {
+                if differentials.len() >= MAX_REPLAYED_SNAPSHOTS {
+                    return Err(invalid_chain_error(
+                        snapshot_id,
+                        &format!("more than {MAX_REPLAYED_SNAPSHOTS} differential snapshots"),
+                    ));
+                }
+                differentials.push((current.manifest.snapshot_id, base_snapshot_id, changes));
+
+                let location = catalog_snapshot_location(storage_profile, parent_snapshot_id)?;
+                current = read_catalog_snapshot(file_io, &location).await?;
+                checksums_valid &= current.checksum_valid()?;
+                if current.manifest.snapshot_id != parent_snapshot_id
+                    || current.manifest.warehouse_id != warehouse_id
+                {
+                    return Err(invalid_chain_error(
+                        snapshot_id,
+                        &format!("manifest at {location} belongs to a different snapshot"),
+                    ));
+                }
+            }
+        }
+    };
+
+    let mut replayed_snapshots = vec![base_snapshot_id];
+    for (differential_id, expected_base_id, changes) in differentials.into_iter().rev() {
+        if expected_base_id != base_snapshot_id {
+            return Err(invalid_chain_error(
+                snapshot_id,
+                &format!(
+                    "snapshot {differential_id} is based on {expected_base_id}, not on {base_snapshot_id}"
+                ),
+            ));
+        }
+        changes.apply(&mut state);
+        replayed_snapshots.push(differential_id);
+    }
+
+    Ok(ReplayedCatalogSnapshot {
+        snapshot_id,
+        kind,
+        created_at,
+        checksum,
+        state,
+        replayed_snapshots,
+        checksums_valid,
+    })
+}
+
+fn invalid_chain_error(snapshot_id: Uuid, reason: &str) -> IcebergErrorResponse {
+    ErrorModel::internal(
+        format!("Failed to replay catalog snapshot {snapshot_id}: {reason}"),
+        "CatalogSnapshotChainInvalid",
+        None,
+    )
+    .into()
+}
+
+/// Schedules the next export of the warehouse if catalog snapshot export is enabled.
+pub(crate) async fn maybe_queue_catalog_snapshot_export<C: Catalog>(
+    warehouse_id: WarehouseId,
+    parent_task_id: Option<TaskId>,
+    transaction: <C::Transaction as Transaction<C::State>>::Transaction<'_>,
+) -> Result<()> {
+    if CONFIG.enable_catalog_snapshot_export {
+        C::queue_catalog_snapshot_export(
+            TaskMetadata {
+                warehouse_id,
+                parent_task_id,
+                entity_id: EntityId::Warehouse(*warehouse_id),
+                schedule_for: Some(Utc::now() + CONFIG.catalog_snapshot_export_interval_seconds),
+            },
+            CatalogSnapshotExportPayload::default(),
+            transaction,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+pub(crate) async fn catalog_snapshot_export_worker<C: Catalog, A: Authorizer, S: SecretStore>(
+    catalog_state: C::State,
+    authorizer: A,
+    secret_state: S,
+    poll_interval: std::time::Duration,
+) {
+    loop {
+        let task = match C::pick_new_task(
+            QUEUE_NAME,
+            DEFAULT_MAX_TIME_SINCE_LAST_HEARTBEAT,
+            catalog_state.clone(),
+        )
+        .await
+        {
+            Ok(task) => task,
+            Err(err) => {
+                tracing::error!("Failed to fetch catalog snapshot export task: {:?}", err);
+                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                continue;
+            }
+        };
+
+        let Some(task) = task else {
+            let jitter = { rand::rng().next_u64() % 500 };
+            tokio::time::sleep(poll_interval + Duration::from_millis(jitter)).await;
+            continue;
+        };
+        let config = match task.task_config::<CatalogSnapshotExportQueueConfig>() {
+            Ok(config) => config,
+            Err(err) => {
+                tracing::error!("Failed to deserialize task config: {:?}", err);
+                continue;
+            }
+        }
+        .unwrap_or_default();
+
+        let span = tracing::debug_span!(
+            "catalog_snapshot_export",
+            warehouse_id = %task.task_metadata.warehouse_id,
+            queue_name = %task.queue_name,
+            task = ?task,
+        );
+
+        instrumented_export::<C, A, S>(
+            catalog_state.clone(),
+            &authorizer,
+            &secret_state,
+            &task,
+            &config,
+        )
+        .instrument(span.or_current())
+        .await;
+    }
+}
+
+async fn instrumented_export<C: Catalog, A: Authorizer, S: SecretStore>(
+    catalog_state: C::State,
+    authorizer: &A,
+    secret_state: &S,
+    task: &Task,
+    config: &CatalogSnapshotExportQueueConfig,
+) {
+    match export::<C, A, S>(
+        catalog_state.clone(),
+        authorizer,
+        secret_state,
+        task,
+        config,
+    )
+    .await
+    {
+        Ok(snapshot) => {
+            tracing::info!(
+                "Exported catalog snapshot {} to {} with checksum {}",
+                snapshot.snapshot_id,
+                snapshot.location,
+                snapshot.checksum
+            );
+        }
+        Err(err) => {
+            tracing::error!("Failed to export catalog snapshot: {}", err.error);
+            super::record_error_with_catalog::<C>(
+                catalog_state.clone(),
+                &format!("Failed to export catalog snapshot: '{:?}'", err.error),
+                config.max_retries(),
+                task.task_id,
+            )
+            .await;
+        }
+    }
+}
+
+async fn export<C: Catalog, A: Authorizer, S: SecretStore>(
+    catalog_state: C::State,
+    authorizer: &A,
+    secret_state: &S,
+    task: &Task,
+    config: &CatalogSnapshotExportQueueConfig,
+) -> Result<CatalogSnapshot> {
+    let warehouse_id = task.task_metadata.warehouse_id;
+
+    let mut trx = C::Transaction::begin_read(catalog_state.clone()).await?;
+    let warehouse = C::require_warehouse(warehouse_id, trx.transaction()).await?;
+    let recent_snapshots = C::list_catalog_snapshots(
+        warehouse_id,
+        i64::from(config.max_differential_snapshots) + 1,
+        trx.transaction(),
+    )
+    .await?;
+    trx.commit().await?;
+
+    let mut state = C::load_catalog_snapshot_state(warehouse_id, catalog_state.clone())
+        .await?
+        .ok_or_else(|| {
+            ErrorModel::not_found(
+                format!("Warehouse {warehouse_id} not found"),
+                "WarehouseNotFound",
+                None,
+            )
+        })?;
+    state.access = Some(
+        load_catalog_snapshot_access::<C, A>(
+            warehouse_id,
+            &state,
+            authorizer,
+            catalog_state.clone(),
+        )
+        .await?,
+    );
+    let namespace_count = i64::try_from(state.namespaces.len()).unwrap_or(i64::MAX);
+    let tabular_count = i64::try_from(state.tabulars.len()).unwrap_or(i64::MAX);
+
+    let secret = maybe_get_secret(warehouse.storage_secret_id, secret_state).await?;
+    let file_io = warehouse.storage_profile.file_io(secret.as_ref()).await?;
+
+    let parent = differential_parent(&recent_snapshots, config.max_differential_snapshots);
+    let content = match parent {
+        Some(parent) => {
+            differential_content(parent, &state, &file_io, &warehouse.storage_profile).await
+        }
+        None => None,
+    }
+    .unwrap_or(CatalogSnapshotContent::Full { state });
+
+    let snapshot_file = CatalogSnapshotFile::new(CatalogSnapshotManifest {
+        format_version: MANIFEST_FORMAT_VERSION,
+        snapshot_id: Uuid::now_v7(),
+        warehouse_id,
+        created_at: Utc::now(),
+        content,
+    })?;
+    let manifest = &snapshot_file.manifest;
+    let location = catalog_snapshot_location(&warehouse.storage_profile, manifest.snapshot_id)?;
+    write_metadata_file(&location, &snapshot_file, CompressionCodec::None, &file_io).await?;
+
+    let (base_snapshot_id, parent_snapshot_id) = match &manifest.content {
+        CatalogSnapshotContent::Full { .. } => (None, None),
+        CatalogSnapshotContent::Differential {
+            base_snapshot_id,
+            parent_snapshot_id,
+            ..
+        } => (Some(*base_snapshot_id), Some(*parent_snapshot_id)),
+    };
+    let snapshot = CatalogSnapshot {
+        snapshot_id: manifest.snapshot_id,
+        warehouse_id,
+        kind: manifest.content.kind(),
+        base_snapshot_id,
+        parent_snapshot_id,
+        location: location.to_string(),
+        checksum: snapshot_file.checksum.clone(),
+        namespace_count,
+        tabular_count,
+        task_id: Some(*task.task_id),
+        created_at: manifest.created_at,
+    };
+    let summary = serde_json::to_string(&snapshot).map_err(|e| {
+        ErrorModel::internal(
+            "Failed to serialize catalog snapshot",
+            "CatalogSnapshotSerializationError",
+            Some(Box::new(e)),
+        )
+    })?;
+
+    let mut trx = C::Transaction::begin_write(catalog_state).await?;
+    C::create_catalog_snapshot(&snapshot, trx.transaction()).await?;
+    let expired = C::delete_expired_catalog_snapshots(
+        warehouse_id,
+        i64::from(config.max_retained_snapshots),
+        trx.transaction(),
+    )
+    .await?;
+    C::retrying_record_task_success(task.task_id, Some(&summary), trx.transaction()).await;
+    maybe_queue_catalog_snapshot_export::<C>(warehouse_id, Some(task.task_id), trx.transaction())
+        .await?;
+    trx.commit().await?;
+
+    let expired_locations = expired
+        .into_iter()
+        .map(|snapshot| snapshot.location)
+        .collect::<Vec<_>>();
+    let (_, failed) = delete_files(&file_io, &expired_locations).await;
+    if failed > 0 {
+        tracing::warn!("Failed to delete {failed} manifests of expired catalog snapshots");
+    }
+
+    Ok(snapshot)
+}
+
+/// The snapshot a new differential snapshot is based on, or `None` if the next
+/// snapshot must be a full one. `recent_snapshots` are ordered newest first.
+fn differential_parent(
+    recent_snapshots: &[CatalogSnapshot],
+    max_differential_snapshots: u32,
+) -> Option<&CatalogSnapshot> {
+    let differentials_since_full = recent_snapshots
+        .iter()
+        .take_while(|s| s.kind == CatalogSnapshotKind::Differential)
+        .count();
+    let max_differential_snapshots = usize::try_from(max_differential_snapshots).ok()?;
+    if differentials_since_full >= max_differential_snapshots {
+        return None;
+    }
+    recent_snapshots.first()
+}
+
+/// Changes since the parent snapshot. Returns `None` if the state of the parent
+/// can't be restored, in which case a full snapshot is exported instead.
+async fn differential_content(
+    parent: &CatalogSnapshot,
+    state: &CatalogSnapshotState,
+    file_io: &StorageIo,
+    storage_profile: &StorageProfile,
+) -> Option<CatalogSnapshotContent> {
+    let replayed = match Location::from_str(&parent.location) {
+        Ok(location) => match read_catalog_snapshot(file_io, &location).await {
+            Ok(file) => replay_catalog_snapshot(file, file_io, storage_profile).await,
+            Err(e) => Err(e),
+        },
+        Err(e) => Err(ErrorModel::internal(
+            format!(
+                "Location '{}' of catalog snapshot is invalid",
+                parent.location
+            ),
+            "InvalidCatalogSnapshotLocation",
+            Some(Box::new(e)),
+        )
+        .into()),
+    };
+    match replayed {
+        Ok(replayed) if replayed.checksums_valid => Some(CatalogSnapshotContent::Differential {
+            base_snapshot_id: replayed.replayed_snapshots[0],
+            parent_snapshot_id: parent.snapshot_id,
+            changes: CatalogSnapshotChanges::between(&replayed.state, state),
+        }),
+        Ok(_) => {
+            tracing::warn!(
+                "Manifests of catalog snapshot {} are corrupted, exporting a full snapshot",
+                parent.snapshot_id
+            );
+            None
+        }
+        Err(e) => {
+            tracing::warn!(
+                "Failed to restore catalog snapshot {}, exporting a full snapshot: {}",
+                parent.snapshot_id,
+                e.error
+            );
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state() -> CatalogSnapshotState {
+        CatalogSnapshotState {
+            warehouse: CatalogSnapshotWarehouse {
+                name: "wh".to_string(),
+                storage_profile: serde_json::json!({"type": "s3", "bucket": "b"}),
+                tabular_expiration_seconds: Some(3600),
+                protected: false,
+                disabled_endpoint_groups: vec![],
+            },
+            task_queue_configs: BTreeMap::from([(
+                "tabular_purge".to_string(),
+                serde_json::json!({}),
+            )]),
+            namespaces: vec![CatalogSnapshotNamespace {
+                namespace_id: Uuid::from_u128(1),
+                name: vec!["ns".to_string()],
+                properties: BTreeMap::from([("owner".to_string(), "me".to_string())]),
+                protected: false,
+                deleted: false,
+            }],
+            tabulars: vec![CatalogSnapshotTabular {
+                tabular_id: Uuid::from_u128(2),
+                typ: TabularType::Table,
+                namespace: vec!["ns".to_string()],
+                name: "tab".to_string(),
+                metadata_location: Some("s3://b/ns/tab/metadata/00001.metadata.json".to_string()),
+                protected: false,
+                deleted: false,
+            }],
+            access: None,
+        }
+    }
+
+    fn access() -> CatalogSnapshotAccess {
+        let user_id = UserId::new_unchecked("oidc", "alice");
+        CatalogSnapshotAccess {
+            grants: vec![
+                ObjectGrant {
+                    object: GrantObject::Namespace(Uuid::from_u128(1)),
+                    relation: "ownership".to_string(),
+                    principal: GrantPrincipal::User(user_id.clone()),
+                },
+                ObjectGrant {
+                    object: GrantObject::Table(Uuid::from_u128(2)),
+                    relation: "select".to_string(),
+                    principal: GrantPrincipal::Role(crate::service::RoleId::new(Uuid::from_u128(
+                        5,
+                    ))),
+                },
+            ],
+            users: vec![CatalogSnapshotUser {
+                id: user_id,
+                name: "Alice".to_string(),
+                email: None,
+                user_type: UserType::Human,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_checksum_survives_round_trip() {
+        let file = CatalogSnapshotFile::new(CatalogSnapshotManifest {
+            format_version: MANIFEST_FORMAT_VERSION,
+            snapshot_id: Uuid::now_v7(),
+            warehouse_id: WarehouseId::new_random(),
+            created_at: Utc::now(),
+            content: CatalogSnapshotContent::Full { state: state() },
+        })
+        .unwrap();
+        let serialized = serde_json::to_vec(&file).unwrap();
+        let mut parsed: CatalogSnapshotFile = serde_json::from_slice(&serialized).unwrap();
+        assert_eq!(parsed, file);
+        assert!(parsed.checksum_valid().unwrap());
+
+        let CatalogSnapshotContent::Full { state } = &mut parsed.manifest.content else {
+            panic!("Expected a full snapshot");
+        };
+        state.tabulars[0].metadata_location = None;
+        assert!(!parsed.checksum_valid().unwrap());
+    }
+
+    #[test]
+    fn test_changes_between_states_replay_onto_previous_state() {
+        let previous = state();
+        let mut current = state();
+        current.warehouse.protected = true;
+        current.task_queue_configs.clear();
+        current
+            .task_queue_configs
+            .insert("tabular_expiration".to_string(), serde_json::json!({}));
+        current.namespaces.insert(
+            0,
+            CatalogSnapshotNamespace {
+                namespace_id: Uuid::from_u128(0),
+                name: vec!["ns0".to_string()],
+                ..current.namespaces[0].clone()
+            },
+        );
+        current.tabulars[0].deleted = true;
+        current.tabulars.push(CatalogSnapshotTabular {
+            tabular_id: Uuid::from_u128(4),
+            typ: TabularType::View,
+            name: "view".to_string(),
+            ..current.tabulars[0].clone()
+        });
+
+        let changes = CatalogSnapshotChanges::between(&previous, &current);
+        assert!(changes.warehouse.is_some());
+        assert_eq!(
+            changes.removed_task_queue_configs,
+            vec!["tabular_purge".to_string()]
+        );
+        assert_eq!(changes.upserted_namespaces.len(), 1);
+        assert!(changes.removed_namespaces.is_empty());
+        assert_eq!(changes.upserted_tabulars.len(), 2);
+
+        let mut replayed = previous.clone();
+        changes.apply(&mut replayed);
+        assert_eq!(replayed, current);
+
+        let changes = CatalogSnapshotChanges::between(&current, &previous);
+        assert_eq!(changes.removed_namespaces, vec![Uuid::from_u128(0)]);
+        assert_eq!(changes.removed_tabulars, vec![Uuid::from_u128(4)]);
+        let mut replayed = current.clone();
+        changes.apply(&mut replayed);
+        assert_eq!(replayed, previous);
+
+        assert_eq!(
+            CatalogSnapshotChanges::between(&previous, &previous),
+            CatalogSnapshotChanges::default()
+        );
+    }
+
+    #[test]
+    fn test_changes_replay_access() {
+        // Snapshots exported before grants were included
+        let previous = state();
+        let mut current = state();
+        current.access = Some(access());
+
+        let changes = CatalogSnapshotChanges::between(&previous, &current);
+        assert_eq!(changes.access, current.access);
+        let mut replayed = previous.clone();
+        changes.apply(&mut replayed);
+        assert_eq!(replayed, current);
+
+        let mut next = current.clone();
+        next.access.as_mut().unwrap().grants.pop();
+        let changes = CatalogSnapshotChanges::between(&current, &next);
+        let mut replayed = current.clone();
+        changes.apply(&mut replayed);
+        assert_eq!(replayed, next);
+        assert!(CatalogSnapshotChanges::between(&next, &next)
+            .access
+            .is_none());
+    }
+
+    #[test]
+    fn test_state_without_access_is_serialized_unchanged() {
+        let file = CatalogSnapshotFile::new(CatalogSnapshotManifest {
+            format_version: MANIFEST_FORMAT_VERSION,
+            snapshot_id: Uuid::now_v7(),
+            warehouse_id: WarehouseId::new_random(),
+            created_at: Utc::now(),
+            content: CatalogSnapshotContent::Full { state: state() },
+        })
+        .unwrap();
+        let serialized = serde_json::to_value(&file).unwrap();
+        assert!(serialized["manifest"]["state"].get("access").is_none());
+    }
+
+    #[test]
+    fn test_diff_access() {
+        let mut expected = state();
+        expected.access = Some(access());
+        // Grants are not compared if the current state doesn't include them
+        assert!(diff_catalog_state(&expected, &state()).is_empty());
+
+        let mut actual = expected.clone();
+        let actual_access = actual.access.as_mut().unwrap();
+        actual_access.grants[1].relation = "modify".to_string();
+        actual_access.users[0].name = "Alice Smith".to_string();
+
+        let summary = diff_catalog_state(&expected, &actual)
+            .into_iter()
+            .map(|d| (d.entity_type, d.name, d.kind, d.attribute))
+            .collect::<Vec<_>>();
+        let table = Uuid::from_u128(2);
+        let role = Uuid::from_u128(5);
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    CatalogSnapshotEntityType::Grant,
+                    format!("select on table {table} to role {role}"),
+                    CatalogSnapshotDifferenceKind::Missing,
+                    None
+                ),
+                (
+                    CatalogSnapshotEntityType::Grant,
+                    format!("modify on table {table} to role {role}"),
+                    CatalogSnapshotDifferenceKind::Unexpected,
+                    None
+                ),
+                (
+                    CatalogSnapshotEntityType::User,
+                    "oidc~alice".to_string(),
+                    CatalogSnapshotDifferenceKind::Changed,
+                    Some("name".to_string())
+                ),
+            ]
+        );
+    }
+
+    fn snapshot(kind: CatalogSnapshotKind) -> CatalogSnapshot {
+        CatalogSnapshot {
+            snapshot_id: Uuid::now_v7(),
+            warehouse_id: WarehouseId::new_random(),
+            kind,
+            base_snapshot_id: None,
+            parent_snapshot_id: None,
+            location: "s3://b/_lakekeeper/catalog-snapshots/s.json".to_string(),
+            checksum: "abc".to_string(),
+            namespace_count: 1,
+            tabular_count: 1,
+            task_id: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_differential_parent() {
+        assert!(differential_parent(&[], 6).is_none());
+
+        let recent = vec![
+            snapshot(CatalogSnapshotKind::Differential),
+            snapshot(CatalogSnapshotKind::Full),
+        ];
+        assert_eq!(
+            differential_parent(&recent, 6).map(|s| s.snapshot_id),
+            Some(recent[0].snapshot_id)
+        );
+        assert!(differential_parent(&recent, 1).is_none());
+        assert!(differential_parent(&recent, 0).is_none());
+    }
+
+    #[test]
+    fn test_diff_catalog_state() {
+        let expected = state();
+        assert!(diff_catalog_state(&expected, &expected).is_empty());
+
+        let mut actual = state();
+        actual.task_queue_configs.clear();
+        actual.namespaces[0]
+            .properties
+            .insert("owner".to_string(), "you".to_string());
+        actual.tabulars[0].metadata_location =
+            Some("s3://b/ns/tab/metadata/00002.metadata.json".to_string());
+        actual.tabulars.push(CatalogSnapshotTabular {
+            tabular_id: Uuid::from_u128(3),
+            typ: TabularType::View,
+            name: "view".to_string(),
+            ..actual.tabulars[0].clone()
+        });
+
+        let differences = diff_catalog_state(&expected, &actual);
+        let summary = differences
+            .iter()
+            .map(|d| {
+                (
+                    d.entity_type,
+                    d.name.as_str(),
+                    d.kind,
+                    d.attribute.as_deref(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    CatalogSnapshotEntityType::TaskQueueConfig,
+                    "tabular_purge",
+                    CatalogSnapshotDifferenceKind::Missing,
+                    None
+                ),
+                (
+                    CatalogSnapshotEntityType::Namespace,
+                    "ns",
+                    CatalogSnapshotDifferenceKind::Changed,
+                    Some("properties")
+                ),
+                (
+                    CatalogSnapshotEntityType::Table,
+                    "ns.tab",
+                    CatalogSnapshotDifferenceKind::Changed,
+                    Some("metadata-location")
+                ),
+                (
+                    CatalogSnapshotEntityType::View,
+                    "ns.view",
+                    CatalogSnapshotDifferenceKind::Unexpected,
+                    None
+                ),
+            ]
+        );
+        assert_eq!(
+            differences[2].expected,
+            Some(serde_json::json!(
+                "s3://b/ns/tab/metadata/00001.metadata.json"
+            ))
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+
+use axum_prometheus::metrics;
+use chrono::Utc;
+
+use super::TaskQueueStats;
+use crate::service::{Catalog, Result};
+
+pub const METRICS_REPORTER_NAME: &str = "task_queue_metrics";
+/// Window in which finished attempts are counted for failure statistics.
+pub const STATS_WINDOW: chrono::Duration = chrono::Duration::seconds(3600);
+const REPORT_INTERVAL: Duration = Duration::from_secs(30);
+
+const QUEUE_DEPTH: &str = "lakekeeper_task_queue_depth";
+const QUEUE_DUE: &str = "lakekeeper_task_queue_due_tasks";
+const QUEUE_IN_FLIGHT: &str = "lakekeeper_task_queue_in_flight_tasks";
+const QUEUE_OLDEST_DUE_TASK_AGE: &str = "lakekeeper_task_queue_oldest_due_task_age_seconds";
+const QUEUE_FAILED_ATTEMPTS: &str = "lakekeeper_task_queue_failed_attempts_last_hour";
+const QUEUE_SUCCEEDED_ATTEMPTS: &str = "lakekeeper_task_queue_succeeded_attempts_last_hour";
+const QUEUE_DEAD_LETTERED: &str = "lakekeeper_task_queue_dead_lettered_tasks";
+const REPORT_ERRORS: &str = "lakekeeper_task_queue_metrics_errors_total";
+
+/// Statistics of all `queue_names` and of all other queues that hold tasks,
+/// ordered by queue name. Registered queues without any tasks are reported as empty.
+///
+/// # Errors
+/// Fails if the statistics cannot be loaded from the catalog.
+pub async fn task_queue_stats<C: Catalog>(
+    queue_names: &[&str],
+    catalog_state: C::State,
+) -> Result<Vec<TaskQueueStats>> {
+    let mut stats = queue_names
+        .iter()
+        .map(|name| ((*name).to_string(), TaskQueueStats::empty(*name)))
+        .collect::<BTreeMap<_, _>>();
+    for queue in C::get_task_queue_stats(STATS_WINDOW, catalog_state).await? {
+        stats.insert(queue.queue_name.clone(), queue);
+    }
+    Ok(stats.into_values().collect())
+}
+
+/// Infinitely running worker that publishes the statistics of all task queues as
+/// Prometheus gauges. The statistics are server-wide, so every instance reports the same values.
+pub async fn task_queue_metrics_worker<C: Catalog>(
+    catalog_state: C::State,
+    queue_names: Arc<Vec<&'static str>>,
+) {
+    describe_metrics();
+    loop {
+        match task_queue_stats::<C>(&queue_names, catalog_state.clone()).await {
+            Ok(stats) => report(&stats),
+            Err(e) => {
+                metrics::counter!(REPORT_ERRORS).increment(1);
+                tracing::error!(?e, "Failed to collect task queue statistics: {}", e.error);
+            }
+        }
+        tokio::time::sleep(REPORT_INTERVAL).await;
+    }
+}
+
+fn describe_metrics() {
+    metrics::describe_gauge!(QUEUE_DEPTH, "Tasks waiting to be picked up");
+    metrics::describe_gauge!(QUEUE_DUE, "Scheduled tasks that are due");
+    metrics::describe_gauge!(QUEUE_IN_FLIGHT, "Tasks currently being processed");
+    metrics::describe_gauge!(
+        QUEUE_OLDEST_DUE_TASK_AGE,
+        metrics::Unit::Seconds,
+        "Time the oldest due task has been waiting for a worker"
+    );
+    metrics::describe_gauge!(
+        QUEUE_FAILED_ATTEMPTS,
+        "Failed task attempts within the last hour"
+    );
+    metrics::describe_gauge!(
+        QUEUE_SUCCEEDED_ATTEMPTS,
+        "Successful task attempts within the last hour"
+    );
+    metrics::describe_gauge!(QUEUE_DEAD_LETTERED, "Tasks in the dead-letter queue");
+    metrics::describe_counter!(REPORT_ERRORS, "Failures to collect task queue statistics");
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn report(stats: &[TaskQueueStats]) {
+    let now = Utc::now();
+    for queue in stats {
+        let labels = [("queue", queue.queue_name.clone())];
+        metrics::gauge!(QUEUE_DEPTH, &labels).set(queue.scheduled as f64);
+        metrics::gauge!(QUEUE_DUE, &labels).set(queue.due as f64);
+        metrics::gauge!(QUEUE_IN_FLIGHT, &labels).set(queue.in_flight as f64);
+        metrics::gauge!(QUEUE_OLDEST_DUE_TASK_AGE, &labels)
+            .set(queue.oldest_due_task_age_seconds(now).unwrap_or_default() as f64);
+        metrics::gauge!(QUEUE_FAILED_ATTEMPTS, &labels).set(queue.failed as f64);
+        metrics::gauge!(QUEUE_SUCCEEDED_ATTEMPTS, &labels).set(queue.succeeded as f64);
+        metrics::gauge!(QUEUE_DEAD_LETTERED, &labels).set(queue.dead_lettered as f64);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_failure_rate_and_age() {
+        let now = Utc::now();
+        let stats = TaskQueueStats {
+            oldest_due_at: Some(now - chrono::Duration::seconds(90)),
+            failed: 1,
+            succeeded: 3,
+            ..TaskQueueStats::empty("tabular_expiration")
+        };
+        assert_eq!(stats.failure_rate(), Some(0.25));
+        assert_eq!(stats.oldest_due_task_age_seconds(now), Some(90));
+
+        let empty = TaskQueueStats::empty("tabular_expiration");
+        assert_eq!(empty.failure_rate(), None);
+        assert_eq!(empty.oldest_due_task_age_seconds(now), None);
+    }
+}
@@ -3,25 +3,44 @@ use std::{
     collections::HashMap,
     fmt::{Debug, Formatter},
     ops::Deref,
+    str::FromStr as _,
     sync::{Arc, LazyLock},
     time::Duration,
 };
 
 use chrono::Utc;
-use futures::future::BoxFuture;
+use futures::{future::BoxFuture, StreamExt as _};
+use iceberg_ext::configs::Location;
 use serde::{de::DeserializeOwned, Serialize};
 use strum::EnumIter;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
 use super::{authz::Authorizer, Transaction, WarehouseId};
-use crate::service::{
-    task_queue::{
-        tabular_expiration_queue::ExpirationQueueConfig, tabular_purge_queue::PurgeQueueConfig,
+use crate::{
+    catalog::io::{delete_file, StorageIo},
+    service::{
+        server_events::{emit_server_event, ServerEvent, ServerEventType},
+        task_queue::{
+            catalog_import_queue::CatalogImportQueueConfig,
+            catalog_snapshot_export_queue::CatalogSnapshotExportQueueConfig,
+            orphan_file_cleanup_queue::OrphanFileCleanupQueueConfig,
+            pii_detection_queue::PiiDetectionQueueConfig,
+            snapshot_expiration_queue::SnapshotExpirationQueueConfig,
+            tabular_expiration_queue::ExpirationQueueConfig, tabular_purge_queue::PurgeQueueConfig,
+        },
+        Catalog, SecretStore,
     },
-    Catalog, SecretStore,
+    CONFIG,
 };
 
+pub mod catalog_import_queue;
+pub mod catalog_snapshot_export_queue;
+pub mod metrics;
+pub mod orphan_file_cleanup_queue;
+pub mod pii_detection_queue;
+pub mod schedule;
+pub mod snapshot_expiration_queue;
 pub mod tabular_expiration_queue;
 pub mod tabular_purge_queue;
 
@@ -34,12 +53,24 @@ pub static BUILT_IN_API_CONFIGS: LazyLock<Vec<QueueApiConfig>> = LazyLock::new(|
     vec![
         tabular_expiration_queue::API_CONFIG.clone(),
         tabular_purge_queue::API_CONFIG.clone(),
+        pii_detection_queue::API_CONFIG.clone(),
+        snapshot_expiration_queue::API_CONFIG.clone(),
+        orphan_file_cleanup_queue::API_CONFIG.clone(),
+        catalog_snapshot_export_queue::API_CONFIG.clone(),
+        catalog_import_queue::API_CONFIG.clone(),
     ]
 });
 
 /// Infinitely running task worker loop function that polls tasks from a queue and
 /// processes.
 pub type TaskQueueWorker = Arc<dyn Fn() -> BoxFuture<'static, ()> + Send + Sync + 'static>;
+/// Infinitely running worker loop function that fires task schedules.
+/// Receives the names of all registered queues.
+pub type TaskScheduleWorker =
+    Arc<dyn Fn(Arc<Vec<&'static str>>) -> BoxFuture<'static, ()> + Send + Sync + 'static>;
+/// Infinitely running worker loop function that reports task queue statistics.
+/// Receives the names of all registered queues.
+pub type TaskQueueMetricsWorker = TaskScheduleWorker;
 type ValidatorFn = Arc<dyn Fn(serde_json::Value) -> serde_json::Result<()> + Send + Sync>;
 
 /// Warehouse specific configuration for a task queue.
@@ -129,6 +160,19 @@ pub struct TaskQueueRegistry {
     registered_queues: HashMap<&'static str, RegisteredQueue>,
     // Mapping of queue names to their worker configuration
     task_workers: HashMap<&'static str, RegisteredTaskQueueWorker>,
+    schedule_worker: Option<RegisteredQueueNamesWorker>,
+    metrics_worker: Option<RegisteredQueueNamesWorker>,
+}
+
+#[derive(Clone)]
+struct RegisteredQueueNamesWorker(TaskScheduleWorker);
+
+impl Debug for RegisteredQueueNamesWorker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("RegisteredQueueNamesWorker")
+            .field(&"Fn(...)")
+            .finish()
+    }
 }
 
 impl Default for TaskQueueRegistry {
@@ -163,6 +207,8 @@ impl TaskQueueRegistry {
         Self {
             registered_queues: HashMap::new(),
             task_workers: HashMap::new(),
+            schedule_worker: None,
+            metrics_worker: None,
         }
     }
 
@@ -200,6 +246,18 @@ impl TaskQueueRegistry {
         self
     }
 
+    /// Registers the worker that fires task schedules. A single scheduler runs per instance.
+    pub fn register_schedule_worker(&mut self, worker_fn: TaskScheduleWorker) -> &mut Self {
+        self.schedule_worker = Some(RegisteredQueueNamesWorker(worker_fn));
+        self
+    }
+
+    /// Registers the worker that publishes task queue statistics as Prometheus metrics.
+    pub fn register_metrics_worker(&mut self, worker_fn: TaskQueueMetricsWorker) -> &mut Self {
+        self.metrics_worker = Some(RegisteredQueueNamesWorker(worker_fn));
+        self
+    }
+
     pub fn register_built_in_queues<C: Catalog, S: SecretStore, A: Authorizer>(
         &mut self,
         catalog_state: C::State,
@@ -208,10 +266,33 @@ impl TaskQueueRegistry {
         poll_interval: Duration,
     ) -> &mut Self {
         let catalog_state_clone = catalog_state.clone();
+        let authorizer_clone = authorizer.clone();
+        let secret_store_clone = secret_store.clone();
+        self.register_queue::<CatalogImportQueueConfig>(QueueRegistration {
+            queue_name: catalog_import_queue::QUEUE_NAME,
+            worker_fn: Arc::new(move || {
+                let catalog_state_clone = catalog_state_clone.clone();
+                let authorizer = authorizer_clone.clone();
+                let secret_store = secret_store_clone.clone();
+                Box::pin(async move {
+                    catalog_import_queue::catalog_import_worker::<C, A, S>(
+                        catalog_state_clone.clone(),
+                        authorizer.clone(),
+                        secret_store.clone(),
+                        poll_interval,
+                    )
+                    .await;
+                })
+            }),
+            num_workers: 1,
+        });
+
+        let catalog_state_clone = catalog_state.clone();
+        let authorizer_clone = authorizer.clone();
         self.register_queue::<ExpirationQueueConfig>(QueueRegistration {
             queue_name: tabular_expiration_queue::QUEUE_NAME,
             worker_fn: Arc::new(move || {
-                let authorizer = authorizer.clone();
+                let authorizer = authorizer_clone.clone();
                 let catalog_state_clone = catalog_state_clone.clone();
                 Box::pin({
                     async move {
@@ -227,6 +308,115 @@ impl TaskQueueRegistry {
             num_workers: 2,
         });
 
+        if CONFIG.enable_pii_detection {
+            let catalog_state_clone = catalog_state.clone();
+            let secret_store = secret_store.clone();
+            self.register_queue::<PiiDetectionQueueConfig>(QueueRegistration {
+                queue_name: pii_detection_queue::QUEUE_NAME,
+                worker_fn: Arc::new(move || {
+                    let catalog_state_clone = catalog_state_clone.clone();
+                    let secret_store = secret_store.clone();
+                    Box::pin(async move {
+                        pii_detection_queue::pii_detection_worker::<C, S>(
+                            catalog_state_clone.clone(),
+                            secret_store.clone(),
+                            poll_interval,
+                        )
+                        .await;
+                    })
+                }),
+                num_workers: 1,
+            });
+        }
+
+        if CONFIG.enable_snapshot_expiration {
+            let catalog_state_clone = catalog_state.clone();
+            let secret_store = secret_store.clone();
+            self.register_queue::<SnapshotExpirationQueueConfig>(QueueRegistration {
+                queue_name: snapshot_expiration_queue::QUEUE_NAME,
+                worker_fn: Arc::new(move || {
+                    let catalog_state_clone = catalog_state_clone.clone();
+                    let secret_store = secret_store.clone();
+                    Box::pin(async move {
+                        snapshot_expiration_queue::snapshot_expiration_worker::<C, S>(
+                            catalog_state_clone.clone(),
+                            secret_store.clone(),
+                            poll_interval,
+                        )
+                        .await;
+                    })
+                }),
+                num_workers: 1,
+            });
+        }
+
+        if CONFIG.enable_orphan_file_cleanup {
+            let catalog_state_clone = catalog_state.clone();
+            let secret_store = secret_store.clone();
+            self.register_queue::<OrphanFileCleanupQueueConfig>(QueueRegistration {
+                queue_name: orphan_file_cleanup_queue::QUEUE_NAME,
+                worker_fn: Arc::new(move || {
+                    let catalog_state_clone = catalog_state_clone.clone();
+                    let secret_store = secret_store.clone();
+                    Box::pin(async move {
+                        orphan_file_cleanup_queue::orphan_file_cleanup_worker::<C, S>(
+                            catalog_state_clone.clone(),
+                            secret_store.clone(),
+                            poll_interval,
+                        )
+                        .await;
+                    })
+                }),
+                num_workers: 1,
+            });
+        }
+
+        if CONFIG.enable_catalog_snapshot_export {
+            let catalog_state_clone = catalog_state.clone();
+            let authorizer = authorizer.clone();
+            let secret_store = secret_store.clone();
+            self.register_queue::<CatalogSnapshotExportQueueConfig>(QueueRegistration {
+                queue_name: catalog_snapshot_export_queue::QUEUE_NAME,
+                worker_fn: Arc::new(move || {
+                    let catalog_state_clone = catalog_state_clone.clone();
+                    let authorizer = authorizer.clone();
+                    let secret_store = secret_store.clone();
+                    Box::pin(async move {
+                        catalog_snapshot_export_queue::catalog_snapshot_export_worker::<C, A, S>(
+                            catalog_state_clone.clone(),
+                            authorizer.clone(),
+                            secret_store.clone(),
+                            poll_interval,
+                        )
+                        .await;
+                    })
+                }),
+                num_workers: 1,
+            });
+        }
+
+        let catalog_state_clone = catalog_state.clone();
+        self.register_schedule_worker(Arc::new(move |queue_names| {
+            let catalog_state_clone = catalog_state_clone.clone();
+            Box::pin(async move {
+                schedule::task_schedule_worker::<C>(
+                    catalog_state_clone.clone(),
+                    queue_names,
+                    poll_interval,
+                )
+                .await;
+            })
+        }));
+
+        let catalog_state_clone = catalog_state.clone();
+        self.register_metrics_worker(Arc::new(move |queue_names| {
+            let catalog_state_clone = catalog_state_clone.clone();
+            Box::pin(async move {
+                metrics::task_queue_metrics_worker::<C>(catalog_state_clone.clone(), queue_names)
+                    .await;
+            })
+        }));
+
         self.register_queue::<PurgeQueueConfig>(QueueRegistration {
             queue_name: tabular_purge_queue::QUEUE_NAME,
             worker_fn: Arc::new(move || {
@@ -260,7 +450,20 @@ impl TaskQueueRegistry {
     pub fn task_queues_runner(&self) -> TaskQueuesRunner {
         let mut registered_task_queues = HashMap::new();
 
+        let disabled_workers = &CONFIG.disabled_task_queue_workers;
+        for name in disabled_workers {
+            if !self.registered_queues.contains_key(name.as_str()) {
+                tracing::warn!("Cannot disable workers of unknown task queue '{name}'");
+            }
+        }
+
         for name in self.registered_queues.keys() {
+            if disabled_workers.iter().any(|disabled| disabled == *name) {
+                tracing::info!(
+                    "Built-in workers of task queue '{name}' are disabled. Tasks must be processed by external workers."
+                );
+                continue;
+            }
             if let Some(worker) = self.task_workers.get(name) {
                 registered_task_queues.insert(
                     *name,
@@ -272,6 +475,24 @@ impl TaskQueueRegistry {
             }
         }
 
+        let queue_names = Arc::new(self.registered_queues.keys().copied().collect::<Vec<_>>());
+        for (name, worker) in [
+            (schedule::SCHEDULER_NAME, &self.schedule_worker),
+            (metrics::METRICS_REPORTER_NAME, &self.metrics_worker),
+        ] {
+            if let Some(RegisteredQueueNamesWorker(worker)) = worker {
+                let queue_names = Arc::clone(&queue_names);
+                let worker = Arc::clone(worker);
+                registered_task_queues.insert(
+                    name,
+                    QueueWorkerConfig {
+                        worker_fn: Arc::new(move || worker(Arc::clone(&queue_names))),
+                        num_workers: 1,
+                    },
+                );
+            }
+        }
+
         TaskQueuesRunner {
             registered_queues: Arc::new(registered_task_queues),
         }
@@ -335,19 +556,35 @@ impl TaskQueuesRunner {
             };
 
             // Log the result
-            match result {
-                Ok(()) => tracing::error!(
-                    "Task queue {} worker {} finished. {log_msg_suffix}",
-                    worker.queue_name,
-                    worker.worker_id
-                ),
-                Err(e) => tracing::error!(
-                    ?e,
-                    "Task queue {} worker {} panicked: {e}. {log_msg_suffix}",
-                    worker.queue_name,
-                    worker.worker_id
-                ),
-            }
+            let error = match result {
+                Ok(()) => {
+                    tracing::error!(
+                        "Task queue {} worker {} finished. {log_msg_suffix}",
+                        worker.queue_name,
+                        worker.worker_id
+                    );
+                    None
+                }
+                Err(e) => {
+                    tracing::error!(
+                        ?e,
+                        "Task queue {} worker {} panicked: {e}. {log_msg_suffix}",
+                        worker.queue_name,
+                        worker.worker_id
+                    );
+                    Some(e.to_string())
+                }
+            };
+            emit_server_event(ServerEvent::new(
+                ServerEventType::TaskWorkerCrashed,
+                None,
+                serde_json::json!({
+                    "queue-name": worker.queue_name,
+                    "worker-id": worker.worker_id,
+                    "error": error,
+                    "restarted": restart_workers,
+                }),
+            ));
 
             // Restart the worker
             if restart_workers {
@@ -462,13 +699,14 @@ pub struct TaskMetadata {
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum EntityId {
     Tabular(Uuid),
+    Warehouse(Uuid),
 }
 
 impl EntityId {
     #[must_use]
     pub fn to_uuid(&self) -> Uuid {
         match self {
-            EntityId::Tabular(id) => *id,
+            EntityId::Tabular(id) | EntityId::Warehouse(id) => *id,
         }
     }
 }
@@ -527,7 +765,8 @@ impl Task {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, EnumIter)]
+#[derive(Debug, Copy, Clone, PartialEq, EnumIter, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
 #[cfg_attr(feature = "sqlx-postgres", derive(sqlx::Type))]
 #[cfg_attr(
     feature = "sqlx-postgres",
@@ -539,7 +778,8 @@ pub enum TaskStatus {
     ShouldStop,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
 #[cfg_attr(feature = "sqlx-postgres", derive(sqlx::Type))]
 #[cfg_attr(
     feature = "sqlx-postgres",
@@ -551,6 +791,77 @@ pub enum TaskOutcome {
     Success,
 }
 
+/// A scheduled or running task of an entity.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TaskInfo {
+    pub task_id: Uuid,
+    pub status: TaskStatus,
+    /// The task is not picked up before this time
+    pub scheduled_for: chrono::DateTime<Utc>,
+    pub picked_up_at: Option<chrono::DateTime<Utc>>,
+    /// Number of times the task has been picked up
+    pub attempt: i32,
+}
+
+/// A finished attempt of a task.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskLogEntry {
+    pub task_id: TaskId,
+    pub attempt: i32,
+    pub status: TaskOutcome,
+    pub started_at: Option<chrono::DateTime<Utc>>,
+    pub duration_ms: Option<i64>,
+    /// Message recorded by the task handler: the error for failed attempts.
+    pub message: Option<String>,
+    pub created_at: chrono::DateTime<Utc>,
+}
+
+/// Server-wide statistics of a task queue.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TaskQueueStats {
+    pub queue_name: String,
+    /// Tasks waiting to be picked up, including tasks scheduled for the future.
+    pub scheduled: i64,
+    /// Scheduled tasks that are due.
+    pub due: i64,
+    /// Tasks that are currently running or should stop.
+    pub in_flight: i64,
+    /// Time the oldest due task became due.
+    pub oldest_due_at: Option<chrono::DateTime<Utc>>,
+    /// Failed attempts within the statistics window.
+    pub failed: i64,
+    /// Successful attempts within the statistics window.
+    pub succeeded: i64,
+    /// Tasks in the dead-letter queue.
+    pub dead_lettered: i64,
+}
+
+impl TaskQueueStats {
+    #[must_use]
+    pub fn empty(queue_name: impl Into<String>) -> Self {
+        Self {
+            queue_name: queue_name.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Seconds the oldest due task has been waiting for a worker at `now`.
+    #[must_use]
+    pub fn oldest_due_task_age_seconds(&self, now: chrono::DateTime<Utc>) -> Option<i64> {
+        self.oldest_due_at
+            .map(|due_at| (now - due_at).num_seconds().max(0))
+    }
+
+    /// Share of failed attempts among all attempts finished within the statistics window.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn failure_rate(&self) -> Option<f64> {
+        let finished = self.failed + self.succeeded;
+        (finished > 0).then(|| self.failed as f64 / finished as f64)
+    }
+}
+
 #[derive(Debug)]
 pub enum Status<'a> {
     Success(Option<&'a str>),
@@ -588,6 +899,28 @@ pub(crate) async fn record_error_with_catalog<C: Catalog>(
     });
 }
 
+/// Number of files deleted concurrently by maintenance tasks.
+const DELETE_CONCURRENCY: usize = 16;
+
+/// Deletes `files` and returns the number of deleted and failed files.
+/// Failures are logged but don't stop the deletion of the remaining files.
+pub(crate) async fn delete_files(file_io: &StorageIo, files: &[String]) -> (usize, usize) {
+    let outcomes = futures::stream::iter(files)
+        .map(|file| async move {
+            let location = Location::from_str(file).map_err(|e| {
+                tracing::warn!("Failed to parse location '{file}' of file to delete: {e:?}");
+            })?;
+            delete_file(file_io, &location).await.map_err(|e| {
+                tracing::warn!("Failed to delete file '{file}': {e:?}");
+            })
+        })
+        .buffer_unordered(DELETE_CONCURRENCY)
+        .collect::<Vec<_>>()
+        .await;
+    let deleted = outcomes.iter().filter(|o| o.is_ok()).count();
+    (deleted, outcomes.len() - deleted)
+}
+
 const fn valid_max_time_since_last_heartbeat(num: i64) -> chrono::Duration {
     assert!(
         num > 0,
@@ -0,0 +1,582 @@
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr as _,
+    sync::LazyLock,
+    time::Duration,
+};
+
+use futures::StreamExt as _;
+use iceberg::{io::FileIO, spec::TableMetadata};
+use iceberg_ext::{catalog::rest::ErrorModel, configs::Location};
+use rand::RngCore as _;
+use serde::{Deserialize, Serialize};
+use tracing::Instrument;
+use utoipa::{PartialSchema, ToSchema};
+
+use super::{
+    QueueApiConfig, QueueConfig, TaskCheckState, TaskId, DEFAULT_MAX_TIME_SINCE_LAST_HEARTBEAT,
+};
+use crate::{
+    api::Result,
+    catalog::{
+        io::{list_files, ListedFile},
+        maybe_get_secret,
+        tables::maybe_queue_orphan_file_cleanup,
+    },
+    service::{task_queue::Task, Catalog, LoadTableResponse, SecretStore, TableId, Transaction},
+};
+
+const PROPERTY_GC_ENABLED: &str = "gc.enabled";
+
+/// Number of files deleted between two heartbeats.
+const DELETE_BATCH_SIZE: usize = 1000;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(crate) struct OrphanFileCleanupPayload {
+    /// Overrides the `dry-run` setting of the queue configuration for this run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) dry_run: Option<bool>,
+}
+
+pub(crate) const QUEUE_NAME: &str = "orphan_file_cleanup";
+pub(crate) static API_CONFIG: LazyLock<QueueApiConfig> = LazyLock::new(|| QueueApiConfig {
+    queue_name: QUEUE_NAME,
+    utoipa_type_name: OrphanFileCleanupQueueConfig::name(),
+    utoipa_schema: OrphanFileCleanupQueueConfig::schema(),
+});
+
+/// Orphan file cleanup settings of a warehouse.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct OrphanFileCleanupQueueConfig {
+    /// Only files that were last modified longer ago than this are considered orphans.
+    /// Protects files of writes that are not committed yet. Default: 259200000 (3 days)
+    #[serde(default = "default_grace_period_ms")]
+    pub(crate) grace_period_ms: i64,
+    /// Only report orphans, don't delete them. Default: false
+    #[serde(default)]
+    pub(crate) dry_run: bool,
+    /// Maximum number of orphan paths stored in the result of a run. Default: 1000
+    #[serde(default = "default_max_reported_orphans")]
+    pub(crate) max_reported_orphans: usize,
+}
+
+fn default_grace_period_ms() -> i64 {
+    3 * 24 * 60 * 60 * 1000
+}
+
+fn default_max_reported_orphans() -> usize {
+    1000
+}
+
+impl Default for OrphanFileCleanupQueueConfig {
+    fn default() -> Self {
+        Self {
+            grace_period_ms: default_grace_period_ms(),
+            dry_run: false,
+            max_reported_orphans: default_max_reported_orphans(),
+        }
+    }
+}
+
+impl QueueConfig for OrphanFileCleanupQueueConfig {}
+
+/// Outcome of a single orphan file cleanup run of a table.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct OrphanFileCleanupResult {
+    /// Number of files found in the table location
+    pub listed_files: usize,
+    /// Number of files referenced by the table metadata
+    pub referenced_files: usize,
+    /// Number of unreferenced files older than the grace period
+    pub orphan_files: usize,
+    pub deleted_files: usize,
+    /// Number of orphans that could not be deleted. They are left in storage.
+    pub failed_deletes: usize,
+    /// Number of unreferenced files that are younger than the grace period
+    pub skipped_too_recent: usize,
+    /// Number of unreferenced files without a modification time. They are never deleted.
+    pub skipped_unknown_age: usize,
+    /// Orphans were only reported, not deleted.
+    pub dry_run: bool,
+    /// Paths of the orphans, limited to `max-reported-orphans` of the queue configuration
+    pub orphans: Vec<String>,
+    /// Not all orphans are contained in `orphans`.
+    pub orphans_truncated: bool,
+    /// The run was stopped before all orphans were found or deleted.
+    pub stopped: bool,
+    /// Reason why the table was not cleaned up, i.e. because `gc.enabled` is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skipped_reason: Option<String>,
+}
+
+pub(crate) async fn orphan_file_cleanup_worker<C: Catalog, S: SecretStore>(
+    catalog_state: C::State,
+    secret_state: S,
+    poll_interval: std::time::Duration,
+) {
+    loop {
+        let task = match C::pick_new_task(
+            QUEUE_NAME,
+            DEFAULT_MAX_TIME_SINCE_LAST_HEARTBEAT,
+            catalog_state.clone(),
+        )
+        .await
+        {
+            Ok(task) => task,
+            Err(err) => {
+                tracing::error!("Failed to fetch orphan file cleanup task: {:?}", err);
+                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                continue;
+            }
+        };
+
+        let Some(task) = task else {
+            let jitter = { rand::rng().next_u64() % 500 };
+            tokio::time::sleep(poll_interval + Duration::from_millis(jitter)).await;
+            continue;
+        };
+        let config = match task.task_config::<OrphanFileCleanupQueueConfig>() {
+            Ok(config) => config,
+            Err(err) => {
+                tracing::error!("Failed to deserialize task config: {:?}", err);
+                continue;
+            }
+        }
+        .unwrap_or_default();
+        let payload = match task.task_state::<OrphanFileCleanupPayload>() {
+            Ok(payload) => payload,
+            Err(err) => {
+                tracing::error!("Failed to deserialize task state: {:?}", err);
+                continue;
+            }
+        };
+
+        let span = tracing::debug_span!(
+            "orphan_file_cleanup",
+            table_id = %task.task_metadata.entity_id.to_uuid(),
+            warehouse_id = %task.task_metadata.warehouse_id,
+            queue_name = %task.queue_name,
+            task = ?task,
+        );
+
+        instrumented_cleanup::<C, S>(
+            catalog_state.clone(),
+            &secret_state,
+            &task,
+            &config,
+            &payload,
+        )
+        .instrument(span.or_current())
+        .await;
+    }
+}
+
+async fn instrumented_cleanup<C: Catalog, S: SecretStore>(
+    catalog_state: C::State,
+    secret_state: &S,
+    task: &Task,
+    config: &OrphanFileCleanupQueueConfig,
+    payload: &OrphanFileCleanupPayload,
+) {
+    match cleanup::<C, S>(catalog_state.clone(), secret_state, task, config, payload).await {
+        Ok(result) => {
+            tracing::debug!(
+                "Cleaned up orphan files: {} orphans, {} deleted, {} failed",
+                result.orphan_files,
+                result.deleted_files,
+                result.failed_deletes
+            );
+        }
+        Err(err) => {
+            tracing::error!("Failed to clean up orphan files: {}", err.error);
+            super::record_error_with_catalog::<C>(
+                catalog_state.clone(),
+                &format!("Failed to clean up orphan files: '{:?}'", err.error),
+                config.max_retries(),
+                task.task_id,
+            )
+            .await;
+        }
+    }
+}
+
+async fn cleanup<C: Catalog, S: SecretStore>(
+    catalog_state: C::State,
+    secret_state: &S,
+    task: &Task,
+    config: &OrphanFileCleanupQueueConfig,
+    payload: &OrphanFileCleanupPayload,
+) -> Result<OrphanFileCleanupResult> {
+    let warehouse_id = task.task_metadata.warehouse_id;
+    let table_id = TableId::from(task.task_metadata.entity_id.to_uuid());
+
+    let mut trx = C::Transaction::begin_read(catalog_state.clone()).await?;
+    let table = C::load_tables(warehouse_id, [table_id], false, trx.transaction())
+        .await?
+        .remove(&table_id);
+    trx.commit().await?;
+
+    let table = match table {
+        Some(table) if table.external => Err("Table is external"),
+        Some(table) if table.metadata_location.is_some() => Ok(table),
+        _ => Err("Table does not exist"),
+    };
+    let table = match table {
+        Ok(table) => table,
+        // The table has been dropped in the meantime or is external.
+        // Such tables are not scheduled again.
+        Err(skipped_reason) => {
+            let result = OrphanFileCleanupResult {
+                skipped_reason: Some(skipped_reason.to_string()),
+                ..Default::default()
+            };
+            let mut trx = C::Transaction::begin_write(catalog_state).await?;
+            C::retrying_record_task_success(
+                task.task_id,
+                Some(&serialize_result(&result)?),
+                trx.transaction(),
+            )
+            .await;
+            trx.commit().await?;
+            return Ok(result);
+        }
+    };
+
+    let dry_run = payload.dry_run.unwrap_or(config.dry_run);
+    let result = if table
+        .table_metadata
+        .properties()
+        .get(PROPERTY_GC_ENABLED)
+        .is_some_and(|v| v.eq_ignore_ascii_case("false"))
+    {
+        OrphanFileCleanupResult {
+            dry_run,
+            skipped_reason: Some(format!("Table property '{PROPERTY_GC_ENABLED}' is false")),
+            ..Default::default()
+        }
+    } else {
+        remove_orphans::<C, S>(
+            catalog_state.clone(),
+            secret_state,
+            task.task_id,
+            &table,
+            config,
+            dry_run,
+        )
+        .await?
+    };
+
+    let mut trx = C::Transaction::begin_write(catalog_state).await?;
+    C::retrying_record_task_success(
+        task.task_id,
+        Some(&serialize_result(&result)?),
+        trx.transaction(),
+    )
+    .await;
+    maybe_queue_orphan_file_cleanup::<C>(
+        warehouse_id,
+        table_id,
+        Some(task.task_id),
+        trx.transaction(),
+    )
+    .await?;
+    trx.commit().await?;
+
+    Ok(result)
+}
+
+async fn remove_orphans<C: Catalog, S: SecretStore>(
+    catalog_state: C::State,
+    secret_state: &S,
+    task_id: TaskId,
+    table: &LoadTableResponse,
+    config: &OrphanFileCleanupQueueConfig,
+    dry_run: bool,
+) -> Result<OrphanFileCleanupResult> {
+    let mut result = OrphanFileCleanupResult {
+        dry_run,
+        ..Default::default()
+    };
+
+    let table_location = Location::from_str(table.table_metadata.location()).map_err(|e| {
+        ErrorModel::internal(
+            format!(
+                "Table location '{}' is not a valid location",
+                table.table_metadata.location()
+            ),
+            "InvalidTableLocation",
+            Some(Box::new(e)),
+        )
+    })?;
+    let secret = maybe_get_secret(table.storage_secret_ident, secret_state).await?;
+    let file_io = table.storage_profile.file_io(secret.as_ref()).await?;
+
+    let referenced =
+        referenced_keys(table, file_io.iceberg_file_io("Orphan file cleanup")?).await?;
+    result.referenced_files = referenced.len();
+    let cutoff = chrono::Utc::now() - chrono::Duration::milliseconds(config.grace_period_ms);
+
+    let mut orphans = Vec::new();
+    let mut pages = list_files(&file_io, &table_location, None).await?;
+    while let Some(page) = pages.next().await {
+        if !heartbeat::<C>(catalog_state.clone(), task_id).await? {
+            result.stopped = true;
+            break;
+        }
+        orphans.extend(find_orphans(
+            page?,
+            &referenced,
+            cutoff,
+            table_location.as_str(),
+            &mut result,
+        ));
+    }
+    result.orphan_files = orphans.len();
+    result.orphans_truncated = orphans.len() > config.max_reported_orphans;
+    result.orphans = orphans
+        .iter()
+        .take(config.max_reported_orphans)
+        .cloned()
+        .collect();
+
+    if dry_run || result.stopped {
+        return Ok(result);
+    }
+
+    for chunk in orphans.chunks(DELETE_BATCH_SIZE) {
+        if !heartbeat::<C>(catalog_state.clone(), task_id).await? {
+            result.stopped = true;
+            break;
+        }
+        let (deleted, failed) = super::delete_files(&file_io, chunk).await;
+        result.deleted_files += deleted;
+        result.failed_deletes += failed;
+    }
+
+    Ok(result)
+}
+
+/// Sends a heartbeat. Returns `false` if the task should stop.
+async fn heartbeat<C: Catalog>(catalog_state: C::State, task_id: TaskId) -> Result<bool> {
+    let mut trx = C::Transaction::begin_write(catalog_state).await?;
+    let check = C::check_and_heartbeat_task(task_id, trx.transaction()).await?;
+    trx.commit().await?;
+    if matches!(check, Some(TaskCheckState::Continue)) {
+        Ok(true)
+    } else {
+        tracing::info!("Orphan file cleanup task {task_id} was asked to stop");
+        Ok(false)
+    }
+}
+
+/// Collects the object keys of all files referenced by the table: metadata files,
+/// manifest lists, manifests, data and delete files of all snapshots, and statistics files.
+/// Entries of manifests are included regardless of their status, as deleted entries
+/// are removed by snapshot expiration.
+async fn referenced_keys(table: &LoadTableResponse, file_io: &FileIO) -> Result<HashSet<String>> {
+    let metadata = &table.table_metadata;
+    let mut referenced = HashSet::new();
+    let mut insert = |path: &str| {
+        referenced.insert(object_key(path).to_string());
+    };
+
+    if let Some(metadata_location) = &table.metadata_location {
+        insert(metadata_location.as_str());
+    }
+    for log in metadata.metadata_log() {
+        insert(&log.metadata_file);
+    }
+    for statistics in metadata.statistics_iter() {
+        insert(&statistics.statistics_path);
+    }
+    for statistics in metadata.partition_statistics_iter() {
+        insert(&statistics.statistics_path);
+    }
+
+    let mut manifests = HashMap::new();
+    for snapshot in metadata.snapshots() {
+        insert(snapshot.manifest_list());
+        let manifest_list = load_manifest_list(snapshot, file_io, metadata).await?;
+        for manifest in manifest_list.entries() {
+            manifests
+                .entry(manifest.manifest_path.clone())
+                .or_insert_with(|| manifest.clone());
+        }
+    }
+    for (path, manifest) in manifests {
+        insert(&path);
+        let manifest = manifest
+            .load_manifest(file_io)
+            .await
+            .map_err(|e| io_error("Failed to read manifest", e))?;
+        for entry in manifest.entries() {
+            insert(entry.data_file().file_path());
+        }
+    }
+
+    Ok(referenced)
+}
+
+async fn load_manifest_list(
+    snapshot: &iceberg::spec::Snapshot,
+    file_io: &FileIO,
+    metadata: &TableMetadata,
+) -> Result<iceberg::spec::ManifestList> {
+    Ok(snapshot
+        .load_manifest_list(file_io, metadata)
+        .await
+        .map_err(|e| io_error("Failed to read manifest list", e))?)
+}
+
+/// Returns the paths of the files in `page` that are neither referenced nor younger
+/// than `cutoff` and updates the counters of `result`.
+///
+/// Listed paths without a scheme are resolved against the storage root of `table_location`.
+fn find_orphans(
+    page: Vec<ListedFile>,
+    referenced: &HashSet<String>,
+    cutoff: chrono::DateTime<chrono::Utc>,
+    table_location: &str,
+    result: &mut OrphanFileCleanupResult,
+) -> Vec<String> {
+    result.listed_files += page.len();
+    page.into_iter()
+        .filter(|file| !referenced.contains(object_key(&file.path)))
+        .filter(|file| match file.last_modified {
+            Some(last_modified) if last_modified < cutoff => true,
+            Some(_) => {
+                result.skipped_too_recent += 1;
+                false
+            }
+            None => {
+                result.skipped_unknown_age += 1;
+                false
+            }
+        })
+        .map(|file| {
+            if file.path.contains("://") {
+                file.path
+            } else {
+                format!(
+                    "{}{}",
+                    storage_root(table_location),
+                    file.path.trim_start_matches('/')
+                )
+            }
+        })
+        .collect()
+}
+
+/// Path of a file relative to the root of its bucket, container or file system.
+///
+/// Storages report the same file with different schemes (e.g. `s3a://` and `s3://`)
+/// or without scheme and authority when listing, so files are compared by their keys.
+fn object_key(path: &str) -> &str {
+    match path.split_once("://") {
+        Some((_, rest)) => rest.split_once('/').map_or("", |(_, key)| key),
+        None => path.trim_start_matches('/'),
+    }
+}
+
+/// `scheme://authority/` prefix of `location`.
+fn storage_root(location: &str) -> &str {
+    let key = object_key(location);
+    &location[..location.len() - key.len()]
+}
+
+fn io_error(message: &str, e: iceberg::Error) -> ErrorModel {
+    ErrorModel::internal(message, "OrphanFileCleanupIOError", Some(Box::new(e)))
+}
+
+fn serialize_result(result: &OrphanFileCleanupResult) -> Result<String> {
+    Ok(serde_json::to_string(result).map_err(|e| {
+        ErrorModel::internal(
+            "Failed to serialize orphan file cleanup result",
+            "OrphanFileCleanupResultSerializationError",
+            Some(Box::new(e)),
+        )
+    })?)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{find_orphans, object_key, storage_root, OrphanFileCleanupResult};
+    use crate::catalog::io::ListedFile;
+
+    #[test]
+    fn test_object_key() {
+        assert_eq!(
+            object_key("s3://bucket/warehouse/table/data/a.parquet"),
+            "warehouse/table/data/a.parquet"
+        );
+        assert_eq!(
+            object_key("s3a://bucket/warehouse/table/data/a.parquet"),
+            "warehouse/table/data/a.parquet"
+        );
+        assert_eq!(
+            object_key("abfss://container@account.dfs.core.windows.net/table/a.parquet"),
+            "table/a.parquet"
+        );
+        assert_eq!(
+            object_key("/warehouse/table/data/a.parquet"),
+            "warehouse/table/data/a.parquet"
+        );
+        assert_eq!(object_key("s3://bucket"), "");
+    }
+
+    #[test]
+    fn test_storage_root() {
+        assert_eq!(storage_root("s3://bucket/warehouse/table"), "s3://bucket/");
+        assert_eq!(storage_root("gs://bucket/warehouse/table/"), "gs://bucket/");
+    }
+
+    #[test]
+    fn test_find_orphans() {
+        let now = chrono::Utc::now();
+        let cutoff = now - chrono::Duration::days(3);
+        let old = Some(now - chrono::Duration::days(4));
+        let file = |path: &str, last_modified| ListedFile {
+            path: path.to_string(),
+            last_modified,
+        };
+        let referenced = HashSet::from([
+            "warehouse/table/metadata/00001.metadata.json".to_string(),
+            "warehouse/table/data/referenced.parquet".to_string(),
+        ]);
+        let page = vec![
+            file(
+                "s3a://bucket/warehouse/table/metadata/00001.metadata.json",
+                old,
+            ),
+            file("s3://bucket/warehouse/table/data/referenced.parquet", old),
+            file("s3://bucket/warehouse/table/data/orphan.parquet", old),
+            file("/warehouse/table/data/relative-orphan.parquet", old),
+            file("s3://bucket/warehouse/table/data/recent.parquet", Some(now)),
+            file("s3://bucket/warehouse/table/data/unknown.parquet", None),
+        ];
+
+        let mut result = OrphanFileCleanupResult::default();
+        let orphans = find_orphans(
+            page,
+            &referenced,
+            cutoff,
+            "s3://bucket/warehouse/table",
+            &mut result,
+        );
+
+        assert_eq!(
+            orphans,
+            vec![
+                "s3://bucket/warehouse/table/data/orphan.parquet".to_string(),
+                "s3://bucket/warehouse/table/data/relative-orphan.parquet".to_string(),
+            ]
+        );
+        assert_eq!(result.listed_files, 6);
+        assert_eq!(result.skipped_too_recent, 1);
+        assert_eq!(result.skipped_unknown_age, 1);
+    }
+}
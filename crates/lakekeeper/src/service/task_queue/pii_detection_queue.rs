@@ -0,0 +1,667 @@
+use std::{collections::HashMap, sync::LazyLock, time::Duration};
+
+use iceberg::{
+    io::FileIO,
+    spec::{
+        ManifestContentType, NestedFieldRef, PrimitiveLiteral, PrimitiveType, Schema,
+        TableMetadata, Type,
+    },
+};
+use iceberg_ext::catalog::rest::ErrorModel;
+use rand::RngCore as _;
+use serde::{Deserialize, Serialize};
+use tracing::Instrument;
+use utoipa::{PartialSchema, ToSchema};
+
+use super::{QueueApiConfig, QueueConfig, DEFAULT_MAX_TIME_SINCE_LAST_HEARTBEAT};
+use crate::{
+    api::Result,
+    catalog::maybe_get_secret,
+    service::{task_queue::Task, Catalog, SecretStore, TableId, Transaction},
+};
+
+/// Maximum number of data manifests read when sampling column bounds.
+const MAX_SAMPLED_MANIFESTS: usize = 4;
+/// Maximum number of data files whose bounds are sampled.
+const MAX_SAMPLED_FILES: usize = 32;
+/// Minimum number of sampled bounds required to derive a suggestion from values.
+const MIN_SAMPLED_BOUNDS: usize = 3;
+/// Confidence of a suggestion that is only based on sampled bounds.
+const BOUNDS_CONFIDENCE: f64 = 0.8;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct PiiDetectionPayload {}
+
+pub(crate) const QUEUE_NAME: &str = "pii_detection";
+pub(crate) static API_CONFIG: LazyLock<QueueApiConfig> = LazyLock::new(|| QueueApiConfig {
+    queue_name: QUEUE_NAME,
+    utoipa_type_name: PiiDetectionQueueConfig::name(),
+    utoipa_schema: PiiDetectionQueueConfig::schema(),
+});
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct PiiDetectionQueueConfig {
+    /// Suggestions with a lower confidence are discarded. Default: 0.5
+    #[serde(default = "default_min_confidence")]
+    pub(crate) min_confidence: f64,
+    /// Additionally inspect the lower bounds of a sample of data files of the
+    /// current snapshot. Requires access to the table's storage. Default: false
+    #[serde(default)]
+    pub(crate) sample_bounds: bool,
+}
+
+fn default_min_confidence() -> f64 {
+    0.5
+}
+
+impl Default for PiiDetectionQueueConfig {
+    fn default() -> Self {
+        Self {
+            min_confidence: default_min_confidence(),
+            sample_bounds: false,
+        }
+    }
+}
+
+impl QueueConfig for PiiDetectionQueueConfig {}
+
+/// A tag suggested for a column by the PII analyzer.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PiiSuggestion {
+    pub(crate) field_id: i32,
+    pub(crate) tag: String,
+    /// Confidence between 0 and 1
+    pub(crate) confidence: f64,
+    /// Human readable explanation of the suggestion
+    pub(crate) reason: String,
+}
+
+pub(crate) async fn pii_detection_worker<C: Catalog, S: SecretStore>(
+    catalog_state: C::State,
+    secret_state: S,
+    poll_interval: std::time::Duration,
+) {
+    loop {
+        let task = match C::pick_new_task(
+            QUEUE_NAME,
+            DEFAULT_MAX_TIME_SINCE_LAST_HEARTBEAT,
+            catalog_state.clone(),
+        )
+        .await
+        {
+            Ok(task) => task,
+            Err(err) => {
+                tracing::error!("Failed to fetch PII detection task: {:?}", err);
+                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                continue;
+            }
+        };
+
+        let Some(task) = task else {
+            let jitter = { rand::rng().next_u64() % 500 };
+            tokio::time::sleep(poll_interval + Duration::from_millis(jitter)).await;
+            continue;
+        };
+        let config = match task.task_config::<PiiDetectionQueueConfig>() {
+            Ok(config) => config,
+            Err(err) => {
+                tracing::error!("Failed to deserialize task config: {:?}", err);
+                continue;
+            }
+        }
+        .unwrap_or_default();
+
+        let span = tracing::debug_span!(
+            "pii_detection",
+            table_id = %task.task_metadata.entity_id.to_uuid(),
+            warehouse_id = %task.task_metadata.warehouse_id,
+            queue_name = %task.queue_name,
+            task = ?task,
+        );
+
+        instrumented_detect::<C, S>(catalog_state.clone(), &secret_state, &task, &config)
+            .instrument(span.or_current())
+            .await;
+    }
+}
+
+async fn instrumented_detect<C: Catalog, S: SecretStore>(
+    catalog_state: C::State,
+    secret_state: &S,
+    task: &Task,
+    config: &PiiDetectionQueueConfig,
+) {
+    match detect::<C, S>(catalog_state.clone(), secret_state, task, config).await {
+        Ok(n_suggestions) => {
+            tracing::debug!("Stored {n_suggestions} column tag suggestions");
+        }
+        Err(err) => {
+            tracing::error!("Failed to detect PII: {}", err.error);
+            super::record_error_with_catalog::<C>(
+                catalog_state.clone(),
+                &format!("Failed to detect PII: '{:?}'", err.error),
+                config.max_retries(),
+                task.task_id,
+            )
+            .await;
+        }
+    }
+}
+
+async fn detect<C: Catalog, S: SecretStore>(
+    catalog_state: C::State,
+    secret_state: &S,
+    task: &Task,
+    config: &PiiDetectionQueueConfig,
+) -> Result<usize> {
+    let warehouse_id = task.task_metadata.warehouse_id;
+    let table_id = TableId::from(task.task_metadata.entity_id.to_uuid());
+
+    let mut trx = C::Transaction::begin_read(catalog_state.clone()).await?;
+    let table = C::load_tables(warehouse_id, [table_id], false, trx.transaction())
+        .await?
+        .remove(&table_id);
+    trx.commit().await?;
+
+    let Some(table) = table else {
+        // The table has been dropped in the meantime - nothing to analyze.
+        let mut trx = C::Transaction::begin_write(catalog_state).await?;
+        C::retrying_record_task_success(task.task_id, None, trx.transaction()).await;
+        trx.commit().await?;
+        return Ok(0);
+    };
+
+    let bounds = if config.sample_bounds {
+        let secret = maybe_get_secret(table.storage_secret_ident, secret_state).await?;
+        let file_io = table.storage_profile.file_io(secret.as_ref()).await?;
+        sample_string_bounds(
+            &table.table_metadata,
+            file_io.iceberg_file_io("Sampling column bounds")?,
+        )
+        .await?
+    } else {
+        HashMap::new()
+    };
+
+    // Columns that are already tagged don't need suggestions for the same tag.
+    let existing_tags = C::get_column_tags(table_id, catalog_state.clone()).await?;
+    let suggestions = suggest_pii_tags(
+        table.table_metadata.current_schema(),
+        &bounds,
+        config.min_confidence,
+    )
+    .into_iter()
+    .filter(|s| {
+        !existing_tags
+            .get(&s.field_id)
+            .is_some_and(|tags| tags.contains(&s.tag))
+    })
+    .collect::<Vec<_>>();
+
+    let mut trx = C::Transaction::begin_write(catalog_state).await?;
+    C::replace_column_tag_suggestions(warehouse_id, table_id, &suggestions, trx.transaction())
+        .await?;
+    C::retrying_record_task_success(task.task_id, None, trx.transaction()).await;
+    trx.commit().await?;
+
+    Ok(suggestions.len())
+}
+
+/// Reads the lower bounds of string columns from a sample of data files of the
+/// current snapshot, keyed by field id.
+///
+/// Only lower bounds are used: writers truncate string bounds, which keeps a valid
+/// prefix for lower bounds but modifies the last character of upper bounds.
+async fn sample_string_bounds(
+    metadata: &TableMetadata,
+    file_io: &FileIO,
+) -> Result<HashMap<i32, Vec<String>>> {
+    let mut bounds: HashMap<i32, Vec<String>> = HashMap::new();
+    let Some(snapshot) = metadata.current_snapshot() else {
+        return Ok(bounds);
+    };
+
+    let manifest_list = snapshot
+        .load_manifest_list(file_io, metadata)
+        .await
+        .map_err(|e| sampling_error("Failed to read manifest list", e))?;
+
+    let mut n_files = 0;
+    for manifest in manifest_list
+        .entries()
+        .iter()
+        .filter(|m| m.content == ManifestContentType::Data)
+        .take(MAX_SAMPLED_MANIFESTS)
+    {
+        let manifest = manifest
+            .load_manifest(file_io)
+            .await
+            .map_err(|e| sampling_error("Failed to read manifest", e))?;
+        for entry in manifest.entries().iter().filter(|e| e.is_alive()) {
+            if n_files >= MAX_SAMPLED_FILES {
+                return Ok(bounds);
+            }
+            n_files += 1;
+            for (field_id, datum) in entry.data_file().lower_bounds() {
+                if let PrimitiveLiteral::String(value) = datum.literal() {
+                    bounds.entry(*field_id).or_default().push(value.clone());
+                }
+            }
+        }
+    }
+
+    Ok(bounds)
+}
+
+fn sampling_error(message: &str, e: iceberg::Error) -> ErrorModel {
+    ErrorModel::internal(message, "PiiDetectionSamplingError", Some(Box::new(e)))
+}
+
+/// Types of columns a rule applies to.
+#[derive(Debug, Clone, Copy)]
+enum ExpectedType {
+    Text,
+    TextOrNumber,
+    TextOrTemporal,
+    TextOrFloatingPoint,
+}
+
+impl ExpectedType {
+    fn accepts(self, ty: &PrimitiveType) -> bool {
+        match ty {
+            PrimitiveType::String => true,
+            PrimitiveType::Int | PrimitiveType::Long | PrimitiveType::Decimal { .. } => {
+                matches!(self, Self::TextOrNumber)
+            }
+            PrimitiveType::Float | PrimitiveType::Double => {
+                matches!(self, Self::TextOrFloatingPoint)
+            }
+            PrimitiveType::Date
+            | PrimitiveType::Timestamp
+            | PrimitiveType::Timestamptz
+            | PrimitiveType::TimestampNs
+            | PrimitiveType::TimestamptzNs => matches!(self, Self::TextOrTemporal),
+            _ => false,
+        }
+    }
+}
+
+struct NameRule {
+    tag: &'static str,
+    /// Keywords in `snake_case`. A keyword matches if its words appear
+    /// consecutively in the words of the column name.
+    keywords: &'static [&'static str],
+    /// Words that prevent a match, i.e. `email` for addresses.
+    unless: &'static [&'static str],
+    expected_type: ExpectedType,
+    confidence: f64,
+}
+
+const NAME_RULES: &[NameRule] = &[
+    NameRule {
+        tag: "pii:email",
+        keywords: &["email", "e_mail", "mail_address"],
+        unless: &[],
+        expected_type: ExpectedType::Text,
+        confidence: 0.9,
+    },
+    NameRule {
+        tag: "pii:phone",
+        keywords: &["phone", "mobile", "telephone", "fax", "msisdn"],
+        unless: &[],
+        expected_type: ExpectedType::TextOrNumber,
+        confidence: 0.85,
+    },
+    NameRule {
+        tag: "pii:national-id",
+        keywords: &[
+            "ssn",
+            "social_security",
+            "national_id",
+            "passport",
+            "tax_id",
+            "driver_license",
+        ],
+        unless: &[],
+        expected_type: ExpectedType::TextOrNumber,
+        confidence: 0.9,
+    },
+    NameRule {
+        tag: "pii:name",
+        keywords: &[
+            "first_name",
+            "last_name",
+            "full_name",
+            "given_name",
+            "family_name",
+            "middle_name",
+            "maiden_name",
+            "surname",
+            "firstname",
+            "lastname",
+            "fullname",
+        ],
+        unless: &[],
+        expected_type: ExpectedType::Text,
+        confidence: 0.8,
+    },
+    NameRule {
+        tag: "pii:address",
+        keywords: &[
+            "address",
+            "street",
+            "zip",
+            "zip_code",
+            "zipcode",
+            "postal_code",
+            "postcode",
+        ],
+        unless: &["email", "mail", "ip", "mac", "web", "url", "wallet"],
+        expected_type: ExpectedType::TextOrNumber,
+        confidence: 0.7,
+    },
+    NameRule {
+        tag: "pii:birth-date",
+        keywords: &[
+            "birth_date",
+            "birthdate",
+            "date_of_birth",
+            "dob",
+            "birthday",
+        ],
+        unless: &[],
+        expected_type: ExpectedType::TextOrTemporal,
+        confidence: 0.9,
+    },
+    NameRule {
+        tag: "pii:ip-address",
+        keywords: &["ip", "ip_address", "ipv4", "ipv6", "ip_addr", "remote_addr"],
+        unless: &[],
+        expected_type: ExpectedType::TextOrNumber,
+        confidence: 0.8,
+    },
+    NameRule {
+        tag: "pii:credit-card",
+        keywords: &[
+            "credit_card",
+            "card_number",
+            "cc_number",
+            "creditcard",
+            "iban",
+        ],
+        unless: &[],
+        expected_type: ExpectedType::TextOrNumber,
+        confidence: 0.9,
+    },
+    NameRule {
+        tag: "pii:geolocation",
+        keywords: &["latitude", "longitude", "geolocation", "geo_location"],
+        unless: &[],
+        expected_type: ExpectedType::TextOrFloatingPoint,
+        confidence: 0.6,
+    },
+];
+
+/// Suggests PII tags for the columns of `schema`.
+///
+/// Columns are matched by name and type. If `bounds` contains sampled lower bounds
+/// for a string column, the values are matched against well known formats as well.
+/// Evidence from names and values is combined, so that a suggestion supported
+/// by both gets a higher confidence than either on its own.
+pub(crate) fn suggest_pii_tags(
+    schema: &Schema,
+    bounds: &HashMap<i32, Vec<String>>,
+    min_confidence: f64,
+) -> Vec<PiiSuggestion> {
+    let mut suggestions: HashMap<(i32, &'static str), (f64, Vec<String>)> = HashMap::new();
+    let mut add = |field_id: i32, tag: &'static str, confidence: f64, reason: String| {
+        let (current, reasons) = suggestions
+            .entry((field_id, tag))
+            .or_insert_with(|| (0.0, vec![]));
+        *current = 1.0 - (1.0 - *current) * (1.0 - confidence);
+        reasons.push(reason);
+    };
+
+    for (field, primitive) in primitive_fields(schema.as_struct().fields()) {
+        let words = name_words(&field.name);
+        for rule in NAME_RULES {
+            if !rule.expected_type.accepts(primitive)
+                || rule.unless.iter().any(|w| words.iter().any(|x| x == w))
+            {
+                continue;
+            }
+            if let Some(keyword) = rule
+                .keywords
+                .iter()
+                .find(|keyword| contains_words(&words, keyword))
+            {
+                add(
+                    field.id,
+                    rule.tag,
+                    rule.confidence,
+                    format!("Column name '{}' matches '{keyword}'", field.name),
+                );
+            }
+        }
+
+        if *primitive != PrimitiveType::String {
+            continue;
+        }
+        let Some(values) = bounds.get(&field.id) else {
+            continue;
+        };
+        if values.len() < MIN_SAMPLED_BOUNDS {
+            continue;
+        }
+        for (tag, format, matches) in VALUE_FORMATS {
+            if values.iter().all(|v| matches(v)) {
+                add(
+                    field.id,
+                    *tag,
+                    BOUNDS_CONFIDENCE,
+                    format!("{} sampled values look like {format}", values.len()),
+                );
+            }
+        }
+    }
+
+    let mut suggestions = suggestions
+        .into_iter()
+        .map(|((field_id, tag), (confidence, reasons))| PiiSuggestion {
+            field_id,
+            tag: tag.to_string(),
+            confidence: (confidence * 100.0).round() / 100.0,
+            reason: reasons.join("; "),
+        })
+        .filter(|s| s.confidence >= min_confidence)
+        .collect::<Vec<_>>();
+    suggestions.sort_by(|a, b| (a.field_id, &a.tag).cmp(&(b.field_id, &b.tag)));
+    suggestions
+}
+
+type ValueFormat = (&'static str, &'static str, fn(&str) -> bool);
+
+const VALUE_FORMATS: &[ValueFormat] = &[
+    ("pii:email", "email addresses", |v| {
+        lazy_regex::regex_is_match!(r"^[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]*$", v)
+    }),
+    ("pii:national-id", "social security numbers", |v| {
+        lazy_regex::regex_is_match!(r"^\d{3}-\d{2}-\d{4}$", v)
+    }),
+    ("pii:ip-address", "IPv4 addresses", |v| {
+        v.parse::<std::net::Ipv4Addr>().is_ok()
+    }),
+];
+
+/// Returns all fields of primitive type, including nested fields. Lists of
+/// primitives are returned as the list field with its element type.
+fn primitive_fields(fields: &[NestedFieldRef]) -> Vec<(&NestedFieldRef, &PrimitiveType)> {
+    let mut result = vec![];
+    for field in fields {
+        match field.field_type.as_ref() {
+            Type::Primitive(primitive) => result.push((field, primitive)),
+            Type::Struct(s) => result.extend(primitive_fields(s.fields())),
+            Type::List(list) => match list.element_field.field_type.as_ref() {
+                Type::Primitive(primitive) => result.push((field, primitive)),
+                _ => result.extend(primitive_fields(std::slice::from_ref(&list.element_field))),
+            },
+            Type::Map(map) => {
+                result.extend(primitive_fields(std::slice::from_ref(&map.key_field)));
+                result.extend(primitive_fields(std::slice::from_ref(&map.value_field)));
+            }
+        }
+    }
+    result
+}
+
+/// Splits a column name into lowercase words at non-alphanumeric characters
+/// and camelCase boundaries. Plural words are singularized.
+fn name_words(name: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut current = String::new();
+    let mut previous_lowercase = false;
+    for c in name.chars() {
+        if !c.is_alphanumeric() {
+            words.push(std::mem::take(&mut current));
+            previous_lowercase = false;
+            continue;
+        }
+        if c.is_uppercase() && previous_lowercase {
+            words.push(std::mem::take(&mut current));
+        }
+        previous_lowercase = c.is_lowercase() || c.is_numeric();
+        current.extend(c.to_lowercase());
+    }
+    words.push(current);
+
+    words
+        .into_iter()
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            if w.len() > 3 && w.ends_with('s') && !w.ends_with("ss") {
+                w[..w.len() - 1].to_string()
+            } else {
+                w
+            }
+        })
+        .collect()
+}
+
+fn contains_words(words: &[String], keyword: &str) -> bool {
+    let keyword = keyword.split('_').collect::<Vec<_>>();
+    words
+        .windows(keyword.len())
+        .any(|window| window.iter().zip(&keyword).all(|(a, b)| a == b))
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use iceberg::spec::{ListType, NestedField, PrimitiveType, Schema, StructType, Type};
+
+    use super::{name_words, suggest_pii_tags};
+
+    fn schema() -> Schema {
+        Schema::builder()
+            .with_fields(vec![
+                NestedField::required(1, "id", Type::Primitive(PrimitiveType::Long)).into(),
+                NestedField::optional(2, "customerEmail", Type::Primitive(PrimitiveType::String))
+                    .into(),
+                NestedField::optional(3, "email_verified", Type::Primitive(PrimitiveType::Boolean))
+                    .into(),
+                NestedField::optional(4, "ip_address", Type::Primitive(PrimitiveType::String))
+                    .into(),
+                NestedField::optional(5, "date_of_birth", Type::Primitive(PrimitiveType::Date))
+                    .into(),
+                NestedField::optional(
+                    6,
+                    "contact",
+                    Type::Struct(StructType::new(vec![NestedField::optional(
+                        7,
+                        "phone_numbers",
+                        Type::List(ListType::new(
+                            NestedField::list_element(
+                                8,
+                                Type::Primitive(PrimitiveType::String),
+                                false,
+                            )
+                            .into(),
+                        )),
+                    )
+                    .into()])),
+                )
+                .into(),
+                NestedField::optional(9, "comment", Type::Primitive(PrimitiveType::String)).into(),
+            ])
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_name_words() {
+        assert_eq!(name_words("customerEmail"), vec!["customer", "email"]);
+        assert_eq!(name_words("IP_Address"), vec!["ip", "address"]);
+        assert_eq!(name_words("phone-numbers"), vec!["phone", "number"]);
+        assert_eq!(name_words("address"), vec!["address"]);
+    }
+
+    #[test]
+    fn test_suggest_pii_tags_by_name() {
+        let suggestions = suggest_pii_tags(&schema(), &HashMap::new(), 0.5);
+        let tags = suggestions
+            .iter()
+            .map(|s| (s.field_id, s.tag.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            tags,
+            vec![
+                (2, "pii:email"),
+                (4, "pii:ip-address"),
+                (5, "pii:birth-date"),
+                (7, "pii:phone"),
+            ]
+        );
+        assert!((suggestions[0].confidence - 0.9).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_suggest_pii_tags_min_confidence() {
+        let suggestions = suggest_pii_tags(&schema(), &HashMap::new(), 0.85);
+        assert!(suggestions.iter().all(|s| s.confidence >= 0.85));
+        assert!(!suggestions.iter().any(|s| s.tag == "pii:ip-address"));
+    }
+
+    #[test]
+    fn test_suggest_pii_tags_by_bounds() {
+        let bounds = HashMap::from([
+            (
+                9,
+                vec![
+                    "alice@example.c".to_string(),
+                    "bob@example.org".to_string(),
+                    "carol@exa".to_string(),
+                ],
+            ),
+            (
+                2,
+                vec![
+                    "alice@example.c".to_string(),
+                    "bob@example.org".to_string(),
+                    "carol@exa".to_string(),
+                ],
+            ),
+        ]);
+        let suggestions = suggest_pii_tags(&schema(), &bounds, 0.5);
+
+        let comment = suggestions.iter().find(|s| s.field_id == 9).unwrap();
+        assert_eq!(comment.tag, "pii:email");
+        assert!((comment.confidence - 0.8).abs() < f64::EPSILON);
+
+        // Name and values agree - confidence is higher than either on its own
+        let email = suggestions.iter().find(|s| s.field_id == 2).unwrap();
+        assert!((email.confidence - 0.98).abs() < f64::EPSILON);
+        assert!(email.reason.contains("sampled values"));
+    }
+}
@@ -0,0 +1,498 @@
+//! Recurring task schedules.
+//!
+//! A schedule enqueues a task of an entity into a task queue whenever its cron
+//! expression fires. Schedules are stored in the catalog and fired by the scheduler
+//! worker of any Lakekeeper instance; due schedules are claimed with row locks so that
+//! every occurrence fires only once.
+//!
+//! If the previous task of the entity is still running when a schedule fires, the
+//! occurrence is skipped. A task that is still pending is rescheduled to run now.
+use std::{fmt::Display, str::FromStr, sync::Arc, time::Duration};
+
+use chrono::{DateTime, Datelike, NaiveDate, Timelike, Utc};
+use rand::RngCore as _;
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{EntityId, TaskInput, TaskMetadata};
+use crate::{
+    api::Result,
+    service::{Catalog, Transaction},
+    WarehouseId,
+};
+
+/// Name under which the scheduler worker is registered in the task queue runner.
+pub const SCHEDULER_NAME: &str = "task_schedules";
+/// Maximum jitter of a schedule: one day.
+pub const MAX_JITTER_SECONDS: u32 = 86_400;
+const MAX_SCHEDULES_PER_POLL: i64 = 100;
+/// Number of days searched for the next occurrence of a cron expression.
+/// Covers the longest gap between two leap days.
+const MAX_SEARCH_DAYS: u32 = 366 * 8 + 2;
+
+/// A recurring schedule that enqueues a task of a tabular into a task queue.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TaskSchedule {
+    pub schedule_id: Uuid,
+    #[schema(value_type = uuid::Uuid)]
+    pub warehouse_id: WarehouseId,
+    pub queue_name: String,
+    /// Table or view the tasks are enqueued for
+    pub tabular_id: Uuid,
+    /// Cron expression in UTC
+    pub cron: String,
+    /// Each run is delayed by a random duration of up to this many seconds
+    pub jitter_seconds: i32,
+    /// Payload of the enqueued tasks
+    pub payload: serde_json::Value,
+    pub paused: bool,
+    /// Next time the schedule fires, including jitter
+    pub next_run_at: DateTime<Utc>,
+    pub last_fired_at: Option<DateTime<Utc>>,
+    /// Task enqueued by the last run
+    pub last_task_id: Option<Uuid>,
+    /// The last run was skipped because the previous task was still running
+    pub last_fire_skipped: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// A new task schedule to store in the catalog.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskScheduleInput {
+    pub schedule_id: Uuid,
+    pub warehouse_id: WarehouseId,
+    pub queue_name: String,
+    pub tabular_id: Uuid,
+    pub cron: String,
+    pub jitter_seconds: i32,
+    pub payload: serde_json::Value,
+    pub paused: bool,
+    pub next_run_at: DateTime<Utc>,
+}
+
+/// A cron expression with five fields: minute, hour, day of month, month and
+/// day of week. Supports `*`, lists (`1,15`), ranges (`1-5`), steps (`*/15`, `0-30/10`),
+/// month and weekday names (`JAN`, `MON`) and the macros `@yearly`, `@monthly`,
+/// `@weekly`, `@daily` and `@hourly`.
+///
+/// Like in Vixie cron, if both day of month and day of week are restricted, a day
+/// matches if either matches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    expression: String,
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    /// Sunday is 0
+    days_of_week: u64,
+    day_of_month_restricted: bool,
+    day_of_week_restricted: bool,
+}
+
+const MONTH_NAMES: &[&str] = &[
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+const WEEKDAY_NAMES: &[&str] = &["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+impl FromStr for CronSchedule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let expression = s.trim();
+        let expanded = match expression.to_lowercase().as_str() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            _ => expression,
+        };
+        let fields = expanded.split_whitespace().collect::<Vec<_>>();
+        let &[minute, hour, day_of_month, month, day_of_week] = fields.as_slice() else {
+            return Err(format!(
+                "Invalid cron expression '{expression}': expected 5 fields (minute hour day-of-month month day-of-week)"
+            ));
+        };
+        let invalid = |field: &str, e: String| {
+            format!("Invalid cron expression '{expression}': invalid {field}: {e}")
+        };
+
+        let mut days_of_week = parse_field(day_of_week, 0, 7, WEEKDAY_NAMES, 0)
+            .map_err(|e| invalid("day of week", e))?;
+        // Both 0 and 7 are Sunday
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week | 1) & !(1 << 7);
+        }
+
+        let schedule = Self {
+            expression: expression.to_string(),
+            minutes: parse_field(minute, 0, 59, &[], 0).map_err(|e| invalid("minute", e))?,
+            hours: parse_field(hour, 0, 23, &[], 0).map_err(|e| invalid("hour", e))?,
+            days_of_month: parse_field(day_of_month, 1, 31, &[], 0)
+                .map_err(|e| invalid("day of month", e))?,
+            months: parse_field(month, 1, 12, MONTH_NAMES, 1).map_err(|e| invalid("month", e))?,
+            days_of_week,
+            day_of_month_restricted: !day_of_month.starts_with('*'),
+            day_of_week_restricted: !day_of_week.starts_with('*'),
+        };
+        if schedule.next_after(Utc::now()).is_none() {
+            return Err(format!(
+                "Invalid cron expression '{expression}': never fires"
+            ));
+        }
+        Ok(schedule)
+    }
+}
+
+impl Display for CronSchedule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.expression)
+    }
+}
+
+impl CronSchedule {
+    /// Returns the first occurrence strictly after `after`, or `None` if the
+    /// expression never fires.
+    #[must_use]
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let start = DateTime::<Utc>::from_timestamp(after.timestamp().div_euclid(60) * 60 + 60, 0)?;
+        let mut date = start.date_naive();
+        for _ in 0..MAX_SEARCH_DAYS {
+            if self.matches_date(date) {
+                let first_day = date == start.date_naive();
+                let first_hour = if first_day { start.hour() } else { 0 };
+                for hour in (first_hour..24).filter(|h| bit(self.hours, *h)) {
+                    let first_minute = if first_day && hour == first_hour {
+                        start.minute()
+                    } else {
+                        0
+                    };
+                    if let Some(minute) = (first_minute..60).find(|m| bit(self.minutes, *m)) {
+                        return date.and_hms_opt(hour, minute, 0).map(|t| t.and_utc());
+                    }
+                }
+            }
+            date = date.succ_opt()?;
+        }
+        None
+    }
+
+    fn matches_date(&self, date: NaiveDate) -> bool {
+        if !bit(self.months, date.month()) {
+            return false;
+        }
+        let day_of_month = bit(self.days_of_month, date.day());
+        let day_of_week = bit(self.days_of_week, date.weekday().num_days_from_sunday());
+        match (self.day_of_month_restricted, self.day_of_week_restricted) {
+            (true, true) => day_of_month || day_of_week,
+            (true, false) => day_of_month,
+            (false, true) => day_of_week,
+            (false, false) => true,
+        }
+    }
+}
+
+fn bit(set: u64, value: u32) -> bool {
+    set & (1 << value) != 0
+}
+
+/// Parses a cron field into a bit set. `names` map to values starting at `names_offset`.
+fn parse_field(
+    field: &str,
+    min: u32,
+    max: u32,
+    names: &[&str],
+    names_offset: u32,
+) -> Result<u64, String> {
+    let value = |s: &str| -> Result<u32, String> {
+        let v = names
+            .iter()
+            .position(|n| n.eq_ignore_ascii_case(s))
+            .map(|i| u32::try_from(i).unwrap_or(u32::MAX) + names_offset)
+            .map_or_else(|| s.parse::<u32>().map_err(|_| format!("'{s}'")), Ok)?;
+        if (min..=max).contains(&v) {
+            Ok(v)
+        } else {
+            Err(format!("{v} is not within {min}-{max}"))
+        }
+    };
+
+    let mut set = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (
+                range,
+                step.parse::<usize>()
+                    .ok()
+                    .filter(|s| *s > 0)
+                    .ok_or_else(|| format!("invalid step '{step}'"))?,
+            ),
+            None => (part, 1),
+        };
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (value(start)?, value(end)?)
+        } else {
+            let start = value(range)?;
+            // `5/15` means every 15 starting at 5
+            (start, if part.contains('/') { max } else { start })
+        };
+        if start > end {
+            return Err(format!("invalid range '{range}'"));
+        }
+        for v in (start..=end).step_by(step) {
+            set |= 1 << v;
+        }
+    }
+    Ok(set)
+}
+
+/// Next run of a schedule after `after`, delayed by a random jitter.
+#[must_use]
+pub fn next_run_at(
+    cron: &CronSchedule,
+    jitter_seconds: i32,
+    after: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    let jitter = u64::try_from(jitter_seconds)
+        .ok()
+        .filter(|j| *j > 0)
+        .map_or(0, |j| rand::rng().next_u64() % (j + 1));
+    cron.next_after(after)
+        .map(|t| t + chrono::Duration::seconds(i64::try_from(jitter).unwrap_or_default()))
+}
+
+/// Infinitely running worker that fires due schedules.
+/// Schedules of queues not contained in `queue_names` are skipped.
+pub async fn task_schedule_worker<C: Catalog>(
+    catalog_state: C::State,
+    queue_names: Arc<Vec<&'static str>>,
+    poll_interval: Duration,
+) {
+    loop {
+        match fire_due_schedules::<C>(catalog_state.clone(), &queue_names).await {
+            Ok(fired) if fired >= usize::try_from(MAX_SCHEDULES_PER_POLL).unwrap_or(0) => continue,
+            Ok(_) => {}
+            Err(e) => {
+                tracing::error!(?e, "Failed to fire task schedules: {}", e.error);
+            }
+        }
+        tokio::time::sleep(poll_interval).await;
+    }
+}
+
+async fn fire_due_schedules<C: Catalog>(
+    catalog_state: C::State,
+    queue_names: &[&'static str],
+) -> Result<usize> {
+    let mut trx = C::Transaction::begin_write(catalog_state).await?;
+    let schedules = C::claim_due_task_schedules(MAX_SCHEDULES_PER_POLL, trx.transaction()).await?;
+    let now = Utc::now();
+
+    for schedule in &schedules {
+        let cron = match CronSchedule::from_str(&schedule.cron) {
+            Ok(cron) => cron,
+            Err(e) => {
+                tracing::warn!(
+                    schedule_id = %schedule.schedule_id,
+                    "Pausing task schedule with invalid cron expression: {e}"
+                );
+                C::set_task_schedule_paused(
+                    schedule.warehouse_id,
+                    schedule.schedule_id,
+                    true,
+                    None,
+                    trx.transaction(),
+                )
+                .await?;
+                continue;
+            }
+        };
+
+        let task_id = if let Some(queue_name) = queue_names
+            .iter()
+            .copied()
+            .find(|q| *q == schedule.queue_name)
+        {
+            let task_id = C::enqueue_or_reschedule_task(
+                queue_name,
+                TaskInput {
+                    task_metadata: TaskMetadata {
+                        warehouse_id: schedule.warehouse_id,
+                        parent_task_id: None,
+                        entity_id: EntityId::Tabular(schedule.tabular_id),
+                        schedule_for: None,
+                    },
+                    payload: schedule.payload.clone(),
+                },
+                trx.transaction(),
+            )
+            .await?;
+            if task_id.is_none() {
+                tracing::info!(
+                    schedule_id = %schedule.schedule_id,
+                    "Skipped run of task schedule, previous task is still running"
+                );
+            }
+            task_id
+        } else {
+            tracing::warn!(
+                schedule_id = %schedule.schedule_id,
+                "Skipping task schedule of queue '{}' which is not registered",
+                schedule.queue_name
+            );
+            None
+        };
+
+        // An expression that parsed before always fires again
+        let next_run = next_run_at(&cron, schedule.jitter_seconds, now)
+            .unwrap_or(now + chrono::Duration::days(1));
+        C::record_task_schedule_fire(schedule.schedule_id, task_id, next_run, trx.transaction())
+            .await?;
+    }
+
+    trx.commit().await?;
+    Ok(schedules.len())
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn at(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+    }
+
+    fn next(expression: &str, after: DateTime<Utc>) -> DateTime<Utc> {
+        CronSchedule::from_str(expression)
+            .unwrap()
+            .next_after(after)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_every_minute_is_strictly_after() {
+        assert_eq!(
+            next("* * * * *", at(2025, 1, 1, 0, 0)),
+            at(2025, 1, 1, 0, 1)
+        );
+        assert_eq!(
+            next(
+                "* * * * *",
+                at(2025, 1, 1, 0, 0) + chrono::Duration::seconds(30)
+            ),
+            at(2025, 1, 1, 0, 1)
+        );
+    }
+
+    #[test]
+    fn test_daily_at() {
+        assert_eq!(
+            next("30 3 * * *", at(2025, 1, 1, 3, 30)),
+            at(2025, 1, 2, 3, 30)
+        );
+        assert_eq!(
+            next("30 3 * * *", at(2025, 1, 1, 2, 0)),
+            at(2025, 1, 1, 3, 30)
+        );
+        assert_eq!(
+            next("@daily", at(2025, 12, 31, 12, 0)),
+            at(2026, 1, 1, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_steps_and_lists() {
+        assert_eq!(
+            next("*/15 * * * *", at(2025, 1, 1, 0, 16)),
+            at(2025, 1, 1, 0, 30)
+        );
+        assert_eq!(
+            next("0 8-18/5 * * *", at(2025, 1, 1, 13, 1)),
+            at(2025, 1, 1, 18, 0)
+        );
+        assert_eq!(
+            next("0 0 1,15 * *", at(2025, 1, 2, 0, 0)),
+            at(2025, 1, 15, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_names_and_weekdays() {
+        // 2025-01-01 is a Wednesday
+        assert_eq!(
+            next("0 2 * * SUN", at(2025, 1, 1, 0, 0)),
+            at(2025, 1, 5, 2, 0)
+        );
+        assert_eq!(
+            next("0 2 * * 7", at(2025, 1, 1, 0, 0)),
+            at(2025, 1, 5, 2, 0)
+        );
+        assert_eq!(
+            next("0 0 1 mar *", at(2025, 1, 1, 0, 0)),
+            at(2025, 3, 1, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_day_of_month_or_day_of_week() {
+        // Either the 10th or a Monday
+        assert_eq!(
+            next("0 0 10 * MON", at(2025, 1, 1, 0, 0)),
+            at(2025, 1, 6, 0, 0)
+        );
+        assert_eq!(
+            next("0 0 10 * MON", at(2025, 1, 6, 0, 0)),
+            at(2025, 1, 10, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_leap_day() {
+        assert_eq!(
+            next("0 0 29 2 *", at(2025, 1, 1, 0, 0)),
+            at(2028, 2, 29, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_invalid_expressions() {
+        for expression in [
+            "",
+            "* * * *",
+            "60 * * * *",
+            "* 24 * * *",
+            "* * 0 * *",
+            "* * * 13 *",
+            "* * * * 8",
+            "*/0 * * * *",
+            "5-1 * * * *",
+            "* * * FOO *",
+            "0 0 30 2 *",
+        ] {
+            assert!(
+                CronSchedule::from_str(expression).is_err(),
+                "{expression} should be invalid"
+            );
+        }
+    }
+
+    #[test]
+    fn test_jitter() {
+        let cron = CronSchedule::from_str("0 * * * *").unwrap();
+        let after = at(2025, 1, 1, 0, 0);
+        for _ in 0..20 {
+            let next = next_run_at(&cron, 60, after).unwrap();
+            assert!(next >= at(2025, 1, 1, 1, 0));
+            assert!(next <= at(2025, 1, 1, 1, 1));
+        }
+        assert_eq!(next_run_at(&cron, 0, after), Some(at(2025, 1, 1, 1, 0)));
+    }
+}
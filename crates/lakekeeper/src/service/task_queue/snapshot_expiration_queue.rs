@@ -0,0 +1,872 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::LazyLock,
+    time::Duration,
+};
+
+use iceberg::{
+    io::FileIO,
+    spec::{SnapshotRetention, TableMetadata, MAIN_BRANCH},
+    TableUpdate,
+};
+use iceberg_ext::catalog::rest::ErrorModel;
+use rand::RngCore as _;
+use serde::{Deserialize, Serialize};
+use tracing::Instrument;
+use utoipa::{PartialSchema, ToSchema};
+
+use super::{
+    QueueApiConfig, QueueConfig, TaskCheckState, TaskId, DEFAULT_MAX_TIME_SINCE_LAST_HEARTBEAT,
+};
+use crate::{
+    api::Result,
+    catalog::{
+        maybe_get_secret,
+        tables::{
+            commit_table_maintenance, invalidate_table_metadata, maybe_queue_snapshot_expiration,
+        },
+    },
+    service::{task_queue::Task, Catalog, LoadTableResponse, SecretStore, TableId, Transaction},
+    WarehouseId,
+};
+
+const PROPERTY_GC_ENABLED: &str = "gc.enabled";
+const PROPERTY_MAX_SNAPSHOT_AGE_MS: &str = "history.expire.max-snapshot-age-ms";
+const PROPERTY_MIN_SNAPSHOTS_TO_KEEP: &str = "history.expire.min-snapshots-to-keep";
+const PROPERTY_MAX_REF_AGE_MS: &str = "history.expire.max-ref-age-ms";
+
+/// Number of files deleted between two heartbeats.
+const DELETE_BATCH_SIZE: usize = 1000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct SnapshotExpirationPayload {}
+
+pub(crate) const QUEUE_NAME: &str = "snapshot_expiration";
+pub(crate) static API_CONFIG: LazyLock<QueueApiConfig> = LazyLock::new(|| QueueApiConfig {
+    queue_name: QUEUE_NAME,
+    utoipa_type_name: SnapshotExpirationQueueConfig::name(),
+    utoipa_schema: SnapshotExpirationQueueConfig::schema(),
+});
+
+/// Retention policy of a warehouse. Tables override the policy with the
+/// `history.expire.*` properties, branches and tags with their own retention settings.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct SnapshotExpirationQueueConfig {
+    /// Snapshots older than this are expired unless they need to be kept to
+    /// satisfy `min-snapshots-to-keep`. Default: 432000000 (5 days)
+    #[serde(default = "default_max_snapshot_age_ms")]
+    pub(crate) max_snapshot_age_ms: i64,
+    /// Minimum number of snapshots to keep in the history of each branch. Default: 1
+    #[serde(default = "default_min_snapshots_to_keep")]
+    pub(crate) min_snapshots_to_keep: i32,
+    /// Branches and tags other than `main` are removed once the snapshot they point
+    /// to is older than this. Default: refs are kept forever
+    #[serde(default)]
+    pub(crate) max_ref_age_ms: Option<i64>,
+    /// Delete manifest lists, manifests, data files and statistics files that are
+    /// no longer reachable from any remaining snapshot. Default: true
+    #[serde(default = "default_delete_files")]
+    pub(crate) delete_files: bool,
+}
+
+fn default_max_snapshot_age_ms() -> i64 {
+    5 * 24 * 60 * 60 * 1000
+}
+
+fn default_min_snapshots_to_keep() -> i32 {
+    1
+}
+
+fn default_delete_files() -> bool {
+    true
+}
+
+impl Default for SnapshotExpirationQueueConfig {
+    fn default() -> Self {
+        Self {
+            max_snapshot_age_ms: default_max_snapshot_age_ms(),
+            min_snapshots_to_keep: default_min_snapshots_to_keep(),
+            max_ref_age_ms: None,
+            delete_files: default_delete_files(),
+        }
+    }
+}
+
+impl QueueConfig for SnapshotExpirationQueueConfig {}
+
+/// Outcome of a single snapshot expiration run of a table.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct SnapshotExpirationResult {
+    pub expired_snapshots: usize,
+    /// Names of the branches and tags that exceeded their maximum age
+    pub removed_refs: Vec<String>,
+    pub deleted_manifest_lists: usize,
+    pub deleted_manifests: usize,
+    pub deleted_data_files: usize,
+    pub deleted_statistics_files: usize,
+    /// Number of files that could not be deleted. They are left in storage.
+    pub failed_deletes: usize,
+    /// The run was stopped before all unreachable files were deleted.
+    pub stopped: bool,
+    /// Reason why the table was not expired, i.e. because `gc.enabled` is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skipped_reason: Option<String>,
+}
+
+pub(crate) async fn snapshot_expiration_worker<C: Catalog, S: SecretStore>(
+    catalog_state: C::State,
+    secret_state: S,
+    poll_interval: std::time::Duration,
+) {
+    loop {
+        let task = match C::pick_new_task(
+            QUEUE_NAME,
+            DEFAULT_MAX_TIME_SINCE_LAST_HEARTBEAT,
+            catalog_state.clone(),
+        )
+        .await
+        {
+            Ok(task) => task,
+            Err(err) => {
+                tracing::error!("Failed to fetch snapshot expiration task: {:?}", err);
+                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                continue;
+            }
+        };
+
+        let Some(task) = task else {
+            let jitter = { rand::rng().next_u64() % 500 };
+            tokio::time::sleep(poll_interval + Duration::from_millis(jitter)).await;
+            continue;
+        };
+        let config = match task.task_config::<SnapshotExpirationQueueConfig>() {
+            Ok(config) => config,
+            Err(err) => {
+                tracing::error!("Failed to deserialize task config: {:?}", err);
+                continue;
+            }
+        }
+        .unwrap_or_default();
+
+        let span = tracing::debug_span!(
+            "snapshot_expiration",
+            table_id = %task.task_metadata.entity_id.to_uuid(),
+            warehouse_id = %task.task_metadata.warehouse_id,
+            queue_name = %task.queue_name,
+            task = ?task,
+        );
+
+        instrumented_expire::<C, S>(catalog_state.clone(), &secret_state, &task, &config)
+            .instrument(span.or_current())
+            .await;
+    }
+}
+
+async fn instrumented_expire<C: Catalog, S: SecretStore>(
+    catalog_state: C::State,
+    secret_state: &S,
+    task: &Task,
+    config: &SnapshotExpirationQueueConfig,
+) {
+    match expire::<C, S>(catalog_state.clone(), secret_state, task, config).await {
+        Ok(result) => {
+            tracing::debug!("Expired snapshots: {result:?}");
+        }
+        Err(err) => {
+            tracing::error!("Failed to expire snapshots: {}", err.error);
+            super::record_error_with_catalog::<C>(
+                catalog_state.clone(),
+                &format!("Failed to expire snapshots: '{:?}'", err.error),
+                config.max_retries(),
+                task.task_id,
+            )
+            .await;
+        }
+    }
+}
+
+async fn expire<C: Catalog, S: SecretStore>(
+    catalog_state: C::State,
+    secret_state: &S,
+    task: &Task,
+    config: &SnapshotExpirationQueueConfig,
+) -> Result<SnapshotExpirationResult> {
+    let warehouse_id = task.task_metadata.warehouse_id;
+    let table_id = TableId::from(task.task_metadata.entity_id.to_uuid());
+
+    let mut trx = C::Transaction::begin_read(catalog_state.clone()).await?;
+    let table = C::load_tables(warehouse_id, [table_id], false, trx.transaction())
+        .await?
+        .remove(&table_id);
+    trx.commit().await?;
+
+    let table = match table {
+        Some(table) if table.external => Err("Table is external"),
+        Some(table) if table.metadata_location.is_some() => Ok(table),
+        _ => Err("Table does not exist"),
+    };
+    let table = match table {
+        Ok(table) => table,
+        // The table has been dropped in the meantime or is external.
+        // Such tables are not scheduled again.
+        Err(skipped_reason) => {
+            let result = SnapshotExpirationResult {
+                skipped_reason: Some(skipped_reason.to_string()),
+                ..Default::default()
+            };
+            let mut trx = C::Transaction::begin_write(catalog_state).await?;
+            C::retrying_record_task_success(
+                task.task_id,
+                Some(&serialize_result(&result)?),
+                trx.transaction(),
+            )
+            .await;
+            trx.commit().await?;
+            return Ok(result);
+        }
+    };
+
+    let properties = table.table_metadata.properties();
+    let result = if properties
+        .get(PROPERTY_GC_ENABLED)
+        .is_some_and(|v| v.eq_ignore_ascii_case("false"))
+    {
+        SnapshotExpirationResult {
+            skipped_reason: Some(format!("Table property '{PROPERTY_GC_ENABLED}' is false")),
+            ..Default::default()
+        }
+    } else {
+        let policy = RetentionPolicy::resolve(config, properties)?;
+        let plan = plan_expiration(
+            &table.table_metadata,
+            &policy,
+            chrono::Utc::now().timestamp_millis(),
+        );
+        expire_snapshots::<C, S>(
+            catalog_state.clone(),
+            secret_state,
+            task.task_id,
+            warehouse_id,
+            &table,
+            plan,
+            config.delete_files,
+        )
+        .await?
+    };
+
+    let mut trx = C::Transaction::begin_write(catalog_state).await?;
+    C::retrying_record_task_success(
+        task.task_id,
+        Some(&serialize_result(&result)?),
+        trx.transaction(),
+    )
+    .await;
+    maybe_queue_snapshot_expiration::<C>(
+        warehouse_id,
+        table_id,
+        Some(task.task_id),
+        trx.transaction(),
+    )
+    .await?;
+    trx.commit().await?;
+
+    Ok(result)
+}
+
+async fn expire_snapshots<C: Catalog, S: SecretStore>(
+    catalog_state: C::State,
+    secret_state: &S,
+    task_id: TaskId,
+    warehouse_id: WarehouseId,
+    table: &LoadTableResponse,
+    plan: ExpirationPlan,
+    delete_files: bool,
+) -> Result<SnapshotExpirationResult> {
+    let mut result = SnapshotExpirationResult {
+        expired_snapshots: plan.expired_snapshot_ids.len(),
+        removed_refs: plan.removed_refs.clone(),
+        ..Default::default()
+    };
+    if plan.is_empty() {
+        return Ok(result);
+    }
+
+    let secret = maybe_get_secret(table.storage_secret_ident, secret_state).await?;
+    let file_io = table.storage_profile.file_io(secret.as_ref()).await?;
+
+    let mut updates = plan
+        .removed_refs
+        .iter()
+        .map(|ref_name| TableUpdate::RemoveSnapshotRef {
+            ref_name: ref_name.clone(),
+        })
+        .collect::<Vec<_>>();
+    if !plan.expired_snapshot_ids.is_empty() {
+        updates.push(TableUpdate::RemoveSnapshots {
+            snapshot_ids: plan.expired_snapshot_ids.clone(),
+        });
+    }
+
+    let previous_metadata = &table.table_metadata;
+    let mut trx = C::Transaction::begin_write(catalog_state.clone()).await?;
+    let new_metadata =
+        commit_table_maintenance::<C>(warehouse_id, table, updates, &file_io, trx.transaction())
+            .await?;
+    trx.commit().await?;
+    invalidate_table_metadata(warehouse_id, [table_id]).await;
+
+    if !delete_files {
+        return Ok(result);
+    }
+
+    let unreachable = find_unreachable_files(
+        previous_metadata,
+        &new_metadata,
+        &plan,
+        file_io.iceberg_file_io("Deleting files of expired snapshots")?,
+    )
+    .await?;
+    let batches = [
+        (FileKind::DataFile, unreachable.data_files),
+        (FileKind::Manifest, unreachable.manifests),
+        (FileKind::ManifestList, unreachable.manifest_lists),
+        (FileKind::Statistics, unreachable.statistics_files),
+    ];
+    for (kind, files) in batches {
+        for chunk in files.chunks(DELETE_BATCH_SIZE) {
+            let mut trx = C::Transaction::begin_write(catalog_state.clone()).await?;
+            let check = C::check_and_heartbeat_task(task_id, trx.transaction()).await?;
+            trx.commit().await?;
+            if !matches!(check, Some(TaskCheckState::Continue)) {
+                tracing::info!("Snapshot expiration task {task_id} was asked to stop");
+                result.stopped = true;
+                return Ok(result);
+            }
+
+            let (deleted, failed) = super::delete_files(&file_io, chunk).await;
+            result.failed_deletes += failed;
+            match kind {
+                FileKind::DataFile => result.deleted_data_files += deleted,
+                FileKind::Manifest => result.deleted_manifests += deleted,
+                FileKind::ManifestList => result.deleted_manifest_lists += deleted,
+                FileKind::Statistics => result.deleted_statistics_files += deleted,
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+#[derive(Debug, Clone, Copy)]
+enum FileKind {
+    DataFile,
+    Manifest,
+    ManifestList,
+    Statistics,
+}
+
+#[derive(Debug, Default)]
+struct UnreachableFiles {
+    manifest_lists: Vec<String>,
+    manifests: Vec<String>,
+    data_files: Vec<String>,
+    statistics_files: Vec<String>,
+}
+
+/// Collects all files that were reachable from the expired snapshots but are
+/// no longer reachable from any snapshot of `new_metadata`.
+async fn find_unreachable_files(
+    previous_metadata: &TableMetadata,
+    new_metadata: &TableMetadata,
+    plan: &ExpirationPlan,
+    file_io: &FileIO,
+) -> Result<UnreachableFiles> {
+    let mut unreachable = UnreachableFiles::default();
+
+    let mut retained_manifests = HashMap::new();
+    for snapshot in new_metadata.snapshots() {
+        let manifest_list = snapshot
+            .load_manifest_list(file_io, new_metadata)
+            .await
+            .map_err(|e| io_error("Failed to read manifest list", e))?;
+        for manifest in manifest_list.entries() {
+            retained_manifests
+                .entry(manifest.manifest_path.clone())
+                .or_insert_with(|| manifest.clone());
+        }
+    }
+
+    let retained_manifest_lists = new_metadata
+        .snapshots()
+        .map(|s| s.manifest_list().to_string())
+        .collect::<HashSet<_>>();
+    let mut candidate_manifests = HashMap::new();
+    for snapshot_id in &plan.expired_snapshot_ids {
+        let Some(snapshot) = previous_metadata.snapshot_by_id(*snapshot_id) else {
+            continue;
+        };
+        if !retained_manifest_lists.contains(snapshot.manifest_list()) {
+            unreachable
+                .manifest_lists
+                .push(snapshot.manifest_list().to_string());
+        }
+        let manifest_list = snapshot
+            .load_manifest_list(file_io, previous_metadata)
+            .await
+            .map_err(|e| io_error("Failed to read manifest list", e))?;
+        for manifest in manifest_list.entries() {
+            if !retained_manifests.contains_key(&manifest.manifest_path) {
+                candidate_manifests
+                    .entry(manifest.manifest_path.clone())
+                    .or_insert_with(|| manifest.clone());
+            }
+        }
+    }
+
+    let mut candidate_files = HashSet::new();
+    for manifest in candidate_manifests.values() {
+        let manifest = manifest
+            .load_manifest(file_io)
+            .await
+            .map_err(|e| io_error("Failed to read manifest", e))?;
+        candidate_files.extend(
+            manifest
+                .entries()
+                .iter()
+                .map(|e| e.data_file().file_path().to_string()),
+        );
+    }
+
+    if !candidate_files.is_empty() {
+        for manifest in retained_manifests.values() {
+            let manifest = manifest
+                .load_manifest(file_io)
+                .await
+                .map_err(|e| io_error("Failed to read manifest", e))?;
+            for entry in manifest.entries().iter().filter(|e| e.is_alive()) {
+                candidate_files.remove(entry.data_file().file_path());
+            }
+        }
+    }
+
+    let retained_statistics = new_metadata
+        .statistics_iter()
+        .map(|s| s.statistics_path.as_str())
+        .chain(
+            new_metadata
+                .partition_statistics_iter()
+                .map(|s| s.statistics_path.as_str()),
+        )
+        .collect::<HashSet<_>>();
+    unreachable.statistics_files = previous_metadata
+        .statistics_iter()
+        .map(|s| s.statistics_path.as_str())
+        .chain(
+            previous_metadata
+                .partition_statistics_iter()
+                .map(|s| s.statistics_path.as_str()),
+        )
+        .filter(|path| !retained_statistics.contains(path))
+        .map(ToString::to_string)
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+
+    unreachable.manifests = candidate_manifests.into_keys().collect();
+    unreachable.data_files = candidate_files.into_iter().collect();
+    Ok(unreachable)
+}
+
+fn io_error(message: &str, e: iceberg::Error) -> ErrorModel {
+    ErrorModel::internal(message, "SnapshotExpirationIOError", Some(Box::new(e)))
+}
+
+fn serialize_result(result: &SnapshotExpirationResult) -> Result<String> {
+    Ok(serde_json::to_string(result).map_err(|e| {
+        ErrorModel::internal(
+            "Failed to serialize snapshot expiration result",
+            "SnapshotExpirationResultSerializationError",
+            Some(Box::new(e)),
+        )
+    })?)
+}
+
+/// Effective retention policy of a table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RetentionPolicy {
+    max_snapshot_age_ms: i64,
+    min_snapshots_to_keep: i32,
+    max_ref_age_ms: Option<i64>,
+}
+
+impl RetentionPolicy {
+    /// Table properties take precedence over the warehouse configuration.
+    fn resolve(
+        config: &SnapshotExpirationQueueConfig,
+        properties: &HashMap<String, String>,
+    ) -> Result<Self> {
+        fn parse<T: std::str::FromStr>(
+            properties: &HashMap<String, String>,
+            key: &str,
+        ) -> Result<Option<T>> {
+            properties
+                .get(key)
+                .map(|v| {
+                    v.parse::<T>().map_err(|_| {
+                        ErrorModel::bad_request(
+                            format!("Table property '{key}' has invalid value '{v}'"),
+                            "InvalidRetentionProperty",
+                            None,
+                        )
+                        .into()
+                    })
+                })
+                .transpose()
+        }
+
+        Ok(Self {
+            max_snapshot_age_ms: parse(properties, PROPERTY_MAX_SNAPSHOT_AGE_MS)?
+                .unwrap_or(config.max_snapshot_age_ms),
+            min_snapshots_to_keep: parse(properties, PROPERTY_MIN_SNAPSHOTS_TO_KEEP)?
+                .unwrap_or(config.min_snapshots_to_keep),
+            max_ref_age_ms: parse(properties, PROPERTY_MAX_REF_AGE_MS)?.or(config.max_ref_age_ms),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct ExpirationPlan {
+    /// Sorted ids of the snapshots to remove
+    expired_snapshot_ids: Vec<i64>,
+    /// Sorted names of the refs to remove
+    removed_refs: Vec<String>,
+}
+
+impl ExpirationPlan {
+    fn is_empty(&self) -> bool {
+        self.expired_snapshot_ids.is_empty() && self.removed_refs.is_empty()
+    }
+}
+
+/// Determines the snapshots and refs to remove at `now_ms`.
+///
+/// * Refs other than `main` are removed once their snapshot is older than their
+///   maximum ref age.
+/// * Branches keep at least `min-snapshots-to-keep` ancestors and all ancestors
+///   younger than the maximum snapshot age. The retention of the branch takes
+///   precedence over the policy.
+/// * Tags keep the snapshot they point to.
+/// * Snapshots that are not referenced by any remaining ref are kept until they
+///   are older than the maximum snapshot age.
+fn plan_expiration(
+    metadata: &TableMetadata,
+    policy: &RetentionPolicy,
+    now_ms: i64,
+) -> ExpirationPlan {
+    let age = |snapshot_id: i64| {
+        metadata
+            .snapshot_by_id(snapshot_id)
+            .map(|s| now_ms - s.timestamp_ms())
+    };
+
+    let mut removed_refs = Vec::new();
+    let mut retained = HashSet::new();
+    let mut referenced = HashSet::new();
+    for (name, snapshot_ref) in metadata.refs() {
+        let max_ref_age_ms = match &snapshot_ref.retention {
+            SnapshotRetention::Branch { max_ref_age_ms, .. }
+            | SnapshotRetention::Tag { max_ref_age_ms } => max_ref_age_ms.or(policy.max_ref_age_ms),
+        };
+        if name != MAIN_BRANCH
+            && max_ref_age_ms
+                .zip(age(snapshot_ref.snapshot_id))
+                .is_some_and(|(max_age, age)| age > max_age)
+        {
+            removed_refs.push(name.clone());
+            continue;
+        }
+
+        match &snapshot_ref.retention {
+            SnapshotRetention::Branch {
+                min_snapshots_to_keep,
+                max_snapshot_age_ms,
+                ..
+            } => {
+                let min_snapshots_to_keep = min_snapshots_to_keep
+                    .unwrap_or(policy.min_snapshots_to_keep)
+                    .max(1);
+                let max_snapshot_age_ms = max_snapshot_age_ms.unwrap_or(policy.max_snapshot_age_ms);
+                let mut n_kept = 0;
+                let mut current = metadata.snapshot_by_id(snapshot_ref.snapshot_id);
+                while let Some(snapshot) = current {
+                    referenced.insert(snapshot.snapshot_id());
+                    if n_kept < min_snapshots_to_keep
+                        || now_ms - snapshot.timestamp_ms() <= max_snapshot_age_ms
+                    {
+                        retained.insert(snapshot.snapshot_id());
+                        n_kept += 1;
+                    }
+                    current = snapshot
+                        .parent_snapshot_id()
+                        .and_then(|id| metadata.snapshot_by_id(id));
+                }
+            }
+            SnapshotRetention::Tag { .. } => {
+                referenced.insert(snapshot_ref.snapshot_id);
+                retained.insert(snapshot_ref.snapshot_id);
+            }
+        }
+    }
+
+    for snapshot in metadata.snapshots() {
+        if !referenced.contains(&snapshot.snapshot_id())
+            && now_ms - snapshot.timestamp_ms() <= policy.max_snapshot_age_ms
+        {
+            retained.insert(snapshot.snapshot_id());
+        }
+    }
+
+    let mut expired_snapshot_ids = metadata
+        .snapshots()
+        .map(|s| s.snapshot_id())
+        .filter(|id| !retained.contains(id))
+        .collect::<Vec<_>>();
+    expired_snapshot_ids.sort_unstable();
+    removed_refs.sort();
+
+    ExpirationPlan {
+        expired_snapshot_ids,
+        removed_refs,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use iceberg::spec::{
+        FormatVersion, NestedField, Operation, PrimitiveType, Schema, Snapshot, SnapshotReference,
+        SnapshotRetention, SortOrder, Summary, TableMetadata, TableMetadataBuilder, Type,
+        UnboundPartitionSpec, MAIN_BRANCH,
+    };
+
+    use super::{plan_expiration, RetentionPolicy, SnapshotExpirationQueueConfig};
+
+    const POLICY: RetentionPolicy = RetentionPolicy {
+        max_snapshot_age_ms: 5000,
+        min_snapshots_to_keep: 1,
+        max_ref_age_ms: None,
+    };
+
+    fn snapshot(id: i64, parent: Option<i64>, timestamp_ms: i64) -> Snapshot {
+        Snapshot::builder()
+            .with_snapshot_id(id)
+            .with_parent_snapshot_id(parent)
+            .with_timestamp_ms(timestamp_ms)
+            .with_sequence_number(id)
+            .with_schema_id(0)
+            .with_manifest_list(format!("/snap-{id}.avro"))
+            .with_summary(Summary {
+                operation: Operation::Append,
+                additional_properties: HashMap::new(),
+            })
+            .build()
+    }
+
+    fn branch(snapshot_id: i64) -> SnapshotReference {
+        SnapshotReference {
+            snapshot_id,
+            retention: SnapshotRetention::Branch {
+                min_snapshots_to_keep: None,
+                max_snapshot_age_ms: None,
+                max_ref_age_ms: None,
+            },
+        }
+    }
+
+    /// Builds a table with the snapshots 1 <- 2 <- 3 on `main`, committed one
+    /// second apart. Returns the metadata and the timestamp of snapshot 1.
+    fn metadata(refs: Vec<(&str, SnapshotReference)>) -> (TableMetadata, i64) {
+        let schema = Schema::builder()
+            .with_fields(vec![NestedField::required(
+                1,
+                "id",
+                Type::Primitive(PrimitiveType::Long),
+            )
+            .into()])
+            .build()
+            .unwrap();
+        let metadata = TableMetadataBuilder::new(
+            schema,
+            UnboundPartitionSpec::builder().build(),
+            SortOrder::unsorted_order(),
+            "s3://bucket/table".to_string(),
+            FormatVersion::V2,
+            HashMap::new(),
+        )
+        .unwrap()
+        .build()
+        .unwrap()
+        .metadata;
+        let base = metadata.last_updated_ms() + 1000;
+
+        let mut builder = metadata.into_builder(None);
+        for s in [
+            snapshot(1, None, base),
+            snapshot(2, Some(1), base + 1000),
+            snapshot(3, Some(2), base + 2000),
+        ] {
+            builder = builder.add_snapshot(s).unwrap();
+        }
+        builder = builder.set_ref(MAIN_BRANCH, branch(3)).unwrap();
+        for (name, reference) in refs {
+            builder = builder.set_ref(name, reference).unwrap();
+        }
+        (builder.build().unwrap().metadata, base)
+    }
+
+    #[test]
+    fn test_expire_old_ancestors() {
+        let (metadata, base) = metadata(vec![]);
+
+        // Ages: 1 -> 6500, 2 -> 5500, 3 -> 4500
+        let plan = plan_expiration(&metadata, &POLICY, base + 6500);
+        assert_eq!(plan.expired_snapshot_ids, vec![1, 2]);
+        assert!(plan.removed_refs.is_empty());
+
+        // The head of a branch is always kept
+        let plan = plan_expiration(&metadata, &POLICY, base + 100_000);
+        assert_eq!(plan.expired_snapshot_ids, vec![1, 2]);
+
+        let policy = RetentionPolicy {
+            min_snapshots_to_keep: 2,
+            ..POLICY
+        };
+        let plan = plan_expiration(&metadata, &policy, base + 100_000);
+        assert_eq!(plan.expired_snapshot_ids, vec![1]);
+
+        let plan = plan_expiration(&metadata, &POLICY, base + 5000);
+        assert!(plan.is_empty());
+    }
+
+    #[test]
+    fn test_branch_retention_overrides_policy() {
+        let (metadata, base) = metadata(vec![(
+            MAIN_BRANCH,
+            SnapshotReference {
+                snapshot_id: 3,
+                retention: SnapshotRetention::Branch {
+                    min_snapshots_to_keep: Some(3),
+                    max_snapshot_age_ms: None,
+                    max_ref_age_ms: None,
+                },
+            },
+        )]);
+
+        let plan = plan_expiration(&metadata, &POLICY, base + 100_000);
+        assert!(plan.is_empty());
+    }
+
+    #[test]
+    fn test_tags_keep_snapshots() {
+        let (metadata, base) = metadata(vec![(
+            "release",
+            SnapshotReference {
+                snapshot_id: 1,
+                retention: SnapshotRetention::Tag {
+                    max_ref_age_ms: None,
+                },
+            },
+        )]);
+
+        let plan = plan_expiration(&metadata, &POLICY, base + 100_000);
+        assert_eq!(plan.expired_snapshot_ids, vec![2]);
+    }
+
+    #[test]
+    fn test_remove_old_refs() {
+        let (metadata, base) = metadata(vec![
+            (
+                "release",
+                SnapshotReference {
+                    snapshot_id: 1,
+                    retention: SnapshotRetention::Tag {
+                        max_ref_age_ms: Some(10_000),
+                    },
+                },
+            ),
+            ("audit", branch(2)),
+        ]);
+
+        let policy = RetentionPolicy {
+            max_ref_age_ms: Some(3000),
+            ..POLICY
+        };
+        // Ages: 1 -> 9000, 2 -> 8000. `release` overrides the ref age of the policy.
+        let plan = plan_expiration(&metadata, &policy, base + 9000);
+        assert_eq!(plan.removed_refs, vec!["audit".to_string()]);
+        assert_eq!(plan.expired_snapshot_ids, vec![2]);
+
+        // `main` is never removed
+        let plan = plan_expiration(&metadata, &policy, base + 100_000);
+        assert_eq!(
+            plan.removed_refs,
+            vec!["audit".to_string(), "release".to_string()]
+        );
+        assert_eq!(plan.expired_snapshot_ids, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_unreferenced_snapshots_expire_by_age() {
+        // Snapshots 4 and 5 are not referenced by any ref, i.e. after a rollback of `main`
+        let (metadata, base) = metadata(vec![]);
+        let metadata = metadata
+            .into_builder(None)
+            .add_snapshot(snapshot(4, Some(3), base + 500))
+            .unwrap()
+            .add_snapshot(snapshot(5, Some(3), base + 3000))
+            .unwrap()
+            .build()
+            .unwrap()
+            .metadata;
+
+        let plan = plan_expiration(&metadata, &POLICY, base + 7500);
+        assert_eq!(plan.expired_snapshot_ids, vec![1, 2, 4]);
+    }
+
+    #[test]
+    fn test_table_properties_override_config() {
+        let config = SnapshotExpirationQueueConfig {
+            max_ref_age_ms: Some(1000),
+            ..Default::default()
+        };
+        let properties = HashMap::from_iter([
+            (
+                "history.expire.max-snapshot-age-ms".to_string(),
+                "60000".to_string(),
+            ),
+            (
+                "history.expire.min-snapshots-to-keep".to_string(),
+                "5".to_string(),
+            ),
+        ]);
+        let policy = RetentionPolicy::resolve(&config, &properties).unwrap();
+        assert_eq!(
+            policy,
+            RetentionPolicy {
+                max_snapshot_age_ms: 60000,
+                min_snapshots_to_keep: 5,
+                max_ref_age_ms: Some(1000),
+            }
+        );
+
+        let properties = HashMap::from_iter([(
+            "history.expire.max-snapshot-age-ms".to_string(),
+            "5 days".to_string(),
+        )]);
+        let error = RetentionPolicy::resolve(&config, &properties).unwrap_err();
+        assert_eq!(error.error.r#type, "InvalidRetentionProperty");
+    }
+}
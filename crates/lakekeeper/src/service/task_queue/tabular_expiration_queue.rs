@@ -6,14 +6,13 @@ use tracing::Instrument;
 use utoipa::{PartialSchema, ToSchema};
 use uuid::Uuid;
 
-use super::{
-    EntityId, QueueApiConfig, QueueConfig, TaskMetadata, DEFAULT_MAX_TIME_SINCE_LAST_HEARTBEAT,
-};
+use super::{QueueApiConfig, QueueConfig, TaskMetadata, DEFAULT_MAX_TIME_SINCE_LAST_HEARTBEAT};
 use crate::{
     api::{
         management::v1::{DeleteKind, TabularType},
         Result,
     },
+    request_metadata::RequestMetadata,
     service::{
         authz::Authorizer,
         task_queue::{tabular_purge_queue::TabularPurgePayload, Task},
@@ -82,7 +81,7 @@ pub(crate) async fn tabular_expiration_worker<C: Catalog, A: Authorizer>(
         }
         .unwrap_or_default();
 
-        let EntityId::Tabular(tabular_id) = expiration.task_metadata.entity_id;
+        let tabular_id = expiration.task_metadata.entity_id.to_uuid();
 
         let span = tracing::debug_span!(
             QUEUE_NAME,
@@ -193,6 +192,10 @@ where
         }
     };
 
+    // Namespaces that were dropped together with the tabular are removed once empty.
+    let dropped_namespaces =
+        C::drop_emptied_namespaces(task.task_metadata.warehouse_id, trx.transaction()).await?;
+
     if matches!(expiration.deletion_kind, DeleteKind::Purge) {
         C::queue_tabular_purge(
             TaskMetadata {
@@ -216,5 +219,19 @@ where
         e
     })?;
 
+    for namespace_id in dropped_namespaces {
+        authorizer
+            .delete_namespace(&RequestMetadata::new_internal(), namespace_id)
+            .await
+            .inspect_err(|e| {
+                tracing::error!(
+                    ?e,
+                    "Failed to delete namespace from authorizer: {}",
+                    e.error
+                );
+            })
+            .ok();
+    }
+
     Ok(())
 }
@@ -0,0 +1,236 @@
+//! Cache hints for clients and the maximum token age of sensitive endpoints.
+//!
+//! Responses of `GET /catalog/v1/config` and `GET /management/v1/whoami` carry the
+//! `x-lakekeeper-cache-hint` header, a structured dictionary (RFC 8941) that tells clients
+//! how long the config and the validation of their token may be reused:
+//!
+//! * `config-max-age`: seconds the config response may be cached.
+//! * `token-max-age`: seconds the token may be used without asking for a new one. It never
+//!   exceeds the expiry of the token, the refresh interval of the JWKS or the remaining
+//!   age of the token on sensitive endpoints.
+//! * `jwks-refresh`: seconds after which keys of the `OpenID` provider are reloaded.
+//!
+//! If `CONFIG.max_token_age_seconds` is set, sensitive endpoints reject tokens that were
+//! issued longer ago, so that a leaked token can't be used to change permissions or the
+//! setup of the server for its whole lifetime.
+use axum::{
+    extract::Request,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use http::{header, HeaderValue, Method};
+use iceberg_ext::catalog::rest::{ErrorModel, IcebergErrorResponse};
+use limes::Authentication;
+
+use crate::{
+    api::endpoints::{CatalogV1Endpoint, Endpoint, ManagementV1Endpoint},
+    request_metadata::RequestMetadata,
+    service::authn::JWKS_REFRESH_INTERVAL,
+    CONFIG,
+};
+
+pub const CACHE_HINT_HEADER: &str = "x-lakekeeper-cache-hint";
+
+/// Endpoints that change permissions or the setup of the server, i.e. all
+/// management and permission endpoints that are not read-only.
+#[must_use]
+pub fn is_sensitive_endpoint(endpoint: Endpoint) -> bool {
+    matches!(
+        endpoint,
+        Endpoint::ManagementV1(_) | Endpoint::PermissionV1(_)
+    ) && !matches!(endpoint.method(), Method::GET | Method::HEAD)
+}
+
+/// Middleware enforcing the maximum token age and adding cache hints.
+///
+/// This middleware needs to run after [`auth_middleware_fn`](crate::service::authn::auth_middleware_fn).
+pub(crate) async fn token_cache_middleware_fn(request: Request, next: Next) -> Response {
+    let Some(metadata) = request.extensions().get::<RequestMetadata>() else {
+        return next.run(request).await;
+    };
+    let Some(endpoint) = metadata.matched_path().and_then(|matched_path| {
+        Endpoint::from_method_and_matched_path(metadata.request_method(), matched_path)
+    }) else {
+        return next.run(request).await;
+    };
+    let authentication = metadata.authentication().cloned();
+    let now = chrono::Utc::now().timestamp();
+
+    if let (Some(max_age), Some(authentication)) =
+        (CONFIG.max_token_age_seconds, authentication.as_ref())
+    {
+        if is_sensitive_endpoint(endpoint) {
+            if let Err(e) = check_token_age(authentication, max_age, now) {
+                return IcebergErrorResponse::from(e).into_response();
+            }
+        }
+    }
+
+    let with_hint = matches!(
+        endpoint,
+        Endpoint::CatalogV1(CatalogV1Endpoint::GetConfig)
+            | Endpoint::ManagementV1(ManagementV1Endpoint::Whoami)
+    );
+    let mut response = next.run(request).await;
+    if with_hint && response.status().is_success() {
+        let hint = CacheHint::new(authentication.as_ref(), now);
+        let headers = response.headers_mut();
+        if let Ok(value) = HeaderValue::from_str(&hint.to_string()) {
+            headers.insert(CACHE_HINT_HEADER, value);
+        }
+        if let Ok(value) =
+            HeaderValue::from_str(&format!("private, max-age={}", hint.config_max_age))
+        {
+            headers.insert(header::CACHE_CONTROL, value);
+        }
+    }
+    response
+}
+
+/// Reject tokens issued more than `max_age` seconds ago, or without an `iat` claim.
+fn check_token_age(
+    authentication: &Authentication,
+    max_age: u64,
+    now: i64,
+) -> Result<(), ErrorModel> {
+    let Some(issued_at) = claim_timestamp(authentication, "iat") else {
+        return Err(ErrorModel::unauthorized(
+            "Token has no `iat` claim and can't be used for this endpoint",
+            "TokenAgeUnknown",
+            None,
+        ));
+    };
+    let age = now.saturating_sub(issued_at);
+    if age > i64::try_from(max_age).unwrap_or(i64::MAX) {
+        return Err(ErrorModel::unauthorized(
+            format!(
+                "Token was issued {age} seconds ago. This endpoint requires a token not older than {max_age} seconds."
+            ),
+            "TokenTooOld",
+            None,
+        ));
+    }
+    Ok(())
+}
+
+fn claim_timestamp(authentication: &Authentication, claim: &str) -> Option<i64> {
+    authentication.claims().get(claim)?.as_i64()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CacheHint {
+    config_max_age: u64,
+    token_max_age: Option<u64>,
+    jwks_refresh: Option<u64>,
+}
+
+impl CacheHint {
+    fn new(authentication: Option<&Authentication>, now: i64) -> Self {
+        let jwks_refresh = CONFIG
+            .openid_provider_uri
+            .is_some()
+            .then_some(JWKS_REFRESH_INTERVAL.as_secs());
+        let token_max_age = authentication.map(|authentication| {
+            let mut max_age = JWKS_REFRESH_INTERVAL.as_secs();
+            if let Some(expires_at) = claim_timestamp(authentication, "exp") {
+                max_age = max_age.min(seconds_until(expires_at, now));
+            }
+            if let (Some(max_token_age), Some(issued_at)) = (
+                CONFIG.max_token_age_seconds,
+                claim_timestamp(authentication, "iat"),
+            ) {
+                let deadline =
+                    issued_at.saturating_add(i64::try_from(max_token_age).unwrap_or(i64::MAX));
+                max_age = max_age.min(seconds_until(deadline, now));
+            }
+            max_age
+        });
+        Self {
+            config_max_age: CONFIG.shared_cache_ttl_seconds,
+            token_max_age,
+            jwks_refresh,
+        }
+    }
+}
+
+fn seconds_until(timestamp: i64, now: i64) -> u64 {
+    u64::try_from(timestamp.saturating_sub(now)).unwrap_or(0)
+}
+
+impl std::fmt::Display for CacheHint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "config-max-age={}", self.config_max_age)?;
+        if let Some(token_max_age) = self.token_max_age {
+            write!(f, ", token-max-age={token_max_age}")?;
+        }
+        if let Some(jwks_refresh) = self.jwks_refresh {
+            write!(f, ", jwks-refresh={jwks_refresh}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{api::endpoints::PermissionV1Endpoint, service::UserId};
+
+    fn authentication(claims: serde_json::Value) -> Authentication {
+        Authentication::builder()
+            .token_header(None)
+            .claims(claims)
+            .subject(UserId::new_unchecked("oidc", "user").into())
+            .name(None)
+            .email(None)
+            .principal_type(None)
+            .build()
+    }
+
+    #[test]
+    fn test_sensitive_endpoints() {
+        assert!(!is_sensitive_endpoint(Endpoint::ManagementV1(
+            ManagementV1Endpoint::Whoami
+        )));
+        assert!(!is_sensitive_endpoint(Endpoint::CatalogV1(
+            CatalogV1Endpoint::CreateTable
+        )));
+        assert!(!is_sensitive_endpoint(PermissionV1Endpoint::Get.into()));
+        assert!(is_sensitive_endpoint(Endpoint::ManagementV1(
+            ManagementV1Endpoint::CreateWarehouse
+        )));
+        assert!(is_sensitive_endpoint(PermissionV1Endpoint::Post.into()));
+    }
+
+    #[test]
+    fn test_check_token_age() {
+        let now = 1_700_000_000;
+        let fresh = authentication(serde_json::json!({"iat": now - 60}));
+        assert!(check_token_age(&fresh, 300, now).is_ok());
+
+        let old = authentication(serde_json::json!({"iat": now - 600}));
+        let err = check_token_age(&old, 300, now).unwrap_err();
+        assert_eq!(err.r#type, "TokenTooOld");
+
+        let unknown = authentication(serde_json::json!({}));
+        let err = check_token_age(&unknown, 300, now).unwrap_err();
+        assert_eq!(err.r#type, "TokenAgeUnknown");
+    }
+
+    #[test]
+    fn test_token_max_age_is_capped_by_expiry() {
+        let now = 1_700_000_000;
+        let expiring = authentication(serde_json::json!({"exp": now + 120}));
+        let hint = CacheHint::new(Some(&expiring), now);
+        assert_eq!(hint.token_max_age, Some(120));
+        assert!(hint.to_string().starts_with(&format!(
+            "config-max-age={}, token-max-age=120",
+            CONFIG.shared_cache_ttl_seconds
+        )));
+
+        let expired = authentication(serde_json::json!({"exp": now - 10}));
+        assert_eq!(CacheHint::new(Some(&expired), now).token_max_age, Some(0));
+
+        let unauthenticated = CacheHint::new(None, now);
+        assert_eq!(unauthenticated.token_max_age, None);
+    }
+}
@@ -0,0 +1,220 @@
+//! Usage of warehouses over time, for example for internal chargeback.
+//!
+//! The [`usage_aggregation_worker`] rolls the endpoint statistics of each warehouse up into
+//! one row per day and records the number of tables, views and snapshots. Counts of entities
+//! are updated while the day is ongoing, so they reflect the end of the day once it is over.
+//! Usage is kept until the warehouse is deleted, independently of the endpoint statistics.
+use std::time::Duration;
+
+use chrono::{Datelike, Days, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{Catalog, Result};
+
+/// Bounds how far the usage of the current day lags behind the endpoint statistics.
+const AGGREGATION_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// Usage of a warehouse on a single day (UTC) as stored by the catalog.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WarehouseUsageDay {
+    pub warehouse_id: uuid::Uuid,
+    pub warehouse_name: String,
+    pub day: NaiveDate,
+    pub api_calls: i64,
+    pub sign_requests: i64,
+    pub tables: i64,
+    pub views: i64,
+    pub snapshots: i64,
+}
+
+/// Length of the periods usage is aggregated in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum UsageGranularity {
+    #[default]
+    Day,
+    Month,
+}
+
+impl UsageGranularity {
+    /// First day of the period containing `day`.
+    pub(crate) fn period_start(self, day: NaiveDate) -> NaiveDate {
+        match self {
+            UsageGranularity::Day => day,
+            UsageGranularity::Month => day.with_day(1).unwrap_or(day),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct WarehouseUsage {
+    pub warehouse_id: uuid::Uuid,
+    pub warehouse_name: String,
+    /// First day of the period (UTC)
+    pub period_start: NaiveDate,
+    /// Number of requests to the Iceberg REST and management API targeting the warehouse
+    pub api_calls: i64,
+    /// Number of remote signing requests for data access
+    pub sign_requests: i64,
+    /// Tables at the end of the period, excluding soft-deleted tables
+    pub tables: i64,
+    /// Views at the end of the period, excluding soft-deleted views
+    pub views: i64,
+    /// Snapshots of all tables at the end of the period
+    pub snapshots: i64,
+    /// Change in the number of tables compared to the end of the previous period.
+    /// `null` if no usage was recorded for the previous period.
+    pub tables_added: Option<i64>,
+    /// Change in the number of snapshots compared to the end of the previous period.
+    /// `null` if no usage was recorded for the previous period.
+    pub snapshots_added: Option<i64>,
+}
+
+/// Combine daily usage into periods of `granularity`, starting with the period containing
+/// `start`. Days before `start` are only used to compute the growth of the first period.
+/// `days` must be ordered by warehouse and day.
+pub(crate) fn summarize_usage(
+    days: Vec<WarehouseUsageDay>,
+    granularity: UsageGranularity,
+    start: NaiveDate,
+) -> Vec<WarehouseUsage> {
+    let mut periods: Vec<WarehouseUsage> = Vec::new();
+    for day in days {
+        let period_start = granularity.period_start(day.day);
+        match periods.last_mut() {
+            Some(last)
+                if last.warehouse_id == day.warehouse_id && last.period_start == period_start =>
+            {
+                last.api_calls += day.api_calls;
+                last.sign_requests += day.sign_requests;
+                last.tables = day.tables;
+                last.views = day.views;
+                last.snapshots = day.snapshots;
+            }
+            _ => {
+                periods.push(WarehouseUsage {
+                    warehouse_id: day.warehouse_id,
+                    warehouse_name: day.warehouse_name,
+                    period_start,
+                    api_calls: day.api_calls,
+                    sign_requests: day.sign_requests,
+                    tables: day.tables,
+                    views: day.views,
+                    snapshots: day.snapshots,
+                    tables_added: None,
+                    snapshots_added: None,
+                });
+            }
+        }
+    }
+
+    // Growth is relative to the end of the previous period, so it is computed once all
+    // days of a period have been added.
+    let mut result: Vec<WarehouseUsage> = Vec::with_capacity(periods.len());
+    for period in periods {
+        let previous = result
+            .last()
+            .filter(|p| p.warehouse_id == period.warehouse_id);
+        let tables_added = previous.map(|p| period.tables - p.tables);
+        let snapshots_added = previous.map(|p| period.snapshots - p.snapshots);
+        result.push(WarehouseUsage {
+            tables_added,
+            snapshots_added,
+            ..period
+        });
+    }
+    let first_period = granularity.period_start(start);
+    result.retain(|p| p.period_start >= first_period);
+    result
+}
+
+/// Infinitely running worker that aggregates the usage of all warehouses.
+/// Runs on every instance, as aggregating a day again replaces its previous result.
+pub async fn usage_aggregation_worker<C: Catalog>(catalog_state: C::State) {
+    loop {
+        if let Err(e) = aggregate_usage::<C>(catalog_state.clone()).await {
+            tracing::error!(?e, "Failed to aggregate warehouse usage: {}", e.error);
+        }
+        tokio::time::sleep(AGGREGATION_INTERVAL).await;
+    }
+}
+
+async fn aggregate_usage<C: Catalog>(catalog_state: C::State) -> Result<()> {
+    let today = Utc::now().date_naive();
+    // Requests of the last hour of yesterday are only flushed after midnight.
+    if let Some(yesterday) = today.checked_sub_days(Days::new(1)) {
+        C::aggregate_warehouse_usage(yesterday, false, catalog_state.clone()).await?;
+    }
+    C::aggregate_warehouse_usage(today, true, catalog_state).await
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn day(warehouse_id: uuid::Uuid, day: &str, api_calls: i64, tables: i64) -> WarehouseUsageDay {
+        WarehouseUsageDay {
+            warehouse_id,
+            warehouse_name: "warehouse".to_string(),
+            day: NaiveDate::parse_from_str(day, "%Y-%m-%d").unwrap(),
+            api_calls,
+            sign_requests: 1,
+            tables,
+            views: 0,
+            snapshots: tables * 10,
+        }
+    }
+
+    #[test]
+    fn test_summarize_usage_by_day() {
+        let warehouse_id = uuid::Uuid::now_v7();
+        let start = NaiveDate::from_ymd_opt(2025, 1, 2).unwrap();
+        let usage = summarize_usage(
+            vec![
+                day(warehouse_id, "2025-01-01", 5, 1),
+                day(warehouse_id, "2025-01-02", 10, 3),
+                day(warehouse_id, "2025-01-03", 20, 2),
+            ],
+            UsageGranularity::Day,
+            start,
+        );
+        assert_eq!(usage.len(), 2);
+        assert_eq!(usage[0].period_start, start);
+        assert_eq!(usage[0].api_calls, 10);
+        assert_eq!(usage[0].tables_added, Some(2));
+        assert_eq!(usage[0].snapshots_added, Some(20));
+        assert_eq!(usage[1].tables_added, Some(-1));
+    }
+
+    #[test]
+    fn test_summarize_usage_by_month() {
+        let warehouse_1 = uuid::Uuid::from_u128(1);
+        let warehouse_2 = uuid::Uuid::from_u128(2);
+        let start = NaiveDate::from_ymd_opt(2025, 2, 10).unwrap();
+        let usage = summarize_usage(
+            vec![
+                day(warehouse_1, "2025-01-31", 5, 1),
+                day(warehouse_1, "2025-02-01", 10, 2),
+                day(warehouse_1, "2025-02-28", 10, 4),
+                day(warehouse_1, "2025-03-01", 1, 4),
+                day(warehouse_2, "2025-02-15", 7, 1),
+            ],
+            UsageGranularity::Month,
+            start,
+        );
+        assert_eq!(usage.len(), 3);
+        assert_eq!(
+            usage[0].period_start,
+            NaiveDate::from_ymd_opt(2025, 2, 1).unwrap()
+        );
+        assert_eq!(usage[0].api_calls, 20);
+        assert_eq!(usage[0].sign_requests, 2);
+        assert_eq!(usage[0].tables, 4);
+        assert_eq!(usage[0].tables_added, Some(3));
+        assert_eq!(usage[1].tables_added, Some(0));
+        // No usage recorded before the first period of the second warehouse
+        assert_eq!(usage[2].warehouse_id, warehouse_2);
+        assert_eq!(usage[2].tables_added, None);
+    }
+}
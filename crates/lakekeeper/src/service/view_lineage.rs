@@ -0,0 +1,422 @@
+//! Source tables of views.
+//!
+//! When a view is created or replaced, the SQL of each representation of each version is
+//! scanned for relations following `FROM` and `JOIN`. The identifiers are resolved against the
+//! default namespace of the version and stored with the version, so that the lineage of a view
+//! can be listed and dropping a table that is still referenced by views can be detected.
+//!
+//! The scan is not a full SQL parser. It understands quoted identifiers, comments, string
+//! literals, subqueries and common table expressions, which covers the views written by
+//! Spark, Trino and most other engines. References it can't resolve are skipped.
+use std::collections::BTreeSet;
+
+use iceberg::{
+    spec::{SqlViewRepresentation, ViewRepresentation, ViewVersion},
+    NamespaceIdent, TableIdent,
+};
+use serde::Serialize;
+
+use super::TableId;
+
+/// Source table of a view, resolved within the warehouse of the view.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ViewLineageSource {
+    /// Namespace of the referenced table
+    #[schema(value_type = Vec<String>)]
+    pub namespace: NamespaceIdent,
+    /// Name of the referenced table
+    pub name: String,
+    /// Id of the referenced table. Not set if no table with this name exists in the
+    /// warehouse, e.g. because it was dropped or belongs to another catalog.
+    #[schema(value_type = Option<uuid::Uuid>)]
+    pub table_id: Option<TableId>,
+}
+
+/// View whose current version references a table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReferencingView {
+    pub view_id: uuid::Uuid,
+    pub namespace: NamespaceIdent,
+    pub name: String,
+}
+
+/// Tables referenced by any representation of a view version, sorted and deduplicated.
+#[must_use]
+pub fn view_version_sources(version: &ViewVersion) -> Vec<TableIdent> {
+    version
+        .representations()
+        .iter()
+        .flat_map(|representation| {
+            let ViewRepresentation::Sql(SqlViewRepresentation { sql, .. }) = representation;
+            referenced_tables(
+                sql,
+                version.default_namespace(),
+                version.default_catalog().map(String::as_str),
+            )
+        })
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter_map(|(namespace, name)| {
+            Some(TableIdent::new(
+                NamespaceIdent::from_vec(namespace).ok()?,
+                name,
+            ))
+        })
+        .collect()
+}
+
+/// Namespace parts and name of a referenced table
+type TableName = (Vec<String>, String);
+
+/// Tables referenced by `sql`. Unqualified names are resolved against `default_namespace`.
+/// A leading part equal to `default_catalog` is removed from qualified names.
+fn referenced_tables(
+    sql: &str,
+    default_namespace: &NamespaceIdent,
+    default_catalog: Option<&str>,
+) -> BTreeSet<TableName> {
+    let tokens = tokenize(sql);
+    let cte_names = cte_names(&tokens);
+    let query_scopes = query_scopes(&tokens);
+
+    let mut tables = BTreeSet::new();
+    for (i, token) in tokens.iter().enumerate() {
+        if !query_scopes[i] || !(token.is_keyword("from") || token.is_keyword("join")) {
+            continue;
+        }
+        let mut pos = i + 1;
+        loop {
+            let Some((parts, next)) = relation_name(&tokens, pos) else {
+                break;
+            };
+            if let Some(table) =
+                resolve_table(parts, default_namespace, default_catalog, &cte_names)
+            {
+                tables.insert(table);
+            }
+            // Continue with the next relation of comma separated `FROM` lists
+            pos = skip_alias(&tokens, next);
+            if !matches!(tokens.get(pos), Some(Token::Comma)) {
+                break;
+            }
+            pos += 1;
+        }
+    }
+    tables
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Word { value: String, quoted: bool },
+    Dot,
+    Comma,
+    LParen,
+    RParen,
+    Other,
+}
+
+impl Token {
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self, Token::Word { value, quoted: false } if value.eq_ignore_ascii_case(keyword))
+    }
+}
+
+/// Words that end a relation instead of being its alias.
+const CLAUSE_KEYWORDS: &[&str] = &[
+    "where",
+    "join",
+    "inner",
+    "left",
+    "right",
+    "full",
+    "outer",
+    "cross",
+    "natural",
+    "on",
+    "using",
+    "group",
+    "order",
+    "having",
+    "limit",
+    "offset",
+    "union",
+    "intersect",
+    "except",
+    "window",
+    "qualify",
+    "lateral",
+    "tablesample",
+    "for",
+    "pivot",
+    "unpivot",
+    "fetch",
+];
+
+fn tokenize(sql: &str) -> Vec<Token> {
+    let chars = sql.chars().collect::<Vec<_>>();
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            c if c.is_whitespace() => i += 1,
+            '-' if chars.get(i + 1) == Some(&'-') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                i += 2;
+            }
+            '\'' => {
+                let (_, next) = quoted(&chars, i, '\'');
+                tokens.push(Token::Other);
+                i = next;
+            }
+            '"' | '`' => {
+                let (value, next) = quoted(&chars, i, c);
+                tokens.push(Token::Word {
+                    value,
+                    quoted: true,
+                });
+                i = next;
+            }
+            '.' => {
+                tokens.push(Token::Dot);
+                i += 1;
+            }
+            ',' => {
+                tokens.push(Token::Comma);
+                i += 1;
+            }
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            c if c.is_alphanumeric() || c == '_' || c == '$' => {
+                let start = i;
+                while i < chars.len()
+                    && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '$')
+                {
+                    i += 1;
+                }
+                tokens.push(Token::Word {
+                    value: chars[start..i].iter().collect(),
+                    quoted: false,
+                });
+            }
+            _ => {
+                tokens.push(Token::Other);
+                i += 1;
+            }
+        }
+    }
+    tokens
+}
+
+/// Content of the quoted section starting at `start` and the position after it.
+/// A doubled quote character is an escaped quote.
+fn quoted(chars: &[char], start: usize, quote: char) -> (String, usize) {
+    let mut value = String::new();
+    let mut i = start + 1;
+    while i < chars.len() {
+        if chars[i] == quote {
+            if chars.get(i + 1) == Some(&quote) {
+                value.push(quote);
+                i += 2;
+                continue;
+            }
+            return (value, i + 1);
+        }
+        value.push(chars[i]);
+        i += 1;
+    }
+    (value, i)
+}
+
+/// Whether each token is part of a query rather than of an expression in parentheses.
+/// Keeps `FROM` in expressions such as `extract(year FROM ts)` from being read as a relation.
+fn query_scopes(tokens: &[Token]) -> Vec<bool> {
+    let mut stack = vec![true];
+    tokens
+        .iter()
+        .enumerate()
+        .map(|(i, token)| {
+            let in_query = *stack.last().unwrap_or(&true);
+            match token {
+                Token::LParen => {
+                    let next = tokens.get(i + 1);
+                    stack.push(next.is_some_and(|next| {
+                        next.is_keyword("select")
+                            || next.is_keyword("with")
+                            || *next == Token::LParen
+                    }));
+                }
+                Token::RParen if stack.len() > 1 => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+            in_query
+        })
+        .collect()
+}
+
+/// Names defined by common table expressions, i.e. `<name> AS (`.
+fn cte_names(tokens: &[Token]) -> BTreeSet<String> {
+    tokens
+        .windows(3)
+        .filter_map(|window| match window {
+            [Token::Word { value, .. }, r#as, Token::LParen] if r#as.is_keyword("as") => {
+                Some(value.to_lowercase())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Dotted name starting at `pos` and the position after it. Subqueries and
+/// table functions are not relation names.
+fn relation_name(tokens: &[Token], pos: usize) -> Option<(Vec<String>, usize)> {
+    let mut parts = vec![];
+    let mut pos = pos;
+    loop {
+        match tokens.get(pos) {
+            Some(Token::Word { value, quoted }) => {
+                if !quoted && parts.is_empty() && value.eq_ignore_ascii_case("lateral") {
+                    return None;
+                }
+                parts.push(value.clone());
+                pos += 1;
+            }
+            _ => return None,
+        }
+        if tokens.get(pos) == Some(&Token::Dot) {
+            pos += 1;
+        } else {
+            break;
+        }
+    }
+    if tokens.get(pos) == Some(&Token::LParen) {
+        return None;
+    }
+    Some((parts, pos))
+}
+
+fn skip_alias(tokens: &[Token], pos: usize) -> usize {
+    let mut pos = pos;
+    if tokens.get(pos).is_some_and(|token| token.is_keyword("as")) {
+        pos += 1;
+    }
+    match tokens.get(pos) {
+        Some(token @ Token::Word { .. })
+            if !CLAUSE_KEYWORDS
+                .iter()
+                .any(|keyword| token.is_keyword(keyword)) =>
+        {
+            pos + 1
+        }
+        _ => pos,
+    }
+}
+
+fn resolve_table(
+    mut parts: Vec<String>,
+    default_namespace: &NamespaceIdent,
+    default_catalog: Option<&str>,
+    cte_names: &BTreeSet<String>,
+) -> Option<TableName> {
+    if parts.len() == 1 && cte_names.contains(&parts[0].to_lowercase()) {
+        return None;
+    }
+    if parts.len() > 2
+        && default_catalog.is_some_and(|catalog| parts[0].eq_ignore_ascii_case(catalog))
+    {
+        parts.remove(0);
+    }
+    let name = parts.pop()?;
+    let namespace = if parts.is_empty() {
+        default_namespace.clone().inner()
+    } else {
+        parts
+    };
+    if namespace.is_empty() {
+        return None;
+    }
+    Some((namespace, name))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn tables(sql: &str) -> Vec<String> {
+        referenced_tables(
+            sql,
+            &NamespaceIdent::from_strs(["default_ns"]).unwrap(),
+            Some("my_catalog"),
+        )
+        .into_iter()
+        .map(|(namespace, name)| format!("{}.{name}", namespace.join(".")))
+        .collect()
+    }
+
+    #[test]
+    fn test_referenced_tables() {
+        assert_eq!(tables("SELECT * FROM t"), vec!["default_ns.t"]);
+        assert_eq!(
+            tables("select a.id from ns.a a join `ns`.\"b\" AS b on a.id = b.id"),
+            vec!["ns.a", "ns.b"]
+        );
+        assert_eq!(
+            tables("SELECT * FROM my_catalog.ns.t, other_catalog.ns.u x, v"),
+            vec!["default_ns.v", "ns.t", "other_catalog.ns.u"]
+        );
+    }
+
+    #[test]
+    fn test_referenced_tables_ignores_non_relations() {
+        assert_eq!(
+            tables(
+                "WITH recent AS (SELECT * FROM ns.events WHERE ts > '2024-01-01 FROM x')
+                -- FROM commented
+                SELECT extract(year FROM ts), (SELECT max(id) FROM ns.dim) /* FROM y */
+                FROM recent JOIN LATERAL explode(tags) JOIN range(10) r ON true"
+            ),
+            vec!["ns.dim", "ns.events"]
+        );
+        assert!(tables("SELECT 1").is_empty());
+    }
+
+    #[test]
+    fn test_view_version_sources_of_all_dialects() {
+        let version: ViewVersion = serde_json::from_value(serde_json::json!({
+            "version-id": 1,
+            "schema-id": 0,
+            "timestamp-ms": 1_719_559_079_091_i64,
+            "summary": {},
+            "representations": [
+                {"type": "sql", "sql": "select id from t", "dialect": "spark"},
+                {"type": "sql", "sql": "select id from \"ns\".\"t\" join u on true", "dialect": "trino"}
+            ],
+            "default-namespace": ["ns"]
+        }))
+        .unwrap();
+        assert_eq!(
+            view_version_sources(&version),
+            vec![
+                TableIdent::from_strs(["ns", "t"]).unwrap(),
+                TableIdent::from_strs(["ns", "u"]).unwrap(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,236 @@
+//! Prometheus metrics labeled with the project and warehouse.
+//!
+//! To bound the cardinality of the labels, only the first `CONFIG.metrics_max_warehouses`
+//! warehouses, ordered by project and warehouse id, are labeled individually. Metrics of all
+//! other warehouses are aggregated with the label value `other`.
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{LazyLock, RwLock},
+    time::{Duration, Instant},
+};
+
+use axum::{
+    extract::{Path, Request},
+    middleware::Next,
+    response::Response,
+};
+use axum_prometheus::metrics;
+
+use super::{endpoint_policy::warehouse_id_from_path, Catalog};
+use crate::{ProjectId, WarehouseId, CONFIG};
+
+const REPORT_INTERVAL: Duration = Duration::from_secs(60);
+
+const HTTP_REQUESTS: &str = "lakekeeper_warehouse_http_requests_total";
+const HTTP_REQUEST_DURATION: &str = "lakekeeper_warehouse_http_request_duration_seconds";
+const NAMESPACES: &str = "lakekeeper_warehouse_namespaces";
+const TABLES: &str = "lakekeeper_warehouse_tables";
+const VIEWS: &str = "lakekeeper_warehouse_views";
+const PENDING_SOFT_DELETES: &str = "lakekeeper_warehouse_pending_soft_deletes";
+const REPORT_ERRORS: &str = "lakekeeper_warehouse_metrics_errors_total";
+
+/// Label value of warehouses and projects exceeding the cardinality limit.
+const OTHER_LABEL: &str = "other";
+/// Label value of requests that don't target a warehouse.
+const NONE_LABEL: &str = "none";
+
+/// Warehouses that are labeled individually, with their project.
+/// Updated by the [`warehouse_metrics_worker`].
+static LABELED_WAREHOUSES: LazyLock<RwLock<HashMap<WarehouseId, ProjectId>>> =
+    LazyLock::new(RwLock::default);
+
+/// Number of entities in an active warehouse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WarehouseEntityCounts {
+    pub project_id: ProjectId,
+    pub warehouse_id: WarehouseId,
+    /// Namespaces that are not soft-deleted.
+    pub namespaces: i64,
+    /// Tables that are not soft-deleted.
+    pub tables: i64,
+    /// Views that are not soft-deleted.
+    pub views: i64,
+    /// Soft-deleted namespaces, tables and views that are waiting for their expiration.
+    pub pending_soft_deletes: i64,
+}
+
+/// Middleware counting requests and their duration per project and warehouse.
+pub(crate) async fn warehouse_metrics_middleware_fn(
+    Path(path_params): Path<HashMap<String, String>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if CONFIG.metrics_max_warehouses == 0 {
+        return next.run(request).await;
+    }
+    let warehouse_id = warehouse_id_from_path(&path_params);
+
+    let start = Instant::now();
+    let response = next.run(request).await;
+
+    let (project, warehouse) = request_labels(warehouse_id);
+    let status = response.status().as_u16().to_string();
+    metrics::counter!(
+        HTTP_REQUESTS,
+        "project" => project.clone(),
+        "warehouse" => warehouse.clone(),
+        "status" => status
+    )
+    .increment(1);
+    metrics::histogram!(
+        HTTP_REQUEST_DURATION,
+        "project" => project,
+        "warehouse" => warehouse
+    )
+    .record(start.elapsed().as_secs_f64());
+
+    response
+}
+
+/// Project and warehouse label values of a request.
+fn request_labels(warehouse_id: Option<WarehouseId>) -> (String, String) {
+    let Some(warehouse_id) = warehouse_id else {
+        return (NONE_LABEL.to_string(), NONE_LABEL.to_string());
+    };
+    let labeled = LABELED_WAREHOUSES
+        .read()
+        .expect("Labeled warehouses lock poisoned");
+    match labeled.get(&warehouse_id) {
+        Some(project_id) => (project_id.to_string(), warehouse_id.to_string()),
+        None => (OTHER_LABEL.to_string(), OTHER_LABEL.to_string()),
+    }
+}
+
+/// Infinitely running worker that publishes the entity counts of all warehouses as
+/// Prometheus gauges. The counts are server-wide, so every instance reports the same values.
+pub async fn warehouse_metrics_worker<C: Catalog>(catalog_state: C::State) {
+    describe_metrics();
+    loop {
+        match C::get_warehouse_entity_counts(catalog_state.clone()).await {
+            Ok(counts) => report(counts),
+            Err(e) => {
+                metrics::counter!(REPORT_ERRORS).increment(1);
+                tracing::error!(?e, "Failed to collect warehouse statistics: {}", e.error);
+            }
+        }
+        tokio::time::sleep(REPORT_INTERVAL).await;
+    }
+}
+
+fn describe_metrics() {
+    metrics::describe_counter!(HTTP_REQUESTS, "Requests by project and warehouse");
+    metrics::describe_histogram!(
+        HTTP_REQUEST_DURATION,
+        metrics::Unit::Seconds,
+        "Duration of requests by project and warehouse"
+    );
+    metrics::describe_gauge!(NAMESPACES, "Namespaces of the warehouse");
+    metrics::describe_gauge!(TABLES, "Tables of the warehouse");
+    metrics::describe_gauge!(VIEWS, "Views of the warehouse");
+    metrics::describe_gauge!(
+        PENDING_SOFT_DELETES,
+        "Soft-deleted namespaces, tables and views waiting for their expiration"
+    );
+    metrics::describe_counter!(REPORT_ERRORS, "Failures to collect warehouse statistics");
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn report(counts: Vec<WarehouseEntityCounts>) {
+    let (labeled, other) = apply_cardinality_limit(counts, CONFIG.metrics_max_warehouses);
+
+    *LABELED_WAREHOUSES
+        .write()
+        .expect("Labeled warehouses lock poisoned") = labeled
+        .iter()
+        .map(|c| (c.warehouse_id, c.project_id.clone()))
+        .collect();
+
+    let labeled = labeled.into_iter().map(|c| {
+        let labels = [
+            ("project", c.project_id.to_string()),
+            ("warehouse", c.warehouse_id.to_string()),
+        ];
+        (labels, c)
+    });
+    let other = other.map(|c| {
+        let labels = [
+            ("project", OTHER_LABEL.to_string()),
+            ("warehouse", OTHER_LABEL.to_string()),
+        ];
+        (labels, c)
+    });
+    for (labels, c) in labeled.chain(other) {
+        metrics::gauge!(NAMESPACES, &labels).set(c.namespaces as f64);
+        metrics::gauge!(TABLES, &labels).set(c.tables as f64);
+        metrics::gauge!(VIEWS, &labels).set(c.views as f64);
+        metrics::gauge!(PENDING_SOFT_DELETES, &labels).set(c.pending_soft_deletes as f64);
+    }
+}
+
+/// Splits the counts into the first `limit` warehouses, ordered by project and warehouse id,
+/// and the sum of all remaining warehouses.
+fn apply_cardinality_limit(
+    counts: Vec<WarehouseEntityCounts>,
+    limit: usize,
+) -> (Vec<WarehouseEntityCounts>, Option<WarehouseEntityCounts>) {
+    let mut counts = counts
+        .into_iter()
+        .map(|c| ((c.project_id.to_string(), c.warehouse_id), c))
+        .collect::<BTreeMap<_, _>>()
+        .into_values()
+        .collect::<Vec<_>>();
+    if counts.len() <= limit {
+        return (counts, None);
+    }
+
+    let other = counts.split_off(limit).into_iter().reduce(|mut sum, c| {
+        sum.namespaces += c.namespaces;
+        sum.tables += c.tables;
+        sum.views += c.views;
+        sum.pending_soft_deletes += c.pending_soft_deletes;
+        sum
+    });
+    (counts, other)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn counts(project: u128, warehouse: u128, tables: i64) -> WarehouseEntityCounts {
+        WarehouseEntityCounts {
+            project_id: ProjectId::from(uuid::Uuid::from_u128(project)),
+            warehouse_id: WarehouseId::from(uuid::Uuid::from_u128(warehouse)),
+            namespaces: 1,
+            tables,
+            views: 0,
+            pending_soft_deletes: 0,
+        }
+    }
+
+    #[test]
+    fn test_cardinality_limit() {
+        let (labeled, other) =
+            apply_cardinality_limit(vec![counts(2, 1, 5), counts(1, 3, 2), counts(1, 2, 1)], 2);
+        assert_eq!(labeled, vec![counts(1, 2, 1), counts(1, 3, 2)]);
+        let other = other.unwrap();
+        assert_eq!(other.tables, 5);
+        assert_eq!(other.namespaces, 1);
+
+        let (labeled, other) = apply_cardinality_limit(vec![counts(1, 1, 1)], 2);
+        assert_eq!(labeled, vec![counts(1, 1, 1)]);
+        assert!(other.is_none());
+    }
+
+    #[test]
+    fn test_request_labels() {
+        assert_eq!(
+            request_labels(None),
+            (NONE_LABEL.to_string(), NONE_LABEL.to_string())
+        );
+        assert_eq!(
+            request_labels(Some(WarehouseId::new_random())),
+            (OTHER_LABEL.to_string(), OTHER_LABEL.to_string())
+        );
+    }
+}
@@ -0,0 +1,241 @@
+use std::{str::FromStr, sync::LazyLock, time::Duration};
+
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use http::{uri::PathAndQuery, Uri};
+use iceberg_ext::catalog::rest::{ErrorModel, IcebergErrorResponse};
+
+use crate::{api::Result, service::Catalog, WarehouseId};
+
+/// Path prefix of warehouse-scoped catalog URLs: `/catalog/w/{slug}/v1/...`
+const WAREHOUSE_SCOPED_PATH_PREFIX: &str = "/catalog/w/";
+const MAX_SLUG_LENGTH: usize = 63;
+
+/// Resolved slugs are cached for the routing middleware, so that requests to
+/// warehouse-scoped URLs don't require an additional database query.
+/// Unknown slugs are cached as well.
+static WAREHOUSE_SLUG_CACHE: LazyLock<moka::future::Cache<String, Option<ResolvedWarehouseSlug>>> =
+    LazyLock::new(|| {
+        moka::future::Cache::builder()
+            .max_capacity(10_000)
+            .time_to_live(Duration::from_secs(30))
+            .build()
+    });
+
+/// Warehouse a slug resolves to.
+///
+/// Inserted into the extensions of requests to warehouse-scoped URLs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedWarehouseSlug {
+    pub warehouse_id: WarehouseId,
+    /// Current slug of the warehouse. Differs from the slug of the request
+    /// if the request used a replaced slug.
+    pub slug: String,
+}
+
+/// A slug of a warehouse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WarehouseSlug {
+    pub slug: String,
+    /// Replaced slugs remain aliases of the warehouse.
+    pub is_current: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Validate a slug for warehouse-scoped URLs.
+///
+/// Slugs consist of lowercase ASCII letters, digits and hyphens, must start and end
+/// with a letter or digit, and are at most 63 characters long.
+///
+/// # Errors
+/// If the slug is invalid.
+pub fn validate_warehouse_slug(slug: &str) -> Result<()> {
+    let valid_chars = slug
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    if slug.is_empty()
+        || slug.len() > MAX_SLUG_LENGTH
+        || !valid_chars
+        || slug.starts_with('-')
+        || slug.ends_with('-')
+    {
+        return Err(ErrorModel::bad_request(
+            format!(
+                "Invalid warehouse slug '{slug}'. Slugs consist of up to {MAX_SLUG_LENGTH} lowercase letters, digits and hyphens, and must start and end with a letter or digit."
+            ),
+            "InvalidWarehouseSlug",
+            None,
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Remove cached slugs after the slugs of a warehouse were changed.
+pub(crate) fn invalidate_warehouse_slugs() {
+    WAREHOUSE_SLUG_CACHE.invalidate_all();
+}
+
+/// Middleware routing warehouse-scoped catalog URLs to the regular catalog API.
+///
+/// `/catalog/w/{slug}/v1/config` is routed to `/catalog/v1/config`, all other
+/// paths `/catalog/w/{slug}/v1/{path}` to `/catalog/v1/{warehouse_id}/{path}`.
+/// The resolved warehouse is added to the request extensions.
+///
+/// The middleware rewrites the request URI, so it has to wrap the router
+/// instead of being added as a route layer.
+pub(crate) async fn warehouse_slug_middleware_fn<C: Catalog>(
+    State(catalog_state): State<C::State>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let Some((slug, path)) = split_warehouse_scoped_path(request.uri().path()) else {
+        return next.run(request).await;
+    };
+    let (slug, path) = (slug.to_string(), path.to_string());
+
+    let resolved = WAREHOUSE_SLUG_CACHE
+        .try_get_with(
+            slug.clone(),
+            C::resolve_warehouse_slug(&slug, catalog_state),
+        )
+        .await;
+    let resolved = match resolved {
+        Ok(Some(resolved)) => resolved,
+        Ok(None) => {
+            return IcebergErrorResponse::from(ErrorModel::not_found(
+                format!("No warehouse with slug '{slug}' found"),
+                "WarehouseSlugNotFound",
+                None,
+            ))
+            .into_response();
+        }
+        Err(e) => {
+            tracing::error!(error=?e, "Failed to resolve warehouse slug '{slug}'");
+            return IcebergErrorResponse::from(ErrorModel::internal(
+                format!("Failed to resolve warehouse slug '{slug}'"),
+                "WarehouseSlugResolutionFailed",
+                None,
+            ))
+            .into_response();
+        }
+    };
+
+    let uri = match rewrite_uri(request.uri(), resolved.warehouse_id, &path) {
+        Ok(uri) => uri,
+        Err(e) => return e.into_response(),
+    };
+    tracing::trace!(
+        "Routing warehouse-scoped request for slug '{slug}' to {}",
+        uri.path()
+    );
+    *request.uri_mut() = uri;
+    request.extensions_mut().insert(resolved);
+    next.run(request).await
+}
+
+/// Splits `/catalog/w/{slug}/v1{path}` into the slug and `path`.
+fn split_warehouse_scoped_path(path: &str) -> Option<(&str, &str)> {
+    let (slug, rest) = path
+        .strip_prefix(WAREHOUSE_SCOPED_PATH_PREFIX)?
+        .split_once('/')?;
+    let rest = rest.strip_prefix("v1")?;
+    (!slug.is_empty() && (rest.is_empty() || rest.starts_with('/'))).then_some((slug, rest))
+}
+
+fn scoped_catalog_path(warehouse_id: WarehouseId, path: &str) -> String {
+    if path == "/config" {
+        "/catalog/v1/config".to_string()
+    } else {
+        format!("/catalog/v1/{warehouse_id}{path}")
+    }
+}
+
+fn rewrite_uri(
+    uri: &Uri,
+    warehouse_id: WarehouseId,
+    path: &str,
+) -> std::result::Result<Uri, IcebergErrorResponse> {
+    let path = scoped_catalog_path(warehouse_id, path);
+    let path_and_query = match uri.query() {
+        Some(query) => format!("{path}?{query}"),
+        None => path,
+    };
+    let mut parts = uri.clone().into_parts();
+    parts.path_and_query = Some(PathAndQuery::from_str(&path_and_query).map_err(|e| {
+        ErrorModel::bad_request(
+            "Invalid warehouse-scoped request path",
+            "InvalidWarehouseScopedPath",
+            Some(Box::new(e)),
+        )
+    })?);
+    Uri::from_parts(parts).map_err(|e| {
+        ErrorModel::bad_request(
+            "Invalid warehouse-scoped request path",
+            "InvalidWarehouseScopedPath",
+            Some(Box::new(e)),
+        )
+        .into()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_warehouse_slug() {
+        for slug in ["sales", "sales-eu-1", "a", "0"] {
+            validate_warehouse_slug(slug).unwrap();
+        }
+        for slug in [
+            "", "Sales", "-sales", "sales-", "sales_eu", "sales/eu", "sälës",
+        ] {
+            validate_warehouse_slug(slug).unwrap_err();
+        }
+        validate_warehouse_slug(&"a".repeat(MAX_SLUG_LENGTH)).unwrap();
+        validate_warehouse_slug(&"a".repeat(MAX_SLUG_LENGTH + 1)).unwrap_err();
+    }
+
+    #[test]
+    fn test_split_warehouse_scoped_path() {
+        assert_eq!(
+            split_warehouse_scoped_path("/catalog/w/sales/v1/config"),
+            Some(("sales", "/config"))
+        );
+        assert_eq!(
+            split_warehouse_scoped_path("/catalog/w/sales/v1/namespaces/ns/tables"),
+            Some(("sales", "/namespaces/ns/tables"))
+        );
+        assert_eq!(
+            split_warehouse_scoped_path("/catalog/w/sales/v1"),
+            Some(("sales", ""))
+        );
+        assert_eq!(
+            split_warehouse_scoped_path("/catalog/w/sales/v12/config"),
+            None
+        );
+        assert_eq!(split_warehouse_scoped_path("/catalog/w//v1/config"), None);
+        assert_eq!(split_warehouse_scoped_path("/catalog/w/sales"), None);
+        assert_eq!(split_warehouse_scoped_path("/catalog/v1/config"), None);
+    }
+
+    #[test]
+    fn test_rewrite_uri() {
+        let warehouse_id = WarehouseId::from(uuid::Uuid::nil());
+        let uri = Uri::from_static("/catalog/w/sales/v1/config?warehouse=sales");
+        assert_eq!(
+            rewrite_uri(&uri, warehouse_id, "/config").unwrap(),
+            "/catalog/v1/config?warehouse=sales"
+        );
+
+        let uri = Uri::from_static("/catalog/w/sales/v1/namespaces/ns/tables/t?snapshots=all");
+        assert_eq!(
+            rewrite_uri(&uri, warehouse_id, "/namespaces/ns/tables/t").unwrap(),
+            "/catalog/v1/00000000-0000-0000-0000-000000000000/namespaces/ns/tables/t?snapshots=all"
+        );
+    }
+}
@@ -0,0 +1,196 @@
+//! Change notifications for the watch API.
+//!
+//! Orchestrators can wait for the next commit of a specific table or view instead of
+//! consuming the events of the whole catalog. Watchers re-read the version of the watched
+//! entity whenever the [`WatchNotifier`] hook reports a change on this instance. Changes made
+//! via other instances are picked up by polling the catalog every [`WATCH_POLL_INTERVAL`].
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    sync::{Arc, LazyLock},
+    time::Duration,
+};
+
+use iceberg::{spec::TableMetadata, TableIdent};
+use iceberg_ext::{
+    catalog::rest::{CommitTransactionRequest, CommitViewRequest, RegisterTableRequest},
+    configs::Location,
+};
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::{
+    api::{
+        iceberg::{
+            types::DropParams,
+            v1::{DataAccess, NamespaceParameters, TableParameters, ViewParameters},
+        },
+        management::v1::warehouse::UndropTabularsRequest,
+        RequestMetadata,
+    },
+    catalog::tables::CommitContext,
+    service::{
+        authz::AuthorizationContext,
+        endpoint_hooks::{EndpointHook, ViewCommit},
+        TableId, UndropTabularResponse, ViewId,
+    },
+    WarehouseId,
+};
+
+/// Interval in which watchers re-read the watched entity without a local notification.
+pub(crate) const WATCH_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+static CHANGES: LazyLock<broadcast::Sender<TabularChange>> =
+    LazyLock::new(|| broadcast::channel(1024).0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TabularChange {
+    pub(crate) warehouse_id: WarehouseId,
+    pub(crate) tabular_id: Uuid,
+}
+
+/// Subscribe to changes of tables and views on this instance.
+/// Subscribe before reading the current version, so that no change is missed.
+pub(crate) fn subscribe() -> broadcast::Receiver<TabularChange> {
+    CHANGES.subscribe()
+}
+
+/// Wait until `change` was reported on this instance or `timeout` elapsed.
+pub(crate) async fn wait_for_change(
+    receiver: &mut broadcast::Receiver<TabularChange>,
+    change: TabularChange,
+    timeout: Duration,
+) {
+    let _ = tokio::time::timeout(timeout, async {
+        loop {
+            match receiver.recv().await {
+                Ok(c) if c == change => return,
+                Ok(_) => {}
+                // Missed notifications might include the watched entity.
+                Err(broadcast::error::RecvError::Lagged(_)) => return,
+                Err(broadcast::error::RecvError::Closed) => std::future::pending::<()>().await,
+            }
+        }
+    })
+    .await;
+}
+
+/// Endpoint hook notifying watchers of committed and dropped tables and views.
+#[derive(Debug, Clone, Default)]
+pub struct WatchNotifier;
+
+impl Display for WatchNotifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "WatchNotifier")
+    }
+}
+
+pub(crate) fn notify(warehouse_id: WarehouseId, ids: impl IntoIterator<Item = Uuid>) {
+    for tabular_id in ids {
+        // Sending only fails if nobody is watching.
+        let _ = CHANGES.send(TabularChange {
+            warehouse_id,
+            tabular_id,
+        });
+    }
+}
+
+#[async_trait::async_trait]
+impl EndpointHook for WatchNotifier {
+    async fn commit_transaction(
+        &self,
+        warehouse_id: WarehouseId,
+        _request: Arc<CommitTransactionRequest>,
+        _commits: Arc<Vec<CommitContext>>,
+        table_ident_map: Arc<HashMap<TableIdent, TableId>>,
+        _authorization: Arc<AuthorizationContext>,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        notify(warehouse_id, table_ident_map.values().map(|id| **id));
+        Ok(())
+    }
+
+    async fn drop_table(
+        &self,
+        warehouse_id: WarehouseId,
+        _parameters: TableParameters,
+        _drop_params: DropParams,
+        table_id: TableId,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        notify(warehouse_id, [*table_id]);
+        Ok(())
+    }
+
+    async fn register_table(
+        &self,
+        warehouse_id: WarehouseId,
+        _parameters: NamespaceParameters,
+        _request: Arc<RegisterTableRequest>,
+        metadata: Arc<TableMetadata>,
+        _metadata_location: Arc<Location>,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        notify(warehouse_id, [metadata.uuid()]);
+        Ok(())
+    }
+
+    async fn commit_view(
+        &self,
+        warehouse_id: WarehouseId,
+        _parameters: ViewParameters,
+        _request: Arc<CommitViewRequest>,
+        view_commit: Arc<ViewCommit>,
+        _data_access: DataAccess,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        notify(warehouse_id, [view_commit.new_metadata.uuid()]);
+        Ok(())
+    }
+
+    async fn drop_view(
+        &self,
+        warehouse_id: WarehouseId,
+        _parameters: ViewParameters,
+        _drop_params: DropParams,
+        view_id: ViewId,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        notify(warehouse_id, [*view_id]);
+        Ok(())
+    }
+
+    async fn undrop_tabular(
+        &self,
+        warehouse_id: WarehouseId,
+        _request: Arc<UndropTabularsRequest>,
+        responses: Arc<Vec<UndropTabularResponse>>,
+        _request_metadata: Arc<RequestMetadata>,
+    ) -> anyhow::Result<()> {
+        notify(warehouse_id, responses.iter().map(|r| *r.table_ident));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_wait_for_change() {
+        let warehouse_id = WarehouseId::new_random();
+        let watched = TabularChange {
+            warehouse_id,
+            tabular_id: Uuid::now_v7(),
+        };
+        let mut receiver = subscribe();
+        notify(warehouse_id, [Uuid::now_v7(), watched.tabular_id]);
+
+        let start = tokio::time::Instant::now();
+        wait_for_change(&mut receiver, watched, Duration::from_secs(10)).await;
+        assert!(start.elapsed() < Duration::from_secs(10));
+
+        // Times out without further changes
+        wait_for_change(&mut receiver, watched, Duration::from_millis(10)).await;
+    }
+}
@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+
+use iceberg::{spec::Schema, NamespaceIdent, TableIdent, TableUpdate};
+use sqlx::PgPool;
+
+use crate::{
+    api::{
+        iceberg::{
+            types::Prefix,
+            v1::{CommitTableRequest, CommitTransactionRequest},
+        },
+        management::v1::{
+            table::{
+                ColumnTagSuggestionKey, ColumnTagSuggestionStatus, ListColumnTagSuggestionsQuery,
+                ReviewColumnTagSuggestionsRequest, SetColumnTagsRequest, TableManagementService,
+            },
+            warehouse::TabularDeleteProfile,
+            ApiServer,
+        },
+    },
+    implementations::postgres::{PostgresCatalog, PostgresTransaction},
+    service::{
+        authz::AllowAllAuthorizer, task_queue::pii_detection_queue::PiiSuggestion, Catalog as _,
+        TableId, Transaction as _,
+    },
+    tests::random_request_metadata,
+};
+
+#[sqlx::test]
+async fn test_column_tags_survive_rename(pool: PgPool) {
+    let (ctx, warehouse) = crate::tests::setup(
+        pool,
+        crate::tests::test_io_profile(),
+        None,
+        AllowAllAuthorizer,
+        TabularDeleteProfile::Hard {},
+        None,
+        1,
+    )
+    .await;
+    let prefix = warehouse.warehouse_id.to_string();
+    crate::tests::create_ns(ctx.clone(), prefix.clone(), "ns".to_string()).await;
+    let table = crate::tests::create_table(ctx.clone(), prefix.clone(), "ns", "tab", false)
+        .await
+        .unwrap();
+    let table_id = TableId::from(table.metadata.uuid());
+
+    ApiServer::set_column_tags(
+        table_id,
+        warehouse.warehouse_id,
+        SetColumnTagsRequest {
+            columns: HashMap::from([("name".to_string(), vec!["pii".to_string()])]),
+        },
+        ctx.clone(),
+        random_request_metadata(),
+    )
+    .await
+    .unwrap();
+
+    // Rename `name` to `full_name`, the field id stays the same
+    let renamed_schema = Schema::builder()
+        .with_fields(
+            table
+                .metadata
+                .current_schema()
+                .as_struct()
+                .fields()
+                .iter()
+                .map(|f| {
+                    if f.name == "name" {
+                        let mut f = f.as_ref().clone();
+                        f.name = "full_name".to_string();
+                        f.into()
+                    } else {
+                        f.clone()
+                    }
+                })
+                .collect::<Vec<_>>(),
+        )
+        .build()
+        .unwrap();
+    crate::catalog::tables::commit_tables_internal(
+        Some(Prefix(prefix)),
+        CommitTransactionRequest {
+            table_changes: vec![CommitTableRequest {
+                identifier: Some(TableIdent::new(
+                    NamespaceIdent::new("ns".to_string()),
+                    "tab".to_string(),
+                )),
+                requirements: vec![],
+                updates: vec![
+                    TableUpdate::AddSchema {
+                        schema: renamed_schema,
+                    },
+                    TableUpdate::SetCurrentSchema { schema_id: -1 },
+                ],
+            }],
+        },
+        ctx.clone(),
+        random_request_metadata(),
+    )
+    .await
+    .unwrap();
+
+    let tags = ApiServer::get_column_tags(
+        table_id,
+        warehouse.warehouse_id,
+        ctx.clone(),
+        random_request_metadata(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(tags.columns.len(), 1);
+    assert_eq!(tags.columns[0].column_name.as_deref(), Some("full_name"));
+    assert_eq!(tags.columns[0].tags, vec!["pii".to_string()]);
+
+    let tagged = ApiServer::list_tagged_columns(
+        warehouse.warehouse_id,
+        "pii".to_string(),
+        ctx.clone(),
+        random_request_metadata(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(tagged.columns.len(), 1);
+    assert_eq!(tagged.columns[0].table_id, table_id);
+    assert_eq!(tagged.columns[0].column_name, "full_name");
+}
+
+#[sqlx::test]
+async fn test_review_column_tag_suggestions(pool: PgPool) {
+    let (ctx, warehouse) = crate::tests::setup(
+        pool,
+        crate::tests::test_io_profile(),
+        None,
+        AllowAllAuthorizer,
+        TabularDeleteProfile::Hard {},
+        None,
+        1,
+    )
+    .await;
+    let prefix = warehouse.warehouse_id.to_string();
+    crate::tests::create_ns(ctx.clone(), prefix.clone(), "ns".to_string()).await;
+    let table = crate::tests::create_table(ctx.clone(), prefix.clone(), "ns", "tab", false)
+        .await
+        .unwrap();
+    let table_id = TableId::from(table.metadata.uuid());
+
+    // PII detection is disabled by default
+    let err = ApiServer::detect_pii(
+        table_id,
+        warehouse.warehouse_id,
+        ctx.clone(),
+        random_request_metadata(),
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.error.r#type, "PiiDetectionDisabled");
+
+    let mut t = PostgresTransaction::begin_write(ctx.v1_state.catalog.clone())
+        .await
+        .unwrap();
+    PostgresCatalog::replace_column_tag_suggestions(
+        warehouse.warehouse_id,
+        table_id,
+        &[PiiSuggestion {
+            field_id: 2,
+            tag: "pii:name".to_string(),
+            confidence: 0.8,
+            reason: "Column name 'name' matches 'name'".to_string(),
+        }],
+        t.transaction(),
+    )
+    .await
+    .unwrap();
+    t.commit().await.unwrap();
+
+    let suggestions = ApiServer::list_column_tag_suggestions(
+        table_id,
+        warehouse.warehouse_id,
+        ListColumnTagSuggestionsQuery {
+            status: Some(ColumnTagSuggestionStatus::Pending),
+        },
+        ctx.clone(),
+        random_request_metadata(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(suggestions.suggestions.len(), 1);
+    assert_eq!(
+        suggestions.suggestions[0].column_name.as_deref(),
+        Some("name")
+    );
+
+    let key = ColumnTagSuggestionKey {
+        field_id: 2,
+        tag: "pii:name".to_string(),
+    };
+
+    // Unknown suggestions fail the whole review
+    let err = ApiServer::review_column_tag_suggestions(
+        table_id,
+        warehouse.warehouse_id,
+        ReviewColumnTagSuggestionsRequest {
+            accept: vec![key.clone()],
+            reject: vec![ColumnTagSuggestionKey {
+                field_id: 1,
+                tag: "pii:email".to_string(),
+            }],
+        },
+        ctx.clone(),
+        random_request_metadata(),
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.error.code, 404);
+
+    let tags = ApiServer::get_column_tags(
+        table_id,
+        warehouse.warehouse_id,
+        ctx.clone(),
+        random_request_metadata(),
+    )
+    .await
+    .unwrap();
+    assert!(tags.columns.is_empty());
+
+    ApiServer::review_column_tag_suggestions(
+        table_id,
+        warehouse.warehouse_id,
+        ReviewColumnTagSuggestionsRequest {
+            accept: vec![key],
+            reject: vec![],
+        },
+        ctx.clone(),
+        random_request_metadata(),
+    )
+    .await
+    .unwrap();
+
+    let tags = ApiServer::get_column_tags(
+        table_id,
+        warehouse.warehouse_id,
+        ctx.clone(),
+        random_request_metadata(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(tags.columns.len(), 1);
+    assert_eq!(tags.columns[0].tags, vec!["pii:name".to_string()]);
+}
@@ -22,17 +22,27 @@ mod test {
                     namespace::{NamespaceDropFlags, NamespaceService},
                     tables::TablesService,
                     views::ViewService,
-                    ListTablesQuery, NamespaceParameters,
+                    DataAccess, ListTablesQuery, NamespaceParameters,
                 },
             },
             management::v1::{
-                namespace::NamespaceManagementService, table::TableManagementService as _,
-                view::ViewManagementService as _, warehouse::TabularDeleteProfile, ApiServer,
+                namespace::NamespaceManagementService,
+                table::TableManagementService as _,
+                view::ViewManagementService as _,
+                warehouse::{
+                    ListDeletedTabularsQuery, Service as _, TabularDeleteProfile,
+                    UndropTabularsRequest,
+                },
+                ApiServer, TabularType,
             },
             RequestMetadata,
         },
         catalog::CatalogServer,
-        service::{ListNamespacesQuery, NamespaceId, TableId},
+        implementations::postgres::PostgresCatalog,
+        service::{
+            task_queue::{tabular_purge_queue, EntityId},
+            Catalog as _, ListNamespacesQuery, NamespaceId, TableId, TabularId,
+        },
         tests::{
             create_ns, create_table, drop_namespace, drop_recursive::setup_drop_test,
             random_request_metadata,
@@ -56,6 +66,8 @@ mod test {
                 page_size: None,
                 return_uuids: false,
                 return_protection_status: false,
+                return_external_status: false,
+                include: None,
             },
             ctx.clone(),
             random_request_metadata(),
@@ -85,6 +97,8 @@ mod test {
                 page_size: None,
                 return_uuids: false,
                 return_protection_status: false,
+                return_external_status: false,
+                include: None,
             },
             ctx.clone(),
             random_request_metadata(),
@@ -210,6 +224,70 @@ mod test {
         .unwrap();
     }
 
+    #[sqlx::test]
+    async fn test_recursive_drop_with_purge_queues_purge_tasks(pool: PgPool) {
+        let setup = setup_drop_test(pool, 0, 0, 0, TabularDeleteProfile::Hard {}).await;
+        let ctx = setup.ctx;
+        let warehouse = setup.warehouse;
+        let prefix = warehouse.warehouse_id.to_string();
+        let ns1 = create_ns(ctx.clone(), prefix.clone(), "ns1".to_string()).await;
+        let child = CatalogServer::create_namespace(
+            Some(Prefix(prefix.clone())),
+            CreateNamespaceRequest {
+                namespace: NamespaceIdent::from_vec(vec!["ns1".to_string(), "child".to_string()])
+                    .unwrap(),
+                properties: None,
+            },
+            ctx.clone(),
+            random_request_metadata(),
+        )
+        .await
+        .unwrap();
+
+        let tab = create_table(ctx.clone(), &prefix, "ns1", "tab", false)
+            .await
+            .unwrap();
+        let child_tab = CatalogServer::create_table(
+            NamespaceParameters {
+                prefix: Some(Prefix(prefix.clone())),
+                namespace: child.namespace.clone(),
+            },
+            crate::catalog::tables::test::create_request(Some("child_tab".to_string()), None),
+            DataAccess::not_specified(),
+            ctx.clone(),
+            random_request_metadata(),
+        )
+        .await
+        .unwrap();
+
+        super::super::drop_namespace(
+            ctx.clone(),
+            NamespaceDropFlags {
+                force: false,
+                purge: true,
+                recursive: true,
+            },
+            NamespaceParameters {
+                prefix: Some(Prefix(prefix.clone())),
+                namespace: ns1.namespace.clone(),
+            },
+        )
+        .await
+        .unwrap();
+
+        for table in [tab, child_tab] {
+            let task = PostgresCatalog::get_entity_task(
+                warehouse.warehouse_id,
+                EntityId::Tabular(table.metadata.uuid()),
+                tabular_purge_queue::QUEUE_NAME,
+                ctx.v1_state.catalog.clone(),
+            )
+            .await
+            .unwrap();
+            assert!(task.is_some());
+        }
+    }
+
     #[sqlx::test]
     async fn test_recursive_drop_with_soft_delete(pool: PgPool) {
         let setup = setup_drop_test(
@@ -229,8 +307,28 @@ mod test {
             prefix: Some(Prefix(prefix.clone())),
             namespace: NamespaceIdent::new("ns0".to_string()),
         };
+        let ns_id = NamespaceId::from(
+            *CatalogServer::list_namespaces(
+                Some(Prefix(prefix.clone())),
+                ListNamespacesQuery {
+                    page_token: PageToken::NotSpecified,
+                    page_size: Some(1),
+                    parent: None,
+                    return_uuids: true,
+                    return_protection_status: false,
+                },
+                ctx.clone(),
+                random_request_metadata(),
+            )
+            .await
+            .unwrap()
+            .namespace_uuids
+            .unwrap()
+            .first()
+            .unwrap(),
+        );
 
-        let e = super::super::drop_namespace(
+        super::super::drop_namespace(
             ctx.clone(),
             NamespaceDropFlags {
                 force: false,
@@ -240,8 +338,80 @@ mod test {
             ns_params.clone(),
         )
         .await
+        .unwrap();
+
+        let e = CatalogServer::namespace_exists(
+            ns_params.clone(),
+            ctx.clone(),
+            random_request_metadata(),
+        )
+        .await
         .unwrap_err();
-        assert_eq!(e.error.code, 400);
+        assert_eq!(e.error.code, 404);
+
+        let deleted = ApiServer::list_soft_deleted_tabulars(
+            warehouse.warehouse_id,
+            ListDeletedTabularsQuery::default(),
+            ctx.clone(),
+            random_request_metadata(),
+        )
+        .await
+        .unwrap()
+        .tabulars;
+        assert_eq!(deleted.len(), 2);
+
+        // Tabulars dropped with the namespace can only be restored through the namespace
+        let table = deleted
+            .iter()
+            .find(|t| matches!(t.typ, TabularType::Table))
+            .unwrap();
+        let e = ApiServer::undrop_tabulars(
+            warehouse.warehouse_id,
+            random_request_metadata(),
+            UndropTabularsRequest {
+                targets: vec![TabularId::Table(table.id)],
+            },
+            ctx.clone(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(e.error.code, 409, "{}", e.error);
+
+        ApiServer::undrop_namespace(
+            ns_id,
+            warehouse.warehouse_id,
+            ctx.clone(),
+            random_request_metadata(),
+        )
+        .await
+        .unwrap();
+
+        let tables = CatalogServer::list_tables(
+            ns_params.clone(),
+            ListTablesQuery {
+                page_token: PageToken::NotSpecified,
+                page_size: None,
+                return_uuids: false,
+                return_protection_status: false,
+                return_external_status: false,
+                include: None,
+            },
+            ctx.clone(),
+            random_request_metadata(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(tables.identifiers.len(), 1);
+        let deleted = ApiServer::list_soft_deleted_tabulars(
+            warehouse.warehouse_id,
+            ListDeletedTabularsQuery::default(),
+            ctx.clone(),
+            random_request_metadata(),
+        )
+        .await
+        .unwrap()
+        .tabulars;
+        assert!(deleted.is_empty());
 
         super::super::drop_namespace(
             ctx.clone(),
@@ -278,6 +448,8 @@ mod test {
                 page_size: None,
                 return_uuids: true,
                 return_protection_status: false,
+                return_external_status: false,
+                include: None,
             },
             ctx.clone(),
             random_request_metadata(),
@@ -410,7 +582,7 @@ mod test {
         )
         .await
         .unwrap_err();
-        assert_eq!(e.error.code, 400, "{}", e.error);
+        assert_eq!(e.error.code, 409, "{}", e.error);
 
         ApiServer::set_namespace_protection(
             ns_id,
@@ -457,6 +629,8 @@ mod test {
                 page_size: None,
                 return_uuids: true,
                 return_protection_status: false,
+                return_external_status: false,
+                include: None,
             },
             ctx.clone(),
             random_request_metadata(),
@@ -541,6 +715,8 @@ mod test {
                 page_size: None,
                 return_uuids: true,
                 return_protection_status: false,
+                return_external_status: false,
+                include: None,
             },
             ctx.clone(),
             random_request_metadata(),
@@ -1,7 +1,9 @@
+mod column_tags;
 mod drop_recursive;
 mod drop_warehouse;
 mod endpoint_stats;
 mod stats;
+mod table_comments;
 mod tasks;
 
 use iceberg::{NamespaceIdent, TableIdent};
@@ -66,6 +68,7 @@ pub(crate) fn minio_profile() -> (StorageProfile, StorageCredential) {
         aws_access_key_id,
         aws_secret_access_key,
         external_id: None,
+        sse_customer_key: None,
     })
     .into();
     let mut profile: StorageProfile = S3Profile::builder()
@@ -288,6 +291,7 @@ pub(crate) fn get_api_context<T: Authorizer>(
             contract_verifiers: ContractVerifiers::new(vec![]),
             hooks: EndpointHookCollection::new(vec![]),
             registered_task_queues,
+            search_index: None,
         },
     }
 }
@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+
+use iceberg::{NamespaceIdent, TableIdent};
+use sqlx::PgPool;
+
+use crate::{
+    api::{
+        iceberg::{
+            types::Prefix,
+            v1::{tables::TablesService as _, DataAccess, TableParameters},
+        },
+        management::v1::{
+            table::{SetColumnCommentsRequest, SetTableCommentRequest, TableManagementService},
+            warehouse::TabularDeleteProfile,
+            ApiServer,
+        },
+        ApiContext,
+    },
+    catalog::CatalogServer,
+    implementations::postgres::{PostgresCatalog, SecretsState},
+    service::{authz::AllowAllAuthorizer, State, TableId},
+    tests::{random_request_metadata, TestWarehouseResponse},
+};
+
+async fn load_table(
+    ctx: ApiContext<State<AllowAllAuthorizer, PostgresCatalog, SecretsState>>,
+    warehouse: &TestWarehouseResponse,
+) -> iceberg::spec::TableMetadata {
+    CatalogServer::load_table(
+        TableParameters {
+            prefix: Some(Prefix(warehouse.warehouse_id.to_string())),
+            table: TableIdent::new(NamespaceIdent::new("ns".to_string()), "tab".to_string()),
+        },
+        DataAccess::not_specified(),
+        ctx,
+        random_request_metadata(),
+    )
+    .await
+    .unwrap()
+    .metadata
+}
+
+#[sqlx::test]
+async fn test_set_table_and_column_comments(pool: PgPool) {
+    let (ctx, warehouse) = crate::tests::setup(
+        pool,
+        crate::tests::test_io_profile(),
+        None,
+        AllowAllAuthorizer,
+        TabularDeleteProfile::Hard {},
+        None,
+        1,
+    )
+    .await;
+    let prefix = warehouse.warehouse_id.to_string();
+    crate::tests::create_ns(ctx.clone(), prefix.clone(), "ns".to_string()).await;
+    let table = crate::tests::create_table(ctx.clone(), prefix, "ns", "tab", false)
+        .await
+        .unwrap();
+    let table_id = TableId::from(table.metadata.uuid());
+
+    ApiServer::set_table_comment(
+        table_id,
+        warehouse.warehouse_id,
+        SetTableCommentRequest {
+            comment: Some("My table".to_string()),
+        },
+        ctx.clone(),
+        random_request_metadata(),
+    )
+    .await
+    .unwrap();
+    ApiServer::set_column_comments(
+        table_id,
+        warehouse.warehouse_id,
+        SetColumnCommentsRequest {
+            columns: HashMap::from([("name".to_string(), Some("Name of the user".to_string()))]),
+        },
+        ctx.clone(),
+        random_request_metadata(),
+    )
+    .await
+    .unwrap();
+
+    let metadata = load_table(ctx.clone(), &warehouse).await;
+    assert_eq!(
+        metadata.properties().get("comment").map(String::as_str),
+        Some("My table")
+    );
+    assert_eq!(metadata.schemas_iter().count(), 2);
+    assert_eq!(
+        metadata
+            .current_schema()
+            .field_by_name("name")
+            .unwrap()
+            .doc
+            .as_deref(),
+        Some("Name of the user")
+    );
+
+    // Removing the comment removes the property
+    ApiServer::set_table_comment(
+        table_id,
+        warehouse.warehouse_id,
+        SetTableCommentRequest { comment: None },
+        ctx.clone(),
+        random_request_metadata(),
+    )
+    .await
+    .unwrap();
+    let metadata = load_table(ctx.clone(), &warehouse).await;
+    assert!(!metadata.properties().contains_key("comment"));
+
+    // Unknown columns are rejected
+    let err = ApiServer::set_column_comments(
+        table_id,
+        warehouse.warehouse_id,
+        SetColumnCommentsRequest {
+            columns: HashMap::from([("unknown".to_string(), None)]),
+        },
+        ctx.clone(),
+        random_request_metadata(),
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.error.r#type, "ColumnNotFound");
+}
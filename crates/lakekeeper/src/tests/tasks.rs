@@ -100,6 +100,8 @@ mod test {
                     .unwrap(),
                 ),
                 max_seconds_since_last_heartbeat: None,
+                max_attempts: None,
+                retry_backoff_seconds: None,
             },
             transaction.transaction(),
         )
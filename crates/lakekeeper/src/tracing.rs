@@ -46,6 +46,12 @@ impl<B> MakeSpan<B> for RestMakeSpan {
                         "x-forwarded-prefix" = %request.headers().get(X_FORWARDED_PREFIX_HEADER).and_then(|v| v.to_str().ok()).unwrap_or("not set"),
                         uri = %request.uri(),
                         version = ?request.version(),
+                        "otel.kind" = "server",
+                        // Recorded once the request has been routed
+                        "otel.name" = tracing::field::Empty,
+                        "http.route" = tracing::field::Empty,
+                        // Set for requests matching a trace target, see `service::log_level`
+                        debug_trace = tracing::field::Empty,
                         request_id = %request
                                     .headers()
                                     .get(X_REQUEST_ID_HEADER)
@@ -54,13 +60,16 @@ impl<B> MakeSpan<B> for RestMakeSpan {
                     )
             }
         }
-        match self.level {
+        let span = match self.level {
             Level::TRACE => make_span!(tracing::Level::TRACE),
             Level::DEBUG => make_span!(tracing::Level::DEBUG),
             Level::INFO => make_span!(tracing::Level::INFO),
             Level::WARN => make_span!(tracing::Level::WARN),
             Level::ERROR => make_span!(tracing::Level::ERROR),
-        }
+        };
+        #[cfg(feature = "otel")]
+        crate::otel::set_remote_parent(&span, request.headers());
+        span
     }
 }
 
@@ -0,0 +1,89 @@
+-- Database state of a server on schema version 20250623114333.
+-- Ids are fixed so that the expected state after migrating is deterministic.
+INSERT INTO server (server_id, terms_accepted, open_for_bootstrap)
+VALUES ('01900000-0000-7000-8000-000000000000', true, false);
+
+INSERT INTO project (project_id, project_name)
+VALUES ('01900000-0000-7000-8000-000000000001', 'Golden Project'),
+       ('golden-secondary', 'Secondary Project');
+
+INSERT INTO warehouse (warehouse_id, warehouse_name, project_id, storage_profile, status,
+                       tabular_delete_mode, tabular_expiration_seconds, protected)
+VALUES ('01900000-0000-7000-8000-000000000101', 'analytics',
+        '01900000-0000-7000-8000-000000000001',
+        '{"type": "s3", "bucket": "golden", "key-prefix": "analytics", "region": "eu-central-1", "sts-enabled": false, "flavor": "aws"}',
+        'active', 'soft', 3600, true),
+       ('01900000-0000-7000-8000-000000000102', 'archive',
+        '01900000-0000-7000-8000-000000000001',
+        '{"type": "s3", "bucket": "golden", "key-prefix": "archive", "region": "eu-central-1", "sts-enabled": false, "flavor": "aws"}',
+        'inactive', 'hard', NULL, false),
+       ('01900000-0000-7000-8000-000000000103', 'staging', 'golden-secondary',
+        '{"type": "s3", "bucket": "golden-staging", "region": "us-east-1", "sts-enabled": true, "flavor": "s3-compat", "endpoint": "http://minio:9000/", "path-style-access": true}',
+        'active', 'hard', NULL, false);
+
+INSERT INTO namespace (namespace_id, warehouse_id, namespace_name, namespace_properties, protected)
+VALUES ('01900000-0000-7000-8000-000000000201', '01900000-0000-7000-8000-000000000101',
+        ARRAY ['sales'], '{"owner": "sales-team"}', false),
+       ('01900000-0000-7000-8000-000000000202', '01900000-0000-7000-8000-000000000101',
+        ARRAY ['sales', 'eu'], '{}', false),
+       ('01900000-0000-7000-8000-000000000203', '01900000-0000-7000-8000-000000000101',
+        ARRAY ['finance'], '{}', true),
+       ('01900000-0000-7000-8000-000000000204', '01900000-0000-7000-8000-000000000103',
+        ARRAY ['raw'], '{}', false);
+
+INSERT INTO tabular (tabular_id, namespace_id, name, typ, metadata_location, fs_protocol,
+                     fs_location, deleted_at, protected)
+VALUES ('01900000-0000-7000-8000-000000000301', '01900000-0000-7000-8000-000000000201',
+        'orders', 'table',
+        's3://golden/analytics/sales/orders/metadata/00001-0190.metadata.json', 's3',
+        'golden/analytics/sales/orders', NULL, true),
+       ('01900000-0000-7000-8000-000000000302', '01900000-0000-7000-8000-000000000202',
+        'customers', 'table',
+        's3://golden/analytics/sales/eu/customers/metadata/00000-0190.metadata.json', 's3',
+        'golden/analytics/sales/eu/customers', NULL, false),
+       ('01900000-0000-7000-8000-000000000303', '01900000-0000-7000-8000-000000000201',
+        'daily_orders', 'view',
+        's3://golden/analytics/sales/daily_orders/metadata/00000-0190.metadata.json', 's3',
+        'golden/analytics/sales/daily_orders', NULL, false),
+       ('01900000-0000-7000-8000-000000000304', '01900000-0000-7000-8000-000000000203',
+        'staged', 'table', NULL, 's3', 'golden/analytics/finance/staged', NULL, false),
+       ('01900000-0000-7000-8000-000000000305', '01900000-0000-7000-8000-000000000203',
+        'dropped', 'table',
+        's3://golden/analytics/finance/dropped/metadata/00000-0190.metadata.json', 's3',
+        'golden/analytics/finance/dropped', '2025-06-20T08:00:00Z', false),
+       ('01900000-0000-7000-8000-000000000306', '01900000-0000-7000-8000-000000000204',
+        'events', 'table',
+        's3://golden-staging/raw/events/metadata/00000-0190.metadata.json', 's3',
+        'golden-staging/raw/events', NULL, false);
+
+INSERT INTO "table" (table_id, table_format_version, last_column_id, last_sequence_number,
+                     last_updated_ms, last_partition_id)
+VALUES ('01900000-0000-7000-8000-000000000301', '2', 3, 1, 1750406400000, 999),
+       ('01900000-0000-7000-8000-000000000302', '2', 2, 0, 1750406400000, 999),
+       ('01900000-0000-7000-8000-000000000304', '2', 1, 0, 1750406400000, 999),
+       ('01900000-0000-7000-8000-000000000305', '1', 1, 0, 1750406400000, 999),
+       ('01900000-0000-7000-8000-000000000306', '2', 4, 3, 1750406400000, 1000);
+
+INSERT INTO view (view_id, view_format_version)
+VALUES ('01900000-0000-7000-8000-000000000303', 'v1');
+
+INSERT INTO task (task_id, warehouse_id, queue_name, status, scheduled_for, task_data,
+                  entity_type, entity_id)
+VALUES ('01900000-0000-7000-8000-000000000401', '01900000-0000-7000-8000-000000000101',
+        'tabular_expiration', 'scheduled', '2025-06-20T09:00:00Z',
+        '{"tabular_type": "table", "deletion_kind": "purge"}', 'tabular',
+        '01900000-0000-7000-8000-000000000305');
+
+INSERT INTO users (id, name, email, user_type, last_updated_with, created_at)
+VALUES ('oidc~alice', 'Alice', 'alice@example.com', 'human', 'create-endpoint',
+        '2025-06-01T10:00:00Z'),
+       ('oidc~bob', 'Bob', NULL, 'human', 'config-call-creation', '2025-06-01T10:00:00Z'),
+       ('kubernetes~ingest', 'Ingest Job', NULL, 'application', 'update-endpoint',
+        '2025-06-02T10:00:00Z');
+
+INSERT INTO role (id, name, description, project_id)
+VALUES ('01900000-0000-7000-8000-000000000501', 'Analysts', 'Read access to analytics',
+        '01900000-0000-7000-8000-000000000001'),
+       ('01900000-0000-7000-8000-000000000502', 'Engineers', NULL,
+        '01900000-0000-7000-8000-000000000001'),
+       ('01900000-0000-7000-8000-000000000503', 'Analysts', NULL, 'golden-secondary');